@@ -0,0 +1,74 @@
+//! Synthetic quote streams shared by the criterion benches.
+
+#![allow(dead_code)]
+
+use rust_autohedge::data::store::Quote;
+use rust_autohedge::events::{Event, MarketEvent};
+
+pub const SYMBOLS: [&str; 5] = ["BTC/USD", "ETH/USD", "SOL/USD", "DOGE/USD", "XRP/USD"];
+
+const TIMESTAMP: &str = "2025-01-01T00:00:00.123456789Z";
+
+/// Round-robin quotes over `SYMBOLS` with a bounded sine-wave mid, 1 bps wide.
+pub struct QuoteGen {
+    n: usize,
+}
+
+impl QuoteGen {
+    pub fn new() -> Self {
+        Self { n: 0 }
+    }
+
+    pub fn next_quote(&mut self) -> Quote {
+        let i = self.n;
+        self.n += 1;
+        let mid = 100.0 + (i as f64 * 0.01).sin();
+        quote(SYMBOLS[i % SYMBOLS.len()], mid)
+    }
+}
+
+/// One symbol climbing 6 bps per quote, so every HFT evaluation clears a
+/// 5 bps momentum edge. Runs switch to a fresh symbol before the mid drifts
+/// far, and the first quote of a run only seeds the lookback.
+pub struct TrendGen {
+    n: usize,
+    run_len: usize,
+}
+
+impl TrendGen {
+    pub fn new(run_len: usize) -> Self {
+        Self {
+            n: 0,
+            run_len: run_len.max(2),
+        }
+    }
+
+    /// The next quote and whether it should trigger a buy.
+    pub fn next_quote(&mut self) -> (Quote, bool) {
+        let (run, step) = (self.n / self.run_len, self.n % self.run_len);
+        self.n += 1;
+        let mid = 100.0 * 1.0006_f64.powi(step as i32);
+        (quote(&format!("TREND{}/USD", run), mid), step > 0)
+    }
+}
+
+pub fn quote(symbol: &str, mid: f64) -> Quote {
+    Quote {
+        symbol: symbol.to_string(),
+        bid_price: mid - 0.005,
+        ask_price: mid + 0.005,
+        bid_size: 1.0,
+        ask_size: 1.0,
+        timestamp: TIMESTAMP.to_string(),
+    }
+}
+
+/// The bus event the WS layer publishes for `quote`.
+pub fn quote_event(quote: &Quote) -> Event {
+    Event::Market(MarketEvent::Quote {
+        symbol: quote.symbol.clone(),
+        bid: quote.bid_price,
+        ask: quote.ask_price,
+        timestamp: quote.timestamp.clone(),
+    })
+}
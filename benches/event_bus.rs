@@ -0,0 +1,49 @@
+//! EventBus fan-out benchmark: publish a batch of quote events and drain it
+//! from every subscriber, for growing subscriber counts.
+//!
+//! Run with `cargo bench --features bench --bench event_bus`.
+
+mod common;
+
+use common::{quote_event, QuoteGen};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_autohedge::bus::EventBus;
+use rust_autohedge::events::Event;
+use std::hint::black_box;
+
+/// Events per measured batch (below the queue capacity: nothing is dropped)
+const BATCH: usize = 256;
+
+fn fan_out(c: &mut Criterion) {
+    let mut gen = QuoteGen::new();
+    let events: Vec<Event> = (0..BATCH).map(|_| quote_event(&gen.next_quote())).collect();
+
+    let mut group = c.benchmark_group("event_bus_fan_out");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    for subscribers in [1usize, 4, 8, 16] {
+        let bus = EventBus::new(BATCH * 2);
+        let mut receivers: Vec<_> = (0..subscribers)
+            .map(|i| bus.subscribe(&format!("bench{}", i)))
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(subscribers),
+            &subscribers,
+            |b, _| {
+                b.iter(|| {
+                    for event in &events {
+                        black_box(bus.publish(event.clone()).ok());
+                    }
+                    for rx in receivers.iter_mut() {
+                        while let Ok(event) = rx.try_recv() {
+                            black_box(event);
+                        }
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, fan_out);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! MarketStore benchmark: quote updates and the reads the strategy and
+//! execution hot paths make on a store at steady state.
+//!
+//! Run with `cargo bench --features bench --bench market_store`.
+
+mod common;
+
+use common::{QuoteGen, SYMBOLS};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rust_autohedge::data::indicators::IndicatorKind;
+use rust_autohedge::data::store::{Bar, MarketStore};
+use std::hint::black_box;
+
+const HISTORY_LIMIT: usize = 500;
+
+/// A store whose rings are full, so updates overwrite in place.
+fn warm_store(gen: &mut QuoteGen) -> MarketStore {
+    let store = MarketStore::new(HISTORY_LIMIT);
+    for _ in 0..HISTORY_LIMIT * SYMBOLS.len() {
+        let q = gen.next_quote();
+        store.update_quote(q.symbol.clone(), q);
+    }
+    for (i, symbol) in SYMBOLS
+        .iter()
+        .cycle()
+        .take(HISTORY_LIMIT * SYMBOLS.len())
+        .enumerate()
+    {
+        let close = 100.0 + (i as f64 * 0.1).sin();
+        store.update_bar(
+            symbol.to_string(),
+            Bar {
+                symbol: symbol.to_string(),
+                open: close,
+                high: close + 0.5,
+                low: close - 0.5,
+                close,
+                volume: 10.0,
+                timestamp: "2025-01-01T00:00:00Z".to_string(),
+            },
+        );
+    }
+    store
+}
+
+fn market_store(c: &mut Criterion) {
+    let mut gen = QuoteGen::new();
+    let store = warm_store(&mut gen);
+
+    let mut group = c.benchmark_group("market_store");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("update_quote", |b| {
+        b.iter(|| {
+            let q = gen.next_quote();
+            store.update_quote(q.symbol.clone(), q);
+        })
+    });
+    group.bench_function("get_latest_quote", |b| {
+        b.iter(|| black_box(store.get_latest_quote(black_box("BTC/USD"))))
+    });
+    group.bench_function("quote_window_50", |b| {
+        b.iter(|| black_box(store.quote_window(black_box("BTC/USD"), 50)))
+    });
+    group.bench_function("get_indicator_rsi14", |b| {
+        b.iter(|| black_box(store.get_indicator(black_box("BTC/USD"), IndicatorKind::Rsi, 14)))
+    });
+    group.bench_function("update_then_read", |b| {
+        b.iter(|| {
+            let q = gen.next_quote();
+            let symbol = q.symbol.clone();
+            store.update_quote(symbol.clone(), q);
+            black_box((store.quote_count(&symbol), store.get_latest_quote(&symbol)))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, market_store);
+criterion_main!(benches);
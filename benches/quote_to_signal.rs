@@ -0,0 +1,126 @@
+//! End-to-end HFT latency: a quote published on the bus until the strategy
+//! engine's buy signal for it arrives.
+//!
+//! The quote is written to the store and published the way the WS layer does;
+//! only quotes expected to trigger a buy are timed (see `TrendGen`), one at a
+//! time so none are coalesced.
+//!
+//! Run with `cargo bench --features bench --bench quote_to_signal`.
+
+mod common;
+
+use common::{quote_event, TrendGen};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rust_autohedge::bus::EventBus;
+use rust_autohedge::config::{AppConfig, LlmRetryConfig};
+use rust_autohedge::data::store::MarketStore;
+use rust_autohedge::events::Event;
+use rust_autohedge::llm::{LLMClient, LLMQueue};
+use rust_autohedge::services::strategy::StrategyEngine;
+use std::time::{Duration, Instant};
+
+/// Quotes per symbol before the generator moves on (keeps the mid bounded)
+const RUN_LEN: usize = 1_000;
+
+fn hft_config() -> AppConfig {
+    serde_yaml::from_str(
+        r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 0.3
+  stop_loss_pct: 0.3
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 500
+warmup_count: 0
+llm_queue_size: 10
+llm_max_concurrent: 1
+no_trade_cooldown_quotes: 0
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 1
+  min_edge_bps: 5.0
+  take_profit_bps: 30.0
+  stop_loss_bps: 30.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "bench"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "http://127.0.0.1:9"
+exit_on_quotes: true
+"#,
+    )
+    .unwrap()
+}
+
+fn quote_to_signal(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap();
+    let config = hft_config();
+    let bus = EventBus::new(4096);
+    let store = MarketStore::new(config.history_limit);
+    let mut signals = bus.subscribe("bench");
+    runtime.block_on(async {
+        let llm = LLMQueue::new(
+            LLMClient::new(String::new(), None, config.llm.model.clone()),
+            1,
+            1,
+            LlmRetryConfig::default(),
+        );
+        StrategyEngine::new(bus.clone(), store.clone(), llm, config.clone())
+            .start()
+            .await;
+    });
+
+    let mut gen = TrendGen::new(RUN_LEN);
+    let mut group = c.benchmark_group("hft");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("quote_to_signal", |b| {
+        b.iter_custom(|iters| {
+            runtime.block_on(async {
+                let mut elapsed = Duration::ZERO;
+                let mut timed = 0;
+                while timed < iters {
+                    let (quote, triggers) = gen.next_quote();
+                    let event = quote_event(&quote);
+                    let start = Instant::now();
+                    store.update_quote(quote.symbol.clone(), quote);
+                    bus.publish(event).ok();
+                    if !triggers {
+                        // Let the seed quote be evaluated on its own: the
+                        // worker coalesces quotes that queue up behind it
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        continue;
+                    }
+                    loop {
+                        match signals.recv().await {
+                            Ok(Event::Signal(_)) => break,
+                            Ok(_) | Err(_) => continue,
+                        }
+                    }
+                    elapsed += start.elapsed();
+                    timed += 1;
+                }
+                elapsed
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, quote_to_signal);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+//! MarketStore hot-path benchmark: full history copies vs ring accessors.
+//!
+//! Replays one second of a 10k msg/s quote stream across a few symbols. After
+//! every quote the strategy reads the symbol's history the way the engine
+//! does: `copy` clones it (`get_quote_history`), `ring` uses `quote_count`
+//! and `get_latest_quote`. A counting allocator reports allocations per
+//! message.
+//!
+//! Run with `cargo bench --bench store_replay`.
+
+use rust_autohedge::data::store::{MarketStore, Quote};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const MESSAGES: usize = 10_000;
+const HISTORY_LIMIT: usize = 500;
+const SYMBOLS: [&str; 5] = ["BTC/USD", "ETH/USD", "SOL/USD", "DOGE/USD", "XRP/USD"];
+
+fn stream() -> Vec<Quote> {
+    (0..MESSAGES)
+        .map(|i| {
+            let mid = 100.0 + (i as f64 * 0.01).sin();
+            Quote {
+                symbol: SYMBOLS[i % SYMBOLS.len()].to_string(),
+                bid_price: mid - 0.01,
+                ask_price: mid + 0.01,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                timestamp: "2025-01-01T00:00:00.123456789Z".to_string(),
+            }
+        })
+        .collect()
+}
+
+fn copy_access(store: &MarketStore, symbol: &str) -> f64 {
+    let history = store.get_quote_history(symbol);
+    history.len() as f64 + history.last().map_or(0.0, |q| q.bid_price)
+}
+
+fn ring_access(store: &MarketStore, symbol: &str) -> f64 {
+    store.quote_count(symbol) as f64 + store.get_latest_quote(symbol).map_or(0.0, |q| q.bid_price)
+}
+
+fn run(name: &str, quotes: &[Quote], access: fn(&MarketStore, &str) -> f64) -> (f64, f64) {
+    let store = MarketStore::new(HISTORY_LIMIT);
+    // Warm-up: fill every ring so the replay runs at steady state
+    for q in quotes.iter().take(HISTORY_LIMIT * SYMBOLS.len()) {
+        store.update_quote(q.symbol.clone(), q.clone());
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for q in quotes {
+        store.update_quote(q.symbol.clone(), q.clone());
+        black_box(access(&store, &q.symbol));
+    }
+    let elapsed = start.elapsed();
+    let allocs_per_msg =
+        (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / quotes.len() as f64;
+    let per_msg_ns = elapsed.as_nanos() as f64 / quotes.len() as f64;
+    println!(
+        "{:<6} {:>10.1} ns/msg {:>10.1} allocs/msg {:>14.0} msg/s max",
+        name,
+        per_msg_ns,
+        allocs_per_msg,
+        1e9 / per_msg_ns
+    );
+    (per_msg_ns, allocs_per_msg)
+}
+
+fn main() {
+    println!(
+        "MarketStore replay ({} quotes over {} symbols, history_limit {})",
+        MESSAGES,
+        SYMBOLS.len(),
+        HISTORY_LIMIT
+    );
+    let quotes = stream();
+    let (copy_ns, copy_allocs) = run("copy", &quotes, copy_access);
+    let (ring_ns, ring_allocs) = run("ring", &quotes, ring_access);
+    println!(
+        "speedup: {:.2}x, allocations: {:.1}x fewer",
+        copy_ns / ring_ns,
+        copy_allocs / ring_allocs
+    );
+}
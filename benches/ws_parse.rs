@@ -0,0 +1,97 @@
+//! WS hot-path parsing benchmark: typed borrowed structs vs `serde_json::Value`.
+//!
+//! Run with `cargo bench --bench ws_parse`. Uses a plain timing loop so it
+//! needs no extra dev-dependencies.
+
+use rust_autohedge::exchange::ws_messages::{parse_alpaca, parse_binance, parse_num};
+use serde_json::Value;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: usize = 200_000;
+
+const ALPACA_FRAME: &str = r#"[{"T":"q","S":"BTC/USD","bp":50000.5,"bs":1.2,"ap":50001.0,"as":0.8,"t":"2025-01-01T00:00:00.123456789Z"},{"T":"t","S":"BTC/USD","p":50000.7,"s":0.01,"i":123456,"t":"2025-01-01T00:00:00.223456789Z","tks":"B"}]"#;
+
+const BINANCE_FRAME: &str = r#"{"e":"bookTicker","u":400900217,"E":1700000000000,"s":"BTCUSDT","b":"50000.10","B":"1.50000000","a":"50000.20","A":"2.50000000"}"#;
+
+fn alpaca_value(text: &str) -> f64 {
+    let mut acc = 0.0;
+    if let Ok(val) = serde_json::from_str::<Value>(text) {
+        if let Some(arr) = val.as_array() {
+            for item in arr {
+                let bid = item.get("bp").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let price = item.get("p").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let sym = item.get("S").and_then(|v| v.as_str()).unwrap_or("");
+                acc += bid + price + sym.len() as f64;
+            }
+        }
+    }
+    acc
+}
+
+fn alpaca_typed(text: &str) -> f64 {
+    let mut acc = 0.0;
+    if let Some(items) = parse_alpaca(text) {
+        for item in items {
+            let sym = item.symbol.as_deref().unwrap_or("");
+            acc += item.bid_price.unwrap_or(0.0) + item.price.unwrap_or(0.0) + sym.len() as f64;
+        }
+    }
+    acc
+}
+
+fn binance_value(text: &str) -> f64 {
+    let v: Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(_) => return 0.0,
+    };
+    let bid = v
+        .get("b")
+        .and_then(|x| x.as_str())
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    let ask = v
+        .get("a")
+        .and_then(|x| x.as_str())
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    bid + ask
+}
+
+fn binance_typed(text: &str) -> f64 {
+    match parse_binance(text) {
+        Some(m) => parse_num(&m.bid_price) + parse_num(&m.ask_price),
+        None => 0.0,
+    }
+}
+
+fn run(name: &str, text: &str, f: fn(&str) -> f64) -> f64 {
+    // Warm-up
+    for _ in 0..1_000 {
+        black_box(f(black_box(text)));
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f(black_box(text)));
+    }
+    let elapsed = start.elapsed();
+    let per_msg_ns = elapsed.as_nanos() as f64 / ITERATIONS as f64;
+    let msgs_per_sec = ITERATIONS as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<16} {:>10.1} ns/msg {:>14.0} msg/s",
+        name, per_msg_ns, msgs_per_sec
+    );
+    per_msg_ns
+}
+
+fn main() {
+    println!("WS parse benchmark ({} iterations)", ITERATIONS);
+
+    let a_val = run("alpaca/value", ALPACA_FRAME, alpaca_value);
+    let a_typ = run("alpaca/typed", ALPACA_FRAME, alpaca_typed);
+    println!("alpaca speedup: {:.2}x", a_val / a_typ);
+
+    let b_val = run("binance/value", BINANCE_FRAME, binance_value);
+    let b_typ = run("binance/typed", BINANCE_FRAME, binance_typed);
+    println!("binance speedup: {:.2}x", b_val / b_typ);
+}
@@ -0,0 +1,15 @@
+//! Compiles `proto/autohedge.proto` when the `grpc` feature is enabled, with
+//! the vendored `protoc` so no system install is needed.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/autohedge.proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/autohedge.proto"], &["proto"])
+            .expect("compile proto/autohedge.proto");
+    }
+}
@@ -1,14 +1,8 @@
+use crate::agents::prompt::{configured_prompt, AgentRole};
 use crate::agents::Agent;
+use crate::config::AppConfig;
 
-pub struct DirectorAgent;
-
-impl Agent for DirectorAgent {
-    fn name(&self) -> &str {
-        "Director-Agent"
-    }
-
-    fn system_prompt(&self) -> &str {
-        r#"You are a Trading Director AI. Your goal is to analyze market data (Recent History & News) and decide if there is a CLEAR trading opportunity.
+const SYSTEM_PROMPT: &str = r#"You are a Trading Director AI. Your goal is to analyze market data (Recent History & News) and decide if there is a CLEAR trading opportunity.
         
 ANALYSIS GUIDELINES:
 - Look for TRENDS in the provided history (e.g., higher highs, lower lows, breakouts, reversals)
@@ -32,6 +26,28 @@ EXAMPLES:
 - Bearish trend with lower lows: {"decision": "trade", "direction": "short", "confidence": 0.7}
 - Choppy, unclear market: {"decision": "no_trade", "confidence": 0.0}
 - Strong uptrend reaching overbought: {"decision": "trade", "direction": "exit", "confidence": 0.75}
-"#
+"#;
+
+#[derive(Default)]
+pub struct DirectorAgent {
+    /// Rendered `prompts.director`, replacing the built-in prompt
+    prompt: Option<String>,
+}
+
+impl DirectorAgent {
+    pub fn new(config: &AppConfig, symbol: &str) -> Self {
+        Self {
+            prompt: configured_prompt(config, AgentRole::Director, symbol),
+        }
+    }
+}
+
+impl Agent for DirectorAgent {
+    fn name(&self) -> &str {
+        "Director-Agent"
+    }
+
+    fn system_prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(SYSTEM_PROMPT)
     }
 }
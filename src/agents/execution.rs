@@ -1,14 +1,8 @@
+use crate::agents::prompt::{configured_prompt, AgentRole};
 use crate::agents::Agent;
+use crate::config::AppConfig;
 
-pub struct ExecutionAgent;
-
-impl Agent for ExecutionAgent {
-    fn name(&self) -> &str {
-        "Execution-Agent"
-    }
-
-    fn system_prompt(&self) -> &str {
-        r#"You are an Execution Trader AI.
+const SYSTEM_PROMPT: &str = r#"You are an Execution Trader AI.
         
 Format the final order based on the Risk Manager's output.
 Output ONLY valid JSON. Do not include markdown formatting or chat text.
@@ -21,6 +15,28 @@ Output JSON:
     "order_type": "market" | "limit",
     "limit_price": null
 }
-"#
+"#;
+
+#[derive(Default)]
+pub struct ExecutionAgent {
+    /// Rendered `prompts.execution`, replacing the built-in prompt
+    prompt: Option<String>,
+}
+
+impl ExecutionAgent {
+    pub fn new(config: &AppConfig, symbol: &str) -> Self {
+        Self {
+            prompt: configured_prompt(config, AgentRole::Execution, symbol),
+        }
+    }
+}
+
+impl Agent for ExecutionAgent {
+    fn name(&self) -> &str {
+        "Execution-Agent"
+    }
+
+    fn system_prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(SYSTEM_PROMPT)
     }
 }
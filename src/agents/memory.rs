@@ -0,0 +1,228 @@
+//! Short-term agent memory.
+//!
+//! With `agent_memory.enabled` every Director decision is kept per
+//! agent+symbol (the last `max_entries`, reasoning cut to
+//! `max_reasoning_chars`), together with what came of it: fills and rejections
+//! of the symbol's orders are attached to its latest decisions, sells with the
+//! move from the entry price. The next prompt for that symbol quotes them, so
+//! the model can build on its own reasoning instead of flip-flopping.
+//!
+//! Closed trades (`Event::TradeClosed`) are kept per symbol as well (the last
+//! `closed_trades`) and quoted as a win/loss record with each trade's result
+//! and entry thesis, so the gate learns which setups have actually paid off.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::config::AgentMemoryConfig;
+use crate::events::{ExecutionReport, TradeClosedEvent};
+use crate::money::float;
+
+/// One remembered decision.
+#[derive(Clone, Debug)]
+struct MemoryEntry {
+    /// "HH:MM UTC"
+    time: String,
+    decision: String,
+    reasoning: String,
+    /// Fills/rejections since the decision, "; "-separated
+    outcome: Option<String>,
+}
+
+/// Result of one closed trade.
+#[derive(Clone, Debug)]
+struct TradeOutcome {
+    pnl_percent: f64,
+    thesis: String,
+}
+
+/// Recent decisions per (agent, symbol); clones share the same memory.
+#[derive(Clone)]
+pub struct MemoryStore {
+    enabled: bool,
+    max_entries: usize,
+    max_reasoning_chars: usize,
+    max_closed_trades: usize,
+    entries: Arc<DashMap<(String, String), VecDeque<MemoryEntry>>>,
+    /// Latest closed trades per symbol
+    closed_trades: Arc<DashMap<String, VecDeque<TradeOutcome>>>,
+    /// Last buy fill price per symbol, for the outcome of the exit
+    entry_prices: Arc<DashMap<String, Decimal>>,
+}
+
+impl MemoryStore {
+    pub fn new(config: &AgentMemoryConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            max_entries: config.max_entries.max(1),
+            max_reasoning_chars: config.max_reasoning_chars,
+            max_closed_trades: config.closed_trades,
+            entries: Arc::new(DashMap::new()),
+            closed_trades: Arc::new(DashMap::new()),
+            entry_prices: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Remember `agent`'s decision on `symbol`.
+    pub fn record(&self, agent: &str, symbol: &str, decision: &str, reasoning: &str) {
+        if !self.enabled {
+            return;
+        }
+        let reasoning = self.shorten(reasoning);
+        let mut entries = self
+            .entries
+            .entry((agent.to_string(), symbol.to_string()))
+            .or_default();
+        if entries.len() >= self.max_entries {
+            entries.pop_front();
+        }
+        entries.push_back(MemoryEntry {
+            time: chrono::Utc::now().format("%H:%M UTC").to_string(),
+            decision: decision.to_string(),
+            reasoning,
+            outcome: None,
+        });
+    }
+
+    /// Attach a fill or rejection to the latest decision of every agent on
+    /// the report's symbol.
+    pub fn record_execution(&self, report: &ExecutionReport) {
+        if !self.enabled || report.status == "partial_fill" {
+            return;
+        }
+        let status = report.status.to_lowercase();
+        let outcome = if status.contains("fill") {
+            let price = report.price.unwrap_or_default();
+            let mut outcome = format!("{} filled @ {}", report.side, price.normalize());
+            if report.side == "buy" {
+                self.entry_prices.insert(report.symbol.clone(), price);
+            } else if let Some((_, entry)) = self.entry_prices.remove(&report.symbol) {
+                if let Some(ratio) = (price - entry).checked_div(entry) {
+                    outcome.push_str(&format!(" ({:+.2}% vs entry)", float(ratio) * 100.0));
+                }
+            }
+            outcome
+        } else if status == "rejected" {
+            format!("{} rejected", report.side)
+        } else {
+            return;
+        };
+        for mut item in self.entries.iter_mut() {
+            if item.key().1 != report.symbol {
+                continue;
+            }
+            if let Some(latest) = item.value_mut().back_mut() {
+                latest.outcome = Some(match latest.outcome.take() {
+                    Some(previous) => format!("{}; {}", previous, outcome),
+                    None => outcome.clone(),
+                });
+            }
+        }
+    }
+
+    /// Remember the result of a closed trade on its symbol.
+    pub fn record_trade_closed(&self, trade: &TradeClosedEvent) {
+        if !self.enabled || self.max_closed_trades == 0 {
+            return;
+        }
+        let thesis = self.shorten(&trade.thesis);
+        let mut trades = self.closed_trades.entry(trade.symbol.clone()).or_default();
+        if trades.len() >= self.max_closed_trades {
+            trades.pop_front();
+        }
+        trades.push_back(TradeOutcome {
+            pnl_percent: trade.pnl_percent,
+            thesis,
+        });
+    }
+
+    /// Prompt section quoting `agent`'s recent decisions on `symbol` and the
+    /// symbol's recent closed trades.
+    pub fn prompt_context(&self, agent: &str, symbol: &str) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+        let mut sections = Vec::new();
+        if let Some(entries) = self.entries.get(&(agent.to_string(), symbol.to_string())) {
+            if !entries.is_empty() {
+                let mut text = format!(
+                    "Your recent decisions on {} (oldest first). Stay consistent with them unless the data has changed:",
+                    symbol
+                );
+                for e in entries.iter() {
+                    text.push_str(&format!("\n- {} {}: {}", e.time, e.decision, e.reasoning));
+                    if let Some(outcome) = &e.outcome {
+                        text.push_str(&format!(" -> {}", outcome));
+                    }
+                }
+                sections.push(text);
+            }
+        }
+        if let Some(trades) = self.closed_trades.get(symbol) {
+            if !trades.is_empty() {
+                let wins = trades.iter().filter(|t| t.pnl_percent > 0.0).count();
+                let net: f64 = trades.iter().map(|t| t.pnl_percent).sum();
+                let mut text = format!(
+                    "Recent closed trades on {}: {} wins / {} losses, net {:+.2}%. Favor setups that have worked:",
+                    symbol,
+                    wins,
+                    trades.len() - wins,
+                    net
+                );
+                for t in trades.iter() {
+                    text.push_str(&format!("\n- {:+.2}%", t.pnl_percent));
+                    if !t.thesis.is_empty() {
+                        text.push_str(&format!(": {}", t.thesis));
+                    }
+                }
+                sections.push(text);
+            }
+        }
+        (!sections.is_empty()).then(|| sections.join("\n\n"))
+    }
+
+    /// `text` on one line, cut to `max_reasoning_chars`.
+    fn shorten(&self, text: &str) -> String {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        match text.char_indices().nth(self.max_reasoning_chars) {
+            Some((cut, _)) => format!("{}...", &text[..cut]),
+            None => text,
+        }
+    }
+}
+
+/// Decision summary and reasoning of a Director response: its JSON
+/// `decision`/`direction`/`confidence` and `thesis`, else the bare verdict and
+/// the raw text.
+pub fn director_decision(response: &str) -> (String, String) {
+    let parsed = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str::<Value>(&response[start..=end]).ok()
+        }
+        _ => None,
+    };
+    if let Some(json) = parsed {
+        if let Some(decision) = json.get("decision").and_then(Value::as_str) {
+            let mut summary = decision.to_string();
+            if let Some(direction) = json.get("direction").and_then(Value::as_str) {
+                summary.push_str(&format!(" {}", direction));
+            }
+            if let Some(confidence) = json.get("confidence").and_then(Value::as_f64) {
+                summary.push_str(&format!(" (confidence {:.2})", confidence));
+            }
+            let thesis = json.get("thesis").and_then(Value::as_str).unwrap_or("");
+            return (summary, thesis.to_string());
+        }
+    }
+    let lower = response.to_lowercase();
+    let decision = if lower.contains("no_trade") || lower.contains("no trade") {
+        "no_trade"
+    } else {
+        "trade"
+    };
+    (decision.to_string(), response.to_string())
+}
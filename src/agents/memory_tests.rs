@@ -0,0 +1,135 @@
+//! Unit tests for agent short-term memory.
+
+#[cfg(test)]
+mod memory_tests {
+    use crate::agents::memory::*;
+    use crate::config::AgentMemoryConfig;
+    use crate::events::{ExecutionReport, TradeClosedEvent};
+    use crate::money::dec;
+
+    const AGENT: &str = "Director-Agent";
+
+    fn memory(max_entries: usize) -> MemoryStore {
+        MemoryStore::new(&AgentMemoryConfig {
+            enabled: true,
+            max_entries,
+            max_reasoning_chars: 40,
+            closed_trades: 2,
+        })
+    }
+
+    fn fill(side: &str, status: &str, price: f64) -> ExecutionReport {
+        ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o1".to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(dec(price)),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Memory Store Tests =============
+
+    #[test]
+    fn test_disabled_memory_records_nothing() {
+        let memory = MemoryStore::new(&AgentMemoryConfig::default());
+        memory.record(AGENT, "BTC/USD", "trade long", "breakout");
+        assert!(memory.prompt_context(AGENT, "BTC/USD").is_none());
+    }
+
+    #[test]
+    fn test_keeps_last_decisions_per_agent_and_symbol() {
+        let memory = memory(2);
+        memory.record(AGENT, "BTC/USD", "no_trade", "choppy");
+        memory.record(AGENT, "BTC/USD", "trade long", "breakout   above\n100");
+        memory.record(AGENT, "BTC/USD", "no_trade", "x".repeat(60).as_str());
+        memory.record(AGENT, "ETH/USD", "trade long", "eth");
+
+        let context = memory.prompt_context(AGENT, "BTC/USD").unwrap();
+        assert!(context.starts_with("Your recent decisions on BTC/USD"));
+        // Oldest dropped, whitespace collapsed, long reasoning cut
+        assert!(!context.contains("choppy"));
+        assert!(context.contains("trade long: breakout above 100"));
+        assert!(context.contains(&format!("no_trade: {}...", "x".repeat(40))));
+        assert!(!context.contains("eth"));
+        assert!(memory.prompt_context("Quant-Agent", "BTC/USD").is_none());
+    }
+
+    #[test]
+    fn test_executions_become_outcomes() {
+        let memory = memory(5);
+        memory.record(AGENT, "BTC/USD", "trade long", "breakout");
+        memory.record_execution(&fill("buy", "new", 100.0));
+        memory.record_execution(&fill("buy", "filled", 100.0));
+        memory.record_execution(&fill("sell", "filled", 101.5));
+
+        let context = memory.prompt_context(AGENT, "BTC/USD").unwrap();
+        assert!(context
+            .ends_with("breakout -> buy filled @ 100; sell filled @ 101.5 (+1.50% vs entry)"));
+
+        // A new decision starts without an outcome
+        memory.record(AGENT, "BTC/USD", "trade long", "retest");
+        memory.record_execution(&fill("buy", "rejected", 99.0));
+        let context = memory.prompt_context(AGENT, "BTC/USD").unwrap();
+        assert!(context.ends_with("retest -> buy rejected"));
+    }
+
+    fn closed(pnl_percent: f64, thesis: &str) -> TradeClosedEvent {
+        TradeClosedEvent {
+            symbol: "BTC/USD".to_string(),
+            thesis: thesis.to_string(),
+            entry_price: dec(100.0),
+            exit_price: dec(100.0 + pnl_percent),
+            qty: dec(1.0),
+            pnl: dec(pnl_percent),
+            pnl_percent,
+            strategy_id: None,
+            correlation_id: None,
+            timestamp: "2026-10-16T14:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_closed_trades_quoted_as_win_loss_record() {
+        let memory = memory(5);
+        memory.record_trade_closed(&closed(-1.0, "fading the open"));
+        memory.record_trade_closed(&closed(2.5, "breakout\nabove 100"));
+        memory.record_trade_closed(&closed(-0.5, ""));
+
+        // Quoted for every agent, even without decisions of its own
+        let context = memory.prompt_context("Quant-Agent", "BTC/USD").unwrap();
+        assert!(
+            context.starts_with("Recent closed trades on BTC/USD: 1 wins / 1 losses, net +2.00%")
+        );
+        assert!(!context.contains("fading"));
+        assert!(context.ends_with("\n- +2.50%: breakout above 100\n- -0.50%"));
+        assert!(memory.prompt_context(AGENT, "ETH/USD").is_none());
+
+        // Follows the agent's own decisions
+        memory.record(AGENT, "BTC/USD", "trade long", "retest");
+        let context = memory.prompt_context(AGENT, "BTC/USD").unwrap();
+        assert!(context.starts_with("Your recent decisions on BTC/USD"));
+        assert!(context.contains("retest\n\nRecent closed trades on BTC/USD"));
+    }
+
+    // ============= Director Decision Tests =============
+
+    #[test]
+    fn test_director_decision_from_json_or_text() {
+        let (decision, reasoning) = director_decision(
+            "Here you go: {\"decision\": \"trade\", \"direction\": \"long\", \"thesis\": \"higher lows\", \"confidence\": 0.8}",
+        );
+        assert_eq!(decision, "trade long (confidence 0.80)");
+        assert_eq!(reasoning, "higher lows");
+
+        let (decision, reasoning) = director_decision("No trade - the market is flat");
+        assert_eq!(decision, "no_trade");
+        assert_eq!(reasoning, "No trade - the market is flat");
+    }
+}
@@ -1,10 +1,19 @@
 pub mod director;
 pub mod execution;
+pub mod memory;
+pub mod postmortem;
+pub mod prompt;
 pub mod quant;
 pub mod risk;
+pub mod sentiment;
 
+#[cfg(test)]
+mod memory_tests;
+#[cfg(test)]
+mod prompt_tests;
+
+use crate::error::Result;
 use crate::llm::{LLMQueue, Priority};
-use std::error::Error;
 
 use tracing::info;
 
@@ -13,20 +22,12 @@ pub trait Agent {
     fn system_prompt(&self) -> &str;
 
     /// Run the agent with normal priority (for new analysis)
-    async fn run(
-        &self,
-        query: &str,
-        llm: &LLMQueue,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+    async fn run(&self, query: &str, llm: &LLMQueue) -> Result<String> {
         self.run_with_priority(query, llm, Priority::Normal).await
     }
 
     /// Run the agent with high priority (for pipeline continuations)
-    async fn run_high_priority(
-        &self,
-        query: &str,
-        llm: &LLMQueue,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+    async fn run_high_priority(&self, query: &str, llm: &LLMQueue) -> Result<String> {
         self.run_with_priority(query, llm, Priority::High).await
     }
 
@@ -36,7 +37,7 @@ pub trait Agent {
         query: &str,
         llm: &LLMQueue,
         priority: Priority,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+    ) -> Result<String> {
         let priority_str = match priority {
             Priority::High => "HIGH",
             Priority::Normal => "NORMAL",
@@ -46,7 +47,9 @@ pub trait Agent {
             priority_str,
             self.name()
         );
-        let response = llm.chat(self.system_prompt(), query, priority).await?;
+        let response = llm
+            .chat(self.name(), self.system_prompt(), query, priority)
+            .await?;
         info!("🤖 [AGENT] Response from {}: {}", self.name(), response);
         Ok(response)
     }
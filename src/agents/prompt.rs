@@ -0,0 +1,105 @@
+//! Per-deployment agent prompts.
+//!
+//! `prompts.<agent>` replaces an agent's built-in system prompt with an
+//! inline template or a template file. Templates use handlebars-style
+//! `{{name}}` placeholders, filled from the symbol being analysed, its
+//! effective TP/SL, a few session settings and `prompts.vars`. Unknown
+//! placeholders are left as written so a typo shows up in the prompt log
+//! instead of silently vanishing.
+
+use std::collections::HashMap;
+use std::fs;
+
+use tracing::warn;
+
+use crate::config::{AppConfig, PromptTemplate};
+
+/// Agents whose system prompt can be configured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgentRole {
+    Director,
+    Quant,
+    Risk,
+    Execution,
+}
+
+impl AgentRole {
+    fn template(self, config: &AppConfig) -> Option<&PromptTemplate> {
+        let prompts = &config.prompts;
+        match self {
+            AgentRole::Director => prompts.director.as_ref(),
+            AgentRole::Quant => prompts.quant.as_ref(),
+            AgentRole::Risk => prompts.risk.as_ref(),
+            AgentRole::Execution => prompts.execution.as_ref(),
+        }
+    }
+}
+
+/// The configured system prompt of `role` for `symbol`, rendered; each
+/// agent's `new` takes it from here. None keeps the agent's built-in prompt
+/// (nothing configured, or the file is unreadable and there is no inline text
+/// to fall back to).
+pub fn configured_prompt(config: &AppConfig, role: AgentRole, symbol: &str) -> Option<String> {
+    let template = role.template(config)?;
+    let file_text = template
+        .file
+        .as_deref()
+        .and_then(|path| match fs::read_to_string(path) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                warn!(
+                    "⚠️ [PROMPTS] Cannot read {:?} prompt {}: {} - using fallback",
+                    role, path, e
+                );
+                None
+            }
+        });
+    let text = file_text.or_else(|| template.text.clone())?;
+    Some(render(&text, &template_vars(config, symbol)))
+}
+
+/// Variables available to templates; `prompts.vars` may override built-ins.
+pub fn template_vars(config: &AppConfig, symbol: &str) -> HashMap<String, String> {
+    let (tp, sl) = config.get_symbol_params(symbol);
+    let mut vars: HashMap<String, String> = [
+        ("symbol", symbol.to_string()),
+        ("tp_pct", tp.to_string()),
+        ("sl_pct", sl.to_string()),
+        ("trading_mode", config.trading_mode.clone()),
+        ("exchange", config.exchange.clone()),
+        (
+            "min_order_amount",
+            config.defaults.min_order_amount.to_string(),
+        ),
+        (
+            "max_order_amount",
+            config.defaults.max_order_amount.to_string(),
+        ),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+    vars.extend(config.prompts.vars.clone());
+    vars
+}
+
+/// Replace each `{{name}}` (whitespace inside the braces is ignored) with its
+/// value in `vars`.
+pub fn render(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + len + 4];
+        out.push_str(&rest[..start]);
+        match vars.get(placeholder[2..placeholder.len() - 2].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[start + len + 4..];
+    }
+    out.push_str(rest);
+    out
+}
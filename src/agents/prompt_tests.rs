@@ -0,0 +1,126 @@
+//! Unit tests for configurable agent prompts.
+
+#[cfg(test)]
+mod prompt_tests {
+    use std::collections::HashMap;
+
+    use crate::agents::director::DirectorAgent;
+    use crate::agents::prompt::*;
+    use crate::agents::quant::QuantAgent;
+    use crate::agents::risk::RiskAgent;
+    use crate::agents::Agent;
+    use crate::config::AppConfig;
+
+    fn test_config(prompts_yaml: &str) -> AppConfig {
+        let yaml = format!(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides:
+  ETH/USD:
+    take_profit_pct: 2.5
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "llm"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+{}
+"#,
+            prompts_yaml
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_render_fills_known_and_keeps_unknown_placeholders() {
+        let vars: HashMap<String, String> = [("symbol", "BTC/USD"), ("tp_pct", "1.5")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(
+            render("Trade {{symbol}} for +{{ tp_pct }}% ({{desk}}) {{", &vars),
+            "Trade BTC/USD for +1.5% ({{desk}}) {{"
+        );
+    }
+
+    #[test]
+    fn test_unconfigured_agents_keep_built_in_prompts() {
+        let config = test_config("");
+        let built_in = DirectorAgent::default().system_prompt().to_string();
+        assert_eq!(
+            DirectorAgent::new(&config, "BTC/USD").system_prompt(),
+            built_in
+        );
+        assert!(configured_prompt(&config, AgentRole::Execution, "BTC/USD").is_none());
+    }
+
+    #[test]
+    fn test_inline_template_uses_symbol_overrides_and_vars() {
+        let config = test_config(
+            r#"prompts:
+  vars:
+    desk: "alt desk"
+  risk:
+    text: "Risk for {{symbol}} on {{desk}}: TP {{tp_pct}}% SL {{sl_pct}}%, max ${{max_order_amount}}"
+"#,
+        );
+        assert_eq!(
+            RiskAgent::new(&config, "ETH/USD").system_prompt(),
+            "Risk for ETH/USD on alt desk: TP 2.5% SL 0.5%, max $100"
+        );
+        // Other agents are untouched
+        assert_eq!(
+            QuantAgent::new(&config, "ETH/USD").system_prompt(),
+            QuantAgent::default().system_prompt()
+        );
+    }
+
+    #[test]
+    fn test_file_template_wins_and_falls_back_to_text_when_unreadable() {
+        let path = std::env::temp_dir().join(format!(
+            "autohedge_prompt_{}.md",
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::write(&path, "Director ({{trading_mode}}) for {{symbol}}").unwrap();
+        let config = test_config(&format!(
+            "prompts:\n  director:\n    file: {:?}\n    text: \"inline {{{{symbol}}}}\"\n",
+            path.display().to_string()
+        ));
+        assert_eq!(
+            configured_prompt(&config, AgentRole::Director, "BTC/USD").as_deref(),
+            Some("Director (crypto) for BTC/USD")
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            configured_prompt(&config, AgentRole::Director, "BTC/USD").as_deref(),
+            Some("inline BTC/USD")
+        );
+    }
+}
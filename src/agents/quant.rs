@@ -1,14 +1,8 @@
+use crate::agents::prompt::{configured_prompt, AgentRole};
 use crate::agents::Agent;
+use crate::config::AppConfig;
 
-pub struct QuantAgent;
-
-impl Agent for QuantAgent {
-    fn name(&self) -> &str {
-        "Quant-Agent"
-    }
-
-    fn system_prompt(&self) -> &str {
-        r#"You are a Quantitative Analyst AI. 
+const SYSTEM_PROMPT: &str = r#"You are a Quantitative Analyst AI. 
 You will be provided with a Trading Thesis and Recent Market History.
 Analyze the tabular data to calculate/estimate technical indicators.
 
@@ -19,6 +13,28 @@ Calculate and Output JSON:
     "resistance_level": 130.00,
     "volatility_check": "pass" | "fail"
 }
-"#
+"#;
+
+#[derive(Default)]
+pub struct QuantAgent {
+    /// Rendered `prompts.quant`, replacing the built-in prompt
+    prompt: Option<String>,
+}
+
+impl QuantAgent {
+    pub fn new(config: &AppConfig, symbol: &str) -> Self {
+        Self {
+            prompt: configured_prompt(config, AgentRole::Quant, symbol),
+        }
+    }
+}
+
+impl Agent for QuantAgent {
+    fn name(&self) -> &str {
+        "Quant-Agent"
+    }
+
+    fn system_prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(SYSTEM_PROMPT)
     }
 }
@@ -1,14 +1,8 @@
+use crate::agents::prompt::{configured_prompt, AgentRole};
 use crate::agents::Agent;
+use crate::config::AppConfig;
 
-pub struct RiskAgent;
-
-impl Agent for RiskAgent {
-    fn name(&self) -> &str {
-        "Risk-Manager"
-    }
-
-    fn system_prompt(&self) -> &str {
-        r#"You are a Risk Manager AI responsible for position sizing and risk management.
+const SYSTEM_PROMPT: &str = r#"You are a Risk Manager AI responsible for position sizing and risk management.
 
 RISK RULES:
 1. Do NOT approve trades that use more than 5% of Buying Power/Cash
@@ -40,6 +34,28 @@ EXAMPLE (Crypto - DOGE at $0.10):
     "take_profit": 0.115,
     "risk_reasoning": "Entry: $0.10, SL: $0.092 (-8%), TP: $0.115 (+15%). Risk/reward: 1.88:1. Position size keeps risk at 4% of account."
 }
-"#
+"#;
+
+#[derive(Default)]
+pub struct RiskAgent {
+    /// Rendered `prompts.risk`, replacing the built-in prompt
+    prompt: Option<String>,
+}
+
+impl RiskAgent {
+    pub fn new(config: &AppConfig, symbol: &str) -> Self {
+        Self {
+            prompt: configured_prompt(config, AgentRole::Risk, symbol),
+        }
+    }
+}
+
+impl Agent for RiskAgent {
+    fn name(&self) -> &str {
+        "Risk-Manager"
+    }
+
+    fn system_prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(SYSTEM_PROMPT)
     }
 }
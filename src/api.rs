@@ -1,53 +1,250 @@
-use crate::llm::LLMQueue;
+use crate::llm::{LLMQueue, Priority};
 use axum::{
-    extract::State,
-    response::IntoResponse,
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    extract::{Extension, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use serde_json::json;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::task::JoinHandle;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use crate::config::AppConfig;
+use crate::bus::{EventBus, Shutdown};
+use crate::config::{ChatterModule, ConditionalOrderRule};
+use crate::data::correlation::CorrelationTracker;
 use crate::data::store::MarketStore;
+use crate::events::{ControlCommand, Event};
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::time_sync::TimeSync;
 use crate::exchange::traits::{MarketDataStream, TradingApi};
-use crate::exchange::ws::WsProvider;
-use crate::exchange::{factory::build_exchange, ws::GenericWsStream};
+use crate::exchange::user_stream::UserDataStream;
+use crate::exchange::{
+    factory::{build_exchange, build_exchange_with_symbols, build_symbol_registry},
+    polling::PollingMarketStream,
+    ws::GenericWsStream,
+};
+use crate::money;
+use crate::services::adaptive::AdaptiveThresholdService;
+use crate::services::api_auth::{credential, query_token, ApiAuth, AuthError, Principal, Role};
+use crate::services::arbitrage::{ArbitrageBook, ArbitrageService};
+use crate::services::balance_audit::BalanceAuditor;
+use crate::services::bulk_close::{BulkCloser, CloseFilter};
+use crate::services::cancel_on_disconnect::CancelOnDisconnect;
+use crate::services::charts::{render_svg, CHART_HEIGHT, CHART_WIDTH};
+use crate::services::circuit_breaker::CircuitBreaker;
+use crate::services::conditional_orders::{
+    parse_rule, ConditionalOrderBook, ConditionalOrderService,
+};
+use crate::services::config_reload::{
+    apply_patch, chatter_json, tunables_json, ConfigWatcher, LiveConfig,
+};
+use crate::services::daily_report::{read_report, DailyReportService};
+use crate::services::diagnostics::DiagLevel;
+use crate::services::equity::{
+    parse_window, read_snapshots, EquityCurve, EquitySnapshotter, EquityStats,
+};
+use crate::services::event_log::{read_log, EventRecorder, ReplayScope, ReplayService};
+use crate::services::halt::HaltDetector;
+use crate::services::health::HealthMonitor;
+use crate::services::hedging::Hedger;
+use crate::services::history_bootstrap::HistoryBootstrapper;
+use crate::services::llm_fallback::LlmFallbackMonitor;
+use crate::services::margin_monitor::MarginMonitor;
+use crate::services::metrics_history::{MetricsHistory, MetricsRecorder, METRICS};
+use crate::services::notifications::NotificationService;
+use crate::services::pairs::PairTrader;
+use crate::services::position_monitor::PositionTracker;
+use crate::services::regime::RegimeService;
 use crate::services::reporting::TradeReporter;
+use crate::services::sentiment::SentimentService;
+#[cfg(feature = "database")]
+use crate::services::storage::Database;
+use crate::services::storage::{DateRange, StorageWriter};
+use crate::services::trade_journal::{read_trade, TradeJournalService};
+use crate::services::trade_log::{sink_for, TradeLogFormat};
+use crate::services::watchdog::{ExchangeWatchdog, WatchedExchange};
 
 pub struct AppState {
     pub trading_handle: Mutex<Option<JoinHandle<()>>>,
     pub websocket_handle: Mutex<Option<JoinHandle<()>>>,
     pub exchange: Mutex<Option<Arc<dyn TradingApi>>>,
+    /// Shutdown signal of the running trading session (used by /stop to drain)
+    pub shutdown: Mutex<Option<Shutdown>>,
+    /// Health monitor of the running trading session (reported by /health)
+    pub health: Mutex<Option<HealthMonitor>>,
+    /// Bus, positions and market data of the running session (used by /ws)
+    pub session: Mutex<Option<Session>>,
     pub llm: LLMQueue,
-    pub config: AppConfig,
+    /// Config for the next /start; tunables are hot-reloaded into the running session
+    pub config: LiveConfig,
+    /// Database writer shared by every session (`database.enabled`)
+    pub storage: Option<StorageWriter>,
+    /// Database `/report` queries
+    #[cfg(feature = "database")]
+    pub database: Option<Database>,
 }
 
+/// Handles into a running trading session for API consumers.
+#[derive(Clone)]
+pub struct Session {
+    pub event_bus: EventBus,
+    pub tracker: PositionTracker,
+    pub market_store: MarketStore,
+    pub circuit_breaker: CircuitBreaker,
+    /// None while `conditional_orders.enabled` is false
+    pub conditional_orders: Option<ConditionalOrderBook>,
+    /// None while `arbitrage.enabled` is false
+    pub arbitrage: Option<ArbitrageBook>,
+    /// None while `equity_snapshots.enabled` is false
+    pub equity: Option<EquityCurve>,
+}
+
+/// How often /ws clients receive a positions/PnL snapshot
+const WS_POSITIONS_INTERVAL: Duration = Duration::from_secs(2);
+
 pub async fn run_server(state: Arc<AppState>) {
-    let app = Router::new()
+    let app = router(state);
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    info!("API Server listening on port 3000");
+    axum::serve(listener, app).await.unwrap();
+}
+
+/// Every API route behind the `api_auth` check.
+pub fn router(state: Arc<AppState>) -> Router {
+    let auth = Arc::new(ApiAuth::new(&state.config.snapshot().api_auth));
+    if !auth.enabled() {
+        warn!("⚠️ [API-AUTH] api_auth disabled - do not expose the API beyond localhost");
+    }
+    let router = Router::new()
         .route("/health", get(health_check))
         .route("/start", post(start_trading))
         .route("/stop", post(stop_trading))
         .route("/assets", get(get_assets))
         .route("/report", get(get_report))
+        .route("/report/daily", get(get_daily_report))
         .route("/stats", get(get_stats))
+        .route("/llm_stats", get(get_llm_stats))
         .route("/sync_positions", post(sync_positions))
         .route("/cancel_all", post(cancel_all_orders))
-        .with_state(state);
+        .route("/orders/close_all", post(close_all_positions))
+        .route("/resume", post(resume_trading))
+        .route("/positions", get(get_positions))
+        .route("/arbitrage", get(get_arbitrage))
+        .route("/equity", get(get_equity))
+        .route("/control", post(post_control))
+        .route("/debug/symbol/{*symbol}", post(debug_symbol))
+        .route("/debug/symbols", get(debug_symbols))
+        .route("/metrics", get(get_metrics))
+        .route(
+            "/conditional_orders",
+            get(list_conditional_orders).post(add_conditional_order),
+        )
+        .route(
+            "/conditional_orders/{id}",
+            axum::routing::delete(cancel_conditional_order),
+        )
+        .route("/charts/{file}", get(get_chart))
+        .route("/trades/{order_id}", get(get_trade_journal))
+        .route("/config", get(get_config).put(put_config))
+        .route("/log_level", get(get_log_level).put(put_log_level))
+        .route("/replay", post(replay_events))
+        .route("/ws", get(ws_handler))
+        .route("/dashboard", get(dashboard));
+    #[cfg(feature = "chaos")]
+    let router = router.route("/chaos", post(post_chaos));
+    router
+        .with_state(state)
+        .layer(middleware::from_fn_with_state(auth, require_auth))
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    info!("API Server listening on port 3000");
-    axum::serve(listener, app).await.unwrap();
+/// Reject requests without a credential allowed to call them (`api_auth`).
+/// `/health` stays open for keep-alive pings, and the dashboard page (which
+/// holds no data; its scripts pass the credential on) loads without one.
+/// The caller's `Principal` is passed on to the handler as an extension.
+async fn require_auth(State(auth): State<Arc<ApiAuth>>, mut req: Request, next: Next) -> Response {
+    if !auth.enabled() || matches!(req.uri().path(), "/health" | "/dashboard") {
+        return next.run(req).await;
+    }
+    let token = {
+        let header = |name: &str| req.headers().get(name).and_then(|v| v.to_str().ok());
+        credential(header("authorization"), header("x-api-key"))
+            .map(str::to_string)
+            .or_else(|| {
+                // Browsers cannot set headers on a WebSocket upgrade
+                (req.uri().path() == "/ws")
+                    .then(|| req.uri().query().and_then(query_token))
+                    .flatten()
+            })
+    };
+    match auth.authorize(token.as_deref(), req.method().as_str(), Instant::now()) {
+        Ok(principal) => {
+            req.extensions_mut().insert(principal);
+            next.run(req).await
+        }
+        Err(e) => {
+            warn!(
+                "🔒 [API-AUTH] {} {} denied: {}",
+                req.method(),
+                req.uri().path(),
+                e
+            );
+            let status = StatusCode::from_u16(e.status()).unwrap_or(StatusCode::UNAUTHORIZED);
+            let mut resp = (status, Json(json!({ "error": e.to_string() }))).into_response();
+            if let AuthError::RateLimited {
+                retry_after_secs, ..
+            } = e
+            {
+                resp.headers_mut()
+                    .insert(header::RETRY_AFTER, retry_after_secs.into());
+            }
+            resp
+        }
+    }
 }
 
-// Lightweight health check endpoint for keep-alive
-async fn health_check() -> impl IntoResponse {
+// Lightweight health check endpoint for keep-alive; includes the trading
+// health score, degradation level and circuit breaker state while a session is running
+async fn health_check(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let trading = state.health.lock().unwrap().as_ref().map(|h| {
+        let snap = h.snapshot();
+        json!({
+            "score": (snap.score * 10.0).round() / 10.0,
+            "level": snap.level.as_str(),
+            "ws_staleness_secs": snap.inputs.ws_staleness_secs.round(),
+            "rest_error_rate": snap.inputs.rest_error_rate,
+            "llm_latency_ms": snap.inputs.llm_latency_ms.map(f64::round),
+            "reconciliation_mismatches": snap.inputs.reconciliation_mismatches,
+            "updated_at": snap.updated_at,
+        })
+    });
+    let circuit_breaker = state.session.lock().unwrap().as_ref().map(|s| {
+        json!({
+            "tripped": s.circuit_breaker.is_tripped(),
+            "reason": s.circuit_breaker.trip_reason(),
+        })
+    });
+    let event_bus = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.event_bus.stats());
     Json(json!({
         "status": "ok",
         "timestamp": chrono::Utc::now().to_rfc3339(),
-        "service": "rust-autohedge"
+        "service": "rust-autohedge",
+        "trading": trading,
+        "circuit_breaker": circuit_breaker,
+        "event_bus": event_bus
     }))
 }
 use axum::extract::Query;
@@ -68,12 +265,59 @@ async fn get_assets(
         .into_response()
 }
 
-async fn get_report(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
+/// Last on-disk trade summary, if one has been written
+fn read_trade_summary() -> Option<Value> {
+    let txt = std::fs::read_to_string("./data/trade_summary.json").ok()?;
+    serde_json::from_str(&txt).ok()
+}
+
+#[derive(serde::Deserialize)]
+struct ReportParams {
+    /// YYYY-MM-DD, inclusive (database backend only)
+    from: Option<String>,
+    to: Option<String>,
+}
+
+async fn get_report(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ReportParams>,
+) -> impl IntoResponse {
+    let range = match DateRange::parse(params.from.as_deref(), params.to.as_deref()) {
+        Ok(range) => range,
+        Err(e) => return (axum::http::StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    #[cfg(feature = "database")]
+    if let Some(db) = &state.database {
+        return match db.report(range).await {
+            Ok(report) => {
+                let mut report = json!(report);
+                report["llm_usage"] = json!(state.llm.usage());
+                Json(report).into_response()
+            }
+            Err(e) => {
+                error!("❌ [DB] Report query failed: {}", e);
+                (
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Report query failed: {}", e),
+                )
+                    .into_response()
+            }
+        };
+    }
+    if !range.is_unbounded() {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            "from/to need the database backend (database.enabled)",
+        )
+            .into_response();
+    }
     // Read the on-disk summary (best-effort) to avoid storing reporter in AppState.
-    let path = std::path::PathBuf::from("./data/trade_summary.json");
-    match std::fs::read_to_string(&path) {
-        Ok(txt) => (axum::http::StatusCode::OK, txt).into_response(),
-        Err(_) => (
+    match read_trade_summary() {
+        Some(mut report) => {
+            report["llm_usage"] = json!(state.llm.usage());
+            Json(report).into_response()
+        }
+        None => (
             axum::http::StatusCode::NOT_FOUND,
             "No report found yet. Start trading first.",
         )
@@ -81,6 +325,63 @@ async fn get_report(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
     }
 }
 
+#[derive(serde::Deserialize)]
+struct DailyReportParams {
+    /// YYYY-MM-DD; defaults to the most recent report
+    date: Option<String>,
+}
+
+// End-of-day session summary written by the daily report job (markdown)
+async fn get_daily_report(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DailyReportParams>,
+) -> impl IntoResponse {
+    let date = match params.date.as_deref() {
+        Some(d) => match chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                return (
+                    axum::http::StatusCode::BAD_REQUEST,
+                    "date must be YYYY-MM-DD",
+                )
+                    .into_response()
+            }
+        },
+        None => None,
+    };
+    let dir = state.config.snapshot().daily_report.dir.clone();
+    match read_report(std::path::Path::new(&dir), date) {
+        Some((_, markdown)) => (
+            [(
+                axum::http::header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8",
+            )],
+            markdown,
+        )
+            .into_response(),
+        None => (
+            axum::http::StatusCode::NOT_FOUND,
+            "No daily report found yet.",
+        )
+            .into_response(),
+    }
+}
+
+// LLM token usage and estimated cost (total, per agent, hourly, daily) next
+// to realized PnL, to tell whether the LLM pipeline pays for its API fees
+async fn get_llm_stats(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let usage = state.llm.usage();
+    let realized_pnl = read_trade_summary()
+        .and_then(|s| s.get("total_realized_pnl").and_then(money::from_json))
+        .map(money::float);
+    Json(json!({
+        "cost_usd": usage.total.cost_usd,
+        "realized_pnl": realized_pnl,
+        "net_after_llm": realized_pnl.map(|pnl| pnl - usage.total.cost_usd),
+        "usage": usage,
+    }))
+}
+
 async fn get_stats(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
     // Read the computed stats (smaller, easier to read)
     let path = std::path::PathBuf::from("./data/trade_stats.json");
@@ -99,23 +400,116 @@ async fn get_stats(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
 }
 
 async fn start_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(json!({"status": start_session(&state)}))
+}
+
+/// Build the exchange and spawn a trading session; returns the status
+/// reported by `/start` (and gRPC `Start`).
+pub fn start_session(state: &AppState) -> &'static str {
     let mut handle_lock = state.trading_handle.lock().unwrap();
     let ws_handle_lock = state.websocket_handle.lock().unwrap();
 
     if handle_lock.is_some() {
-        return Json(json!({"status": "already_running"})).into_response();
+        return "already_running";
     }
 
     let llm = state.llm.clone();
-    let config = state.config.clone();
+    let live_config = state.config.clone();
+    let config = live_config.snapshot();
 
-    // Build exchange synchronously and store in state
-    let (exchange, maybe_store) = build_exchange(&config);
+    // Create Event Bus (carries the shutdown signal /stop uses to drain services)
+    let event_bus = crate::bus::EventBus::new(config.event_bus.queue_capacity)
+        .with_service_capacity(config.event_bus.service_capacity.clone());
+    {
+        let mut shutdown_lock = state.shutdown.lock().unwrap();
+        *shutdown_lock = Some(event_bus.shutdown().clone());
+    }
+    // Record the session from its first event (replay with POST /replay)
+    if config.event_log.enabled {
+        EventRecorder::new(&config.event_log, event_bus.clone()).start();
+    }
+    if config.trade_journal.enabled {
+        TradeJournalService::new(&config.trade_journal, event_bus.clone()).start();
+    }
+
+    // Build exchange synchronously and store in state. All REST calls go
+    // through the watchdog so repeated failures switch to safety mode, and
+    // entry rejections feed halt/delisting detection.
+    // One symbol registry for the session: REST adapters, WS parsers, the user
+    // stream and the tracker all resolve venue spellings to the same ids
+    let symbol_registry = build_symbol_registry(&config);
+    let (raw_exchange, maybe_store) = build_exchange_with_symbols(&config, symbol_registry.clone());
+    // Soak runs: inject venue, stream and LLM faults (`chaos.*`)
+    #[cfg(feature = "chaos")]
+    let raw_exchange: Arc<dyn TradingApi> = match crate::chaos::install(&config.chaos) {
+        Some(chaos) => Arc::new(crate::chaos::ChaosExchange::new(raw_exchange, chaos)),
+        None => raw_exchange,
+    };
+    #[cfg(not(feature = "chaos"))]
+    if config.chaos.enabled {
+        warn!("⚠️ [CHAOS] chaos.enabled is set but this build lacks the `chaos` feature");
+    }
+    let watchdog = ExchangeWatchdog::new(config.watchdog.clone());
+    let halt_detector = HaltDetector::new(event_bus.clone(), &config);
+    let exchange: Arc<dyn TradingApi> = Arc::new(
+        WatchedExchange::new(raw_exchange.clone(), watchdog.clone())
+            .with_halt_detector(halt_detector.clone()),
+    );
     {
         let mut exchange_lock = state.exchange.lock().unwrap();
         *exchange_lock = Some(exchange.clone());
     }
 
+    // Position Tracker (shared between Execution, Monitor and the health score)
+    let position_tracker = PositionTracker::new().with_symbol_registry(symbol_registry.clone());
+    let health = HealthMonitor::new(
+        event_bus.clone(),
+        &config,
+        watchdog.clone(),
+        llm.clone(),
+        position_tracker.clone(),
+    );
+    {
+        let mut health_lock = state.health.lock().unwrap();
+        *health_lock = Some(health.clone());
+    }
+
+    // Market store: if exchange doesn't provide one, make a local one.
+    let market_store = maybe_store.unwrap_or_else(|| MarketStore::new(config.history_limit));
+    market_store.tick_filter.configure(&config.tick_filter);
+    market_store.data_quality.configure(&config.data_quality);
+    for (currency, rate) in &config.fx.rates {
+        market_store.set_fx_rate(currency, *rate);
+    }
+    // Real fill updates from the venue's user-data stream, when it has one
+    let user_stream = UserDataStream::for_exchange(&config, exchange.name())
+        .map(|stream| stream.with_symbol_registry(symbol_registry.clone()));
+    let streamed_fills = user_stream.is_some();
+    let circuit_breaker =
+        CircuitBreaker::new(event_bus.clone(), &config).with_streamed_fills(streamed_fills);
+    let conditional_orders = config
+        .conditional_orders
+        .enabled
+        .then(|| ConditionalOrderBook::from_config(&config.conditional_orders));
+    let arbitrage = config.arbitrage.enabled.then(ArbitrageBook::new);
+    let equity = config
+        .equity_snapshots
+        .enabled
+        .then(|| EquitySnapshotter::new(event_bus.clone(), &config, market_store.clone()));
+    {
+        let mut session_lock = state.session.lock().unwrap();
+        *session_lock = Some(Session {
+            event_bus: event_bus.clone(),
+            tracker: position_tracker.clone(),
+            market_store: market_store.clone(),
+            circuit_breaker: circuit_breaker.clone(),
+            conditional_orders: conditional_orders.clone(),
+            arbitrage: arbitrage.clone(),
+            equity: equity.as_ref().map(EquitySnapshotter::curve),
+        });
+    }
+
+    let storage = state.storage.clone();
     let handle = tokio::spawn(async move {
         let trading_mode = config.trading_mode.clone();
         let is_crypto = trading_mode.to_lowercase() == "crypto";
@@ -123,65 +517,164 @@ async fn start_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse
 
         let symbols = config.symbols.clone();
 
-        // Create Event Bus
-        let event_bus = crate::bus::EventBus::new(1000);
+        // Correct signed-request timestamps for local clock drift before the first private call
+        if config.time_sync.enabled {
+            let time_sync = TimeSync::new(raw_exchange.clone(), &config.time_sync);
+            time_sync.sync().await;
+            time_sync.start(event_bus.shutdown().clone());
+        }
 
-        // Market store: if exchange doesn't provide one, make a local one.
-        let market_store = maybe_store.unwrap_or_else(|| MarketStore::new(config.history_limit));
+        // Reconcile orders the last run left working, then keep the venue's dead man's switch armed
+        if config.cancel_on_disconnect.enabled {
+            let guard = CancelOnDisconnect::new(
+                exchange.clone(),
+                position_tracker.clone(),
+                &config.cancel_on_disconnect,
+            );
+            guard.sweep().await;
+            guard.start(event_bus.shutdown().clone());
+        }
 
-        // Start Streaming (provider-specific WS)
-        let ws_provider = match exchange.name() {
-            "alpaca" => {
-                let api_key = config.alpaca.api_key.clone();
-                let secret = config.alpaca.secret_key.clone();
-                GenericWsStream::alpaca(api_key, secret, is_crypto)
-            }
-            "binance" => {
-                let (key, secret) = if let Some(c) = &config.binance {
-                    (Some(c.api_key.clone()), Some(c.secret_key.clone()))
-                } else {
-                    (None, None)
-                };
-                GenericWsStream::binance(key, secret)
-            }
-            "coinbase" => {
-                let (key, secret) = if let Some(c) = &config.coinbase {
-                    (Some(c.api_key.clone()), Some(c.secret_key.clone()))
-                } else {
-                    (None, None)
-                };
-                GenericWsStream::coinbase(key, secret)
-            }
-            "kraken" => {
-                let (key, secret) = if let Some(c) = &config.kraken {
-                    (Some(c.api_key.clone()), Some(c.secret_key.clone()))
-                } else {
-                    (None, None)
-                };
-                GenericWsStream::kraken(key, secret)
-            }
-            _ => GenericWsStream {
-                provider: WsProvider::AlpacaCrypto,
-                api_key: None,
-                api_secret: None,
-            },
+        // Backfill recent bars so strategies are past warm-up before live data arrives
+        if config.history_bootstrap.enabled {
+            HistoryBootstrapper::new(exchange.clone(), market_store.clone(), &config)
+                .run(&symbols)
+                .await;
+        }
+
+        // Tick/step sizes and minimums used to round orders before submission
+        let symbol_meta = SymbolMetaRegistry::new();
+        symbol_meta.load(exchange.as_ref(), &symbols).await;
+
+        // Start Streaming (provider-specific WS, or REST polling if configured)
+        let market_stream: Box<dyn MarketDataStream> = if config.market_data.polling() {
+            Box::new(PollingMarketStream::new(
+                exchange.clone(),
+                &config.market_data,
+            ))
+        } else {
+            let ws = GenericWsStream::for_exchange(&config, exchange.name());
+            Box::new(
+                ws.with_symbol_registry(symbol_registry.clone())
+                    .with_chatter(config.chatter_for(ChatterModule::Ws)),
+            )
         };
 
-        if let Err(e) = ws_provider
+        if let Err(e) = market_stream
             .start(market_store.clone(), symbols.clone(), event_bus.clone())
             .await
         {
-            error!("WS start failed: {}", e);
+            error!("Market data stream start failed: {}", e);
         }
 
         info!("Initializing EDA Services...");
 
-        // Start Trade Reporter (writes JSONL + summary under ./data)
-        let reporter = TradeReporter::new(std::path::PathBuf::from("./data/trades.jsonl"));
+        // Probe the raw venue while REST failures are outstanding
+        watchdog.start_heartbeat(raw_exchange, event_bus.shutdown().clone());
+
+        // Follow venue halt notices (symbols go exit-only)
+        halt_detector.start().await;
+
+        // Score system health and walk the degradation ladder
+        health.start().await;
+
+        // Append-only metrics history behind /charts
+        if config.metrics_history.enabled {
+            MetricsRecorder::new(
+                event_bus.clone(),
+                &config,
+                llm.clone(),
+                position_tracker.clone(),
+            )
+            .with_server_clock(exchange.server_clock())
+            .with_tick_filter(market_store.tick_filter.clone())
+            .start()
+            .await;
+        }
+
+        // Kill switch on drawdown / losing streak / order errors (reset via /resume)
+        circuit_breaker.start().await;
+
+        // Apply config file edits to the running services
+        if config.config_reload.enabled {
+            ConfigWatcher::new(event_bus.clone(), live_config, &config)
+                .start()
+                .await;
+        }
+
+        // Start Trade Reporter (writes the trade log + summary under ./data)
+        let log_format = TradeLogFormat::parse(&config.trade_log.format).unwrap_or_else(|| {
+            warn!(
+                "⚠️ Unknown trade_log.format '{}', using jsonl",
+                config.trade_log.format
+            );
+            TradeLogFormat::Jsonl
+        });
+        let reporter = TradeReporter::new(std::path::PathBuf::from(format!(
+            "./data/trades.{}",
+            log_format.extension()
+        )))
+        .with_sink(Arc::from(sink_for(log_format)))
+        .with_streamed_fills(streamed_fills)
+        .with_fx(market_store.clone(), &config)
+        .with_mark_to_market(market_store.clone(), &config.mark_to_market)
+        .with_drawdown_sizing(&config.sizing.drawdown)
+        .with_ab_test(&config)
+        .with_storage(storage);
+        if config.sizing.drawdown.enabled && !config.mark_to_market.enabled {
+            warn!("⚠️ sizing.drawdown needs mark_to_market.enabled for the equity curve - entries stay full size");
+        }
         reporter.start(event_bus.clone()).await;
 
-        // Create Position Tracker (shared between Execution and Monitor)
-        let position_tracker = crate::services::position_monitor::PositionTracker::new();
+        // The reporter's valuation, persisted and windowed behind /equity
+        if let Some(equity) = &equity {
+            equity.start(reporter.clone()).await;
+        }
+
+        // Attribute venue cash changes to fills, fees, funding and transfers
+        if config.balance_audit.enabled {
+            BalanceAuditor::new(exchange.clone(), event_bus.clone(), &config)
+                .with_streamed_fills(streamed_fills)
+                .start()
+                .await;
+        }
+
+        // User-defined orders that fire on market conditions
+        if let Some(book) = conditional_orders {
+            ConditionalOrderService::new(book, market_store.clone(), event_bus.clone())
+                .start()
+                .await;
+        }
+
+        // Operator alerts (Telegram / Discord / webhook)
+        if config.notifications.enabled {
+            NotificationService::new(&config.notifications, event_bus.clone())
+                .start()
+                .await;
+        }
+
+        // End-of-day session summary under daily_report.dir
+        if config.daily_report.enabled {
+            let daily = DailyReportService::new(
+                &config.daily_report,
+                reporter.clone(),
+                llm.clone(),
+                event_bus.clone(),
+            );
+            if let Err(e) = daily.start().await {
+                warn!("⚠️ Failed to schedule daily report: {}", e);
+            }
+        }
+
+        // Order fill updates (partial fills, real prices) once consumers are subscribed
+        if let Some(user_stream) = &user_stream {
+            user_stream.start(event_bus.clone()).await;
+        }
+
+        // Indicator rules take over llm/hybrid decisions while the LLM is failing
+        if config.llm_fallback.enabled {
+            LlmFallbackMonitor::new(event_bus.clone(), llm.clone(), &config).start();
+        }
 
         // Start Strategy Engine
         let strategy_engine = crate::services::strategy::StrategyEngine::new(
@@ -192,17 +685,53 @@ async fn start_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse
         );
         strategy_engine.start().await;
 
+        // Score news per symbol for the strategies (HFT entry veto, LLM context)
+        if config.sentiment.enabled {
+            SentimentService::new(
+                event_bus.clone(),
+                market_store.clone(),
+                llm.clone(),
+                config.clone(),
+            )
+            .start()
+            .await;
+        }
+
+        // Classify each symbol's regime (gates strategies per `regime.strategies`)
+        if config.regime.enabled {
+            RegimeService::new(event_bus.clone(), market_store.clone(), config.clone())
+                .start()
+                .await;
+        }
+
+        // Tune HFT entry thresholds per symbol from fill and win rates
+        if config.adaptive_hft.enabled {
+            AdaptiveThresholdService::new(event_bus.clone(), market_store.clone(), config.clone())
+                .start();
+        }
+
         // Start Risk Engine
-        let risk_engine = crate::services::risk::RiskEngine::new(
+        let mut risk_engine = crate::services::risk::RiskEngine::new(
             event_bus.clone(),
             exchange.clone(),
             llm.clone(),
             config.clone(),
-        );
+        )
+        .with_market_store(market_store.clone());
+        if config.correlation_guard.enabled {
+            let correlation = CorrelationTracker::new(&config.correlation_guard);
+            correlation.clone().start(
+                market_store.clone(),
+                symbols.clone(),
+                event_bus.shutdown().clone(),
+            );
+            risk_engine = risk_engine.with_correlation_guard(correlation, position_tracker.clone());
+        }
+        let policy = risk_engine.policy();
         risk_engine.start().await;
 
-        // Start Execution Engine (use fast engine for HFT mode)
-        if config.strategy_mode.to_lowercase() == "hft" {
+        // Start Execution Engine (use fast engine when any symbol runs HFT)
+        if config.uses_strategy_mode("hft") {
             info!("⚡ Using Fast Execution Engine for HFT mode");
             let execution_engine = crate::services::execution_fast::ExecutionEngine::new(
                 event_bus.clone(),
@@ -211,7 +740,10 @@ async fn start_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse
                 llm.clone(),
                 config.clone(),
                 position_tracker.clone(),
-            );
+            )
+            .with_symbol_meta(symbol_meta.clone())
+            .with_reporter(reporter.clone())
+            .with_policy(policy);
             execution_engine.start().await;
         } else {
             let execution_engine = crate::services::execution::ExecutionEngine::new(
@@ -221,64 +753,724 @@ async fn start_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse
                 llm.clone(),
                 config.clone(),
                 position_tracker.clone(),
-            );
+            )
+            .with_symbol_meta(symbol_meta.clone())
+            .with_policy(policy);
             execution_engine.start().await;
         }
 
+        // Spread trades on configured pairs, next to the strategy mode
+        if config.pairs.enabled {
+            PairTrader::new(
+                event_bus.clone(),
+                exchange.clone(),
+                market_store.clone(),
+                symbol_meta.clone(),
+                &config,
+            )
+            .start()
+            .await;
+        }
+
+        // Same symbols compared across venues, traded with `arbitrage.execute`
+        if let Some(book) = arbitrage {
+            ArbitrageService::connect(
+                event_bus.clone(),
+                exchange.clone(),
+                symbol_meta.clone(),
+                book,
+                &config,
+            )
+            .await
+            .start();
+        }
+
+        // Offset large exposure to the reference asset in the hedge instrument
+        if config.hedging.enabled {
+            Hedger::new(
+                event_bus.clone(),
+                exchange.clone(),
+                position_tracker.clone(),
+                market_store.clone(),
+                symbol_meta.clone(),
+                &config,
+            )
+            .start();
+        }
+
+        // Futures: reduce positions before the margin ratio reaches a margin call
+        if config
+            .futures()
+            .is_some_and(|futures| futures.margin_monitor.enabled)
+        {
+            MarginMonitor::new(
+                event_bus.clone(),
+                exchange.clone(),
+                position_tracker.clone(),
+                market_store.clone(),
+                symbol_meta.clone(),
+                &config,
+            )
+            .start();
+        }
+
         // Start Position Monitor
         let position_monitor = crate::services::position_monitor::PositionMonitor::new(
             event_bus.clone(),
             exchange.clone(),
             position_tracker.clone(),
             config.clone(),
-        );
+        )
+        .with_watchdog(watchdog);
         position_monitor.start().await;
 
         info!("🚀 All EDA Services Started. Trading System Active.");
 
-        loop {
-            tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
-        }
+        event_bus.shutdown().stopped().await;
     });
 
     *handle_lock = Some(handle);
 
-    Json(json!({"status": "started"})).into_response()
+    "started"
 }
 
 async fn stop_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let mut handle_lock = state.trading_handle.lock().unwrap();
-    let mut ws_handle_lock = state.websocket_handle.lock().unwrap();
+    Json(json!({"status": stop_session(&state).await}))
+}
+
+/// Drain and stop the running session; returns the status reported by
+/// `/stop` (and gRPC `Stop`).
+pub async fn stop_session(state: &AppState) -> &'static str {
+    // Take everything out of state first: the locks must not be held across awaits.
+    let handle = state.trading_handle.lock().unwrap().take();
+    let ws_handle = state.websocket_handle.lock().unwrap().take();
+    let exchange = state.exchange.lock().unwrap().take();
+    let shutdown = state.shutdown.lock().unwrap().take();
+    state.health.lock().unwrap().take();
+    state.session.lock().unwrap().take();
 
     let mut stopped_something = false;
 
-    // Abort the main trading task (which contains all the spawned services including WS)
-    if let Some(handle) = handle_lock.take() {
+    // Graceful drain: stop intake, let in-flight orders finish, flush, then exit
+    if let Some(shutdown) = shutdown {
+        let cfg = state.config.snapshot().shutdown;
+        info!(
+            "🛑 Draining trading system ({} order(s) in flight, timeout {}s)...",
+            shutdown.orders_in_flight(),
+            cfg.drain_timeout_secs
+        );
+        shutdown.begin_drain();
+        if !shutdown
+            .wait_orders(Duration::from_secs(cfg.drain_timeout_secs))
+            .await
+        {
+            warn!(
+                "⚠️ Drain timed out with {} order(s) still in flight",
+                shutdown.orders_in_flight()
+            );
+        }
+
+        if cfg.cancel_open_orders {
+            if let Some(ex) = &exchange {
+                match ex.cancel_all_orders().await {
+                    Ok(_) => info!("🧹 Cancelled open orders"),
+                    Err(e) => error!("❌ Failed to cancel open orders on stop: {}", e),
+                }
+            }
+        }
+
+        shutdown.finish();
+        if !shutdown.wait_workers(Duration::from_secs(5)).await {
+            warn!("⚠️ Reporter did not flush before stop completed");
+        }
+        stopped_something = true;
+    }
+
+    // Abort whatever is still running (the trading task normally exits on its own)
+    if let Some(handle) = handle {
         info!("Aborting trading task...");
         handle.abort();
         stopped_something = true;
     }
 
     // Abort WebSocket handle if it exists separately
-    if let Some(ws_handle) = ws_handle_lock.take() {
+    if let Some(ws_handle) = ws_handle {
         info!("Aborting WebSocket task...");
         ws_handle.abort();
         stopped_something = true;
     }
 
-    // Clear exchange from state
-    {
-        let mut exchange_lock = state.exchange.lock().unwrap();
-        if exchange_lock.take().is_some() {
-            info!("Cleared exchange from state");
-        }
+    if exchange.is_some() {
+        info!("Cleared exchange from state");
     }
 
     if stopped_something {
         info!("✅ Trading system stopped successfully");
-        Json(json!({"status": "stopped"})).into_response()
+        "stopped"
     } else {
-        Json(json!({"status": "not_running"})).into_response()
+        "not_running"
+    }
+}
+
+/// Manually reset a tripped circuit breaker so new entries are accepted again.
+async fn resume_trading(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let breaker = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.circuit_breaker.clone());
+    let Some(breaker) = breaker else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+    match breaker.resume() {
+        Some(reason) => Json(json!({"status": "resumed", "cleared": reason})).into_response(),
+        None => Json(json!({"status": "not_tripped"})).into_response(),
+    }
+}
+
+/// Current hot-reloadable tunables.
+async fn get_config(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(tunables_json(&state.config.snapshot()))
+}
+
+/// Update tunables, e.g. `{"hft.min_edge_bps": 8, "chatter_level": "verbose"}`.
+/// A running session picks the change up without a restart.
+async fn put_config(
+    State(state): State<Arc<AppState>>,
+    Json(patch): Json<serde_json::Map<String, Value>>,
+) -> impl IntoResponse {
+    let bus = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.event_bus.clone());
+    match state
+        .config
+        .apply(bus.as_ref(), "api", |config| apply_patch(config, &patch))
+    {
+        Ok(changed) => Json(json!({
+            "status": "ok",
+            "changed": changed,
+            "running": bus.is_some(),
+        }))
+        .into_response(),
+        Err(e) => (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
+/// Effective chatter level per module.
+async fn get_log_level(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let config = state.config.snapshot();
+    Json(json!({
+        "default": config.chatter_level.as_str(),
+        "modules": chatter_json(&config),
+    }))
+}
+
+#[derive(serde::Deserialize)]
+struct LogLevelParams {
+    /// "strategy", "execution", "monitor" or "ws"; omitted sets the default
+    module: Option<String>,
+    /// "low", "normal", "verbose", or "default" to drop a module's override
+    level: String,
+}
+
+/// Change log verbosity at runtime, e.g. `PUT /log_level?module=monitor&level=verbose`.
+/// Goes through the same path as `PUT /config`, so a running session picks it up.
+async fn put_log_level(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<LogLevelParams>,
+) -> impl IntoResponse {
+    let (key, value) = match params.module.as_deref() {
+        None => ("chatter_level".to_string(), json!(params.level)),
+        Some(module) => {
+            let value = if params.level.eq_ignore_ascii_case("default") {
+                Value::Null
+            } else {
+                json!(params.level)
+            };
+            (format!("chatter.{}", module.to_lowercase()), value)
+        }
+    };
+    let patch = serde_json::Map::from_iter([(key, value)]);
+    let bus = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.event_bus.clone());
+    match state
+        .config
+        .apply(bus.as_ref(), "api", |config| apply_patch(config, &patch))
+    {
+        Ok(changed) => {
+            let config = state.config.snapshot();
+            Json(json!({
+                "status": "ok",
+                "changed": !changed.is_empty(),
+                "default": config.chatter_level.as_str(),
+                "modules": chatter_json(&config),
+            }))
+            .into_response()
+        }
+        Err(e) => (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ReplayParams {
+    /// Log file name under event_log.dir
+    file: String,
+    /// 1.0 = original timing, 10.0 = ten times faster, 0 = back to back
+    #[serde(default = "default_replay_speed")]
+    speed: f64,
+    /// "market" (default) or "all"
+    scope: Option<String>,
+}
+
+fn default_replay_speed() -> f64 {
+    1.0
+}
+
+/// Re-publish a recorded session onto the running bus.
+async fn replay_events(
+    State(state): State<Arc<AppState>>,
+    Json(params): Json<ReplayParams>,
+) -> impl IntoResponse {
+    let bad_request = |msg: String| {
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"error": msg})),
+        )
+            .into_response()
+    };
+    let bus = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.event_bus.clone());
+    let Some(bus) = bus else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+    let scope = match params.scope.as_deref() {
+        None => ReplayScope::Market,
+        Some(name) => match ReplayScope::parse(name) {
+            Some(scope) => scope,
+            None => return bad_request(format!("unknown scope '{}' (market, all)", name)),
+        },
+    };
+    let config = state.config.snapshot();
+    // Replayed orders would be executed for real
+    if scope == ReplayScope::All && !config.is_paper_venue() {
+        return bad_request("scope 'all' is only allowed on paper venues".to_string());
+    }
+    // Only plain file names: the log directory is the only place read from
+    if std::path::Path::new(&params.file).file_name() != Some(params.file.as_ref()) {
+        return bad_request("file must be a file name under event_log.dir".to_string());
+    }
+    let path = std::path::Path::new(&config.event_log.dir).join(&params.file);
+    let records = match read_log(&path) {
+        Ok(records) => records,
+        Err(e) => {
+            return (
+                axum::http::StatusCode::NOT_FOUND,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+
+    let total = records.len();
+    let speed = params.speed;
+    info!(
+        "⏪ [REPLAY] Replaying {} ({} events, {:?} at {}x)",
+        params.file, total, scope, speed
+    );
+    tokio::spawn(async move {
+        let published = ReplayService::new(bus, speed, scope).run(records).await;
+        info!("⏪ [REPLAY] Done: {} event(s) re-published", published);
+    });
+    Json(json!({
+        "status": "replaying",
+        "file": params.file,
+        "events": total,
+        "speed": speed,
+    }))
+    .into_response()
+}
+
+#[derive(serde::Deserialize)]
+struct DebugSymbolParams {
+    /// "debug"/"verbose", "trace" or "off"
+    level: Option<String>,
+    /// Seconds until diagnostics switch off again (default 300, max 3600)
+    ttl: Option<u64>,
+}
+
+/// Temporarily elevate diagnostics for one symbol (see `services/diagnostics.rs`).
+async fn debug_symbol(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Path(symbol): axum::extract::Path<String>,
+    Query(params): Query<DebugSymbolParams>,
+) -> impl IntoResponse {
+    let bus = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.event_bus.clone());
+    let Some(bus) = bus else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+
+    let level = params.level.as_deref().unwrap_or("trace");
+    if level.eq_ignore_ascii_case("off") {
+        let was_active = bus.diagnostics().disable(&symbol);
+        return Json(json!({"symbol": symbol, "status": "off", "was_active": was_active}))
+            .into_response();
+    }
+    let Some(level) = DiagLevel::parse(level) else {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("unknown level '{}' (debug, trace, off)", level)})),
+        )
+            .into_response();
+    };
+
+    let ttl = bus.diagnostics().enable(
+        &symbol,
+        level,
+        Duration::from_secs(params.ttl.unwrap_or(300).max(1)),
+    );
+    Json(json!({
+        "symbol": symbol,
+        "status": "on",
+        "level": level.as_str(),
+        "ttl_secs": ttl.as_secs(),
+        "expires_at": (chrono::Utc::now() + chrono::Duration::seconds(ttl.as_secs() as i64)).to_rfc3339(),
+    }))
+    .into_response()
+}
+
+/// Symbols currently under per-symbol diagnostics, and quotes dropped by the
+/// tick filter.
+async fn debug_symbols(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let session = state.session.lock().unwrap().clone();
+    let rejected_ticks = session
+        .as_ref()
+        .map(|s| s.market_store.tick_filter.rejected())
+        .unwrap_or_default();
+    let active: Vec<Value> = session
+        .as_ref()
+        .map(|s| s.event_bus.diagnostics().active())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(symbol, level, remaining)| {
+            json!({
+                "symbol": symbol,
+                "level": level.as_str(),
+                "remaining_secs": remaining.as_secs(),
+            })
+        })
+        .collect();
+    Json(json!({ "symbols": active, "rejected_ticks": rejected_ticks }))
+}
+
+#[cfg(feature = "chaos")]
+#[derive(serde::Deserialize)]
+struct ChaosParams {
+    paused: bool,
+}
+
+/// Pause or resume fault injection, e.g. to let a soak run settle before
+/// comparing positions with the venue.
+#[cfg(feature = "chaos")]
+async fn post_chaos(Query(params): Query<ChaosParams>) -> impl IntoResponse {
+    let Some(chaos) = crate::chaos::injector() else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "chaos_disabled"})),
+        )
+            .into_response();
+    };
+    chaos.set_paused(params.paused);
+    Json(json!({"status": "ok", "paused": params.paused, "stats": chaos.stats()})).into_response()
+}
+
+/// Per-symbol feed quality (`data_quality`) and dropped ticks.
+async fn get_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let session = state.session.lock().unwrap().clone();
+    let Some(session) = session else {
+        return Json(json!({ "data_quality": {}, "rejected_ticks": {} }));
+    };
+    #[allow(unused_mut)]
+    let mut metrics = json!({
+        "data_quality": session.market_store.data_quality.snapshot(),
+        "rejected_ticks": session.market_store.tick_filter.rejected(),
+    });
+    #[cfg(feature = "chaos")]
+    if let Some(stats) = crate::chaos::stats() {
+        metrics["chaos"] = json!(stats);
+    }
+    Json(metrics)
+}
+
+/// Body of `POST /conditional_orders`: a rule sentence or a structured rule.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ConditionalOrderBody {
+    Sentence {
+        /// e.g. "BUY 100 USD of SOL/USD if drop_from_high_pct >= 3"
+        rule: String,
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        order_type: Option<String>,
+        #[serde(default)]
+        repeat: bool,
+        #[serde(default)]
+        cooldown_secs: Option<u64>,
+    },
+    Rule(ConditionalOrderRule),
+}
+
+/// Combined arbitrage positions: inventory moved per venue and P&L.
+async fn get_arbitrage(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let session = state.session.lock().unwrap().clone();
+    let Some(session) = session else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+    match session.arbitrage {
+        Some(book) => {
+            let positions: Vec<Value> = book
+                .snapshot()
+                .into_iter()
+                .map(|p| {
+                    let net = p.net();
+                    let mut value = json!(p);
+                    value["net"] = json!(net);
+                    value
+                })
+                .collect();
+            Json(json!({ "positions": positions })).into_response()
+        }
+        None => (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "error", "message": "arbitrage.enabled is false"})),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct EquityParams {
+    /// Lookback such as `30m`, `24h` or `7d` (default 24h)
+    window: Option<String>,
+}
+
+/// Account equity snapshots over `window` for charting, with a summary. Read
+/// from the daily files while no session is running.
+async fn get_equity(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<EquityParams>,
+) -> impl IntoResponse {
+    let window = params.window.unwrap_or_else(|| "24h".to_string());
+    let span = match parse_window(&window) {
+        Ok(span) => span,
+        Err(e) => {
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                Json(json!({"error": e})),
+            )
+                .into_response()
+        }
+    };
+    let now = chrono::Utc::now();
+    let since = now
+        .checked_sub_signed(span)
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+    let curve = state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|s| s.equity.clone());
+    let snapshots = match curve {
+        Some(curve) => curve.since(since),
+        None => {
+            let config = state.config.snapshot().equity_snapshots;
+            if !config.enabled {
+                return (
+                    axum::http::StatusCode::CONFLICT,
+                    Json(
+                        json!({"status": "error", "message": "equity_snapshots.enabled is false"}),
+                    ),
+                )
+                    .into_response();
+            }
+            read_snapshots(std::path::Path::new(&config.dir), since, now)
+        }
+    };
+    Json(json!({
+        "window": window,
+        "count": snapshots.len(),
+        "stats": EquityStats::from_snapshots(&snapshots),
+        "snapshots": snapshots,
+    }))
+    .into_response()
+}
+
+/// The session's conditional order book, or the 409 to answer with.
+fn conditional_order_book(
+    state: &AppState,
+) -> Result<ConditionalOrderBook, (axum::http::StatusCode, Json<Value>)> {
+    let conflict = |body: Value| (axum::http::StatusCode::CONFLICT, Json(body));
+    let session = state.session.lock().unwrap().clone();
+    let Some(session) = session else {
+        return Err(conflict(json!({"status": "not_running"})));
+    };
+    session.conditional_orders.ok_or_else(|| {
+        conflict(json!({"status": "error", "message": "conditional_orders.enabled is false"}))
+    })
+}
+
+async fn list_conditional_orders(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match conditional_order_book(&state) {
+        Ok(book) => Json(json!({ "orders": book.list() })).into_response(),
+        Err(conflict) => conflict.into_response(),
+    }
+}
+
+async fn add_conditional_order(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ConditionalOrderBody>,
+) -> impl IntoResponse {
+    let book = match conditional_order_book(&state) {
+        Ok(book) => book,
+        Err(conflict) => return conflict.into_response(),
+    };
+    let rule = match body {
+        ConditionalOrderBody::Rule(rule) => Ok(rule),
+        ConditionalOrderBody::Sentence {
+            rule,
+            id,
+            order_type,
+            repeat,
+            cooldown_secs,
+        } => parse_rule(&rule).map(|mut rule| {
+            rule.id = id;
+            rule.order_type = order_type.unwrap_or(rule.order_type);
+            rule.repeat = repeat;
+            rule.cooldown_secs = cooldown_secs.unwrap_or(rule.cooldown_secs);
+            rule
+        }),
+    };
+    match rule.and_then(|rule| book.add(rule)) {
+        Ok(order) => Json(json!({"status": "armed", "order": order})).into_response(),
+        Err(e) => (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"status": "error", "message": e})),
+        )
+            .into_response(),
+    }
+}
+
+async fn cancel_conditional_order(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let book = match conditional_order_book(&state) {
+        Ok(book) => book,
+        Err(conflict) => return conflict.into_response(),
+    };
+    if book.cancel(&id) {
+        Json(json!({"status": "cancelled", "id": id})).into_response()
+    } else {
+        (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("no conditional order '{}'", id)})),
+        )
+            .into_response()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ChartParams {
+    /// Most recent samples to draw (default `metrics_history.chart_points`)
+    points: Option<usize>,
+}
+
+/// Render a recorded metric as an SVG area chart (`/charts/equity.svg`).
+async fn get_chart(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Path(file): axum::extract::Path<String>,
+    Query(params): Query<ChartParams>,
+) -> impl IntoResponse {
+    let metric = file.strip_suffix(".svg").unwrap_or(&file);
+    if !MetricsHistory::is_known(metric) {
+        return (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("unknown metric '{}'", metric), "metrics": METRICS})),
+        )
+            .into_response();
+    }
+
+    let config = state.config.snapshot().metrics_history;
+    let points = params.points.unwrap_or(config.chart_points).max(1);
+    match MetricsHistory::new(&config.dir).read_tail(metric, points) {
+        Ok(samples) => (
+            [(axum::http::header::CONTENT_TYPE, "image/svg+xml")],
+            render_svg(metric, &samples, CHART_WIDTH, CHART_HEIGHT),
+        )
+            .into_response(),
+        Err(e) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("failed to read {}: {}", metric, e)})),
+        )
+            .into_response(),
+    }
+}
+
+/// Journaled decision chain of the trade that placed `order_id`.
+async fn get_trade_journal(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Path(order_id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let dir = state.config.snapshot().trade_journal.dir;
+    match read_trade(std::path::Path::new(&dir), &order_id) {
+        Some(entry) => Json(json!(entry)).into_response(),
+        None => (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("no journaled trade with order '{}'", order_id)})),
+        )
+            .into_response(),
     }
 }
 
@@ -336,7 +1528,7 @@ async fn cancel_all_orders(State(state): State<Arc<AppState>>) -> impl IntoRespo
             ex
         } else {
             info!("Exchange not initialized in state, building temporary instance for cancellation...");
-            let (ex, _) = build_exchange(&state.config);
+            let (ex, _) = build_exchange(&state.config.snapshot());
             ex
         }
     };
@@ -352,3 +1544,295 @@ async fn cancel_all_orders(State(state): State<Arc<AppState>>) -> impl IntoRespo
             .into_response(),
     }
 }
+
+/// Close every position (optionally `?symbol=` / `?side=long|short`): exit
+/// legs are cancelled first, then the rest is sold at market and the venue
+/// re-read; positions still held are returned as `residual`.
+async fn close_all_positions(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<CloseFilter>,
+) -> impl IntoResponse {
+    let Some(filter) = filter.validated() else {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({"status": "error", "message": "side must be long or short"})),
+        )
+            .into_response();
+    };
+    let session = state.session.lock().unwrap().clone();
+    let exchange = state.exchange.lock().unwrap().clone();
+    let (Some(session), Some(exchange)) = (session, exchange) else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+    let closer = BulkCloser::new(
+        exchange,
+        session.tracker,
+        session.market_store,
+        session.event_bus,
+        &state.config.snapshot(),
+    );
+    let report = closer.close_all(&filter).await;
+    let status = if report.residual.is_empty()
+        && report
+            .outcomes
+            .iter()
+            .all(|o| o.status != "failed" && o.status != "skipped")
+    {
+        "success"
+    } else {
+        "partial"
+    };
+    Json(json!({"status": status, "report": report})).into_response()
+}
+
+// Live control/UI channel: streams bus events plus periodic position/PnL
+// snapshots, and accepts ControlCommand JSON (e.g. {"cmd":"pause_symbol","symbol":"BTC/USD"}).
+/// Browser dashboard over `/ws` (and `/report` for the equity curve).
+async fn dashboard() -> impl IntoResponse {
+    axum::response::Html(include_str!("../static/dashboard.html"))
+}
+
+/// The upgrade is a GET, so any role may stream; commands sent over the socket
+/// are checked per frame against the caller (None while `api_auth` is off).
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    principal: Option<Extension<Principal>>,
+) -> impl IntoResponse {
+    let principal = principal.map(|Extension(p)| p);
+    ws.on_upgrade(move |socket| ws_session(socket, state, principal))
+}
+
+async fn ws_session(socket: WebSocket, state: Arc<AppState>, principal: Option<Principal>) {
+    let (mut sender, mut receiver) = socket.split();
+
+    let session = state.session.lock().unwrap().clone();
+    let Some(session) = session else {
+        let msg = json!({"type": "error", "message": "Trading not started. Start trading first with /start"});
+        let _ = sender.send(Message::Text(msg.to_string().into())).await;
+        let _ = sender.send(Message::Close(None)).await;
+        return;
+    };
+
+    info!("🖥️ [WS-API] Dashboard client connected");
+    let mut rx = session.event_bus.subscribe("ws_client");
+    let shutdown = session.event_bus.shutdown().clone();
+    let mut ticker = tokio::time::interval(WS_POSITIONS_INTERVAL);
+
+    loop {
+        let outgoing = tokio::select! {
+            _ = shutdown.stopped() => break,
+            event = rx.recv() => match event {
+                Ok(event) => match event.to_json() {
+                    Some(value) => value,
+                    None => continue,
+                },
+                Err(RecvError::Lagged(skipped)) => json!({"type": "lagged", "skipped": skipped}),
+                Err(RecvError::Closed) => break,
+            },
+            _ = ticker.tick() => positions_snapshot(&session, &state.llm),
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Text(text))) => handle_ws_command(&text, principal.as_ref(), &session),
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+        if sender
+            .send(Message::Text(outgoing.to_string().into()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    let _ = sender.send(Message::Close(None)).await;
+    info!("🖥️ [WS-API] Dashboard client disconnected");
+}
+
+/// Open positions and working orders of the running session (the `/ws`
+/// `positions` snapshot).
+async fn get_positions(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let session = state.session.lock().unwrap().clone();
+    match session {
+        Some(session) => Json(positions_snapshot(&session, &state.llm)).into_response(),
+        None => (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response(),
+    }
+}
+
+/// Operator command over HTTP, same as on `/ws`, e.g.
+/// `{"cmd": "close_position", "symbol": "BTC/USD"}`.
+async fn post_control(
+    State(state): State<Arc<AppState>>,
+    Json(command): Json<ControlCommand>,
+) -> impl IntoResponse {
+    let session = state.session.lock().unwrap().clone();
+    let Some(session) = session else {
+        return (
+            axum::http::StatusCode::CONFLICT,
+            Json(json!({"status": "not_running"})),
+        )
+            .into_response();
+    };
+    let reply = send_command(command, &session);
+    if reply["type"] == "error" {
+        (axum::http::StatusCode::BAD_REQUEST, Json(reply)).into_response()
+    } else {
+        Json(reply).into_response()
+    }
+}
+
+/// Parse a `/ws` control message and send it; returns the reply. Commands
+/// change state, so they need the admin role like `POST /control`.
+fn handle_ws_command(text: &str, principal: Option<&Principal>, session: &Session) -> Value {
+    if let Some(p) = principal.filter(|p| p.role < Role::Admin) {
+        let e = AuthError::Forbidden {
+            name: p.name.clone(),
+            role: p.role.as_str(),
+        };
+        warn!("🔒 [WS-API] Command denied: {}", e);
+        return json!({"type": "error", "message": e.to_string()});
+    }
+    match serde_json::from_str(text) {
+        Ok(command) => send_command(command, session),
+        Err(e) => json!({"type": "error", "message": format!("Invalid command: {}", e)}),
+    }
+}
+
+/// Validate a control command and publish it on the bus; returns the reply.
+pub fn send_command(command: ControlCommand, session: &Session) -> Value {
+    let symbol = command.symbol().to_string();
+    let invalid = match &command {
+        ControlCommand::ClosePosition { .. } | ControlCommand::AdjustExits { .. }
+            if !session.tracker.has_position(&symbol) =>
+        {
+            Some(format!("No tracked position for {}", symbol))
+        }
+        ControlCommand::AdjustExits {
+            take_profit,
+            stop_loss,
+            ..
+        } => match (take_profit, stop_loss) {
+            (None, None) => Some("adjust_exits needs take_profit and/or stop_loss".to_string()),
+            (tp, sl)
+                if tp.is_some_and(|v| v <= Decimal::ZERO)
+                    || sl.is_some_and(|v| v <= Decimal::ZERO) =>
+            {
+                Some("take_profit/stop_loss must be positive".to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    if let Some(message) = invalid {
+        return json!({"type": "error", "message": message});
+    }
+
+    info!("🎛️ [CONTROL] Operator command: {:?}", command);
+    let ack = json!({"type": "ack", "command": &command});
+    match session.event_bus.publish(Event::Control(command)) {
+        Ok(_) => ack,
+        Err(_) => json!({"type": "error", "message": "No services are listening"}),
+    }
+}
+
+/// Latest bid of `symbol`, the price positions are marked to.
+pub fn mark_price(session: &Session, symbol: &str) -> Option<Decimal> {
+    session
+        .market_store
+        .get_latest_quote(symbol)
+        .map(|q| money::dec(q.bid_price))
+        .filter(|b| *b > Decimal::ZERO)
+}
+
+/// Tracked positions marked to the latest bid, with unrealized PnL, plus
+/// working entry orders and the LLM queue depth.
+fn positions_snapshot(session: &Session, llm: &LLMQueue) -> Value {
+    let mut total_pnl = Decimal::ZERO;
+    let positions: Vec<Value> = session
+        .tracker
+        .get_all_positions()
+        .into_iter()
+        .map(|p| {
+            let price = mark_price(session, &p.symbol);
+            let pnl = price.map(|px| (px - p.entry_price) * p.filled_qty);
+            total_pnl += pnl.unwrap_or_default();
+            json!({
+                "symbol": p.symbol,
+                "qty": p.qty,
+                "filled_qty": p.filled_qty,
+                "remaining_qty": p.remaining_qty,
+                "entry_price": p.entry_price,
+                "current_price": price,
+                "unrealized_pnl": pnl,
+                "unrealized_pnl_pct": price.and_then(|px| {
+                    (px - p.entry_price)
+                        .checked_div(p.entry_price)
+                        .map(|r| money::float(r) * 100.0)
+                }),
+                "take_profit": p.take_profit,
+                "stop_loss": p.stop_loss,
+                "is_closing": p.is_closing,
+            })
+        })
+        .collect();
+
+    let pending_orders: Vec<Value> = session
+        .tracker
+        .get_all_pending_orders()
+        .into_iter()
+        .map(|o| {
+            json!({
+                "symbol": o.symbol,
+                "order_id": o.order_id,
+                "side": o.side,
+                "qty": o.qty,
+                "filled_qty": o.filled_qty,
+                "limit_price": o.limit_price,
+                "created_at": o.created_at,
+            })
+        })
+        .collect();
+
+    // Hedges are shown apart: they are not part of the strategy's book
+    let hedges: Vec<Value> = session
+        .tracker
+        .get_all_hedges()
+        .into_iter()
+        .map(|h| {
+            let price = mark_price(session, &h.symbol);
+            json!({
+                "symbol": h.symbol,
+                "side": h.side,
+                "qty": h.qty,
+                "entry_price": h.entry_price,
+                "entry_time": h.entry_time.to_rfc3339(),
+                "current_price": price,
+                "unrealized_pnl_pct": price.map(|px| h.pnl_pct(px)),
+            })
+        })
+        .collect();
+
+    json!({
+        "type": "positions",
+        "positions": positions,
+        "pending_orders": pending_orders,
+        "hedges": hedges,
+        "unrealized_pnl": total_pnl,
+        "llm_queue": {
+            "high": llm.depth(Priority::High),
+            "normal": llm.depth(Priority::Normal),
+            "capacity": llm.capacity(),
+            "fallback": llm.is_degraded(),
+        },
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    })
+}
@@ -0,0 +1,150 @@
+//! Tests for the HTTP/WebSocket API (auth of `/ws` commands).
+
+#[cfg(test)]
+mod api_tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::Value;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use crate::api::{router, AppState, Session};
+    use crate::bus::EventBus;
+    use crate::config::AppConfig;
+    use crate::data::store::MarketStore;
+    use crate::events::Event;
+    use crate::llm::{LLMClient, LLMQueue};
+    use crate::services::circuit_breaker::CircuitBreaker;
+    use crate::services::config_reload::LiveConfig;
+    use crate::services::position_monitor::PositionTracker;
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+api_auth:
+  enabled: true
+  keys:
+    - name: "dashboard"
+      key: "read-key-123"
+      role: "read"
+    - name: "ops"
+      key: "admin-key-456"
+      role: "admin"
+"#,
+        )
+        .unwrap()
+    }
+
+    /// Serve the API with a running session on a free local port.
+    async fn serve(bus: &EventBus) -> std::net::SocketAddr {
+        let config = test_config();
+        let session = Session {
+            event_bus: bus.clone(),
+            tracker: PositionTracker::new(),
+            market_store: MarketStore::new(10),
+            circuit_breaker: CircuitBreaker::new(bus.clone(), &config),
+            conditional_orders: None,
+            arbitrage: None,
+            equity: None,
+        };
+        let state = Arc::new(AppState {
+            trading_handle: Mutex::new(None),
+            websocket_handle: Mutex::new(None),
+            exchange: Mutex::new(None),
+            shutdown: Mutex::new(None),
+            health: Mutex::new(None),
+            session: Mutex::new(Some(session)),
+            llm: LLMQueue::new(
+                LLMClient::new("k".to_string(), None, "test-model".to_string()),
+                1,
+                10,
+                config.llm_retry.clone(),
+            ),
+            config: LiveConfig::new(config),
+            storage: None,
+            #[cfg(feature = "database")]
+            database: None,
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+        addr
+    }
+
+    /// Send `command` over `/ws` as `token`; the reply to it.
+    async fn ws_command(addr: std::net::SocketAddr, token: &str, command: &str) -> Value {
+        let url = format!("ws://{}/ws?token={}", addr, token);
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        socket
+            .send(Message::Text(command.to_string()))
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(Message::Text(text))) = socket.next().await {
+                let reply: Value = serde_json::from_str(&text).unwrap();
+                if matches!(reply["type"].as_str(), Some("ack" | "error")) {
+                    return reply;
+                }
+            }
+            panic!("socket closed without a reply");
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ws_command_needs_admin_role() {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe("test");
+        let addr = serve(&bus).await;
+        let pause = r#"{"cmd":"pause_symbol","symbol":"BTC/USD"}"#;
+
+        // A read key may open the stream but not send commands
+        let reply = ws_command(addr, "read-key-123", pause).await;
+        assert_eq!(reply["type"], "error");
+        assert!(reply["message"]
+            .as_str()
+            .unwrap()
+            .contains("dashboard (read)"));
+        assert!(rx.try_recv().is_err());
+
+        let reply = ws_command(addr, "admin-key-456", pause).await;
+        assert_eq!(reply["type"], "ack");
+        assert!(matches!(rx.try_recv(), Ok(Event::Control(_))));
+    }
+}
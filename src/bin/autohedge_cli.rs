@@ -0,0 +1,255 @@
+//! Operate a running bot through its HTTP API.
+//!
+//! Usage: autohedge-cli [--url http://localhost:3000] [--token KEY] <command>
+//!
+//! e.g. `autohedge-cli status`, `autohedge-cli close BTC/USD`,
+//! `autohedge-cli config set hft.min_edge_bps 12`, `autohedge-cli report --today`.
+//! `--url` and `--token` default to `AUTOHEDGE_URL` and `AUTOHEDGE_TOKEN`.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use reqwest::{Method, RequestBuilder};
+use serde_json::{json, Value};
+
+#[derive(Parser)]
+#[command(name = "autohedge-cli", about = "Operate a running AutoHedge bot")]
+struct Cli {
+    /// Base URL of the bot's API
+    #[arg(long, env = "AUTOHEDGE_URL", default_value = "http://localhost:3000")]
+    url: String,
+    /// API key or JWT (with `api_auth` enabled)
+    #[arg(long, env = "AUTOHEDGE_TOKEN")]
+    token: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start a trading session
+    Start,
+    /// Drain and stop the trading session
+    Stop,
+    /// Health, circuit breaker, open positions and working orders
+    Status,
+    /// Exit a tracked position at the next tick
+    Close { symbol: String },
+    /// Stop new entries for a symbol
+    Pause { symbol: String },
+    /// Allow new entries for a paused symbol again
+    Unpause { symbol: String },
+    /// Reset a tripped circuit breaker
+    Resume,
+    /// Cancel every open order on the exchange
+    CancelAll,
+    /// Show or change the hot-reloadable tunables
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Session stats, or a daily report
+    Report {
+        /// Today's daily report (UTC)
+        #[arg(long, conflicts_with = "date")]
+        today: bool,
+        /// Daily report of YYYY-MM-DD
+        #[arg(long)]
+        date: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    Get,
+    /// e.g. `config set hft.min_edge_bps 12`; VALUE is parsed as JSON, else
+    /// taken as a string
+    Set {
+        key: String,
+        value: String,
+    },
+}
+
+struct Api {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl Api {
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let req = self.client.request(
+            method,
+            format!("{}{}", self.url.trim_end_matches('/'), path),
+        );
+        match &self.token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+
+    /// Send `req`; the response body on success, else the error to print.
+    async fn send(&self, req: RequestBuilder) -> Result<String, String> {
+        let resp = req.send().await.map_err(|e| e.to_string())?;
+        let status = resp.status();
+        let body = resp.text().await.map_err(|e| e.to_string())?;
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(format!("{}: {}", status, body))
+        }
+    }
+
+    async fn json(&self, req: RequestBuilder) -> Result<Value, String> {
+        let body = self.send(req).await?;
+        serde_json::from_str(&body).map_err(|e| format!("Invalid response: {}", e))
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// `value` as plain text: strings unquoted, null as "-".
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+async fn status(api: &Api) -> Result<String, String> {
+    let health = api.json(api.request(Method::GET, "/health")).await?;
+    let mut out = match health.get("trading").filter(|t| !t.is_null()) {
+        Some(trading) => format!(
+            "Trading: running (health {} - {})",
+            text(&trading["score"]),
+            text(&trading["level"])
+        ),
+        None => return Ok("Trading: stopped".to_string()),
+    };
+    if health["circuit_breaker"]["tripped"] == true {
+        out.push_str(&format!(
+            "\nCircuit breaker: TRIPPED ({})",
+            text(&health["circuit_breaker"]["reason"])
+        ));
+    }
+
+    let snapshot = api.json(api.request(Method::GET, "/positions")).await?;
+    let positions = snapshot["positions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    out.push_str(&format!(
+        "\nUnrealized PnL: {}\n\nPositions ({}):",
+        text(&snapshot["unrealized_pnl"]),
+        positions.len()
+    ));
+    for p in &positions {
+        out.push_str(&format!(
+            "\n  {:<12} qty {:<12} entry {:<12} last {:<12} pnl {} ({}%){}",
+            text(&p["symbol"]),
+            text(&p["filled_qty"]),
+            text(&p["entry_price"]),
+            text(&p["current_price"]),
+            text(&p["unrealized_pnl"]),
+            p["unrealized_pnl_pct"]
+                .as_f64()
+                .map_or("-".to_string(), |v| format!("{:+.2}", v)),
+            if p["is_closing"] == true {
+                " [closing]"
+            } else {
+                ""
+            }
+        ));
+    }
+    let orders = snapshot["pending_orders"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    out.push_str(&format!("\n\nPending orders ({}):", orders.len()));
+    for o in &orders {
+        out.push_str(&format!(
+            "\n  {:<12} {:<4} qty {:<12} filled {:<12} limit {}",
+            text(&o["symbol"]),
+            text(&o["side"]),
+            text(&o["qty"]),
+            text(&o["filled_qty"]),
+            text(&o["limit_price"])
+        ));
+    }
+    Ok(out)
+}
+
+async fn control(api: &Api, cmd: &str, symbol: String) -> Result<String, String> {
+    let command = json!({ "cmd": cmd, "symbol": symbol });
+    let reply = api
+        .json(api.request(Method::POST, "/control").json(&command))
+        .await?;
+    Ok(pretty(&reply))
+}
+
+async fn run(api: &Api, command: Command) -> Result<String, String> {
+    match command {
+        Command::Start => api.send(api.request(Method::POST, "/start")).await,
+        Command::Stop => api.send(api.request(Method::POST, "/stop")).await,
+        Command::Status => status(api).await,
+        Command::Close { symbol } => control(api, "close_position", symbol).await,
+        Command::Pause { symbol } => control(api, "pause_symbol", symbol).await,
+        Command::Unpause { symbol } => control(api, "resume_symbol", symbol).await,
+        Command::Resume => api.send(api.request(Method::POST, "/resume")).await,
+        Command::CancelAll => api.send(api.request(Method::POST, "/cancel_all")).await,
+        Command::Config {
+            action: ConfigAction::Get,
+        } => Ok(pretty(
+            &api.json(api.request(Method::GET, "/config")).await?,
+        )),
+        Command::Config {
+            action: ConfigAction::Set { key, value },
+        } => {
+            let value = serde_json::from_str(&value).unwrap_or(Value::String(value));
+            let patch = json!({ key: value });
+            let reply = api
+                .json(api.request(Method::PUT, "/config").json(&patch))
+                .await?;
+            Ok(pretty(&reply))
+        }
+        Command::Report { today, date } => {
+            let date = if today {
+                Some(chrono::Utc::now().date_naive().to_string())
+            } else {
+                date
+            };
+            match date {
+                Some(date) => {
+                    let req = api
+                        .request(Method::GET, "/report/daily")
+                        .query(&[("date", date)]);
+                    api.send(req).await
+                }
+                None => Ok(pretty(&api.json(api.request(Method::GET, "/stats")).await?)),
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let api = Api {
+        client: reqwest::Client::new(),
+        url: cli.url,
+        token: cli.token,
+    };
+    match run(&api, cli.command).await {
+        Ok(out) => {
+            println!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Convert a trade log between formats.
+//!
+//! Usage: convert_trade_log <input> <output> [--from jsonl|csv|msgpack] [--to jsonl|csv|msgpack]
+//!
+//! Formats default to the file extensions, e.g.
+//! `convert_trade_log data/trades.jsonl data/trades.csv`.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use rust_autohedge::services::trade_log::{convert_log, TradeLogFormat};
+
+const USAGE: &str =
+    "usage: convert_trade_log <input> <output> [--from jsonl|csv|msgpack] [--to jsonl|csv|msgpack]";
+
+fn main() -> ExitCode {
+    let mut paths = Vec::new();
+    let mut from = None;
+    let mut to = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" | "--to" => {
+                let Some(format) = args.next().as_deref().and_then(TradeLogFormat::parse) else {
+                    eprintln!("{} needs one of: jsonl, csv, msgpack", arg);
+                    return ExitCode::FAILURE;
+                };
+                if arg == "--from" {
+                    from = Some(format);
+                } else {
+                    to = Some(format);
+                }
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    let [input, output] = paths.as_slice() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let (Some(from), Some(to)) = (
+        from.or_else(|| TradeLogFormat::from_path(input)),
+        to.or_else(|| TradeLogFormat::from_path(output)),
+    ) else {
+        eprintln!("Cannot infer formats from the file extensions; pass --from/--to");
+        return ExitCode::FAILURE;
+    };
+
+    match convert_log(input, from, output, to) {
+        Ok(count) => {
+            println!(
+                "Converted {} entries: {} ({}) -> {} ({})",
+                count,
+                input.display(),
+                from.extension(),
+                output.display(),
+                to.extension()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Conversion failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
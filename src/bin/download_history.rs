@@ -0,0 +1,78 @@
+//! Download historical bars into the local cache.
+//!
+//! Usage: download_history [--days N] [--timeframe 1Min] [SYMBOL ...]
+//!
+//! Reads `config.yaml` for the exchange, its credentials and
+//! `history_download`; symbols default to `symbols`. Re-running it resumes
+//! after the last cached bar of each symbol.
+
+use std::process::ExitCode;
+
+use rust_autohedge::config::AppConfig;
+use rust_autohedge::data::downloader::HistoryDownloader;
+use rust_autohedge::exchange::factory::build_exchange;
+use rust_autohedge::logging;
+
+const USAGE: &str =
+    "usage: download_history [--days N] [--timeframe 1Min|5Min|1Hour|1Day] [SYMBOL ...]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut config = AppConfig::load();
+    logging::init(&config.logging);
+
+    let mut symbols = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => match args.next().and_then(|d| d.parse().ok()) {
+                Some(days) => config.history_download.days = days,
+                None => {
+                    eprintln!("--days needs a number");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--timeframe" => match args.next() {
+                Some(timeframe) => config.history_download.timeframe = timeframe,
+                None => {
+                    eprintln!("{}", USAGE);
+                    return ExitCode::FAILURE;
+                }
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => symbols.push(arg),
+        }
+    }
+    if symbols.is_empty() {
+        symbols = config.symbols.clone();
+    }
+
+    let (exchange, _) = build_exchange(&config);
+    let downloader = HistoryDownloader::new(exchange, &config.history_download);
+    let now = chrono::Utc::now();
+    let start = now - chrono::Duration::days(config.history_download.days as i64);
+
+    let mut failed = false;
+    for symbol in &symbols {
+        match downloader.download(symbol, start, now).await {
+            Ok(added) => println!(
+                "{}: {} new bar(s) -> {}",
+                symbol,
+                added,
+                downloader.path(symbol).display()
+            ),
+            Err(e) => {
+                eprintln!("{}: download failed: {}", symbol, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
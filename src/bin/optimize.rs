@@ -0,0 +1,117 @@
+//! Walk-forward grid search of the HFT tunables.
+//!
+//! Usage: optimize [--folds N] FILE ...
+//!
+//! FILEs are bar caches from `download_history` (`.csv`) or event logs
+//! (`.jsonl` / `.msgpack`); their quotes are replayed together, oldest first.
+//! The grid and fold count come from `optimize` in `config.yaml`, everything
+//! else from the live config. Prints the per-fold winners and the stable set
+//! and writes the full report to `optimize.report_dir`.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use rust_autohedge::config::AppConfig;
+use rust_autohedge::logging;
+use rust_autohedge::services::optimizer::{
+    load_quotes, merge_quotes, walk_forward, write_report, HftParams,
+};
+
+const USAGE: &str = "usage: optimize [--folds N] FILE.csv|FILE.jsonl|FILE.msgpack ...";
+
+fn params(p: &HftParams) -> String {
+    format!(
+        "min_edge {:>6.1}  tp {:>6.1}  sl {:>6.1}  max_spread {:>6.1}",
+        p.min_edge_bps, p.take_profit_bps, p.stop_loss_bps, p.max_spread_bps
+    )
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut config = AppConfig::load();
+    logging::init(&config.logging);
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--folds" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(folds) => config.optimize.folds = folds,
+                None => {
+                    eprintln!("--folds needs a number");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+    if files.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let mut quotes = Vec::new();
+    for file in &files {
+        match load_quotes(file, config.optimize.bar_spread_bps) {
+            Ok(loaded) => quotes.extend(loaded),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let quotes = merge_quotes(quotes);
+
+    let report = match walk_forward(&config, &quotes).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Optimization failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "{} quote(s), {} candidate(s), {} fold(s)",
+        report.quotes,
+        report.candidates,
+        report.folds.len()
+    );
+    println!("baseline   {}", params(&report.baseline));
+    for fold in &report.folds {
+        println!(
+            "fold {:<4}  {}  | train {:+.2} ({} trades)  test {:+.2} ({} trades, {:.0}% won)",
+            fold.fold,
+            params(&fold.params),
+            fold.train.pnl,
+            fold.train.trades,
+            fold.test.pnl,
+            fold.test.trades,
+            fold.test.win_rate_pct
+        );
+    }
+    match &report.stable {
+        Some(stable) => println!(
+            "stable     {}  | worst test {:+.2}, mean test {:+.2} ({} trades)",
+            params(&stable.params),
+            stable.worst_test_pnl,
+            stable.mean_test_pnl,
+            stable.test_trades
+        ),
+        None => println!("stable     none (no candidate traded in every test window)"),
+    }
+
+    match write_report(&report, Path::new(&config.optimize.report_dir)) {
+        Ok(path) => {
+            println!("report -> {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Writing the report failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
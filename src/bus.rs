@@ -1,22 +1,425 @@
+//! In-process event bus.
+//!
+//! Every subscriber gets its own queue, named after the service reading it
+//! (`subscribe("risk")`). Market data and other state updates go to a bounded
+//! lane of `capacity` events (`with_service_capacity` per service); when a
+//! slow subscriber fills it the oldest events are dropped, the subscriber's
+//! next `recv` returns `RecvError::Lagged(n)` and reading resumes, as with a
+//! broadcast channel. Orders and executions go to an unbounded priority lane
+//! that is never dropped and is always read first, so a quote flood can
+//! neither lose nor delay them (`subscribe_in_order` keeps publish order and
+//! only skips droppable events on overflow). Per-subscriber depth, drops and
+//! lag incidents are reported by `stats`.
+
 use crate::events::Event;
-use tokio::sync::broadcast;
+use crate::services::diagnostics::SymbolDiagnostics;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::{RecvError, SendError, TryRecvError};
+use tokio::sync::{watch, Notify};
+use tracing::warn;
+
+/// What a full subscriber queue does with a new event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Bounded: the oldest queued event is dropped (market data, state updates)
+    DropOldest,
+    /// Unbounded priority lane, read before anything else (orders, executions)
+    NeverDrop,
+}
+
+impl OverflowPolicy {
+    pub fn for_event(event: &Event) -> Self {
+        match event {
+            Event::Order(_) | Event::Execution(_) => OverflowPolicy::NeverDrop,
+            _ => OverflowPolicy::DropOldest,
+        }
+    }
+}
+
+/// Queue health of one subscriber.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SubscriberStats {
+    pub service: String,
+    pub capacity: usize,
+    /// Bounded-lane events waiting to be read
+    pub queued: usize,
+    /// Priority-lane (order/execution) events waiting to be read
+    pub priority_queued: usize,
+    /// Deepest the bounded lane has been
+    pub high_water: usize,
+    /// Events dropped because the subscriber fell behind
+    pub dropped: u64,
+    /// Times it fell behind (one per run of drops)
+    pub lag_incidents: u64,
+}
+
+#[derive(Default)]
+struct QueueState {
+    events: VecDeque<Event>,
+    priority: VecDeque<Event>,
+    /// Dropped since the subscriber last saw `Lagged`
+    lagged: u64,
+    high_water: usize,
+    dropped: u64,
+    lag_incidents: u64,
+}
+
+struct SubscriberQueue {
+    service: String,
+    capacity: usize,
+    /// Deliver in publish order: orders and executions share the bounded
+    /// lane (still never dropped) instead of jumping the queue
+    ordered: bool,
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+impl SubscriberQueue {
+    fn push(&self, event: Event) {
+        {
+            let mut state = self.state.lock().unwrap();
+            let policy = OverflowPolicy::for_event(&event);
+            if policy == OverflowPolicy::NeverDrop && !self.ordered {
+                state.priority.push_back(event);
+            } else {
+                if state.events.len() >= self.capacity {
+                    let oldest = state
+                        .events
+                        .iter()
+                        .position(|e| OverflowPolicy::for_event(e) == OverflowPolicy::DropOldest);
+                    if let Some(i) = oldest {
+                        state.events.remove(i);
+                        self.record_drop(&mut state);
+                    }
+                }
+                state.events.push_back(event);
+                state.high_water = state.high_water.max(state.events.len());
+            }
+        }
+        self.notify.notify_one();
+    }
+
+    fn record_drop(&self, state: &mut QueueState) {
+        if state.lagged == 0 {
+            state.lag_incidents += 1;
+            warn!(
+                "⚠️ [BUS] {} fell {} events behind - dropping its oldest events",
+                self.service, self.capacity
+            );
+        }
+        state.lagged += 1;
+        state.dropped += 1;
+    }
+
+    /// Next event: priority lane first, then a pending lag notice, then the
+    /// bounded lane.
+    fn take(&self) -> Option<Result<Event, u64>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(event) = state.priority.pop_front() {
+            return Some(Ok(event));
+        }
+        if state.lagged > 0 {
+            return Some(Err(std::mem::take(&mut state.lagged)));
+        }
+        state.events.pop_front().map(Ok)
+    }
+
+    fn stats(&self) -> SubscriberStats {
+        let state = self.state.lock().unwrap();
+        SubscriberStats {
+            service: self.service.clone(),
+            capacity: self.capacity,
+            queued: state.events.len(),
+            priority_queued: state.priority.len(),
+            high_water: state.high_water,
+            dropped: state.dropped,
+            lag_incidents: state.lag_incidents,
+        }
+    }
+}
+
+#[derive(Default)]
+struct BusShared {
+    subscribers: Mutex<Vec<Weak<SubscriberQueue>>>,
+    closed: AtomicBool,
+}
+
+impl BusShared {
+    fn live(&self) -> Vec<Arc<SubscriberQueue>> {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| s.strong_count() > 0);
+        subscribers.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
+/// Closes the bus when the last `EventBus` clone is dropped.
+struct SenderGuard(Arc<BusShared>);
+
+impl Drop for SenderGuard {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::SeqCst);
+        for queue in self.0.live() {
+            queue.notify.notify_one();
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct EventBus {
-    tx: broadcast::Sender<Event>,
+    shared: Arc<BusShared>,
+    _sender: Arc<SenderGuard>,
+    capacity: usize,
+    service_capacity: Arc<HashMap<String, usize>>,
+    shutdown: Shutdown,
+    diagnostics: SymbolDiagnostics,
 }
 
 impl EventBus {
+    /// Bus whose subscribers queue up to `capacity` droppable events each.
     pub fn new(capacity: usize) -> Self {
-        let (tx, _rx) = broadcast::channel(capacity);
-        Self { tx }
+        let shared = Arc::new(BusShared::default());
+        Self {
+            _sender: Arc::new(SenderGuard(shared.clone())),
+            shared,
+            capacity: capacity.max(1),
+            service_capacity: Arc::new(HashMap::new()),
+            shutdown: Shutdown::new(),
+            diagnostics: SymbolDiagnostics::new(),
+        }
+    }
+
+    /// Queue sizes for individual services, overriding `capacity`.
+    pub fn with_service_capacity(mut self, service_capacity: HashMap<String, usize>) -> Self {
+        self.service_capacity = Arc::new(service_capacity);
+        self
+    }
+
+    /// Receive every event published from now on, as `service`. Orders and
+    /// executions are delivered ahead of anything already queued.
+    pub fn subscribe(&self, service: &str) -> BusReceiver {
+        self.subscribe_with(service, false)
+    }
+
+    /// Like `subscribe`, but every event arrives in publish order (for
+    /// recorders, which must keep the session's chronology).
+    pub fn subscribe_in_order(&self, service: &str) -> BusReceiver {
+        self.subscribe_with(service, true)
+    }
+
+    fn subscribe_with(&self, service: &str, ordered: bool) -> BusReceiver {
+        let capacity = self
+            .service_capacity
+            .get(service)
+            .copied()
+            .unwrap_or(self.capacity)
+            .max(1);
+        let queue = Arc::new(SubscriberQueue {
+            service: service.to_string(),
+            capacity,
+            ordered,
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+        });
+        self.shared
+            .subscribers
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(&queue));
+        BusReceiver {
+            queue,
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Queue `event` for every subscriber. Returns how many there are; an
+    /// error (with the event) when there are none.
+    pub fn publish(&self, event: Event) -> Result<usize, SendError<Event>> {
+        let subscribers = self.shared.live();
+        let Some((last, rest)) = subscribers.split_last() else {
+            return Err(SendError(event));
+        };
+        for queue in rest {
+            queue.push(event.clone());
+        }
+        last.push(event);
+        Ok(subscribers.len())
+    }
+
+    /// Queue health of every live subscriber.
+    pub fn stats(&self) -> Vec<SubscriberStats> {
+        self.shared.live().iter().map(|q| q.stats()).collect()
+    }
+
+    /// Shutdown signal shared by every service on this bus.
+    pub fn shutdown(&self) -> &Shutdown {
+        &self.shutdown
+    }
+
+    /// Per-symbol verbose diagnostics shared by every service on this bus.
+    pub fn diagnostics(&self) -> &SymbolDiagnostics {
+        &self.diagnostics
+    }
+}
+
+/// One service's subscription to the bus.
+pub struct BusReceiver {
+    queue: Arc<SubscriberQueue>,
+    shared: Arc<BusShared>,
+}
+
+impl BusReceiver {
+    /// Next event. `Lagged(n)` once after `n` events were dropped (reading
+    /// then continues with the oldest kept); `Closed` when every `EventBus`
+    /// is gone and the queue is empty.
+    pub async fn recv(&mut self) -> Result<Event, RecvError> {
+        loop {
+            match self.queue.take() {
+                Some(Ok(event)) => return Ok(event),
+                Some(Err(n)) => return Err(RecvError::Lagged(n)),
+                None if self.shared.closed.load(Ordering::SeqCst) => return Err(RecvError::Closed),
+                None => self.queue.notify.notified().await,
+            }
+        }
+    }
+
+    /// Next event if one is queued.
+    pub fn try_recv(&mut self) -> Result<Event, TryRecvError> {
+        match self.queue.take() {
+            Some(Ok(event)) => Ok(event),
+            Some(Err(n)) => Err(TryRecvError::Lagged(n)),
+            None if self.shared.closed.load(Ordering::SeqCst) => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+/// Lifecycle phase broadcast to services over a watch channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownPhase {
+    Running,
+    /// Stop accepting new signals/orders; in-flight orders are allowed to finish.
+    Draining,
+    /// Drain is over: remaining loops flush and exit.
+    Stopped,
+}
+
+/// Graceful shutdown coordinator.
+///
+/// Order submissions hold an `order` guard and flushing services (reporter)
+/// hold a `worker` guard, so `/stop` can wait for both before tearing down.
+#[derive(Clone)]
+pub struct Shutdown {
+    tx: Arc<watch::Sender<ShutdownPhase>>,
+    orders: Arc<AtomicUsize>,
+    workers: Arc<AtomicUsize>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(ShutdownPhase::Running);
+        Self {
+            tx: Arc::new(tx),
+            orders: Arc::new(AtomicUsize::new(0)),
+            workers: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn phase(&self) -> ShutdownPhase {
+        *self.tx.borrow()
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.phase() != ShutdownPhase::Running
+    }
+
+    /// Enter `Draining` (no-op if already past it).
+    pub fn begin_drain(&self) {
+        self.tx.send_if_modified(|p| {
+            if *p == ShutdownPhase::Running {
+                *p = ShutdownPhase::Draining;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    pub fn finish(&self) {
+        self.tx.send_replace(ShutdownPhase::Stopped);
+    }
+
+    /// Resolves once shutdown has started.
+    pub async fn draining(&self) {
+        let mut rx = self.tx.subscribe();
+        let _ = rx.wait_for(|p| *p != ShutdownPhase::Running).await;
+    }
+
+    /// Resolves once the drain is over.
+    pub async fn stopped(&self) {
+        let mut rx = self.tx.subscribe();
+        let _ = rx.wait_for(|p| *p == ShutdownPhase::Stopped).await;
+    }
+
+    /// Mark an order submission in flight until the guard is dropped.
+    pub fn track_order(&self) -> InFlightGuard {
+        InFlightGuard::new(self.orders.clone())
     }
 
-    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
-        self.tx.subscribe()
+    /// Mark a service that must flush before shutdown completes.
+    pub fn track_worker(&self) -> InFlightGuard {
+        InFlightGuard::new(self.workers.clone())
     }
 
-    pub fn publish(&self, event: Event) -> Result<usize, broadcast::error::SendError<Event>> {
-        self.tx.send(event)
+    pub fn orders_in_flight(&self) -> usize {
+        self.orders.load(Ordering::SeqCst)
+    }
+
+    /// Wait for in-flight orders to finish. Returns false on timeout.
+    pub async fn wait_orders(&self, timeout: Duration) -> bool {
+        Self::wait_zero(&self.orders, timeout).await
+    }
+
+    /// Wait for flushing services to exit. Returns false on timeout.
+    pub async fn wait_workers(&self, timeout: Duration) -> bool {
+        Self::wait_zero(&self.workers, timeout).await
+    }
+
+    async fn wait_zero(counter: &AtomicUsize, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while counter.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        true
+    }
+}
+
+/// Decrements its counter on drop.
+pub struct InFlightGuard {
+    counter: Arc<AtomicUsize>,
+}
+
+impl InFlightGuard {
+    fn new(counter: Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self { counter }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::SeqCst);
     }
 }
@@ -2,20 +2,56 @@
 
 #[cfg(test)]
 mod bus_tests {
-    use crate::bus::EventBus;
+    use crate::bus::{EventBus, OverflowPolicy, ShutdownPhase};
     use crate::events::{AnalysisSignal, Event, ExecutionReport, MarketEvent, OrderRequest};
+    use crate::money::dec;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+    fn quote(i: usize) -> Event {
+        Event::Market(MarketEvent::Quote {
+            symbol: "BTC/USD".to_string(),
+            bid: i as f64,
+            ask: i as f64 + 1.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        })
+    }
+
+    fn fill(order_id: &str) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: order_id.to_string(),
+            status: "filled".to_string(),
+            side: "buy".to_string(),
+            price: Some(dec(100.0)),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn bid(event: Event) -> f64 {
+        match event {
+            Event::Market(MarketEvent::Quote { bid, .. }) => bid,
+            other => panic!("Expected Market Quote event, got {:?}", other),
+        }
+    }
 
     #[tokio::test]
     async fn test_eventbus_new() {
         let bus = EventBus::new(100);
         // Should be able to create a bus without panicking
-        let _rx = bus.subscribe();
+        let _rx = bus.subscribe("test");
     }
 
     #[tokio::test]
     async fn test_eventbus_publish_subscribe() {
         let bus = EventBus::new(100);
-        let mut rx = bus.subscribe();
+        let mut rx = bus.subscribe("test");
 
         let event = Event::Market(MarketEvent::Quote {
             symbol: "BTC/USD".to_string(),
@@ -47,8 +83,8 @@ mod bus_tests {
     #[tokio::test]
     async fn test_eventbus_multiple_subscribers() {
         let bus = EventBus::new(100);
-        let mut rx1 = bus.subscribe();
-        let mut rx2 = bus.subscribe();
+        let mut rx1 = bus.subscribe("test");
+        let mut rx2 = bus.subscribe("test");
 
         let event = Event::Signal(AnalysisSignal {
             symbol: "ETH/USD".to_string(),
@@ -56,6 +92,15 @@ mod bus_tests {
             confidence: 0.85,
             thesis: "Bullish momentum".to_string(),
             market_context: "tp=3500, sl=3200".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         });
 
         bus.publish(event).unwrap();
@@ -71,16 +116,22 @@ mod bus_tests {
     #[tokio::test]
     async fn test_eventbus_order_event() {
         let bus = EventBus::new(100);
-        let mut rx = bus.subscribe();
+        let mut rx = bus.subscribe("test");
 
         let order = OrderRequest {
             symbol: "SOL/USD".to_string(),
             action: "buy".to_string(),
-            qty: 10.0,
+            qty: dec(10.0),
             order_type: "limit".to_string(),
-            limit_price: Some(100.0),
-            stop_loss: Some(95.0),
-            take_profit: Some(110.0),
+            limit_price: Some(dec(100.0)),
+            stop_loss: Some(dec(95.0)),
+            take_profit: Some(dec(110.0)),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         bus.publish(Event::Order(order)).unwrap();
@@ -88,10 +139,10 @@ mod bus_tests {
         if let Ok(Event::Order(req)) = rx.recv().await {
             assert_eq!(req.symbol, "SOL/USD");
             assert_eq!(req.action, "buy");
-            assert_eq!(req.qty, 10.0);
-            assert_eq!(req.limit_price, Some(100.0));
-            assert_eq!(req.stop_loss, Some(95.0));
-            assert_eq!(req.take_profit, Some(110.0));
+            assert_eq!(req.qty, dec(10.0));
+            assert_eq!(req.limit_price, Some(dec(100.0)));
+            assert_eq!(req.stop_loss, Some(dec(95.0)));
+            assert_eq!(req.take_profit, Some(dec(110.0)));
         } else {
             panic!("Expected Order event");
         }
@@ -100,15 +151,20 @@ mod bus_tests {
     #[tokio::test]
     async fn test_eventbus_execution_report() {
         let bus = EventBus::new(100);
-        let mut rx = bus.subscribe();
+        let mut rx = bus.subscribe("test");
 
         let report = ExecutionReport {
             symbol: "DOGE/USD".to_string(),
             order_id: "order123".to_string(),
             status: "filled".to_string(),
             side: "buy".to_string(),
-            price: Some(0.08),
-            qty: Some(1000.0),
+            price: Some(dec(0.08)),
+            qty: Some(dec(1000.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
         };
 
         bus.publish(Event::Execution(report)).unwrap();
@@ -118,8 +174,8 @@ mod bus_tests {
             assert_eq!(exec.order_id, "order123");
             assert_eq!(exec.status, "filled");
             assert_eq!(exec.side, "buy");
-            assert_eq!(exec.price, Some(0.08));
-            assert_eq!(exec.qty, Some(1000.0));
+            assert_eq!(exec.price, Some(dec(0.08)));
+            assert_eq!(exec.qty, Some(dec(1000.0)));
         } else {
             panic!("Expected Execution event");
         }
@@ -128,7 +184,7 @@ mod bus_tests {
     #[tokio::test]
     async fn test_eventbus_trade_event() {
         let bus = EventBus::new(100);
-        let mut rx = bus.subscribe();
+        let mut rx = bus.subscribe("test");
 
         let event = Event::Market(MarketEvent::Trade {
             symbol: "XRP/USD".to_string(),
@@ -158,7 +214,7 @@ mod bus_tests {
     async fn test_eventbus_capacity() {
         // Test that bus respects capacity
         let bus = EventBus::new(5);
-        let _rx = bus.subscribe(); // Must have at least one subscriber
+        let _rx = bus.subscribe("test"); // Must have at least one subscriber
 
         // Publish multiple events
         for i in 0..10 {
@@ -172,4 +228,164 @@ mod bus_tests {
         }
         // Should not panic - channel handles overflow by lagging
     }
+
+    // ============= Backpressure Tests =============
+
+    #[test]
+    fn test_overflow_policy_per_event() {
+        assert_eq!(
+            OverflowPolicy::for_event(&quote(1)),
+            OverflowPolicy::DropOldest
+        );
+        assert_eq!(
+            OverflowPolicy::for_event(&fill("o1")),
+            OverflowPolicy::NeverDrop
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_drops_oldest_quotes_and_reports_lag() {
+        let bus = EventBus::new(3);
+        let mut rx = bus.subscribe("strategy");
+        for i in 0..5 {
+            bus.publish(quote(i)).unwrap();
+        }
+
+        let stats = bus.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].service, "strategy");
+        assert_eq!((stats[0].queued, stats[0].high_water), (3, 3));
+        assert_eq!((stats[0].dropped, stats[0].lag_incidents), (2, 1));
+
+        // Lag is reported once, then reading resumes with the oldest kept
+        assert!(matches!(rx.recv().await, Err(RecvError::Lagged(2))));
+        assert_eq!(bid(rx.recv().await.unwrap()), 2.0);
+
+        // A second run of drops is a new incident
+        for i in 5..8 {
+            bus.publish(quote(i)).unwrap();
+        }
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Lagged(2))));
+        assert_eq!(bus.stats()[0].lag_incidents, 2);
+        assert_eq!(bid(rx.try_recv().unwrap()), 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_executions_jump_a_quote_flood_and_are_never_dropped() {
+        let bus = EventBus::new(2);
+        let mut rx = bus.subscribe("execution");
+        for i in 0..10 {
+            bus.publish(quote(i)).unwrap();
+            if i % 3 == 0 {
+                bus.publish(fill(&format!("o{}", i))).unwrap();
+            }
+        }
+        assert_eq!(bus.stats()[0].priority_queued, 4);
+
+        for expected in ["o0", "o3", "o6", "o9"] {
+            match rx.try_recv() {
+                Ok(Event::Execution(report)) => assert_eq!(report.order_id, expected),
+                other => panic!("Expected Execution event, got {:?}", other),
+            }
+        }
+        // Then the lag, then the newest quotes
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Lagged(8))));
+        assert_eq!(bid(rx.try_recv().unwrap()), 8.0);
+    }
+
+    #[tokio::test]
+    async fn test_in_order_subscriber_keeps_publish_order() {
+        let bus = EventBus::new(3);
+        let mut rx = bus.subscribe_in_order("event_log");
+        bus.publish(quote(0)).unwrap();
+        bus.publish(fill("o1")).unwrap();
+        bus.publish(quote(1)).unwrap();
+        // Full: the oldest quote goes, never the fill
+        bus.publish(quote(2)).unwrap();
+
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Lagged(1))));
+        assert!(matches!(rx.try_recv(), Ok(Event::Execution(_))));
+        assert_eq!(bid(rx.try_recv().unwrap()), 1.0);
+        assert_eq!(bid(rx.try_recv().unwrap()), 2.0);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_service_capacity_override_and_closed_bus() {
+        let bus =
+            EventBus::new(100).with_service_capacity(HashMap::from([("reporter".to_string(), 1)]));
+        let mut reporter = bus.subscribe("reporter");
+        let strategy = bus.subscribe("strategy");
+        bus.publish(quote(0)).unwrap();
+        bus.publish(quote(1)).unwrap();
+
+        let stats = bus.stats();
+        assert_eq!((stats[0].capacity, stats[0].dropped), (1, 1));
+        assert_eq!((stats[1].capacity, stats[1].dropped), (100, 0));
+
+        // Dropped subscribers leave the stats; a dropped bus closes once drained
+        drop(strategy);
+        assert_eq!(bus.stats().len(), 1);
+        drop(bus);
+        assert!(matches!(reporter.recv().await, Err(RecvError::Lagged(1))));
+        assert_eq!(bid(reporter.recv().await.unwrap()), 1.0);
+        assert!(matches!(reporter.recv().await, Err(RecvError::Closed)));
+    }
+
+    // ============= Shutdown Tests =============
+
+    #[tokio::test]
+    async fn test_shutdown_phases() {
+        let bus = EventBus::new(10);
+        let shutdown = bus.shutdown().clone();
+        assert_eq!(shutdown.phase(), ShutdownPhase::Running);
+        assert!(!shutdown.is_draining());
+
+        shutdown.begin_drain();
+        assert_eq!(bus.shutdown().phase(), ShutdownPhase::Draining);
+        // Resolves immediately once the phase has been reached
+        tokio::time::timeout(Duration::from_secs(1), shutdown.draining())
+            .await
+            .expect("draining should resolve");
+
+        shutdown.finish();
+        shutdown.begin_drain(); // no going back
+        assert_eq!(shutdown.phase(), ShutdownPhase::Stopped);
+        assert!(shutdown.is_draining());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_wakes_waiting_service() {
+        let bus = EventBus::new(10);
+        let shutdown = bus.shutdown().clone();
+        let waiter = tokio::spawn(async move { shutdown.stopped().await });
+
+        bus.shutdown().begin_drain();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        bus.shutdown().finish();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("stopped should resolve")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_orders() {
+        let bus = EventBus::new(10);
+        let shutdown = bus.shutdown().clone();
+        let guard = shutdown.track_order();
+        assert_eq!(shutdown.orders_in_flight(), 1);
+        assert!(!shutdown.wait_orders(Duration::from_millis(60)).await);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            drop(guard);
+        });
+        assert!(shutdown.wait_orders(Duration::from_secs(2)).await);
+        assert_eq!(shutdown.orders_in_flight(), 0);
+        // Workers are tracked separately
+        assert!(shutdown.wait_workers(Duration::from_millis(10)).await);
+    }
 }
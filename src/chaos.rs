@@ -0,0 +1,392 @@
+//! Fault injection for soak runs (`chaos` feature, `chaos.*`).
+//!
+//! With `chaos.enabled` the session installs a `ChaosInjector` and wraps the
+//! venue in `ChaosExchange`: a share of REST calls hang for `rest_timeout_ms`
+//! and fail with a retryable timeout (an order submission sometimes reaches
+//! the venue first, as a real timeout can), a share of fills is reported late
+//! (the order reads as open for `fill_delay_ms`), LLM calls fail with
+//! transient errors and the market stream is dropped at random, on average
+//! `ws_disconnects_per_hour` times an hour. Counts of injected faults are
+//! served on `/metrics` so a 24h soak can be checked against what reconnects,
+//! retries and tracker reconciliation recovered.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::config::ChaosConfig;
+use crate::error::AutoHedgeError;
+use crate::exchange::time_sync::ServerClock;
+use crate::exchange::traits::{ExchangeResult, TradingApi};
+use crate::exchange::types::{
+    AccountSummary, BracketLegs, ExchangeCapabilities, OpenOrder, OrderAck, PlaceOrderRequest,
+    Position, SymbolMeta, TopOfBook,
+};
+
+/// Injector of the running session, if chaos is on.
+static INJECTOR: RwLock<Option<Arc<ChaosInjector>>> = RwLock::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    WsDisconnect,
+    RestTimeout,
+    LlmFailure,
+    DelayedFill,
+}
+
+/// Faults injected so far.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ChaosStats {
+    pub ws_disconnects: u64,
+    pub rest_timeouts: u64,
+    pub llm_failures: u64,
+    pub delayed_fills: u64,
+}
+
+struct ChaosState {
+    rng: StdRng,
+    paused: bool,
+    next_disconnect: Option<Instant>,
+    stats: ChaosStats,
+}
+
+/// Decides which calls fail, from one (optionally seeded) RNG.
+pub struct ChaosInjector {
+    config: ChaosConfig,
+    state: Mutex<ChaosState>,
+}
+
+impl ChaosInjector {
+    pub fn new(config: &ChaosConfig) -> Self {
+        let rng = if config.seed == 0 {
+            StdRng::from_entropy()
+        } else {
+            StdRng::seed_from_u64(config.seed)
+        };
+        let injector = Self {
+            config: config.clone(),
+            state: Mutex::new(ChaosState {
+                rng,
+                paused: false,
+                next_disconnect: None,
+                stats: ChaosStats::default(),
+            }),
+        };
+        injector.schedule_disconnect(Instant::now());
+        injector
+    }
+
+    /// Whether `fault` hits the current call.
+    pub fn roll(&self, fault: Fault) -> bool {
+        self.roll_at(fault, Instant::now())
+    }
+
+    /// `roll` at `now`. Disconnects follow a Poisson schedule instead of a
+    /// per-call rate: true once the next one is due.
+    pub fn roll_at(&self, fault: Fault, now: Instant) -> bool {
+        if self.state.lock().unwrap().paused {
+            return false;
+        }
+        let pct = match fault {
+            Fault::WsDisconnect => return self.disconnect_due(now),
+            Fault::RestTimeout => self.config.rest_timeout_pct,
+            Fault::LlmFailure => self.config.llm_failure_pct,
+            Fault::DelayedFill => self.config.delayed_fill_pct,
+        };
+        if !self.chance(pct) {
+            return false;
+        }
+        let stats = &mut self.state.lock().unwrap().stats;
+        match fault {
+            Fault::RestTimeout => stats.rest_timeouts += 1,
+            Fault::LlmFailure => stats.llm_failures += 1,
+            Fault::DelayedFill => stats.delayed_fills += 1,
+            Fault::WsDisconnect => stats.ws_disconnects += 1,
+        }
+        true
+    }
+
+    fn disconnect_due(&self, now: Instant) -> bool {
+        let due = {
+            let state = self.state.lock().unwrap();
+            state.next_disconnect.is_some_and(|at| now >= at)
+        };
+        if due {
+            self.state.lock().unwrap().stats.ws_disconnects += 1;
+            self.schedule_disconnect(now);
+        }
+        due
+    }
+
+    /// True with probability `pct` percent.
+    fn chance(&self, pct: f64) -> bool {
+        pct > 0.0 && self.state.lock().unwrap().rng.gen::<f64>() * 100.0 < pct
+    }
+
+    fn schedule_disconnect(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        let rate = self.config.ws_disconnects_per_hour;
+        state.next_disconnect = (rate > 0.0).then(|| {
+            // Exponential gap with mean 1h / rate
+            let u: f64 = state.rng.gen_range(f64::EPSILON..1.0);
+            now + Duration::from_secs_f64(-u.ln() * 3600.0 / rate)
+        });
+    }
+
+    /// Stop (or resume) injecting, e.g. to let a soak run settle before its
+    /// end state is checked.
+    pub fn set_paused(&self, paused: bool) {
+        self.state.lock().unwrap().paused = paused;
+    }
+
+    pub fn rest_timeout(&self) -> Duration {
+        Duration::from_millis(self.config.rest_timeout_ms)
+    }
+
+    pub fn fill_delay(&self) -> Duration {
+        Duration::from_millis(self.config.fill_delay_ms)
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+}
+
+/// Install the session's injector (or clear it when chaos is off).
+pub fn install(config: &ChaosConfig) -> Option<Arc<ChaosInjector>> {
+    let injector = config.enabled.then(|| Arc::new(ChaosInjector::new(config)));
+    if injector.is_some() {
+        warn!(
+            "🐒 [CHAOS] Fault injection on: {:.1} WS drops/h, {:.1}% REST timeouts, {:.1}% LLM failures, {:.1}% delayed fills",
+            config.ws_disconnects_per_hour,
+            config.rest_timeout_pct,
+            config.llm_failure_pct,
+            config.delayed_fill_pct
+        );
+    }
+    *INJECTOR.write().unwrap() = injector.clone();
+    injector
+}
+
+pub fn injector() -> Option<Arc<ChaosInjector>> {
+    INJECTOR.read().unwrap().clone()
+}
+
+/// Whether `fault` hits the current call of the running session.
+pub fn inject(fault: Fault) -> bool {
+    injector().is_some_and(|c| c.roll(fault))
+}
+
+/// Faults injected in the running session.
+pub fn stats() -> Option<ChaosStats> {
+    injector().map(|c| c.stats())
+}
+
+fn timeout_error(op: &str) -> AutoHedgeError {
+    AutoHedgeError::Exchange {
+        message: format!("chaos: {} timed out", op),
+        status: None,
+        retryable: true,
+    }
+}
+
+fn is_fill(status: &str) -> bool {
+    status.eq_ignore_ascii_case("filled") || status.eq_ignore_ascii_case("partially_filled")
+}
+
+/// `TradingApi` decorator that injects REST timeouts and late fills.
+pub struct ChaosExchange {
+    inner: Arc<dyn TradingApi>,
+    chaos: Arc<ChaosInjector>,
+    /// Order id -> when its delayed fill becomes visible
+    hidden_fills: Mutex<HashMap<String, Instant>>,
+    /// Fills already shown (or rolled and not delayed)
+    shown_fills: Mutex<HashSet<String>>,
+}
+
+impl ChaosExchange {
+    pub fn new(inner: Arc<dyn TradingApi>, chaos: Arc<ChaosInjector>) -> Self {
+        Self {
+            inner,
+            chaos,
+            hidden_fills: Mutex::new(HashMap::new()),
+            shown_fills: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Hang and fail instead of calling the venue, if a timeout is rolled.
+    async fn timeout(&self, op: &str) -> ExchangeResult<()> {
+        if !self.chaos.roll(Fault::RestTimeout) {
+            return Ok(());
+        }
+        warn!("🐒 [CHAOS] Timing out {}", op);
+        tokio::time::sleep(self.chaos.rest_timeout()).await;
+        Err(timeout_error(op))
+    }
+
+    /// Report a fill as still open while its delay runs.
+    fn delay_fill(&self, ack: OrderAck) -> OrderAck {
+        if !is_fill(&ack.status) {
+            return ack;
+        }
+        let now = Instant::now();
+        let mut hidden = self.hidden_fills.lock().unwrap();
+        let reveal_at = match hidden.get(&ack.id) {
+            Some(at) => *at,
+            None => {
+                if !self.shown_fills.lock().unwrap().insert(ack.id.clone())
+                    || !self.chaos.roll(Fault::DelayedFill)
+                {
+                    return ack;
+                }
+                warn!("🐒 [CHAOS] Hiding the fill of {}", ack.id);
+                let at = now + self.chaos.fill_delay();
+                hidden.insert(ack.id.clone(), at);
+                at
+            }
+        };
+        if now >= reveal_at {
+            hidden.remove(&ack.id);
+            return ack;
+        }
+        OrderAck {
+            id: ack.id,
+            status: "new".to_string(),
+            raw: json!({}),
+        }
+    }
+}
+
+#[async_trait]
+impl TradingApi for ChaosExchange {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> ExchangeCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn get_account(&self) -> ExchangeResult<AccountSummary> {
+        self.timeout("get_account").await?;
+        self.inner.get_account().await
+    }
+
+    async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
+        self.timeout("get_positions").await?;
+        self.inner.get_positions().await
+    }
+
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        self.timeout("get_order").await?;
+        self.inner
+            .get_order(order_id)
+            .await
+            .map(|a| self.delay_fill(a))
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        self.timeout("cancel_order").await?;
+        self.inner.cancel_order(order_id).await
+    }
+
+    async fn cancel_all_orders(&self) -> ExchangeResult<()> {
+        self.timeout("cancel_all_orders").await?;
+        self.inner.cancel_all_orders().await
+    }
+
+    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
+        if self.chaos.roll(Fault::RestTimeout) {
+            // Half the timeouts hit after the venue took the order
+            let reached = self.chaos.chance(50.0);
+            warn!(
+                "🐒 [CHAOS] Timing out submit_order {} (reached venue: {})",
+                order.symbol, reached
+            );
+            if reached {
+                let _ = self.inner.submit_order(order).await;
+            }
+            tokio::time::sleep(self.chaos.rest_timeout()).await;
+            return Err(timeout_error("submit_order"));
+        }
+        self.inner
+            .submit_order(order)
+            .await
+            .map(|a| self.delay_fill(a))
+    }
+
+    async fn submit_bracket_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        self.timeout("submit_bracket_order").await?;
+        self.inner.submit_bracket_order(order, legs).await
+    }
+
+    async fn submit_oco_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        self.timeout("submit_oco_order").await?;
+        self.inner.submit_oco_order(order, legs).await
+    }
+
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        self.timeout("replace_order").await?;
+        self.inner.replace_order(order_id, order).await
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        self.timeout("get_open_orders").await?;
+        self.inner.get_open_orders().await
+    }
+
+    async fn cancel_orders_after(&self, timeout_secs: u64) -> ExchangeResult<bool> {
+        self.timeout("cancel_orders_after").await?;
+        self.inner.cancel_orders_after(timeout_secs).await
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        self.inner.get_symbol_meta(symbol).await
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        self.timeout("get_best_bid_ask").await?;
+        self.inner.get_best_bid_ask(symbol).await
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        self.inner.server_clock()
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        self.inner.get_server_time().await
+    }
+
+    async fn get_historical_bars(&self, symbol: &str, timeframe: &str) -> ExchangeResult<Value> {
+        self.inner.get_historical_bars(symbol, timeframe).await
+    }
+
+    async fn get_bars_since(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        self.inner.get_bars_since(symbol, timeframe, since).await
+    }
+}
@@ -0,0 +1,123 @@
+//! Unit tests for chaos injection - fault rates, the disconnect schedule and
+//! late fills.
+
+#[cfg(test)]
+mod chaos_tests {
+    use crate::chaos::*;
+    use crate::config::ChaosConfig;
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::*;
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn config() -> ChaosConfig {
+        ChaosConfig {
+            enabled: true,
+            seed: 11,
+            ws_disconnects_per_hour: 0.0,
+            rest_timeout_pct: 0.0,
+            rest_timeout_ms: 1,
+            llm_failure_pct: 0.0,
+            delayed_fill_pct: 0.0,
+            fill_delay_ms: 60_000,
+        }
+    }
+
+    /// Venue on which every order is filled on arrival.
+    fn filling() -> Arc<MockExchange> {
+        let exchange = MockExchange::new("filling").with_capabilities(|c| {
+            c.supports_notional_market_buy = true;
+            c.supports_ws_quotes = true;
+        });
+        Arc::new(exchange)
+    }
+
+    #[test]
+    fn test_rates_follow_the_config_and_the_seed() {
+        let never = ChaosInjector::new(&config());
+        assert!((0..1000).all(|_| !never.roll(Fault::RestTimeout)));
+        assert!((0..1000).all(|_| !never.roll(Fault::WsDisconnect)));
+
+        let cfg = ChaosConfig {
+            rest_timeout_pct: 20.0,
+            llm_failure_pct: 100.0,
+            ..config()
+        };
+        let (a, b) = (ChaosInjector::new(&cfg), ChaosInjector::new(&cfg));
+        let rolls = |c: &ChaosInjector| {
+            (0..1000)
+                .map(|_| c.roll(Fault::RestTimeout))
+                .collect::<Vec<_>>()
+        };
+        let hits = rolls(&a);
+        // Same seed, same sequence
+        assert_eq!(hits, rolls(&b));
+        let count = hits.iter().filter(|h| **h).count();
+        assert!((120..280).contains(&count), "{}", count);
+        assert!(a.roll(Fault::LlmFailure));
+        assert_eq!(a.stats().rest_timeouts, count as u64);
+        assert_eq!(a.stats().llm_failures, 1);
+
+        a.set_paused(true);
+        assert!(!a.roll(Fault::LlmFailure));
+    }
+
+    #[test]
+    fn test_disconnects_follow_the_hourly_rate() {
+        let chaos = ChaosInjector::new(&ChaosConfig {
+            ws_disconnects_per_hour: 60.0,
+            ..config()
+        });
+        let start = Instant::now();
+        // Checked every second for 10 simulated hours: ~600 drops
+        let drops = (1..=36_000)
+            .filter(|s| chaos.roll_at(Fault::WsDisconnect, start + Duration::from_secs(*s)))
+            .count();
+        assert!((450..750).contains(&drops), "{}", drops);
+        assert_eq!(chaos.stats().ws_disconnects, drops as u64);
+    }
+
+    #[tokio::test]
+    async fn test_delayed_fills_read_as_open() {
+        let chaos = Arc::new(ChaosInjector::new(&ChaosConfig {
+            delayed_fill_pct: 100.0,
+            ..config()
+        }));
+        let venue = ChaosExchange::new(filling(), chaos.clone());
+        let order = PlaceOrderRequest {
+            symbol: "BTC/USD".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            qty: Some(Decimal::ONE),
+            notional: None,
+            limit_price: None,
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        };
+        let ack = venue.submit_order(order).await.unwrap();
+        assert_eq!(ack.status, "new");
+        // Still hidden on a later read
+        assert_eq!(venue.get_order(&ack.id).await.unwrap().status, "new");
+        assert_eq!(chaos.stats().delayed_fills, 1);
+
+        // Another order's fill rolls once and shows when not delayed
+        chaos.set_paused(true);
+        assert_eq!(venue.get_order("other").await.unwrap().status, "filled");
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_fail_as_retryable() {
+        let chaos = Arc::new(ChaosInjector::new(&ChaosConfig {
+            rest_timeout_pct: 100.0,
+            ..config()
+        }));
+        let venue = ChaosExchange::new(filling(), chaos);
+        let err = venue.get_positions().await.unwrap_err();
+        assert!(err.is_retryable());
+        assert!(err.to_string().contains("timed out"), "{}", err);
+    }
+}
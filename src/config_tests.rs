@@ -90,6 +90,7 @@ max_order_amount: 100.0
 "#;
         let defaults: Defaults = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(defaults.limit_order_expiration_days, None);
+        assert_eq!(defaults.max_hold_minutes, None);
     }
 
     // ============= SymbolConfig Tests =============
@@ -115,6 +116,18 @@ take_profit_pct: 1.5
 
         assert_eq!(config.take_profit_pct, Some(1.5));
         assert_eq!(config.stop_loss_pct, None);
+        assert_eq!(config.strategy_mode, None);
+    }
+
+    #[test]
+    fn test_symbol_config_strategy_mode() {
+        let yaml = r#"
+strategy_mode: "llm"
+"#;
+        let config: SymbolConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.strategy_mode, Some("llm".to_string()));
+        assert_eq!(config.take_profit_pct, None);
     }
 
     // ============= HftConfig Tests =============
@@ -168,6 +181,21 @@ model: "gpt-4"
             Some("https://api.openai.com/v1".to_string())
         );
         assert_eq!(config.model, "gpt-4");
+        assert_eq!(config.pricing.prompt_per_million, 10.0);
+        assert_eq!(config.pricing.completion_per_million, 30.0);
+    }
+
+    #[test]
+    fn test_llm_config_pricing_override() {
+        let yaml = r#"
+model: "gpt-4o-mini"
+pricing:
+  prompt_per_million: 0.15
+"#;
+        let config: LlmConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.pricing.prompt_per_million, 0.15);
+        assert_eq!(config.pricing.completion_per_million, 30.0);
     }
 
     #[test]
@@ -213,6 +241,49 @@ base_url: "https://api.binance.com"
 
         assert_eq!(config.api_key, "BINANCE_KEY");
         assert_eq!(config.base_url, "https://api.binance.com");
+        assert!(!config.futures.enabled);
+        assert_eq!(config.futures.base_url, "https://fapi.binance.com");
+        assert_eq!(config.futures.leverage, 2);
+    }
+
+    #[test]
+    fn test_is_paper_venue() {
+        let mut config = create_test_config();
+        config.alpaca.base_url = "https://paper-api.alpaca.markets".to_string();
+        assert!(config.is_paper_venue());
+        config.alpaca.base_url = "https://api.alpaca.markets".to_string();
+        assert!(!config.is_paper_venue());
+        // Other exchanges without a config section are not paper
+        config.exchange = "kraken".to_string();
+        assert!(!config.is_paper_venue());
+    }
+
+    #[test]
+    fn test_futures_only_on_binance_when_enabled() {
+        let mut config = create_test_config();
+        let mut binance: BinanceConfig = serde_yaml::from_str(
+            r#"
+api_key: "K"
+secret_key: "S"
+base_url: "https://api.binance.com"
+futures:
+  enabled: true
+  leverage: 5
+"#,
+        )
+        .unwrap();
+        config.binance = Some(binance.clone());
+        // Exchange is alpaca
+        assert!(config.futures().is_none());
+
+        config.exchange = "Binance".to_string();
+        let futures = config.futures().unwrap();
+        assert_eq!(futures.leverage, 5);
+        assert_eq!(futures.margin_type, "ISOLATED");
+
+        binance.futures.enabled = false;
+        config.binance = Some(binance);
+        assert!(config.futures().is_none());
     }
 
     #[test]
@@ -262,6 +333,7 @@ symbol_overrides:
     stop_loss_pct: 1.0
   "ETH/USD":
     take_profit_pct: 1.5
+    strategy_mode: "LLM"
 
 history_limit: 50
 warmup_count: 50
@@ -337,6 +409,94 @@ exit_on_quotes: true
         assert_eq!(sl, 0.5);
     }
 
+    // ============= strategy_mode_for Tests =============
+
+    #[test]
+    fn test_strategy_mode_for_override() {
+        let config = create_test_config();
+
+        assert_eq!(config.strategy_mode_for("ETH/USD"), "llm");
+    }
+
+    #[test]
+    fn test_strategy_mode_for_falls_back_to_global() {
+        let config = create_test_config();
+
+        // BTC/USD overrides TP/SL only; SOL/USD has no override at all
+        assert_eq!(config.strategy_mode_for("BTC/USD"), "hft");
+        assert_eq!(config.strategy_mode_for("SOL/USD"), "hft");
+    }
+
+    #[test]
+    fn test_uses_strategy_mode() {
+        let mut config = create_test_config();
+        assert!(config.uses_strategy_mode("hft"));
+        assert!(config.uses_strategy_mode("llm"));
+        assert!(!config.uses_strategy_mode("hybrid"));
+
+        // Per-symbol modes count even when the global mode differs
+        config.strategy_mode = "llm".to_string();
+        let overrides = config.symbol_overrides.as_mut().unwrap();
+        overrides.get_mut("BTC/USD").unwrap().strategy_mode = Some("hft".to_string());
+        assert!(config.uses_strategy_mode("hft"));
+    }
+
+    // ============= max_hold_for Tests =============
+
+    #[test]
+    fn test_max_hold_for_default_and_override() {
+        let mut config = create_test_config();
+        assert_eq!(config.max_hold_for("SOL/USD"), None);
+
+        config.defaults.max_hold_minutes = Some(30.0);
+        let overrides = config.symbol_overrides.as_mut().unwrap();
+        overrides.get_mut("BTC/USD").unwrap().max_hold_minutes = Some(120.0);
+        assert_eq!(
+            config.max_hold_for("SOL/USD"),
+            Some(chrono::Duration::minutes(30))
+        );
+        assert_eq!(
+            config.max_hold_for("BTC/USD"),
+            Some(chrono::Duration::minutes(120))
+        );
+    }
+
+    #[test]
+    fn test_max_hold_for_zero_disables() {
+        let mut config = create_test_config();
+        config.defaults.max_hold_minutes = Some(30.0);
+        let overrides = config.symbol_overrides.as_mut().unwrap();
+        overrides.get_mut("ETH/USD").unwrap().max_hold_minutes = Some(0.0);
+        assert_eq!(config.max_hold_for("ETH/USD"), None);
+    }
+
+    // ============= quote_currency_for Tests =============
+
+    #[test]
+    fn test_quote_currency_from_symbol() {
+        let config = create_test_config();
+        assert_eq!(config.fx.reporting_currency, "USD");
+        assert_eq!(config.quote_currency_for("BTC/USD"), "USD");
+        assert_eq!(config.quote_currency_for("ETH/usdt"), "USDT");
+        // No separator: assumed to be in the reporting currency
+        assert_eq!(config.quote_currency_for("AAPL"), "USD");
+    }
+
+    #[test]
+    fn test_quote_currency_override() {
+        let mut config = create_test_config();
+        let overrides = config.symbol_overrides.as_mut().unwrap();
+        overrides.get_mut("ETH/USD").unwrap().quote_currency = Some("eur".to_string());
+        assert_eq!(config.quote_currency_for("ETH/USD"), "EUR");
+    }
+
+    #[test]
+    fn test_fx_default_rates() {
+        let config = create_test_config();
+        assert_eq!(config.fx.rates["USDT"], 1.0);
+        assert_eq!(config.fx.rates["USDC"], 1.0);
+    }
+
     // ============= Full Config Tests =============
 
     #[test]
@@ -350,6 +510,30 @@ exit_on_quotes: true
         assert!(config.exit_on_quotes);
     }
 
+    #[test]
+    fn test_chatter_for_module_overrides() {
+        let mut config = create_test_config();
+        assert_eq!(config.chatter_level, ChatterLevel::Normal);
+        config.chatter = serde_yaml::from_str("monitor: verbose\nws: LOW").unwrap();
+        assert_eq!(
+            config.chatter_for(ChatterModule::Monitor),
+            ChatterLevel::Verbose
+        );
+        assert_eq!(config.chatter_for(ChatterModule::Ws), ChatterLevel::Low);
+        assert_eq!(
+            config.chatter_for(ChatterModule::Strategy),
+            ChatterLevel::Normal
+        );
+
+        // Raising the global level drops the overrides
+        config.set_chatter(ChatterLevel::Verbose);
+        assert!(ChatterModule::ALL
+            .iter()
+            .all(|m| config.chatter_for(*m).is_verbose()));
+
+        assert!(serde_yaml::from_str::<ChatterConfig>("monitor: loud").is_err());
+    }
+
     #[test]
     fn test_config_optional_exchanges() {
         let config = create_test_config();
@@ -415,4 +599,52 @@ exit_on_quotes: true
         // Spread filter should be reasonable
         assert!(config.hft.max_spread_bps > 0.0);
     }
+
+    // ============= Warmup Priors Tests =============
+
+    fn with_warmup_priors(mut config: AppConfig) -> AppConfig {
+        let yaml = r#"
+enabled: true
+initial_size_factor: 0.2
+priors:
+  "BTC/USD":
+    expected_spread_bps: 2.0
+    volatility_bps: 8.0
+"#;
+        config.warmup = serde_yaml::from_str(yaml).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_warmup_disabled_by_default() {
+        let config = create_test_config();
+        assert!(!config.warmup.enabled);
+        assert!(config.warmup_prior("BTC/USD", 0).is_none());
+        assert_eq!(config.warmup_size_factor("BTC/USD", 0), 1.0);
+    }
+
+    #[test]
+    fn test_warmup_config_defaults() {
+        let config = with_warmup_priors(create_test_config());
+        assert_eq!(config.warmup.spread_tolerance, 2.0);
+        assert_eq!(config.warmup.priors["BTC/USD"].volatility_bps, 8.0);
+    }
+
+    #[test]
+    fn test_warmup_prior_only_while_warming_up() {
+        let config = with_warmup_priors(create_test_config());
+        assert!(config.warmup_prior("BTC/USD", 10).is_some());
+        assert!(config.warmup_prior("BTC/USD", 50).is_none());
+        assert!(config.warmup_prior("ETH/USD", 10).is_none());
+    }
+
+    #[test]
+    fn test_warmup_size_factor_ramps_to_full() {
+        let config = with_warmup_priors(create_test_config());
+        assert!((config.warmup_size_factor("BTC/USD", 0) - 0.2).abs() < 1e-9);
+        assert!((config.warmup_size_factor("BTC/USD", 25) - 0.6).abs() < 1e-9);
+        assert_eq!(config.warmup_size_factor("BTC/USD", 50), 1.0);
+        // No prior: unchanged sizing
+        assert_eq!(config.warmup_size_factor("ETH/USD", 0), 1.0);
+    }
 }
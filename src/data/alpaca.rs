@@ -1,11 +1,20 @@
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::Deserialize;
 use serde_json::Value;
-use std::error::Error;
 
 use crate::data::store::MarketStore;
 // use tracing::{info, error}; // Keep for other logs if needed, but ws logs are gone.
-use crate::config::AlpacaConfig;
+use crate::config::{AlpacaConfig, RateLimitConfig};
+use crate::error::{AutoHedgeError, Result};
+use crate::exchange::rate_limit::RestLimiter;
+
+/// Query suffix of a bars request: a page from `start`, else the latest bars.
+fn bars_range(start: Option<&str>) -> String {
+    match start {
+        Some(start) => format!("start={}&limit=1000", start),
+        None => "limit=100".to_string(),
+    }
+}
 
 #[derive(Clone)]
 pub struct AlpacaClient {
@@ -14,6 +23,7 @@ pub struct AlpacaClient {
     api_key: String,
     secret_key: String,
     pub market_store: MarketStore,
+    limiter: RestLimiter,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -36,6 +46,38 @@ pub struct OrderRequest {
     pub time_in_force: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit_price: Option<String>,
+    /// Trigger of "stop" / "stop_limit" orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<String>,
+    /// "bracket" / "oco" / "oto" when exit legs are attached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<OrderLeg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<OrderLeg>,
+    /// Our id for the order (tagged orders only; up to 128 characters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+/// Attached exit leg for Alpaca advanced order classes.
+#[derive(serde::Serialize, Debug)]
+pub struct OrderLeg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<String>,
+}
+
+/// `PATCH /v2/orders/{id}` body: the fields to change on a resting order.
+#[derive(serde::Serialize, Debug)]
+pub struct ReplaceOrderRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_price: Option<String>,
+    pub time_in_force: String,
 }
 
 impl AlpacaClient {
@@ -52,183 +94,323 @@ impl AlpacaClient {
             api_key,
             secret_key,
             market_store: MarketStore::new(history_limit),
+            limiter: RestLimiter::alpaca(&RateLimitConfig::default()),
         }
     }
 
-    pub async fn get_account(&self) -> Result<Account, Box<dyn Error + Send + Sync>> {
+    /// Throttle REST calls with `limiter` (shared by clones of this client).
+    pub fn with_rate_limiter(mut self, limiter: RestLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    fn authed(&self, req: RequestBuilder) -> RequestBuilder {
+        req.header("APCA-API-KEY-ID", &self.api_key)
+            .header("APCA-API-SECRET-KEY", &self.secret_key)
+    }
+
+    pub async fn get_account(&self) -> Result<Account> {
         let url = format!("{}/v2/account", self.base_url);
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let status = resp.status();
         let body = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Alpaca get_account failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_account failed ({}): {}", status, body),
+            ));
         }
 
-        let account: Account = serde_json::from_str(&body)
-            .map_err(|e| format!("Alpaca get_account decode failed: {} (body: {})", e, body))?;
+        let account: Account = serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_account decode failed: {} (body: {})",
+                e, body
+            ))
+        })?;
         Ok(account)
     }
 
+    /// Stock bars: the latest 100, or up to 1000 from `start` (RFC3339).
     pub async fn get_historical_bars(
         &self,
         symbol: &str,
         timeframe: &str,
-    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        start: Option<&str>,
+    ) -> Result<Value> {
         let url = format!(
-            "{}/v2/stocks/{}/bars?timeframe={}&limit=100",
-            self.base_url, symbol, timeframe
+            "{}/v2/stocks/{}/bars?timeframe={}&{}",
+            self.base_url,
+            symbol,
+            timeframe,
+            bars_range(start)
         );
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let data: Value = resp.json().await?;
         Ok(data)
     }
 
-    pub async fn get_assets(
-        &self,
-        asset_class: Option<String>,
-    ) -> Result<Vec<Value>, Box<dyn Error + Send + Sync>> {
+    pub async fn get_assets(&self, asset_class: Option<String>) -> Result<Vec<Value>> {
         let mut url = format!("{}/v2/assets?status=active", self.base_url);
         if let Some(param) = asset_class {
             url.push_str(&format!("&asset_class={}", param));
         }
 
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let status = resp.status();
         let body = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Alpaca get_assets failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_assets failed ({}): {}", status, body),
+            ));
         }
 
-        let assets: Vec<Value> = serde_json::from_str(&body)
-            .map_err(|e| format!("Alpaca get_assets decode failed: {} (body: {})", e, body))?;
+        let assets: Vec<Value> = serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_assets decode failed: {} (body: {})",
+                e, body
+            ))
+        })?;
         Ok(assets)
     }
 
-    pub async fn get_positions(&self) -> Result<Vec<Value>, Box<dyn Error + Send + Sync>> {
+    /// One asset by symbol ("BTC/USD" or "AAPL").
+    pub async fn get_asset(&self, symbol: &str) -> Result<Value> {
+        let url = format!("{}/v2/assets/{}", self.base_url, symbol.replace('/', "%2F"));
+        let resp = self
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_asset failed ({}): {}", status, body),
+            ));
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_asset decode failed: {} (body: {})",
+                e, body
+            ))
+        })
+    }
+
+    pub async fn get_positions(&self) -> Result<Vec<Value>> {
         let url = format!("{}/v2/positions", self.base_url);
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let status = resp.status();
         let body = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Alpaca get_positions failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_positions failed ({}): {}", status, body),
+            ));
         }
 
-        let positions: Vec<Value> = serde_json::from_str(&body)
-            .map_err(|e| format!("Alpaca get_positions decode failed: {} (body: {})", e, body))?;
+        let positions: Vec<Value> = serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_positions decode failed: {} (body: {})",
+                e, body
+            ))
+        })?;
         Ok(positions)
     }
 
+    /// Latest quote: `{"quotes": {"BTC/USD": {"bp", "ap", ...}}}` for crypto,
+    /// `{"symbol", "quote": {"bp", "ap", ...}}` for equities.
+    pub async fn get_latest_quote(&self, symbol: &str, crypto: bool) -> Result<Value> {
+        let url = if crypto {
+            format!(
+                "https://data.alpaca.markets/v1beta3/crypto/us/latest/quotes?symbols={}",
+                symbol
+            )
+        } else {
+            format!(
+                "https://data.alpaca.markets/v2/stocks/{}/quotes/latest",
+                symbol
+            )
+        };
+        let resp = self
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca latest quote failed ({}): {}", status, body),
+            ));
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca latest quote decode failed: {} (body: {})",
+                e, body
+            ))
+        })
+    }
+
+    /// Crypto bars: the latest 100, or up to 1000 from `start` (RFC3339).
     pub async fn get_crypto_bars(
         &self,
         symbol: &str,
         timeframe: &str,
-    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        start: Option<&str>,
+    ) -> Result<Value> {
         let url = format!(
-            "https://data.alpaca.markets/v1beta3/crypto/us/bars?symbols={}&timeframe={}&limit=100",
-            symbol, timeframe
+            "https://data.alpaca.markets/v1beta3/crypto/us/bars?symbols={}&timeframe={}&{}",
+            symbol,
+            timeframe,
+            bars_range(start)
         );
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let data: Value = resp.json().await?;
         Ok(data)
     }
 
-    pub async fn get_order(&self, order_id: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+    pub async fn get_order(&self, order_id: &str) -> Result<Value> {
         let url = format!("{}/v2/orders/{}", self.base_url, order_id);
         let resp = self
-            .client
-            .get(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
             .await?;
 
         let status = resp.status();
         let body = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Alpaca get_order failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_order failed ({}): {}", status, body),
+            ));
         }
 
-        let order: Value = serde_json::from_str(&body)
-            .map_err(|e| format!("Alpaca get_order decode failed: {} (body: {})", e, body))?;
+        let order: Value = serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_order decode failed: {} (body: {})",
+                e, body
+            ))
+        })?;
         Ok(order)
     }
 
-    pub async fn cancel_order(&self, order_id: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
         let url = format!("{}/v2/orders/{}", self.base_url, order_id);
         let resp = self
-            .client
-            .delete(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.delete(&url)))
             .await?;
 
         let status = resp.status();
         if !status.is_success() {
             let body = resp.text().await?;
-            return Err(format!("Alpaca cancel_order failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca cancel_order failed ({}): {}", status, body),
+            ));
         }
         Ok(())
     }
 
-    pub async fn cancel_all_orders(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// Replace a resting order; Alpaca answers with the new order (new id) and
+    /// marks the old one `replaced`.
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        replace: &ReplaceOrderRequest,
+    ) -> Result<Value> {
+        let url = format!("{}/v2/orders/{}", self.base_url, order_id);
+        let resp = self
+            .limiter
+            .send(1, || self.authed(self.client.patch(&url)).json(replace))
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca replace_order failed ({}): {}", status, body),
+            ));
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca replace_order decode failed: {} (body: {})",
+                e, body
+            ))
+        })
+    }
+
+    /// Working orders (`GET /v2/orders?status=open`).
+    pub async fn get_open_orders(&self) -> Result<Vec<Value>> {
+        let url = format!("{}/v2/orders?status=open&limit=500", self.base_url);
+        let resp = self
+            .limiter
+            .send(1, || self.authed(self.client.get(&url)))
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca get_open_orders failed ({}): {}", status, body),
+            ));
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Alpaca get_open_orders decode failed: {} (body: {})",
+                e, body
+            ))
+        })
+    }
+
+    pub async fn cancel_all_orders(&self) -> Result<()> {
         let url = format!("{}/v2/orders", self.base_url);
         let resp = self
-            .client
-            .delete(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.delete(&url)))
             .await?;
 
         let status = resp.status();
         if !status.is_success() {
             let body = resp.text().await?;
-            return Err(format!("Alpaca cancel_all_orders failed ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Alpaca cancel_all_orders failed ({}): {}", status, body),
+            ));
         }
         Ok(())
     }
 
-    pub async fn submit_order(
-        &self,
-        order: OrderRequest,
-        trading_mode: &str,
-    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+    pub async fn submit_order(&self, order: OrderRequest, trading_mode: &str) -> Result<Value> {
         let is_crypto = trading_mode.eq_ignore_ascii_case("crypto");
         let url = if is_crypto {
             format!("{}/v2/orders", self.base_url)
@@ -237,25 +419,31 @@ impl AlpacaClient {
         };
 
         let resp = self
-            .client
-            .post(&url)
-            .header("APCA-API-KEY-ID", &self.api_key)
-            .header("APCA-API-SECRET-KEY", &self.secret_key)
-            .json(&order)
-            .send()
+            .limiter
+            .send(1, || self.authed(self.client.post(&url)).json(&order))
             .await?;
 
         let status = resp.status();
         let body = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Failed to place order ({}): {}", status, body).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Failed to place order ({}): {}", status, body),
+            ));
         }
 
-        let data: Value = serde_json::from_str(&body)
-            .map_err(|e| format!("Failed to decode order response: {} (body: {})", e, body))?;
+        let data: Value = serde_json::from_str(&body).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Failed to decode order response: {} (body: {})",
+                e, body
+            ))
+        })?;
 
         if data.get("id").is_none() {
-            return Err(format!("Failed to place order: {:?}", data).into());
+            return Err(AutoHedgeError::exchange(format!(
+                "Failed to place order: {:?}",
+                data
+            )));
         }
         Ok(data)
     }
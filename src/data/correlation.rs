@@ -0,0 +1,171 @@
+//! Rolling return correlations between traded symbols.
+//!
+//! `CorrelationTracker` samples every symbol's quote mid on one shared clock
+//! (`correlation_guard.sample_secs`), so the return series of different
+//! symbols line up sample for sample, and keeps the last `window` returns of
+//! each. The risk engine asks it whether a buy would add to a cluster of
+//! highly correlated holdings (BTC, ETH and SOL moving together are one bet,
+//! not three) and skips the entry once that cluster already holds
+//! `max_correlated_positions` symbols.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use crate::bus::Shutdown;
+use crate::config::CorrelationGuardConfig;
+use crate::data::store::MarketStore;
+use crate::events::EntrySkippedEvent;
+
+/// Pearson correlation of the most recent `n` samples of both series, or None
+/// with fewer than `min_samples` or a flat series.
+pub fn correlation(a: &[f64], b: &[f64], min_samples: usize) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < min_samples.max(2) {
+        return None;
+    }
+    let (a, b) = (&a[a.len() - n..], &b[b.len() - n..]);
+    let mean_a = a.iter().sum::<f64>() / n as f64;
+    let mean_b = b.iter().sum::<f64>() / n as f64;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return None;
+    }
+    Some(cov / (var_a * var_b).sqrt())
+}
+
+/// Shared rolling mid samples, consulted by the risk engine for every buy.
+#[derive(Clone)]
+pub struct CorrelationTracker {
+    config: CorrelationGuardConfig,
+    /// Sampled mids per symbol, oldest first (`window` + 1 kept)
+    mids: Arc<RwLock<HashMap<String, VecDeque<f64>>>>,
+}
+
+impl CorrelationTracker {
+    pub fn new(config: &CorrelationGuardConfig) -> Self {
+        Self {
+            config: config.clone(),
+            mids: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Append one sampled mid of `symbol`.
+    pub fn record(&self, symbol: &str, mid: f64) {
+        if mid <= 0.0 {
+            return;
+        }
+        let mut mids = self.mids.write().unwrap();
+        let series = mids.entry(symbol.to_string()).or_default();
+        if series.len() > self.config.window {
+            series.pop_front();
+        }
+        series.push_back(mid);
+    }
+
+    /// Sample the latest mid of each of `symbols`. Symbols without a quote yet
+    /// are skipped; a quiet symbol repeats its last mid (a zero return).
+    pub fn sample(&self, store: &MarketStore, symbols: &[String]) {
+        for symbol in symbols {
+            if let Some(q) = store.get_latest_quote(symbol) {
+                if q.bid_price > 0.0 && q.ask_price >= q.bid_price {
+                    self.record(symbol, (q.bid_price + q.ask_price) / 2.0);
+                }
+            }
+        }
+    }
+
+    /// Sample-to-sample returns of `symbol`, oldest first.
+    pub fn returns(&self, symbol: &str) -> Vec<f64> {
+        let mids = self.mids.read().unwrap();
+        mids.get(symbol)
+            .map(|m| {
+                m.iter()
+                    .zip(m.iter().skip(1))
+                    .map(|(a, b)| (b - a) / a)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Rolling correlation of two symbols, once both have `min_samples` returns.
+    pub fn correlation(&self, a: &str, b: &str) -> Option<f64> {
+        correlation(&self.returns(a), &self.returns(b), self.config.min_samples)
+    }
+
+    /// Held symbols (other than `symbol`) correlated with it at or above the
+    /// threshold, with their correlation, sorted by symbol.
+    pub fn correlated_holdings(&self, symbol: &str, held: &[String]) -> Vec<(String, f64)> {
+        let mut correlated: Vec<(String, f64)> = held
+            .iter()
+            .filter(|s| s.as_str() != symbol)
+            .filter_map(|s| {
+                let c = self.correlation(symbol, s)?;
+                (c >= self.config.threshold).then(|| (s.clone(), c))
+            })
+            .collect();
+        correlated.sort_by(|a, b| a.0.cmp(&b.0));
+        correlated.dedup_by(|a, b| a.0 == b.0);
+        correlated
+    }
+
+    /// The skip to publish if buying `symbol` at `now` would exceed the
+    /// correlated-holdings limit given the `held` symbols.
+    pub fn check_entry(
+        &self,
+        symbol: &str,
+        held: &[String],
+        now: DateTime<Utc>,
+    ) -> Option<EntrySkippedEvent> {
+        if !self.config.enabled || self.config.max_correlated_positions == 0 {
+            return None;
+        }
+        let correlated = self.correlated_holdings(symbol, held);
+        if correlated.len() < self.config.max_correlated_positions {
+            return None;
+        }
+        let list: Vec<String> = correlated
+            .iter()
+            .map(|(s, c)| format!("{} ({:.2})", s, c))
+            .collect();
+        Some(EntrySkippedEvent {
+            symbol: symbol.to_string(),
+            reason: "correlation_guard".to_string(),
+            detail: format!(
+                "{} held symbols correlated >= {:.2}: {}",
+                correlated.len(),
+                self.config.threshold,
+                list.join(", ")
+            ),
+            timestamp: now.to_rfc3339(),
+        })
+    }
+
+    /// Sample `symbols` every `sample_secs` until the session drains.
+    pub fn start(self, store: MarketStore, symbols: Vec<String>, shutdown: Shutdown) {
+        let period = Duration::from_secs(self.config.sample_secs.max(1));
+        tokio::spawn(async move {
+            info!(
+                "🔗 [CORRELATION] Sampling {} symbol(s) every {}s (window {})",
+                symbols.len(),
+                period.as_secs(),
+                self.config.window
+            );
+            let mut tick = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = tick.tick() => self.sample(&store, &symbols),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,153 @@
+//! Unit tests for rolling correlations and the correlated-holdings guard.
+
+#[cfg(test)]
+mod correlation_tests {
+    use crate::config::CorrelationGuardConfig;
+    use crate::data::correlation::*;
+    use crate::data::store::{MarketStore, Quote};
+    use chrono::Utc;
+
+    fn config(max_correlated_positions: usize) -> CorrelationGuardConfig {
+        CorrelationGuardConfig {
+            enabled: true,
+            window: 10,
+            min_samples: 5,
+            threshold: 0.9,
+            max_correlated_positions,
+            ..CorrelationGuardConfig::default()
+        }
+    }
+
+    /// BTC, ETH and SOL move together; GOLD moves against them.
+    fn tracker(max_correlated_positions: usize) -> CorrelationTracker {
+        let tracker = CorrelationTracker::new(&config(max_correlated_positions));
+        let moves = [1.0, -2.0, 3.0, -1.0, 2.0, 0.5, -1.5];
+        let mut price = 100.0;
+        for m in moves {
+            price += m;
+            tracker.record("BTC/USD", price * 300.0);
+            tracker.record("ETH/USD", price * 20.0 + m * 0.1);
+            tracker.record("SOL/USD", price);
+            tracker.record("GOLD/USD", 300.0 - price);
+        }
+        tracker
+    }
+
+    fn held(symbols: &[&str]) -> Vec<String> {
+        symbols.iter().map(|s| s.to_string()).collect()
+    }
+
+    // ============= Statistics Tests =============
+
+    #[test]
+    fn test_correlation() {
+        let a = vec![0.01, -0.02, 0.03, -0.01, 0.02, 0.0];
+        let b: Vec<f64> = a.iter().map(|x| x * 2.0).collect();
+        let c: Vec<f64> = a.iter().map(|x| -x).collect();
+        assert!((correlation(&a, &b, 5).unwrap() - 1.0).abs() < 1e-9);
+        assert!((correlation(&a, &c, 5).unwrap() + 1.0).abs() < 1e-9);
+        // Too few samples, or a flat series
+        assert!(correlation(&a, &b, 10).is_none());
+        assert!(correlation(&a, &[0.0; 6], 5).is_none());
+    }
+
+    #[test]
+    fn test_returns_keep_rolling_window() {
+        let tracker = CorrelationTracker::new(&config(2));
+        for i in 0..20 {
+            tracker.record("BTC/USD", 100.0 + i as f64);
+        }
+        tracker.record("BTC/USD", 0.0);
+        let r = tracker.returns("BTC/USD");
+        assert_eq!(r.len(), 10);
+        assert!((r[9] - 1.0 / 118.0).abs() < 1e-12);
+        assert!(tracker.returns("ETH/USD").is_empty());
+    }
+
+    #[test]
+    fn test_tracker_correlation_needs_min_samples() {
+        let tracker = tracker(2);
+        assert!(tracker.correlation("BTC/USD", "SOL/USD").unwrap() > 0.99);
+        assert!(tracker.correlation("BTC/USD", "GOLD/USD").unwrap() < -0.99);
+
+        let young = CorrelationTracker::new(&config(2));
+        for price in [100.0, 101.0, 99.0] {
+            young.record("BTC/USD", price);
+            young.record("SOL/USD", price);
+        }
+        assert!(young.correlation("BTC/USD", "SOL/USD").is_none());
+    }
+
+    #[test]
+    fn test_sample_reads_latest_mids() {
+        let store = MarketStore::new(10);
+        let tracker = CorrelationTracker::new(&config(2));
+        let symbols = held(&["BTC/USD", "ETH/USD"]);
+        for mid in [100.0, 110.0] {
+            store.update_quote(
+                "BTC/USD".to_string(),
+                Quote {
+                    symbol: "BTC/USD".to_string(),
+                    bid_price: mid - 0.5,
+                    ask_price: mid + 0.5,
+                    bid_size: 1.0,
+                    ask_size: 1.0,
+                    timestamp: "2026-03-02T10:00:00Z".to_string(),
+                },
+            );
+            tracker.sample(&store, &symbols);
+        }
+        assert_eq!(tracker.returns("BTC/USD"), vec![0.1]);
+        // No quote yet: nothing sampled
+        assert!(tracker.returns("ETH/USD").is_empty());
+    }
+
+    // ============= Guard Tests =============
+
+    #[test]
+    fn test_correlated_holdings() {
+        let tracker = tracker(2);
+        let correlated = tracker.correlated_holdings(
+            "SOL/USD",
+            &held(&["SOL/USD", "GOLD/USD", "ETH/USD", "BTC/USD"]),
+        );
+        let symbols: Vec<&str> = correlated.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(symbols, ["BTC/USD", "ETH/USD"]);
+        assert!(correlated.iter().all(|(_, c)| *c >= 0.9));
+    }
+
+    #[test]
+    fn test_check_entry_blocks_concentrated_cluster() {
+        let tracker = tracker(2);
+        let skip = tracker
+            .check_entry("SOL/USD", &held(&["BTC/USD", "ETH/USD"]), Utc::now())
+            .unwrap();
+        assert_eq!(skip.symbol, "SOL/USD");
+        assert_eq!(skip.reason, "correlation_guard");
+        assert!(skip
+            .detail
+            .starts_with("2 held symbols correlated >= 0.90: BTC/USD ("));
+
+        // One correlated holding (plus an uncorrelated one) is still allowed
+        assert!(tracker
+            .check_entry("SOL/USD", &held(&["BTC/USD", "GOLD/USD"]), Utc::now())
+            .is_none());
+    }
+
+    #[test]
+    fn test_check_entry_disabled() {
+        let tracker = tracker(0);
+        assert!(tracker
+            .check_entry("SOL/USD", &held(&["BTC/USD", "ETH/USD"]), Utc::now())
+            .is_none());
+
+        let off = CorrelationTracker::new(&CorrelationGuardConfig {
+            enabled: false,
+            ..config(1)
+        });
+        off.record("BTC/USD", 1.0);
+        assert!(off
+            .check_entry("SOL/USD", &held(&["BTC/USD"]), Utc::now())
+            .is_none());
+    }
+}
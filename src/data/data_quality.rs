@@ -0,0 +1,225 @@
+//! Per-symbol market data quality (`data_quality.*`).
+//!
+//! `MarketStore::accept_quote` records every accepted quote here. Over the
+//! last `window_secs` each symbol gets a message rate, the median bid/ask
+//! spread, its longest quote gap and the share of the window it was stale
+//! (time beyond `stale_after_secs` since the previous quote). `/metrics`
+//! serves the figures; the strategy engine skips symbols that breach
+//! `min_msgs_per_min`, `max_median_spread_bps` or `max_stale_pct` until
+//! their feed recovers.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::config::DataQualityConfig;
+
+/// Verdicts are recomputed at most this often per symbol.
+const VERDICT_TTL: Duration = Duration::from_secs(1);
+
+/// Feed quality of one symbol over the window.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SymbolQuality {
+    /// Quotes in the window
+    pub samples: usize,
+    pub msgs_per_min: f64,
+    pub median_spread_bps: Option<f64>,
+    /// Longest gap between quotes (including the one still open)
+    pub max_gap_secs: f64,
+    /// Time since the last quote
+    pub last_quote_secs: f64,
+    /// Share of the window spent beyond `stale_after_secs` without a quote
+    pub stale_pct: f64,
+    /// First breached threshold; None while the feed is healthy (or too new
+    /// to judge)
+    pub degraded: Option<String>,
+}
+
+#[derive(Debug)]
+struct SymbolFeed {
+    first_seen: Instant,
+    /// Arrival and spread (bps) per quote in the window, oldest first
+    quotes: VecDeque<(Instant, f64)>,
+    verdict: Option<(Instant, Option<String>)>,
+}
+
+#[derive(Debug, Default)]
+struct QualityState {
+    /// Disabled until `configure`
+    config: Option<DataQualityConfig>,
+    symbols: HashMap<String, SymbolFeed>,
+}
+
+/// Rolling feed-quality figures, shared by every clone of the store.
+#[derive(Clone, Debug, Default)]
+pub struct DataQualityMonitor {
+    state: Arc<Mutex<QualityState>>,
+}
+
+impl DataQualityMonitor {
+    /// Apply `config`; recorded quotes are kept.
+    pub fn configure(&self, config: &DataQualityConfig) {
+        let mut state = self.state.lock().unwrap();
+        state.config = config.enabled.then(|| config.clone());
+        for feed in state.symbols.values_mut() {
+            feed.verdict = None;
+        }
+    }
+
+    pub fn record(&self, symbol: &str, bid: f64, ask: f64) {
+        self.record_at(symbol, bid, ask, Instant::now());
+    }
+
+    /// Record a quote for `symbol` that arrived at `now`.
+    pub fn record_at(&self, symbol: &str, bid: f64, ask: f64, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        let Some(config) = state.config.clone() else {
+            return;
+        };
+        let mid = (bid + ask) / 2.0;
+        let spread_bps = if mid > 0.0 {
+            (ask - bid) / mid * 10_000.0
+        } else {
+            0.0
+        };
+        let feed = state
+            .symbols
+            .entry(symbol.to_string())
+            .or_insert_with(|| SymbolFeed {
+                first_seen: now,
+                quotes: VecDeque::new(),
+                verdict: None,
+            });
+        feed.quotes.push_back((now, spread_bps));
+        let window = Duration::from_secs(config.window_secs.max(1));
+        if let Some(start) = now.checked_sub(window) {
+            while feed.quotes.front().is_some_and(|(at, _)| *at < start) {
+                feed.quotes.pop_front();
+            }
+        }
+        while feed.quotes.len() > config.max_samples.max(2) {
+            feed.quotes.pop_front();
+        }
+    }
+
+    pub fn snapshot(&self) -> BTreeMap<String, SymbolQuality> {
+        self.snapshot_at(Instant::now())
+    }
+
+    /// Figures of every recorded symbol at `now`.
+    pub fn snapshot_at(&self, now: Instant) -> BTreeMap<String, SymbolQuality> {
+        let state = self.state.lock().unwrap();
+        let Some(config) = &state.config else {
+            return BTreeMap::new();
+        };
+        state
+            .symbols
+            .iter()
+            .map(|(symbol, feed)| (symbol.clone(), measure(config, feed, now)))
+            .collect()
+    }
+
+    pub fn degraded(&self, symbol: &str) -> Option<String> {
+        self.degraded_at(symbol, Instant::now())
+    }
+
+    /// Why `symbol`'s feed is degraded at `now`, if it is. Symbols never
+    /// quoted are left to the stale-data watch.
+    pub fn degraded_at(&self, symbol: &str, now: Instant) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        let config = state.config.clone()?;
+        let feed = state.symbols.get_mut(symbol)?;
+        if let Some((at, verdict)) = &feed.verdict {
+            if now.saturating_duration_since(*at) < VERDICT_TTL {
+                return verdict.clone();
+            }
+        }
+        let verdict = measure(&config, feed, now).degraded;
+        feed.verdict = Some((now, verdict.clone()));
+        verdict
+    }
+}
+
+fn measure(config: &DataQualityConfig, feed: &SymbolFeed, now: Instant) -> SymbolQuality {
+    let window = Duration::from_secs(config.window_secs.max(1));
+    let mut start = now
+        .checked_sub(window)
+        .map_or(feed.first_seen, |s| s.max(feed.first_seen));
+    // Capped at max_samples: the figures cover the quotes kept
+    if feed.quotes.len() >= config.max_samples.max(2) {
+        if let Some((oldest, _)) = feed.quotes.front() {
+            start = start.max(*oldest);
+        }
+    }
+    let span = now.saturating_duration_since(start).as_secs_f64().max(1.0);
+    let stale_after = Duration::from_secs_f64(config.stale_after_secs.max(0.0));
+
+    let mut spreads = Vec::new();
+    let mut prev = start;
+    let mut max_gap = Duration::ZERO;
+    let mut stale = Duration::ZERO;
+    for &(at, spread) in &feed.quotes {
+        if at < start {
+            continue;
+        }
+        spreads.push(spread);
+        let gap = at.saturating_duration_since(prev);
+        max_gap = max_gap.max(gap);
+        stale += gap.saturating_sub(stale_after);
+        prev = at;
+    }
+    let open_gap = now.saturating_duration_since(prev);
+    max_gap = max_gap.max(open_gap);
+    stale += open_gap.saturating_sub(stale_after);
+
+    let median_spread_bps = (!spreads.is_empty()).then(|| {
+        let mid = spreads.len() / 2;
+        *spreads.select_nth_unstable_by(mid, f64::total_cmp).1
+    });
+    let mut quality = SymbolQuality {
+        samples: spreads.len(),
+        msgs_per_min: spreads.len() as f64 / span * 60.0,
+        median_spread_bps,
+        max_gap_secs: max_gap.as_secs_f64(),
+        last_quote_secs: feed.quotes.back().map_or(0.0, |(at, _)| {
+            now.saturating_duration_since(*at).as_secs_f64()
+        }),
+        stale_pct: (stale.as_secs_f64() / span * 100.0).min(100.0),
+        degraded: None,
+    };
+
+    let watched_full_window = now.saturating_duration_since(feed.first_seen) >= window;
+    if quality.samples >= config.min_samples || watched_full_window {
+        quality.degraded = verdict(config, &quality);
+    }
+    quality
+}
+
+/// First threshold `quality` breaches.
+fn verdict(config: &DataQualityConfig, quality: &SymbolQuality) -> Option<String> {
+    if config.max_stale_pct > 0.0 && quality.stale_pct > config.max_stale_pct {
+        return Some(format!(
+            "stale {:.0}% of the window > {:.0}%",
+            quality.stale_pct, config.max_stale_pct
+        ));
+    }
+    if config.min_msgs_per_min > 0.0 && quality.msgs_per_min < config.min_msgs_per_min {
+        return Some(format!(
+            "{:.1} quotes/min < {:.1}",
+            quality.msgs_per_min, config.min_msgs_per_min
+        ));
+    }
+    match quality.median_spread_bps {
+        Some(spread)
+            if config.max_median_spread_bps > 0.0 && spread > config.max_median_spread_bps =>
+        {
+            Some(format!(
+                "median spread {:.1} bps > {:.1}",
+                spread, config.max_median_spread_bps
+            ))
+        }
+        _ => None,
+    }
+}
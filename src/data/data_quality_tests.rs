@@ -0,0 +1,118 @@
+//! Unit tests for the data quality monitor: rolling figures, thresholds and
+//! recovery.
+
+#[cfg(test)]
+mod data_quality_tests {
+    use std::time::{Duration, Instant};
+
+    use crate::config::DataQualityConfig;
+    use crate::data::data_quality::*;
+    use crate::data::store::{MarketStore, Quote};
+
+    fn monitor(config: DataQualityConfig) -> DataQualityMonitor {
+        let monitor = DataQualityMonitor::default();
+        monitor.configure(&config);
+        monitor
+    }
+
+    fn secs(start: Instant, s: f64) -> Instant {
+        start + Duration::from_secs_f64(s)
+    }
+
+    #[test]
+    fn test_rate_spread_and_gaps_over_the_window() {
+        let m = monitor(DataQualityConfig {
+            window_secs: 60,
+            stale_after_secs: 5.0,
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+        // One quote a second for 30s at 10 bps, then a 20s gap
+        for i in 0..=30 {
+            m.record_at("BTC/USD", 99.95, 100.05, secs(t0, i as f64));
+        }
+        let q = &m.snapshot_at(secs(t0, 50.0))["BTC/USD"];
+        assert_eq!(q.samples, 31);
+        assert!((q.msgs_per_min - 31.0 / 50.0 * 60.0).abs() < 1e-9);
+        assert!((q.median_spread_bps.unwrap() - 10.0).abs() < 1e-6);
+        assert!((q.max_gap_secs - 20.0).abs() < 1e-6);
+        assert!((q.last_quote_secs - 20.0).abs() < 1e-6);
+        // 15s of the 20s gap is stale
+        assert!((q.stale_pct - 30.0).abs() < 1e-6);
+        // Thresholds are off by default
+        assert_eq!(q.degraded, None);
+
+        // Quotes older than the window drop out
+        m.record_at("BTC/USD", 99.95, 100.05, secs(t0, 100.0));
+        let q = &m.snapshot_at(secs(t0, 100.0))["BTC/USD"];
+        assert_eq!(q.samples, 1);
+    }
+
+    #[test]
+    fn test_thresholds_degrade_and_recover() {
+        let config = DataQualityConfig {
+            window_secs: 60,
+            stale_after_secs: 5.0,
+            min_samples: 5,
+            max_stale_pct: 25.0,
+            max_median_spread_bps: 50.0,
+            ..Default::default()
+        };
+        let m = monitor(config);
+        let t0 = Instant::now();
+        for i in 0..10 {
+            m.record_at("ETH/USD", 99.0, 101.0, secs(t0, i as f64));
+        }
+        // 200 bps median spread
+        let reason = m.degraded_at("ETH/USD", secs(t0, 10.0)).unwrap();
+        assert!(reason.contains("spread"), "{}", reason);
+
+        // Tight quotes again; the stale window still trips later
+        for i in 10..40 {
+            m.record_at("ETH/USD", 99.99, 100.01, secs(t0, i as f64));
+        }
+        assert_eq!(m.degraded_at("ETH/USD", secs(t0, 40.0)), None);
+        let reason = m.degraded_at("ETH/USD", secs(t0, 70.0)).unwrap();
+        assert!(reason.starts_with("stale"), "{}", reason);
+
+        // Unknown symbols are not judged
+        assert_eq!(m.degraded_at("SOL/USD", secs(t0, 70.0)), None);
+    }
+
+    #[test]
+    fn test_new_symbols_wait_for_min_samples() {
+        let m = monitor(DataQualityConfig {
+            window_secs: 60,
+            min_samples: 20,
+            min_msgs_per_min: 30.0,
+            ..Default::default()
+        });
+        let t0 = Instant::now();
+        m.record_at("BTC/USD", 99.0, 101.0, t0);
+        assert_eq!(m.degraded_at("BTC/USD", secs(t0, 10.0)), None);
+        // A full window later the slow feed is judged anyway
+        let reason = m.degraded_at("BTC/USD", secs(t0, 61.0)).unwrap();
+        assert!(reason.contains("quotes/min"), "{}", reason);
+    }
+
+    #[test]
+    fn test_store_records_quotes_once_configured() {
+        let store = MarketStore::new(10);
+        let quote = Quote {
+            symbol: "BTC/USD".to_string(),
+            bid_price: 100.0,
+            ask_price: 100.1,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: String::new(),
+        };
+        store
+            .accept_quote("BTC/USD".to_string(), quote.clone())
+            .unwrap();
+        assert!(store.data_quality.snapshot().is_empty());
+
+        store.data_quality.configure(&DataQualityConfig::default());
+        store.accept_quote("BTC/USD".to_string(), quote).unwrap();
+        assert_eq!(store.data_quality.snapshot()["BTC/USD"].samples, 1);
+    }
+}
@@ -0,0 +1,139 @@
+//! Unit tests for the historical bar downloader and its CSV cache.
+
+#[cfg(test)]
+mod downloader_tests {
+    use crate::config::HistoryDownloadConfig;
+    use crate::data::downloader::*;
+    use crate::data::store::Bar;
+    use crate::exchange::mock::MockExchange;
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "autohedge_downloader_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn minute(m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, 12, m, 0).unwrap()
+    }
+
+    type Requests = Arc<Mutex<Vec<DateTime<Utc>>>>;
+
+    /// Downloader over a Binance-shaped venue serving 1-minute klines for
+    /// minutes 0..=9, at most `page` per request; the `since` of each
+    /// request is recorded.
+    fn downloader(dir: &Path, page: usize) -> (Requests, HistoryDownloader) {
+        let requests = Requests::default();
+        let seen = requests.clone();
+        let exchange = MockExchange::new("binance")
+            .with_capabilities(|c| c.supports_notional_market_buy = true)
+            .on_bars_since(move |_, since| {
+                seen.lock().unwrap().push(since);
+                let klines: Vec<Value> = (0..10)
+                    .map(minute)
+                    .filter(|t| *t >= since)
+                    .take(page)
+                    .map(|t| json!([t.timestamp_millis(), "100", "101", "99", "100.5", "2"]))
+                    .collect();
+                Ok(Value::Array(klines))
+            });
+        let config = HistoryDownloadConfig {
+            dir: dir.display().to_string(),
+            page_delay_ms: 0,
+            ..HistoryDownloadConfig::default()
+        };
+        (
+            requests,
+            HistoryDownloader::new(Arc::new(exchange), &config),
+        )
+    }
+
+    // ============= Cache Tests =============
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let dir = temp_dir("roundtrip");
+        let path = cache_path(&dir, "kraken", "BTC/USD", "5Min");
+        assert!(path.ends_with("kraken/BTC-USD_5Min.csv"));
+        assert!(read_cache(&path, "BTC/USD").unwrap().is_empty());
+
+        let bar = |ts: &str, close| Bar {
+            symbol: "BTC/USD".to_string(),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close,
+            volume: 10.0,
+            timestamp: ts.to_string(),
+        };
+        append_bars(&path, &[bar("2026-10-16T12:00:00.000Z", 1.5)]).unwrap();
+        append_bars(&path, &[bar("2026-10-16T12:05:00.000Z", 1.8)]).unwrap();
+
+        let bars = read_cache(&path, "BTC/USD").unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[1].close, 1.8);
+        assert_eq!(bars[1].timestamp, "2026-10-16T12:05:00.000Z");
+        // One header only
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.matches("timestamp").count(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // ============= Download Tests =============
+
+    #[tokio::test]
+    async fn test_download_pages_and_skips_forming_bar() {
+        let dir = temp_dir("pages");
+        let (requests, downloader) = downloader(&dir, 4);
+
+        // Minute 9 is still forming at 12:09:30
+        let now = minute(9) + chrono::Duration::seconds(30);
+        let added = downloader
+            .download("BTC/USD", minute(2), now)
+            .await
+            .unwrap();
+        assert_eq!(added, 7);
+
+        let bars = read_cache(&downloader.path("BTC/USD"), "BTC/USD").unwrap();
+        assert_eq!(bars.first().unwrap().timestamp, "2026-10-16T12:02:00.000Z");
+        assert_eq!(bars.last().unwrap().timestamp, "2026-10-16T12:08:00.000Z");
+        // 2-5, 6-8, then an empty page
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1], minute(5) + chrono::Duration::seconds(1));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_cached_bars() {
+        let dir = temp_dir("resume");
+        let (_, first) = downloader(&dir, 100);
+        first
+            .download("BTC/USD", minute(0), minute(5))
+            .await
+            .unwrap();
+
+        let (requests, again) = downloader(&dir, 100);
+        let added = again
+            .download("BTC/USD", minute(0), minute(10))
+            .await
+            .unwrap();
+        assert_eq!(added, 5);
+        assert_eq!(
+            requests.lock().unwrap()[0],
+            minute(4) + chrono::Duration::seconds(1)
+        );
+
+        let bars = read_cache(&again.path("BTC/USD"), "BTC/USD").unwrap();
+        assert_eq!(bars.len(), 10);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,360 @@
+//! Incremental technical indicators.
+//!
+//! Each indicator keeps O(period) state and is updated one sample at a time,
+//! so reading a value never rescans history. Price-based indicators consume
+//! quote mids; ATR and VWAP need high/low/volume and consume bars.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use super::store::Bar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndicatorKind {
+    Sma,
+    Ema,
+    Rsi,
+    /// Standard 12/26 EMAs; `period` is the signal-line length (9 = standard)
+    Macd,
+    Atr,
+    Vwap,
+    /// `period` SMA with bands at 2 standard deviations
+    Bollinger,
+}
+
+impl IndicatorKind {
+    /// Bar-driven indicators need OHLCV; the rest run on quote mids.
+    pub fn uses_bars(self) -> bool {
+        matches!(self, IndicatorKind::Atr | IndicatorKind::Vwap)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum IndicatorValue {
+    Single(f64),
+    Macd {
+        macd: f64,
+        signal: f64,
+        histogram: f64,
+    },
+    Bands {
+        upper: f64,
+        middle: f64,
+        lower: f64,
+    },
+}
+
+impl IndicatorValue {
+    /// Headline number: the value, the MACD line, or the middle band.
+    pub fn value(&self) -> f64 {
+        match *self {
+            IndicatorValue::Single(v) => v,
+            IndicatorValue::Macd { macd, .. } => macd,
+            IndicatorValue::Bands { middle, .. } => middle,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Sma {
+    fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            window: VecDeque::with_capacity(period.max(1)),
+            sum: 0.0,
+            sum_sq: 0.0,
+        }
+    }
+
+    fn update(&mut self, x: f64) {
+        self.window.push_back(x);
+        self.sum += x;
+        self.sum_sq += x * x;
+        if self.window.len() > self.period {
+            if let Some(old) = self.window.pop_front() {
+                self.sum -= old;
+                self.sum_sq -= old * old;
+            }
+        }
+    }
+
+    fn mean(&self) -> Option<f64> {
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+
+    fn std_dev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let var = (self.sum_sq / self.period as f64 - mean * mean).max(0.0);
+        Some(var.sqrt())
+    }
+}
+
+/// EMA seeded with the SMA of the first `period` samples.
+#[derive(Clone, Debug)]
+struct Ema {
+    period: usize,
+    alpha: f64,
+    seed: Vec<f64>,
+    value: Option<f64>,
+}
+
+impl Ema {
+    fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            seed: Vec::with_capacity(period),
+            value: None,
+        }
+    }
+
+    fn update(&mut self, x: f64) {
+        match self.value {
+            Some(v) => self.value = Some(v + self.alpha * (x - v)),
+            None => {
+                self.seed.push(x);
+                if self.seed.len() == self.period {
+                    self.value = Some(self.seed.iter().sum::<f64>() / self.period as f64);
+                    self.seed = Vec::new();
+                }
+            }
+        }
+    }
+}
+
+/// Wilder-smoothed average, seeded with the simple mean of the first `period` samples.
+#[derive(Clone, Debug)]
+struct Wilder {
+    period: usize,
+    count: usize,
+    value: f64,
+}
+
+impl Wilder {
+    fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            count: 0,
+            value: 0.0,
+        }
+    }
+
+    fn update(&mut self, x: f64) {
+        self.count += 1;
+        if self.count <= self.period {
+            self.value += (x - self.value) / self.count as f64;
+        } else {
+            self.value = (self.value * (self.period as f64 - 1.0) + x) / self.period as f64;
+        }
+    }
+
+    fn get(&self) -> Option<f64> {
+        (self.count >= self.period).then_some(self.value)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum State {
+    Sma(Sma),
+    Ema(Ema),
+    Rsi {
+        prev: Option<f64>,
+        gain: Wilder,
+        loss: Wilder,
+    },
+    Macd {
+        fast: Ema,
+        slow: Ema,
+        signal: Ema,
+    },
+    Atr {
+        prev_close: Option<f64>,
+        tr: Wilder,
+    },
+    Vwap {
+        period: usize,
+        window: VecDeque<(f64, f64)>,
+        pv: f64,
+        vol: f64,
+    },
+    Bollinger(Sma),
+}
+
+/// One incrementally-updated indicator.
+#[derive(Clone, Debug)]
+pub struct Indicator {
+    kind: IndicatorKind,
+    state: State,
+}
+
+impl Indicator {
+    pub fn new(kind: IndicatorKind, period: usize) -> Self {
+        let state = match kind {
+            IndicatorKind::Sma => State::Sma(Sma::new(period)),
+            IndicatorKind::Ema => State::Ema(Ema::new(period)),
+            IndicatorKind::Rsi => State::Rsi {
+                prev: None,
+                gain: Wilder::new(period),
+                loss: Wilder::new(period),
+            },
+            IndicatorKind::Macd => State::Macd {
+                fast: Ema::new(12),
+                slow: Ema::new(26),
+                signal: Ema::new(period),
+            },
+            IndicatorKind::Atr => State::Atr {
+                prev_close: None,
+                tr: Wilder::new(period),
+            },
+            IndicatorKind::Vwap => State::Vwap {
+                period: period.max(1),
+                window: VecDeque::with_capacity(period.max(1)),
+                pv: 0.0,
+                vol: 0.0,
+            },
+            IndicatorKind::Bollinger => State::Bollinger(Sma::new(period)),
+        };
+        Self { kind, state }
+    }
+
+    pub fn kind(&self) -> IndicatorKind {
+        self.kind
+    }
+
+    /// Feed a price sample (quote mid). Ignored by bar-driven indicators.
+    pub fn update_price(&mut self, price: f64) {
+        match &mut self.state {
+            State::Sma(sma) | State::Bollinger(sma) => sma.update(price),
+            State::Ema(ema) => ema.update(price),
+            State::Rsi { prev, gain, loss } => {
+                if let Some(p) = *prev {
+                    let change = price - p;
+                    gain.update(change.max(0.0));
+                    loss.update((-change).max(0.0));
+                }
+                *prev = Some(price);
+            }
+            State::Macd { fast, slow, signal } => {
+                fast.update(price);
+                slow.update(price);
+                if let (Some(f), Some(s)) = (fast.value, slow.value) {
+                    signal.update(f - s);
+                }
+            }
+            State::Atr { .. } | State::Vwap { .. } => {}
+        }
+    }
+
+    /// Feed a bar. Price-based indicators ignore bars (they track quote mids).
+    pub fn update_bar(&mut self, bar: &Bar) {
+        match &mut self.state {
+            State::Atr { prev_close, tr } => {
+                let range = match *prev_close {
+                    Some(pc) => (bar.high - bar.low)
+                        .max((bar.high - pc).abs())
+                        .max((bar.low - pc).abs()),
+                    None => bar.high - bar.low,
+                };
+                tr.update(range);
+                *prev_close = Some(bar.close);
+            }
+            State::Vwap {
+                period,
+                window,
+                pv,
+                vol,
+            } => {
+                let typical = (bar.high + bar.low + bar.close) / 3.0;
+                window.push_back((typical * bar.volume, bar.volume));
+                *pv += typical * bar.volume;
+                *vol += bar.volume;
+                if window.len() > *period {
+                    if let Some((old_pv, old_vol)) = window.pop_front() {
+                        *pv -= old_pv;
+                        *vol -= old_vol;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Current value, or None until enough samples have been seen.
+    pub fn value(&self) -> Option<IndicatorValue> {
+        match &self.state {
+            State::Sma(sma) => sma.mean().map(IndicatorValue::Single),
+            State::Ema(ema) => ema.value.map(IndicatorValue::Single),
+            State::Rsi { gain, loss, .. } => {
+                let (g, l) = (gain.get()?, loss.get()?);
+                let rsi = if l == 0.0 {
+                    100.0
+                } else {
+                    100.0 - 100.0 / (1.0 + g / l)
+                };
+                Some(IndicatorValue::Single(rsi))
+            }
+            State::Macd { fast, slow, signal } => {
+                let macd = fast.value? - slow.value?;
+                let signal = signal.value?;
+                Some(IndicatorValue::Macd {
+                    macd,
+                    signal,
+                    histogram: macd - signal,
+                })
+            }
+            State::Atr { tr, .. } => tr.get().map(IndicatorValue::Single),
+            State::Vwap { vol, pv, .. } => (*vol > 0.0).then(|| IndicatorValue::Single(pv / vol)),
+            State::Bollinger(sma) => {
+                let middle = sma.mean()?;
+                let sd = sma.std_dev()?;
+                Some(IndicatorValue::Bands {
+                    upper: middle + 2.0 * sd,
+                    middle,
+                    lower: middle - 2.0 * sd,
+                })
+            }
+        }
+    }
+}
+
+/// All indicators tracked for one symbol, keyed by (kind, period).
+#[derive(Clone, Debug, Default)]
+pub struct IndicatorSet {
+    indicators: HashMap<(IndicatorKind, usize), Indicator>,
+}
+
+impl IndicatorSet {
+    pub fn contains(&self, kind: IndicatorKind, period: usize) -> bool {
+        self.indicators.contains_key(&(kind, period))
+    }
+
+    pub fn insert(&mut self, period: usize, indicator: Indicator) {
+        self.indicators
+            .insert((indicator.kind(), period), indicator);
+    }
+
+    pub fn get(&self, kind: IndicatorKind, period: usize) -> Option<IndicatorValue> {
+        self.indicators.get(&(kind, period))?.value()
+    }
+
+    pub fn update_price(&mut self, price: f64) {
+        for ind in self.indicators.values_mut() {
+            ind.update_price(price);
+        }
+    }
+
+    pub fn update_bar(&mut self, bar: &Bar) {
+        for ind in self.indicators.values_mut() {
+            ind.update_bar(bar);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Unit tests for incremental technical indicators and their MarketStore integration.
+
+#[cfg(test)]
+mod indicators_tests {
+    use crate::data::indicators::*;
+    use crate::data::store::{Bar, MarketStore, Quote};
+
+    fn quote(symbol: &str, mid: f64) -> Quote {
+        Quote {
+            symbol: symbol.to_string(),
+            bid_price: mid - 0.5,
+            ask_price: mid + 0.5,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn bar(high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar {
+            symbol: "BTC/USD".to_string(),
+            open: close,
+            high,
+            low,
+            close,
+            volume,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn single(v: Option<IndicatorValue>) -> f64 {
+        match v {
+            Some(IndicatorValue::Single(x)) => x,
+            other => panic!("expected single value, got {:?}", other),
+        }
+    }
+
+    // ============= Price Indicator Tests =============
+
+    #[test]
+    fn test_sma_rolls_window() {
+        let mut sma = Indicator::new(IndicatorKind::Sma, 3);
+        sma.update_price(1.0);
+        sma.update_price(2.0);
+        assert!(sma.value().is_none());
+        sma.update_price(3.0);
+        assert_eq!(single(sma.value()), 2.0);
+        sma.update_price(10.0);
+        assert_eq!(single(sma.value()), 5.0);
+    }
+
+    #[test]
+    fn test_ema_seeded_with_sma() {
+        let mut ema = Indicator::new(IndicatorKind::Ema, 3);
+        for p in [1.0, 2.0, 3.0] {
+            ema.update_price(p);
+        }
+        assert_eq!(single(ema.value()), 2.0);
+        // alpha = 0.5
+        ema.update_price(4.0);
+        assert_eq!(single(ema.value()), 3.0);
+    }
+
+    #[test]
+    fn test_rsi_extremes() {
+        let mut up = Indicator::new(IndicatorKind::Rsi, 14);
+        let mut down = Indicator::new(IndicatorKind::Rsi, 14);
+        for i in 0..15 {
+            up.update_price(100.0 + i as f64);
+            down.update_price(100.0 - i as f64);
+        }
+        assert_eq!(single(up.value()), 100.0);
+        assert_eq!(single(down.value()), 0.0);
+    }
+
+    #[test]
+    fn test_rsi_balanced_is_fifty() {
+        let mut rsi = Indicator::new(IndicatorKind::Rsi, 4);
+        for p in [100.0, 101.0, 100.0, 101.0, 100.0] {
+            rsi.update_price(p);
+        }
+        assert!((single(rsi.value()) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_macd_flat_series_is_zero() {
+        let mut macd = Indicator::new(IndicatorKind::Macd, 9);
+        for _ in 0..40 {
+            macd.update_price(50.0);
+        }
+        match macd.value() {
+            Some(IndicatorValue::Macd {
+                macd,
+                signal,
+                histogram,
+            }) => {
+                assert_eq!(macd, 0.0);
+                assert_eq!(signal, 0.0);
+                assert_eq!(histogram, 0.0);
+            }
+            other => panic!("expected macd, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_macd_needs_slow_plus_signal_samples() {
+        let mut macd = Indicator::new(IndicatorKind::Macd, 9);
+        for i in 0..33 {
+            macd.update_price(100.0 + i as f64);
+        }
+        assert!(macd.value().is_none());
+        macd.update_price(133.0);
+        assert!(macd.value().unwrap().value() > 0.0);
+    }
+
+    #[test]
+    fn test_bollinger_bands() {
+        let mut bb = Indicator::new(IndicatorKind::Bollinger, 4);
+        for p in [2.0, 4.0, 4.0, 6.0] {
+            bb.update_price(p);
+        }
+        // mean 4, population std dev sqrt(2)
+        match bb.value() {
+            Some(IndicatorValue::Bands {
+                upper,
+                middle,
+                lower,
+            }) => {
+                assert_eq!(middle, 4.0);
+                assert!((upper - (4.0 + 2.0 * 2f64.sqrt())).abs() < 1e-9);
+                assert!((lower - (4.0 - 2.0 * 2f64.sqrt())).abs() < 1e-9);
+            }
+            other => panic!("expected bands, got {:?}", other),
+        }
+    }
+
+    // ============= Bar Indicator Tests =============
+
+    #[test]
+    fn test_atr_uses_true_range() {
+        let mut atr = Indicator::new(IndicatorKind::Atr, 2);
+        atr.update_bar(&bar(11.0, 9.0, 10.0, 1.0)); // TR 2
+        atr.update_bar(&bar(16.0, 14.0, 15.0, 1.0)); // TR max(2, 6, 4) = 6
+        assert_eq!(single(atr.value()), 4.0);
+        // Prices are ignored by bar indicators
+        atr.update_price(1_000.0);
+        assert_eq!(single(atr.value()), 4.0);
+    }
+
+    #[test]
+    fn test_vwap_rolling() {
+        let mut vwap = Indicator::new(IndicatorKind::Vwap, 2);
+        vwap.update_bar(&bar(10.0, 10.0, 10.0, 1.0));
+        vwap.update_bar(&bar(20.0, 20.0, 20.0, 3.0));
+        assert_eq!(single(vwap.value()), 17.5);
+        vwap.update_bar(&bar(30.0, 30.0, 30.0, 1.0));
+        assert_eq!(single(vwap.value()), 22.5);
+    }
+
+    // ============= Store Integration Tests =============
+
+    #[test]
+    fn test_store_seeds_from_history_then_updates() {
+        let store = MarketStore::new(100);
+        for p in [1.0, 2.0, 3.0] {
+            store.update_quote("BTC/USD".to_string(), quote("BTC/USD", p));
+        }
+        assert_eq!(
+            single(store.get_indicator("BTC/USD", IndicatorKind::Sma, 3)),
+            2.0
+        );
+
+        store.update_quote("BTC/USD".to_string(), quote("BTC/USD", 4.0));
+        assert_eq!(
+            single(store.get_indicator("BTC/USD", IndicatorKind::Sma, 3)),
+            3.0
+        );
+    }
+
+    #[test]
+    fn test_store_indicator_isolated_per_symbol() {
+        let store = MarketStore::new(100);
+        assert!(store
+            .get_indicator("ETH/USD", IndicatorKind::Sma, 1)
+            .is_none());
+        store.update_quote("BTC/USD".to_string(), quote("BTC/USD", 5.0));
+        store.update_quote("ETH/USD".to_string(), quote("ETH/USD", 7.0));
+        assert_eq!(
+            single(store.get_indicator("ETH/USD", IndicatorKind::Sma, 1)),
+            7.0
+        );
+    }
+
+    #[test]
+    fn test_store_bar_indicator() {
+        let store = MarketStore::new(100);
+        store.update_bar("BTC/USD".to_string(), bar(10.0, 10.0, 10.0, 2.0));
+        assert_eq!(
+            single(store.get_indicator("BTC/USD", IndicatorKind::Vwap, 5)),
+            10.0
+        );
+        store.update_bar("BTC/USD".to_string(), bar(20.0, 20.0, 20.0, 2.0));
+        assert_eq!(
+            single(store.get_indicator("BTC/USD", IndicatorKind::Vwap, 5)),
+            15.0
+        );
+    }
+
+    #[test]
+    fn test_store_seed_races_no_quote() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let store = Arc::new(MarketStore::new(10_000));
+        let writer = {
+            let store = Arc::clone(&store);
+            thread::spawn(move || {
+                for i in 1..=5_000 {
+                    store.update_quote("BTC/USD".to_string(), quote("BTC/USD", i as f64));
+                }
+            })
+        };
+        // Each EMA is seeded while quotes arrive; one skipped would show forever
+        for period in 2..=40 {
+            store.get_indicator("BTC/USD", IndicatorKind::Ema, period);
+            thread::yield_now();
+        }
+        writer.join().unwrap();
+
+        for period in 2..=40 {
+            let mut expected = Indicator::new(IndicatorKind::Ema, period);
+            (1..=5_000).for_each(|i| expected.update_price(i as f64));
+            assert_eq!(
+                store.get_indicator("BTC/USD", IndicatorKind::Ema, period),
+                expected.value()
+            );
+        }
+    }
+}
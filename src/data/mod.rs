@@ -1,5 +1,21 @@
 pub mod alpaca;
+pub mod correlation;
+pub mod data_quality;
+pub mod downloader;
+pub mod indicators;
+pub mod ring;
 pub mod store;
+pub mod tick_filter;
 
+#[cfg(test)]
+mod correlation_tests;
+#[cfg(test)]
+mod data_quality_tests;
+#[cfg(test)]
+mod downloader_tests;
+#[cfg(test)]
+mod indicators_tests;
 #[cfg(test)]
 mod store_tests;
+#[cfg(test)]
+mod tick_filter_tests;
@@ -0,0 +1,66 @@
+//! Fixed-capacity history ring used by `MarketStore`.
+//!
+//! The backing vector is allocated once; when full, a push overwrites the
+//! oldest element in place. Readers get the length, the latest element or
+//! the last `n` elements without copying the rest of the history.
+
+#[derive(Clone, Debug)]
+pub struct Ring<T> {
+    buf: Vec<T>,
+    /// Index of the oldest element once the ring is full
+    head: usize,
+    capacity: usize,
+}
+
+impl<T> Ring<T> {
+    /// A ring holding at most `capacity` elements (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            buf: Vec::with_capacity(capacity),
+            head: 0,
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.buf.len() < self.capacity {
+            self.buf.push(item);
+        } else {
+            self.buf[self.head] = item;
+            self.head = (self.head + 1) % self.capacity;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The most recently pushed element.
+    pub fn latest(&self) -> Option<&T> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        self.buf
+            .get((self.head + self.buf.len() - 1) % self.buf.len())
+    }
+
+    /// Oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        let (newer, older) = self.buf.split_at(self.head);
+        older.iter().chain(newer.iter())
+    }
+
+    /// The last `n` elements (fewer if the ring holds fewer), oldest first.
+    pub fn window(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter().skip(self.buf.len().saturating_sub(n))
+    }
+}
@@ -1,9 +1,15 @@
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
+use super::data_quality::DataQualityMonitor;
+use super::indicators::{Indicator, IndicatorKind, IndicatorSet, IndicatorValue};
+use super::ring::Ring;
+use super::tick_filter::{TickFilter, TickReject};
+use crate::config::AppConfig;
+use crate::events::MarketRegime;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Quote {
     #[serde(rename = "S")]
@@ -52,12 +58,45 @@ pub struct Bar {
     pub timestamp: String,
 }
 
+/// Latest news sentiment for a symbol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sentiment {
+    pub symbol: String,
+    /// -1.0 (bearish) .. 1.0 (bullish)
+    pub score: f64,
+    pub headline: String,
+    /// When the item was scored (RFC3339)
+    pub timestamp: String,
+}
+
+/// HFT entry thresholds of a symbol, as tuned by `AdaptiveThresholdService`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HftThresholds {
+    pub min_edge_bps: f64,
+    pub max_spread_bps: f64,
+}
+
 #[derive(Clone, Debug)]
 pub struct MarketStore {
-    pub historical_bars: Arc<DashMap<String, VecDeque<Bar>>>,
-    pub historical_trades: Arc<DashMap<String, VecDeque<Trade>>>, // Use DashMap for concurrent access
-    pub historical_quotes: Arc<DashMap<String, VecDeque<Quote>>>, // Use DashMap for concurrent access
+    /// Per-symbol rings of the last `limit` bars / trades / quotes. Hot paths
+    /// should use the count / latest / window accessors: the `get_*_history`
+    /// getters copy the whole ring.
+    pub historical_bars: Arc<DashMap<String, Ring<Bar>>>,
+    pub historical_trades: Arc<DashMap<String, Ring<Trade>>>,
+    pub historical_quotes: Arc<DashMap<String, Ring<Quote>>>,
     pub news: Arc<Mutex<Vec<Value>>>,
+    pub indicators: Arc<DashMap<String, IndicatorSet>>,
+    pub sentiment: Arc<DashMap<String, Sentiment>>,
+    /// Latest classified regime per symbol (`RegimeService`)
+    pub regimes: Arc<DashMap<String, MarketRegime>>,
+    /// Adapted HFT thresholds per symbol (unset = `hft.*` as configured)
+    pub hft_thresholds: Arc<DashMap<String, HftThresholds>>,
+    /// Fallback FX rates: units of reporting currency per unit of the key
+    pub fx_rates: Arc<DashMap<String, f64>>,
+    /// Bad-tick filter applied by `accept_quote` (off until configured)
+    pub tick_filter: TickFilter,
+    /// Feed quality of quotes stored by `accept_quote` (off until configured)
+    pub data_quality: DataQualityMonitor,
     pub limit: usize,
 }
 
@@ -68,41 +107,57 @@ impl MarketStore {
             historical_trades: Arc::new(DashMap::new()),
             historical_quotes: Arc::new(DashMap::new()),
             news: Arc::new(Mutex::new(Vec::new())),
+            indicators: Arc::new(DashMap::new()),
+            sentiment: Arc::new(DashMap::new()),
+            regimes: Arc::new(DashMap::new()),
+            hft_thresholds: Arc::new(DashMap::new()),
+            fx_rates: Arc::new(DashMap::new()),
+            tick_filter: TickFilter::default(),
+            data_quality: DataQualityMonitor::default(),
             limit,
         }
     }
 
     pub fn update_bar(&self, symbol: String, bar: Bar) {
-        let mut queue = self
-            .historical_bars
+        // Held until the bar is stored, so `get_indicator` seeds either
+        // before it (and gets the update) or after it (and reads it)
+        let mut set = self.indicators.entry(symbol.clone()).or_default();
+        set.update_bar(&bar);
+        self.historical_bars
             .entry(symbol)
-            .or_insert_with(VecDeque::new);
-        if queue.len() >= self.limit {
-            queue.pop_front();
-        }
-        queue.push_back(bar);
+            .or_insert_with(|| Ring::new(self.limit))
+            .push(bar);
     }
 
     pub fn update_trade(&self, symbol: String, trade: Trade) {
-        let mut queue = self
-            .historical_trades
+        self.historical_trades
             .entry(symbol)
-            .or_insert_with(VecDeque::new);
-        if queue.len() >= self.limit {
-            queue.pop_front();
-        }
-        queue.push_back(trade);
+            .or_insert_with(|| Ring::new(self.limit))
+            .push(trade);
+    }
+
+    /// Store a live quote unless the tick filter drops it. Callers should not
+    /// publish rejected quotes either.
+    pub fn accept_quote(&self, symbol: String, quote: Quote) -> Result<(), TickReject> {
+        self.tick_filter
+            .check(&symbol, quote.bid_price, quote.ask_price)?;
+        self.data_quality
+            .record(&symbol, quote.bid_price, quote.ask_price);
+        self.update_quote(symbol, quote);
+        Ok(())
     }
 
     pub fn update_quote(&self, symbol: String, quote: Quote) {
-        let mut queue = self
-            .historical_quotes
-            .entry(symbol)
-            .or_insert_with(VecDeque::new);
-        if queue.len() >= self.limit {
-            queue.pop_front();
+        let mid = (quote.bid_price + quote.ask_price) / 2.0;
+        // Held until the quote is stored (see `update_bar`)
+        let mut set = self.indicators.entry(symbol.clone()).or_default();
+        if mid > 0.0 {
+            set.update_price(mid);
         }
-        queue.push_back(quote);
+        self.historical_quotes
+            .entry(symbol)
+            .or_insert_with(|| Ring::new(self.limit))
+            .push(quote);
     }
 
     pub fn add_news(&self, news_item: Value) {
@@ -116,7 +171,7 @@ impl MarketStore {
     pub fn get_latest_bar(&self, symbol: &str) -> Option<Bar> {
         self.historical_bars
             .get(symbol)
-            .and_then(|q| q.back().cloned())
+            .and_then(|r| r.latest().cloned())
     }
 
     pub fn get_bar_history(&self, symbol: &str) -> Vec<Bar> {
@@ -148,14 +203,122 @@ impl MarketStore {
         }
     }
 
+    /// Stored quotes for `symbol`, without copying them.
+    pub fn quote_count(&self, symbol: &str) -> usize {
+        self.historical_quotes.get(symbol).map_or(0, |r| r.len())
+    }
+
+    /// The last `n` quotes for `symbol`, oldest first.
+    pub fn quote_window(&self, symbol: &str, n: usize) -> Vec<Quote> {
+        self.historical_quotes
+            .get(symbol)
+            .map(|r| r.window(n).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Current value of an indicator for `symbol`. The first request for a
+    /// (kind, period) seeds it from stored history; afterwards it is updated
+    /// incrementally by `update_quote` / `update_bar`.
+    pub fn get_indicator(
+        &self,
+        symbol: &str,
+        kind: IndicatorKind,
+        period: usize,
+    ) -> Option<IndicatorValue> {
+        if let Some(set) = self.indicators.get(symbol) {
+            if set.contains(kind, period) {
+                return set.get(kind, period);
+            }
+        }
+
+        // Seeded under the entry, so no update lands between the seed and the insert
+        let mut set = self.indicators.entry(symbol.to_string()).or_default();
+        if set.contains(kind, period) {
+            return set.get(kind, period);
+        }
+        let mut indicator = Indicator::new(kind, period);
+        if kind.uses_bars() {
+            if let Some(bars) = self.historical_bars.get(symbol) {
+                bars.iter().for_each(|bar| indicator.update_bar(bar));
+            }
+        } else if let Some(quotes) = self.historical_quotes.get(symbol) {
+            for q in quotes.iter() {
+                let mid = (q.bid_price + q.ask_price) / 2.0;
+                if mid > 0.0 {
+                    indicator.update_price(mid);
+                }
+            }
+        }
+        let value = indicator.value();
+        set.insert(period, indicator);
+        value
+    }
+
     pub fn get_latest_quote(&self, symbol: &str) -> Option<Quote> {
         self.historical_quotes
             .get(symbol)
-            .and_then(|q| q.back().cloned())
+            .and_then(|r| r.latest().cloned())
     }
 
     pub fn get_latest_news(&self) -> Vec<Value> {
         let news = self.news.lock().unwrap();
         news.clone()
     }
+
+    /// Record the latest sentiment for `sentiment.symbol`, replacing the previous one.
+    pub fn update_sentiment(&self, sentiment: Sentiment) {
+        self.sentiment.insert(sentiment.symbol.clone(), sentiment);
+    }
+
+    pub fn get_sentiment(&self, symbol: &str) -> Option<Sentiment> {
+        self.sentiment.get(symbol).map(|s| s.clone())
+    }
+
+    pub fn set_regime(&self, symbol: &str, regime: MarketRegime) {
+        self.regimes.insert(symbol.to_string(), regime);
+    }
+
+    pub fn get_regime(&self, symbol: &str) -> Option<MarketRegime> {
+        self.regimes.get(symbol).map(|r| *r)
+    }
+
+    pub fn set_hft_thresholds(&self, symbol: &str, thresholds: HftThresholds) {
+        self.hft_thresholds.insert(symbol.to_string(), thresholds);
+    }
+
+    pub fn get_hft_thresholds(&self, symbol: &str) -> Option<HftThresholds> {
+        self.hft_thresholds.get(symbol).map(|t| *t)
+    }
+
+    /// Set the fallback rate of `currency` (units of reporting currency per unit).
+    pub fn set_fx_rate(&self, currency: &str, rate: f64) {
+        self.fx_rates.insert(currency.to_uppercase(), rate);
+    }
+
+    /// Units of `reporting` per unit of `currency`: 1 for the same currency,
+    /// else the mid of the latest `<currency>/<reporting>` quote (or the
+    /// inverse of `<reporting>/<currency>`), else the fallback rate.
+    pub fn fx_rate(&self, currency: &str, reporting: &str) -> Option<f64> {
+        let (currency, reporting) = (currency.to_uppercase(), reporting.to_uppercase());
+        if currency == reporting {
+            return Some(1.0);
+        }
+        let mid = |symbol: String| {
+            self.get_latest_quote(&symbol)
+                .filter(|q| q.bid_price > 0.0 && q.ask_price > 0.0)
+                .map(|q| (q.bid_price + q.ask_price) / 2.0)
+        };
+        mid(format!("{}/{}", currency, reporting))
+            .or_else(|| mid(format!("{}/{}", reporting, currency)).map(|m| 1.0 / m))
+            .or_else(|| self.fx_rates.get(&currency).map(|r| *r))
+            .filter(|r| r.is_finite() && *r > 0.0)
+    }
+
+    /// Units of the reporting currency per unit of `symbol`'s quote currency.
+    pub fn quote_fx_rate(&self, config: &AppConfig, symbol: &str) -> Option<f64> {
+        self.fx_rate(
+            &config.quote_currency_for(symbol),
+            &config.fx.reporting_currency,
+        )
+    }
 }
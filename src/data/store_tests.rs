@@ -2,7 +2,8 @@
 
 #[cfg(test)]
 mod store_tests {
-    use crate::data::store::{Bar, MarketStore, Quote, Trade};
+    use crate::data::ring::Ring;
+    use crate::data::store::{Bar, MarketStore, Quote, Sentiment, Trade};
 
     #[test]
     fn test_market_store_new() {
@@ -77,6 +78,51 @@ mod store_tests {
         assert_eq!(history[2].bid_price, 104.0);
     }
 
+    #[test]
+    fn test_ring_overwrites_oldest_in_order() {
+        let mut ring = Ring::new(3);
+        assert!(ring.latest().is_none());
+        for i in 0..7 {
+            ring.push(i);
+        }
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.latest(), Some(&6));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(ring.window(2).copied().collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(ring.window(10).count(), 3);
+        // A zero limit still keeps the latest element
+        let mut ring = Ring::new(0);
+        ring.push(1);
+        ring.push(2);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_quote_count_and_window() {
+        let store = MarketStore::new(4);
+        assert_eq!(store.quote_count("ADA/USD"), 0);
+        assert!(store.quote_window("ADA/USD", 2).is_empty());
+
+        for i in 0..6 {
+            let quote = Quote {
+                symbol: "ADA/USD".to_string(),
+                bid_price: 1.0 + i as f64,
+                ask_price: 1.5 + i as f64,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                timestamp: format!("2025-01-01T00:00:0{}Z", i),
+            };
+            store.update_quote("ADA/USD".to_string(), quote);
+        }
+
+        assert_eq!(store.quote_count("ADA/USD"), 4);
+        let window = store.quote_window("ADA/USD", 2);
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].bid_price, 5.0);
+        assert_eq!(window[1].bid_price, 6.0);
+        assert_eq!(store.get_latest_quote("ADA/USD").unwrap().bid_price, 6.0);
+    }
+
     #[test]
     fn test_update_and_get_trade() {
         let store = MarketStore::new(100);
@@ -255,6 +301,26 @@ mod store_tests {
         assert_eq!(news[2]["headline"], "News 4");
     }
 
+    #[test]
+    fn test_sentiment_latest_wins() {
+        let store = MarketStore::new(10);
+        assert!(store.get_sentiment("BTC/USD").is_none());
+
+        for (score, headline) in [(0.4, "ETF inflows"), (-0.7, "Exchange hacked")] {
+            store.update_sentiment(Sentiment {
+                symbol: "BTC/USD".to_string(),
+                score,
+                headline: headline.to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+            });
+        }
+
+        let latest = store.get_sentiment("BTC/USD").unwrap();
+        assert_eq!(latest.score, -0.7);
+        assert_eq!(latest.headline, "Exchange hacked");
+        assert!(store.get_sentiment("ETH/USD").is_none());
+    }
+
     #[test]
     fn test_concurrent_access() {
         use std::sync::Arc;
@@ -292,4 +358,46 @@ mod store_tests {
             assert_eq!(history.len(), 100);
         }
     }
+
+    fn fx_quote(symbol: &str, bid: f64, ask: f64) -> Quote {
+        Quote {
+            symbol: symbol.to_string(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fx_rate_same_currency_is_one() {
+        let store = MarketStore::new(100);
+        assert_eq!(store.fx_rate("usd", "USD"), Some(1.0));
+        assert_eq!(store.fx_rate("EUR", "USD"), None);
+    }
+
+    #[test]
+    fn test_fx_rate_prefers_live_quote_over_fallback() {
+        let store = MarketStore::new(100);
+        store.set_fx_rate("btc", 60000.0);
+        assert_eq!(store.fx_rate("BTC", "USD"), Some(60000.0));
+
+        store.update_quote("BTC/USD".to_string(), fx_quote("BTC/USD", 49990.0, 50010.0));
+        assert_eq!(store.fx_rate("BTC", "USD"), Some(50000.0));
+    }
+
+    #[test]
+    fn test_fx_rate_inverts_reverse_pair() {
+        let store = MarketStore::new(100);
+        store.update_quote("EUR/USD".to_string(), fx_quote("EUR/USD", 1.24, 1.26));
+        assert_eq!(store.fx_rate("USD", "EUR"), Some(0.8));
+    }
+
+    #[test]
+    fn test_fx_rate_rejects_non_positive_fallback() {
+        let store = MarketStore::new(100);
+        store.set_fx_rate("EUR", 0.0);
+        assert_eq!(store.fx_rate("EUR", "USD"), None);
+    }
 }
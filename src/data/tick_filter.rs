@@ -0,0 +1,176 @@
+//! Bad-tick filter in front of `MarketStore` quotes (`tick_filter.*`).
+//!
+//! Thin pairs occasionally print a quote far from the market (a bid 90%
+//! below the last one), which would trip stop-losses or fake an HFT edge.
+//! Each quote is checked before it is stored or published:
+//! - a zero, negative or non-finite bid/ask, or a crossed book, is dropped
+//! - once `min_samples` mids are known, a mid further than
+//!   `max_deviation_pct` from the median of the last `window` accepted mids
+//!   is dropped
+//!
+//! `reset_after` outliers in a row are taken as a real move: the next one is
+//! accepted and the median restarts from it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tracing::{debug, warn};
+
+use super::ring::Ring;
+use crate::config::TickFilterConfig;
+
+/// Why a quote was dropped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TickReject {
+    /// Bid or ask is zero, negative or not a number
+    BadPrice,
+    /// Bid above ask
+    Crossed,
+    /// Mid too far from the rolling median
+    Outlier { deviation_pct: f64 },
+}
+
+impl std::fmt::Display for TickReject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TickReject::BadPrice => write!(f, "zero or invalid price"),
+            TickReject::Crossed => write!(f, "crossed bid/ask"),
+            TickReject::Outlier { deviation_pct } => {
+                write!(f, "{:.2}% from the rolling median", deviation_pct)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SymbolTicks {
+    mids: Ring<f64>,
+    /// Reused for the median so the hot path does not allocate
+    scratch: Vec<f64>,
+    outliers_in_row: u32,
+    rejected: u64,
+}
+
+impl SymbolTicks {
+    fn new(window: usize) -> Self {
+        Self {
+            mids: Ring::new(window),
+            scratch: Vec::with_capacity(window),
+            outliers_in_row: 0,
+            rejected: 0,
+        }
+    }
+
+    fn median(&mut self) -> f64 {
+        self.scratch.clear();
+        self.scratch.extend(self.mids.iter().copied());
+        let mid = self.scratch.len() / 2;
+        *self.scratch.select_nth_unstable_by(mid, f64::total_cmp).1
+    }
+}
+
+#[derive(Debug, Default)]
+struct FilterState {
+    /// Disabled until `configure`
+    config: Option<TickFilterConfig>,
+    symbols: HashMap<String, SymbolTicks>,
+    rejected_total: u64,
+}
+
+/// Per-symbol tick validation, shared by every clone of the store.
+#[derive(Clone, Debug, Default)]
+pub struct TickFilter {
+    state: Arc<Mutex<FilterState>>,
+}
+
+impl TickFilter {
+    /// Apply `config`; symbol history restarts.
+    pub fn configure(&self, config: &TickFilterConfig) {
+        let mut state = self.state.lock().unwrap();
+        state.config = config.enabled.then(|| config.clone());
+        state.symbols.clear();
+    }
+
+    /// Accept or reject a quote for `symbol`. Rejections are counted.
+    pub fn check(&self, symbol: &str, bid: f64, ask: f64) -> Result<(), TickReject> {
+        let mut state = self.state.lock().unwrap();
+        let Some(config) = state.config.clone() else {
+            return Ok(());
+        };
+        let result = Self::validate(&mut state, &config, symbol, bid, ask);
+        if let Err(reason) = result {
+            state.rejected_total += 1;
+            let ticks = state
+                .symbols
+                .entry(symbol.to_string())
+                .or_insert_with(|| SymbolTicks::new(config.window));
+            ticks.rejected += 1;
+            match reason {
+                TickReject::Outlier { .. } => warn!(
+                    "🚫 [TICK] Dropped {} quote bid={} ask={}: {}",
+                    symbol, bid, ask, reason
+                ),
+                _ => debug!(
+                    "🚫 [TICK] Dropped {} quote bid={} ask={}: {}",
+                    symbol, bid, ask, reason
+                ),
+            }
+        }
+        result
+    }
+
+    fn validate(
+        state: &mut FilterState,
+        config: &TickFilterConfig,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+    ) -> Result<(), TickReject> {
+        if !(bid.is_finite() && ask.is_finite() && bid > 0.0 && ask > 0.0) {
+            return Err(TickReject::BadPrice);
+        }
+        if bid > ask {
+            return Err(TickReject::Crossed);
+        }
+        let mid = (bid + ask) / 2.0;
+        if !state.symbols.contains_key(symbol) {
+            state
+                .symbols
+                .insert(symbol.to_string(), SymbolTicks::new(config.window));
+        }
+        let ticks = state.symbols.get_mut(symbol).unwrap();
+
+        if ticks.mids.len() >= config.min_samples.max(1) {
+            let median = ticks.median();
+            let deviation_pct = ((mid - median) / median).abs() * 100.0;
+            if deviation_pct > config.max_deviation_pct {
+                ticks.outliers_in_row += 1;
+                if config.reset_after == 0 || ticks.outliers_in_row <= config.reset_after {
+                    return Err(TickReject::Outlier { deviation_pct });
+                }
+                // The market really moved: start over from here
+                ticks.mids = Ring::new(config.window);
+            }
+        }
+        ticks.outliers_in_row = 0;
+        ticks.mids.push(mid);
+        Ok(())
+    }
+
+    /// Quotes dropped since start, all symbols.
+    pub fn rejected_total(&self) -> u64 {
+        self.state.lock().unwrap().rejected_total
+    }
+
+    /// Quotes dropped per symbol (symbols without rejections omitted).
+    pub fn rejected(&self) -> HashMap<String, u64> {
+        self.state
+            .lock()
+            .unwrap()
+            .symbols
+            .iter()
+            .filter(|(_, t)| t.rejected > 0)
+            .map(|(symbol, t)| (symbol.clone(), t.rejected))
+            .collect()
+    }
+}
@@ -0,0 +1,116 @@
+//! Unit tests for the bad-tick filter: invalid and crossed quotes, median
+//! outliers and the reset after a sustained move.
+
+#[cfg(test)]
+mod tick_filter_tests {
+    use crate::config::TickFilterConfig;
+    use crate::data::store::{MarketStore, Quote};
+    use crate::data::tick_filter::*;
+
+    fn filter(min_samples: usize, reset_after: u32) -> TickFilter {
+        let filter = TickFilter::default();
+        filter.configure(&TickFilterConfig {
+            enabled: true,
+            max_deviation_pct: 10.0,
+            window: 20,
+            min_samples,
+            reset_after,
+        });
+        filter
+    }
+
+    fn quote(bid: f64, ask: f64) -> Quote {
+        Quote {
+            symbol: "BTC/USD".to_string(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: String::new(),
+        }
+    }
+
+    // ============= Validation Tests =============
+
+    #[test]
+    fn test_unconfigured_filter_accepts_everything() {
+        let filter = TickFilter::default();
+        assert_eq!(filter.check("BTC/USD", 0.0, 0.0), Ok(()));
+        assert_eq!(filter.check("BTC/USD", 101.0, 100.0), Ok(()));
+        assert_eq!(filter.rejected_total(), 0);
+    }
+
+    #[test]
+    fn test_rejects_zero_and_crossed_quotes() {
+        let filter = filter(1, 3);
+        assert_eq!(
+            filter.check("BTC/USD", 0.0, 100.0),
+            Err(TickReject::BadPrice)
+        );
+        assert_eq!(
+            filter.check("BTC/USD", 100.0, f64::NAN),
+            Err(TickReject::BadPrice)
+        );
+        assert_eq!(
+            filter.check("BTC/USD", 101.0, 100.0),
+            Err(TickReject::Crossed)
+        );
+        // A locked book is fine
+        assert_eq!(filter.check("BTC/USD", 100.0, 100.0), Ok(()));
+        assert_eq!(filter.rejected_total(), 3);
+        assert_eq!(filter.rejected()["BTC/USD"], 3);
+    }
+
+    #[test]
+    fn test_rejects_outliers_after_min_samples() {
+        let filter = filter(3, 3);
+        // Not enough history to judge the first jump
+        for mid in [100.0, 101.0, 150.0] {
+            assert!(filter.check("ETH/USD", mid - 0.5, mid + 0.5).is_ok());
+        }
+        // Median is 101: 89 (-11.9%) is out, 95 (-5.9%) is in
+        match filter.check("ETH/USD", 88.5, 89.5) {
+            Err(TickReject::Outlier { deviation_pct }) => {
+                assert!((deviation_pct - 11.88).abs() < 0.01)
+            }
+            other => panic!("expected outlier, got {:?}", other),
+        }
+        assert!(filter.check("ETH/USD", 94.5, 95.5).is_ok());
+        assert_eq!(filter.rejected().get("ETH/USD"), Some(&1));
+        assert!(!filter.rejected().contains_key("BTC/USD"));
+    }
+
+    #[test]
+    fn test_sustained_move_resets_median() {
+        let filter = filter(2, 2);
+        for _ in 0..5 {
+            assert!(filter.check("SOL/USD", 99.0, 101.0).is_ok());
+        }
+        // The first `reset_after` jumps are dropped, the next one is taken
+        assert!(filter.check("SOL/USD", 149.0, 151.0).is_err());
+        assert!(filter.check("SOL/USD", 149.0, 151.0).is_err());
+        assert!(filter.check("SOL/USD", 149.0, 151.0).is_ok());
+        // ...and the new level is the reference from then on
+        assert!(filter.check("SOL/USD", 150.0, 152.0).is_ok());
+        assert!(filter.check("SOL/USD", 99.0, 101.0).is_err());
+        assert_eq!(filter.rejected_total(), 3);
+    }
+
+    // ============= Store Tests =============
+
+    #[test]
+    fn test_store_accept_quote_skips_rejected() {
+        let store = MarketStore::new(10);
+        store.tick_filter.configure(&TickFilterConfig::default());
+        assert!(store
+            .accept_quote("BTC/USD".to_string(), quote(100.0, 101.0))
+            .is_ok());
+        assert_eq!(
+            store.accept_quote("BTC/USD".to_string(), quote(102.0, 101.0)),
+            Err(TickReject::Crossed)
+        );
+        let latest = store.get_latest_quote("BTC/USD").unwrap();
+        assert_eq!(latest.bid_price, 100.0);
+        assert_eq!(store.tick_filter.rejected_total(), 1);
+    }
+}
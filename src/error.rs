@@ -4,135 +4,167 @@
 
 use thiserror::Error;
 
-/// Top-level trading system errors
+/// Crate-wide result type.
+pub type Result<T> = std::result::Result<T, AutoHedgeError>;
+
+/// Errors crossing module boundaries (exchange REST, LLM, market data streams).
+///
+/// Messages are kept verbatim so logs and text-based classifiers (halt
+/// detection, insufficient-balance handling) see the same strings as before;
+/// callers decide retry vs abort with `is_retryable()`.
 #[derive(Error, Debug)]
-pub enum TradingError {
-    #[error("Insufficient balance for {symbol}: requested {requested}, available {available}")]
-    InsufficientBalance {
-        symbol: String,
-        requested: f64,
-        available: f64,
+pub enum AutoHedgeError {
+    /// Venue REST failure. `retryable` is set for transport errors, timeouts,
+    /// throttling (429, Binance 418) and 5xx; rejections and decode failures are final.
+    #[error("{message}")]
+    Exchange {
+        message: String,
+        status: Option<u16>,
+        retryable: bool,
     },
 
-    #[error("Rate limited for {symbol} (cooldown: {cooldown_ms}ms)")]
-    RateLimited { symbol: String, cooldown_ms: u64 },
+    /// The adapter does not implement this operation (raised locally, never by the venue).
+    #[error("{0}")]
+    Unsupported(String),
 
-    #[error("Position not found: {symbol}")]
-    PositionNotFound { symbol: String },
+    #[error("LLM error: {0}")]
+    Llm(String),
 
-    #[error("Invalid quantity {qty} for {symbol}")]
-    InvalidQuantity { symbol: String, qty: f64 },
-
-    #[error("Invalid price {price} for {symbol}")]
-    InvalidPrice { symbol: String, price: f64 },
-
-    #[error("Order not found: {order_id}")]
-    OrderNotFound { order_id: String },
-
-    #[error("Pending order already exists for {symbol}")]
-    PendingOrderExists { symbol: String },
-
-    #[error("Exchange API error: {0}")]
-    Exchange(#[from] ExchangeError),
-
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+    /// LLM failure worth retrying: transport errors, timeouts, rate limits and
+    /// server errors. Displays like `Llm` so logs read the same.
+    #[error("LLM error: {0}")]
+    LlmTransient(String),
 
     #[error("Configuration error: {0}")]
     Config(String),
 
-    #[error("Parse error: {0}")]
-    Parse(String),
-}
-
-/// Exchange-specific errors
-#[derive(Error, Debug)]
-pub enum ExchangeError {
-    #[error("HTTP {status}: {body}")]
-    Http { status: u16, body: String },
-
-    #[error("Order rejected: {reason}")]
-    OrderRejected { reason: String },
-
-    #[error("Authentication failed: {reason}")]
-    AuthFailed { reason: String },
-
-    #[error("Invalid symbol: {symbol}")]
-    InvalidSymbol { symbol: String },
+    #[error("WebSocket error: {0}")]
+    Ws(String),
 
-    #[error("Market closed for {symbol}")]
-    MarketClosed { symbol: String },
+    /// Local file or encoding failure (trade/event logs, bar caches, audit
+    /// trails, reports).
+    #[error("{0}")]
+    Io(String),
+}
 
-    #[error("Order size too small: {symbol} (min: {min})")]
-    OrderTooSmall { symbol: String, min: f64 },
+impl AutoHedgeError {
+    /// Non-retryable venue error without an HTTP status (rejections, decode failures).
+    pub fn exchange(message: impl Into<String>) -> Self {
+        Self::Exchange {
+            message: message.into(),
+            status: None,
+            retryable: false,
+        }
+    }
 
-    #[error("Order size too large: {symbol} (max: {max})")]
-    OrderTooLarge { symbol: String, max: f64 },
+    /// Venue answered with a non-success HTTP status.
+    pub fn http(status: u16, message: impl Into<String>) -> Self {
+        Self::Exchange {
+            message: message.into(),
+            status: Some(status),
+            retryable: matches!(status, 408 | 418 | 429) || status >= 500,
+        }
+    }
 
-    #[error("WebSocket error: {0}")]
-    WebSocket(String),
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Exchange { retryable, .. } => *retryable,
+            Self::Ws(_) | Self::LlmTransient(_) => true,
+            Self::Unsupported(_) | Self::Llm(_) | Self::Config(_) | Self::Io(_) => false,
+        }
+    }
 
-    #[error("Deserialization error: {0}")]
-    Deserialization(#[from] serde_json::Error),
+    /// HTTP status of a venue error, if the venue answered.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Exchange { status, .. } => *status,
+            _ => None,
+        }
+    }
 }
 
-/// Position tracker errors
-#[derive(Error, Debug)]
-pub enum TrackerError {
-    #[error("Position not found: {symbol}")]
-    PositionNotFound { symbol: String },
+impl From<reqwest::Error> for AutoHedgeError {
+    fn from(err: reqwest::Error) -> Self {
+        let status = err.status().map(|s| s.as_u16());
+        let retryable = err.is_timeout()
+            || err.is_connect()
+            || err.is_request()
+            || status.is_some_and(|s| matches!(s, 418 | 429) || s >= 500);
+        Self::Exchange {
+            message: err.to_string(),
+            status,
+            retryable,
+        }
+    }
+}
 
-    #[error("Order not found: {order_id}")]
-    OrderNotFound { order_id: String },
+impl From<serde_json::Error> for AutoHedgeError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::exchange(err.to_string())
+    }
+}
 
-    #[error("Position already exists: {symbol}")]
-    PositionExists { symbol: String },
+impl From<std::io::Error> for AutoHedgeError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
 }
 
-/// Strategy-related errors
-#[derive(Error, Debug)]
-pub enum StrategyError {
-    #[error("Invalid quote: bid={bid}, ask={ask}")]
-    InvalidQuote { bid: f64, ask: f64 },
-
-    #[error("Spread too wide for {symbol}: {spread_bps} bps (max: {max_spread_bps})")]
-    SpreadTooWide {
-        symbol: String,
-        spread_bps: f64,
-        max_spread_bps: f64,
-    },
+impl From<csv::Error> for AutoHedgeError {
+    fn from(err: csv::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
 
-    #[error("Insufficient edge for {symbol}: {edge_bps} bps (min: {min_edge_bps})")]
-    InsufficientEdge {
-        symbol: String,
-        edge_bps: f64,
-        min_edge_bps: f64,
-    },
+impl From<rmp_serde::encode::Error> for AutoHedgeError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
 
-    #[error("Not enough data for {symbol}: have {count}, need {required}")]
-    InsufficientData {
-        symbol: String,
-        count: usize,
-        required: usize,
-    },
+impl From<rmp_serde::decode::Error> for AutoHedgeError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        Self::Io(err.to_string())
+    }
 }
 
-/// Conversion helpers for legacy code
-impl From<Box<dyn std::error::Error + Send + Sync>> for TradingError {
-    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        TradingError::Parse(err.to_string())
+/// Cron jobs fail to build on a bad schedule expression.
+impl From<tokio_cron_scheduler::JobSchedulerError> for AutoHedgeError {
+    fn from(err: tokio_cron_scheduler::JobSchedulerError) -> Self {
+        Self::Config(err.to_string())
     }
 }
 
-impl From<String> for TradingError {
-    fn from(err: String) -> Self {
-        TradingError::Config(err)
+impl From<tokio_tungstenite::tungstenite::Error> for AutoHedgeError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::Ws(err.to_string())
     }
 }
 
-impl From<&str> for TradingError {
-    fn from(err: &str) -> Self {
-        TradingError::Config(err.to_string())
+impl From<async_openai::error::OpenAIError> for AutoHedgeError {
+    fn from(err: async_openai::error::OpenAIError) -> Self {
+        use async_openai::error::OpenAIError;
+
+        let transient = match &err {
+            OpenAIError::Reqwest(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.is_request()
+                    || e.status()
+                        .is_some_and(|s| s.as_u16() == 429 || s.is_server_error())
+            }
+            OpenAIError::ApiError(e) => {
+                e.r#type.as_deref() == Some("server_error")
+                    || e.code.as_deref() == Some("rate_limit_exceeded")
+            }
+            OpenAIError::StreamError(_) => true,
+            _ => false,
+        };
+        if transient {
+            Self::LlmTransient(err.to_string())
+        } else {
+            Self::Llm(err.to_string())
+        }
     }
 }
 
@@ -0,0 +1,74 @@
+//! Unit tests for AutoHedgeError classification.
+
+#[cfg(test)]
+mod error_tests {
+    use crate::error::AutoHedgeError;
+
+    // ============= Retry Classification Tests =============
+
+    #[test]
+    fn test_http_status_retryable() {
+        assert!(AutoHedgeError::http(429, "rate limited").is_retryable());
+        assert!(AutoHedgeError::http(503, "unavailable").is_retryable());
+        assert!(AutoHedgeError::http(408, "timeout").is_retryable());
+        assert!(!AutoHedgeError::http(403, "insufficient balance").is_retryable());
+        assert!(!AutoHedgeError::http(422, "invalid qty").is_retryable());
+    }
+
+    #[test]
+    fn test_non_exchange_errors() {
+        assert!(AutoHedgeError::Ws("closed".to_string()).is_retryable());
+        assert!(!AutoHedgeError::exchange("decode failed").is_retryable());
+        assert!(!AutoHedgeError::Unsupported("no".to_string()).is_retryable());
+        assert!(!AutoHedgeError::Config("bad".to_string()).is_retryable());
+        assert!(!AutoHedgeError::Llm("quota".to_string()).is_retryable());
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "trades.jsonl");
+        assert!(!AutoHedgeError::from(missing).is_retryable());
+    }
+
+    #[test]
+    fn test_status_and_message_preserved() {
+        let err = AutoHedgeError::http(403, "Alpaca get_order failed (403 Forbidden): {}");
+        assert_eq!(err.status(), Some(403));
+        assert_eq!(
+            err.to_string(),
+            "Alpaca get_order failed (403 Forbidden): {}"
+        );
+        assert_eq!(AutoHedgeError::exchange("x").status(), None);
+    }
+
+    #[test]
+    fn test_json_error_is_final() {
+        let err: AutoHedgeError = serde_json::from_str::<serde_json::Value>("{")
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, AutoHedgeError::Exchange { .. }));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_openai_api_errors_classified() {
+        use async_openai::error::{ApiError, OpenAIError};
+
+        let api_error = |r#type: &str, code: &str| {
+            OpenAIError::ApiError(ApiError {
+                message: "upstream".to_string(),
+                r#type: Some(r#type.to_string()),
+                param: None,
+                code: Some(code.to_string()),
+            })
+        };
+
+        let err: AutoHedgeError = api_error("server_error", "").into();
+        assert!(matches!(err, AutoHedgeError::LlmTransient(_)));
+        assert!(err.is_retryable());
+        assert!(err.to_string().starts_with("LLM error: "));
+
+        let err: AutoHedgeError = api_error("requests", "rate_limit_exceeded").into();
+        assert!(err.is_retryable());
+
+        let err: AutoHedgeError = api_error("insufficient_quota", "insufficient_quota").into();
+        assert!(matches!(err, AutoHedgeError::Llm(_)));
+        assert!(!err.is_retryable());
+    }
+}
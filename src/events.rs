@@ -1,4 +1,11 @@
-#[derive(Clone, Debug)]
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::AppConfig;
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub enum MarketEvent {
     Quote {
         symbol: String,
@@ -12,44 +19,885 @@ pub enum MarketEvent {
         size: f64,
         timestamp: String,
     },
+    /// News sentiment for a symbol, scored by `SentimentAgent` (-1 bearish .. 1 bullish)
+    Sentiment {
+        symbol: String,
+        score: f64,
+        headline: String,
+        timestamp: String,
+    },
+    /// Perpetual futures funding (Binance USD-M `markPriceUpdate`)
+    FundingRate {
+        symbol: String,
+        /// Rate for the current interval (0.0001 = 0.01%, paid by longs when positive)
+        rate: f64,
+        /// Mark price, the price liquidation is measured against
+        mark_price: f64,
+        /// Next funding settlement (epoch ms)
+        next_funding_time: i64,
+        timestamp: String,
+    },
+    /// A symbol's market regime changed (`RegimeService`)
+    Regime {
+        symbol: String,
+        regime: MarketRegime,
+        /// Realized volatility (RMS) of quote-to-quote mid returns
+        volatility_bps: f64,
+        /// Mean quoted spread over the window
+        spread_bps: f64,
+        trades_per_minute: f64,
+        /// Net mid move over the summed absolute moves (1 = straight line)
+        efficiency: f64,
+        timestamp: String,
+    },
+    /// A symbol's HFT entry thresholds were adjusted (`AdaptiveThresholdService`)
+    HftThresholds {
+        symbol: String,
+        min_edge_bps: f64,
+        max_spread_bps: f64,
+        /// Filled share of the recent entry signals
+        fill_rate: Option<f64>,
+        /// Winning share of the recent closed trades
+        win_rate: Option<f64>,
+        reason: String,
+        timestamp: String,
+    },
     // We can add Bar later if needed
 }
 
-#[derive(Clone, Debug)]
+impl MarketEvent {
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketEvent::Quote { symbol, .. }
+            | MarketEvent::Trade { symbol, .. }
+            | MarketEvent::Sentiment { symbol, .. }
+            | MarketEvent::FundingRate { symbol, .. }
+            | MarketEvent::Regime { symbol, .. }
+            | MarketEvent::HftThresholds { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// How a symbol is trading, as classified by `RegimeDetector`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketRegime {
+    /// Directional moves that momentum can follow
+    Trending,
+    /// Mean-reverting noise around a level
+    Choppy,
+    /// Wide spreads or too few trades to trade safely
+    Illiquid,
+}
+
+impl MarketRegime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketRegime::Trending => "trending",
+            MarketRegime::Choppy => "choppy",
+            MarketRegime::Illiquid => "illiquid",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct AnalysisSignal {
     pub symbol: String,
     pub signal: String, // "buy", "sell", "no_trade"
     pub confidence: f64,
     pub thesis: String,
     pub market_context: String, // Snapshot of data used
+    /// Strategy instance that produced it (`ab_test.live_id` / `shadow_id`);
+    /// None outside A/B tests and for exits from `PositionMonitor`
+    #[serde(default)]
+    pub strategy_id: Option<String>,
+    /// Id shared by every event of one trade (signal, order, execution,
+    /// position and its exit); set when the strategy engine publishes it
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Strategy mode and agent decisions behind it; set by the strategy
+    /// engine and `PositionMonitor` exits
+    #[serde(default)]
+    pub origin: Option<TradeOrigin>,
+    /// Quant agent output behind an LLM entry (`thesis` is the Director's)
+    #[serde(default)]
+    pub quant_analysis: Option<String>,
+    /// Entry limit the strategy wants; None lets execution price the entry
+    #[serde(default)]
+    pub entry_limit: Option<Decimal>,
+    /// Take-profit price the strategy targets
+    #[serde(default)]
+    pub take_profit: Option<Decimal>,
+    /// Stop-loss price the strategy targets
+    #[serde(default)]
+    pub stop_loss: Option<Decimal>,
+    /// RFC3339 time after which the entry is stale and must not be placed
+    #[serde(default)]
+    pub valid_until: Option<String>,
+    /// Free-form tags (experiment labels, ...) carried onto the order, its
+    /// executions and the reporter's trades
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
-#[derive(Clone, Debug)]
+impl AnalysisSignal {
+    /// Tags of the order placed for this signal: the configured `order_tags`,
+    /// overridden by the signal's own `metadata`, plus `strategy` (the A/B
+    /// instance, else the origin mode) and `signal_id` (the correlation id)
+    /// unless already set.
+    pub fn order_metadata(&self, order_tags: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut metadata = order_tags.clone();
+        metadata.extend(self.metadata.clone());
+        let strategy = self
+            .strategy_id
+            .clone()
+            .or_else(|| self.origin.as_ref().map(|o| o.mode.clone()));
+        if let Some(strategy) = strategy {
+            metadata.entry(TAG_STRATEGY.to_string()).or_insert(strategy);
+        }
+        if let Some(id) = &self.correlation_id {
+            metadata
+                .entry(TAG_SIGNAL_ID.to_string())
+                .or_insert_with(|| id.clone());
+        }
+        metadata
+    }
+}
+
+/// `metadata` tag naming the strategy behind an order
+pub const TAG_STRATEGY: &str = "strategy";
+/// `metadata` tag holding the id of the signal an order was placed for
+pub const TAG_SIGNAL_ID: &str = "signal_id";
+
+/// `metadata` as `k=v` pairs in key order (for logs and text formats).
+pub fn format_metadata(metadata: &HashMap<String, String>) -> String {
+    let mut pairs: Vec<_> = metadata.iter().collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// What produced a signal and its order, for P&L attribution by origin.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TradeOrigin {
+    /// Strategy mode ("hft", "llm", "hybrid", a registered custom strategy),
+    /// "monitor_exit" for `PositionMonitor` exits, "conditional" for
+    /// conditional orders or "fallback" for rule-based entries while the LLM
+    /// is down
+    pub mode: String,
+    /// Director decision that found (or, in hybrid mode, gated) the trade
+    #[serde(default)]
+    pub director_decision_id: Option<String>,
+    /// Quant decision that confirmed it (llm mode)
+    #[serde(default)]
+    pub quant_decision_id: Option<String>,
+}
+
+impl TradeOrigin {
+    pub const MONITOR_EXIT: &'static str = "monitor_exit";
+    /// Orders from `services::conditional_orders` rules
+    pub const CONDITIONAL: &'static str = "conditional";
+    /// Entries of the rule-based director while the LLM is down
+    /// (`services::llm_fallback`)
+    pub const FALLBACK: &'static str = "fallback";
+
+    pub fn new(mode: &str) -> Self {
+        Self {
+            mode: mode.to_string(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct OrderRequest {
     pub symbol: String,
     pub action: String, // "buy", "sell"
-    pub qty: f64,
+    pub qty: Decimal,
     pub order_type: String, // "market", "limit"
-    pub limit_price: Option<f64>,
-    pub stop_loss: Option<f64>,
-    pub take_profit: Option<f64>,
+    pub limit_price: Option<Decimal>,
+    pub stop_loss: Option<Decimal>,
+    pub take_profit: Option<Decimal>,
+    /// Strategy instance of the signal the order came from
+    #[serde(default)]
+    pub strategy_id: Option<String>,
+    /// Correlation id of the signal the order came from
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Origin of the signal the order came from
+    #[serde(default)]
+    pub origin: Option<TradeOrigin>,
+    /// Why the risk engine let it through (Risk agent response, or the HFT
+    /// fast path)
+    #[serde(default)]
+    pub risk_verdict: Option<String>,
+    /// RFC3339 time after which execution drops the entry (the signal's
+    /// `valid_until`)
+    #[serde(default)]
+    pub valid_until: Option<String>,
+    /// Tags of the signal (see `AnalysisSignal::order_metadata`), passed to
+    /// the venue and onto the order's executions
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
-#[derive(Clone, Debug)]
+impl OrderRequest {
+    /// Whether the request is past its `valid_until` at `now`. An unparseable
+    /// time counts as expired.
+    pub fn expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.valid_until.as_deref().is_some_and(|until| {
+            chrono::DateTime::parse_from_rfc3339(until).map_or(true, |until| now >= until)
+        })
+    }
+}
+
+/// Order outcome. Submission acks carry the REST status ("new", "accepted",
+/// "filled", "rejected"); fill updates from a user-data stream carry "fill" /
+/// "partial_fill" with the price and quantity of that execution.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct ExecutionReport {
     pub symbol: String,
     pub order_id: String,
-    pub status: String, // "filled", "new", "rejected"
+    pub status: String, // "filled", "new", "rejected", "fill", "partial_fill"
     pub side: String,   // "buy", "sell"
-    pub price: Option<f64>,
-    pub qty: Option<f64>,
+    pub price: Option<Decimal>,
+    pub qty: Option<Decimal>,
+    /// Quote mid when the order was priced (submission acks only)
+    pub signal_price: Option<Decimal>,
+    /// Submitted limit; `None` for market orders and venue fill updates
+    pub limit_price: Option<Decimal>,
+    /// Strategy instance of the order (submission acks and shadow fills only)
+    #[serde(default)]
+    pub strategy_id: Option<String>,
+    /// Correlation id of the order (submission acks only)
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Tags of the order (submission acks only)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
-// Global Event Enum
+impl ExecutionReport {
+    /// A venue fill update from a user-data stream.
+    pub fn is_fill_update(&self) -> bool {
+        self.status == "fill" || self.status == "partial_fill"
+    }
+
+    /// Whether this report should be booked as a fill. With `streamed_fills`
+    /// only real fill updates count; otherwise accepted submissions are taken
+    /// as filled at the submitted price.
+    pub fn counts_as_fill(&self, streamed_fills: bool) -> bool {
+        if streamed_fills {
+            return self.is_fill_update();
+        }
+        let st = self.status.to_lowercase();
+        st.contains("fill") || st == "new" || st == "accepted"
+    }
+}
+
+/// Exchange-agnostic account state changes.
+///
+/// Published by the account cache (and user-data streams where available) so
+/// sizing, risk and reporting can react without waiting for the next refresh.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub enum AccountEvent {
+    BalanceUpdated {
+        cash: Option<Decimal>,
+        buying_power: Option<Decimal>,
+        portfolio_value: Option<Decimal>,
+        timestamp: String,
+    },
+    MarginChanged {
+        previous_buying_power: Decimal,
+        buying_power: Decimal,
+        timestamp: String,
+    },
+    /// One attributed balance change from the balance audit trail
+    BalanceAudited(BalanceChangeEvent),
+}
+
+/// A cash change between two account snapshots, split into what explains it
+/// (`services::balance_audit`):
+/// `change == fill_cash_flow - fees + funding + transfer + unexplained`.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct BalanceChangeEvent {
+    pub cash_before: Decimal,
+    pub cash_after: Decimal,
+    pub change: Decimal,
+    /// Cash of fills booked since the previous snapshot (sells +, buys -)
+    pub fill_cash_flow: Decimal,
+    /// Traded notional of those fills
+    pub fill_notional: Decimal,
+    pub fills: u64,
+    /// Shortfall within the fee tolerance of the fill notional (positive)
+    pub fees: Decimal,
+    /// Margin venues: change without fills (funding payments)
+    pub funding: Decimal,
+    /// Spot: change without fills (deposit > 0, withdrawal < 0)
+    pub transfer: Decimal,
+    /// Residual none of the above accounts for
+    pub unexplained: Decimal,
+    pub portfolio_value: Option<Decimal>,
+    /// "fills", "funding", "deposit", "withdrawal" or "unexplained"
+    pub kind: String,
+    pub timestamp: String,
+}
+
+/// A symbol entered or left exit-only mode (venue halt/delisting notice or
+/// persistent order rejections). Entries are refused while `exit_only` is set.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SymbolStatusEvent {
+    pub symbol: String,
+    pub exit_only: bool,
+    pub reason: String,
+    /// "venue" (exchange status notice) or "rejections" (order rejection pattern)
+    pub source: String,
+    pub timestamp: String,
+}
+
+/// Steps of the degradation ladder, from normal trading to fully halted.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub enum DegradationLevel {
+    Full,
+    /// Entries allowed at `health.reduced_size_factor` of normal size
+    Reduced,
+    /// No new entries; open positions are still managed and exited
+    ExitOnly,
+    /// No strategy evaluation or entries; only position protection runs
+    Halted,
+}
+
+impl DegradationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DegradationLevel::Full => "full",
+            DegradationLevel::Reduced => "reduced",
+            DegradationLevel::ExitOnly => "exit_only",
+            DegradationLevel::Halted => "halted",
+        }
+    }
+
+    pub fn allows_entries(&self) -> bool {
+        *self <= DegradationLevel::Reduced
+    }
+}
+
+/// The composite health score moved the system to a different degradation level.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct HealthEvent {
+    pub level: DegradationLevel,
+    pub previous: DegradationLevel,
+    /// Composite score, 0 (unusable) to 100 (healthy)
+    pub score: f64,
+    /// Entry size multiplier for this level (1.0 at full, 0.0 when entries are off)
+    pub size_factor: f64,
+    /// Weakest components at the time of the transition
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// Operator commands (from the `/ws` control channel).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Stop new entries for a symbol (open positions are still managed)
+    PauseSymbol {
+        symbol: String,
+    },
+    ResumeSymbol {
+        symbol: String,
+    },
+    /// Exit the tracked position at the next tick
+    ClosePosition {
+        symbol: String,
+    },
+    /// Move the take-profit and/or stop-loss of a tracked position
+    AdjustExits {
+        symbol: String,
+        take_profit: Option<Decimal>,
+        stop_loss: Option<Decimal>,
+    },
+}
+
+impl ControlCommand {
+    pub fn symbol(&self) -> &str {
+        match self {
+            ControlCommand::PauseSymbol { symbol }
+            | ControlCommand::ResumeSymbol { symbol }
+            | ControlCommand::ClosePosition { symbol }
+            | ControlCommand::AdjustExits { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// System-wide trading state changes (circuit breaker).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub enum SystemEvent {
+    /// Stop all new entries until an operator resumes trading
+    Halt {
+        reason: String,
+        /// Also close every open position
+        flatten: bool,
+        timestamp: String,
+    },
+    /// Manual reset (`POST /resume`)
+    Resume { timestamp: String },
+}
+
+/// A proposed order breached a post-trade risk limit and was resized or rejected.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct RiskLimitEvent {
+    pub symbol: String,
+    pub side: String,
+    /// "resized" or "rejected"
+    pub action: String,
+    pub requested_qty: Decimal,
+    /// Quantity allowed through (0 when rejected)
+    pub approved_qty: Decimal,
+    pub price: Decimal,
+    /// Violated limit ("max_symbol_exposure_pct", "max_var_pct", ...)
+    pub limit: String,
+    /// Post-trade value of that metric at the requested size
+    pub value: Decimal,
+    pub threshold: Decimal,
+    pub timestamp: String,
+}
+
+/// A symbol hit its stop-loss too often and gets no new entries until `until`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SymbolCooldownEvent {
+    pub symbol: String,
+    /// Stop-loss exits that started the cooldown
+    pub stop_losses: u32,
+    pub window_minutes: u64,
+    /// RFC3339 end of the cooldown
+    pub until: String,
+    pub timestamp: String,
+}
+
+/// A buy signal was dropped by an entry guard before reaching execution.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct EntrySkippedEvent {
+    pub symbol: String,
+    /// Guard that skipped it ("correlation_guard")
+    pub reason: String,
+    /// Human-readable specifics (correlated holdings, ...)
+    pub detail: String,
+    pub timestamp: String,
+}
+
+/// A prospective order was blocked by a pre-trade compliance check
+/// (`services::policy`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct OrderRejectedEvent {
+    pub symbol: String,
+    pub side: String,
+    /// Machine-readable rule ("restricted_symbol", "max_trade_notional",
+    /// "max_volume_pct", "trading_window", ...)
+    pub reason: String,
+    /// Human-readable specifics (limits and observed values)
+    pub detail: String,
+    pub correlation_id: Option<String>,
+    pub timestamp: String,
+}
+
+/// The margin monitor sold part of a futures position to bring the account's
+/// margin ratio back down (`services::margin_monitor`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct DeleverageEvent {
+    pub symbol: String,
+    /// Quantity sold
+    pub qty: Decimal,
+    pub price: Decimal,
+    /// Account margin ratio (%) that triggered it
+    pub margin_ratio_pct: f64,
+    /// Ratio (%) the reduction aims for
+    pub target_pct: f64,
+    pub order_id: String,
+    pub timestamp: String,
+}
+
+/// One venue's bid clears another's ask by more than both taker fees
+/// (`services::arbitrage`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct ArbitrageEvent {
+    pub symbol: String,
+    /// Venue with the lower ask
+    pub buy_venue: String,
+    /// Venue with the higher bid
+    pub sell_venue: String,
+    /// Ask on `buy_venue`
+    pub buy_price: f64,
+    /// Bid on `sell_venue`
+    pub sell_price: f64,
+    /// Gross spread of the bid over the ask
+    pub spread_bps: f64,
+    /// Taker fees of both venues
+    pub fee_bps: f64,
+    /// Spread left after fees
+    pub net_bps: f64,
+    pub timestamp: String,
+}
+
+/// Both legs of an arbitrage trade were placed (`arbitrage.execute`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct ArbitrageTradeEvent {
+    pub symbol: String,
+    pub buy_venue: String,
+    pub sell_venue: String,
+    /// Quantity bought and sold on both venues
+    pub qty: Decimal,
+    pub buy_price: Decimal,
+    pub sell_price: Decimal,
+    /// Bought minus sold still held after the mismatch between the legs was
+    /// unwound; non-zero only when that unwind failed
+    pub unhedged: Decimal,
+    /// Spread captured on `qty` after both fees
+    pub pnl: Decimal,
+    pub timestamp: String,
+}
+
+/// LLM decisions switched to (or back from) the rule-based fallback
+/// (`services::llm_fallback`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct LlmFallbackEvent {
+    /// True while entries are decided by the indicator rules, not the LLM
+    pub active: bool,
+    /// LLM failure rate over `llm_fallback.window_secs`
+    pub error_rate: f64,
+    /// Calls in the window
+    pub calls: usize,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// A position was closed (fully or in part) and its P&L realized.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct TradeClosedEvent {
+    pub symbol: String,
+    /// Thesis of the buy signal that opened the position (empty when unknown)
+    pub thesis: String,
+    pub entry_price: Decimal,
+    pub exit_price: Decimal,
+    pub qty: Decimal,
+    /// Realized P&L in the reporting currency
+    pub pnl: Decimal,
+    pub pnl_percent: f64,
+    pub strategy_id: Option<String>,
+    pub correlation_id: Option<String>,
+    pub timestamp: String,
+}
+
+/// A streaming connection (market data or user-data stream) dropped.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct DisconnectEvent {
+    /// "market_ws" or the user stream name ("binance userDataStream", ...)
+    pub stream: String,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// Tunables of the running session changed (config file edit or `PUT /config`).
 #[derive(Clone, Debug)]
+pub struct ConfigUpdateEvent {
+    /// Full config with the new values applied
+    pub config: Arc<AppConfig>,
+    /// Changed keys ("hft", "defaults.take_profit_pct", "symbols", ...)
+    pub changed: Vec<String>,
+    /// "file" or "api"
+    pub source: String,
+    pub timestamp: String,
+}
+
+// Global Event Enum (serde form is what the event log records)
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub enum Event {
     Market(MarketEvent),
     Signal(AnalysisSignal),
     Order(OrderRequest),
     Execution(ExecutionReport),
+    Account(AccountEvent),
+    SymbolStatus(SymbolStatusEvent),
+    Health(HealthEvent),
+    Control(ControlCommand),
+    System(SystemEvent),
+    RiskLimit(RiskLimitEvent),
+    Cooldown(SymbolCooldownEvent),
+    EntrySkipped(EntrySkippedEvent),
+    OrderRejected(OrderRejectedEvent),
+    Deleverage(DeleverageEvent),
+    Arbitrage(ArbitrageEvent),
+    ArbitrageTrade(ArbitrageTradeEvent),
+    LlmFallback(LlmFallbackEvent),
+    TradeClosed(TradeClosedEvent),
+    /// Not recorded: a replay runs under the current config
+    #[serde(skip)]
+    Config(ConfigUpdateEvent),
+    Disconnected(DisconnectEvent),
+}
+
+impl Event {
+    /// JSON form streamed to `/ws` clients. Market data is not streamed (too
+    /// chatty for a dashboard); everything else is.
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        let value = match self {
+            Event::Market(MarketEvent::Regime {
+                symbol,
+                regime,
+                volatility_bps,
+                spread_bps,
+                trades_per_minute,
+                efficiency,
+                timestamp,
+            }) => json!({
+                "type": "regime",
+                "symbol": symbol,
+                "regime": regime,
+                "volatility_bps": volatility_bps,
+                "spread_bps": spread_bps,
+                "trades_per_minute": trades_per_minute,
+                "efficiency": efficiency,
+                "timestamp": timestamp,
+            }),
+            Event::Market(MarketEvent::HftThresholds {
+                symbol,
+                min_edge_bps,
+                max_spread_bps,
+                fill_rate,
+                win_rate,
+                reason,
+                timestamp,
+            }) => json!({
+                "type": "hft_thresholds",
+                "symbol": symbol,
+                "min_edge_bps": min_edge_bps,
+                "max_spread_bps": max_spread_bps,
+                "fill_rate": fill_rate,
+                "win_rate": win_rate,
+                "reason": reason,
+                "timestamp": timestamp,
+            }),
+            Event::Market(_) => return None,
+            Event::Signal(s) => json!({
+                "type": "signal",
+                "symbol": s.symbol,
+                "signal": s.signal,
+                "confidence": s.confidence,
+                "thesis": s.thesis,
+                "strategy_id": s.strategy_id,
+                "correlation_id": s.correlation_id,
+                "origin": s.origin,
+                "metadata": s.metadata,
+            }),
+            Event::Order(o) => json!({
+                "type": "order",
+                "symbol": o.symbol,
+                "action": o.action,
+                "qty": o.qty,
+                "order_type": o.order_type,
+                "limit_price": o.limit_price,
+                "stop_loss": o.stop_loss,
+                "take_profit": o.take_profit,
+                "strategy_id": o.strategy_id,
+                "correlation_id": o.correlation_id,
+                "origin": o.origin,
+                "metadata": o.metadata,
+            }),
+            Event::Execution(r) => json!({
+                "type": "execution",
+                "symbol": r.symbol,
+                "order_id": r.order_id,
+                "status": r.status,
+                "side": r.side,
+                "price": r.price,
+                "qty": r.qty,
+                "strategy_id": r.strategy_id,
+                "correlation_id": r.correlation_id,
+                "metadata": r.metadata,
+            }),
+            Event::Account(AccountEvent::BalanceUpdated {
+                cash,
+                buying_power,
+                portfolio_value,
+                timestamp,
+            }) => json!({
+                "type": "account",
+                "kind": "balance_updated",
+                "cash": cash,
+                "buying_power": buying_power,
+                "portfolio_value": portfolio_value,
+                "timestamp": timestamp,
+            }),
+            Event::Account(AccountEvent::MarginChanged {
+                previous_buying_power,
+                buying_power,
+                timestamp,
+            }) => json!({
+                "type": "account",
+                "kind": "margin_changed",
+                "previous_buying_power": previous_buying_power,
+                "buying_power": buying_power,
+                "timestamp": timestamp,
+            }),
+            Event::Account(AccountEvent::BalanceAudited(b)) => json!({
+                "type": "account",
+                "kind": "balance_audited",
+                "change_kind": b.kind,
+                "change": b.change,
+                "fill_cash_flow": b.fill_cash_flow,
+                "fees": b.fees,
+                "funding": b.funding,
+                "transfer": b.transfer,
+                "unexplained": b.unexplained,
+                "timestamp": b.timestamp,
+            }),
+            Event::SymbolStatus(s) => json!({
+                "type": "symbol_status",
+                "symbol": s.symbol,
+                "exit_only": s.exit_only,
+                "reason": s.reason,
+                "source": s.source,
+                "timestamp": s.timestamp,
+            }),
+            Event::Health(h) => json!({
+                "type": "health",
+                "level": h.level.as_str(),
+                "previous": h.previous.as_str(),
+                "score": h.score,
+                "size_factor": h.size_factor,
+                "reason": h.reason,
+                "timestamp": h.timestamp,
+            }),
+            Event::Control(c) => json!({
+                "type": "control",
+                "command": c,
+            }),
+            Event::System(SystemEvent::Halt {
+                reason,
+                flatten,
+                timestamp,
+            }) => json!({
+                "type": "system",
+                "kind": "halt",
+                "reason": reason,
+                "flatten": flatten,
+                "timestamp": timestamp,
+            }),
+            Event::System(SystemEvent::Resume { timestamp }) => json!({
+                "type": "system",
+                "kind": "resume",
+                "timestamp": timestamp,
+            }),
+            Event::RiskLimit(r) => json!({
+                "type": "risk_limit",
+                "symbol": r.symbol,
+                "side": r.side,
+                "action": r.action,
+                "requested_qty": r.requested_qty,
+                "approved_qty": r.approved_qty,
+                "price": r.price,
+                "limit": r.limit,
+                "value": r.value,
+                "threshold": r.threshold,
+                "timestamp": r.timestamp,
+            }),
+            Event::Cooldown(c) => json!({
+                "type": "cooldown",
+                "symbol": c.symbol,
+                "stop_losses": c.stop_losses,
+                "window_minutes": c.window_minutes,
+                "until": c.until,
+                "timestamp": c.timestamp,
+            }),
+            Event::EntrySkipped(e) => json!({
+                "type": "entry_skipped",
+                "symbol": e.symbol,
+                "reason": e.reason,
+                "detail": e.detail,
+                "timestamp": e.timestamp,
+            }),
+            Event::OrderRejected(r) => json!({
+                "type": "order_rejected",
+                "symbol": r.symbol,
+                "side": r.side,
+                "reason": r.reason,
+                "detail": r.detail,
+                "correlation_id": r.correlation_id,
+                "timestamp": r.timestamp,
+            }),
+            Event::Deleverage(d) => json!({
+                "type": "deleverage",
+                "symbol": d.symbol,
+                "qty": d.qty,
+                "price": d.price,
+                "margin_ratio_pct": d.margin_ratio_pct,
+                "target_pct": d.target_pct,
+                "order_id": d.order_id,
+                "timestamp": d.timestamp,
+            }),
+            Event::Arbitrage(a) => json!({
+                "type": "arbitrage",
+                "symbol": a.symbol,
+                "buy_venue": a.buy_venue,
+                "sell_venue": a.sell_venue,
+                "buy_price": a.buy_price,
+                "sell_price": a.sell_price,
+                "spread_bps": a.spread_bps,
+                "fee_bps": a.fee_bps,
+                "net_bps": a.net_bps,
+                "timestamp": a.timestamp,
+            }),
+            Event::ArbitrageTrade(t) => json!({
+                "type": "arbitrage_trade",
+                "symbol": t.symbol,
+                "buy_venue": t.buy_venue,
+                "sell_venue": t.sell_venue,
+                "qty": t.qty,
+                "buy_price": t.buy_price,
+                "sell_price": t.sell_price,
+                "unhedged": t.unhedged,
+                "pnl": t.pnl,
+                "timestamp": t.timestamp,
+            }),
+            Event::LlmFallback(f) => json!({
+                "type": "llm_fallback",
+                "active": f.active,
+                "error_rate": f.error_rate,
+                "calls": f.calls,
+                "reason": f.reason,
+                "timestamp": f.timestamp,
+            }),
+            Event::TradeClosed(t) => json!({
+                "type": "trade_closed",
+                "symbol": t.symbol,
+                "thesis": t.thesis,
+                "entry_price": t.entry_price,
+                "exit_price": t.exit_price,
+                "qty": t.qty,
+                "pnl": t.pnl,
+                "pnl_percent": t.pnl_percent,
+                "strategy_id": t.strategy_id,
+                "correlation_id": t.correlation_id,
+                "timestamp": t.timestamp,
+            }),
+            Event::Config(c) => json!({
+                "type": "config",
+                "changed": c.changed,
+                "source": c.source,
+                "timestamp": c.timestamp,
+            }),
+            Event::Disconnected(d) => json!({
+                "type": "disconnected",
+                "stream": d.stream,
+                "reason": d.reason,
+                "timestamp": d.timestamp,
+            }),
+        };
+        Some(value)
+    }
 }
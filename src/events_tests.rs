@@ -3,6 +3,8 @@
 #[cfg(test)]
 mod events_tests {
     use crate::events::*;
+    use crate::money::dec;
+    use std::collections::HashMap;
 
     // ============= MarketEvent::Quote Tests =============
 
@@ -113,6 +115,15 @@ mod events_tests {
             confidence: 0.85,
             thesis: "Bullish momentum detected".to_string(),
             market_context: "tp=51000, sl=49000".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(signal.symbol, "BTC/USD");
@@ -128,6 +139,15 @@ mod events_tests {
             confidence: 0.75,
             thesis: "Bearish divergence".to_string(),
             market_context: "current_price=3000".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(signal.signal, "sell");
@@ -141,6 +161,15 @@ mod events_tests {
             confidence: 0.0,
             thesis: "Market too volatile".to_string(),
             market_context: "spread_bps=100".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(signal.signal, "no_trade");
@@ -155,11 +184,70 @@ mod events_tests {
             confidence: 1.0,
             thesis: "HFT momentum: edge_bps=15.0, spread_bps=5.0".to_string(),
             market_context: "tp=0.082, sl=0.078".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: Some(dec(0.082)),
+            stop_loss: Some(dec(0.078)),
+            valid_until: None,
+            ..Default::default()
         };
 
         assert!(signal.thesis.starts_with("HFT"));
-        assert!(signal.market_context.contains("tp="));
-        assert!(signal.market_context.contains("sl="));
+        assert_eq!(signal.take_profit, Some(dec(0.082)));
+        assert_eq!(signal.stop_loss, Some(dec(0.078)));
+    }
+
+    #[test]
+    fn test_order_metadata_merges_tags() {
+        let signal: AnalysisSignal = serde_json::from_value(serde_json::json!({
+            "symbol": "BTC/USD",
+            "signal": "buy",
+            "confidence": 0.9,
+            "thesis": "HFT edge",
+            "market_context": "",
+            "correlation_id": "c1",
+            "origin": {"mode": "hft"},
+            "metadata": {"experiment": "signal-label"}
+        }))
+        .unwrap();
+        let order_tags = HashMap::from([
+            ("experiment".to_string(), "config-label".to_string()),
+            ("desk".to_string(), "crypto".to_string()),
+        ]);
+
+        let tags = signal.order_metadata(&order_tags);
+        assert_eq!(tags["experiment"], "signal-label");
+        assert_eq!(tags["desk"], "crypto");
+        assert_eq!(tags[TAG_STRATEGY], "hft");
+        assert_eq!(tags[TAG_SIGNAL_ID], "c1");
+        assert_eq!(
+            format_metadata(&tags),
+            "desk=crypto,experiment=signal-label,signal_id=c1,strategy=hft"
+        );
+
+        // The A/B instance names the strategy; untagged signals add nothing else
+        let ab = AnalysisSignal {
+            strategy_id: Some("live-a".to_string()),
+            correlation_id: None,
+            metadata: HashMap::new(),
+            ..signal
+        };
+        let tags = ab.order_metadata(&HashMap::new());
+        assert_eq!(
+            tags,
+            HashMap::from([(TAG_STRATEGY.to_string(), "live-a".to_string())])
+        );
+        // Empty tags are left out of serialized events
+        let json = serde_json::to_value(AnalysisSignal {
+            strategy_id: None,
+            origin: None,
+            ..ab
+        })
+        .unwrap();
+        assert!(json.get("metadata").is_none());
     }
 
     // ============= OrderRequest Tests =============
@@ -169,11 +257,17 @@ mod events_tests {
         let order = OrderRequest {
             symbol: "BTC/USD".to_string(),
             action: "buy".to_string(),
-            qty: 0.1,
+            qty: dec(0.1),
             order_type: "market".to_string(),
             limit_price: None,
-            stop_loss: Some(49000.0),
-            take_profit: Some(51000.0),
+            stop_loss: Some(dec(49000.0)),
+            take_profit: Some(dec(51000.0)),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(order.symbol, "BTC/USD");
@@ -187,15 +281,21 @@ mod events_tests {
         let order = OrderRequest {
             symbol: "ETH/USD".to_string(),
             action: "buy".to_string(),
-            qty: 1.0,
+            qty: dec(1.0),
             order_type: "limit".to_string(),
-            limit_price: Some(2950.0),
-            stop_loss: Some(2850.0),
-            take_profit: Some(3100.0),
+            limit_price: Some(dec(2950.0)),
+            stop_loss: Some(dec(2850.0)),
+            take_profit: Some(dec(3100.0)),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(order.order_type, "limit");
-        assert_eq!(order.limit_price, Some(2950.0));
+        assert_eq!(order.limit_price, Some(dec(2950.0)));
     }
 
     #[test]
@@ -203,11 +303,17 @@ mod events_tests {
         let order = OrderRequest {
             symbol: "SOL/USD".to_string(),
             action: "sell".to_string(),
-            qty: 10.0,
+            qty: dec(10.0),
             order_type: "market".to_string(),
             limit_price: None,
             stop_loss: None,
             take_profit: None,
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(order.action, "sell");
@@ -220,16 +326,52 @@ mod events_tests {
         let order = OrderRequest {
             symbol: "DOGE/USD".to_string(),
             action: "buy".to_string(),
-            qty: 0.0, // Execution will determine
+            qty: dec(0.0), // Execution will determine
             order_type: "hft_buy".to_string(),
             limit_price: None,
-            stop_loss: Some(0.078),
-            take_profit: Some(0.082),
+            stop_loss: Some(dec(0.078)),
+            take_profit: Some(dec(0.082)),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         };
 
         assert_eq!(order.order_type, "hft_buy");
     }
 
+    #[test]
+    fn test_order_request_expiry() {
+        let mut order = OrderRequest {
+            symbol: "DOGE/USD".to_string(),
+            action: "buy".to_string(),
+            qty: dec(0.0),
+            order_type: "hft_buy".to_string(),
+            limit_price: Some(dec(0.08)),
+            stop_loss: None,
+            take_profit: None,
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
+        };
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert!(!order.expired(now));
+
+        order.valid_until = Some("2026-01-01T12:00:00.500Z".to_string());
+        assert!(!order.expired(now));
+        assert!(order.expired(now + chrono::Duration::milliseconds(500)));
+
+        order.valid_until = Some("soon".to_string());
+        assert!(order.expired(now));
+    }
+
     // ============= ExecutionReport Tests =============
 
     #[test]
@@ -239,14 +381,19 @@ mod events_tests {
             order_id: "order123".to_string(),
             status: "filled".to_string(),
             side: "buy".to_string(),
-            price: Some(50000.0),
-            qty: Some(0.1),
+            price: Some(dec(50000.0)),
+            qty: Some(dec(0.1)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
         };
 
         assert_eq!(report.status, "filled");
         assert_eq!(report.side, "buy");
-        assert_eq!(report.price, Some(50000.0));
-        assert_eq!(report.qty, Some(0.1));
+        assert_eq!(report.price, Some(dec(50000.0)));
+        assert_eq!(report.qty, Some(dec(0.1)));
     }
 
     #[test]
@@ -256,8 +403,13 @@ mod events_tests {
             order_id: "order456".to_string(),
             status: "new".to_string(),
             side: "sell".to_string(),
-            price: Some(3000.0),
-            qty: Some(1.0),
+            price: Some(dec(3000.0)),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
         };
 
         assert_eq!(report.status, "new");
@@ -272,6 +424,11 @@ mod events_tests {
             side: "buy".to_string(),
             price: None,
             qty: None,
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
         };
 
         assert_eq!(report.status, "rejected");
@@ -279,6 +436,31 @@ mod events_tests {
         assert!(report.qty.is_none());
     }
 
+    #[test]
+    fn test_execution_report_counts_as_fill() {
+        let report = |status: &str| ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o".to_string(),
+            status: status.to_string(),
+            side: "buy".to_string(),
+            price: Some(dec(1.0)),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        };
+
+        // Acks are booked as fills only while fills are not streamed
+        assert!(report("new").counts_as_fill(false));
+        assert!(!report("new").counts_as_fill(true));
+        assert!(!report("filled").counts_as_fill(true));
+        assert!(report("partial_fill").counts_as_fill(true));
+        assert!(report("fill").counts_as_fill(true));
+        assert!(!report("rejected").counts_as_fill(false));
+    }
+
     // ============= Event Enum Tests =============
 
     #[test]
@@ -301,6 +483,15 @@ mod events_tests {
             confidence: 0.9,
             thesis: "Strong momentum".to_string(),
             market_context: "context".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         });
 
         assert!(matches!(event, Event::Signal(_)));
@@ -311,11 +502,17 @@ mod events_tests {
         let event = Event::Order(OrderRequest {
             symbol: "SOL/USD".to_string(),
             action: "buy".to_string(),
-            qty: 1.0,
+            qty: dec(1.0),
             order_type: "limit".to_string(),
-            limit_price: Some(100.0),
+            limit_price: Some(dec(100.0)),
             stop_loss: None,
             take_profit: None,
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
         });
 
         assert!(matches!(event, Event::Order(_)));
@@ -328,13 +525,34 @@ mod events_tests {
             order_id: "order123".to_string(),
             status: "filled".to_string(),
             side: "buy".to_string(),
-            price: Some(0.08),
-            qty: Some(10000.0),
+            price: Some(dec(0.08)),
+            qty: Some(dec(10000.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
         });
 
         assert!(matches!(event, Event::Execution(_)));
     }
 
+    #[test]
+    fn test_event_account_transfer() {
+        let event = Event::Account(AccountEvent::BalanceAudited(BalanceChangeEvent {
+            change: dec(250.0),
+            transfer: dec(250.0),
+            kind: "deposit".to_string(),
+            ..Default::default()
+        }));
+
+        if let Event::Account(AccountEvent::BalanceAudited(change)) = event {
+            assert_eq!(change.transfer, dec(250.0));
+        } else {
+            panic!("Expected Account event");
+        }
+    }
+
     #[test]
     fn test_event_clone() {
         let event = Event::Market(MarketEvent::Trade {
@@ -360,10 +578,129 @@ mod events_tests {
             confidence: 0.8,
             thesis: "Test".to_string(),
             market_context: "ctx".to_string(),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
         });
 
         let debug = format!("{:?}", event);
         assert!(debug.contains("Signal"));
         assert!(debug.contains("LTC/USD"));
     }
+
+    // ============= ControlCommand Tests =============
+
+    #[test]
+    fn test_control_command_from_json() {
+        let cmd: ControlCommand =
+            serde_json::from_str(r#"{"cmd":"pause_symbol","symbol":"BTC/USD"}"#).unwrap();
+        assert!(matches!(cmd, ControlCommand::PauseSymbol { ref symbol } if symbol == "BTC/USD"));
+
+        let cmd: ControlCommand = serde_json::from_str(
+            r#"{"cmd":"adjust_exits","symbol":"ETH/USD","take_profit":3100.0}"#,
+        )
+        .unwrap();
+        if let ControlCommand::AdjustExits {
+            take_profit,
+            stop_loss,
+            ..
+        } = &cmd
+        {
+            assert_eq!(*take_profit, Some(dec(3100.0)));
+            assert_eq!(*stop_loss, None);
+        } else {
+            panic!("Expected AdjustExits");
+        }
+        assert_eq!(cmd.symbol(), "ETH/USD");
+
+        assert!(serde_json::from_str::<ControlCommand>(r#"{"cmd":"launch"}"#).is_err());
+    }
+
+    // ============= Event JSON Tests =============
+
+    #[test]
+    fn test_event_to_json_skips_market_data() {
+        let event = Event::Market(MarketEvent::Trade {
+            symbol: "BTC/USD".to_string(),
+            price: 50000.0,
+            size: 0.1,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        });
+        assert!(event.to_json().is_none());
+    }
+
+    #[test]
+    fn test_signal_without_correlation_id_deserializes() {
+        // Event logs recorded before correlation ids existed still replay
+        let signal: AnalysisSignal = serde_json::from_str(
+            r#"{"symbol":"BTC/USD","signal":"buy","confidence":0.9,"thesis":"t","market_context":""}"#,
+        )
+        .unwrap();
+        assert_eq!(signal.correlation_id, None);
+        assert_eq!(signal.strategy_id, None);
+        assert_eq!(signal.take_profit, None);
+        assert_eq!(signal.valid_until, None);
+    }
+
+    #[test]
+    fn test_event_to_json() {
+        let event = Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o-1".to_string(),
+            status: "filled".to_string(),
+            side: "buy".to_string(),
+            price: Some(dec(50000.0)),
+            qty: Some(dec(0.01)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: Some("c0ffee".to_string()),
+            ..Default::default()
+        });
+        let json = event.to_json().unwrap();
+        assert_eq!(json["type"], "execution");
+        assert_eq!(json["order_id"], "o-1");
+        assert_eq!(json["price"], 50000.0);
+        assert_eq!(json["correlation_id"], "c0ffee");
+
+        let event = Event::Control(ControlCommand::ClosePosition {
+            symbol: "ETH/USD".to_string(),
+        });
+        let json = event.to_json().unwrap();
+        assert_eq!(json["type"], "control");
+        assert_eq!(json["command"]["cmd"], "close_position");
+        assert_eq!(json["command"]["symbol"], "ETH/USD");
+
+        let event = Event::RiskLimit(RiskLimitEvent {
+            symbol: "BTC/USD".to_string(),
+            side: "buy".to_string(),
+            action: "resized".to_string(),
+            requested_qty: dec(1.0),
+            approved_qty: dec(0.5),
+            price: dec(100.0),
+            limit: "max_symbol_exposure_pct".to_string(),
+            value: dec(30.0),
+            threshold: dec(25.0),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        });
+        let json = event.to_json().unwrap();
+        assert_eq!(json["type"], "risk_limit");
+        assert_eq!(json["limit"], "max_symbol_exposure_pct");
+        assert_eq!(json["approved_qty"], 0.5);
+
+        let event = Event::Disconnected(DisconnectEvent {
+            stream: "market_ws".to_string(),
+            reason: "closed by venue".to_string(),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        });
+        let json = event.to_json().unwrap();
+        assert_eq!(json["type"], "disconnected");
+        assert_eq!(json["stream"], "market_ws");
+    }
 }
@@ -1,20 +1,95 @@
 use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
 use serde_json::Value;
 
-use crate::data::alpaca::{AlpacaClient, OrderRequest as AlpacaOrderRequest};
+use crate::data::alpaca::{
+    AlpacaClient, OrderLeg as AlpacaOrderLeg, OrderRequest as AlpacaOrderRequest,
+    ReplaceOrderRequest as AlpacaReplaceRequest,
+};
+use crate::error::AutoHedgeError;
+use crate::money;
 
 use super::{
+    symbol_registry::{SymbolRegistry, Venue},
     traits::{ExchangeResult, TradingApi},
     types::{
-        AccountSummary, ExchangeCapabilities, OrderAck, OrderType, PlaceOrderRequest, Position,
-        Side, TimeInForce,
+        AccountSummary, BracketLegs, ExchangeCapabilities, OpenOrder, OrderAck, OrderType,
+        PlaceOrderRequest, Position, Side, SymbolMeta, TimeInForce, TopOfBook,
     },
 };
 
+/// Longest `client_order_id` Alpaca accepts
+const ALPACA_CLIENT_ID_LEN: usize = 128;
+
+/// Trading rules from `GET /v2/assets/{symbol}`. Crypto assets carry
+/// `price_increment`, `min_trade_increment` and `min_order_size`; equities
+/// trade on a one-cent grid in whole shares unless `fractionable`.
+pub fn symbol_meta_from_asset(symbol: &str, asset: &Value) -> SymbolMeta {
+    let field = |key: &str| asset.get(key).and_then(money::from_json);
+    let is_equity = asset.get("class").and_then(|c| c.as_str()) == Some("us_equity");
+    let fractionable = asset
+        .get("fractionable")
+        .and_then(|f| f.as_bool())
+        .unwrap_or(false);
+    SymbolMeta {
+        symbol: symbol.to_string(),
+        tick_size: field("price_increment").unwrap_or(if is_equity {
+            Decimal::new(1, 2)
+        } else {
+            Decimal::ZERO
+        }),
+        step_size: field("min_trade_increment").unwrap_or(if is_equity && !fractionable {
+            Decimal::ONE
+        } else {
+            Decimal::ZERO
+        }),
+        min_qty: field("min_order_size").unwrap_or_default(),
+        min_notional: Decimal::ZERO,
+    }
+}
+
+/// Best bid/ask from a latest-quote response (crypto `quotes.<symbol>` or
+/// equity `quote`).
+pub fn top_of_book_from_latest_quote(symbol: &str, raw: &Value) -> Option<TopOfBook> {
+    let quote = raw
+        .get("quotes")
+        .and_then(|q| q.get(symbol))
+        .or_else(|| raw.get("quote"))?;
+    TopOfBook::new(
+        quote.get("bp").and_then(money::from_json),
+        quote.get("ap").and_then(money::from_json),
+    )
+}
+
+fn alpaca_time_in_force(tif: TimeInForce) -> &'static str {
+    match tif {
+        TimeInForce::Day => "day",
+        TimeInForce::Gtc | TimeInForce::PostOnly => "gtc",
+        TimeInForce::Ioc => "ioc",
+    }
+}
+
+fn alpaca_order_ack(raw: Value) -> OrderAck {
+    let field = |key: &str| {
+        raw.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string()
+    };
+    OrderAck {
+        id: field("id"),
+        status: field("status"),
+        raw,
+    }
+}
+
 #[derive(Clone)]
 pub struct AlpacaExchange {
     inner: AlpacaClient,
     trading_mode: String,
+    /// Resolves position symbols ("BTCUSD") to canonical ids ("BTC/USD")
+    symbols: SymbolRegistry,
 }
 
 impl AlpacaExchange {
@@ -22,9 +97,16 @@ impl AlpacaExchange {
         Self {
             inner,
             trading_mode,
+            symbols: SymbolRegistry::new(Venue::Alpaca, &[]),
         }
     }
 
+    /// Translate symbols through the session's shared registry.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
     pub fn market_store(&self) -> crate::data::store::MarketStore {
         self.inner.market_store.clone()
     }
@@ -38,11 +120,21 @@ impl TradingApi for AlpacaExchange {
 
     fn capabilities(&self) -> ExchangeCapabilities {
         // Alpaca crypto supports notional market buy in /v2/orders.
+        // Bracket/OCO order classes, stop-market orders and shorting are
+        // equities-only; crypto takes stop_limit. Fractional and crypto
+        // orders must be worth at least $1.
+        let is_crypto = self.trading_mode.eq_ignore_ascii_case("crypto");
         ExchangeCapabilities {
-            supports_notional_market_buy: self.trading_mode.eq_ignore_ascii_case("crypto"),
+            supports_notional_market_buy: is_crypto,
             supports_ws_quotes: true,
             supports_ws_trades: true,
             supports_news: true,
+            supports_bracket_orders: !is_crypto,
+            supports_order_replace: true,
+            supports_stop_orders: !is_crypto,
+            supports_oco: !is_crypto,
+            supports_short: !is_crypto,
+            min_notional: Decimal::ONE,
         }
     }
 
@@ -64,19 +156,10 @@ impl TradingApi for AlpacaExchange {
                 .and_then(|x| x.as_str())
                 .unwrap_or_default()
                 .to_string();
-            let qty = v
-                .get("qty")
-                .and_then(|x| x.as_str())
-                .and_then(|s| s.parse::<f64>().ok())
-                .or_else(|| v.get("qty").and_then(|x| x.as_f64()))
-                .unwrap_or(0.0);
-            let avg_entry_price = v
-                .get("avg_entry_price")
-                .and_then(|x| x.as_str())
-                .and_then(|s| s.parse::<f64>().ok())
-                .or_else(|| v.get("avg_entry_price").and_then(|x| x.as_f64()));
+            let qty = v.get("qty").and_then(money::from_json).unwrap_or_default();
+            let avg_entry_price = v.get("avg_entry_price").and_then(money::from_json);
             out.push(Position {
-                symbol,
+                symbol: self.symbols.canonical(&symbol),
                 qty,
                 avg_entry_price,
             });
@@ -86,17 +169,7 @@ impl TradingApi for AlpacaExchange {
 
     async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
         let raw = self.inner.get_order(order_id).await?;
-        let id = raw
-            .get("id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-        let status = raw
-            .get("status")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-        Ok(OrderAck { id, status, raw })
+        Ok(alpaca_order_ack(raw))
     }
 
     async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
@@ -109,6 +182,26 @@ impl TradingApi for AlpacaExchange {
         Ok(())
     }
 
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        let orders = self.inner.get_open_orders().await?;
+        Ok(Some(
+            orders
+                .iter()
+                .filter_map(|o| {
+                    let field = |key: &str| o.get(key).and_then(|v| v.as_str());
+                    Some(OpenOrder {
+                        id: field("id")?.to_string(),
+                        symbol: self.symbols.canonical(field("symbol").unwrap_or_default()),
+                        side: field("side").unwrap_or_default().to_string(),
+                        created_at: field("created_at")
+                            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                            .map(|t| t.with_timezone(&Utc)),
+                    })
+                })
+                .collect(),
+        ))
+    }
+
     async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
         let side = match order.side {
             Side::Buy => "buy",
@@ -118,13 +211,17 @@ impl TradingApi for AlpacaExchange {
         let type_ = match order.order_type {
             OrderType::Market => "market",
             OrderType::Limit => "limit",
+            OrderType::StopMarket if self.trading_mode.eq_ignore_ascii_case("crypto") => {
+                return Err(AutoHedgeError::Unsupported(
+                    "Alpaca crypto has no stop-market orders (use stop_limit)".to_string(),
+                ));
+            }
+            OrderType::StopMarket => "stop",
+            OrderType::StopLimit => "stop_limit",
         };
 
-        let time_in_force = match order.time_in_force {
-            TimeInForce::Day => "day",
-            TimeInForce::Gtc => "gtc",
-            TimeInForce::Ioc => "ioc",
-        };
+        let time_in_force = alpaca_time_in_force(order.time_in_force);
+        let client_order_id = order.client_order_id(ALPACA_CLIENT_ID_LEN);
 
         let api_req = AlpacaOrderRequest {
             symbol: order.symbol,
@@ -134,28 +231,131 @@ impl TradingApi for AlpacaExchange {
             type_: type_.to_string(),
             time_in_force: time_in_force.to_string(),
             limit_price: order.limit_price.map(|p| p.to_string()),
+            stop_price: order.stop_price.map(|p| p.to_string()),
+            order_class: order.bracket.map(|_| "bracket".to_string()),
+            take_profit: order.bracket.map(|b| AlpacaOrderLeg {
+                limit_price: Some(b.take_profit.to_string()),
+                stop_price: None,
+            }),
+            stop_loss: order.bracket.map(|b| AlpacaOrderLeg {
+                limit_price: None,
+                stop_price: Some(b.stop_loss.to_string()),
+            }),
+            client_order_id,
         };
 
         let raw: Value = self.inner.submit_order(api_req, &self.trading_mode).await?;
-        let id = raw
-            .get("id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-        let status = raw
-            .get("status")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
+        Ok(alpaca_order_ack(raw))
+    }
+
+    async fn submit_bracket_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        if !self.capabilities().supports_bracket_orders {
+            return Err(AutoHedgeError::Unsupported(
+                "Alpaca bracket orders are only available for equities".to_string(),
+            ));
+        }
+        self.submit_order(PlaceOrderRequest {
+            bracket: Some(legs),
+            ..order
+        })
+        .await
+    }
+
+    async fn submit_oco_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        if !self.capabilities().supports_oco {
+            return Err(AutoHedgeError::Unsupported(
+                "Alpaca OCO orders are only available for equities".to_string(),
+            ));
+        }
+        let api_req = AlpacaOrderRequest {
+            symbol: order.symbol,
+            qty: order.qty.map(|q| q.to_string()),
+            notional: None,
+            side: match order.side {
+                Side::Buy => "buy",
+                Side::Sell => "sell",
+            }
+            .to_string(),
+            type_: "limit".to_string(),
+            time_in_force: alpaca_time_in_force(order.time_in_force).to_string(),
+            limit_price: None,
+            stop_price: None,
+            order_class: Some("oco".to_string()),
+            take_profit: Some(AlpacaOrderLeg {
+                limit_price: Some(legs.take_profit.to_string()),
+                stop_price: None,
+            }),
+            stop_loss: Some(AlpacaOrderLeg {
+                limit_price: None,
+                stop_price: Some(legs.stop_loss.to_string()),
+            }),
+            client_order_id: None,
+        };
+        let raw: Value = self.inner.submit_order(api_req, &self.trading_mode).await?;
+        Ok(alpaca_order_ack(raw))
+    }
+
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        let replace = AlpacaReplaceRequest {
+            qty: order.qty.map(|q| q.to_string()),
+            limit_price: order.limit_price.map(|p| p.to_string()),
+            time_in_force: alpaca_time_in_force(order.time_in_force).to_string(),
+        };
+        let raw = self.inner.replace_order(order_id, &replace).await?;
+        Ok(alpaca_order_ack(raw))
+    }
 
-        Ok(OrderAck { id, status, raw })
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        let asset = self.inner.get_asset(symbol).await?;
+        Ok(Some(symbol_meta_from_asset(symbol, &asset)))
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        let crypto = self.trading_mode.eq_ignore_ascii_case("crypto");
+        let raw = self.inner.get_latest_quote(symbol, crypto).await?;
+        Ok(top_of_book_from_latest_quote(symbol, &raw))
     }
 
     async fn get_historical_bars(&self, symbol: &str, timeframe: &str) -> ExchangeResult<Value> {
         if self.trading_mode.eq_ignore_ascii_case("crypto") {
-            Ok(self.inner.get_crypto_bars(symbol, timeframe).await?)
+            Ok(self.inner.get_crypto_bars(symbol, timeframe, None).await?)
+        } else {
+            Ok(self
+                .inner
+                .get_historical_bars(symbol, timeframe, None)
+                .await?)
+        }
+    }
+
+    async fn get_bars_since(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        let start = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+        if self.trading_mode.eq_ignore_ascii_case("crypto") {
+            Ok(self
+                .inner
+                .get_crypto_bars(symbol, timeframe, Some(&start))
+                .await?)
         } else {
-            Ok(self.inner.get_historical_bars(symbol, timeframe).await?)
+            Ok(self
+                .inner
+                .get_historical_bars(symbol, timeframe, Some(&start))
+                .await?)
         }
     }
 }
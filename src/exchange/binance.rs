@@ -1,18 +1,427 @@
-//! Binance Spot adapter (REST + WS minimal).
+//! Binance Spot and USD-M futures adapter.
+//!
+//! Private endpoints (orders, account) are signed with HMAC-SHA256 over the
+//! query string, `recvWindow` and `timestamp` included (the timestamp is
+//! corrected by the measured server clock offset). `GET`/`DELETE
+//! /api/v3/order` need the symbol as well as the id, so the adapter remembers
+//! the symbol of every order it submitted. Orders are fitted to the symbol's
+//! LOT_SIZE, PRICE_FILTER and (MIN_)NOTIONAL filters from `exchangeInfo`
+//! (cached per symbol) before they are sent.
+//!
+//! With `binance.futures.enabled` the same flow runs against the `/fapi`
+//! perpetuals API: margin type and leverage are set on a symbol before its
+//! first order, sells are `reduceOnly` so an exit can never open a short, and
+//! the account's available margin is reported as buying power.
 
 use async_trait::async_trait;
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use dashmap::{DashMap, DashSet};
+use reqwest::{Client, Method, RequestBuilder};
+use ring::hmac;
+use rust_decimal::Decimal;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
 
 use super::{
+    rate_limit::RestLimiter,
+    symbol_registry::{SymbolRegistry, Venue},
+    symbols::timeframe_minutes,
+    time_sync::{binance_server_time, ServerClock},
     traits::{ExchangeResult, TradingApi},
     types::{
-        AccountSummary, ExchangeCapabilities, OrderAck, OrderType, PlaceOrderRequest, Position,
-        Side, TimeInForce,
+        AccountSummary, BracketLegs, ExchangeCapabilities, OpenOrder, OrderAck, OrderType,
+        PlaceOrderRequest, Position, Side, SymbolMeta, TimeInForce, TopOfBook,
     },
 };
 
-use crate::config::BinanceConfig;
+use crate::config::{BinanceConfig, BinanceFuturesConfig, RateLimitConfig};
+use crate::error::AutoHedgeError;
+use crate::money;
+
+/// Request weights from the Binance Spot API docs.
+const ORDER_WEIGHT: u32 = 1;
+const QUERY_ORDER_WEIGHT: u32 = 4;
+const OPEN_ORDERS_ALL_WEIGHT: u32 = 80;
+const ACCOUNT_WEIGHT: u32 = 20;
+const EXCHANGE_INFO_WEIGHT: u32 = 20;
+const TICKER_PRICE_ALL_WEIGHT: u32 = 4;
+const KLINES_WEIGHT: u32 = 2;
+const BOOK_TICKER_WEIGHT: u32 = 2;
+const FUTURES_SETTING_WEIGHT: u32 = 1;
+const SERVER_TIME_WEIGHT: u32 = 1;
+const COUNTDOWN_CANCEL_WEIGHT: u32 = 10;
+
+/// Futures error for a margin type that is already set.
+const NO_MARGIN_TYPE_CHANGE: &str = "-4046";
+
+/// Longest `newClientOrderId` Binance accepts
+const BINANCE_CLIENT_ID_LEN: usize = 36;
+
+/// How far the server clock may drift from the request timestamp.
+const RECV_WINDOW_MS: u64 = 5000;
+
+/// Hex HMAC-SHA256 of `query`, the `signature` parameter Binance expects.
+pub fn sign_query(secret: &str, query: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::sign(&key, query.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Digits after the decimal point of a filter step ("0.00001000" -> 5).
+fn step_decimals(step: Decimal) -> usize {
+    if step <= Decimal::ZERO {
+        return 8;
+    }
+    step.normalize().scale() as usize
+}
+
+/// `value` snapped down to a multiple of `step`.
+fn floor_to_step(value: Decimal, step: Decimal) -> Decimal {
+    if step <= Decimal::ZERO {
+        return value;
+    }
+    (value / step).floor() * step
+}
+
+/// Symbol trading rules from `exchangeInfo`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SymbolFilters {
+    /// LOT_SIZE
+    pub step_size: Decimal,
+    pub min_qty: Decimal,
+    pub max_qty: Decimal,
+    /// PRICE_FILTER
+    pub tick_size: Decimal,
+    /// MIN_NOTIONAL (older symbols) or NOTIONAL
+    pub min_notional: Decimal,
+}
+
+/// Order values formatted to the symbol's precision.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FittedOrder {
+    pub quantity: Option<String>,
+    pub quote_order_qty: Option<String>,
+    pub price: Option<String>,
+    pub stop_price: Option<String>,
+}
+
+impl SymbolFilters {
+    /// Filters for `symbol` from an `exchangeInfo` response.
+    pub fn from_exchange_info(raw: &Value, symbol: &str) -> Option<Self> {
+        let info = raw
+            .get("symbols")?
+            .as_array()?
+            .iter()
+            .find(|s| s.get("symbol").and_then(|v| v.as_str()) == Some(symbol))?;
+        let mut filters = SymbolFilters::default();
+        for filter in info.get("filters")?.as_array()? {
+            let value = |key: &str| {
+                filter
+                    .get(key)
+                    .and_then(money::from_json)
+                    .unwrap_or_default()
+            };
+            match filter.get("filterType").and_then(|v| v.as_str()) {
+                Some("LOT_SIZE") => {
+                    filters.step_size = value("stepSize");
+                    filters.min_qty = value("minQty");
+                    filters.max_qty = value("maxQty");
+                }
+                Some("PRICE_FILTER") => filters.tick_size = value("tickSize"),
+                // Futures MIN_NOTIONAL carries the minimum as "notional"
+                Some("MIN_NOTIONAL") | Some("NOTIONAL") => {
+                    filters.min_notional = filters
+                        .min_notional
+                        .max(value("minNotional"))
+                        .max(value("notional"))
+                }
+                _ => {}
+            }
+        }
+        Some(filters)
+    }
+
+    /// Snap `order` to the lot step and tick size. Orders that end up below the
+    /// minimum quantity or notional are rejected here rather than by the venue.
+    pub fn fit(&self, order: &PlaceOrderRequest) -> ExchangeResult<FittedOrder> {
+        let too_small = |what: &str| {
+            AutoHedgeError::exchange(format!(
+                "Binance {} order for {} below {}",
+                match order.side {
+                    Side::Buy => "BUY",
+                    Side::Sell => "SELL",
+                },
+                order.symbol,
+                what
+            ))
+        };
+
+        let price = match order.order_type {
+            OrderType::Limit | OrderType::StopLimit => {
+                let limit = order.limit_price.ok_or_else(|| {
+                    AutoHedgeError::Config("Binance LIMIT order needs a limit_price".to_string())
+                })?;
+                Some(floor_to_step(limit, self.tick_size))
+            }
+            OrderType::Market | OrderType::StopMarket => None,
+        };
+        let stop_price = match order.order_type {
+            OrderType::StopMarket | OrderType::StopLimit => {
+                let stop = order.stop_price.ok_or_else(|| {
+                    AutoHedgeError::Config("Binance stop order needs a stop_price".to_string())
+                })?;
+                Some(floor_to_step(stop, self.tick_size))
+            }
+            OrderType::Market | OrderType::Limit => None,
+        };
+
+        let qty = match order.qty {
+            Some(qty) => {
+                let mut qty = floor_to_step(qty, self.step_size);
+                if self.max_qty > Decimal::ZERO {
+                    qty = qty.min(self.max_qty);
+                }
+                if qty <= Decimal::ZERO || qty < self.min_qty {
+                    return Err(too_small(&format!("LOT_SIZE minQty {}", self.min_qty)));
+                }
+                Some(qty)
+            }
+            None => None,
+        };
+
+        let notional = match (qty, price.or(stop_price), order.notional) {
+            (Some(q), Some(p), _) => Some(q * p),
+            (None, _, Some(n)) => Some(n),
+            _ => None,
+        };
+        if notional.is_some_and(|n| n < self.min_notional) {
+            return Err(too_small(&format!("minNotional {}", self.min_notional)));
+        }
+        if qty.is_none() && order.notional.is_none() {
+            return Err(AutoHedgeError::Config(
+                "Binance order needs qty or notional".to_string(),
+            ));
+        }
+
+        Ok(FittedOrder {
+            quantity: qty.map(|q| format!("{:.*}", step_decimals(self.step_size), q)),
+            quote_order_qty: match qty {
+                Some(_) => None,
+                None => order.notional.map(|n| format!("{:.2}", n)),
+            },
+            price: price.map(|p| format!("{:.*}", step_decimals(self.tick_size), p)),
+            stop_price: stop_price.map(|p| format!("{:.*}", step_decimals(self.tick_size), p)),
+        })
+    }
+}
+
+/// Free + locked balance per asset from `GET /api/v3/account`.
+fn balances(raw: &Value) -> Vec<(String, Decimal, Decimal)> {
+    raw.get("balances")
+        .and_then(|b| b.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|b| {
+            Some((
+                b.get("asset")?.as_str()?.to_string(),
+                b.get("free").and_then(money::from_json).unwrap_or_default(),
+                b.get("locked")
+                    .and_then(money::from_json)
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Account summary: free `quote` balance as cash and buying power; holdings
+/// valued at `prices` (keyed by Binance symbol, e.g. "BTCUSDT") for the total.
+pub fn account_from_balances(
+    raw: &Value,
+    prices: &HashMap<String, Decimal>,
+    quote: &str,
+) -> AccountSummary {
+    let mut cash = Decimal::ZERO;
+    let mut portfolio_value = Decimal::ZERO;
+    for (asset, free, locked) in balances(raw) {
+        if asset == quote {
+            cash = free;
+            portfolio_value += free + locked;
+        } else if let Some(price) = prices.get(&format!("{}{}", asset, quote)) {
+            portfolio_value += (free + locked) * price;
+        }
+    }
+    AccountSummary {
+        buying_power: Some(cash),
+        cash: Some(cash),
+        portfolio_value: Some(portfolio_value),
+    }
+}
+
+/// Non-zero holdings other than `quote`, as canonical "ASSET/QUOTE" positions.
+pub fn positions_from_balances(raw: &Value, quote: &str) -> Vec<Position> {
+    balances(raw)
+        .into_iter()
+        .filter(|(asset, free, locked)| asset != quote && free + locked > Decimal::ZERO)
+        .map(|(asset, free, locked)| Position {
+            symbol: format!("{}/{}", asset, quote),
+            qty: free + locked,
+            avg_entry_price: None,
+        })
+        .collect()
+}
+
+/// Futures account summary from `GET /fapi/v2/account`: available margin as
+/// buying power, wallet balance as cash and margin balance (wallet plus
+/// unrealized PnL) as portfolio value.
+pub fn futures_account(raw: &Value) -> AccountSummary {
+    let field = |key: &str| raw.get(key).and_then(money::from_json);
+    AccountSummary {
+        buying_power: field("availableBalance"),
+        cash: field("totalWalletBalance"),
+        portfolio_value: field("totalMarginBalance"),
+    }
+}
+
+/// Open perpetual positions from `GET /fapi/v2/account`, as "BASE/QUOTE"
+/// symbols for contracts margined in `quote`.
+pub fn futures_positions(raw: &Value, quote: &str) -> Vec<Position> {
+    raw.get("positions")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| {
+            let symbol = p.get("symbol")?.as_str()?;
+            let qty = p.get("positionAmt").and_then(money::from_json)?;
+            if qty == Decimal::ZERO {
+                return None;
+            }
+            Some(Position {
+                symbol: format!("{}/{}", symbol.strip_suffix(quote)?, quote),
+                qty,
+                avg_entry_price: p
+                    .get("entryPrice")
+                    .and_then(money::from_json)
+                    .filter(|p| *p > Decimal::ZERO),
+            })
+        })
+        .collect()
+}
+
+/// Order response as an `OrderAck`: lowercase status ("new", "partially_filled",
+/// "filled", "canceled", ...) and `executedQty` copied to `filled_qty`, the
+/// field the position monitor reads.
+/// Best bid/ask from `GET /api/v3/ticker/bookTicker?symbol=`
+/// (`{"symbol", "bidPrice", "bidQty", "askPrice", "askQty"}`).
+pub fn top_of_book_from_book_ticker(raw: &Value) -> Option<TopOfBook> {
+    TopOfBook::new(
+        raw.get("bidPrice").and_then(money::from_json),
+        raw.get("askPrice").and_then(money::from_json),
+    )
+}
+
+pub fn order_ack(mut raw: Value) -> OrderAck {
+    let id = raw
+        .get("orderId")
+        .and_then(|v| v.as_i64())
+        .map(|i| i.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let status = raw
+        .get("status")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_lowercase();
+    // Futures report `avgPrice`; spot only the quote amount traded
+    let executed = raw.get("executedQty").and_then(money::from_json);
+    let avg_price = raw
+        .get("avgPrice")
+        .and_then(money::from_json)
+        .filter(|p| *p > Decimal::ZERO)
+        .or_else(|| {
+            let quote = raw.get("cummulativeQuoteQty").and_then(money::from_json)?;
+            executed.filter(|q| *q > Decimal::ZERO).map(|q| quote / q)
+        });
+    if let Some(obj) = raw.as_object_mut() {
+        if let Some(executed) = obj.get("executedQty").cloned() {
+            obj.insert("filled_qty".to_string(), executed);
+        }
+        if let Some(avg_price) = avg_price {
+            obj.insert(
+                "filled_avg_price".to_string(),
+                Value::from(money::float(avg_price)),
+            );
+        }
+    }
+    OrderAck { id, status, raw }
+}
+
+/// Orders of an `openOrders` response (spot and futures: `[{"symbol",
+/// "orderId", "side": "BUY", "time": <ms>, ...}]`).
+pub fn open_orders(raw: &Value) -> Vec<OpenOrder> {
+    raw.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|o| {
+            Some(OpenOrder {
+                id: o.get("orderId")?.as_i64()?.to_string(),
+                symbol: o.get("symbol")?.as_str()?.to_string(),
+                side: o
+                    .get("side")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or_default()
+                    .to_lowercase(),
+                created_at: o
+                    .get("time")
+                    .and_then(|t| t.as_i64())
+                    .and_then(DateTime::from_timestamp_millis),
+            })
+        })
+        .collect()
+}
+
+fn binance_side(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    }
+}
+
+/// Kline interval ("1m", "4h", "1d") of an Alpaca-style timeframe.
+fn kline_interval(timeframe: &str) -> ExchangeResult<String> {
+    match timeframe_minutes(timeframe) {
+        Some(m) if m % 1440 == 0 => Ok(format!("{}d", m / 1440)),
+        Some(m) if m % 60 == 0 => Ok(format!("{}h", m / 60)),
+        Some(m) => Ok(format!("{}m", m)),
+        None => Err(AutoHedgeError::Config(format!(
+            "Unsupported timeframe '{}'",
+            timeframe
+        ))),
+    }
+}
+
+/// The new order of a spot `POST /api/v3/order/cancelReplace` response
+/// (`{"cancelResult", "newOrderResult", "cancelResponse", "newOrderResponse"}`).
+pub fn cancel_replace_new_order(raw: &Value) -> Option<Value> {
+    if raw.get("newOrderResult").and_then(|r| r.as_str()) != Some("SUCCESS") {
+        return None;
+    }
+    raw.get("newOrderResponse").cloned()
+}
+
+/// Leg order ids of a spot `POST /api/v3/orderList/oco` response
+/// (`{"orderListId", "listOrderStatus", "orders": [{"symbol", "orderId"}, ...]}`).
+pub fn oco_order_ids(raw: &Value) -> Vec<String> {
+    raw.get("orders")
+        .and_then(|o| o.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|o| o.get("orderId")?.as_i64().map(|id| id.to_string()))
+        .collect()
+}
 
 #[derive(Clone)]
 pub struct BinanceExchange {
@@ -20,23 +429,239 @@ pub struct BinanceExchange {
     base_url: String,
     api_key: String,
     api_secret: String,
+    quote_asset: String,
+    limiter: RestLimiter,
+    /// Trading rules per Binance symbol, fetched on first order
+    filters: Arc<DashMap<String, SymbolFilters>>,
+    /// Binance symbol of each order id submitted through this adapter
+    order_symbols: Arc<DashMap<String, String>>,
+    /// USD-M futures mode
+    futures: Option<BinanceFuturesConfig>,
+    /// Futures symbols whose margin type and leverage are set
+    leveraged: Arc<DashSet<String>>,
+    /// Request timestamps, corrected by the measured server offset
+    clock: ServerClock,
+    /// Canonical <-> Binance symbols ("BTC/USDT" <-> "BTCUSDT")
+    symbols: SymbolRegistry,
 }
 
 impl BinanceExchange {
     pub fn new(config: BinanceConfig) -> Self {
+        let futures = Some(config.futures).filter(|f| f.enabled);
         Self {
             client: Client::new(),
-            base_url: config.base_url,
+            base_url: match &futures {
+                Some(f) => f.base_url.clone(),
+                None => config.base_url,
+            },
             api_key: config.api_key,
             api_secret: config.secret_key,
+            quote_asset: config.quote_asset,
+            limiter: RestLimiter::binance(&RateLimitConfig::default()),
+            filters: Arc::new(DashMap::new()),
+            order_symbols: Arc::new(DashMap::new()),
+            futures,
+            leveraged: Arc::new(DashSet::new()),
+            clock: ServerClock::new(),
+            symbols: SymbolRegistry::new(Venue::Binance, &[]),
+        }
+    }
+
+    /// `spot` or, in futures mode, `futures`.
+    fn path(&self, spot: &'static str, futures: &'static str) -> &'static str {
+        if self.futures.is_some() {
+            futures
+        } else {
+            spot
         }
     }
 
-    fn auth_headers(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        // Proper Binance signing requires HMAC SHA256 query signing.
-        // Placeholder header for compile-time wiring.
-        req.header("X-MBX-APIKEY", &self.api_key)
-            .header("X-MBX-APISECRET", &self.api_secret)
+    /// Throttle REST calls with `limiter` (shared by clones of this adapter).
+    pub fn with_rate_limiter(mut self, limiter: RestLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Translate symbols through the session's shared registry.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Signed request; the timestamp is taken (on the server clock) when the
+    /// request is built, so re-sends after a 429 are signed afresh.
+    fn signed(&self, method: Method, path: &str, params: &[(&str, String)]) -> RequestBuilder {
+        let mut query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        query.push(format!("recvWindow={}", RECV_WINDOW_MS));
+        query.push(format!("timestamp={}", self.clock.now_ms()));
+        let query = query.join("&");
+        let signature = sign_query(&self.api_secret, &query);
+        self.client
+            .request(
+                method,
+                format!(
+                    "{}{}?{}&signature={}",
+                    self.base_url, path, query, signature
+                ),
+            )
+            .header("X-MBX-APIKEY", &self.api_key)
+    }
+
+    /// Send a request built by `build` and decode its JSON body.
+    async fn send_json(
+        &self,
+        weight: u32,
+        what: &str,
+        build: impl Fn() -> RequestBuilder,
+    ) -> ExchangeResult<Value> {
+        let resp = self.limiter.send(weight, build).await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Binance {} failed ({}): {}", what, status, text),
+            ));
+        }
+        serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Binance {} decode failed: {} (body: {})",
+                what, e, text
+            ))
+        })
+    }
+
+    async fn symbol_filters(&self, symbol: &str) -> ExchangeResult<SymbolFilters> {
+        if let Some(filters) = self.filters.get(symbol) {
+            return Ok(filters.clone());
+        }
+        let endpoint = format!(
+            "{}{}?symbol={}",
+            self.base_url,
+            self.path("/api/v3/exchangeInfo", "/fapi/v1/exchangeInfo"),
+            symbol
+        );
+        let raw = self
+            .send_json(EXCHANGE_INFO_WEIGHT, "exchangeInfo", || {
+                self.client.get(&endpoint)
+            })
+            .await?;
+        let filters = SymbolFilters::from_exchange_info(&raw, symbol).ok_or_else(|| {
+            AutoHedgeError::exchange(format!("Binance exchangeInfo has no {}", symbol))
+        })?;
+        self.filters.insert(symbol.to_string(), filters.clone());
+        Ok(filters)
+    }
+
+    fn symbol_for_order(&self, order_id: &str) -> ExchangeResult<String> {
+        self.order_symbols
+            .get(order_id)
+            .map(|s| s.clone())
+            .ok_or_else(|| {
+                AutoHedgeError::exchange(format!(
+                    "Binance order {} was not submitted by this session (symbol unknown)",
+                    order_id
+                ))
+            })
+    }
+
+    /// `type` (and `timeInForce`) of `order`.
+    fn order_type_params(&self, order: &PlaceOrderRequest) -> Vec<(&'static str, String)> {
+        match order.order_type {
+            OrderType::Market => vec![("type", "MARKET".to_string())],
+            // Spot post-only is its own order type (no time in force)
+            OrderType::Limit
+                if self.futures.is_none()
+                    && matches!(order.time_in_force, TimeInForce::PostOnly) =>
+            {
+                vec![("type", "LIMIT_MAKER".to_string())]
+            }
+            OrderType::Limit => {
+                // Spot has no DAY orders; GTX is the futures post-only TIF
+                let tif = match order.time_in_force {
+                    TimeInForce::Ioc => "IOC",
+                    TimeInForce::Day | TimeInForce::Gtc => "GTC",
+                    TimeInForce::PostOnly => "GTX",
+                };
+                vec![
+                    ("type", "LIMIT".to_string()),
+                    ("timeInForce", tif.to_string()),
+                ]
+            }
+            OrderType::StopMarket => {
+                let type_ = if self.futures.is_some() {
+                    "STOP_MARKET"
+                } else {
+                    "STOP_LOSS"
+                };
+                vec![("type", type_.to_string())]
+            }
+            OrderType::StopLimit => {
+                let type_ = if self.futures.is_some() {
+                    "STOP"
+                } else {
+                    "STOP_LOSS_LIMIT"
+                };
+                vec![
+                    ("type", type_.to_string()),
+                    ("timeInForce", "GTC".to_string()),
+                ]
+            }
+        }
+    }
+
+    async fn account(&self) -> ExchangeResult<Value> {
+        let path = self.path("/api/v3/account", "/fapi/v2/account");
+        self.send_json(ACCOUNT_WEIGHT, "account", || {
+            self.signed(Method::GET, path, &[])
+        })
+        .await
+    }
+
+    /// Set margin type and `leverage` on a futures symbol once per session.
+    /// An unchanged margin type is reported as an error by Binance and ignored.
+    async fn prepare_futures_symbol(
+        &self,
+        symbol: &str,
+        leverage: u32,
+        futures: &BinanceFuturesConfig,
+    ) -> ExchangeResult<()> {
+        if self.leveraged.contains(symbol) {
+            return Ok(());
+        }
+        let params = [
+            ("symbol", symbol.to_string()),
+            ("marginType", futures.margin_type.to_uppercase()),
+        ];
+        if let Err(e) = self
+            .send_json(FUTURES_SETTING_WEIGHT, "marginType", || {
+                self.signed(Method::POST, "/fapi/v1/marginType", &params)
+            })
+            .await
+        {
+            if !e.to_string().contains(NO_MARGIN_TYPE_CHANGE) {
+                warn!(
+                    "⚠️ [BINANCE] Could not set {} margin on {}: {}",
+                    futures.margin_type, symbol, e
+                );
+            }
+        }
+        let params = [
+            ("symbol", symbol.to_string()),
+            ("leverage", leverage.to_string()),
+        ];
+        self.send_json(FUTURES_SETTING_WEIGHT, "leverage", || {
+            self.signed(Method::POST, "/fapi/v1/leverage", &params)
+        })
+        .await?;
+        info!(
+            "⚙️ [BINANCE] {} set to {}x {} margin",
+            symbol,
+            leverage,
+            futures.margin_type.to_uppercase()
+        );
+        self.leveraged.insert(symbol.to_string());
+        Ok(())
     }
 }
 
@@ -48,81 +673,410 @@ impl TradingApi for BinanceExchange {
 
     fn capabilities(&self) -> ExchangeCapabilities {
         ExchangeCapabilities {
-            supports_notional_market_buy: true,
+            // USD-M has no quoteOrderQty
+            supports_notional_market_buy: self.futures.is_none(),
             supports_ws_quotes: true,
             supports_ws_trades: true,
             supports_news: false,
+            supports_bracket_orders: false,
+            // Spot cancelReplace, futures order modify
+            supports_order_replace: true,
+            // Spot STOP_LOSS, futures STOP_MARKET
+            supports_stop_orders: true,
+            // Spot orderList/oco
+            supports_oco: self.futures.is_none(),
+            // Futures sells are reduceOnly (long-only)
+            supports_short: false,
+            min_notional: Decimal::new(5, 0),
         }
     }
 
     async fn get_account(&self) -> ExchangeResult<AccountSummary> {
-        Ok(AccountSummary {
-            buying_power: None,
-            cash: None,
-            portfolio_value: None,
-        })
+        let raw = self.account().await?;
+        if self.futures.is_some() {
+            return Ok(futures_account(&raw));
+        }
+        let endpoint = format!("{}/api/v3/ticker/price", self.base_url);
+        let tickers = self
+            .send_json(TICKER_PRICE_ALL_WEIGHT, "ticker/price", || {
+                self.client.get(&endpoint)
+            })
+            .await?;
+        let prices: HashMap<String, Decimal> = tickers
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| {
+                Some((
+                    t.get("symbol")?.as_str()?.to_string(),
+                    t.get("price").and_then(money::from_json)?,
+                ))
+            })
+            .collect();
+        Ok(account_from_balances(&raw, &prices, &self.quote_asset))
     }
 
     async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
-        // Placeholder
-        Ok(vec![])
+        let raw = self.account().await?;
+        if self.futures.is_some() {
+            return Ok(self
+                .symbols
+                .canonical_positions(futures_positions(&raw, &self.quote_asset)));
+        }
+        Ok(self
+            .symbols
+            .canonical_positions(positions_from_balances(&raw, &self.quote_asset)))
     }
 
-    async fn get_order(&self, _order_id: &str) -> ExchangeResult<OrderAck> {
-        Err("Binance get_order not implemented".into())
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        let symbol = self.symbol_for_order(order_id)?;
+        let params = [("symbol", symbol), ("orderId", order_id.to_string())];
+        let path = self.path("/api/v3/order", "/fapi/v1/order");
+        let raw = self
+            .send_json(QUERY_ORDER_WEIGHT, "get_order", || {
+                self.signed(Method::GET, path, &params)
+            })
+            .await?;
+        Ok(order_ack(raw))
     }
 
-    async fn cancel_order(&self, _order_id: &str) -> ExchangeResult<()> {
-        Err("Binance cancel_order not implemented".into())
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        let symbol = self.symbol_for_order(order_id)?;
+        let params = [("symbol", symbol), ("orderId", order_id.to_string())];
+        let path = self.path("/api/v3/order", "/fapi/v1/order");
+        self.send_json(ORDER_WEIGHT, "cancel_order", || {
+            self.signed(Method::DELETE, path, &params)
+        })
+        .await?;
+        self.order_symbols.remove(order_id);
+        Ok(())
     }
 
     async fn cancel_all_orders(&self) -> ExchangeResult<()> {
-        Err("Binance cancel_all_orders not implemented".into())
+        let open_path = self.path("/api/v3/openOrders", "/fapi/v1/openOrders");
+        let open = self
+            .send_json(OPEN_ORDERS_ALL_WEIGHT, "openOrders", || {
+                self.signed(Method::GET, open_path, &[])
+            })
+            .await?;
+        let mut symbols: Vec<String> = open
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|o| o.get("symbol").and_then(|s| s.as_str()).map(str::to_string))
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        let cancel_path = self.path("/api/v3/openOrders", "/fapi/v1/allOpenOrders");
+        for symbol in symbols {
+            let params = [("symbol", symbol)];
+            self.send_json(ORDER_WEIGHT, "cancel_all_orders", || {
+                self.signed(Method::DELETE, cancel_path, &params)
+            })
+            .await?;
+        }
+        self.order_symbols.clear();
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        let path = self.path("/api/v3/openOrders", "/fapi/v1/openOrders");
+        let raw = self
+            .send_json(OPEN_ORDERS_ALL_WEIGHT, "openOrders", || {
+                self.signed(Method::GET, path, &[])
+            })
+            .await?;
+        let orders = open_orders(&raw);
+        // So they can be cancelled by id
+        for order in &orders {
+            self.order_symbols
+                .insert(order.id.clone(), order.symbol.clone());
+        }
+        Ok(Some(self.symbols.canonical_orders(orders)))
     }
 
-    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
-        // Minimal placeholder. Real Binance endpoint is POST /api/v3/order with signed query.
-        let endpoint = format!("{}/api/v3/order", self.base_url);
-        let _tif = match order.time_in_force {
-            TimeInForce::Day => "DAY",
-            TimeInForce::Gtc => "GTC",
-            TimeInForce::Ioc => "IOC",
+    /// USD-M futures auto-cancel (`countdownCancelAll`) on every symbol traded
+    /// this session; spot has no such switch.
+    async fn cancel_orders_after(&self, timeout_secs: u64) -> ExchangeResult<bool> {
+        if self.futures.is_none() {
+            return Ok(false);
+        }
+        let symbols: Vec<String> = self.leveraged.iter().map(|s| s.clone()).collect();
+        for symbol in symbols {
+            let params = [
+                ("symbol", symbol),
+                ("countdownTime", (timeout_secs * 1000).to_string()),
+            ];
+            self.send_json(COUNTDOWN_CANCEL_WEIGHT, "countdownCancelAll", || {
+                self.signed(Method::POST, "/fapi/v1/countdownCancelAll", &params)
+            })
+            .await?;
+        }
+        Ok(true)
+    }
+
+    async fn submit_order(&self, mut order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
+        let symbol = self.symbols.native(&order.symbol);
+        if let Some(futures) = &self.futures {
+            let leverage = futures.leverage_for(&order.symbol);
+            self.prepare_futures_symbol(&symbol, leverage, futures)
+                .await?;
+            // No quoteOrderQty on USD-M: size notional orders off the limit or the ask
+            if let (None, Some(notional)) = (order.qty, order.notional) {
+                let price = match (order.order_type, order.limit_price) {
+                    (OrderType::Limit, Some(limit)) => Some(limit),
+                    _ => self
+                        .get_best_bid_ask(&order.symbol)
+                        .await?
+                        .map(|book| book.ask),
+                };
+                let price = price.filter(|p| *p > Decimal::ZERO).ok_or_else(|| {
+                    AutoHedgeError::exchange(format!(
+                        "Binance futures has no price to size {} notional order",
+                        order.symbol
+                    ))
+                })?;
+                order.qty = Some(notional / price);
+                order.notional = None;
+            }
+        }
+        let fitted = self.symbol_filters(&symbol).await?.fit(&order)?;
+
+        let mut params = vec![
+            ("symbol", symbol.clone()),
+            ("side", binance_side(order.side).to_string()),
+        ];
+        params.extend(self.order_type_params(&order));
+        if let Some(quantity) = fitted.quantity {
+            params.push(("quantity", quantity));
+        }
+        if let Some(quote_qty) = fitted.quote_order_qty {
+            params.push(("quoteOrderQty", quote_qty));
+        }
+        if let Some(price) = fitted.price {
+            params.push(("price", price));
+        }
+        if let Some(stop_price) = fitted.stop_price {
+            params.push(("stopPrice", stop_price));
+        }
+        // Long-only: a futures sell may only close, never open a short
+        if self.futures.is_some() && matches!(order.side, Side::Sell) {
+            params.push(("reduceOnly", "true".to_string()));
+        }
+        if let Some(id) = order.client_order_id(BINANCE_CLIENT_ID_LEN) {
+            params.push(("newClientOrderId", id));
+        }
+        params.push(("newOrderRespType", "RESULT".to_string()));
+
+        let path = self.path("/api/v3/order", "/fapi/v1/order");
+        let raw = self
+            .send_json(ORDER_WEIGHT, "submit_order", || {
+                self.signed(Method::POST, path, &params)
+            })
+            .await?;
+        let ack = order_ack(raw);
+        self.order_symbols.insert(ack.id.clone(), symbol);
+        Ok(ack)
+    }
+
+    async fn submit_oco_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        if self.futures.is_some() {
+            return Err(AutoHedgeError::Unsupported(
+                "Binance futures has no OCO orders".to_string(),
+            ));
+        }
+        if !matches!(order.side, Side::Sell) {
+            return Err(AutoHedgeError::Config(
+                "Binance OCO exits are sells (take-profit above, stop below)".to_string(),
+            ));
+        }
+        let symbol = self.symbols.native(&order.symbol);
+        let filters = self.symbol_filters(&symbol).await?;
+        let take_profit = filters.fit(&PlaceOrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(legs.take_profit),
+            stop_price: None,
+            ..order.clone()
+        })?;
+        let stop = filters.fit(&PlaceOrderRequest {
+            order_type: OrderType::StopMarket,
+            limit_price: None,
+            stop_price: Some(legs.stop_loss),
+            ..order
+        })?;
+        let (Some(quantity), Some(above), Some(below)) =
+            (take_profit.quantity, take_profit.price, stop.stop_price)
+        else {
+            return Err(AutoHedgeError::Config(
+                "Binance OCO order needs a qty".to_string(),
+            ));
         };
-        let _side = match order.side {
-            Side::Buy => "BUY",
-            Side::Sell => "SELL",
+        let params = [
+            ("symbol", symbol.clone()),
+            ("side", "SELL".to_string()),
+            ("quantity", quantity),
+            ("aboveType", "LIMIT_MAKER".to_string()),
+            ("abovePrice", above),
+            ("belowType", "STOP_LOSS".to_string()),
+            ("belowStopPrice", below),
+            ("newOrderRespType", "RESULT".to_string()),
+        ];
+        let raw = self
+            .send_json(ORDER_WEIGHT, "orderList/oco", || {
+                self.signed(Method::POST, "/api/v3/orderList/oco", &params)
+            })
+            .await?;
+        let ids = oco_order_ids(&raw);
+        let Some(first) = ids.first().cloned() else {
+            return Err(AutoHedgeError::exchange(format!(
+                "Binance OCO for {} returned no orders: {}",
+                symbol, raw
+            )));
         };
-        let _type = match order.order_type {
-            OrderType::Market => "MARKET",
-            OrderType::Limit => "LIMIT",
+        for id in ids {
+            self.order_symbols.insert(id, symbol.clone());
+        }
+        Ok(OrderAck {
+            id: first,
+            status: "new".to_string(),
+            raw,
+        })
+    }
+
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        let symbol = self.symbol_for_order(order_id)?;
+        let fitted = self.symbol_filters(&symbol).await?.fit(&order)?;
+        let (Some(quantity), Some(price)) = (fitted.quantity, fitted.price) else {
+            return Err(AutoHedgeError::Config(
+                "Binance replace_order needs a LIMIT order with a qty".to_string(),
+            ));
         };
+        let side = binance_side(order.side).to_string();
 
-        let resp = self
-            .auth_headers(self.client.post(&endpoint))
-            .send()
+        let raw = if self.futures.is_some() {
+            // Modify keeps the order id (and its queue position when only qty shrinks)
+            let params = [
+                ("symbol", symbol.clone()),
+                ("orderId", order_id.to_string()),
+                ("side", side),
+                ("quantity", quantity),
+                ("price", price),
+            ];
+            self.send_json(ORDER_WEIGHT, "modify_order", || {
+                self.signed(Method::PUT, "/fapi/v1/order", &params)
+            })
+            .await?
+        } else {
+            // STOP_ON_FAILURE: no new order unless the old one was cancelled
+            let mut params = vec![("symbol", symbol.clone()), ("side", side)];
+            params.extend(self.order_type_params(&order));
+            params.extend([
+                ("cancelReplaceMode", "STOP_ON_FAILURE".to_string()),
+                ("cancelOrderId", order_id.to_string()),
+                ("quantity", quantity),
+                ("price", price),
+                ("newOrderRespType", "RESULT".to_string()),
+            ]);
+            let raw = self
+                .send_json(ORDER_WEIGHT, "cancelReplace", || {
+                    self.signed(Method::POST, "/api/v3/order/cancelReplace", &params)
+                })
+                .await?;
+            cancel_replace_new_order(&raw).ok_or_else(|| {
+                AutoHedgeError::exchange(format!(
+                    "Binance cancelReplace of {} placed no new order: {}",
+                    order_id, raw
+                ))
+            })?
+        };
+        let ack = order_ack(raw);
+        if ack.id != order_id {
+            self.order_symbols.remove(order_id);
+        }
+        self.order_symbols.insert(ack.id.clone(), symbol);
+        Ok(ack)
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        let filters = self.symbol_filters(&self.symbols.native(symbol)).await?;
+        Ok(Some(SymbolMeta {
+            symbol: symbol.to_string(),
+            tick_size: filters.tick_size,
+            step_size: filters.step_size,
+            min_qty: filters.min_qty,
+            min_notional: filters.min_notional,
+        }))
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        let endpoint = format!(
+            "{}{}?symbol={}",
+            self.base_url,
+            self.path("/api/v3/ticker/bookTicker", "/fapi/v1/ticker/bookTicker"),
+            self.symbols.native(symbol)
+        );
+        let raw = self
+            .send_json(BOOK_TICKER_WEIGHT, "bookTicker", || {
+                self.client.get(&endpoint)
+            })
             .await?;
-        let status = resp.status();
-        let text = resp.text().await?;
-        if !status.is_success() {
-            return Err(format!("Binance submit_order failed ({}): {}", status, text).into());
-        }
-        let raw: Value = serde_json::from_str(&text)
-            .map_err(|e| format!("Binance submit_order decode failed: {} (body: {})", e, text))?;
-
-        let id = raw
-            .get("orderId")
-            .and_then(|v| v.as_i64())
-            .map(|i| i.to_string())
-            .unwrap_or_else(|| "unknown".to_string());
-        let status = raw
-            .get("status")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-        Ok(OrderAck { id, status, raw })
-    }
-
-    async fn get_historical_bars(&self, _symbol: &str, _timeframe: &str) -> ExchangeResult<Value> {
-        Ok(Value::Null)
+        Ok(top_of_book_from_book_ticker(&raw))
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        Some(self.clock.clone())
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        let endpoint = format!(
+            "{}{}",
+            self.base_url,
+            self.path("/api/v3/time", "/fapi/v1/time")
+        );
+        let raw = self
+            .send_json(SERVER_TIME_WEIGHT, "time", || self.client.get(&endpoint))
+            .await?;
+        binance_server_time(&raw).map(Some).ok_or_else(|| {
+            AutoHedgeError::exchange(format!("Binance time has no serverTime: {}", raw))
+        })
+    }
+
+    async fn get_historical_bars(&self, symbol: &str, timeframe: &str) -> ExchangeResult<Value> {
+        // Public klines: [[open_time, "o", "h", "l", "c", "v", close_time, ...], ...]
+        let endpoint = format!(
+            "{}{}?symbol={}&interval={}&limit=100",
+            self.base_url,
+            self.path("/api/v3/klines", "/fapi/v1/klines"),
+            self.symbols.native(symbol),
+            kline_interval(timeframe)?
+        );
+        self.send_json(KLINES_WEIGHT, "klines", || self.client.get(&endpoint))
+            .await
+    }
+
+    async fn get_bars_since(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        let endpoint = format!(
+            "{}{}?symbol={}&interval={}&startTime={}&limit=1000",
+            self.base_url,
+            self.path("/api/v3/klines", "/fapi/v1/klines"),
+            self.symbols.native(symbol),
+            kline_interval(timeframe)?,
+            since.timestamp_millis()
+        );
+        self.send_json(KLINES_WEIGHT, "klines", || self.client.get(&endpoint))
+            .await
     }
 }
@@ -0,0 +1,489 @@
+//! Unit tests for the Binance Spot adapter (signing, symbol filters, account mapping).
+
+#[cfg(test)]
+mod binance_tests {
+    use crate::config::{BinanceConfig, BinanceFuturesConfig};
+    use crate::exchange::binance::*;
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::{BracketLegs, OrderType, PlaceOrderRequest, Side, TimeInForce};
+    use crate::money::dec;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn filters() -> SymbolFilters {
+        SymbolFilters {
+            step_size: dec(0.00001),
+            min_qty: dec(0.00001),
+            max_qty: dec(9000.0),
+            tick_size: dec(0.01),
+            min_notional: dec(5.0),
+        }
+    }
+
+    fn order(
+        order_type: OrderType,
+        qty: Option<Decimal>,
+        notional: Option<Decimal>,
+    ) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Buy,
+            order_type,
+            qty,
+            notional,
+            limit_price: Some(dec(60_000.126)),
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Signing Tests =============
+
+    #[test]
+    fn test_sign_query_matches_binance_docs() {
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+        assert_eq!(
+            sign_query(secret, query),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    // ============= Symbol Filter Tests =============
+
+    #[test]
+    fn test_filters_from_exchange_info() {
+        let raw = json!({"symbols": [{
+            "symbol": "BTCUSDT",
+            "filters": [
+                {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01000000"},
+                {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+                {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true},
+            ]
+        }]});
+        assert_eq!(
+            SymbolFilters::from_exchange_info(&raw, "BTCUSDT"),
+            Some(filters())
+        );
+        assert_eq!(SymbolFilters::from_exchange_info(&raw, "ETHUSDT"), None);
+    }
+
+    #[test]
+    fn test_filters_from_futures_exchange_info() {
+        let raw = json!({"symbols": [{
+            "symbol": "BTCUSDT",
+            "filters": [
+                {"filterType": "PRICE_FILTER", "tickSize": "0.10"},
+                {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"},
+                {"filterType": "MIN_NOTIONAL", "notional": "100"},
+            ]
+        }]});
+        let filters = SymbolFilters::from_exchange_info(&raw, "BTCUSDT").unwrap();
+        assert_eq!(filters.min_notional, dec(100.0));
+        assert_eq!(filters.step_size, dec(0.001));
+    }
+
+    #[test]
+    fn test_fit_limit_order_rounds_to_step_and_tick() {
+        let fitted = filters()
+            .fit(&order(OrderType::Limit, Some(dec(0.0123456)), None))
+            .unwrap();
+        assert_eq!(fitted.quantity.as_deref(), Some("0.01234"));
+        assert_eq!(fitted.price.as_deref(), Some("60000.12"));
+        assert_eq!(fitted.quote_order_qty, None);
+    }
+
+    #[test]
+    fn test_fit_notional_market_buy() {
+        let fitted = filters()
+            .fit(&order(OrderType::Market, None, Some(dec(25.0))))
+            .unwrap();
+        assert_eq!(fitted.quantity, None);
+        assert_eq!(fitted.quote_order_qty.as_deref(), Some("25.00"));
+        assert_eq!(fitted.price, None);
+    }
+
+    #[test]
+    fn test_fit_stop_orders_round_trigger_to_tick() {
+        let mut stop = order(OrderType::StopMarket, Some(dec(0.01)), None);
+        stop.stop_price = Some(dec(59_000.129));
+        let fitted = filters().fit(&stop).unwrap();
+        assert_eq!(fitted.stop_price.as_deref(), Some("59000.12"));
+        assert_eq!(fitted.price, None);
+
+        stop.order_type = OrderType::StopLimit;
+        let fitted = filters().fit(&stop).unwrap();
+        assert_eq!(fitted.stop_price.as_deref(), Some("59000.12"));
+        assert_eq!(fitted.price.as_deref(), Some("60000.12"));
+
+        stop.stop_price = None;
+        assert!(filters().fit(&stop).is_err());
+    }
+
+    #[test]
+    fn test_fit_rejects_below_minimums() {
+        let lot = filters()
+            .fit(&order(OrderType::Limit, Some(dec(0.000004)), None))
+            .unwrap_err();
+        assert!(lot.to_string().contains("LOT_SIZE"));
+        assert!(!lot.is_retryable());
+
+        // 0.00005 BTC at 60k is $3, under the $5 minimum
+        let notional = filters()
+            .fit(&order(OrderType::Limit, Some(dec(0.00005)), None))
+            .unwrap_err();
+        assert!(notional.to_string().contains("minNotional"));
+
+        assert!(filters()
+            .fit(&order(OrderType::Market, None, Some(dec(4.0))))
+            .is_err());
+    }
+
+    // ============= Account Mapping Tests =============
+
+    #[test]
+    fn test_account_and_positions_from_balances() {
+        let raw = json!({"balances": [
+            {"asset": "USDT", "free": "900.00", "locked": "100.00"},
+            {"asset": "BTC", "free": "0.01000000", "locked": "0.00000000"},
+            {"asset": "ETH", "free": "0.00000000", "locked": "0.00000000"},
+        ]});
+        let prices = HashMap::from([("BTCUSDT".to_string(), dec(60_000.0))]);
+
+        let account = account_from_balances(&raw, &prices, "USDT");
+        assert_eq!(account.cash, Some(dec(900.0)));
+        assert_eq!(account.buying_power, Some(dec(900.0)));
+        assert_eq!(account.portfolio_value, Some(dec(1600.0)));
+
+        let positions = positions_from_balances(&raw, "USDT");
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "BTC/USDT");
+        assert_eq!(positions[0].qty, dec(0.01));
+    }
+
+    #[test]
+    fn test_futures_account_and_positions() {
+        let raw = json!({
+            "totalWalletBalance": "1000.00",
+            "totalMarginBalance": "1012.50",
+            "availableBalance": "700.00",
+            "positions": [
+                {"symbol": "BTCUSDT", "positionAmt": "0.010", "entryPrice": "60000.0"},
+                {"symbol": "ETHUSDT", "positionAmt": "0.000", "entryPrice": "0.0"},
+                {"symbol": "BTCUSDC", "positionAmt": "0.5", "entryPrice": "61000.0"},
+            ]
+        });
+
+        let account = futures_account(&raw);
+        assert_eq!(account.buying_power, Some(dec(700.0)));
+        assert_eq!(account.cash, Some(dec(1000.0)));
+        assert_eq!(account.portfolio_value, Some(dec(1012.5)));
+
+        // Flat and other-margin contracts are skipped
+        let positions = futures_positions(&raw, "USDT");
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "BTC/USDT");
+        assert_eq!(positions[0].qty, dec(0.01));
+        assert_eq!(positions[0].avg_entry_price, Some(dec(60_000.0)));
+    }
+
+    #[test]
+    fn test_order_ack_normalizes_status_and_fill() {
+        let ack = order_ack(json!({
+            "symbol": "BTCUSDT",
+            "orderId": 28,
+            "status": "PARTIALLY_FILLED",
+            "executedQty": "0.00500000",
+        }));
+        assert_eq!(ack.id, "28");
+        assert_eq!(ack.status, "partially_filled");
+        assert_eq!(ack.raw["filled_qty"], "0.00500000");
+        assert!(ack.raw.get("filled_avg_price").is_none());
+
+        // Spot: quote amount over quantity; futures: `avgPrice`
+        let spot = order_ack(json!({
+            "orderId": 29,
+            "status": "FILLED",
+            "executedQty": "0.5",
+            "cummulativeQuoteQty": "30000.0",
+        }));
+        assert_eq!(spot.raw["filled_avg_price"], 60_000.0);
+        let futures = order_ack(json!({
+            "orderId": 30,
+            "status": "FILLED",
+            "executedQty": "0.5",
+            "avgPrice": "60100.0",
+            "cummulativeQuoteQty": "0",
+        }));
+        assert_eq!(futures.raw["filled_avg_price"], 60_100.0);
+    }
+
+    #[test]
+    fn test_cancel_replace_new_order() {
+        let raw = json!({
+            "cancelResult": "SUCCESS",
+            "newOrderResult": "SUCCESS",
+            "cancelResponse": {"orderId": 28, "status": "CANCELED"},
+            "newOrderResponse": {"orderId": 29, "status": "NEW", "executedQty": "0.00000000"},
+        });
+        let ack = order_ack(cancel_replace_new_order(&raw).unwrap());
+        assert_eq!(ack.id, "29");
+        assert_eq!(ack.status, "new");
+
+        let failed = json!({
+            "cancelResult": "SUCCESS",
+            "newOrderResult": "FAILURE",
+            "newOrderResponse": {"code": -2010, "msg": "Account has insufficient balance"},
+        });
+        assert!(cancel_replace_new_order(&failed).is_none());
+    }
+
+    #[test]
+    fn test_open_orders_from_response() {
+        let raw = json!([
+            {"orderId": 28, "symbol": "BTCUSDT", "side": "BUY", "time": 1_760_616_000_000_i64},
+            {"symbol": "ETHUSDT", "side": "SELL"},
+        ]);
+        let orders = open_orders(&raw);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(
+            (orders[0].id.as_str(), orders[0].side.as_str()),
+            ("28", "buy")
+        );
+        assert_eq!(orders[0].symbol, "BTCUSDT");
+        assert_eq!(
+            orders[0].created_at.map(|t| t.timestamp()),
+            Some(1_760_616_000)
+        );
+    }
+
+    // ============= REST Flow Tests =============
+
+    #[tokio::test]
+    async fn test_submit_then_query_and_cancel_signed() {
+        use axum::{extract::RawQuery, http::HeaderMap, routing::get, Json, Router};
+
+        fn check_signed(headers: &HeaderMap, query: &str) {
+            assert_eq!(headers["x-mbx-apikey"], "K");
+            let (payload, signature) = query.rsplit_once("&signature=").unwrap();
+            assert!(payload.contains("recvWindow=5000&timestamp="));
+            assert_eq!(signature, sign_query("S", payload));
+        }
+
+        let app = Router::new()
+            .route(
+                "/api/v3/exchangeInfo",
+                get(|| async {
+                    Json(json!({"symbols": [{"symbol": "BTCUSDT", "filters": [
+                        {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000", "stepSize": "0.00001"},
+                        {"filterType": "PRICE_FILTER", "tickSize": "0.01"},
+                        {"filterType": "MIN_NOTIONAL", "minNotional": "10"},
+                    ]}]}))
+                }),
+            )
+            .route(
+                "/api/v3/order",
+                get(|headers: HeaderMap, RawQuery(q): RawQuery| async move {
+                    let q = q.unwrap();
+                    check_signed(&headers, &q);
+                    assert!(q.starts_with("symbol=BTCUSDT&orderId=7&"));
+                    Json(json!({"orderId": 7, "status": "FILLED", "executedQty": "0.001"}))
+                })
+                .post(|headers: HeaderMap, RawQuery(q): RawQuery| async move {
+                    let q = q.unwrap();
+                    check_signed(&headers, &q);
+                    assert!(q.contains("type=MARKET&quantity=0.00100&newOrderRespType=RESULT"));
+                    Json(json!({"orderId": 7, "status": "NEW", "executedQty": "0"}))
+                })
+                .delete(|headers: HeaderMap, RawQuery(q): RawQuery| async move {
+                    check_signed(&headers, &q.unwrap());
+                    Json(json!({"orderId": 7, "status": "CANCELED"}))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let exchange = BinanceExchange::new(BinanceConfig {
+            api_key: "K".to_string(),
+            secret_key: "S".to_string(),
+            base_url,
+            quote_asset: "USDT".to_string(),
+            futures: BinanceFuturesConfig::default(),
+        });
+        let mut request = order(OrderType::Market, Some(dec(0.001)), None);
+        request.limit_price = None;
+        let ack = exchange.submit_order(request).await.unwrap();
+        assert_eq!((ack.id.as_str(), ack.status.as_str()), ("7", "new"));
+
+        let filled = exchange.get_order("7").await.unwrap();
+        assert_eq!(filled.status, "filled");
+        assert_eq!(filled.raw["filled_qty"], "0.001");
+
+        exchange.cancel_order("7").await.unwrap();
+        // The symbol is forgotten once the order is canceled
+        assert!(exchange.get_order("7").await.is_err());
+
+        // Below the 10 USDT MIN_NOTIONAL: rejected before reaching the venue
+        let mut small = order(OrderType::Limit, Some(dec(0.0001)), None);
+        small.limit_price = Some(dec(60_000.0));
+        assert!(exchange.submit_order(small).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_futures_orders_set_leverage_once_and_reduce_only_sells() {
+        use axum::{extract::RawQuery, routing::get, routing::post, Json, Router};
+        use std::sync::{Arc, Mutex};
+
+        let calls: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let record = |calls: &Arc<Mutex<Vec<String>>>, path: &str, q: Option<String>| {
+            let q = q.unwrap_or_default();
+            let params = q
+                .split("&recvWindow")
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            calls.lock().unwrap().push(format!("{} {}", path, params));
+        };
+
+        let (c1, c2, c3) = (calls.clone(), calls.clone(), calls.clone());
+        let app = Router::new()
+            .route(
+                "/fapi/v1/exchangeInfo",
+                get(|| async {
+                    Json(json!({"symbols": [{"symbol": "BTCUSDT", "filters": [
+                        {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"},
+                        {"filterType": "PRICE_FILTER", "tickSize": "0.10"},
+                        {"filterType": "MIN_NOTIONAL", "notional": "5"},
+                    ]}]}))
+                }),
+            )
+            .route(
+                "/fapi/v1/ticker/bookTicker",
+                get(|| async { Json(json!({"bidPrice": "49990.0", "askPrice": "50000.0"})) }),
+            )
+            .route(
+                "/fapi/v1/marginType",
+                post(move |RawQuery(q): RawQuery| async move {
+                    record(&c1, "marginType", q);
+                    (
+                        axum::http::StatusCode::BAD_REQUEST,
+                        Json(json!({"code": -4046, "msg": "No need to change margin type."})),
+                    )
+                }),
+            )
+            .route(
+                "/fapi/v1/leverage",
+                post(move |RawQuery(q): RawQuery| async move {
+                    record(&c2, "leverage", q);
+                    Json(json!({"leverage": 3, "symbol": "BTCUSDT"}))
+                }),
+            )
+            .route(
+                "/fapi/v1/order",
+                post(move |RawQuery(q): RawQuery| async move {
+                    record(&c3, "order", q);
+                    Json(json!({"orderId": 9, "status": "NEW", "executedQty": "0"}))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let exchange = BinanceExchange::new(BinanceConfig {
+            api_key: "K".to_string(),
+            secret_key: "S".to_string(),
+            base_url: "http://127.0.0.1:1".to_string(),
+            quote_asset: "USDT".to_string(),
+            futures: BinanceFuturesConfig {
+                enabled: true,
+                base_url,
+                leverage: 3,
+                ..BinanceFuturesConfig::default()
+            },
+        });
+        assert!(!exchange.capabilities().supports_notional_market_buy);
+
+        // $100 notional at the 50k ask -> 0.002 BTC
+        let mut buy = order(OrderType::Market, None, Some(dec(100.0)));
+        buy.limit_price = None;
+        exchange.submit_order(buy).await.unwrap();
+        let mut sell = order(OrderType::Market, Some(dec(0.002)), None);
+        sell.side = Side::Sell;
+        sell.limit_price = None;
+        exchange.submit_order(sell).await.unwrap();
+
+        let calls = calls.lock().unwrap().clone();
+        assert_eq!(
+            calls,
+            vec![
+                "marginType symbol=BTCUSDT&marginType=ISOLATED",
+                "leverage symbol=BTCUSDT&leverage=3",
+                "order symbol=BTCUSDT&side=BUY&type=MARKET&quantity=0.002&newOrderRespType=RESULT",
+                "order symbol=BTCUSDT&side=SELL&type=MARKET&quantity=0.002&reduceOnly=true&newOrderRespType=RESULT",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spot_oco_exit_fits_both_legs() {
+        use axum::{extract::RawQuery, routing::get, routing::post, Json, Router};
+        use std::sync::{Arc, Mutex};
+
+        let calls: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let app = Router::new()
+            .route(
+                "/api/v3/exchangeInfo",
+                get(|| async {
+                    Json(json!({"symbols": [{"symbol": "BTCUSDT", "filters": [
+                        {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000", "stepSize": "0.00001"},
+                        {"filterType": "PRICE_FILTER", "tickSize": "0.01"},
+                        {"filterType": "NOTIONAL", "minNotional": "5"},
+                    ]}]}))
+                }),
+            )
+            .route(
+                "/api/v3/orderList/oco",
+                post(move |RawQuery(q): RawQuery| async move {
+                    let q = q.unwrap_or_default();
+                    let params = q.split("&recvWindow").next().unwrap_or_default();
+                    recorded.lock().unwrap().push(params.to_string());
+                    Json(json!({"orderListId": 3, "listOrderStatus": "EXEC_STARTED", "orders": [
+                        {"symbol": "BTCUSDT", "orderId": 11},
+                        {"symbol": "BTCUSDT", "orderId": 12},
+                    ]}))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let exchange = BinanceExchange::new(BinanceConfig {
+            api_key: "K".to_string(),
+            secret_key: "S".to_string(),
+            base_url,
+            quote_asset: "USDT".to_string(),
+            futures: BinanceFuturesConfig::default(),
+        });
+        assert!(exchange.capabilities().supports_oco);
+        let mut exit = order(OrderType::Limit, Some(dec(0.0012345)), None);
+        exit.side = Side::Sell;
+        let legs = BracketLegs {
+            take_profit: dec(61_000.129),
+            stop_loss: dec(59_000.001),
+        };
+        let ack = exchange.submit_oco_order(exit, legs).await.unwrap();
+        assert_eq!((ack.id.as_str(), ack.status.as_str()), ("11", "new"));
+        assert_eq!(
+            calls.lock().unwrap().clone(),
+            vec![
+                "symbol=BTCUSDT&side=SELL&quantity=0.00123&aboveType=LIMIT_MAKER&abovePrice=61000.12&belowType=STOP_LOSS&belowStopPrice=59000.00&newOrderRespType=RESULT"
+            ]
+        );
+        assert_eq!(oco_order_ids(&ack.raw), ["11", "12"]);
+    }
+}
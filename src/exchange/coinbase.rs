@@ -1,44 +1,568 @@
 use async_trait::async_trait;
-use reqwest::Client;
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL},
+    Engine,
+};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder};
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+    signature::{EcdsaKeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use rust_decimal::Decimal;
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use super::{
-    symbols::to_coinbase_product_id,
+    rate_limit::RestLimiter,
+    symbol_registry::{SymbolRegistry, Venue},
+    time_sync::{coinbase_server_time, ServerClock},
     traits::{ExchangeResult, TradingApi},
     types::{
-        AccountSummary, ExchangeCapabilities, OrderAck, OrderType, PlaceOrderRequest, Position,
-        Side, TimeInForce,
+        AccountSummary, ExchangeCapabilities, OpenOrder, OrderAck, OrderType, PlaceOrderRequest,
+        Position, Side, SymbolMeta, TimeInForce, TopOfBook,
     },
 };
 
-use crate::config::CoinbaseConfig;
+use crate::config::{CoinbaseConfig, RateLimitConfig};
+use crate::error::AutoHedgeError;
+use crate::money;
+
+/// `batch_cancel` accepts at most this many ids per call.
+const CANCEL_BATCH: usize = 100;
+
+/// Length kept for tagged `client_order_id`s (that of the default UUID)
+const COINBASE_CLIENT_ID_LEN: usize = 36;
+
+/// Lifetime of a CDP request JWT (Coinbase caps it at two minutes).
+const JWT_TTL_SECS: i64 = 120;
+
+fn num(value: &Value) -> Option<Decimal> {
+    money::from_json(value)
+}
+
+/// DER length prefix (short form below 128 bytes).
+fn der_len(len: usize) -> Vec<u8> {
+    match len {
+        0..=0x7f => vec![len as u8],
+        0x80..=0xff => vec![0x81, len as u8],
+        _ => vec![0x82, (len >> 8) as u8, len as u8],
+    }
+}
+
+/// P-256 key from a PEM secret. CDP keys ship as SEC1 ("EC PRIVATE KEY"),
+/// which is wrapped into the PKCS#8 envelope ring expects; "PRIVATE KEY"
+/// (PKCS#8) PEMs are used as-is.
+pub fn parse_ec_key(pem: &str) -> ExchangeResult<EcdsaKeyPair> {
+    let invalid = |why: &str| AutoHedgeError::Config(format!("Coinbase secret_key: {}", why));
+    let body: String = pem
+        .replace("\\n", "\n")
+        .lines()
+        .filter(|l| !l.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+    let der = BASE64
+        .decode(body)
+        .map_err(|_| invalid("PEM body is not base64"))?;
+    let pkcs8 = if pem.contains("BEGIN EC PRIVATE KEY") {
+        // PrivateKeyInfo { version 0, AlgorithmIdentifier { ecPublicKey, prime256v1 }, OCTET STRING { sec1 } }
+        let mut inner = vec![0x02, 0x01, 0x00];
+        inner.extend_from_slice(&[
+            0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+            0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07,
+        ]);
+        inner.push(0x04);
+        inner.extend(der_len(der.len()));
+        inner.extend_from_slice(&der);
+        let mut wrapped = vec![0x30];
+        wrapped.extend(der_len(inner.len()));
+        wrapped.extend(inner);
+        wrapped
+    } else {
+        der
+    };
+    EcdsaKeyPair::from_pkcs8(
+        &ECDSA_P256_SHA256_FIXED_SIGNING,
+        &pkcs8,
+        &SystemRandom::new(),
+    )
+    .map_err(|e| invalid(&format!("not a P-256 private key ({})", e)))
+}
+
+/// ES256 JWT for one CDP request; `uri` is "<METHOD> <host><path>".
+pub fn build_jwt(key: &EcdsaKeyPair, key_name: &str, uri: &str, now: i64) -> Option<String> {
+    let rng = SystemRandom::new();
+    let mut nonce = [0u8; 16];
+    rng.fill(&mut nonce).ok()?;
+    let nonce: String = nonce.iter().map(|b| format!("{:02x}", b)).collect();
+    let header = json!({"alg": "ES256", "typ": "JWT", "kid": key_name, "nonce": nonce});
+    let claims = json!({
+        "sub": key_name,
+        "iss": "cdp",
+        "nbf": now,
+        "exp": now + JWT_TTL_SECS,
+        "uri": uri,
+    });
+    let signing_input = format!(
+        "{}.{}",
+        BASE64_URL.encode(header.to_string()),
+        BASE64_URL.encode(claims.to_string())
+    );
+    let signature = key.sign(&rng, signing_input.as_bytes()).ok()?;
+    Some(format!(
+        "{}.{}",
+        signing_input,
+        BASE64_URL.encode(signature.as_ref())
+    ))
+}
+
+/// Legacy key `CB-ACCESS-SIGN`: hex HMAC-SHA256 over `timestamp + METHOD + path + body`.
+pub fn sign_hmac(secret: &str, timestamp: i64, method: &str, path: &str, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let message = format!("{}{}{}{}", timestamp, method, path, body);
+    hmac::sign(&key, message.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// How requests are authenticated, picked from the shape of `secret_key`.
+#[derive(Clone)]
+enum Credentials {
+    /// CDP key: `api_key` is the key name, `secret_key` the EC private key PEM
+    Jwt(Arc<EcdsaKeyPair>),
+    /// Legacy Advanced Trade key with a plain secret
+    Hmac,
+    /// Unparseable PEM; every private call fails with this message
+    Invalid(String),
+}
+
+/// `order_configuration` for an order.
+pub fn order_configuration(order: &PlaceOrderRequest) -> ExchangeResult<Value> {
+    let base_size = order.qty.map(|q| q.to_string());
+    match order.order_type {
+        OrderType::Market => {
+            let config = match (order.side, order.notional, base_size) {
+                (Side::Buy, Some(notional), _) => {
+                    json!({"quote_size": format!("{:.2}", notional)})
+                }
+                (_, _, Some(base)) => json!({"base_size": base}),
+                _ => {
+                    return Err(AutoHedgeError::Config(
+                        "Coinbase market order needs qty (or notional for buys)".to_string(),
+                    ))
+                }
+            };
+            Ok(json!({"market_market_ioc": config}))
+        }
+        OrderType::Limit => {
+            let (Some(base), Some(price)) = (base_size, order.limit_price) else {
+                return Err(AutoHedgeError::Config(
+                    "Coinbase limit order needs qty and limit_price".to_string(),
+                ));
+            };
+            Ok(match order.time_in_force {
+                TimeInForce::Ioc => json!({"sor_limit_ioc": {
+                    "base_size": base,
+                    "limit_price": price.to_string(),
+                }}),
+                // Spot has no DAY orders
+                TimeInForce::Day | TimeInForce::Gtc | TimeInForce::PostOnly => {
+                    json!({"limit_limit_gtc": {
+                        "base_size": base,
+                        "limit_price": price.to_string(),
+                        "post_only": matches!(order.time_in_force, TimeInForce::PostOnly),
+                    }})
+                }
+            })
+        }
+        // Sells trigger on the way down, buys on the way up
+        OrderType::StopLimit => {
+            let (Some(base), Some(price), Some(stop)) =
+                (base_size, order.limit_price, order.stop_price)
+            else {
+                return Err(AutoHedgeError::Config(
+                    "Coinbase stop-limit order needs qty, limit_price and stop_price".to_string(),
+                ));
+            };
+            let direction = match order.side {
+                Side::Buy => "STOP_DIRECTION_STOP_UP",
+                Side::Sell => "STOP_DIRECTION_STOP_DOWN",
+            };
+            Ok(json!({"stop_limit_stop_limit_gtc": {
+                "base_size": base,
+                "limit_price": price.to_string(),
+                "stop_price": stop.to_string(),
+                "stop_direction": direction,
+            }}))
+        }
+        OrderType::StopMarket => Err(AutoHedgeError::Unsupported(
+            "Coinbase has no stop-market orders (use stop-limit)".to_string(),
+        )),
+    }
+}
+
+/// Historical order as an `OrderAck`: OPEN/PENDING/QUEUED map to "new" (or
+/// "partially_filled" once something filled), FILLED/CANCELLED/EXPIRED/FAILED
+/// to "filled"/"canceled"/"expired"/"rejected"; `filled_size` is copied to
+/// `filled_qty`, the field the position monitor reads.
+pub fn order_ack(mut order: Value) -> OrderAck {
+    let id = order
+        .get("order_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+    let filled = order.get("filled_size").and_then(num).unwrap_or_default();
+    let status = match order.get("status").and_then(|s| s.as_str()) {
+        Some("FILLED") => "filled",
+        Some("OPEN") if filled > Decimal::ZERO => "partially_filled",
+        Some("OPEN") | Some("PENDING") | Some("QUEUED") => "new",
+        Some("CANCELLED") | Some("CANCEL_QUEUED") => "canceled",
+        Some("EXPIRED") => "expired",
+        Some("FAILED") => "rejected",
+        _ => "unknown",
+    }
+    .to_string();
+    if let Some(obj) = order.as_object_mut() {
+        obj.insert("filled_qty".to_string(), Value::from(money::float(filled)));
+    }
+    OrderAck {
+        id,
+        status,
+        raw: order,
+    }
+}
+
+/// Failure reasons of a `POST /orders/edit` response
+/// (`{"success": false, "errors": [{"edit_failure_reason", "preview_failure_reason"}]}`),
+/// or None when the edit went through.
+pub fn edit_failure(raw: &Value) -> Option<String> {
+    if raw.get("success").and_then(|s| s.as_bool()) == Some(true) {
+        return None;
+    }
+    let reasons: Vec<&str> = raw
+        .get("errors")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .flat_map(|e| {
+            ["edit_failure_reason", "preview_failure_reason"]
+                .into_iter()
+                .filter_map(|key| e.get(key).and_then(|r| r.as_str()))
+        })
+        .collect();
+    Some(if reasons.is_empty() {
+        "UNKNOWN".to_string()
+    } else {
+        reasons.join(", ")
+    })
+}
+
+/// Orders of a `GET /orders/historical/batch?order_status=OPEN` response
+/// (`{"orders": [{"order_id", "product_id", "side": "BUY", "created_time"}]}`).
+pub fn open_orders(raw: &Value) -> Vec<OpenOrder> {
+    raw.get("orders")
+        .and_then(|o| o.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|o| {
+            let field = |key: &str| o.get(key).and_then(|v| v.as_str());
+            Some(OpenOrder {
+                id: field("order_id")?.to_string(),
+                symbol: field("product_id").unwrap_or_default().to_string(),
+                side: field("side").unwrap_or_default().to_lowercase(),
+                created_at: field("created_time")
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| t.with_timezone(&Utc)),
+            })
+        })
+        .collect()
+}
+
+/// Trading rules from `GET /products/{product_id}` (`base_increment`,
+/// `price_increment`, `base_min_size`, `quote_min_size`).
+pub fn symbol_meta_from_product(symbol: &str, product: &Value) -> SymbolMeta {
+    let field = |key: &str| product.get(key).and_then(num);
+    SymbolMeta {
+        symbol: symbol.to_string(),
+        tick_size: field("price_increment")
+            .or_else(|| field("quote_increment"))
+            .unwrap_or_default(),
+        step_size: field("base_increment").unwrap_or_default(),
+        min_qty: field("base_min_size").unwrap_or_default(),
+        min_notional: field("quote_min_size").unwrap_or_default(),
+    }
+}
+
+/// Best bid/ask from `GET /best_bid_ask?product_ids=` (`{"pricebooks":
+/// [{"product_id", "bids": [{"price", "size"}], "asks": [...]}]}`).
+pub fn top_of_book_from_pricebooks(raw: &Value) -> Option<TopOfBook> {
+    let book = raw.get("pricebooks")?.get(0)?;
+    let side = |key: &str| {
+        book.get(key)
+            .and_then(|levels| levels.get(0))
+            .and_then(|level| level.get("price"))
+            .and_then(num)
+    };
+    TopOfBook::new(side("bids"), side("asks"))
+}
+
+/// Size-weighted average price and total commission of `fills`.
+pub fn summarize_fills(fills: &[Value]) -> Option<(Decimal, Decimal)> {
+    let (mut qty, mut cost, mut commission) = (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+    for fill in fills {
+        let field = |key: &str| fill.get(key).and_then(num).unwrap_or_default();
+        qty += field("size");
+        cost += field("size") * field("price");
+        commission += field("commission");
+    }
+    (qty > Decimal::ZERO).then(|| (cost / qty, commission))
+}
+
+/// Available + hold balance per currency from `accounts`.
+fn balances(raw: &Value) -> Vec<(String, Decimal, Decimal)> {
+    raw.get("accounts")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|a| {
+            Some((
+                a.get("currency")?.as_str()?.to_string(),
+                a.pointer("/available_balance/value")
+                    .and_then(num)
+                    .unwrap_or_default(),
+                a.pointer("/hold/value").and_then(num).unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Account summary: available `quote` balance as cash and buying power;
+/// holdings valued at `prices` (keyed by product id, e.g. "BTC-USD") for the total.
+pub fn account_from_balances(
+    raw: &Value,
+    prices: &HashMap<String, Decimal>,
+    quote: &str,
+) -> AccountSummary {
+    let mut cash = Decimal::ZERO;
+    let mut portfolio_value = Decimal::ZERO;
+    for (currency, available, hold) in balances(raw) {
+        if currency == quote {
+            cash = available;
+            portfolio_value += available + hold;
+        } else if let Some(price) = prices.get(&format!("{}-{}", currency, quote)) {
+            portfolio_value += (available + hold) * price;
+        }
+    }
+    AccountSummary {
+        buying_power: Some(cash),
+        cash: Some(cash),
+        portfolio_value: Some(portfolio_value),
+    }
+}
+
+/// Non-zero holdings other than `quote`, as canonical "ASSET/QUOTE" positions.
+pub fn positions_from_balances(raw: &Value, quote: &str) -> Vec<Position> {
+    balances(raw)
+        .into_iter()
+        .filter(|(currency, available, hold)| currency != quote && available + hold > Decimal::ZERO)
+        .map(|(currency, available, hold)| Position {
+            symbol: format!("{}/{}", currency, quote),
+            qty: available + hold,
+            avg_entry_price: None,
+        })
+        .collect()
+}
 
 /// Coinbase Advanced Trade adapter.
 ///
-/// NOTE: Proper Coinbase signing (CB-ACCESS-* headers) is required for live trading.
-/// This implementation is a compile-safe scaffold and may need signing added before use.
+/// CDP keys (EC private key PEM as `secret_key`) sign each request with a
+/// short-lived ES256 JWT; legacy keys use the `CB-ACCESS-*` HMAC headers.
 #[derive(Clone)]
 pub struct CoinbaseExchange {
     client: Client,
     base_url: String,
     api_key: String,
     api_secret: String,
+    quote_asset: String,
+    credentials: Credentials,
+    limiter: RestLimiter,
+    /// JWT/HMAC timestamps, corrected by the measured server offset
+    clock: ServerClock,
+    /// Canonical <-> Coinbase product ids ("BTC/USD" <-> "BTC-USD")
+    symbols: SymbolRegistry,
 }
 
 impl CoinbaseExchange {
     pub fn new(config: CoinbaseConfig) -> Self {
+        let credentials = if config.secret_key.contains("-----BEGIN") {
+            match parse_ec_key(&config.secret_key) {
+                Ok(key) => Credentials::Jwt(Arc::new(key)),
+                Err(e) => Credentials::Invalid(e.to_string()),
+            }
+        } else {
+            Credentials::Hmac
+        };
         Self {
             client: Client::new(),
             base_url: config.base_url,
             api_key: config.api_key,
             api_secret: config.secret_key,
+            quote_asset: config.quote_asset,
+            credentials,
+            limiter: RestLimiter::coinbase(&RateLimitConfig::default()),
+            clock: ServerClock::new(),
+            symbols: SymbolRegistry::new(Venue::Coinbase, &[]),
+        }
+    }
+
+    /// Throttle REST calls with `limiter` (shared by clones of this adapter).
+    pub fn with_rate_limiter(mut self, limiter: RestLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Translate symbols through the session's shared registry.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Signed request for `path` (query string included). Built per attempt so
+    /// a re-send after a 429 carries a fresh timestamp/JWT.
+    fn signed(&self, method: Method, path: &str, body: Option<&Value>) -> RequestBuilder {
+        let now = self.clock.now_ms() / 1000;
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let mut req = self
+            .client
+            .request(method.clone(), format!("{}{}", self.base_url, path));
+        match &self.credentials {
+            Credentials::Jwt(key) => {
+                let host = self
+                    .base_url
+                    .split("://")
+                    .last()
+                    .unwrap_or(&self.base_url)
+                    .trim_end_matches('/');
+                let uri_path = path.split('?').next().unwrap_or(path);
+                let uri = format!("{} {}{}", method, host, uri_path);
+                if let Some(jwt) = build_jwt(key, &self.api_key, &uri, now) {
+                    req = req.bearer_auth(jwt);
+                }
+            }
+            Credentials::Hmac => {
+                let sign = sign_hmac(&self.api_secret, now, method.as_str(), path, &body);
+                req = req
+                    .header("CB-ACCESS-KEY", &self.api_key)
+                    .header("CB-ACCESS-SIGN", sign)
+                    .header("CB-ACCESS-TIMESTAMP", now.to_string());
+            }
+            Credentials::Invalid(_) => {}
+        }
+        if body.is_empty() {
+            req
+        } else {
+            req.header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body)
+        }
+    }
+
+    /// Send a signed request and decode its JSON body.
+    async fn send_json(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+        what: &str,
+    ) -> ExchangeResult<Value> {
+        if let Credentials::Invalid(e) = &self.credentials {
+            return Err(AutoHedgeError::Config(e.clone()));
         }
+        let resp = self
+            .limiter
+            .send(1, || self.signed(method.clone(), path, body.as_ref()))
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Coinbase {} failed ({}): {}", what, status, text),
+            ));
+        }
+        serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Coinbase {} decode failed: {} (body: {})",
+                what, e, text
+            ))
+        })
+    }
+
+    async fn accounts(&self) -> ExchangeResult<Value> {
+        self.send_json(
+            Method::GET,
+            "/api/v3/brokerage/accounts?limit=250",
+            None,
+            "accounts",
+        )
+        .await
     }
 
-    fn auth_headers(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        // Placeholder: real implementation must add timestamp + signature.
-        req.header("CB-ACCESS-KEY", &self.api_key)
-            .header("CB-ACCESS-SECRET", &self.api_secret)
+    /// Fills of one order, newest first.
+    pub async fn get_fills(&self, order_id: &str) -> ExchangeResult<Vec<Value>> {
+        let raw = self
+            .send_json(
+                Method::GET,
+                &format!(
+                    "/api/v3/brokerage/orders/historical/fills?order_id={}",
+                    order_id
+                ),
+                None,
+                "fills",
+            )
+            .await?;
+        Ok(raw
+            .get("fills")
+            .and_then(|f| f.as_array())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn batch_cancel(&self, order_ids: &[String]) -> ExchangeResult<()> {
+        let raw = self
+            .send_json(
+                Method::POST,
+                "/api/v3/brokerage/orders/batch_cancel",
+                Some(json!({"order_ids": order_ids})),
+                "cancel",
+            )
+            .await?;
+        let failures: Vec<String> = raw
+            .get("results")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|r| r.get("success").and_then(|s| s.as_bool()) != Some(true))
+            .map(|r| {
+                format!(
+                    "{}: {}",
+                    r.get("order_id").and_then(|v| v.as_str()).unwrap_or("?"),
+                    r.get("failure_reason")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("UNKNOWN")
+                )
+            })
+            .collect();
+        if !failures.is_empty() {
+            return Err(AutoHedgeError::exchange(format!(
+                "Coinbase cancel failed: {}",
+                failures.join(", ")
+            )));
+        }
+        Ok(())
     }
 }
 
@@ -54,96 +578,147 @@ impl TradingApi for CoinbaseExchange {
             supports_ws_quotes: false,
             supports_ws_trades: true,
             supports_news: false,
+            supports_bracket_orders: false,
+            // GTC limit orders only (orders/edit)
+            supports_order_replace: true,
+            // Stop-limit only
+            supports_stop_orders: false,
+            supports_oco: false,
+            supports_short: false,
+            min_notional: Decimal::ONE,
         }
     }
 
     async fn get_account(&self) -> ExchangeResult<AccountSummary> {
-        // Coinbase exposes balances per account.
-        Ok(AccountSummary {
-            buying_power: None,
-            cash: None,
-            portfolio_value: None,
-        })
+        let raw = self.accounts().await?;
+        let products: Vec<String> = positions_from_balances(&raw, &self.quote_asset)
+            .iter()
+            .map(|p| format!("product_ids={}", self.symbols.native(&p.symbol)))
+            .collect();
+        let mut prices = HashMap::new();
+        if !products.is_empty() {
+            // {"pricebooks": [{"product_id": "BTC-USD", "bids": [{"price", "size"}], "asks": [...]}]}
+            let books = self
+                .send_json(
+                    Method::GET,
+                    &format!("/api/v3/brokerage/best_bid_ask?{}", products.join("&")),
+                    None,
+                    "best_bid_ask",
+                )
+                .await?;
+            for book in books
+                .get("pricebooks")
+                .and_then(|b| b.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let side = |key: &str| book.pointer(&format!("/{}/0/price", key)).and_then(num);
+                if let (Some(product), Some(bid), Some(ask)) = (
+                    book.get("product_id").and_then(|p| p.as_str()),
+                    side("bids"),
+                    side("asks"),
+                ) {
+                    prices.insert(product.to_string(), (bid + ask) / Decimal::TWO);
+                }
+            }
+        }
+        Ok(account_from_balances(&raw, &prices, &self.quote_asset))
     }
 
     async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
-        // Placeholder
-        Ok(vec![])
+        let raw = self.accounts().await?;
+        Ok(self
+            .symbols
+            .canonical_positions(positions_from_balances(&raw, &self.quote_asset)))
     }
 
-    async fn get_order(&self, _order_id: &str) -> ExchangeResult<OrderAck> {
-        Err("Coinbase get_order not implemented".into())
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        let raw = self
+            .send_json(
+                Method::GET,
+                &format!("/api/v3/brokerage/orders/historical/{}", order_id),
+                None,
+                "get_order",
+            )
+            .await?;
+        let mut ack = order_ack(raw.get("order").cloned().unwrap_or(raw));
+        if ack.raw.get("filled_qty").and_then(|q| q.as_f64()) > Some(0.0) {
+            let fills = self.get_fills(order_id).await?;
+            if let (Some((avg_price, commission)), Some(obj)) =
+                (summarize_fills(&fills), ack.raw.as_object_mut())
+            {
+                obj.insert(
+                    "filled_avg_price".to_string(),
+                    Value::from(money::float(avg_price)),
+                );
+                obj.insert(
+                    "fill_commission".to_string(),
+                    Value::from(money::float(commission)),
+                );
+                obj.insert("fills".to_string(), Value::from(fills));
+            }
+        }
+        Ok(ack)
     }
 
-    async fn cancel_order(&self, _order_id: &str) -> ExchangeResult<()> {
-        Err("Coinbase cancel_order not implemented".into())
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        self.batch_cancel(&[order_id.to_string()]).await
     }
 
     async fn cancel_all_orders(&self) -> ExchangeResult<()> {
-        Err("Coinbase cancel_all_orders not implemented".into())
+        let open: Vec<String> = self
+            .get_open_orders()
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|o| o.id)
+            .collect();
+        for chunk in open.chunks(CANCEL_BATCH) {
+            self.batch_cancel(chunk).await?;
+        }
+        Ok(())
     }
 
-    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
-        let endpoint = format!("{}/api/v3/brokerage/orders", self.base_url);
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        let raw = self
+            .send_json(
+                Method::GET,
+                "/api/v3/brokerage/orders/historical/batch?order_status=OPEN",
+                None,
+                "open_orders",
+            )
+            .await?;
+        Ok(Some(self.symbols.canonical_orders(open_orders(&raw))))
+    }
 
+    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
         let side = match order.side {
             Side::Buy => "BUY",
             Side::Sell => "SELL",
         };
-        let _tif = match order.time_in_force {
-            TimeInForce::Day => "DAY",
-            TimeInForce::Gtc => "GTC",
-            TimeInForce::Ioc => "IOC",
-        };
-
-        let product_id = to_coinbase_product_id(&order.symbol);
-
-        let body = match order.order_type {
-            OrderType::Market => json!({
-                "client_order_id": uuid::Uuid::new_v4().to_string(),
-                "product_id": product_id,
-                "side": side,
-                "order_configuration": {
-                    "market_market_ioc": {
-                        "quote_size": order.notional.map(|n| format!("{:.2}", n)),
-                        "base_size": order.qty.map(|q| q.to_string())
-                    }
-                }
-            }),
-            OrderType::Limit => json!({
-                "client_order_id": uuid::Uuid::new_v4().to_string(),
-                "product_id": product_id,
-                "side": side,
-                "order_configuration": {
-                    "limit_limit_gtc": {
-                        "base_size": order.qty.map(|q| q.to_string()),
-                        "limit_price": "0",
-                        "post_only": false
-                    }
-                }
-            }),
-        };
-
-        let resp = self
-            .auth_headers(self.client.post(&endpoint))
-            .json(&body)
-            .send()
-            .await?;
-        let status = resp.status();
-        let text = resp.text().await?;
-        if !status.is_success() {
-            return Err(format!("Coinbase submit_order failed ({}): {}", status, text).into());
-        }
+        let body = json!({
+            "client_order_id": order
+                .client_order_id(COINBASE_CLIENT_ID_LEN)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            "product_id": self.symbols.native(&order.symbol),
+            "side": side,
+            "order_configuration": order_configuration(&order)?,
+        });
 
-        let raw: Value = serde_json::from_str(&text).map_err(|e| {
-            format!(
-                "Coinbase submit_order decode failed: {} (body: {})",
-                e, text
+        // {"success": true, "success_response": {"order_id": ...}} or
+        // {"success": false, "error_response": {"error": ..., "message": ...}}
+        let raw = self
+            .send_json(
+                Method::POST,
+                "/api/v3/brokerage/orders",
+                Some(body),
+                "submit_order",
             )
-        })?;
+            .await?;
 
         let id = raw
-            .pointer("/order_id")
+            .pointer("/success_response/order_id")
+            .or_else(|| raw.pointer("/order_id"))
             .and_then(|v| v.as_str())
             .unwrap_or("unknown")
             .to_string();
@@ -162,6 +737,98 @@ impl TradingApi for CoinbaseExchange {
         })
     }
 
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        let (OrderType::Limit, Some(size), Some(price)) =
+            (order.order_type, order.qty, order.limit_price)
+        else {
+            return Err(AutoHedgeError::Config(
+                "Coinbase order edit needs a limit order with qty and limit_price".to_string(),
+            ));
+        };
+        // The order keeps its id; only GTC limit orders can be edited
+        let raw = self
+            .send_json(
+                Method::POST,
+                "/api/v3/brokerage/orders/edit",
+                Some(json!({
+                    "order_id": order_id,
+                    "price": price.to_string(),
+                    "size": size.to_string(),
+                })),
+                "edit_order",
+            )
+            .await?;
+        if let Some(reason) = edit_failure(&raw) {
+            return Err(AutoHedgeError::exchange(format!(
+                "Coinbase edit of {} failed: {}",
+                order_id, reason
+            )));
+        }
+        Ok(OrderAck {
+            id: order_id.to_string(),
+            status: "new".to_string(),
+            raw,
+        })
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        let product = self
+            .send_json(
+                Method::GET,
+                &format!("/api/v3/brokerage/products/{}", self.symbols.native(symbol)),
+                None,
+                "product",
+            )
+            .await?;
+        Ok(Some(symbol_meta_from_product(symbol, &product)))
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        let raw = self
+            .send_json(
+                Method::GET,
+                &format!(
+                    "/api/v3/brokerage/best_bid_ask?product_ids={}",
+                    self.symbols.native(symbol)
+                ),
+                None,
+                "best_bid_ask",
+            )
+            .await?;
+        Ok(top_of_book_from_pricebooks(&raw))
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        Some(self.clock.clone())
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        // Public and unsigned: a skewed clock must not break its own measurement
+        let endpoint = format!("{}/api/v3/brokerage/time", self.base_url);
+        let resp = self.limiter.send(1, || self.client.get(&endpoint)).await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Coinbase time failed ({}): {}", status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Coinbase time decode failed: {} (body: {})",
+                e, text
+            ))
+        })?;
+        coinbase_server_time(&raw)
+            .map(Some)
+            .ok_or_else(|| AutoHedgeError::exchange(format!("Coinbase time has no epoch: {}", raw)))
+    }
+
     async fn get_historical_bars(&self, _symbol: &str, _timeframe: &str) -> ExchangeResult<Value> {
         Ok(Value::Null)
     }
@@ -3,35 +3,65 @@ use std::sync::Arc;
 use crate::{config::AppConfig, data::alpaca::AlpacaClient};
 
 use super::{
-    alpaca::AlpacaExchange, binance::BinanceExchange, coinbase::CoinbaseExchange,
-    kraken::KrakenExchange, traits::TradingApi,
+    alpaca::AlpacaExchange,
+    binance::BinanceExchange,
+    coinbase::CoinbaseExchange,
+    kraken::KrakenExchange,
+    rate_limit::RestLimiter,
+    symbol_registry::{SymbolRegistry, Venue},
+    traits::TradingApi,
 };
 
+/// Symbol registry of the configured venue with `config.symbols` registered.
+pub fn build_symbol_registry(config: &AppConfig) -> SymbolRegistry {
+    SymbolRegistry::new(
+        Venue::from_name(&config.exchange).unwrap_or_default(),
+        &config.symbols,
+    )
+}
+
 pub fn build_exchange(
     config: &AppConfig,
+) -> (Arc<dyn TradingApi>, Option<crate::data::store::MarketStore>) {
+    build_exchange_with_symbols(config, build_symbol_registry(config))
+}
+
+/// Like `build_exchange`, translating symbols through `symbols` (shared with
+/// the session's WS feeds and tracker).
+pub fn build_exchange_with_symbols(
+    config: &AppConfig,
+    symbols: SymbolRegistry,
 ) -> (Arc<dyn TradingApi>, Option<crate::data::store::MarketStore>) {
     let exchange = &config.exchange;
 
     match exchange.to_lowercase().as_str() {
         "alpaca" => {
-            let alpaca_client = AlpacaClient::new(config.alpaca.clone(), config.history_limit);
-            let alpaca = AlpacaExchange::new(alpaca_client.clone(), config.trading_mode.clone());
+            let alpaca_client = AlpacaClient::new(config.alpaca.clone(), config.history_limit)
+                .with_rate_limiter(RestLimiter::alpaca(&config.rate_limit));
+            let alpaca = AlpacaExchange::new(alpaca_client.clone(), config.trading_mode.clone())
+                .with_symbol_registry(symbols);
             let store = Some(alpaca.market_store());
             (Arc::new(alpaca), store)
         }
         "binance" => {
-            let config = config.binance.clone().expect("Binance config missing");
-            let ex = BinanceExchange::new(config);
+            let venue = config.binance.clone().expect("Binance config missing");
+            let ex = BinanceExchange::new(venue)
+                .with_rate_limiter(RestLimiter::binance(&config.rate_limit))
+                .with_symbol_registry(symbols);
             (Arc::new(ex), None)
         }
         "coinbase" => {
-            let config = config.coinbase.clone().expect("Coinbase config missing");
-            let ex = CoinbaseExchange::new(config);
+            let venue = config.coinbase.clone().expect("Coinbase config missing");
+            let ex = CoinbaseExchange::new(venue)
+                .with_rate_limiter(RestLimiter::coinbase(&config.rate_limit))
+                .with_symbol_registry(symbols);
             (Arc::new(ex), None)
         }
         "kraken" => {
-            let config = config.kraken.clone().expect("Kraken config missing");
-            let ex = KrakenExchange::new(config);
+            let venue = config.kraken.clone().expect("Kraken config missing");
+            let ex = KrakenExchange::new(venue)
+                .with_rate_limiter(RestLimiter::kraken(&config.rate_limit))
+                .with_symbol_registry(symbols);
             (Arc::new(ex), None)
         }
         other => {
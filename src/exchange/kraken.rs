@@ -1,25 +1,240 @@
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
 use reqwest::Client;
+use ring::{digest, hmac};
+use rust_decimal::Decimal;
 use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use super::{
-    symbols::to_kraken_pair,
+    rate_limit::RestLimiter,
+    symbol_registry::{SymbolRegistry, Venue},
+    symbols::{from_kraken_asset, timeframe_minutes, to_kraken_rest_pair},
+    time_sync::{kraken_server_time, ServerClock},
     traits::{ExchangeResult, TradingApi},
-    types::{AccountSummary, ExchangeCapabilities, OrderAck, PlaceOrderRequest, Position},
+    types::{
+        AccountSummary, ExchangeCapabilities, OpenOrder, OrderAck, OrderType, PlaceOrderRequest,
+        Position, Side, SymbolMeta, TimeInForce, TopOfBook,
+    },
 };
 
-use crate::config::KrakenConfig;
+use crate::config::{KrakenConfig, RateLimitConfig};
+use crate::error::AutoHedgeError;
+use crate::money;
+
+/// API counter cost of a REST call (ledger/trade history would cost 2).
+const CALL_COST: u32 = 1;
+
+/// Kraken API secrets are base64; the decoded bytes are the HMAC key.
+pub fn decode_secret(secret: &str) -> ExchangeResult<Vec<u8>> {
+    BASE64
+        .decode(secret)
+        .map_err(|e| AutoHedgeError::Config(format!("Kraken secret_key is not base64: {}", e)))
+}
+
+/// `API-Sign` for a private call: base64 HMAC-SHA512 with the decoded secret
+/// over `path + SHA256(nonce + post_data)`.
+pub fn sign_request(key: &[u8], path: &str, nonce: u64, post_data: &str) -> String {
+    let hash = digest::digest(
+        &digest::SHA256,
+        format!("{}{}", nonce, post_data).as_bytes(),
+    );
+    let mut message = path.as_bytes().to_vec();
+    message.extend_from_slice(hash.as_ref());
+    let key = hmac::Key::new(hmac::HMAC_SHA512, key);
+    BASE64.encode(hmac::sign(&key, &message).as_ref())
+}
+
+/// `result` of a Kraken response, or its `error` list as an `AutoHedgeError`.
+/// Rate-limit and service-unavailable errors are retryable.
+pub fn kraken_result(raw: Value, what: &str) -> ExchangeResult<Value> {
+    let errors: Vec<String> = raw
+        .get("error")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.as_str().map(str::to_string))
+        .collect();
+    if !errors.is_empty() {
+        let retryable = errors.iter().any(|e| {
+            e.starts_with("EAPI:Rate limit")
+                || e.starts_with("EService:")
+                || e.starts_with("EGeneral:Temporary lockout")
+        });
+        return Err(AutoHedgeError::Exchange {
+            message: format!("Kraken {} failed: {}", what, errors.join(", ")),
+            status: None,
+            retryable,
+        });
+    }
+    Ok(raw.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Balances from `Balance` keyed by canonical asset ("XXBT" -> "BTC").
+fn balances(result: &Value) -> Vec<(String, Decimal)> {
+    result
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(asset, qty)| Some((from_kraken_asset(asset), money::from_json(qty)?)))
+        .collect()
+}
+
+/// Non-zero holdings other than `quote`, as canonical "ASSET/QUOTE" positions.
+pub fn positions_from_balances(result: &Value, quote: &str) -> Vec<Position> {
+    balances(result)
+        .into_iter()
+        .filter(|(asset, qty)| asset != quote && *qty > Decimal::ZERO)
+        .map(|(asset, qty)| Position {
+            symbol: format!("{}/{}", asset, quote),
+            qty,
+            avg_entry_price: None,
+        })
+        .collect()
+}
+
+/// Account summary: `quote` balance as cash and buying power; the total comes
+/// from `TradeBalance` (`eb`, equivalent balance of all assets).
+pub fn account_from_balances(
+    balance: &Value,
+    trade_balance: &Value,
+    quote: &str,
+) -> AccountSummary {
+    let cash = balances(balance)
+        .into_iter()
+        .find(|(asset, _)| asset == quote)
+        .map(|(_, qty)| qty)
+        .unwrap_or_default();
+    AccountSummary {
+        buying_power: Some(cash),
+        cash: Some(cash),
+        portfolio_value: trade_balance.get("eb").and_then(money::from_json),
+    }
+}
+
+/// `QueryOrders` entry as an `OrderAck`. Kraken's pending/open/closed states are
+/// mapped to "new", "partially_filled" and "filled", and `vol_exec` is copied
+/// to `filled_qty`, the field the position monitor reads.
+pub fn order_ack(id: &str, mut info: Value) -> OrderAck {
+    let executed = info
+        .get("vol_exec")
+        .and_then(money::from_json)
+        .unwrap_or_default();
+    let status = match info.get("status").and_then(|s| s.as_str()) {
+        Some("closed") => "filled",
+        Some("open") if executed > Decimal::ZERO => "partially_filled",
+        Some("pending") | Some("open") => "new",
+        Some("canceled") => "canceled",
+        Some("expired") => "expired",
+        _ => "unknown",
+    }
+    .to_string();
+    // `price` is the average executed price (0 until something fills)
+    let avg_price = info
+        .get("price")
+        .and_then(money::from_json)
+        .filter(|p| *p > Decimal::ZERO && executed > Decimal::ZERO);
+    if let Some(obj) = info.as_object_mut() {
+        obj.insert(
+            "filled_qty".to_string(),
+            Value::from(money::float(executed)),
+        );
+        if let Some(avg_price) = avg_price {
+            obj.insert(
+                "filled_avg_price".to_string(),
+                Value::from(money::float(avg_price)),
+            );
+        }
+    }
+    OrderAck {
+        id: id.to_string(),
+        status,
+        raw: info,
+    }
+}
+
+/// Orders of an `OpenOrders` result (`{"open": {"<txid>": {"opentm":
+/// 1688666559.89, "descr": {"pair": "XBTUSD", "type": "buy", ...}}}}`).
+pub fn open_orders(result: &Value) -> Vec<OpenOrder> {
+    result
+        .get("open")
+        .and_then(|o| o.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(txid, info)| {
+            let descr = |key: &str| {
+                info.pointer(&format!("/descr/{}", key))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            OpenOrder {
+                id: txid.clone(),
+                symbol: descr("pair"),
+                side: descr("type"),
+                created_at: info
+                    .get("opentm")
+                    .and_then(|t| t.as_f64())
+                    .and_then(|t| DateTime::from_timestamp_millis((t * 1000.0) as i64)),
+            }
+        })
+        .collect()
+}
+
+/// Trading rules from an `AssetPairs` result (`{"XXBTZUSD": {lot_decimals,
+/// tick_size, ordermin, costmin, ...}}`); the volume step is `10^-lot_decimals`.
+pub fn symbol_meta_from_asset_pair(symbol: &str, result: &Value) -> Option<SymbolMeta> {
+    let pair = result.as_object()?.values().next()?;
+    let decimals = |key: &str| {
+        pair.get(key)
+            .and_then(|d| d.as_u64())
+            .map(|d| Decimal::new(1, d as u32))
+    };
+    let field = |key: &str| pair.get(key).and_then(money::from_json);
+    Some(SymbolMeta {
+        symbol: symbol.to_string(),
+        tick_size: field("tick_size")
+            .or_else(|| decimals("pair_decimals"))
+            .unwrap_or_default(),
+        step_size: decimals("lot_decimals").unwrap_or_default(),
+        min_qty: field("ordermin").unwrap_or_default(),
+        min_notional: field("costmin").unwrap_or_default(),
+    })
+}
+
+/// Best bid/ask from a `Ticker` result (`{"XXBTZUSD": {"a": [price, whole_lot,
+/// lot], "b": [...], ...}}`).
+pub fn top_of_book_from_ticker(result: &Value) -> Option<TopOfBook> {
+    let pair = result.as_object()?.values().next()?;
+    let side = |key: &str| {
+        pair.get(key)
+            .and_then(|s| s.get(0))
+            .and_then(money::from_json)
+    };
+    TopOfBook::new(side("b"), side("a"))
+}
 
 /// Kraken Spot adapter.
 ///
-/// NOTE: Proper Kraken authentication (API-Key + API-Sign) is required for private endpoints.
-/// This implementation is a compile-safe scaffold.
+/// Private endpoints are form POSTs carrying a strictly increasing `nonce`,
+/// signed with `API-Key`/`API-Sign`. Pairs are sent in REST form ("XBTUSD") and
+/// balance keys ("XXBT", "ZUSD") are mapped back to canonical assets.
 #[derive(Clone)]
 pub struct KrakenExchange {
     client: Client,
     base_url: String,
     api_key: String,
     api_secret: String,
+    quote_asset: String,
+    limiter: RestLimiter,
+    /// Last nonce sent (shared by clones so concurrent calls never reuse one)
+    nonce: Arc<AtomicU64>,
+    /// Nonce clock, corrected by the measured server offset
+    clock: ServerClock,
+    /// Resolves Kraken pairs ("XBTUSD", "XBT/USD") to canonical ids
+    symbols: SymbolRegistry,
 }
 
 impl KrakenExchange {
@@ -29,13 +244,118 @@ impl KrakenExchange {
             base_url: config.base_url,
             api_key: config.api_key,
             api_secret: config.secret_key,
+            quote_asset: config.quote_asset,
+            limiter: RestLimiter::kraken(&RateLimitConfig::default()),
+            nonce: Arc::new(AtomicU64::new(0)),
+            clock: ServerClock::new(),
+            symbols: SymbolRegistry::new(Venue::Kraken, &[]),
         }
     }
 
-    fn auth_headers(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        // Placeholder: real implementation must add Kraken API-Sign.
-        req.header("API-Key", &self.api_key)
-            .header("API-Secret", &self.api_secret)
+    /// Throttle REST calls with `limiter` (shared by clones of this adapter).
+    pub fn with_rate_limiter(mut self, limiter: RestLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Translate symbols through the session's shared registry.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Millisecond (server) clock, bumped past the last nonce if calls land in
+    /// the same ms or the offset moved it back.
+    fn next_nonce(&self) -> u64 {
+        let now = self.clock.now_ms() as u64;
+        let prev = self
+            .nonce
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(now.max(last + 1))
+            })
+            .unwrap_or(0);
+        now.max(prev + 1)
+    }
+
+    /// Signed POST to `/0/private/<method>`. Each (re-)send gets a fresh nonce.
+    async fn private(&self, method: &str, params: &[(&str, String)]) -> ExchangeResult<Value> {
+        let path = format!("/0/private/{}", method);
+        let endpoint = format!("{}{}", self.base_url, path);
+        let key = decode_secret(&self.api_secret)?;
+        let resp = self
+            .limiter
+            .send(CALL_COST, || {
+                let nonce = self.next_nonce();
+                let body = std::iter::once(format!("nonce={}", nonce))
+                    .chain(params.iter().map(|(k, v)| format!("{}={}", k, v)))
+                    .collect::<Vec<_>>()
+                    .join("&");
+                let sign = sign_request(&key, &path, nonce, &body);
+                self.client
+                    .post(&endpoint)
+                    .header("API-Key", &self.api_key)
+                    .header("API-Sign", sign)
+                    .header(
+                        reqwest::header::CONTENT_TYPE,
+                        "application/x-www-form-urlencoded",
+                    )
+                    .body(body)
+            })
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Kraken {} failed ({}): {}", method, status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Kraken {} decode failed: {} (body: {})",
+                method, e, text
+            ))
+        })?;
+        kraken_result(raw, method)
+    }
+
+    /// Public OHLC bars, from `since` when given.
+    async fn get_ohlc(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> ExchangeResult<Value> {
+        // Public OHLC: {"error": [], "result": {"XXBTZUSD": [[time, "o", "h", "l", "c", "vwap", "v", count], ...], "last": ...}}
+        let interval = timeframe_minutes(timeframe).ok_or_else(|| {
+            AutoHedgeError::Config(format!("Unsupported timeframe '{}'", timeframe))
+        })?;
+        let mut endpoint = format!(
+            "{}/0/public/OHLC?pair={}&interval={}",
+            self.base_url,
+            to_kraken_rest_pair(symbol),
+            interval
+        );
+        if let Some(since) = since {
+            endpoint.push_str(&format!("&since={}", since.timestamp()));
+        }
+        let resp = self
+            .limiter
+            .send(CALL_COST, || self.client.get(&endpoint))
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Kraken OHLC failed ({}): {}", status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!("Kraken OHLC decode failed: {} (body: {})", e, text))
+        })?;
+        kraken_result(raw.clone(), "OHLC")?;
+        Ok(raw)
     }
 }
 
@@ -51,60 +371,256 @@ impl TradingApi for KrakenExchange {
             supports_ws_quotes: true,
             supports_ws_trades: true,
             supports_news: false,
+            supports_bracket_orders: false,
+            supports_order_replace: true,
+            supports_stop_orders: true,
+            supports_oco: false,
+            supports_short: false,
+            // Per-pair costmin from AssetPairs
+            min_notional: Decimal::ZERO,
         }
     }
 
     async fn get_account(&self) -> ExchangeResult<AccountSummary> {
-        Ok(AccountSummary {
-            buying_power: None,
-            cash: None,
-            portfolio_value: None,
-        })
+        let balance = self.private("Balance", &[]).await?;
+        let trade_balance = self
+            .private("TradeBalance", &[("asset", self.quote_asset.clone())])
+            .await?;
+        Ok(account_from_balances(
+            &balance,
+            &trade_balance,
+            &self.quote_asset,
+        ))
     }
 
     async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
-        // Placeholder
-        Ok(vec![])
+        let balance = self.private("Balance", &[]).await?;
+        Ok(self
+            .symbols
+            .canonical_positions(positions_from_balances(&balance, &self.quote_asset)))
     }
 
-    async fn get_order(&self, _order_id: &str) -> ExchangeResult<OrderAck> {
-        Err("Kraken get_order not implemented".into())
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        let result = self
+            .private("QueryOrders", &[("txid", order_id.to_string())])
+            .await?;
+        let info = result.get(order_id).cloned().ok_or_else(|| {
+            AutoHedgeError::exchange(format!("Kraken QueryOrders has no {}", order_id))
+        })?;
+        Ok(order_ack(order_id, info))
     }
 
-    async fn cancel_order(&self, _order_id: &str) -> ExchangeResult<()> {
-        Err("Kraken cancel_order not implemented".into())
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        self.private("CancelOrder", &[("txid", order_id.to_string())])
+            .await?;
+        Ok(())
     }
 
     async fn cancel_all_orders(&self) -> ExchangeResult<()> {
-        Err("Kraken cancel_all_orders not implemented".into())
+        let result = self.private("OpenOrders", &[]).await?;
+        for order in open_orders(&result) {
+            self.cancel_order(&order.id).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        let result = self.private("OpenOrders", &[]).await?;
+        Ok(Some(self.symbols.canonical_orders(open_orders(&result))))
+    }
+
+    /// Kraken's dead man's switch (`CancelAllOrdersAfter`).
+    async fn cancel_orders_after(&self, timeout_secs: u64) -> ExchangeResult<bool> {
+        self.private(
+            "CancelAllOrdersAfter",
+            &[("timeout", timeout_secs.to_string())],
+        )
+        .await?;
+        Ok(true)
     }
 
     async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
-        // Kraken private endpoint: /0/private/AddOrder. Requires nonce + signature.
-        // We keep a stub request that returns an error if not configured.
-        let _pair = to_kraken_pair(&order.symbol);
+        let volume = order.qty.ok_or_else(|| {
+            AutoHedgeError::Config("Kraken orders need a base qty (no notional orders)".to_string())
+        })?;
+        let side = match order.side {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+        };
+        let mut params = vec![
+            ("pair", to_kraken_rest_pair(&order.symbol)),
+            ("type", side.to_string()),
+        ];
+        match order.order_type {
+            OrderType::Market => params.push(("ordertype", "market".to_string())),
+            OrderType::Limit => {
+                let price = order.limit_price.ok_or_else(|| {
+                    AutoHedgeError::Config("Kraken limit order needs a limit_price".to_string())
+                })?;
+                // Spot has no DAY orders
+                let tif = match order.time_in_force {
+                    TimeInForce::Ioc => "IOC",
+                    TimeInForce::Day | TimeInForce::Gtc | TimeInForce::PostOnly => "GTC",
+                };
+                params.push(("ordertype", "limit".to_string()));
+                params.push(("price", price.to_string()));
+                params.push(("timeinforce", tif.to_string()));
+                if matches!(order.time_in_force, TimeInForce::PostOnly) {
+                    params.push(("oflags", "post".to_string()));
+                }
+            }
+            // The trigger goes in `price`; a stop-limit's limit in `price2`
+            OrderType::StopMarket | OrderType::StopLimit => {
+                let stop = order.stop_price.ok_or_else(|| {
+                    AutoHedgeError::Config("Kraken stop order needs a stop_price".to_string())
+                })?;
+                params.push(("price", stop.to_string()));
+                if matches!(order.order_type, OrderType::StopLimit) {
+                    let limit = order.limit_price.ok_or_else(|| {
+                        AutoHedgeError::Config(
+                            "Kraken stop-limit order needs a limit_price".to_string(),
+                        )
+                    })?;
+                    params.push(("ordertype", "stop-loss-limit".to_string()));
+                    params.push(("price2", limit.to_string()));
+                } else {
+                    params.push(("ordertype", "stop-loss".to_string()));
+                }
+            }
+        }
+        params.push(("volume", volume.to_string()));
+
+        // {"descr": {"order": "buy 1.25 XBTUSD @ limit 37500.0"}, "txid": ["OUF4EM-..."]}
+        let result = self.private("AddOrder", &params).await?;
+        let id = result
+            .get("txid")
+            .and_then(|t| t.get(0))
+            .and_then(|t| t.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        Ok(OrderAck {
+            id,
+            status: "new".to_string(),
+            raw: result,
+        })
+    }
 
-        let endpoint = format!("{}/0/private/AddOrder", self.base_url);
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        let mut params = vec![("txid", order_id.to_string())];
+        if let Some(qty) = order.qty {
+            params.push(("order_qty", qty.to_string()));
+        }
+        if let Some(price) = order.limit_price {
+            params.push(("limit_price", price.to_string()));
+        }
+        // Amended in place: {"amend_id": "TTW6PD-..."}, the txid is unchanged
+        let result = self.private("AmendOrder", &params).await?;
+        Ok(OrderAck {
+            id: order_id.to_string(),
+            status: "new".to_string(),
+            raw: result,
+        })
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        let endpoint = format!(
+            "{}/0/public/AssetPairs?pair={}",
+            self.base_url,
+            to_kraken_rest_pair(symbol)
+        );
         let resp = self
-            .auth_headers(self.client.post(&endpoint))
-            .send()
+            .limiter
+            .send(CALL_COST, || self.client.get(&endpoint))
             .await?;
         let status = resp.status();
         let text = resp.text().await?;
         if !status.is_success() {
-            return Err(format!("Kraken submit_order failed ({}): {}", status, text).into());
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Kraken AssetPairs failed ({}): {}", status, text),
+            ));
         }
-        let raw: Value = serde_json::from_str(&text)
-            .map_err(|e| format!("Kraken submit_order decode failed: {} (body: {})", e, text))?;
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Kraken AssetPairs decode failed: {} (body: {})",
+                e, text
+            ))
+        })?;
+        let result = kraken_result(raw, "AssetPairs")?;
+        Ok(symbol_meta_from_asset_pair(symbol, &result))
+    }
 
-        Ok(OrderAck {
-            id: "unknown".to_string(),
-            status: "unknown".to_string(),
-            raw,
-        })
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        let endpoint = format!(
+            "{}/0/public/Ticker?pair={}",
+            self.base_url,
+            to_kraken_rest_pair(symbol)
+        );
+        let resp = self
+            .limiter
+            .send(CALL_COST, || self.client.get(&endpoint))
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Kraken Ticker failed ({}): {}", status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!(
+                "Kraken Ticker decode failed: {} (body: {})",
+                e, text
+            ))
+        })?;
+        let result = kraken_result(raw, "Ticker")?;
+        Ok(top_of_book_from_ticker(&result))
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        Some(self.clock.clone())
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        let endpoint = format!("{}/0/public/Time", self.base_url);
+        let resp = self
+            .limiter
+            .send(CALL_COST, || self.client.get(&endpoint))
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Kraken Time failed ({}): {}", status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text).map_err(|e| {
+            AutoHedgeError::exchange(format!("Kraken Time decode failed: {} (body: {})", e, text))
+        })?;
+        let result = kraken_result(raw, "Time")?;
+        kraken_server_time(&result)
+            .map(Some)
+            .ok_or_else(|| AutoHedgeError::exchange("Kraken Time has no unixtime"))
+    }
+
+    async fn get_historical_bars(&self, symbol: &str, timeframe: &str) -> ExchangeResult<Value> {
+        self.get_ohlc(symbol, timeframe, None).await
     }
 
-    async fn get_historical_bars(&self, _symbol: &str, _timeframe: &str) -> ExchangeResult<Value> {
-        Ok(Value::Null)
+    /// Kraken serves only the latest 720 bars of an interval, so older
+    /// `since` values return that window.
+    async fn get_bars_since(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        self.get_ohlc(symbol, timeframe, Some(since)).await
     }
 }
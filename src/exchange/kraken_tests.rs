@@ -0,0 +1,198 @@
+//! Unit tests for the Kraken Spot adapter (API-Sign, balances, order status mapping).
+
+#[cfg(test)]
+mod kraken_tests {
+    use crate::config::KrakenConfig;
+    use crate::exchange::kraken::*;
+    use crate::exchange::symbols::{from_kraken_asset, to_kraken_rest_pair};
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::{OrderType, PlaceOrderRequest, Side, TimeInForce};
+    use crate::money::dec;
+    use serde_json::json;
+
+    const SECRET: &str =
+        "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==";
+
+    // ============= Signing Tests =============
+
+    #[test]
+    fn test_sign_request_matches_kraken_docs() {
+        let key = decode_secret(SECRET).unwrap();
+        assert_eq!(
+            sign_request(
+                &key,
+                "/0/private/AddOrder",
+                1616492376594,
+                "nonce=1616492376594&ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25"
+            ),
+            "4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ=="
+        );
+        assert!(decode_secret("not base64!").is_err());
+    }
+
+    // ============= Symbol Mapping Tests =============
+
+    #[test]
+    fn test_pair_and_asset_normalization() {
+        assert_eq!(to_kraken_rest_pair("BTC/USD"), "XBTUSD");
+        assert_eq!(to_kraken_rest_pair("ETH/USD"), "ETHUSD");
+        assert_eq!(from_kraken_asset("XXBT"), "BTC");
+        assert_eq!(from_kraken_asset("ZUSD"), "USD");
+        assert_eq!(from_kraken_asset("XETH"), "ETH");
+        assert_eq!(from_kraken_asset("XXDG"), "DOGE");
+        assert_eq!(from_kraken_asset("SOL"), "SOL");
+    }
+
+    // ============= Response Mapping Tests =============
+
+    #[test]
+    fn test_kraken_result_errors() {
+        let ok = kraken_result(json!({"error": [], "result": {"a": 1}}), "Balance").unwrap();
+        assert_eq!(ok["a"], 1);
+
+        let rejected =
+            kraken_result(json!({"error": ["EOrder:Insufficient funds"]}), "AddOrder").unwrap_err();
+        assert!(rejected.to_string().contains("EOrder:Insufficient funds"));
+        assert!(!rejected.is_retryable());
+
+        let throttled =
+            kraken_result(json!({"error": ["EAPI:Rate limit exceeded"]}), "Balance").unwrap_err();
+        assert!(throttled.is_retryable());
+    }
+
+    #[test]
+    fn test_account_and_positions_from_balances() {
+        let balance = json!({"ZUSD": "1000.5000", "XXBT": "0.0100000000", "XETH": "0.0000000000"});
+        let trade_balance = json!({"eb": "1601.2300", "tb": "1000.5000"});
+
+        let account = account_from_balances(&balance, &trade_balance, "USD");
+        assert_eq!(account.cash, Some(dec(1000.5)));
+        assert_eq!(account.buying_power, Some(dec(1000.5)));
+        assert_eq!(account.portfolio_value, Some(dec(1601.23)));
+
+        let positions = positions_from_balances(&balance, "USD");
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "BTC/USD");
+        assert_eq!(positions[0].qty, dec(0.01));
+    }
+
+    #[test]
+    fn test_order_ack_status_mapping() {
+        let cases = [
+            (json!({"status": "pending", "vol_exec": "0"}), "new"),
+            (
+                json!({"status": "open", "vol_exec": "0.5"}),
+                "partially_filled",
+            ),
+            (json!({"status": "closed", "vol_exec": "1.0"}), "filled"),
+            (json!({"status": "canceled", "vol_exec": "0"}), "canceled"),
+        ];
+        for (info, expected) in cases {
+            assert_eq!(order_ack("O1", info).status, expected);
+        }
+        let ack = order_ack(
+            "O1",
+            json!({"status": "closed", "vol_exec": "1.25", "price": "2010.5"}),
+        );
+        assert_eq!(ack.raw["filled_qty"], 1.25);
+        assert_eq!(ack.raw["filled_avg_price"], 2010.5);
+        let resting = order_ack(
+            "O2",
+            json!({"status": "open", "vol_exec": "0", "price": "0"}),
+        );
+        assert!(resting.raw.get("filled_avg_price").is_none());
+    }
+
+    #[test]
+    fn test_open_orders_from_result() {
+        let result = json!({"open": {
+            "OQCLML-BW3P3-BUCMWZ": {
+                "opentm": 1760616000.5,
+                "descr": {"pair": "XBTUSD", "type": "buy", "ordertype": "limit"},
+            },
+        }});
+        let orders = open_orders(&result);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, "OQCLML-BW3P3-BUCMWZ");
+        assert_eq!(
+            (orders[0].symbol.as_str(), orders[0].side.as_str()),
+            ("XBTUSD", "buy")
+        );
+        assert_eq!(
+            orders[0].created_at.map(|t| t.timestamp_millis()),
+            Some(1_760_616_000_500)
+        );
+        assert!(open_orders(&json!({})).is_empty());
+    }
+
+    // ============= REST Flow Tests =============
+
+    #[tokio::test]
+    async fn test_add_query_and_cancel_signed() {
+        use axum::{extract::Path, http::HeaderMap, routing::post, Json, Router};
+
+        let app = Router::new().route(
+            "/0/private/{method}",
+            post(
+                |Path(method): Path<String>, headers: HeaderMap, body: String| async move {
+                    let nonce: u64 = body
+                        .split('&')
+                        .find_map(|kv| kv.strip_prefix("nonce="))
+                        .unwrap()
+                        .parse()
+                        .unwrap();
+                    let key = decode_secret(SECRET).unwrap();
+                    let path = format!("/0/private/{}", method);
+                    assert_eq!(headers["api-key"], "K");
+                    assert_eq!(headers["api-sign"], sign_request(&key, &path, nonce, &body).as_str());
+                    let result = match method.as_str() {
+                        "AddOrder" => {
+                            assert!(body.contains("pair=XBTUSD&type=buy&ordertype=limit&price=50000&timeinforce=GTC&volume=0.01"));
+                            json!({"descr": {"order": "buy 0.01 XBTUSD @ limit 50000"}, "txid": ["OABC12-DEF34-GHI56"]})
+                        }
+                        "QueryOrders" => json!({"OABC12-DEF34-GHI56": {"status": "closed", "vol": "0.01", "vol_exec": "0.01"}}),
+                        "OpenOrders" => json!({"open": {"OABC12-DEF34-GHI56": {"status": "open"}}}),
+                        "CancelOrder" => json!({"count": 1}),
+                        other => panic!("unexpected {}", other),
+                    };
+                    Json(json!({"error": [], "result": result}))
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let exchange = KrakenExchange::new(KrakenConfig {
+            api_key: "K".to_string(),
+            secret_key: SECRET.to_string(),
+            base_url,
+            quote_asset: "USD".to_string(),
+        });
+        let ack = exchange
+            .submit_order(PlaceOrderRequest {
+                symbol: "BTC/USD".to_string(),
+                side: Side::Buy,
+                order_type: OrderType::Limit,
+                qty: Some(dec(0.01)),
+                notional: None,
+                limit_price: Some(dec(50_000.0)),
+                time_in_force: TimeInForce::Day,
+                bracket: None,
+                stop_price: None,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            (ack.id.as_str(), ack.status.as_str()),
+            ("OABC12-DEF34-GHI56", "new")
+        );
+
+        let filled = exchange.get_order("OABC12-DEF34-GHI56").await.unwrap();
+        assert_eq!(filled.status, "filled");
+        assert_eq!(filled.raw["filled_qty"], 0.01);
+
+        exchange.cancel_all_orders().await.unwrap();
+    }
+}
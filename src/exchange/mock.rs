@@ -0,0 +1,405 @@
+//! Configurable in-memory venue for unit tests.
+//!
+//! `MockExchange` answers from a fixed account, position list and open-order
+//! book, records every submitted order, cancel and replace, and counts calls
+//! per method. Anything scripted (fills, rejections, quotes, venue clocks)
+//! goes into `on_*` hooks, which receive the mock so they can read its log.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+
+use super::time_sync::ServerClock;
+use super::traits::{ExchangeResult, TradingApi};
+use super::types::{
+    AccountSummary, ExchangeCapabilities, OpenOrder, OrderAck, PlaceOrderRequest, Position,
+    SymbolMeta, TopOfBook,
+};
+use crate::error::AutoHedgeError;
+
+type Hook<T> = Box<dyn Fn(&MockExchange) -> ExchangeResult<T> + Send + Sync>;
+type IdHook<T> = Box<dyn Fn(&MockExchange, &str) -> ExchangeResult<T> + Send + Sync>;
+type OrderHook =
+    Box<dyn Fn(&MockExchange, &PlaceOrderRequest) -> ExchangeResult<OrderAck> + Send + Sync>;
+type ReplaceHook =
+    Box<dyn Fn(&MockExchange, &str, &PlaceOrderRequest) -> ExchangeResult<OrderAck> + Send + Sync>;
+type BarsHook = Box<dyn Fn(&MockExchange, DateTime<Utc>) -> ExchangeResult<Value> + Send + Sync>;
+
+/// Order acknowledgement with `id`, `status` and venue payload `raw`.
+pub fn ack(id: &str, status: &str, raw: Value) -> OrderAck {
+    OrderAck {
+        id: id.to_string(),
+        status: status.to_string(),
+        raw,
+    }
+}
+
+/// Capabilities with every feature off and no minimum order value.
+pub fn no_capabilities() -> ExchangeCapabilities {
+    ExchangeCapabilities {
+        supports_notional_market_buy: false,
+        supports_ws_quotes: false,
+        supports_ws_trades: false,
+        supports_news: false,
+        supports_bracket_orders: false,
+        supports_order_replace: false,
+        supports_stop_orders: false,
+        supports_oco: false,
+        supports_short: false,
+        min_notional: Decimal::ZERO,
+    }
+}
+
+/// In-memory `TradingApi`. Without hooks, orders fill on arrival (id
+/// `<name>-<n>`), lookups report them filled and cancels succeed.
+pub struct MockExchange {
+    name: &'static str,
+    capabilities: ExchangeCapabilities,
+    account: AccountSummary,
+    positions: Mutex<Vec<Position>>,
+    open_orders: Option<Vec<OpenOrder>>,
+    clock: Option<ServerClock>,
+    down: AtomicBool,
+    calls: Mutex<Vec<String>>,
+    counts: Mutex<HashMap<&'static str, usize>>,
+    submitted: Mutex<Vec<PlaceOrderRequest>>,
+    replaced: Mutex<Vec<(String, PlaceOrderRequest)>>,
+    on_submit: Option<OrderHook>,
+    on_get_order: Option<IdHook<OrderAck>>,
+    on_cancel: Option<IdHook<()>>,
+    on_cancel_all: Option<Hook<()>>,
+    on_replace: Option<ReplaceHook>,
+    on_best_bid_ask: Option<IdHook<Option<TopOfBook>>>,
+    on_symbol_meta: Option<IdHook<Option<SymbolMeta>>>,
+    on_server_time: Option<Hook<Option<i64>>>,
+    on_bars_since: Option<BarsHook>,
+}
+
+impl MockExchange {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            capabilities: no_capabilities(),
+            account: AccountSummary {
+                buying_power: None,
+                cash: None,
+                portfolio_value: None,
+            },
+            positions: Mutex::new(Vec::new()),
+            open_orders: None,
+            clock: None,
+            down: AtomicBool::new(false),
+            calls: Mutex::new(Vec::new()),
+            counts: Mutex::new(HashMap::new()),
+            submitted: Mutex::new(Vec::new()),
+            replaced: Mutex::new(Vec::new()),
+            on_submit: None,
+            on_get_order: None,
+            on_cancel: None,
+            on_cancel_all: None,
+            on_replace: None,
+            on_best_bid_ask: None,
+            on_symbol_meta: None,
+            on_server_time: None,
+            on_bars_since: None,
+        }
+    }
+
+    pub fn with_capabilities(mut self, edit: impl FnOnce(&mut ExchangeCapabilities)) -> Self {
+        edit(&mut self.capabilities);
+        self
+    }
+
+    pub fn with_account(mut self, account: AccountSummary) -> Self {
+        self.account = account;
+        self
+    }
+
+    pub fn with_positions(self, positions: Vec<Position>) -> Self {
+        self.set_positions(positions);
+        self
+    }
+
+    /// Book served by `get_open_orders` (None: the venue cannot list orders).
+    pub fn with_open_orders(mut self, open_orders: Option<Vec<OpenOrder>>) -> Self {
+        self.open_orders = open_orders;
+        self
+    }
+
+    pub fn with_server_clock(mut self, clock: ServerClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn on_submit(
+        mut self,
+        hook: impl Fn(&MockExchange, &PlaceOrderRequest) -> ExchangeResult<OrderAck>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.on_submit = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_get_order(
+        mut self,
+        hook: impl Fn(&MockExchange, &str) -> ExchangeResult<OrderAck> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_get_order = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_cancel(
+        mut self,
+        hook: impl Fn(&MockExchange, &str) -> ExchangeResult<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_cancel = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_cancel_all(
+        mut self,
+        hook: impl Fn(&MockExchange) -> ExchangeResult<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_cancel_all = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_replace(
+        mut self,
+        hook: impl Fn(&MockExchange, &str, &PlaceOrderRequest) -> ExchangeResult<OrderAck>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.on_replace = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_best_bid_ask(
+        mut self,
+        hook: impl Fn(&MockExchange, &str) -> ExchangeResult<Option<TopOfBook>> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_best_bid_ask = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_symbol_meta(
+        mut self,
+        hook: impl Fn(&MockExchange, &str) -> ExchangeResult<Option<SymbolMeta>> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_symbol_meta = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_server_time(
+        mut self,
+        hook: impl Fn(&MockExchange) -> ExchangeResult<Option<i64>> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_server_time = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_bars_since(
+        mut self,
+        hook: impl Fn(&MockExchange, DateTime<Utc>) -> ExchangeResult<Value> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_bars_since = Some(Box::new(hook));
+        self
+    }
+
+    /// While set, every REST call fails with a 503.
+    pub fn set_down(&self, down: bool) {
+        self.down.store(down, Ordering::SeqCst);
+    }
+
+    pub fn set_positions(&self, positions: Vec<Position>) {
+        *self.positions.lock().unwrap() = positions;
+    }
+
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions.lock().unwrap().clone()
+    }
+
+    /// Orders submitted so far, oldest first (rejected ones included).
+    pub fn submitted(&self) -> Vec<PlaceOrderRequest> {
+        self.submitted.lock().unwrap().clone()
+    }
+
+    /// Replace requests so far as (old order id, replacement).
+    pub fn replaced(&self) -> Vec<(String, PlaceOrderRequest)> {
+        self.replaced.lock().unwrap().clone()
+    }
+
+    /// Orders and cancels in call order: `<Side> <symbol> <qty>` and `cancel <id>`.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Ids of cancelled orders, oldest first.
+    pub fn cancelled(&self) -> Vec<String> {
+        self.calls()
+            .iter()
+            .filter_map(|c| c.strip_prefix("cancel "))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Calls of trait method `method` so far.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.counts
+            .lock()
+            .unwrap()
+            .get(method)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Count a call of `method`; Err while the venue is down.
+    fn call(&self, method: &'static str) -> ExchangeResult<()> {
+        *self.counts.lock().unwrap().entry(method).or_insert(0) += 1;
+        if self.down.load(Ordering::SeqCst) {
+            return Err(AutoHedgeError::http(503, "503 Service Unavailable"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TradingApi for MockExchange {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn capabilities(&self) -> ExchangeCapabilities {
+        self.capabilities.clone()
+    }
+
+    async fn get_account(&self) -> ExchangeResult<AccountSummary> {
+        self.call("get_account")?;
+        Ok(self.account.clone())
+    }
+
+    async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
+        self.call("get_positions")?;
+        Ok(self.positions())
+    }
+
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        self.call("get_order")?;
+        match &self.on_get_order {
+            Some(hook) => hook(self, order_id),
+            None => Ok(ack(order_id, "filled", json!({}))),
+        }
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("cancel {}", order_id));
+        self.call("cancel_order")?;
+        match &self.on_cancel {
+            Some(hook) => hook(self, order_id),
+            None => Ok(()),
+        }
+    }
+
+    async fn cancel_all_orders(&self) -> ExchangeResult<()> {
+        self.call("cancel_all_orders")?;
+        match &self.on_cancel_all {
+            Some(hook) => hook(self),
+            None => Ok(()),
+        }
+    }
+
+    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
+        self.calls.lock().unwrap().push(format!(
+            "{:?} {} {}",
+            order.side,
+            order.symbol,
+            order.qty.unwrap_or_default()
+        ));
+        let n = {
+            let mut submitted = self.submitted.lock().unwrap();
+            submitted.push(order.clone());
+            submitted.len()
+        };
+        self.call("submit_order")?;
+        match &self.on_submit {
+            Some(hook) => hook(self, &order),
+            None => Ok(ack(&format!("{}-{}", self.name, n), "filled", json!({}))),
+        }
+    }
+
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        self.replaced
+            .lock()
+            .unwrap()
+            .push((order_id.to_string(), order.clone()));
+        self.call("replace_order")?;
+        match &self.on_replace {
+            Some(hook) => hook(self, order_id, &order),
+            None => Err(AutoHedgeError::Unsupported(format!(
+                "{} does not support order replace",
+                self.name
+            ))),
+        }
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        self.call("get_open_orders")?;
+        Ok(self.open_orders.clone())
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        self.call("get_symbol_meta")?;
+        match &self.on_symbol_meta {
+            Some(hook) => hook(self, symbol),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        self.call("get_best_bid_ask")?;
+        match &self.on_best_bid_ask {
+            Some(hook) => hook(self, symbol),
+            None => Ok(None),
+        }
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        self.clock.clone()
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        self.call("get_server_time")?;
+        match &self.on_server_time {
+            Some(hook) => hook(self),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_bars_since(
+        &self,
+        _symbol: &str,
+        _timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        self.call("get_bars_since")?;
+        match &self.on_bars_since {
+            Some(hook) => hook(self, since),
+            None => Ok(Value::Null),
+        }
+    }
+}
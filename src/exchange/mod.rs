@@ -8,7 +8,43 @@ pub mod alpaca;
 pub mod binance;
 pub mod coinbase;
 pub mod kraken;
+#[cfg(test)]
+pub mod mock;
+// Driven through the library by tests/parity.rs and tests/soak.rs; the
+// binary compiles it without using it
+#[cfg(feature = "parity")]
+#[allow(dead_code)]
+pub mod paper;
+pub mod polling;
+pub mod rate_limit;
+pub mod symbol_meta;
+pub mod symbol_registry;
+pub mod time_sync;
+pub mod user_stream;
 pub mod ws;
+pub mod ws_messages;
 
+#[cfg(test)]
+mod binance_tests;
+#[cfg(test)]
+mod coinbase_tests;
+#[cfg(test)]
+mod kraken_tests;
+#[cfg(all(test, feature = "parity"))]
+mod paper_tests;
+#[cfg(test)]
+mod polling_tests;
+#[cfg(test)]
+mod rate_limit_tests;
+#[cfg(test)]
+mod symbol_meta_tests;
+#[cfg(test)]
+mod symbol_registry_tests;
+#[cfg(test)]
+mod time_sync_tests;
 #[cfg(test)]
 mod types_tests;
+#[cfg(test)]
+mod user_stream_tests;
+#[cfg(test)]
+mod ws_messages_tests;
@@ -0,0 +1,361 @@
+//! In-process paper venue for the sim-to-live parity harness (`parity` feature).
+//!
+//! `PaperExchange` implements `TradingApi` over a cash balance, long positions
+//! and an order book of its own, so the full service stack can trade against
+//! it without a network. Quotes are fed with `on_quote`, before the harness
+//! publishes them, the way a venue sees a price before its stream does.
+//! Market orders fill at the touch; a limit order marketable on arrival
+//! fills at the touch, one resting fills at its limit once a quote reaches
+//! it; stop orders trigger once the touch trades through the stop and then
+//! fill like a market or limit order. Every submitted order is kept, with its final state, for
+//! assertions.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::error::AutoHedgeError;
+
+use super::traits::{ExchangeResult, TradingApi};
+use super::types::{
+    AccountSummary, ExchangeCapabilities, OpenOrder, OrderAck, OrderType, PlaceOrderRequest,
+    Position, Side, TopOfBook,
+};
+
+/// One order as the paper venue holds it.
+#[derive(Clone, Debug)]
+pub struct PaperOrder {
+    pub id: String,
+    pub request: PlaceOrderRequest,
+    /// "new", "filled", "canceled" or "rejected"
+    pub status: String,
+    pub filled_qty: Decimal,
+    pub fill_price: Option<Decimal>,
+    /// Stop orders: the stop price has been traded through
+    pub triggered: bool,
+}
+
+impl PaperOrder {
+    fn ack(&self) -> OrderAck {
+        OrderAck {
+            id: self.id.clone(),
+            status: self.status.clone(),
+            raw: json!({
+                "filled_qty": self.filled_qty.to_string(),
+                "filled_avg_price": self.fill_price.map(|p| p.to_string()),
+            }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PaperBook {
+    cash: Decimal,
+    /// Symbol -> (qty, average entry price)
+    positions: HashMap<String, (Decimal, Decimal)>,
+    orders: Vec<PaperOrder>,
+    quotes: HashMap<String, TopOfBook>,
+}
+
+impl PaperBook {
+    /// Fill `orders[index]` at `price` if cash or holdings cover it; a fill the
+    /// account cannot cover rejects the order.
+    fn fill(&mut self, index: usize, price: Decimal) {
+        let order = &self.orders[index];
+        let symbol = order.request.symbol.clone();
+        let qty = match (order.request.qty, order.request.notional) {
+            (Some(qty), _) => qty,
+            (None, Some(notional)) if price > Decimal::ZERO => notional / price,
+            _ => Decimal::ZERO,
+        };
+        let (held, avg) = self
+            .positions
+            .get(&symbol)
+            .copied()
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+        let covered = qty > Decimal::ZERO
+            && match order.request.side {
+                Side::Buy => qty * price <= self.cash,
+                Side::Sell => qty <= held,
+            };
+        if !covered {
+            self.orders[index].status = "rejected".to_string();
+            return;
+        }
+        match order.request.side {
+            Side::Buy => {
+                self.cash -= qty * price;
+                let total = held + qty;
+                self.positions
+                    .insert(symbol, (total, (held * avg + qty * price) / total));
+            }
+            Side::Sell => {
+                self.cash += qty * price;
+                if held - qty > Decimal::ZERO {
+                    self.positions.insert(symbol, (held - qty, avg));
+                } else {
+                    self.positions.remove(&symbol);
+                }
+            }
+        }
+        let order = &mut self.orders[index];
+        order.status = "filled".to_string();
+        order.filled_qty = qty;
+        order.fill_price = Some(price);
+    }
+
+    /// Match working order `index` against the latest quote of its symbol.
+    /// A limit marketable on arrival takes the touch; one reached while
+    /// resting fills at its limit.
+    fn match_order(&mut self, index: usize, arriving: bool) {
+        let order = &self.orders[index];
+        if order.status != "new" {
+            return;
+        }
+        let Some(book) = self.quotes.get(&order.request.symbol).copied() else {
+            return;
+        };
+        let (request, triggered) = (order.request.clone(), order.triggered);
+        // Buys pay the ask, sells receive the bid
+        let touch = match request.side {
+            Side::Buy => book.ask,
+            Side::Sell => book.bid,
+        };
+        let through = |price: Decimal| match request.side {
+            Side::Buy => touch >= price,
+            Side::Sell => touch <= price,
+        };
+        let stop = matches!(
+            request.order_type,
+            OrderType::StopMarket | OrderType::StopLimit
+        );
+        if stop && !triggered {
+            match request.stop_price {
+                Some(stop_price) if through(stop_price) => self.orders[index].triggered = true,
+                _ => return,
+            }
+        }
+        let price = match request.order_type {
+            OrderType::Market | OrderType::StopMarket => Some(touch),
+            OrderType::Limit | OrderType::StopLimit => request
+                .limit_price
+                .filter(|&limit| match request.side {
+                    Side::Buy => touch <= limit,
+                    Side::Sell => touch >= limit,
+                })
+                .map(|limit| if arriving { touch } else { limit }),
+        };
+        if let Some(price) = price {
+            self.fill(index, price);
+        }
+    }
+}
+
+/// Paper venue trading long positions against fed quotes. Cheap to clone;
+/// clones share the book.
+#[derive(Clone)]
+pub struct PaperExchange {
+    book: Arc<Mutex<PaperBook>>,
+    capabilities: ExchangeCapabilities,
+}
+
+impl PaperExchange {
+    /// A venue holding `cash` in the quote currency and no positions, with
+    /// exits left to the bot (no bracket, OCO or stop orders).
+    pub fn new(cash: Decimal) -> Self {
+        Self {
+            book: Arc::new(Mutex::new(PaperBook {
+                cash,
+                ..PaperBook::default()
+            })),
+            capabilities: ExchangeCapabilities {
+                supports_notional_market_buy: true,
+                supports_ws_quotes: true,
+                supports_ws_trades: false,
+                supports_news: false,
+                supports_bracket_orders: false,
+                supports_order_replace: false,
+                supports_stop_orders: false,
+                supports_oco: false,
+                supports_short: false,
+                min_notional: Decimal::ZERO,
+            },
+        }
+    }
+
+    /// Advertise `capabilities` instead (e.g. native stops). Bracket and OCO
+    /// submissions are still refused.
+    pub fn with_capabilities(mut self, capabilities: ExchangeCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Start with `qty` of `symbol` bought at `avg_entry_price`, as a position
+    /// left by an earlier session.
+    pub fn with_position(self, symbol: &str, qty: Decimal, avg_entry_price: Decimal) -> Self {
+        self.book
+            .lock()
+            .unwrap()
+            .positions
+            .insert(symbol.to_string(), (qty, avg_entry_price));
+        self
+    }
+
+    /// New top of book for `symbol`; working orders it reaches are filled.
+    pub fn on_quote(&self, symbol: &str, bid: Decimal, ask: Decimal) {
+        let mut book = self.book.lock().unwrap();
+        book.quotes
+            .insert(symbol.to_string(), TopOfBook { bid, ask });
+        for index in 0..book.orders.len() {
+            if book.orders[index].request.symbol == symbol {
+                book.match_order(index, false);
+            }
+        }
+    }
+
+    pub fn cash(&self) -> Decimal {
+        self.book.lock().unwrap().cash
+    }
+
+    /// Held quantity of `symbol` (zero when flat).
+    pub fn position_qty(&self, symbol: &str) -> Decimal {
+        self.book
+            .lock()
+            .unwrap()
+            .positions
+            .get(symbol)
+            .map_or(Decimal::ZERO, |(qty, _)| *qty)
+    }
+
+    /// Every order submitted so far, oldest first, in its current state.
+    pub fn orders(&self) -> Vec<PaperOrder> {
+        self.book.lock().unwrap().orders.clone()
+    }
+}
+
+#[async_trait]
+impl TradingApi for PaperExchange {
+    fn name(&self) -> &'static str {
+        "paper"
+    }
+
+    fn capabilities(&self) -> ExchangeCapabilities {
+        self.capabilities.clone()
+    }
+
+    async fn get_account(&self) -> ExchangeResult<AccountSummary> {
+        let book = self.book.lock().unwrap();
+        let holdings: Decimal = book
+            .positions
+            .iter()
+            .map(|(symbol, (qty, avg))| {
+                let mark = book.quotes.get(symbol).map_or(*avg, |q| q.bid);
+                qty * mark
+            })
+            .sum();
+        Ok(AccountSummary {
+            buying_power: Some(book.cash),
+            cash: Some(book.cash),
+            portfolio_value: Some(book.cash + holdings),
+        })
+    }
+
+    async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
+        let book = self.book.lock().unwrap();
+        Ok(book
+            .positions
+            .iter()
+            .map(|(symbol, (qty, avg))| Position {
+                symbol: symbol.clone(),
+                qty: *qty,
+                avg_entry_price: Some(*avg),
+            })
+            .collect())
+    }
+
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        let book = self.book.lock().unwrap();
+        book.orders
+            .iter()
+            .find(|o| o.id == order_id)
+            .map(PaperOrder::ack)
+            .ok_or_else(|| AutoHedgeError::http(404, format!("order {} not found", order_id)))
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        let mut book = self.book.lock().unwrap();
+        let Some(order) = book.orders.iter_mut().find(|o| o.id == order_id) else {
+            return Err(AutoHedgeError::http(
+                404,
+                format!("order {} not found", order_id),
+            ));
+        };
+        if order.status != "new" {
+            return Err(AutoHedgeError::http(
+                422,
+                format!("order {} is already {}", order_id, order.status),
+            ));
+        }
+        order.status = "canceled".to_string();
+        Ok(())
+    }
+
+    async fn cancel_all_orders(&self) -> ExchangeResult<()> {
+        let mut book = self.book.lock().unwrap();
+        for order in book.orders.iter_mut().filter(|o| o.status == "new") {
+            order.status = "canceled".to_string();
+        }
+        Ok(())
+    }
+
+    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
+        let mut book = self.book.lock().unwrap();
+        let index = book.orders.len();
+        book.orders.push(PaperOrder {
+            id: format!("paper-{}", index + 1),
+            request: order,
+            status: "new".to_string(),
+            filled_qty: Decimal::ZERO,
+            fill_price: None,
+            triggered: false,
+        });
+        book.match_order(index, true);
+        let order = &book.orders[index];
+        if order.status == "rejected" {
+            return Err(AutoHedgeError::http(
+                403,
+                format!(
+                    "insufficient balance for {:?} {}",
+                    order.request.side, order.request.symbol
+                ),
+            ));
+        }
+        Ok(order.ack())
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        let book = self.book.lock().unwrap();
+        Ok(Some(
+            book.orders
+                .iter()
+                .filter(|o| o.status == "new")
+                .map(|o| OpenOrder {
+                    id: o.id.clone(),
+                    symbol: o.request.symbol.clone(),
+                    side: match o.request.side {
+                        Side::Buy => "buy".to_string(),
+                        Side::Sell => "sell".to_string(),
+                    },
+                    created_at: None,
+                })
+                .collect(),
+        ))
+    }
+
+    async fn get_best_bid_ask(&self, symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        Ok(self.book.lock().unwrap().quotes.get(symbol).copied())
+    }
+}
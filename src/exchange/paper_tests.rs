@@ -0,0 +1,123 @@
+//! Unit tests for the in-process paper venue.
+
+#[cfg(test)]
+mod paper_tests {
+    use crate::exchange::paper::*;
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use rust_decimal::Decimal;
+
+    fn order(side: Side, order_type: OrderType, qty: f64, limit: Option<f64>) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            symbol: "BTC/USD".to_string(),
+            side,
+            order_type,
+            qty: Some(dec(qty)),
+            notional: None,
+            limit_price: limit.map(dec),
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_marketable_limit_fills_at_the_touch() {
+        let paper = PaperExchange::new(dec(1000.0));
+        paper.on_quote("BTC/USD", dec(99.0), dec(100.0));
+
+        let ack = paper
+            .submit_order(order(Side::Buy, OrderType::Limit, 2.0, Some(101.0)))
+            .await
+            .unwrap();
+        assert_eq!(ack.status, "filled");
+        assert_eq!(paper.cash(), dec(800.0));
+        assert_eq!(paper.position_qty("BTC/USD"), dec(2.0));
+
+        let positions = paper.get_positions().await.unwrap();
+        assert_eq!(positions[0].avg_entry_price, Some(dec(100.0)));
+    }
+
+    #[tokio::test]
+    async fn test_resting_limit_fills_at_its_limit_once_reached() {
+        let paper = PaperExchange::new(dec(1000.0)).with_position("BTC/USD", dec(2.0), dec(100.0));
+        paper.on_quote("BTC/USD", dec(99.0), dec(100.0));
+
+        let ack = paper
+            .submit_order(order(Side::Sell, OrderType::Limit, 2.0, Some(105.0)))
+            .await
+            .unwrap();
+        assert_eq!(ack.status, "new");
+
+        // The market gaps through the limit; the resting order gets its price
+        paper.on_quote("BTC/USD", dec(107.0), dec(108.0));
+        let ack = paper.get_order(&ack.id).await.unwrap();
+        assert_eq!(ack.status, "filled");
+        assert_eq!(ack.raw["filled_qty"], "2");
+        assert_eq!(paper.cash(), dec(1210.0));
+        assert_eq!(paper.position_qty("BTC/USD"), Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_stop_triggers_through_the_stop_price() {
+        let paper =
+            PaperExchange::new(Decimal::ZERO).with_position("BTC/USD", dec(1.0), dec(100.0));
+        paper.on_quote("BTC/USD", dec(99.0), dec(100.0));
+
+        let mut stop = order(Side::Sell, OrderType::StopMarket, 1.0, None);
+        stop.stop_price = Some(dec(95.0));
+        let ack = paper.submit_order(stop).await.unwrap();
+        assert_eq!(ack.status, "new");
+
+        paper.on_quote("BTC/USD", dec(96.0), dec(97.0));
+        assert_eq!(paper.get_order(&ack.id).await.unwrap().status, "new");
+        paper.on_quote("BTC/USD", dec(94.0), dec(95.0));
+        assert_eq!(paper.get_order(&ack.id).await.unwrap().status, "filled");
+        assert_eq!(paper.cash(), dec(94.0));
+    }
+
+    #[tokio::test]
+    async fn test_uncovered_orders_are_rejected_and_kept() {
+        let paper = PaperExchange::new(dec(50.0));
+        paper.on_quote("BTC/USD", dec(99.0), dec(100.0));
+
+        assert!(paper
+            .submit_order(order(Side::Buy, OrderType::Market, 1.0, None))
+            .await
+            .is_err());
+        assert!(paper
+            .submit_order(order(Side::Sell, OrderType::Market, 1.0, None))
+            .await
+            .is_err());
+        let statuses: Vec<String> = paper.orders().into_iter().map(|o| o.status).collect();
+        assert_eq!(statuses, ["rejected", "rejected"]);
+        assert_eq!(paper.cash(), dec(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_only_working_orders() {
+        let paper = PaperExchange::new(dec(1000.0));
+        paper.on_quote("BTC/USD", dec(99.0), dec(100.0));
+        let resting = paper
+            .submit_order(order(Side::Buy, OrderType::Limit, 1.0, Some(90.0)))
+            .await
+            .unwrap();
+        let filled = paper
+            .submit_order(order(Side::Buy, OrderType::Market, 1.0, None))
+            .await
+            .unwrap();
+
+        assert_eq!(paper.get_open_orders().await.unwrap().unwrap().len(), 1);
+        paper.cancel_order(&resting.id).await.unwrap();
+        assert!(paper.cancel_order(&filled.id).await.is_err());
+        assert_eq!(
+            paper.get_order(&resting.id).await.unwrap().status,
+            "canceled"
+        );
+        // A cancelled order no longer fills
+        paper.on_quote("BTC/USD", dec(85.0), dec(86.0));
+        assert_eq!(paper.position_qty("BTC/USD"), dec(1.0));
+    }
+}
@@ -0,0 +1,163 @@
+//! REST polling fallback for market data.
+//!
+//! With `market_data.transport: polling` the session reads quotes from the
+//! venue's REST best bid/ask (`TradingApi::get_best_bid_ask`) instead of its
+//! WebSocket, every `poll_interval_ms` (or the exchange's entry in
+//! `poll_interval_overrides_ms`). A changed top of book is written to the
+//! `MarketStore` and published as the same `MarketEvent::Quote` the stream
+//! sends; unchanged polls are dropped, so quote-counting strategies see the
+//! cadence of a book ticker rather than of the timer. REST quotes carry no
+//! sizes and no trades are published. Symbols added by a config reload are
+//! polled from the next round.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use super::traits::{ExchangeResult, MarketDataStream, TradingApi};
+use super::types::TopOfBook;
+use crate::{
+    bus::EventBus,
+    config::MarketDataConfig,
+    data::store::{MarketStore, Quote},
+    events::{Event, MarketEvent},
+    money::float,
+};
+
+/// Last published quote per symbol, and the symbols whose polls are failing.
+#[derive(Default)]
+pub struct QuotePoller {
+    last: HashMap<String, TopOfBook>,
+    failing: HashSet<String>,
+}
+
+impl QuotePoller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Poll every symbol once and publish the quotes that changed. Returns
+    /// how many were published.
+    pub async fn poll(
+        &mut self,
+        exchange: &dyn TradingApi,
+        symbols: &[String],
+        store: &MarketStore,
+        bus: &EventBus,
+    ) -> usize {
+        let mut published = 0;
+        for symbol in symbols {
+            let book = match exchange.get_best_bid_ask(symbol).await {
+                Ok(Some(book)) => book,
+                Ok(None) => continue,
+                Err(e) => {
+                    if self.failing.insert(symbol.clone()) {
+                        warn!("⚠️ [POLL] {} quote poll failed: {}", symbol, e);
+                    }
+                    continue;
+                }
+            };
+            if self.failing.remove(symbol) {
+                info!("✅ [POLL] {} quote poll recovered", symbol);
+            }
+            if self.last.get(symbol) == Some(&book) {
+                continue;
+            }
+            self.last.insert(symbol.clone(), book);
+
+            let (bid, ask) = (float(book.bid), float(book.ask));
+            let timestamp = chrono::Utc::now().to_rfc3339();
+            let quote = Quote {
+                symbol: symbol.clone(),
+                bid_price: bid,
+                ask_price: ask,
+                bid_size: 0.0,
+                ask_size: 0.0,
+                timestamp: timestamp.clone(),
+            };
+            if store.accept_quote(symbol.clone(), quote).is_err() {
+                continue;
+            }
+            bus.publish(Event::Market(MarketEvent::Quote {
+                symbol: symbol.clone(),
+                bid,
+                ask,
+                timestamp,
+            }))
+            .ok();
+            published += 1;
+        }
+        published
+    }
+}
+
+/// `MarketDataStream` that polls REST quotes on a timer.
+#[derive(Clone)]
+pub struct PollingMarketStream {
+    exchange: Arc<dyn TradingApi>,
+    interval: Duration,
+}
+
+impl PollingMarketStream {
+    pub fn new(exchange: Arc<dyn TradingApi>, config: &MarketDataConfig) -> Self {
+        let interval_ms = config.poll_interval_ms_for(exchange.name()).max(1);
+        Self {
+            exchange,
+            interval: Duration::from_millis(interval_ms),
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for PollingMarketStream {
+    async fn start(
+        &self,
+        store: MarketStore,
+        symbols: Vec<String>,
+        event_bus: EventBus,
+    ) -> ExchangeResult<()> {
+        info!(
+            "📡 [POLL] Polling {} quotes every {}ms for {} symbols",
+            self.exchange.name(),
+            self.interval.as_millis(),
+            symbols.len()
+        );
+        let exchange = self.exchange.clone();
+        let interval = self.interval;
+        let shutdown = event_bus.shutdown().clone();
+        let mut rx = event_bus.subscribe("market_data");
+        let mut symbols = symbols;
+        tokio::spawn(async move {
+            let mut poller = QuotePoller::new();
+            let mut tick = tokio::time::interval(interval);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => {
+                        info!("📡 [POLL] Quote polling stopped (shutdown)");
+                        return;
+                    }
+                    event = rx.recv() => {
+                        if let Ok(Event::Config(update)) = event {
+                            for symbol in &update.config.symbols {
+                                if !symbols.contains(symbol) {
+                                    info!("📡 [POLL] Polling added symbol {}", symbol);
+                                    symbols.push(symbol.clone());
+                                }
+                            }
+                        }
+                    }
+                    _ = tick.tick() => {
+                        poller
+                            .poll(exchange.as_ref(), &symbols, &store, &event_bus)
+                            .await;
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Unit tests for the REST polling market-data stream.
+
+#[cfg(test)]
+mod polling_tests {
+    use crate::bus::{BusReceiver, EventBus};
+    use crate::config::MarketDataConfig;
+    use crate::data::store::MarketStore;
+    use crate::error::AutoHedgeError;
+    use crate::events::{Event, MarketEvent};
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::polling::*;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    type Books = Arc<Mutex<HashMap<String, Option<TopOfBook>>>>;
+
+    /// Venue whose REST quotes are set by the test; symbols without one fail.
+    fn quote_exchange() -> (MockExchange, Books) {
+        let books = Books::default();
+        let quotes = books.clone();
+        let exchange = MockExchange::new("kraken")
+            .with_capabilities(|c| c.supports_notional_market_buy = true)
+            .on_best_bid_ask(move |_, symbol| match quotes.lock().unwrap().get(symbol) {
+                Some(book) => Ok(*book),
+                None => Err(AutoHedgeError::http(503, "unavailable")),
+            });
+        (exchange, books)
+    }
+
+    fn set(books: &Books, symbol: &str, book: Option<(f64, f64)>) {
+        books.lock().unwrap().insert(
+            symbol.to_string(),
+            book.map(|(bid, ask)| TopOfBook {
+                bid: dec(bid),
+                ask: dec(ask),
+            }),
+        );
+    }
+
+    fn quotes(rx: &mut BusReceiver) -> Vec<(String, f64, f64)> {
+        let mut out = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let Event::Market(MarketEvent::Quote {
+                symbol, bid, ask, ..
+            }) = event
+            {
+                out.push((symbol, bid, ask));
+            }
+        }
+        out
+    }
+
+    // ============= Config Tests =============
+
+    #[test]
+    fn test_market_data_config_defaults_and_overrides() {
+        let config = MarketDataConfig::default();
+        assert!(!config.polling());
+        assert_eq!(config.poll_interval_ms_for("kraken"), 1000);
+
+        let config: MarketDataConfig = serde_yaml::from_str(
+            "transport: Polling\npoll_interval_ms: 500\npoll_interval_overrides_ms:\n  kraken: 2000\n",
+        )
+        .unwrap();
+        assert!(config.polling());
+        assert_eq!(config.poll_interval_ms_for("kraken"), 2000);
+        assert_eq!(config.poll_interval_ms_for("binance"), 500);
+    }
+
+    // ============= Poll Tests =============
+
+    #[tokio::test]
+    async fn test_poll_publishes_changed_quotes_only() {
+        let (exchange, books) = quote_exchange();
+        let store = MarketStore::new(10);
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let symbols = vec!["BTC/USD".to_string(), "ETH/USD".to_string()];
+        let mut poller = QuotePoller::new();
+
+        set(&books, "BTC/USD", Some((100.0, 100.5)));
+        set(&books, "ETH/USD", Some((10.0, 10.1)));
+        assert_eq!(poller.poll(&exchange, &symbols, &store, &bus).await, 2);
+        assert_eq!(
+            quotes(&mut rx),
+            vec![
+                ("BTC/USD".to_string(), 100.0, 100.5),
+                ("ETH/USD".to_string(), 10.0, 10.1)
+            ]
+        );
+        let stored = store.get_latest_quote("BTC/USD").unwrap();
+        assert_eq!((stored.bid_price, stored.ask_price), (100.0, 100.5));
+
+        // Unchanged books are not republished
+        set(&books, "BTC/USD", Some((100.1, 100.5)));
+        assert_eq!(poller.poll(&exchange, &symbols, &store, &bus).await, 1);
+        assert_eq!(quotes(&mut rx), vec![("BTC/USD".to_string(), 100.1, 100.5)]);
+    }
+
+    #[tokio::test]
+    async fn test_poll_skips_failed_and_empty_books() {
+        let (exchange, books) = quote_exchange();
+        let store = MarketStore::new(10);
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let symbols = vec!["BTC/USD".to_string(), "SOL/USD".to_string()];
+        let mut poller = QuotePoller::new();
+
+        // BTC/USD errors, SOL/USD has no book
+        set(&books, "SOL/USD", None);
+        assert_eq!(poller.poll(&exchange, &symbols, &store, &bus).await, 0);
+        assert!(quotes(&mut rx).is_empty());
+        assert!(store.get_latest_quote("BTC/USD").is_none());
+
+        // Once the venue answers again the quote flows
+        set(&books, "BTC/USD", Some((100.0, 100.5)));
+        assert_eq!(poller.poll(&exchange, &symbols, &store, &bus).await, 1);
+        assert_eq!(quotes(&mut rx).len(), 1);
+    }
+}
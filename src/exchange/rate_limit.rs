@@ -0,0 +1,200 @@
+//! Client-side REST rate limiting shared by every clone of an exchange adapter.
+//!
+//! Each venue gets one token bucket sized to its published limits:
+//! - Alpaca: plain requests/sec with a burst allowance (200 req/min per key)
+//! - Binance: request weight per minute; each endpoint costs its documented weight
+//! - Kraken: the API call counter (max counter, decaying at a fixed rate per second)
+//! - Coinbase: plain requests/sec
+//!
+//! Requests wait for tokens before they are sent. A 429 (or Binance's 418 IP
+//! ban) pauses the whole bucket for the server's `Retry-After` and the request
+//! is re-sent, up to `rate_limit.max_retries` times.
+
+use reqwest::{header::HeaderMap, RequestBuilder, Response, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::warn;
+
+use super::traits::ExchangeResult;
+use crate::config::RateLimitConfig;
+
+/// Longest pause taken from a `Retry-After`/backoff before re-sending.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+/// Token bucket: `capacity` tokens, refilled continuously at `refill_per_sec`.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        let capacity = capacity.max(1.0);
+        Self {
+            capacity,
+            refill_per_sec: refill_per_sec.max(f64::MIN_POSITIVE),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    /// Take `cost` tokens at `now`, or return how long to wait before trying again.
+    /// Costs above the capacity are clamped so they can still go through.
+    pub fn try_acquire_at(&self, cost: f64, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(until) = state.paused_until {
+            if until > now {
+                return Err(until - now);
+            }
+            state.paused_until = None;
+        }
+        let elapsed = now
+            .saturating_duration_since(state.last_refill)
+            .as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = now;
+
+        let cost = cost.min(self.capacity);
+        if state.tokens >= cost {
+            state.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (cost - state.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+
+    /// Hold every request until `now + duration` and drain the bucket
+    /// (the venue's view of our usage is ahead of ours).
+    pub fn pause_at(&self, duration: Duration, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        let until = now + duration;
+        if state.paused_until.is_none_or(|u| u < until) {
+            state.paused_until = Some(until);
+        }
+        state.tokens = 0.0;
+        state.last_refill = until;
+    }
+}
+
+/// Delay before re-sending after a throttling response: `Retry-After` seconds
+/// when the venue sends it, else exponential backoff from 1s.
+pub fn retry_after(headers: &HeaderMap, attempt: u32) -> Duration {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+        .unwrap_or_else(|| Duration::from_secs(1u64 << attempt.min(6)))
+        .min(MAX_RETRY_AFTER)
+}
+
+/// Status codes that mean "slow down" rather than a failed request.
+pub fn is_throttled(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.as_u16() == 418
+}
+
+/// Per-venue limiter handed to an adapter; clones share the bucket.
+#[derive(Clone)]
+pub struct RestLimiter {
+    venue: &'static str,
+    bucket: Option<Arc<TokenBucket>>,
+    max_retries: u32,
+}
+
+impl RestLimiter {
+    fn build(venue: &'static str, config: &RateLimitConfig, capacity: f64, rate: f64) -> Self {
+        Self {
+            venue,
+            bucket: config
+                .enabled
+                .then(|| Arc::new(TokenBucket::new(capacity, rate))),
+            max_retries: config.max_retries,
+        }
+    }
+
+    pub fn alpaca(config: &RateLimitConfig) -> Self {
+        Self::build(
+            "alpaca",
+            config,
+            config.alpaca_burst as f64,
+            config.alpaca_requests_per_sec,
+        )
+    }
+
+    /// Costs are request weights.
+    pub fn binance(config: &RateLimitConfig) -> Self {
+        let weight = config.binance_weight_per_minute as f64;
+        Self::build("binance", config, weight, weight / 60.0)
+    }
+
+    /// Costs are counter increments.
+    pub fn kraken(config: &RateLimitConfig) -> Self {
+        Self::build(
+            "kraken",
+            config,
+            config.kraken_max_counter as f64,
+            config.kraken_decay_per_sec,
+        )
+    }
+
+    pub fn coinbase(config: &RateLimitConfig) -> Self {
+        let rate = config.coinbase_requests_per_sec;
+        Self::build("coinbase", config, rate, rate)
+    }
+
+    /// Wait until `cost` tokens are available (no-op when limiting is disabled).
+    pub async fn acquire(&self, cost: u32) {
+        let Some(bucket) = &self.bucket else {
+            return;
+        };
+        while let Err(wait) = bucket.try_acquire_at(cost as f64, Instant::now()) {
+            sleep(wait).await;
+        }
+    }
+
+    /// Send the request built by `build` once tokens are available, re-sending
+    /// after the venue's `Retry-After` while it answers 429/418. The final
+    /// response is returned as-is, so callers keep their own status handling.
+    pub async fn send(
+        &self,
+        cost: u32,
+        build: impl Fn() -> RequestBuilder,
+    ) -> ExchangeResult<Response> {
+        let mut attempt = 0;
+        loop {
+            self.acquire(cost).await;
+            let resp = build().send().await?;
+            if !is_throttled(resp.status()) || attempt >= self.max_retries {
+                return Ok(resp);
+            }
+            let wait = retry_after(resp.headers(), attempt);
+            warn!(
+                "🐢 [RATE_LIMIT] {} answered {}; pausing requests for {:.1}s (retry {}/{})",
+                self.venue,
+                resp.status(),
+                wait.as_secs_f64(),
+                attempt + 1,
+                self.max_retries
+            );
+            match &self.bucket {
+                Some(bucket) => bucket.pause_at(wait, Instant::now()),
+                None => sleep(wait).await,
+            }
+            attempt += 1;
+        }
+    }
+}
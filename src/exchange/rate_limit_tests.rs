@@ -0,0 +1,161 @@
+//! Unit tests for REST rate limiting (token buckets, Retry-After handling).
+
+#[cfg(test)]
+mod rate_limit_tests {
+    use crate::config::RateLimitConfig;
+    use crate::exchange::rate_limit::*;
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    // ============= Token Bucket Tests =============
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let bucket = TokenBucket::new(3.0, 1.0);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert!(bucket.try_acquire_at(1.0, now).is_ok());
+        }
+        let wait = bucket.try_acquire_at(1.0, now).unwrap_err();
+        assert!((wait.as_secs_f64() - 1.0).abs() < 1e-6);
+
+        // One second later one token has refilled
+        assert!(bucket
+            .try_acquire_at(1.0, now + Duration::from_secs(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_bucket_weighted_costs() {
+        // Binance-style: 1200 weight/min refills 20/s
+        let bucket = TokenBucket::new(1200.0, 20.0);
+        let now = Instant::now();
+        assert!(bucket.try_acquire_at(1190.0, now).is_ok());
+        let wait = bucket.try_acquire_at(20.0, now).unwrap_err();
+        assert!((wait.as_secs_f64() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bucket_clamps_cost_above_capacity() {
+        let bucket = TokenBucket::new(2.0, 1.0);
+        assert!(bucket.try_acquire_at(10.0, Instant::now()).is_ok());
+    }
+
+    #[test]
+    fn test_bucket_refill_capped_at_capacity() {
+        let bucket = TokenBucket::new(5.0, 1.0);
+        let now = Instant::now();
+        assert!(bucket.try_acquire_at(5.0, now).is_ok());
+        let later = now + Duration::from_secs(60);
+        assert!(bucket.try_acquire_at(5.0, later).is_ok());
+        assert!(bucket.try_acquire_at(1.0, later).is_err());
+    }
+
+    #[test]
+    fn test_pause_blocks_and_drains() {
+        let bucket = TokenBucket::new(10.0, 1.0);
+        let now = Instant::now();
+        bucket.pause_at(Duration::from_secs(5), now);
+        let wait = bucket
+            .try_acquire_at(1.0, now + Duration::from_secs(2))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(3));
+
+        // After the pause the bucket refills from empty
+        let after = now + Duration::from_secs(5);
+        assert!(bucket.try_acquire_at(1.0, after).is_err());
+        assert!(bucket
+            .try_acquire_at(1.0, after + Duration::from_secs(1))
+            .is_ok());
+    }
+
+    // ============= Retry-After Tests =============
+
+    #[test]
+    fn test_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers, 0), Duration::from_secs(7));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("100000"));
+        assert_eq!(retry_after(&headers, 0), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_retry_after_backoff_without_header() {
+        let headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, 0), Duration::from_secs(1));
+        assert_eq!(retry_after(&headers, 2), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_is_throttled() {
+        assert!(is_throttled(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_throttled(StatusCode::IM_A_TEAPOT));
+        assert!(!is_throttled(StatusCode::FORBIDDEN));
+        assert!(!is_throttled(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    // ============= Limiter Tests =============
+
+    #[tokio::test]
+    async fn test_disabled_limiter_never_waits() {
+        let limiter = RestLimiter::kraken(&RateLimitConfig {
+            enabled: false,
+            ..RateLimitConfig::default()
+        });
+        let started = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire(1).await;
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_after_429() {
+        use axum::{http::StatusCode as AxumStatus, response::IntoResponse, routing::get, Router};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = Router::new().route(
+            "/orders",
+            get(move || {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        (
+                            AxumStatus::TOO_MANY_REQUESTS,
+                            [("retry-after", "0")],
+                            "slow down",
+                        )
+                            .into_response()
+                    } else {
+                        "ok".into_response()
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/orders", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = reqwest::Client::new();
+        let limiter = RestLimiter::alpaca(&RateLimitConfig::default());
+        let resp = limiter.send(1, || client.get(&url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // With retries disabled the 429 is handed back to the caller
+        hits.store(0, Ordering::SeqCst);
+        let limiter = RestLimiter::alpaca(&RateLimitConfig {
+            max_retries: 0,
+            ..RateLimitConfig::default()
+        });
+        let resp = limiter.send(1, || client.get(&url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
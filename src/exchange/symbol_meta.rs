@@ -0,0 +1,94 @@
+//! Per-symbol trading rules (tick size, step size, minimums).
+//!
+//! Raw sizing math produces quantities like 0.00312849213 that venues reject
+//! with precision errors. `SymbolMetaRegistry` holds each symbol's
+//! `SymbolMeta`, prefetched from the exchange at startup and fetched on first
+//! use for symbols added later; the execution engines run every order through
+//! `normalize` before submission. Symbols the venue publishes no rules for are
+//! passed through unchanged.
+
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::{
+    traits::TradingApi,
+    types::{PlaceOrderRequest, SymbolMeta},
+};
+
+/// Cached `SymbolMeta` per symbol; clones share the cache.
+#[derive(Clone, Default)]
+pub struct SymbolMetaRegistry {
+    metas: Arc<DashMap<String, Option<SymbolMeta>>>,
+}
+
+impl SymbolMetaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached rules for `symbol`, fetched from `exchange` on first use. A venue
+    /// without rules for the symbol is remembered; fetch errors are retried on
+    /// the next order.
+    pub async fn meta_for(&self, exchange: &dyn TradingApi, symbol: &str) -> Option<SymbolMeta> {
+        if let Some(cached) = self.metas.get(symbol) {
+            return cached.clone();
+        }
+        match exchange.get_symbol_meta(symbol).await {
+            Ok(meta) => {
+                self.metas.insert(symbol.to_string(), meta.clone());
+                meta
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ [SYMBOL_META] {} rules unavailable from {}: {}",
+                    symbol,
+                    exchange.name(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Prefetch rules for `symbols` (startup).
+    pub async fn load(&self, exchange: &dyn TradingApi, symbols: &[String]) {
+        for symbol in symbols {
+            if let Some(meta) = self.meta_for(exchange, symbol).await {
+                info!(
+                    "📏 [SYMBOL_META] {} tick={} step={} min_qty={} min_notional={}",
+                    symbol, meta.tick_size, meta.step_size, meta.min_qty, meta.min_notional
+                );
+            }
+        }
+    }
+
+    /// Round `order` to its symbol's increments (see `SymbolMeta::normalize`).
+    /// `ref_price` values market orders for the minimum-notional check. The
+    /// venue's `capabilities().min_notional` stands in where the symbol's
+    /// rules carry no minimum notional, or there are no rules at all.
+    pub async fn normalize(
+        &self,
+        exchange: &dyn TradingApi,
+        order: PlaceOrderRequest,
+        ref_price: Decimal,
+    ) -> Result<PlaceOrderRequest, String> {
+        let venue_min = exchange.capabilities().min_notional;
+        match self.meta_for(exchange, &order.symbol).await {
+            Some(mut meta) => {
+                if meta.min_notional.is_zero() {
+                    meta.min_notional = venue_min;
+                }
+                meta.normalize(order, ref_price)
+            }
+            None if venue_min > Decimal::ZERO => SymbolMeta {
+                symbol: order.symbol.clone(),
+                min_notional: venue_min,
+                ..SymbolMeta::default()
+            }
+            .normalize(order, ref_price),
+            None => Ok(order),
+        }
+    }
+}
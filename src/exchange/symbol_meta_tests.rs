@@ -0,0 +1,259 @@
+//! Unit tests for symbol trading rules, order normalization and venue parsers.
+
+#[cfg(test)]
+mod symbol_meta_tests {
+    use crate::error::AutoHedgeError;
+    use crate::exchange::alpaca::symbol_meta_from_asset;
+    use crate::exchange::coinbase::symbol_meta_from_product;
+    use crate::exchange::kraken::symbol_meta_from_asset_pair;
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::symbol_meta::SymbolMetaRegistry;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn btc_meta() -> SymbolMeta {
+        SymbolMeta {
+            symbol: "BTC/USD".to_string(),
+            tick_size: dec(0.1),
+            step_size: dec(0.00001),
+            min_qty: dec(0.0001),
+            min_notional: dec(10.0),
+        }
+    }
+
+    fn limit(side: Side, qty: Decimal, price: Decimal) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            symbol: "BTC/USD".to_string(),
+            side,
+            order_type: OrderType::Limit,
+            qty: Some(qty),
+            notional: None,
+            limit_price: Some(price),
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Rounding Tests =============
+
+    #[test]
+    fn test_round_qty_floors_to_step() {
+        let meta = btc_meta();
+        assert_eq!(meta.round_qty(dec(0.00312849213)), dec(0.00312));
+        assert_eq!(meta.round_qty(dec(0.00312849213)).to_string(), "0.00312");
+        // Exact multiples survive float representation error
+        assert_eq!(meta.round_qty(dec(0.3)), dec(0.3));
+    }
+
+    #[test]
+    fn test_round_price_by_side() {
+        let meta = btc_meta();
+        assert_eq!(meta.round_price(dec(50_000.27), Side::Buy), dec(50_000.2));
+        assert_eq!(meta.round_price(dec(50_000.27), Side::Sell), dec(50_000.3));
+        assert_eq!(meta.round_price(dec(50_000.2), Side::Sell), dec(50_000.2));
+    }
+
+    #[test]
+    fn test_zero_increments_pass_through() {
+        let meta = SymbolMeta {
+            symbol: "X".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(meta.round_qty(dec(0.123456789)), dec(0.123456789));
+        assert_eq!(meta.round_price(dec(1.23456), Side::Buy), dec(1.23456));
+    }
+
+    // ============= Normalize Tests =============
+
+    #[test]
+    fn test_normalize_rounds_limit_order() {
+        let order = btc_meta()
+            .normalize(
+                limit(Side::Buy, dec(0.00312849213), dec(50_000.27)),
+                Decimal::ZERO,
+            )
+            .unwrap();
+        assert_eq!(order.qty, Some(dec(0.00312)));
+        assert_eq!(order.limit_price, Some(dec(50_000.2)));
+    }
+
+    #[test]
+    fn test_normalize_rejects_below_minimums() {
+        let meta = btc_meta();
+        let err = meta
+            .normalize(
+                limit(Side::Buy, dec(0.000099), dec(50_000.0)),
+                Decimal::ZERO,
+            )
+            .unwrap_err();
+        assert!(err.contains("min qty"));
+
+        // 0.0001 BTC at $50k = $5 < $10
+        let err = meta
+            .normalize(limit(Side::Sell, dec(0.0001), dec(50_000.0)), Decimal::ZERO)
+            .unwrap_err();
+        assert!(err.contains("min notional"));
+    }
+
+    #[test]
+    fn test_normalize_market_orders() {
+        let meta = btc_meta();
+        let mut market = limit(Side::Sell, dec(0.0001), dec(0.0));
+        market.order_type = OrderType::Market;
+        market.limit_price = None;
+        // Valued at the reference price
+        assert!(meta.normalize(market.clone(), dec(200_000.0)).is_ok());
+        assert!(meta.normalize(market, dec(50_000.0)).is_err());
+
+        let mut notional = limit(Side::Buy, dec(0.0), dec(0.0));
+        notional.order_type = OrderType::Market;
+        notional.qty = None;
+        notional.limit_price = None;
+        notional.notional = Some(dec(25.006));
+        let order = meta.normalize(notional.clone(), dec(50_000.0)).unwrap();
+        assert_eq!(order.notional, Some(dec(25.0)));
+        notional.notional = Some(dec(5.0));
+        assert!(meta.normalize(notional, dec(50_000.0)).is_err());
+    }
+
+    // ============= Registry Tests =============
+
+    /// Venue that publishes rules for BTC/USD only, with a venue-wide
+    /// minimum order value of `min_notional`.
+    fn meta_exchange(min_notional: Decimal) -> MockExchange {
+        MockExchange::new("meta")
+            .with_capabilities(|c| {
+                c.supports_notional_market_buy = true;
+                c.min_notional = min_notional;
+            })
+            .on_symbol_meta(|_, symbol| match symbol {
+                "BTC/USD" => Ok(Some(btc_meta())),
+                "ERR/USD" => Err(AutoHedgeError::http(503, "unavailable")),
+                _ => Ok(None),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_registry_caches_lookups() {
+        let exchange = meta_exchange(Decimal::ZERO);
+        let registry = SymbolMetaRegistry::new();
+        registry
+            .load(&exchange, &["BTC/USD".to_string(), "ETH/USD".to_string()])
+            .await;
+        assert_eq!(exchange.call_count("get_symbol_meta"), 2);
+
+        // Known and rule-less symbols are both cached
+        assert_eq!(
+            registry.meta_for(&exchange, "BTC/USD").await,
+            Some(btc_meta())
+        );
+        assert_eq!(registry.meta_for(&exchange, "ETH/USD").await, None);
+        assert_eq!(exchange.call_count("get_symbol_meta"), 2);
+
+        // Errors are retried
+        registry.meta_for(&exchange, "ERR/USD").await;
+        registry.meta_for(&exchange, "ERR/USD").await;
+        assert_eq!(exchange.call_count("get_symbol_meta"), 4);
+    }
+
+    #[tokio::test]
+    async fn test_registry_normalize_passes_unknown_symbols() {
+        let exchange = meta_exchange(Decimal::ZERO);
+        let registry = SymbolMetaRegistry::new();
+        let order = registry
+            .normalize(
+                &exchange,
+                limit(Side::Buy, dec(0.00312849213), dec(50_000.27)),
+                Decimal::ZERO,
+            )
+            .await
+            .unwrap();
+        assert_eq!(order.qty, Some(dec(0.00312)));
+
+        let mut eth = limit(Side::Buy, dec(0.123456789), dec(3_000.123));
+        eth.symbol = "ETH/USD".to_string();
+        let order = registry
+            .normalize(&exchange, eth, Decimal::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(order.qty, Some(dec(0.123456789)));
+    }
+
+    #[tokio::test]
+    async fn test_registry_applies_venue_min_notional_without_symbol_minimum() {
+        let exchange = meta_exchange(dec(5.0));
+        let registry = SymbolMetaRegistry::new();
+        let mut eth = limit(Side::Buy, dec(0.001), dec(3_000.0));
+        eth.symbol = "ETH/USD".to_string();
+        // $3 order on a symbol without rules: below the venue's $5
+        assert!(registry
+            .normalize(&exchange, eth.clone(), Decimal::ZERO)
+            .await
+            .is_err());
+        eth.qty = Some(dec(0.002));
+        assert!(registry
+            .normalize(&exchange, eth, Decimal::ZERO)
+            .await
+            .is_ok());
+
+        // The symbol's own minimum wins where it has one
+        let btc = limit(Side::Buy, dec(0.00018), dec(50_000.0));
+        assert!(registry
+            .normalize(&exchange, btc, Decimal::ZERO)
+            .await
+            .is_err());
+    }
+
+    // ============= Venue Parser Tests =============
+
+    #[test]
+    fn test_kraken_asset_pair() {
+        let result = json!({"XXBTZUSD": {
+            "altname": "XBTUSD", "pair_decimals": 1, "lot_decimals": 8,
+            "ordermin": "0.0001", "costmin": "0.5", "tick_size": "0.1"
+        }});
+        let meta = symbol_meta_from_asset_pair("BTC/USD", &result).unwrap();
+        assert_eq!(meta.tick_size, dec(0.1));
+        assert_eq!(meta.step_size, dec(0.00000001));
+        assert_eq!(meta.min_qty, dec(0.0001));
+        assert_eq!(meta.min_notional, dec(0.5));
+        assert!(symbol_meta_from_asset_pair("BTC/USD", &json!({})).is_none());
+    }
+
+    #[test]
+    fn test_coinbase_product() {
+        let product = json!({
+            "product_id": "BTC-USD", "price_increment": "0.01",
+            "base_increment": "0.00000001", "base_min_size": "0.00000001",
+            "quote_min_size": "1"
+        });
+        let meta = symbol_meta_from_product("BTC/USD", &product);
+        assert_eq!(meta.tick_size, dec(0.01));
+        assert_eq!(meta.step_size, dec(0.00000001));
+        assert_eq!(meta.min_notional, dec(1.0));
+    }
+
+    #[test]
+    fn test_alpaca_asset() {
+        let crypto = json!({
+            "class": "crypto", "price_increment": 1.0,
+            "min_trade_increment": 0.0001, "min_order_size": 0.0001
+        });
+        let meta = symbol_meta_from_asset("BTC/USD", &crypto);
+        assert_eq!(meta.tick_size, dec(1.0));
+        assert_eq!(meta.step_size, dec(0.0001));
+
+        let whole_shares = json!({"class": "us_equity", "fractionable": false});
+        let meta = symbol_meta_from_asset("BRK.A", &whole_shares);
+        assert_eq!((meta.tick_size, meta.step_size), (dec(0.01), dec(1.0)));
+        let fractional = json!({"class": "us_equity", "fractionable": true});
+        assert_eq!(
+            symbol_meta_from_asset("AAPL", &fractional).step_size,
+            Decimal::ZERO
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Canonical symbol ids and their venue-native forms.
+//!
+//! Internally every symbol is canonical: "BASE/QUOTE" for crypto
+//! ("BTC/USD", "ETH/USDT"), the plain ticker for stocks ("AAPL"). Venues
+//! spell them differently: Binance "BTCUSDT" (lowercase in stream names),
+//! Coinbase "BTC-USD", Kraken "XBT/USD" on its socket and "XBTUSD" over REST,
+//! Alpaca "BTC/USD" on orders but "BTCUSD" on positions. `SymbolRegistry`
+//! owns that mapping for one session venue: WS parsers, REST adapters, the
+//! user stream and the position tracker translate through it at the edge,
+//! so a symbol never reaches the services in a venue's spelling.
+//!
+//! Registered (configured) symbols are canonical as spelled in the config,
+//! and any venue spelling of them resolves back to it. Unregistered
+//! native symbols fall back to rules: any '/', '-' or '_' separator splits
+//! base and quote (Kraken's XBT/XDG become BTC/DOGE); a Binance or Kraken
+//! symbol without one is split at a known quote suffix; anything else
+//! (stock tickers) is kept.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+
+use super::symbols::{
+    to_binance_stream_symbol, to_binance_symbol, to_coinbase_product_id, to_kraken_pair,
+};
+use super::types::{OpenOrder, Position};
+
+/// Quote assets a separator-less Binance/Kraken symbol is split at, longest
+/// first.
+const QUOTE_SUFFIXES: [&str; 10] = [
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USD", "EUR", "GBP", "BTC", "ETH",
+];
+
+/// Venue whose symbol spelling a registry translates to and from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Venue {
+    #[default]
+    Alpaca,
+    Binance,
+    Coinbase,
+    Kraken,
+}
+
+impl Venue {
+    /// Venue of an exchange name ("alpaca", "binance", ...); None for others.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "alpaca" => Some(Venue::Alpaca),
+            "binance" => Some(Venue::Binance),
+            "coinbase" => Some(Venue::Coinbase),
+            "kraken" => Some(Venue::Kraken),
+            _ => None,
+        }
+    }
+}
+
+/// Uppercase alphanumerics only: "btc-usd", "BTC/USD" and "BTCUSD" share a key.
+fn compact(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Canonical base asset for a venue alias.
+fn canonical_base(base: &str) -> &str {
+    match base {
+        "XBT" => "BTC",
+        "XDG" => "DOGE",
+        other => other,
+    }
+}
+
+/// Canonical <-> native symbols of one venue; clones share the mapping.
+#[derive(Clone, Default)]
+pub struct SymbolRegistry {
+    venue: Venue,
+    /// Compact native spelling -> canonical id
+    canonical: Arc<DashMap<String, String>>,
+}
+
+impl SymbolRegistry {
+    /// Registry for `venue` with `symbols` (canonical) registered.
+    pub fn new(venue: Venue, symbols: &[String]) -> Self {
+        let registry = Self {
+            venue,
+            canonical: Arc::new(DashMap::new()),
+        };
+        for symbol in symbols {
+            registry.register(symbol);
+        }
+        registry
+    }
+
+    pub fn venue(&self) -> Venue {
+        self.venue
+    }
+
+    /// Register a canonical id (symbols added while a session runs). It is
+    /// kept as spelled, so every spelling of it resolves to the configured one.
+    pub fn register(&self, canonical: &str) {
+        for spelling in [canonical.to_string(), self.native_of(canonical)] {
+            self.canonical
+                .insert(compact(&spelling), canonical.to_string());
+        }
+    }
+
+    /// Native spelling of `canonical` for orders and REST paths.
+    pub fn native(&self, canonical: &str) -> String {
+        self.native_of(canonical)
+    }
+
+    /// Native spelling of `canonical` for stream subscriptions (Binance
+    /// streams are lowercase).
+    pub fn stream_native(&self, canonical: &str) -> String {
+        match self.venue {
+            Venue::Binance => to_binance_stream_symbol(canonical),
+            _ => self.native_of(canonical),
+        }
+    }
+
+    /// Canonical id of a symbol as the venue sent it.
+    pub fn canonical(&self, native: &str) -> String {
+        if let Some(canonical) = self.canonical.get(&compact(native)) {
+            return canonical.clone();
+        }
+        self.canonical_of(native)
+    }
+
+    /// `positions` with canonical symbols.
+    pub fn canonical_positions(&self, positions: Vec<Position>) -> Vec<Position> {
+        positions
+            .into_iter()
+            .map(|p| Position {
+                symbol: self.canonical(&p.symbol),
+                ..p
+            })
+            .collect()
+    }
+
+    /// `orders` with canonical symbols.
+    pub fn canonical_orders(&self, orders: Vec<OpenOrder>) -> Vec<OpenOrder> {
+        orders
+            .into_iter()
+            .map(|o| OpenOrder {
+                symbol: self.canonical(&o.symbol),
+                ..o
+            })
+            .collect()
+    }
+
+    fn native_of(&self, canonical: &str) -> String {
+        match self.venue {
+            Venue::Alpaca => canonical.to_string(),
+            Venue::Binance => to_binance_symbol(canonical),
+            Venue::Coinbase => to_coinbase_product_id(canonical),
+            Venue::Kraken => to_kraken_pair(canonical),
+        }
+    }
+
+    /// Rule-based canonical id of an unregistered symbol.
+    fn canonical_of(&self, native: &str) -> String {
+        let trimmed = native.trim();
+        if let Some((base, quote)) = trimmed.split_once(['/', '-', '_']) {
+            let (base, quote) = (base.to_uppercase(), quote.to_uppercase());
+            return format!("{}/{}", canonical_base(&base), quote);
+        }
+        if matches!(self.venue, Venue::Binance | Venue::Kraken) {
+            let upper = trimmed.to_uppercase();
+            for quote in QUOTE_SUFFIXES {
+                if let Some(base) = upper.strip_suffix(quote).filter(|b| !b.is_empty()) {
+                    return format!("{}/{}", canonical_base(base), quote);
+                }
+            }
+        }
+        trimmed.to_string()
+    }
+}
@@ -0,0 +1,86 @@
+//! Unit tests for the symbol registry.
+
+#[cfg(test)]
+mod symbol_registry_tests {
+    use crate::exchange::symbol_registry::*;
+    use crate::exchange::types::{OpenOrder, Position};
+    use rust_decimal::Decimal;
+
+    fn registry(venue: Venue, symbols: &[&str]) -> SymbolRegistry {
+        let symbols: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        SymbolRegistry::new(venue, &symbols)
+    }
+
+    #[test]
+    fn test_native_spellings_per_venue() {
+        assert_eq!(registry(Venue::Alpaca, &[]).native("BTC/USD"), "BTC/USD");
+        assert_eq!(registry(Venue::Binance, &[]).native("BTC/USDT"), "BTCUSDT");
+        assert_eq!(
+            registry(Venue::Binance, &[]).stream_native("BTC/USDT"),
+            "btcusdt"
+        );
+        assert_eq!(registry(Venue::Coinbase, &[]).native("ETH/USD"), "ETH-USD");
+        assert_eq!(registry(Venue::Kraken, &[]).native("BTC/USD"), "XBT/USD");
+    }
+
+    #[test]
+    fn test_every_spelling_of_a_registered_symbol_resolves_to_config() {
+        let kraken = registry(Venue::Kraken, &["BTC/USD"]);
+        for native in ["XBT/USD", "XBTUSD", "BTC/USD", "btc-usd"] {
+            assert_eq!(kraken.canonical(native), "BTC/USD", "{}", native);
+        }
+
+        let alpaca = registry(Venue::Alpaca, &["BTC/USD", "AAPL"]);
+        assert_eq!(alpaca.canonical("BTCUSD"), "BTC/USD");
+        assert_eq!(alpaca.canonical("AAPL"), "AAPL");
+
+        let binance = registry(Venue::Binance, &[]);
+        binance.register("SOL/USDT");
+        assert_eq!(binance.canonical("solusdt"), "SOL/USDT");
+    }
+
+    #[test]
+    fn test_unregistered_symbols_fall_back_to_rules() {
+        let binance = registry(Venue::Binance, &[]);
+        assert_eq!(binance.canonical("ETHUSDT"), "ETH/USDT");
+        assert_eq!(binance.canonical("ethfdusd"), "ETH/FDUSD");
+
+        let kraken = registry(Venue::Kraken, &[]);
+        assert_eq!(kraken.canonical("XDG/USD"), "DOGE/USD");
+        assert_eq!(kraken.canonical("XBTEUR"), "BTC/EUR");
+
+        let coinbase = registry(Venue::Coinbase, &[]);
+        assert_eq!(coinbase.canonical("SOL-USD"), "SOL/USD");
+
+        // Stock tickers are kept as-is
+        assert_eq!(registry(Venue::Alpaca, &[]).canonical("MSFT"), "MSFT");
+    }
+
+    #[test]
+    fn test_positions_and_orders_are_canonicalized() {
+        let alpaca = registry(Venue::Alpaca, &["ETH/USD"]);
+        let positions = alpaca.canonical_positions(vec![Position {
+            symbol: "ETHUSD".to_string(),
+            qty: Decimal::ONE,
+            avg_entry_price: None,
+        }]);
+        assert_eq!(positions[0].symbol, "ETH/USD");
+
+        let coinbase = registry(Venue::Coinbase, &["BTC/USD"]);
+        let orders = coinbase.canonical_orders(vec![OpenOrder {
+            id: "1".to_string(),
+            symbol: "BTC-USD".to_string(),
+            side: "buy".to_string(),
+            created_at: None,
+        }]);
+        assert_eq!(orders[0].symbol, "BTC/USD");
+    }
+
+    #[test]
+    fn test_clones_share_registrations() {
+        let original = registry(Venue::Binance, &[]);
+        let clone = original.clone();
+        original.register("PEPE/USDT");
+        assert_eq!(clone.canonical("PEPEUSDT"), "PEPE/USDT");
+    }
+}
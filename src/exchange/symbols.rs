@@ -17,7 +17,46 @@ pub fn to_kraken_pair(canonical: &str) -> String {
     s
 }
 
+/// REST pair name, e.g. "BTC/USD" -> "XBTUSD".
+pub fn to_kraken_rest_pair(canonical: &str) -> String {
+    to_kraken_pair(canonical).replace('/', "")
+}
+
+/// Canonical asset for a Kraken balance key: legacy four-letter codes drop their
+/// X/Z prefix ("XXBT", "ZUSD") and XBT/XDG become BTC/DOGE.
+pub fn from_kraken_asset(asset: &str) -> String {
+    let code = match asset.len() {
+        4 if asset.starts_with('X') || asset.starts_with('Z') => &asset[1..],
+        _ => asset,
+    };
+    match code {
+        "XBT" => "BTC".to_string(),
+        "XDG" => "DOGE".to_string(),
+        other => other.to_string(),
+    }
+}
+
 pub fn to_binance_stream_symbol(canonical: &str) -> String {
     // Binance spot commonly uses e.g. BTCUSDT; for USD-quoted pairs keep BTCUSD.
     canonical.replace('/', "").to_lowercase()
 }
+
+/// REST symbol, e.g. "BTC/USDT" -> "BTCUSDT".
+pub fn to_binance_symbol(canonical: &str) -> String {
+    canonical.replace('/', "").to_uppercase()
+}
+
+/// Minutes in an Alpaca-style bar timeframe ("1Min", "15Min", "1Hour", "1Day").
+/// Venues without named timeframes (Binance klines, Kraken OHLC) map from this.
+pub fn timeframe_minutes(timeframe: &str) -> Option<u32> {
+    let unit_start = timeframe.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = timeframe.split_at(unit_start);
+    let count: u32 = count.parse().ok().filter(|n| *n > 0)?;
+    let minutes = match unit.to_lowercase().as_str() {
+        "min" | "t" | "m" => 1,
+        "hour" | "h" => 60,
+        "day" | "d" => 1440,
+        _ => return None,
+    };
+    Some(count * minutes)
+}
@@ -0,0 +1,183 @@
+//! Venue clock offset measurement.
+//!
+//! Binance rejects signed requests whose `timestamp` is further than
+//! `recvWindow` from its own clock, Coinbase checks the JWT `nbf`/`exp` and
+//! HMAC timestamp, and Kraken nonces are millisecond timestamps. Those adapters
+//! stamp requests from a `ServerClock`: local time plus the last measured
+//! offset, shared by every clone of the adapter. `TimeSync` re-measures the
+//! offset every `time_sync.interval_secs` the NTP way: of `probes` server-time
+//! requests the one with the shortest round trip is kept, assuming the venue
+//! read its clock half-way through it. Drift of `warn_drift_ms` or more is
+//! logged; the current offset is charted as `clock_offset_ms`.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::traits::{ExchangeResult, TradingApi};
+use crate::bus::Shutdown;
+use crate::config::TimeSyncConfig;
+
+/// One server-time round trip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockSample {
+    /// Venue clock minus local clock
+    pub offset_ms: i64,
+    pub rtt_ms: i64,
+}
+
+/// Offset of a server time read between local `sent_ms` and `received_ms`.
+pub fn clock_sample(sent_ms: i64, server_ms: i64, received_ms: i64) -> ClockSample {
+    let rtt_ms = (received_ms - sent_ms).max(0);
+    ClockSample {
+        offset_ms: server_ms - (sent_ms + rtt_ms / 2),
+        rtt_ms,
+    }
+}
+
+/// Local clock corrected by the last measured venue offset.
+#[derive(Clone, Debug, Default)]
+pub struct ServerClock {
+    offset_ms: Arc<AtomicI64>,
+    synced: Arc<AtomicBool>,
+}
+
+impl ServerClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Venue time in epoch milliseconds.
+    pub fn now_ms(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis() + self.offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Last measured offset, or None before the first measurement.
+    pub fn offset_ms(&self) -> Option<i64> {
+        self.synced
+            .load(Ordering::Relaxed)
+            .then(|| self.offset_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn set_offset_ms(&self, offset_ms: i64) {
+        self.offset_ms.store(offset_ms, Ordering::Relaxed);
+        self.synced.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `GET /api/v3/time` (spot) or `/fapi/v1/time` (futures): `{"serverTime": ms}`.
+pub fn binance_server_time(raw: &Value) -> Option<i64> {
+    raw.get("serverTime")?.as_i64()
+}
+
+/// `Time` result: `{"unixtime": secs, "rfc1123": ...}` (whole seconds only).
+pub fn kraken_server_time(result: &Value) -> Option<i64> {
+    Some(result.get("unixtime")?.as_i64()? * 1000)
+}
+
+/// `GET /api/v3/brokerage/time`: `{"iso", "epochSeconds", "epochMillis"}`
+/// (numbers sent as strings).
+pub fn coinbase_server_time(raw: &Value) -> Option<i64> {
+    let field = |key: &str| {
+        let v = raw.get(key)?;
+        v.as_i64().or_else(|| v.as_str()?.parse().ok())
+    };
+    field("epochMillis").or_else(|| field("epochSeconds").map(|s| s * 1000))
+}
+
+/// Keeps the exchange's `ServerClock` in step with the venue.
+pub struct TimeSync {
+    exchange: Arc<dyn TradingApi>,
+    config: TimeSyncConfig,
+}
+
+impl TimeSync {
+    pub fn new(exchange: Arc<dyn TradingApi>, config: &TimeSyncConfig) -> Self {
+        Self {
+            exchange,
+            config: config.clone(),
+        }
+    }
+
+    /// Shortest-round-trip sample of `probes` server-time requests. None when
+    /// the venue has no time endpoint; an error only if every probe failed.
+    pub async fn measure(&self) -> ExchangeResult<Option<ClockSample>> {
+        let mut best: Option<ClockSample> = None;
+        let mut last_err = None;
+        for _ in 0..self.config.probes.max(1) {
+            let sent = chrono::Utc::now().timestamp_millis();
+            let server = match self.exchange.get_server_time().await {
+                Ok(Some(server)) => server,
+                Ok(None) => return Ok(None),
+                Err(e) => {
+                    last_err = Some(e);
+                    continue;
+                }
+            };
+            let sample = clock_sample(sent, server, chrono::Utc::now().timestamp_millis());
+            if best.is_none_or(|b| sample.rtt_ms < b.rtt_ms) {
+                best = Some(sample);
+            }
+        }
+        match (best, last_err) {
+            (None, Some(e)) => Err(e),
+            (best, _) => Ok(best),
+        }
+    }
+
+    /// Measure and apply the offset. Returns the sample applied, if any.
+    pub async fn sync(&self) -> Option<ClockSample> {
+        let clock = self.exchange.server_clock()?;
+        let sample = match self.measure().await {
+            Ok(sample) => sample?,
+            Err(e) => {
+                warn!(
+                    "⚠️ [TIME-SYNC] {} server time unavailable: {}",
+                    self.exchange.name(),
+                    e
+                );
+                return None;
+            }
+        };
+        let previous = clock.offset_ms();
+        clock.set_offset_ms(sample.offset_ms);
+        if sample.offset_ms.unsigned_abs() >= self.config.warn_drift_ms {
+            warn!(
+                "⏱️ [TIME-SYNC] Local clock is {}ms {} {} (rtt {}ms) - signed requests corrected; check NTP on this host",
+                sample.offset_ms.abs(),
+                if sample.offset_ms > 0 { "behind" } else { "ahead of" },
+                self.exchange.name(),
+                sample.rtt_ms
+            );
+        } else if previous != Some(sample.offset_ms) {
+            info!(
+                "⏱️ [TIME-SYNC] {} clock offset {:+}ms (rtt {}ms)",
+                self.exchange.name(),
+                sample.offset_ms,
+                sample.rtt_ms
+            );
+        }
+        Some(sample)
+    }
+
+    /// Re-sync every `interval_secs` until the session drains.
+    pub fn start(self, shutdown: Shutdown) {
+        let period = Duration::from_secs(self.config.interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(period);
+            // The first measurement is taken synchronously at session start
+            tick.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = tick.tick() => {
+                        self.sync().await;
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,118 @@
+//! Unit tests for venue clock sync (offset math, time parsing, measurement).
+
+#[cfg(test)]
+mod time_sync_tests {
+    use crate::config::TimeSyncConfig;
+    use crate::error::AutoHedgeError;
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::time_sync::*;
+    use crate::exchange::traits::TradingApi;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// Venue whose clock runs `skew_ms` ahead of ours; the first `failures`
+    /// time requests fail.
+    fn skewed(skew_ms: i64, failures: usize) -> Arc<MockExchange> {
+        let exchange = MockExchange::new("skewed")
+            .with_server_clock(ServerClock::new())
+            .on_server_time(move |venue| {
+                if venue.call_count("get_server_time") <= failures {
+                    return Err(AutoHedgeError::http(503, "unavailable"));
+                }
+                Ok(Some(chrono::Utc::now().timestamp_millis() + skew_ms))
+            });
+        Arc::new(exchange)
+    }
+
+    fn config(probes: u32) -> TimeSyncConfig {
+        TimeSyncConfig {
+            probes,
+            ..TimeSyncConfig::default()
+        }
+    }
+
+    // ============= Offset Tests =============
+
+    #[test]
+    fn test_clock_sample_uses_round_trip_midpoint() {
+        // Sent at 1000, answered at 1100; the venue read 6050 half-way through
+        assert_eq!(
+            clock_sample(1_000, 6_050, 1_100),
+            ClockSample {
+                offset_ms: 5_000,
+                rtt_ms: 100
+            }
+        );
+        assert_eq!(clock_sample(1_000, 900, 1_000).offset_ms, -100);
+    }
+
+    #[test]
+    fn test_server_clock_applies_offset() {
+        let clock = ServerClock::new();
+        assert_eq!(clock.offset_ms(), None);
+
+        let shared = clock.clone();
+        shared.set_offset_ms(-2_000);
+        assert_eq!(clock.offset_ms(), Some(-2_000));
+        let drift = chrono::Utc::now().timestamp_millis() - clock.now_ms();
+        assert!((1_990..=2_010).contains(&drift), "{}", drift);
+    }
+
+    // ============= Parsing Tests =============
+
+    #[test]
+    fn test_parse_server_times() {
+        assert_eq!(
+            binance_server_time(&json!({"serverTime": 1_700_000_000_123i64})),
+            Some(1_700_000_000_123)
+        );
+        assert_eq!(
+            kraken_server_time(&json!({"unixtime": 1_700_000_000, "rfc1123": "..."})),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(
+            coinbase_server_time(&json!({
+                "iso": "2023-11-14T22:13:20Z",
+                "epochSeconds": "1700000000",
+                "epochMillis": "1700000000123"
+            })),
+            Some(1_700_000_000_123)
+        );
+        assert_eq!(
+            coinbase_server_time(&json!({"epochSeconds": "1700000000"})),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(binance_server_time(&json!({})), None);
+    }
+
+    // ============= Sync Tests =============
+
+    #[tokio::test]
+    async fn test_sync_applies_measured_offset() {
+        let exchange = skewed(4_000, 1);
+        let sync = TimeSync::new(exchange.clone(), &config(3));
+
+        let sample = sync.sync().await.unwrap();
+        assert!((3_990..=4_010).contains(&sample.offset_ms), "{:?}", sample);
+        assert_eq!(exchange.call_count("get_server_time"), 3);
+        let offset = exchange.server_clock().unwrap().offset_ms().unwrap();
+        assert_eq!(offset, sample.offset_ms);
+    }
+
+    #[tokio::test]
+    async fn test_measure_fails_only_when_every_probe_fails() {
+        let exchange = skewed(0, 5);
+        let sync = TimeSync::new(exchange.clone(), &config(2));
+        assert!(sync.measure().await.is_err());
+        assert!(sync.sync().await.is_none());
+        assert_eq!(exchange.server_clock().unwrap().offset_ms(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_venues_without_clock() {
+        let exchange = Arc::new(MockExchange::new("skewed"));
+        let sync = TimeSync::new(exchange, &config(3));
+        assert!(sync.measure().await.unwrap().is_none());
+        assert!(sync.sync().await.is_none());
+    }
+}
@@ -1,11 +1,16 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 
-use crate::{bus::EventBus, data::store::MarketStore};
+use crate::{bus::EventBus, data::store::MarketStore, error::AutoHedgeError};
 
-use super::types::{AccountSummary, ExchangeCapabilities, OrderAck, PlaceOrderRequest, Position};
+use super::time_sync::ServerClock;
+use super::types::{
+    AccountSummary, BracketLegs, ExchangeCapabilities, OpenOrder, OrderAck, PlaceOrderRequest,
+    Position, SymbolMeta, TopOfBook,
+};
 
-pub type ExchangeResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+pub type ExchangeResult<T> = crate::error::Result<T>;
 
 #[async_trait]
 pub trait TradingApi: Send + Sync {
@@ -19,10 +24,101 @@ pub trait TradingApi: Send + Sync {
     async fn cancel_all_orders(&self) -> ExchangeResult<()>;
     async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck>;
 
+    /// Submit an entry with exchange-managed TP/SL legs (one cancels the other).
+    /// Only valid when `capabilities().supports_bracket_orders` is true.
+    async fn submit_bracket_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        let _ = (order, legs);
+        Err(AutoHedgeError::Unsupported(format!(
+            "{} does not support bracket orders",
+            self.name()
+        )))
+    }
+
+    /// Submit an exit pair for a held position: a limit at
+    /// `legs.take_profit` and a stop at `legs.stop_loss`, where a fill of one
+    /// cancels the other. `order` carries the symbol, side and qty. Only valid
+    /// when `capabilities().supports_oco` is true.
+    async fn submit_oco_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        let _ = (order, legs);
+        Err(AutoHedgeError::Unsupported(format!(
+            "{} does not support OCO orders",
+            self.name()
+        )))
+    }
+
+    /// Replace resting order `order_id` with `order` (same symbol and side,
+    /// new qty/limit) in one request. Only valid when
+    /// `capabilities().supports_order_replace` is true. On error the old order
+    /// may or may not still be live; read it back before acting on it. The
+    /// returned ack carries the replacement's id, which may be a new one.
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        let _ = (order_id, order);
+        Err(AutoHedgeError::Unsupported(format!(
+            "{} does not support order replace",
+            self.name()
+        )))
+    }
+
+    /// Working orders on the account; `None` where the adapter cannot list them.
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        Ok(None)
+    }
+
+    /// Arm or refresh the venue's cancel-on-disconnect timer: every open
+    /// order is cancelled unless it is called again within `timeout_secs`;
+    /// 0 disarms it. `false` where the venue has no such switch.
+    async fn cancel_orders_after(&self, timeout_secs: u64) -> ExchangeResult<bool> {
+        let _ = timeout_secs;
+        Ok(false)
+    }
+
+    /// Tick/step size and order minimums for `symbol`, where the venue publishes them.
+    async fn get_symbol_meta(&self, _symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        Ok(None)
+    }
+
+    /// Best bid/ask over REST, polled when the quote stream goes stale.
+    async fn get_best_bid_ask(&self, _symbol: &str) -> ExchangeResult<Option<TopOfBook>> {
+        Ok(None)
+    }
+
+    /// Clock that stamps signed requests, for venues that check timestamps.
+    fn server_clock(&self) -> Option<ServerClock> {
+        None
+    }
+
+    /// Venue time in epoch ms from its public time endpoint.
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        Ok(None)
+    }
+
     /// Optional helper for strategy warmup/backfill.
     async fn get_historical_bars(&self, _symbol: &str, _timeframe: &str) -> ExchangeResult<Value> {
         Ok(Value::Null)
     }
+
+    /// One page of bars starting at `since` (same shape as
+    /// `get_historical_bars`), for the history downloader.
+    async fn get_bars_since(
+        &self,
+        _symbol: &str,
+        _timeframe: &str,
+        _since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        Ok(Value::Null)
+    }
 }
 
 #[async_trait]
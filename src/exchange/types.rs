@@ -1,21 +1,24 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AccountSummary {
-    pub buying_power: Option<f64>,
-    pub cash: Option<f64>,
-    pub portfolio_value: Option<f64>,
+    pub buying_power: Option<Decimal>,
+    pub cash: Option<Decimal>,
+    pub portfolio_value: Option<Decimal>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Position {
     pub symbol: String,
-    pub qty: f64,
-    pub avg_entry_price: Option<f64>,
+    pub qty: Decimal,
+    pub avg_entry_price: Option<Decimal>,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     Buy,
@@ -27,6 +30,12 @@ pub enum Side {
 pub enum OrderType {
     Market,
     Limit,
+    /// Market order once the price trades through `stop_price`
+    #[serde(rename = "stop_market")]
+    StopMarket,
+    /// Limit order at `limit_price` once the price trades through `stop_price`
+    #[serde(rename = "stop_limit")]
+    StopLimit,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -35,6 +44,17 @@ pub enum TimeInForce {
     Day,
     Gtc,
     Ioc, // Immediate Or Cancel - for crypto limit orders
+    /// Resting (GTC) limit that is refused or expired instead of taking
+    /// liquidity. Venues without a maker-only flag (Alpaca) get a plain GTC.
+    #[serde(rename = "post_only")]
+    PostOnly,
+}
+
+/// Exit legs attached to an entry order (bracket / OCO).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BracketLegs {
+    pub take_profit: Decimal,
+    pub stop_loss: Decimal,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -43,11 +63,79 @@ pub struct PlaceOrderRequest {
     pub side: Side,
     pub order_type: OrderType,
     /// Quantity in base units. If notional is set, qty may be None.
-    pub qty: Option<f64>,
+    pub qty: Option<Decimal>,
     /// Notional in quote currency. If qty is set, notional may be None.
-    pub notional: Option<f64>,
-    pub limit_price: Option<f64>,
+    pub notional: Option<Decimal>,
+    pub limit_price: Option<Decimal>,
     pub time_in_force: TimeInForce,
+    /// Attached TP/SL legs. Only honored when the exchange supports bracket orders.
+    #[serde(default)]
+    pub bracket: Option<BracketLegs>,
+    /// Trigger price of stop orders
+    #[serde(default)]
+    pub stop_price: Option<Decimal>,
+    /// Only closes or shrinks a position (exits, unwinds, hedge closes). The
+    /// watchdog lets these through in safety mode and refuses the rest.
+    #[serde(default)]
+    pub reduce_only: bool,
+    /// Tags of the originating `OrderRequest`; venues with client order ids
+    /// carry them as its suffix (see `client_order_id`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// An empty GTC market buy; fill in the rest with struct update syntax.
+impl Default for PlaceOrderRequest {
+    fn default() -> Self {
+        Self {
+            symbol: String::new(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            qty: None,
+            notional: None,
+            limit_price: None,
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            reduce_only: false,
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+/// Random prefix of a tagged client order id, keeping it unique
+const CLIENT_ID_UNIQUE_LEN: usize = 12;
+
+impl PlaceOrderRequest {
+    /// Client order id for a venue accepting up to `max_len` characters: a
+    /// random prefix followed by the tag values in key order (the signal id is
+    /// left out, it is in our own logs), reduced to `[A-Za-z0-9_-]` and cut to
+    /// length. None for untagged orders, which keep the venue's default.
+    pub fn client_order_id(&self, max_len: usize) -> Option<String> {
+        if self.metadata.is_empty() {
+            return None;
+        }
+        let mut tags: Vec<_> = self
+            .metadata
+            .iter()
+            .filter(|(k, _)| k.as_str() != crate::events::TAG_SIGNAL_ID)
+            .collect();
+        tags.sort();
+        let mut id = uuid::Uuid::new_v4().simple().to_string();
+        id.truncate(CLIENT_ID_UNIQUE_LEN);
+        for (_, value) in tags {
+            id.push('-');
+            id.extend(value.chars().map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            }));
+        }
+        id.truncate(max_len.max(CLIENT_ID_UNIQUE_LEN));
+        Some(id)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -57,6 +145,37 @@ pub struct OrderAck {
     pub raw: Value,
 }
 
+/// A working order as the venue lists it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub id: String,
+    /// Canonical symbol ("BTC/USD"); adapters translate the venue's spelling
+    pub symbol: String,
+    /// "buy" or "sell"
+    pub side: String,
+    /// When the order was placed, if the venue says
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Best bid/ask polled over REST.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TopOfBook {
+    pub bid: Decimal,
+    pub ask: Decimal,
+}
+
+impl TopOfBook {
+    /// Both sides present and positive.
+    pub fn new(bid: Option<Decimal>, ask: Option<Decimal>) -> Option<Self> {
+        match (bid, ask) {
+            (Some(bid), Some(ask)) if bid > Decimal::ZERO && ask > Decimal::ZERO => {
+                Some(Self { bid, ask })
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NormalizedQuote {
     pub symbol: String,
@@ -81,4 +200,105 @@ pub struct ExchangeCapabilities {
     pub supports_ws_quotes: bool,
     pub supports_ws_trades: bool,
     pub supports_news: bool,
+    /// Native bracket entries (TP/SL legs attached to the entry order);
+    /// otherwise PositionMonitor places the exits once the entry fills.
+    #[serde(default)]
+    pub supports_bracket_orders: bool,
+    /// Resting orders can be amended in place (`replace_order`); otherwise
+    /// PositionMonitor moves a TP leg by cancelling and re-placing it.
+    #[serde(default)]
+    pub supports_order_replace: bool,
+    /// Native stop-market sells (`OrderType::StopMarket`); PositionMonitor then
+    /// rests the stop-loss on the exchange and watches the take-profit locally.
+    #[serde(default)]
+    pub supports_stop_orders: bool,
+    /// Native one-cancels-the-other exit pair (`submit_oco_order`) for a
+    /// position entered without a bracket; the exchange then manages both legs.
+    #[serde(default)]
+    pub supports_oco: bool,
+    /// Sells may open a short. Without it, the hedger and the pairs trader
+    /// refuse short legs instead of sending sells the venue would reject.
+    #[serde(default)]
+    pub supports_short: bool,
+    /// Venue-wide minimum order value in quote currency, applied when a
+    /// symbol's own rules carry none (zero = unknown).
+    #[serde(default)]
+    pub min_notional: Decimal,
+}
+
+/// Venue trading rules for one symbol. Zero means the venue sets no constraint.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolMeta {
+    pub symbol: String,
+    /// Limit price increment
+    pub tick_size: Decimal,
+    /// Base quantity increment
+    pub step_size: Decimal,
+    pub min_qty: Decimal,
+    /// Minimum order value in quote currency
+    pub min_notional: Decimal,
+}
+
+/// `value` snapped to a multiple of `increment`, rounding up or down.
+fn snap(value: Decimal, increment: Decimal, up: bool) -> Decimal {
+    if increment <= Decimal::ZERO {
+        return value;
+    }
+    let steps = value / increment;
+    let steps = if up { steps.ceil() } else { steps.floor() };
+    (steps * increment).normalize()
+}
+
+impl SymbolMeta {
+    /// Quantity rounded down to the step size.
+    pub fn round_qty(&self, qty: Decimal) -> Decimal {
+        snap(qty, self.step_size, false)
+    }
+
+    /// Limit price on the tick grid, rounded away from the market's favour so
+    /// the order never pays more (buy: down) or asks less (sell: up).
+    pub fn round_price(&self, price: Decimal, side: Side) -> Decimal {
+        snap(price, self.tick_size, matches!(side, Side::Sell))
+    }
+
+    /// Round `order` to the symbol's increments. Orders that end up below the
+    /// minimum quantity or notional (valued at `ref_price`) are rejected.
+    pub fn normalize(
+        &self,
+        mut order: PlaceOrderRequest,
+        ref_price: Decimal,
+    ) -> Result<PlaceOrderRequest, String> {
+        if let Some(price) = order.limit_price {
+            order.limit_price = Some(self.round_price(price, order.side));
+        }
+        let price = order.limit_price.unwrap_or(ref_price);
+        if let Some(qty) = order.qty {
+            let qty = self.round_qty(qty);
+            if qty <= Decimal::ZERO || qty < self.min_qty {
+                return Err(format!(
+                    "{} qty {} below min qty {} (step {})",
+                    order.symbol, qty, self.min_qty, self.step_size
+                ));
+            }
+            if price > Decimal::ZERO && qty * price < self.min_notional {
+                return Err(format!(
+                    "{} notional ${:.2} below min notional ${:.2}",
+                    order.symbol,
+                    qty * price,
+                    self.min_notional
+                ));
+            }
+            order.qty = Some(qty);
+        }
+        if let Some(notional) = order.notional {
+            if notional < self.min_notional {
+                return Err(format!(
+                    "{} notional ${:.2} below min notional ${:.2}",
+                    order.symbol, notional, self.min_notional
+                ));
+            }
+            order.notional = Some(snap(notional, Decimal::new(1, 2), false));
+        }
+        Ok(order)
+    }
 }
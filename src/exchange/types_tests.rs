@@ -3,39 +3,41 @@
 #[cfg(test)]
 mod types_tests {
     use crate::exchange::types::*;
+    use crate::money::dec;
     use serde_json::json;
+    use std::collections::HashMap;
 
     // ============= AccountSummary Tests =============
 
     #[test]
     fn test_account_summary_full() {
         let summary = AccountSummary {
-            buying_power: Some(10000.0),
-            cash: Some(5000.0),
-            portfolio_value: Some(15000.0),
+            buying_power: Some(dec(10000.0)),
+            cash: Some(dec(5000.0)),
+            portfolio_value: Some(dec(15000.0)),
         };
-        assert_eq!(summary.buying_power, Some(10000.0));
-        assert_eq!(summary.cash, Some(5000.0));
-        assert_eq!(summary.portfolio_value, Some(15000.0));
+        assert_eq!(summary.buying_power, Some(dec(10000.0)));
+        assert_eq!(summary.cash, Some(dec(5000.0)));
+        assert_eq!(summary.portfolio_value, Some(dec(15000.0)));
     }
 
     #[test]
     fn test_account_summary_partial() {
         let summary = AccountSummary {
             buying_power: None,
-            cash: Some(5000.0),
+            cash: Some(dec(5000.0)),
             portfolio_value: None,
         };
         assert_eq!(summary.buying_power, None);
-        assert_eq!(summary.cash, Some(5000.0));
+        assert_eq!(summary.cash, Some(dec(5000.0)));
     }
 
     #[test]
     fn test_account_summary_serialization() {
         let summary = AccountSummary {
-            buying_power: Some(10000.0),
-            cash: Some(5000.0),
-            portfolio_value: Some(15000.0),
+            buying_power: Some(dec(10000.0)),
+            cash: Some(dec(5000.0)),
+            portfolio_value: Some(dec(15000.0)),
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("buying_power"));
@@ -48,19 +50,19 @@ mod types_tests {
     fn test_position_creation() {
         let pos = Position {
             symbol: "BTC/USD".to_string(),
-            qty: 0.5,
-            avg_entry_price: Some(50000.0),
+            qty: dec(0.5),
+            avg_entry_price: Some(dec(50000.0)),
         };
         assert_eq!(pos.symbol, "BTC/USD");
-        assert_eq!(pos.qty, 0.5);
-        assert_eq!(pos.avg_entry_price, Some(50000.0));
+        assert_eq!(pos.qty, dec(0.5));
+        assert_eq!(pos.avg_entry_price, Some(dec(50000.0)));
     }
 
     #[test]
     fn test_position_without_entry_price() {
         let pos = Position {
             symbol: "ETH/USD".to_string(),
-            qty: 2.0,
+            qty: dec(2.0),
             avg_entry_price: None,
         };
         assert_eq!(pos.avg_entry_price, None);
@@ -106,6 +108,14 @@ mod types_tests {
         assert_eq!(json, "\"limit\"");
     }
 
+    #[test]
+    fn test_order_type_stops() {
+        let json = serde_json::to_string(&OrderType::StopMarket).unwrap();
+        assert_eq!(json, "\"stop_market\"");
+        let ot: OrderType = serde_json::from_str("\"stop_limit\"").unwrap();
+        assert!(matches!(ot, OrderType::StopLimit));
+    }
+
     // ============= TimeInForce Tests =============
 
     #[test]
@@ -137,15 +147,18 @@ mod types_tests {
             symbol: "BTC/USD".to_string(),
             side: Side::Buy,
             order_type: OrderType::Market,
-            qty: Some(0.1),
+            qty: Some(dec(0.1)),
             notional: None,
             limit_price: None,
             time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
         };
         assert_eq!(req.symbol, "BTC/USD");
         assert!(matches!(req.side, Side::Buy));
         assert!(matches!(req.order_type, OrderType::Market));
-        assert_eq!(req.qty, Some(0.1));
+        assert_eq!(req.qty, Some(dec(0.1)));
         assert_eq!(req.notional, None);
     }
 
@@ -155,14 +168,17 @@ mod types_tests {
             symbol: "ETH/USD".to_string(),
             side: Side::Sell,
             order_type: OrderType::Limit,
-            qty: Some(1.0),
+            qty: Some(dec(1.0)),
             notional: None,
-            limit_price: Some(3500.0),
+            limit_price: Some(dec(3500.0)),
             time_in_force: TimeInForce::Day,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
         };
         assert!(matches!(req.side, Side::Sell));
         assert!(matches!(req.order_type, OrderType::Limit));
-        assert_eq!(req.limit_price, Some(3500.0));
+        assert_eq!(req.limit_price, Some(dec(3500.0)));
     }
 
     #[test]
@@ -172,12 +188,77 @@ mod types_tests {
             side: Side::Buy,
             order_type: OrderType::Market,
             qty: None,
-            notional: Some(100.0),
+            notional: Some(dec(100.0)),
             limit_price: None,
             time_in_force: TimeInForce::Ioc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
         };
         assert_eq!(req.qty, None);
-        assert_eq!(req.notional, Some(100.0));
+        assert_eq!(req.notional, Some(dec(100.0)));
+    }
+
+    #[test]
+    fn test_place_order_request_bracket_defaults_none() {
+        let req: PlaceOrderRequest = serde_json::from_value(json!({
+            "symbol": "AAPL",
+            "side": "buy",
+            "order_type": "limit",
+            "qty": 1.0,
+            "notional": null,
+            "limit_price": 190.0,
+            "time_in_force": "day"
+        }))
+        .unwrap();
+        assert!(req.bracket.is_none());
+    }
+
+    #[test]
+    fn test_client_order_id_carries_tags() {
+        let mut req: PlaceOrderRequest = serde_json::from_value(json!({
+            "symbol": "BTC/USDT",
+            "side": "buy",
+            "order_type": "market",
+            "qty": 0.1,
+            "notional": null,
+            "limit_price": null,
+            "time_in_force": "gtc"
+        }))
+        .unwrap();
+        assert!(req.metadata.is_empty());
+        assert_eq!(req.client_order_id(36), None);
+
+        req.metadata = HashMap::from([
+            ("strategy".to_string(), "hft".to_string()),
+            ("experiment".to_string(), "rsi v2".to_string()),
+            ("signal_id".to_string(), "0123456789abcdef".to_string()),
+        ]);
+        let id = req.client_order_id(128).unwrap();
+        let (unique, tags) = id.split_at(12);
+        assert!(unique.chars().all(|c| c.is_ascii_hexdigit()));
+        // Key order, sanitized, without the signal id
+        assert_eq!(tags, "-rsi_v2-hft");
+        assert_ne!(req.client_order_id(128), Some(id));
+        assert_eq!(req.client_order_id(16).unwrap().len(), 16);
+        // Tags go out with the order, untagged orders serialize as before
+        assert_eq!(
+            serde_json::to_value(&req).unwrap()["metadata"]["strategy"],
+            "hft"
+        );
+    }
+
+    #[test]
+    fn test_bracket_legs_roundtrip() {
+        let legs = BracketLegs {
+            take_profit: dec(200.0),
+            stop_loss: dec(180.0),
+        };
+        let v = serde_json::to_value(legs).unwrap();
+        assert_eq!(v, json!({"take_profit": 200.0, "stop_loss": 180.0}));
+        let back: BracketLegs = serde_json::from_value(v).unwrap();
+        assert_eq!(back.take_profit, dec(200.0));
+        assert_eq!(back.stop_loss, dec(180.0));
     }
 
     // ============= OrderAck Tests =============
@@ -202,6 +283,12 @@ mod types_tests {
             supports_ws_quotes: true,
             supports_ws_trades: true,
             supports_news: true,
+            supports_bracket_orders: false,
+            supports_order_replace: false,
+            supports_stop_orders: false,
+            supports_oco: false,
+            supports_short: false,
+            min_notional: dec(0.0),
         };
         assert!(caps.supports_notional_market_buy);
         assert!(caps.supports_ws_quotes);
@@ -216,10 +303,68 @@ mod types_tests {
             supports_ws_quotes: true,
             supports_ws_trades: true,
             supports_news: false,
+            supports_bracket_orders: false,
+            supports_order_replace: false,
+            supports_stop_orders: false,
+            supports_oco: false,
+            supports_short: false,
+            min_notional: dec(0.0),
         };
         assert!(!caps.supports_notional_market_buy);
         assert!(!caps.supports_news);
     }
+
+    // ============= TopOfBook Tests =============
+
+    #[test]
+    fn test_top_of_book_requires_both_sides() {
+        assert_eq!(
+            TopOfBook::new(Some(dec(99.5)), Some(dec(100.0))),
+            Some(TopOfBook {
+                bid: dec(99.5),
+                ask: dec(100.0)
+            })
+        );
+        assert_eq!(TopOfBook::new(None, Some(dec(100.0))), None);
+        assert_eq!(TopOfBook::new(Some(dec(0.0)), Some(dec(100.0))), None);
+    }
+
+    #[test]
+    fn test_top_of_book_from_venue_payloads() {
+        use crate::exchange::{alpaca, binance, coinbase, kraken};
+        let expected = Some(TopOfBook {
+            bid: dec(64000.5),
+            ask: dec(64001.0),
+        });
+
+        let crypto = json!({"quotes": {"BTC/USD": {"bp": 64000.5, "ap": 64001.0, "bs": 1.2}}});
+        assert_eq!(
+            alpaca::top_of_book_from_latest_quote("BTC/USD", &crypto),
+            expected
+        );
+        let equity = json!({"symbol": "AAPL", "quote": {"bp": 64000.5, "ap": 64001.0}});
+        assert_eq!(
+            alpaca::top_of_book_from_latest_quote("AAPL", &equity),
+            expected
+        );
+
+        let ticker = json!({"symbol": "BTCUSDT", "bidPrice": "64000.50000000", "bidQty": "1.0",
+            "askPrice": "64001.00000000", "askQty": "2.0"});
+        assert_eq!(binance::top_of_book_from_book_ticker(&ticker), expected);
+
+        let result = json!({"XXBTZUSD": {"a": ["64001.00000", "1", "1.000"],
+            "b": ["64000.50000", "2", "2.000"], "c": ["64000.7", "0.1"]}});
+        assert_eq!(kraken::top_of_book_from_ticker(&result), expected);
+
+        let books = json!({"pricebooks": [{"product_id": "BTC-USD",
+            "bids": [{"price": "64000.5", "size": "0.3"}],
+            "asks": [{"price": "64001", "size": "0.2"}]}]});
+        assert_eq!(coinbase::top_of_book_from_pricebooks(&books), expected);
+        assert_eq!(
+            coinbase::top_of_book_from_pricebooks(&json!({"pricebooks": []})),
+            None
+        );
+    }
 }
 
 #[cfg(test)]
@@ -288,4 +433,23 @@ mod symbols_tests {
         assert_eq!(result, "dogeusd");
         assert!(result.chars().all(|c| c.is_lowercase() || c.is_numeric()));
     }
+
+    // ============= Timeframe Conversion =============
+
+    #[test]
+    fn test_timeframe_minutes() {
+        assert_eq!(timeframe_minutes("1Min"), Some(1));
+        assert_eq!(timeframe_minutes("15Min"), Some(15));
+        assert_eq!(timeframe_minutes("1Hour"), Some(60));
+        assert_eq!(timeframe_minutes("4H"), Some(240));
+        assert_eq!(timeframe_minutes("1Day"), Some(1440));
+    }
+
+    #[test]
+    fn test_timeframe_minutes_invalid() {
+        assert_eq!(timeframe_minutes("Min"), None);
+        assert_eq!(timeframe_minutes("0Min"), None);
+        assert_eq!(timeframe_minutes("1Week"), None);
+        assert_eq!(timeframe_minutes("60"), None);
+    }
 }
@@ -0,0 +1,385 @@
+//! Authenticated user-data streams (order fill updates).
+//!
+//! Alpaca `trade_updates` and Binance `userDataStream` push every execution of
+//! our orders, including partial fills. Each fill is published as an
+//! `Event::Execution` with status "fill" / "partial_fill" and the price and
+//! quantity of that execution; cancels, expiries and post-acceptance rejects
+//! are published with their own status. Report symbols are canonicalized
+//! through the session's `SymbolRegistry` ("BTCUSDT" -> "BTC/USDT"). Dropped
+//! connections are re-opened after `user_stream.reconnect_secs`.
+
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, UserStreamConfig};
+use crate::error::AutoHedgeError;
+use crate::events::{DisconnectEvent, Event, ExecutionReport};
+use crate::money;
+
+use super::symbol_registry::{SymbolRegistry, Venue};
+use super::traits::ExchangeResult;
+
+#[derive(Clone)]
+pub enum UserStreamProvider {
+    Alpaca {
+        api_key: String,
+        secret_key: String,
+        base_url: String,
+    },
+    Binance {
+        api_key: String,
+        base_url: String,
+    },
+}
+
+#[derive(Clone)]
+pub struct UserDataStream {
+    provider: UserStreamProvider,
+    config: UserStreamConfig,
+    client: Client,
+    symbols: SymbolRegistry,
+}
+
+impl UserDataStream {
+    pub fn new(provider: UserStreamProvider, config: UserStreamConfig) -> Self {
+        let venue = match provider {
+            UserStreamProvider::Alpaca { .. } => Venue::Alpaca,
+            UserStreamProvider::Binance { .. } => Venue::Binance,
+        };
+        Self {
+            provider,
+            config,
+            client: Client::new(),
+            symbols: SymbolRegistry::new(venue, &[]),
+        }
+    }
+
+    /// Share the session's symbol registry for report symbols.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// User stream for the configured exchange, or None if it has none (or the
+    /// stream is disabled).
+    pub fn for_exchange(config: &AppConfig, exchange: &str) -> Option<Self> {
+        if !config.user_stream.enabled {
+            return None;
+        }
+        let provider = match exchange {
+            "alpaca" => UserStreamProvider::Alpaca {
+                api_key: config.alpaca.api_key.clone(),
+                secret_key: config.alpaca.secret_key.clone(),
+                base_url: config.alpaca.base_url.clone(),
+            },
+            // USD-M futures fills come from order polling (its user stream is a
+            // different API)
+            "binance" if config.futures().is_some() => return None,
+            "binance" => {
+                let c = config.binance.as_ref()?;
+                UserStreamProvider::Binance {
+                    api_key: c.api_key.clone(),
+                    base_url: c.base_url.clone(),
+                }
+            }
+            _ => return None,
+        };
+        Some(Self::new(provider, config.user_stream.clone()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.provider {
+            UserStreamProvider::Alpaca { .. } => "alpaca trade_updates",
+            UserStreamProvider::Binance { .. } => "binance userDataStream",
+        }
+    }
+
+    /// Keep the stream connected until shutdown, reconnecting on errors.
+    pub async fn start(&self, event_bus: EventBus) {
+        let stream = self.clone();
+        tokio::spawn(async move {
+            let shutdown = event_bus.shutdown().clone();
+            let delay = Duration::from_secs(stream.config.reconnect_secs.max(1));
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    result = stream.run(&event_bus) => {
+                        let reason = match result {
+                            Ok(()) => {
+                                warn!("⚠️ [USER_STREAM] {} closed by venue", stream.name());
+                                "closed by venue".to_string()
+                            }
+                            Err(e) => {
+                                error!("❌ [USER_STREAM] {} failed: {}", stream.name(), e);
+                                e.to_string()
+                            }
+                        };
+                        event_bus
+                            .publish(Event::Disconnected(DisconnectEvent {
+                                stream: stream.name().to_string(),
+                                reason,
+                                timestamp: chrono::Utc::now().to_rfc3339(),
+                            }))
+                            .ok();
+                    }
+                }
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                info!("[USER_STREAM] Reconnecting {}...", stream.name());
+            }
+            info!("[USER_STREAM] {} stopped", stream.name());
+        });
+    }
+
+    /// One connection: authenticate, subscribe and publish until the socket closes.
+    async fn run(&self, bus: &EventBus) -> ExchangeResult<()> {
+        let (url, listen_key) = match &self.provider {
+            UserStreamProvider::Alpaca { base_url, .. } => (alpaca_stream_url(base_url), None),
+            UserStreamProvider::Binance { base_url, .. } => {
+                let key = self.binance_listen_key().await?;
+                (binance_stream_url(base_url, &key), Some(key))
+            }
+        };
+
+        let (ws, _) = connect_async(url.as_str())
+            .await
+            .map_err(|e| AutoHedgeError::Ws(format!("user stream connect failed: {e}")))?;
+        let (mut write, mut read) = ws.split();
+
+        if let UserStreamProvider::Alpaca {
+            api_key,
+            secret_key,
+            ..
+        } = &self.provider
+        {
+            let auth = json!({"action": "auth", "key": api_key, "secret": secret_key});
+            write.send(Message::Text(auth.to_string())).await?;
+            let listen = json!({"action": "listen", "data": {"streams": ["trade_updates"]}});
+            write.send(Message::Text(listen.to_string())).await?;
+        }
+        info!("📡 [USER_STREAM] Connected to {}", self.name());
+
+        let mut keepalive =
+            tokio::time::interval(Duration::from_secs(self.config.keepalive_secs.max(60)));
+        keepalive.tick().await;
+
+        loop {
+            let msg = tokio::select! {
+                _ = keepalive.tick() => {
+                    if let Some(key) = &listen_key {
+                        if let Err(e) = self.binance_keepalive(key).await {
+                            warn!("⚠️ [USER_STREAM] Listen key keepalive failed: {}", e);
+                        }
+                    }
+                    continue;
+                }
+                msg = read.next() => match msg {
+                    Some(msg) => msg?,
+                    None => return Ok(()),
+                },
+            };
+            let text = match msg {
+                Message::Text(text) => text,
+                // Alpaca sends trade_updates as binary frames on some endpoints
+                Message::Binary(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Message::Ping(p) => {
+                    let _ = write.send(Message::Pong(p)).await;
+                    continue;
+                }
+                Message::Close(_) => return Ok(()),
+                _ => continue,
+            };
+            #[cfg(feature = "chaos")]
+            if crate::chaos::inject(crate::chaos::Fault::WsDisconnect) {
+                return Err(AutoHedgeError::Ws("chaos: injected disconnect".to_string()));
+            }
+            let report = match &self.provider {
+                UserStreamProvider::Alpaca { .. } => parse_alpaca_trade_update(&text)?,
+                UserStreamProvider::Binance { .. } => parse_binance_execution_report(&text),
+            };
+            if let Some(mut report) = report {
+                report.symbol = self.symbols.canonical(&report.symbol);
+                info!(
+                    "📥 [USER_STREAM] {} {} {} qty={:?} @ {:?} (order {})",
+                    report.status,
+                    report.side,
+                    report.symbol,
+                    report.qty,
+                    report.price,
+                    report.order_id
+                );
+                bus.publish(Event::Execution(report)).ok();
+            }
+        }
+    }
+
+    async fn binance_listen_key(&self) -> ExchangeResult<String> {
+        let UserStreamProvider::Binance { api_key, base_url } = &self.provider else {
+            return Err(AutoHedgeError::Config(
+                "listen keys are Binance-only".to_string(),
+            ));
+        };
+        let resp = self
+            .client
+            .post(format!("{}/api/v3/userDataStream", base_url))
+            .header("X-MBX-APIKEY", api_key)
+            .send()
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            return Err(AutoHedgeError::http(
+                status.as_u16(),
+                format!("Binance listen key request failed ({}): {}", status, text),
+            ));
+        }
+        let raw: Value = serde_json::from_str(&text)?;
+        raw.get("listenKey")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                AutoHedgeError::exchange(format!("Binance listen key missing (body: {})", text))
+            })
+    }
+
+    async fn binance_keepalive(&self, listen_key: &str) -> ExchangeResult<()> {
+        let UserStreamProvider::Binance { api_key, base_url } = &self.provider else {
+            return Ok(());
+        };
+        let resp = self
+            .client
+            .put(format!("{}/api/v3/userDataStream", base_url))
+            .header("X-MBX-APIKEY", api_key)
+            .query(&[("listenKey", listen_key)])
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(AutoHedgeError::http(
+                resp.status().as_u16(),
+                format!("keepalive returned {}", resp.status()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Trading API base URL -> trade_updates stream URL
+/// (`https://paper-api.alpaca.markets` -> `wss://paper-api.alpaca.markets/stream`).
+pub fn alpaca_stream_url(base_url: &str) -> String {
+    let host = base_url
+        .trim_end_matches('/')
+        .trim_end_matches("/v2")
+        .replacen("https://", "wss://", 1)
+        .replacen("http://", "ws://", 1);
+    format!("{}/stream", host)
+}
+
+/// REST base URL -> user-data stream URL for `listen_key` (spot or spot testnet).
+pub fn binance_stream_url(base_url: &str, listen_key: &str) -> String {
+    let host = if base_url.contains("testnet") {
+        "wss://testnet.binance.vision/ws"
+    } else {
+        "wss://stream.binance.com:9443/ws"
+    };
+    format!("{}/{}", host, listen_key)
+}
+
+/// Parse an Alpaca `trade_updates` frame. Errors on a failed authorization so
+/// the stream reconnects instead of silently receiving nothing.
+pub fn parse_alpaca_trade_update(text: &str) -> ExchangeResult<Option<ExecutionReport>> {
+    let Ok(msg) = serde_json::from_str::<Value>(text) else {
+        return Ok(None);
+    };
+    let data = &msg["data"];
+    match msg["stream"].as_str() {
+        Some("authorization") => {
+            if data["status"].as_str() != Some("authorized") {
+                return Err(AutoHedgeError::Ws(format!(
+                    "Alpaca user stream not authorized: {}",
+                    data
+                )));
+            }
+            return Ok(None);
+        }
+        Some("trade_updates") => {}
+        _ => return Ok(None),
+    }
+
+    let status = match data["event"].as_str() {
+        Some("fill") => "fill",
+        Some("partial_fill") => "partial_fill",
+        Some("canceled") => "canceled",
+        Some("expired") => "expired",
+        Some("rejected") => "rejected",
+        _ => return Ok(None),
+    };
+    let order = &data["order"];
+    let is_fill = status == "fill" || status == "partial_fill";
+    // Fill events carry the execution's own price/qty; others fall back to the order
+    let (price, qty) = if is_fill {
+        (num(&data["price"]), num(&data["qty"]))
+    } else {
+        (num(&order["limit_price"]), num(&order["qty"]))
+    };
+    Ok(Some(ExecutionReport {
+        symbol: order["symbol"].as_str().unwrap_or_default().to_string(),
+        order_id: order["id"].as_str().unwrap_or_default().to_string(),
+        status: status.to_string(),
+        side: order["side"].as_str().unwrap_or_default().to_lowercase(),
+        price,
+        qty,
+        signal_price: None,
+        limit_price: None,
+        strategy_id: None,
+        correlation_id: None,
+        metadata: HashMap::new(),
+    }))
+}
+
+/// Parse a Binance `executionReport` event (other user-data events are ignored).
+pub fn parse_binance_execution_report(text: &str) -> Option<ExecutionReport> {
+    let msg: Value = serde_json::from_str(text).ok()?;
+    if msg["e"].as_str() != Some("executionReport") {
+        return None;
+    }
+    let status = match (msg["x"].as_str()?, msg["X"].as_str()?) {
+        ("TRADE", "FILLED") => "fill",
+        ("TRADE", _) => "partial_fill",
+        ("CANCELED", _) => "canceled",
+        ("EXPIRED", _) => "expired",
+        ("REJECTED", _) => "rejected",
+        _ => return None,
+    };
+    let (price, qty) = if status == "fill" || status == "partial_fill" {
+        (num(&msg["L"]), num(&msg["l"]))
+    } else {
+        (num(&msg["p"]), num(&msg["q"]))
+    };
+    Some(ExecutionReport {
+        symbol: msg["s"].as_str()?.to_string(),
+        order_id: msg["i"].as_i64().map(|i| i.to_string()).unwrap_or_default(),
+        status: status.to_string(),
+        side: msg["S"].as_str().unwrap_or_default().to_lowercase(),
+        price,
+        qty,
+        signal_price: None,
+        limit_price: None,
+        strategy_id: None,
+        correlation_id: None,
+        metadata: HashMap::new(),
+    })
+}
+
+/// Venues send decimals as strings (sometimes numbers); zero means "not set".
+fn num(v: &Value) -> Option<Decimal> {
+    money::from_json(v).filter(|n| *n > Decimal::ZERO)
+}
@@ -0,0 +1,107 @@
+//! Unit tests for user-data stream parsing (Alpaca trade_updates, Binance executionReport).
+
+#[cfg(test)]
+mod user_stream_tests {
+    use crate::exchange::user_stream::*;
+    use crate::money::dec;
+
+    // ============= URL Tests =============
+
+    #[test]
+    fn test_alpaca_stream_url() {
+        assert_eq!(
+            alpaca_stream_url("https://paper-api.alpaca.markets"),
+            "wss://paper-api.alpaca.markets/stream"
+        );
+        assert_eq!(
+            alpaca_stream_url("https://api.alpaca.markets/v2/"),
+            "wss://api.alpaca.markets/stream"
+        );
+    }
+
+    #[test]
+    fn test_binance_stream_url() {
+        assert_eq!(
+            binance_stream_url("https://api.binance.com", "abc"),
+            "wss://stream.binance.com:9443/ws/abc"
+        );
+        assert_eq!(
+            binance_stream_url("https://testnet.binance.vision", "abc"),
+            "wss://testnet.binance.vision/ws/abc"
+        );
+    }
+
+    // ============= Alpaca Tests =============
+
+    #[test]
+    fn test_alpaca_partial_fill_uses_execution_price_and_qty() {
+        let text = r#"{"stream":"trade_updates","data":{"event":"partial_fill","price":"50010.5","qty":"0.004","position_qty":"0.004","timestamp":"2025-01-01T00:00:00Z",
+            "order":{"id":"ord-1","symbol":"BTC/USD","side":"buy","qty":"0.01","filled_qty":"0.004","limit_price":"50020","status":"partially_filled"}}}"#;
+        let report = parse_alpaca_trade_update(text).unwrap().unwrap();
+        assert_eq!(report.status, "partial_fill");
+        assert!(report.is_fill_update());
+        assert_eq!(report.symbol, "BTC/USD");
+        assert_eq!(report.order_id, "ord-1");
+        assert_eq!(report.side, "buy");
+        assert_eq!(report.price, Some(dec(50010.5)));
+        assert_eq!(report.qty, Some(dec(0.004)));
+    }
+
+    #[test]
+    fn test_alpaca_cancel_and_ignored_events() {
+        let cancel = r#"{"stream":"trade_updates","data":{"event":"canceled",
+            "order":{"id":"ord-2","symbol":"ETH/USD","side":"sell","qty":"1","limit_price":"3100"}}}"#;
+        let report = parse_alpaca_trade_update(cancel).unwrap().unwrap();
+        assert_eq!(report.status, "canceled");
+        assert!(!report.is_fill_update());
+        assert_eq!(report.price, Some(dec(3100.0)));
+
+        let new = r#"{"stream":"trade_updates","data":{"event":"new","order":{"id":"x","symbol":"ETH/USD","side":"buy"}}}"#;
+        assert!(parse_alpaca_trade_update(new).unwrap().is_none());
+        let listening = r#"{"stream":"listening","data":{"streams":["trade_updates"]}}"#;
+        assert!(parse_alpaca_trade_update(listening).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_alpaca_unauthorized_is_an_error() {
+        let ok =
+            r#"{"stream":"authorization","data":{"status":"authorized","action":"authenticate"}}"#;
+        assert!(parse_alpaca_trade_update(ok).unwrap().is_none());
+        let denied = r#"{"stream":"authorization","data":{"status":"unauthorized","action":"authenticate"}}"#;
+        assert!(parse_alpaca_trade_update(denied).is_err());
+    }
+
+    // ============= Binance Tests =============
+
+    #[test]
+    fn test_binance_trade_execution_reports() {
+        let partial = r#"{"e":"executionReport","E":1,"s":"BTCUSDT","S":"BUY","o":"LIMIT","q":"1.00000000","p":"100.00","x":"TRADE","X":"PARTIALLY_FILLED","i":4293153,"l":"0.40000000","z":"0.40000000","L":"99.90"}"#;
+        let report = parse_binance_execution_report(partial).unwrap();
+        assert_eq!(report.status, "partial_fill");
+        assert_eq!(report.symbol, "BTCUSDT");
+        assert_eq!(report.order_id, "4293153");
+        assert_eq!(report.side, "buy");
+        assert_eq!(report.price, Some(dec(99.9)));
+        assert_eq!(report.qty, Some(dec(0.4)));
+
+        let filled = partial
+            .replace("PARTIALLY_FILLED", "FILLED")
+            .replace(r#""l":"0.40000000""#, r#""l":"0.60000000""#);
+        let report = parse_binance_execution_report(&filled).unwrap();
+        assert_eq!(report.status, "fill");
+        assert_eq!(report.qty, Some(dec(0.6)));
+    }
+
+    #[test]
+    fn test_binance_non_fill_events() {
+        let canceled = r#"{"e":"executionReport","s":"BTCUSDT","S":"SELL","q":"1.0","p":"120.0","x":"CANCELED","X":"CANCELED","i":7}"#;
+        let report = parse_binance_execution_report(canceled).unwrap();
+        assert_eq!(report.status, "canceled");
+        assert_eq!(report.price, Some(dec(120.0)));
+
+        let new = r#"{"e":"executionReport","s":"BTCUSDT","S":"BUY","x":"NEW","X":"NEW","i":8}"#;
+        assert!(parse_binance_execution_report(new).is_none());
+        let balance = r#"{"e":"outboundAccountPosition","E":1,"B":[]}"#;
+        assert!(parse_binance_execution_report(balance).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::{json, Value};
+use std::collections::HashSet;
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
     connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
@@ -9,17 +10,28 @@ use tracing::{error, info, warn};
 
 use crate::{
     bus::EventBus,
+    config::{AppConfig, ChatterLevel, ChatterModule},
     data::store::{MarketStore, Quote, Trade},
-    events::{Event, MarketEvent},
+    error::AutoHedgeError,
+    events::{DisconnectEvent, Event, MarketEvent, SymbolStatusEvent},
 };
 
+use super::symbol_registry::{SymbolRegistry, Venue};
 use super::traits::{ExchangeResult, MarketDataStream};
+use super::ws_messages::{
+    alpaca_status_halted, parse_alpaca, parse_binance, parse_coinbase, parse_num,
+};
+
+/// Characters of a frame logged at `chatter.ws: verbose`
+const WS_LOG_CHARS: usize = 300;
 
 #[derive(Clone)]
 pub enum WsProvider {
     AlpacaCrypto,
     AlpacaStocks,
     Binance,
+    /// Binance USD-M perpetuals (quotes plus mark price / funding)
+    BinanceFutures,
     Coinbase,
     Kraken,
 }
@@ -29,6 +41,10 @@ pub struct GenericWsStream {
     pub provider: WsProvider,
     pub api_key: Option<String>,
     pub api_secret: Option<String>,
+    /// Translates subscriptions to venue symbols and incoming symbols back
+    pub symbols: SymbolRegistry,
+    /// `chatter.ws`; `verbose` logs every frame received
+    pub chatter: ChatterLevel,
 }
 
 impl GenericWsStream {
@@ -41,6 +57,8 @@ impl GenericWsStream {
             },
             api_key: Some(api_key),
             api_secret: Some(api_secret),
+            chatter: ChatterLevel::Normal,
+            symbols: SymbolRegistry::new(Venue::Alpaca, &[]),
         }
     }
 
@@ -49,6 +67,18 @@ impl GenericWsStream {
             provider: WsProvider::Binance,
             api_key,
             api_secret,
+            chatter: ChatterLevel::Normal,
+            symbols: SymbolRegistry::new(Venue::Binance, &[]),
+        }
+    }
+
+    pub fn binance_futures(api_key: Option<String>, api_secret: Option<String>) -> Self {
+        Self {
+            provider: WsProvider::BinanceFutures,
+            api_key,
+            api_secret,
+            chatter: ChatterLevel::Normal,
+            symbols: SymbolRegistry::new(Venue::Binance, &[]),
         }
     }
 
@@ -57,6 +87,8 @@ impl GenericWsStream {
             provider: WsProvider::Coinbase,
             api_key,
             api_secret,
+            chatter: ChatterLevel::Normal,
+            symbols: SymbolRegistry::new(Venue::Coinbase, &[]),
         }
     }
 
@@ -65,14 +97,77 @@ impl GenericWsStream {
             provider: WsProvider::Kraken,
             api_key,
             api_secret,
+            chatter: ChatterLevel::Normal,
+            symbols: SymbolRegistry::new(Venue::Kraken, &[]),
         }
     }
 
+    /// Market data stream of `exchange` with the keys from its config section
+    /// (unknown names get an unauthenticated Alpaca crypto stream).
+    pub fn for_exchange(config: &AppConfig, exchange: &str) -> Self {
+        let keys = |key: Option<(&String, &String)>| match key {
+            Some((key, secret)) => (Some(key.clone()), Some(secret.clone())),
+            None => (None, None),
+        };
+        match exchange {
+            "alpaca" => Self::alpaca(
+                config.alpaca.api_key.clone(),
+                config.alpaca.secret_key.clone(),
+                config.trading_mode.eq_ignore_ascii_case("crypto"),
+            ),
+            "binance" => {
+                let (key, secret) =
+                    keys(config.binance.as_ref().map(|c| (&c.api_key, &c.secret_key)));
+                if config.futures().is_some() {
+                    Self::binance_futures(key, secret)
+                } else {
+                    Self::binance(key, secret)
+                }
+            }
+            "coinbase" => {
+                let (key, secret) = keys(
+                    config
+                        .coinbase
+                        .as_ref()
+                        .map(|c| (&c.api_key, &c.secret_key)),
+                );
+                Self::coinbase(key, secret)
+            }
+            "kraken" => {
+                let (key, secret) =
+                    keys(config.kraken.as_ref().map(|c| (&c.api_key, &c.secret_key)));
+                Self::kraken(key, secret)
+            }
+            _ => Self {
+                provider: WsProvider::AlpacaCrypto,
+                api_key: None,
+                api_secret: None,
+                symbols: Default::default(),
+                chatter: ChatterLevel::Normal,
+            },
+        }
+    }
+
+    /// Share the session's symbol registry (configured symbols resolve to
+    /// their configured spelling).
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Start at the configured `chatter.ws` level (later followed through
+    /// config updates).
+    pub fn with_chatter(mut self, chatter: ChatterLevel) -> Self {
+        self.chatter = chatter;
+        self
+    }
+
     fn ws_url(&self) -> &'static str {
         match self.provider {
             WsProvider::AlpacaCrypto => "wss://stream.data.alpaca.markets/v1beta3/crypto/us",
             WsProvider::AlpacaStocks => "wss://stream.data.alpaca.markets/v2/iex",
             WsProvider::Binance => "wss://stream.binance.com:9443/ws",
+            WsProvider::BinanceFutures => "wss://fstream.binance.com/ws",
             WsProvider::Coinbase => "wss://advanced-trade-ws.coinbase.com",
             WsProvider::Kraken => "wss://ws.kraken.com",
         }
@@ -102,12 +197,33 @@ impl GenericWsStream {
         let sub = if is_crypto {
             json!({"action":"subscribe","quotes":symbols,"trades":symbols})
         } else {
-            json!({"action":"subscribe","bars":symbols})
+            json!({"action":"subscribe","bars":symbols,"statuses":symbols})
         };
         write.send(Message::Text(sub.to_string())).await?;
         Ok(())
     }
 
+    async fn subscribe(
+        provider: &WsProvider,
+        write: &mut futures_util::stream::SplitSink<
+            WebSocketStream<MaybeTlsStream<TcpStream>>,
+            Message,
+        >,
+        symbols: &[String],
+        registry: &SymbolRegistry,
+    ) -> ExchangeResult<()> {
+        let native: Vec<String> = symbols.iter().map(|s| registry.stream_native(s)).collect();
+        let symbols = native.as_slice();
+        match provider {
+            WsProvider::AlpacaCrypto => Self::alpaca_subscribe(write, symbols, true).await,
+            WsProvider::AlpacaStocks => Self::alpaca_subscribe(write, symbols, false).await,
+            WsProvider::Binance => Self::binance_subscribe(write, symbols).await,
+            WsProvider::BinanceFutures => Self::binance_futures_subscribe(write, symbols).await,
+            WsProvider::Coinbase => Self::coinbase_subscribe(write, symbols).await,
+            WsProvider::Kraken => Self::kraken_subscribe(write, symbols).await,
+        }
+    }
+
     async fn binance_subscribe(
         write: &mut futures_util::stream::SplitSink<
             WebSocketStream<MaybeTlsStream<TcpStream>>,
@@ -118,9 +234,27 @@ impl GenericWsStream {
         // Binance combined streams need lowercase like "btcusdt@trade" and "btcusdt@bookTicker"
         let mut streams: Vec<String> = Vec::new();
         for s in symbols {
-            let stream_sym = s.to_lowercase();
-            streams.push(format!("{}@trade", stream_sym));
-            streams.push(format!("{}@bookTicker", stream_sym));
+            streams.push(format!("{}@trade", s));
+            streams.push(format!("{}@bookTicker", s));
+        }
+        let sub = json!({"method":"SUBSCRIBE","params":streams,"id":1});
+        write.send(Message::Text(sub.to_string())).await?;
+        Ok(())
+    }
+
+    async fn binance_futures_subscribe(
+        write: &mut futures_util::stream::SplitSink<
+            WebSocketStream<MaybeTlsStream<TcpStream>>,
+            Message,
+        >,
+        symbols: &[String],
+    ) -> ExchangeResult<()> {
+        // USD-M has no raw trade stream (aggTrade's "a" id clashes with bookTicker's
+        // ask); quotes plus the 1s mark price stream that carries funding
+        let mut streams: Vec<String> = Vec::new();
+        for s in symbols {
+            streams.push(format!("{}@bookTicker", s));
+            streams.push(format!("{}@markPrice@1s", s));
         }
         let sub = json!({"method":"SUBSCRIBE","params":streams,"id":1});
         write.send(Message::Text(sub.to_string())).await?;
@@ -134,12 +268,8 @@ impl GenericWsStream {
         >,
         symbols: &[String],
     ) -> ExchangeResult<()> {
-        // Subscribe to market_trades channel. Coinbase uses product_ids like "BTC-USD".
-        let product_ids: Vec<String> = symbols
-            .iter()
-            .map(|s| crate::exchange::symbols::to_coinbase_product_id(s))
-            .collect();
-        let sub = json!({"type":"subscribe","product_ids":product_ids,"channel":"market_trades"});
+        // Subscribe to market_trades channel (product_ids like "BTC-USD").
+        let sub = json!({"type":"subscribe","product_ids":symbols,"channel":"market_trades"});
         write.send(Message::Text(sub.to_string())).await?;
         Ok(())
     }
@@ -151,183 +281,147 @@ impl GenericWsStream {
         >,
         symbols: &[String],
     ) -> ExchangeResult<()> {
-        let pairs: Vec<String> = symbols
-            .iter()
-            .map(|s| crate::exchange::symbols::to_kraken_pair(s))
-            .collect();
-        // Subscribe to trades and ticker.
-        let sub_trades = json!({"event":"subscribe","pair":pairs,"subscription": {"name":"trade"}});
+        // Subscribe to trades and ticker (pairs like "XBT/USD").
+        let sub_trades =
+            json!({"event":"subscribe","pair":symbols,"subscription": {"name":"trade"}});
         write.send(Message::Text(sub_trades.to_string())).await?;
-        let sub_ticker = json!({"event":"subscribe","pair":symbols.iter().map(|s| crate::exchange::symbols::to_kraken_pair(s)).collect::<Vec<_>>(),"subscription": {"name":"ticker"}});
+        let sub_ticker =
+            json!({"event":"subscribe","pair":symbols,"subscription": {"name":"ticker"}});
         write.send(Message::Text(sub_ticker.to_string())).await?;
         Ok(())
     }
 
-    async fn process_alpaca(text: &str, store: &MarketStore, bus: &EventBus) {
-        if let Ok(val) = serde_json::from_str::<Value>(text) {
-            if let Some(arr) = val.as_array() {
-                for item in arr {
-                    if let Some(t) = item.get("T").and_then(|v| v.as_str()) {
-                        match t {
-                            "t" => {
-                                if let Some(s) = item.get("S").and_then(|v| v.as_str()) {
-                                    let price =
-                                        item.get("p").and_then(|p| p.as_f64()).unwrap_or(0.0);
-                                    let size =
-                                        item.get("s").and_then(|sz| sz.as_f64()).unwrap_or(0.0);
-                                    let timestamp = item
-                                        .get("t")
-                                        .and_then(|t| t.as_str())
-                                        .unwrap_or("")
-                                        .to_string();
-                                    let id = item.get("i").and_then(|i| i.as_u64());
-
-                                    let trade = Trade {
-                                        symbol: s.to_string(),
-                                        price,
-                                        size,
-                                        timestamp: timestamp.clone(),
-                                        id,
-                                    };
-                                    store.update_trade(s.to_string(), trade);
-                                    bus.publish(Event::Market(MarketEvent::Trade {
-                                        symbol: s.to_string(),
-                                        price,
-                                        size,
-                                        timestamp,
-                                    }))
-                                    .ok();
-                                }
-                            }
-                            "q" => {
-                                if let Some(s) = item.get("S").and_then(|v| v.as_str()) {
-                                    let bid =
-                                        item.get("bp").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                                    let ask =
-                                        item.get("ap").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                                    let bid_size =
-                                        item.get("bs").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                                    let ask_size =
-                                        item.get("as").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                                    let timestamp = item
-                                        .get("t")
-                                        .and_then(|t| t.as_str())
-                                        .unwrap_or("")
-                                        .to_string();
-
-                                    let quote = Quote {
-                                        symbol: s.to_string(),
-                                        bid_price: bid,
-                                        ask_price: ask,
-                                        bid_size,
-                                        ask_size,
-                                        timestamp: timestamp.clone(),
-                                    };
-                                    store.update_quote(s.to_string(), quote);
-                                    bus.publish(Event::Market(MarketEvent::Quote {
-                                        symbol: s.to_string(),
-                                        bid,
-                                        ask,
-                                        timestamp,
-                                    }))
-                                    .ok();
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
-    }
+    async fn process_alpaca(
+        text: &str,
+        store: &MarketStore,
+        bus: &EventBus,
+        registry: &SymbolRegistry,
+    ) {
+        let Some(items) = parse_alpaca(text) else {
+            return;
+        };
+        for item in items {
+            let Some(s) = item.symbol.as_deref() else {
+                continue;
+            };
+            let s = registry.canonical(s);
+            let s = s.as_str();
+            let timestamp = item.timestamp.as_deref().unwrap_or("").to_string();
+            match item.kind.as_ref() {
+                "t" => {
+                    let price = item.price.unwrap_or(0.0);
+                    let size = item.size.unwrap_or(0.0);
 
-    async fn process_binance(text: &str, store: &MarketStore, bus: &EventBus) {
-        if let Ok(v) = serde_json::from_str::<Value>(text) {
-            // trade event
-            if v.get("e").and_then(|x| x.as_str()) == Some("trade") {
-                let symbol = v
-                    .get("s")
-                    .and_then(|x| x.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let price = v
-                    .get("p")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let size = v
-                    .get("q")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let timestamp = v
-                    .get("T")
-                    .and_then(|x| x.as_i64())
-                    .map(|t| t.to_string())
-                    .unwrap_or_default();
-                let id = v.get("t").and_then(|x| x.as_u64());
-
-                if !symbol.is_empty() {
                     let trade = Trade {
-                        symbol: symbol.clone(),
+                        symbol: s.to_string(),
                         price,
                         size,
                         timestamp: timestamp.clone(),
-                        id,
+                        id: item.id,
                     };
-                    store.update_trade(symbol.clone(), trade);
+                    store.update_trade(s.to_string(), trade);
                     bus.publish(Event::Market(MarketEvent::Trade {
-                        symbol,
+                        symbol: s.to_string(),
                         price,
                         size,
                         timestamp,
                     }))
                     .ok();
                 }
-            }
-            // bookTicker event
-            if v.get("e").and_then(|x| x.as_str()) == Some("bookTicker") {
-                let symbol = v
-                    .get("s")
-                    .and_then(|x| x.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let bid = v
-                    .get("b")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let ask = v
-                    .get("a")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let bid_size = v
-                    .get("B")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let ask_size = v
-                    .get("A")
-                    .and_then(|x| x.as_str())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0);
-                let timestamp = v
-                    .get("E")
-                    .and_then(|x| x.as_i64())
-                    .map(|t| t.to_string())
-                    .unwrap_or_default();
-
-                if !symbol.is_empty() {
+                "q" => {
+                    let bid = item.bid_price.unwrap_or(0.0);
+                    let ask = item.ask_price.unwrap_or(0.0);
+
                     let quote = Quote {
-                        symbol: symbol.clone(),
+                        symbol: s.to_string(),
                         bid_price: bid,
                         ask_price: ask,
-                        bid_size,
-                        ask_size,
+                        bid_size: item.bid_size.unwrap_or(0.0),
+                        ask_size: item.ask_size.unwrap_or(0.0),
                         timestamp: timestamp.clone(),
                     };
-                    store.update_quote(symbol.clone(), quote);
+                    if store.accept_quote(s.to_string(), quote).is_ok() {
+                        bus.publish(Event::Market(MarketEvent::Quote {
+                            symbol: s.to_string(),
+                            bid,
+                            ask,
+                            timestamp,
+                        }))
+                        .ok();
+                    }
+                }
+                "s" => {
+                    let code = item.status_code.as_deref().unwrap_or("");
+                    if let Some(exit_only) = alpaca_status_halted(code) {
+                        bus.publish(Event::SymbolStatus(SymbolStatusEvent {
+                            symbol: s.to_string(),
+                            exit_only,
+                            reason: format!(
+                                "{} ({})",
+                                item.status_message.as_deref().unwrap_or("trading status"),
+                                code
+                            ),
+                            source: "venue".to_string(),
+                            timestamp,
+                        }))
+                        .ok();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn process_binance(
+        text: &str,
+        store: &MarketStore,
+        bus: &EventBus,
+        registry: &SymbolRegistry,
+    ) {
+        let Some(v) = parse_binance(text) else {
+            return;
+        };
+        let Some(symbol) = v.symbol.as_deref().filter(|s| !s.is_empty()) else {
+            return;
+        };
+        let symbol = registry.canonical(symbol);
+
+        match v.event.as_deref() {
+            Some("trade") => {
+                let price = parse_num(&v.price);
+                let size = parse_num(&v.qty);
+                let timestamp = v.trade_time.map(|t| t.to_string()).unwrap_or_default();
+
+                let trade = Trade {
+                    symbol: symbol.clone(),
+                    price,
+                    size,
+                    timestamp: timestamp.clone(),
+                    id: v.trade_id,
+                };
+                store.update_trade(symbol.clone(), trade);
+                bus.publish(Event::Market(MarketEvent::Trade {
+                    symbol,
+                    price,
+                    size,
+                    timestamp,
+                }))
+                .ok();
+            }
+            Some("bookTicker") => {
+                let bid = parse_num(&v.bid_price);
+                let ask = parse_num(&v.ask_price);
+                let timestamp = v.event_time.map(|t| t.to_string()).unwrap_or_default();
+
+                let quote = Quote {
+                    symbol: symbol.clone(),
+                    bid_price: bid,
+                    ask_price: ask,
+                    bid_size: parse_num(&v.bid_qty),
+                    ask_size: parse_num(&v.ask_qty),
+                    timestamp: timestamp.clone(),
+                };
+                if store.accept_quote(symbol.clone(), quote).is_ok() {
                     bus.publish(Event::Market(MarketEvent::Quote {
                         symbol,
                         bid,
@@ -337,65 +431,67 @@ impl GenericWsStream {
                     .ok();
                 }
             }
+            Some("markPriceUpdate") => {
+                bus.publish(Event::Market(MarketEvent::FundingRate {
+                    symbol,
+                    rate: parse_num(&v.funding_rate),
+                    mark_price: parse_num(&v.price),
+                    next_funding_time: v.trade_time.unwrap_or_default(),
+                    timestamp: v.event_time.map(|t| t.to_string()).unwrap_or_default(),
+                }))
+                .ok();
+            }
+            _ => {}
         }
     }
 
-    async fn process_coinbase(text: &str, store: &MarketStore, bus: &EventBus) {
-        if let Ok(v) = serde_json::from_str::<Value>(text) {
-            if v.get("channel").and_then(|c| c.as_str()) == Some("market_trades") {
-                if let Some(events) = v.get("events").and_then(|e| e.as_array()) {
-                    for ev in events {
-                        if let Some(trades) = ev.get("trades").and_then(|t| t.as_array()) {
-                            for tr in trades {
-                                let product_id =
-                                    tr.get("product_id").and_then(|x| x.as_str()).unwrap_or("");
-                                let symbol = product_id.replace('-', "/");
-                                let price = tr
-                                    .get("price")
-                                    .and_then(|x| x.as_str())
-                                    .and_then(|s| s.parse::<f64>().ok())
-                                    .unwrap_or(0.0);
-                                let size = tr
-                                    .get("size")
-                                    .and_then(|x| x.as_str())
-                                    .and_then(|s| s.parse::<f64>().ok())
-                                    .unwrap_or(0.0);
-                                let timestamp = tr
-                                    .get("time")
-                                    .and_then(|x| x.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-                                let id = tr
-                                    .get("trade_id")
-                                    .and_then(|x| x.as_str())
-                                    .and_then(|s| s.parse::<u64>().ok());
-
-                                if price > 0.0 {
-                                    let trade = Trade {
-                                        symbol: symbol.clone(),
-                                        price,
-                                        size,
-                                        timestamp: timestamp.clone(),
-                                        id,
-                                    };
-                                    store.update_trade(symbol.clone(), trade);
-                                    bus.publish(Event::Market(MarketEvent::Trade {
-                                        symbol,
-                                        price,
-                                        size,
-                                        timestamp,
-                                    }))
-                                    .ok();
-                                }
-                            }
-                        }
-                    }
+    async fn process_coinbase(
+        text: &str,
+        store: &MarketStore,
+        bus: &EventBus,
+        registry: &SymbolRegistry,
+    ) {
+        let Some(msg) = parse_coinbase(text) else {
+            return;
+        };
+        if msg.channel.as_deref() != Some("market_trades") {
+            return;
+        }
+        for ev in &msg.events {
+            for tr in &ev.trades {
+                let symbol = registry.canonical(&tr.product_id);
+                let price = parse_num(&tr.price);
+                let size = parse_num(&tr.size);
+                let timestamp = tr.time.as_deref().unwrap_or("").to_string();
+                let id = tr.trade_id.as_deref().and_then(|s| s.parse::<u64>().ok());
+
+                if price > 0.0 {
+                    let trade = Trade {
+                        symbol: symbol.clone(),
+                        price,
+                        size,
+                        timestamp: timestamp.clone(),
+                        id,
+                    };
+                    store.update_trade(symbol.clone(), trade);
+                    bus.publish(Event::Market(MarketEvent::Trade {
+                        symbol,
+                        price,
+                        size,
+                        timestamp,
+                    }))
+                    .ok();
                 }
             }
         }
     }
 
-    async fn process_kraken(text: &str, store: &MarketStore, bus: &EventBus) {
+    async fn process_kraken(
+        text: &str,
+        store: &MarketStore,
+        bus: &EventBus,
+        registry: &SymbolRegistry,
+    ) {
         // Kraken WS uses array messages for data, object messages for system/status.
         if let Ok(v) = serde_json::from_str::<Value>(text) {
             if v.is_array() {
@@ -411,7 +507,7 @@ impl GenericWsStream {
                     .get(arr.len() - 1)
                     .and_then(|x| x.as_str())
                     .unwrap_or("");
-                let symbol = pair.replace("XBT/", "BTC/");
+                let symbol = registry.canonical(pair);
 
                 if channel_name == "trade" {
                     if let Some(trades) = arr.get(1).and_then(|x| x.as_array()) {
@@ -483,16 +579,18 @@ impl GenericWsStream {
                             .unwrap_or(0.0);
                         let timestamp = chrono::Utc::now().to_rfc3339();
 
-                        if bid > 0.0 && ask > 0.0 {
-                            let quote = Quote {
-                                symbol: symbol.clone(),
-                                bid_price: bid,
-                                ask_price: ask,
-                                bid_size,
-                                ask_size,
-                                timestamp: timestamp.clone(),
-                            };
-                            store.update_quote(symbol.clone(), quote);
+                        let quote = Quote {
+                            symbol: symbol.clone(),
+                            bid_price: bid,
+                            ask_price: ask,
+                            bid_size,
+                            ask_size,
+                            timestamp: timestamp.clone(),
+                        };
+                        if bid > 0.0
+                            && ask > 0.0
+                            && store.accept_quote(symbol.clone(), quote).is_ok()
+                        {
                             bus.publish(Event::Market(MarketEvent::Quote {
                                 symbol,
                                 bid,
@@ -521,61 +619,103 @@ impl MarketDataStream for GenericWsStream {
 
         let (ws_stream, _) = connect_async(ws_url)
             .await
-            .map_err(|e| format!("WS connect failed: {e}"))?;
+            .map_err(|e| AutoHedgeError::Ws(format!("WS connect failed: {e}")))?;
         let (mut write, mut read) = ws_stream.split();
 
         let provider = self.provider.clone();
 
-        match provider {
-            WsProvider::AlpacaCrypto => {
-                let key = self.api_key.clone().unwrap_or_default();
-                let secret = self.api_secret.clone().unwrap_or_default();
-                Self::alpaca_auth(&mut write, &key, &secret).await?;
-                Self::alpaca_subscribe(&mut write, &symbols, true).await?;
-            }
-            WsProvider::AlpacaStocks => {
-                let key = self.api_key.clone().unwrap_or_default();
-                let secret = self.api_secret.clone().unwrap_or_default();
-                Self::alpaca_auth(&mut write, &key, &secret).await?;
-                Self::alpaca_subscribe(&mut write, &symbols, false).await?;
-            }
-            WsProvider::Binance => {
-                Self::binance_subscribe(&mut write, &symbols).await?;
-            }
-            WsProvider::Coinbase => {
-                Self::coinbase_subscribe(&mut write, &symbols).await?;
-            }
-            WsProvider::Kraken => {
-                Self::kraken_subscribe(&mut write, &symbols).await?;
-            }
+        if let WsProvider::AlpacaCrypto | WsProvider::AlpacaStocks = provider {
+            let key = self.api_key.clone().unwrap_or_default();
+            let secret = self.api_secret.clone().unwrap_or_default();
+            Self::alpaca_auth(&mut write, &key, &secret).await?;
         }
-
+        let registry = self.symbols.clone();
+        Self::subscribe(&provider, &mut write, &symbols, &registry).await?;
+
+        let shutdown = event_bus.shutdown().clone();
+        // Symbols added by a config reload are subscribed on the live socket
+        let mut rx = event_bus.subscribe("market_data");
+        let mut subscribed: HashSet<String> = symbols.into_iter().collect();
+        let mut chatter = self.chatter;
         tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
+            let reason = loop {
+                let msg = tokio::select! {
+                    _ = shutdown.stopped() => {
+                        let _ = write.send(Message::Close(None)).await;
+                        info!("WS closed (shutdown)");
+                        return;
+                    }
+                    event = rx.recv() => {
+                        if let Ok(Event::Config(update)) = event {
+                            chatter = update.config.chatter_for(ChatterModule::Ws);
+                            let added: Vec<String> = update
+                                .config
+                                .symbols
+                                .iter()
+                                .filter(|s| !subscribed.contains(*s))
+                                .cloned()
+                                .collect();
+                            if !added.is_empty() {
+                                info!("WS subscribing to added symbols: {:?}", added);
+                                for symbol in &added {
+                                    registry.register(symbol);
+                                }
+                                match Self::subscribe(&provider, &mut write, &added, &registry)
+                                    .await
+                                {
+                                    Ok(()) => subscribed.extend(added),
+                                    Err(e) => error!("WS subscribe failed: {}", e),
+                                }
+                            }
+                        }
+                        continue;
+                    }
+                    msg = read.next() => match msg {
+                        Some(msg) => msg,
+                        None => break "closed by venue".to_string(),
+                    },
+                };
+                #[cfg(feature = "chaos")]
+                if crate::chaos::inject(crate::chaos::Fault::WsDisconnect) {
+                    break "chaos: injected disconnect".to_string();
+                }
+                if let (true, Ok(Message::Text(text))) = (chatter.is_verbose(), &msg) {
+                    let frame: String = text.chars().take(WS_LOG_CHARS).collect();
+                    info!("WS frame ({} bytes): {}", text.len(), frame);
+                }
                 match msg {
                     Ok(Message::Text(text)) => match provider {
                         WsProvider::AlpacaCrypto | WsProvider::AlpacaStocks => {
-                            Self::process_alpaca(&text, &store, &event_bus).await
+                            Self::process_alpaca(&text, &store, &event_bus, &registry).await
                         }
-                        WsProvider::Binance => {
-                            Self::process_binance(&text, &store, &event_bus).await
+                        WsProvider::Binance | WsProvider::BinanceFutures => {
+                            Self::process_binance(&text, &store, &event_bus, &registry).await
                         }
                         WsProvider::Coinbase => {
-                            Self::process_coinbase(&text, &store, &event_bus).await
+                            Self::process_coinbase(&text, &store, &event_bus, &registry).await
+                        }
+                        WsProvider::Kraken => {
+                            Self::process_kraken(&text, &store, &event_bus, &registry).await
                         }
-                        WsProvider::Kraken => Self::process_kraken(&text, &store, &event_bus).await,
                     },
                     Ok(Message::Ping(p)) => {
                         let _ = write.send(Message::Pong(p)).await;
                     }
                     Err(e) => {
                         error!("WS error: {}", e);
-                        break;
+                        break e.to_string();
                     }
                     _ => {}
                 }
-            }
+            };
             warn!("WS loop ended");
+            event_bus
+                .publish(Event::Disconnected(DisconnectEvent {
+                    stream: "market_ws".to_string(),
+                    reason,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                }))
+                .ok();
         });
 
         Ok(())
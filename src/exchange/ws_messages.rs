@@ -0,0 +1,152 @@
+//! Typed WebSocket message formats for the market-data hot path.
+//!
+//! Parsing into `serde_json::Value` allocates a map per message. These flat
+//! structs borrow string fields straight from the frame (`Cow` falls back to
+//! an owned string only when the payload contains escapes) and ignore any
+//! fields we don't read.
+//!
+//! Kraken publishes heterogeneous JSON arrays, so it stays on the `Value` path.
+
+use serde::{Deserialize, Deserializer};
+use std::borrow::Cow;
+
+/// `Option<Cow<str>>` does not borrow by default; route through a newtype that does.
+fn opt_cow<'de, D>(deserializer: D) -> Result<Option<Cow<'de, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Option::<Borrowed<'de>>::deserialize(deserializer).map(|o| o.map(|b| b.0))
+}
+
+/// Alpaca stream item. Frames are JSON arrays of these; `kind` is the `T` tag
+/// ("t" trade, "q" quote, "b" bar, "s" trading status, "success", "subscription", "error").
+#[derive(Debug, Deserialize)]
+pub struct AlpacaMessage<'a> {
+    #[serde(rename = "T", borrow)]
+    pub kind: Cow<'a, str>,
+    #[serde(rename = "S", borrow, default, deserialize_with = "opt_cow")]
+    pub symbol: Option<Cow<'a, str>>,
+    #[serde(rename = "t", borrow, default, deserialize_with = "opt_cow")]
+    pub timestamp: Option<Cow<'a, str>>,
+    // Trade fields
+    #[serde(rename = "p", default)]
+    pub price: Option<f64>,
+    #[serde(rename = "s", default)]
+    pub size: Option<f64>,
+    #[serde(rename = "i", default)]
+    pub id: Option<u64>,
+    // Quote fields
+    #[serde(rename = "bp", default)]
+    pub bid_price: Option<f64>,
+    #[serde(rename = "ap", default)]
+    pub ask_price: Option<f64>,
+    #[serde(rename = "bs", default)]
+    pub bid_size: Option<f64>,
+    #[serde(rename = "as", default)]
+    pub ask_size: Option<f64>,
+    // Trading status fields (stocks)
+    #[serde(rename = "sc", borrow, default, deserialize_with = "opt_cow")]
+    pub status_code: Option<Cow<'a, str>>,
+    #[serde(rename = "sm", borrow, default, deserialize_with = "opt_cow")]
+    pub status_message: Option<Cow<'a, str>>,
+}
+
+/// Binance `trade` / `bookTicker` / `markPriceUpdate` payload. Binance sends
+/// decimals as strings.
+#[derive(Debug, Deserialize)]
+pub struct BinanceMessage<'a> {
+    #[serde(rename = "e", borrow, default, deserialize_with = "opt_cow")]
+    pub event: Option<Cow<'a, str>>,
+    #[serde(rename = "E", default)]
+    pub event_time: Option<i64>,
+    #[serde(rename = "s", borrow, default, deserialize_with = "opt_cow")]
+    pub symbol: Option<Cow<'a, str>>,
+    // Trade fields
+    #[serde(rename = "t", default)]
+    pub trade_id: Option<u64>,
+    #[serde(rename = "p", borrow, default, deserialize_with = "opt_cow")]
+    pub price: Option<Cow<'a, str>>,
+    #[serde(rename = "q", borrow, default, deserialize_with = "opt_cow")]
+    pub qty: Option<Cow<'a, str>>,
+    #[serde(rename = "T", default)]
+    pub trade_time: Option<i64>,
+    // bookTicker fields
+    #[serde(rename = "b", borrow, default, deserialize_with = "opt_cow")]
+    pub bid_price: Option<Cow<'a, str>>,
+    #[serde(rename = "B", borrow, default, deserialize_with = "opt_cow")]
+    pub bid_qty: Option<Cow<'a, str>>,
+    #[serde(rename = "a", borrow, default, deserialize_with = "opt_cow")]
+    pub ask_price: Option<Cow<'a, str>>,
+    #[serde(rename = "A", borrow, default, deserialize_with = "opt_cow")]
+    pub ask_qty: Option<Cow<'a, str>>,
+    // markPriceUpdate fields (futures): mark price in `p`, next funding time in `T`
+    #[serde(rename = "r", borrow, default, deserialize_with = "opt_cow")]
+    pub funding_rate: Option<Cow<'a, str>>,
+}
+
+/// Coinbase Advanced Trade envelope (`market_trades` channel).
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseMessage<'a> {
+    #[serde(borrow, default, deserialize_with = "opt_cow")]
+    pub channel: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    pub events: Vec<CoinbaseEvent<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseEvent<'a> {
+    #[serde(borrow, default)]
+    pub trades: Vec<CoinbaseTrade<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseTrade<'a> {
+    #[serde(borrow, default)]
+    pub product_id: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "opt_cow")]
+    pub price: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "opt_cow")]
+    pub size: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "opt_cow")]
+    pub time: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "opt_cow")]
+    pub trade_id: Option<Cow<'a, str>>,
+}
+
+/// Parse an optional decimal string, defaulting to 0.0 like the legacy path.
+#[inline]
+pub fn parse_num(s: &Option<Cow<'_, str>>) -> f64 {
+    s.as_deref()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+/// Parse an Alpaca frame (always an array).
+pub fn parse_alpaca(text: &str) -> Option<Vec<AlpacaMessage<'_>>> {
+    serde_json::from_str(text).ok()
+}
+
+/// Alpaca trading-status code -> halted (Some(true)), resumed (Some(false)),
+/// or None for informational codes.
+pub fn alpaca_status_halted(code: &str) -> Option<bool> {
+    match code {
+        // Trading halt (UTP / CTA), volatility trading pause
+        "H" | "2" | "P" => Some(true),
+        // Trading / quotation resumption
+        "T" | "3" | "Q" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a single Binance stream payload.
+pub fn parse_binance(text: &str) -> Option<BinanceMessage<'_>> {
+    serde_json::from_str(text).ok()
+}
+
+/// Parse a Coinbase Advanced Trade frame.
+pub fn parse_coinbase(text: &str) -> Option<CoinbaseMessage<'_>> {
+    serde_json::from_str(text).ok()
+}
@@ -0,0 +1,105 @@
+//! Unit tests for typed WebSocket message parsing.
+
+#[cfg(test)]
+mod ws_messages_tests {
+    use crate::exchange::ws_messages::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_parse_alpaca_quote_and_trade() {
+        let text = r#"[{"T":"q","S":"BTC/USD","bp":50000.5,"bs":1.2,"ap":50001.0,"as":0.8,"t":"2025-01-01T00:00:00Z"},
+                       {"T":"t","S":"ETH/USD","p":3000.0,"s":0.5,"i":42,"t":"2025-01-01T00:00:01Z","tks":"B"}]"#;
+        let items = parse_alpaca(text).unwrap();
+        assert_eq!(items.len(), 2);
+
+        assert_eq!(items[0].kind, "q");
+        assert_eq!(items[0].symbol.as_deref(), Some("BTC/USD"));
+        assert_eq!(items[0].bid_price, Some(50000.5));
+        assert_eq!(items[0].ask_size, Some(0.8));
+
+        assert_eq!(items[1].kind, "t");
+        assert_eq!(items[1].price, Some(3000.0));
+        assert_eq!(items[1].id, Some(42));
+    }
+
+    #[test]
+    fn test_parse_alpaca_borrows_symbol() {
+        let text = r#"[{"T":"q","S":"SOL/USD","bp":1.0,"ap":2.0}]"#;
+        let items = parse_alpaca(text).unwrap();
+        assert!(matches!(items[0].symbol, Some(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn test_parse_alpaca_control_message() {
+        let text = r#"[{"T":"success","msg":"authenticated"}]"#;
+        let items = parse_alpaca(text).unwrap();
+        assert_eq!(items[0].kind, "success");
+        assert!(items[0].symbol.is_none());
+    }
+
+    #[test]
+    fn test_parse_binance_book_ticker() {
+        let text = r#"{"e":"bookTicker","E":1700000000000,"s":"BTCUSDT","b":"50000.10","B":"1.5","a":"50000.20","A":"2.5"}"#;
+        let msg = parse_binance(text).unwrap();
+        assert_eq!(msg.event.as_deref(), Some("bookTicker"));
+        assert_eq!(parse_num(&msg.bid_price), 50000.10);
+        assert_eq!(parse_num(&msg.ask_qty), 2.5);
+        assert_eq!(msg.event_time, Some(1700000000000));
+    }
+
+    #[test]
+    fn test_parse_binance_mark_price_update() {
+        let text = r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15","i":"11784.62","P":"11784.25","r":"0.00038167","T":1562306400000}"#;
+        let msg = parse_binance(text).unwrap();
+        assert_eq!(msg.event.as_deref(), Some("markPriceUpdate"));
+        assert_eq!(parse_num(&msg.price), 11794.15);
+        assert_eq!(parse_num(&msg.funding_rate), 0.00038167);
+        assert_eq!(msg.trade_time, Some(1562306400000));
+    }
+
+    #[test]
+    fn test_parse_binance_trade() {
+        let text = r#"{"e":"trade","E":1,"s":"ETHUSDT","t":12345,"p":"3000.5","q":"0.25","T":1700000000001,"m":true,"M":true}"#;
+        let msg = parse_binance(text).unwrap();
+        assert_eq!(msg.trade_id, Some(12345));
+        assert_eq!(parse_num(&msg.price), 3000.5);
+        assert_eq!(parse_num(&msg.qty), 0.25);
+        assert_eq!(msg.trade_time, Some(1700000000001));
+    }
+
+    #[test]
+    fn test_parse_num_invalid_defaults_zero() {
+        assert_eq!(parse_num(&None), 0.0);
+        assert_eq!(parse_num(&Some(Cow::Borrowed("abc"))), 0.0);
+    }
+
+    #[test]
+    fn test_parse_coinbase_market_trades() {
+        let text = r#"{"channel":"market_trades","events":[{"type":"update","trades":[
+            {"trade_id":"99","product_id":"BTC-USD","price":"50000.0","size":"0.01","side":"BUY","time":"2025-01-01T00:00:00Z"}]}]}"#;
+        let msg = parse_coinbase(text).unwrap();
+        assert_eq!(msg.channel.as_deref(), Some("market_trades"));
+        let trade = &msg.events[0].trades[0];
+        assert_eq!(trade.product_id, "BTC-USD");
+        assert_eq!(parse_num(&trade.price), 50000.0);
+        assert_eq!(trade.trade_id.as_deref(), Some("99"));
+    }
+
+    #[test]
+    fn test_parse_alpaca_trading_status() {
+        let text = r#"[{"T":"s","S":"AAPL","sc":"H","sm":"Trading Halt","rc":"T12","t":"2025-01-01T15:00:00Z"}]"#;
+        let items = parse_alpaca(text).unwrap();
+        assert_eq!(items[0].kind, "s");
+        assert_eq!(items[0].status_code.as_deref(), Some("H"));
+        assert_eq!(items[0].status_message.as_deref(), Some("Trading Halt"));
+        assert_eq!(alpaca_status_halted("H"), Some(true));
+        assert_eq!(alpaca_status_halted("T"), Some(false));
+        assert_eq!(alpaca_status_halted("X"), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        assert!(parse_alpaca("not json").is_none());
+        assert!(parse_binance("{").is_none());
+    }
+}
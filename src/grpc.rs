@@ -0,0 +1,272 @@
+//! gRPC control server (`proto/autohedge.proto`, `--features grpc`).
+//!
+//! Same control surface as the HTTP API, over the same `AppState`: start and
+//! stop the session, read positions and working orders, cancel open orders,
+//! send operator commands and stream bus events. Calls are authorized with
+//! the `api_auth` credentials, passed as `authorization` or `x-api-key`
+//! metadata.
+
+// tonic handlers return `Result<_, Status>` by signature
+#![allow(clippy::result_large_err)]
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures_util::Stream;
+use rust_decimal::Decimal;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status};
+use tracing::{error, info, warn};
+
+use crate::api::{mark_price, send_command, start_session, stop_session, AppState, Session};
+use crate::events::ControlCommand;
+use crate::services::api_auth::{credential, ApiAuth};
+
+pub mod proto {
+    tonic::include_proto!("autohedge.v1");
+}
+
+use proto::auto_hedge_server::{AutoHedge, AutoHedgeServer};
+use proto::control_request::Command;
+use proto::{
+    ControlReply, ControlRequest, Empty, EventMessage, Order, OrdersReply, Position,
+    PositionsReply, StatusReply,
+};
+
+/// Serve the gRPC API on `port` until the process exits.
+pub async fn serve(state: Arc<AppState>, port: u16) {
+    let auth = ApiAuth::new(&state.config.snapshot().api_auth);
+    let service = GrpcService { state, auth };
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("gRPC Server listening on port {}", port);
+    if let Err(e) = tonic::transport::Server::builder()
+        .add_service(AutoHedgeServer::new(service))
+        .serve(addr)
+        .await
+    {
+        error!("❌ [GRPC] Server stopped: {}", e);
+    }
+}
+
+struct GrpcService {
+    state: Arc<AppState>,
+    auth: ApiAuth,
+}
+
+impl GrpcService {
+    /// Check the caller's credential; `write` calls need the admin role,
+    /// like non-GET HTTP requests.
+    fn authorize<T>(&self, request: &Request<T>, rpc: &str, write: bool) -> Result<(), Status> {
+        if !self.auth.enabled() {
+            return Ok(());
+        }
+        let metadata = |name: &str| request.metadata().get(name).and_then(|v| v.to_str().ok());
+        let token = credential(metadata("authorization"), metadata("x-api-key"));
+        let method = if write { "POST" } else { "GET" };
+        self.auth
+            .authorize(token, method, Instant::now())
+            .map(|_| ())
+            .map_err(|e| {
+                warn!("🔒 [GRPC] {} denied: {}", rpc, e);
+                match e.status() {
+                    401 => Status::unauthenticated(e.to_string()),
+                    403 => Status::permission_denied(e.to_string()),
+                    _ => Status::resource_exhausted(e.to_string()),
+                }
+            })
+    }
+
+    fn session(&self) -> Result<Session, Status> {
+        self.state
+            .session
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| Status::failed_precondition("not_running"))
+    }
+}
+
+fn status(status: &str) -> Response<StatusReply> {
+    Response::new(StatusReply {
+        status: status.to_string(),
+    })
+}
+
+fn decimal(field: &str, value: Option<String>) -> Result<Option<Decimal>, Status> {
+    value
+        .map(|v| {
+            Decimal::from_str(v.trim())
+                .map_err(|e| Status::invalid_argument(format!("{}: {}", field, e)))
+        })
+        .transpose()
+}
+
+impl TryFrom<ControlRequest> for ControlCommand {
+    type Error = Status;
+
+    fn try_from(request: ControlRequest) -> Result<Self, Status> {
+        Ok(match request.command {
+            Some(Command::PauseSymbol(symbol)) => ControlCommand::PauseSymbol { symbol },
+            Some(Command::ResumeSymbol(symbol)) => ControlCommand::ResumeSymbol { symbol },
+            Some(Command::ClosePosition(symbol)) => ControlCommand::ClosePosition { symbol },
+            Some(Command::AdjustExits(adjust)) => ControlCommand::AdjustExits {
+                symbol: adjust.symbol,
+                take_profit: decimal("take_profit", adjust.take_profit)?,
+                stop_loss: decimal("stop_loss", adjust.stop_loss)?,
+            },
+            None => return Err(Status::invalid_argument("missing command")),
+        })
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<EventMessage, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl AutoHedge for GrpcService {
+    async fn start(&self, request: Request<Empty>) -> Result<Response<StatusReply>, Status> {
+        self.authorize(&request, "Start", true)?;
+        Ok(status(start_session(&self.state)))
+    }
+
+    async fn stop(&self, request: Request<Empty>) -> Result<Response<StatusReply>, Status> {
+        self.authorize(&request, "Stop", true)?;
+        Ok(status(stop_session(&self.state).await))
+    }
+
+    async fn get_positions(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<PositionsReply>, Status> {
+        self.authorize(&request, "GetPositions", false)?;
+        let session = self.session()?;
+        let mut total_pnl = Decimal::ZERO;
+        let positions = session
+            .tracker
+            .get_all_positions()
+            .into_iter()
+            .map(|p| {
+                let price = mark_price(&session, &p.symbol);
+                let pnl = price.map(|px| (px - p.entry_price) * p.filled_qty);
+                total_pnl += pnl.unwrap_or_default();
+                Position {
+                    symbol: p.symbol,
+                    qty: p.qty.to_string(),
+                    filled_qty: p.filled_qty.to_string(),
+                    remaining_qty: p.remaining_qty.to_string(),
+                    entry_price: p.entry_price.to_string(),
+                    current_price: price.map(|px| px.to_string()),
+                    unrealized_pnl: pnl.map(|v| v.to_string()),
+                    take_profit: p.take_profit.to_string(),
+                    stop_loss: p.stop_loss.to_string(),
+                    is_closing: p.is_closing,
+                }
+            })
+            .collect();
+        Ok(Response::new(PositionsReply {
+            positions,
+            unrealized_pnl: total_pnl.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }))
+    }
+
+    async fn list_orders(&self, request: Request<Empty>) -> Result<Response<OrdersReply>, Status> {
+        self.authorize(&request, "ListOrders", false)?;
+        let orders = self
+            .session()?
+            .tracker
+            .get_all_pending_orders()
+            .into_iter()
+            .map(|o| Order {
+                symbol: o.symbol,
+                order_id: o.order_id,
+                side: o.side,
+                qty: o.qty.to_string(),
+                filled_qty: o.filled_qty.to_string(),
+                limit_price: o.limit_price.to_string(),
+                created_at: o.created_at,
+            })
+            .collect();
+        Ok(Response::new(OrdersReply { orders }))
+    }
+
+    async fn cancel_all_orders(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<StatusReply>, Status> {
+        self.authorize(&request, "CancelAllOrders", true)?;
+        let exchange = self
+            .state
+            .exchange
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| Status::failed_precondition("not_running"))?;
+        match exchange.cancel_all_orders().await {
+            Ok(_) => Ok(status("success")),
+            Err(e) => Err(Status::internal(format!(
+                "Failed to cancel all orders: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn control(
+        &self,
+        request: Request<ControlRequest>,
+    ) -> Result<Response<ControlReply>, Status> {
+        self.authorize(&request, "Control", true)?;
+        let session = self.session()?;
+        let command = ControlCommand::try_from(request.into_inner())?;
+        let reply = send_command(command, &session);
+        if reply["type"] == "error" {
+            return Err(Status::invalid_argument(
+                reply["message"].as_str().unwrap_or_default(),
+            ));
+        }
+        Ok(Response::new(ControlReply {
+            command: reply["command"].to_string(),
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        self.authorize(&request, "StreamEvents", false)?;
+        let session = self.session()?;
+        info!("🖥️ [GRPC] Event stream client connected");
+        let rx = session.event_bus.subscribe("grpc_client");
+        let shutdown = session.event_bus.shutdown().clone();
+
+        let stream = futures_util::stream::unfold(
+            (rx, shutdown),
+            |(mut rx, shutdown)| async move {
+                loop {
+                    let value = tokio::select! {
+                        _ = shutdown.stopped() => return None,
+                        event = rx.recv() => match event {
+                            Ok(event) => match event.to_json() {
+                                Some(value) => value,
+                                None => continue,
+                            },
+                            Err(RecvError::Lagged(skipped)) => json!({"type": "lagged", "skipped": skipped}),
+                            Err(RecvError::Closed) => return None,
+                        },
+                    };
+                    let message = EventMessage {
+                        r#type: value["type"].as_str().unwrap_or_default().to_string(),
+                        json: value.to_string(),
+                    };
+                    return Some((Ok(message), (rx, shutdown)));
+                }
+            },
+        );
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod agents;
 pub mod bus;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod config;
 pub mod constants;
 pub mod data;
@@ -12,16 +14,32 @@ pub mod error;
 pub mod events;
 pub mod exchange;
 pub mod llm;
+pub mod logging;
+pub mod money;
 pub mod services;
 
 // Re-export commonly used types
 pub use bus::EventBus;
 pub use config::AppConfig;
-pub use events::{AnalysisSignal, Event, ExecutionReport, MarketEvent, OrderRequest};
+pub use error::AutoHedgeError;
+pub use events::{
+    AccountEvent, AnalysisSignal, ArbitrageEvent, ArbitrageTradeEvent, BalanceChangeEvent,
+    ConfigUpdateEvent, ControlCommand, DegradationLevel, DeleverageEvent, EntrySkippedEvent, Event,
+    ExecutionReport, HealthEvent, LlmFallbackEvent, MarketEvent, OrderRejectedEvent, OrderRequest,
+    RiskLimitEvent, SymbolCooldownEvent, SymbolStatusEvent, SystemEvent,
+};
 
 #[cfg(test)]
 mod bus_tests;
+#[cfg(all(test, feature = "chaos"))]
+mod chaos_tests;
 #[cfg(test)]
 mod config_tests;
 #[cfg(test)]
+mod error_tests;
+#[cfg(test)]
 mod events_tests;
+#[cfg(test)]
+mod logging_tests;
+#[cfg(test)]
+mod money_tests;
@@ -1,63 +1,84 @@
+pub mod provider;
 pub mod queue;
+pub mod retry;
+pub mod usage;
 
-use async_openai::{
-    config::OpenAIConfig,
-    types::{ChatCompletionRequestMessage, CreateChatCompletionRequestArgs},
-    Client,
-};
-use std::error::Error;
+#[cfg(test)]
+mod provider_tests;
+#[cfg(test)]
+mod retry_tests;
+#[cfg(test)]
+mod usage_tests;
 
+use std::sync::Arc;
+
+use crate::config::{LlmConfig, LlmPricingConfig};
+
+use provider::{LlmProvider, OpenAiProvider};
 pub use queue::{LLMQueue, Priority};
+use usage::TokenUsage;
 
 #[derive(Clone)]
 pub struct LLMClient {
-    pub client: Client<OpenAIConfig>,
+    /// Backend the chat requests go to (`llm.provider`)
+    pub provider: Arc<dyn LlmProvider>,
     pub model: String,
+    /// Token prices used to estimate the cost of each call
+    pub pricing: LlmPricingConfig,
 }
 
 impl LLMClient {
+    /// Client for an OpenAI-compatible API.
     pub fn new(api_key: String, base_url: Option<String>, model: String) -> Self {
-        let mut config = OpenAIConfig::new().with_api_key(api_key);
-        if let Some(url) = base_url {
-            config = config.with_api_base(url);
+        Self {
+            provider: Arc::new(OpenAiProvider::new(api_key, base_url)),
+            model,
+            pricing: LlmPricingConfig::default(),
+        }
+    }
+
+    /// Client for the backend selected by `config.provider`.
+    pub fn from_config(config: &LlmConfig) -> Self {
+        Self {
+            provider: Arc::from(provider::provider_for(config)),
+            model: config.model.clone(),
+            pricing: config.pricing.clone(),
         }
-        let client = Client::with_config(config);
-        Self { client, model }
+    }
+
+    pub fn with_pricing(mut self, pricing: LlmPricingConfig) -> Self {
+        self.pricing = pricing;
+        self
     }
 
     pub async fn chat(
         &self,
         system_prompt: &str,
         user_input: &str,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+    ) -> crate::error::Result<(String, TokenUsage)> {
         use tracing::info;
 
-        info!("🤖 Sending request to LLM (Model: {})...", self.model);
-
-        let request = CreateChatCompletionRequestArgs::default()
-            .model(&self.model)
-            .messages([
-                ChatCompletionRequestMessage::System(
-                    async_openai::types::ChatCompletionRequestSystemMessageArgs::default()
-                        .content(system_prompt)
-                        .build()?,
-                ),
-                ChatCompletionRequestMessage::User(
-                    async_openai::types::ChatCompletionRequestUserMessageArgs::default()
-                        .content(user_input)
-                        .build()?,
-                ),
-            ])
-            .build()?;
-
-        let response = self.client.chat().create(request).await?;
-
-        info!("🤖 LLM Response received.");
-
-        Ok(response.choices[0]
-            .message
-            .content
-            .clone()
-            .unwrap_or_default())
+        info!(
+            "🤖 Sending request to LLM (Provider: {}, Model: {})...",
+            self.provider.name(),
+            self.model
+        );
+
+        #[cfg(feature = "chaos")]
+        if crate::chaos::inject(crate::chaos::Fault::LlmFailure) {
+            return Err(crate::error::AutoHedgeError::LlmTransient(
+                "chaos: injected failure".to_string(),
+            ));
+        }
+
+        let (content, usage) = self
+            .provider
+            .chat(&self.model, system_prompt, user_input)
+            .await?;
+        info!(
+            "🤖 LLM Response received ({} prompt + {} completion tokens).",
+            usage.prompt_tokens, usage.completion_tokens
+        );
+        Ok((content, usage))
     }
 }
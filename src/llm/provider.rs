@@ -0,0 +1,550 @@
+//! LLM backends behind `LLMClient`, selected by `llm.provider`.
+//!
+//! Each provider shapes the (system, user) prompt pair into its native request,
+//! reads the reply text and token counts back, and with `llm.stream` set reads
+//! the streamed form instead (SSE for OpenAI/Anthropic/llama.cpp, NDJSON for
+//! Ollama). HTTP 429/5xx and transport failures surface as
+//! `AutoHedgeError::LlmTransient` so the queue retries them.
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestUserMessageArgs, ChatCompletionStreamOptions,
+        CreateChatCompletionRequestArgs,
+    },
+    Client,
+};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+
+use super::usage::TokenUsage;
+use crate::config::LlmConfig;
+use crate::error::{AutoHedgeError, Result};
+
+/// Supported `llm.provider` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LlmProviderKind {
+    OpenAi,
+    Anthropic,
+    LlamaCpp,
+    Ollama,
+}
+
+impl LlmProviderKind {
+    /// Parse a config value ("openai", "anthropic", "llamacpp", "ollama").
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "openai" | "openai_compatible" => Some(Self::OpenAi),
+            "anthropic" | "claude" => Some(Self::Anthropic),
+            "llamacpp" | "llama.cpp" | "llama_cpp" => Some(Self::LlamaCpp),
+            "ollama" => Some(Self::Ollama),
+            _ => None,
+        }
+    }
+
+    /// Base URL used when `llm.base_url` is unset.
+    pub fn default_base_url(self) -> &'static str {
+        match self {
+            Self::OpenAi => "https://api.openai.com/v1",
+            Self::Anthropic => "https://api.anthropic.com",
+            Self::LlamaCpp => "http://localhost:8080",
+            Self::Ollama => "http://localhost:11434",
+        }
+    }
+}
+
+/// A chat backend: one system + user prompt in, reply text and usage out.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn chat(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        user_input: &str,
+    ) -> Result<(String, TokenUsage)>;
+}
+
+/// Build the provider for `config.provider` (unknown values fall back to OpenAI).
+pub fn provider_for(config: &LlmConfig) -> Box<dyn LlmProvider> {
+    let kind = LlmProviderKind::parse(&config.provider).unwrap_or_else(|| {
+        tracing::warn!(
+            "🤖 Unknown llm.provider '{}', using openai",
+            config.provider
+        );
+        LlmProviderKind::OpenAi
+    });
+    let api_key = config.api_key.clone().unwrap_or_default();
+    let base_url = config
+        .base_url
+        .clone()
+        .unwrap_or_else(|| kind.default_base_url().to_string())
+        .trim_end_matches('/')
+        .to_string();
+    let settings = HttpSettings {
+        http: reqwest::Client::new(),
+        base_url,
+        max_tokens: config.max_tokens,
+        stream: config.stream,
+    };
+    match kind {
+        LlmProviderKind::OpenAi => Box::new(
+            OpenAiProvider::new(api_key, config.base_url.clone()).with_stream(config.stream),
+        ),
+        LlmProviderKind::Anthropic => Box::new(AnthropicProvider { api_key, settings }),
+        LlmProviderKind::LlamaCpp => Box::new(LlamaCppProvider { settings }),
+        LlmProviderKind::Ollama => Box::new(OllamaProvider { settings }),
+    }
+}
+
+/// Reply text and usage accumulated from a streamed response.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamedReply {
+    pub text: String,
+    pub usage: TokenUsage,
+}
+
+// ============================================================================
+// OpenAI-compatible
+// ============================================================================
+
+/// OpenAI and any OpenAI-compatible chat completions API.
+pub struct OpenAiProvider {
+    client: Client<OpenAIConfig>,
+    stream: bool,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: String, base_url: Option<String>) -> Self {
+        let mut config = OpenAIConfig::new().with_api_key(api_key);
+        if let Some(url) = base_url {
+            config = config.with_api_base(url);
+        }
+        Self {
+            client: Client::with_config(config),
+            stream: false,
+        }
+    }
+
+    pub fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = stream;
+        self
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn chat(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        user_input: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let mut args = CreateChatCompletionRequestArgs::default();
+        args.model(model).messages([
+            ChatCompletionRequestMessage::System(
+                ChatCompletionRequestSystemMessageArgs::default()
+                    .content(system_prompt)
+                    .build()?,
+            ),
+            ChatCompletionRequestMessage::User(
+                ChatCompletionRequestUserMessageArgs::default()
+                    .content(user_input)
+                    .build()?,
+            ),
+        ]);
+
+        if !self.stream {
+            let response = self.client.chat().create(args.build()?).await?;
+            let usage = response
+                .usage
+                .as_ref()
+                .map(|u| TokenUsage {
+                    prompt_tokens: u.prompt_tokens as u64,
+                    completion_tokens: u.completion_tokens as u64,
+                })
+                .unwrap_or_default();
+            let content = response
+                .choices
+                .first()
+                .and_then(|c| c.message.content.clone())
+                .unwrap_or_default();
+            return Ok((content, usage));
+        }
+
+        let request = args
+            .stream(true)
+            .stream_options(ChatCompletionStreamOptions {
+                include_usage: true,
+            })
+            .build()?;
+        let mut stream = self.client.chat().create_stream(request).await?;
+        let mut reply = StreamedReply::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            for choice in &chunk.choices {
+                if let Some(text) = &choice.delta.content {
+                    reply.text.push_str(text);
+                }
+            }
+            if let Some(u) = &chunk.usage {
+                reply.usage = TokenUsage {
+                    prompt_tokens: u.prompt_tokens as u64,
+                    completion_tokens: u.completion_tokens as u64,
+                };
+            }
+        }
+        Ok((reply.text, reply.usage))
+    }
+}
+
+// ============================================================================
+// Native HTTP providers
+// ============================================================================
+
+/// Connection and request settings shared by the native HTTP providers.
+struct HttpSettings {
+    http: reqwest::Client,
+    base_url: String,
+    max_tokens: u32,
+    stream: bool,
+}
+
+impl HttpSettings {
+    /// POST `body` and read either the JSON reply or, when streaming, every
+    /// event line into `on_event`.
+    async fn post(
+        &self,
+        request: reqwest::RequestBuilder,
+        body: &Value,
+        mut on_event: impl FnMut(&Value),
+    ) -> Result<Option<Value>> {
+        let mut response = request.json(body).send().await.map_err(http_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(status_error(status.as_u16(), &text));
+        }
+        if !self.stream {
+            return response.json().await.map(Some).map_err(http_error);
+        }
+
+        let mut buffer = String::new();
+        while let Some(chunk) = response.chunk().await.map_err(http_error)? {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(pos) = buffer.find('\n') {
+                let line: String = buffer.drain(..=pos).collect();
+                if let Some(event) = stream_line(&line) {
+                    on_event(&event);
+                }
+            }
+        }
+        if let Some(event) = stream_line(&buffer) {
+            on_event(&event);
+        }
+        Ok(None)
+    }
+}
+
+/// JSON payload of one SSE (`data: {...}`) or NDJSON line, if any.
+pub fn stream_line(line: &str) -> Option<Value> {
+    let line = line.trim();
+    let payload = line.strip_prefix("data:").map_or(line, str::trim_start);
+    if payload.is_empty() || payload == "[DONE]" {
+        return None;
+    }
+    serde_json::from_str(payload).ok()
+}
+
+/// Transport failures are retried like rate limits and server errors.
+fn http_error(err: reqwest::Error) -> AutoHedgeError {
+    let status = err.status().map(|s| s.as_u16());
+    if err.is_timeout()
+        || err.is_connect()
+        || err.is_request()
+        || status.is_some_and(is_transient_status)
+    {
+        AutoHedgeError::LlmTransient(err.to_string())
+    } else {
+        AutoHedgeError::Llm(err.to_string())
+    }
+}
+
+/// 429, 5xx and Anthropic's 529 "overloaded" are transient.
+fn is_transient_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+pub fn status_error(status: u16, body: &str) -> AutoHedgeError {
+    let message = format!("HTTP {}: {}", status, body.trim());
+    if is_transient_status(status) {
+        AutoHedgeError::LlmTransient(message)
+    } else {
+        AutoHedgeError::Llm(message)
+    }
+}
+
+fn tokens(value: &Value) -> u64 {
+    value.as_u64().unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Anthropic Messages API
+// ---------------------------------------------------------------------------
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+pub struct AnthropicProvider {
+    api_key: String,
+    settings: HttpSettings,
+}
+
+/// `POST /v1/messages` body; the system prompt is a top-level field.
+pub fn anthropic_request(
+    model: &str,
+    system_prompt: &str,
+    user_input: &str,
+    max_tokens: u32,
+    stream: bool,
+) -> Value {
+    json!({
+        "model": model,
+        "max_tokens": max_tokens,
+        "system": system_prompt,
+        "messages": [{"role": "user", "content": user_input}],
+        "stream": stream,
+    })
+}
+
+/// Text blocks and usage of a Messages API response.
+pub fn anthropic_reply(response: &Value) -> Result<StreamedReply> {
+    if let Some(error) = response.get("error") {
+        return Err(AutoHedgeError::Llm(error.to_string()));
+    }
+    let text = response["content"]
+        .as_array()
+        .ok_or_else(|| AutoHedgeError::Llm(format!("unexpected response: {}", response)))?
+        .iter()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    Ok(StreamedReply {
+        text,
+        usage: TokenUsage {
+            prompt_tokens: tokens(&response["usage"]["input_tokens"]),
+            completion_tokens: tokens(&response["usage"]["output_tokens"]),
+        },
+    })
+}
+
+/// Fold one Messages API stream event into `reply`.
+pub fn anthropic_stream_event(event: &Value, reply: &mut StreamedReply) {
+    match event["type"].as_str() {
+        Some("message_start") => {
+            reply.usage.prompt_tokens = tokens(&event["message"]["usage"]["input_tokens"]);
+        }
+        Some("content_block_delta") => {
+            if let Some(text) = event["delta"]["text"].as_str() {
+                reply.text.push_str(text);
+            }
+        }
+        Some("message_delta") => {
+            reply.usage.completion_tokens = tokens(&event["usage"]["output_tokens"]);
+        }
+        _ => {}
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    async fn chat(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        user_input: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let s = &self.settings;
+        let body = anthropic_request(model, system_prompt, user_input, s.max_tokens, s.stream);
+        let request = s
+            .http
+            .post(format!("{}/v1/messages", s.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION);
+
+        let mut reply = StreamedReply::default();
+        let mut stream_error = None;
+        let response = s
+            .post(request, &body, |event| {
+                if event["type"] == "error" {
+                    stream_error = Some(event["error"].to_string());
+                }
+                anthropic_stream_event(event, &mut reply);
+            })
+            .await?;
+        if let Some(error) = stream_error {
+            return Err(AutoHedgeError::LlmTransient(error));
+        }
+        if let Some(response) = response {
+            reply = anthropic_reply(&response)?;
+        }
+        Ok((reply.text, reply.usage))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// llama.cpp native server
+// ---------------------------------------------------------------------------
+
+pub struct LlamaCppProvider {
+    settings: HttpSettings,
+}
+
+/// `POST /completion` body. The native endpoint takes a raw prompt, so the
+/// system and user prompts are laid out in a plain chat transcript.
+pub fn llamacpp_request(
+    system_prompt: &str,
+    user_input: &str,
+    max_tokens: u32,
+    stream: bool,
+) -> Value {
+    json!({
+        "prompt": format!(
+            "### System:\n{}\n\n### User:\n{}\n\n### Assistant:\n",
+            system_prompt, user_input
+        ),
+        "n_predict": max_tokens,
+        "stream": stream,
+        "cache_prompt": true,
+    })
+}
+
+/// Fold a `/completion` response (or one streamed chunk) into `reply`; the
+/// final chunk carries the token counts.
+pub fn llamacpp_event(event: &Value, reply: &mut StreamedReply) {
+    if let Some(text) = event["content"].as_str() {
+        reply.text.push_str(text);
+    }
+    if event.get("tokens_predicted").is_some() {
+        reply.usage = TokenUsage {
+            prompt_tokens: tokens(&event["tokens_evaluated"]),
+            completion_tokens: tokens(&event["tokens_predicted"]),
+        };
+    }
+}
+
+#[async_trait]
+impl LlmProvider for LlamaCppProvider {
+    fn name(&self) -> &'static str {
+        "llamacpp"
+    }
+
+    async fn chat(
+        &self,
+        _model: &str,
+        system_prompt: &str,
+        user_input: &str,
+    ) -> Result<(String, TokenUsage)> {
+        // The server runs the model it was started with
+        let s = &self.settings;
+        let body = llamacpp_request(system_prompt, user_input, s.max_tokens, s.stream);
+        let request = s.http.post(format!("{}/completion", s.base_url));
+
+        let mut reply = StreamedReply::default();
+        if let Some(response) = s
+            .post(request, &body, |event| llamacpp_event(event, &mut reply))
+            .await?
+        {
+            llamacpp_event(&response, &mut reply);
+        }
+        Ok((reply.text, reply.usage))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ollama native API
+// ---------------------------------------------------------------------------
+
+pub struct OllamaProvider {
+    settings: HttpSettings,
+}
+
+/// `POST /api/chat` body.
+pub fn ollama_request(
+    model: &str,
+    system_prompt: &str,
+    user_input: &str,
+    max_tokens: u32,
+    stream: bool,
+) -> Value {
+    json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": user_input},
+        ],
+        "stream": stream,
+        "options": {"num_predict": max_tokens},
+    })
+}
+
+/// Fold an `/api/chat` response (or one streamed line) into `reply`; the
+/// `done` line carries the token counts.
+pub fn ollama_event(event: &Value, reply: &mut StreamedReply) -> Result<()> {
+    if let Some(error) = event["error"].as_str() {
+        return Err(AutoHedgeError::Llm(error.to_string()));
+    }
+    if let Some(text) = event["message"]["content"].as_str() {
+        reply.text.push_str(text);
+    }
+    if event["done"].as_bool() == Some(true) {
+        reply.usage = TokenUsage {
+            prompt_tokens: tokens(&event["prompt_eval_count"]),
+            completion_tokens: tokens(&event["eval_count"]),
+        };
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    async fn chat(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        user_input: &str,
+    ) -> Result<(String, TokenUsage)> {
+        let s = &self.settings;
+        let body = ollama_request(model, system_prompt, user_input, s.max_tokens, s.stream);
+        let request = s.http.post(format!("{}/api/chat", s.base_url));
+
+        let mut reply = StreamedReply::default();
+        let mut result = Ok(());
+        let response = s
+            .post(request, &body, |event| {
+                if result.is_ok() {
+                    result = ollama_event(event, &mut reply);
+                }
+            })
+            .await?;
+        result?;
+        if let Some(response) = response {
+            ollama_event(&response, &mut reply)?;
+        }
+        Ok((reply.text, reply.usage))
+    }
+}
@@ -0,0 +1,164 @@
+//! Unit tests for LLM provider selection and request/response shaping.
+
+#[cfg(test)]
+mod provider_tests {
+    use crate::llm::provider::*;
+    use crate::llm::usage::TokenUsage;
+    use serde_json::json;
+
+    fn usage(prompt_tokens: u64, completion_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+
+    // ============= Provider Selection Tests =============
+
+    #[test]
+    fn test_parse_provider_names() {
+        assert_eq!(
+            LlmProviderKind::parse("OpenAI"),
+            Some(LlmProviderKind::OpenAi)
+        );
+        assert_eq!(
+            LlmProviderKind::parse("anthropic"),
+            Some(LlmProviderKind::Anthropic)
+        );
+        assert_eq!(
+            LlmProviderKind::parse("llama.cpp"),
+            Some(LlmProviderKind::LlamaCpp)
+        );
+        assert_eq!(
+            LlmProviderKind::parse(" ollama "),
+            Some(LlmProviderKind::Ollama)
+        );
+        assert_eq!(LlmProviderKind::parse("bard"), None);
+    }
+
+    #[test]
+    fn test_default_base_urls() {
+        assert_eq!(
+            LlmProviderKind::Anthropic.default_base_url(),
+            "https://api.anthropic.com"
+        );
+        assert_eq!(
+            LlmProviderKind::Ollama.default_base_url(),
+            "http://localhost:11434"
+        );
+    }
+
+    // ============= Stream Parsing Tests =============
+
+    #[test]
+    fn test_stream_line_reads_sse_and_ndjson() {
+        assert_eq!(stream_line("data: {\"a\":1}\n"), Some(json!({"a": 1})));
+        assert_eq!(stream_line("{\"done\":true}"), Some(json!({"done": true})));
+        assert_eq!(stream_line("data: [DONE]"), None);
+        assert_eq!(stream_line("event: message_stop"), None);
+        assert_eq!(stream_line("\n"), None);
+    }
+
+    #[test]
+    fn test_status_errors_classified() {
+        assert!(status_error(429, "slow down").is_retryable());
+        assert!(status_error(529, "overloaded").is_retryable());
+        assert!(!status_error(401, "bad key").is_retryable());
+    }
+
+    // ============= Anthropic Tests =============
+
+    #[test]
+    fn test_anthropic_request_shape() {
+        let body = anthropic_request("claude-x", "be terse", "hi", 512, false);
+        assert_eq!(body["system"], "be terse");
+        assert_eq!(body["max_tokens"], 512);
+        assert_eq!(body["messages"], json!([{"role": "user", "content": "hi"}]));
+        assert_eq!(body["stream"], false);
+    }
+
+    #[test]
+    fn test_anthropic_reply() {
+        let response = json!({
+            "content": [
+                {"type": "text", "text": "{\"decision\":"},
+                {"type": "text", "text": "\"no_trade\"}"}
+            ],
+            "usage": {"input_tokens": 120, "output_tokens": 9}
+        });
+        let reply = anthropic_reply(&response).unwrap();
+        assert_eq!(reply.text, "{\"decision\":\"no_trade\"}");
+        assert_eq!(reply.usage, usage(120, 9));
+
+        assert!(anthropic_reply(&json!({"error": {"type": "invalid_request_error"}})).is_err());
+    }
+
+    #[test]
+    fn test_anthropic_stream_events() {
+        let mut reply = StreamedReply::default();
+        for event in [
+            json!({"type": "message_start", "message": {"usage": {"input_tokens": 40}}}),
+            json!({"type": "content_block_delta", "delta": {"type": "text_delta", "text": "Hel"}}),
+            json!({"type": "ping"}),
+            json!({"type": "content_block_delta", "delta": {"type": "text_delta", "text": "lo"}}),
+            json!({"type": "message_delta", "usage": {"output_tokens": 2}}),
+        ] {
+            anthropic_stream_event(&event, &mut reply);
+        }
+        assert_eq!(reply.text, "Hello");
+        assert_eq!(reply.usage, usage(40, 2));
+    }
+
+    // ============= llama.cpp Tests =============
+
+    #[test]
+    fn test_llamacpp_request_lays_out_transcript() {
+        let body = llamacpp_request("sys", "question", 256, true);
+        let prompt = body["prompt"].as_str().unwrap();
+        assert!(prompt.contains("sys"));
+        assert!(prompt.find("sys") < prompt.find("question"));
+        assert!(prompt.ends_with("### Assistant:\n"));
+        assert_eq!(body["n_predict"], 256);
+        assert_eq!(body["stream"], true);
+    }
+
+    #[test]
+    fn test_llamacpp_stream_chunks() {
+        let mut reply = StreamedReply::default();
+        llamacpp_event(&json!({"content": "ok", "stop": false}), &mut reply);
+        llamacpp_event(
+            &json!({"content": "", "stop": true, "tokens_evaluated": 30, "tokens_predicted": 1}),
+            &mut reply,
+        );
+        assert_eq!(reply.text, "ok");
+        assert_eq!(reply.usage, usage(30, 1));
+    }
+
+    // ============= Ollama Tests =============
+
+    #[test]
+    fn test_ollama_request_shape() {
+        let body = ollama_request("llama3", "sys", "hi", 128, false);
+        assert_eq!(body["model"], "llama3");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "hi");
+        assert_eq!(body["options"]["num_predict"], 128);
+    }
+
+    #[test]
+    fn test_ollama_stream_lines() {
+        let mut reply = StreamedReply::default();
+        for line in [
+            json!({"message": {"role": "assistant", "content": "a"}, "done": false}),
+            json!({"message": {"role": "assistant", "content": "b"}, "done": false}),
+            json!({"message": {"role": "assistant", "content": ""}, "done": true,
+                   "prompt_eval_count": 25, "eval_count": 2}),
+        ] {
+            ollama_event(&line, &mut reply).unwrap();
+        }
+        assert_eq!(reply.text, "ab");
+        assert_eq!(reply.usage, usage(25, 2));
+
+        assert!(ollama_event(&json!({"error": "model not found"}), &mut reply).is_err());
+    }
+}
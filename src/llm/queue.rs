@@ -1,8 +1,17 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
 use tokio::sync::{mpsc, oneshot, Semaphore};
-use tracing::info;
+use tracing::{info, warn};
 
+use super::retry::{self, LlmBreaker};
+use super::usage::{LlmCall, TokenUsage, UsageSnapshot, UsageTracker};
 use super::LLMClient;
+use crate::config::LlmRetryConfig;
+use crate::error::{AutoHedgeError, Result};
 
 /// Priority level for LLM requests
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -13,11 +22,30 @@ pub enum Priority {
     Normal,
 }
 
+/// Smoothing factor for the latency moving average
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Call outcomes kept for `error_rate` (oldest dropped first)
+const OUTCOME_HISTORY: usize = 1000;
+
+/// Timestamped call outcomes (true = success), oldest first
+type Outcomes = Arc<Mutex<VecDeque<(Instant, bool)>>>;
+
+fn record_outcome(outcomes: &Outcomes, success: bool) {
+    let mut outcomes = outcomes.lock().unwrap();
+    if outcomes.len() >= OUTCOME_HISTORY {
+        outcomes.pop_front();
+    }
+    outcomes.push_back((Instant::now(), success));
+}
+
 /// A request to be queued for LLM processing
 struct QueuedRequest {
+    /// Agent the call is made for (usage is aggregated per agent)
+    agent: String,
     system_prompt: String,
     user_input: String,
-    response_tx: oneshot::Sender<Result<String, String>>,
+    response_tx: oneshot::Sender<Result<String>>,
 }
 
 /// LLM Queue that limits concurrent requests and prioritizes pipeline continuations
@@ -25,29 +53,67 @@ struct QueuedRequest {
 pub struct LLMQueue {
     high_tx: mpsc::Sender<QueuedRequest>,
     normal_tx: mpsc::Sender<QueuedRequest>,
+    /// Moving average of completed call latency (ms), None until the first call
+    latency_ms: Arc<Mutex<Option<f64>>>,
+    usage: UsageTracker,
+    /// Outcome of every call, breaker short-circuits counting as failures
+    outcomes: Outcomes,
+    /// Set while `services::llm_fallback` has decisions on the rule-based path
+    degraded: Arc<AtomicBool>,
 }
 
 impl LLMQueue {
-    /// Create a new LLM Queue with the given client and max concurrent requests
-    pub fn new(client: LLMClient, max_concurrent: usize, queue_size: usize) -> Self {
+    /// Create a new LLM Queue with the given client, max concurrent requests
+    /// and timeout/retry/breaker policy
+    pub fn new(
+        client: LLMClient,
+        max_concurrent: usize,
+        queue_size: usize,
+        retry: LlmRetryConfig,
+    ) -> Self {
         let (high_tx, high_rx) = mpsc::channel::<QueuedRequest>(queue_size);
         let (normal_tx, normal_rx) = mpsc::channel::<QueuedRequest>(queue_size);
 
         let semaphore = Arc::new(Semaphore::new(max_concurrent));
+        let latency_ms = Arc::new(Mutex::new(None));
+        let usage = UsageTracker::new(&client.model, client.pricing.clone());
+        let outcomes = Outcomes::default();
 
         // Spawn the queue processor
-        tokio::spawn(Self::process_queue(client, semaphore, high_rx, normal_rx));
+        tokio::spawn(Self::process_queue(
+            client,
+            semaphore,
+            high_rx,
+            normal_rx,
+            latency_ms.clone(),
+            usage.clone(),
+            outcomes.clone(),
+            retry,
+        ));
 
-        Self { high_tx, normal_tx }
+        Self {
+            high_tx,
+            normal_tx,
+            latency_ms,
+            usage,
+            outcomes,
+            degraded: Arc::new(AtomicBool::new(false)),
+        }
     }
 
     /// Process queued requests, prioritizing high-priority over normal-priority
+    #[allow(clippy::too_many_arguments)]
     async fn process_queue(
         client: LLMClient,
         semaphore: Arc<Semaphore>,
         mut high_rx: mpsc::Receiver<QueuedRequest>,
         mut normal_rx: mpsc::Receiver<QueuedRequest>,
+        latency_ms: Arc<Mutex<Option<f64>>>,
+        usage: UsageTracker,
+        outcomes: Outcomes,
+        retry: LlmRetryConfig,
     ) {
+        let breaker = Arc::new(Mutex::new(LlmBreaker::new(&retry)));
         info!(
             "📬 [QUEUE] LLM Queue processor started (max concurrent: {})",
             semaphore.available_permits()
@@ -73,12 +139,19 @@ impl LLMQueue {
                 }
             };
 
+            // Short-circuit while the breaker is open
+            if breaker.lock().unwrap().is_open(Instant::now()) {
+                record_outcome(&outcomes, false);
+                let _ = request.response_tx.send(retry::fallback(&retry));
+                continue;
+            }
+
             // Acquire semaphore permit
             let permit = semaphore.clone().acquire_owned().await;
             if permit.is_err() {
                 let _ = request
                     .response_tx
-                    .send(Err("Semaphore closed".to_string()));
+                    .send(Err(AutoHedgeError::Llm("Semaphore closed".to_string())));
                 continue;
             }
             let permit = permit.unwrap();
@@ -88,28 +161,151 @@ impl LLMQueue {
 
             // Spawn the actual LLM call
             let client_clone = client.clone();
+            let latency_ms = latency_ms.clone();
+            let usage = usage.clone();
+            let retry = retry.clone();
+            let breaker = breaker.clone();
+            let outcomes = outcomes.clone();
             tokio::spawn(async move {
-                let result = client_clone
-                    .chat(&request.system_prompt, &request.user_input)
-                    .await
-                    .map_err(|e| e.to_string());
+                let started = Instant::now();
+                let result = Self::call_with_retry(&client_clone, &request, &retry).await;
+                record_outcome(&outcomes, result.is_ok());
+                if breaker
+                    .lock()
+                    .unwrap()
+                    .record(result.is_ok(), Instant::now())
+                {
+                    warn!(
+                        "🔌 [QUEUE] LLM circuit breaker open for {}s after repeated failures; answering with fallback",
+                        retry.breaker_open_secs
+                    );
+                }
 
+                let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+                let mut avg = latency_ms.lock().unwrap();
+                *avg = Some(match *avg {
+                    Some(prev) => prev + LATENCY_EWMA_ALPHA * (elapsed - prev),
+                    None => elapsed,
+                });
+                drop(avg);
+
+                let result = result.map(|(content, tokens)| {
+                    usage.record(&request.agent, tokens, Utc::now());
+                    content
+                });
                 let _ = request.response_tx.send(result);
                 drop(permit); // Release permit when done
             });
         }
     }
 
-    /// Send a chat request with the specified priority
+    /// One LLM call with a per-attempt timeout and jittered-backoff retries
+    /// for transient errors
+    async fn call_with_retry(
+        client: &LLMClient,
+        request: &QueuedRequest,
+        retry: &LlmRetryConfig,
+    ) -> Result<(String, TokenUsage)> {
+        let timeout = Duration::from_secs(retry.timeout_secs.max(1));
+        let mut attempt = 0;
+        loop {
+            let result = tokio::time::timeout(
+                timeout,
+                client.chat(&request.system_prompt, &request.user_input),
+            )
+            .await
+            .unwrap_or_else(|_| {
+                Err(AutoHedgeError::LlmTransient(format!(
+                    "request timed out after {}s",
+                    timeout.as_secs()
+                )))
+            });
+            match result {
+                Err(e) if e.is_retryable() && attempt < retry.max_retries => {
+                    attempt += 1;
+                    let delay = retry::backoff_delay(retry, attempt, rand::random::<f64>());
+                    warn!(
+                        "📬 [QUEUE] {} call failed ({}), retry {}/{} in {}ms",
+                        request.agent,
+                        e,
+                        attempt,
+                        retry.max_retries,
+                        delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Requests waiting in the given priority queue (not yet dispatched)
+    pub fn depth(&self, priority: Priority) -> usize {
+        let tx = match priority {
+            Priority::High => &self.high_tx,
+            Priority::Normal => &self.normal_tx,
+        };
+        tx.max_capacity() - tx.capacity()
+    }
+
+    /// Moving average latency of completed LLM calls in milliseconds
+    pub fn latency_ms(&self) -> Option<f64> {
+        *self.latency_ms.lock().unwrap()
+    }
+
+    /// Failure rate of the calls made within `window`, with their count
+    /// (0 when none were made)
+    pub fn error_rate(&self, window: Duration) -> (f64, usize) {
+        let now = Instant::now();
+        let outcomes = self.outcomes.lock().unwrap();
+        let recent: Vec<bool> = outcomes
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= window)
+            .map(|(_, ok)| *ok)
+            .collect();
+        if recent.is_empty() {
+            return (0.0, 0);
+        }
+        let failures = recent.iter().filter(|ok| !**ok).count();
+        (failures as f64 / recent.len() as f64, recent.len())
+    }
+
+    /// Whether decisions are on the rule-based fallback (`llm_fallback`)
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::Relaxed)
+    }
+
+    pub fn set_degraded(&self, degraded: bool) {
+        self.degraded.store(degraded, Ordering::Relaxed);
+    }
+
+    /// Token usage and estimated cost of completed calls
+    pub fn usage(&self) -> UsageSnapshot {
+        self.usage.snapshot()
+    }
+
+    /// Send every completed call to `tx` (the `database` call log).
+    pub fn log_calls_to(&self, tx: mpsc::UnboundedSender<LlmCall>) {
+        self.usage.log_calls_to(tx);
+    }
+
+    /// Per-priority queue capacity
+    pub fn capacity(&self) -> usize {
+        self.normal_tx.max_capacity()
+    }
+
+    /// Send a chat request on behalf of `agent` with the specified priority
     pub async fn chat(
         &self,
+        agent: &str,
         system_prompt: &str,
         user_input: &str,
         priority: Priority,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<String> {
         let (response_tx, response_rx) = oneshot::channel();
 
         let request = QueuedRequest {
+            agent: agent.to_string(),
             system_prompt: system_prompt.to_string(),
             user_input: user_input.to_string(),
             response_tx,
@@ -122,32 +318,38 @@ impl LLMQueue {
         };
 
         if send_result.is_err() {
-            return Err("Failed to queue LLM request".into());
+            return Err(AutoHedgeError::Llm(
+                "Failed to queue LLM request".to_string(),
+            ));
         }
 
         // Wait for response
         match response_rx.await {
             Ok(Ok(response)) => Ok(response),
-            Ok(Err(e)) => Err(e.into()),
-            Err(_) => Err("LLM request was cancelled".into()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(AutoHedgeError::Llm("LLM request was cancelled".to_string())),
         }
     }
 
     /// Convenience method for normal priority chat
     pub async fn chat_normal(
         &self,
+        agent: &str,
         system_prompt: &str,
         user_input: &str,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        self.chat(system_prompt, user_input, Priority::Normal).await
+    ) -> Result<String> {
+        self.chat(agent, system_prompt, user_input, Priority::Normal)
+            .await
     }
 
     /// Convenience method for high priority chat (pipeline continuations)
     pub async fn chat_high(
         &self,
+        agent: &str,
         system_prompt: &str,
         user_input: &str,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        self.chat(system_prompt, user_input, Priority::High).await
+    ) -> Result<String> {
+        self.chat(agent, system_prompt, user_input, Priority::High)
+            .await
     }
 }
@@ -0,0 +1,95 @@
+//! Retry and circuit-breaker policy of the LLM queue.
+//!
+//! Each call gets `llm_retry.timeout_secs` per attempt and up to `max_retries`
+//! retries with jittered exponential backoff when the error is transient. The
+//! breaker watches the outcome of the last `breaker_window` calls; once the
+//! failure rate reaches `breaker_failure_rate` it opens for `breaker_open_secs`
+//! and calls are answered with `fallback_response` instead of reaching the API.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::LlmRetryConfig;
+use crate::error::{AutoHedgeError, Result};
+
+/// Backoff before retry number `attempt` (1-based): `backoff_base_ms` doubled
+/// per retry, capped at `backoff_max_ms`, then scaled into [50%, 100%) by
+/// `jitter` in [0, 1) so concurrent callers do not retry in lockstep.
+pub fn backoff_delay(config: &LlmRetryConfig, attempt: u32, jitter: f64) -> Duration {
+    let exp = config
+        .backoff_base_ms
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(20))
+        .min(config.backoff_max_ms);
+    let ms = exp as f64 * (0.5 + 0.5 * jitter.clamp(0.0, 1.0));
+    Duration::from_millis(ms as u64)
+}
+
+/// What a call gets while the breaker is open
+pub fn fallback(config: &LlmRetryConfig) -> Result<String> {
+    if config.fallback_response.is_empty() {
+        Err(AutoHedgeError::Llm(
+            "LLM circuit breaker open, call skipped".to_string(),
+        ))
+    } else {
+        Ok(config.fallback_response.clone())
+    }
+}
+
+/// Failure-rate circuit breaker over the most recent LLM call outcomes.
+pub struct LlmBreaker {
+    config: LlmRetryConfig,
+    /// Most recent outcomes (true = success), oldest first
+    outcomes: VecDeque<bool>,
+    open_until: Option<Instant>,
+}
+
+impl LlmBreaker {
+    pub fn new(config: &LlmRetryConfig) -> Self {
+        Self {
+            config: config.clone(),
+            outcomes: VecDeque::new(),
+            open_until: None,
+        }
+    }
+
+    /// Whether calls are short-circuited at `now`. Once the open period ends
+    /// the window starts empty, so the breaker needs fresh failures to reopen.
+    pub fn is_open(&mut self, now: Instant) -> bool {
+        match self.open_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                self.open_until = None;
+                self.outcomes.clear();
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Failure rate over the current window (0 when empty)
+    pub fn failure_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|ok| !**ok).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+
+    /// Record a call outcome; returns true if it opened the breaker.
+    pub fn record(&mut self, success: bool, now: Instant) -> bool {
+        if !self.config.breaker_enabled || self.open_until.is_some() {
+            return false;
+        }
+        self.outcomes.push_back(success);
+        while self.outcomes.len() > self.config.breaker_window.max(1) {
+            self.outcomes.pop_front();
+        }
+        if self.outcomes.len() < self.config.breaker_min_calls.max(1)
+            || self.failure_rate() < self.config.breaker_failure_rate
+        {
+            return false;
+        }
+        self.open_until = Some(now + Duration::from_secs(self.config.breaker_open_secs));
+        true
+    }
+}
@@ -0,0 +1,110 @@
+//! Unit tests for the LLM retry backoff and circuit breaker.
+
+#[cfg(test)]
+mod retry_tests {
+    use crate::config::LlmRetryConfig;
+    use crate::llm::retry::*;
+    use std::time::{Duration, Instant};
+
+    fn config() -> LlmRetryConfig {
+        LlmRetryConfig {
+            backoff_base_ms: 100,
+            backoff_max_ms: 1_000,
+            breaker_window: 4,
+            breaker_min_calls: 3,
+            breaker_failure_rate: 0.5,
+            breaker_open_secs: 30,
+            ..LlmRetryConfig::default()
+        }
+    }
+
+    // ============= Backoff Tests =============
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let cfg = config();
+        assert_eq!(backoff_delay(&cfg, 1, 1.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&cfg, 2, 1.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&cfg, 3, 1.0), Duration::from_millis(400));
+        assert_eq!(backoff_delay(&cfg, 5, 1.0), Duration::from_millis(1_000));
+        assert_eq!(backoff_delay(&cfg, 64, 1.0), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_within_half_to_full() {
+        let cfg = config();
+        assert_eq!(backoff_delay(&cfg, 2, 0.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&cfg, 2, 0.5), Duration::from_millis(150));
+    }
+
+    // ============= Fallback Tests =============
+
+    #[test]
+    fn test_fallback_default_is_no_trade() {
+        let response = fallback(&LlmRetryConfig::default()).unwrap();
+        assert!(response.contains("no_trade"));
+    }
+
+    #[test]
+    fn test_empty_fallback_fails_calls() {
+        let cfg = LlmRetryConfig {
+            fallback_response: String::new(),
+            ..config()
+        };
+        assert!(fallback(&cfg).is_err());
+    }
+
+    // ============= Breaker Tests =============
+
+    #[test]
+    fn test_breaker_needs_min_calls() {
+        let mut breaker = LlmBreaker::new(&config());
+        let now = Instant::now();
+        assert!(!breaker.record(false, now));
+        assert!(!breaker.record(false, now));
+        assert!(!breaker.is_open(now));
+        assert!(breaker.record(false, now));
+        assert!(breaker.is_open(now));
+    }
+
+    #[test]
+    fn test_breaker_stays_closed_below_failure_rate() {
+        let mut breaker = LlmBreaker::new(&config());
+        let now = Instant::now();
+        for ok in [true, true, false, true, true, true, false] {
+            assert!(!breaker.record(ok, now));
+        }
+        assert!(breaker.failure_rate() < 0.5);
+        assert!(!breaker.is_open(now));
+    }
+
+    #[test]
+    fn test_breaker_closes_after_open_period() {
+        let mut breaker = LlmBreaker::new(&config());
+        let now = Instant::now();
+        breaker.record(false, now);
+        breaker.record(false, now);
+        breaker.record(false, now);
+        assert!(breaker.is_open(now + Duration::from_secs(29)));
+        assert!(!breaker.is_open(now + Duration::from_secs(30)));
+        assert_eq!(breaker.failure_rate(), 0.0);
+
+        // A fresh streak is needed to reopen
+        let later = now + Duration::from_secs(31);
+        assert!(!breaker.record(false, later));
+        assert!(!breaker.is_open(later));
+    }
+
+    #[test]
+    fn test_disabled_breaker_never_opens() {
+        let mut breaker = LlmBreaker::new(&LlmRetryConfig {
+            breaker_enabled: false,
+            ..config()
+        });
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert!(!breaker.record(false, now));
+        }
+        assert!(!breaker.is_open(now));
+    }
+}
@@ -0,0 +1,153 @@
+//! Token usage and estimated cost of LLM calls.
+//!
+//! Every completed call records the prompt/completion tokens reported by the
+//! API, priced with `llm.pricing`, and is aggregated in total, per agent, per
+//! UTC hour and per UTC day so API spend can be weighed against realized PnL.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::config::LlmPricingConfig;
+
+/// Hourly buckets kept (two days)
+const HOURLY_BUCKETS: usize = 48;
+/// Daily buckets kept
+const DAILY_BUCKETS: usize = 30;
+
+/// Tokens billed for one completion, as reported by the API
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, usage: TokenUsage, cost_usd: f64) {
+        self.calls += 1;
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        self.cost_usd += cost_usd;
+    }
+}
+
+/// Aggregated usage; hourly keys are "YYYY-MM-DDTHH:00Z", daily "YYYY-MM-DD"
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct UsageSnapshot {
+    pub model: String,
+    pub total: UsageTotals,
+    pub per_agent: BTreeMap<String, UsageTotals>,
+    pub hourly: BTreeMap<String, UsageTotals>,
+    pub daily: BTreeMap<String, UsageTotals>,
+}
+
+/// One completed call, as handed to the call log (`database`)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LlmCall {
+    pub ts: String,
+    pub agent: String,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Shared usage accumulator, fed by the LLM queue after every call.
+#[derive(Clone)]
+pub struct UsageTracker {
+    pricing: LlmPricingConfig,
+    state: Arc<Mutex<UsageSnapshot>>,
+    /// Receives every recorded call once `log_calls_to` is set
+    call_log: Arc<Mutex<Option<UnboundedSender<LlmCall>>>>,
+}
+
+impl UsageTracker {
+    pub fn new(model: &str, pricing: LlmPricingConfig) -> Self {
+        Self {
+            pricing,
+            state: Arc::new(Mutex::new(UsageSnapshot {
+                model: model.to_string(),
+                ..Default::default()
+            })),
+            call_log: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Also send every call recorded from now on to `tx`.
+    pub fn log_calls_to(&self, tx: UnboundedSender<LlmCall>) {
+        *self.call_log.lock().unwrap() = Some(tx);
+    }
+
+    /// Estimated USD cost of `usage` at the configured per-million prices
+    pub fn cost_usd(&self, usage: TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.pricing.prompt_per_million
+            + usage.completion_tokens as f64 * self.pricing.completion_per_million)
+            / 1_000_000.0
+    }
+
+    /// Record one completed call made on behalf of `agent` at `at`
+    pub fn record(&self, agent: &str, usage: TokenUsage, at: DateTime<Utc>) {
+        let cost = self.cost_usd(usage);
+        let mut state = self.state.lock().unwrap();
+        if let Some(tx) = self.call_log.lock().unwrap().as_ref() {
+            let _ = tx.send(LlmCall {
+                ts: at.to_rfc3339(),
+                agent: agent.to_string(),
+                model: state.model.clone(),
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                cost_usd: cost,
+            });
+        }
+        state.total.add(usage, cost);
+        state
+            .per_agent
+            .entry(agent.to_string())
+            .or_default()
+            .add(usage, cost);
+        add_bucket(
+            &mut state.hourly,
+            at.format("%Y-%m-%dT%H:00Z").to_string(),
+            HOURLY_BUCKETS,
+            usage,
+            cost,
+        );
+        add_bucket(
+            &mut state.daily,
+            at.format("%Y-%m-%d").to_string(),
+            DAILY_BUCKETS,
+            usage,
+            cost,
+        );
+    }
+
+    pub fn snapshot(&self) -> UsageSnapshot {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+/// Add to the bucket at `key`, dropping the oldest buckets beyond `keep`
+/// (keys sort chronologically)
+fn add_bucket(
+    buckets: &mut BTreeMap<String, UsageTotals>,
+    key: String,
+    keep: usize,
+    usage: TokenUsage,
+    cost_usd: f64,
+) {
+    buckets.entry(key).or_default().add(usage, cost_usd);
+    while buckets.len() > keep {
+        buckets.pop_first();
+    }
+}
@@ -0,0 +1,97 @@
+//! Unit tests for LLM token usage and cost tracking.
+
+#[cfg(test)]
+mod usage_tests {
+    use crate::config::LlmPricingConfig;
+    use crate::llm::usage::*;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn tracker() -> UsageTracker {
+        UsageTracker::new(
+            "gpt-test",
+            LlmPricingConfig {
+                prompt_per_million: 2.0,
+                completion_per_million: 8.0,
+            },
+        )
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, hour, 15, 0).unwrap()
+    }
+
+    fn usage(prompt_tokens: u64, completion_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+
+    // ============= Cost Tests =============
+
+    #[test]
+    fn test_cost_uses_per_million_prices() {
+        let cost = tracker().cost_usd(usage(500_000, 100_000));
+        assert!((cost - 1.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_default_pricing_matches_config_defaults() {
+        let tracker = UsageTracker::new("gpt-test", LlmPricingConfig::default());
+        let cost = tracker.cost_usd(usage(1_000_000, 1_000_000));
+        assert!((cost - 40.0).abs() < 1e-9);
+    }
+
+    // ============= Aggregation Tests =============
+
+    #[test]
+    fn test_record_aggregates_total_and_per_agent() {
+        let tracker = tracker();
+        tracker.record("Director", usage(1000, 200), at(9));
+        tracker.record("Director", usage(1000, 100), at(9));
+        tracker.record("Quant", usage(3000, 500), at(9));
+
+        let snap = tracker.snapshot();
+        assert_eq!(snap.model, "gpt-test");
+        assert_eq!(snap.total.calls, 3);
+        assert_eq!(snap.total.prompt_tokens, 5000);
+        assert_eq!(snap.total.completion_tokens, 800);
+        assert_eq!(snap.per_agent["Director"].calls, 2);
+        assert_eq!(snap.per_agent["Director"].completion_tokens, 300);
+        assert_eq!(snap.per_agent["Quant"].prompt_tokens, 3000);
+        let per_agent_cost: f64 = snap.per_agent.values().map(|t| t.cost_usd).sum();
+        assert!((per_agent_cost - snap.total.cost_usd).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_record_buckets_by_hour_and_day() {
+        let tracker = tracker();
+        tracker.record("Director", usage(100, 10), at(9));
+        tracker.record("Director", usage(100, 10), at(10));
+        tracker.record("Director", usage(100, 10), at(10));
+
+        let snap = tracker.snapshot();
+        assert_eq!(snap.hourly.len(), 2);
+        assert_eq!(snap.hourly["2024-03-01T09:00Z"].calls, 1);
+        assert_eq!(snap.hourly["2024-03-01T10:00Z"].calls, 2);
+        assert_eq!(snap.daily.len(), 1);
+        assert_eq!(snap.daily["2024-03-01"].calls, 3);
+    }
+
+    #[test]
+    fn test_old_hourly_buckets_are_dropped() {
+        let tracker = tracker();
+        let start = at(0);
+        for h in 0..50 {
+            tracker.record("Director", usage(10, 1), start + Duration::hours(h));
+        }
+
+        let snap = tracker.snapshot();
+        assert_eq!(snap.hourly.len(), 48);
+        assert!(!snap.hourly.contains_key("2024-03-01T00:00Z"));
+        assert!(!snap.hourly.contains_key("2024-03-01T01:00Z"));
+        assert!(snap.hourly.contains_key("2024-03-03T01:00Z"));
+        assert_eq!(snap.total.calls, 50);
+        assert_eq!(snap.daily.len(), 3);
+    }
+}
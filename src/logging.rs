@@ -0,0 +1,43 @@
+//! Log output and per-trade correlation.
+//!
+//! `logging.format: json` writes one JSON object per log line (timestamp,
+//! level, target, message and fields) instead of the human-readable text, for
+//! shipping to Loki/Datadog. Every signal the strategy engine publishes gets a
+//! `correlation_id` that the order, execution report, tracked position and its
+//! exit carry along; the risk engine and both execution services handle each
+//! one inside a `trade` span with that id, so with JSON output one query on
+//! `span.correlation_id` returns a trade's whole lifecycle.
+
+use tracing::Span;
+
+use crate::config::LoggingConfig;
+
+/// Install the global subscriber for `config.format`.
+pub fn init(config: &LoggingConfig) {
+    let builder = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO);
+    let result = if config.json() {
+        builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .try_init()
+    } else {
+        builder.try_init()
+    };
+    result.expect("setting default subscriber failed");
+}
+
+/// Fresh id for a new trade.
+pub fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Span the handling of one trade's signal or order runs in.
+pub fn trade_span(symbol: &str, correlation_id: Option<&str>) -> Span {
+    tracing::info_span!(
+        "trade",
+        symbol = %symbol,
+        correlation_id = correlation_id.unwrap_or("-")
+    )
+}
@@ -0,0 +1,24 @@
+//! Unit tests for log setup and trade correlation ids.
+
+#[cfg(test)]
+mod logging_tests {
+    use crate::config::LoggingConfig;
+    use crate::logging::*;
+
+    #[test]
+    fn test_logging_config_format() {
+        assert!(!LoggingConfig::default().json());
+        let config: LoggingConfig = serde_yaml::from_str("format: JSON").unwrap();
+        assert!(config.json());
+        let config: LoggingConfig = serde_yaml::from_str("format: text").unwrap();
+        assert!(!config.json());
+    }
+
+    #[test]
+    fn test_new_correlation_id_is_unique() {
+        let (a, b) = (new_correlation_id(), new_correlation_id());
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 32);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
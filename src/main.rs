@@ -1,61 +1,117 @@
 mod agents;
 mod api;
+#[cfg(test)]
+mod api_tests;
 mod bus;
+#[cfg(feature = "chaos")]
+mod chaos;
 mod config;
-mod data;
+pub mod data;
+pub mod error;
 mod events;
 mod exchange;
+#[cfg(feature = "grpc")]
+mod grpc;
 mod llm;
+mod logging;
+mod money;
 pub mod services;
 
 use api::{run_server, AppState};
 use config::AppConfig;
 use llm::{LLMClient, LLMQueue};
+use services::config_reload::LiveConfig;
 use services::keep_alive::KeepAliveService;
+use services::storage::StorageWriter;
 use std::sync::{Arc, Mutex};
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Load Configuration (it selects the log format)
+    let config = AppConfig::load();
+
     // Setup Logging
-    let subscriber = tracing_subscriber::FmtSubscriber::builder()
-        .with_max_level(tracing::Level::INFO)
-        .finish();
-    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    logging::init(&config.logging);
 
     info!("Starting AutoHedge Rust...");
-
-    // Load Configuration
-    let config = AppConfig::load();
     info!("Loaded Configuration: {:?}", config);
 
     // Initialize Clients
     info!("Initializing AI Clients...");
-    let api_key = config.llm.api_key.clone().unwrap_or_default();
-    let base_url = config.llm.base_url.clone();
-    if let Some(url) = &base_url {
-        info!("Using Custom OpenAI Base URL: {}", url);
+    info!("Using LLM Provider: {}", config.llm.provider);
+    if let Some(url) = &config.llm.base_url {
+        info!("Using Custom LLM Base URL: {}", url);
     }
+    info!("Using LLM Model: {}", config.llm.model);
 
-    let model = config.llm.model.clone();
-    info!("Using LLM Model: {}", model);
-
-    let llm_client = LLMClient::new(api_key, base_url, model);
+    let llm_client = LLMClient::from_config(&config.llm);
 
     // Create LLM Queue with max concurrent requests from config
     info!(
         "📬 Initializing LLM Queue (max concurrent: {}, size: {})...",
         config.llm_max_concurrent, config.llm_queue_size
     );
-    let llm_queue = LLMQueue::new(llm_client, config.llm_max_concurrent, config.llm_queue_size);
+    let llm_queue = LLMQueue::new(
+        llm_client,
+        config.llm_max_concurrent,
+        config.llm_queue_size,
+        config.llm_retry.clone(),
+    );
+
+    // Database storage of orders, fills, trades, equity and LLM calls
+    #[cfg(feature = "database")]
+    let database = if config.database.enabled {
+        match services::storage::Database::connect(&config.database).await {
+            Ok(db) => {
+                info!(
+                    "🗄️ [DB] Connected to {}",
+                    config
+                        .database
+                        .url
+                        .split('@')
+                        .next_back()
+                        .unwrap_or_default()
+                );
+                Some(db)
+            }
+            Err(e) => {
+                tracing::error!("❌ [DB] Database unavailable, using flat files: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    #[cfg(feature = "database")]
+    let storage = database.as_ref().map(|db| {
+        let (writer, rx) = StorageWriter::channel();
+        db.start(rx);
+        llm_queue.log_calls_to(writer.llm_calls());
+        writer
+    });
+    #[cfg(not(feature = "database"))]
+    let storage: Option<StorageWriter> = None;
+    #[cfg(not(feature = "database"))]
+    if config.database.enabled {
+        tracing::warn!(
+            "⚠️ [DB] database.enabled is set but this build lacks the `database` feature"
+        );
+    }
 
     // Create App State
     let app_state = Arc::new(AppState {
         trading_handle: Mutex::new(None),
         websocket_handle: Mutex::new(None),
         exchange: Mutex::new(None),
+        shutdown: Mutex::new(None),
+        health: Mutex::new(None),
+        session: Mutex::new(None),
         llm: llm_queue,
-        config,
+        config: LiveConfig::new(config),
+        storage,
+        #[cfg(feature = "database")]
+        database,
     });
 
     // Start Keep-Alive Service (prevents free hosting from scaling down)
@@ -81,6 +137,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("ℹ️ KEEP_ALIVE_URL not set - keep-alive service disabled (set it for production)");
     }
 
+    // Start gRPC Server (same control surface as the HTTP API)
+    let grpc_config = app_state.config.snapshot().grpc;
+    if grpc_config.enabled {
+        #[cfg(feature = "grpc")]
+        tokio::spawn(grpc::serve(app_state.clone(), grpc_config.port));
+        #[cfg(not(feature = "grpc"))]
+        tracing::warn!("⚠️ [GRPC] grpc.enabled is set but this build lacks the `grpc` feature");
+    }
+
     // Start API Server
     info!("Initializing API Server...");
     run_server(app_state).await;
@@ -0,0 +1,34 @@
+//! Decimal money and quantity helpers.
+//!
+//! Prices, quantities and notionals that get booked (orders, fills, positions,
+//! PnL) are `rust_decimal::Decimal`, so repeated fills and partial exits add up
+//! exactly. Market data and statistics (indicators, bps, percentiles) stay
+//! `f64`; these helpers convert at that boundary. With the `serde-float`
+//! feature decimals serialize as JSON numbers and deserialize from numbers or
+//! strings, as exchanges send them.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+/// `value` as a decimal without binary noise (`0.1` -> `0.1`); NaN and
+/// infinities map to zero.
+pub fn dec(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+/// `value` as an `f64` for market-data and statistics math.
+pub fn float(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+/// Decimal from a JSON string ("0.00012") or number, as venues send either.
+pub fn from_json(value: &serde_json::Value) -> Option<Decimal> {
+    let text = match value {
+        serde_json::Value::String(s) => s.trim().to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    text.parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
@@ -0,0 +1,62 @@
+//! Unit tests for the Decimal money helpers.
+
+#[cfg(test)]
+mod money_tests {
+    use crate::events::ControlCommand;
+    use crate::money::{dec, float, from_json};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    // ============= Conversion Tests =============
+
+    #[test]
+    fn test_dec_drops_binary_noise() {
+        assert_eq!(dec(0.1).to_string(), "0.1");
+        assert_eq!(dec(0.1) + dec(0.2), dec(0.3));
+        assert_eq!(dec(f64::NAN), Decimal::ZERO);
+        assert_eq!(dec(f64::INFINITY), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_repeated_fills_add_up_exactly() {
+        let total = (0..10).fold(Decimal::ZERO, |acc, _| acc + dec(0.1));
+        assert_eq!(total, Decimal::ONE);
+        assert_eq!(float(total), 1.0);
+    }
+
+    #[test]
+    fn test_from_json_strings_and_numbers() {
+        assert_eq!(from_json(&json!("0.00012000")), Some(dec(0.00012)));
+        assert_eq!(from_json(&json!(" 42.5 ")), Some(dec(42.5)));
+        assert_eq!(from_json(&json!(60000.5)), Some(dec(60000.5)));
+        assert_eq!(from_json(&json!("1e-8")), Some(dec(0.00000001)));
+        assert_eq!(from_json(&json!("n/a")), None);
+        assert_eq!(from_json(&json!(null)), None);
+    }
+
+    // ============= Serde Tests =============
+
+    #[test]
+    fn test_amounts_accept_strings_and_numbers() {
+        let command: ControlCommand = serde_json::from_value(json!({
+            "cmd": "adjust_exits",
+            "symbol": "BTC/USD",
+            "take_profit": "61000.10",
+            "stop_loss": 59000.5,
+        }))
+        .unwrap();
+        let ControlCommand::AdjustExits {
+            take_profit,
+            stop_loss,
+            ..
+        } = &command
+        else {
+            panic!("expected adjust_exits");
+        };
+        assert_eq!(*take_profit, Some(dec(61000.1)));
+        assert_eq!(*stop_loss, Some(dec(59000.5)));
+
+        let out = serde_json::to_value(&command).unwrap();
+        assert_eq!(out["stop_loss"], json!(59000.5));
+    }
+}
@@ -0,0 +1,354 @@
+//! Adaptive HFT thresholds.
+//!
+//! `ThresholdController` follows the outcomes of each symbol's HFT and hybrid
+//! entries and nudges its `min_edge_bps` / `max_spread_bps` by `step_pct`:
+//! - a loss while the recent win rate is below `target_win_rate` tightens
+//!   (higher edge requirement, narrower spread cap)
+//! - a win at or above the target moves both back toward `hft.*`
+//! - `unfilled_streak` entry signals in a row that were not filled within
+//!   `fill_timeout_secs` relax (lower edge requirement, wider spread cap)
+//!
+//! Values stay within the `adaptive_hft` bounds. `AdaptiveThresholdService`
+//! stores them in `MarketStore`, where the HFT evaluator picks them up, and
+//! publishes each change as `MarketEvent::HftThresholds`.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::info;
+
+use crate::bus::EventBus;
+use crate::config::{AdaptiveHftConfig, AppConfig, HftConfig};
+use crate::data::store::{HftThresholds, MarketStore};
+use crate::events::{AnalysisSignal, Event, MarketEvent};
+
+/// Strategy modes whose entries are gated by the HFT thresholds
+const HFT_MODES: [&str; 2] = ["hft", "hybrid"];
+
+/// How often unfilled signals are checked for expiry
+const EXPIRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// A threshold change and the statistics behind it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adjustment {
+    pub symbol: String,
+    pub thresholds: HftThresholds,
+    pub fill_rate: Option<f64>,
+    pub win_rate: Option<f64>,
+    pub reason: String,
+}
+
+#[derive(Default)]
+struct SymbolStats {
+    /// Entry signals awaiting a fill: correlation id, signal time
+    pending: VecDeque<(String, DateTime<Utc>)>,
+    /// Recent signal outcomes (true = filled)
+    fills: VecDeque<bool>,
+    /// Recent closed trades (true = win)
+    trades: VecDeque<bool>,
+    unfilled_streak: usize,
+    thresholds: Option<HftThresholds>,
+}
+
+fn push_bounded(ring: &mut VecDeque<bool>, value: bool, size: usize) {
+    ring.push_back(value);
+    while ring.len() > size.max(1) {
+        ring.pop_front();
+    }
+}
+
+fn rate(ring: &VecDeque<bool>) -> Option<f64> {
+    (!ring.is_empty()).then(|| ring.iter().filter(|&&v| v).count() as f64 / ring.len() as f64)
+}
+
+/// Per-symbol threshold feedback, owned by the service loop.
+pub struct ThresholdController {
+    config: AdaptiveHftConfig,
+    base: HftThresholds,
+    symbols: HashMap<String, SymbolStats>,
+    /// Correlation id -> symbol of filled entries, until their trade closes
+    open: HashMap<String, String>,
+}
+
+impl ThresholdController {
+    pub fn new(config: &AdaptiveHftConfig, hft: &HftConfig) -> Self {
+        Self {
+            config: config.clone(),
+            base: HftThresholds {
+                min_edge_bps: hft.min_edge_bps,
+                max_spread_bps: hft.max_spread_bps,
+            },
+            symbols: HashMap::new(),
+            open: HashMap::new(),
+        }
+    }
+
+    /// New bounds and base thresholds after a config reload; history is kept.
+    pub fn set_config(&mut self, config: &AdaptiveHftConfig, hft: &HftConfig) {
+        let symbols = std::mem::take(&mut self.symbols);
+        let open = std::mem::take(&mut self.open);
+        *self = Self {
+            symbols,
+            open,
+            ..Self::new(config, hft)
+        };
+    }
+
+    /// Current thresholds of `symbol`.
+    pub fn thresholds(&self, symbol: &str) -> HftThresholds {
+        self.symbols
+            .get(symbol)
+            .and_then(|s| s.thresholds)
+            .unwrap_or(self.base)
+    }
+
+    /// Track an HFT or hybrid entry signal until it fills or expires.
+    pub fn on_signal(&mut self, signal: &AnalysisSignal, now: DateTime<Utc>) {
+        let hft_entry = signal.signal.eq_ignore_ascii_case("buy")
+            && signal
+                .origin
+                .as_ref()
+                .is_some_and(|o| HFT_MODES.contains(&o.mode.as_str()));
+        let Some(id) = signal.correlation_id.clone().filter(|_| hft_entry) else {
+            return;
+        };
+        self.symbols
+            .entry(signal.symbol.clone())
+            .or_default()
+            .pending
+            .push_back((id, now));
+    }
+
+    /// A buy fill for `correlation_id`.
+    pub fn on_fill(&mut self, correlation_id: &str) {
+        let window = self.config.window;
+        for (symbol, stats) in self.symbols.iter_mut() {
+            let Some(i) = stats
+                .pending
+                .iter()
+                .position(|(id, _)| id == correlation_id)
+            else {
+                continue;
+            };
+            stats.pending.remove(i);
+            stats.unfilled_streak = 0;
+            push_bounded(&mut stats.fills, true, window);
+            self.open.insert(correlation_id.to_string(), symbol.clone());
+            return;
+        }
+    }
+
+    /// Count signals older than `fill_timeout_secs` as unfilled; relaxes
+    /// symbols that reached `unfilled_streak`.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<Adjustment> {
+        let cutoff = now - Duration::seconds(self.config.fill_timeout_secs as i64);
+        let mut relaxed = Vec::new();
+        for (symbol, stats) in self.symbols.iter_mut() {
+            while stats.pending.front().is_some_and(|(_, at)| *at <= cutoff) {
+                stats.pending.pop_front();
+                stats.unfilled_streak += 1;
+                push_bounded(&mut stats.fills, false, self.config.window);
+            }
+            if self.config.unfilled_streak > 0
+                && stats.unfilled_streak >= self.config.unfilled_streak
+            {
+                relaxed.push((symbol.clone(), stats.unfilled_streak));
+                stats.unfilled_streak = 0;
+            }
+        }
+        relaxed
+            .into_iter()
+            .filter_map(|(symbol, streak)| {
+                let step = 1.0 + self.config.step_pct / 100.0;
+                let current = self.thresholds(&symbol);
+                self.adjust(
+                    &symbol,
+                    HftThresholds {
+                        min_edge_bps: current.min_edge_bps / step,
+                        max_spread_bps: current.max_spread_bps * step,
+                    },
+                    format!("{} signals in a row not filled", streak),
+                )
+            })
+            .collect()
+    }
+
+    /// A closed trade of a tracked entry.
+    pub fn on_trade_closed(&mut self, correlation_id: &str, win: bool) -> Option<Adjustment> {
+        let symbol = self.open.remove(correlation_id)?;
+        let stats = self.symbols.entry(symbol.clone()).or_default();
+        push_bounded(&mut stats.trades, win, self.config.window);
+        if stats.trades.len() < self.config.min_trades.max(1) {
+            return None;
+        }
+        let win_rate = rate(&stats.trades)?;
+        let step = 1.0 + self.config.step_pct / 100.0;
+        let current = self.thresholds(&symbol);
+        if !win && win_rate < self.config.target_win_rate {
+            self.adjust(
+                &symbol,
+                HftThresholds {
+                    min_edge_bps: current.min_edge_bps * step,
+                    max_spread_bps: current.max_spread_bps / step,
+                },
+                format!("loss at win rate {:.0}%", win_rate * 100.0),
+            )
+        } else if win && win_rate >= self.config.target_win_rate {
+            let toward = |value: f64, base: f64| {
+                if value > base {
+                    (value / step).max(base)
+                } else {
+                    (value * step).min(base)
+                }
+            };
+            self.adjust(
+                &symbol,
+                HftThresholds {
+                    min_edge_bps: toward(current.min_edge_bps, self.base.min_edge_bps),
+                    max_spread_bps: toward(current.max_spread_bps, self.base.max_spread_bps),
+                },
+                format!("win at win rate {:.0}%", win_rate * 100.0),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Clamp and apply `target`; the adjustment if anything changed.
+    fn adjust(
+        &mut self,
+        symbol: &str,
+        target: HftThresholds,
+        reason: String,
+    ) -> Option<Adjustment> {
+        let config = &self.config;
+        let clamped = HftThresholds {
+            min_edge_bps: target
+                .min_edge_bps
+                .min(config.min_edge_ceiling_bps)
+                .max(config.min_edge_floor_bps),
+            max_spread_bps: target
+                .max_spread_bps
+                .min(config.max_spread_ceiling_bps)
+                .max(config.max_spread_floor_bps),
+        };
+        let current = self.thresholds(symbol);
+        if (clamped.min_edge_bps - current.min_edge_bps).abs() < 1e-9
+            && (clamped.max_spread_bps - current.max_spread_bps).abs() < 1e-9
+        {
+            return None;
+        }
+        let stats = self.symbols.entry(symbol.to_string()).or_default();
+        stats.thresholds = Some(clamped);
+        Some(Adjustment {
+            symbol: symbol.to_string(),
+            thresholds: clamped,
+            fill_rate: rate(&stats.fills),
+            win_rate: rate(&stats.trades),
+            reason,
+        })
+    }
+}
+
+/// Applies and publishes threshold adjustments for the session.
+pub struct AdaptiveThresholdService {
+    event_bus: EventBus,
+    store: MarketStore,
+    config: AppConfig,
+}
+
+impl AdaptiveThresholdService {
+    pub fn new(event_bus: EventBus, store: MarketStore, config: AppConfig) -> Self {
+        Self {
+            event_bus,
+            store,
+            config,
+        }
+    }
+
+    pub fn start(self) {
+        let mut rx = self.event_bus.subscribe("adaptive_hft");
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🎚️ Adaptive HFT thresholds started (min_edge {:.1}-{:.1} bps, max_spread {:.1}-{:.1} bps)",
+                self.config.adaptive_hft.min_edge_floor_bps,
+                self.config.adaptive_hft.min_edge_ceiling_bps,
+                self.config.adaptive_hft.max_spread_floor_bps,
+                self.config.adaptive_hft.max_spread_ceiling_bps
+            );
+            let mut controller =
+                ThresholdController::new(&self.config.adaptive_hft, &self.config.hft);
+            let mut expiry = tokio::time::interval(EXPIRY_INTERVAL);
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = expiry.tick() => {
+                        for adjustment in controller.expire(Utc::now()) {
+                            self.publish(adjustment);
+                        }
+                        continue;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                let adjustment = match event {
+                    Event::Signal(signal) => {
+                        controller.on_signal(&signal, Utc::now());
+                        None
+                    }
+                    Event::Execution(report)
+                        if report.side.eq_ignore_ascii_case("buy")
+                            && report.status.to_lowercase().contains("fill") =>
+                    {
+                        if let Some(id) = &report.correlation_id {
+                            controller.on_fill(id);
+                        }
+                        None
+                    }
+                    Event::TradeClosed(trade) => trade.correlation_id.as_deref().and_then(|id| {
+                        controller.on_trade_closed(id, trade.pnl > rust_decimal::Decimal::ZERO)
+                    }),
+                    Event::Config(update) => {
+                        controller.set_config(&update.config.adaptive_hft, &update.config.hft);
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(adjustment) = adjustment {
+                    self.publish(adjustment);
+                }
+            }
+        });
+    }
+
+    fn publish(&self, adjustment: Adjustment) {
+        let Adjustment {
+            symbol,
+            thresholds,
+            fill_rate,
+            win_rate,
+            reason,
+        } = adjustment;
+        info!(
+            "🎚️ [ADAPTIVE] {}: min_edge {:.2} bps, max_spread {:.2} bps ({})",
+            symbol, thresholds.min_edge_bps, thresholds.max_spread_bps, reason
+        );
+        self.store.set_hft_thresholds(&symbol, thresholds);
+        self.event_bus
+            .publish(Event::Market(MarketEvent::HftThresholds {
+                symbol,
+                min_edge_bps: thresholds.min_edge_bps,
+                max_spread_bps: thresholds.max_spread_bps,
+                fill_rate,
+                win_rate,
+                reason,
+                timestamp: Utc::now().to_rfc3339(),
+            }))
+            .ok();
+    }
+}
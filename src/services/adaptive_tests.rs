@@ -0,0 +1,168 @@
+//! Unit tests for adaptive HFT thresholds - tightening after losses, relaxing
+//! after unfilled signals and the configured bounds.
+
+#[cfg(test)]
+mod adaptive_tests {
+    use crate::config::{AdaptiveHftConfig, HftConfig};
+    use crate::data::store::HftThresholds;
+    use crate::events::{AnalysisSignal, TradeOrigin};
+    use crate::services::adaptive::*;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn hft() -> HftConfig {
+        HftConfig {
+            evaluate_every_quotes: 5,
+            min_edge_bps: 10.0,
+            take_profit_bps: 50.0,
+            stop_loss_bps: 25.0,
+            max_spread_bps: 20.0,
+            min_volume_ratio: 0.5,
+            use_vwap_filter: false,
+            momentum_lookback: 20,
+            signal_ttl_ms: 0,
+        }
+    }
+
+    fn config() -> AdaptiveHftConfig {
+        AdaptiveHftConfig {
+            enabled: true,
+            window: 10,
+            min_trades: 3,
+            unfilled_streak: 3,
+            fill_timeout_secs: 30,
+            ..AdaptiveHftConfig::default()
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn signal(id: &str, mode: &str) -> AnalysisSignal {
+        AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: "buy".to_string(),
+            confidence: 1.0,
+            thesis: "HFT momentum".to_string(),
+            market_context: String::new(),
+            strategy_id: None,
+            correlation_id: Some(id.to_string()),
+            origin: Some(TradeOrigin::new(mode)),
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
+        }
+    }
+
+    /// Enter and close trade `id` with the given outcome.
+    fn trade(controller: &mut ThresholdController, id: &str, win: bool) -> Option<Adjustment> {
+        controller.on_signal(&signal(id, "hft"), now());
+        controller.on_fill(id);
+        controller.on_trade_closed(id, win)
+    }
+
+    // ============= Win Rate Tests =============
+
+    #[test]
+    fn test_losses_tighten_after_min_trades() {
+        let mut controller = ThresholdController::new(&config(), &hft());
+        assert_eq!(trade(&mut controller, "1", false), None);
+        assert_eq!(trade(&mut controller, "2", false), None);
+
+        let adjustment = trade(&mut controller, "3", false).unwrap();
+        assert_eq!(adjustment.symbol, "BTC/USD");
+        assert!((adjustment.thresholds.min_edge_bps - 11.0).abs() < 1e-9);
+        assert!(adjustment.thresholds.max_spread_bps < 20.0);
+        assert_eq!(adjustment.win_rate, Some(0.0));
+        assert_eq!(adjustment.fill_rate, Some(1.0));
+    }
+
+    #[test]
+    fn test_wins_move_back_toward_configured_thresholds() {
+        let mut controller = ThresholdController::new(&config(), &hft());
+        for id in ["1", "2", "3"] {
+            trade(&mut controller, id, false);
+        }
+        let tightened = controller.thresholds("BTC/USD");
+        assert!(tightened.min_edge_bps > 10.0);
+
+        // Win rate still below target: a win changes nothing
+        assert_eq!(trade(&mut controller, "4", true), None);
+        for id in ["5", "6", "7"] {
+            trade(&mut controller, id, true);
+        }
+        assert_eq!(
+            controller.thresholds("BTC/USD"),
+            HftThresholds {
+                min_edge_bps: 10.0,
+                max_spread_bps: 20.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_thresholds_stay_within_bounds() {
+        let config = AdaptiveHftConfig {
+            min_edge_ceiling_bps: 12.0,
+            max_spread_floor_bps: 18.0,
+            ..config()
+        };
+        let mut controller = ThresholdController::new(&config, &hft());
+        for i in 0..20 {
+            trade(&mut controller, &i.to_string(), false);
+        }
+        assert_eq!(
+            controller.thresholds("BTC/USD"),
+            HftThresholds {
+                min_edge_bps: 12.0,
+                max_spread_bps: 18.0,
+            }
+        );
+        // Already at the bounds: no further adjustments
+        assert_eq!(trade(&mut controller, "x", false), None);
+    }
+
+    // ============= Fill Rate Tests =============
+
+    #[test]
+    fn test_unfilled_streak_relaxes() {
+        let mut controller = ThresholdController::new(&config(), &hft());
+        for id in ["1", "2", "3"] {
+            controller.on_signal(&signal(id, "hybrid"), now());
+        }
+        // Not expired yet
+        assert!(controller.expire(now() + Duration::seconds(10)).is_empty());
+
+        let adjustments = controller.expire(now() + Duration::seconds(30));
+        assert_eq!(adjustments.len(), 1);
+        let relaxed = adjustments[0].thresholds;
+        assert!(relaxed.min_edge_bps < 10.0);
+        assert!((relaxed.max_spread_bps - 22.0).abs() < 1e-9);
+        assert_eq!(adjustments[0].fill_rate, Some(0.0));
+        // The streak starts over
+        assert!(controller.expire(now() + Duration::seconds(60)).is_empty());
+    }
+
+    #[test]
+    fn test_fills_reset_the_streak_and_other_modes_are_ignored() {
+        let mut controller = ThresholdController::new(&config(), &hft());
+        let later = now() + Duration::seconds(30);
+        controller.on_signal(&signal("1", "hft"), now());
+        controller.on_signal(&signal("2", "hft"), now());
+        assert!(controller.expire(later).is_empty());
+        controller.on_signal(&signal("3", "hft"), later);
+        controller.on_fill("3");
+        controller.on_signal(&signal("4", "hft"), later);
+        controller.on_signal(&signal("5", "llm"), later);
+        controller.on_signal(&signal("6", "llm"), later);
+        // One unfilled since the fill (LLM signals are not tracked)
+        assert!(controller.expire(later + Duration::seconds(30)).is_empty());
+        // LLM entries never count toward the win rate either
+        assert_eq!(controller.on_trade_closed("5", false), None);
+    }
+}
@@ -0,0 +1,98 @@
+//! Queue depth-aware admission control for Director (gate) LLM calls.
+//!
+//! When the normal-priority LLM queue backs up, Director refreshes are only
+//! admitted for the symbols with the most recent opportunity, so gate latency
+//! stays bounded for the symbols most likely to trade.
+
+use crate::config::{AppConfig, ChatterModule, LlmAdmissionConfig};
+use crate::data::store::{MarketStore, Quote};
+use crate::llm::{LLMQueue, Priority};
+use tracing::info;
+
+/// Recent opportunity for a symbol: absolute mid move across the quote window,
+/// measured in average spreads (a move the spread would eat scores low).
+pub fn opportunity_score(history: &[Quote]) -> f64 {
+    let mids: Vec<f64> = history
+        .iter()
+        .filter(|q| q.bid_price > 0.0 && q.ask_price >= q.bid_price)
+        .map(|q| (q.bid_price + q.ask_price) / 2.0)
+        .collect();
+    let (Some(first), Some(last)) = (mids.first(), mids.last()) else {
+        return 0.0;
+    };
+
+    let move_bps = ((last - first) / first).abs() * 10_000.0;
+    let avg_spread_bps = history
+        .iter()
+        .filter(|q| q.bid_price > 0.0 && q.ask_price >= q.bid_price)
+        .map(|q| (q.ask_price - q.bid_price) / ((q.bid_price + q.ask_price) / 2.0) * 10_000.0)
+        .sum::<f64>()
+        / mids.len() as f64;
+
+    move_bps / avg_spread_bps.max(1.0)
+}
+
+/// Number of top-ranked symbols admitted at this queue fill ratio (None = everyone).
+pub fn admitted_slots(config: &LlmAdmissionConfig, fill_ratio: f64) -> Option<usize> {
+    if !config.enabled || fill_ratio < config.soft_depth_ratio {
+        None
+    } else if fill_ratio < config.hard_depth_ratio {
+        Some(config.top_symbols.max(1))
+    } else {
+        Some(1)
+    }
+}
+
+/// Zero-based rank of `symbol` among `scores` (highest score first; ties by name).
+pub fn rank_of(symbol: &str, scores: &[(String, f64)]) -> usize {
+    let mine = scores
+        .iter()
+        .find(|(s, _)| s == symbol)
+        .map(|(_, v)| *v)
+        .unwrap_or(0.0);
+    scores
+        .iter()
+        .filter(|(s, v)| *v > mine || (*v == mine && s.as_str() < symbol))
+        .count()
+}
+
+/// Whether a Director refresh for `symbol` may be queued right now.
+pub fn admit_director(
+    symbol: &str,
+    store: &MarketStore,
+    llm: &LLMQueue,
+    config: &AppConfig,
+) -> bool {
+    let depth = llm.depth(Priority::Normal);
+    let fill_ratio = depth as f64 / llm.capacity().max(1) as f64;
+    let Some(slots) = admitted_slots(&config.llm_admission, fill_ratio) else {
+        return true;
+    };
+
+    let mut symbols = config.symbols.clone();
+    if !symbols.iter().any(|s| s == symbol) {
+        symbols.push(symbol.to_string());
+    }
+    let scores: Vec<(String, f64)> = symbols
+        .into_iter()
+        .map(|s| {
+            let score = opportunity_score(&store.get_quote_history(&s));
+            (s, score)
+        })
+        .collect();
+
+    let rank = rank_of(symbol, &scores);
+    let admitted = rank < slots;
+    if !admitted && !config.chatter_for(ChatterModule::Strategy).is_low() {
+        info!(
+            "🚦 [ADMISSION] Shed Director refresh for {} (queue {}/{} full, rank {}/{}, top {} admitted)",
+            symbol,
+            depth,
+            llm.capacity(),
+            rank + 1,
+            scores.len(),
+            slots
+        );
+    }
+    admitted
+}
@@ -0,0 +1,82 @@
+//! Unit tests for LLM admission control - opportunity ranking and load shedding.
+
+#[cfg(test)]
+mod admission_tests {
+    use crate::config::LlmAdmissionConfig;
+    use crate::data::store::Quote;
+    use crate::services::admission::*;
+
+    fn quote(bid: f64, ask: f64) -> Quote {
+        Quote {
+            symbol: "BTC/USD".to_string(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn scores(entries: &[(&str, f64)]) -> Vec<(String, f64)> {
+        entries.iter().map(|(s, v)| (s.to_string(), *v)).collect()
+    }
+
+    // ============= Opportunity Score Tests =============
+
+    #[test]
+    fn test_opportunity_score_empty() {
+        assert_eq!(opportunity_score(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_opportunity_score_flat_market() {
+        let history = vec![quote(100.0, 100.1); 10];
+        assert_eq!(opportunity_score(&history), 0.0);
+    }
+
+    #[test]
+    fn test_opportunity_score_move_vs_spread() {
+        // ~100 bps move with ~10 bps spread => ~10 spreads
+        let history = vec![quote(99.95, 100.05), quote(100.95, 101.05)];
+        let score = opportunity_score(&history);
+        assert!((score - 10.0).abs() < 0.5, "score={}", score);
+
+        // Same move with a wider spread scores lower
+        let wide = vec![quote(99.75, 100.25), quote(100.75, 101.25)];
+        assert!(opportunity_score(&wide) < score);
+    }
+
+    // ============= Admission Tests =============
+
+    #[test]
+    fn test_admitted_slots_thresholds() {
+        let cfg = LlmAdmissionConfig::default();
+        assert_eq!(admitted_slots(&cfg, 0.1), None);
+        assert_eq!(admitted_slots(&cfg, 0.5), Some(3));
+        assert_eq!(admitted_slots(&cfg, 0.95), Some(1));
+    }
+
+    #[test]
+    fn test_admitted_slots_disabled() {
+        let cfg = LlmAdmissionConfig {
+            enabled: false,
+            ..LlmAdmissionConfig::default()
+        };
+        assert_eq!(admitted_slots(&cfg, 1.0), None);
+    }
+
+    #[test]
+    fn test_rank_of() {
+        let s = scores(&[("BTC/USD", 5.0), ("ETH/USD", 9.0), ("SOL/USD", 1.0)]);
+        assert_eq!(rank_of("ETH/USD", &s), 0);
+        assert_eq!(rank_of("BTC/USD", &s), 1);
+        assert_eq!(rank_of("SOL/USD", &s), 2);
+    }
+
+    #[test]
+    fn test_rank_of_ties_broken_by_name() {
+        let s = scores(&[("ETH/USD", 2.0), ("BTC/USD", 2.0)]);
+        assert_eq!(rank_of("BTC/USD", &s), 0);
+        assert_eq!(rank_of("ETH/USD", &s), 1);
+    }
+}
@@ -0,0 +1,302 @@
+//! HTTP API authentication and roles.
+//!
+//! With `api_auth.enabled`, every endpoint except `/health` needs a credential:
+//! a static API key from `api_auth.keys` or an HS256 JWT signed with
+//! `api_auth.jwt_secret`, sent as `Authorization: Bearer <credential>` or
+//! `X-API-Key: <key>` (`?token=` on the `/ws` upgrade, which browsers cannot
+//! send headers with). `read` credentials may call GET endpoints and stream
+//! `/ws`; `/start`, `/stop`, `/cancel_all`, commands sent over `/ws` and every
+//! other mutating call need `admin`. Keys are
+//! compared in constant time, and each key (or JWT subject) gets
+//! `rate_limit_per_minute` requests per minute.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::{digest, hmac};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::config::ApiAuthConfig;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Read,
+    Admin,
+}
+
+impl Role {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "read" | "read_only" | "readonly" => Some(Role::Read),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Read => "read",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Role needed for a request with HTTP `method`: reads are open to every
+    /// role, anything that changes state is admin-only.
+    pub fn required_for(method: &str) -> Self {
+        match method.to_uppercase().as_str() {
+            "GET" | "HEAD" | "OPTIONS" => Role::Read,
+            _ => Role::Admin,
+        }
+    }
+}
+
+/// Authenticated caller of a request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Principal {
+    pub name: String,
+    pub role: Role,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("missing API credential")]
+    Missing,
+    #[error("invalid API credential")]
+    Invalid,
+    #[error("{name} ({role}) may not call this endpoint")]
+    Forbidden { name: String, role: &'static str },
+    #[error("rate limit exceeded for {name}")]
+    RateLimited { name: String, retry_after_secs: u64 },
+}
+
+impl AuthError {
+    pub fn status(&self) -> u16 {
+        match self {
+            AuthError::Missing | AuthError::Invalid => 401,
+            AuthError::Forbidden { .. } => 403,
+            AuthError::RateLimited { .. } => 429,
+        }
+    }
+}
+
+/// Equality whose timing depends on neither the contents nor the lengths of
+/// the inputs (both are hashed first).
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let (a, b) = (
+        digest::digest(&digest::SHA256, a),
+        digest::digest(&digest::SHA256, b),
+    );
+    a.as_ref()
+        .iter()
+        .zip(b.as_ref())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Credential of a request: the `Authorization: Bearer` value, else `X-API-Key`.
+pub fn credential<'a>(authorization: Option<&'a str>, api_key: Option<&'a str>) -> Option<&'a str> {
+    authorization
+        .and_then(|h| {
+            h.strip_prefix("Bearer ")
+                .or_else(|| h.strip_prefix("bearer "))
+        })
+        .or(api_key)
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+}
+
+/// `token` parameter of a query string (the `/ws` credential).
+pub fn query_token(query: &str) -> Option<String> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(k, _)| k == "token")
+        .map(|(_, v)| v.into_owned())
+        .filter(|v| !v.is_empty())
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    sub: String,
+    role: String,
+    exp: i64,
+    #[serde(default)]
+    nbf: Option<i64>,
+}
+
+/// Principal of an HS256 JWT signed with `secret`, valid at `now` (epoch
+/// seconds). Tokens without `exp` are rejected.
+pub fn verify_jwt(token: &str, secret: &[u8], now: i64) -> Option<Principal> {
+    let mut parts = token.split('.');
+    let (header, claims, signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let header: JwtHeader = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).ok()?).ok()?;
+    if header.alg != "HS256" {
+        return None;
+    }
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    let signed = &token[..token.rfind('.')?];
+    hmac::verify(
+        &key,
+        signed.as_bytes(),
+        &URL_SAFE_NO_PAD.decode(signature).ok()?,
+    )
+    .ok()?;
+
+    let claims: JwtClaims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).ok()?).ok()?;
+    if now >= claims.exp || claims.nbf.is_some_and(|nbf| now < nbf) {
+        return None;
+    }
+    Some(Principal {
+        name: claims.sub,
+        role: Role::parse(&claims.role)?,
+    })
+}
+
+/// Fixed one-minute request windows per principal.
+struct RateLimiter {
+    per_minute: u32,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// Count a request by `name` at `now`; Err with the seconds until its
+    /// window resets once the limit is used up.
+    fn check(&self, name: &str, now: Instant) -> Result<(), u64> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let mut windows = self.windows.lock().unwrap();
+        let (start, count) = windows.entry(name.to_string()).or_insert((now, 0));
+        if now.duration_since(*start) >= RATE_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.per_minute {
+            let reset = RATE_WINDOW.saturating_sub(now.duration_since(*start));
+            return Err(reset.as_secs().max(1));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+struct ApiKey {
+    name: String,
+    key: Vec<u8>,
+    role: Role,
+}
+
+/// Checks API requests against `api_auth`.
+pub struct ApiAuth {
+    enabled: bool,
+    keys: Vec<ApiKey>,
+    jwt_secret: Option<Vec<u8>>,
+    limiter: RateLimiter,
+}
+
+impl ApiAuth {
+    pub fn new(config: &ApiAuthConfig) -> Self {
+        let keys: Vec<ApiKey> = config
+            .keys
+            .iter()
+            .filter_map(|k| {
+                let Some(role) = Role::parse(&k.role) else {
+                    warn!(
+                        "⚠️ [API-AUTH] Unknown role '{}' for key '{}' - key disabled",
+                        k.role, k.name
+                    );
+                    return None;
+                };
+                if k.key.is_empty() {
+                    warn!("⚠️ [API-AUTH] Empty key '{}' - key disabled", k.name);
+                    return None;
+                }
+                Some(ApiKey {
+                    name: k.name.clone(),
+                    key: k.key.as_bytes().to_vec(),
+                    role,
+                })
+            })
+            .collect();
+        let jwt_secret = config
+            .jwt_secret
+            .as_ref()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.as_bytes().to_vec());
+        if config.enabled && keys.is_empty() && jwt_secret.is_none() {
+            warn!(
+                "⚠️ [API-AUTH] Enabled without keys or jwt_secret - every request will be rejected"
+            );
+        }
+        Self {
+            enabled: config.enabled,
+            keys,
+            jwt_secret,
+            limiter: RateLimiter {
+                per_minute: config.rate_limit_per_minute,
+                windows: Mutex::new(HashMap::new()),
+            },
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Who `credential` belongs to. Every key is compared, so the timing does
+    /// not reveal which one matched.
+    pub fn authenticate(&self, credential: Option<&str>) -> Result<Principal, AuthError> {
+        let credential = credential.ok_or(AuthError::Missing)?;
+        let mut matched = None;
+        for key in &self.keys {
+            if constant_time_eq(credential.as_bytes(), &key.key) && matched.is_none() {
+                matched = Some(Principal {
+                    name: key.name.clone(),
+                    role: key.role,
+                });
+            }
+        }
+        if matched.is_none() && credential.matches('.').count() == 2 {
+            if let Some(secret) = &self.jwt_secret {
+                matched = verify_jwt(credential, secret, chrono::Utc::now().timestamp());
+            }
+        }
+        matched.ok_or(AuthError::Invalid)
+    }
+
+    /// Authenticate, check the role needed for `method` and count the request
+    /// against the caller's rate limit.
+    pub fn authorize(
+        &self,
+        credential: Option<&str>,
+        method: &str,
+        now: Instant,
+    ) -> Result<Principal, AuthError> {
+        let principal = self.authenticate(credential)?;
+        if principal.role < Role::required_for(method) {
+            return Err(AuthError::Forbidden {
+                name: principal.name,
+                role: principal.role.as_str(),
+            });
+        }
+        self.limiter
+            .check(&principal.name, now)
+            .map_err(|retry_after_secs| AuthError::RateLimited {
+                name: principal.name.clone(),
+                retry_after_secs,
+            })?;
+        Ok(principal)
+    }
+}
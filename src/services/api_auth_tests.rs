@@ -0,0 +1,185 @@
+//! Unit tests for API authentication (keys, JWTs, roles, rate limits).
+
+#[cfg(test)]
+mod api_auth_tests {
+    use crate::config::{ApiAuthConfig, ApiKeyConfig};
+    use crate::services::api_auth::*;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use ring::hmac;
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+
+    const SECRET: &[u8] = b"jwt-test-secret";
+
+    fn key(name: &str, key: &str, role: &str) -> ApiKeyConfig {
+        ApiKeyConfig {
+            name: name.to_string(),
+            key: key.to_string(),
+            role: role.to_string(),
+        }
+    }
+
+    fn auth(rate_limit_per_minute: u32) -> ApiAuth {
+        ApiAuth::new(&ApiAuthConfig {
+            enabled: true,
+            keys: vec![
+                key("dashboard", "read-key-123", "read"),
+                key("ops", "admin-key-456", "admin"),
+                key("typo", "other-key", "superuser"),
+            ],
+            jwt_secret: Some(String::from_utf8(SECRET.to_vec()).unwrap()),
+            rate_limit_per_minute,
+        })
+    }
+
+    fn jwt(claims: serde_json::Value, secret: &[u8]) -> String {
+        let encode = |v: serde_json::Value| URL_SAFE_NO_PAD.encode(v.to_string());
+        let signed = format!(
+            "{}.{}",
+            encode(json!({"alg": "HS256", "typ": "JWT"})),
+            encode(claims)
+        );
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+        let signature = URL_SAFE_NO_PAD.encode(hmac::sign(&key, signed.as_bytes()));
+        format!("{}.{}", signed, signature)
+    }
+
+    // ============= Credential Tests =============
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"admin-key-456", b"admin-key-456"));
+        assert!(!constant_time_eq(b"admin-key-456", b"admin-key-457"));
+        assert!(!constant_time_eq(b"admin-key-456", b"admin"));
+        assert!(!constant_time_eq(b"", b"x"));
+    }
+
+    #[test]
+    fn test_credential_extraction() {
+        assert_eq!(credential(Some("Bearer abc"), Some("xyz")), Some("abc"));
+        assert_eq!(credential(Some("Basic abc"), Some("xyz")), Some("xyz"));
+        assert_eq!(credential(None, Some(" xyz ")), Some("xyz"));
+        assert_eq!(credential(Some("Bearer "), None), None);
+        assert_eq!(query_token("a=1&token=ab%2Bc"), Some("ab+c".to_string()));
+        assert_eq!(query_token("a=1"), None);
+    }
+
+    #[test]
+    fn test_required_role() {
+        assert_eq!(Role::required_for("GET"), Role::Read);
+        assert_eq!(Role::required_for("head"), Role::Read);
+        assert_eq!(Role::required_for("POST"), Role::Admin);
+        assert_eq!(Role::required_for("PUT"), Role::Admin);
+        assert_eq!(Role::parse("Read_Only"), Some(Role::Read));
+        assert_eq!(Role::parse("root"), None);
+    }
+
+    // ============= JWT Tests =============
+
+    #[test]
+    fn test_verify_jwt() {
+        let token = jwt(json!({"sub": "ci", "role": "admin", "exp": 2_000}), SECRET);
+        assert_eq!(
+            verify_jwt(&token, SECRET, 1_000),
+            Some(Principal {
+                name: "ci".to_string(),
+                role: Role::Admin
+            })
+        );
+        // Expired, wrong secret, not yet valid, no expiry
+        assert_eq!(verify_jwt(&token, SECRET, 2_000), None);
+        assert_eq!(verify_jwt(&token, b"other", 1_000), None);
+        let early = jwt(
+            json!({"sub": "ci", "role": "read", "exp": 2_000, "nbf": 1_500}),
+            SECRET,
+        );
+        assert_eq!(verify_jwt(&early, SECRET, 1_000), None);
+        let forever = jwt(json!({"sub": "ci", "role": "read"}), SECRET);
+        assert_eq!(verify_jwt(&forever, SECRET, 1_000), None);
+    }
+
+    #[test]
+    fn test_verify_jwt_rejects_tampered_claims() {
+        let token = jwt(json!({"sub": "ci", "role": "read", "exp": 2_000}), SECRET);
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged =
+            URL_SAFE_NO_PAD.encode(json!({"sub": "ci", "role": "admin", "exp": 2_000}).to_string());
+        let tampered = format!("{}.{}.{}", parts[0], forged, parts[2]);
+        assert_eq!(verify_jwt(&tampered, SECRET, 1_000), None);
+
+        let none_alg = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(json!({"alg": "none"}).to_string()),
+            parts[1]
+        );
+        assert_eq!(verify_jwt(&none_alg, SECRET, 1_000), None);
+    }
+
+    // ============= Authorization Tests =============
+
+    #[test]
+    fn test_authenticate_keys() {
+        let auth = auth(0);
+        assert_eq!(auth.authenticate(None), Err(AuthError::Missing));
+        assert_eq!(auth.authenticate(Some("nope")), Err(AuthError::Invalid));
+        assert_eq!(
+            auth.authenticate(Some("read-key-123")).unwrap().name,
+            "dashboard"
+        );
+        assert_eq!(
+            auth.authenticate(Some("admin-key-456")).unwrap().role,
+            Role::Admin
+        );
+        // Keys with an unknown role are disabled
+        assert_eq!(
+            auth.authenticate(Some("other-key")),
+            Err(AuthError::Invalid)
+        );
+
+        let exp = chrono::Utc::now().timestamp() + 60;
+        let token = jwt(json!({"sub": "ci", "role": "read", "exp": exp}), SECRET);
+        assert_eq!(auth.authenticate(Some(&token)).unwrap().name, "ci");
+    }
+
+    #[test]
+    fn test_authorize_roles() {
+        let auth = auth(0);
+        let now = Instant::now();
+        assert!(auth.authorize(Some("read-key-123"), "GET", now).is_ok());
+        let err = auth
+            .authorize(Some("read-key-123"), "POST", now)
+            .unwrap_err();
+        assert_eq!(err.status(), 403);
+        assert_eq!(
+            err.to_string(),
+            "dashboard (read) may not call this endpoint"
+        );
+        assert!(auth.authorize(Some("admin-key-456"), "POST", now).is_ok());
+        assert_eq!(auth.authorize(None, "GET", now).unwrap_err().status(), 401);
+    }
+
+    #[test]
+    fn test_authorize_rate_limits_per_key() {
+        let auth = auth(2);
+        let start = Instant::now();
+        assert!(auth.authorize(Some("read-key-123"), "GET", start).is_ok());
+        assert!(auth.authorize(Some("read-key-123"), "GET", start).is_ok());
+        let err = auth
+            .authorize(Some("read-key-123"), "GET", start + Duration::from_secs(15))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AuthError::RateLimited {
+                name: "dashboard".to_string(),
+                retry_after_secs: 45
+            }
+        );
+        assert_eq!(err.status(), 429);
+
+        // Other keys have their own budget; the window resets after a minute
+        assert!(auth.authorize(Some("admin-key-456"), "POST", start).is_ok());
+        assert!(auth
+            .authorize(Some("read-key-123"), "GET", start + Duration::from_secs(60))
+            .is_ok());
+    }
+}
@@ -0,0 +1,610 @@
+//! Cross-exchange arbitrage (`arbitrage.*`).
+//!
+//! Every venue in `arbitrage.venues` streams quotes of the arbitrage symbols:
+//! the session's own exchange through the session bus, the others over a
+//! market data stream of their own, with a private bus and store so their
+//! quotes never reach the strategies. `ArbitrageScanner` keeps the latest
+//! bid/ask per venue and symbol. When one venue's bid clears another's ask by
+//! more than both taker fees plus `min_profit_bps`, an `Event::Arbitrage`
+//! signal is published (at most once per `cooldown_ms` per symbol; quotes
+//! older than `max_quote_age_ms` are left out).
+//!
+//! With `execute` the signal is traded. `ArbitrageExecutor` sends both legs
+//! at once as IOC limits at the quoted prices, buying on the cheap venue and
+//! selling on the rich one. Whatever one leg filled beyond the other is
+//! unwound at market on its venue, so the trade ends flat across venues. If
+//! a leg's final state cannot be read back nothing is unwound and new
+//! entries are halted until an operator resumes trading.
+//! `ArbitrageBook` tracks the combined position: per symbol, the base asset
+//! moved on each venue (netting to zero unless an unwind failed) and the
+//! spread captured. Spot venues cannot sell short, so the rich venue needs
+//! the base asset on hand. Arbitrage legs bypass the position tracker, so
+//! the position monitor never puts exits on them.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, ArbitrageConfig};
+use crate::data::store::MarketStore;
+use crate::events::{ArbitrageEvent, ArbitrageTradeEvent, Event, MarketEvent, SystemEvent};
+use crate::exchange::factory::build_exchange_with_symbols;
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::symbol_registry::{SymbolRegistry, Venue};
+use crate::exchange::traits::{MarketDataStream, TradingApi};
+use crate::exchange::types::{PlaceOrderRequest, Side, TimeInForce};
+use crate::exchange::ws::GenericWsStream;
+use crate::money::dec;
+use crate::services::execution_utils::{
+    fill_legs, halt_unhedged, limit_order, market_order, market_tif, normalize_order,
+};
+
+/// Quotes from the venue streams waiting for the scanner
+const FEED_QUEUE: usize = 1024;
+
+/// Latest top of book of one symbol on one venue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VenueQuote {
+    pub bid: f64,
+    pub ask: f64,
+    pub received: Instant,
+}
+
+/// Symbols compared: `arbitrage.symbols`, else the session's `symbols`.
+pub fn arbitrage_symbols(config: &AppConfig) -> Vec<String> {
+    if config.arbitrage.symbols.is_empty() {
+        config.symbols.clone()
+    } else {
+        config.arbitrage.symbols.clone()
+    }
+}
+
+/// Most profitable buy-here / sell-there pair among `quotes` (venue, quote)
+/// that clears both fees by `min_profit_bps`.
+pub fn best_opportunity(
+    symbol: &str,
+    quotes: &[(&str, VenueQuote)],
+    config: &ArbitrageConfig,
+) -> Option<ArbitrageEvent> {
+    let mut best: Option<ArbitrageEvent> = None;
+    for (buy_venue, buy) in quotes {
+        for (sell_venue, sell) in quotes {
+            if buy_venue == sell_venue || buy.ask <= 0.0 || sell.bid <= 0.0 {
+                continue;
+            }
+            let spread_bps = (sell.bid - buy.ask) / buy.ask * 10_000.0;
+            let fee_bps = config.fee_for(buy_venue) + config.fee_for(sell_venue);
+            let net_bps = spread_bps - fee_bps;
+            if net_bps < config.min_profit_bps
+                || best.as_ref().is_some_and(|b| b.net_bps >= net_bps)
+            {
+                continue;
+            }
+            best = Some(ArbitrageEvent {
+                symbol: symbol.to_string(),
+                buy_venue: buy_venue.to_string(),
+                sell_venue: sell_venue.to_string(),
+                buy_price: buy.ask,
+                sell_price: sell.bid,
+                spread_bps,
+                fee_bps,
+                net_bps,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+    }
+    best
+}
+
+/// Latest quotes per venue and the signal cooldown of each symbol.
+pub struct ArbitrageScanner {
+    config: ArbitrageConfig,
+    /// Symbol -> venue -> latest quote
+    quotes: HashMap<String, HashMap<String, VenueQuote>>,
+    last_signal: HashMap<String, Instant>,
+}
+
+impl ArbitrageScanner {
+    pub fn new(config: &ArbitrageConfig, symbols: &[String]) -> Self {
+        Self {
+            config: config.clone(),
+            quotes: symbols
+                .iter()
+                .map(|s| (s.clone(), HashMap::new()))
+                .collect(),
+            last_signal: HashMap::new(),
+        }
+    }
+
+    /// Record a quote from `venue`. Returns a signal when the fresh quotes
+    /// of `symbol` now show an opportunity outside its cooldown.
+    pub fn on_quote(
+        &mut self,
+        venue: &str,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+        now: Instant,
+    ) -> Option<ArbitrageEvent> {
+        let venues = self.quotes.get_mut(symbol)?;
+        venues.insert(
+            venue.to_string(),
+            VenueQuote {
+                bid,
+                ask,
+                received: now,
+            },
+        );
+        let cooldown = Duration::from_millis(self.config.cooldown_ms);
+        if self
+            .last_signal
+            .get(symbol)
+            .is_some_and(|at| now.duration_since(*at) < cooldown)
+        {
+            return None;
+        }
+        let max_age = Duration::from_millis(self.config.max_quote_age_ms);
+        let fresh: Vec<(&str, VenueQuote)> = venues
+            .iter()
+            .filter(|(_, q)| now.duration_since(q.received) <= max_age)
+            .map(|(v, q)| (v.as_str(), *q))
+            .collect();
+        let signal = best_opportunity(symbol, &fresh, &self.config)?;
+        self.last_signal.insert(symbol.to_string(), now);
+        Some(signal)
+    }
+}
+
+/// Combined arbitrage position of one symbol across venues.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ArbitragePosition {
+    pub symbol: String,
+    /// Base asset bought (+) or sold (-) on each venue
+    pub inventory: BTreeMap<String, Decimal>,
+    pub trades: u32,
+    /// Spread captured after fees
+    pub pnl: Decimal,
+}
+
+impl ArbitragePosition {
+    /// Inventory left unhedged across venues (zero while every leg matched).
+    pub fn net(&self) -> Decimal {
+        self.inventory.values().sum()
+    }
+}
+
+/// Arbitrage trades per symbol; clones share the book.
+#[derive(Clone, Default)]
+pub struct ArbitrageBook {
+    positions: Arc<Mutex<BTreeMap<String, ArbitragePosition>>>,
+}
+
+impl ArbitrageBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, trade: &ArbitrageTradeEvent) {
+        let mut positions = self.positions.lock().unwrap();
+        let position = positions
+            .entry(trade.symbol.clone())
+            .or_insert_with(|| ArbitragePosition {
+                symbol: trade.symbol.clone(),
+                ..ArbitragePosition::default()
+            });
+        let bought = trade.qty + trade.unhedged.max(Decimal::ZERO);
+        let sold = trade.qty + (-trade.unhedged).max(Decimal::ZERO);
+        *position
+            .inventory
+            .entry(trade.buy_venue.clone())
+            .or_default() += bought;
+        *position
+            .inventory
+            .entry(trade.sell_venue.clone())
+            .or_default() -= sold;
+        position.trades += 1;
+        position.pnl += trade.pnl;
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<ArbitragePosition> {
+        self.positions.lock().unwrap().get(symbol).cloned()
+    }
+
+    pub fn snapshot(&self) -> Vec<ArbitragePosition> {
+        self.positions.lock().unwrap().values().cloned().collect()
+    }
+}
+
+/// A venue arbitrage legs can be sent to.
+#[derive(Clone)]
+pub struct ArbitrageVenue {
+    pub name: String,
+    pub exchange: Arc<dyn TradingApi>,
+    pub symbol_meta: SymbolMetaRegistry,
+}
+
+/// Legs into both venues of a signal and unwinds any mismatch.
+#[derive(Clone)]
+pub struct ArbitrageExecutor {
+    venues: HashMap<String, ArbitrageVenue>,
+    config: ArbitrageConfig,
+    market_tif: TimeInForce,
+    /// Halts new entries when a leg's fill cannot be established
+    event_bus: Option<EventBus>,
+}
+
+impl ArbitrageExecutor {
+    pub fn new(venues: Vec<ArbitrageVenue>, config: &AppConfig) -> Self {
+        Self {
+            venues: venues.into_iter().map(|v| (v.name.clone(), v)).collect(),
+            config: config.arbitrage.clone(),
+            market_tif: market_tif(config),
+            event_bus: None,
+        }
+    }
+
+    /// Publish `SystemEvent::Halt` when a leg is left in an unknown state.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Trade `signal`. Returns the trade once anything filled, None when
+    /// the legs could not be sent or neither filled.
+    pub async fn execute(&self, signal: &ArbitrageEvent) -> Option<ArbitrageTradeEvent> {
+        let buy = self.venues.get(&signal.buy_venue)?;
+        let sell = self.venues.get(&signal.sell_venue)?;
+        let symbol = &signal.symbol;
+
+        // Both legs the same size, rounded to both venues' steps
+        let qty = dec(self.config.leg_notional / signal.buy_price);
+        let buy_req = Self::leg(buy, symbol, Side::Buy, qty, signal.buy_price).await?;
+        let sell_req = Self::leg(
+            sell,
+            symbol,
+            Side::Sell,
+            buy_req.qty.unwrap_or(qty),
+            signal.sell_price,
+        )
+        .await?;
+        let sell_qty = sell_req.qty.unwrap_or(qty);
+        let buy_req = if sell_qty < buy_req.qty.unwrap_or(qty) {
+            Self::leg(buy, symbol, Side::Buy, sell_qty, signal.buy_price).await?
+        } else {
+            buy_req
+        };
+        let buy_price = buy_req.limit_price.unwrap_or(dec(signal.buy_price));
+        let sell_price = sell_req.limit_price.unwrap_or(dec(signal.sell_price));
+
+        let fills = fill_legs(
+            [
+                (buy.exchange.as_ref(), buy_req),
+                (sell.exchange.as_ref(), sell_req),
+            ],
+            Duration::from_millis(self.config.leg_timeout_ms),
+            Duration::from_millis(self.config.poll_ms.max(1)),
+        )
+        .await;
+        // Unwinding against a leg that may have filled could double the exposure
+        let [Some(bought), Some(sold)] = fills else {
+            halt_unhedged(
+                self.event_bus.as_ref(),
+                format!(
+                    "{} arbitrage legs on {} / {} unreconciled (bought {:?}, sold {:?}) - check both venues for an unhedged position",
+                    symbol, buy.name, sell.name, fills[0], fills[1]
+                ),
+            );
+            return None;
+        };
+        let qty = bought.min(sold);
+        let mut unhedged = bought - sold;
+        if !unhedged.is_zero() {
+            // Flatten the excess on the venue that filled more
+            let (venue, side) = if unhedged > Decimal::ZERO {
+                (buy, Side::Sell)
+            } else {
+                (sell, Side::Buy)
+            };
+            warn!(
+                "⚠️ [ARB] {} legs filled {} bought / {} sold - unwinding {} on {}",
+                symbol,
+                bought,
+                sold,
+                unhedged.abs(),
+                venue.name
+            );
+            let unwind = market_order(symbol, side, unhedged.abs(), self.market_tif);
+            match venue.exchange.submit_order(unwind).await {
+                Ok(_) => unhedged = Decimal::ZERO,
+                Err(e) => error!(
+                    "❌ [ARB] Failed to unwind {:?} {} {} on {} ({}) - flatten it manually",
+                    side,
+                    unhedged.abs(),
+                    symbol,
+                    venue.name,
+                    e
+                ),
+            }
+        }
+        if qty.is_zero() && unhedged.is_zero() {
+            info!(
+                "💱 [ARB] {} {} / {} legs left nothing held",
+                symbol, buy.name, sell.name
+            );
+            return None;
+        }
+
+        let fees = qty
+            * (buy_price * dec(self.config.fee_for(&buy.name))
+                + sell_price * dec(self.config.fee_for(&sell.name)))
+            / dec(10_000.0);
+        Some(ArbitrageTradeEvent {
+            symbol: symbol.clone(),
+            buy_venue: buy.name.clone(),
+            sell_venue: sell.name.clone(),
+            qty,
+            buy_price,
+            sell_price,
+            unhedged,
+            pnl: qty * (sell_price - buy_price) - fees,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// IOC limit for one leg, fitted to the venue's filters.
+    async fn leg(
+        venue: &ArbitrageVenue,
+        symbol: &str,
+        side: Side,
+        qty: Decimal,
+        price: f64,
+    ) -> Option<PlaceOrderRequest> {
+        let req = limit_order(symbol, side, qty, dec(price), TimeInForce::Ioc);
+        normalize_order(&venue.symbol_meta, venue.exchange.as_ref(), req, dec(price)).await
+    }
+}
+
+/// Whether `exchange` has the config section its client and stream need.
+fn venue_configured(config: &AppConfig, exchange: &str) -> bool {
+    match exchange {
+        "alpaca" => true,
+        "binance" => config.binance.is_some(),
+        "coinbase" => config.coinbase.is_some(),
+        "kraken" => config.kraken.is_some(),
+        _ => false,
+    }
+}
+
+/// Compares the arbitrage venues' quotes and trades the opportunities.
+pub struct ArbitrageService {
+    event_bus: EventBus,
+    /// Venues streamed apart from the session, with their private bus
+    feeds: Vec<(String, EventBus)>,
+    /// The session's exchange, when it is one of the venues
+    session_venue: Option<String>,
+    scanner: ArbitrageScanner,
+    /// None unless `arbitrage.execute`
+    executor: Option<ArbitrageExecutor>,
+    book: ArbitrageBook,
+}
+
+impl ArbitrageService {
+    /// Open a market data stream (and, to execute, a client) on every venue
+    /// but the session's own.
+    pub async fn connect(
+        event_bus: EventBus,
+        exchange: Arc<dyn TradingApi>,
+        symbol_meta: SymbolMetaRegistry,
+        book: ArbitrageBook,
+        config: &AppConfig,
+    ) -> Self {
+        let arbitrage = &config.arbitrage;
+        let symbols = arbitrage_symbols(config);
+        let mut venues = Vec::new();
+        let mut feeds = Vec::new();
+        let mut session_venue = None;
+        for name in &arbitrage.venues {
+            let name = name.to_lowercase();
+            if name == exchange.name() {
+                for symbol in symbols.iter().filter(|s| !config.symbols.contains(s)) {
+                    warn!(
+                        "⚠️ [ARB] {} is not in `symbols` and will not be streamed from {}",
+                        symbol, name
+                    );
+                }
+                venues.push(ArbitrageVenue {
+                    name: name.clone(),
+                    exchange: exchange.clone(),
+                    symbol_meta: symbol_meta.clone(),
+                });
+                session_venue = Some(name);
+                continue;
+            }
+            let Some(venue) = Venue::from_name(&name).filter(|_| venue_configured(config, &name))
+            else {
+                warn!("⚠️ [ARB] {} has no config section - not compared", name);
+                continue;
+            };
+            let mut venue_config = config.clone();
+            venue_config.exchange = name.clone();
+            let registry = SymbolRegistry::new(venue, &symbols);
+            let (venue_exchange, store) =
+                build_exchange_with_symbols(&venue_config, registry.clone());
+            let store = store.unwrap_or_else(|| MarketStore::new(config.history_limit));
+            let bus = EventBus::new(config.event_bus.queue_capacity);
+            let stream =
+                GenericWsStream::for_exchange(&venue_config, &name).with_symbol_registry(registry);
+            if let Err(e) = stream.start(store, symbols.clone(), bus.clone()).await {
+                warn!("⚠️ [ARB] {} market data stream failed: {}", name, e);
+                continue;
+            }
+            let venue_meta = SymbolMetaRegistry::new();
+            if arbitrage.execute {
+                venue_meta.load(venue_exchange.as_ref(), &symbols).await;
+            }
+            venues.push(ArbitrageVenue {
+                name: name.clone(),
+                exchange: venue_exchange,
+                symbol_meta: venue_meta,
+            });
+            feeds.push((name, bus));
+        }
+        if venues.len() < 2 {
+            warn!(
+                "⚠️ [ARB] {} venue(s) streaming - nothing to compare",
+                venues.len()
+            );
+        }
+
+        let executor = arbitrage
+            .execute
+            .then(|| ArbitrageExecutor::new(venues, config).with_event_bus(event_bus.clone()));
+        Self {
+            event_bus,
+            feeds,
+            session_venue,
+            scanner: ArbitrageScanner::new(arbitrage, &symbols),
+            executor,
+            book,
+        }
+    }
+
+    pub fn start(self) {
+        let Self {
+            event_bus,
+            feeds,
+            session_venue,
+            mut scanner,
+            executor,
+            book,
+        } = self;
+        let (tx, mut quotes) = mpsc::channel::<(String, String, f64, f64)>(FEED_QUEUE);
+        for (venue, bus) in &feeds {
+            let mut rx = bus.subscribe("arbitrage");
+            let stopped = bus.shutdown().clone();
+            let (venue, tx) = (venue.clone(), tx.clone());
+            tokio::spawn(async move {
+                loop {
+                    let event = tokio::select! {
+                        _ = stopped.stopped() => break,
+                        event = rx.recv() => event,
+                    };
+                    match event {
+                        Ok(Event::Market(MarketEvent::Quote {
+                            symbol, bid, ask, ..
+                        })) => {
+                            // Market data: dropped if the scanner falls behind
+                            let _ = tx.try_send((venue.clone(), symbol, bid, ask));
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+
+        let mut rx = event_bus.subscribe("arbitrage");
+        let shutdown = event_bus.shutdown().clone();
+        let busy: Arc<Mutex<HashSet<String>>> = Arc::default();
+        tokio::spawn(async move {
+            info!(
+                "💱 Arbitrage scanner started ({} venue stream(s){}, min {:.1} bps net, {})",
+                feeds.len(),
+                session_venue
+                    .as_deref()
+                    .map(|v| format!(" + session {}", v))
+                    .unwrap_or_default(),
+                scanner.config.min_profit_bps,
+                if executor.is_some() {
+                    "executing"
+                } else {
+                    "signals only"
+                }
+            );
+            // Kept open so the quote channel outlives the venue streams
+            let _tx = tx;
+            // Set by the circuit breaker; cleared by /resume
+            let mut halted = false;
+            loop {
+                let (venue, symbol, bid, ask) = tokio::select! {
+                    _ = shutdown.draining() => break,
+                    Some(quote) = quotes.recv() => quote,
+                    event = rx.recv() => match event {
+                        Ok(Event::Market(MarketEvent::Quote { symbol, bid, ask, .. })) => {
+                            match &session_venue {
+                                Some(venue) => (venue.clone(), symbol, bid, ask),
+                                None => continue,
+                            }
+                        }
+                        Ok(Event::System(system)) => {
+                            halted = matches!(system, SystemEvent::Halt { .. });
+                            continue;
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                let Some(signal) = scanner.on_quote(&venue, &symbol, bid, ask, Instant::now())
+                else {
+                    continue;
+                };
+                info!(
+                    "💱 [ARB] {}: buy {} @ {:.4}, sell {} @ {:.4} ({:.1} bps, {:.1} bps net of fees)",
+                    signal.symbol,
+                    signal.buy_venue,
+                    signal.buy_price,
+                    signal.sell_venue,
+                    signal.sell_price,
+                    signal.spread_bps,
+                    signal.net_bps
+                );
+                event_bus.publish(Event::Arbitrage(signal.clone())).ok();
+
+                let Some(executor) = executor.clone() else {
+                    continue;
+                };
+                if halted || !busy.lock().unwrap().insert(symbol.clone()) {
+                    continue;
+                }
+                let (book, bus, busy) = (book.clone(), event_bus.clone(), busy.clone());
+                tokio::spawn(async move {
+                    if let Some(trade) = executor.execute(&signal).await {
+                        info!(
+                            "💱 [ARB] {} {} bought on {} @ {} / sold on {} @ {} (P&L {})",
+                            trade.symbol,
+                            trade.qty,
+                            trade.buy_venue,
+                            trade.buy_price,
+                            trade.sell_venue,
+                            trade.sell_price,
+                            trade.pnl
+                        );
+                        book.record(&trade);
+                        bus.publish(Event::ArbitrageTrade(trade)).ok();
+                    }
+                    busy.lock().unwrap().remove(&symbol);
+                });
+            }
+            for (_, bus) in &feeds {
+                bus.shutdown().begin_drain();
+                bus.shutdown().finish();
+            }
+            for position in book.snapshot() {
+                if !position.net().is_zero() {
+                    warn!(
+                        "⚠️ [ARB] Stopping with {} {} unhedged across venues",
+                        position.net(),
+                        position.symbol
+                    );
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,259 @@
+//! Unit tests for cross-exchange arbitrage - opportunity detection, the
+//! scanner's staleness and cooldown, leg execution and the combined book.
+
+#[cfg(test)]
+mod arbitrage_tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use crate::config::{AppConfig, ArbitrageConfig};
+    use crate::error::AutoHedgeError;
+    use crate::events::ArbitrageEvent;
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::symbol_meta::SymbolMetaRegistry;
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::arbitrage::*;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn config() -> ArbitrageConfig {
+        ArbitrageConfig {
+            enabled: true,
+            venues: vec!["binance".to_string(), "kraken".to_string()],
+            default_fee_bps: 10.0,
+            min_profit_bps: 5.0,
+            ..ArbitrageConfig::default()
+        }
+    }
+
+    fn quote(bid: f64, ask: f64) -> VenueQuote {
+        VenueQuote {
+            bid,
+            ask,
+            received: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_opportunity_must_clear_both_fees() {
+        // 30 bps gross - 20 bps fees = 10 bps net
+        let quotes = [
+            ("binance", quote(99.9, 100.0)),
+            ("kraken", quote(100.3, 100.4)),
+        ];
+        let signal = best_opportunity("BTC/USDT", &quotes, &config()).unwrap();
+        assert_eq!(signal.buy_venue, "binance");
+        assert_eq!(signal.sell_venue, "kraken");
+        assert_eq!(signal.buy_price, 100.0);
+        assert_eq!(signal.sell_price, 100.3);
+        assert!((signal.spread_bps - 30.0).abs() < 1e-6);
+        assert!((signal.net_bps - 10.0).abs() < 1e-6);
+
+        // Dearer fees on Kraken eat the spread
+        let mut config = config();
+        config.fee_bps.insert("kraken".to_string(), 20.0);
+        assert!(best_opportunity("BTC/USDT", &quotes, &config).is_none());
+    }
+
+    #[test]
+    fn test_opportunity_picks_the_widest_pair() {
+        let quotes = [
+            ("binance", quote(99.9, 100.0)),
+            ("kraken", quote(100.3, 100.4)),
+            ("coinbase", quote(100.5, 100.6)),
+        ];
+        let signal = best_opportunity("BTC/USDT", &quotes, &config()).unwrap();
+        assert_eq!(signal.buy_venue, "binance");
+        assert_eq!(signal.sell_venue, "coinbase");
+
+        // Spread within the venues' own bid/ask: nothing to take
+        let quotes = [
+            ("binance", quote(99.9, 100.0)),
+            ("kraken", quote(99.95, 100.05)),
+        ];
+        assert!(best_opportunity("BTC/USDT", &quotes, &config()).is_none());
+    }
+
+    #[test]
+    fn test_scanner_skips_stale_quotes_and_cools_down() {
+        let mut config = config();
+        config.max_quote_age_ms = 1000;
+        config.cooldown_ms = 5000;
+        let mut scanner = ArbitrageScanner::new(&config, &["BTC/USDT".to_string()]);
+        let start = Instant::now();
+
+        assert!(scanner
+            .on_quote("binance", "BTC/USDT", 99.9, 100.0, start)
+            .is_none());
+        // Binance's quote is 2s old by now
+        let later = start + Duration::from_secs(2);
+        assert!(scanner
+            .on_quote("kraken", "BTC/USDT", 100.3, 100.4, later)
+            .is_none());
+
+        let signal = scanner.on_quote("binance", "BTC/USDT", 99.9, 100.0, later);
+        assert!(signal.is_some());
+        // Same opportunity within the cooldown
+        assert!(scanner
+            .on_quote(
+                "binance",
+                "BTC/USDT",
+                99.9,
+                100.0,
+                later + Duration::from_millis(500)
+            )
+            .is_none());
+
+        // Symbols outside the arbitrage list are ignored
+        assert!(scanner
+            .on_quote("binance", "ETH/USDT", 1.0, 1.0, later)
+            .is_none());
+    }
+
+    /// Venue whose orders fill completely; limit orders are rejected if `reject`.
+    fn arb_venue(name: &'static str, reject: bool) -> Arc<MockExchange> {
+        let exchange = MockExchange::new(name)
+            .with_capabilities(|c| c.supports_ws_quotes = true)
+            .on_submit(move |_, order| {
+                if reject && matches!(order.order_type, OrderType::Limit) {
+                    return Err(AutoHedgeError::exchange("insufficient balance"));
+                }
+                Ok(ack(&format!("{}-1", name), "filled", json!({})))
+            });
+        Arc::new(exchange)
+    }
+
+    /// Orders sent to `venue` as `<type> <side> <qty>`.
+    fn orders(venue: &MockExchange) -> Vec<String> {
+        venue
+            .submitted()
+            .iter()
+            .map(|o| {
+                format!(
+                    "{:?} {:?} {}",
+                    o.order_type,
+                    o.side,
+                    o.qty.unwrap_or_default()
+                )
+            })
+            .collect()
+    }
+
+    fn app_config() -> AppConfig {
+        let mut app: AppConfig = serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "binance"
+symbols: ["BTC/USDT"]
+defaults: { take_profit_pct: 1.0, stop_loss_pct: 0.5, min_order_amount: 10.0, max_order_amount: 100.0 }
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft: { evaluate_every_quotes: 5, min_edge_bps: 10.0, take_profit_bps: 50.0, stop_loss_bps: 25.0, max_spread_bps: 30.0 }
+hybrid: { gate_refresh_quotes: 100, no_trade_cooldown_quotes: 50 }
+llm: { api_key: null, base_url: null, model: "test-model" }
+alpaca: { api_key: "K", secret_key: "S", base_url: "https://paper-api.alpaca.markets" }
+exit_on_quotes: true
+"#,
+        )
+        .unwrap();
+        app.arbitrage = ArbitrageConfig {
+            execute: true,
+            leg_notional: 1000.0,
+            ..config()
+        };
+        app
+    }
+
+    fn executor(sell_rejects: bool) -> (ArbitrageExecutor, [Arc<MockExchange>; 2]) {
+        let exchanges = [
+            arb_venue("binance", false),
+            arb_venue("kraken", sell_rejects),
+        ];
+        let venues = exchanges
+            .iter()
+            .map(|exchange| ArbitrageVenue {
+                name: exchange.name().to_string(),
+                exchange: exchange.clone(),
+                symbol_meta: SymbolMetaRegistry::new(),
+            })
+            .collect();
+        (ArbitrageExecutor::new(venues, &app_config()), exchanges)
+    }
+
+    fn signal() -> ArbitrageEvent {
+        best_opportunity(
+            "BTC/USDT",
+            &[
+                ("binance", quote(99.9, 100.0)),
+                ("kraken", quote(100.3, 100.4)),
+            ],
+            &config(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_legs_into_both_venues() {
+        let (executor, [binance, kraken]) = executor(false);
+        let trade = executor.execute(&signal()).await.unwrap();
+
+        assert_eq!(orders(&binance), ["Limit Buy 10"]);
+        assert_eq!(orders(&kraken), ["Limit Sell 10"]);
+        assert_eq!(trade.qty, dec(10.0));
+        assert_eq!(trade.unhedged, Decimal::ZERO);
+        // 10 * 0.3 spread - 10 bps of 1000 and 1003 in fees
+        assert_eq!(trade.pnl, dec(3.0) - dec(2.003));
+
+        let book = ArbitrageBook::new();
+        book.record(&trade);
+        book.record(&trade);
+        let position = book.get("BTC/USDT").unwrap();
+        assert_eq!(position.trades, 2);
+        assert_eq!(position.inventory["binance"], dec(20.0));
+        assert_eq!(position.inventory["kraken"], dec(-20.0));
+        assert!(position.net().is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_leg_unwinds_the_filled_one() {
+        let (executor, [binance, kraken]) = executor(true);
+        assert!(executor.execute(&signal()).await.is_none());
+
+        assert_eq!(orders(&kraken), ["Limit Sell 10"]);
+        // The bought leg is sold back at market on its own venue
+        assert_eq!(orders(&binance), ["Limit Buy 10", "Market Sell 10"]);
+    }
+
+    #[test]
+    fn test_book_counts_failed_unwinds_as_unhedged() {
+        let book = ArbitrageBook::new();
+        let mut trade = crate::events::ArbitrageTradeEvent {
+            symbol: "BTC/USDT".to_string(),
+            buy_venue: "binance".to_string(),
+            sell_venue: "kraken".to_string(),
+            qty: dec(6.0),
+            buy_price: dec(100.0),
+            sell_price: dec(100.3),
+            unhedged: dec(4.0),
+            pnl: dec(1.0),
+            timestamp: String::new(),
+        };
+        book.record(&trade);
+        let position = book.get("BTC/USDT").unwrap();
+        assert_eq!(position.inventory["binance"], dec(10.0));
+        assert_eq!(position.inventory["kraken"], dec(-6.0));
+        assert_eq!(position.net(), dec(4.0));
+
+        trade.unhedged = dec(-4.0);
+        book.record(&trade);
+        assert!(book.get("BTC/USDT").unwrap().net().is_zero());
+        assert_eq!(book.snapshot().len(), 1);
+    }
+}
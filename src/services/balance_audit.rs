@@ -0,0 +1,275 @@
+//! Balance audit trail (`balance_audit`).
+//!
+//! The reporter's P&L is computed from our own fills and marks; the venue's
+//! equity also moves with fees, funding payments and deposits or withdrawals,
+//! so the two silently drift apart. `BalanceAuditor` snapshots `get_account`
+//! every `interval_secs`, accumulates the cash flow of the fills booked on the
+//! bus in between, and attributes each cash change: what the fills explain,
+//! a shortfall within `fee_tolerance_bps` of the fill notional as fees, a
+//! change without fills as a transfer (spot) or funding (margin venues), and
+//! anything else as unexplained. Every change is appended to `path` as JSONL
+//! and published as `AccountEvent::BalanceAudited` for the reporter's totals.
+//!
+//! On margin venues the wallet moves by realized P&L rather than notional, so
+//! a change in an interval with fills is booked to the fills as a whole.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, BalanceAuditConfig};
+use crate::error::Result;
+use crate::events::{AccountEvent, BalanceChangeEvent, Event, ExecutionReport};
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::AccountSummary;
+use crate::money::dec;
+
+/// Cash flow of the fills booked since the last snapshot.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FillFlow {
+    /// Sells +, buys -
+    pub cash_flow: Decimal,
+    pub notional: Decimal,
+    pub fills: u64,
+}
+
+impl FillFlow {
+    /// Add `exec` if it is booked as a fill and carries a price and quantity.
+    pub fn record(&mut self, exec: &ExecutionReport, streamed_fills: bool) {
+        if !exec.counts_as_fill(streamed_fills) {
+            return;
+        }
+        let (Some(price), Some(qty)) = (exec.price, exec.qty) else {
+            return;
+        };
+        let notional = (price * qty).abs();
+        if exec.side.eq_ignore_ascii_case("sell") {
+            self.cash_flow += notional;
+        } else {
+            self.cash_flow -= notional;
+        }
+        self.notional += notional;
+        self.fills += 1;
+    }
+}
+
+/// Attribute the cash change from `before` to `after` given the fills in
+/// between. None when nothing moved (or less than `min_change` without fills).
+pub fn attribute(
+    config: &BalanceAuditConfig,
+    margin: bool,
+    before: Decimal,
+    after: Decimal,
+    flow: &FillFlow,
+) -> Option<BalanceChangeEvent> {
+    let min_change = dec(config.min_change.max(0.0));
+    let change = after - before;
+    if flow.fills == 0 && (change.is_zero() || change.abs() < min_change) {
+        return None;
+    }
+
+    let mut event = BalanceChangeEvent {
+        cash_before: before,
+        cash_after: after,
+        change,
+        fill_cash_flow: Decimal::ZERO,
+        fill_notional: flow.notional,
+        fills: flow.fills,
+        fees: Decimal::ZERO,
+        funding: Decimal::ZERO,
+        transfer: Decimal::ZERO,
+        unexplained: Decimal::ZERO,
+        portfolio_value: None,
+        kind: "fills".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if flow.fills == 0 {
+        if margin {
+            event.funding = change;
+            event.kind = "funding".to_string();
+        } else {
+            event.transfer = change;
+            event.kind = if change > Decimal::ZERO {
+                "deposit"
+            } else {
+                "withdrawal"
+            }
+            .to_string();
+        }
+        return Some(event);
+    }
+
+    if margin {
+        event.fill_cash_flow = change;
+        return Some(event);
+    }
+
+    event.fill_cash_flow = flow.cash_flow;
+    let residual = change - flow.cash_flow;
+    let fee_budget = flow.notional * dec(config.fee_tolerance_bps.max(0.0) / 10_000.0);
+    if residual.abs() < min_change {
+        // Rounding: the fills explain the change
+        event.unexplained = residual;
+    } else if residual < Decimal::ZERO && -residual <= fee_budget {
+        event.fees = -residual;
+    } else {
+        event.unexplained = residual;
+        event.kind = "unexplained".to_string();
+    }
+    Some(event)
+}
+
+/// Snapshot-to-snapshot state of the audit.
+#[derive(Clone, Debug)]
+pub struct BalanceAudit {
+    config: BalanceAuditConfig,
+    margin: bool,
+    last_cash: Option<Decimal>,
+    flow: FillFlow,
+}
+
+impl BalanceAudit {
+    /// `margin` is true on futures venues (wallet moves by realized P&L).
+    pub fn new(config: &BalanceAuditConfig, margin: bool) -> Self {
+        Self {
+            config: config.clone(),
+            margin,
+            last_cash: None,
+            flow: FillFlow::default(),
+        }
+    }
+
+    pub fn record_fill(&mut self, exec: &ExecutionReport, streamed_fills: bool) {
+        self.flow.record(exec, streamed_fills);
+    }
+
+    /// Compare `account` with the previous snapshot. The first snapshot (or
+    /// one without cash) only sets the baseline.
+    pub fn observe(&mut self, account: &AccountSummary) -> Option<BalanceChangeEvent> {
+        let cash = account.cash?;
+        let Some(before) = self.last_cash.replace(cash) else {
+            self.flow = FillFlow::default();
+            return None;
+        };
+        let flow = std::mem::take(&mut self.flow);
+        let mut event = attribute(&self.config, self.margin, before, cash, &flow)?;
+        event.portfolio_value = account.portfolio_value;
+        Some(event)
+    }
+}
+
+/// Periodically snapshots the account and publishes attributed changes.
+#[derive(Clone)]
+pub struct BalanceAuditor {
+    exchange: Arc<dyn TradingApi>,
+    event_bus: EventBus,
+    config: BalanceAuditConfig,
+    margin: bool,
+    streamed_fills: bool,
+}
+
+impl BalanceAuditor {
+    pub fn new(exchange: Arc<dyn TradingApi>, event_bus: EventBus, config: &AppConfig) -> Self {
+        Self {
+            exchange,
+            event_bus,
+            config: config.balance_audit.clone(),
+            margin: config.futures().is_some(),
+            streamed_fills: false,
+        }
+    }
+
+    /// Book only streamed fill updates (a user-data stream is running).
+    pub fn with_streamed_fills(mut self, streamed_fills: bool) -> Self {
+        self.streamed_fills = streamed_fills;
+        self
+    }
+
+    pub async fn start(&self) {
+        let mut rx = self.event_bus.subscribe("balance_audit");
+        let auditor = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+        let path = PathBuf::from(&self.config.path);
+
+        tokio::spawn(async move {
+            info!(
+                "🧾 [AUDIT] Snapshotting the account every {}s -> {}",
+                auditor.config.interval_secs.max(1),
+                path.display()
+            );
+            let mut audit = BalanceAudit::new(&auditor.config, auditor.margin);
+            let mut ticker =
+                tokio::time::interval(Duration::from_secs(auditor.config.interval_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = ticker.tick() => {
+                        let account = match auditor.exchange.get_account().await {
+                            Ok(account) => account,
+                            Err(e) => {
+                                warn!("⚠️ [AUDIT] Account snapshot failed: {}", e);
+                                continue;
+                            }
+                        };
+                        if let Some(change) = audit.observe(&account) {
+                            auditor.record(&path, change);
+                        }
+                    }
+                    event = rx.recv() => match event {
+                        Ok(Event::Execution(exec)) => {
+                            audit.record_fill(&exec, auditor.streamed_fills);
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(n)) => {
+                            warn!("⚠️ [AUDIT] Missed {} events - fill flow may be incomplete", n);
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+
+    fn record(&self, path: &Path, change: BalanceChangeEvent) {
+        if change.kind == "fills" {
+            info!(
+                "🧾 [AUDIT] Cash {:+.2} from {} fill(s), fees {:.2}",
+                change.change, change.fills, change.fees
+            );
+        } else {
+            warn!(
+                "🧾 [AUDIT] Cash {:+.2} booked as {} (fills explain {:+.2})",
+                change.change, change.kind, change.fill_cash_flow
+            );
+        }
+        if let Err(e) = append_jsonl(path, &change) {
+            warn!("⚠️ [AUDIT] Failed to write balance audit: {}", e);
+        }
+        self.event_bus
+            .publish(Event::Account(AccountEvent::BalanceAudited(change)))
+            .ok();
+    }
+}
+
+fn append_jsonl(path: &Path, change: &BalanceChangeEvent) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut f = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    let line = serde_json::to_string(change)?;
+    writeln!(f, "{}", line)?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Unit tests for the balance audit trail - fill flow and change attribution.
+
+#[cfg(test)]
+mod balance_audit_tests {
+    use crate::config::BalanceAuditConfig;
+    use crate::events::ExecutionReport;
+    use crate::exchange::types::AccountSummary;
+    use crate::money::dec;
+    use crate::services::balance_audit::*;
+    use rust_decimal::Decimal;
+
+    fn exec(side: &str, status: &str, price: f64, qty: f64) -> ExecutionReport {
+        ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o-1".to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(dec(price)),
+            qty: Some(dec(qty)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        }
+    }
+
+    fn account(cash: f64) -> AccountSummary {
+        AccountSummary {
+            buying_power: None,
+            cash: Some(dec(cash)),
+            portfolio_value: Some(dec(cash)),
+        }
+    }
+
+    #[test]
+    fn test_fill_flow_follows_side_and_fill_mode() {
+        let mut flow = FillFlow::default();
+        flow.record(&exec("buy", "fill", 100.0, 2.0), true);
+        flow.record(&exec("sell", "partial_fill", 110.0, 1.0), true);
+        // Submission acks only count without a fill stream
+        flow.record(&exec("buy", "new", 100.0, 5.0), true);
+        assert_eq!(flow.cash_flow, dec(-90.0));
+        assert_eq!(flow.notional, dec(310.0));
+        assert_eq!(flow.fills, 2);
+
+        flow.record(&exec("buy", "new", 100.0, 5.0), false);
+        assert_eq!(flow.fills, 3);
+    }
+
+    #[test]
+    fn test_fee_shortfall_is_booked_as_fees() {
+        let config = BalanceAuditConfig::default();
+        let mut audit = BalanceAudit::new(&config, false);
+        assert!(audit.observe(&account(1000.0)).is_none());
+
+        // Bought 200 of notional, paid 0.20 (10 bps) in fees
+        audit.record_fill(&exec("buy", "fill", 100.0, 2.0), true);
+        let change = audit.observe(&account(799.80)).unwrap();
+        assert_eq!(change.kind, "fills");
+        assert_eq!(change.fill_cash_flow, dec(-200.0));
+        assert_eq!(change.fees, dec(0.2));
+        assert_eq!(change.unexplained, Decimal::ZERO);
+        assert_eq!(
+            change.fill_cash_flow - change.fees + change.unexplained,
+            change.change
+        );
+
+        // The flow was consumed by the snapshot
+        assert!(audit.observe(&account(799.80)).is_none());
+    }
+
+    #[test]
+    fn test_changes_without_fills_are_transfers_or_funding() {
+        let config = BalanceAuditConfig::default();
+        let mut spot = BalanceAudit::new(&config, false);
+        spot.observe(&account(1000.0));
+        let deposit = spot.observe(&account(1500.0)).unwrap();
+        assert_eq!(deposit.kind, "deposit");
+        assert_eq!(deposit.transfer, dec(500.0));
+        let withdrawal = spot.observe(&account(1200.0)).unwrap();
+        assert_eq!(withdrawal.kind, "withdrawal");
+        // Below min_change: ignored
+        assert!(spot.observe(&account(1200.005)).is_none());
+
+        let mut margin = BalanceAudit::new(&config, true);
+        margin.observe(&account(1000.0));
+        let funding = margin.observe(&account(999.5)).unwrap();
+        assert_eq!(funding.kind, "funding");
+        assert_eq!(funding.funding, dec(-0.5));
+    }
+
+    #[test]
+    fn test_shortfall_beyond_fee_tolerance_is_unexplained() {
+        let config = BalanceAuditConfig::default();
+        let mut audit = BalanceAudit::new(&config, false);
+        audit.observe(&account(1000.0));
+        audit.record_fill(&exec("sell", "fill", 100.0, 1.0), true);
+        // Sold 100 but cash only rose 90: 50 bps allows 0.50 of fees
+        let change = audit.observe(&account(1090.0)).unwrap();
+        assert_eq!(change.kind, "unexplained");
+        assert_eq!(change.fees, Decimal::ZERO);
+        assert_eq!(change.unexplained, dec(-10.0));
+    }
+}
@@ -0,0 +1,383 @@
+//! Bulk position close (`POST /orders/close_all`).
+//!
+//! `/cancel_all` only cancels orders and leaves positions without take-profit
+//! coverage. `BulkCloser` flattens positions, optionally filtered by symbol
+//! or side, in a fixed order per position: working entries are cancelled,
+//! then the resting TP leg (and native stop) is cancelled and its final state
+//! read back, together with any other venue order on the symbol; only then is
+//! the rest sold at market. Venue positions the tracker does not know
+//! (except hedges) are closed the same way. The venue's positions are then
+//! re-read until the closed symbols are flat, and whatever is left is reported.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::AppConfig;
+use crate::data::store::MarketStore;
+use crate::events::{Event, ExecutionReport};
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::{OrderType, PlaceOrderRequest, Position, Side, TimeInForce};
+use crate::money::dec;
+use crate::services::position_monitor::{
+    OcoOutcome, PositionInfo, PositionMonitor, PositionTracker,
+};
+
+/// Venue position reads while waiting for closes to settle.
+const RECONCILE_ATTEMPTS: usize = 3;
+const RECONCILE_DELAY: Duration = Duration::from_millis(500);
+
+/// Which positions to close (`?symbol=&side=`); empty closes everything.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CloseFilter {
+    pub symbol: Option<String>,
+    /// "long" or "short"
+    pub side: Option<String>,
+}
+
+impl CloseFilter {
+    /// Validate `side`; None when it is not "long" or "short".
+    pub fn validated(self) -> Option<Self> {
+        match self.side.as_deref().map(str::to_lowercase).as_deref() {
+            None => Some(self),
+            Some(side @ ("long" | "short")) => Some(Self {
+                side: Some(side.to_string()),
+                ..self
+            }),
+            Some(_) => None,
+        }
+    }
+
+    fn matches(&self, symbol: &str, qty: Decimal) -> bool {
+        let side_ok = match self.side.as_deref() {
+            Some("long") => qty > Decimal::ZERO,
+            Some("short") => qty < Decimal::ZERO,
+            _ => true,
+        };
+        side_ok && self.symbol.as_deref().is_none_or(|s| s == symbol)
+    }
+}
+
+/// What happened to one position.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CloseOutcome {
+    pub symbol: String,
+    /// Signed quantity that was held (negative = short)
+    pub qty: Decimal,
+    /// "closed" (market order placed), "tp_filled" (an exit leg filled while
+    /// being cancelled), "flat" (only unfilled entries were working),
+    /// "skipped" (a leg could not be confirmed dead) or "failed"
+    pub status: String,
+    pub order_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BulkCloseReport {
+    pub outcomes: Vec<CloseOutcome>,
+    /// Venue orders cancelled (entries, exit legs and untracked orders)
+    pub cancelled_orders: usize,
+    /// Positions still held on the venue after reconciliation
+    pub residual: Vec<Position>,
+}
+
+#[derive(Clone)]
+pub struct BulkCloser {
+    exchange: Arc<dyn TradingApi>,
+    tracker: PositionTracker,
+    store: MarketStore,
+    bus: EventBus,
+    is_crypto: bool,
+}
+
+impl BulkCloser {
+    pub fn new(
+        exchange: Arc<dyn TradingApi>,
+        tracker: PositionTracker,
+        store: MarketStore,
+        bus: EventBus,
+        config: &AppConfig,
+    ) -> Self {
+        Self {
+            exchange,
+            tracker,
+            store,
+            bus,
+            is_crypto: config.trading_mode.eq_ignore_ascii_case("crypto"),
+        }
+    }
+
+    /// Close every position matching `filter`, then reconcile with the venue.
+    pub async fn close_all(&self, filter: &CloseFilter) -> BulkCloseReport {
+        let mut report = BulkCloseReport::default();
+        let venue_positions = match self.exchange.get_positions().await {
+            Ok(positions) => positions,
+            Err(e) => {
+                warn!(
+                    "⚠️ [CLOSE-ALL] Cannot read venue positions: {} (closing tracked ones only)",
+                    e
+                );
+                Vec::new()
+            }
+        };
+        let open_orders = self
+            .exchange
+            .get_open_orders()
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        let mut targets: Vec<(String, Decimal)> = self
+            .tracker
+            .get_all_positions()
+            .into_iter()
+            .map(|p| (p.symbol, p.filled_qty))
+            .filter(|(symbol, qty)| filter.matches(symbol, *qty))
+            .collect();
+        let tracked: HashSet<String> = targets.iter().map(|(s, _)| s.clone()).collect();
+        for position in &venue_positions {
+            let is_hedge = self.tracker.get_hedge(&position.symbol).is_some()
+                && filter.symbol.as_deref() != Some(position.symbol.as_str());
+            if !position.qty.is_zero()
+                && !is_hedge
+                && !tracked.contains(&position.symbol)
+                && filter.matches(&position.symbol, position.qty)
+            {
+                targets.push((position.symbol.clone(), position.qty));
+            }
+        }
+        info!(
+            "🧹 [CLOSE-ALL] Closing {} position(s) (symbol={:?}, side={:?})",
+            targets.len(),
+            filter.symbol,
+            filter.side
+        );
+
+        for (symbol, qty) in targets {
+            // Orders on the symbol that would re-open or double-close it
+            let stray: Vec<String> = open_orders
+                .iter()
+                .filter(|o| o.symbol == symbol)
+                .map(|o| o.id.clone())
+                .collect();
+            let outcome = self.close_one(&symbol, qty, &stray, &mut report).await;
+            report.outcomes.push(outcome);
+        }
+
+        report.residual = self.reconcile(&report.outcomes).await;
+        report
+    }
+
+    /// Cancel the position's orders (entries, exit legs, `stray`), then sell
+    /// (or buy back) what is left at market.
+    async fn close_one(
+        &self,
+        symbol: &str,
+        qty: Decimal,
+        stray: &[String],
+        report: &mut BulkCloseReport,
+    ) -> CloseOutcome {
+        let mut outcome = CloseOutcome {
+            symbol: symbol.to_string(),
+            qty,
+            status: "closed".to_string(),
+            order_id: None,
+            error: None,
+        };
+        let position = self.tracker.get_position(symbol);
+        let mut remaining = qty.abs();
+        let mut handled: HashSet<String> = HashSet::new();
+
+        if let Some(position) = &position {
+            // Keep the monitor's exit logic off the position while it is flattened
+            self.tracker.mark_closing(symbol);
+            let entries: Vec<String> = self
+                .tracker
+                .get_all_pending_orders()
+                .into_iter()
+                .filter(|o| o.symbol == symbol && o.side == "buy")
+                .map(|o| o.order_id)
+                .collect();
+            self.tracker
+                .cancel_working_entries(symbol, self.exchange.as_ref())
+                .await;
+            report.cancelled_orders += entries.len();
+            handled.extend(entries);
+
+            for (leg, order_id) in [
+                ("TP", position.open_order_id.as_deref()),
+                ("stop", position.stop_order_id.as_deref()),
+            ] {
+                let Some(order_id) = order_id else {
+                    continue;
+                };
+                handled.insert(order_id.to_string());
+                let (leg_outcome, filled) =
+                    PositionMonitor::cancel_exit_leg(order_id, leg, self.exchange.as_ref()).await;
+                match leg_outcome {
+                    OcoOutcome::Retry => {
+                        // A live leg plus a market sell would sell twice
+                        warn!(
+                            "⚠️ [CLOSE-ALL] {} leg {} of {} unconfirmed - leaving {} open",
+                            leg, order_id, symbol, symbol
+                        );
+                        self.tracker.add_position(position.clone());
+                        outcome.status = "skipped".to_string();
+                        outcome.error = Some(format!("{} leg {} still working", leg, order_id));
+                        return outcome;
+                    }
+                    OcoOutcome::TakeProfitFilled | OcoOutcome::StopMayFire => {
+                        report.cancelled_orders += 1;
+                        self.tracker.remove_pending_order(order_id);
+                        remaining = (remaining - filled).max(Decimal::ZERO);
+                    }
+                }
+            }
+        }
+
+        for order_id in stray.iter().filter(|id| !handled.contains(*id)) {
+            match self.exchange.cancel_order(order_id).await {
+                Ok(()) => report.cancelled_orders += 1,
+                Err(e) => warn!(
+                    "⚠️ [CLOSE-ALL] Cancel of {} order {} failed: {}",
+                    symbol, order_id, e
+                ),
+            }
+            self.tracker.remove_pending_order(order_id);
+        }
+
+        if remaining <= Decimal::ZERO {
+            info!("🧹 [CLOSE-ALL] {} already flat", symbol);
+            self.tracker.remove_position(symbol);
+            outcome.status = if qty.is_zero() { "flat" } else { "tp_filled" }.to_string();
+            return outcome;
+        }
+
+        let side = if qty < Decimal::ZERO {
+            Side::Buy
+        } else {
+            Side::Sell
+        };
+        let order = PlaceOrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::Market,
+            qty: Some(remaining),
+            notional: None,
+            limit_price: None,
+            time_in_force: if self.is_crypto {
+                TimeInForce::Gtc
+            } else {
+                TimeInForce::Day
+            },
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        match self.exchange.submit_order(order).await {
+            Ok(ack) => {
+                info!(
+                    "🧹 [CLOSE-ALL] {:?} {} qty={} at market (order {})",
+                    side, symbol, remaining, ack.id
+                );
+                self.tracker.remove_position(symbol);
+                self.publish_execution(symbol, side, remaining, &ack.id, &ack.status, &position);
+                outcome.order_id = Some(ack.id);
+            }
+            Err(e) => {
+                error!("❌ [CLOSE-ALL] Market close of {} failed: {}", symbol, e);
+                if let Some(position) = position {
+                    // Back under the monitor, without the cancelled legs
+                    self.tracker.add_position(PositionInfo {
+                        open_order_id: None,
+                        stop_order_id: None,
+                        ..position
+                    });
+                }
+                outcome.status = "failed".to_string();
+                outcome.error = Some(e.to_string());
+            }
+        }
+        outcome
+    }
+
+    /// Book the close with the reporter like any other exit.
+    fn publish_execution(
+        &self,
+        symbol: &str,
+        side: Side,
+        qty: Decimal,
+        order_id: &str,
+        status: &str,
+        position: &Option<PositionInfo>,
+    ) {
+        let quote = self.store.get_latest_quote(symbol);
+        let price = quote.as_ref().map(|q| match side {
+            Side::Sell => dec(q.bid_price),
+            Side::Buy => dec(q.ask_price),
+        });
+        let report = ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: order_id.to_string(),
+            status: status.to_string(),
+            side: match side {
+                Side::Sell => "sell".to_string(),
+                Side::Buy => "buy".to_string(),
+            },
+            price,
+            qty: Some(qty),
+            signal_price: quote.map(|q| dec((q.bid_price + q.ask_price) / 2.0)),
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: position.as_ref().and_then(|p| p.correlation_id.clone()),
+            metadata: HashMap::new(),
+        };
+        self.bus.publish(Event::Execution(report)).ok();
+    }
+
+    /// Venue positions of the closed symbols still held once the market
+    /// orders had a moment to fill.
+    async fn reconcile(&self, outcomes: &[CloseOutcome]) -> Vec<Position> {
+        let closed: HashSet<&str> = outcomes
+            .iter()
+            .filter(|o| o.status == "closed")
+            .map(|o| o.symbol.as_str())
+            .collect();
+        if closed.is_empty() {
+            return Vec::new();
+        }
+        let mut residual = Vec::new();
+        for attempt in 0..RECONCILE_ATTEMPTS {
+            if attempt > 0 {
+                sleep(RECONCILE_DELAY).await;
+            }
+            match self.exchange.get_positions().await {
+                Ok(positions) => {
+                    residual = positions
+                        .into_iter()
+                        .filter(|p| !p.qty.is_zero() && closed.contains(p.symbol.as_str()))
+                        .collect();
+                    if residual.is_empty() {
+                        return residual;
+                    }
+                }
+                Err(e) => warn!("⚠️ [CLOSE-ALL] Reconcile read failed: {}", e),
+            }
+        }
+        for position in &residual {
+            warn!(
+                "⚠️ [CLOSE-ALL] {} still holds {} after close",
+                position.symbol, position.qty
+            );
+            self.tracker.record_mismatch();
+        }
+        residual
+    }
+}
@@ -0,0 +1,277 @@
+//! Unit tests for bulk position close - sequencing, filters and reconciliation.
+
+#[cfg(test)]
+mod bulk_close_tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::bus::EventBus;
+    use crate::config::AppConfig;
+    use crate::data::store::MarketStore;
+    use crate::error::AutoHedgeError;
+    use crate::events::Event;
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::bulk_close::*;
+    use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    /// Venue whose positions go flat when closed at market (unless `sticky`);
+    /// `order_status` is the final status (and filled qty) of an order after
+    /// its cancel, `canceled` with nothing filled by default.
+    fn flatten_venue(
+        positions: Vec<Position>,
+        open: Vec<OpenOrder>,
+        order_status: HashMap<String, (String, Decimal)>,
+        sticky: bool,
+    ) -> Arc<MockExchange> {
+        let exchange = MockExchange::new("flatten")
+            .with_capabilities(|c| c.supports_short = true)
+            .with_positions(positions)
+            .with_open_orders(Some(open))
+            .on_cancel_all(|_| Err(AutoHedgeError::Unsupported("cancel_all_orders".to_string())))
+            .on_get_order(move |_, id| {
+                let (status, filled) = order_status
+                    .get(id)
+                    .cloned()
+                    .unwrap_or(("canceled".to_string(), Decimal::ZERO));
+                Ok(ack(
+                    id,
+                    &status,
+                    json!({ "filled_qty": filled.to_string() }),
+                ))
+            })
+            .on_submit(move |venue, order| {
+                if !sticky {
+                    let mut positions = venue.positions();
+                    positions.retain(|p| p.symbol != order.symbol);
+                    venue.set_positions(positions);
+                }
+                Ok(ack(&format!("close-{}", order.symbol), "filled", json!({})))
+            });
+        Arc::new(exchange)
+    }
+
+    fn position(symbol: &str, qty: f64) -> Position {
+        Position {
+            symbol: symbol.to_string(),
+            qty: dec(qty),
+            avg_entry_price: None,
+        }
+    }
+
+    fn open_order(id: &str, symbol: &str, side: &str) -> OpenOrder {
+        OpenOrder {
+            id: id.to_string(),
+            symbol: symbol.to_string(),
+            side: side.to_string(),
+            created_at: None,
+        }
+    }
+
+    fn tracked(tracker: &PositionTracker, symbol: &str, qty: f64, tp_order: &str) {
+        tracker.add_position(PositionInfo {
+            symbol: symbol.to_string(),
+            entry_price: dec(100.0),
+            qty: dec(qty),
+            filled_qty: dec(qty),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(98.0),
+            take_profit: dec(102.0),
+            entry_time: chrono::Utc::now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: Some(tp_order.to_string()),
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(100.0),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(98.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: Some(format!("corr-{}", symbol)),
+        });
+        tracker.add_pending_order(PendingOrder {
+            order_id: tp_order.to_string(),
+            symbol: symbol.to_string(),
+            side: "sell".to_string(),
+            limit_price: dec(102.0),
+            qty: dec(qty),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(qty),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            stop_loss: None,
+            take_profit: None,
+            last_check_time: None,
+            bracket: false,
+            correlation_id: None,
+        });
+    }
+
+    fn closer(venue: Arc<MockExchange>, tracker: &PositionTracker, bus: &EventBus) -> BulkCloser {
+        let config: AppConfig = serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults: { take_profit_pct: 1.0, stop_loss_pct: 0.5, min_order_amount: 10.0, max_order_amount: 100.0 }
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft: { evaluate_every_quotes: 5, min_edge_bps: 10.0, take_profit_bps: 50.0, stop_loss_bps: 25.0, max_spread_bps: 30.0 }
+hybrid: { gate_refresh_quotes: 100, no_trade_cooldown_quotes: 50 }
+llm: { api_key: null, base_url: null, model: "test-model" }
+alpaca: { api_key: "K", secret_key: "S", base_url: "https://paper-api.alpaca.markets" }
+exit_on_quotes: true
+"#,
+        )
+        .unwrap();
+        BulkCloser::new(
+            venue,
+            tracker.clone(),
+            MarketStore::new(10),
+            bus.clone(),
+            &config,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tp_leg_is_cancelled_before_the_market_sell() {
+        let venue = flatten_venue(
+            vec![position("BTC/USD", 0.5), position("AAVE/USD", 3.0)],
+            vec![
+                open_order("tp-btc", "BTC/USD", "sell"),
+                open_order("stray-btc", "BTC/USD", "sell"),
+            ],
+            HashMap::new(),
+            false,
+        );
+        let tracker = PositionTracker::new();
+        tracked(&tracker, "BTC/USD", 0.5, "tp-btc");
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+
+        let report = closer(venue.clone(), &tracker, &bus)
+            .close_all(&CloseFilter::default())
+            .await;
+
+        let calls = venue.calls();
+        assert_eq!(
+            calls,
+            vec![
+                "cancel tp-btc",
+                "cancel stray-btc",
+                "Sell BTC/USD 0.5",
+                // Untracked venue holding is closed too
+                "Sell AAVE/USD 3",
+            ]
+        );
+        assert_eq!(report.cancelled_orders, 2);
+        assert!(report.residual.is_empty());
+        assert!(report.outcomes.iter().all(|o| o.status == "closed"));
+        assert!(!tracker.has_position("BTC/USD"));
+        assert!(tracker.get_all_pending_orders().is_empty());
+
+        let Ok(Event::Execution(exec)) = rx.try_recv() else {
+            panic!("close was not published");
+        };
+        assert_eq!(exec.side, "sell");
+        assert_eq!(exec.correlation_id.as_deref(), Some("corr-BTC/USD"));
+    }
+
+    #[tokio::test]
+    async fn test_filled_tp_leg_only_sells_the_rest() {
+        let venue = flatten_venue(
+            vec![position("BTC/USD", 1.0)],
+            Vec::new(),
+            HashMap::from([("tp".to_string(), ("filled".to_string(), dec(1.0)))]),
+            false,
+        );
+        let tracker = PositionTracker::new();
+        tracked(&tracker, "BTC/USD", 1.0, "tp");
+        let bus = EventBus::new(16);
+
+        let report = closer(venue.clone(), &tracker, &bus)
+            .close_all(&CloseFilter::default())
+            .await;
+
+        assert_eq!(report.outcomes[0].status, "tp_filled");
+        assert_eq!(venue.calls(), vec!["cancel tp"]);
+        assert!(!tracker.has_position("BTC/USD"));
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_leg_keeps_the_position() {
+        let venue = flatten_venue(
+            vec![position("BTC/USD", 1.0)],
+            Vec::new(),
+            HashMap::from([("tp".to_string(), ("new".to_string(), Decimal::ZERO))]),
+            false,
+        );
+        let tracker = PositionTracker::new();
+        tracked(&tracker, "BTC/USD", 1.0, "tp");
+        let bus = EventBus::new(16);
+
+        let report = closer(venue.clone(), &tracker, &bus)
+            .close_all(&CloseFilter::default())
+            .await;
+
+        assert_eq!(report.outcomes[0].status, "skipped");
+        assert_eq!(venue.calls(), vec!["cancel tp"]);
+        let position = tracker.get_position("BTC/USD").unwrap();
+        assert!(!position.is_closing);
+    }
+
+    #[tokio::test]
+    async fn test_filters_and_residual_positions() {
+        let venue = flatten_venue(
+            vec![
+                position("BTC/USD", 1.0),
+                position("ETH/USD", -2.0),
+                position("SOL/USD", 5.0),
+            ],
+            Vec::new(),
+            HashMap::new(),
+            true,
+        );
+        let tracker = PositionTracker::new();
+        let bus = EventBus::new(16);
+        let closer = closer(venue.clone(), &tracker, &bus);
+
+        let shorts = CloseFilter {
+            side: Some("SHORT".to_string()),
+            ..Default::default()
+        }
+        .validated()
+        .unwrap();
+        let report = closer.close_all(&shorts).await;
+        assert_eq!(venue.calls(), vec!["Buy ETH/USD 2"]);
+        // The venue never flattened it
+        assert_eq!(report.residual.len(), 1);
+        assert_eq!(report.residual[0].symbol, "ETH/USD");
+        assert_eq!(
+            tracker.recent_mismatches(std::time::Duration::from_secs(60)),
+            1
+        );
+
+        let before = venue.calls().len();
+        let sol = CloseFilter {
+            symbol: Some("SOL/USD".to_string()),
+            side: None,
+        };
+        closer.close_all(&sol).await;
+        assert_eq!(venue.calls()[before..], ["Sell SOL/USD 5"]);
+
+        let bad_side = CloseFilter {
+            side: Some("sideways".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_side.validated().is_none());
+    }
+}
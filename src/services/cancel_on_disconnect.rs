@@ -0,0 +1,473 @@
+//! Cancel-on-disconnect and startup reconciliation of orders left working.
+//!
+//! While a session runs, the venue's dead man's switch is re-armed every
+//! `cancel_on_disconnect.refresh_secs` (Kraken `CancelAllOrdersAfter`, Binance
+//! futures `countdownCancelAll`), so the venue cancels the open orders of a
+//! dead process after `timeout_secs`. Each refresh also saves a tracker
+//! snapshot: the working orders and open positions with their TP/SL levels.
+//!
+//! At the next start the venue's open orders are reconciled with it before
+//! trading resumes. Orders the snapshot records are tracked again: entries as
+//! pending orders, exit legs together with the position they close (if the
+//! venue still holds it), so the monitor neither loses nor duplicates them.
+//! The rest are handled per `unknown_orders`; by default only buy orders a
+//! crashed run left behind (listed in or older than its last snapshot) are
+//! cancelled. Sells are left alone; they only reduce exposure.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::bus::Shutdown;
+use crate::config::CancelOnDisconnectConfig;
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::OpenOrder;
+use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+
+/// What to do with open orders the snapshot has no record of
+/// (`cancel_on_disconnect.unknown_orders`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownOrderPolicy {
+    /// Buys the last run left behind after a crash (see `orphans`)
+    OrphanedBuys,
+    AllBuys,
+    All,
+    Keep,
+}
+
+impl UnknownOrderPolicy {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "orphaned_buys" | "orphans" => Some(UnknownOrderPolicy::OrphanedBuys),
+            "all_buys" | "buys" => Some(UnknownOrderPolicy::AllBuys),
+            "all" => Some(UnknownOrderPolicy::All),
+            "keep" | "none" => Some(UnknownOrderPolicy::Keep),
+            _ => None,
+        }
+    }
+}
+
+/// A working order as the snapshot records it (`PendingOrder` without its
+/// polling state).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrderRecord {
+    pub order_id: String,
+    pub symbol: String,
+    pub side: String,
+    pub limit_price: Decimal,
+    pub qty: Decimal,
+    pub filled_qty: Decimal,
+    pub created_at: String,
+    pub stop_loss: Option<Decimal>,
+    pub take_profit: Option<Decimal>,
+    pub bracket: bool,
+    pub correlation_id: Option<String>,
+}
+
+impl From<&PendingOrder> for OrderRecord {
+    fn from(order: &PendingOrder) -> Self {
+        Self {
+            order_id: order.order_id.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            limit_price: order.limit_price,
+            qty: order.qty,
+            filled_qty: order.filled_qty,
+            created_at: order.created_at.clone(),
+            stop_loss: order.stop_loss,
+            take_profit: order.take_profit,
+            bracket: order.bracket,
+            correlation_id: order.correlation_id.clone(),
+        }
+    }
+}
+
+impl OrderRecord {
+    pub fn to_pending(&self) -> PendingOrder {
+        PendingOrder {
+            order_id: self.order_id.clone(),
+            symbol: self.symbol.clone(),
+            side: self.side.clone(),
+            limit_price: self.limit_price,
+            qty: self.qty,
+            filled_qty: self.filled_qty,
+            remaining_qty: (self.qty - self.filled_qty).max(Decimal::ZERO),
+            created_at: self.created_at.clone(),
+            stop_loss: self.stop_loss,
+            take_profit: self.take_profit,
+            last_check_time: None,
+            bracket: self.bracket,
+            correlation_id: self.correlation_id.clone(),
+        }
+    }
+}
+
+/// An open position with the exit levels and legs it was managed with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PositionRecord {
+    pub symbol: String,
+    pub entry_price: Decimal,
+    pub stop_loss: Decimal,
+    pub take_profit: Decimal,
+    pub entry_time: String,
+    pub highest_price: Decimal,
+    pub trailing_stop_active: bool,
+    pub trailing_stop_price: Decimal,
+    /// Resting TP leg
+    pub open_order_id: Option<String>,
+    /// Resting native stop
+    pub stop_order_id: Option<String>,
+    pub bracket_managed: bool,
+    pub correlation_id: Option<String>,
+}
+
+impl From<&PositionInfo> for PositionRecord {
+    fn from(position: &PositionInfo) -> Self {
+        Self {
+            symbol: position.symbol.clone(),
+            entry_price: position.entry_price,
+            stop_loss: position.stop_loss,
+            take_profit: position.take_profit,
+            entry_time: position.entry_time.clone(),
+            highest_price: position.highest_price,
+            trailing_stop_active: position.trailing_stop_active,
+            trailing_stop_price: position.trailing_stop_price,
+            open_order_id: position.open_order_id.clone(),
+            stop_order_id: position.stop_order_id.clone(),
+            bracket_managed: position.bracket_managed,
+            correlation_id: position.correlation_id.clone(),
+        }
+    }
+}
+
+impl PositionRecord {
+    /// The position at the `qty` the venue holds now. Legs no longer in
+    /// `open` are dropped, so the monitor re-places them.
+    pub fn to_position(&self, qty: Decimal, open: &HashSet<&str>) -> PositionInfo {
+        let still_open = |id: &Option<String>| id.clone().filter(|id| open.contains(id.as_str()));
+        PositionInfo {
+            symbol: self.symbol.clone(),
+            entry_price: self.entry_price,
+            qty,
+            filled_qty: qty,
+            remaining_qty: Decimal::ZERO,
+            stop_loss: self.stop_loss,
+            take_profit: self.take_profit,
+            entry_time: self.entry_time.clone(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: still_open(&self.open_order_id),
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: self.highest_price,
+            trailing_stop_active: self.trailing_stop_active,
+            trailing_stop_price: self.trailing_stop_price,
+            bracket_managed: self.bracket_managed,
+            stop_order_id: still_open(&self.stop_order_id),
+            correlation_id: self.correlation_id.clone(),
+        }
+    }
+}
+
+/// Working orders of a session at one point in time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrackerSnapshot {
+    pub saved_at: DateTime<Utc>,
+    /// Entry orders still working
+    pub pending_orders: Vec<String>,
+    /// Take-profit orders of open positions
+    pub exit_orders: Vec<String>,
+    /// Written by a clean stop (nothing to sweep)
+    pub clean_shutdown: bool,
+    /// Working orders with their metadata (absent from older snapshots)
+    #[serde(default)]
+    pub orders: Vec<OrderRecord>,
+    /// Open positions with their exit levels
+    #[serde(default)]
+    pub positions: Vec<PositionRecord>,
+}
+
+impl TrackerSnapshot {
+    pub fn capture(tracker: &PositionTracker, now: DateTime<Utc>) -> Self {
+        let pending = tracker.get_all_pending_orders();
+        let positions = tracker.get_all_positions();
+        Self {
+            saved_at: now,
+            pending_orders: pending.iter().map(|o| o.order_id.clone()).collect(),
+            exit_orders: positions
+                .iter()
+                .filter_map(|p| p.open_order_id.clone())
+                .collect(),
+            clean_shutdown: false,
+            orders: pending.iter().map(OrderRecord::from).collect(),
+            positions: positions.iter().map(PositionRecord::from).collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&text)
+            .map_err(|e| warn!("⚠️ [ORDER-GUARD] Ignoring unreadable {:?}: {}", path, e))
+            .ok()
+    }
+
+    /// Write to a temp file and rename it over `path`, so a crash mid-write
+    /// keeps the previous snapshot.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Buy orders among `open` left behind by the run that wrote this
+    /// snapshot; none after a clean stop.
+    pub fn orphans<'a>(&self, open: &'a [OpenOrder]) -> Vec<&'a OpenOrder> {
+        if self.clean_shutdown {
+            return Vec::new();
+        }
+        open.iter()
+            .filter(|o| o.side.eq_ignore_ascii_case("buy"))
+            .filter(|o| {
+                self.pending_orders.contains(&o.id)
+                    || o.created_at.is_some_and(|t| t <= self.saved_at)
+            })
+            .collect()
+    }
+
+    /// Orders among `open` to cancel under `policy`, leaving the `adopted`
+    /// ones (tracked again) alone.
+    pub fn to_cancel<'a>(
+        &self,
+        open: &'a [OpenOrder],
+        adopted: &HashSet<String>,
+        policy: UnknownOrderPolicy,
+    ) -> Vec<&'a OpenOrder> {
+        let candidates = match policy {
+            UnknownOrderPolicy::OrphanedBuys => self.orphans(open),
+            UnknownOrderPolicy::AllBuys => open
+                .iter()
+                .filter(|o| o.side.eq_ignore_ascii_case("buy"))
+                .collect(),
+            UnknownOrderPolicy::All => open.iter().collect(),
+            UnknownOrderPolicy::Keep => Vec::new(),
+        };
+        candidates
+            .into_iter()
+            .filter(|o| !adopted.contains(&o.id))
+            .collect()
+    }
+}
+
+/// Keeps the venue's dead man's switch armed and the snapshot fresh.
+pub struct CancelOnDisconnect {
+    exchange: Arc<dyn TradingApi>,
+    tracker: PositionTracker,
+    config: CancelOnDisconnectConfig,
+}
+
+impl CancelOnDisconnect {
+    pub fn new(
+        exchange: Arc<dyn TradingApi>,
+        tracker: PositionTracker,
+        config: &CancelOnDisconnectConfig,
+    ) -> Self {
+        Self {
+            exchange,
+            tracker,
+            config: config.clone(),
+        }
+    }
+
+    /// Reconcile the venue's open orders with the last snapshot: track the
+    /// known ones again and cancel unknown ones per `unknown_orders`. Returns
+    /// how many were cancelled.
+    pub async fn sweep(&self) -> usize {
+        if !self.config.enabled || !self.config.startup_sweep {
+            return 0;
+        }
+        let path = Path::new(&self.config.snapshot_path);
+        let Some(snapshot) = TrackerSnapshot::load(path) else {
+            return 0;
+        };
+        let open = match self.exchange.get_open_orders().await {
+            Ok(Some(open)) => open,
+            Ok(None) => {
+                warn!(
+                    "⚠️ [ORDER-GUARD] {} cannot list open orders - startup sweep skipped",
+                    self.exchange.name()
+                );
+                return 0;
+            }
+            Err(e) => {
+                warn!("⚠️ [ORDER-GUARD] Startup sweep skipped: {}", e);
+                return 0;
+            }
+        };
+
+        let adopted = self.adopt(&snapshot, &open).await;
+        let policy = UnknownOrderPolicy::parse(&self.config.unknown_orders).unwrap_or_else(|| {
+            warn!(
+                "⚠️ [ORDER-GUARD] Unknown unknown_orders policy '{}' - cancelling orphaned buys",
+                self.config.unknown_orders
+            );
+            UnknownOrderPolicy::OrphanedBuys
+        });
+        if snapshot.clean_shutdown && policy == UnknownOrderPolicy::OrphanedBuys {
+            info!("🧹 [ORDER-GUARD] Last session stopped cleanly - no orphaned orders");
+        }
+
+        let mut cancelled = 0;
+        for order in snapshot.to_cancel(&open, &adopted, policy) {
+            match self.exchange.cancel_order(&order.id).await {
+                Ok(()) => {
+                    cancelled += 1;
+                    warn!(
+                        "🧹 [ORDER-GUARD] Cancelled unknown {} {} {} (placed {})",
+                        order.symbol,
+                        order.side,
+                        order.id,
+                        order
+                            .created_at
+                            .map_or("at an unknown time".to_string(), |t| t.to_rfc3339())
+                    );
+                }
+                Err(e) => warn!(
+                    "⚠️ [ORDER-GUARD] Failed to cancel unknown {} {} {}: {}",
+                    order.symbol, order.side, order.id, e
+                ),
+            }
+        }
+        info!(
+            "🧹 [ORDER-GUARD] Startup sweep: {} order(s) tracked again, {} unknown order(s) cancelled (snapshot of {})",
+            adopted.len(),
+            cancelled,
+            snapshot.saved_at.to_rfc3339()
+        );
+        cancelled
+    }
+
+    /// Track the snapshot's orders that are still open again, positions first:
+    /// an exit leg is only taken back with the position the venue still holds.
+    /// Returns the ids of the orders taken back.
+    async fn adopt(&self, snapshot: &TrackerSnapshot, open: &[OpenOrder]) -> HashSet<String> {
+        let open_ids: HashSet<&str> = open.iter().map(|o| o.id.as_str()).collect();
+        let mut adopted = HashSet::new();
+        if !snapshot.positions.is_empty() {
+            match self.exchange.get_positions().await {
+                Ok(held) => {
+                    for record in &snapshot.positions {
+                        let Some(qty) = held
+                            .iter()
+                            .find(|p| p.symbol == record.symbol)
+                            .map(|p| p.qty)
+                            .filter(|qty| *qty > Decimal::ZERO)
+                        else {
+                            continue;
+                        };
+                        if self.tracker.has_position(&record.symbol) {
+                            continue;
+                        }
+                        let position = record.to_position(qty, &open_ids);
+                        info!(
+                            "♻️ [ORDER-GUARD] Tracking {} {} again (SL ${:.8}, TP ${:.8}, TP leg {:?})",
+                            qty,
+                            record.symbol,
+                            position.stop_loss,
+                            position.take_profit,
+                            position.open_order_id
+                        );
+                        adopted.extend(position.open_order_id.clone());
+                        adopted.extend(position.stop_order_id.clone());
+                        self.tracker.add_position(position);
+                    }
+                }
+                Err(e) => warn!(
+                    "⚠️ [ORDER-GUARD] Cannot read venue positions - snapshot positions not restored: {}",
+                    e
+                ),
+            }
+        }
+        for record in &snapshot.orders {
+            if !open_ids.contains(record.order_id.as_str()) {
+                continue;
+            }
+            if !record.side.eq_ignore_ascii_case("buy")
+                && !self.tracker.has_position(&record.symbol)
+            {
+                continue;
+            }
+            self.tracker.add_pending_order(record.to_pending());
+            adopted.insert(record.order_id.clone());
+        }
+        adopted
+    }
+
+    /// Re-arm the switch and save the snapshot every `refresh_secs` until the
+    /// session stops; then disarm it and mark the snapshot clean.
+    pub fn start(self, shutdown: Shutdown) {
+        if !self.config.enabled {
+            return;
+        }
+        let period = Duration::from_secs(self.config.refresh_secs.max(1));
+        tokio::spawn(async move {
+            let _worker = shutdown.track_worker();
+            let mut tick = tokio::time::interval(period);
+            let mut supported = None;
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = tick.tick() => {
+                        self.arm(self.config.timeout_secs, &mut supported).await;
+                        self.save(false);
+                    }
+                }
+            }
+            if supported == Some(true) {
+                self.arm(0, &mut supported).await;
+            }
+            self.save(true);
+        });
+    }
+
+    async fn arm(&self, timeout_secs: u64, supported: &mut Option<bool>) {
+        match self.exchange.cancel_orders_after(timeout_secs).await {
+            Ok(armed) => {
+                if *supported != Some(armed) {
+                    match (armed, timeout_secs) {
+                        (true, 0) => info!("🛡️ [ORDER-GUARD] Cancel-on-disconnect disarmed"),
+                        (true, _) => info!(
+                            "🛡️ [ORDER-GUARD] Cancel-on-disconnect armed on {} ({}s)",
+                            self.exchange.name(),
+                            timeout_secs
+                        ),
+                        (false, _) => info!(
+                            "🛡️ [ORDER-GUARD] {} has no cancel-on-disconnect - relying on the startup sweep",
+                            self.exchange.name()
+                        ),
+                    }
+                }
+                *supported = Some(armed);
+            }
+            Err(e) => warn!("⚠️ [ORDER-GUARD] Failed to arm cancel-on-disconnect: {}", e),
+        }
+    }
+
+    fn save(&self, clean_shutdown: bool) {
+        let snapshot = TrackerSnapshot {
+            clean_shutdown,
+            ..TrackerSnapshot::capture(&self.tracker, Utc::now())
+        };
+        if let Err(e) = snapshot.save(Path::new(&self.config.snapshot_path)) {
+            warn!("⚠️ [ORDER-GUARD] Failed to save tracker snapshot: {}", e);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Unit tests for cancel-on-disconnect - snapshot persistence and the startup
+//! reconciliation of open orders.
+
+#[cfg(test)]
+mod cancel_on_disconnect_tests {
+    use crate::config::CancelOnDisconnectConfig;
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::cancel_on_disconnect::*;
+    use crate::services::position_monitor::PositionTracker;
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    /// Venue with a fixed open-order book and positions.
+    fn order_book(open: Option<Vec<OpenOrder>>, positions: Vec<Position>) -> Arc<MockExchange> {
+        Arc::new(
+            MockExchange::new("book")
+                .with_open_orders(open)
+                .with_positions(positions),
+        )
+    }
+
+    fn saved_at() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn open(id: &str, side: &str, age_secs: i64) -> OpenOrder {
+        OpenOrder {
+            id: id.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: side.to_string(),
+            created_at: Some(saved_at() - Duration::seconds(age_secs)),
+        }
+    }
+
+    fn snapshot() -> TrackerSnapshot {
+        TrackerSnapshot {
+            saved_at: saved_at(),
+            pending_orders: vec!["tracked".to_string()],
+            exit_orders: vec!["tp".to_string()],
+            clean_shutdown: false,
+            orders: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("autohedge_cod_{}", std::process::id()));
+        dir.join(name)
+    }
+
+    // ============= Snapshot Tests =============
+
+    #[test]
+    fn test_orphans_are_old_or_tracked_buys() {
+        let book = vec![
+            open("old", "buy", 30),
+            // Placed after the snapshot, but tracked by it
+            open("tracked", "BUY", -5),
+            // Placed after the last snapshot by nobody we know
+            open("new", "buy", -5),
+            open("tp", "sell", 30),
+        ];
+        let ids: Vec<&str> = snapshot()
+            .orphans(&book)
+            .iter()
+            .map(|o| o.id.as_str())
+            .collect();
+        assert_eq!(ids, ["old", "tracked"]);
+    }
+
+    #[test]
+    fn test_clean_shutdown_has_no_orphans() {
+        let clean = TrackerSnapshot {
+            clean_shutdown: true,
+            ..snapshot()
+        };
+        assert!(clean.orphans(&[open("old", "buy", 30)]).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_order_policies() {
+        let book = vec![
+            open("old", "buy", 30),
+            open("new", "buy", -5),
+            open("tp", "sell", 30),
+            open("adopted", "buy", 30),
+        ];
+        let adopted = HashSet::from(["adopted".to_string()]);
+        let ids = |policy| -> Vec<String> {
+            snapshot()
+                .to_cancel(&book, &adopted, policy)
+                .iter()
+                .map(|o| o.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(UnknownOrderPolicy::OrphanedBuys), ["old"]);
+        assert_eq!(ids(UnknownOrderPolicy::AllBuys), ["old", "new"]);
+        assert_eq!(ids(UnknownOrderPolicy::All), ["old", "new", "tp"]);
+        assert!(ids(UnknownOrderPolicy::Keep).is_empty());
+        assert_eq!(
+            UnknownOrderPolicy::parse(" All_Buys "),
+            Some(UnknownOrderPolicy::AllBuys)
+        );
+        assert_eq!(UnknownOrderPolicy::parse("sells"), None);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let path = temp_path("round_trip.json");
+        snapshot().save(&path).unwrap();
+        assert_eq!(TrackerSnapshot::load(&path), Some(snapshot()));
+        assert!(!path.with_extension("tmp").exists());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(TrackerSnapshot::load(&path), None);
+        assert_eq!(TrackerSnapshot::load(&temp_path("missing.json")), None);
+    }
+
+    // ============= Startup Sweep Tests =============
+
+    #[tokio::test]
+    async fn test_sweep_cancels_orphaned_buys() {
+        let path = temp_path("sweep.json");
+        snapshot().save(&path).unwrap();
+        let exchange = order_book(
+            Some(vec![open("old", "buy", 30), open("tp", "sell", 30)]),
+            Vec::new(),
+        );
+        let config = CancelOnDisconnectConfig {
+            snapshot_path: path.to_string_lossy().into_owned(),
+            ..CancelOnDisconnectConfig::default()
+        };
+
+        let guard = CancelOnDisconnect::new(exchange.clone(), PositionTracker::new(), &config);
+        assert_eq!(guard.sweep().await, 1);
+        assert_eq!(exchange.cancelled(), ["old"]);
+
+        // Sweep disabled
+        let guard = CancelOnDisconnect::new(
+            exchange.clone(),
+            PositionTracker::new(),
+            &CancelOnDisconnectConfig {
+                startup_sweep: false,
+                ..config.clone()
+            },
+        );
+        assert_eq!(guard.sweep().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_tracks_known_orders_again() {
+        let path = temp_path("reconcile.json");
+        let record = |id: &str, side: &str, price: f64| OrderRecord {
+            order_id: id.to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: side.to_string(),
+            limit_price: dec(price),
+            qty: dec(0.5),
+            filled_qty: dec(0.0),
+            created_at: saved_at().to_rfc3339(),
+            stop_loss: Some(dec(95.0)),
+            take_profit: Some(dec(110.0)),
+            bracket: false,
+            correlation_id: Some("corr-1".to_string()),
+        };
+        TrackerSnapshot {
+            orders: vec![record("entry", "buy", 100.0), record("tp", "sell", 110.0)],
+            positions: vec![PositionRecord {
+                symbol: "BTC/USD".to_string(),
+                entry_price: dec(100.0),
+                stop_loss: dec(95.0),
+                take_profit: dec(110.0),
+                entry_time: saved_at().to_rfc3339(),
+                highest_price: dec(104.0),
+                trailing_stop_active: false,
+                trailing_stop_price: dec(95.0),
+                open_order_id: Some("tp".to_string()),
+                stop_order_id: Some("stop-gone".to_string()),
+                bracket_managed: false,
+                correlation_id: Some("corr-1".to_string()),
+            }],
+            ..snapshot()
+        }
+        .save(&path)
+        .unwrap();
+        let symbol_order = |id: &str, side: &str, age_secs: i64| OpenOrder {
+            symbol: "BTC/USD".to_string(),
+            ..open(id, side, age_secs)
+        };
+        let exchange = order_book(
+            Some(vec![
+                symbol_order("entry", "buy", 30),
+                symbol_order("tp", "sell", 30),
+                symbol_order("old", "buy", 60),
+                symbol_order("manual", "buy", -5),
+            ]),
+            vec![Position {
+                symbol: "BTC/USD".to_string(),
+                qty: dec(0.4),
+                avg_entry_price: Some(dec(100.0)),
+            }],
+        );
+        let tracker = PositionTracker::new();
+        let config = CancelOnDisconnectConfig {
+            snapshot_path: path.to_string_lossy().into_owned(),
+            ..CancelOnDisconnectConfig::default()
+        };
+
+        let guard = CancelOnDisconnect::new(exchange.clone(), tracker.clone(), &config);
+        assert_eq!(guard.sweep().await, 1);
+        // Known orders survive; only the unknown pre-snapshot buy goes
+        assert_eq!(exchange.cancelled(), ["old"]);
+
+        let position = tracker.get_position("BTC/USD").unwrap();
+        assert_eq!(position.filled_qty, dec(0.4));
+        assert_eq!(position.take_profit, dec(110.0));
+        assert_eq!(position.stop_loss, dec(95.0));
+        assert_eq!(position.open_order_id.as_deref(), Some("tp"));
+        // Its native stop is gone: left for the monitor to re-place
+        assert!(position.stop_order_id.is_none());
+
+        let mut pending = tracker.get_all_pending_orders();
+        pending.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].order_id, "entry");
+        assert_eq!(pending[0].take_profit, Some(dec(110.0)));
+        assert_eq!(pending[0].stop_loss, Some(dec(95.0)));
+        assert_eq!(pending[0].remaining_qty, dec(0.5));
+        assert_eq!(pending[1].order_id, "tp");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_skipped_when_venue_cannot_list_orders() {
+        let path = temp_path("unlisted.json");
+        snapshot().save(&path).unwrap();
+        let exchange = order_book(None, Vec::new());
+        let config = CancelOnDisconnectConfig {
+            snapshot_path: path.to_string_lossy().into_owned(),
+            ..CancelOnDisconnectConfig::default()
+        };
+        let guard = CancelOnDisconnect::new(exchange.clone(), PositionTracker::new(), &config);
+        assert_eq!(guard.sweep().await, 0);
+        assert!(exchange.cancelled().is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Per-strategy capital buckets.
+//!
+//! With `capital_allocation.enabled` the account's capital (buying power plus
+//! what is already deployed) is split by `capital_allocation.buckets`, keyed
+//! by strategy mode. A symbol's entries belong to its `strategy_mode_for`
+//! bucket: they are sized against what that bucket has left, and the sized
+//! notional is reserved with `CapitalAllocator::reserve` until the order is
+//! tracked, so concurrent entries cannot overdraw it. One strategy can no
+//! longer consume the capital meant for the others.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rust_decimal::Decimal;
+
+use crate::config::{AppConfig, CapitalAllocationConfig};
+use crate::data::store::MarketStore;
+use crate::money::dec;
+use crate::services::position_monitor::PositionTracker;
+
+/// Capital deployed per strategy bucket (reporting currency): open positions
+/// at their entry price plus working buy orders at their limit.
+pub fn bucket_usage(
+    config: &AppConfig,
+    tracker: &PositionTracker,
+    store: &MarketStore,
+) -> HashMap<String, Decimal> {
+    // Without a rate the notional is taken as is
+    let fx = |symbol: &str| dec(store.quote_fx_rate(config, symbol).unwrap_or(1.0));
+    let mut usage: HashMap<String, Decimal> = HashMap::new();
+    for p in tracker.get_all_positions() {
+        *usage
+            .entry(config.strategy_mode_for(&p.symbol))
+            .or_default() += p.qty * p.entry_price * fx(&p.symbol);
+    }
+    for o in tracker.get_all_pending_orders() {
+        if o.side.eq_ignore_ascii_case("buy") {
+            *usage
+                .entry(config.strategy_mode_for(&o.symbol))
+                .or_default() += o.remaining_qty * o.limit_price * fx(&o.symbol);
+        }
+    }
+    usage
+}
+
+/// Why an entry got no capital.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CapitalDenied {
+    /// The strategy mode has no bucket
+    NoBucket,
+    /// The bucket has only this much left
+    Exhausted { free: Decimal },
+}
+
+impl std::fmt::Display for CapitalDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CapitalDenied::NoBucket => write!(f, "no capital bucket"),
+            CapitalDenied::Exhausted { free } => {
+                write!(f, "bucket exhausted (${:.2} left)", free)
+            }
+        }
+    }
+}
+
+/// Capital reserved by entries in flight, per bucket.
+type Reserved = Arc<Mutex<HashMap<String, Decimal>>>;
+
+/// In-flight reservations per bucket, shared by every entry task.
+#[derive(Clone, Default)]
+pub struct CapitalAllocator {
+    reserved: Reserved,
+}
+
+impl CapitalAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What `bucket` has left: its share of `buying_power` plus all deployed
+    /// capital, less its own deployed and reserved capital. Without
+    /// allocation this is `buying_power`.
+    pub fn available(
+        &self,
+        config: &CapitalAllocationConfig,
+        bucket: &str,
+        buying_power: Decimal,
+        usage: &HashMap<String, Decimal>,
+    ) -> Result<Decimal, CapitalDenied> {
+        if !config.enabled {
+            return Ok(buying_power);
+        }
+        let reserved = self.reserved.lock().unwrap();
+        Self::free(config, bucket, buying_power, usage, &reserved).map(|f| f.min(buying_power))
+    }
+
+    /// Reserve `amount` of `bucket` for an entry; released when the
+    /// reservation drops (the order is tracked, and counted by
+    /// `bucket_usage`, by then).
+    pub fn reserve(
+        &self,
+        config: &CapitalAllocationConfig,
+        bucket: &str,
+        amount: Decimal,
+        buying_power: Decimal,
+        usage: &HashMap<String, Decimal>,
+    ) -> Result<CapitalReservation, CapitalDenied> {
+        if !config.enabled {
+            return Ok(CapitalReservation { held: None });
+        }
+        let mut reserved = self.reserved.lock().unwrap();
+        let free = Self::free(config, bucket, buying_power, usage, &reserved)?;
+        if amount > free {
+            return Err(CapitalDenied::Exhausted { free });
+        }
+        *reserved.entry(bucket.to_string()).or_default() += amount;
+        Ok(CapitalReservation {
+            held: Some((self.reserved.clone(), bucket.to_string(), amount)),
+        })
+    }
+
+    fn free(
+        config: &CapitalAllocationConfig,
+        bucket: &str,
+        buying_power: Decimal,
+        usage: &HashMap<String, Decimal>,
+        reserved: &HashMap<String, Decimal>,
+    ) -> Result<Decimal, CapitalDenied> {
+        let pct = config
+            .buckets
+            .iter()
+            .find(|(mode, _)| mode.eq_ignore_ascii_case(bucket))
+            .map(|(_, pct)| *pct)
+            .ok_or(CapitalDenied::NoBucket)?;
+        let total = buying_power + usage.values().copied().sum::<Decimal>();
+        let cap = total * dec(pct) / Decimal::ONE_HUNDRED;
+        let used = usage.get(bucket).copied().unwrap_or_default()
+            + reserved.get(bucket).copied().unwrap_or_default();
+        Ok((cap - used).max(Decimal::ZERO))
+    }
+}
+
+/// Capital held for an entry in flight.
+pub struct CapitalReservation {
+    held: Option<(Reserved, String, Decimal)>,
+}
+
+impl Drop for CapitalReservation {
+    fn drop(&mut self) {
+        if let Some((reserved, bucket, amount)) = self.held.take() {
+            if let Some(r) = reserved.lock().unwrap().get_mut(&bucket) {
+                *r -= amount;
+            }
+        }
+    }
+}
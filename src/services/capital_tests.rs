@@ -0,0 +1,185 @@
+//! Unit tests for per-strategy capital buckets.
+
+#[cfg(test)]
+mod capital_tests {
+    use crate::config::{AppConfig, CapitalAllocationConfig};
+    use crate::data::store::MarketStore;
+    use crate::money::dec;
+    use crate::services::capital::*;
+    use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+
+    const CONFIG_YAML: &str = r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides:
+  ETH/USD:
+    strategy_mode: "llm"
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+capital_allocation:
+  enabled: true
+  buckets:
+    hft: 60
+    LLM: 40
+"#;
+
+    fn buckets() -> CapitalAllocationConfig {
+        AppConfig::parse(CONFIG_YAML).unwrap().capital_allocation
+    }
+
+    fn usage(hft: f64, llm: f64) -> HashMap<String, Decimal> {
+        HashMap::from([("hft".to_string(), dec(hft)), ("llm".to_string(), dec(llm))])
+    }
+
+    // ============= Allocator Tests =============
+
+    #[test]
+    fn test_available_is_bucket_share_less_deployed() {
+        let allocator = CapitalAllocator::new();
+        // 1000 total: hft may hold 600, llm 400
+        let deployed = usage(200.0, 100.0);
+        let free = allocator.available(&buckets(), "hft", dec(700.0), &deployed);
+        assert_eq!(free, Ok(dec(400.0)));
+        let free = allocator.available(&buckets(), "llm", dec(700.0), &deployed);
+        assert_eq!(free, Ok(dec(300.0)));
+        // Never more than the account can pay for
+        let free = allocator.available(&buckets(), "hft", dec(700.0), &usage(0.0, 300.0));
+        assert_eq!(free, Ok(dec(600.0)));
+    }
+
+    #[test]
+    fn test_overdrawn_or_unlisted_buckets_are_refused() {
+        let allocator = CapitalAllocator::new();
+        let free = allocator.available(&buckets(), "hft", dec(300.0), &usage(700.0, 0.0));
+        assert_eq!(free, Ok(Decimal::ZERO));
+        assert_eq!(
+            allocator
+                .available(&buckets(), "hybrid", dec(1000.0), &HashMap::new())
+                .unwrap_err(),
+            CapitalDenied::NoBucket
+        );
+    }
+
+    #[test]
+    fn test_disabled_allocation_passes_buying_power_through() {
+        let allocator = CapitalAllocator::new();
+        let config = CapitalAllocationConfig::default();
+        assert_eq!(
+            allocator.available(&config, "hybrid", dec(500.0), &usage(900.0, 0.0)),
+            Ok(dec(500.0))
+        );
+        assert!(allocator
+            .reserve(&config, "hybrid", dec(5000.0), dec(500.0), &HashMap::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_reservations_hold_capital_until_dropped() {
+        let allocator = CapitalAllocator::new();
+        let usage = HashMap::new();
+        let first = allocator
+            .reserve(&buckets(), "hft", dec(400.0), dec(1000.0), &usage)
+            .unwrap();
+        // 200 of the 600 left: a second 400 would overdraw
+        assert_eq!(
+            allocator
+                .reserve(&buckets(), "hft", dec(400.0), dec(1000.0), &usage)
+                .err(),
+            Some(CapitalDenied::Exhausted { free: dec(200.0) })
+        );
+        assert_eq!(
+            allocator.available(&buckets(), "hft", dec(1000.0), &usage),
+            Ok(dec(200.0))
+        );
+        // Other buckets are untouched
+        assert_eq!(
+            allocator.available(&buckets(), "llm", dec(1000.0), &usage),
+            Ok(dec(400.0))
+        );
+
+        drop(first);
+        assert!(allocator
+            .reserve(&buckets(), "hft", dec(400.0), dec(1000.0), &usage)
+            .is_ok());
+    }
+
+    // ============= Usage Tests =============
+
+    #[test]
+    fn test_bucket_usage_follows_symbol_strategy_mode() {
+        let config = AppConfig::parse(CONFIG_YAML).unwrap();
+        let store = MarketStore::new(10);
+        let tracker = PositionTracker::new();
+        tracker.add_position(PositionInfo {
+            symbol: "BTC/USD".to_string(),
+            entry_price: dec(100.0),
+            qty: dec(2.0),
+            filled_qty: dec(2.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(99.0),
+            take_profit: dec(101.0),
+            entry_time: chrono::Utc::now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: None,
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(100.0),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(99.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
+        });
+        tracker.add_pending_order(PendingOrder {
+            order_id: "o1".to_string(),
+            symbol: "ETH/USD".to_string(),
+            side: "buy".to_string(),
+            limit_price: dec(50.0),
+            qty: dec(3.0),
+            filled_qty: dec(1.0),
+            remaining_qty: dec(2.0),
+            created_at: "2026-10-16T12:00:00Z".to_string(),
+            stop_loss: None,
+            take_profit: None,
+            last_check_time: None,
+            bracket: false,
+            correlation_id: None,
+        });
+
+        let usage = bucket_usage(&config, &tracker, &store);
+        assert_eq!(usage.get("hft"), Some(&dec(200.0)));
+        assert_eq!(usage.get("llm"), Some(&dec(100.0)));
+    }
+}
@@ -0,0 +1,122 @@
+//! Server-side SVG sparkline/area charts for `GET /charts/{metric}.svg`.
+
+use chrono::{DateTime, Utc};
+
+pub const CHART_WIDTH: u32 = 640;
+pub const CHART_HEIGHT: u32 = 200;
+
+const PAD_X: f64 = 10.0;
+const PAD_TOP: f64 = 28.0;
+const PAD_BOTTOM: f64 = 22.0;
+
+/// Compact value label (no trailing noise on large or tiny numbers).
+pub fn format_value(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1000.0 {
+        format!("{:.0}", value)
+    } else if abs >= 1.0 {
+        format!("{:.2}", value)
+    } else {
+        format!("{:.4}", value)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn time_label(ts: i64) -> String {
+    DateTime::<Utc>::from_timestamp(ts, 0)
+        .map(|t| t.format("%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Plot coordinates of `points` (oldest first) inside a `width` x `height`
+/// chart. A flat series is drawn through the vertical middle.
+pub fn chart_coords(points: &[(i64, f64)], width: u32, height: u32) -> Vec<(f64, f64)> {
+    let (w, h) = (
+        width as f64 - 2.0 * PAD_X,
+        height as f64 - PAD_TOP - PAD_BOTTOM,
+    );
+    let min = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    let last = points.len().saturating_sub(1).max(1) as f64;
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, v))| {
+            let x = PAD_X + w * i as f64 / last;
+            let frac = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            };
+            (x, PAD_TOP + h * (1.0 - frac))
+        })
+        .collect()
+}
+
+/// Area chart with a line on top, a title with last/min/max and the time span.
+pub fn render_svg(title: &str, points: &[(i64, f64)], width: u32, height: u32) -> String {
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="monospace" font-size="12"><rect width="100%" height="100%" fill="#ffffff"/>"##,
+        w = width,
+        h = height
+    );
+
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        svg.push_str(&format!(
+            r##"<text x="{}" y="18" fill="#333">{}</text><text x="{}" y="{}" fill="#999" text-anchor="middle">no data yet</text></svg>"##,
+            PAD_X,
+            escape(title),
+            width / 2,
+            height / 2
+        ));
+        return svg;
+    };
+
+    let min = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    svg.push_str(&format!(
+        r##"<text x="{}" y="18" fill="#333">{}  last {}  min {}  max {}</text>"##,
+        PAD_X,
+        escape(title),
+        format_value(last.1),
+        format_value(min),
+        format_value(max)
+    ));
+
+    let mut coords = chart_coords(points, width, height);
+    if coords.len() == 1 {
+        // A single sample is drawn as a flat line across the chart
+        coords.push((width as f64 - PAD_X, coords[0].1));
+    }
+    let line: Vec<String> = coords
+        .iter()
+        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+        .collect();
+    let baseline = height as f64 - PAD_BOTTOM;
+    svg.push_str(&format!(
+        r##"<polygon points="{:.1},{:.1} {} {:.1},{:.1}" fill="#4a90d9" fill-opacity="0.2"/><polyline points="{}" fill="none" stroke="#4a90d9" stroke-width="1.5"/>"##,
+        coords[0].0,
+        baseline,
+        line.join(" "),
+        coords[coords.len() - 1].0,
+        baseline,
+        line.join(" ")
+    ));
+
+    svg.push_str(&format!(
+        r##"<text x="{}" y="{}" fill="#999">{}</text><text x="{}" y="{}" fill="#999" text-anchor="end">{}</text></svg>"##,
+        PAD_X,
+        height as f64 - 6.0,
+        time_label(first.0),
+        width as f64 - PAD_X,
+        height as f64 - 6.0,
+        time_label(last.0)
+    ));
+    svg
+}
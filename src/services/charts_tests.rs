@@ -0,0 +1,54 @@
+//! Unit tests for server-side SVG chart rendering.
+
+#[cfg(test)]
+mod charts_tests {
+    use crate::services::charts::*;
+
+    // ============= Layout Tests =============
+
+    #[test]
+    fn test_coords_span_chart_and_invert_y() {
+        let coords = chart_coords(&[(1, 10.0), (2, 20.0), (3, 15.0)], 640, 200);
+        assert_eq!(coords.len(), 3);
+        assert!(coords[0].0 < coords[1].0 && coords[1].0 < coords[2].0);
+        // Higher values are drawn closer to the top
+        assert!(coords[1].1 < coords[2].1 && coords[2].1 < coords[0].1);
+    }
+
+    #[test]
+    fn test_flat_series_is_centered() {
+        let coords = chart_coords(&[(1, 5.0), (2, 5.0)], 640, 200);
+        assert_eq!(coords[0].1, coords[1].1);
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(12345.678), "12346");
+        assert_eq!(format_value(12.345), "12.35");
+        assert_eq!(format_value(0.012345), "0.0123");
+    }
+
+    // ============= Render Tests =============
+
+    #[test]
+    fn test_render_series() {
+        let svg = render_svg("equity", &[(0, 100.0), (60, 110.0)], 640, 200);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("<polyline"));
+        assert!(svg.contains("<polygon"));
+        assert!(svg.contains("last 110.00"));
+        assert!(svg.contains("01-01 00:00"));
+    }
+
+    #[test]
+    fn test_render_empty_and_single_point() {
+        let svg = render_svg("llm_latency_ms", &[], 640, 200);
+        assert!(svg.contains("no data yet"));
+        assert!(!svg.contains("<polyline"));
+
+        let svg = render_svg("open_positions", &[(0, 2.0)], 640, 200);
+        assert!(svg.contains("<polyline"));
+        assert!(svg.contains("last 2.00"));
+    }
+}
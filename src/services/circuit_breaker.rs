@@ -0,0 +1,260 @@
+//! Kill switch for runaway losses.
+//!
+//! Watches account equity, round-trip results and order rejections. When the
+//! daily drawdown, the losing streak or the order error rate crosses its limit
+//! the breaker trips: it publishes `SystemEvent::Halt`, the execution engines
+//! refuse new buys and (with `flatten_on_trip`) the position monitor closes
+//! every tracked position. Trading stays halted until an operator calls
+//! `POST /resume`. Halts published by other services (a multi-leg trade whose
+//! fill could not be established) are adopted so the same reset clears them.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, CircuitBreakerConfig};
+use crate::events::{AccountEvent, Event, SystemEvent};
+use crate::money::float;
+
+#[derive(Default)]
+struct BreakerState {
+    /// Why the breaker tripped; None while trading is allowed
+    tripped: Option<String>,
+    /// UTC day the drawdown baseline belongs to
+    day: Option<NaiveDate>,
+    day_start_equity: Option<Decimal>,
+    last_equity: Option<Decimal>,
+    /// Entry price of open positions, to score round trips
+    entries: HashMap<String, Decimal>,
+    consecutive_losses: usize,
+    /// Recent order submissions (true = rejected), newest last
+    outcomes: VecDeque<bool>,
+}
+
+/// Trips on daily drawdown, consecutive losses or order error rate.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    event_bus: EventBus,
+    state: Arc<Mutex<BreakerState>>,
+    /// Fills arrive from a user-data stream (see `TradeReporter::with_streamed_fills`)
+    streamed_fills: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(event_bus: EventBus, config: &AppConfig) -> Self {
+        Self {
+            config: config.circuit_breaker.clone(),
+            event_bus,
+            state: Arc::new(Mutex::new(BreakerState::default())),
+            streamed_fills: false,
+        }
+    }
+
+    /// Score round trips from user-data stream fills instead of submission acks.
+    pub fn with_streamed_fills(mut self, streamed_fills: bool) -> Self {
+        self.streamed_fills = streamed_fills;
+        self
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.state.lock().unwrap().tripped.is_some()
+    }
+
+    pub fn trip_reason(&self) -> Option<String> {
+        self.state.lock().unwrap().tripped.clone()
+    }
+
+    /// Losing round trips in a row since the last win (or reset).
+    pub fn consecutive_losses(&self) -> usize {
+        self.state.lock().unwrap().consecutive_losses
+    }
+
+    /// Drawdown (%) from the start-of-day portfolio value, if known.
+    pub fn daily_drawdown_pct(&self) -> Option<f64> {
+        let state = self.state.lock().unwrap();
+        drawdown_pct(state.day_start_equity?, state.last_equity?)
+    }
+
+    /// Feed one bus event. Returns the halt to publish if this event tripped the breaker.
+    pub fn on_event(&self, event: &Event, now: DateTime<Utc>) -> Option<SystemEvent> {
+        if !self.config.enabled {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+        match event {
+            Event::Account(AccountEvent::BalanceUpdated {
+                portfolio_value: Some(equity),
+                ..
+            }) if *equity > Decimal::ZERO => {
+                // The day starts from the last value seen before midnight UTC
+                let today = now.date_naive();
+                if state.day != Some(today) {
+                    state.day = Some(today);
+                    state.day_start_equity = Some(state.last_equity.unwrap_or(*equity));
+                }
+                state.last_equity = Some(*equity);
+            }
+            // Deposits/withdrawals are not trading results: move the baseline with
+            // the transfers the balance audit attributes
+            Event::Account(AccountEvent::BalanceAudited(change)) if !change.transfer.is_zero() => {
+                if let Some(start) = state.day_start_equity.as_mut() {
+                    *start += change.transfer;
+                }
+            }
+            Event::Execution(report) => {
+                // Submission outcomes feed the error rate; streamed fill updates don't
+                if !report.is_fill_update() {
+                    if report.status.eq_ignore_ascii_case("rejected") {
+                        self.record_outcome(&mut state, true);
+                    } else if report.counts_as_fill(false) {
+                        self.record_outcome(&mut state, false);
+                    }
+                }
+                // With partial fills the first sell fill closes out the round trip
+                if report.counts_as_fill(self.streamed_fills) {
+                    let price = report.price.unwrap_or_default();
+                    if report.side == "buy" {
+                        state.entries.insert(report.symbol.clone(), price);
+                    } else if let Some(entry) = state.entries.remove(&report.symbol) {
+                        if price > Decimal::ZERO && price < entry {
+                            state.consecutive_losses += 1;
+                        } else {
+                            state.consecutive_losses = 0;
+                        }
+                    }
+                }
+            }
+            // Halts raised elsewhere (an unreconciled leg) are shown and reset here too
+            Event::System(SystemEvent::Halt { reason, .. }) => {
+                state.tripped.get_or_insert_with(|| reason.clone());
+                return None;
+            }
+            _ => return None,
+        }
+
+        if state.tripped.is_some() {
+            return None;
+        }
+        let reason = self.check_limits(&state)?;
+        error!(
+            "🛑 [CIRCUIT] Breaker tripped: {} - new entries halted",
+            reason
+        );
+        state.tripped = Some(reason.clone());
+        Some(SystemEvent::Halt {
+            reason,
+            flatten: self.config.flatten_on_trip,
+            timestamp: now.to_rfc3339(),
+        })
+    }
+
+    fn record_outcome(&self, state: &mut BreakerState, rejected: bool) {
+        state.outcomes.push_back(rejected);
+        while state.outcomes.len() > self.config.error_rate_window.max(1) {
+            state.outcomes.pop_front();
+        }
+    }
+
+    fn check_limits(&self, state: &BreakerState) -> Option<String> {
+        let cfg = &self.config;
+        if cfg.max_daily_drawdown_pct > 0.0 {
+            if let (Some(start), Some(equity)) = (state.day_start_equity, state.last_equity) {
+                if let Some(dd) = drawdown_pct(start, equity) {
+                    if dd >= cfg.max_daily_drawdown_pct {
+                        return Some(format!(
+                            "daily drawdown {:.2}% >= {:.2}% (${:.2} -> ${:.2})",
+                            dd, cfg.max_daily_drawdown_pct, start, equity
+                        ));
+                    }
+                }
+            }
+        }
+        if cfg.max_consecutive_losses > 0 && state.consecutive_losses >= cfg.max_consecutive_losses
+        {
+            return Some(format!(
+                "{} consecutive losing trades",
+                state.consecutive_losses
+            ));
+        }
+        // Only judged over a full window so a single early rejection can't trip it
+        let window = cfg.error_rate_window.max(1);
+        if cfg.max_order_error_rate > 0.0 && state.outcomes.len() >= window {
+            let rejected = state.outcomes.iter().filter(|r| **r).count();
+            let rate = rejected as f64 / state.outcomes.len() as f64;
+            if rate >= cfg.max_order_error_rate {
+                return Some(format!(
+                    "order error rate {:.0}% over the last {} orders",
+                    rate * 100.0,
+                    state.outcomes.len()
+                ));
+            }
+        }
+        None
+    }
+
+    /// Manual reset. Clears the counters and restarts the drawdown baseline from
+    /// the current equity, then publishes `SystemEvent::Resume`. Returns the
+    /// reason the breaker had tripped, or None if it was not tripped.
+    pub fn resume(&self) -> Option<String> {
+        let reason = {
+            let mut state = self.state.lock().unwrap();
+            let reason = state.tripped.take()?;
+            state.consecutive_losses = 0;
+            state.outcomes.clear();
+            state.day_start_equity = state.last_equity;
+            reason
+        };
+        info!("▶️ [CIRCUIT] Breaker reset by operator (was: {})", reason);
+        self.event_bus
+            .publish(Event::System(SystemEvent::Resume {
+                timestamp: Utc::now().to_rfc3339(),
+            }))
+            .ok();
+        Some(reason)
+    }
+
+    pub async fn start(&self) {
+        if !self.config.enabled {
+            info!("[CIRCUIT] Circuit breaker disabled");
+            return;
+        }
+        let mut rx = self.event_bus.subscribe("circuit_breaker");
+        let breaker = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+
+        tokio::spawn(async move {
+            info!(
+                "🛡️ Circuit Breaker Started (drawdown {:.1}%, losses {}, error rate {:.0}%)",
+                breaker.config.max_daily_drawdown_pct,
+                breaker.config.max_consecutive_losses,
+                breaker.config.max_order_error_rate * 100.0
+            );
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => {
+                            if let Some(halt) = breaker.on_event(&event, Utc::now()) {
+                                breaker.event_bus.publish(Event::System(halt)).ok();
+                            }
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            warn!("[CIRCUIT] Lagged behind the bus, skipped {} events", n);
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+}
+
+/// Percentage drop from `start` to `equity` (negative when up on the day).
+fn drawdown_pct(start: Decimal, equity: Decimal) -> Option<f64> {
+    (start > Decimal::ZERO).then(|| float((start - equity) / start * Decimal::ONE_HUNDRED))
+}
@@ -0,0 +1,270 @@
+//! Unit tests for the drawdown / losing streak / error rate circuit breaker.
+
+#[cfg(test)]
+mod circuit_breaker_tests {
+    use crate::bus::EventBus;
+    use crate::config::AppConfig;
+    use crate::events::{AccountEvent, BalanceChangeEvent, Event, ExecutionReport, SystemEvent};
+    use crate::money::dec;
+    use crate::services::circuit_breaker::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn breaker(config: &AppConfig) -> CircuitBreaker {
+        CircuitBreaker::new(EventBus::new(16), config)
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn equity(value: Decimal) -> Event {
+        Event::Account(AccountEvent::BalanceUpdated {
+            cash: None,
+            buying_power: None,
+            portfolio_value: Some(value),
+            timestamp: String::new(),
+        })
+    }
+
+    fn execution(symbol: &str, side: &str, status: &str, price: Decimal) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: "1".to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(price),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    // ============= Drawdown Tests =============
+
+    #[test]
+    fn test_trips_on_daily_drawdown() {
+        let cb = breaker(&test_config());
+        assert!(cb.on_event(&equity(dec(1000.0)), at(2, 9)).is_none());
+        assert!(cb.on_event(&equity(dec(960.0)), at(2, 10)).is_none());
+
+        let halt = cb
+            .on_event(&equity(dec(949.0)), at(2, 11))
+            .expect("should trip");
+        match halt {
+            SystemEvent::Halt {
+                reason, flatten, ..
+            } => {
+                assert!(reason.contains("daily drawdown"));
+                assert!(!flatten);
+            }
+            other => panic!("expected halt, got {:?}", other),
+        }
+        assert!(cb.is_tripped());
+
+        // Already tripped: no repeated halt
+        assert!(cb.on_event(&equity(dec(900.0)), at(2, 12)).is_none());
+    }
+
+    #[test]
+    fn test_drawdown_baseline_resets_each_day() {
+        let cb = breaker(&test_config());
+        cb.on_event(&equity(dec(1000.0)), at(2, 9));
+        cb.on_event(&equity(dec(960.0)), at(2, 23));
+        // New day starts from 960, so 930 is only ~3.1% down
+        assert!(cb.on_event(&equity(dec(930.0)), at(3, 1)).is_none());
+        assert!((cb.daily_drawdown_pct().unwrap() - 3.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_withdrawal_is_not_drawdown() {
+        let cb = breaker(&test_config());
+        cb.on_event(&equity(dec(1000.0)), at(2, 9));
+        cb.on_event(
+            &Event::Account(AccountEvent::BalanceAudited(BalanceChangeEvent {
+                change: dec(-200.0),
+                transfer: dec(-200.0),
+                kind: "withdrawal".to_string(),
+                ..Default::default()
+            })),
+            at(2, 10),
+        );
+        assert!(cb.on_event(&equity(dec(800.0)), at(2, 11)).is_none());
+        assert!(!cb.is_tripped());
+    }
+
+    // ============= Loss Streak Tests =============
+
+    #[test]
+    fn test_trips_on_consecutive_losses() {
+        let mut config = test_config();
+        config.circuit_breaker.max_consecutive_losses = 3;
+        config.circuit_breaker.flatten_on_trip = true;
+        let cb = breaker(&config);
+        let now = at(2, 9);
+
+        for i in 0..2 {
+            cb.on_event(&execution("BTC/USD", "buy", "filled", dec(100.0)), now);
+            assert!(cb
+                .on_event(&execution("BTC/USD", "sell", "filled", dec(99.0)), now)
+                .is_none());
+            assert_eq!(cb.consecutive_losses(), i + 1);
+        }
+
+        // A win resets the streak
+        cb.on_event(&execution("BTC/USD", "buy", "filled", dec(100.0)), now);
+        cb.on_event(&execution("BTC/USD", "sell", "filled", dec(101.0)), now);
+        assert_eq!(cb.consecutive_losses(), 0);
+
+        let mut halt = None;
+        for _ in 0..3 {
+            cb.on_event(&execution("ETH/USD", "buy", "new", dec(10.0)), now);
+            halt = cb.on_event(&execution("ETH/USD", "sell", "filled", dec(9.5)), now);
+        }
+        match halt.expect("third loss should trip") {
+            SystemEvent::Halt {
+                reason, flatten, ..
+            } => {
+                assert!(reason.contains("3 consecutive losing trades"));
+                assert!(flatten);
+            }
+            other => panic!("expected halt, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_adopts_halts_published_elsewhere() {
+        let cb = breaker(&test_config());
+        let halt = Event::System(SystemEvent::Halt {
+            reason: "ETH/USD / BTC/USD pair legs unreconciled".to_string(),
+            flatten: false,
+            timestamp: String::new(),
+        });
+        assert!(cb.on_event(&halt, at(2, 9)).is_none());
+        assert!(cb.is_tripped());
+        assert_eq!(
+            cb.resume().as_deref(),
+            Some("ETH/USD / BTC/USD pair legs unreconciled")
+        );
+        assert!(!cb.is_tripped());
+    }
+
+    // ============= Error Rate Tests =============
+
+    #[test]
+    fn test_trips_on_order_error_rate_over_full_window() {
+        let mut config = test_config();
+        config.circuit_breaker.error_rate_window = 4;
+        let cb = breaker(&config);
+        let now = at(2, 9);
+
+        // Two rejections out of two is not judged until the window fills
+        assert!(cb
+            .on_event(&execution("BTC/USD", "buy", "rejected", dec(100.0)), now)
+            .is_none());
+        assert!(cb
+            .on_event(&execution("BTC/USD", "buy", "rejected", dec(100.0)), now)
+            .is_none());
+        assert!(cb
+            .on_event(&execution("BTC/USD", "buy", "filled", dec(100.0)), now)
+            .is_none());
+        let halt = cb.on_event(&execution("BTC/USD", "sell", "filled", dec(101.0)), now);
+        assert!(
+            matches!(halt, Some(SystemEvent::Halt { reason, .. }) if reason.contains("error rate 50%"))
+        );
+    }
+
+    #[test]
+    fn test_zero_limits_disable_checks() {
+        let mut config = test_config();
+        config.circuit_breaker.max_daily_drawdown_pct = 0.0;
+        config.circuit_breaker.max_order_error_rate = 0.0;
+        config.circuit_breaker.error_rate_window = 1;
+        let cb = breaker(&config);
+        cb.on_event(&equity(dec(1000.0)), at(2, 9));
+        assert!(cb.on_event(&equity(dec(100.0)), at(2, 10)).is_none());
+        assert!(cb
+            .on_event(
+                &execution("BTC/USD", "buy", "rejected", dec(1.0)),
+                at(2, 10)
+            )
+            .is_none());
+
+        config.circuit_breaker = Default::default();
+        config.circuit_breaker.enabled = false;
+        let cb = breaker(&config);
+        cb.on_event(&equity(dec(1000.0)), at(2, 9));
+        assert!(cb.on_event(&equity(dec(100.0)), at(2, 10)).is_none());
+    }
+
+    // ============= Resume Tests =============
+
+    #[tokio::test]
+    async fn test_resume_publishes_and_rebases() {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let cb = CircuitBreaker::new(bus, &test_config());
+
+        assert!(cb.resume().is_none());
+
+        cb.on_event(&equity(dec(1000.0)), at(2, 9));
+        cb.on_event(&equity(dec(900.0)), at(2, 10))
+            .expect("should trip");
+        let reason = cb.resume().expect("was tripped");
+        assert!(reason.contains("daily drawdown"));
+        assert!(!cb.is_tripped());
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            Event::System(SystemEvent::Resume { .. })
+        ));
+
+        // Drawdown is measured from the equity at resume time
+        assert!(cb.on_event(&equity(dec(880.0)), at(2, 11)).is_none());
+        assert!(cb.on_event(&equity(dec(850.0)), at(2, 12)).is_some());
+    }
+}
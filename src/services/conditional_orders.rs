@@ -0,0 +1,710 @@
+//! Conditional orders (`conditional_orders.*`, `/conditional_orders`).
+//!
+//! A rule names a symbol, a side, an amount and a condition over the
+//! symbol's live market data:
+//!
+//! ```text
+//! BUY 100 USD of SOL/USD if drop_from_high_pct >= 3% and spread_bps < 10bps
+//! ```
+//!
+//! Rules come from `conditional_orders.rules` or `POST /conditional_orders`
+//! (structured, or as a sentence like the one above). On every quote of its
+//! symbol an armed rule's condition is evaluated; when it holds, an
+//! `OrderRequest` tagged with the `conditional` origin is published and goes
+//! through execution like any other order (circuit breaker, pre-trade check,
+//! capital buckets, order limits). Buys use the rule's amount instead of the
+//! configured sizing; sells close the tracked position. A one-shot rule is
+//! then done, a `repeat` rule re-arms after `cooldown_secs`.
+//!
+//! Conditions combine comparisons with `and`, `or`, `not` and parentheses,
+//! with `+ - * /` arithmetic. Numbers may carry a `%` or `bps` suffix, which
+//! is only a reading aid. Variables: `price` (mid), `bid`, `ask`,
+//! `spread_bps`, `bid_size`, `ask_size`, `high_24h`, `low_24h` (of mids over
+//! the last 24h, seeded from stored bars), `drop_from_high_pct` and
+//! `rise_from_low_pct`. A condition over a variable that is not known yet
+//! does not hold.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{ConditionalOrderRule, ConditionalOrdersConfig};
+use crate::data::store::{Bar, MarketStore};
+use crate::events::{Event, MarketEvent, OrderRequest, TradeOrigin, TAG_SIGNAL_ID, TAG_STRATEGY};
+use crate::logging::new_correlation_id;
+use crate::money::dec;
+
+/// Variables a condition may use.
+pub const VARIABLES: [&str; 10] = [
+    "price",
+    "bid",
+    "ask",
+    "spread_bps",
+    "bid_size",
+    "ask_size",
+    "high_24h",
+    "low_24h",
+    "drop_from_high_pct",
+    "rise_from_low_pct",
+];
+
+/// Minutes the 24h high/low cover.
+const RANGE_MINUTES: i64 = 24 * 60;
+
+// ============= Expressions =============
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+/// Parsed condition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Num(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    /// Arithmetic, comparison or logical operator
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| format!("bad number '{}'", text))?;
+            // Unit suffixes are a reading aid only
+            if chars.get(i) == Some(&'%') {
+                i += 1;
+            } else if chars[i..]
+                .iter()
+                .take(3)
+                .collect::<String>()
+                .eq_ignore_ascii_case("bps")
+            {
+                i += 3;
+            }
+            tokens.push(Token::Num(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(Token::Ident(word.to_lowercase()));
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else {
+            let two: String = chars[i..].iter().take(2).collect();
+            let op = match two.as_str() {
+                "<=" => Some("<="),
+                ">=" => Some(">="),
+                "==" => Some("=="),
+                "!=" => Some("!="),
+                _ => None,
+            };
+            if let Some(op) = op {
+                tokens.push(Token::Op(op));
+                i += 2;
+                continue;
+            }
+            let op = match c {
+                '<' => "<",
+                '>' => ">",
+                '+' => "+",
+                '-' => "-",
+                '*' => "*",
+                '/' => "/",
+                _ => return Err(format!("unexpected '{}'", c)),
+            };
+            tokens.push(Token::Op(op));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(w)) if w == word) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn op(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        if let Some(Token::Op(op)) = self.peek() {
+            if let Some(found) = ops.iter().find(|o| *o == op) {
+                self.pos += 1;
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.keyword("or") {
+            left = Expr::Binary("or", Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.not()?;
+        while self.keyword("and") {
+            left = Expr::Binary("and", Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.keyword("not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.sum()?;
+        match self.op(&["<", "<=", ">", ">=", "==", "!="]) {
+            Some(op) => Ok(Expr::Binary(op, Box::new(left), Box::new(self.sum()?))),
+            None => Ok(left),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(op) = self.op(&["+", "-"]) {
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(op) = self.op(&["*", "/"]) {
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.op(&["-"]).is_some() {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Ident(name)) if VARIABLES.contains(&name.as_str()) => Ok(Expr::Var(name)),
+            Some(Token::Ident(name)) => Err(format!(
+                "unknown variable '{}' (known: {})",
+                name,
+                VARIABLES.join(", ")
+            )),
+            Some(Token::LParen) => {
+                let inner = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of condition".to_string()),
+        }
+    }
+}
+
+/// Parse a condition.
+pub fn parse_condition(input: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let expr = parser.or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(format!("unexpected {:?} after the condition", token)),
+    }
+}
+
+impl Expr {
+    /// Value of the expression (comparisons and logic give 1.0 / 0.0); None
+    /// when a variable is unknown or a division is by zero.
+    pub fn eval(&self, vars: &MarketVars) -> Option<f64> {
+        let truth = |b: bool| if b { 1.0 } else { 0.0 };
+        Some(match self {
+            Expr::Num(n) => *n,
+            Expr::Var(name) => vars.get(name)?,
+            Expr::Neg(inner) => -inner.eval(vars)?,
+            Expr::Not(inner) => truth(inner.eval(vars)? == 0.0),
+            Expr::Binary(op, left, right) => {
+                let l = left.eval(vars)?;
+                // Short-circuit: an unknown on the other side does not matter
+                match *op {
+                    "and" if l == 0.0 => return Some(0.0),
+                    "or" if l != 0.0 => return Some(1.0),
+                    _ => {}
+                }
+                let r = right.eval(vars)?;
+                match *op {
+                    "+" => l + r,
+                    "-" => l - r,
+                    "*" => l * r,
+                    "/" if r == 0.0 => return None,
+                    "/" => l / r,
+                    "<" => truth(l < r),
+                    "<=" => truth(l <= r),
+                    ">" => truth(l > r),
+                    ">=" => truth(l >= r),
+                    "==" => truth(l == r),
+                    "!=" => truth(l != r),
+                    "and" | "or" => truth(r != 0.0),
+                    _ => return None,
+                }
+            }
+        })
+    }
+
+    /// Whether the condition holds (false while a variable is unknown).
+    pub fn holds(&self, vars: &MarketVars) -> bool {
+        self.eval(vars).is_some_and(|v| v != 0.0)
+    }
+}
+
+// ============= Market data =============
+
+/// Values a condition is evaluated against.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarketVars {
+    pub bid: f64,
+    pub ask: f64,
+    pub bid_size: Option<f64>,
+    pub ask_size: Option<f64>,
+    pub high_24h: Option<f64>,
+    pub low_24h: Option<f64>,
+}
+
+impl MarketVars {
+    pub fn get(&self, name: &str) -> Option<f64> {
+        let price = (self.bid + self.ask) / 2.0;
+        match name {
+            "price" => Some(price),
+            "bid" => Some(self.bid),
+            "ask" => Some(self.ask),
+            "spread_bps" => (price > 0.0).then(|| (self.ask - self.bid) / price * 10_000.0),
+            "bid_size" => self.bid_size,
+            "ask_size" => self.ask_size,
+            "high_24h" => self.high_24h,
+            "low_24h" => self.low_24h,
+            "drop_from_high_pct" => self
+                .high_24h
+                .filter(|h| *h > 0.0)
+                .map(|h| (h - price) / h * 100.0),
+            "rise_from_low_pct" => self
+                .low_24h
+                .filter(|l| *l > 0.0)
+                .map(|l| (price - l) / l * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// High and low of mids over the last 24h, in minute buckets.
+#[derive(Clone, Debug, Default)]
+pub struct RollingRange {
+    /// (unix minute, high, low), oldest first
+    buckets: VecDeque<(i64, f64, f64)>,
+}
+
+impl RollingRange {
+    /// Range seeded from stored bars of the last 24h.
+    pub fn seeded(bars: &[Bar], now: DateTime<Utc>) -> Self {
+        let mut range = Self::default();
+        let mut bars: Vec<(i64, &Bar)> = bars
+            .iter()
+            .filter_map(|bar| {
+                let at = DateTime::parse_from_rfc3339(&bar.timestamp).ok()?;
+                Some((at.timestamp() / 60, bar))
+            })
+            .collect();
+        bars.sort_by_key(|(minute, _)| *minute);
+        for (minute, bar) in bars {
+            range.record_range(minute, bar.high, bar.low, now);
+        }
+        range
+    }
+
+    pub fn record(&mut self, price: f64, now: DateTime<Utc>) {
+        self.record_range(now.timestamp() / 60, price, price, now);
+    }
+
+    fn record_range(&mut self, minute: i64, high: f64, low: f64, now: DateTime<Utc>) {
+        if !(high > 0.0 && low > 0.0) {
+            return;
+        }
+        match self.buckets.back_mut() {
+            Some((last, h, l)) if *last == minute => {
+                *h = h.max(high);
+                *l = l.min(low);
+            }
+            Some((last, ..)) if *last > minute => {}
+            _ => self.buckets.push_back((minute, high, low)),
+        }
+        let oldest = now.timestamp() / 60 - RANGE_MINUTES;
+        while self.buckets.front().is_some_and(|(m, ..)| *m <= oldest) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// (high, low) over the last 24h.
+    pub fn high_low(&self) -> Option<(f64, f64)> {
+        let high = self.buckets.iter().map(|b| b.1).reduce(f64::max)?;
+        let low = self.buckets.iter().map(|b| b.2).reduce(f64::min)?;
+        Some((high, low))
+    }
+}
+
+// ============= Rules =============
+
+/// A conditional order and its state (`GET /conditional_orders`).
+#[derive(Clone, Debug, Serialize)]
+pub struct ConditionalOrder {
+    pub id: String,
+    pub symbol: String,
+    pub side: String,
+    pub notional: Option<f64>,
+    pub qty: Option<f64>,
+    pub when: String,
+    pub order_type: String,
+    pub repeat: bool,
+    pub cooldown_secs: u64,
+    /// "armed" or "fired" (one-shot rule done)
+    pub status: String,
+    pub triggers: u64,
+    pub last_triggered: Option<String>,
+    #[serde(skip)]
+    condition: Option<Expr>,
+    #[serde(skip)]
+    rearm_at: Option<DateTime<Utc>>,
+}
+
+impl ConditionalOrder {
+    /// Validate `rule`; `id` is used when it has none.
+    pub fn from_rule(rule: ConditionalOrderRule, id: String) -> Result<Self, String> {
+        let side = rule.side.to_lowercase();
+        let order_type = rule.order_type.to_lowercase();
+        if rule.symbol.trim().is_empty() {
+            return Err("symbol is required".to_string());
+        }
+        let positive = |v: Option<f64>| v.is_some_and(|v| v > 0.0);
+        match side.as_str() {
+            "buy" if positive(rule.notional) == positive(rule.qty) => {
+                return Err("a buy needs exactly one of notional or qty".to_string());
+            }
+            "buy" => {}
+            "sell" if rule.notional.is_some() || rule.qty.is_some() => {
+                return Err("a sell closes the tracked position; drop notional/qty".to_string());
+            }
+            "sell" => {}
+            other => return Err(format!("side must be buy or sell, not '{}'", other)),
+        }
+        if !matches!(order_type.as_str(), "limit" | "market") {
+            return Err(format!(
+                "order_type must be limit or market, not '{}'",
+                order_type
+            ));
+        }
+        let condition = parse_condition(&rule.when)?;
+        Ok(Self {
+            id: rule.id.filter(|id| !id.trim().is_empty()).unwrap_or(id),
+            symbol: rule.symbol.trim().to_string(),
+            side,
+            notional: rule.notional.filter(|v| *v > 0.0),
+            qty: rule.qty.filter(|v| *v > 0.0),
+            when: rule.when,
+            order_type,
+            repeat: rule.repeat,
+            cooldown_secs: rule.cooldown_secs,
+            status: "armed".to_string(),
+            triggers: 0,
+            last_triggered: None,
+            condition: Some(condition),
+            rearm_at: None,
+        })
+    }
+
+    /// The order to submit if the rule fires on `vars` at `now`.
+    fn fire(&mut self, vars: &MarketVars, now: DateTime<Utc>) -> Option<OrderRequest> {
+        if self.status != "armed" || self.rearm_at.is_some_and(|at| now < at) {
+            return None;
+        }
+        if !self.condition.as_ref()?.holds(vars) {
+            return None;
+        }
+        let qty = match (self.qty, self.notional) {
+            (Some(qty), _) => dec(qty),
+            (None, Some(notional)) if vars.ask > 0.0 => dec(notional / vars.ask),
+            (None, Some(_)) => return None,
+            // Sells close the tracked position
+            (None, None) => Decimal::ZERO,
+        };
+        self.triggers += 1;
+        self.last_triggered = Some(now.to_rfc3339());
+        if self.repeat {
+            self.rearm_at = Some(now + Duration::seconds(self.cooldown_secs as i64));
+        } else {
+            self.status = "fired".to_string();
+        }
+        let correlation_id = new_correlation_id();
+        Some(OrderRequest {
+            symbol: self.symbol.clone(),
+            action: self.side.clone(),
+            qty,
+            order_type: self.order_type.clone(),
+            limit_price: None,
+            stop_loss: None,
+            take_profit: None,
+            strategy_id: None,
+            correlation_id: Some(correlation_id.clone()),
+            origin: Some(TradeOrigin::new(TradeOrigin::CONDITIONAL)),
+            risk_verdict: Some(format!("conditional order {}: {}", self.id, self.when)),
+            valid_until: None,
+            metadata: HashMap::from([
+                (
+                    TAG_STRATEGY.to_string(),
+                    TradeOrigin::CONDITIONAL.to_string(),
+                ),
+                (TAG_SIGNAL_ID.to_string(), correlation_id),
+                ("conditional_id".to_string(), self.id.clone()),
+            ]),
+        })
+    }
+}
+
+/// Parse a rule sentence: `BUY <amount> <currency> of <SYMBOL> if <cond>`,
+/// `BUY <qty> <SYMBOL> if <cond>` or `SELL <SYMBOL> if <cond>`.
+pub fn parse_rule(sentence: &str) -> Result<ConditionalOrderRule, String> {
+    let lower = sentence.to_lowercase();
+    let split = lower
+        .find(" if ")
+        .ok_or_else(|| "expected '<order> if <condition>'".to_string())?;
+    let (head, when) = (&sentence[..split], sentence[split + 4..].trim());
+    let words: Vec<&str> = head.split_whitespace().collect();
+    let amount = |s: &str| {
+        s.parse::<f64>()
+            .ok()
+            .filter(|v| *v > 0.0)
+            .ok_or_else(|| format!("bad amount '{}'", s))
+    };
+    let (side, symbol, notional, qty) =
+        match words.as_slice() {
+            [side, symbol] if side.eq_ignore_ascii_case("sell") => ("sell", *symbol, None, None),
+            [side, value, _currency, of, symbol]
+                if side.eq_ignore_ascii_case("buy") && of.eq_ignore_ascii_case("of") =>
+            {
+                ("buy", *symbol, Some(amount(value)?), None)
+            }
+            [side, value, symbol] if side.eq_ignore_ascii_case("buy") => {
+                ("buy", *symbol, None, Some(amount(value)?))
+            }
+            _ => return Err(
+                "expected 'BUY <amount> USD of <SYMBOL>', 'BUY <qty> <SYMBOL>' or 'SELL <SYMBOL>'"
+                    .to_string(),
+            ),
+        };
+    Ok(ConditionalOrderRule {
+        id: None,
+        symbol: symbol.to_uppercase(),
+        side: side.to_string(),
+        notional,
+        qty,
+        when: when.to_string(),
+        order_type: "limit".to_string(),
+        repeat: false,
+        cooldown_secs: 300,
+    })
+}
+
+/// Live conditional orders, shared by the service and the API.
+#[derive(Clone, Default)]
+pub struct ConditionalOrderBook {
+    orders: Arc<Mutex<Vec<ConditionalOrder>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ConditionalOrderBook {
+    /// Book with the configured rules; invalid ones are logged and skipped.
+    pub fn from_config(config: &ConditionalOrdersConfig) -> Self {
+        let book = Self::default();
+        for rule in &config.rules {
+            if let Err(e) = book.add(rule.clone()) {
+                warn!("⚠️ [CONDITIONAL] Skipping rule for {}: {}", rule.symbol, e);
+            }
+        }
+        book
+    }
+
+    pub fn add(&self, rule: ConditionalOrderRule) -> Result<ConditionalOrder, String> {
+        let id = format!("cond-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let order = ConditionalOrder::from_rule(rule, id)?;
+        let mut orders = self.orders.lock().unwrap();
+        if orders.iter().any(|o| o.id == order.id) {
+            return Err(format!("id '{}' already exists", order.id));
+        }
+        info!(
+            "🎯 [CONDITIONAL] Armed {}: {} {} if {}",
+            order.id, order.side, order.symbol, order.when
+        );
+        orders.push(order.clone());
+        Ok(order)
+    }
+
+    pub fn list(&self) -> Vec<ConditionalOrder> {
+        self.orders.lock().unwrap().clone()
+    }
+
+    /// Remove a rule; false if there is none with `id`.
+    pub fn cancel(&self, id: &str) -> bool {
+        let mut orders = self.orders.lock().unwrap();
+        let before = orders.len();
+        orders.retain(|o| o.id != id);
+        orders.len() != before
+    }
+
+    /// Orders of the rules on `symbol` that fire on `vars` at `now`.
+    pub fn evaluate(
+        &self,
+        symbol: &str,
+        vars: &MarketVars,
+        now: DateTime<Utc>,
+    ) -> Vec<OrderRequest> {
+        self.orders
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|o| o.symbol == symbol)
+            .filter_map(|o| o.fire(vars, now))
+            .collect()
+    }
+}
+
+/// Evaluates the book on every quote and publishes the orders that fire.
+pub struct ConditionalOrderService {
+    book: ConditionalOrderBook,
+    store: MarketStore,
+    event_bus: EventBus,
+}
+
+impl ConditionalOrderService {
+    pub fn new(book: ConditionalOrderBook, store: MarketStore, event_bus: EventBus) -> Self {
+        Self {
+            book,
+            store,
+            event_bus,
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut rx = self.event_bus.subscribe("conditional_orders");
+        let book = self.book.clone();
+        let store = self.store.clone();
+        let bus = self.event_bus.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+
+        tokio::spawn(async move {
+            info!(
+                "🎯 [CONDITIONAL] Watching {} conditional order(s)",
+                book.list().len()
+            );
+            // Every quoted symbol keeps a range, so rules added later have one
+            let mut ranges: HashMap<String, RollingRange> = HashMap::new();
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                let Event::Market(MarketEvent::Quote {
+                    symbol, bid, ask, ..
+                }) = event
+                else {
+                    continue;
+                };
+                if !(bid > 0.0 && ask >= bid) {
+                    continue;
+                }
+                let now = Utc::now();
+                let range = ranges
+                    .entry(symbol.clone())
+                    .or_insert_with(|| RollingRange::seeded(&store.get_bar_history(&symbol), now));
+                range.record((bid + ask) / 2.0, now);
+                let (high_24h, low_24h) = range.high_low().unzip();
+                let sizes = store.get_latest_quote(&symbol);
+                let vars = MarketVars {
+                    bid,
+                    ask,
+                    bid_size: sizes.as_ref().map(|q| q.bid_size),
+                    ask_size: sizes.as_ref().map(|q| q.ask_size),
+                    high_24h,
+                    low_24h,
+                };
+                for order in book.evaluate(&symbol, &vars, now) {
+                    info!(
+                        "🎯 [CONDITIONAL] Triggered {} {} ({})",
+                        order.action,
+                        order.symbol,
+                        order.risk_verdict.as_deref().unwrap_or_default()
+                    );
+                    bus.publish(Event::Order(order)).ok();
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,175 @@
+//! Unit tests for conditional orders - condition language, rule sentences and
+//! trigger/re-arm behaviour of the book.
+
+#[cfg(test)]
+mod conditional_orders_tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::config::{ConditionalOrderRule, ConditionalOrdersConfig};
+    use crate::data::store::Bar;
+    use crate::events::TradeOrigin;
+    use crate::money::dec;
+    use crate::services::conditional_orders::*;
+
+    fn vars(bid: f64, ask: f64, high: f64) -> MarketVars {
+        MarketVars {
+            bid,
+            ask,
+            high_24h: Some(high),
+            low_24h: Some(bid.min(high)),
+            ..Default::default()
+        }
+    }
+
+    fn rule(sentence: &str) -> ConditionalOrderRule {
+        parse_rule(sentence).unwrap()
+    }
+
+    #[test]
+    fn test_conditions_parse_and_evaluate() {
+        let v = vars(96.99, 97.01, 100.0);
+        let holds = |cond: &str| parse_condition(cond).unwrap().holds(&v);
+        assert!(holds("drop_from_high_pct >= 3% and spread_bps < 10bps"));
+        assert!(!holds("drop_from_high_pct >= 3.5"));
+        assert!(holds("price < high_24h * 0.98 or bid > 1000"));
+        assert!(holds("not (ask - bid > 1) and -price < 0"));
+        assert!(holds("(price + 3) / 2 == 50"));
+        // Unknown values never hold, division by zero included
+        assert!(!holds("bid_size > 0"));
+        assert!(!holds("price / (bid - bid) > 1"));
+        // ...unless short-circuited away
+        assert!(holds("price > 0 or bid_size > 0"));
+
+        assert!(parse_condition("volume > 5")
+            .unwrap_err()
+            .contains("unknown"));
+        assert!(parse_condition("(price > 5").is_err());
+        assert!(parse_condition("price > 5 5").is_err());
+        assert!(parse_condition("price # 5").is_err());
+    }
+
+    #[test]
+    fn test_rule_sentences() {
+        let buy = rule("BUY 100 USD of SOL/USD if drop_from_high_pct >= 3% and spread_bps < 10bps");
+        assert_eq!(buy.symbol, "SOL/USD");
+        assert_eq!(buy.side, "buy");
+        assert_eq!(buy.notional, Some(100.0));
+        assert_eq!(buy.qty, None);
+        assert_eq!(buy.when, "drop_from_high_pct >= 3% and spread_bps < 10bps");
+
+        let qty = rule("buy 0.5 eth/usd IF price < 2000");
+        assert_eq!((qty.symbol.as_str(), qty.qty), ("ETH/USD", Some(0.5)));
+
+        let sell = rule("SELL BTC/USD if rise_from_low_pct > 5");
+        assert_eq!((sell.side.as_str(), sell.notional), ("sell", None));
+
+        assert!(parse_rule("BUY 100 USD of SOL/USD").is_err());
+        assert!(parse_rule("BUY -5 SOL/USD if price > 1").is_err());
+        assert!(parse_rule("HOLD SOL/USD if price > 1").is_err());
+    }
+
+    #[test]
+    fn test_rules_are_validated() {
+        let book = ConditionalOrderBook::default();
+        let mut r = rule("BUY 100 USD of SOL/USD if price > 1");
+        r.qty = Some(1.0);
+        assert!(book.add(r).unwrap_err().contains("exactly one"));
+        let mut r = rule("SELL SOL/USD if price > 1");
+        r.qty = Some(1.0);
+        assert!(book.add(r).is_err());
+        let mut r = rule("SELL SOL/USD if price > 1");
+        r.order_type = "stop".to_string();
+        assert!(book.add(r).is_err());
+
+        let mut r = rule("SELL SOL/USD if price > 1");
+        r.id = Some("exit".to_string());
+        book.add(r.clone()).unwrap();
+        assert!(book.add(r).unwrap_err().contains("already exists"));
+
+        // Invalid config rules are skipped, valid ones armed
+        let config = ConditionalOrdersConfig {
+            enabled: true,
+            rules: vec![
+                rule("BUY 1 SOL/USD if price > 1"),
+                ConditionalOrderRule {
+                    when: "price >".to_string(),
+                    ..rule("BUY 1 SOL/USD if price > 1")
+                },
+            ],
+        };
+        let book = ConditionalOrderBook::from_config(&config);
+        assert_eq!(book.list().len(), 1);
+        assert_eq!(book.list()[0].status, "armed");
+    }
+
+    #[test]
+    fn test_one_shot_and_repeat_triggers() {
+        let book = ConditionalOrderBook::default();
+        let once = book
+            .add(rule("BUY 100 USD of SOL/USD if drop_from_high_pct >= 3"))
+            .unwrap();
+        let mut repeat = rule("BUY 2 SOL/USD if drop_from_high_pct >= 3");
+        repeat.repeat = true;
+        repeat.cooldown_secs = 60;
+        book.add(repeat).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        // Only 1% off the high: nothing
+        assert!(book
+            .evaluate("SOL/USD", &vars(98.9, 99.1, 100.0), now)
+            .is_empty());
+
+        let dipped = vars(96.9, 97.1, 100.0);
+        let orders = book.evaluate("SOL/USD", &dipped, now);
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].qty, dec(100.0 / 97.1));
+        assert_eq!(orders[0].order_type, "limit");
+        assert_eq!(
+            orders[0].origin.as_ref().unwrap().mode,
+            TradeOrigin::CONDITIONAL
+        );
+        assert!(orders[0].risk_verdict.as_ref().unwrap().contains(&once.id));
+        assert_eq!(orders[1].qty, dec(2.0));
+        // Other symbols are not evaluated
+        assert!(book.evaluate("BTC/USD", &dipped, now).is_empty());
+
+        // The one-shot is done; the repeat waits out its cooldown
+        assert!(book
+            .evaluate("SOL/USD", &dipped, now + Duration::seconds(30))
+            .is_empty());
+        let again = book.evaluate("SOL/USD", &dipped, now + Duration::seconds(60));
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].qty, dec(2.0));
+
+        let listed = book.list();
+        assert_eq!(listed[0].status, "fired");
+        assert_eq!(listed[1].triggers, 2);
+        assert!(book.cancel(&once.id));
+        assert!(!book.cancel(&once.id));
+        assert_eq!(book.list().len(), 1);
+    }
+
+    #[test]
+    fn test_rolling_range_seeds_from_bars_and_expires() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap();
+        let bar = |hours_ago: i64, high: f64, low: f64| Bar {
+            symbol: "SOL/USD".to_string(),
+            open: low,
+            high,
+            low,
+            close: high,
+            volume: 1.0,
+            timestamp: (now - Duration::hours(hours_ago)).to_rfc3339(),
+        };
+        // The 30h-old bar is outside the window
+        let mut range = RollingRange::seeded(&[bar(2, 110.0, 100.0), bar(30, 150.0, 50.0)], now);
+        assert_eq!(range.high_low(), Some((110.0, 100.0)));
+
+        range.record(95.0, now);
+        assert_eq!(range.high_low(), Some((110.0, 95.0)));
+        // A day later only the new quote remains
+        range.record(105.0, now + Duration::hours(23));
+        range.record(104.0, now + Duration::hours(25));
+        assert_eq!(range.high_low(), Some((105.0, 104.0)));
+    }
+}
@@ -0,0 +1,369 @@
+//! Config hot reload.
+//!
+//! Tunables (`hft`, `defaults`, `symbol_overrides`, `symbols`, `chatter_level`,
+//! `chatter`) can change while a session runs, either by editing the config
+//! file (its modification time is polled every `config_reload.poll_secs`) or
+//! through `PUT /config` (and `PUT /log_level` for chatter). Accepted changes are stored in the shared `LiveConfig` and
+//! published as `Event::Config`; services swap in the new config from that
+//! event. Everything else (exchange, credentials, strategy mode, ...) still
+//! needs a restart and is ignored on reload.
+
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, ChatterLevel, ChatterModule, SymbolConfig};
+use crate::events::{ConfigUpdateEvent, Event};
+
+/// Config shared by the API and the reloaders (the next `/start` uses it too).
+#[derive(Clone)]
+pub struct LiveConfig {
+    inner: Arc<RwLock<AppConfig>>,
+}
+
+impl LiveConfig {
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(config)),
+        }
+    }
+
+    pub fn snapshot(&self) -> AppConfig {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Run `update` on a copy of the config. If it succeeds and changed
+    /// anything, store the copy and publish `Event::Config` on `bus` (when a
+    /// session is running). Returns the changed keys.
+    pub fn apply(
+        &self,
+        bus: Option<&EventBus>,
+        source: &str,
+        update: impl FnOnce(&mut AppConfig) -> Result<Vec<String>, String>,
+    ) -> Result<Vec<String>, String> {
+        let mut config = self.snapshot();
+        let changed = update(&mut config)?;
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+        let config = Arc::new(config);
+        *self.inner.write().unwrap() = (*config).clone();
+        info!(
+            "🔧 [CONFIG] Reloaded from {}: {}",
+            source,
+            changed.join(", ")
+        );
+        if let Some(bus) = bus {
+            bus.publish(Event::Config(ConfigUpdateEvent {
+                config,
+                changed: changed.clone(),
+                source: source.to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            }))
+            .ok();
+        }
+        Ok(changed)
+    }
+}
+
+/// Copy the hot-reloadable sections of `file` into `config`.
+/// Returns the sections that differed.
+pub fn apply_tunables(config: &mut AppConfig, file: &AppConfig) -> Vec<String> {
+    let mut changed = Vec::new();
+    if config.hft != file.hft {
+        config.hft = file.hft.clone();
+        changed.push("hft".to_string());
+    }
+    if config.defaults != file.defaults {
+        config.defaults = file.defaults.clone();
+        changed.push("defaults".to_string());
+    }
+    if config.symbol_overrides != file.symbol_overrides {
+        config.symbol_overrides = file.symbol_overrides.clone();
+        changed.push("symbol_overrides".to_string());
+    }
+    if config.symbols != file.symbols {
+        config.symbols = file.symbols.clone();
+        changed.push("symbols".to_string());
+    }
+    if config.chatter_level != file.chatter_level {
+        config.chatter_level = file.chatter_level;
+        changed.push("chatter_level".to_string());
+    }
+    if config.chatter != file.chatter {
+        config.chatter = file.chatter.clone();
+        changed.push("chatter".to_string());
+    }
+    changed
+}
+
+/// Hot-reloadable values, keyed like `apply_patch` expects them (`GET /config`).
+pub fn tunables_json(config: &AppConfig) -> Value {
+    let overrides: Map<String, Value> = config
+        .symbol_overrides
+        .iter()
+        .flatten()
+        .map(|(symbol, o)| {
+            (
+                symbol.clone(),
+                json!({
+                    "take_profit_pct": o.take_profit_pct,
+                    "stop_loss_pct": o.stop_loss_pct,
+                    "strategy_mode": o.strategy_mode,
+                    "max_hold_minutes": o.max_hold_minutes,
+                }),
+            )
+        })
+        .collect();
+    json!({
+        "symbols": config.symbols,
+        "chatter_level": config.chatter_level.as_str(),
+        "chatter": chatter_json(config),
+        "defaults": {
+            "take_profit_pct": config.defaults.take_profit_pct,
+            "stop_loss_pct": config.defaults.stop_loss_pct,
+            "min_order_amount": config.defaults.min_order_amount,
+            "max_order_amount": config.defaults.max_order_amount,
+            "max_hold_minutes": config.defaults.max_hold_minutes,
+        },
+        "hft": {
+            "min_edge_bps": config.hft.min_edge_bps,
+            "take_profit_bps": config.hft.take_profit_bps,
+            "stop_loss_bps": config.hft.stop_loss_bps,
+            "max_spread_bps": config.hft.max_spread_bps,
+            "min_volume_ratio": config.hft.min_volume_ratio,
+            "evaluate_every_quotes": config.hft.evaluate_every_quotes,
+        },
+        "symbol_overrides": overrides,
+    })
+}
+
+fn number(key: &str, value: &Value) -> Result<f64, String> {
+    value
+        .as_f64()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .ok_or_else(|| format!("{} must be a non-negative number", key))
+}
+
+fn set_f64(field: &mut f64, key: &str, value: &Value) -> Result<bool, String> {
+    let v = number(key, value)?;
+    let changed = *field != v;
+    *field = v;
+    Ok(changed)
+}
+
+/// Optional value; null clears it.
+fn set_opt_f64(field: &mut Option<f64>, key: &str, value: &Value) -> Result<bool, String> {
+    let v = if value.is_null() {
+        None
+    } else {
+        Some(number(key, value)?)
+    };
+    let changed = *field != v;
+    *field = v;
+    Ok(changed)
+}
+
+/// Effective chatter level of every module (`GET /log_level`).
+pub fn chatter_json(config: &AppConfig) -> Value {
+    ChatterModule::ALL
+        .iter()
+        .map(|m| {
+            (
+                m.as_str().to_string(),
+                json!(config.chatter_for(*m).as_str()),
+            )
+        })
+        .collect::<Map<String, Value>>()
+        .into()
+}
+
+fn chatter_level(key: &str, value: &Value) -> Result<ChatterLevel, String> {
+    value
+        .as_str()
+        .and_then(ChatterLevel::parse)
+        .ok_or_else(|| format!("{} must be low, normal or verbose", key))
+}
+
+/// Apply a `PUT /config` body: an object of dotted keys, e.g.
+/// `{"hft.min_edge_bps": 8, "defaults.take_profit_pct": 1.5,
+///   "symbol_overrides.BTC/USD.stop_loss_pct": 0.8, "chatter.monitor": "verbose"}`.
+/// Stops at the first unknown key or bad value (`LiveConfig::apply` then
+/// discards the partly updated copy). Returns the keys whose value changed.
+pub fn apply_patch(
+    config: &mut AppConfig,
+    patch: &Map<String, Value>,
+) -> Result<Vec<String>, String> {
+    let mut changed = Vec::new();
+    for (key, value) in patch {
+        let did_change = match key.as_str() {
+            "symbols" => {
+                let symbols: Vec<String> = serde_json::from_value(value.clone())
+                    .map_err(|_| "symbols must be a list of strings".to_string())?;
+                if symbols.is_empty() {
+                    return Err("symbols must not be empty".to_string());
+                }
+                let changed = config.symbols != symbols;
+                config.symbols = symbols;
+                changed
+            }
+            "chatter_level" => {
+                let level = chatter_level(key, value)?;
+                let changed = config.chatter_level != level;
+                config.chatter_level = level;
+                changed
+            }
+            other if other.starts_with("chatter.") => {
+                let module = ChatterModule::parse(&other["chatter.".len()..])
+                    .ok_or_else(|| format!("{} is not a hot-reloadable key", other))?;
+                // null clears the override
+                let level = match value {
+                    Value::Null => None,
+                    _ => Some(chatter_level(key, value)?),
+                };
+                let changed = config.chatter.get(module) != level;
+                config.chatter.set(module, level);
+                changed
+            }
+            "defaults.take_profit_pct" => {
+                set_f64(&mut config.defaults.take_profit_pct, key, value)?
+            }
+            "defaults.stop_loss_pct" => set_f64(&mut config.defaults.stop_loss_pct, key, value)?,
+            "defaults.min_order_amount" => {
+                set_f64(&mut config.defaults.min_order_amount, key, value)?
+            }
+            "defaults.max_order_amount" => {
+                set_f64(&mut config.defaults.max_order_amount, key, value)?
+            }
+            "defaults.max_hold_minutes" => {
+                set_opt_f64(&mut config.defaults.max_hold_minutes, key, value)?
+            }
+            "hft.min_edge_bps" => set_f64(&mut config.hft.min_edge_bps, key, value)?,
+            "hft.take_profit_bps" => set_f64(&mut config.hft.take_profit_bps, key, value)?,
+            "hft.stop_loss_bps" => set_f64(&mut config.hft.stop_loss_bps, key, value)?,
+            "hft.max_spread_bps" => set_f64(&mut config.hft.max_spread_bps, key, value)?,
+            "hft.min_volume_ratio" => set_f64(&mut config.hft.min_volume_ratio, key, value)?,
+            "hft.evaluate_every_quotes" => {
+                let n = number(key, value)? as usize;
+                let changed = config.hft.evaluate_every_quotes != n;
+                config.hft.evaluate_every_quotes = n;
+                changed
+            }
+            other => {
+                let Some((symbol, field)) = other
+                    .strip_prefix("symbol_overrides.")
+                    .and_then(|rest| rest.rsplit_once('.'))
+                else {
+                    return Err(format!("{} is not a hot-reloadable key", other));
+                };
+                let overrides = config.symbol_overrides.get_or_insert_with(Default::default);
+                let entry = overrides.entry(symbol.to_string()).or_insert(SymbolConfig {
+                    take_profit_pct: None,
+                    stop_loss_pct: None,
+                    strategy_mode: None,
+                    max_hold_minutes: None,
+                    quote_currency: None,
+                });
+                // null clears the override
+                if field == "strategy_mode" {
+                    let mode = match value {
+                        Value::Null => None,
+                        Value::String(mode) => Some(mode.to_lowercase()),
+                        _ => return Err(format!("{} must be a string", key)),
+                    };
+                    let changed = entry.strategy_mode != mode;
+                    entry.strategy_mode = mode;
+                    changed
+                } else {
+                    let slot = match field {
+                        "take_profit_pct" => &mut entry.take_profit_pct,
+                        "stop_loss_pct" => &mut entry.stop_loss_pct,
+                        "max_hold_minutes" => &mut entry.max_hold_minutes,
+                        _ => return Err(format!("{} is not a hot-reloadable key", other)),
+                    };
+                    set_opt_f64(slot, key, value)?
+                }
+            }
+        };
+        if did_change {
+            changed.push(key.clone());
+        }
+    }
+    Ok(changed)
+}
+
+/// Polls the config file and applies edits to the running session.
+pub struct ConfigWatcher {
+    event_bus: EventBus,
+    live: LiveConfig,
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl ConfigWatcher {
+    pub fn new(event_bus: EventBus, live: LiveConfig, config: &AppConfig) -> Self {
+        Self {
+            event_bus,
+            live,
+            path: PathBuf::from(&config.config_reload.path),
+            interval: Duration::from_secs(config.config_reload.poll_secs.max(1)),
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    /// Re-read the file and apply its tunables. Parse errors keep the running config.
+    pub fn reload(&self) {
+        let parsed = std::fs::read_to_string(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| AppConfig::parse(&content).map_err(|e| e.to_string()));
+        let file = match parsed {
+            Ok(file) => file,
+            Err(e) => {
+                warn!(
+                    "⚠️ [CONFIG] Ignoring edit to {}: {}",
+                    self.path.display(),
+                    e
+                );
+                return;
+            }
+        };
+        let _ = self.live.apply(Some(&self.event_bus), "file", |config| {
+            Ok(apply_tunables(config, &file))
+        });
+    }
+
+    pub async fn start(self) {
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🔧 [CONFIG] Watching {} every {}s",
+                self.path.display(),
+                self.interval.as_secs()
+            );
+            // First tick also picks up edits made while no session was running
+            let mut last_modified = None;
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = ticker.tick() => {
+                        let modified = self.modified();
+                        if modified.is_some() && modified != last_modified {
+                            last_modified = modified;
+                            self.reload();
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,318 @@
+//! Unit tests for config hot reload (file tunables, PUT /config patches, publishing).
+
+#[cfg(test)]
+mod config_reload_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, ChatterLevel, ChatterModule};
+    use crate::events::Event;
+    use crate::services::config_reload::*;
+    use serde_json::{json, Map, Value};
+
+    const CONFIG_YAML: &str = r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#;
+
+    fn test_config() -> AppConfig {
+        AppConfig::parse(CONFIG_YAML).unwrap()
+    }
+
+    fn patch(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    // ============= File Reload Tests =============
+
+    #[test]
+    fn test_apply_tunables_reports_changed_sections() {
+        let mut config = test_config();
+        let mut file = test_config();
+        file.hft.min_edge_bps = 4.0;
+        file.symbols.push("ETH/USD".to_string());
+        file.chatter_level = ChatterLevel::Verbose;
+
+        let changed = apply_tunables(&mut config, &file);
+        assert_eq!(changed, vec!["hft", "symbols", "chatter_level"]);
+        assert_eq!(config.hft.min_edge_bps, 4.0);
+        assert_eq!(config.symbols, vec!["BTC/USD", "ETH/USD"]);
+        assert_eq!(config.chatter_level, ChatterLevel::Verbose);
+    }
+
+    #[test]
+    fn test_apply_tunables_ignores_restart_only_fields() {
+        let mut config = test_config();
+        let mut file = test_config();
+        file.exchange = "binance".to_string();
+        file.strategy_mode = "llm".to_string();
+
+        assert!(apply_tunables(&mut config, &file).is_empty());
+        assert_eq!(config.exchange, "alpaca");
+        assert_eq!(config.strategy_mode, "hft");
+    }
+
+    #[test]
+    fn test_watcher_reload_publishes_file_edit() {
+        let path = std::env::temp_dir().join(format!(
+            "autohedge_config_reload_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            CONFIG_YAML.replace("min_edge_bps: 10.0", "min_edge_bps: 6.5"),
+        )
+        .unwrap();
+        let mut config = test_config();
+        config.config_reload.path = path.to_string_lossy().into_owned();
+
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let live = LiveConfig::new(config.clone());
+        ConfigWatcher::new(bus.clone(), live.clone(), &config).reload();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(live.snapshot().hft.min_edge_bps, 6.5);
+        match rx.try_recv().unwrap() {
+            Event::Config(update) => {
+                assert_eq!(update.changed, vec!["hft"]);
+                assert_eq!(update.source, "file");
+                assert_eq!(update.config.hft.min_edge_bps, 6.5);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_watcher_keeps_config_on_parse_error() {
+        let path = std::env::temp_dir().join(format!(
+            "autohedge_config_reload_bad_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "symbols: [unterminated").unwrap();
+        let mut config = test_config();
+        config.config_reload.path = path.to_string_lossy().into_owned();
+
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let live = LiveConfig::new(config.clone());
+        ConfigWatcher::new(bus.clone(), live.clone(), &config).reload();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(live.snapshot().hft.min_edge_bps, 10.0);
+        assert!(rx.try_recv().is_err());
+    }
+
+    // ============= PUT /config Tests =============
+
+    #[test]
+    fn test_apply_patch_dotted_keys() {
+        let mut config = test_config();
+        let changed = apply_patch(
+            &mut config,
+            &patch(json!({
+                "hft.min_edge_bps": 8,
+                "defaults.take_profit_pct": 1.5,
+                "chatter_level": "verbose",
+            })),
+        )
+        .unwrap();
+        assert_eq!(changed.len(), 3);
+        assert_eq!(config.hft.min_edge_bps, 8.0);
+        assert_eq!(config.defaults.take_profit_pct, 1.5);
+        assert_eq!(config.chatter_level, ChatterLevel::Verbose);
+    }
+
+    #[test]
+    fn test_apply_patch_module_chatter() {
+        let mut config = test_config();
+        apply_patch(&mut config, &patch(json!({"chatter.monitor": "verbose"}))).unwrap();
+        assert_eq!(
+            config.chatter_for(ChatterModule::Monitor),
+            ChatterLevel::Verbose
+        );
+        assert_eq!(
+            config.chatter_for(ChatterModule::Strategy),
+            ChatterLevel::Low
+        );
+        assert_eq!(tunables_json(&config)["chatter"]["monitor"], "verbose");
+
+        // null falls back to chatter_level again
+        apply_patch(&mut config, &patch(json!({"chatter.monitor": null}))).unwrap();
+        assert_eq!(
+            config.chatter_for(ChatterModule::Monitor),
+            ChatterLevel::Low
+        );
+
+        assert!(apply_patch(&mut config, &patch(json!({"chatter.db": "verbose"}))).is_err());
+        assert!(apply_patch(&mut config, &patch(json!({"chatter_level": "loud"}))).is_err());
+    }
+
+    #[test]
+    fn test_apply_patch_symbol_override() {
+        let mut config = test_config();
+        apply_patch(
+            &mut config,
+            &patch(json!({"symbol_overrides.BTC/USD.stop_loss_pct": 0.8})),
+        )
+        .unwrap();
+        assert_eq!(config.get_symbol_params("BTC/USD"), (1.0, 0.8));
+
+        // null clears the override again
+        apply_patch(
+            &mut config,
+            &patch(json!({"symbol_overrides.BTC/USD.stop_loss_pct": null})),
+        )
+        .unwrap();
+        assert_eq!(config.get_symbol_params("BTC/USD"), (1.0, 0.5));
+    }
+
+    #[test]
+    fn test_apply_patch_symbol_strategy_mode() {
+        let mut config = test_config();
+        apply_patch(
+            &mut config,
+            &patch(json!({"symbol_overrides.ETH/USD.strategy_mode": "LLM"})),
+        )
+        .unwrap();
+        assert_eq!(config.strategy_mode_for("ETH/USD"), "llm");
+        assert_eq!(config.strategy_mode_for("BTC/USD"), "hft");
+
+        assert!(apply_patch(
+            &mut config,
+            &patch(json!({"symbol_overrides.ETH/USD.strategy_mode": 1})),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_apply_patch_max_hold() {
+        let mut config = test_config();
+        apply_patch(
+            &mut config,
+            &patch(json!({
+                "defaults.max_hold_minutes": 45,
+                "symbol_overrides.BTC/USD.max_hold_minutes": 90,
+            })),
+        )
+        .unwrap();
+        assert_eq!(
+            config.max_hold_for("ETH/USD"),
+            Some(chrono::Duration::minutes(45))
+        );
+        assert_eq!(
+            config.max_hold_for("BTC/USD"),
+            Some(chrono::Duration::minutes(90))
+        );
+
+        apply_patch(
+            &mut config,
+            &patch(json!({"defaults.max_hold_minutes": null})),
+        )
+        .unwrap();
+        assert_eq!(config.defaults.max_hold_minutes, None);
+    }
+
+    #[test]
+    fn test_apply_patch_unchanged_value_not_reported() {
+        let mut config = test_config();
+        let changed = apply_patch(&mut config, &patch(json!({"hft.min_edge_bps": 10.0}))).unwrap();
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn test_apply_patch_rejects_bad_input() {
+        let mut config = test_config();
+        for body in [
+            json!({"exchange": "binance"}),
+            json!({"hft.min_edge_bps": "fast"}),
+            json!({"defaults.stop_loss_pct": -1}),
+            json!({"chatter_level": "loud"}),
+            json!({"symbols": []}),
+            json!({"symbol_overrides.BTC/USD.max_order_amount": 5}),
+        ] {
+            assert!(
+                apply_patch(&mut config, &patch(body.clone())).is_err(),
+                "{}",
+                body
+            );
+        }
+    }
+
+    #[test]
+    fn test_live_config_apply_is_all_or_nothing() {
+        let live = LiveConfig::new(test_config());
+        let result = live.apply(None, "api", |config| {
+            apply_patch(config, &patch(json!({"hft.min_edge_bps": 3, "bogus": 1})))
+        });
+        assert!(result.is_err());
+        assert_eq!(live.snapshot().hft.min_edge_bps, 10.0);
+    }
+
+    #[test]
+    fn test_live_config_apply_publishes_changes() {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let live = LiveConfig::new(test_config());
+
+        let changed = live
+            .apply(Some(&bus), "api", |config| {
+                apply_patch(config, &patch(json!({"symbols": ["BTC/USD", "SOL/USD"]})))
+            })
+            .unwrap();
+        assert_eq!(changed, vec!["symbols"]);
+        assert_eq!(live.snapshot().symbols, vec!["BTC/USD", "SOL/USD"]);
+        match rx.try_recv().unwrap() {
+            Event::Config(update) => {
+                assert_eq!(update.source, "api");
+                assert_eq!(update.config.symbols.len(), 2);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        // No-op updates are not published
+        live.apply(Some(&bus), "api", |config| {
+            apply_patch(config, &patch(json!({"symbols": ["BTC/USD", "SOL/USD"]})))
+        })
+        .unwrap();
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_tunables_json_uses_patch_keys() {
+        let json = tunables_json(&test_config());
+        assert_eq!(json["hft"]["min_edge_bps"], 10.0);
+        assert_eq!(json["defaults"]["take_profit_pct"], 1.0);
+        assert_eq!(json["symbols"], json!(["BTC/USD"]));
+    }
+}
@@ -0,0 +1,331 @@
+//! End-of-day session summary.
+//!
+//! On the `daily_report.cron` schedule (same scheduler as the keep-alive ping)
+//! the day's closed trades are pulled from the `TradeReporter` summary, joined
+//! with error counts collected from the bus (rejected orders, risk rejections,
+//! halts, health degradations) and written to `<dir>/YYYY-MM-DD.md`. With
+//! `llm_commentary` the `PostMortemAgent` adds a short natural-language review.
+//! `/report/daily` serves the files.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_cron_scheduler::{Job, JobScheduler};
+use tracing::{error, info, warn};
+
+use crate::agents::{postmortem::PostMortemAgent, Agent};
+use crate::bus::EventBus;
+use crate::config::DailyReportConfig;
+use crate::error::Result;
+use crate::events::{Event, SystemEvent};
+use crate::llm::LLMQueue;
+use crate::services::reporting::{ClosedTrade, PerformanceSummary, TradeReporter};
+
+/// Days of error counts kept in memory
+const ERROR_DAYS_KEPT: usize = 7;
+
+/// Operational problems seen on the bus during one UTC day.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ErrorCounts {
+    pub rejected_orders: u64,
+    pub risk_rejections: u64,
+    pub halts: u64,
+    pub health_degradations: u64,
+}
+
+impl ErrorCounts {
+    /// Count `event` if it reports a problem.
+    pub fn record(&mut self, event: &Event) {
+        match event {
+            Event::Execution(r) if r.status.to_lowercase().contains("reject") => {
+                self.rejected_orders += 1
+            }
+            Event::RiskLimit(r) if r.action == "rejected" => self.risk_rejections += 1,
+            Event::System(SystemEvent::Halt { .. }) => self.halts += 1,
+            Event::Health(h) if h.level > h.previous => self.health_degradations += 1,
+            _ => {}
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.rejected_orders + self.risk_rejections + self.halts + self.health_degradations
+    }
+}
+
+/// One day's session summary.
+#[derive(Clone, Debug, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub reporting_currency: Option<String>,
+    pub closed_trades: u64,
+    pub winning_trades: u64,
+    pub losing_trades: u64,
+    pub realized_pnl: Decimal,
+    pub win_rate_pct: f64,
+    pub best_trade: Option<ClosedTrade>,
+    pub worst_trade: Option<ClosedTrade>,
+    /// Realized P&L of the day per symbol
+    pub per_symbol_pnl: BTreeMap<String, Decimal>,
+    pub open_positions: usize,
+    /// Session totals (since /start) for context
+    pub session_orders: u64,
+    pub session_filled: u64,
+    pub session_rejected: u64,
+    pub errors: ErrorCounts,
+    pub commentary: Option<String>,
+}
+
+impl DailyReport {
+    /// Summarize the trades of `summary` closed on `date` (UTC).
+    pub fn build(summary: &PerformanceSummary, errors: ErrorCounts, date: NaiveDate) -> Self {
+        let trades: Vec<&ClosedTrade> = summary
+            .history
+            .values()
+            .flatten()
+            .filter(|t| {
+                DateTime::parse_from_rfc3339(&t.sell_time)
+                    .is_ok_and(|at| at.with_timezone(&Utc).date_naive() == date)
+            })
+            .collect();
+
+        let mut per_symbol_pnl = BTreeMap::new();
+        for trade in &trades {
+            *per_symbol_pnl
+                .entry(trade.symbol.clone())
+                .or_insert(Decimal::ZERO) += trade.pnl;
+        }
+        let winning_trades = trades.iter().filter(|t| t.pnl > Decimal::ZERO).count() as u64;
+        let closed_trades = trades.len() as u64;
+
+        Self {
+            date,
+            reporting_currency: summary.reporting_currency.clone(),
+            closed_trades,
+            winning_trades,
+            losing_trades: closed_trades - winning_trades,
+            realized_pnl: trades.iter().map(|t| t.pnl).sum(),
+            win_rate_pct: if closed_trades > 0 {
+                winning_trades as f64 / closed_trades as f64 * 100.0
+            } else {
+                0.0
+            },
+            best_trade: trades.iter().max_by_key(|t| t.pnl).map(|t| (*t).clone()),
+            worst_trade: trades.iter().min_by_key(|t| t.pnl).map(|t| (*t).clone()),
+            per_symbol_pnl,
+            open_positions: summary.open_positions.len(),
+            session_orders: summary.total_orders,
+            session_filled: summary.filled,
+            session_rejected: summary.rejected,
+            errors,
+            commentary: None,
+        }
+    }
+
+    /// Markdown document written to `<dir>/YYYY-MM-DD.md`.
+    pub fn to_markdown(&self) -> String {
+        let ccy = self.reporting_currency.as_deref().unwrap_or("");
+        let trade_line = |t: &ClosedTrade| {
+            format!(
+                "{} {} @ {} -> {}: {:.4} {} ({:+.2}%)",
+                t.symbol, t.qty, t.buy_price, t.sell_price, t.pnl, ccy, t.pnl_percent
+            )
+        };
+
+        let mut md = format!("# Daily Session Summary - {}\n\n", self.date);
+        md.push_str("## Performance\n\n");
+        md.push_str(&format!(
+            "- Realized PnL: {:.4} {}\n",
+            self.realized_pnl, ccy
+        ));
+        md.push_str(&format!(
+            "- Closed trades: {} ({} won, {} lost, win rate {:.1}%)\n",
+            self.closed_trades, self.winning_trades, self.losing_trades, self.win_rate_pct
+        ));
+        md.push_str(&format!("- Open positions: {}\n", self.open_positions));
+        md.push_str(&format!(
+            "- Session totals: {} orders, {} fills, {} rejected\n",
+            self.session_orders, self.session_filled, self.session_rejected
+        ));
+
+        md.push_str("\n## Best / Worst Trades\n\n");
+        match (&self.best_trade, &self.worst_trade) {
+            (Some(best), Some(worst)) => {
+                md.push_str(&format!("- Best: {}\n", trade_line(best)));
+                md.push_str(&format!("- Worst: {}\n", trade_line(worst)));
+            }
+            _ => md.push_str("- No trades closed\n"),
+        }
+
+        if !self.per_symbol_pnl.is_empty() {
+            md.push_str("\n## PnL by Symbol\n\n| Symbol | PnL |\n|---|---:|\n");
+            for (symbol, pnl) in &self.per_symbol_pnl {
+                md.push_str(&format!("| {} | {:.4} |\n", symbol, pnl));
+            }
+        }
+
+        md.push_str("\n## Errors\n\n");
+        md.push_str(&format!(
+            "- Rejected orders: {}\n- Risk-limit rejections: {}\n- Halts: {}\n- Health degradations: {}\n",
+            self.errors.rejected_orders,
+            self.errors.risk_rejections,
+            self.errors.halts,
+            self.errors.health_degradations
+        ));
+
+        if let Some(commentary) = &self.commentary {
+            md.push_str("\n## Post-Mortem\n\n");
+            md.push_str(commentary.trim());
+            md.push('\n');
+        }
+        md
+    }
+}
+
+/// Path of the report for `date`.
+pub fn report_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// Write `report` to `<dir>/YYYY-MM-DD.md`.
+pub fn write_report(dir: &Path, report: &DailyReport) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = report_path(dir, report.date);
+    std::fs::write(&path, report.to_markdown())?;
+    Ok(path)
+}
+
+/// Date and markdown of the report for `date`, or of the most recent one.
+pub fn read_report(dir: &Path, date: Option<NaiveDate>) -> Option<(NaiveDate, String)> {
+    let date = match date {
+        Some(date) => date,
+        None => std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().into_string().ok()?;
+                NaiveDate::parse_from_str(name.strip_suffix(".md")?, "%Y-%m-%d").ok()
+            })
+            .max()?,
+    };
+    let markdown = std::fs::read_to_string(report_path(dir, date)).ok()?;
+    Some((date, markdown))
+}
+
+/// Scheduled writer of the daily session summary.
+#[derive(Clone)]
+pub struct DailyReportService {
+    config: DailyReportConfig,
+    reporter: TradeReporter,
+    llm: LLMQueue,
+    event_bus: EventBus,
+    /// Error counts per UTC day, most recent `ERROR_DAYS_KEPT`
+    errors: Arc<Mutex<HashMap<NaiveDate, ErrorCounts>>>,
+}
+
+impl DailyReportService {
+    pub fn new(
+        config: &DailyReportConfig,
+        reporter: TradeReporter,
+        llm: LLMQueue,
+        event_bus: EventBus,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            reporter,
+            llm,
+            event_bus,
+            errors: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Count errors from the bus and schedule the report; both stop when the
+    /// session drains.
+    pub async fn start(&self) -> Result<()> {
+        let scheduler = JobScheduler::new().await?;
+        let service = self.clone();
+        let job = Job::new_async(self.config.cron.as_str(), move |_uuid, _l| {
+            let service = service.clone();
+            Box::pin(async move {
+                service.generate(Utc::now().date_naive()).await;
+            })
+        })?;
+        scheduler.add(job).await?;
+        scheduler.start().await?;
+        info!(
+            "📝 [DAILY-REPORT] Scheduled with cron '{}' -> {}",
+            self.config.cron, self.config.dir
+        );
+
+        let mut rx = self.event_bus.subscribe("daily_report");
+        let shutdown = self.event_bus.shutdown().clone();
+        let errors = self.errors.clone();
+        tokio::spawn(async move {
+            let mut scheduler = scheduler;
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => break,
+                    event = rx.recv() => event,
+                };
+                match event {
+                    Ok(event) => {
+                        let mut errors = errors.lock().unwrap();
+                        errors
+                            .entry(Utc::now().date_naive())
+                            .or_default()
+                            .record(&event);
+                        while errors.len() > ERROR_DAYS_KEPT {
+                            let Some(oldest) = errors.keys().min().copied() else {
+                                break;
+                            };
+                            errors.remove(&oldest);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            if let Err(e) = scheduler.shutdown().await {
+                warn!("⚠️ [DAILY-REPORT] Failed to stop scheduler: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    /// Build, optionally annotate and write the report for `date`.
+    pub async fn generate(&self, date: NaiveDate) -> DailyReport {
+        let errors = self
+            .errors
+            .lock()
+            .unwrap()
+            .get(&date)
+            .cloned()
+            .unwrap_or_default();
+        let mut report = DailyReport::build(&self.reporter.summary(), errors, date);
+
+        if self.config.llm_commentary {
+            let query = serde_json::to_string_pretty(&report).unwrap_or_default();
+            match PostMortemAgent.run(&query, &self.llm).await {
+                Ok(commentary) => report.commentary = Some(commentary),
+                Err(e) => warn!("⚠️ [DAILY-REPORT] Post-mortem failed: {}", e),
+            }
+        }
+
+        match write_report(Path::new(&self.config.dir), &report) {
+            Ok(path) => info!(
+                "📝 [DAILY-REPORT] {}: {} trades, PnL {:.4}, {} errors -> {}",
+                date,
+                report.closed_trades,
+                report.realized_pnl,
+                report.errors.total(),
+                path.display()
+            ),
+            Err(e) => error!("❌ [DAILY-REPORT] Failed to write report: {}", e),
+        }
+        report
+    }
+}
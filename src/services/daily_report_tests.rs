@@ -0,0 +1,171 @@
+//! Unit tests for the end-of-day session summary.
+
+#[cfg(test)]
+mod daily_report_tests {
+    use crate::events::{DegradationLevel, Event, ExecutionReport, HealthEvent, SystemEvent};
+    use crate::money::dec;
+    use crate::services::daily_report::*;
+    use crate::services::reporting::{ClosedTrade, PerformanceSummary};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn trade(symbol: &str, sell_time: &str, pnl: Decimal) -> ClosedTrade {
+        ClosedTrade {
+            symbol: symbol.to_string(),
+            buy_time: "2026-03-01T09:00:00Z".to_string(),
+            sell_time: sell_time.to_string(),
+            buy_price: dec(100.0),
+            sell_price: dec(100.0) + pnl,
+            qty: dec(1.0),
+            pnl,
+            pnl_percent: 0.0,
+            origin: None,
+            ..Default::default()
+        }
+    }
+
+    fn summary() -> PerformanceSummary {
+        let mut s = PerformanceSummary {
+            total_orders: 9,
+            filled: 8,
+            rejected: 1,
+            ..Default::default()
+        };
+        s.history.insert(
+            "BTC/USD".to_string(),
+            vec![
+                trade("BTC/USD", "2026-03-01T10:00:00Z", dec(5.0)),
+                trade("BTC/USD", "2026-03-02T10:00:00Z", dec(-3.0)),
+                trade("BTC/USD", "2026-03-02T23:30:00+00:00", dec(12.0)),
+            ],
+        );
+        s.history.insert(
+            "ETH/USD".to_string(),
+            vec![trade("ETH/USD", "2026-03-02T01:00:00Z", dec(-4.5))],
+        );
+        s
+    }
+
+    fn execution(status: &str) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o1".to_string(),
+            status: status.to_string(),
+            side: "buy".to_string(),
+            price: None,
+            qty: None,
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn health(previous: DegradationLevel, level: DegradationLevel) -> Event {
+        Event::Health(HealthEvent {
+            level,
+            previous,
+            score: 50.0,
+            size_factor: 0.5,
+            reason: "test".to_string(),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        })
+    }
+
+    // ============= Report Tests =============
+
+    #[test]
+    fn test_build_only_counts_trades_closed_that_day() {
+        let report = DailyReport::build(&summary(), ErrorCounts::default(), day(2));
+        assert_eq!(report.closed_trades, 3);
+        assert_eq!(report.winning_trades, 1);
+        assert_eq!(report.losing_trades, 2);
+        assert_eq!(report.realized_pnl, dec(4.5));
+        assert!((report.win_rate_pct - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report.per_symbol_pnl["BTC/USD"], dec(9.0));
+        assert_eq!(report.per_symbol_pnl["ETH/USD"], dec(-4.5));
+        assert_eq!(report.session_orders, 9);
+    }
+
+    #[test]
+    fn test_build_picks_best_and_worst() {
+        let report = DailyReport::build(&summary(), ErrorCounts::default(), day(2));
+        assert_eq!(report.best_trade.unwrap().pnl, dec(12.0));
+        let worst = report.worst_trade.unwrap();
+        assert_eq!(worst.symbol, "ETH/USD");
+        assert_eq!(worst.pnl, dec(-4.5));
+    }
+
+    #[test]
+    fn test_quiet_day_has_no_trades() {
+        let report = DailyReport::build(&summary(), ErrorCounts::default(), day(5));
+        assert_eq!(report.closed_trades, 0);
+        assert_eq!(report.win_rate_pct, 0.0);
+        assert!(report.best_trade.is_none());
+        assert!(report.to_markdown().contains("No trades closed"));
+    }
+
+    #[test]
+    fn test_markdown_sections() {
+        let mut report = DailyReport::build(&summary(), ErrorCounts::default(), day(2));
+        let md = report.to_markdown();
+        assert!(md.starts_with("# Daily Session Summary - 2026-03-02"));
+        assert!(md.contains("## Best / Worst Trades"));
+        assert!(md.contains("| ETH/USD | -4.5000 |"));
+        assert!(!md.contains("## Post-Mortem"));
+
+        report.commentary = Some("A choppy day.\n".to_string());
+        assert!(report
+            .to_markdown()
+            .ends_with("## Post-Mortem\n\nA choppy day.\n"));
+    }
+
+    // ============= Error Count Tests =============
+
+    #[test]
+    fn test_error_counts() {
+        let mut errors = ErrorCounts::default();
+        errors.record(&execution("rejected"));
+        errors.record(&execution("filled"));
+        errors.record(&Event::System(SystemEvent::Halt {
+            reason: "max drawdown".to_string(),
+            flatten: false,
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        errors.record(&health(DegradationLevel::Full, DegradationLevel::Reduced));
+        errors.record(&health(DegradationLevel::Reduced, DegradationLevel::Full));
+
+        assert_eq!(errors.rejected_orders, 1);
+        assert_eq!(errors.halts, 1);
+        assert_eq!(errors.health_degradations, 1);
+        assert_eq!(errors.total(), 3);
+    }
+
+    // ============= File Tests =============
+
+    #[test]
+    fn test_write_and_read_reports() {
+        let dir = std::env::temp_dir().join(format!("autohedge_daily_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(read_report(&dir, None).is_none());
+
+        for d in [1, 2] {
+            let report = DailyReport::build(&summary(), ErrorCounts::default(), day(d));
+            write_report(&dir, &report).unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let (date, md) = read_report(&dir, None).unwrap();
+        assert_eq!(date, day(2));
+        assert!(md.contains("2026-03-02"));
+        assert_eq!(read_report(&dir, Some(day(1))).unwrap().0, day(1));
+        assert!(read_report(&dir, Some(day(3))).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
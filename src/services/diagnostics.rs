@@ -0,0 +1,184 @@
+//! Per-symbol verbose diagnostics with a TTL.
+//!
+//! `POST /debug/symbol/{symbol}?level=trace&ttl=300` turns on verbose output for
+//! one symbol without touching `chatter_level` or the per-module `chatter`. Services apply it to
+//! the config clone they already make per event, so every existing "verbose"
+//! log line (gate decisions, skip reasons) lights up for that symbol only.
+//! `trace` additionally logs sizing math and policy decisions. Output is
+//! budgeted per symbol per second and the mode switches itself off at expiry.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use crate::config::{AppConfig, ChatterLevel};
+
+/// Diagnostic evaluations allowed per symbol per second; the rest are dropped.
+pub const DIAG_BUDGET_PER_SEC: u32 = 20;
+
+/// Longest TTL accepted by `enable`.
+pub const MAX_DIAG_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagLevel {
+    /// Symbol treated as verbose in every module (gate decisions, skip reasons)
+    Verbose,
+    /// Verbose plus sizing math and policy decisions
+    Trace,
+}
+
+impl DiagLevel {
+    /// Parse a query value ("debug"/"verbose", "trace").
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "debug" | "verbose" => Some(DiagLevel::Verbose),
+            "trace" => Some(DiagLevel::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagLevel::Verbose => "verbose",
+            DiagLevel::Trace => "trace",
+        }
+    }
+}
+
+struct DiagEntry {
+    level: DiagLevel,
+    expires: Instant,
+    window_start: Instant,
+    used: u32,
+    suppressed: u64,
+}
+
+/// Symbols currently under diagnostics. Shared by every service on a bus.
+#[derive(Clone, Default)]
+pub struct SymbolDiagnostics {
+    entries: Arc<DashMap<String, DiagEntry>>,
+}
+
+impl SymbolDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable diagnostics for `symbol` (replacing any previous level). The TTL is
+    /// capped at `MAX_DIAG_TTL`; returns the TTL actually applied.
+    pub fn enable(&self, symbol: &str, level: DiagLevel, ttl: Duration) -> Duration {
+        let ttl = ttl.min(MAX_DIAG_TTL);
+        let now = Instant::now();
+        self.entries.insert(
+            symbol.to_string(),
+            DiagEntry {
+                level,
+                expires: now + ttl,
+                window_start: now,
+                used: 0,
+                suppressed: 0,
+            },
+        );
+        info!(
+            "🔬 [DIAG] {} diagnostics for {} ({}s)",
+            level.as_str(),
+            symbol,
+            ttl.as_secs()
+        );
+        ttl
+    }
+
+    /// Turn diagnostics off for `symbol`. Returns false if none were active.
+    pub fn disable(&self, symbol: &str) -> bool {
+        let removed = self.entries.remove(symbol).is_some();
+        if removed {
+            info!("🔬 [DIAG] Diagnostics for {} turned off", symbol);
+        }
+        removed
+    }
+
+    /// Active level for `symbol`, dropping the entry once it has expired.
+    pub fn level(&self, symbol: &str) -> Option<DiagLevel> {
+        self.level_at(symbol, Instant::now())
+    }
+
+    fn level_at(&self, symbol: &str, now: Instant) -> Option<DiagLevel> {
+        let level = {
+            let entry = self.entries.get(symbol)?;
+            if now < entry.expires {
+                return Some(entry.level);
+            }
+            entry.level
+        };
+        self.entries.remove(symbol);
+        info!(
+            "🔬 [DIAG] {} diagnostics for {} expired",
+            level.as_str(),
+            symbol
+        );
+        None
+    }
+
+    /// Active symbols with their level and remaining TTL.
+    pub fn active(&self) -> Vec<(String, DiagLevel, Duration)> {
+        let now = Instant::now();
+        let mut active: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| now < e.expires)
+            .map(|e| (e.key().clone(), e.level, e.expires - now))
+            .collect();
+        active.sort_by(|a, b| a.0.cmp(&b.0));
+        active
+    }
+
+    /// Spend one unit of the symbol's per-second budget if it is diagnosed at
+    /// `min_level` or above. Returns false when not diagnosed or throttled.
+    pub fn allow(&self, symbol: &str, min_level: DiagLevel) -> bool {
+        self.allow_at(symbol, min_level, Instant::now())
+    }
+
+    pub(crate) fn allow_at(&self, symbol: &str, min_level: DiagLevel, now: Instant) -> bool {
+        match self.level_at(symbol, now) {
+            Some(level) if level >= min_level => {}
+            _ => return false,
+        }
+        let Some(mut entry) = self.entries.get_mut(symbol) else {
+            return false;
+        };
+        if now.duration_since(entry.window_start) >= Duration::from_secs(1) {
+            if entry.suppressed > 0 {
+                info!(
+                    "🔬 [DIAG] {}: throttled {} diagnostic evaluation(s)",
+                    symbol, entry.suppressed
+                );
+            }
+            entry.window_start = now;
+            entry.used = 0;
+            entry.suppressed = 0;
+        }
+        if entry.used >= DIAG_BUDGET_PER_SEC {
+            entry.suppressed += 1;
+            return false;
+        }
+        entry.used += 1;
+        true
+    }
+
+    /// Raise `config` to verbose chatter for this evaluation if `symbol` is
+    /// diagnosed (and within budget). Returns true if the config was changed.
+    pub fn apply(&self, symbol: &str, config: &mut AppConfig) -> bool {
+        if !self.allow(symbol, DiagLevel::Verbose) {
+            return false;
+        }
+        config.set_chatter(ChatterLevel::Verbose);
+        true
+    }
+
+    /// True if trace-only diagnostics (sizing math, policy decisions) should be
+    /// logged for `symbol` right now.
+    pub fn trace(&self, symbol: &str) -> bool {
+        self.allow(symbol, DiagLevel::Trace)
+    }
+}
@@ -0,0 +1,127 @@
+//! Unit tests for per-symbol diagnostics (levels, TTL expiry, throttling).
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use crate::config::{AppConfig, ChatterLevel};
+    use crate::services::diagnostics::*;
+    use std::time::{Duration, Instant};
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    // ============= Level Tests =============
+
+    #[test]
+    fn test_parse_levels() {
+        assert_eq!(DiagLevel::parse("trace"), Some(DiagLevel::Trace));
+        assert_eq!(DiagLevel::parse("DEBUG"), Some(DiagLevel::Verbose));
+        assert_eq!(DiagLevel::parse("verbose"), Some(DiagLevel::Verbose));
+        assert_eq!(DiagLevel::parse("loud"), None);
+    }
+
+    #[test]
+    fn test_apply_only_affects_diagnosed_symbol() {
+        let diag = SymbolDiagnostics::new();
+        diag.enable("BTC/USD", DiagLevel::Verbose, Duration::from_secs(60));
+
+        let mut config = test_config();
+        assert!(!diag.apply("ETH/USD", &mut config));
+        assert_eq!(config.chatter_level, ChatterLevel::Low);
+
+        assert!(diag.apply("BTC/USD", &mut config));
+        assert_eq!(config.chatter_level, ChatterLevel::Verbose);
+    }
+
+    #[test]
+    fn test_trace_requires_trace_level() {
+        let diag = SymbolDiagnostics::new();
+        diag.enable("BTC/USD", DiagLevel::Verbose, Duration::from_secs(60));
+        assert!(!diag.trace("BTC/USD"));
+
+        diag.enable("BTC/USD", DiagLevel::Trace, Duration::from_secs(60));
+        assert!(diag.trace("BTC/USD"));
+        assert!(diag.allow("BTC/USD", DiagLevel::Verbose));
+    }
+
+    // ============= TTL Tests =============
+
+    #[test]
+    fn test_ttl_is_capped_and_expires() {
+        let diag = SymbolDiagnostics::new();
+        let ttl = diag.enable("BTC/USD", DiagLevel::Trace, Duration::from_secs(86_400));
+        assert_eq!(ttl, MAX_DIAG_TTL);
+
+        let later = Instant::now() + MAX_DIAG_TTL + Duration::from_secs(1);
+        assert!(!diag.allow_at("BTC/USD", DiagLevel::Verbose, later));
+        // Expired entries are dropped
+        assert!(diag.level("BTC/USD").is_none());
+        assert!(diag.active().is_empty());
+    }
+
+    #[test]
+    fn test_disable() {
+        let diag = SymbolDiagnostics::new();
+        assert!(!diag.disable("BTC/USD"));
+        diag.enable("BTC/USD", DiagLevel::Verbose, Duration::from_secs(60));
+        assert_eq!(diag.active().len(), 1);
+        assert!(diag.disable("BTC/USD"));
+        assert!(diag.level("BTC/USD").is_none());
+    }
+
+    // ============= Throttle Tests =============
+
+    #[test]
+    fn test_budget_per_second() {
+        let diag = SymbolDiagnostics::new();
+        diag.enable("BTC/USD", DiagLevel::Verbose, Duration::from_secs(60));
+        let start = Instant::now();
+
+        for _ in 0..DIAG_BUDGET_PER_SEC {
+            assert!(diag.allow_at("BTC/USD", DiagLevel::Verbose, start));
+        }
+        assert!(!diag.allow_at("BTC/USD", DiagLevel::Verbose, start));
+
+        // Budget refills in the next one-second window
+        let next = start + Duration::from_millis(1100);
+        assert!(diag.allow_at("BTC/USD", DiagLevel::Verbose, next));
+    }
+}
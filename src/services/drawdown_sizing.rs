@@ -0,0 +1,50 @@
+//! Drawdown-aware entry sizing (`sizing.drawdown`).
+//!
+//! At every mark the `TradeReporter` compares the latest equity sample with
+//! the peak of the last `peak_window` samples. The deepest step of the curve
+//! the drawdown reached sets the target size factor (the defaults halve
+//! entries from 5% and stop them from 10%). A deeper drawdown cuts size at
+//! once; once it eases, size climbs back by `recovery_step` per mark.
+
+use std::collections::VecDeque;
+
+use crate::config::DrawdownSizingConfig;
+use crate::money::float;
+use crate::services::reporting::EquitySample;
+
+/// Drawdown (%) of the newest sample from the peak of the last `window`
+/// samples (all of them when 0). None without a positive peak.
+pub fn drawdown_pct(curve: &VecDeque<EquitySample>, window: usize) -> Option<f64> {
+    let latest = float(curve.back()?.equity);
+    let window = if window == 0 { curve.len() } else { window };
+    let peak = curve
+        .iter()
+        .rev()
+        .take(window)
+        .map(|s| float(s.equity))
+        .fold(f64::MIN, f64::max);
+    if peak <= 0.0 {
+        return None;
+    }
+    Some(((peak - latest) / peak * 100.0).max(0.0))
+}
+
+/// Size factor of the deepest step `drawdown_pct` reached (1.0 above them all).
+pub fn target_factor(config: &DrawdownSizingConfig, drawdown_pct: f64) -> f64 {
+    config
+        .steps
+        .iter()
+        .filter(|step| drawdown_pct >= step.drawdown_pct)
+        .max_by(|a, b| a.drawdown_pct.total_cmp(&b.drawdown_pct))
+        .map_or(1.0, |step| step.size_factor.clamp(0.0, 1.0))
+}
+
+/// Factor after one mark: down to `target` at once, back up by at most
+/// `recovery_step`.
+pub fn next_factor(config: &DrawdownSizingConfig, current: f64, target: f64) -> f64 {
+    if target <= current {
+        target
+    } else {
+        (current + config.recovery_step.max(0.0)).min(target)
+    }
+}
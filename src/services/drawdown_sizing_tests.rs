@@ -0,0 +1,57 @@
+//! Unit tests for drawdown-aware sizing.
+
+#[cfg(test)]
+mod drawdown_sizing_tests {
+    use std::collections::VecDeque;
+
+    use crate::config::DrawdownSizingConfig;
+    use crate::money::dec;
+    use crate::services::drawdown_sizing::*;
+    use crate::services::reporting::EquitySample;
+    use rust_decimal::Decimal;
+
+    fn curve(equity: &[f64]) -> VecDeque<EquitySample> {
+        equity
+            .iter()
+            .map(|e| EquitySample {
+                ts: String::new(),
+                realized_pnl: Decimal::ZERO,
+                unrealized_pnl: Decimal::ZERO,
+                equity: dec(*e),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_drawdown_from_rolling_peak() {
+        let samples = curve(&[1000.0, 1200.0, 1100.0, 1080.0]);
+        assert!((drawdown_pct(&samples, 0).unwrap() - 10.0).abs() < 1e-9);
+        // The 1200 peak fell out of a 2-sample window
+        assert!((drawdown_pct(&samples, 2).unwrap() - 1.818_181_818).abs() < 1e-6);
+        // New highs are no drawdown; no positive peak is no reading
+        assert_eq!(drawdown_pct(&curve(&[1000.0, 1100.0]), 0), Some(0.0));
+        assert_eq!(drawdown_pct(&curve(&[0.0, 0.0]), 0), None);
+        assert_eq!(drawdown_pct(&VecDeque::new(), 0), None);
+    }
+
+    #[test]
+    fn test_deepest_step_reached_sets_the_target() {
+        let config = DrawdownSizingConfig::default();
+        assert_eq!(target_factor(&config, 4.9), 1.0);
+        assert_eq!(target_factor(&config, 5.0), 0.5);
+        assert_eq!(target_factor(&config, 9.0), 0.5);
+        assert_eq!(target_factor(&config, 12.0), 0.0);
+    }
+
+    #[test]
+    fn test_size_drops_at_once_and_recovers_gradually() {
+        let config = DrawdownSizingConfig {
+            recovery_step: 0.25,
+            ..DrawdownSizingConfig::default()
+        };
+        assert_eq!(next_factor(&config, 1.0, 0.0), 0.0);
+        assert_eq!(next_factor(&config, 0.0, 1.0), 0.25);
+        assert_eq!(next_factor(&config, 0.25, 0.5), 0.5);
+        assert_eq!(next_factor(&config, 0.9, 1.0), 1.0);
+    }
+}
@@ -0,0 +1,291 @@
+//! Account equity curve (`equity_snapshots`).
+//!
+//! Every `interval_secs` the snapshotter records the trade reporter's valuation
+//! (`TradeReporter::equity_now`: starting portfolio value plus net transfers,
+//! realized P&L and open positions marked to the quote mid), so `/equity` and
+//! the `/report` equity curve never disagree. Snapshots are kept in an
+//! `EquityCurve` ring buffer of `max_snapshots` and appended to
+//! `<dir>/YYYY-MM-DD.jsonl` for their UTC day; the buffer is seeded from those
+//! files at startup so the curve survives restarts. `GET /equity?window=24h`
+//! serves a window of it, from the files while no session is running.
+//!
+//! No snapshot is taken before the venue has reported a portfolio value.
+
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::AppConfig;
+use crate::data::store::MarketStore;
+use crate::money::float;
+use crate::services::reporting::{EquitySample, TradeReporter};
+
+/// Days of daily files read back into the curve at startup, at most
+const MAX_RESTORE_DAYS: u64 = 31;
+
+/// Account value at one point in time, on the reporter's basis.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EquitySnapshot {
+    pub ts: DateTime<Utc>,
+    pub realized_pnl: Decimal,
+    /// Open positions marked to the quote mid
+    pub unrealized_pnl: Decimal,
+    pub equity: Decimal,
+}
+
+impl EquitySnapshot {
+    /// `sample` (taken at `ts`) as a curve point.
+    pub fn from_sample(sample: &EquitySample, ts: DateTime<Utc>) -> Self {
+        Self {
+            ts,
+            realized_pnl: sample.realized_pnl,
+            unrealized_pnl: sample.unrealized_pnl,
+            equity: sample.equity,
+        }
+    }
+}
+
+/// In-memory ring buffer of snapshots, oldest first; clones share it.
+#[derive(Clone, Debug)]
+pub struct EquityCurve {
+    snapshots: Arc<Mutex<VecDeque<EquitySnapshot>>>,
+    capacity: usize,
+}
+
+impl EquityCurve {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: Arc::new(Mutex::new(VecDeque::new())),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&self, snapshot: EquitySnapshot) {
+        let mut snapshots = self.snapshots.lock().unwrap();
+        while snapshots.len() >= self.capacity {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    /// Snapshots taken at or after `since`, oldest first.
+    pub fn since(&self, since: DateTime<Utc>) -> Vec<EquitySnapshot> {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.ts >= since)
+            .cloned()
+            .collect()
+    }
+
+    pub fn latest(&self) -> Option<EquitySnapshot> {
+        self.snapshots.lock().unwrap().back().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Daily file holding `date`'s snapshots.
+pub fn snapshot_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.jsonl", date.format("%Y-%m-%d")))
+}
+
+/// Append `snapshot` as one JSON line to its day's file.
+pub fn append_snapshot(dir: &Path, snapshot: &EquitySnapshot) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut line = serde_json::to_vec(snapshot)?;
+    line.push(b'\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(snapshot_path(dir, snapshot.ts.date_naive()))?
+        .write_all(&line)
+}
+
+/// Snapshots in `[since, until]` from the daily files, oldest first. Missing
+/// days and unreadable lines (a torn write after a crash) are skipped.
+pub fn read_snapshots(
+    dir: &Path,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Vec<EquitySnapshot> {
+    let mut snapshots = Vec::new();
+    let mut date = since.date_naive();
+    while date <= until.date_naive() {
+        if let Ok(text) = std::fs::read_to_string(snapshot_path(dir, date)) {
+            snapshots.extend(
+                text.lines()
+                    .filter_map(|line| serde_json::from_str::<EquitySnapshot>(line).ok())
+                    .filter(|s| s.ts >= since && s.ts <= until),
+            );
+        }
+        let Some(next) = date.succ_opt() else { break };
+        date = next;
+    }
+    snapshots
+}
+
+/// `/equity` window such as `90s`, `30m`, `24h` or `7d`.
+pub fn parse_window(window: &str) -> Result<chrono::Duration, String> {
+    let window = window.trim();
+    let split = window
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(window.len());
+    let (amount, unit) = window.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid window '{}' (e.g. 24h)", window))?;
+    let secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        _ => {
+            return Err(format!(
+                "invalid window unit in '{}' (s, m, h or d)",
+                window
+            ))
+        }
+    };
+    if amount <= 0 {
+        return Err(format!("window '{}' must be positive", window));
+    }
+    amount
+        .checked_mul(secs)
+        .and_then(chrono::Duration::try_seconds)
+        .ok_or_else(|| format!("window '{}' is too long", window))
+}
+
+/// Summary of a window of the curve.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct EquityStats {
+    pub start: Decimal,
+    pub end: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub change_pct: f64,
+    /// Largest peak-to-trough fall within the window
+    pub max_drawdown_pct: f64,
+}
+
+impl EquityStats {
+    /// None for an empty window.
+    pub fn from_snapshots(snapshots: &[EquitySnapshot]) -> Option<Self> {
+        let (first, last) = (snapshots.first()?, snapshots.last()?);
+        let pct = |from: Decimal, to: Decimal| {
+            if from > Decimal::ZERO {
+                float((to - from) / from) * 100.0
+            } else {
+                0.0
+            }
+        };
+        let mut stats = Self {
+            start: first.equity,
+            end: last.equity,
+            high: first.equity,
+            low: first.equity,
+            change_pct: pct(first.equity, last.equity),
+            max_drawdown_pct: 0.0,
+        };
+        let mut peak = first.equity;
+        for s in snapshots {
+            stats.high = stats.high.max(s.equity);
+            stats.low = stats.low.min(s.equity);
+            peak = peak.max(s.equity);
+            stats.max_drawdown_pct = stats.max_drawdown_pct.max(-pct(peak, s.equity));
+        }
+        Some(stats)
+    }
+}
+
+/// Scheduled writer of the equity curve.
+#[derive(Clone)]
+pub struct EquitySnapshotter {
+    event_bus: EventBus,
+    store: MarketStore,
+    curve: EquityCurve,
+    dir: PathBuf,
+    interval: Duration,
+}
+
+impl EquitySnapshotter {
+    pub fn new(event_bus: EventBus, config: &AppConfig, store: MarketStore) -> Self {
+        let config = &config.equity_snapshots;
+        Self {
+            event_bus,
+            store,
+            curve: EquityCurve::new(config.max_snapshots),
+            dir: PathBuf::from(&config.dir),
+            interval: Duration::from_secs(config.interval_secs.max(1)),
+        }
+    }
+
+    /// The curve being recorded (shared with `/equity`).
+    pub fn curve(&self) -> EquityCurve {
+        self.curve.clone()
+    }
+
+    /// Value the account through `reporter` into the curve and today's file;
+    /// None (nothing recorded) until the reporter knows its starting equity.
+    pub fn record(&self, reporter: &TradeReporter, now: DateTime<Utc>) -> Option<EquitySnapshot> {
+        let sample = reporter.equity_now(&self.store, now)?;
+        let snapshot = EquitySnapshot::from_sample(&sample, now);
+        if let Err(e) = append_snapshot(&self.dir, &snapshot) {
+            warn!("⚠️ [EQUITY] Failed to persist snapshot: {}", e);
+        }
+        self.curve.push(snapshot.clone());
+        Some(snapshot)
+    }
+
+    /// Seed the curve from the daily files, then snapshot `reporter`'s
+    /// valuation every interval.
+    pub async fn start(&self, reporter: TradeReporter) {
+        let now = Utc::now();
+        let lookback_secs = self
+            .interval
+            .as_secs()
+            .saturating_mul(self.curve.capacity as u64)
+            .min(MAX_RESTORE_DAYS * 86_400);
+        let since = now - chrono::Duration::seconds(lookback_secs as i64);
+        let restored = read_snapshots(&self.dir, since, now);
+        for snapshot in restored {
+            self.curve.push(snapshot);
+        }
+
+        let snapshotter = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+
+        tokio::spawn(async move {
+            info!(
+                "💰 [EQUITY] Snapshotting every {}s ({} restored)",
+                snapshotter.interval.as_secs(),
+                snapshotter.curve.len()
+            );
+            let mut ticker = tokio::time::interval(snapshotter.interval);
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = ticker.tick() => {
+                        snapshotter.record(&reporter, Utc::now());
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,107 @@
+//! Unit tests for the equity curve - reporter samples, the ring buffer, daily
+//! files and the `/equity` window.
+
+#[cfg(test)]
+mod equity_tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    use crate::money::dec;
+    use crate::services::equity::*;
+    use crate::services::reporting::EquitySample;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    fn point(ts: DateTime<Utc>, equity: f64) -> EquitySnapshot {
+        EquitySnapshot {
+            ts,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            equity: dec(equity),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_from_a_reporter_sample() {
+        let sample = EquitySample {
+            ts: at(1).to_rfc3339(),
+            realized_pnl: dec(10.0),
+            unrealized_pnl: dec(-4.0),
+            equity: dec(1006.0),
+        };
+        let snapshot = EquitySnapshot::from_sample(&sample, at(1));
+        assert_eq!(snapshot.ts, at(1));
+        assert_eq!(
+            (
+                snapshot.realized_pnl,
+                snapshot.unrealized_pnl,
+                snapshot.equity
+            ),
+            (dec(10.0), dec(-4.0), dec(1006.0))
+        );
+    }
+
+    #[test]
+    fn test_curve_keeps_the_latest_snapshots() {
+        let curve = EquityCurve::new(3);
+        assert!(curve.is_empty());
+        for hour in 0..5 {
+            curve.push(point(at(hour), 100.0 + hour as f64));
+        }
+        assert_eq!(curve.len(), 3);
+        assert_eq!(curve.latest().unwrap().equity, dec(104.0));
+        let window = curve.since(at(3));
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].ts, at(3));
+    }
+
+    #[test]
+    fn test_daily_files_round_trip() {
+        let dir = std::env::temp_dir().join(format!("autohedge_equity_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let day_one = point(at(23), 100.0);
+        let day_two = point(at(23) + Duration::hours(2), 101.0);
+        append_snapshot(&dir, &day_one).unwrap();
+        append_snapshot(&dir, &day_two).unwrap();
+        assert!(snapshot_path(&dir, at(0).date_naive()).exists());
+        // A torn final record is skipped
+        let second_day = snapshot_path(&dir, day_two.ts.date_naive());
+        let mut text = std::fs::read_to_string(&second_day).unwrap();
+        text.push_str("{\"ts\":\"2026-03-02T02:0");
+        std::fs::write(&second_day, text).unwrap();
+
+        let all = read_snapshots(&dir, at(0), at(23) + Duration::days(1));
+        assert_eq!(all, vec![day_one.clone(), day_two]);
+        assert_eq!(read_snapshots(&dir, at(0), at(23)), vec![day_one]);
+        assert!(read_snapshots(&dir, at(0) - Duration::days(3), at(0)).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("24h"), Ok(Duration::hours(24)));
+        assert_eq!(parse_window("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_window("7d"), Ok(Duration::days(7)));
+        assert_eq!(parse_window("90s"), Ok(Duration::seconds(90)));
+        for bad in ["", "h", "24", "24x", "0h", "-1h", "99999999999999999d"] {
+            assert!(parse_window(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_window_stats() {
+        assert!(EquityStats::from_snapshots(&[]).is_none());
+        let curve: Vec<EquitySnapshot> = [100.0, 110.0, 99.0, 105.0]
+            .iter()
+            .enumerate()
+            .map(|(i, e)| point(at(i as u32), *e))
+            .collect();
+        let stats = EquityStats::from_snapshots(&curve).unwrap();
+        assert_eq!((stats.start, stats.end), (dec(100.0), dec(105.0)));
+        assert_eq!((stats.high, stats.low), (dec(110.0), dec(99.0)));
+        assert!((stats.change_pct - 5.0).abs() < 1e-9);
+        assert!((stats.max_drawdown_pct - 10.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,323 @@
+//! Event sourcing: append-only log of the bus and session replay.
+//!
+//! `EventRecorder` writes every event published on the `EventBus` (except
+//! config updates) to `<dir>/events-<session start>.<ext>`, one
+//! `RecordedEvent` per line in JSONL or as back-to-back MessagePack maps.
+//! `ReplayService` re-publishes a recorded session onto a bus, keeping the
+//! original spacing divided by `speed`, so "why did it buy here?" can be
+//! reproduced step by step against the same market data.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::EventLogConfig;
+use crate::error::{AutoHedgeError, Result};
+use crate::events::Event;
+
+/// Unflushed records are written out at least this often
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventLogFormat {
+    Jsonl,
+    MessagePack,
+}
+
+impl EventLogFormat {
+    /// Parse a config value ("jsonl", "msgpack").
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "jsonl" | "json" => Some(EventLogFormat::Jsonl),
+            "msgpack" | "messagepack" | "mpk" => Some(EventLogFormat::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::parse)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            EventLogFormat::Jsonl => "jsonl",
+            EventLogFormat::MessagePack => "msgpack",
+        }
+    }
+}
+
+/// One logged bus event.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedEvent {
+    /// Publish time (epoch ms, as seen by the recorder)
+    pub ts_ms: i64,
+    pub event: Event,
+}
+
+/// Whether `event` goes into the log.
+pub fn is_recorded(event: &Event) -> bool {
+    !matches!(event, Event::Config(_))
+}
+
+/// Log file of the session started at `started`.
+pub fn session_path(dir: &Path, format: EventLogFormat, started: DateTime<Utc>) -> PathBuf {
+    dir.join(format!(
+        "events-{}.{}",
+        started.format("%Y%m%dT%H%M%SZ"),
+        format.extension()
+    ))
+}
+
+pub fn write_record(
+    out: &mut dyn Write,
+    format: EventLogFormat,
+    record: &RecordedEvent,
+) -> Result<()> {
+    match format {
+        EventLogFormat::Jsonl => writeln!(out, "{}", serde_json::to_string(record)?)?,
+        // Named (map) encoding so old logs still decode if event fields are added
+        EventLogFormat::MessagePack => out.write_all(&rmp_serde::to_vec_named(record)?)?,
+    }
+    Ok(())
+}
+
+/// Decode every record of a log written in `format`.
+pub fn read_records(input: &mut dyn Read, format: EventLogFormat) -> Result<Vec<RecordedEvent>> {
+    let mut records = Vec::new();
+    match format {
+        EventLogFormat::Jsonl => {
+            for line in BufReader::new(input).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                records.push(serde_json::from_str(&line)?);
+            }
+        }
+        EventLogFormat::MessagePack => {
+            let mut bytes = Vec::new();
+            input.read_to_end(&mut bytes)?;
+            let mut cursor = std::io::Cursor::new(bytes.as_slice());
+            while (cursor.position() as usize) < bytes.len() {
+                let mut de = rmp_serde::Deserializer::new(&mut cursor);
+                records.push(Deserialize::deserialize(&mut de)?);
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Read the log at `path`, with the format taken from its extension.
+pub fn read_log(path: &Path) -> Result<Vec<RecordedEvent>> {
+    let format = EventLogFormat::from_path(path).ok_or_else(|| {
+        AutoHedgeError::Io(format!("{}: unknown event log extension", path.display()))
+    })?;
+    let mut file =
+        File::open(path).map_err(|e| AutoHedgeError::Io(format!("{}: {}", path.display(), e)))?;
+    read_records(&mut file, format)
+}
+
+/// Appends the session's bus events to the event log.
+pub struct EventRecorder {
+    event_bus: EventBus,
+    path: PathBuf,
+    format: EventLogFormat,
+}
+
+impl EventRecorder {
+    pub fn new(config: &EventLogConfig, event_bus: EventBus) -> Self {
+        let format = EventLogFormat::parse(&config.format).unwrap_or_else(|| {
+            warn!(
+                "⚠️ Unknown event_log.format '{}', using jsonl",
+                config.format
+            );
+            EventLogFormat::Jsonl
+        });
+        Self {
+            event_bus,
+            path: session_path(Path::new(&config.dir), format, Utc::now()),
+            format,
+        }
+    }
+
+    /// Subscribe now (so nothing published after this call is missed) and
+    /// record until the session is stopped.
+    pub fn start(self) {
+        let mut rx = self.event_bus.subscribe_in_order("event_log");
+        let shutdown = self.event_bus.shutdown().clone();
+        let worker = shutdown.track_worker();
+
+        let file = self
+            .path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+            });
+        let mut out = match file {
+            Ok(f) => BufWriter::new(f),
+            Err(e) => {
+                error!("❌ [EVENT-LOG] Cannot open {}: {}", self.path.display(), e);
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let _worker = worker;
+            info!(
+                "🎞️ [EVENT-LOG] Recording bus events to {}",
+                self.path.display()
+            );
+            let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+            let mut recorded: u64 = 0;
+            let mut dropped: u64 = 0;
+            loop {
+                let event = tokio::select! {
+                    // Drain is over: record whatever is still queued, then flush and exit
+                    _ = shutdown.stopped() => {
+                        while let Ok(event) = rx.try_recv() {
+                            recorded += self.record(&mut out, &event) as u64;
+                        }
+                        break;
+                    }
+                    _ = flush.tick() => {
+                        if let Err(e) = out.flush() {
+                            warn!("⚠️ [EVENT-LOG] Flush failed: {}", e);
+                        }
+                        continue;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(n)) => {
+                            dropped += n;
+                            warn!("⚠️ [EVENT-LOG] Recorder lagged, {} event(s) not logged", n);
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                recorded += self.record(&mut out, &event) as u64;
+            }
+            if let Err(e) = out.flush() {
+                error!("❌ [EVENT-LOG] Final flush failed: {}", e);
+            }
+            info!(
+                "🎞️ [EVENT-LOG] Stopped: {} event(s) recorded, {} dropped",
+                recorded, dropped
+            );
+        });
+    }
+
+    fn record(&self, out: &mut dyn Write, event: &Event) -> bool {
+        if !is_recorded(event) {
+            return false;
+        }
+        let record = RecordedEvent {
+            ts_ms: Utc::now().timestamp_millis(),
+            event: event.clone(),
+        };
+        match write_record(out, self.format, &record) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("⚠️ [EVENT-LOG] Failed to write event: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Which recorded events a replay re-publishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayScope {
+    /// Market data only: the running strategies re-derive every decision
+    Market,
+    /// Everything, including orders and fills (paper sessions and dashboards)
+    All,
+}
+
+impl ReplayScope {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "market" => Some(ReplayScope::Market),
+            "all" => Some(ReplayScope::All),
+            _ => None,
+        }
+    }
+
+    pub fn includes(&self, event: &Event) -> bool {
+        match self {
+            ReplayScope::Market => matches!(event, Event::Market(_)),
+            ReplayScope::All => true,
+        }
+    }
+}
+
+/// Wait before re-publishing a record logged `gap_ms` after the previous one.
+/// `speed` 1.0 keeps the original timing, 10.0 is ten times faster; 0 (or
+/// less) replays back to back.
+pub fn replay_delay(gap_ms: i64, speed: f64) -> Duration {
+    if speed <= 0.0 || gap_ms <= 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(gap_ms as f64 / 1000.0 / speed)
+}
+
+/// Re-publishes a recorded session onto a bus.
+pub struct ReplayService {
+    event_bus: EventBus,
+    speed: f64,
+    scope: ReplayScope,
+}
+
+impl ReplayService {
+    pub fn new(event_bus: EventBus, speed: f64, scope: ReplayScope) -> Self {
+        Self {
+            event_bus,
+            speed,
+            scope,
+        }
+    }
+
+    /// Publish the in-scope `records` in order. Stops early when the session
+    /// starts draining. Returns the number of events published.
+    pub async fn run(&self, records: Vec<RecordedEvent>) -> usize {
+        let shutdown = self.event_bus.shutdown().clone();
+        let mut published = 0;
+        let mut last_ts = None;
+        for record in records {
+            if !self.scope.includes(&record.event) {
+                continue;
+            }
+            let delay = replay_delay(record.ts_ms - last_ts.unwrap_or(record.ts_ms), self.speed);
+            last_ts = Some(record.ts_ms);
+            if delay.is_zero() {
+                if shutdown.is_draining() {
+                    break;
+                }
+                // Let subscribers keep up with a back-to-back replay
+                tokio::task::yield_now().await;
+            } else {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+            let _ = self.event_bus.publish(record.event);
+            published += 1;
+        }
+        published
+    }
+}
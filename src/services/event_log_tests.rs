@@ -0,0 +1,228 @@
+//! Unit tests for the event log (encoding, recording, replay pacing).
+
+#[cfg(test)]
+mod event_log_tests {
+    use crate::bus::EventBus;
+    use crate::config::EventLogConfig;
+    use crate::events::{ControlCommand, Event, ExecutionReport, MarketEvent, SystemEvent};
+    use crate::money::dec;
+    use crate::services::event_log::*;
+    use chrono::{TimeZone, Utc};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    fn quote(symbol: &str, bid: f64) -> Event {
+        Event::Market(MarketEvent::Quote {
+            symbol: symbol.to_string(),
+            bid,
+            ask: bid + 1.0,
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        })
+    }
+
+    fn execution() -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: "o1".to_string(),
+            status: "filled".to_string(),
+            side: "buy".to_string(),
+            price: Some(dec(101.5)),
+            qty: Some(dec(0.25)),
+            signal_price: None,
+            limit_price: Some(dec(101.5)),
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn records() -> Vec<RecordedEvent> {
+        vec![
+            RecordedEvent {
+                ts_ms: 1_000,
+                event: quote("BTC/USD", 100.0),
+            },
+            RecordedEvent {
+                ts_ms: 1_250,
+                event: execution(),
+            },
+            RecordedEvent {
+                ts_ms: 1_300,
+                event: Event::Control(ControlCommand::AdjustExits {
+                    symbol: "BTC/USD".to_string(),
+                    take_profit: Some(dec(110.0)),
+                    stop_loss: None,
+                }),
+            },
+            RecordedEvent {
+                ts_ms: 2_000,
+                event: Event::System(SystemEvent::Resume {
+                    timestamp: "2026-03-02T10:00:01Z".to_string(),
+                }),
+            },
+        ]
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("autohedge_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn only_file(dir: &Path) -> PathBuf {
+        let files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        files[0].clone()
+    }
+
+    // ============= Format Tests =============
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(EventLogFormat::parse("JSONL"), Some(EventLogFormat::Jsonl));
+        assert_eq!(
+            EventLogFormat::parse("msgpack"),
+            Some(EventLogFormat::MessagePack)
+        );
+        assert_eq!(EventLogFormat::parse("csv"), None);
+        assert_eq!(
+            EventLogFormat::from_path(Path::new("data/events/x.msgpack")),
+            Some(EventLogFormat::MessagePack)
+        );
+    }
+
+    #[test]
+    fn test_session_path() {
+        let started = Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        assert_eq!(
+            session_path(Path::new("data/events"), EventLogFormat::Jsonl, started),
+            PathBuf::from("data/events/events-20260302T093000Z.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        for format in [EventLogFormat::Jsonl, EventLogFormat::MessagePack] {
+            let mut buf = Vec::new();
+            for record in records() {
+                write_record(&mut buf, format, &record).unwrap();
+            }
+            let decoded = read_records(&mut buf.as_slice(), format).unwrap();
+            assert_eq!(decoded.len(), 4, "{:?}", format);
+            assert_eq!(decoded[1].ts_ms, 1_250);
+            match &decoded[1].event {
+                Event::Execution(r) => {
+                    assert_eq!(r.price, Some(dec(101.5)));
+                    assert_eq!(r.qty, Some(dec(0.25)));
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+            match &decoded[2].event {
+                Event::Control(ControlCommand::AdjustExits { take_profit, .. }) => {
+                    assert_eq!(*take_profit, Some(dec(110.0)))
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_msgpack_is_smaller_than_jsonl() {
+        let encoded = |format| {
+            let mut buf = Vec::new();
+            for record in records() {
+                write_record(&mut buf, format, &record).unwrap();
+            }
+            buf.len()
+        };
+        assert!(encoded(EventLogFormat::MessagePack) < encoded(EventLogFormat::Jsonl));
+    }
+
+    // ============= Replay Tests =============
+
+    #[test]
+    fn test_replay_delay_scales_gaps() {
+        assert_eq!(replay_delay(500, 1.0), Duration::from_millis(500));
+        assert_eq!(replay_delay(500, 10.0), Duration::from_millis(50));
+        assert_eq!(replay_delay(500, 0.0), Duration::ZERO);
+        assert_eq!(replay_delay(-5, 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_replay_scope() {
+        assert_eq!(ReplayScope::parse("ALL"), Some(ReplayScope::All));
+        assert_eq!(ReplayScope::parse("orders"), None);
+        assert!(ReplayScope::Market.includes(&quote("BTC/USD", 1.0)));
+        assert!(!ReplayScope::Market.includes(&execution()));
+        assert!(ReplayScope::All.includes(&execution()));
+    }
+
+    #[tokio::test]
+    async fn test_replay_publishes_in_scope_events_in_order() {
+        let bus = EventBus::new(100);
+        let mut rx = bus.subscribe("test");
+        let mut recorded = records();
+        recorded.push(RecordedEvent {
+            ts_ms: 2_100,
+            event: quote("ETH/USD", 50.0),
+        });
+
+        let published = ReplayService::new(bus.clone(), 0.0, ReplayScope::Market)
+            .run(recorded)
+            .await;
+        assert_eq!(published, 2);
+        let symbols: Vec<String> = (0..2)
+            .map(|_| match rx.try_recv().unwrap() {
+                Event::Market(MarketEvent::Quote { symbol, .. }) => symbol,
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(symbols, ["BTC/USD", "ETH/USD"]);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_stops_when_draining() {
+        let bus = EventBus::new(100);
+        bus.shutdown().begin_drain();
+        let published = ReplayService::new(bus, 1.0, ReplayScope::All)
+            .run(records())
+            .await;
+        assert_eq!(published, 0);
+    }
+
+    // ============= Recorder Tests =============
+
+    #[tokio::test]
+    async fn test_recorder_logs_session_until_stopped() {
+        let dir = temp_dir("event_log");
+        let bus = EventBus::new(100);
+        let config = EventLogConfig {
+            enabled: true,
+            dir: dir.to_string_lossy().to_string(),
+            format: "msgpack".to_string(),
+        };
+        EventRecorder::new(&config, bus.clone()).start();
+
+        bus.publish(quote("BTC/USD", 100.0)).unwrap();
+        bus.publish(execution()).unwrap();
+        bus.shutdown().finish();
+        assert!(bus.shutdown().wait_workers(Duration::from_secs(2)).await);
+
+        let path = only_file(&dir);
+        assert_eq!(
+            EventLogFormat::from_path(&path),
+            Some(EventLogFormat::MessagePack)
+        );
+        let logged = read_log(&path).unwrap();
+        assert_eq!(logged.len(), 2);
+        assert!(matches!(logged[0].event, Event::Market(_)));
+        assert!(matches!(logged[1].event, Event::Execution(_)));
+        assert!(logged[0].ts_ms <= logged[1].ts_ms);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
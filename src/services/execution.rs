@@ -2,8 +2,9 @@ use crate::agents::{execution::ExecutionAgent, Agent};
 use crate::bus::EventBus;
 use crate::config::AppConfig;
 use crate::data::store::MarketStore;
-use crate::events::{Event, ExecutionReport, OrderRequest};
+use crate::events::{Event, ExecutionReport, OrderRequest, SystemEvent, TradeOrigin};
 use crate::exchange::{
+    symbol_meta::SymbolMetaRegistry,
     traits::TradingApi,
     types::{
         OrderType as ExOrderType, PlaceOrderRequest as ExPlaceOrderRequest, Side as ExSide,
@@ -11,9 +12,20 @@ use crate::exchange::{
     },
 };
 use crate::llm::LLMQueue;
+use crate::logging::trade_span;
+use crate::money::{dec, float};
+use crate::services::capital::{bucket_usage, CapitalAllocator};
+use crate::services::execution_utils::{
+    margin_buying_power, normalize_order, publish_rejection, stop_limit_exit, submit_entry,
+};
+use crate::services::health::scale_entry_sizing;
+use crate::services::policy::PolicyEngine;
 use crate::services::position_monitor::{PositionInfo, PositionTracker};
+use crate::services::pretrade::{pre_trade_check, ProposedOrder};
+use rust_decimal::Decimal;
 use std::sync::Arc;
-use tracing::{error, info};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn, Instrument};
 
 pub struct ExecutionEngine {
     event_bus: EventBus,
@@ -22,12 +34,17 @@ pub struct ExecutionEngine {
     llm: LLMQueue,
     config: AppConfig,
     tracker: PositionTracker,
+    symbol_meta: SymbolMetaRegistry,
+    /// Per-strategy capital buckets (`capital_allocation`)
+    allocator: CapitalAllocator,
+    /// Size limits of the compliance policy, checked on the sized entry
+    policy: Option<PolicyEngine>,
 }
 
 #[derive(serde::Deserialize)]
 struct ExecutionOutput {
     action: String,
-    qty: f64,
+    qty: Decimal,
     order_type: String,
 }
 
@@ -47,17 +64,37 @@ impl ExecutionEngine {
             llm,
             config,
             tracker,
+            symbol_meta: SymbolMetaRegistry::new(),
+            allocator: CapitalAllocator::new(),
+            policy: None,
         }
     }
 
+    /// Share the startup-loaded symbol rules used to round orders.
+    pub fn with_symbol_meta(mut self, symbol_meta: SymbolMetaRegistry) -> Self {
+        self.symbol_meta = symbol_meta;
+        self
+    }
+
+    /// Check sized entries against `policy.max_trade_notional`,
+    /// `max_volume_pct` and `max_daily_turnover` (the risk engine's policy).
+    pub fn with_policy(mut self, policy: PolicyEngine) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     pub async fn start(&self) {
-        let mut rx = self.event_bus.subscribe();
+        let mut rx = self.event_bus.subscribe("execution");
         let exchange_clone = self.exchange.clone();
         let store_clone = self.market_store.clone();
         let llm_clone = self.llm.clone();
         let bus_clone = self.event_bus.clone();
-        let config_clone = self.config.clone();
+        let mut config_clone = self.config.clone();
         let tracker_clone = self.tracker.clone();
+        let symbol_meta_clone = self.symbol_meta.clone();
+        let allocator_clone = self.allocator.clone();
+        let policy_clone = self.policy.clone();
+        let shutdown = self.event_bus.shutdown().clone();
 
         tokio::spawn(async move {
             info!("⚡ Execution Engine Started");
@@ -68,8 +105,43 @@ impl ExecutionEngine {
                 config_clone.defaults.min_order_amount,
                 config_clone.defaults.max_order_amount
             );
-            while let Ok(event) = rx.recv().await {
+            // Entry size multiplier from the degradation ladder (Event::Health)
+            let mut health_size_factor = 1.0;
+            // Set by the circuit breaker (Event::System); cleared by /resume
+            let mut halted = false;
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => {
+                        info!("[EXECUTION] Draining - no new orders accepted");
+                        break;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if let Event::Health(health) = &event {
+                    health_size_factor = health.size_factor;
+                    continue;
+                }
+                // Hot-reloaded tunables apply to orders received from now on
+                if let Event::Config(update) = &event {
+                    config_clone = (*update.config).clone();
+                    continue;
+                }
+                if let Event::System(system) = &event {
+                    halted = matches!(system, SystemEvent::Halt { .. });
+                    continue;
+                }
                 if let Event::Order(req) = event {
+                    if halted && req.action == "buy" {
+                        warn!(
+                            "🛑 [EXECUTION] Circuit breaker tripped - rejecting BUY {}",
+                            req.symbol
+                        );
+                        continue;
+                    }
                     info!("[EXECUTION] Received OrderRequest: symbol={} action={} order_type={} limit_price={:?} sl={:?} tp={:?}",
                           req.symbol, req.action, req.order_type, req.limit_price, req.stop_loss, req.take_profit);
 
@@ -77,18 +149,44 @@ impl ExecutionEngine {
                     let store = store_clone.clone();
                     let llm = llm_clone.clone();
                     let bus = bus_clone.clone();
-                    let config = config_clone.clone();
+                    let mut config = config_clone.clone();
+                    if req.action == "buy" {
+                        scale_entry_sizing(&mut config, health_size_factor);
+                    }
+                    bus.diagnostics().apply(&req.symbol, &mut config);
                     let tracker = tracker_clone.clone();
-
-                    tokio::spawn(async move {
-                        Self::execute_order(req, exchange, store, llm, bus, config, tracker).await;
-                    });
+                    let symbol_meta = symbol_meta_clone.clone();
+                    let allocator = allocator_clone.clone();
+                    let policy = policy_clone.clone();
+                    let in_flight = shutdown.track_order();
+
+                    let span = trade_span(&req.symbol, req.correlation_id.as_deref());
+                    tokio::spawn(
+                        async move {
+                            let _in_flight = in_flight;
+                            Self::execute_order(
+                                req,
+                                exchange,
+                                store,
+                                llm,
+                                bus,
+                                config,
+                                tracker,
+                                symbol_meta,
+                                allocator,
+                                policy,
+                            )
+                            .await;
+                        }
+                        .instrument(span),
+                    );
                 }
             }
-            info!("[EXECUTION] Event loop ended (channel closed)");
+            info!("[EXECUTION] Event loop ended");
         });
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn execute_order(
         req: OrderRequest,
         exchange: Arc<dyn TradingApi>,
@@ -97,6 +195,9 @@ impl ExecutionEngine {
         bus: EventBus,
         config: AppConfig,
         tracker: PositionTracker,
+        symbol_meta: SymbolMetaRegistry,
+        allocator: CapitalAllocator,
+        policy: Option<PolicyEngine>,
     ) {
         let is_crypto = config.trading_mode.to_lowercase() == "crypto";
         info!(
@@ -111,17 +212,15 @@ impl ExecutionEngine {
                 req.symbol
             );
 
-            let estimated_price = store
-                .get_latest_quote(&req.symbol)
-                .map(|q| q.bid_price)
-                .unwrap_or(0.0);
+            let quote = store.get_latest_quote(&req.symbol);
+            let estimated_price = quote.as_ref().map(|q| dec(q.bid_price)).unwrap_or_default();
 
             info!(
                 "[EXECUTION] Estimated SELL price for {}: ${:.8}",
                 req.symbol, estimated_price
             );
 
-            if estimated_price == 0.0 {
+            if estimated_price.is_zero() {
                 error!(
                     "[EXECUTION] Cannot estimate price for {}. No market data available.",
                     req.symbol
@@ -130,7 +229,17 @@ impl ExecutionEngine {
             }
 
             // Prefer local tracker qty; fall back to exchange positions as a safety net.
-            let tracked_qty = tracker.get_position(&req.symbol).map(|p| p.qty);
+            // Only the filled part of a partially filled entry is owned.
+            let tracked = tracker.get_position(&req.symbol);
+            if tracked
+                .as_ref()
+                .is_some_and(|p| p.remaining_qty > Decimal::ZERO)
+            {
+                tracker
+                    .cancel_working_entries(&req.symbol, exchange.as_ref())
+                    .await;
+            }
+            let tracked_qty = tracked.map(|p| p.filled_qty);
             info!(
                 "[EXECUTION] Tracker qty for {}: {:?}",
                 req.symbol, tracked_qty
@@ -146,19 +255,19 @@ impl ExecutionEngine {
                 match exchange.get_positions().await {
                     Ok(positions) => {
                         let position = positions.into_iter().find(|p| p.symbol == req.symbol);
-                        position.map(|p| p.qty).unwrap_or(0.0)
+                        position.map(|p| p.qty).unwrap_or_default()
                     }
                     Err(e) => {
                         error!(
                             "[EXECUTION] Failed to fetch positions for sell {}: {}",
                             req.symbol, e
                         );
-                        0.0
+                        Decimal::ZERO
                     }
                 }
             };
 
-            if qty <= 0.0 {
+            if qty <= Decimal::ZERO {
                 error!("[EXECUTION] No quantity found for {} position", req.symbol);
                 return;
             }
@@ -169,6 +278,27 @@ impl ExecutionEngine {
                 ExTimeInForce::Day
             };
 
+            // Stop-loss exits rest as a limit under the stop first (`stop_limit`)
+            if let Some(stop) = req.stop_loss.filter(|_| config.stop_limit.enabled) {
+                stop_limit_exit(
+                    exchange.as_ref(),
+                    &symbol_meta,
+                    &tracker,
+                    &bus,
+                    &req,
+                    qty,
+                    stop,
+                    (
+                        estimated_price,
+                        quote.map(|q| dec((q.bid_price + q.ask_price) / 2.0)),
+                    ),
+                    time_in_force,
+                    &config.stop_limit,
+                )
+                .await;
+                return;
+            }
+
             let api_req = ExPlaceOrderRequest {
                 symbol: req.symbol.clone(),
                 qty: Some(qty),
@@ -177,7 +307,17 @@ impl ExecutionEngine {
                 order_type: ExOrderType::Market,
                 time_in_force,
                 limit_price: None,
+                bracket: None,
+                stop_price: None,
+                reduce_only: true,
+                metadata: req.metadata.clone(),
             };
+            let Some(api_req) =
+                normalize_order(&symbol_meta, exchange.as_ref(), api_req, estimated_price).await
+            else {
+                return;
+            };
+            let qty = api_req.qty.unwrap_or(qty);
 
             info!(
                 "[ORDER] Submitting SELL: qty={:.8} symbol={} est_price=${:.8} est_value=${:.2}",
@@ -203,6 +343,11 @@ impl ExecutionEngine {
                         side: "sell".to_string(),
                         price: Some(estimated_price),
                         qty: Some(qty),
+                        signal_price: quote.map(|q| dec((q.bid_price + q.ask_price) / 2.0)),
+                        limit_price: None,
+                        strategy_id: req.strategy_id.clone(),
+                        correlation_id: req.correlation_id.clone(),
+                        metadata: req.metadata.clone(),
                     };
                     info!(
                         "[EXECUTION] Publishing ExecutionReport for SELL {}",
@@ -210,24 +355,63 @@ impl ExecutionEngine {
                     );
                     bus.publish(Event::Execution(report)).ok();
                 }
-                Err(e) => error!("[FAILED] SELL Order Submission: {}", e),
+                Err(e) => {
+                    error!("[FAILED] SELL Order Submission: {}", e);
+                    publish_rejection(&bus, &req.symbol, "sell", estimated_price, qty);
+                }
             }
 
             return;
         }
 
+        // The signal went stale before it could be placed
+        if req.expired(chrono::Utc::now()) {
+            warn!(
+                "⌛ [EXECUTION] Skip {}: entry expired (valid until {})",
+                req.symbol,
+                req.valid_until.as_deref().unwrap_or_default()
+            );
+            return;
+        }
+
+        // Conditional orders carry their own amount and skip the agent
+        let conditional = req.qty > Decimal::ZERO
+            && req
+                .origin
+                .as_ref()
+                .is_some_and(|o| o.mode == TradeOrigin::CONDITIONAL);
+
         // Handle buy orders (original logic with ExecutionAgent) or HFT fast path
-        let mut order = if req.order_type == "hft_buy" {
+        let mut order = if conditional {
+            info!("[EXECUTION] Conditional order for {}", req.symbol);
+            ExecutionOutput {
+                action: "buy".to_string(),
+                qty: req.qty,
+                order_type: req.order_type.clone(),
+            }
+        } else if req.order_type == "hft_buy" {
             info!("[EXECUTION] HFT Fast Path for {}", req.symbol);
             ExecutionOutput {
                 action: "buy".to_string(),
-                qty: 0.0, // Will be sized to min_order_amount by logic below
+                qty: Decimal::ZERO, // Will be sized to min_order_amount by logic below
                 order_type: "limit".to_string(),
             }
+        } else if req
+            .origin
+            .as_ref()
+            .is_some_and(|o| o.mode == TradeOrigin::FALLBACK)
+        {
+            // Rule-based entry while the LLM is down: no Execution agent either
+            info!("[EXECUTION] Fallback Fast Path for {}", req.symbol);
+            ExecutionOutput {
+                action: "buy".to_string(),
+                qty: Decimal::ZERO, // Will be sized to min_order_amount by logic below
+                order_type: "market".to_string(),
+            }
         } else {
             info!("[EXECUTION] BUY path (agent-driven) for {}", req.symbol);
 
-            let execution_agent = ExecutionAgent;
+            let execution_agent = ExecutionAgent::new(&config, &req.symbol);
             let exec_input = format!(
                 "Symbol: {}\nRisk Analysis: Approved\nAction: Create Order JSON",
                 req.symbol
@@ -268,15 +452,15 @@ impl ExecutionEngine {
         );
 
         if order.action == "buy" || order.action == "sell" {
-            let history = store.get_quote_history(&req.symbol);
-            let estimated_price = if let Some(latest) = history.last() {
+            let latest = store.get_latest_quote(&req.symbol);
+            let estimated_price = if let Some(latest) = &latest {
                 if order.action == "buy" {
-                    latest.ask_price
+                    dec(latest.ask_price)
                 } else {
-                    latest.bid_price
+                    dec(latest.bid_price)
                 }
             } else {
-                0.0
+                Decimal::ZERO
             };
 
             info!(
@@ -284,7 +468,7 @@ impl ExecutionEngine {
                 req.symbol, estimated_price
             );
 
-            if estimated_price == 0.0 {
+            if estimated_price.is_zero() {
                 error!(
                     "[EXECUTION] Cannot estimate price for {}. No market data available.",
                     req.symbol
@@ -299,12 +483,39 @@ impl ExecutionEngine {
                 req.symbol, order.qty, estimated_value
             );
 
-            if estimated_value < config.defaults.min_order_amount {
+            if order.action == "buy" && !conditional {
+                let size_factor =
+                    config.warmup_size_factor(&req.symbol, store.quote_count(&req.symbol));
+                if size_factor < 1.0 {
+                    estimated_value *= dec(size_factor);
+                    order.qty *= dec(size_factor);
+                    info!(
+                        "[EXECUTION] Warmup sizing for {} ({:.0}%) => qty={:.8} est_value=${:.2}",
+                        req.symbol,
+                        size_factor * 100.0,
+                        order.qty,
+                        estimated_value
+                    );
+                }
+            }
+
+            // Limits and balance are in the reporting currency; values in the quote currency
+            let Some(fx) = store.quote_fx_rate(&config, &req.symbol).map(dec) else {
+                error!(
+                    "[EXECUTION] No {} rate for {}. Cannot apply order limits.",
+                    config.quote_currency_for(&req.symbol),
+                    req.symbol
+                );
+                return;
+            };
+            let min_order_amount = dec(config.defaults.min_order_amount) / fx;
+            let max_order_amount = dec(config.defaults.max_order_amount) / fx;
+            if estimated_value < min_order_amount {
                 info!(
                     "[RISK] Order value ${:.2} is below minimum ${:.2}. Adjusting.",
-                    estimated_value, config.defaults.min_order_amount
+                    estimated_value, min_order_amount
                 );
-                estimated_value = config.defaults.min_order_amount;
+                estimated_value = min_order_amount;
                 order.qty = estimated_value / estimated_price;
                 info!(
                     "[RISK] Adjusted qty for min order => qty={:.8} est_value=${:.2}",
@@ -312,12 +523,12 @@ impl ExecutionEngine {
                 );
             }
 
-            if estimated_value > config.defaults.max_order_amount {
+            if estimated_value > max_order_amount {
                 info!(
                     "[RISK] Order value ${:.2} exceeds limit ${:.2}. Capping.",
-                    estimated_value, config.defaults.max_order_amount
+                    estimated_value, max_order_amount
                 );
-                estimated_value = config.defaults.max_order_amount;
+                estimated_value = max_order_amount;
                 order.qty = estimated_value / estimated_price;
                 info!(
                     "[RISK] Adjusted qty for max cap => qty={:.8} est_value=${:.2}",
@@ -325,17 +536,36 @@ impl ExecutionEngine {
                 );
             }
 
-            // Balance Check (Post-Adjustment)
+            // Balance Check (Post-Adjustment), against the strategy's capital bucket
+            let mut account_snapshot = None;
+            let bucket = config.strategy_mode_for(&req.symbol);
+            let mut total_power = Decimal::ZERO;
             if order.action == "buy" {
                 match exchange.get_account().await {
                     Ok(account) => {
-                        let buying_power = account.buying_power.or(account.cash).unwrap_or(0.0);
+                        let balance = account.buying_power.or(account.cash).unwrap_or_default();
+                        total_power = margin_buying_power(&config, &req.symbol, balance);
+                        let buying_power = match allocator.available(
+                            &config.capital_allocation,
+                            &bucket,
+                            total_power,
+                            &bucket_usage(&config, &tracker, &store),
+                        ) {
+                            Ok(available) => available / fx,
+                            Err(denied) => {
+                                warn!(
+                                    "💼 [CAPITAL] Skip {} ({} bucket): {}",
+                                    req.symbol, bucket, denied
+                                );
+                                return;
+                            }
+                        };
                         let required_funds = estimated_value; // No buffer here, exact check against value
 
                         if buying_power < required_funds {
-                            let max_affordable = buying_power * 0.99; // 1% buffer for fees
-                            if max_affordable < config.defaults.min_order_amount {
-                                error!("[EXECUTION] Insufficient funds. Available: ${:.2}, Min Required: ${:.2}", buying_power, config.defaults.min_order_amount);
+                            let max_affordable = buying_power * Decimal::new(99, 2); // 1% buffer for fees
+                            if max_affordable < min_order_amount {
+                                error!("[EXECUTION] Insufficient funds. Available: ${:.2}, Min Required: ${:.2}", buying_power, min_order_amount);
                                 return;
                             }
 
@@ -343,6 +573,7 @@ impl ExecutionEngine {
                             estimated_value = max_affordable;
                             order.qty = estimated_value / estimated_price;
                         }
+                        account_snapshot = Some(account);
                     }
                     Err(e) => {
                         error!("[EXECUTION] Failed to fetch account balance: {}", e);
@@ -351,6 +582,61 @@ impl ExecutionEngine {
                 }
             }
 
+            // Simulate the entry against the current book; may shrink or veto it
+            if order.action == "buy" {
+                let proposed = ProposedOrder {
+                    symbol: req.symbol.clone(),
+                    qty: float(order.qty),
+                    price: float(estimated_price),
+                };
+                match pre_trade_check(
+                    &bus,
+                    &config,
+                    &tracker,
+                    &store,
+                    account_snapshot.as_ref(),
+                    &proposed,
+                ) {
+                    Some(qty) if qty < proposed.qty => {
+                        order.qty = dec(qty);
+                        estimated_value = order.qty * estimated_price;
+                    }
+                    Some(_) => {}
+                    None => return,
+                }
+                if let Some(policy) = &policy {
+                    let decision = policy.check_size(&req.symbol, float(estimated_value));
+                    if !decision.allowed {
+                        bus.publish(Event::OrderRejected(
+                            decision.rejection(req.correlation_id.clone()),
+                        ))
+                        .ok();
+                        return;
+                    }
+                }
+            }
+            // Hold the notional until the order is tracked (concurrent entries)
+            let _reservation = if order.action == "buy" {
+                match allocator.reserve(
+                    &config.capital_allocation,
+                    &bucket,
+                    estimated_value * fx,
+                    total_power,
+                    &bucket_usage(&config, &tracker, &store),
+                ) {
+                    Ok(reservation) => Some(reservation),
+                    Err(denied) => {
+                        warn!(
+                            "💼 [CAPITAL] Skip {} ({} bucket): {}",
+                            req.symbol, bucket, denied
+                        );
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
             // Force Limit Order for Buy
             let mut order_type_enum = if order.order_type.to_lowercase() == "limit" {
                 ExOrderType::Limit
@@ -403,6 +689,38 @@ impl ExecutionEngine {
                 notional,
                 time_in_force,
                 limit_price,
+                bracket: None,
+                stop_price: None,
+                reduce_only: false,
+                metadata: req.metadata.clone(),
+            };
+            let Some(api_req) =
+                normalize_order(&symbol_meta, exchange.as_ref(), api_req, estimated_price).await
+            else {
+                return;
+            };
+            if let Some(qty) = api_req.qty {
+                order.qty = qty;
+            }
+            let limit_price = api_req.limit_price;
+            let estimated_price = limit_price.unwrap_or(estimated_price);
+
+            // IMPORTANT: Always calculate TP/SL from actual entry price
+            // Don't use req.stop_loss/take_profit as those may be stale
+            let exits = if order.action == "buy" {
+                let (tp_pct, sl_pct) = config.get_symbol_params(&req.symbol);
+                let stop_loss =
+                    estimated_price * (Decimal::ONE - dec(sl_pct) / Decimal::ONE_HUNDRED);
+                let take_profit =
+                    estimated_price * (Decimal::ONE + dec(tp_pct) / Decimal::ONE_HUNDRED);
+
+                info!(
+                    "[EXECUTION] TP/SL from entry ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
+                    estimated_price, take_profit, tp_pct, stop_loss, sl_pct
+                );
+                Some((take_profit, stop_loss))
+            } else {
+                None
             };
 
             info!(
@@ -411,23 +729,21 @@ impl ExecutionEngine {
                 req.symbol
             );
 
-            match exchange.submit_order(api_req).await {
-                Ok(res) => {
+            let result = match exits {
+                Some((take_profit, stop_loss)) => {
+                    submit_entry(exchange.as_ref(), api_req, take_profit, stop_loss).await
+                }
+                None => exchange.submit_order(api_req).await.map(|ack| (ack, false)),
+            };
+
+            match result {
+                Ok((res, bracket)) => {
                     info!(
-                        "[SUCCESS] Order Placed: id={} status={}",
-                        res.id, res.status
+                        "[SUCCESS] Order Placed: id={} status={} bracket={}",
+                        res.id, res.status, bracket
                     );
 
-                    if order.action == "buy" {
-                        // IMPORTANT: Always calculate TP/SL from actual entry price
-                        // Don't use req.stop_loss/take_profit as those may be stale
-                        let (tp_pct, sl_pct) = config.get_symbol_params(&req.symbol);
-                        let stop_loss = estimated_price * (1.0 - sl_pct / 100.0);
-                        let take_profit = estimated_price * (1.0 + tp_pct / 100.0);
-
-                        info!("[EXECUTION] TP/SL from entry ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
-                                      estimated_price, take_profit, tp_pct, stop_loss, sl_pct);
-
+                    if let Some((take_profit, stop_loss)) = exits {
                         if matches!(order_type_enum, ExOrderType::Limit) {
                             let pending = crate::services::position_monitor::PendingOrder {
                                 order_id: res.id.clone(),
@@ -435,10 +751,14 @@ impl ExecutionEngine {
                                 side: "buy".to_string(),
                                 limit_price: estimated_price,
                                 qty: order.qty,
+                                filled_qty: Decimal::ZERO,
+                                remaining_qty: order.qty,
                                 created_at: chrono::Utc::now().to_rfc3339(),
                                 stop_loss: Some(stop_loss),
                                 take_profit: Some(take_profit),
                                 last_check_time: None,
+                                bracket,
+                                correlation_id: req.correlation_id.clone(),
                             };
                             tracker.add_pending_order(pending);
                         } else {
@@ -446,6 +766,8 @@ impl ExecutionEngine {
                                 symbol: req.symbol.clone(),
                                 entry_price: estimated_price,
                                 qty: order.qty,
+                                filled_qty: order.qty,
+                                remaining_qty: Decimal::ZERO,
                                 stop_loss,
                                 take_profit,
                                 entry_time: chrono::Utc::now().to_rfc3339(),
@@ -457,6 +779,9 @@ impl ExecutionEngine {
                                 highest_price: estimated_price,
                                 trailing_stop_active: false,
                                 trailing_stop_price: stop_loss,
+                                bracket_managed: bracket,
+                                stop_order_id: None,
+                                correlation_id: req.correlation_id.clone(),
                             };
                             tracker.add_position(position_info);
                         }
@@ -469,11 +794,21 @@ impl ExecutionEngine {
                         side: order.action.clone(),
                         price: Some(estimated_price),
                         qty: Some(order.qty),
+                        signal_price: latest
+                            .as_ref()
+                            .map(|q| dec((q.bid_price + q.ask_price) / 2.0)),
+                        limit_price,
+                        strategy_id: req.strategy_id.clone(),
+                        correlation_id: req.correlation_id.clone(),
+                        metadata: req.metadata.clone(),
                     };
 
                     bus.publish(Event::Execution(report)).ok();
                 }
-                Err(e) => error!("[FAILED] Order Submission: {}", e),
+                Err(e) => {
+                    error!("[FAILED] Order Submission: {}", e);
+                    publish_rejection(&bus, &req.symbol, &order.action, estimated_price, order.qty);
+                }
             }
         } else {
             info!("[EXECUTION] Invalid action '{}'", order.action);
@@ -1,9 +1,11 @@
 use crate::agents::{execution::ExecutionAgent, Agent};
 use crate::bus::EventBus;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ChatterModule};
+use crate::data::indicators::IndicatorKind;
 use crate::data::store::MarketStore;
-use crate::events::{Event, ExecutionReport, OrderRequest};
+use crate::events::{Event, ExecutionReport, OrderRequest, SystemEvent, TradeOrigin};
 use crate::exchange::{
+    symbol_meta::SymbolMetaRegistry,
     traits::TradingApi,
     types::{
         OrderType as ExOrderType, PlaceOrderRequest as ExPlaceOrderRequest, Side as ExSide,
@@ -11,12 +13,28 @@ use crate::exchange::{
     },
 };
 use crate::llm::LLMQueue;
+use crate::logging::trade_span;
+use crate::money::{dec, float};
+use crate::services::capital::{bucket_usage, CapitalAllocator};
 use crate::services::execution_utils::{
-    aggressive_limit_price, compute_order_sizing, AccountCache, RateLimiter,
+    aggressive_limit_price, await_passive_fill, compute_order_sizing, depth_cap,
+    margin_buying_power, normalize_order, passive_limit_price, publish_rejection, stop_limit_exit,
+    submit_entry, AccountCache, PassiveOutcome, RateLimiter, SizingMethod, SizingStrategy,
+    Throttle,
 };
+use crate::services::health::scale_entry_sizing;
+use crate::services::policy::PolicyEngine;
 use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+use crate::services::pretrade::{pre_trade_check, ProposedOrder};
+use crate::services::reporting::{TradeReporter, WinLoss};
+use rust_decimal::Decimal;
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn, Instrument};
+
+/// How often a resting post-only entry is polled for fills
+const PASSIVE_POLL: Duration = Duration::from_millis(200);
 
 /// High-performance execution engine optimized for frequent small trades.
 pub struct ExecutionEngine {
@@ -28,6 +46,13 @@ pub struct ExecutionEngine {
     tracker: PositionTracker,
     account_cache: AccountCache,
     rate_limiter: RateLimiter,
+    symbol_meta: SymbolMetaRegistry,
+    /// Closed-trade record for Kelly sizing
+    reporter: Option<TradeReporter>,
+    /// Per-strategy capital buckets (`capital_allocation`)
+    allocator: CapitalAllocator,
+    /// Size limits of the compliance policy, checked on the sized entry
+    policy: Option<PolicyEngine>,
 }
 
 #[derive(serde::Deserialize)]
@@ -49,29 +74,63 @@ impl ExecutionEngine {
         tracker: PositionTracker,
     ) -> Self {
         let micro_config = &config.micro_trade;
+        let account_cache = AccountCache::new(exchange.clone(), micro_config.account_cache_secs)
+            .with_event_bus(event_bus.clone());
 
         Self {
             event_bus,
-            exchange: exchange.clone(),
+            exchange,
             market_store,
             llm,
             config: config.clone(),
             tracker,
-            account_cache: AccountCache::new(exchange, micro_config.account_cache_secs),
-            rate_limiter: RateLimiter::new(micro_config.min_order_interval_ms),
+            account_cache,
+            rate_limiter: RateLimiter::new(micro_config.min_order_interval_ms).with_order_caps(
+                micro_config.max_orders_per_minute_per_symbol,
+                micro_config.max_orders_per_hour,
+            ),
+            symbol_meta: SymbolMetaRegistry::new(),
+            reporter: None,
+            allocator: CapitalAllocator::new(),
+            policy: None,
         }
     }
 
+    /// Read the session's win/loss record for `sizing.method: kelly` and its
+    /// drawdown size factor (`sizing.drawdown`).
+    pub fn with_reporter(mut self, reporter: TradeReporter) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Share the startup-loaded symbol rules used to round orders.
+    pub fn with_symbol_meta(mut self, symbol_meta: SymbolMetaRegistry) -> Self {
+        self.symbol_meta = symbol_meta;
+        self
+    }
+
+    /// Check sized entries against `policy.max_trade_notional`,
+    /// `max_volume_pct` and `max_daily_turnover` (the risk engine's policy).
+    pub fn with_policy(mut self, policy: PolicyEngine) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     pub async fn start(&self) {
-        let mut rx = self.event_bus.subscribe();
+        let mut rx = self.event_bus.subscribe("execution");
         let exchange = self.exchange.clone();
         let store = self.market_store.clone();
         let llm = self.llm.clone();
         let bus = self.event_bus.clone();
-        let config = self.config.clone();
+        let mut config = self.config.clone();
         let tracker = self.tracker.clone();
         let account_cache = self.account_cache.clone();
         let rate_limiter = self.rate_limiter.clone();
+        let symbol_meta = self.symbol_meta.clone();
+        let reporter = self.reporter.clone();
+        let allocator = self.allocator.clone();
+        let policy = self.policy.clone();
+        let shutdown = self.event_bus.shutdown().clone();
 
         tokio::spawn(async move {
             info!("⚡ Execution Engine Started (High-Performance Mode)");
@@ -82,11 +141,58 @@ impl ExecutionEngine {
                 config.defaults.min_order_amount,
                 config.defaults.max_order_amount
             );
+            if SizingMethod::parse(&config.sizing.method).is_none() {
+                warn!(
+                    "⚠️ [EXECUTION] Unknown sizing.method '{}', using balance_pct",
+                    config.sizing.method
+                );
+            }
+
+            // Entry size multiplier from the degradation ladder (Event::Health)
+            let mut health_size_factor = 1.0;
+            // Set by the circuit breaker (Event::System); cleared by /resume
+            let mut halted = false;
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => {
+                        info!("[EXECUTION] Draining - no new orders accepted");
+                        break;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                // Keep sizing in sync with balance changes pushed by other components
+                if let Event::Account(account_event) = &event {
+                    account_cache.apply_event(account_event).await;
+                    continue;
+                }
+                if let Event::Health(health) = &event {
+                    health_size_factor = health.size_factor;
+                    continue;
+                }
+                // Hot-reloaded tunables apply to orders received from now on
+                if let Event::Config(update) = &event {
+                    config = (*update.config).clone();
+                    continue;
+                }
+                if let Event::System(system) = &event {
+                    halted = matches!(system, SystemEvent::Halt { .. });
+                    continue;
+                }
 
-            while let Ok(event) = rx.recv().await {
                 if let Event::Order(req) = event {
+                    if halted && req.action == "buy" {
+                        warn!(
+                            "🛑 [EXECUTION] Circuit breaker tripped - rejecting BUY {}",
+                            req.symbol
+                        );
+                        continue;
+                    }
                     // Skip verbose logging for performance
-                    if config.chatter_level != "low" {
+                    if !config.chatter_for(ChatterModule::Execution).is_low() {
                         info!(
                             "[EXECUTION] Received: {} {} {}",
                             req.action, req.symbol, req.order_type
@@ -98,32 +204,60 @@ impl ExecutionEngine {
                     let store = store.clone();
                     let llm = llm.clone();
                     let bus = bus.clone();
-                    let config = config.clone();
+                    let mut config = config.clone();
+                    if req.action == "buy" {
+                        let drawdown_factor =
+                            reporter.as_ref().map_or(1.0, |r| r.drawdown_size_factor());
+                        if drawdown_factor <= 0.0 {
+                            warn!(
+                                "📉 [EXECUTION] Drawdown sizing paused entries - rejecting BUY {}",
+                                req.symbol
+                            );
+                            continue;
+                        }
+                        scale_entry_sizing(&mut config, health_size_factor * drawdown_factor);
+                    }
+                    bus.diagnostics().apply(&req.symbol, &mut config);
                     let tracker = tracker.clone();
                     let account_cache = account_cache.clone();
                     let rate_limiter = rate_limiter.clone();
+                    let symbol_meta = symbol_meta.clone();
+                    let record = reporter.as_ref().map(|r| r.win_loss()).unwrap_or_default();
+                    let allocator = allocator.clone();
+                    let policy = policy.clone();
+                    let in_flight = shutdown.track_order();
 
                     // Spawn non-blocking execution
-                    tokio::spawn(async move {
-                        Self::execute_fast(
-                            req,
-                            exchange,
-                            store,
-                            llm,
-                            bus,
-                            config,
-                            tracker,
-                            account_cache,
-                            rate_limiter,
-                        )
-                        .await;
-                    });
+                    let span = trade_span(&req.symbol, req.correlation_id.as_deref());
+                    tokio::spawn(
+                        async move {
+                            let _in_flight = in_flight;
+                            Self::execute_fast(
+                                req,
+                                exchange,
+                                store,
+                                llm,
+                                bus,
+                                config,
+                                tracker,
+                                account_cache,
+                                rate_limiter,
+                                symbol_meta,
+                                record,
+                                allocator,
+                                policy,
+                            )
+                            .await;
+                        }
+                        .instrument(span),
+                    );
                 }
             }
         });
     }
 
     /// Fast execution path optimized for HFT and micro-trades.
+    #[allow(clippy::too_many_arguments)]
     async fn execute_fast(
         req: OrderRequest,
         exchange: Arc<dyn TradingApi>,
@@ -134,27 +268,72 @@ impl ExecutionEngine {
         tracker: PositionTracker,
         account_cache: AccountCache,
         rate_limiter: RateLimiter,
+        symbol_meta: SymbolMetaRegistry,
+        record: WinLoss,
+        allocator: CapitalAllocator,
+        policy: Option<PolicyEngine>,
     ) {
         let is_crypto = config.trading_mode.to_lowercase() == "crypto";
         let micro_config = &config.micro_trade;
 
         // ========== SELL PATH (Fast) ==========
         if req.action == "sell" {
-            Self::execute_sell(&req, &exchange, &store, &tracker, &bus, is_crypto).await;
+            Self::execute_sell(
+                &req,
+                &exchange,
+                &store,
+                &tracker,
+                &bus,
+                &symbol_meta,
+                &config,
+            )
+            .await;
             return;
         }
 
         // ========== BUY PATH (Optimized) ==========
 
+        // The signal went stale before it could be placed
+        if req.expired(chrono::Utc::now()) {
+            warn!(
+                "⌛ [EXECUTION] Skip {}: entry expired (valid until {})",
+                req.symbol,
+                req.valid_until.as_deref().unwrap_or_default()
+            );
+            return;
+        }
+
+        // Too many entries already working (a runaway signal loop piles them up)
+        if let Some(max) = micro_config.max_pending_orders {
+            let pending = tracker.get_all_pending_orders().len();
+            if pending >= max {
+                warn!(
+                    "🚦 [EXECUTION] Skip {}: {} pending orders (max_pending_orders {})",
+                    req.symbol, pending, max
+                );
+                return;
+            }
+        }
+
         // Rate limit check per symbol (don't spam orders for the same symbol)
-        if !rate_limiter.try_acquire(&req.symbol).await {
-            if config.chatter_level != "low" {
-                info!(
-                    "[EXECUTION] Rate limited for {} (cooldown: {}ms)",
-                    req.symbol, config.micro_trade.min_order_interval_ms
+        match rate_limiter.try_acquire(&req.symbol).await {
+            Ok(()) => {}
+            Err(Throttle::Cooldown) => {
+                if !config.chatter_for(ChatterModule::Execution).is_low() {
+                    info!(
+                        "[EXECUTION] Rate limited for {} (cooldown: {}ms)",
+                        req.symbol, config.micro_trade.min_order_interval_ms
+                    );
+                }
+                return;
+            }
+            Err(throttle) => {
+                warn!(
+                    "🚦 [EXECUTION] Throttled {}: {} reached",
+                    req.symbol, throttle
                 );
+                return;
             }
-            return;
         }
 
         // Check if we already have a position
@@ -174,7 +353,7 @@ impl ExecutionEngine {
                 };
 
                 if position_valid {
-                    if config.chatter_level != "low" {
+                    if !config.chatter_for(ChatterModule::Execution).is_low() {
                         info!(
                             "[EXECUTION] Skip {}: already have position (stacking disabled)",
                             req.symbol
@@ -192,7 +371,7 @@ impl ExecutionEngine {
                 }
             } else {
                 // Multiple positions allowed - just log and continue
-                if config.chatter_level != "low" {
+                if !config.chatter_for(ChatterModule::Execution).is_low() {
                     info!("[EXECUTION] Position exists for {} but multiple positions allowed - proceeding", req.symbol);
                 }
             }
@@ -201,7 +380,7 @@ impl ExecutionEngine {
         // Check for pending orders on this symbol
         let pending = tracker.get_all_pending_orders();
         if pending.iter().any(|p| p.symbol == req.symbol) {
-            if config.chatter_level != "low" {
+            if !config.chatter_for(ChatterModule::Execution).is_low() {
                 info!("[EXECUTION] Skip {}: pending order exists", req.symbol);
             }
             return;
@@ -216,44 +395,196 @@ impl ExecutionEngine {
             }
         };
 
-        // Calculate aggressive limit price for faster fills
-        let limit_price = aggressive_limit_price(
-            quote.bid_price,
-            quote.ask_price,
-            "buy",
-            micro_config.aggression_bps,
-        );
+        // The strategy's entry limit, else an aggressive price for faster fills
+        let limit_price = req.limit_price.unwrap_or_else(|| {
+            dec(aggressive_limit_price(
+                quote.bid_price,
+                quote.ask_price,
+                "buy",
+                micro_config.aggression_bps,
+            ))
+        });
 
         // Get cached buying power (reduces API calls from every order to every 30s)
         let buying_power = account_cache.buying_power().await;
-        if buying_power <= 0.0 {
+        if buying_power <= Decimal::ZERO {
             error!("[EXECUTION] No buying power available");
             return;
         }
 
+        // Balance and order limits are in the reporting currency; size in the quote currency
+        let Some(fx) = store.quote_fx_rate(&config, &req.symbol).map(dec) else {
+            error!(
+                "[EXECUTION] No {} rate for {} - cannot size order",
+                config.quote_currency_for(&req.symbol),
+                req.symbol
+            );
+            return;
+        };
+        // Size from what the symbol's strategy bucket has left
+        let total_power = margin_buying_power(&config, &req.symbol, buying_power);
+        let bucket = config.strategy_mode_for(&req.symbol);
+        let usage = bucket_usage(&config, &tracker, &store);
+        let buying_power =
+            match allocator.available(&config.capital_allocation, &bucket, total_power, &usage) {
+                Ok(available) => available / fx,
+                Err(denied) => {
+                    warn!(
+                        "💼 [CAPITAL] Skip {} ({} bucket): {}",
+                        req.symbol, bucket, denied
+                    );
+                    return;
+                }
+            };
+        let min_order = dec(config.defaults.min_order_amount) / fx;
+        let max_order = dec(config.defaults.max_order_amount) / fx;
+
+        // Conditional orders carry their own amount and skip the agent
+        let conditional = req.qty > Decimal::ZERO
+            && req
+                .origin
+                .as_ref()
+                .is_some_and(|o| o.mode == TradeOrigin::CONDITIONAL);
+
+        // Scale down while warming up on priors
+        let size_factor = if conditional {
+            1.0
+        } else {
+            config.warmup_size_factor(&req.symbol, store.quote_count(&req.symbol))
+        };
+        if size_factor < 1.0 && !config.chatter_for(ChatterModule::Execution).is_low() {
+            info!(
+                "[EXECUTION] Warmup sizing for {}: {:.0}% of target",
+                req.symbol,
+                size_factor * 100.0
+            );
+        }
+
         // Compute optimal order size
-        let sizing = match compute_order_sizing(
+        let atr = store
+            .get_indicator(&req.symbol, IndicatorKind::Atr, config.sizing.atr_period)
+            .map(|v| v.value());
+        let strategy = if conditional {
+            // The rule's quantity, still within the order limits
+            SizingStrategy::FixedNotional(req.qty * limit_price)
+        } else {
+            SizingStrategy::from_config(&config, fx, atr, &record).scaled(dec(size_factor))
+        };
+        let max_qty = depth_cap(&config, quote.bid_price, quote.ask_price, quote.ask_size);
+        let mut sizing = match compute_order_sizing(
             limit_price,
             buying_power,
-            config.defaults.min_order_amount,
-            config.defaults.max_order_amount,
-            micro_config.target_balance_pct,
+            min_order,
+            max_order,
+            &strategy,
+            max_qty,
         ) {
             Some(s) => s,
             None => {
                 error!(
-                    "[EXECUTION] Cannot size order for {} (balance=${:.2})",
-                    req.symbol, buying_power
+                    "[EXECUTION] Cannot size order for {} (balance=${:.2}, sizing={:?}, depth cap={:?})",
+                    req.symbol, buying_power, strategy, max_qty
+                );
+                return;
+            }
+        };
+        if max_qty.is_some_and(|cap| sizing.notional == cap * limit_price)
+            && !config.chatter_for(ChatterModule::Execution).is_low()
+        {
+            info!(
+                "[EXECUTION] {} entry capped at {:.0}% of visible ask size {:.8}",
+                req.symbol,
+                config.sizing.depth_cap_fraction * 100.0,
+                quote.ask_size
+            );
+        }
+
+        if bus.diagnostics().trace(&req.symbol) {
+            info!(
+                "🔬 [DIAG] Sizing {}: bid={:.8} ask={:.8} aggression={}bps -> limit={:.8} | buying_power=${:.2} sizing={:?} x{:.2} min=${:.2} max=${:.2} -> qty={:.8} notional=${:.2}",
+                req.symbol,
+                quote.bid_price,
+                quote.ask_price,
+                micro_config.aggression_bps,
+                limit_price,
+                buying_power,
+                strategy,
+                size_factor,
+                min_order,
+                max_order,
+                sizing.qty,
+                sizing.notional
+            );
+        }
+
+        // Simulate the entry against the current book; may shrink or veto it
+        let account = account_cache.snapshot().await;
+        let proposed = ProposedOrder {
+            symbol: req.symbol.clone(),
+            qty: float(sizing.qty),
+            price: float(limit_price),
+        };
+        match pre_trade_check(&bus, &config, &tracker, &store, account.as_ref(), &proposed) {
+            Some(qty) if qty < proposed.qty => {
+                sizing.qty = dec(qty);
+                sizing.notional = sizing.qty * limit_price;
+            }
+            Some(_) => {}
+            None => return,
+        }
+        if let Some(policy) = &policy {
+            let decision = policy.check_size(&req.symbol, float(sizing.notional));
+            if !decision.allowed {
+                bus.publish(Event::OrderRejected(
+                    decision.rejection(req.correlation_id.clone()),
+                ))
+                .ok();
+                return;
+            }
+        }
+
+        // Hold the notional until the order is tracked (concurrent entries)
+        let _reservation = match allocator.reserve(
+            &config.capital_allocation,
+            &bucket,
+            sizing.notional * fx,
+            total_power,
+            &bucket_usage(&config, &tracker, &store),
+        ) {
+            Ok(reservation) => reservation,
+            Err(denied) => {
+                warn!(
+                    "💼 [CAPITAL] Skip {} ({} bucket): {}",
+                    req.symbol, bucket, denied
                 );
                 return;
             }
         };
 
         // Determine if HFT fast path or LLM path
-        let is_hft = req.order_type == "hft_buy" || config.strategy_mode.to_lowercase() == "hft";
+        let is_hft = req.order_type == "hft_buy" || config.strategy_mode_for(&req.symbol) == "hft";
         let use_llm_filter = config.micro_trade.use_llm_filter;
 
-        let (action, order_type) = if is_hft && !use_llm_filter {
+        let (action, order_type) = if conditional {
+            let order_type = if req.order_type == "market" {
+                ExOrderType::Market
+            } else {
+                ExOrderType::Limit
+            };
+            ("buy".to_string(), order_type)
+        } else if req
+            .origin
+            .as_ref()
+            .is_some_and(|o| o.mode == TradeOrigin::FALLBACK)
+        {
+            // Rule-based entry while the LLM is down (`llm_fallback`)
+            let order_type = if is_hft {
+                ExOrderType::Limit
+            } else {
+                ExOrderType::Market
+            };
+            ("buy".to_string(), order_type)
+        } else if is_hft && !use_llm_filter {
             // Pure HFT: Skip LLM entirely, use limit order
             ("buy".to_string(), ExOrderType::Limit)
         } else if is_hft && use_llm_filter {
@@ -261,7 +592,7 @@ impl ExecutionEngine {
             match Self::get_llm_validation(&req.symbol, &llm, &config).await {
                 Some(approved) if approved => ("buy".to_string(), ExOrderType::Limit),
                 _ => {
-                    if config.chatter_level != "low" {
+                    if !config.chatter_for(ChatterModule::Execution).is_low() {
                         info!("[EXECUTION] LLM filter rejected trade for {}", req.symbol);
                     }
                     return;
@@ -269,14 +600,14 @@ impl ExecutionEngine {
             }
         } else {
             // Full LLM path: Call agent for complete decision
-            match Self::get_llm_decision(&req.symbol, &llm).await {
+            match Self::get_llm_decision(&req.symbol, &llm, &config).await {
                 Some((a, ot)) => (a, ot),
                 None => return,
             }
         };
 
         if action != "buy" {
-            if config.chatter_level != "low" {
+            if !config.chatter_for(ChatterModule::Execution).is_low() {
                 info!(
                     "[EXECUTION] Agent decided '{}' for {}, skipping",
                     action, req.symbol
@@ -285,6 +616,41 @@ impl ExecutionEngine {
             return;
         }
 
+        // Maker first: rest a post-only bid, escalate if it has not filled in time
+        // (a strategy-priced entry rests at its own limit instead)
+        let mut order_type = order_type;
+        if micro_config.post_only
+            && req.limit_price.is_none()
+            && matches!(order_type, ExOrderType::Limit)
+        {
+            let Some(escalate_qty) = Self::enter_passive(
+                &req,
+                exchange.as_ref(),
+                &symbol_meta,
+                &config,
+                &tracker,
+                &bus,
+                (quote.bid_price, quote.ask_price),
+                sizing.qty,
+            )
+            .await
+            else {
+                account_cache.invalidate().await;
+                return;
+            };
+            sizing.qty = escalate_qty;
+            sizing.notional = escalate_qty * limit_price;
+            if micro_config.escalate_to.eq_ignore_ascii_case("market") {
+                order_type = ExOrderType::Market;
+            }
+            if !config.chatter_for(ChatterModule::Execution).is_low() {
+                info!(
+                    "[EXECUTION] Escalating {} qty={:.8} as {:?}",
+                    req.symbol, escalate_qty, order_type
+                );
+            }
+        }
+
         // Build order request
         // For crypto: Use configured time-in-force (gtc or ioc)
         // For stocks: Use Day
@@ -314,9 +680,23 @@ impl ExecutionEngine {
             } else {
                 None
             },
+            bracket: None,
+            stop_price: None,
+            reduce_only: false,
+            metadata: req.metadata.clone(),
+        };
+        let Some(api_req) =
+            normalize_order(&symbol_meta, exchange.as_ref(), api_req, limit_price).await
+        else {
+            return;
         };
+        let limit_price = api_req.limit_price.unwrap_or(limit_price);
+        if let Some(qty) = api_req.qty {
+            sizing.qty = qty;
+            sizing.notional = qty * limit_price;
+        }
 
-        if config.chatter_level != "low" {
+        if !config.chatter_for(ChatterModule::Execution).is_low() {
             info!(
                 "[ORDER] {} {} qty={:.6} @ ${:.4} (${:.2})",
                 if matches!(order_type, ExOrderType::Limit) {
@@ -331,27 +711,36 @@ impl ExecutionEngine {
             );
         }
 
-        // Submit order
-        match exchange.submit_order(api_req).await {
-            Ok(res) => {
-                if config.chatter_level != "low" {
-                    info!("[SUCCESS] Order {} status={}", res.id, res.status);
+        // IMPORTANT: Calculate TP/SL from the actual limit price we're buying at.
+        // The request's levels are only kept when the strategy priced the entry
+        // itself; otherwise they are from signal time (stale mid price)
+        let (tp_pct, sl_pct) = config.get_symbol_params(&req.symbol);
+        let (stop_loss, take_profit) = match (req.limit_price, req.stop_loss, req.take_profit) {
+            (Some(_), Some(sl), Some(tp)) => (sl, tp),
+            _ => (
+                limit_price * (Decimal::ONE - dec(sl_pct) / Decimal::ONE_HUNDRED),
+                limit_price * (Decimal::ONE + dec(tp_pct) / Decimal::ONE_HUNDRED),
+            ),
+        };
+
+        if !config.chatter_for(ChatterModule::Execution).is_low() {
+            info!("[EXECUTION] TP/SL calculated from limit_price ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
+                  limit_price, take_profit, tp_pct, stop_loss, sl_pct);
+        }
+
+        // Submit order (with native bracket legs where supported)
+        match submit_entry(exchange.as_ref(), api_req, take_profit, stop_loss).await {
+            Ok((res, bracket)) => {
+                if !config.chatter_for(ChatterModule::Execution).is_low() {
+                    info!(
+                        "[SUCCESS] Order {} status={} bracket={}",
+                        res.id, res.status, bracket
+                    );
                 }
 
                 // Invalidate account cache after successful order
                 account_cache.invalidate().await;
 
-                // IMPORTANT: Always calculate TP/SL from the actual limit price we're buying at
-                // Don't use req.stop_loss/take_profit as those are from signal time (stale mid price)
-                let (tp_pct, sl_pct) = config.get_symbol_params(&req.symbol);
-                let stop_loss = limit_price * (1.0 - sl_pct / 100.0);
-                let take_profit = limit_price * (1.0 + tp_pct / 100.0);
-
-                if config.chatter_level != "low" {
-                    info!("[EXECUTION] TP/SL calculated from limit_price ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
-                          limit_price, take_profit, tp_pct, stop_loss, sl_pct);
-                }
-
                 // Track as pending order (limit) or position (market)
                 if matches!(order_type, ExOrderType::Limit) {
                     let pending = PendingOrder {
@@ -360,30 +749,26 @@ impl ExecutionEngine {
                         side: "buy".to_string(),
                         limit_price,
                         qty: sizing.qty,
+                        filled_qty: Decimal::ZERO,
+                        remaining_qty: sizing.qty,
                         created_at: chrono::Utc::now().to_rfc3339(),
                         stop_loss: Some(stop_loss),
                         take_profit: Some(take_profit),
                         last_check_time: None,
+                        bracket,
+                        correlation_id: req.correlation_id.clone(),
                     };
                     tracker.add_pending_order(pending);
                 } else {
-                    let position = PositionInfo {
-                        symbol: req.symbol.clone(),
-                        entry_price: limit_price,
-                        qty: sizing.qty,
+                    tracker.add_position(filled_entry(
+                        &req.symbol,
+                        limit_price,
+                        sizing.qty,
                         stop_loss,
                         take_profit,
-                        entry_time: chrono::Utc::now().to_rfc3339(),
-                        side: "buy".to_string(),
-                        is_closing: false,
-                        open_order_id: None,
-                        last_recreate_attempt: None,
-                        recreate_attempts: 0,
-                        highest_price: limit_price,
-                        trailing_stop_active: false,
-                        trailing_stop_price: stop_loss,
-                    };
-                    tracker.add_position(position);
+                        bracket,
+                        req.correlation_id.clone(),
+                    ));
                 }
 
                 // Publish execution report
@@ -394,13 +779,149 @@ impl ExecutionEngine {
                     side: "buy".to_string(),
                     price: Some(limit_price),
                     qty: Some(sizing.qty),
+                    signal_price: Some(dec((quote.bid_price + quote.ask_price) / 2.0)),
+                    limit_price: matches!(order_type, ExOrderType::Limit).then_some(limit_price),
+                    strategy_id: req.strategy_id.clone(),
+                    correlation_id: req.correlation_id.clone(),
+                    metadata: req.metadata.clone(),
                 };
                 bus.publish(Event::Execution(report)).ok();
             }
             Err(e) => {
                 error!("[FAILED] Order for {}: {}", req.symbol, e);
+                publish_rejection(&bus, &req.symbol, "buy", limit_price, sizing.qty);
+            }
+        }
+    }
+
+    /// Rest a post-only buy at/inside the spread for `escalation_timeout_ms`.
+    /// Returns the quantity to escalate when nothing filled. A (partial) fill
+    /// becomes the position as is (exits managed locally): the tracker holds
+    /// one entry order per position, so the rest is not chased.
+    #[allow(clippy::too_many_arguments)]
+    async fn enter_passive(
+        req: &OrderRequest,
+        exchange: &dyn TradingApi,
+        symbol_meta: &SymbolMetaRegistry,
+        config: &AppConfig,
+        tracker: &PositionTracker,
+        bus: &EventBus,
+        (bid, ask): (f64, f64),
+        qty: Decimal,
+    ) -> Option<Decimal> {
+        let micro_config = &config.micro_trade;
+        let price = dec(passive_limit_price(
+            bid,
+            ask,
+            micro_config.passive_offset_bps,
+        ));
+        let api_req = ExPlaceOrderRequest {
+            symbol: req.symbol.clone(),
+            side: ExSide::Buy,
+            order_type: ExOrderType::Limit,
+            qty: Some(qty),
+            notional: None,
+            time_in_force: ExTimeInForce::PostOnly,
+            limit_price: Some(price),
+            bracket: None,
+            stop_price: None,
+            reduce_only: false,
+            metadata: req.metadata.clone(),
+        };
+        let api_req = normalize_order(symbol_meta, exchange, api_req, price).await?;
+        let price = api_req.limit_price.unwrap_or(price);
+        let qty = api_req.qty.unwrap_or(qty);
+
+        let (tp_pct, sl_pct) = config.get_symbol_params(&req.symbol);
+        let stop_loss = price * (Decimal::ONE - dec(sl_pct) / Decimal::ONE_HUNDRED);
+        let take_profit = price * (Decimal::ONE + dec(tp_pct) / Decimal::ONE_HUNDRED);
+
+        let ack = match exchange.submit_order(api_req).await {
+            Ok(ack) => ack,
+            Err(e) => {
+                // Usually "would take liquidity": the book moved through our price
+                info!(
+                    "[EXECUTION] Post-only {} @ ${:.8} refused ({}), escalating",
+                    req.symbol, price, e
+                );
+                return Some(qty);
             }
+        };
+        if !config.chatter_for(ChatterModule::Execution).is_low() {
+            info!(
+                "[ORDER] POST-ONLY {} qty={:.8} @ ${:.8} (id={}, escalate after {}ms)",
+                req.symbol, qty, price, ack.id, micro_config.escalation_timeout_ms
+            );
+        }
+
+        let timeout = Duration::from_millis(micro_config.escalation_timeout_ms);
+        let (filled, order_id) =
+            match await_passive_fill(exchange, &ack.id, timeout, PASSIVE_POLL).await {
+                PassiveOutcome::Filled(done) => (
+                    done.raw
+                        .get("filled_qty")
+                        .and_then(crate::money::from_json)
+                        .filter(|q| *q > Decimal::ZERO)
+                        .unwrap_or(qty),
+                    done.id,
+                ),
+                PassiveOutcome::Unfilled { filled, .. } => (filled, ack.id),
+                PassiveOutcome::Unknown => {
+                    // Leave it to the position monitor's pending-order checks
+                    tracker.add_pending_order(PendingOrder {
+                        order_id: ack.id,
+                        symbol: req.symbol.clone(),
+                        side: "buy".to_string(),
+                        limit_price: price,
+                        qty,
+                        filled_qty: Decimal::ZERO,
+                        remaining_qty: qty,
+                        created_at: chrono::Utc::now().to_rfc3339(),
+                        stop_loss: Some(stop_loss),
+                        take_profit: Some(take_profit),
+                        last_check_time: None,
+                        bracket: false,
+                        correlation_id: req.correlation_id.clone(),
+                    });
+                    return None;
+                }
+            };
+
+        if filled > Decimal::ZERO {
+            tracker.add_position(filled_entry(
+                &req.symbol,
+                price,
+                filled,
+                stop_loss,
+                take_profit,
+                false,
+                req.correlation_id.clone(),
+            ));
+            let report = ExecutionReport {
+                symbol: req.symbol.clone(),
+                order_id,
+                status: if filled >= qty {
+                    "filled"
+                } else {
+                    "partially_filled"
+                }
+                .to_string(),
+                side: "buy".to_string(),
+                price: Some(price),
+                qty: Some(filled),
+                signal_price: Some(dec((bid + ask) / 2.0)),
+                limit_price: Some(price),
+                strategy_id: req.strategy_id.clone(),
+                correlation_id: req.correlation_id.clone(),
+                metadata: req.metadata.clone(),
+            };
+            bus.publish(Event::Execution(report)).ok();
+            info!(
+                "[SUCCESS] Passive fill {} qty={:.8} of {:.8} @ ${:.8}",
+                req.symbol, filled, qty, price
+            );
         }
+        (filled <= Decimal::ZERO).then_some(qty)
     }
 
     /// Fast sell execution
@@ -410,34 +931,39 @@ impl ExecutionEngine {
         store: &MarketStore,
         tracker: &PositionTracker,
         bus: &EventBus,
-        is_crypto: bool,
+        symbol_meta: &SymbolMetaRegistry,
+        config: &AppConfig,
     ) {
+        let is_crypto = config.trading_mode.to_lowercase() == "crypto";
         // Get sell price from latest quote
-        let price = store
-            .get_latest_quote(&req.symbol)
-            .map(|q| q.bid_price)
-            .unwrap_or(0.0);
+        let quote = store.get_latest_quote(&req.symbol);
+        let price = quote.as_ref().map(|q| dec(q.bid_price)).unwrap_or_default();
 
-        if price <= 0.0 {
+        if price <= Decimal::ZERO {
             error!("[EXECUTION] No price for SELL {}", req.symbol);
             return;
         }
 
-        // Get quantity from tracker or exchange
+        // Get quantity from tracker or exchange; only the filled part is owned
         let qty = if let Some(pos) = tracker.get_position(&req.symbol) {
-            pos.qty
+            if pos.remaining_qty > Decimal::ZERO {
+                tracker
+                    .cancel_working_entries(&req.symbol, exchange.as_ref())
+                    .await;
+            }
+            pos.filled_qty
         } else {
             match exchange.get_positions().await {
                 Ok(positions) => positions
                     .into_iter()
                     .find(|p| p.symbol == req.symbol)
                     .map(|p| p.qty)
-                    .unwrap_or(0.0),
-                Err(_) => 0.0,
+                    .unwrap_or_default(),
+                Err(_) => Decimal::ZERO,
             }
         };
 
-        if qty <= 0.0 {
+        if qty <= Decimal::ZERO {
             error!("[EXECUTION] No qty for SELL {}", req.symbol);
             return;
         }
@@ -448,6 +974,24 @@ impl ExecutionEngine {
             ExTimeInForce::Day
         };
 
+        // Stop-loss exits rest as a limit under the stop first (`stop_limit`)
+        if let Some(stop) = req.stop_loss.filter(|_| config.stop_limit.enabled) {
+            stop_limit_exit(
+                exchange.as_ref(),
+                symbol_meta,
+                tracker,
+                bus,
+                req,
+                qty,
+                stop,
+                (price, quote.map(|q| dec((q.bid_price + q.ask_price) / 2.0))),
+                time_in_force,
+                &config.stop_limit,
+            )
+            .await;
+            return;
+        }
+
         let api_req = ExPlaceOrderRequest {
             symbol: req.symbol.clone(),
             qty: Some(qty),
@@ -456,7 +1000,16 @@ impl ExecutionEngine {
             order_type: ExOrderType::Market, // Market sell for immediate exit
             time_in_force,
             limit_price: None,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: req.metadata.clone(),
+        };
+        let Some(api_req) = normalize_order(symbol_meta, exchange.as_ref(), api_req, price).await
+        else {
+            return;
         };
+        let qty = api_req.qty.unwrap_or(qty);
 
         info!("[ORDER] SELL {} qty={:.6} @ ${:.4}", req.symbol, qty, price);
 
@@ -472,16 +1025,28 @@ impl ExecutionEngine {
                     side: "sell".to_string(),
                     price: Some(price),
                     qty: Some(qty),
+                    signal_price: quote.map(|q| dec((q.bid_price + q.ask_price) / 2.0)),
+                    limit_price: None,
+                    strategy_id: req.strategy_id.clone(),
+                    correlation_id: req.correlation_id.clone(),
+                    metadata: req.metadata.clone(),
                 };
                 bus.publish(Event::Execution(report)).ok();
             }
-            Err(e) => error!("[FAILED] SELL {}: {}", req.symbol, e),
+            Err(e) => {
+                error!("[FAILED] SELL {}: {}", req.symbol, e);
+                publish_rejection(bus, &req.symbol, "sell", price, qty);
+            }
         }
     }
 
     /// Get decision from LLM (slower path)
-    async fn get_llm_decision(symbol: &str, llm: &LLMQueue) -> Option<(String, ExOrderType)> {
-        let agent = ExecutionAgent;
+    async fn get_llm_decision(
+        symbol: &str,
+        llm: &LLMQueue,
+        config: &AppConfig,
+    ) -> Option<(String, ExOrderType)> {
+        let agent = ExecutionAgent::new(config, symbol);
         let input = format!(
             "Symbol: {}\nRisk Analysis: Approved\nAction: Create Order JSON",
             symbol
@@ -511,7 +1076,7 @@ impl ExecutionEngine {
     /// Returns true if the trade should proceed, false to skip.
     /// This is faster than full LLM decision-making as it only asks yes/no.
     async fn get_llm_validation(symbol: &str, llm: &LLMQueue, config: &AppConfig) -> Option<bool> {
-        let agent = ExecutionAgent;
+        let agent = ExecutionAgent::new(config, symbol);
 
         // Create a concise prompt for quick validation
         let input = format!(
@@ -549,3 +1114,36 @@ impl ExecutionEngine {
         }
     }
 }
+
+/// Position for an entry that filled at `price` without a working order.
+fn filled_entry(
+    symbol: &str,
+    price: Decimal,
+    qty: Decimal,
+    stop_loss: Decimal,
+    take_profit: Decimal,
+    bracket: bool,
+    correlation_id: Option<String>,
+) -> PositionInfo {
+    PositionInfo {
+        symbol: symbol.to_string(),
+        entry_price: price,
+        qty,
+        filled_qty: qty,
+        remaining_qty: Decimal::ZERO,
+        stop_loss,
+        take_profit,
+        entry_time: chrono::Utc::now().to_rfc3339(),
+        side: "buy".to_string(),
+        is_closing: false,
+        open_order_id: None,
+        last_recreate_attempt: None,
+        recreate_attempts: 0,
+        highest_price: price,
+        trailing_stop_active: false,
+        trailing_stop_price: stop_loss,
+        bracket_managed: bracket,
+        stop_order_id: None,
+        correlation_id,
+    }
+}
@@ -1,11 +1,22 @@
 use dashmap::DashMap;
-use std::sync::Arc;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::warn;
+use tracing::{error, warn};
 
-use crate::exchange::traits::TradingApi;
-use crate::exchange::types::AccountSummary;
+use crate::bus::EventBus;
+use crate::config::{AppConfig, BinanceFuturesConfig, StopLimitConfig};
+use crate::events::{AccountEvent, Event, ExecutionReport, OrderRequest, SystemEvent};
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::traits::{ExchangeResult, TradingApi};
+use crate::exchange::types::{
+    AccountSummary, BracketLegs, OrderAck, OrderType, PlaceOrderRequest, Side, TimeInForce,
+};
+use crate::money::{self, dec, float};
+use crate::services::position_monitor::{PendingOrder, PositionTracker};
+use crate::services::reporting::WinLoss;
 
 /// Cached account balance to reduce API calls.
 /// Refreshes every `refresh_interval` or on explicit invalidation.
@@ -14,6 +25,7 @@ pub struct AccountCache {
     exchange: Arc<dyn TradingApi>,
     cache: Arc<RwLock<CachedAccount>>,
     refresh_interval: Duration,
+    event_bus: Option<EventBus>,
 }
 
 struct CachedAccount {
@@ -30,11 +42,23 @@ impl AccountCache {
                 last_fetch: None,
             })),
             refresh_interval: Duration::from_secs(refresh_interval_secs),
+            event_bus: None,
         }
     }
 
+    /// Publish `Event::Account` on the bus whenever a refresh observes a change.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Latest cached account snapshot (no refresh).
+    pub async fn snapshot(&self) -> Option<AccountSummary> {
+        self.cache.read().await.summary.clone()
+    }
+
     /// Get cached buying power. Refreshes if stale or missing.
-    pub async fn buying_power(&self) -> f64 {
+    pub async fn buying_power(&self) -> Decimal {
         let should_refresh = {
             let cache = self.cache.read().await;
             match cache.last_fetch {
@@ -52,7 +76,7 @@ impl AccountCache {
             .summary
             .as_ref()
             .and_then(|s| s.buying_power.or(s.cash))
-            .unwrap_or(0.0)
+            .unwrap_or_default()
     }
 
     /// Force refresh (call after successful order to update balance)
@@ -61,12 +85,40 @@ impl AccountCache {
         cache.last_fetch = None;
     }
 
+    /// Apply an account update pushed by a user-data stream.
+    pub async fn apply_event(&self, event: &AccountEvent) {
+        if let AccountEvent::BalanceUpdated {
+            cash,
+            buying_power,
+            portfolio_value,
+            ..
+        } = event
+        {
+            let mut cache = self.cache.write().await;
+            cache.summary = Some(AccountSummary {
+                buying_power: *buying_power,
+                cash: *cash,
+                portfolio_value: *portfolio_value,
+            });
+            cache.last_fetch = Some(Instant::now());
+        }
+    }
+
     async fn refresh(&self) {
         match self.exchange.get_account().await {
             Ok(summary) => {
-                let mut cache = self.cache.write().await;
-                cache.summary = Some(summary);
-                cache.last_fetch = Some(Instant::now());
+                let events = {
+                    let mut cache = self.cache.write().await;
+                    let events = diff_account(cache.summary.as_ref(), &summary);
+                    cache.summary = Some(summary);
+                    cache.last_fetch = Some(Instant::now());
+                    events
+                };
+                if let Some(bus) = &self.event_bus {
+                    for ev in events {
+                        bus.publish(Event::Account(ev)).ok();
+                    }
+                }
             }
             Err(e) => {
                 warn!("[CACHE] Failed to refresh account: {}", e);
@@ -75,29 +127,264 @@ impl AccountCache {
     }
 }
 
+/// Compare two account snapshots and describe what changed. Cash moves are
+/// not classified here: only the balance audit, which reconciles them with
+/// the fills booked in between, attributes transfers (`BalanceAudited`).
+pub fn diff_account(
+    previous: Option<&AccountSummary>,
+    current: &AccountSummary,
+) -> Vec<AccountEvent> {
+    let changed = |a: Option<Decimal>, b: Option<Decimal>| a != b;
+
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    let mut events = Vec::new();
+
+    let prev = match previous {
+        Some(p) => p,
+        None => {
+            events.push(AccountEvent::BalanceUpdated {
+                cash: current.cash,
+                buying_power: current.buying_power,
+                portfolio_value: current.portfolio_value,
+                timestamp,
+            });
+            return events;
+        }
+    };
+
+    let cash_changed = changed(prev.cash, current.cash);
+    let bp_changed = changed(prev.buying_power, current.buying_power);
+
+    if cash_changed || bp_changed || changed(prev.portfolio_value, current.portfolio_value) {
+        events.push(AccountEvent::BalanceUpdated {
+            cash: current.cash,
+            buying_power: current.buying_power,
+            portfolio_value: current.portfolio_value,
+            timestamp: timestamp.clone(),
+        });
+    }
+
+    // Buying power moved while cash did not: margin/collateral change.
+    if bp_changed && !cash_changed {
+        if let (Some(p), Some(c)) = (prev.buying_power, current.buying_power) {
+            events.push(AccountEvent::MarginChanged {
+                previous_buying_power: p,
+                buying_power: c,
+                timestamp,
+            });
+        }
+    }
+
+    events
+}
+
+/// Notional an account can open in `symbol` from `balance`: the balance itself
+/// on spot. On futures an entry ties up its initial margin (notional /
+/// leverage) and must keep its maintenance margin covered, so the balance
+/// backs notional up to balance / (1/leverage + maintenance rate).
+pub fn margin_buying_power(config: &AppConfig, symbol: &str, balance: Decimal) -> Decimal {
+    match config.futures() {
+        Some(futures) => {
+            let per_unit = initial_margin(Decimal::ONE, futures.leverage_for(symbol))
+                + maintenance_margin(Decimal::ONE, futures);
+            balance.checked_div(per_unit).unwrap_or(balance)
+        }
+        None => balance,
+    }
+}
+
+/// Margin posted to open `notional` at `leverage`.
+pub fn initial_margin(notional: Decimal, leverage: u32) -> Decimal {
+    notional / Decimal::from(leverage.max(1))
+}
+
+/// Margin the venue requires to keep `notional` open.
+pub fn maintenance_margin(notional: Decimal, futures: &BinanceFuturesConfig) -> Decimal {
+    notional * dec(futures.maintenance_margin_pct) / Decimal::ONE_HUNDRED
+}
+
 /// Pre-computed order sizing for fast execution.
 #[derive(Clone, Debug)]
 pub struct OrderSizing {
-    pub qty: f64,
-    pub notional: f64,
-    pub limit_price: f64,
+    pub qty: Decimal,
+    pub notional: Decimal,
+    pub limit_price: Decimal,
+}
+
+/// Entry sizing method (`sizing.method`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizingMethod {
+    BalancePct,
+    Atr,
+    Kelly,
+    FixedNotional,
+}
+
+impl SizingMethod {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "balance_pct" | "balance" => Some(SizingMethod::BalancePct),
+            "atr" => Some(SizingMethod::Atr),
+            "kelly" => Some(SizingMethod::Kelly),
+            "fixed_notional" | "fixed" => Some(SizingMethod::FixedNotional),
+            _ => None,
+        }
+    }
 }
 
-/// Calculate order sizing based on config and available balance.
+/// How much of the buying power one entry commits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizingStrategy {
+    /// Share of buying power (0.02 = 2%)
+    BalancePct(Decimal),
+    /// Same notional for every entry (quote currency)
+    FixedNotional(Decimal),
+    /// Lose `risk_pct` of buying power (0.01 = 1%) if price falls by `stop_distance`
+    AtrRisk {
+        risk_pct: Decimal,
+        stop_distance: Decimal,
+    },
+    /// Fractional Kelly share of buying power, already capped (0 = no edge)
+    Kelly(Decimal),
+}
+
+impl SizingStrategy {
+    /// Strategy for `config.sizing.method`. `fx` converts the reporting
+    /// currency to the symbol's quote currency; `atr` is the symbol's ATR and
+    /// `record` the session's closed trades. Falls back to balance_pct while
+    /// the ATR or the trade record is not there yet.
+    pub fn from_config(
+        config: &AppConfig,
+        fx: Decimal,
+        atr: Option<f64>,
+        record: &WinLoss,
+    ) -> Self {
+        let sizing = &config.sizing;
+        let fallback = SizingStrategy::BalancePct(dec(config.micro_trade.target_balance_pct));
+        match SizingMethod::parse(&sizing.method).unwrap_or(SizingMethod::BalancePct) {
+            SizingMethod::BalancePct => fallback,
+            SizingMethod::FixedNotional => {
+                SizingStrategy::FixedNotional(dec(sizing.fixed_notional) / fx)
+            }
+            SizingMethod::Atr => match atr.filter(|a| *a > 0.0) {
+                Some(atr) if sizing.atr_stop_multiple > 0.0 => SizingStrategy::AtrRisk {
+                    risk_pct: dec(sizing.risk_per_trade_pct / 100.0),
+                    stop_distance: dec(atr * sizing.atr_stop_multiple),
+                },
+                _ => fallback,
+            },
+            SizingMethod::Kelly => {
+                if record.wins + record.losses < sizing.kelly_min_trades.max(1) {
+                    return fallback;
+                }
+                let Some(full) = kelly_fraction(record) else {
+                    return fallback;
+                };
+                let share = (full * sizing.kelly_fraction).clamp(0.0, sizing.kelly_max_pct / 100.0);
+                SizingStrategy::Kelly(dec(share))
+            }
+        }
+    }
+
+    /// Shrink entries by `factor` (warm-up, reduced health level).
+    pub fn scaled(self, factor: Decimal) -> Self {
+        match self {
+            SizingStrategy::BalancePct(pct) => SizingStrategy::BalancePct(pct * factor),
+            SizingStrategy::FixedNotional(n) => SizingStrategy::FixedNotional(n * factor),
+            SizingStrategy::AtrRisk {
+                risk_pct,
+                stop_distance,
+            } => SizingStrategy::AtrRisk {
+                risk_pct: risk_pct * factor,
+                stop_distance,
+            },
+            SizingStrategy::Kelly(share) => SizingStrategy::Kelly(share * factor),
+        }
+    }
+
+    /// Notional before the min/max clamp. `None` when the strategy wants no
+    /// position (Kelly without an edge).
+    pub fn target_notional(&self, price: Decimal, buying_power: Decimal) -> Option<Decimal> {
+        match *self {
+            SizingStrategy::BalancePct(pct) => Some(buying_power * pct),
+            SizingStrategy::FixedNotional(notional) => Some(notional),
+            SizingStrategy::AtrRisk {
+                risk_pct,
+                stop_distance,
+            } => {
+                if stop_distance <= Decimal::ZERO {
+                    return None;
+                }
+                // Quantity whose loss at the stop is the risk budget
+                Some(buying_power * risk_pct / stop_distance * price)
+            }
+            SizingStrategy::Kelly(share) if share > Decimal::ZERO => Some(buying_power * share),
+            SizingStrategy::Kelly(_) => None,
+        }
+    }
+}
+
+/// Full Kelly fraction `p - (1 - p) / b` from a win/loss record, with `p` the
+/// win rate and `b` the average win over the average loss. Negative when the
+/// record has no edge; `None` until there is at least one win and one loss.
+pub fn kelly_fraction(record: &WinLoss) -> Option<f64> {
+    if record.wins == 0 || record.losses == 0 || record.total_loss <= Decimal::ZERO {
+        return None;
+    }
+    let avg_win = float(record.total_profit) / record.wins as f64;
+    let avg_loss = float(record.total_loss) / record.losses as f64;
+    let p = record.wins as f64 / (record.wins + record.losses) as f64;
+    let b = avg_win / avg_loss;
+    if b <= 0.0 {
+        return Some(-1.0);
+    }
+    Some(p - (1.0 - p) / b)
+}
+
+/// Quantity resting within `window_bps` of `mid` on one side of the book,
+/// given its `(price, size)` levels.
+pub fn liquidity_within_bps(levels: &[(f64, f64)], mid: f64, window_bps: f64) -> f64 {
+    if mid <= 0.0 {
+        return 0.0;
+    }
+    levels
+        .iter()
+        .filter(|(price, _)| ((price - mid).abs() / mid) * 10_000.0 <= window_bps)
+        .map(|(_, size)| size.max(0.0))
+        .sum()
+}
+
+/// Largest entry quantity that takes at most `sizing.depth_cap_fraction` of
+/// the ask liquidity within `depth_window_bps` of mid. Only the touch is
+/// streamed, so the visible liquidity is the best ask size (when the ask is
+/// inside the window). None when the cap is off or the venue sends no sizes.
+pub fn depth_cap(config: &AppConfig, bid: f64, ask: f64, ask_size: f64) -> Option<Decimal> {
+    let sizing = &config.sizing;
+    if sizing.depth_cap_fraction <= 0.0 || ask_size <= 0.0 {
+        return None;
+    }
+    let mid = (bid + ask) / 2.0;
+    let visible = liquidity_within_bps(&[(ask, ask_size)], mid, sizing.depth_window_bps);
+    Some(dec(visible * sizing.depth_cap_fraction))
+}
+
+/// Calculate order sizing from the sizing strategy and available balance,
+/// capped at `max_qty` (visible liquidity, see `depth_cap`) when given.
 /// Returns None if order cannot be placed.
 pub fn compute_order_sizing(
-    price: f64,
-    buying_power: f64,
-    min_order: f64,
-    max_order: f64,
-    target_pct_of_balance: f64,
+    price: Decimal,
+    buying_power: Decimal,
+    min_order: Decimal,
+    max_order: Decimal,
+    strategy: &SizingStrategy,
+    max_qty: Option<Decimal>,
 ) -> Option<OrderSizing> {
-    if price <= 0.0 || buying_power <= 0.0 {
+    if price <= Decimal::ZERO || buying_power <= Decimal::ZERO {
         return None;
     }
 
-    // Target notional = percentage of buying power, clamped to min/max
-    let mut notional = buying_power * target_pct_of_balance;
+    // Target notional from the strategy, clamped to min/max
+    let mut notional = strategy.target_notional(price, buying_power)?;
 
     // Clamp to configured limits
     if notional < min_order {
@@ -108,7 +395,7 @@ pub fn compute_order_sizing(
     }
 
     // Safety: don't exceed 95% of buying power (leave room for fees)
-    let max_affordable = buying_power * 0.95;
+    let max_affordable = buying_power * Decimal::new(95, 2);
     if notional > max_affordable {
         if max_affordable < min_order {
             return None; // Can't afford minimum order
@@ -116,7 +403,19 @@ pub fn compute_order_sizing(
         notional = max_affordable;
     }
 
-    let qty = notional / price;
+    // Don't take more than the configured share of the book: a smaller entry
+    // beats one that walks the book; below the minimum order, skip it
+    if let Some(max_qty) = max_qty {
+        if notional > max_qty * price {
+            notional = max_qty * price;
+            if notional < min_order {
+                return None;
+            }
+        }
+    }
+
+    // Decimal division keeps up to 28 digits; no venue takes more than 8
+    let qty = (notional / price).round_dp_with_strategy(8, RoundingStrategy::ToZero);
 
     Some(OrderSizing {
         qty,
@@ -141,12 +440,524 @@ pub fn aggressive_limit_price(bid: f64, ask: f64, side: &str, aggression_bps: f6
     }
 }
 
+/// Post-only buy price: `offset_bps` above the bid, kept strictly below the
+/// ask so the order rests instead of taking liquidity.
+pub fn passive_limit_price(bid: f64, ask: f64, offset_bps: f64) -> f64 {
+    let price = bid * (1.0 + offset_bps.max(0.0) / 10_000.0);
+    if price < ask {
+        price
+    } else {
+        bid
+    }
+}
+
+/// How a resting post-only entry ended.
+#[derive(Clone, Debug)]
+pub enum PassiveOutcome {
+    /// Filled completely while resting
+    Filled(OrderAck),
+    /// Cancelled on timeout or killed by the venue (it would have crossed);
+    /// `filled` executed before that, at `avg_price` when the venue reports it
+    Unfilled {
+        filled: Decimal,
+        avg_price: Option<Decimal>,
+    },
+    /// Final state could not be read; the order is left to the position monitor
+    Unknown,
+}
+
+/// Wait up to `timeout` for the post-only order `order_id` to fill, polling
+/// every `poll`. On timeout it is cancelled and its final state read back, so
+/// a fill that raced the cancel is not escalated twice.
+pub async fn await_passive_fill(
+    exchange: &dyn TradingApi,
+    order_id: &str,
+    timeout: Duration,
+    poll: Duration,
+) -> PassiveOutcome {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        tokio::time::sleep(poll.min(deadline - now)).await;
+        match exchange.get_order(order_id).await {
+            Ok(ack) => {
+                if let Some(outcome) = passive_outcome(ack) {
+                    return outcome;
+                }
+            }
+            Err(e) => warn!(
+                "⚠️ [EXECUTION] Passive order {} lookup failed: {}",
+                order_id, e
+            ),
+        }
+    }
+
+    if let Err(e) = exchange.cancel_order(order_id).await {
+        warn!(
+            "⚠️ [EXECUTION] Failed to cancel passive order {}: {}",
+            order_id, e
+        );
+    }
+    match exchange.get_order(order_id).await {
+        Ok(ack) => passive_outcome(ack).unwrap_or(PassiveOutcome::Unknown),
+        Err(e) => {
+            warn!(
+                "⚠️ [EXECUTION] Passive order {} state unknown after cancel: {}",
+                order_id, e
+            );
+            PassiveOutcome::Unknown
+        }
+    }
+}
+
+/// Terminal outcome of an order state, or None while it is still working.
+fn passive_outcome(ack: OrderAck) -> Option<PassiveOutcome> {
+    match ack.status.to_lowercase().as_str() {
+        "filled" => Some(PassiveOutcome::Filled(ack)),
+        "canceled" | "cancelled" | "expired" | "rejected" => Some(PassiveOutcome::Unfilled {
+            filled: ack
+                .raw
+                .get("filled_qty")
+                .and_then(money::from_json)
+                .unwrap_or(Decimal::ZERO),
+            avg_price: filled_avg_price(&ack.raw),
+        }),
+        _ => None,
+    }
+}
+
+/// Average fill price of an order state (`filled_avg_price`), if the venue
+/// reported one.
+pub fn filled_avg_price(raw: &serde_json::Value) -> Option<Decimal> {
+    raw.get("filled_avg_price")
+        .and_then(money::from_json)
+        .filter(|p| *p > Decimal::ZERO)
+}
+
+/// Order-state reads for a leg still unknown after its wait
+const LEG_RECONCILE_ATTEMPTS: usize = 3;
+
+/// Limit order for one leg of a multi-leg trade.
+pub fn limit_order(
+    symbol: &str,
+    side: Side,
+    qty: Decimal,
+    price: Decimal,
+    time_in_force: TimeInForce,
+) -> PlaceOrderRequest {
+    PlaceOrderRequest {
+        symbol: symbol.to_string(),
+        side,
+        order_type: OrderType::Limit,
+        qty: Some(qty),
+        notional: None,
+        limit_price: Some(price),
+        time_in_force,
+        bracket: None,
+        stop_price: None,
+        reduce_only: false,
+        metadata: HashMap::new(),
+    }
+}
+
+/// Time in force of market orders: GTC for crypto, DAY for stocks.
+pub fn market_tif(config: &AppConfig) -> TimeInForce {
+    if config.trading_mode.eq_ignore_ascii_case("crypto") {
+        TimeInForce::Gtc
+    } else {
+        TimeInForce::Day
+    }
+}
+
+/// Market order for `qty` of `symbol` (unwinds and exits of multi-leg trades).
+pub fn market_order(
+    symbol: &str,
+    side: Side,
+    qty: Decimal,
+    time_in_force: TimeInForce,
+) -> PlaceOrderRequest {
+    PlaceOrderRequest {
+        order_type: OrderType::Market,
+        limit_price: None,
+        reduce_only: true,
+        ..limit_order(symbol, side, qty, Decimal::ZERO, time_in_force)
+    }
+}
+
+/// Submit one leg and wait up to `timeout` for its final state, polling
+/// every `poll`. Returns the quantity filled (zero when rejected), or None
+/// when the venue never reported a final state, so the leg may hold a fill.
+pub async fn fill_leg(
+    exchange: &dyn TradingApi,
+    req: PlaceOrderRequest,
+    timeout: Duration,
+    poll: Duration,
+) -> Option<Decimal> {
+    let (symbol, side, qty) = (req.symbol.clone(), req.side, req.qty.unwrap_or_default());
+    let ack = match exchange.submit_order(req).await {
+        Ok(ack) => ack,
+        Err(e) => {
+            warn!(
+                "⚠️ [EXECUTION] {:?} {} leg on {} rejected: {}",
+                side,
+                symbol,
+                exchange.name(),
+                e
+            );
+            return Some(Decimal::ZERO);
+        }
+    };
+    if ack.status.eq_ignore_ascii_case("filled") {
+        return Some(qty);
+    }
+    let outcome = match await_passive_fill(exchange, &ack.id, timeout, poll).await {
+        PassiveOutcome::Unknown => reconcile_leg(exchange, &ack.id, poll).await,
+        outcome => outcome,
+    };
+    match outcome {
+        PassiveOutcome::Filled(_) => Some(qty),
+        PassiveOutcome::Unfilled { filled, .. } => Some(filled),
+        PassiveOutcome::Unknown => {
+            error!(
+                "❌ [EXECUTION] {:?} {} leg {} on {} state unknown - it may have filled",
+                side,
+                symbol,
+                ack.id,
+                exchange.name()
+            );
+            None
+        }
+    }
+}
+
+/// Read the state of a leg left unknown again, cancelling it if it is still
+/// working, until it is final or the attempts run out.
+async fn reconcile_leg(
+    exchange: &dyn TradingApi,
+    order_id: &str,
+    poll: Duration,
+) -> PassiveOutcome {
+    for _ in 0..LEG_RECONCILE_ATTEMPTS {
+        tokio::time::sleep(poll).await;
+        match exchange.get_order(order_id).await {
+            Ok(ack) => match passive_outcome(ack) {
+                Some(outcome) => return outcome,
+                None => {
+                    if let Err(e) = exchange.cancel_order(order_id).await {
+                        warn!("⚠️ [EXECUTION] Failed to cancel leg {}: {}", order_id, e);
+                    }
+                }
+            },
+            Err(e) => warn!("⚠️ [EXECUTION] Leg {} lookup failed: {}", order_id, e),
+        }
+    }
+    PassiveOutcome::Unknown
+}
+
+/// Send two legs at once and wait for both (see `fill_leg`).
+pub async fn fill_legs(
+    legs: [(&dyn TradingApi, PlaceOrderRequest); 2],
+    timeout: Duration,
+    poll: Duration,
+) -> [Option<Decimal>; 2] {
+    let [(exchange_a, leg_a), (exchange_b, leg_b)] = legs;
+    let (a, b) = tokio::join!(
+        fill_leg(exchange_a, leg_a, timeout, poll),
+        fill_leg(exchange_b, leg_b, timeout, poll)
+    );
+    [a, b]
+}
+
+/// Halt new entries over a leg whose fill is unknown: the account may hold
+/// an unhedged position until an operator checks it and calls `POST /resume`.
+pub fn halt_unhedged(bus: Option<&EventBus>, reason: String) {
+    error!("🛑 [EXECUTION] {} - new entries halted", reason);
+    if let Some(bus) = bus {
+        bus.publish(Event::System(SystemEvent::Halt {
+            reason,
+            flatten: false,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }))
+        .ok();
+    }
+}
+
+/// Limit price of a stop-limit exit: `buffer_bps` under the stop.
+pub fn stop_limit_price(stop: Decimal, buffer_bps: f64) -> Decimal {
+    stop * (Decimal::ONE - dec(buffer_bps.max(0.0)) / dec(10_000.0))
+}
+
+/// Polling interval of a resting stop-limit exit
+const STOP_LIMIT_POLL: Duration = Duration::from_millis(200);
+
+/// Stop-loss exit of `qty` as a limit under `stop` (`stop_limit`): whatever
+/// has not filled after `timeout_ms` is cancelled and sold at market. The
+/// limit leg is reported at its limit (as a partial exit when market sells
+/// the rest); if neither leg sells it all, the rest stays tracked and the
+/// shortfall is published as a rejection. A limit whose final state cannot be
+/// read becomes the position's exit order, so the monitor confirms it dead
+/// before the stop fires again.
+#[allow(clippy::too_many_arguments)]
+pub async fn stop_limit_exit(
+    exchange: &dyn TradingApi,
+    symbol_meta: &SymbolMetaRegistry,
+    tracker: &PositionTracker,
+    bus: &EventBus,
+    req: &OrderRequest,
+    qty: Decimal,
+    stop: Decimal,
+    (bid, signal_price): (Decimal, Option<Decimal>),
+    time_in_force: TimeInForce,
+    config: &StopLimitConfig,
+) {
+    let symbol = req.symbol.as_str();
+    let price = stop_limit_price(stop, config.buffer_bps);
+    let limit_req = PlaceOrderRequest {
+        symbol: symbol.to_string(),
+        side: Side::Sell,
+        order_type: OrderType::Limit,
+        qty: Some(qty),
+        notional: None,
+        time_in_force,
+        limit_price: Some(price),
+        bracket: None,
+        stop_price: None,
+        reduce_only: true,
+        metadata: req.metadata.clone(),
+    };
+    let Some(limit_req) = normalize_order(symbol_meta, exchange, limit_req, price).await else {
+        return;
+    };
+    let price = limit_req.limit_price.unwrap_or(price);
+    let qty = limit_req.qty.unwrap_or(qty);
+    let report =
+        |order_id: String, status: &str, price: Decimal, qty: Decimal, limit| ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id,
+            status: status.to_string(),
+            side: "sell".to_string(),
+            price: Some(price),
+            qty: Some(qty),
+            signal_price,
+            limit_price: limit,
+            strategy_id: req.strategy_id.clone(),
+            correlation_id: req.correlation_id.clone(),
+            metadata: req.metadata.clone(),
+        };
+
+    let mut reports = Vec::new();
+    let mut remaining = qty;
+    match exchange.submit_order(limit_req).await {
+        Ok(ack) => {
+            warn!(
+                "🛑 [EXECUTION] Stop-limit SELL {} qty={} @ ${:.8} (stop ${:.8}, market after {}ms)",
+                symbol, qty, price, stop, config.timeout_ms
+            );
+            let timeout = Duration::from_millis(config.timeout_ms);
+            // Reported at the venue's average fill, the limit being only its worst case
+            let (filled, fill_price, order_id) =
+                match await_passive_fill(exchange, &ack.id, timeout, STOP_LIMIT_POLL).await {
+                    PassiveOutcome::Filled(done) => (
+                        done.raw
+                            .get("filled_qty")
+                            .and_then(money::from_json)
+                            .filter(|q| *q > Decimal::ZERO)
+                            .unwrap_or(qty),
+                        filled_avg_price(&done.raw).unwrap_or(price),
+                        done.id,
+                    ),
+                    PassiveOutcome::Unfilled { filled, avg_price } => {
+                        (filled, avg_price.unwrap_or(price), ack.id)
+                    }
+                    PassiveOutcome::Unknown => {
+                        warn!(
+                            "⚠️ [EXECUTION] Stop-limit {} for {} unconfirmed - left to the monitor",
+                            ack.id, symbol
+                        );
+                        tracker.add_pending_order(PendingOrder {
+                            order_id: ack.id.clone(),
+                            symbol: symbol.to_string(),
+                            side: "sell".to_string(),
+                            limit_price: price,
+                            qty,
+                            filled_qty: Decimal::ZERO,
+                            remaining_qty: qty,
+                            created_at: chrono::Utc::now().to_rfc3339(),
+                            stop_loss: None,
+                            take_profit: None,
+                            last_check_time: None,
+                            bracket: false,
+                            correlation_id: req.correlation_id.clone(),
+                        });
+                        if let Some(mut position) = tracker.get_position(symbol) {
+                            position.open_order_id = Some(ack.id);
+                            position.is_closing = false;
+                            tracker.add_position(position);
+                        }
+                        return;
+                    }
+                };
+            remaining = (qty - filled).max(Decimal::ZERO);
+            if filled > Decimal::ZERO {
+                let status = if remaining > Decimal::ZERO {
+                    "partial_fill"
+                } else {
+                    "filled"
+                };
+                reports.push(report(order_id, status, fill_price, filled, Some(price)));
+            }
+        }
+        Err(e) => warn!(
+            "⚠️ [EXECUTION] Stop-limit SELL {} refused ({}), selling at market",
+            symbol, e
+        ),
+    }
+
+    if remaining > Decimal::ZERO {
+        let market_req = PlaceOrderRequest {
+            symbol: symbol.to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Market,
+            qty: Some(remaining),
+            notional: None,
+            time_in_force,
+            limit_price: None,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: req.metadata.clone(),
+        };
+        match normalize_order(symbol_meta, exchange, market_req, bid).await {
+            Some(market_req) => {
+                let market_qty = market_req.qty.unwrap_or(remaining);
+                match exchange.submit_order(market_req).await {
+                    Ok(ack) => {
+                        warn!(
+                            "🛑 [EXECUTION] Stop-limit for {} escalated: SELL {} at market (id={})",
+                            symbol, market_qty, ack.id
+                        );
+                        reports.push(report(ack.id, &ack.status, bid, market_qty, None));
+                        remaining = (remaining - market_qty).max(Decimal::ZERO);
+                    }
+                    Err(e) => {
+                        error!("[FAILED] Stop-limit market SELL {}: {}", symbol, e);
+                    }
+                }
+            }
+            // Below the venue's minimum: nothing more can be sold
+            None => remaining = Decimal::ZERO,
+        }
+    }
+
+    if remaining > Decimal::ZERO {
+        if let Some(mut position) = tracker.get_position(symbol) {
+            position.set_filled_qty(remaining);
+            tracker.add_position(position);
+        }
+        publish_rejection(bus, symbol, "sell", bid, remaining);
+    } else {
+        tracker.remove_position(symbol);
+    }
+    for report in reports {
+        bus.publish(Event::Execution(report)).ok();
+    }
+}
+
+/// Submit an entry order, attaching TP/SL as a native bracket when the venue supports it.
+/// Returns the ack plus whether the exit legs are now managed by the exchange.
+pub async fn submit_entry(
+    exchange: &dyn TradingApi,
+    req: PlaceOrderRequest,
+    take_profit: Decimal,
+    stop_loss: Decimal,
+) -> ExchangeResult<(OrderAck, bool)> {
+    if exchange.capabilities().supports_bracket_orders {
+        let legs = BracketLegs {
+            take_profit,
+            stop_loss,
+        };
+        let ack = exchange.submit_bracket_order(req, legs).await?;
+        Ok((ack, true))
+    } else {
+        let ack = exchange.submit_order(req).await?;
+        Ok((ack, false))
+    }
+}
+
+/// Round `req` to the symbol's tick/step size before submission. Orders that
+/// fall below the venue minimums are skipped (logged) instead of being sent.
+pub async fn normalize_order(
+    symbol_meta: &SymbolMetaRegistry,
+    exchange: &dyn TradingApi,
+    req: PlaceOrderRequest,
+    ref_price: Decimal,
+) -> Option<PlaceOrderRequest> {
+    match symbol_meta.normalize(exchange, req, ref_price).await {
+        Ok(req) => Some(req),
+        Err(reason) => {
+            warn!("📏 [EXECUTION] Not submitting: {}", reason);
+            None
+        }
+    }
+}
+
+/// Publish a "rejected" report for an order the venue refused, so the reporter
+/// and circuit breaker see submission failures.
+pub fn publish_rejection(bus: &EventBus, symbol: &str, side: &str, price: Decimal, qty: Decimal) {
+    let report = ExecutionReport {
+        symbol: symbol.to_string(),
+        order_id: String::new(),
+        status: "rejected".to_string(),
+        side: side.to_string(),
+        price: Some(price),
+        qty: Some(qty),
+        signal_price: None,
+        limit_price: None,
+        strategy_id: None,
+        correlation_id: None,
+        metadata: HashMap::new(),
+    };
+    bus.publish(Event::Execution(report)).ok();
+}
+
+/// Why `RateLimiter::try_acquire` refused an order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Throttle {
+    /// Within `min_order_interval_ms` of the symbol's last order
+    Cooldown,
+    /// `max_orders_per_minute_per_symbol` orders in the last minute
+    SymbolPerMinute(u32),
+    /// `max_orders_per_hour` orders across all symbols in the last hour
+    GlobalPerHour(u32),
+}
+
+impl std::fmt::Display for Throttle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Throttle::Cooldown => write!(f, "cooldown"),
+            Throttle::SymbolPerMinute(max) => write!(f, "{} orders/min for the symbol", max),
+            Throttle::GlobalPerHour(max) => write!(f, "{} orders/hour overall", max),
+        }
+    }
+}
+
 /// Rate limiter to prevent API abuse.
-/// Uses per-symbol tracking so different symbols can trade independently.
+/// Uses per-symbol tracking so different symbols can trade independently;
+/// optional rolling caps per symbol per minute and overall per hour stop a
+/// runaway signal loop from spraying orders.
 #[derive(Clone)]
 pub struct RateLimiter {
     last_order_per_symbol: Arc<DashMap<String, Instant>>,
     min_interval: Duration,
+    /// Order times within the last minute per symbol
+    symbol_orders: Arc<DashMap<String, VecDeque<Instant>>>,
+    /// Order times within the last hour (its lock also serializes acquisitions)
+    global_orders: Arc<Mutex<VecDeque<Instant>>>,
+    per_symbol_per_minute: Option<u32>,
+    global_per_hour: Option<u32>,
 }
 
 impl RateLimiter {
@@ -154,24 +965,70 @@ impl RateLimiter {
         Self {
             last_order_per_symbol: Arc::new(DashMap::new()),
             min_interval: Duration::from_millis(min_interval_ms),
+            symbol_orders: Arc::new(DashMap::new()),
+            global_orders: Arc::new(Mutex::new(VecDeque::new())),
+            per_symbol_per_minute: None,
+            global_per_hour: None,
         }
     }
 
-    /// Returns true if order is allowed for this symbol, false if rate limited.
-    /// Each symbol has independent rate limiting.
-    pub async fn try_acquire(&self, symbol: &str) -> bool {
-        let now = Instant::now();
+    /// Cap orders per symbol per rolling minute and overall per rolling hour.
+    pub fn with_order_caps(
+        mut self,
+        per_symbol_per_minute: Option<u32>,
+        global_per_hour: Option<u32>,
+    ) -> Self {
+        self.per_symbol_per_minute = per_symbol_per_minute;
+        self.global_per_hour = global_per_hour;
+        self
+    }
+
+    /// Ok (and the order is counted) if an order for this symbol is allowed
+    /// now. Each symbol has independent rate limiting.
+    pub async fn try_acquire(&self, symbol: &str) -> Result<(), Throttle> {
+        self.try_acquire_at(symbol, Instant::now())
+    }
+
+    /// `try_acquire` at `now`.
+    pub fn try_acquire_at(&self, symbol: &str, now: Instant) -> Result<(), Throttle> {
+        const MINUTE: Duration = Duration::from_secs(60);
+        const HOUR: Duration = Duration::from_secs(3600);
+        let mut global = self.global_orders.lock().unwrap();
 
         // Check if this symbol is rate limited
         if let Some(entry) = self.last_order_per_symbol.get(symbol) {
-            let last_order_time = *entry.value(); // Get the actual Instant value
-            if now.duration_since(last_order_time) < self.min_interval {
-                return false; // Still in cooldown
+            if now.saturating_duration_since(*entry.value()) < self.min_interval {
+                return Err(Throttle::Cooldown); // Still in cooldown
+            }
+        }
+        while global
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) >= HOUR)
+        {
+            global.pop_front();
+        }
+        if let Some(max) = self.global_per_hour {
+            if global.len() >= max as usize {
+                return Err(Throttle::GlobalPerHour(max));
+            }
+        }
+        let mut recent = self.symbol_orders.entry(symbol.to_string()).or_default();
+        while recent
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) >= MINUTE)
+        {
+            recent.pop_front();
+        }
+        if let Some(max) = self.per_symbol_per_minute {
+            if recent.len() >= max as usize {
+                return Err(Throttle::SymbolPerMinute(max));
             }
         }
 
         // Update last order time for this symbol
         self.last_order_per_symbol.insert(symbol.to_string(), now);
-        true
+        recent.push_back(now);
+        global.push_back(now);
+        Ok(())
     }
 }
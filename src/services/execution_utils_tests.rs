@@ -2,81 +2,88 @@
 
 #[cfg(test)]
 mod execution_utils_tests {
+    use crate::money::dec;
     use crate::services::execution_utils::*;
+    use rust_decimal::Decimal;
 
     // ============= Order Sizing Tests =============
 
     #[test]
     fn test_compute_order_sizing_basic() {
         let result = compute_order_sizing(
-            100.0,   // price
-            10000.0, // buying_power
-            10.0,    // min_order
-            100.0,   // max_order
-            0.05,    // target 5% of balance
+            dec(100.0),                             // price
+            dec(10000.0),                           // buying_power
+            dec(10.0),                              // min_order
+            dec(100.0),                             // max_order
+            &SizingStrategy::BalancePct(dec(0.05)), // target 5% of balance
+            None,
         );
 
         assert!(result.is_some());
         let sizing = result.unwrap();
-        assert_eq!(sizing.notional, 100.0); // 5% of 10000 = 500, clamped to max 100
-        assert_eq!(sizing.qty, 1.0); // 100 / 100 = 1
-        assert_eq!(sizing.limit_price, 100.0);
+        assert_eq!(sizing.notional, dec(100.0)); // 5% of 10000 = 500, clamped to max 100
+        assert_eq!(sizing.qty, dec(1.0)); // 100 / 100 = 1
+        assert_eq!(sizing.limit_price, dec(100.0));
     }
 
     #[test]
     fn test_compute_order_sizing_min_order() {
         let result = compute_order_sizing(
-            100.0, // price
-            100.0, // buying_power (small)
-            10.0,  // min_order
-            100.0, // max_order
-            0.05,  // target 5% = $5, but min is $10
+            dec(100.0),                             // price
+            dec(100.0),                             // buying_power (small)
+            dec(10.0),                              // min_order
+            dec(100.0),                             // max_order
+            &SizingStrategy::BalancePct(dec(0.05)), // target 5% = $5, but min is $10
+            None,
         );
 
         assert!(result.is_some());
         let sizing = result.unwrap();
-        assert_eq!(sizing.notional, 10.0); // Bumped up to min
+        assert_eq!(sizing.notional, dec(10.0)); // Bumped up to min
     }
 
     #[test]
     fn test_compute_order_sizing_max_order() {
         let result = compute_order_sizing(
-            100.0,    // price
-            100000.0, // buying_power (large)
-            10.0,     // min_order
-            100.0,    // max_order
-            0.10,     // target 10% = $10000, clamped to max $100
+            dec(100.0),                             // price
+            dec(100000.0),                          // buying_power (large)
+            dec(10.0),                              // min_order
+            dec(100.0),                             // max_order
+            &SizingStrategy::BalancePct(dec(0.10)), // target 10% = $10000, clamped to max $100
+            None,
         );
 
         assert!(result.is_some());
         let sizing = result.unwrap();
-        assert_eq!(sizing.notional, 100.0); // Clamped to max
+        assert_eq!(sizing.notional, dec(100.0)); // Clamped to max
     }
 
     #[test]
     fn test_compute_order_sizing_95_percent_cap() {
         // Test that we don't exceed 95% of buying power
         let result = compute_order_sizing(
-            100.0, // price
-            50.0,  // buying_power (only $50)
-            10.0,  // min_order
-            100.0, // max_order
-            0.50,  // target 50% = $25, but max affordable is $47.50 (95%)
+            dec(100.0),                             // price
+            dec(50.0),                              // buying_power (only $50)
+            dec(10.0),                              // min_order
+            dec(100.0),                             // max_order
+            &SizingStrategy::BalancePct(dec(0.50)), // target 50% = $25, but max affordable is $47.50 (95%)
+            None,
         );
 
         assert!(result.is_some());
         let sizing = result.unwrap();
-        assert_eq!(sizing.notional, 25.0); // 50% of $50 = $25
+        assert_eq!(sizing.notional, dec(25.0)); // 50% of $50 = $25
     }
 
     #[test]
     fn test_compute_order_sizing_cant_afford_min() {
         let result = compute_order_sizing(
-            100.0, // price
-            5.0,   // buying_power (only $5)
-            10.0,  // min_order ($10 minimum)
-            100.0, // max_order
-            0.50,  // target 50% = $2.50
+            dec(100.0),                             // price
+            dec(5.0),                               // buying_power (only $5)
+            dec(10.0),                              // min_order ($10 minimum)
+            dec(100.0),                             // max_order
+            &SizingStrategy::BalancePct(dec(0.50)), // target 50% = $2.50
+            None,
         );
 
         // Can't afford minimum order
@@ -86,8 +93,12 @@ mod execution_utils_tests {
     #[test]
     fn test_compute_order_sizing_zero_price() {
         let result = compute_order_sizing(
-            0.0, // invalid price
-            10000.0, 10.0, 100.0, 0.05,
+            Decimal::ZERO, // invalid price
+            dec(10000.0),
+            dec(10.0),
+            dec(100.0),
+            &SizingStrategy::BalancePct(dec(0.05)),
+            None,
         );
         assert!(result.is_none());
     }
@@ -95,8 +106,12 @@ mod execution_utils_tests {
     #[test]
     fn test_compute_order_sizing_negative_price() {
         let result = compute_order_sizing(
-            -100.0, // invalid price
-            10000.0, 10.0, 100.0, 0.05,
+            dec(-100.0), // invalid price
+            dec(10000.0),
+            dec(10.0),
+            dec(100.0),
+            &SizingStrategy::BalancePct(dec(0.05)),
+            None,
         );
         assert!(result.is_none());
     }
@@ -104,8 +119,12 @@ mod execution_utils_tests {
     #[test]
     fn test_compute_order_sizing_zero_buying_power() {
         let result = compute_order_sizing(
-            100.0, 0.0, // no buying power
-            10.0, 100.0, 0.05,
+            dec(100.0),
+            Decimal::ZERO, // no buying power
+            dec(10.0),
+            dec(100.0),
+            &SizingStrategy::BalancePct(dec(0.05)),
+            None,
         );
         assert!(result.is_none());
     }
@@ -114,17 +133,18 @@ mod execution_utils_tests {
     fn test_compute_order_sizing_exact_fit() {
         // Notional fits exactly within constraints
         let result = compute_order_sizing(
-            50.0,   // price
-            1000.0, // buying_power
-            50.0,   // min_order
-            50.0,   // max_order (same as min)
-            0.05,   // target 5% = $50
+            dec(50.0),                              // price
+            dec(1000.0),                            // buying_power
+            dec(50.0),                              // min_order
+            dec(50.0),                              // max_order (same as min)
+            &SizingStrategy::BalancePct(dec(0.05)), // target 5% = $50
+            None,
         );
 
         assert!(result.is_some());
         let sizing = result.unwrap();
-        assert_eq!(sizing.notional, 50.0);
-        assert_eq!(sizing.qty, 1.0);
+        assert_eq!(sizing.notional, dec(50.0));
+        assert_eq!(sizing.qty, dec(1.0));
     }
 
     // ============= Aggressive Limit Price Tests =============
@@ -187,12 +207,340 @@ mod execution_utils_tests {
         assert!((price - 100.1).abs() < 0.01);
     }
 
+    // ============= Passive Entry Tests =============
+
+    use crate::error::AutoHedgeError;
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::traits::ExchangeResult;
+    use crate::exchange::types::{OrderAck, PlaceOrderRequest, Side};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn state(status: &str, filled_qty: &str) -> ExchangeResult<OrderAck> {
+        Ok(OrderAck {
+            id: "p1".to_string(),
+            status: status.to_string(),
+            raw: json!({ "filled_qty": filled_qty }),
+        })
+    }
+
+    /// Venue answering `get_order` from a script (the last state repeats); a
+    /// cancel leaves a resting order cancelled with 0.004 filled. The first
+    /// order submitted is the scripted one, later ones fill at market.
+    fn scripted(states: Vec<ExchangeResult<OrderAck>>) -> MockExchange {
+        let states = Arc::new(Mutex::new(states));
+        let cancelled = states.clone();
+        MockExchange::new("scripted")
+            .with_capabilities(|c| {
+                c.supports_notional_market_buy = true;
+                c.supports_ws_quotes = true;
+                c.supports_ws_trades = true;
+            })
+            .on_get_order(move |_, _| {
+                let mut states = states.lock().unwrap();
+                if states.len() > 1 {
+                    states.remove(0)
+                } else {
+                    match &states[0] {
+                        Ok(ack) => Ok(ack.clone()),
+                        Err(_) => Err(AutoHedgeError::Unsupported("lookup failed".to_string())),
+                    }
+                }
+            })
+            .on_cancel(move |_, _| {
+                let mut states = cancelled.lock().unwrap();
+                if matches!(states.last(), Some(Ok(ack)) if ack.status == "new") {
+                    *states = vec![state("canceled", "0.004")];
+                }
+                Ok(())
+            })
+            .on_submit(|mock, _| {
+                let id = if mock.submitted().len() == 1 {
+                    "p1"
+                } else {
+                    "m1"
+                };
+                Ok(ack(id, "filled", json!({})))
+            })
+    }
+
+    const POLL: Duration = Duration::from_millis(5);
+
+    #[test]
+    fn test_passive_limit_price_rests_inside_spread() {
+        assert_eq!(passive_limit_price(100.0, 101.0, 0.0), 100.0);
+        // 10 bps above the bid is still below the ask
+        assert!((passive_limit_price(100.0, 101.0, 10.0) - 100.1).abs() < 1e-9);
+        // An offset that would reach the ask falls back to joining the bid
+        assert_eq!(passive_limit_price(100.0, 100.05, 10.0), 100.0);
+        assert_eq!(passive_limit_price(100.0, 101.0, -5.0), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_await_passive_fill_filled_while_resting() {
+        let exchange = scripted(vec![state("new", "0"), state("filled", "0.01")]);
+        let outcome = await_passive_fill(&exchange, "p1", Duration::from_millis(500), POLL).await;
+        assert!(matches!(outcome, PassiveOutcome::Filled(ack) if ack.status == "filled"));
+        assert_eq!(exchange.cancelled().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_await_passive_fill_cancels_on_timeout() {
+        let exchange = scripted(vec![state("new", "0")]);
+        let outcome = await_passive_fill(&exchange, "p1", Duration::from_millis(30), POLL).await;
+        assert!(matches!(outcome, PassiveOutcome::Unfilled { filled, .. } if filled == dec(0.004)));
+        assert_eq!(exchange.cancelled().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_await_passive_fill_venue_kill_is_unfilled() {
+        // Post-only order rejected because it would have crossed
+        let exchange = scripted(vec![state("expired", "0")]);
+        let outcome = await_passive_fill(&exchange, "p1", Duration::from_millis(500), POLL).await;
+        assert!(matches!(outcome, PassiveOutcome::Unfilled { filled, .. } if filled.is_zero()));
+        assert_eq!(exchange.cancelled().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_await_passive_fill_unknown_when_state_unreadable() {
+        let exchange = scripted(vec![Err(AutoHedgeError::Unsupported("down".to_string()))]);
+        let outcome = await_passive_fill(&exchange, "p1", Duration::from_millis(20), POLL).await;
+        assert!(matches!(outcome, PassiveOutcome::Unknown));
+        assert_eq!(exchange.cancelled().len(), 1);
+    }
+
+    // ============= Multi-Leg Tests =============
+
+    /// Venue resting every leg; lookups fail until `answers_after` has passed,
+    /// then report the leg cancelled with 0.4 filled.
+    fn lossy(answers_after: Duration) -> MockExchange {
+        let answers_at = std::time::Instant::now() + answers_after;
+        MockExchange::new("lossy")
+            .on_submit(|_, _| Ok(ack("l1", "new", json!({}))))
+            .on_get_order(move |_, id| {
+                if std::time::Instant::now() < answers_at {
+                    return Err(AutoHedgeError::http(503, "unavailable"));
+                }
+                Ok(ack(id, "canceled", json!({ "filled_qty": "0.4" })))
+            })
+    }
+
+    fn ioc_leg() -> PlaceOrderRequest {
+        limit_order("ETH/USD", Side::Buy, dec(1.0), dec(100.0), TimeInForce::Ioc)
+    }
+
+    #[tokio::test]
+    async fn test_fill_leg_reconciles_a_lost_state() {
+        // Every read fails through the wait and the cancel; a later one answers
+        let exchange = lossy(Duration::from_millis(60));
+        let poll = Duration::from_millis(25);
+        let filled = fill_leg(&exchange, ioc_leg(), Duration::from_millis(30), poll).await;
+        assert_eq!(filled, Some(dec(0.4)));
+    }
+
+    #[tokio::test]
+    async fn test_fill_leg_unknown_when_the_venue_never_answers() {
+        let exchange = lossy(Duration::from_secs(60));
+        let filled = fill_leg(&exchange, ioc_leg(), Duration::from_millis(10), POLL).await;
+        assert_eq!(filled, None);
+
+        // Rejected legs filled nothing
+        let exchange = MockExchange::new("rejecting")
+            .on_submit(|_, _| Err(AutoHedgeError::http(400, "insufficient balance")));
+        let filled = fill_leg(&exchange, ioc_leg(), Duration::from_millis(10), POLL).await;
+        assert_eq!(filled, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_market_order_drops_the_limit() {
+        let order = market_order("ETH/USD", Side::Sell, dec(0.5), TimeInForce::Gtc);
+        assert!(matches!(order.order_type, OrderType::Market));
+        assert_eq!((order.qty, order.limit_price), (Some(dec(0.5)), None));
+        assert_eq!(order.side, Side::Sell);
+    }
+
+    #[test]
+    fn test_market_tif_follows_trading_mode() {
+        let mut config = sizing_config("  method: \"balance_pct\"");
+        assert!(matches!(market_tif(&config), TimeInForce::Gtc));
+        config.trading_mode = "stocks".to_string();
+        assert!(matches!(market_tif(&config), TimeInForce::Day));
+    }
+
+    // ============= Stop-Limit Exit Tests =============
+
+    use crate::bus::EventBus;
+    use crate::config::StopLimitConfig;
+    use crate::events::{Event, OrderRequest};
+    use crate::exchange::symbol_meta::SymbolMetaRegistry;
+    use crate::exchange::types::{OrderType, TimeInForce};
+    use crate::services::position_monitor::{PositionInfo, PositionTracker};
+
+    #[test]
+    fn test_stop_limit_price_sits_under_the_stop() {
+        assert_eq!(stop_limit_price(dec(100.0), 30.0), dec(99.7));
+        assert_eq!(stop_limit_price(dec(100.0), 0.0), dec(100.0));
+        // A negative buffer never lifts the limit above the stop
+        assert_eq!(stop_limit_price(dec(100.0), -10.0), dec(100.0));
+    }
+
+    fn stopped_position(tracker: &PositionTracker) {
+        tracker.add_position(PositionInfo {
+            symbol: "BTC/USD".to_string(),
+            entry_price: dec(105.0),
+            qty: dec(0.01),
+            filled_qty: dec(0.01),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(100.0),
+            take_profit: dec(110.0),
+            entry_time: chrono::Utc::now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: true,
+            open_order_id: None,
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(105.0),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(100.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
+        });
+    }
+
+    fn stop_exit_request() -> OrderRequest {
+        OrderRequest {
+            symbol: "BTC/USD".to_string(),
+            action: "sell".to_string(),
+            qty: dec(0.01),
+            order_type: "market".to_string(),
+            limit_price: None,
+            stop_loss: Some(dec(100.0)),
+            take_profit: None,
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
+        }
+    }
+
+    async fn run_stop_limit_exit(exchange: &MockExchange, tracker: &PositionTracker) -> Vec<Event> {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let config = StopLimitConfig {
+            enabled: true,
+            buffer_bps: 30.0,
+            timeout_ms: 30,
+        };
+        stop_limit_exit(
+            exchange,
+            &SymbolMetaRegistry::new(),
+            tracker,
+            &bus,
+            &stop_exit_request(),
+            dec(0.01),
+            dec(100.0),
+            (dec(99.0), Some(dec(99.5))),
+            TimeInForce::Gtc,
+            &config,
+        )
+        .await;
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_stop_limit_exit_filled_at_limit() {
+        let exchange = scripted(vec![state("new", "0"), state("filled", "0.01")]);
+        let tracker = PositionTracker::new();
+        stopped_position(&tracker);
+        let events = run_stop_limit_exit(&exchange, &tracker).await;
+
+        let submitted = exchange.submitted();
+        assert_eq!(submitted.len(), 1);
+        assert!(matches!(submitted[0].order_type, OrderType::Limit));
+        assert_eq!(submitted[0].limit_price, Some(dec(99.7)));
+        let [Event::Execution(report)] = events.as_slice() else {
+            panic!("expected a single report, got {:?}", events);
+        };
+        assert_eq!(report.status, "filled");
+        assert_eq!(
+            (report.price, report.qty),
+            (Some(dec(99.7)), Some(dec(0.01)))
+        );
+        assert!(tracker.get_position("BTC/USD").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stop_limit_exit_reports_the_average_fill() {
+        // Filled above its limit: the report carries the venue's average
+        let exchange = scripted(vec![Ok(OrderAck {
+            id: "p1".to_string(),
+            status: "filled".to_string(),
+            raw: json!({ "filled_qty": "0.01", "filled_avg_price": "99.85" }),
+        })]);
+        let tracker = PositionTracker::new();
+        stopped_position(&tracker);
+        let events = run_stop_limit_exit(&exchange, &tracker).await;
+
+        let [Event::Execution(report)] = events.as_slice() else {
+            panic!("expected a single report, got {:?}", events);
+        };
+        assert_eq!(report.price, Some(dec(99.85)));
+        assert_eq!(report.limit_price, Some(dec(99.7)));
+    }
+
+    #[tokio::test]
+    async fn test_stop_limit_exit_escalates_rest_to_market() {
+        // Rests unfilled until cancelled with 0.004 done
+        let exchange = scripted(vec![state("new", "0")]);
+        let tracker = PositionTracker::new();
+        stopped_position(&tracker);
+        let events = run_stop_limit_exit(&exchange, &tracker).await;
+
+        assert_eq!(exchange.cancelled().len(), 1);
+        let submitted = exchange.submitted();
+        assert_eq!(submitted.len(), 2);
+        assert!(matches!(submitted[1].order_type, OrderType::Market));
+        assert_eq!(submitted[1].qty, Some(dec(0.006)));
+        let [Event::Execution(limit), Event::Execution(market)] = events.as_slice() else {
+            panic!("expected two reports, got {:?}", events);
+        };
+        assert_eq!(limit.status, "partial_fill");
+        assert_eq!(
+            (limit.price, limit.qty),
+            (Some(dec(99.7)), Some(dec(0.004)))
+        );
+        assert_eq!(
+            (market.price, market.qty),
+            (Some(dec(99.0)), Some(dec(0.006)))
+        );
+        assert_eq!(market.limit_price, None);
+        assert!(tracker.get_position("BTC/USD").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stop_limit_exit_unknown_leaves_order_to_monitor() {
+        let exchange = scripted(vec![Err(AutoHedgeError::Unsupported("down".to_string()))]);
+        let tracker = PositionTracker::new();
+        stopped_position(&tracker);
+        let events = run_stop_limit_exit(&exchange, &tracker).await;
+
+        assert!(events.is_empty());
+        assert_eq!(exchange.submitted().len(), 1);
+        let position = tracker.get_position("BTC/USD").unwrap();
+        assert_eq!(position.open_order_id.as_deref(), Some("p1"));
+        assert!(!position.is_closing);
+    }
+
     // ============= Rate Limiter Tests =============
 
     #[tokio::test]
     async fn test_rate_limiter_first_call() {
         let limiter = RateLimiter::new(100); // 100ms interval
-        let allowed = limiter.try_acquire("BTC/USD").await;
+        let allowed = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(allowed);
     }
 
@@ -200,8 +548,8 @@ mod execution_utils_tests {
     async fn test_rate_limiter_immediate_second_call() {
         let limiter = RateLimiter::new(100); // 100ms interval
 
-        let first = limiter.try_acquire("BTC/USD").await;
-        let second = limiter.try_acquire("BTC/USD").await;
+        let first = limiter.try_acquire("BTC/USD").await.is_ok();
+        let second = limiter.try_acquire("BTC/USD").await.is_ok();
 
         assert!(first);
         assert!(!second); // Should be rate limited
@@ -211,13 +559,13 @@ mod execution_utils_tests {
     async fn test_rate_limiter_after_interval() {
         let limiter = RateLimiter::new(50); // 50ms interval
 
-        let first = limiter.try_acquire("BTC/USD").await;
+        let first = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(first);
 
         // Wait for interval to pass
         tokio::time::sleep(tokio::time::Duration::from_millis(60)).await;
 
-        let second = limiter.try_acquire("BTC/USD").await;
+        let second = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(second); // Should be allowed now
     }
 
@@ -229,7 +577,7 @@ mod execution_utils_tests {
         let mut denied_count = 0;
 
         for _ in 0..10 {
-            if limiter.try_acquire("BTC/USD").await {
+            if limiter.try_acquire("BTC/USD").await.is_ok() {
                 allowed_count += 1;
             } else {
                 denied_count += 1;
@@ -245,15 +593,15 @@ mod execution_utils_tests {
     async fn test_rate_limiter_with_delays() {
         let limiter = RateLimiter::new(20); // 20ms interval
 
-        let first = limiter.try_acquire("BTC/USD").await;
+        let first = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(first);
 
         tokio::time::sleep(tokio::time::Duration::from_millis(25)).await;
-        let second = limiter.try_acquire("BTC/USD").await;
+        let second = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(second);
 
         tokio::time::sleep(tokio::time::Duration::from_millis(25)).await;
-        let third = limiter.try_acquire("BTC/USD").await;
+        let third = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(third);
     }
 
@@ -262,16 +610,16 @@ mod execution_utils_tests {
         let limiter = RateLimiter::new(100); // 100ms interval
 
         // Different symbols should not interfere
-        let btc1 = limiter.try_acquire("BTC/USD").await;
-        let eth1 = limiter.try_acquire("ETH/USD").await;
-        let sol1 = limiter.try_acquire("SOL/USD").await;
+        let btc1 = limiter.try_acquire("BTC/USD").await.is_ok();
+        let eth1 = limiter.try_acquire("ETH/USD").await.is_ok();
+        let sol1 = limiter.try_acquire("SOL/USD").await.is_ok();
 
         assert!(btc1);
         assert!(eth1);
         assert!(sol1);
 
         // But same symbol should be rate limited
-        let btc2 = limiter.try_acquire("BTC/USD").await;
+        let btc2 = limiter.try_acquire("BTC/USD").await.is_ok();
         assert!(!btc2);
     }
 
@@ -280,22 +628,22 @@ mod execution_utils_tests {
         let limiter = RateLimiter::new(250); // 250ms interval (production config)
 
         // First call should succeed
-        let first = limiter.try_acquire("TEST/USD").await;
+        let first = limiter.try_acquire("TEST/USD").await.is_ok();
         assert!(first, "First call should be allowed");
 
         // Immediate second call should fail
-        let second = limiter.try_acquire("TEST/USD").await;
+        let second = limiter.try_acquire("TEST/USD").await.is_ok();
         assert!(!second, "Immediate second call should be rate limited");
 
         // Wait exactly 250ms
         tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
 
         // Third call should now succeed
-        let third = limiter.try_acquire("TEST/USD").await;
+        let third = limiter.try_acquire("TEST/USD").await.is_ok();
         assert!(third, "Call after 250ms should be allowed");
 
         // Fourth immediate call should fail again
-        let fourth = limiter.try_acquire("TEST/USD").await;
+        let fourth = limiter.try_acquire("TEST/USD").await.is_ok();
         assert!(!fourth, "Immediate call after third should be rate limited");
     }
 
@@ -303,56 +651,432 @@ mod execution_utils_tests {
     async fn test_rate_limiter_slightly_before_interval() {
         let limiter = RateLimiter::new(250); // 250ms interval
 
-        let first = limiter.try_acquire("TIMING/USD").await;
+        let first = limiter.try_acquire("TIMING/USD").await.is_ok();
         assert!(first);
 
         // Wait 240ms (slightly less than 250ms)
         tokio::time::sleep(tokio::time::Duration::from_millis(240)).await;
 
-        let second = limiter.try_acquire("TIMING/USD").await;
+        let second = limiter.try_acquire("TIMING/USD").await.is_ok();
         assert!(!second, "Call at 240ms should still be rate limited");
 
         // Wait remaining 15ms (total 255ms)
         tokio::time::sleep(tokio::time::Duration::from_millis(15)).await;
 
-        let third = limiter.try_acquire("TIMING/USD").await;
+        let third = limiter.try_acquire("TIMING/USD").await.is_ok();
         assert!(third, "Call at 255ms should be allowed");
     }
 
+    #[test]
+    fn test_rate_limiter_per_symbol_minute_cap() {
+        let limiter = RateLimiter::new(0).with_order_caps(Some(2), None);
+        let t0 = std::time::Instant::now();
+        let at = |secs| t0 + std::time::Duration::from_secs(secs);
+
+        assert!(limiter.try_acquire_at("BTC/USD", at(0)).is_ok());
+        assert!(limiter.try_acquire_at("BTC/USD", at(10)).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at("BTC/USD", at(20)),
+            Err(Throttle::SymbolPerMinute(2))
+        );
+        // Other symbols have their own window
+        assert!(limiter.try_acquire_at("ETH/USD", at(20)).is_ok());
+        // The first order leaves the window a minute later; refusals are not counted
+        assert!(limiter.try_acquire_at("BTC/USD", at(60)).is_ok());
+        assert!(limiter.try_acquire_at("BTC/USD", at(69)).is_err());
+        assert!(limiter.try_acquire_at("BTC/USD", at(70)).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_global_hour_cap() {
+        let limiter = RateLimiter::new(1000).with_order_caps(None, Some(3));
+        let t0 = std::time::Instant::now();
+        let at = |secs| t0 + std::time::Duration::from_secs(secs);
+
+        assert!(limiter.try_acquire_at("BTC/USD", at(0)).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at("BTC/USD", at(0)),
+            Err(Throttle::Cooldown)
+        );
+        assert!(limiter.try_acquire_at("ETH/USD", at(5)).is_ok());
+        assert!(limiter.try_acquire_at("SOL/USD", at(10)).is_ok());
+        let throttle = limiter.try_acquire_at("XRP/USD", at(1800)).unwrap_err();
+        assert_eq!(throttle, Throttle::GlobalPerHour(3));
+        assert_eq!(throttle.to_string(), "3 orders/hour overall");
+        assert!(limiter.try_acquire_at("XRP/USD", at(3600)).is_ok());
+    }
+
     // ============= OrderSizing Struct Tests =============
 
     #[test]
     fn test_order_sizing_struct() {
         let sizing = OrderSizing {
-            qty: 10.0,
-            notional: 1000.0,
-            limit_price: 100.0,
+            qty: dec(10.0),
+            notional: dec(1000.0),
+            limit_price: dec(100.0),
         };
-        assert_eq!(sizing.qty, 10.0);
-        assert_eq!(sizing.notional, 1000.0);
-        assert_eq!(sizing.limit_price, 100.0);
+        assert_eq!(sizing.qty, dec(10.0));
+        assert_eq!(sizing.notional, dec(1000.0));
+        assert_eq!(sizing.limit_price, dec(100.0));
     }
 
     #[test]
     fn test_order_sizing_clone() {
         let sizing = OrderSizing {
-            qty: 5.0,
-            notional: 500.0,
-            limit_price: 100.0,
+            qty: dec(5.0),
+            notional: dec(500.0),
+            limit_price: dec(100.0),
         };
         let cloned = sizing.clone();
-        assert_eq!(cloned.qty, 5.0);
+        assert_eq!(cloned.qty, dec(5.0));
     }
 
     #[test]
     fn test_order_sizing_debug() {
         let sizing = OrderSizing {
-            qty: 1.0,
-            notional: 100.0,
-            limit_price: 100.0,
+            qty: dec(1.0),
+            notional: dec(100.0),
+            limit_price: dec(100.0),
         };
         let debug = format!("{:?}", sizing);
         assert!(debug.contains("OrderSizing"));
         assert!(debug.contains("qty"));
     }
+
+    // ============= Sizing Strategy Tests =============
+
+    use crate::config::AppConfig;
+    use crate::services::reporting::WinLoss;
+
+    fn sizing_config(sizing_yaml: &str) -> AppConfig {
+        let yaml = format!(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+sizing:
+{}
+"#,
+            sizing_yaml
+        );
+        AppConfig::parse(&yaml).unwrap()
+    }
+
+    fn record(wins: u64, losses: u64, total_profit: f64, total_loss: f64) -> WinLoss {
+        WinLoss {
+            wins,
+            losses,
+            total_profit: dec(total_profit),
+            total_loss: dec(total_loss),
+        }
+    }
+
+    #[test]
+    fn test_parse_sizing_methods() {
+        assert_eq!(SizingMethod::parse("ATR"), Some(SizingMethod::Atr));
+        assert_eq!(
+            SizingMethod::parse("fixed_notional"),
+            Some(SizingMethod::FixedNotional)
+        );
+        assert_eq!(SizingMethod::parse("martingale"), None);
+    }
+
+    #[test]
+    fn test_default_is_balance_pct() {
+        let config = sizing_config("  method: \"balance_pct\"");
+        let strategy =
+            SizingStrategy::from_config(&config, dec(1.0), Some(5.0), &record(50, 10, 500.0, 50.0));
+        assert_eq!(
+            strategy,
+            SizingStrategy::BalancePct(dec(config.micro_trade.target_balance_pct))
+        );
+        // Unknown methods fall back too
+        let config = sizing_config("  method: \"martingale\"");
+        assert!(matches!(
+            SizingStrategy::from_config(&config, dec(1.0), None, &WinLoss::default()),
+            SizingStrategy::BalancePct(_)
+        ));
+    }
+
+    #[test]
+    fn test_atr_risk_sizes_to_stop_distance() {
+        let config =
+            sizing_config("  method: \"atr\"\n  risk_per_trade_pct: 1.0\n  atr_stop_multiple: 2.0");
+        let strategy =
+            SizingStrategy::from_config(&config, dec(1.0), Some(2.5), &WinLoss::default());
+        assert_eq!(
+            strategy,
+            SizingStrategy::AtrRisk {
+                risk_pct: dec(0.01),
+                stop_distance: dec(5.0),
+            }
+        );
+        // $10k buying power risks $100; a $5 stop allows 20 units at $100
+        assert_eq!(
+            strategy.target_notional(dec(100.0), dec(10000.0)),
+            Some(dec(2000.0))
+        );
+        // Wider stops mean smaller positions
+        let wide = SizingStrategy::from_config(&config, dec(1.0), Some(10.0), &WinLoss::default());
+        assert_eq!(
+            wide.target_notional(dec(100.0), dec(10000.0)),
+            Some(dec(500.0))
+        );
+    }
+
+    #[test]
+    fn test_atr_falls_back_without_bars() {
+        let config = sizing_config("  method: \"atr\"");
+        assert!(matches!(
+            SizingStrategy::from_config(&config, dec(1.0), None, &WinLoss::default()),
+            SizingStrategy::BalancePct(_)
+        ));
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        // 60% winners, wins twice the size of losses: 0.6 - 0.4 / 2 = 0.4
+        let f = kelly_fraction(&record(6, 4, 120.0, 40.0)).unwrap();
+        assert!((f - 0.4).abs() < 1e-9);
+        // 40% winners at 1:1 has no edge
+        assert!(kelly_fraction(&record(4, 6, 40.0, 60.0)).unwrap() < 0.0);
+        assert!(kelly_fraction(&record(5, 0, 50.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_kelly_strategy_is_fractional_and_capped() {
+        let config = sizing_config(
+            "  method: \"kelly\"\n  kelly_fraction: 0.5\n  kelly_max_pct: 10.0\n  kelly_min_trades: 10",
+        );
+        // Half of 0.4 = 20%, capped at 10%
+        let strategy =
+            SizingStrategy::from_config(&config, dec(1.0), None, &record(6, 4, 120.0, 40.0));
+        assert_eq!(strategy, SizingStrategy::Kelly(dec(0.1)));
+
+        // Not enough closed trades yet
+        assert!(matches!(
+            SizingStrategy::from_config(&config, dec(1.0), None, &record(3, 2, 60.0, 20.0)),
+            SizingStrategy::BalancePct(_)
+        ));
+
+        // No edge: no entry
+        let none = SizingStrategy::from_config(&config, dec(1.0), None, &record(4, 6, 40.0, 60.0));
+        assert_eq!(none, SizingStrategy::Kelly(Decimal::ZERO));
+        assert!(
+            compute_order_sizing(dec(100.0), dec(1000.0), dec(10.0), dec(100.0), &none, None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_fixed_notional_in_quote_currency() {
+        let config = sizing_config("  method: \"fixed_notional\"\n  fixed_notional: 50.0");
+        // 2 reporting units per quote unit
+        let strategy = SizingStrategy::from_config(&config, dec(2.0), None, &WinLoss::default());
+        assert_eq!(strategy, SizingStrategy::FixedNotional(dec(25.0)));
+        let sizing =
+            compute_order_sizing(dec(5.0), dec(1000.0), dec(5.0), dec(50.0), &strategy, None)
+                .unwrap();
+        assert_eq!(sizing.notional, dec(25.0));
+        assert_eq!(sizing.qty, dec(5.0));
+    }
+
+    #[test]
+    fn test_scaled_shrinks_each_strategy() {
+        let half = dec(0.5);
+        assert_eq!(
+            SizingStrategy::BalancePct(dec(0.04)).scaled(half),
+            SizingStrategy::BalancePct(dec(0.02))
+        );
+        assert_eq!(
+            SizingStrategy::FixedNotional(dec(50.0)).scaled(half),
+            SizingStrategy::FixedNotional(dec(25.0))
+        );
+        assert_eq!(
+            SizingStrategy::AtrRisk {
+                risk_pct: dec(0.01),
+                stop_distance: dec(5.0)
+            }
+            .scaled(half),
+            SizingStrategy::AtrRisk {
+                risk_pct: dec(0.005),
+                stop_distance: dec(5.0)
+            }
+        );
+    }
+
+    // ============= Depth Cap Tests =============
+
+    #[test]
+    fn test_liquidity_within_bps() {
+        let asks = [(100.05, 2.0), (100.2, 5.0), (100.6, 10.0)];
+        // 100.6 is 55 bps above mid
+        assert_eq!(liquidity_within_bps(&asks, 100.05, 25.0), 7.0);
+        assert_eq!(liquidity_within_bps(&asks, 100.05, 1.0), 2.0);
+        assert_eq!(liquidity_within_bps(&asks, 0.0, 25.0), 0.0);
+    }
+
+    #[test]
+    fn test_depth_cap_from_top_of_book() {
+        let config = sizing_config("  depth_cap_fraction: 0.2\n  depth_window_bps: 25.0");
+        assert_eq!(
+            depth_cap(&config, 0.0999, 0.1001, 50_000.0),
+            Some(dec(10_000.0))
+        );
+        // Ask outside the window: nothing visible to take
+        assert_eq!(
+            depth_cap(&config, 0.099, 0.101, 50_000.0),
+            Some(Decimal::ZERO)
+        );
+        // No sizes from the venue, or the cap is off
+        assert_eq!(depth_cap(&config, 0.0999, 0.1001, 0.0), None);
+        let off = sizing_config("  method: \"balance_pct\"");
+        assert_eq!(depth_cap(&off, 0.0999, 0.1001, 50_000.0), None);
+    }
+
+    #[test]
+    fn test_compute_order_sizing_caps_to_depth() {
+        let strategy = SizingStrategy::BalancePct(dec(0.05));
+        // $100 wanted at $0.10, only 400 units may be taken
+        let sizing = compute_order_sizing(
+            dec(0.1),
+            dec(10000.0),
+            dec(10.0),
+            dec(100.0),
+            &strategy,
+            Some(dec(400.0)),
+        )
+        .unwrap();
+        assert_eq!(sizing.qty, dec(400.0));
+        assert_eq!(sizing.notional, dec(40.0));
+
+        // A cap above the target changes nothing
+        let sizing = compute_order_sizing(
+            dec(0.1),
+            dec(10000.0),
+            dec(10.0),
+            dec(100.0),
+            &strategy,
+            Some(dec(5000.0)),
+        )
+        .unwrap();
+        assert_eq!(sizing.qty, dec(1000.0));
+
+        // Too thin for the minimum order: no entry
+        assert!(compute_order_sizing(
+            dec(0.1),
+            dec(10000.0),
+            dec(10.0),
+            dec(100.0),
+            &strategy,
+            Some(dec(50.0)),
+        )
+        .is_none());
+    }
+
+    // ============= Account Diff Tests =============
+
+    use crate::events::AccountEvent;
+    use crate::exchange::types::AccountSummary;
+
+    fn account(cash: Decimal, bp: Decimal) -> AccountSummary {
+        AccountSummary {
+            buying_power: Some(bp),
+            cash: Some(cash),
+            portfolio_value: Some(cash),
+        }
+    }
+
+    #[test]
+    fn test_diff_account_first_snapshot() {
+        let events = diff_account(None, &account(dec(100.0), dec(100.0)));
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], AccountEvent::BalanceUpdated { .. }));
+    }
+
+    #[test]
+    fn test_diff_account_unchanged() {
+        let a = account(dec(100.0), dec(100.0));
+        assert!(diff_account(Some(&a), &a).is_empty());
+    }
+
+    #[test]
+    fn test_diff_account_cash_change_is_a_balance_update() {
+        let events = diff_account(
+            Some(&account(dec(100.0), dec(100.0))),
+            &account(dec(150.0), dec(150.0)),
+        );
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            AccountEvent::BalanceUpdated { cash: Some(cash), .. } if cash == dec(150.0)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exit_fill_between_refreshes_is_not_a_transfer() {
+        // An exchange-side exit filled since the last refresh; nobody invalidated
+        let exchange = MockExchange::new("venue").with_account(account(dec(1050.0), dec(1050.0)));
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let cache = AccountCache::new(Arc::new(exchange), 0).with_event_bus(bus);
+        cache
+            .apply_event(&AccountEvent::BalanceUpdated {
+                cash: Some(dec(1000.0)),
+                buying_power: Some(dec(1000.0)),
+                portfolio_value: Some(dec(1000.0)),
+                timestamp: "2026-03-02T10:00:00Z".to_string(),
+            })
+            .await;
+
+        assert_eq!(cache.buying_power().await, dec(1050.0));
+        let mut events = Vec::new();
+        while let Ok(Event::Account(event)) = rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], AccountEvent::BalanceUpdated { .. }));
+    }
+
+    #[test]
+    fn test_diff_account_margin_changed() {
+        let events = diff_account(
+            Some(&account(dec(100.0), dec(200.0))),
+            &account(dec(100.0), dec(150.0)),
+        );
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, AccountEvent::MarginChanged { buying_power, .. } if *buying_power == dec(150.0))));
+    }
 }
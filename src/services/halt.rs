@@ -0,0 +1,193 @@
+//! Halt / delisting detection.
+//!
+//! A symbol is moved to exit-only mode when the venue announces a halt (Alpaca
+//! trading-status stream) or when entry orders keep getting rejected, either
+//! with a message that names a halt/delisting or `max_consecutive_rejections`
+//! times in a row. Transitions are published as `Event::SymbolStatus`; risk and
+//! strategy stop producing entries for the symbol and the position monitor
+//! closes any open position.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, HaltDetectionConfig};
+use crate::events::{Event, SymbolStatusEvent};
+
+/// Rejection text (lowercased) that means the market itself is gone or closed.
+const HALT_MARKERS: &[&str] = &[
+    "halt",
+    "delist",
+    "not tradable",
+    "not active",
+    "inactive",
+    "suspended",
+    "trading_disabled",
+    "trading disabled",
+    "cancel_only",
+    "cancel only",
+    "market is closed",
+    "invalid symbol",
+    "unknown asset pair",
+];
+
+/// The marker matched by an order rejection message, if it indicates a halt/delisting.
+pub fn classify_rejection(message: &str) -> Option<&'static str> {
+    let lower = message.to_lowercase();
+    HALT_MARKERS.iter().copied().find(|m| lower.contains(m))
+}
+
+/// Server errors, timeouts and transport failures say nothing about the symbol
+/// and must not count towards `max_consecutive_rejections`.
+pub fn looks_like_outage(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    [
+        "(500",
+        "(502",
+        "(503",
+        "(504",
+        "timed out",
+        "timeout",
+        "connection",
+        "error sending request",
+    ]
+    .iter()
+    .any(|m| lower.contains(m))
+}
+
+#[derive(Debug, Default)]
+struct SymbolHaltState {
+    consecutive_rejections: u32,
+    exit_only: bool,
+}
+
+/// Shared per-symbol halt state, fed by order results and venue notices.
+#[derive(Clone)]
+pub struct HaltDetector {
+    config: HaltDetectionConfig,
+    event_bus: EventBus,
+    state: Arc<Mutex<HashMap<String, SymbolHaltState>>>,
+}
+
+impl HaltDetector {
+    pub fn new(event_bus: EventBus, config: &AppConfig) -> Self {
+        Self {
+            config: config.halt_detection.clone(),
+            event_bus,
+            state: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn is_exit_only(&self, symbol: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .get(symbol)
+            .is_some_and(|s| s.exit_only)
+    }
+
+    /// An entry was accepted by the venue.
+    pub fn record_accepted(&self, symbol: &str) {
+        if let Some(s) = self.state.lock().unwrap().get_mut(symbol) {
+            s.consecutive_rejections = 0;
+        }
+    }
+
+    /// An entry was rejected. Returns true if the rejection names a
+    /// halt/delisting (a symbol problem, not a venue outage).
+    pub fn record_rejection(&self, symbol: &str, message: &str) -> bool {
+        let marker = classify_rejection(message);
+        if marker.is_none() && looks_like_outage(message) {
+            return false;
+        }
+        let reason = {
+            let mut state = self.state.lock().unwrap();
+            let s = state.entry(symbol.to_string()).or_default();
+            s.consecutive_rejections += 1;
+            if !self.config.enabled || s.exit_only {
+                return marker.is_some();
+            }
+            match marker {
+                Some(m) => format!("entry rejected ({}): {}", m, message),
+                None if s.consecutive_rejections
+                    >= self.config.max_consecutive_rejections.max(1) =>
+                {
+                    format!(
+                        "{} consecutive entry rejections (last: {})",
+                        s.consecutive_rejections, message
+                    )
+                }
+                None => return false,
+            }
+        };
+        self.transition(symbol, true, &reason, "rejections");
+        self.event_bus
+            .publish(Event::SymbolStatus(SymbolStatusEvent {
+                symbol: symbol.to_string(),
+                exit_only: true,
+                reason,
+                source: "rejections".to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            }))
+            .ok();
+        marker.is_some()
+    }
+
+    /// Apply a venue status notice already published on the bus.
+    pub fn apply_venue_status(&self, symbol: &str, exit_only: bool, reason: &str) {
+        if self.config.enabled && self.is_exit_only(symbol) != exit_only {
+            self.transition(symbol, exit_only, reason, "venue");
+        }
+    }
+
+    fn transition(&self, symbol: &str, exit_only: bool, reason: &str, source: &str) {
+        {
+            let mut state = self.state.lock().unwrap();
+            let s = state.entry(symbol.to_string()).or_default();
+            s.exit_only = exit_only;
+            s.consecutive_rejections = 0;
+        }
+        if exit_only {
+            error!(
+                "🚫 [HALT] {} moved to EXIT-ONLY ({}): {}",
+                symbol, source, reason
+            );
+        } else {
+            info!(
+                "✅ [HALT] {} re-enabled for entries ({}): {}",
+                symbol, source, reason
+            );
+        }
+    }
+
+    /// Follow venue status notices published on the bus (e.g. by the WS stream).
+    pub async fn start(&self) {
+        let mut rx = self.event_bus.subscribe("halt");
+        let detector = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if let Event::SymbolStatus(status) = event {
+                    if status.source == "venue" {
+                        detector.apply_venue_status(
+                            &status.symbol,
+                            status.exit_only,
+                            &status.reason,
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,186 @@
+//! Unit tests for halt/delisting detection - rejection classification and exit-only mode.
+
+#[cfg(test)]
+mod halt_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, WatchdogConfig};
+    use crate::error::AutoHedgeError;
+    use crate::events::Event;
+    use crate::exchange::mock::MockExchange;
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::halt::*;
+    use crate::services::watchdog::{ExchangeWatchdog, WatchedExchange};
+    use std::sync::Arc;
+
+    fn test_config(max_rejections: u32) -> AppConfig {
+        let yaml = format!(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+halt_detection:
+  enabled: true
+  max_consecutive_rejections: {}
+"#,
+            max_rejections
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    /// Venue that rejects every order with a fixed message.
+    fn rejecting(message: &'static str) -> Arc<MockExchange> {
+        let exchange = MockExchange::new("rejecting")
+            .with_capabilities(|c| {
+                c.supports_notional_market_buy = true;
+                c.supports_ws_quotes = true;
+                c.supports_ws_trades = true;
+            })
+            .on_submit(move |_, _| Err(AutoHedgeError::http(403, message)));
+        Arc::new(exchange)
+    }
+
+    fn buy() -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            symbol: "BTC/USD".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            qty: None,
+            notional: Some(dec(50.0)),
+            limit_price: None,
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Classification Tests =============
+
+    #[test]
+    fn test_classify_rejection() {
+        assert_eq!(
+            classify_rejection("Alpaca order failed (403): asset BTC/USD is not tradable"),
+            Some("not tradable")
+        );
+        assert_eq!(
+            classify_rejection("Kraken error: EOrder:Trading halted"),
+            Some("halt")
+        );
+        assert_eq!(classify_rejection("insufficient balance"), None);
+    }
+
+    #[test]
+    fn test_outages_are_not_symbol_problems() {
+        assert!(looks_like_outage("Alpaca order failed (503): unavailable"));
+        assert!(looks_like_outage("error sending request for url"));
+        assert!(!looks_like_outage(
+            "Alpaca order failed (422): qty too small"
+        ));
+    }
+
+    // ============= Detector Tests =============
+
+    #[tokio::test]
+    async fn test_halt_message_moves_symbol_to_exit_only() {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let detector = HaltDetector::new(bus.clone(), &test_config(3));
+
+        assert!(detector.record_rejection("BTC/USD", "symbol is halted"));
+        assert!(detector.is_exit_only("BTC/USD"));
+        assert!(!detector.is_exit_only("ETH/USD"));
+
+        match rx.recv().await.unwrap() {
+            Event::SymbolStatus(status) => {
+                assert_eq!(status.symbol, "BTC/USD");
+                assert!(status.exit_only);
+                assert_eq!(status.source, "rejections");
+            }
+            _ => panic!("expected SymbolStatus event"),
+        }
+    }
+
+    #[test]
+    fn test_consecutive_rejections_threshold() {
+        let detector = HaltDetector::new(EventBus::new(16), &test_config(3));
+
+        detector.record_rejection("BTC/USD", "qty too small");
+        detector.record_rejection("BTC/USD", "qty too small");
+        detector.record_accepted("BTC/USD");
+        detector.record_rejection("BTC/USD", "qty too small");
+        detector.record_rejection("BTC/USD", "qty too small");
+        assert!(!detector.is_exit_only("BTC/USD"));
+
+        // Outages do not count towards the threshold
+        detector.record_rejection("BTC/USD", "request timed out");
+        assert!(!detector.is_exit_only("BTC/USD"));
+
+        detector.record_rejection("BTC/USD", "qty too small");
+        assert!(detector.is_exit_only("BTC/USD"));
+    }
+
+    #[test]
+    fn test_venue_status_toggles_exit_only() {
+        let detector = HaltDetector::new(EventBus::new(16), &test_config(3));
+
+        detector.apply_venue_status("BTC/USD", true, "Trading halt");
+        assert!(detector.is_exit_only("BTC/USD"));
+        detector.apply_venue_status("BTC/USD", false, "Trading resumed");
+        assert!(!detector.is_exit_only("BTC/USD"));
+    }
+
+    // ============= WatchedExchange Tests =============
+
+    #[tokio::test]
+    async fn test_exit_only_symbol_entries_refused_without_safe_mode() {
+        let inner = rejecting("Alpaca order failed (403): asset is not active");
+        let watchdog = ExchangeWatchdog::new(WatchdogConfig {
+            failure_threshold: 1,
+            ..WatchdogConfig::default()
+        });
+        let detector = HaltDetector::new(EventBus::new(16), &test_config(3));
+        let watched = WatchedExchange::new(inner.clone(), watchdog.clone())
+            .with_halt_detector(detector.clone());
+
+        assert!(watched.submit_order(buy()).await.is_err());
+        assert!(detector.is_exit_only("BTC/USD"));
+        // A symbol rejection is an answer from the venue, not an outage
+        assert!(!watchdog.is_safe_mode());
+
+        assert!(watched.submit_order(buy()).await.is_err());
+        assert_eq!(inner.submitted().len(), 1);
+    }
+}
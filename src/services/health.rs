@@ -0,0 +1,301 @@
+//! Composite health score and degradation ladder.
+//!
+//! WS staleness, REST error rate (from the exchange watchdog), LLM latency and
+//! position reconciliation mismatches are each scored 0-1 and combined into a
+//! 0-100 score. The score maps onto a fixed ladder - full trading, reduced
+//! size, exit-only, halted - so the system degrades one predictable step at a
+//! time. Transitions are published as `Event::Health` and the latest snapshot
+//! is served by `/health`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, HealthConfig};
+use crate::events::{DegradationLevel, Event, HealthEvent};
+use crate::llm::LLMQueue;
+use crate::services::position_monitor::PositionTracker;
+use crate::services::watchdog::ExchangeWatchdog;
+
+const WS_WEIGHT: f64 = 0.35;
+const REST_WEIGHT: f64 = 0.35;
+const LLM_WEIGHT: f64 = 0.15;
+const RECONCILIATION_WEIGHT: f64 = 0.15;
+
+/// Raw measurements the score is computed from.
+#[derive(Clone, Debug, Default)]
+pub struct HealthInputs {
+    pub ws_staleness_secs: f64,
+    pub rest_error_rate: f64,
+    /// None until the first LLM call completes
+    pub llm_latency_ms: Option<f64>,
+    pub reconciliation_mismatches: usize,
+}
+
+/// Per-component scores (1.0 = healthy, 0.0 = unusable).
+pub fn component_scores(inputs: &HealthInputs, config: &HealthConfig) -> [(&'static str, f64); 4] {
+    let stale = config.ws_stale_secs.max(1) as f64;
+    let ws = 1.0 - ((inputs.ws_staleness_secs - stale) / (3.0 * stale)).clamp(0.0, 1.0);
+
+    let rest =
+        1.0 - (inputs.rest_error_rate / config.max_rest_error_rate.max(0.01)).clamp(0.0, 1.0);
+
+    let llm = match inputs.llm_latency_ms {
+        Some(ms) => {
+            let limit = config.llm_latency_ms.max(1.0);
+            1.0 - ((ms - limit) / (2.0 * limit)).clamp(0.0, 1.0)
+        }
+        None => 1.0,
+    };
+
+    let reconciliation = 1.0
+        - (inputs.reconciliation_mismatches as f64 / config.max_mismatches.max(1) as f64)
+            .clamp(0.0, 1.0);
+
+    [
+        ("ws", ws),
+        ("rest", rest),
+        ("llm", llm),
+        ("reconciliation", reconciliation),
+    ]
+}
+
+/// Weighted composite score, 0-100.
+pub fn composite_score(inputs: &HealthInputs, config: &HealthConfig) -> f64 {
+    let [ws, rest, llm, reconciliation] = component_scores(inputs, config);
+    100.0
+        * (WS_WEIGHT * ws.1
+            + REST_WEIGHT * rest.1
+            + LLM_WEIGHT * llm.1
+            + RECONCILIATION_WEIGHT * reconciliation.1)
+}
+
+fn level_below(score: f64, config: &HealthConfig) -> DegradationLevel {
+    if score < config.halted_below {
+        DegradationLevel::Halted
+    } else if score < config.exit_only_below {
+        DegradationLevel::ExitOnly
+    } else if score < config.reduced_below {
+        DegradationLevel::Reduced
+    } else {
+        DegradationLevel::Full
+    }
+}
+
+/// Next level given the score. Degrades immediately; steps back up only once the
+/// score clears the threshold by `recovery_margin`.
+pub fn level_for_score(
+    score: f64,
+    current: DegradationLevel,
+    config: &HealthConfig,
+) -> DegradationLevel {
+    let raw = level_below(score, config);
+    if raw >= current {
+        return raw;
+    }
+    level_below(score - config.recovery_margin, config).min(current)
+}
+
+/// Entry size multiplier for a level.
+pub fn size_factor(level: DegradationLevel, config: &HealthConfig) -> f64 {
+    match level {
+        DegradationLevel::Full => 1.0,
+        DegradationLevel::Reduced => config.reduced_size_factor.clamp(0.0, 1.0),
+        DegradationLevel::ExitOnly | DegradationLevel::Halted => 0.0,
+    }
+}
+
+/// Shrink entry sizing limits for an order handled while in the reduced level.
+pub fn scale_entry_sizing(config: &mut AppConfig, factor: f64) {
+    if factor < 1.0 {
+        config.defaults.max_order_amount *= factor;
+        config.micro_trade.target_balance_pct *= factor;
+        config.sizing.risk_per_trade_pct *= factor;
+        config.sizing.kelly_fraction *= factor;
+        config.sizing.fixed_notional *= factor;
+    }
+}
+
+/// Latest evaluation, as served by `/health`.
+#[derive(Clone, Debug)]
+pub struct HealthSnapshot {
+    pub score: f64,
+    pub level: DegradationLevel,
+    pub inputs: HealthInputs,
+    pub updated_at: String,
+}
+
+struct HealthState {
+    last_market_data: Instant,
+    snapshot: HealthSnapshot,
+}
+
+/// Periodically scores system health and walks the degradation ladder.
+#[derive(Clone)]
+pub struct HealthMonitor {
+    config: HealthConfig,
+    event_bus: EventBus,
+    watchdog: ExchangeWatchdog,
+    llm: LLMQueue,
+    tracker: PositionTracker,
+    state: Arc<Mutex<HealthState>>,
+}
+
+impl HealthMonitor {
+    pub fn new(
+        event_bus: EventBus,
+        config: &AppConfig,
+        watchdog: ExchangeWatchdog,
+        llm: LLMQueue,
+        tracker: PositionTracker,
+    ) -> Self {
+        Self {
+            config: config.health.clone(),
+            event_bus,
+            watchdog,
+            llm,
+            tracker,
+            state: Arc::new(Mutex::new(HealthState {
+                last_market_data: Instant::now(),
+                snapshot: HealthSnapshot {
+                    score: 100.0,
+                    level: DegradationLevel::Full,
+                    inputs: HealthInputs::default(),
+                    updated_at: chrono::Utc::now().to_rfc3339(),
+                },
+            })),
+        }
+    }
+
+    pub fn snapshot(&self) -> HealthSnapshot {
+        self.state.lock().unwrap().snapshot.clone()
+    }
+
+    pub fn record_market_data(&self) {
+        self.state.lock().unwrap().last_market_data = Instant::now();
+    }
+
+    fn inputs(&self) -> HealthInputs {
+        let window = Duration::from_secs(self.config.window_secs.max(1));
+        let rest_error_rate = if self.watchdog.is_safe_mode() {
+            1.0
+        } else {
+            self.watchdog.error_rate(window)
+        };
+        HealthInputs {
+            ws_staleness_secs: self
+                .state
+                .lock()
+                .unwrap()
+                .last_market_data
+                .elapsed()
+                .as_secs_f64(),
+            rest_error_rate,
+            llm_latency_ms: self.llm.latency_ms(),
+            reconciliation_mismatches: self.tracker.recent_mismatches(window),
+        }
+    }
+
+    /// Recompute the score. Returns the transition event if the level changed.
+    pub fn evaluate(&self) -> Option<HealthEvent> {
+        let inputs = self.inputs();
+        let score = composite_score(&inputs, &self.config);
+
+        let mut state = self.state.lock().unwrap();
+        let previous = state.snapshot.level;
+        let level = if self.config.enabled {
+            level_for_score(score, previous, &self.config)
+        } else {
+            DegradationLevel::Full
+        };
+        let reason = describe(&inputs, &self.config);
+        state.snapshot = HealthSnapshot {
+            score,
+            level,
+            inputs,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+        drop(state);
+
+        if level == previous {
+            return None;
+        }
+        if level > previous {
+            warn!(
+                "📉 [HEALTH] Degraded {} -> {} (score {:.0}): {}",
+                previous.as_str(),
+                level.as_str(),
+                score,
+                reason
+            );
+        } else {
+            info!(
+                "📈 [HEALTH] Recovered {} -> {} (score {:.0})",
+                previous.as_str(),
+                level.as_str(),
+                score
+            );
+        }
+        Some(HealthEvent {
+            level,
+            previous,
+            score,
+            size_factor: size_factor(level, &self.config),
+            reason,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Track market data arrival and evaluate every `eval_interval_secs`.
+    pub async fn start(&self) {
+        let mut rx = self.event_bus.subscribe("health");
+        let monitor = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+        let interval = Duration::from_secs(self.config.eval_interval_secs.max(1));
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = ticker.tick() => {
+                        if let Some(event) = monitor.evaluate() {
+                            monitor.event_bus.publish(Event::Health(event)).ok();
+                        }
+                    }
+                    event = rx.recv() => match event {
+                        Ok(Event::Market(_)) => monitor.record_market_data(),
+                        Ok(_) => {}
+                        // Lagging behind a busy feed still means data is flowing
+                        Err(RecvError::Lagged(_)) => monitor.record_market_data(),
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+}
+
+/// Unhealthy components with their raw values, e.g. "ws 0.40 (75s stale)".
+fn describe(inputs: &HealthInputs, config: &HealthConfig) -> String {
+    let parts: Vec<String> = component_scores(inputs, config)
+        .iter()
+        .filter(|(_, s)| *s < 1.0)
+        .map(|(name, s)| {
+            let detail = match *name {
+                "ws" => format!("{:.0}s stale", inputs.ws_staleness_secs),
+                "rest" => format!("{:.0}% errors", inputs.rest_error_rate * 100.0),
+                "llm" => format!("{:.0}ms", inputs.llm_latency_ms.unwrap_or_default()),
+                _ => format!("{} mismatches", inputs.reconciliation_mismatches),
+            };
+            format!("{} {:.2} ({})", name, s, detail)
+        })
+        .collect();
+    if parts.is_empty() {
+        "all components healthy".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
@@ -0,0 +1,210 @@
+//! Unit tests for the composite health score and degradation ladder.
+
+#[cfg(test)]
+mod health_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, HealthConfig, LlmRetryConfig, WatchdogConfig};
+    use crate::events::DegradationLevel;
+    use crate::llm::{LLMClient, LLMQueue};
+    use crate::services::health::*;
+    use crate::services::position_monitor::PositionTracker;
+    use crate::services::watchdog::ExchangeWatchdog;
+
+    fn healthy() -> HealthInputs {
+        HealthInputs {
+            ws_staleness_secs: 1.0,
+            rest_error_rate: 0.0,
+            llm_latency_ms: Some(2000.0),
+            reconciliation_mismatches: 0,
+        }
+    }
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    // ============= Score Tests =============
+
+    #[test]
+    fn test_healthy_inputs_score_full() {
+        let config = HealthConfig::default();
+        assert!((composite_score(&healthy(), &config) - 100.0).abs() < 1e-9);
+        assert!((composite_score(&HealthInputs::default(), &config) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_components_degrade_linearly() {
+        let config = HealthConfig::default();
+        let inputs = HealthInputs {
+            ws_staleness_secs: 60.0,   // 30s past the 30s limit, zero at 120s
+            rest_error_rate: 0.25,     // half of max_rest_error_rate
+            llm_latency_ms: Some(1e9), // far past 3x the limit
+            reconciliation_mismatches: 10,
+        };
+        let scores = component_scores(&inputs, &config);
+        assert!((scores[0].1 - 2.0 / 3.0).abs() < 1e-9);
+        assert!((scores[1].1 - 0.5).abs() < 1e-9);
+        assert_eq!(scores[2].1, 0.0);
+        assert_eq!(scores[3].1, 0.0);
+    }
+
+    #[test]
+    fn test_total_outage_scores_zero() {
+        let inputs = HealthInputs {
+            ws_staleness_secs: 1000.0,
+            rest_error_rate: 1.0,
+            llm_latency_ms: Some(1e9),
+            reconciliation_mismatches: 100,
+        };
+        assert_eq!(composite_score(&inputs, &HealthConfig::default()), 0.0);
+    }
+
+    // ============= Ladder Tests =============
+
+    #[test]
+    fn test_degrades_immediately() {
+        let config = HealthConfig::default();
+        let full = DegradationLevel::Full;
+        assert_eq!(level_for_score(90.0, full, &config), DegradationLevel::Full);
+        assert_eq!(
+            level_for_score(70.0, full, &config),
+            DegradationLevel::Reduced
+        );
+        assert_eq!(
+            level_for_score(40.0, full, &config),
+            DegradationLevel::ExitOnly
+        );
+        assert_eq!(
+            level_for_score(10.0, full, &config),
+            DegradationLevel::Halted
+        );
+    }
+
+    #[test]
+    fn test_recovery_requires_margin() {
+        let config = HealthConfig::default();
+        let reduced = DegradationLevel::Reduced;
+        assert_eq!(
+            level_for_score(82.0, reduced, &config),
+            DegradationLevel::Reduced
+        );
+        assert_eq!(
+            level_for_score(86.0, reduced, &config),
+            DegradationLevel::Full
+        );
+
+        // Recovery from halted can skip steps once the score is clearly back
+        let halted = DegradationLevel::Halted;
+        assert_eq!(
+            level_for_score(27.0, halted, &config),
+            DegradationLevel::Halted
+        );
+        assert_eq!(
+            level_for_score(60.0, halted, &config),
+            DegradationLevel::Reduced
+        );
+    }
+
+    #[test]
+    fn test_size_factor_per_level() {
+        let config = HealthConfig::default();
+        assert_eq!(size_factor(DegradationLevel::Full, &config), 1.0);
+        assert_eq!(size_factor(DegradationLevel::Reduced, &config), 0.5);
+        assert_eq!(size_factor(DegradationLevel::ExitOnly, &config), 0.0);
+        assert!(!DegradationLevel::ExitOnly.allows_entries());
+        assert!(DegradationLevel::Reduced.allows_entries());
+    }
+
+    #[test]
+    fn test_scale_entry_sizing() {
+        let mut config = test_config();
+        let target = config.micro_trade.target_balance_pct;
+        scale_entry_sizing(&mut config, 0.5);
+        assert_eq!(config.defaults.max_order_amount, 50.0);
+        assert_eq!(config.micro_trade.target_balance_pct, target * 0.5);
+        assert_eq!(config.sizing.risk_per_trade_pct, 0.5);
+        assert_eq!(config.sizing.fixed_notional, 25.0);
+    }
+
+    // ============= Monitor Tests =============
+
+    #[tokio::test]
+    async fn test_monitor_publishes_transition_once() {
+        let config = test_config();
+        let watchdog = ExchangeWatchdog::new(WatchdogConfig::default());
+        let tracker = PositionTracker::new();
+        let llm = LLMQueue::new(
+            LLMClient::new(String::new(), None, "test-model".to_string()),
+            1,
+            1,
+            LlmRetryConfig::default(),
+        );
+        let monitor = HealthMonitor::new(
+            EventBus::new(16),
+            &config,
+            watchdog.clone(),
+            llm,
+            tracker.clone(),
+        );
+
+        assert!(monitor.evaluate().is_none());
+
+        // Every REST call failing: REST component drops to zero (score 65)
+        for _ in 0..4 {
+            watchdog.record_failure("get_positions", "503");
+        }
+        let event = monitor.evaluate().expect("level should change");
+        assert_eq!(event.previous, DegradationLevel::Full);
+        assert_eq!(event.level, DegradationLevel::Reduced);
+        assert_eq!(event.size_factor, 0.5);
+        assert_eq!(monitor.snapshot().level, DegradationLevel::Reduced);
+
+        // Positions out of sync with the venue as well (score 50)
+        for _ in 0..5 {
+            tracker.record_mismatch();
+        }
+        assert!(monitor.evaluate().is_none());
+        assert!((monitor.snapshot().score - 50.0).abs() < 1e-6);
+
+        // Same conditions: no repeated event
+        assert!(monitor.evaluate().is_none());
+    }
+}
@@ -0,0 +1,398 @@
+//! Portfolio delta hedging.
+//!
+//! Every `hedging.check_secs` the held positions are weighted by their beta
+//! to `hedging.reference_symbol` into a USD exposure ("delta"). Above
+//! `max_delta_usd` the `Hedger` offsets `hedge_ratio` of it with a market
+//! order in `hedging.instrument`: sold short when the instrument moves with
+//! the reference (a perpetual), bought when it moves against it (an inverse
+//! ETF). The hedge is kept in `PositionTracker` apart from the strategy's
+//! long positions, so the position monitor never manages or sells it, and has
+//! its own exit rules (see `HedgePlanner`). A short hedge needs a venue with
+//! `supports_short`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio::time::Duration;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, HedgingConfig};
+use crate::data::store::MarketStore;
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::{OrderType, PlaceOrderRequest, Side, TimeInForce};
+use crate::money::{dec, float};
+use crate::services::execution_utils::{market_tif, normalize_order};
+use crate::services::position_monitor::{PositionInfo, PositionTracker};
+
+/// An open hedge in the hedge instrument.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HedgePosition {
+    pub symbol: String,
+    pub side: Side,
+    pub qty: Decimal,
+    /// Average entry price
+    pub entry_price: Decimal,
+    pub entry_time: DateTime<Utc>,
+}
+
+impl HedgePosition {
+    /// USD exposure to the reference asset at `price` (negative offsets longs).
+    pub fn delta(&self, price: f64, instrument_beta: f64) -> f64 {
+        let sign = if self.side == Side::Buy { 1.0 } else { -1.0 };
+        sign * float(self.qty) * price * instrument_beta
+    }
+
+    /// Unrealized P&L at `price`, in percent of the entry notional.
+    pub fn pnl_pct(&self, price: Decimal) -> f64 {
+        let move_pct = (price - self.entry_price)
+            .checked_div(self.entry_price)
+            .map_or(0.0, float)
+            * 100.0;
+        if self.side == Side::Buy {
+            move_pct
+        } else {
+            -move_pct
+        }
+    }
+
+    /// Add `qty` bought or sold at `price` on the hedge's side.
+    pub fn add(&mut self, qty: Decimal, price: Decimal) {
+        let total = self.qty + qty;
+        if total > Decimal::ZERO {
+            self.entry_price = (self.entry_price * self.qty + price * qty) / total;
+        }
+        self.qty = total;
+    }
+}
+
+/// What the hedge should do next.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HedgeAction {
+    /// Open (or add to) the hedge
+    Open {
+        side: Side,
+        qty: Decimal,
+    },
+    Close {
+        reason: &'static str,
+    },
+}
+
+/// Beta-weighted USD exposure of `positions` to the reference asset, marked
+/// at `price` (entry price when there is no quote). The hedge instrument
+/// itself is left out.
+pub fn portfolio_delta(
+    positions: &[PositionInfo],
+    price: impl Fn(&str) -> Option<f64>,
+    config: &HedgingConfig,
+) -> f64 {
+    positions
+        .iter()
+        .filter(|p| p.symbol != config.instrument)
+        .map(|p| {
+            let mark = price(&p.symbol)
+                .filter(|px| *px > 0.0)
+                .unwrap_or_else(|| float(p.entry_price));
+            float(p.filled_qty) * mark * config.beta(&p.symbol)
+        })
+        .sum()
+}
+
+/// Hedge decisions. After a hedge is stopped out, no new one is opened until
+/// the exposure has been back below `close_below_usd`, so a trending market
+/// does not stop out hedge after hedge.
+pub struct HedgePlanner {
+    config: HedgingConfig,
+    armed: bool,
+}
+
+impl HedgePlanner {
+    pub fn new(config: &HedgingConfig) -> Self {
+        Self {
+            config: config.clone(),
+            armed: true,
+        }
+    }
+
+    /// Next action for portfolio `delta` (USD) with `hedge` open and the
+    /// hedge instrument at `price`.
+    pub fn plan(
+        &mut self,
+        delta: f64,
+        hedge: Option<&HedgePosition>,
+        price: f64,
+        now: DateTime<Utc>,
+    ) -> Option<HedgeAction> {
+        self.observe(delta);
+        let config = &self.config;
+        if price.is_nan() || price <= 0.0 || config.instrument_beta == 0.0 {
+            return None;
+        }
+        let Some(hedge) = hedge else {
+            return (self.armed && delta > config.max_delta_usd)
+                .then(|| self.open(delta, price))
+                .flatten();
+        };
+
+        if delta < config.close_below_usd {
+            return Some(HedgeAction::Close {
+                reason: "exposure_normalized",
+            });
+        }
+        if config.stop_loss_pct > 0.0 && hedge.pnl_pct(dec(price)) <= -config.stop_loss_pct {
+            self.armed = false;
+            return Some(HedgeAction::Close {
+                reason: "stop_loss",
+            });
+        }
+        if config.max_hold_minutes > 0.0
+            && (now - hedge.entry_time).num_seconds() as f64 >= config.max_hold_minutes * 60.0
+        {
+            return Some(HedgeAction::Close { reason: "max_hold" });
+        }
+        // Exposure grew past the threshold again: top up
+        let net = delta + hedge.delta(price, config.instrument_beta);
+        if net > config.max_delta_usd {
+            return self.open(net, price);
+        }
+        None
+    }
+
+    /// Re-arm once the exposure is back below `close_below_usd`.
+    fn observe(&mut self, delta: f64) {
+        if delta < self.config.close_below_usd {
+            self.armed = true;
+        }
+    }
+
+    fn open(&self, delta: f64, price: f64) -> Option<HedgeAction> {
+        let beta = self.config.instrument_beta;
+        let qty = delta * self.config.hedge_ratio / (beta.abs() * price);
+        (qty > 0.0 && qty.is_finite()).then(|| HedgeAction::Open {
+            side: if beta > 0.0 { Side::Sell } else { Side::Buy },
+            qty: dec(qty),
+        })
+    }
+}
+
+/// Opens, tops up and closes the session's hedge.
+pub struct Hedger {
+    event_bus: EventBus,
+    exchange: Arc<dyn TradingApi>,
+    tracker: PositionTracker,
+    store: MarketStore,
+    symbol_meta: SymbolMetaRegistry,
+    config: HedgingConfig,
+    market_tif: TimeInForce,
+}
+
+impl Hedger {
+    pub fn new(
+        event_bus: EventBus,
+        exchange: Arc<dyn TradingApi>,
+        tracker: PositionTracker,
+        store: MarketStore,
+        symbol_meta: SymbolMetaRegistry,
+        config: &AppConfig,
+    ) -> Self {
+        Self {
+            event_bus,
+            exchange,
+            tracker,
+            store,
+            symbol_meta,
+            config: config.hedging.clone(),
+            market_tif: market_tif(config),
+        }
+    }
+
+    pub fn start(self) {
+        // A perp hedge is a short; inverse ETFs are bought and work anywhere
+        if self.config.instrument_beta > 0.0 && !self.exchange.capabilities().supports_short {
+            warn!(
+                "⚠️ [HEDGE] {} cannot sell {} short - hedger not started",
+                self.exchange.name(),
+                self.config.instrument
+            );
+            return;
+        }
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🛡️ Hedger started ({} vs {}, hedging above ${:.0})",
+                self.config.instrument, self.config.reference_symbol, self.config.max_delta_usd
+            );
+            let mut planner = HedgePlanner::new(&self.config);
+            let mut check =
+                tokio::time::interval(Duration::from_secs(self.config.check_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = check.tick() => self.check(&mut planner).await,
+                }
+            }
+            if let Some(hedge) = self.tracker.get_hedge(&self.config.instrument) {
+                warn!(
+                    "⚠️ [HEDGE] Session stopped with hedge open: {:?} {} {}",
+                    hedge.side, hedge.qty, hedge.symbol
+                );
+            }
+        });
+    }
+
+    fn mid(&self, symbol: &str) -> Option<f64> {
+        self.store
+            .get_latest_quote(symbol)
+            .map(|q| (q.bid_price + q.ask_price) / 2.0)
+            .filter(|mid| *mid > 0.0)
+    }
+
+    /// Instrument price: streamed quote, else the venue's top of book.
+    async fn instrument_price(&self) -> Option<f64> {
+        if let Some(mid) = self.mid(&self.config.instrument) {
+            return Some(mid);
+        }
+        match self
+            .exchange
+            .get_best_bid_ask(&self.config.instrument)
+            .await
+        {
+            Ok(Some(top)) => Some(float((top.bid + top.ask) / Decimal::TWO)),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("⚠️ [HEDGE] No price for {}: {}", self.config.instrument, e);
+                None
+            }
+        }
+    }
+
+    async fn check(&self, planner: &mut HedgePlanner) {
+        let delta = portfolio_delta(
+            &self.tracker.get_all_positions(),
+            |symbol| self.mid(symbol),
+            &self.config,
+        );
+        let hedge = self.tracker.get_hedge(&self.config.instrument);
+        // Nothing to hedge: skip the price lookup
+        if hedge.is_none() && delta <= self.config.max_delta_usd {
+            planner.observe(delta);
+            return;
+        }
+        let Some(price) = self.instrument_price().await else {
+            return;
+        };
+        match planner.plan(delta, hedge.as_ref(), price, Utc::now()) {
+            Some(HedgeAction::Open { side, qty }) => {
+                self.open(side, qty, price, delta, hedge).await;
+            }
+            Some(HedgeAction::Close { reason }) => {
+                if let Some(hedge) = hedge {
+                    self.close(hedge, price, reason).await;
+                }
+            }
+            None => {}
+        }
+    }
+
+    async fn open(
+        &self,
+        side: Side,
+        qty: Decimal,
+        price: f64,
+        delta: f64,
+        hedge: Option<HedgePosition>,
+    ) {
+        let req = PlaceOrderRequest {
+            symbol: self.config.instrument.clone(),
+            side,
+            order_type: OrderType::Market,
+            qty: Some(qty),
+            notional: None,
+            limit_price: None,
+            time_in_force: self.market_tif,
+            bracket: None,
+            stop_price: None,
+            reduce_only: false,
+            metadata: HashMap::new(),
+        };
+        let Some(req) =
+            normalize_order(&self.symbol_meta, self.exchange.as_ref(), req, dec(price)).await
+        else {
+            return;
+        };
+        let qty = req.qty.unwrap_or(qty);
+        if let Err(e) = self.exchange.submit_order(req).await {
+            warn!(
+                "⚠️ [HEDGE] {:?} {} {} failed: {}",
+                side, qty, self.config.instrument, e
+            );
+            return;
+        }
+        let hedge = match hedge {
+            Some(mut hedge) => {
+                hedge.add(qty, dec(price));
+                hedge
+            }
+            None => HedgePosition {
+                symbol: self.config.instrument.clone(),
+                side,
+                qty,
+                entry_price: dec(price),
+                entry_time: Utc::now(),
+            },
+        };
+        info!(
+            "🛡️ [HEDGE] {:?} {} {} @ {:.2} against ${:.0} exposure to {} (hedge now {})",
+            side,
+            qty,
+            self.config.instrument,
+            price,
+            delta,
+            self.config.reference_symbol,
+            hedge.qty
+        );
+        self.tracker.set_hedge(hedge);
+    }
+
+    async fn close(&self, hedge: HedgePosition, price: f64, reason: &str) {
+        let side = if hedge.side == Side::Buy {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+        let req = PlaceOrderRequest {
+            symbol: hedge.symbol.clone(),
+            side,
+            order_type: OrderType::Market,
+            qty: Some(hedge.qty),
+            notional: None,
+            limit_price: None,
+            time_in_force: self.market_tif,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        // Retried on the next check while the hedge stays tracked
+        if let Err(e) = self.exchange.submit_order(req).await {
+            warn!(
+                "⚠️ [HEDGE] Closing {} {} ({}) failed: {}",
+                hedge.qty, hedge.symbol, reason, e
+            );
+            return;
+        }
+        self.tracker.remove_hedge(&hedge.symbol);
+        info!(
+            "🛡️ [HEDGE] Closed {} {} @ {:.2} ({}, {:+.2}%)",
+            hedge.qty,
+            hedge.symbol,
+            price,
+            reason,
+            hedge.pnl_pct(dec(price))
+        );
+    }
+}
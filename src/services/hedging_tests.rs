@@ -0,0 +1,207 @@
+//! Unit tests for delta hedging - portfolio delta, hedge sizing and side,
+//! and the hedge's exit rules.
+
+#[cfg(test)]
+mod hedging_tests {
+    use crate::config::HedgingConfig;
+    use crate::exchange::types::Side;
+    use crate::money::dec;
+    use crate::services::hedging::*;
+    use crate::services::position_monitor::{PositionInfo, PositionTracker};
+    use chrono::{DateTime, Duration, Utc};
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+
+    fn config() -> HedgingConfig {
+        HedgingConfig {
+            enabled: true,
+            reference_symbol: "BTC/USD".to_string(),
+            instrument: "BTCUSDT".to_string(),
+            betas: HashMap::from([("ETH/USD".to_string(), 1.5)]),
+            max_delta_usd: 1000.0,
+            close_below_usd: 250.0,
+            stop_loss_pct: 5.0,
+            ..HedgingConfig::default()
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn position(symbol: &str, qty: f64, entry: f64) -> PositionInfo {
+        PositionInfo {
+            symbol: symbol.to_string(),
+            entry_price: dec(entry),
+            qty: dec(qty),
+            filled_qty: dec(qty),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(entry * 0.95),
+            take_profit: dec(entry * 1.05),
+            entry_time: now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: None,
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(entry),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(entry * 0.95),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
+        }
+    }
+
+    fn short_hedge(qty: f64, entry: f64) -> HedgePosition {
+        HedgePosition {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Sell,
+            qty: dec(qty),
+            entry_price: dec(entry),
+            entry_time: now(),
+        }
+    }
+
+    // ============= Delta Tests =============
+
+    #[test]
+    fn test_portfolio_delta_is_beta_weighted() {
+        let positions = [
+            position("BTC/USD", 0.02, 50_000.0),
+            position("ETH/USD", 1.0, 3000.0),
+            position("SOL/USD", 10.0, 100.0),
+            // A stray position in the instrument is not exposure to hedge
+            position("BTCUSDT", 1.0, 50_000.0),
+        ];
+        let price = |symbol: &str| (symbol == "BTC/USD").then_some(60_000.0);
+        // 0.02 * 60000 * 1 + 1 * 3000 * 1.5 + 10 * 100 * 1 (default beta)
+        let delta = portfolio_delta(&positions, price, &config());
+        assert!((delta - 6700.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hedge_delta_and_pnl_by_side() {
+        let short = short_hedge(0.1, 60_000.0);
+        assert!((short.delta(60_000.0, 1.0) + 6000.0).abs() < 1e-6);
+        assert!((short.pnl_pct(dec(57_000.0)) - 5.0).abs() < 1e-9);
+
+        let mut long = HedgePosition {
+            side: Side::Buy,
+            ..short_hedge(10.0, 20.0)
+        };
+        // Inverse ETF: long shares are negative exposure
+        assert!((long.delta(20.0, -1.0) + 200.0).abs() < 1e-6);
+        long.add(dec(10.0), dec(30.0));
+        assert_eq!(long.qty, dec(20.0));
+        assert_eq!(long.entry_price, dec(25.0));
+    }
+
+    // ============= Planner Tests =============
+
+    #[test]
+    fn test_opens_short_perp_above_threshold() {
+        let mut planner = HedgePlanner::new(&config());
+        assert_eq!(planner.plan(900.0, None, 60_000.0, now()), None);
+        assert_eq!(
+            planner.plan(3000.0, None, 60_000.0, now()),
+            Some(HedgeAction::Open {
+                side: Side::Sell,
+                qty: dec(0.05),
+            })
+        );
+    }
+
+    #[test]
+    fn test_inverse_etf_is_bought() {
+        let config = HedgingConfig {
+            instrument: "BITI".to_string(),
+            instrument_beta: -2.0,
+            hedge_ratio: 0.5,
+            ..config()
+        };
+        let mut planner = HedgePlanner::new(&config);
+        assert_eq!(
+            planner.plan(4000.0, None, 10.0, now()),
+            Some(HedgeAction::Open {
+                side: Side::Buy,
+                qty: dec(100.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hedge_exit_rules() {
+        let hedge = short_hedge(0.05, 60_000.0);
+        let mut planner = HedgePlanner::new(&config());
+        // Hedged and within bounds: hold
+        assert_eq!(planner.plan(3000.0, Some(&hedge), 60_000.0, now()), None);
+        assert_eq!(
+            planner.plan(100.0, Some(&hedge), 60_000.0, now()),
+            Some(HedgeAction::Close {
+                reason: "exposure_normalized"
+            })
+        );
+
+        let timed = HedgingConfig {
+            max_hold_minutes: 60.0,
+            ..config()
+        };
+        assert_eq!(
+            HedgePlanner::new(&timed).plan(
+                3000.0,
+                Some(&hedge),
+                60_000.0,
+                now() + Duration::minutes(61)
+            ),
+            Some(HedgeAction::Close { reason: "max_hold" })
+        );
+    }
+
+    #[test]
+    fn test_stop_loss_waits_for_exposure_to_fall_before_rehedging() {
+        let hedge = short_hedge(0.05, 60_000.0);
+        let mut planner = HedgePlanner::new(&config());
+        assert_eq!(
+            planner.plan(3000.0, Some(&hedge), 63_500.0, now()),
+            Some(HedgeAction::Close {
+                reason: "stop_loss"
+            })
+        );
+        assert_eq!(planner.plan(3000.0, None, 63_500.0, now()), None);
+        assert_eq!(planner.plan(100.0, None, 63_500.0, now()), None);
+        assert!(planner.plan(3000.0, None, 63_500.0, now()).is_some());
+    }
+
+    #[test]
+    fn test_tops_up_when_exposure_outgrows_the_hedge() {
+        let hedge = short_hedge(0.05, 60_000.0);
+        let mut planner = HedgePlanner::new(&config());
+        // 5000 long against 3000 short: 2000 unhedged
+        assert_eq!(
+            planner.plan(5000.0, Some(&hedge), 60_000.0, now()),
+            Some(HedgeAction::Open {
+                side: Side::Sell,
+                qty: dec(2000.0 / 60_000.0),
+            })
+        );
+    }
+
+    // ============= Tracker Tests =============
+
+    #[test]
+    fn test_hedges_are_tracked_apart_from_positions() {
+        let tracker = PositionTracker::new();
+        tracker.set_hedge(short_hedge(0.05, 60_000.0));
+        assert!(tracker.get_all_positions().is_empty());
+        assert!(!tracker.has_position("BTCUSDT"));
+        assert_eq!(tracker.get_all_hedges().len(), 1);
+        assert_eq!(
+            tracker.remove_hedge("BTCUSDT"),
+            Some(short_hedge(0.05, 60_000.0))
+        );
+        assert_eq!(tracker.get_hedge("BTCUSDT"), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! Startup history backfill.
+//!
+//! Strategies stay idle until `warmup_count` quotes per symbol are in the
+//! `MarketStore`, which on quiet markets can take many minutes. Before the
+//! live streams start, `HistoryBootstrapper` pulls the most recent bars over
+//! REST (Alpaca crypto/stock bars, Binance klines, Kraken OHLC), stores them
+//! and seeds one quote per bar at its close so the warm-up check passes at once.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::data::store::{Bar, MarketStore, Quote};
+use crate::exchange::traits::TradingApi;
+
+/// Per-symbol cap on the REST call so a slow venue cannot hold up startup.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn num(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// RFC3339 with milliseconds, the shape live quotes carry.
+fn timestamp_from_secs(secs: f64) -> Option<String> {
+    DateTime::<Utc>::from_timestamp_millis((secs * 1000.0) as i64)
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+fn timestamp_from_rfc3339(ts: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(ts).ok().map(|t| {
+        t.with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Millis, true)
+    })
+}
+
+/// Alpaca bars: `{"bars": {"BTC/USD": [{t, o, h, l, c, v}, ...]}}` for crypto,
+/// `{"bars": [{t, o, h, l, c, v}, ...]}` for stocks.
+pub fn parse_alpaca_bars(symbol: &str, raw: &Value) -> Vec<Bar> {
+    let items = match raw.get("bars") {
+        Some(Value::Object(by_symbol)) => by_symbol.get(symbol).and_then(|b| b.as_array()),
+        Some(Value::Array(items)) => Some(items),
+        _ => None,
+    };
+    items
+        .into_iter()
+        .flatten()
+        .filter_map(|b| {
+            Some(Bar {
+                symbol: symbol.to_string(),
+                open: num(b.get("o")?)?,
+                high: num(b.get("h")?)?,
+                low: num(b.get("l")?)?,
+                close: num(b.get("c")?)?,
+                volume: b.get("v").and_then(num).unwrap_or(0.0),
+                timestamp: timestamp_from_rfc3339(b.get("t")?.as_str()?)?,
+            })
+        })
+        .collect()
+}
+
+/// Binance klines: `[[open_time_ms, "o", "h", "l", "c", "v", ...], ...]`.
+pub fn parse_binance_klines(symbol: &str, raw: &Value) -> Vec<Bar> {
+    raw.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|k| {
+            let k = k.as_array()?;
+            Some(Bar {
+                symbol: symbol.to_string(),
+                open: num(k.get(1)?)?,
+                high: num(k.get(2)?)?,
+                low: num(k.get(3)?)?,
+                close: num(k.get(4)?)?,
+                volume: k.get(5).and_then(num).unwrap_or(0.0),
+                timestamp: timestamp_from_secs(num(k.first()?)? / 1000.0)?,
+            })
+        })
+        .collect()
+}
+
+/// Kraken OHLC: `{"result": {"<pair>": [[time, "o", "h", "l", "c", "vwap", "v", count], ...], "last": ...}}`.
+/// The result key is Kraken's own pair name, so the first series is used.
+pub fn parse_kraken_ohlc(symbol: &str, raw: &Value) -> Vec<Bar> {
+    let series = raw
+        .get("result")
+        .and_then(|r| r.as_object())
+        .and_then(|r| r.iter().find(|(key, _)| *key != "last"))
+        .and_then(|(_, v)| v.as_array());
+    series
+        .into_iter()
+        .flatten()
+        .filter_map(|k| {
+            let k = k.as_array()?;
+            Some(Bar {
+                symbol: symbol.to_string(),
+                open: num(k.get(1)?)?,
+                high: num(k.get(2)?)?,
+                low: num(k.get(3)?)?,
+                close: num(k.get(4)?)?,
+                volume: k.get(6).and_then(num).unwrap_or(0.0),
+                timestamp: timestamp_from_secs(num(k.first()?)?)?,
+            })
+        })
+        .collect()
+}
+
+/// Bars from a venue's `get_historical_bars` response, oldest first.
+pub fn parse_bars(venue: &str, symbol: &str, raw: &Value) -> Vec<Bar> {
+    let mut bars = match venue {
+        "alpaca" => parse_alpaca_bars(symbol, raw),
+        "binance" => parse_binance_klines(symbol, raw),
+        "kraken" => parse_kraken_ohlc(symbol, raw),
+        _ => Vec::new(),
+    };
+    bars.retain(|b| b.close > 0.0);
+    bars.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    bars
+}
+
+/// Warm-up quote for a historical bar: bid and ask at the close.
+pub fn quote_from_bar(bar: &Bar) -> Quote {
+    Quote {
+        symbol: bar.symbol.clone(),
+        bid_price: bar.close,
+        ask_price: bar.close,
+        bid_size: 0.0,
+        ask_size: 0.0,
+        timestamp: bar.timestamp.clone(),
+    }
+}
+
+/// Pre-fills `MarketStore` with recent bars before the live streams start.
+pub struct HistoryBootstrapper {
+    exchange: Arc<dyn TradingApi>,
+    store: MarketStore,
+    timeframe: String,
+    max_bars: usize,
+}
+
+impl HistoryBootstrapper {
+    pub fn new(exchange: Arc<dyn TradingApi>, store: MarketStore, config: &AppConfig) -> Self {
+        Self {
+            exchange,
+            store,
+            timeframe: config.history_bootstrap.timeframe.clone(),
+            max_bars: config.history_bootstrap.bars,
+        }
+    }
+
+    /// Store the most recent `max_bars` bars and their warm-up quotes.
+    /// Returns how many bars were seeded.
+    pub fn seed(&self, symbol: &str, bars: &[Bar]) -> usize {
+        let recent = &bars[bars.len().saturating_sub(self.max_bars)..];
+        for bar in recent {
+            self.store.update_bar(symbol.to_string(), bar.clone());
+            self.store
+                .update_quote(symbol.to_string(), quote_from_bar(bar));
+        }
+        recent.len()
+    }
+
+    /// Fetch and seed every symbol. Failures are logged; the symbol then warms
+    /// up from live quotes as before.
+    pub async fn run(&self, symbols: &[String]) {
+        let venue = self.exchange.name();
+        for symbol in symbols {
+            let fetched = tokio::time::timeout(
+                FETCH_TIMEOUT,
+                self.exchange.get_historical_bars(symbol, &self.timeframe),
+            )
+            .await;
+            let raw = match fetched {
+                Ok(Ok(raw)) => raw,
+                Ok(Err(e)) => {
+                    warn!("⚠️ [BOOTSTRAP] {} history fetch failed: {}", symbol, e);
+                    continue;
+                }
+                Err(_) => {
+                    warn!(
+                        "⚠️ [BOOTSTRAP] {} history fetch timed out after {}s",
+                        symbol,
+                        FETCH_TIMEOUT.as_secs()
+                    );
+                    continue;
+                }
+            };
+            let bars = parse_bars(venue, symbol, &raw);
+            if bars.is_empty() {
+                info!(
+                    "📜 [BOOTSTRAP] No {} history from {} for {}",
+                    self.timeframe, venue, symbol
+                );
+                continue;
+            }
+            let seeded = self.seed(symbol, &bars);
+            info!(
+                "📜 [BOOTSTRAP] Seeded {} {} bar(s) for {} (history={})",
+                seeded,
+                self.timeframe,
+                symbol,
+                self.store.quote_count(symbol)
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Unit tests for the startup history backfill (venue bar parsing, store seeding).
+
+#[cfg(test)]
+mod history_bootstrap_tests {
+    use crate::config::AppConfig;
+    use crate::config::{BinanceConfig, BinanceFuturesConfig};
+    use crate::data::store::MarketStore;
+    use crate::exchange::binance::BinanceExchange;
+    use crate::services::history_bootstrap::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const CONFIG_YAML: &str = r#"
+trading_mode: "crypto"
+exchange: "binance"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 3
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+history_bootstrap:
+  bars: 3
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#;
+
+    fn bootstrapper(store: MarketStore) -> HistoryBootstrapper {
+        let config = AppConfig::parse(CONFIG_YAML).unwrap();
+        let exchange = BinanceExchange::new(BinanceConfig {
+            api_key: "K".to_string(),
+            secret_key: "S".to_string(),
+            base_url: "http://127.0.0.1:1".to_string(),
+            quote_asset: "USDT".to_string(),
+            futures: BinanceFuturesConfig::default(),
+        });
+        HistoryBootstrapper::new(Arc::new(exchange), store, &config)
+    }
+
+    // ============= Parsing Tests =============
+
+    #[test]
+    fn test_parse_alpaca_crypto_bars() {
+        let raw = json!({"bars": {"BTC/USD": [
+            {"t": "2024-05-01T12:00:00Z", "o": 100.0, "h": 102.0, "l": 99.0, "c": 101.0, "v": 3.5},
+            {"t": "2024-05-01T12:01:00Z", "o": 101.0, "h": 103.0, "l": 100.0, "c": 102.5, "v": 1.0},
+        ]}, "next_page_token": null});
+        let bars = parse_bars("alpaca", "BTC/USD", &raw);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].close, 101.0);
+        assert_eq!(bars[0].volume, 3.5);
+        assert_eq!(bars[1].timestamp, "2024-05-01T12:01:00.000Z");
+        assert!(parse_bars("alpaca", "ETH/USD", &raw).is_empty());
+    }
+
+    #[test]
+    fn test_parse_alpaca_stock_bars() {
+        let raw = json!({"bars": [
+            {"t": "2024-05-01T13:30:00Z", "o": 180.0, "h": 181.0, "l": 179.5, "c": 180.5, "v": 1200},
+        ], "symbol": "AAPL"});
+        let bars = parse_bars("alpaca", "AAPL", &raw);
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].symbol, "AAPL");
+        assert_eq!(bars[0].volume, 1200.0);
+    }
+
+    #[test]
+    fn test_parse_binance_klines() {
+        let raw = json!([
+            [
+                1714564860000i64,
+                "101.0",
+                "103.0",
+                "100.0",
+                "102.5",
+                "1.0",
+                1714564919999i64,
+                "102.5",
+                10,
+                "0.5",
+                "51.2",
+                "0"
+            ],
+            [
+                1714564800000i64,
+                "100.0",
+                "102.0",
+                "99.0",
+                "101.0",
+                "3.5",
+                1714564859999i64,
+                "353.5",
+                20,
+                "1.5",
+                "151.5",
+                "0"
+            ],
+        ]);
+        let bars = parse_bars("binance", "BTC/USD", &raw);
+        assert_eq!(bars.len(), 2);
+        // Sorted oldest first
+        assert_eq!(bars[0].timestamp, "2024-05-01T12:00:00.000Z");
+        assert_eq!(bars[0].open, 100.0);
+        assert_eq!(bars[1].close, 102.5);
+        assert_eq!(bars[1].volume, 1.0);
+    }
+
+    #[test]
+    fn test_parse_kraken_ohlc() {
+        let raw = json!({"error": [], "result": {
+            "XXBTZUSD": [
+                [1714564800, "100.0", "102.0", "99.0", "101.0", "100.7", "3.5", 20],
+                [1714564860, "101.0", "103.0", "100.0", "102.5", "101.9", "1.0", 10],
+            ],
+            "last": 1714564860
+        }});
+        let bars = parse_bars("kraken", "BTC/USD", &raw);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].symbol, "BTC/USD");
+        assert_eq!(bars[0].timestamp, "2024-05-01T12:00:00.000Z");
+        assert_eq!(bars[0].volume, 3.5);
+        assert_eq!(bars[1].high, 103.0);
+    }
+
+    #[test]
+    fn test_parse_skips_malformed_and_unsupported() {
+        let raw = json!([["bad"], [1714564800000i64, "x", "1", "1", "1", "1"]]);
+        assert!(parse_bars("binance", "BTC/USD", &raw).is_empty());
+        assert!(parse_bars("kraken", "BTC/USD", &json!({"error": []})).is_empty());
+        assert!(parse_bars("coinbase", "BTC/USD", &json!(null)).is_empty());
+    }
+
+    // ============= Seeding Tests =============
+
+    #[test]
+    fn test_quote_from_bar_uses_close() {
+        let raw = json!([[1714564800000i64, "100.0", "102.0", "99.0", "101.0", "3.5"]]);
+        let bar = &parse_bars("binance", "BTC/USD", &raw)[0];
+        let quote = quote_from_bar(bar);
+        assert_eq!(quote.bid_price, 101.0);
+        assert_eq!(quote.ask_price, 101.0);
+        assert_eq!(quote.timestamp, bar.timestamp);
+    }
+
+    #[test]
+    fn test_seed_keeps_most_recent_bars() {
+        let store = MarketStore::new(50);
+        let raw = json!([
+            [1714564800000i64, "1", "1", "1", "100.0", "1"],
+            [1714564860000i64, "1", "1", "1", "101.0", "1"],
+            [1714564920000i64, "1", "1", "1", "102.0", "1"],
+            [1714564980000i64, "1", "1", "1", "103.0", "1"],
+        ]);
+        let bars = parse_bars("binance", "BTC/USD", &raw);
+
+        let seeded = bootstrapper(store.clone()).seed("BTC/USD", &bars);
+        assert_eq!(seeded, 3);
+        let quotes = store.get_quote_history("BTC/USD");
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[0].bid_price, 101.0);
+        assert_eq!(quotes[2].bid_price, 103.0);
+        assert_eq!(store.get_bar_history("BTC/USD").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_survives_fetch_failure() {
+        let store = MarketStore::new(50);
+        bootstrapper(store.clone())
+            .run(&["BTC/USD".to_string()])
+            .await;
+        assert!(store.get_quote_history("BTC/USD").is_empty());
+    }
+}
@@ -6,6 +6,8 @@ use std::time::Duration;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{info, warn};
 
+use crate::error::{AutoHedgeError, Result};
+
 pub struct KeepAliveService {
     base_url: String,
     client: Client,
@@ -30,7 +32,7 @@ impl KeepAliveService {
     ///
     /// Pings the service every 10 seconds to prevent free-tier scaling down
     /// Most free hosting services scale down after 5-30 minutes of inactivity
-    pub async fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn start(&self) -> Result<()> {
         let scheduler = JobScheduler::new().await?;
 
         let url = self.base_url.clone();
@@ -69,10 +71,7 @@ impl KeepAliveService {
     }
 
     /// Perform a lightweight ping to the service
-    async fn ping_service(
-        base_url: &str,
-        client: &Client,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn ping_service(base_url: &str, client: &Client) -> Result<()> {
         // Try health endpoint first, fall back to stats if not available
         let endpoints = vec![
             format!("{}/health", base_url),
@@ -98,18 +97,19 @@ impl KeepAliveService {
                             endpoint,
                             response.status()
                         );
-                        last_error = Some(format!("Non-success status: {}", response.status()));
+                        last_error = Some(AutoHedgeError::http(
+                            response.status().as_u16(),
+                            format!("Non-success status: {}", response.status()),
+                        ));
                     }
                 }
                 Err(e) => {
-                    last_error = Some(format!("Request failed: {}", e));
+                    last_error = Some(AutoHedgeError::exchange(format!("Request failed: {}", e)));
                 }
             }
         }
 
-        Err(last_error
-            .unwrap_or_else(|| "All endpoints failed".to_string())
-            .into())
+        Err(last_error.unwrap_or_else(|| AutoHedgeError::exchange("All endpoints failed")))
     }
 
     /// Start with a custom cron schedule
@@ -121,7 +121,7 @@ impl KeepAliveService {
     /// ```no_run
     /// # use rust_autohedge::services::keep_alive::KeepAliveService;
     /// # #[tokio::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn main() -> rust_autohedge::error::Result<()> {
     /// let service = KeepAliveService::new("http://localhost:3000".to_string());
     ///
     /// // Every 10 seconds (default)
@@ -135,10 +135,7 @@ impl KeepAliveService {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn start_with_schedule(
-        &self,
-        cron_expression: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn start_with_schedule(&self, cron_expression: &str) -> Result<()> {
         let scheduler = JobScheduler::new().await?;
 
         let url = self.base_url.clone();
@@ -0,0 +1,264 @@
+//! Rule-based fallback for the LLM modes (`llm_fallback`).
+//!
+//! When the LLM endpoint is down the Director answers every call with the
+//! breaker's `no_trade` (or an error), so the llm and hybrid modes stop
+//! trading altogether. The `LlmFallbackMonitor` watches the queue's failure
+//! rate over `window_secs`; once at least `min_calls` calls were made and
+//! `error_rate` of them failed it flags the queue as degraded and publishes
+//! `Event::LlmFallback`. While degraded:
+//!
+//! - llm mode replaces Director -> Quant with `rule_director` and emits
+//!   entries with origin mode `fallback`, which risk and execution approve and
+//!   size without their agents;
+//! - hybrid mode refreshes its gate with `rule_director` instead of the
+//!   Director.
+//!
+//! No LLM calls are made meanwhile, so the failures age out of the window;
+//! once fewer than `min_calls` remain (or the rate drops below the threshold)
+//! the monitor hands decisions back to the LLM, which then either recovers or
+//! fails its way back into fallback.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, LlmFallbackConfig};
+use crate::data::indicators::{IndicatorKind, IndicatorValue};
+use crate::data::store::MarketStore;
+use crate::events::{AnalysisSignal, Event, LlmFallbackEvent, TradeOrigin};
+use crate::llm::LLMQueue;
+use crate::money::dec;
+
+/// Indicator readings the rules decide on.
+#[derive(Clone, Debug, Default)]
+pub struct RuleInputs {
+    pub mid: f64,
+    pub spread_bps: f64,
+    /// RSI(14)
+    pub rsi: Option<f64>,
+    /// EMA(20)
+    pub ema: Option<f64>,
+    /// MACD(12,26,9) histogram
+    pub macd_histogram: Option<f64>,
+}
+
+/// Whether the rules take a long entry, with the thesis explaining why (or
+/// why not).
+pub fn rule_decision(inputs: &RuleInputs, config: &LlmFallbackConfig) -> (bool, String) {
+    let Some(rsi) = inputs.rsi else {
+        return (false, "FALLBACK no_trade: RSI warming up".to_string());
+    };
+    let mut reasons = vec![format!("RSI {:.1}", rsi)];
+    if rsi < config.rsi_min || rsi > config.rsi_max {
+        return (
+            false,
+            format!(
+                "FALLBACK no_trade: RSI {:.1} outside {:.0}-{:.0}",
+                rsi, config.rsi_min, config.rsi_max
+            ),
+        );
+    }
+    if config.require_above_ema {
+        match inputs.ema {
+            Some(ema) if inputs.mid > ema => {
+                reasons.push(format!("mid {:.8} above EMA(20) {:.8}", inputs.mid, ema))
+            }
+            Some(ema) => {
+                return (
+                    false,
+                    format!(
+                        "FALLBACK no_trade: mid {:.8} not above EMA(20) {:.8}",
+                        inputs.mid, ema
+                    ),
+                )
+            }
+            None => return (false, "FALLBACK no_trade: EMA warming up".to_string()),
+        }
+    }
+    if config.require_macd_positive {
+        match inputs.macd_histogram {
+            Some(h) if h > 0.0 => reasons.push(format!("MACD histogram {:+.8}", h)),
+            Some(h) => {
+                return (
+                    false,
+                    format!("FALLBACK no_trade: MACD histogram {:+.8}", h),
+                )
+            }
+            None => return (false, "FALLBACK no_trade: MACD warming up".to_string()),
+        }
+    }
+    if inputs.spread_bps > config.max_spread_bps {
+        return (
+            false,
+            format!(
+                "FALLBACK no_trade: spread {:.2} bps > {:.2}",
+                inputs.spread_bps, config.max_spread_bps
+            ),
+        );
+    }
+    reasons.push(format!("spread {:.2} bps", inputs.spread_bps));
+    (true, format!("FALLBACK trade: {}", reasons.join(", ")))
+}
+
+/// `rule_decision` on the symbol's latest quote and indicators.
+pub fn rule_director(
+    store: &MarketStore,
+    symbol: &str,
+    config: &LlmFallbackConfig,
+) -> (bool, String) {
+    let Some(quote) = store.get_latest_quote(symbol) else {
+        return (false, "FALLBACK no_trade: no quote".to_string());
+    };
+    let (bid, ask) = (quote.bid_price, quote.ask_price);
+    if bid <= 0.0 || ask < bid {
+        return (false, "FALLBACK no_trade: invalid quote".to_string());
+    }
+    let mid = (bid + ask) / 2.0;
+    let single = |kind, period| match store.get_indicator(symbol, kind, period) {
+        Some(IndicatorValue::Single(v)) => Some(v),
+        _ => None,
+    };
+    let inputs = RuleInputs {
+        mid,
+        spread_bps: (ask - bid) / mid * 10_000.0,
+        rsi: single(IndicatorKind::Rsi, 14),
+        ema: single(IndicatorKind::Ema, 20),
+        macd_histogram: match store.get_indicator(symbol, IndicatorKind::Macd, 9) {
+            Some(IndicatorValue::Macd { histogram, .. }) => Some(histogram),
+            _ => None,
+        },
+    };
+    rule_decision(&inputs, config)
+}
+
+/// Entry signal of the rules for `symbol`, or the no-trade thesis. TP/SL are
+/// the symbol's configured percentages around the ask.
+pub fn fallback_signal(
+    store: &MarketStore,
+    symbol: &str,
+    config: &AppConfig,
+) -> Result<AnalysisSignal, String> {
+    let (trade, thesis) = rule_director(store, symbol, &config.llm_fallback);
+    if !trade {
+        return Err(thesis);
+    }
+    let ask = store
+        .get_latest_quote(symbol)
+        .map(|q| q.ask_price)
+        .unwrap_or_default();
+    let (tp_pct, sl_pct) = config.get_symbol_params(symbol);
+    let tp = ask * (1.0 + tp_pct / 100.0);
+    let sl = ask * (1.0 - sl_pct / 100.0);
+    Ok(AnalysisSignal {
+        symbol: symbol.to_string(),
+        signal: "buy".to_string(),
+        confidence: 0.0,
+        thesis,
+        market_context: format!("tp={:.8}, sl={:.8}", tp, sl),
+        strategy_id: None,
+        correlation_id: None,
+        origin: Some(TradeOrigin::new(TradeOrigin::FALLBACK)),
+        quant_analysis: None,
+        entry_limit: None,
+        take_profit: Some(dec(tp)),
+        stop_loss: Some(dec(sl)),
+        valid_until: None,
+        metadata: HashMap::new(),
+    })
+}
+
+/// New fallback state for the measured failure `rate` over `calls`, or None
+/// when it stays as it is.
+pub fn fallback_transition(
+    active: bool,
+    rate: f64,
+    calls: usize,
+    config: &LlmFallbackConfig,
+) -> Option<bool> {
+    let failing = calls >= config.min_calls.max(1) && rate >= config.error_rate;
+    (failing != active).then_some(failing)
+}
+
+/// Flags the LLM queue as degraded while its calls are failing.
+pub struct LlmFallbackMonitor {
+    event_bus: EventBus,
+    llm: LLMQueue,
+    config: LlmFallbackConfig,
+}
+
+impl LlmFallbackMonitor {
+    pub fn new(event_bus: EventBus, llm: LLMQueue, config: &AppConfig) -> Self {
+        Self {
+            event_bus,
+            llm,
+            config: config.llm_fallback.clone(),
+        }
+    }
+
+    pub fn start(self) {
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🧭 LLM fallback monitor started (rules take over at {:.0}% errors over {}s)",
+                self.config.error_rate * 100.0,
+                self.config.window_secs
+            );
+            let mut check =
+                tokio::time::interval(Duration::from_secs(self.config.check_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = check.tick() => {
+                        self.check();
+                    }
+                }
+            }
+        });
+    }
+
+    /// Switch modes if the error rate calls for it; returns the published event.
+    pub fn check(&self) -> Option<LlmFallbackEvent> {
+        let (rate, calls) = self
+            .llm
+            .error_rate(Duration::from_secs(self.config.window_secs));
+        let active = fallback_transition(self.llm.is_degraded(), rate, calls, &self.config)?;
+        self.llm.set_degraded(active);
+        let reason = if active {
+            warn!(
+                "🧭 [FALLBACK] LLM failing ({:.0}% of {} calls) - entries now decided by indicator rules",
+                rate * 100.0,
+                calls
+            );
+            format!(
+                "{:.0}% of {} LLM calls failed in {}s",
+                rate * 100.0,
+                calls,
+                self.config.window_secs
+            )
+        } else {
+            info!(
+                "🧭 [FALLBACK] Handing decisions back to the LLM ({:.0}% of {} calls failed)",
+                rate * 100.0,
+                calls
+            );
+            if calls < self.config.min_calls {
+                "failures aged out of the window, retrying the LLM".to_string()
+            } else {
+                "LLM error rate back below the threshold".to_string()
+            }
+        };
+        let event = LlmFallbackEvent {
+            active,
+            error_rate: rate,
+            calls,
+            reason,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        self.event_bus
+            .publish(Event::LlmFallback(event.clone()))
+            .ok();
+        Some(event)
+    }
+}
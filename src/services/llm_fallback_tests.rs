@@ -0,0 +1,216 @@
+//! Unit tests for the LLM fallback - the indicator rules, the fallback signal
+//! and the monitor's switch on the queue's error rate.
+
+#[cfg(test)]
+mod llm_fallback_tests {
+    use std::sync::Arc;
+
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, LlmFallbackConfig, LlmRetryConfig};
+    use crate::data::store::{MarketStore, Quote};
+    use crate::error::{AutoHedgeError, Result};
+    use crate::events::{Event, TradeOrigin};
+    use crate::llm::provider::LlmProvider;
+    use crate::llm::usage::TokenUsage;
+    use crate::llm::{LLMClient, LLMQueue};
+    use crate::money::dec;
+    use crate::services::llm_fallback::*;
+    use async_trait::async_trait;
+
+    fn rules() -> LlmFallbackConfig {
+        LlmFallbackConfig {
+            enabled: true,
+            ..LlmFallbackConfig::default()
+        }
+    }
+
+    fn inputs() -> RuleInputs {
+        RuleInputs {
+            mid: 101.0,
+            spread_bps: 2.0,
+            rsi: Some(55.0),
+            ema: Some(100.0),
+            macd_histogram: Some(0.05),
+        }
+    }
+
+    #[test]
+    fn test_rules_take_trend_entries_only() {
+        let (trade, thesis) = rule_decision(&inputs(), &rules());
+        assert!(trade);
+        assert!(thesis.starts_with("FALLBACK trade: RSI 55.0"));
+
+        let overbought = RuleInputs {
+            rsi: Some(80.0),
+            ..inputs()
+        };
+        let below_ema = RuleInputs {
+            ema: Some(102.0),
+            ..inputs()
+        };
+        let falling_macd = RuleInputs {
+            macd_histogram: Some(-0.01),
+            ..inputs()
+        };
+        let wide = RuleInputs {
+            spread_bps: 50.0,
+            ..inputs()
+        };
+        let warming_up = RuleInputs {
+            rsi: None,
+            ..inputs()
+        };
+        for case in [overbought, below_ema, falling_macd, wide, warming_up] {
+            let (trade, thesis) = rule_decision(&case, &rules());
+            assert!(!trade, "{:?}", case);
+            assert!(thesis.starts_with("FALLBACK no_trade"));
+        }
+
+        // Disabled filters no longer block
+        let mut lenient = rules();
+        lenient.require_above_ema = false;
+        lenient.require_macd_positive = false;
+        let case = RuleInputs {
+            ema: None,
+            macd_histogram: Some(-0.01),
+            ..inputs()
+        };
+        assert!(rule_decision(&case, &lenient).0);
+    }
+
+    #[test]
+    fn test_transition_needs_min_calls_and_rate() {
+        let config = rules();
+        assert_eq!(fallback_transition(false, 1.0, 4, &config), None);
+        assert_eq!(fallback_transition(false, 0.5, 5, &config), Some(true));
+        assert_eq!(fallback_transition(true, 0.6, 10, &config), None);
+        assert_eq!(fallback_transition(true, 0.2, 10, &config), Some(false));
+        // Failures aged out of the window: try the LLM again
+        assert_eq!(fallback_transition(true, 1.0, 2, &config), Some(false));
+    }
+
+    fn app_config() -> AppConfig {
+        let mut app: AppConfig = serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "binance"
+symbols: ["BTC/USDT"]
+defaults: { take_profit_pct: 1.0, stop_loss_pct: 0.5, min_order_amount: 10.0, max_order_amount: 100.0 }
+history_limit: 100
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "llm"
+chatter_level: "low"
+hft: { evaluate_every_quotes: 5, min_edge_bps: 10.0, take_profit_bps: 50.0, stop_loss_bps: 25.0, max_spread_bps: 30.0 }
+hybrid: { gate_refresh_quotes: 100, no_trade_cooldown_quotes: 50 }
+llm: { api_key: null, base_url: null, model: "test-model" }
+alpaca: { api_key: "K", secret_key: "S", base_url: "https://paper-api.alpaca.markets" }
+exit_on_quotes: true
+"#,
+        )
+        .unwrap();
+        app.llm_fallback = rules();
+        app
+    }
+
+    /// Steadily accelerating uptrend with a 1-cent spread
+    fn trending_store() -> MarketStore {
+        let store = MarketStore::new(100);
+        for i in 0..60 {
+            let mid = 100.0 * 1.001f64.powi(i);
+            store.update_quote(
+                "BTC/USDT".to_string(),
+                Quote {
+                    symbol: "BTC/USDT".to_string(),
+                    bid_price: mid - 0.005,
+                    ask_price: mid + 0.005,
+                    bid_size: 1.0,
+                    ask_size: 1.0,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn test_fallback_signal_from_store() {
+        let mut config = app_config();
+        // A straight uptrend pins RSI at 100
+        let err = fallback_signal(&trending_store(), "BTC/USDT", &config).unwrap_err();
+        assert!(err.contains("RSI 100.0 outside"));
+        assert!(fallback_signal(&MarketStore::new(10), "BTC/USDT", &config).is_err());
+
+        config.llm_fallback.rsi_max = 100.0;
+        let store = trending_store();
+        let signal = fallback_signal(&store, "BTC/USDT", &config).unwrap();
+        assert_eq!(signal.signal, "buy");
+        assert!(signal.thesis.starts_with("FALLBACK trade"));
+        assert_eq!(signal.origin.unwrap().mode, TradeOrigin::FALLBACK);
+        let ask = store.get_latest_quote("BTC/USDT").unwrap().ask_price;
+        assert_eq!(signal.take_profit, Some(dec(ask * 1.01)));
+        assert_eq!(signal.stop_loss, Some(dec(ask * 0.995)));
+    }
+
+    /// Provider whose every call fails
+    struct DownProvider;
+
+    #[async_trait]
+    impl LlmProvider for DownProvider {
+        fn name(&self) -> &'static str {
+            "down"
+        }
+
+        async fn chat(
+            &self,
+            _model: &str,
+            _system_prompt: &str,
+            _user_input: &str,
+        ) -> Result<(String, TokenUsage)> {
+            Err(AutoHedgeError::Llm("503 Service Unavailable".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monitor_degrades_and_recovers() {
+        let client = LLMClient {
+            provider: Arc::new(DownProvider),
+            ..LLMClient::new(String::new(), None, "test-model".to_string())
+        };
+        let retry = LlmRetryConfig {
+            breaker_enabled: false,
+            ..LlmRetryConfig::default()
+        };
+        let llm = LLMQueue::new(client, 1, 10, retry);
+        for _ in 0..5 {
+            assert!(llm.chat_normal("Director", "", "").await.is_err());
+        }
+        let (rate, calls) = llm.error_rate(std::time::Duration::from_secs(60));
+        assert_eq!((rate, calls), (1.0, 5));
+
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let config = app_config();
+        let monitor = LlmFallbackMonitor::new(bus.clone(), llm.clone(), &config);
+        let event = monitor.check().expect("should degrade");
+        assert!(event.active);
+        assert!(llm.is_degraded());
+        assert!(monitor.check().is_none());
+        let Ok(Event::LlmFallback(published)) = rx.try_recv() else {
+            panic!("degraded mode was not announced");
+        };
+        assert!(published.active);
+        assert_eq!(published.calls, 5);
+
+        // Nothing left in an empty window: decisions go back to the LLM
+        let mut config = config;
+        config.llm_fallback.window_secs = 0;
+        let event = LlmFallbackMonitor::new(bus, llm.clone(), &config)
+            .check()
+            .expect("should recover");
+        assert!(!event.active);
+        assert!(!llm.is_degraded());
+    }
+}
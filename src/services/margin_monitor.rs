@@ -0,0 +1,342 @@
+//! Futures margin monitor (`binance.futures.margin_monitor`).
+//!
+//! The liquidation guard in the position monitor closes a single position as
+//! price nears its own liquidation price. This service watches the account as
+//! a whole: every `check_secs` it values the tracked positions at the bid,
+//! sums their maintenance margin and divides it by the venue's margin balance
+//! (wallet plus unrealized P&L). The venue liquidates at a ratio of 100%; at
+//! `deleverage_at_pct` the `MarginMonitor` sells part of the largest positions
+//! at market until the ratio is back at `target_pct`.
+//!
+//! Each reduction follows the bulk close order: working entries and the
+//! resting exit legs are cancelled first (a TP leg sized for the whole
+//! position would over-sell), then the reduced quantity goes out at market.
+//! The position stays tracked with what is left, and the monitor re-places its
+//! TP leg for the new size. On cross margin the ratio is the venue's; with
+//! isolated margin it is an account-wide view, as each position's own ratio
+//! does not move on a partial close.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, BinanceFuturesConfig, MarginMonitorConfig};
+use crate::data::store::MarketStore;
+use crate::events::{DeleverageEvent, Event, ExecutionReport};
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::{OrderType, PlaceOrderRequest, Side, TimeInForce};
+use crate::money::{dec, float};
+use crate::services::execution_utils::{maintenance_margin, market_tif, normalize_order};
+use crate::services::position_monitor::{
+    OcoOutcome, PositionInfo, PositionMonitor, PositionTracker,
+};
+
+/// A held futures position valued at the current price.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarginPosition {
+    pub symbol: String,
+    pub qty: Decimal,
+    pub price: Decimal,
+}
+
+impl MarginPosition {
+    fn notional(&self) -> Decimal {
+        self.qty * self.price
+    }
+}
+
+/// Maintenance margin of `positions` in percent of `margin_balance`. Infinite
+/// when positions are held against an empty or negative balance.
+pub fn margin_ratio_pct(
+    positions: &[MarginPosition],
+    margin_balance: Decimal,
+    futures: &BinanceFuturesConfig,
+) -> f64 {
+    let maintenance: Decimal = positions
+        .iter()
+        .map(|p| maintenance_margin(p.notional(), futures))
+        .sum();
+    if maintenance.is_zero() {
+        return 0.0;
+    }
+    if margin_balance <= Decimal::ZERO {
+        return f64::INFINITY;
+    }
+    float(maintenance / margin_balance) * 100.0
+}
+
+/// Quantities to sell, largest positions first, so the maintenance margin of
+/// what is left is `target_pct` of `margin_balance`. Closing a position
+/// realizes its P&L into the wallet, so the margin balance stays as it is.
+pub fn deleverage_plan(
+    positions: &[MarginPosition],
+    margin_balance: Decimal,
+    futures: &BinanceFuturesConfig,
+    target_pct: f64,
+) -> Vec<(String, Decimal)> {
+    let rate = maintenance_margin(Decimal::ONE, futures);
+    if rate <= Decimal::ZERO {
+        return Vec::new();
+    }
+    let allowed = (margin_balance * dec(target_pct) / Decimal::ONE_HUNDRED).max(Decimal::ZERO);
+    let notional: Decimal = positions.iter().map(MarginPosition::notional).sum();
+    let mut excess = notional - allowed / rate;
+    if excess <= Decimal::ZERO {
+        return Vec::new();
+    }
+
+    let mut by_size: Vec<&MarginPosition> = positions
+        .iter()
+        .filter(|p| p.notional() > Decimal::ZERO)
+        .collect();
+    by_size.sort_by_key(|p| std::cmp::Reverse(p.notional()));
+    let mut plan = Vec::new();
+    for position in by_size {
+        if excess <= Decimal::ZERO {
+            break;
+        }
+        let qty = if position.notional() <= excess {
+            position.qty
+        } else {
+            excess / position.price
+        };
+        excess -= position.notional().min(excess);
+        plan.push((position.symbol.clone(), qty));
+    }
+    plan
+}
+
+/// Watches the account margin ratio and deleverages ahead of a margin call.
+pub struct MarginMonitor {
+    event_bus: EventBus,
+    exchange: Arc<dyn TradingApi>,
+    tracker: PositionTracker,
+    store: MarketStore,
+    symbol_meta: SymbolMetaRegistry,
+    futures: BinanceFuturesConfig,
+    config: MarginMonitorConfig,
+    market_tif: TimeInForce,
+}
+
+impl MarginMonitor {
+    pub fn new(
+        event_bus: EventBus,
+        exchange: Arc<dyn TradingApi>,
+        tracker: PositionTracker,
+        store: MarketStore,
+        symbol_meta: SymbolMetaRegistry,
+        config: &AppConfig,
+    ) -> Self {
+        let futures = config.futures().cloned().unwrap_or_default();
+        Self {
+            event_bus,
+            exchange,
+            tracker,
+            store,
+            symbol_meta,
+            config: futures.margin_monitor.clone(),
+            futures,
+            market_tif: market_tif(config),
+        }
+    }
+
+    pub fn start(self) {
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "📐 Margin monitor started (deleveraging at {:.0}% margin ratio, back to {:.0}%)",
+                self.config.deleverage_at_pct, self.config.target_pct
+            );
+            let mut check =
+                tokio::time::interval(Duration::from_secs(self.config.check_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = check.tick() => self.check().await,
+                }
+            }
+        });
+    }
+
+    /// Tracked positions at the bid (entry price until a quote arrives).
+    fn positions(&self) -> Vec<(PositionInfo, MarginPosition)> {
+        self.tracker
+            .get_all_positions()
+            .into_iter()
+            .filter(|p| p.filled_qty > Decimal::ZERO && !p.is_closing)
+            .map(|p| {
+                let price = self
+                    .store
+                    .get_latest_quote(&p.symbol)
+                    .map(|q| dec(q.bid_price))
+                    .filter(|bid| *bid > Decimal::ZERO)
+                    .unwrap_or(p.entry_price);
+                let valued = MarginPosition {
+                    symbol: p.symbol.clone(),
+                    qty: p.filled_qty,
+                    price,
+                };
+                (p, valued)
+            })
+            .collect()
+    }
+
+    pub async fn check(&self) {
+        let held = self.positions();
+        if held.is_empty() {
+            return;
+        }
+        let margin_balance = match self.exchange.get_account().await {
+            Ok(account) => match account.portfolio_value {
+                Some(balance) => balance,
+                None => return,
+            },
+            Err(e) => {
+                warn!("⚠️ [MARGIN] Cannot read margin balance: {}", e);
+                return;
+            }
+        };
+        let valued: Vec<MarginPosition> = held.iter().map(|(_, v)| v.clone()).collect();
+        let ratio = margin_ratio_pct(&valued, margin_balance, &self.futures);
+        if ratio < self.config.deleverage_at_pct {
+            return;
+        }
+        warn!(
+            "🚨 [MARGIN] Margin ratio {:.1}% (balance ${:.2}) at or above {:.0}% - deleveraging to {:.0}%",
+            ratio, margin_balance, self.config.deleverage_at_pct, self.config.target_pct
+        );
+        for (symbol, qty) in deleverage_plan(
+            &valued,
+            margin_balance,
+            &self.futures,
+            self.config.target_pct,
+        ) {
+            let Some((position, valued)) = held.iter().find(|(p, _)| p.symbol == symbol) else {
+                continue;
+            };
+            self.reduce(position.clone(), qty, valued.price, ratio)
+                .await;
+        }
+    }
+
+    /// Sell `qty` of `position` at market once its orders are out of the way.
+    async fn reduce(&self, mut position: PositionInfo, qty: Decimal, price: Decimal, ratio: f64) {
+        let symbol = position.symbol.clone();
+        // Keep the position monitor's exits off the position meanwhile
+        self.tracker.mark_closing(&symbol);
+        self.tracker
+            .cancel_working_entries(&symbol, self.exchange.as_ref())
+            .await;
+        let mut held = position.filled_qty;
+        for (leg, order_id) in [
+            ("TP", position.open_order_id.clone()),
+            ("stop", position.stop_order_id.clone()),
+        ] {
+            let Some(order_id) = order_id else {
+                continue;
+            };
+            let (outcome, filled) =
+                PositionMonitor::cancel_exit_leg(&order_id, leg, self.exchange.as_ref()).await;
+            match outcome {
+                OcoOutcome::Retry => {
+                    warn!(
+                        "⚠️ [MARGIN] {} leg {} of {} unconfirmed - not reducing it this round",
+                        leg, order_id, symbol
+                    );
+                    self.tracker.add_position(position);
+                    return;
+                }
+                OcoOutcome::TakeProfitFilled | OcoOutcome::StopMayFire => {
+                    self.tracker.remove_pending_order(&order_id);
+                    held = (held - filled).max(Decimal::ZERO);
+                }
+            }
+        }
+        position.open_order_id = None;
+        position.stop_order_id = None;
+        position.remaining_qty = Decimal::ZERO;
+        if held <= Decimal::ZERO {
+            info!("📐 [MARGIN] {} closed by its exit leg", symbol);
+            self.tracker.remove_position(&symbol);
+            return;
+        }
+        position.set_filled_qty(held);
+
+        let req = PlaceOrderRequest {
+            symbol: symbol.clone(),
+            side: Side::Sell,
+            order_type: OrderType::Market,
+            qty: Some(qty.min(held)),
+            notional: None,
+            limit_price: None,
+            time_in_force: self.market_tif,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        let Some(req) =
+            normalize_order(&self.symbol_meta, self.exchange.as_ref(), req, price).await
+        else {
+            // Back under the monitor, which re-places its TP leg
+            self.tracker.add_position(position);
+            return;
+        };
+        let sold = req.qty.unwrap_or(qty).min(held);
+        match self.exchange.submit_order(req).await {
+            Ok(ack) => {
+                let left = held - sold;
+                warn!(
+                    "📐 [MARGIN] Deleveraged {}: sold {} @ ${:.4} ({} left, order {})",
+                    symbol, sold, price, left, ack.id
+                );
+                let report = ExecutionReport {
+                    symbol: symbol.clone(),
+                    order_id: ack.id.clone(),
+                    // Booked as a partial exit so the reporter keeps the rest
+                    // open (futures have no user-data stream to report fills)
+                    status: if left > Decimal::ZERO {
+                        "partial_fill".to_string()
+                    } else {
+                        ack.status.clone()
+                    },
+                    side: "sell".to_string(),
+                    price: Some(price),
+                    qty: Some(sold),
+                    signal_price: None,
+                    limit_price: None,
+                    strategy_id: None,
+                    correlation_id: position.correlation_id.clone(),
+                    metadata: HashMap::new(),
+                };
+                if left > Decimal::ZERO {
+                    position.set_filled_qty(left);
+                    self.tracker.add_position(position);
+                } else {
+                    self.tracker.remove_position(&symbol);
+                }
+                self.event_bus.publish(Event::Execution(report)).ok();
+                self.event_bus
+                    .publish(Event::Deleverage(DeleverageEvent {
+                        symbol,
+                        qty: sold,
+                        price,
+                        margin_ratio_pct: ratio,
+                        target_pct: self.config.target_pct,
+                        order_id: ack.id,
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                    }))
+                    .ok();
+            }
+            Err(e) => {
+                error!("❌ [MARGIN] Deleverage sell of {} failed: {}", symbol, e);
+                self.tracker.add_position(position);
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Unit tests for the futures margin monitor - margin ratio, the deleverage
+//! plan and the partial close sequence.
+
+#[cfg(test)]
+mod margin_monitor_tests {
+    use std::sync::Arc;
+
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, BinanceFuturesConfig};
+    use crate::data::store::{MarketStore, Quote};
+    use crate::events::Event;
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::symbol_meta::SymbolMetaRegistry;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::margin_monitor::*;
+    use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn futures() -> BinanceFuturesConfig {
+        BinanceFuturesConfig {
+            enabled: true,
+            maintenance_margin_pct: 0.5,
+            ..BinanceFuturesConfig::default()
+        }
+    }
+
+    fn held(symbol: &str, qty: f64, price: f64) -> MarginPosition {
+        MarginPosition {
+            symbol: symbol.to_string(),
+            qty: dec(qty),
+            price: dec(price),
+        }
+    }
+
+    #[test]
+    fn test_margin_ratio() {
+        // 200 notional at 0.5% = 1 maintenance margin on a 10 balance
+        let positions = [held("BTC/USDT", 2.0, 100.0)];
+        assert!((margin_ratio_pct(&positions, dec(10.0), &futures()) - 10.0).abs() < 1e-9);
+        assert_eq!(margin_ratio_pct(&[], dec(10.0), &futures()), 0.0);
+        assert!(margin_ratio_pct(&positions, dec(-1.0), &futures()).is_infinite());
+    }
+
+    #[test]
+    fn test_plan_reduces_largest_position_to_target() {
+        // 1400 notional -> 7 maintenance on 10 = 70%; 50% allows 1000 notional
+        let positions = [held("ETH/USDT", 4.0, 100.0), held("BTC/USDT", 10.0, 100.0)];
+        let plan = deleverage_plan(&positions, dec(10.0), &futures(), 50.0);
+        assert_eq!(plan, vec![("BTC/USDT".to_string(), dec(4.0))]);
+
+        // 15% allows 300: all of BTC, then 100 of ETH
+        let plan = deleverage_plan(&positions, dec(10.0), &futures(), 15.0);
+        assert_eq!(
+            plan,
+            vec![
+                ("BTC/USDT".to_string(), dec(10.0)),
+                ("ETH/USDT".to_string(), dec(1.0)),
+            ]
+        );
+
+        // Already below target
+        assert!(deleverage_plan(&positions, dec(100.0), &futures(), 50.0).is_empty());
+    }
+
+    #[test]
+    fn test_plan_closes_everything_without_margin_balance() {
+        let positions = [held("ETH/USDT", 4.0, 100.0), held("BTC/USDT", 10.0, 100.0)];
+        let plan = deleverage_plan(&positions, dec(-5.0), &futures(), 50.0);
+        assert_eq!(
+            plan,
+            vec![
+                ("BTC/USDT".to_string(), dec(10.0)),
+                ("ETH/USDT".to_string(), dec(4.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_symbol_leverage_overrides_default() {
+        let mut futures = futures();
+        futures.leverage = 3;
+        futures.symbol_leverage.insert("ETH/USDT".to_string(), 10);
+        assert_eq!(futures.leverage_for("ETH/USDT"), 10);
+        assert_eq!(futures.leverage_for("BTC/USDT"), 3);
+        futures.symbol_leverage.insert("SOL/USDT".to_string(), 0);
+        assert_eq!(futures.leverage_for("SOL/USDT"), 1);
+    }
+
+    fn futures_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "binance"
+symbols: ["BTC/USDT"]
+defaults: { take_profit_pct: 1.0, stop_loss_pct: 0.5, min_order_amount: 10.0, max_order_amount: 100.0 }
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft: { evaluate_every_quotes: 5, min_edge_bps: 10.0, take_profit_bps: 50.0, stop_loss_bps: 25.0, max_spread_bps: 30.0 }
+hybrid: { gate_refresh_quotes: 100, no_trade_cooldown_quotes: 50 }
+llm: { api_key: null, base_url: null, model: "test-model" }
+alpaca: { api_key: "K", secret_key: "S", base_url: "https://paper-api.alpaca.markets" }
+binance:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://api.binance.com"
+  futures:
+    enabled: true
+    maintenance_margin_pct: 0.5
+    margin_monitor: { enabled: true, deleverage_at_pct: 70.0, target_pct: 50.0 }
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn tracked(tracker: &PositionTracker, symbol: &str, qty: f64) {
+        tracker.add_position(PositionInfo {
+            symbol: symbol.to_string(),
+            entry_price: dec(100.0),
+            qty: dec(qty),
+            filled_qty: dec(qty),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(98.0),
+            take_profit: dec(102.0),
+            entry_time: chrono::Utc::now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: Some("tp-1".to_string()),
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(100.0),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(98.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: Some("corr-1".to_string()),
+        });
+        tracker.add_pending_order(PendingOrder {
+            order_id: "tp-1".to_string(),
+            symbol: symbol.to_string(),
+            side: "sell".to_string(),
+            limit_price: dec(102.0),
+            qty: dec(qty),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(qty),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            stop_loss: None,
+            take_profit: None,
+            last_check_time: None,
+            bracket: false,
+            correlation_id: None,
+        });
+    }
+
+    #[tokio::test]
+    async fn test_partial_close_cancels_tp_leg_then_sells_the_excess() {
+        // 1000 notional -> 5 maintenance on 6 = 83%; 50% allows 600 notional
+        let venue = Arc::new(
+            MockExchange::new("margin")
+                .with_account(AccountSummary {
+                    buying_power: None,
+                    cash: None,
+                    portfolio_value: Some(dec(6.0)),
+                })
+                .on_get_order(|_, id| Ok(ack(id, "canceled", json!({ "filled_qty": "0" }))))
+                .on_submit(|_, _| Ok(ack("reduce-1", "new", json!({})))),
+        );
+        let tracker = PositionTracker::new();
+        tracked(&tracker, "BTC/USDT", 10.0);
+        let store = MarketStore::new(10);
+        store.update_quote(
+            "BTC/USDT".to_string(),
+            Quote {
+                symbol: "BTC/USDT".to_string(),
+                bid_price: 100.0,
+                ask_price: 100.1,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+
+        MarginMonitor::new(
+            bus.clone(),
+            venue.clone(),
+            tracker.clone(),
+            store,
+            SymbolMetaRegistry::new(),
+            &futures_config(),
+        )
+        .check()
+        .await;
+
+        let calls = venue.calls();
+        assert_eq!(calls, vec!["cancel tp-1", "Sell BTC/USDT 4"]);
+        let position = tracker.get_position("BTC/USDT").unwrap();
+        assert_eq!(position.filled_qty, dec(6.0));
+        assert_eq!(position.qty, dec(6.0));
+        // Left for the position monitor to re-place at the new size
+        assert!(position.open_order_id.is_none());
+        assert!(!position.is_closing);
+        assert!(tracker.get_all_pending_orders().is_empty());
+
+        let Ok(Event::Execution(exec)) = rx.try_recv() else {
+            panic!("reduction was not published");
+        };
+        assert_eq!(exec.status, "partial_fill");
+        assert_eq!(exec.qty, Some(dec(4.0)));
+        assert_eq!(exec.correlation_id.as_deref(), Some("corr-1"));
+        let Ok(Event::Deleverage(event)) = rx.try_recv() else {
+            panic!("deleverage was not published");
+        };
+        assert_eq!(event.qty, dec(4.0));
+        assert!(event.margin_ratio_pct > 80.0);
+    }
+}
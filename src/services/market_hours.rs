@@ -0,0 +1,149 @@
+//! Trading calendar for stock sessions.
+//!
+//! With `trading_mode: stocks` the `StrategyEngine` only evaluates symbols
+//! while the market is open: the regular session (09:30-16:00 US Eastern) on
+//! weekdays that are not in `market_hours.holidays`, plus the pre-market
+//! (04:00) and after-hours (to 20:00) sessions with `extended_hours`. Days in
+//! `early_closes` end at 13:00 (17:00 after hours). With
+//! `flatten_before_close_mins` entries stop that long before the last traded
+//! session closes and the position monitor closes every open position, so
+//! nothing is held overnight. Crypto and futures trade 24/7 and bypass it.
+//!
+//! Eastern time is derived from UTC with the US daylight-saving rule (EDT from
+//! the second Sunday of March to the first Sunday of November).
+
+use std::collections::HashSet;
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+
+use crate::config::MarketHoursConfig;
+
+/// Where the exchange's trading day is at a given moment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketSession {
+    PreMarket,
+    Regular,
+    AfterHours,
+    Closed,
+}
+
+fn hm(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+/// `n`th Sunday (1-based) of `month`.
+fn nth_sunday(year: i32, month: u32, n: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n as u8).unwrap()
+}
+
+/// US Eastern UTC offset at `utc`: EDT (-4h) from 02:00 EST on the second
+/// Sunday of March until 02:00 EDT on the first Sunday of November, else EST.
+pub fn eastern_offset(utc: DateTime<Utc>) -> FixedOffset {
+    let year = utc.year();
+    // 02:00 EST = 07:00 UTC, 02:00 EDT = 06:00 UTC
+    let dst_start = nth_sunday(year, 3, 2).and_time(hm(7, 0)).and_utc();
+    let dst_end = nth_sunday(year, 11, 1).and_time(hm(6, 0)).and_utc();
+    let hours = if utc >= dst_start && utc < dst_end {
+        -4
+    } else {
+        -5
+    };
+    FixedOffset::east_opt(hours * 3600).unwrap()
+}
+
+/// Exchange-local (US Eastern) wall-clock time of `utc`.
+pub fn eastern_time(utc: DateTime<Utc>) -> NaiveDateTime {
+    utc.with_timezone(&eastern_offset(utc)).naive_local()
+}
+
+pub struct TradingCalendar {
+    always_open: bool,
+    extended_hours: bool,
+    holidays: HashSet<NaiveDate>,
+    early_closes: HashSet<NaiveDate>,
+    flatten_before_close: Duration,
+}
+
+impl TradingCalendar {
+    pub fn new(trading_mode: &str, config: &MarketHoursConfig) -> Self {
+        Self {
+            always_open: !config.enabled || !trading_mode.eq_ignore_ascii_case("stocks"),
+            extended_hours: config.extended_hours,
+            holidays: config.holidays.iter().copied().collect(),
+            early_closes: config.early_closes.iter().copied().collect(),
+            flatten_before_close: Duration::minutes(config.flatten_before_close_mins as i64),
+        }
+    }
+
+    /// Whether positions are closed ahead of the session close.
+    pub fn flattens(&self) -> bool {
+        !self.always_open && self.flatten_before_close > Duration::zero()
+    }
+
+    pub fn session(&self, now: DateTime<Utc>) -> MarketSession {
+        if self.always_open {
+            return MarketSession::Regular;
+        }
+        let local = eastern_time(now);
+        let Some((close, after_close)) = self.closes(local.date()) else {
+            return MarketSession::Closed;
+        };
+        let time = local.time();
+        if time < hm(4, 0) {
+            MarketSession::Closed
+        } else if time < hm(9, 30) {
+            MarketSession::PreMarket
+        } else if time < close {
+            MarketSession::Regular
+        } else if time < after_close {
+            MarketSession::AfterHours
+        } else {
+            MarketSession::Closed
+        }
+    }
+
+    /// In a session the bot trades (regular, or extended with `extended_hours`).
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        match self.session(now) {
+            MarketSession::Regular => true,
+            MarketSession::PreMarket | MarketSession::AfterHours => self.extended_hours,
+            MarketSession::Closed => false,
+        }
+    }
+
+    /// Open, and not yet in the pre-close flatten window.
+    pub fn accepts_entries(&self, now: DateTime<Utc>) -> bool {
+        self.is_open(now) && !self.should_flatten(now)
+    }
+
+    /// Within `flatten_before_close_mins` of today's last traded close.
+    pub fn should_flatten(&self, now: DateTime<Utc>) -> bool {
+        if !self.flattens() || !self.is_open(now) {
+            return false;
+        }
+        let local = eastern_time(now);
+        let Some((close, after_close)) = self.closes(local.date()) else {
+            return false;
+        };
+        let last_close = if self.extended_hours {
+            after_close
+        } else {
+            close
+        };
+        local >= local.date().and_time(last_close) - self.flatten_before_close
+    }
+
+    /// Regular and after-hours close of `date`; `None` when the market is shut.
+    fn closes(&self, date: NaiveDate) -> Option<(NaiveTime, NaiveTime)> {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) || self.holidays.contains(&date) {
+            return None;
+        }
+        if self.early_closes.contains(&date) {
+            Some((hm(13, 0), hm(17, 0)))
+        } else {
+            Some((hm(16, 0), hm(20, 0)))
+        }
+    }
+}
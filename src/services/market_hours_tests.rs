@@ -0,0 +1,111 @@
+//! Unit tests for the stock trading calendar.
+
+#[cfg(test)]
+mod market_hours_tests {
+    use crate::config::MarketHoursConfig;
+    use crate::services::market_hours::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn utc(y: i32, m: u32, d: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, hour, minute, 0).unwrap()
+    }
+
+    fn calendar(yaml: &str) -> TradingCalendar {
+        let config: MarketHoursConfig = serde_yaml::from_str(yaml).unwrap();
+        TradingCalendar::new("stocks", &config)
+    }
+
+    // ============= Time Zone Tests =============
+
+    #[test]
+    fn test_eastern_offset_follows_us_dst() {
+        let hours = |t| eastern_offset(t).local_minus_utc() / 3600;
+        // 2026: EDT from March 8 to November 1
+        assert_eq!(hours(utc(2026, 3, 8, 6, 59)), -5);
+        assert_eq!(hours(utc(2026, 3, 8, 7, 0)), -4);
+        assert_eq!(hours(utc(2026, 11, 1, 5, 59)), -4);
+        assert_eq!(hours(utc(2026, 11, 1, 6, 0)), -5);
+        assert_eq!(hours(utc(2027, 1, 4, 15, 0)), -5);
+    }
+
+    // ============= Session Tests =============
+
+    #[test]
+    fn test_sessions_on_a_regular_day() {
+        let cal = TradingCalendar::new("stocks", &MarketHoursConfig::default());
+        // Friday 2026-10-16, EDT
+        assert_eq!(cal.session(utc(2026, 10, 16, 7, 59)), MarketSession::Closed);
+        assert_eq!(
+            cal.session(utc(2026, 10, 16, 8, 0)),
+            MarketSession::PreMarket
+        );
+        assert_eq!(
+            cal.session(utc(2026, 10, 16, 13, 30)),
+            MarketSession::Regular
+        );
+        assert_eq!(
+            cal.session(utc(2026, 10, 16, 20, 0)),
+            MarketSession::AfterHours
+        );
+        assert_eq!(cal.session(utc(2026, 10, 17, 0, 0)), MarketSession::Closed);
+        assert!(!cal.is_open(utc(2026, 10, 16, 13, 29)));
+        assert!(cal.is_open(utc(2026, 10, 16, 19, 59)));
+        assert!(!cal.is_open(utc(2026, 10, 16, 20, 0)));
+        // Saturday
+        assert_eq!(cal.session(utc(2026, 10, 17, 15, 0)), MarketSession::Closed);
+    }
+
+    #[test]
+    fn test_holidays_early_closes_and_extended_hours() {
+        let cal = TradingCalendar::new("stocks", &MarketHoursConfig::default());
+        // Thanksgiving, then the 13:00 EST early close the day after
+        assert_eq!(cal.session(utc(2026, 11, 26, 16, 0)), MarketSession::Closed);
+        assert!(cal.is_open(utc(2026, 11, 27, 17, 59)));
+        assert_eq!(
+            cal.session(utc(2026, 11, 27, 18, 0)),
+            MarketSession::AfterHours
+        );
+
+        let extended = calendar("extended_hours: true\nholidays: [\"2026-10-19\"]\n");
+        assert!(extended.is_open(utc(2026, 10, 16, 8, 0)));
+        assert!(extended.is_open(utc(2026, 10, 16, 23, 59)));
+        // Overriding the holidays replaces the defaults
+        assert!(extended.is_open(utc(2026, 11, 26, 16, 0)));
+        assert!(!extended.is_open(utc(2026, 10, 19, 16, 0)));
+    }
+
+    #[test]
+    fn test_crypto_and_disabled_calendar_are_always_open() {
+        let saturday = utc(2026, 10, 17, 3, 0);
+        let config = MarketHoursConfig::default();
+        assert!(TradingCalendar::new("crypto", &config).accepts_entries(saturday));
+
+        let config: MarketHoursConfig =
+            serde_yaml::from_str("enabled: false\nflatten_before_close_mins: 15\n").unwrap();
+        let cal = TradingCalendar::new("stocks", &config);
+        assert!(cal.accepts_entries(saturday));
+        assert!(!cal.flattens());
+    }
+
+    // ============= Flatten Tests =============
+
+    #[test]
+    fn test_flatten_window_before_close() {
+        let cal = calendar("flatten_before_close_mins: 15\n");
+        assert!(cal.flattens());
+        assert!(cal.accepts_entries(utc(2026, 10, 16, 19, 44)));
+        assert!(!cal.should_flatten(utc(2026, 10, 16, 19, 44)));
+        assert!(cal.should_flatten(utc(2026, 10, 16, 19, 45)));
+        assert!(!cal.accepts_entries(utc(2026, 10, 16, 19, 45)));
+        // Closed: nothing left to flatten
+        assert!(!cal.should_flatten(utc(2026, 10, 16, 20, 0)));
+        // Early close
+        assert!(cal.should_flatten(utc(2026, 11, 27, 17, 45)));
+
+        // With extended hours the after-hours close is the last one
+        let cal = calendar("extended_hours: true\nflatten_before_close_mins: 15\n");
+        assert!(!cal.should_flatten(utc(2026, 10, 16, 19, 45)));
+        assert!(cal.should_flatten(utc(2026, 10, 16, 23, 45)));
+        assert!(!TradingCalendar::new("stocks", &MarketHoursConfig::default()).flattens());
+    }
+}
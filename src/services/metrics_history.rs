@@ -0,0 +1,238 @@
+//! Append-only metrics history.
+//!
+//! Every `metrics_history.sample_interval_secs` the recorder appends one sample
+//! per metric to `<dir>/<metric>.bin`. Records are fixed 16 bytes (unix seconds
+//! as i64, value as f64, little-endian), so the latest N points are read with a
+//! single seek and a torn final record after a crash is simply ignored.
+//! `GET /charts/{metric}.svg` renders them (see `services/charts.rs`).
+
+use std::fs::OpenOptions;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::AppConfig;
+use crate::data::tick_filter::TickFilter;
+use crate::events::{AccountEvent, Event};
+use crate::exchange::time_sync::ServerClock;
+use crate::llm::LLMQueue;
+use crate::money;
+use crate::services::position_monitor::PositionTracker;
+
+/// Recorded series, in chart index order.
+pub const METRICS: [&str; 6] = [
+    "equity",
+    "open_positions",
+    "llm_latency_ms",
+    "event_rate",
+    "clock_offset_ms",
+    "rejected_ticks",
+];
+
+const RECORD_LEN: u64 = 16;
+
+/// On-disk series under one directory.
+#[derive(Clone, Debug)]
+pub struct MetricsHistory {
+    dir: PathBuf,
+}
+
+impl MetricsHistory {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn is_known(metric: &str) -> bool {
+        METRICS.contains(&metric)
+    }
+
+    fn path(&self, metric: &str) -> PathBuf {
+        self.dir.join(format!("{}.bin", metric))
+    }
+
+    /// Append one sample.
+    pub fn append(&self, metric: &str, ts: i64, value: f64) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let mut record = [0u8; RECORD_LEN as usize];
+        record[..8].copy_from_slice(&ts.to_le_bytes());
+        record[8..].copy_from_slice(&value.to_le_bytes());
+        let mut f = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(metric))?;
+        f.write_all(&record)
+    }
+
+    /// The most recent `max_points` samples, oldest first (empty if never recorded).
+    pub fn read_tail(&self, metric: &str, max_points: usize) -> io::Result<Vec<(i64, f64)>> {
+        let mut f = match std::fs::File::open(self.path(metric)) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let records = f.metadata()?.len() / RECORD_LEN;
+        let take = records.min(max_points as u64);
+        f.seek(SeekFrom::Start((records - take) * RECORD_LEN))?;
+
+        let mut buf = vec![0u8; (take * RECORD_LEN) as usize];
+        f.read_exact(&mut buf)?;
+        Ok(buf
+            .chunks_exact(RECORD_LEN as usize)
+            .map(|r| {
+                let ts = i64::from_le_bytes(r[..8].try_into().unwrap());
+                let value = f64::from_le_bytes(r[8..].try_into().unwrap());
+                (ts, value)
+            })
+            .collect())
+    }
+}
+
+/// Values for one sampling tick. Metrics without a reading yet are omitted.
+pub fn sample_values(
+    equity: Option<f64>,
+    open_positions: usize,
+    llm_latency_ms: Option<f64>,
+    events: u64,
+    elapsed: Duration,
+    clock_offset_ms: Option<i64>,
+    rejected_ticks: Option<u64>,
+) -> Vec<(&'static str, f64)> {
+    let mut values = Vec::with_capacity(METRICS.len());
+    if let Some(equity) = equity {
+        values.push(("equity", equity));
+    }
+    values.push(("open_positions", open_positions as f64));
+    if let Some(latency) = llm_latency_ms {
+        values.push(("llm_latency_ms", latency));
+    }
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        values.push(("event_rate", events as f64 / secs));
+    }
+    if let Some(offset) = clock_offset_ms {
+        values.push(("clock_offset_ms", offset as f64));
+    }
+    if let Some(rejected) = rejected_ticks {
+        values.push(("rejected_ticks", rejected as f64));
+    }
+    values
+}
+
+/// Samples the running session into a `MetricsHistory`.
+#[derive(Clone)]
+pub struct MetricsRecorder {
+    history: MetricsHistory,
+    event_bus: EventBus,
+    llm: LLMQueue,
+    tracker: PositionTracker,
+    interval: Duration,
+    /// Venue clock whose measured offset is charted
+    clock: Option<ServerClock>,
+    /// Quotes dropped per interval are charted from its counter
+    tick_filter: Option<TickFilter>,
+}
+
+impl MetricsRecorder {
+    pub fn new(
+        event_bus: EventBus,
+        config: &AppConfig,
+        llm: LLMQueue,
+        tracker: PositionTracker,
+    ) -> Self {
+        Self {
+            history: MetricsHistory::new(&config.metrics_history.dir),
+            event_bus,
+            llm,
+            tracker,
+            interval: Duration::from_secs(config.metrics_history.sample_interval_secs.max(1)),
+            clock: None,
+            tick_filter: None,
+        }
+    }
+
+    /// Chart the venue clock offset measured by `time_sync`.
+    pub fn with_server_clock(mut self, clock: Option<ServerClock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Chart quotes dropped by the store's tick filter.
+    pub fn with_tick_filter(mut self, filter: TickFilter) -> Self {
+        self.tick_filter = Some(filter);
+        self
+    }
+
+    /// Count bus events and track equity; append a sample every interval.
+    pub async fn start(&self) {
+        let mut rx = self.event_bus.subscribe("metrics_history");
+        let recorder = self.clone();
+        let shutdown = self.event_bus.shutdown().clone();
+
+        tokio::spawn(async move {
+            info!(
+                "📈 [METRICS] Recording every {}s",
+                recorder.interval.as_secs()
+            );
+            let mut ticker = tokio::time::interval(recorder.interval);
+            ticker.tick().await;
+            let mut equity: Option<f64> = None;
+            let mut events: u64 = 0;
+            let mut window_start = Instant::now();
+            let mut rejected_seen = recorder
+                .tick_filter
+                .as_ref()
+                .map_or(0, |f| f.rejected_total());
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = ticker.tick() => {
+                        let rejected = recorder.tick_filter.as_ref().map(|f| {
+                            let total = f.rejected_total();
+                            let delta = total.saturating_sub(rejected_seen);
+                            rejected_seen = total;
+                            delta
+                        });
+                        let values = sample_values(
+                            equity,
+                            recorder.tracker.get_all_positions().len(),
+                            recorder.llm.latency_ms(),
+                            events,
+                            window_start.elapsed(),
+                            recorder.clock.as_ref().and_then(|c| c.offset_ms()),
+                            rejected,
+                        );
+                        events = 0;
+                        window_start = Instant::now();
+                        let ts = chrono::Utc::now().timestamp();
+                        for (metric, value) in values {
+                            if let Err(e) = recorder.history.append(metric, ts, value) {
+                                warn!("⚠️ [METRICS] Failed to append {}: {}", metric, e);
+                            }
+                        }
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => {
+                            events += 1;
+                            if let Event::Account(AccountEvent::BalanceUpdated {
+                                cash,
+                                portfolio_value,
+                                ..
+                            }) = event
+                            {
+                                if let Some(value) = portfolio_value.or(cash) {
+                                    equity = Some(money::float(value));
+                                }
+                            }
+                        }
+                        // Missed events still happened
+                        Err(RecvError::Lagged(n)) => events += n,
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+}
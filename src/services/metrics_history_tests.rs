@@ -0,0 +1,103 @@
+//! Unit tests for the append-only metrics history (record format, tail reads, sampling).
+
+#[cfg(test)]
+mod metrics_history_tests {
+    use crate::services::metrics_history::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("autohedge_metrics_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    // ============= Storage Tests =============
+
+    #[test]
+    fn test_append_and_read_tail() {
+        let dir = temp_dir("tail");
+        let history = MetricsHistory::new(&dir);
+        for i in 0..5 {
+            history
+                .append("equity", 1_000 + i, 100.0 + i as f64)
+                .unwrap();
+        }
+        assert_eq!(
+            std::fs::metadata(dir.join("equity.bin")).unwrap().len(),
+            5 * 16
+        );
+
+        let all = history.read_tail("equity", 100).unwrap();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[0], (1_000, 100.0));
+
+        let last_two = history.read_tail("equity", 2).unwrap();
+        assert_eq!(last_two, vec![(1_003, 103.0), (1_004, 104.0)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_series_is_empty() {
+        let history = MetricsHistory::new(temp_dir("missing"));
+        assert!(history.read_tail("equity", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_torn_record_is_ignored() {
+        let dir = temp_dir("torn");
+        let history = MetricsHistory::new(&dir);
+        history.append("event_rate", 1, 2.5).unwrap();
+        history.append("event_rate", 2, 3.5).unwrap();
+        // Simulate a crash halfway through a write
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join("event_rate.bin"))
+            .unwrap();
+        f.write_all(&[1, 2, 3]).unwrap();
+
+        let points = history.read_tail("event_rate", 10).unwrap();
+        assert_eq!(points, vec![(1, 2.5), (2, 3.5)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_known_metrics() {
+        for metric in METRICS {
+            assert!(MetricsHistory::is_known(metric));
+        }
+        assert!(!MetricsHistory::is_known("../config"));
+    }
+
+    // ============= Sampling Tests =============
+
+    #[test]
+    fn test_sample_values() {
+        let values = sample_values(
+            Some(1000.0),
+            3,
+            Some(250.0),
+            120,
+            Duration::from_secs(60),
+            Some(-350),
+            Some(4),
+        );
+        assert_eq!(
+            values,
+            vec![
+                ("equity", 1000.0),
+                ("open_positions", 3.0),
+                ("llm_latency_ms", 250.0),
+                ("event_rate", 2.0),
+                ("clock_offset_ms", -350.0),
+                ("rejected_ticks", 4.0),
+            ]
+        );
+
+        // No equity, LLM, clock or tick filter reading yet
+        let values = sample_values(None, 0, None, 0, Duration::from_secs(60), None, None);
+        assert_eq!(values, vec![("open_positions", 0.0), ("event_rate", 0.0)]);
+    }
+}
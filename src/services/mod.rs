@@ -1,16 +1,145 @@
+pub mod adaptive;
+pub mod admission;
+pub mod api_auth;
+pub mod arbitrage;
+pub mod balance_audit;
+pub mod bulk_close;
+pub mod cancel_on_disconnect;
+pub mod capital;
+pub mod charts;
+pub mod circuit_breaker;
+pub mod conditional_orders;
+pub mod config_reload;
+pub mod daily_report;
+pub mod diagnostics;
+pub mod drawdown_sizing;
+pub mod equity;
+pub mod event_log;
 pub mod execution;
 pub mod execution_fast;
 pub mod execution_utils;
+pub mod halt;
+pub mod health;
+pub mod hedging;
+pub mod history_bootstrap;
 pub mod keep_alive;
+pub mod llm_fallback;
+pub mod margin_monitor;
+pub mod market_hours;
+pub mod metrics_history;
+pub mod notifications;
+pub mod optimizer;
+pub mod pairs;
+pub mod policy;
 pub mod position_monitor;
+pub mod pretrade;
+pub mod regime;
 pub mod reporting;
 pub mod risk;
+pub mod risk_guard;
+pub mod sentiment;
+pub mod shadow;
+pub mod signal_gate;
+pub mod stale_data;
+pub mod storage;
 pub mod strategy;
+pub mod strategy_registry;
+pub mod strategy_workers;
+pub mod synthetic_fill;
+pub mod trade_journal;
+pub mod trade_log;
+pub mod watchdog;
 pub mod websocket_service;
 
+#[cfg(test)]
+mod adaptive_tests;
+#[cfg(test)]
+mod admission_tests;
+#[cfg(test)]
+mod api_auth_tests;
+#[cfg(test)]
+mod arbitrage_tests;
+#[cfg(test)]
+mod balance_audit_tests;
+#[cfg(test)]
+mod bulk_close_tests;
+#[cfg(test)]
+mod cancel_on_disconnect_tests;
+#[cfg(test)]
+mod capital_tests;
+#[cfg(test)]
+mod charts_tests;
+#[cfg(test)]
+mod circuit_breaker_tests;
+#[cfg(test)]
+mod conditional_orders_tests;
+#[cfg(test)]
+mod config_reload_tests;
+#[cfg(test)]
+mod daily_report_tests;
+#[cfg(test)]
+mod diagnostics_tests;
+#[cfg(test)]
+mod drawdown_sizing_tests;
+#[cfg(test)]
+mod equity_tests;
+#[cfg(test)]
+mod event_log_tests;
 #[cfg(test)]
 mod execution_utils_tests;
 #[cfg(test)]
+mod halt_tests;
+#[cfg(test)]
+mod health_tests;
+#[cfg(test)]
+mod hedging_tests;
+#[cfg(test)]
+mod history_bootstrap_tests;
+#[cfg(test)]
+mod llm_fallback_tests;
+#[cfg(test)]
+mod margin_monitor_tests;
+#[cfg(test)]
+mod market_hours_tests;
+#[cfg(test)]
+mod metrics_history_tests;
+#[cfg(test)]
+mod notifications_tests;
+#[cfg(test)]
+mod optimizer_tests;
+#[cfg(test)]
+mod pairs_tests;
+#[cfg(test)]
+mod policy_tests;
+#[cfg(test)]
 mod position_monitor_tests;
 #[cfg(test)]
+mod pretrade_tests;
+#[cfg(test)]
+mod regime_tests;
+#[cfg(test)]
 mod reporting_tests;
+#[cfg(test)]
+mod risk_guard_tests;
+#[cfg(test)]
+mod sentiment_tests;
+#[cfg(test)]
+mod shadow_tests;
+#[cfg(test)]
+mod signal_gate_tests;
+#[cfg(test)]
+mod stale_data_tests;
+#[cfg(test)]
+mod storage_tests;
+#[cfg(test)]
+mod strategy_registry_tests;
+#[cfg(test)]
+mod strategy_workers_tests;
+#[cfg(test)]
+mod synthetic_fill_tests;
+#[cfg(test)]
+mod trade_journal_tests;
+#[cfg(test)]
+mod trade_log_tests;
+#[cfg(test)]
+mod watchdog_tests;
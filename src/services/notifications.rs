@@ -0,0 +1,258 @@
+//! Operator alerts to Telegram, Discord and a generic webhook.
+//!
+//! `NotificationService` subscribes to the bus and turns order fills,
+//! stop-loss exits, circuit breaker halts and dropped streams into short text
+//! alerts, each type switchable under `notifications.events`. Alerts of one
+//! type for one symbol are spaced by `min_interval_secs` and at most
+//! `max_per_minute` go out in total, so a fill storm or a flapping socket
+//! cannot flood the channels. Delivery is best-effort and never blocks trading.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{NotificationConfig, NotificationEventsConfig};
+use crate::events::{Event, SystemEvent};
+use crate::services::risk_guard::is_stop_loss_exit;
+
+/// Alert categories, each with its own enable flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    OrderFilled,
+    StopLoss,
+    CircuitBreaker,
+    WsDisconnected,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::OrderFilled => "order_filled",
+            AlertKind::StopLoss => "stop_loss",
+            AlertKind::CircuitBreaker => "circuit_breaker",
+            AlertKind::WsDisconnected => "ws_disconnected",
+        }
+    }
+
+    pub fn enabled(&self, events: &NotificationEventsConfig) -> bool {
+        match self {
+            AlertKind::OrderFilled => events.order_filled,
+            AlertKind::StopLoss => events.stop_loss,
+            AlertKind::CircuitBreaker => events.circuit_breaker,
+            AlertKind::WsDisconnected => events.ws_disconnected,
+        }
+    }
+}
+
+/// One alert ready to send.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    /// Symbol or stream the alert is about (rate-limit key with `kind`)
+    pub subject: String,
+    pub text: String,
+}
+
+/// The alert for `event`, if it is one of the alerted types.
+pub fn alert_for(event: &Event) -> Option<Alert> {
+    match event {
+        Event::Execution(r) if r.status.eq_ignore_ascii_case("filled") || r.status == "fill" => {
+            let detail = match (r.qty, r.price) {
+                (Some(qty), Some(price)) => format!(" {} @ {}", qty, price),
+                _ => String::new(),
+            };
+            Some(Alert {
+                kind: AlertKind::OrderFilled,
+                subject: r.symbol.clone(),
+                text: format!(
+                    "✅ Filled: {} {}{}",
+                    r.side.to_uppercase(),
+                    r.symbol,
+                    detail
+                ),
+            })
+        }
+        Event::Signal(s) if is_stop_loss_exit(s) => Some(Alert {
+            kind: AlertKind::StopLoss,
+            subject: s.symbol.clone(),
+            text: format!("🛑 Stop-loss hit: {} - {}", s.symbol, s.thesis),
+        }),
+        Event::System(SystemEvent::Halt {
+            reason, flatten, ..
+        }) => Some(Alert {
+            kind: AlertKind::CircuitBreaker,
+            subject: "system".to_string(),
+            text: format!(
+                "🚨 Circuit breaker tripped: {}{}",
+                reason,
+                if *flatten {
+                    " (flattening positions)"
+                } else {
+                    ""
+                }
+            ),
+        }),
+        Event::Disconnected(d) => Some(Alert {
+            kind: AlertKind::WsDisconnected,
+            subject: d.stream.clone(),
+            text: format!("🔌 Stream disconnected: {} ({})", d.stream, d.reason),
+        }),
+        _ => None,
+    }
+}
+
+/// Per-(type, subject) spacing plus a global per-minute cap.
+pub struct AlertLimiter {
+    min_interval: Duration,
+    max_per_minute: u32,
+    last_sent: HashMap<(AlertKind, String), Instant>,
+    /// Send times within the last minute, oldest first
+    recent: VecDeque<Instant>,
+}
+
+impl AlertLimiter {
+    pub fn new(config: &NotificationConfig) -> Self {
+        Self {
+            min_interval: Duration::from_secs(config.min_interval_secs),
+            max_per_minute: config.max_per_minute,
+            last_sent: HashMap::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Whether `alert` may go out at `now`; records it if so.
+    pub fn allow(&mut self, alert: &Alert, now: Instant) -> bool {
+        let key = (alert.kind, alert.subject.clone());
+        if self
+            .last_sent
+            .get(&key)
+            .is_some_and(|at| now.saturating_duration_since(*at) < self.min_interval)
+        {
+            return false;
+        }
+        while self
+            .recent
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) >= Duration::from_secs(60))
+        {
+            self.recent.pop_front();
+        }
+        if self.max_per_minute > 0 && self.recent.len() >= self.max_per_minute as usize {
+            return false;
+        }
+        self.recent.push_back(now);
+        self.last_sent.insert(key, now);
+        true
+    }
+}
+
+/// `sendMessage` body for the Telegram Bot API.
+pub fn telegram_payload(chat_id: &str, alert: &Alert) -> Value {
+    json!({ "chat_id": chat_id, "text": alert.text })
+}
+
+/// Discord webhook body.
+pub fn discord_payload(alert: &Alert) -> Value {
+    json!({ "content": alert.text })
+}
+
+/// Generic webhook body: alert type and text plus the event's `/ws` JSON.
+pub fn webhook_payload(alert: &Alert, event: &Event) -> Value {
+    json!({
+        "type": alert.kind.as_str(),
+        "text": alert.text,
+        "event": event.to_json(),
+    })
+}
+
+/// Sends bus alerts to the configured channels.
+pub struct NotificationService {
+    config: NotificationConfig,
+    event_bus: EventBus,
+    client: Client,
+}
+
+impl NotificationService {
+    pub fn new(config: &NotificationConfig, event_bus: EventBus) -> Self {
+        Self {
+            config: config.clone(),
+            event_bus,
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to create HTTP client for notifications"),
+        }
+    }
+
+    /// (url, body) per configured channel
+    fn deliveries(&self, alert: &Alert, event: &Event) -> Vec<(String, Value)> {
+        let mut out = Vec::new();
+        if let (Some(token), Some(chat_id)) = (
+            &self.config.telegram_bot_token,
+            &self.config.telegram_chat_id,
+        ) {
+            out.push((
+                format!("https://api.telegram.org/bot{}/sendMessage", token),
+                telegram_payload(chat_id, alert),
+            ));
+        }
+        if let Some(url) = &self.config.discord_webhook_url {
+            out.push((url.clone(), discord_payload(alert)));
+        }
+        if let Some(url) = &self.config.webhook_url {
+            out.push((url.clone(), webhook_payload(alert, event)));
+        }
+        out
+    }
+
+    pub async fn start(self) {
+        let mut rx = self.event_bus.subscribe("notifications");
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🔔 [NOTIFY] Notification service started (one alert per type/symbol every {}s, max {}/min)",
+                self.config.min_interval_secs, self.config.max_per_minute
+            );
+            let mut limiter = AlertLimiter::new(&self.config);
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                let Some(alert) = alert_for(&event) else {
+                    continue;
+                };
+                if !alert.kind.enabled(&self.config.events)
+                    || !limiter.allow(&alert, Instant::now())
+                {
+                    continue;
+                }
+                for (url, body) in self.deliveries(&alert, &event) {
+                    let client = self.client.clone();
+                    let kind = alert.kind.as_str();
+                    tokio::spawn(async move {
+                        let result = client.post(&url).json(&body).send().await;
+                        match result {
+                            Ok(resp) if resp.status().is_success() => {}
+                            Ok(resp) => {
+                                warn!("⚠️ [NOTIFY] {} alert rejected: {}", kind, resp.status())
+                            }
+                            Err(e) => {
+                                warn!("⚠️ [NOTIFY] {} alert failed: {}", kind, e.without_url())
+                            }
+                        }
+                    });
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,159 @@
+//! Unit tests for operator alerts (event mapping, rate limiting, payloads).
+
+#[cfg(test)]
+mod notifications_tests {
+    use crate::config::{NotificationConfig, NotificationEventsConfig};
+    use crate::events::{AnalysisSignal, DisconnectEvent, Event, ExecutionReport, SystemEvent};
+    use crate::money::dec;
+    use crate::services::notifications::*;
+    use std::time::{Duration, Instant};
+
+    fn execution(symbol: &str, status: &str) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: "o1".to_string(),
+            status: status.to_string(),
+            side: "sell".to_string(),
+            price: Some(dec(101.5)),
+            qty: Some(dec(0.5)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn exit_signal(reason: &str) -> Event {
+        Event::Signal(AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: "sell".to_string(),
+            confidence: 1.0,
+            thesis: "Price 95.00 hit stop 95.50".to_string(),
+            market_context: format!("Reason: {}", reason),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
+        })
+    }
+
+    fn alert(kind: AlertKind, subject: &str) -> Alert {
+        Alert {
+            kind,
+            subject: subject.to_string(),
+            text: "test".to_string(),
+        }
+    }
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    // ============= Event Mapping Tests =============
+
+    #[test]
+    fn test_fills_alert_but_acks_do_not() {
+        let a = alert_for(&execution("ETH/USD", "filled")).unwrap();
+        assert_eq!(a.kind, AlertKind::OrderFilled);
+        assert_eq!(a.subject, "ETH/USD");
+        assert_eq!(a.text, "✅ Filled: SELL ETH/USD 0.5 @ 101.5");
+
+        assert!(alert_for(&execution("ETH/USD", "fill")).is_some());
+        assert!(alert_for(&execution("ETH/USD", "new")).is_none());
+        assert!(alert_for(&execution("ETH/USD", "partial_fill")).is_none());
+    }
+
+    #[test]
+    fn test_only_stop_loss_exits_alert() {
+        let a = alert_for(&exit_signal("stop_loss")).unwrap();
+        assert_eq!(a.kind, AlertKind::StopLoss);
+        assert!(alert_for(&exit_signal("take_profit")).is_none());
+        assert!(alert_for(&exit_signal("forced_close")).is_none());
+    }
+
+    #[test]
+    fn test_halt_and_disconnect_alerts() {
+        let halt = alert_for(&Event::System(SystemEvent::Halt {
+            reason: "daily loss limit".to_string(),
+            flatten: true,
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(halt.kind, AlertKind::CircuitBreaker);
+        assert!(halt
+            .text
+            .contains("daily loss limit (flattening positions)"));
+
+        let ws = alert_for(&Event::Disconnected(DisconnectEvent {
+            stream: "market_ws".to_string(),
+            reason: "closed by venue".to_string(),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(ws.kind, AlertKind::WsDisconnected);
+        assert_eq!(ws.subject, "market_ws");
+    }
+
+    #[test]
+    fn test_event_flags() {
+        let events = NotificationEventsConfig {
+            order_filled: false,
+            ..NotificationEventsConfig::default()
+        };
+        assert!(!AlertKind::OrderFilled.enabled(&events));
+        assert!(AlertKind::StopLoss.enabled(&events));
+        assert!(AlertKind::WsDisconnected.enabled(&events));
+    }
+
+    // ============= Rate Limit Tests =============
+
+    #[test]
+    fn test_limiter_spaces_same_type_and_subject() {
+        let mut limiter = AlertLimiter::new(&NotificationConfig {
+            min_interval_secs: 60,
+            max_per_minute: 0,
+            ..NotificationConfig::default()
+        });
+        let now = Instant::now();
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "BTC/USD"), now));
+        assert!(!limiter.allow(&alert(AlertKind::OrderFilled, "BTC/USD"), now + secs(30)));
+        // Other symbols and types are independent
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "ETH/USD"), now + secs(30)));
+        assert!(limiter.allow(&alert(AlertKind::StopLoss, "BTC/USD"), now + secs(30)));
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "BTC/USD"), now + secs(60)));
+    }
+
+    #[test]
+    fn test_limiter_caps_per_minute() {
+        let mut limiter = AlertLimiter::new(&NotificationConfig {
+            min_interval_secs: 0,
+            max_per_minute: 2,
+            ..NotificationConfig::default()
+        });
+        let now = Instant::now();
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "A"), now));
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "B"), now + secs(1)));
+        assert!(!limiter.allow(&alert(AlertKind::OrderFilled, "C"), now + secs(2)));
+        assert!(limiter.allow(&alert(AlertKind::OrderFilled, "C"), now + secs(60)));
+    }
+
+    // ============= Payload Tests =============
+
+    #[test]
+    fn test_channel_payloads() {
+        let event = execution("BTC/USD", "filled");
+        let a = alert_for(&event).unwrap();
+        assert_eq!(telegram_payload("42", &a)["chat_id"], "42");
+        assert_eq!(discord_payload(&a)["content"], a.text);
+
+        let body = webhook_payload(&a, &event);
+        assert_eq!(body["type"], "order_filled");
+        assert_eq!(body["event"]["type"], "execution");
+    }
+}
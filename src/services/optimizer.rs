@@ -0,0 +1,345 @@
+//! Walk-forward optimization of the HFT tunables (`optimize`).
+//!
+//! Recorded quotes (an event log) or cached bars (bid/ask a synthetic
+//! `bar_spread_bps` around each close) are replayed through `HftStrategy`,
+//! and its signals are paper-filled by a `ShadowBook` the same way an A/B
+//! shadow is. Every point of the `optimize` grid is backtested on each of
+//! `folds + 1` consecutive windows: fold k picks the best set on window k and
+//! reports how it did out of sample on window k+1. The stable pick is the set
+//! with the best worst-window out-of-sample P&L among those that traded in
+//! every test window (ties: mean P&L), so one lucky window cannot carry it.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::agents::memory::MemoryStore;
+use crate::config::{AppConfig, ChatterLevel, HftConfig, OptimizeConfig};
+use crate::data::downloader::read_cache;
+use crate::data::store::{Bar, MarketStore, Quote};
+use crate::error::{AutoHedgeError, Result};
+use crate::events::{Event, ExecutionReport, MarketEvent};
+use crate::llm::{LLMClient, LLMQueue};
+use crate::money::float;
+use crate::services::event_log::read_log;
+use crate::services::reporting::StrategyResult;
+use crate::services::shadow::ShadowBook;
+use crate::services::strategy::HftStrategy;
+use crate::services::strategy_registry::{Strategy, StrategyContext};
+
+/// The tunables under search.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct HftParams {
+    pub min_edge_bps: f64,
+    pub take_profit_bps: f64,
+    pub stop_loss_bps: f64,
+    pub max_spread_bps: f64,
+}
+
+impl HftParams {
+    pub fn of(hft: &HftConfig) -> Self {
+        Self {
+            min_edge_bps: hft.min_edge_bps,
+            take_profit_bps: hft.take_profit_bps,
+            stop_loss_bps: hft.stop_loss_bps,
+            max_spread_bps: hft.max_spread_bps,
+        }
+    }
+
+    pub fn apply(&self, config: &mut AppConfig) {
+        config.hft.min_edge_bps = self.min_edge_bps;
+        config.hft.take_profit_bps = self.take_profit_bps;
+        config.hft.stop_loss_bps = self.stop_loss_bps;
+        config.hft.max_spread_bps = self.max_spread_bps;
+    }
+}
+
+/// Every combination of the `optimize` candidate lists; an empty list keeps
+/// the `base` value.
+pub fn grid(config: &OptimizeConfig, base: &HftConfig) -> Vec<HftParams> {
+    let values = |list: &[f64], current: f64| {
+        if list.is_empty() {
+            vec![current]
+        } else {
+            list.to_vec()
+        }
+    };
+    let mut params = Vec::new();
+    for &min_edge_bps in &values(&config.min_edge_bps, base.min_edge_bps) {
+        for &take_profit_bps in &values(&config.take_profit_bps, base.take_profit_bps) {
+            for &stop_loss_bps in &values(&config.stop_loss_bps, base.stop_loss_bps) {
+                for &max_spread_bps in &values(&config.max_spread_bps, base.max_spread_bps) {
+                    params.push(HftParams {
+                        min_edge_bps,
+                        take_profit_bps,
+                        stop_loss_bps,
+                        max_spread_bps,
+                    });
+                }
+            }
+        }
+    }
+    params
+}
+
+/// One quote per bar at its close, `spread_bps` wide.
+pub fn quotes_from_bars(bars: &[Bar], spread_bps: f64) -> Vec<Quote> {
+    let half = spread_bps / 20_000.0;
+    bars.iter()
+        .filter(|b| b.close > 0.0)
+        .map(|b| Quote {
+            symbol: b.symbol.clone(),
+            bid_price: b.close * (1.0 - half),
+            ask_price: b.close * (1.0 + half),
+            bid_size: b.volume,
+            ask_size: b.volume,
+            timestamp: b.timestamp.clone(),
+        })
+        .collect()
+}
+
+/// Quotes of a bar cache (`<SYMBOL>_<timeframe>.csv`, see `download_history`)
+/// or of an event log (`.jsonl` / `.msgpack`).
+pub fn load_quotes(path: &Path, bar_spread_bps: f64) -> Result<Vec<Quote>> {
+    if path.extension().and_then(|e| e.to_str()) == Some("csv") {
+        let symbol = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.rsplit_once('_'))
+            .map(|(symbol, _)| symbol.replace('-', "/"))
+            .ok_or_else(|| {
+                AutoHedgeError::Io(format!(
+                    "{}: expected <SYMBOL>_<timeframe>.csv",
+                    path.display()
+                ))
+            })?;
+        return Ok(quotes_from_bars(
+            &read_cache(path, &symbol)?,
+            bar_spread_bps,
+        ));
+    }
+    Ok(read_log(path)?
+        .into_iter()
+        .filter_map(|r| match r.event {
+            Event::Market(MarketEvent::Quote {
+                symbol,
+                bid,
+                ask,
+                timestamp,
+            }) => Some(Quote {
+                symbol,
+                bid_price: bid,
+                ask_price: ask,
+                bid_size: 0.0,
+                ask_size: 0.0,
+                timestamp,
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+fn quote_time(quote: &Quote) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&quote.timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Sort quotes of several files into one replay, oldest first.
+pub fn merge_quotes(mut quotes: Vec<Quote>) -> Vec<Quote> {
+    quotes.sort_by_key(quote_time);
+    quotes
+}
+
+/// Realized result of one backtest; positions still open at the end are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct BacktestScore {
+    pub pnl: f64,
+    pub trades: u64,
+    pub win_rate_pct: f64,
+}
+
+/// Replays quotes through a fresh `HftStrategy` and paper book per run.
+pub struct Backtester {
+    llm: LLMQueue,
+}
+
+impl Backtester {
+    /// Needs a Tokio runtime (the strategy context carries an idle LLM queue;
+    /// HFT never calls it).
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            llm: LLMQueue::new(
+                LLMClient::new(String::new(), None, config.llm.model.clone()),
+                1,
+                1,
+                config.llm_retry.clone(),
+            ),
+        }
+    }
+
+    pub async fn run(&self, config: &AppConfig, quotes: &[Quote]) -> BacktestScore {
+        let mut config = config.clone();
+        config.set_chatter(ChatterLevel::Low);
+        let ctx = StrategyContext {
+            store: MarketStore::new(config.history_limit),
+            llm: self.llm.clone(),
+            memory: MemoryStore::new(&config.agent_memory),
+            config: config.clone(),
+        };
+        let strategy = HftStrategy::default();
+        let mut book = ShadowBook::new("optimize", config);
+        let mut result = StrategyResult::default();
+        let mut record = |fill: ExecutionReport| {
+            if let (Some(price), Some(qty)) = (fill.price, fill.qty) {
+                result.book(&fill.symbol, &fill.side, price, qty, |pnl| pnl);
+            }
+        };
+
+        for quote in quotes {
+            let (symbol, bid, ask) = (&quote.symbol, quote.bid_price, quote.ask_price);
+            ctx.store.update_quote(symbol.clone(), quote.clone());
+            if let Some(fill) = book.on_quote(symbol, bid, ask) {
+                record(fill);
+            }
+            if let Some(signal) = strategy.on_quote(symbol, bid, ask, &ctx).await {
+                if let Some(fill) = book.on_signal(&signal) {
+                    record(fill);
+                }
+            }
+        }
+        BacktestScore {
+            pnl: float(result.realized_pnl),
+            trades: result.closed_trades,
+            win_rate_pct: result.win_rate_pct(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FoldReport {
+    pub fold: usize,
+    pub train_from: String,
+    pub train_to: String,
+    pub test_from: String,
+    pub test_to: String,
+    /// Best set on the training window
+    pub params: HftParams,
+    pub train: BacktestScore,
+    pub test: BacktestScore,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StableParams {
+    pub params: HftParams,
+    pub worst_test_pnl: f64,
+    pub mean_test_pnl: f64,
+    pub test_trades: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OptimizeReport {
+    pub generated_at: String,
+    pub quotes: usize,
+    pub candidates: usize,
+    /// The `hft` section the search started from
+    pub baseline: HftParams,
+    pub folds: Vec<FoldReport>,
+    /// None when no candidate traded in every test window
+    pub stable: Option<StableParams>,
+}
+
+/// Grid-search `config.optimize` over `quotes` (oldest first) with
+/// walk-forward folds.
+pub async fn walk_forward(config: &AppConfig, quotes: &[Quote]) -> Result<OptimizeReport> {
+    let folds = config.optimize.folds.max(1);
+    let windows = folds + 1;
+    if quotes.len() < windows * 2 {
+        return Err(AutoHedgeError::Config(format!(
+            "{} quote(s) are too few for {} walk-forward fold(s)",
+            quotes.len(),
+            folds
+        )));
+    }
+    let bounds: Vec<usize> = (0..=windows).map(|i| i * quotes.len() / windows).collect();
+    let windows: Vec<&[Quote]> = bounds.windows(2).map(|b| &quotes[b[0]..b[1]]).collect();
+
+    let candidates = grid(&config.optimize, &config.hft);
+    let backtester = Backtester::new(config);
+    // scores[candidate][window]
+    let mut scores = Vec::with_capacity(candidates.len());
+    for params in &candidates {
+        let mut run_config = config.clone();
+        params.apply(&mut run_config);
+        let mut row = Vec::with_capacity(windows.len());
+        for window in &windows {
+            row.push(backtester.run(&run_config, window).await);
+        }
+        scores.push(row);
+    }
+
+    let span = |w: &[Quote]| {
+        (
+            w.first().map(|q| q.timestamp.clone()).unwrap_or_default(),
+            w.last().map(|q| q.timestamp.clone()).unwrap_or_default(),
+        )
+    };
+    let mut fold_reports = Vec::with_capacity(folds);
+    for fold in 0..folds {
+        let best = (0..candidates.len())
+            .max_by(|&a, &b| scores[a][fold].pnl.total_cmp(&scores[b][fold].pnl))
+            .unwrap_or_default();
+        let (train_from, train_to) = span(windows[fold]);
+        let (test_from, test_to) = span(windows[fold + 1]);
+        fold_reports.push(FoldReport {
+            fold: fold + 1,
+            train_from,
+            train_to,
+            test_from,
+            test_to,
+            params: candidates[best],
+            train: scores[best][fold].clone(),
+            test: scores[best][fold + 1].clone(),
+        });
+    }
+
+    let stable = candidates
+        .iter()
+        .zip(&scores)
+        .filter(|(_, row)| row[1..].iter().all(|s| s.trades > 0))
+        .map(|(params, row)| {
+            let test = &row[1..];
+            StableParams {
+                params: *params,
+                worst_test_pnl: test.iter().map(|s| s.pnl).fold(f64::INFINITY, f64::min),
+                mean_test_pnl: test.iter().map(|s| s.pnl).sum::<f64>() / test.len() as f64,
+                test_trades: test.iter().map(|s| s.trades).sum(),
+            }
+        })
+        .max_by(|a, b| {
+            a.worst_test_pnl
+                .total_cmp(&b.worst_test_pnl)
+                .then(a.mean_test_pnl.total_cmp(&b.mean_test_pnl))
+        });
+
+    Ok(OptimizeReport {
+        generated_at: Utc::now().to_rfc3339(),
+        quotes: quotes.len(),
+        candidates: candidates.len(),
+        baseline: HftParams::of(&config.hft),
+        folds: fold_reports,
+        stable,
+    })
+}
+
+/// Write `report` as `<dir>/optimize-<timestamp>.json`.
+pub fn write_report(report: &OptimizeReport, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "optimize-{}.json",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, serde_json::to_string_pretty(report)?)?;
+    Ok(path)
+}
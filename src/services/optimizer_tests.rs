@@ -0,0 +1,171 @@
+//! Unit tests for the walk-forward HFT parameter optimizer.
+
+#[cfg(test)]
+mod optimizer_tests {
+    use crate::config::{AppConfig, OptimizeConfig};
+    use crate::data::downloader::append_bars;
+    use crate::data::store::{Bar, Quote};
+    use crate::services::optimizer::*;
+    use chrono::{TimeZone, Utc};
+
+    const CONFIG_YAML: &str = r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#;
+
+    fn test_config() -> AppConfig {
+        AppConfig::parse(CONFIG_YAML).unwrap()
+    }
+
+    /// `n` one-second quotes, 2 bps wide, with the mid rising 5 bps a quote.
+    fn uptrend(n: usize) -> Vec<Quote> {
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        (0..n)
+            .map(|i| {
+                let mid = 100.0 * (1.0005f64).powi(i as i32);
+                Quote {
+                    symbol: "BTC/USD".to_string(),
+                    bid_price: mid * 0.9999,
+                    ask_price: mid * 1.0001,
+                    bid_size: 1.0,
+                    ask_size: 1.0,
+                    timestamp: (start + chrono::Duration::seconds(i as i64)).to_rfc3339(),
+                }
+            })
+            .collect()
+    }
+
+    // ============= Grid Tests =============
+
+    #[test]
+    fn test_grid_expands_candidates_and_keeps_unlisted_values() {
+        let config = test_config();
+        let params = grid(&OptimizeConfig::default(), &config.hft);
+        assert_eq!(params.len(), 4 * 4 * 3);
+        assert!(params.iter().all(|p| p.max_spread_bps == 30.0));
+        assert!(params.contains(&HftParams {
+            min_edge_bps: 20.0,
+            take_profit_bps: 100.0,
+            stop_loss_bps: 40.0,
+            max_spread_bps: 30.0,
+        }));
+
+        let none = OptimizeConfig {
+            min_edge_bps: Vec::new(),
+            take_profit_bps: Vec::new(),
+            stop_loss_bps: Vec::new(),
+            ..OptimizeConfig::default()
+        };
+        assert_eq!(grid(&none, &config.hft), vec![HftParams::of(&config.hft)]);
+    }
+
+    // ============= Data Tests =============
+
+    #[test]
+    fn test_load_quotes_from_bar_cache() {
+        let dir = std::env::temp_dir().join(format!("autohedge_optimizer_{}", std::process::id()));
+        let path = dir.join("BTC-USD_1Min.csv");
+        let bar = Bar {
+            symbol: "BTC/USD".to_string(),
+            open: 99.0,
+            high: 101.0,
+            low: 98.0,
+            close: 100.0,
+            volume: 3.0,
+            timestamp: "2026-10-16T12:00:00.000Z".to_string(),
+        };
+        append_bars(&path, &[bar]).unwrap();
+
+        let quotes = load_quotes(&path, 2.0).unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].symbol, "BTC/USD");
+        assert!((quotes[0].bid_price - 99.99).abs() < 1e-9);
+        assert!((quotes[0].ask_price - 100.01).abs() < 1e-9);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // ============= Backtest Tests =============
+
+    #[tokio::test]
+    async fn test_backtest_books_take_profit_exits() {
+        let config = test_config();
+        let score = Backtester::new(&config).run(&config, &uptrend(200)).await;
+        assert!(score.trades > 0);
+        assert!(score.pnl > 0.0);
+        assert_eq!(score.win_rate_pct, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_backtest_respects_min_edge() {
+        let mut config = test_config();
+        config.hft.min_edge_bps = 10_000.0;
+        let score = Backtester::new(&config).run(&config, &uptrend(200)).await;
+        assert_eq!(score, BacktestScore::default());
+    }
+
+    // ============= Walk-Forward Tests =============
+
+    #[tokio::test]
+    async fn test_walk_forward_picks_set_that_trades_out_of_sample() {
+        let mut config = test_config();
+        config.optimize = OptimizeConfig {
+            min_edge_bps: vec![10_000.0, 10.0],
+            take_profit_bps: vec![50.0],
+            stop_loss_bps: Vec::new(),
+            folds: 2,
+            ..OptimizeConfig::default()
+        };
+        let quotes = uptrend(300);
+        let report = walk_forward(&config, &quotes).await.unwrap();
+
+        assert_eq!(report.candidates, 2);
+        assert_eq!(report.quotes, 300);
+        assert_eq!(report.folds.len(), 2);
+        assert_eq!(report.folds[0].train_from, quotes[0].timestamp);
+        assert_eq!(report.folds[1].test_to, quotes[299].timestamp);
+        assert!(report.folds.iter().all(|f| f.params.min_edge_bps == 10.0));
+        assert!(report.folds.iter().all(|f| f.test.trades > 0));
+
+        let stable = report.stable.unwrap();
+        assert_eq!(stable.params.min_edge_bps, 10.0);
+        assert!(stable.worst_test_pnl > 0.0);
+        assert!(stable.mean_test_pnl >= stable.worst_test_pnl);
+    }
+
+    #[tokio::test]
+    async fn test_walk_forward_needs_enough_quotes() {
+        let config = test_config();
+        assert!(walk_forward(&config, &uptrend(5)).await.is_err());
+    }
+}
@@ -0,0 +1,601 @@
+//! Pairs (spread) trading.
+//!
+//! `PairStrategy` tracks the log price ratio `symbol_a / symbol_b` of each
+//! pair in `pairs.pairs` over the last `pairs.window` quotes. Once the ratio's
+//! z-score reaches `entry_z` the spread is entered: with A rich (+z) A is sold
+//! short and B bought, with A cheap (-z) the reverse. It is closed when the
+//! z-score is back within `exit_z`, or at a loss beyond `stop_z`.
+//!
+//! `PairExecutor` sends both entry legs at once as aggressive limits and waits
+//! up to `leg_timeout_ms` for them. A leg still working then is cancelled and
+//! whatever filled, on either leg, is unwound at market, so a failed entry
+//! never leaves one leg of the spread naked. A leg whose final state cannot
+//! be read back is not unwound: new entries are halted until an operator has
+//! checked the venue and calls `POST /resume`. Exits go out at market.
+//!
+//! Pair legs are held by `PairTrader`, not the position tracker, so the
+//! position monitor never puts a take-profit on half of a spread. Spreads are
+//! only entered on venues whose capabilities include `supports_short`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, PairConfig, PairsConfig};
+use crate::data::ring::Ring;
+use crate::data::store::MarketStore;
+use crate::events::{Event, MarketEvent, SystemEvent};
+use crate::exchange::symbol_meta::SymbolMetaRegistry;
+use crate::exchange::traits::TradingApi;
+use crate::exchange::types::{PlaceOrderRequest, Side, TimeInForce};
+use crate::money::{dec, float};
+use crate::services::execution_utils::{
+    aggressive_limit_price, fill_legs, halt_unhedged, limit_order, market_order, market_tif,
+    normalize_order,
+};
+
+/// Wait between attempts to close legs a failed exit left open
+const EXIT_RETRY: Duration = Duration::from_secs(10);
+
+/// Which way a spread is held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpreadSide {
+    /// Long `symbol_a`, short `symbol_b`: the ratio is expected to rise
+    Long,
+    /// Short `symbol_a`, long `symbol_b`
+    Short,
+}
+
+impl SpreadSide {
+    /// Sides of the A and B legs.
+    pub fn leg_sides(self) -> (Side, Side) {
+        match self {
+            SpreadSide::Long => (Side::Buy, Side::Sell),
+            SpreadSide::Short => (Side::Sell, Side::Buy),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpreadSide::Long => "long",
+            SpreadSide::Short => "short",
+        }
+    }
+}
+
+/// What the strategy wants done with a pair.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PairSignal {
+    Enter {
+        side: SpreadSide,
+        z: f64,
+    },
+    /// `stop` when closed beyond `stop_z` rather than at `exit_z`
+    Exit {
+        z: f64,
+        stop: bool,
+    },
+}
+
+/// Rolling z-score of one pair's log price ratio.
+#[derive(Debug)]
+pub struct PairStrategy {
+    log_ratios: Ring<f64>,
+    position: Option<SpreadSide>,
+    min_samples: usize,
+    entry_z: f64,
+    exit_z: f64,
+    stop_z: f64,
+}
+
+impl PairStrategy {
+    pub fn new(config: &PairsConfig) -> Self {
+        Self {
+            log_ratios: Ring::new(config.window.max(2)),
+            position: None,
+            min_samples: config.min_samples.max(2),
+            entry_z: config.entry_z,
+            exit_z: config.exit_z,
+            stop_z: config.stop_z,
+        }
+    }
+
+    pub fn position(&self) -> Option<SpreadSide> {
+        self.position
+    }
+
+    /// Record the spread actually held (after execution).
+    pub fn set_position(&mut self, position: Option<SpreadSide>) {
+        self.position = position;
+    }
+
+    /// Z-score of `log_ratio` against the ratios seen so far; None during
+    /// warm-up or while the ratio has not moved.
+    fn zscore(&self, log_ratio: f64) -> Option<f64> {
+        let n = self.log_ratios.len();
+        if n < self.min_samples {
+            return None;
+        }
+        let mean = self.log_ratios.iter().sum::<f64>() / n as f64;
+        let variance = self
+            .log_ratios
+            .iter()
+            .map(|r| (r - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        let std = variance.sqrt();
+        (std > 0.0).then(|| (log_ratio - mean) / std)
+    }
+
+    /// Feed the latest prices of both legs.
+    pub fn on_prices(&mut self, price_a: f64, price_b: f64) -> Option<PairSignal> {
+        if !(price_a.is_finite() && price_b.is_finite() && price_a > 0.0 && price_b > 0.0) {
+            return None;
+        }
+        let log_ratio = (price_a / price_b).ln();
+        let z = self.zscore(log_ratio);
+        self.log_ratios.push(log_ratio);
+        let z = z?;
+
+        match self.position {
+            None if z >= self.entry_z => Some(PairSignal::Enter {
+                side: SpreadSide::Short,
+                z,
+            }),
+            None if z <= -self.entry_z => Some(PairSignal::Enter {
+                side: SpreadSide::Long,
+                z,
+            }),
+            None => None,
+            Some(side) => {
+                // How far the ratio sits on the losing side of the mean
+                let adverse = match side {
+                    SpreadSide::Long => -z,
+                    SpreadSide::Short => z,
+                };
+                if self.stop_z > 0.0 && adverse >= self.stop_z {
+                    Some(PairSignal::Exit { z, stop: true })
+                } else if adverse <= self.exit_z {
+                    Some(PairSignal::Exit { z, stop: false })
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// One leg of a pair trade. For held legs `price` is the entry price; for
+/// orders it is the reference price the quantity was sized at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leg {
+    pub symbol: String,
+    pub side: Side,
+    pub qty: Decimal,
+    pub price: Decimal,
+}
+
+impl Leg {
+    /// The order that closes `qty` of this leg.
+    fn closing(&self, qty: Decimal) -> Leg {
+        Leg {
+            side: match self.side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            },
+            qty,
+            ..self.clone()
+        }
+    }
+
+    /// P&L of closing this leg at `exit_price`.
+    pub fn pnl(&self, exit_price: Decimal) -> Decimal {
+        match self.side {
+            Side::Buy => (exit_price - self.price) * self.qty,
+            Side::Sell => (self.price - exit_price) * self.qty,
+        }
+    }
+}
+
+/// How much of an entry leg filled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegFill {
+    pub leg: Leg,
+    pub filled: Decimal,
+}
+
+/// Market orders flattening whatever filled of an entry, or None when every
+/// leg filled completely (the spread is on).
+pub fn unwind_plan(fills: &[LegFill]) -> Option<Vec<Leg>> {
+    if fills.iter().all(|f| f.filled >= f.leg.qty) {
+        return None;
+    }
+    Some(
+        fills
+            .iter()
+            .filter(|f| f.filled > Decimal::ZERO)
+            .map(|f| f.leg.closing(f.filled))
+            .collect(),
+    )
+}
+
+/// Submits pair legs together and unwinds entries that do not complete.
+#[derive(Clone)]
+pub struct PairExecutor {
+    exchange: Arc<dyn TradingApi>,
+    symbol_meta: SymbolMetaRegistry,
+    leg_timeout: Duration,
+    poll: Duration,
+    aggression_bps: f64,
+    market_tif: TimeInForce,
+    /// Halts new entries when a leg's fill cannot be established
+    event_bus: Option<EventBus>,
+}
+
+impl PairExecutor {
+    pub fn new(
+        exchange: Arc<dyn TradingApi>,
+        symbol_meta: SymbolMetaRegistry,
+        config: &AppConfig,
+    ) -> Self {
+        let pairs = &config.pairs;
+        Self {
+            exchange,
+            symbol_meta,
+            leg_timeout: Duration::from_millis(pairs.leg_timeout_ms),
+            poll: Duration::from_millis(pairs.poll_ms.max(1)),
+            aggression_bps: pairs.aggression_bps,
+            market_tif: market_tif(config),
+            event_bus: None,
+        }
+    }
+
+    /// Publish `SystemEvent::Halt` when a leg is left in an unknown state.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Enter both `legs` (sized at their reference price) as aggressive limits
+    /// priced off `quotes` (bid, ask per leg). Returns the legs held, or None
+    /// when the entry failed and was unwound.
+    pub async fn enter(&self, legs: [Leg; 2], quotes: [(f64, f64); 2]) -> Option<Vec<Leg>> {
+        // Every spread has a short leg (warned about at startup)
+        if !self.exchange.capabilities().supports_short
+            && legs.iter().any(|leg| leg.side == Side::Sell)
+        {
+            return None;
+        }
+        // Round both legs first: if either cannot be sent, neither is
+        let mut orders = Vec::with_capacity(2);
+        for (leg, (bid, ask)) in legs.iter().zip(quotes) {
+            let side = if leg.side == Side::Buy { "buy" } else { "sell" };
+            let limit = dec(aggressive_limit_price(bid, ask, side, self.aggression_bps));
+            let req = limit_order(&leg.symbol, leg.side, leg.qty, limit, TimeInForce::Gtc);
+            let req =
+                normalize_order(&self.symbol_meta, self.exchange.as_ref(), req, leg.price).await?;
+            orders.push(req);
+        }
+
+        let legs: Vec<Leg> = orders
+            .iter()
+            .map(|req| Leg {
+                symbol: req.symbol.clone(),
+                side: req.side,
+                qty: req.qty.unwrap_or_default(),
+                price: req.limit_price.unwrap_or_default(),
+            })
+            .collect();
+        let exchange = self.exchange.as_ref();
+        let [a, b]: [PlaceOrderRequest; 2] = orders.try_into().ok()?;
+        let filled = fill_legs([(exchange, a), (exchange, b)], self.leg_timeout, self.poll).await;
+        // Unwinding against a leg that may have filled could leave it naked
+        let [Some(filled_a), Some(filled_b)] = filled else {
+            halt_unhedged(
+                self.event_bus.as_ref(),
+                format!(
+                    "{} / {} pair legs unreconciled (filled {:?}, {:?}) - check for an unhedged position",
+                    legs[0].symbol, legs[1].symbol, filled[0], filled[1]
+                ),
+            );
+            return None;
+        };
+        let fills: Vec<LegFill> = legs
+            .into_iter()
+            .zip([filled_a, filled_b])
+            .map(|(leg, filled)| LegFill { leg, filled })
+            .collect();
+        let Some(unwind) = unwind_plan(&fills) else {
+            return Some(fills.into_iter().map(|f| f.leg).collect());
+        };
+
+        warn!(
+            "⚠️ [PAIRS] {} / {} entry incomplete (filled {} of {}, {} of {}) - unwinding",
+            fills[0].leg.symbol,
+            fills[1].leg.symbol,
+            fills[0].filled,
+            fills[0].leg.qty,
+            fills[1].filled,
+            fills[1].leg.qty
+        );
+        let stuck = self.submit_market(unwind).await;
+        for leg in &stuck {
+            error!(
+                "❌ [PAIRS] Failed to unwind {:?} {} {} - flatten it manually",
+                leg.side, leg.qty, leg.symbol
+            );
+        }
+        None
+    }
+
+    /// Close `legs` at market. Returns the legs still held (close failed).
+    pub async fn exit(&self, legs: &[Leg]) -> Vec<Leg> {
+        let closing = legs.iter().map(|leg| leg.closing(leg.qty)).collect();
+        self.submit_market(closing)
+            .await
+            .iter()
+            .map(|order| order.closing(order.qty))
+            .collect()
+    }
+
+    /// Submit `orders` at market together. Returns the orders that failed.
+    async fn submit_market(&self, orders: Vec<Leg>) -> Vec<Leg> {
+        let results = futures_util::future::join_all(orders.iter().map(|leg| {
+            self.exchange.submit_order(market_order(
+                &leg.symbol,
+                leg.side,
+                leg.qty,
+                self.market_tif,
+            ))
+        }))
+        .await;
+        orders
+            .into_iter()
+            .zip(results)
+            .filter_map(|(leg, result)| match result {
+                Ok(_) => None,
+                Err(e) => {
+                    warn!(
+                        "⚠️ [PAIRS] Market {:?} {} {} failed: {}",
+                        leg.side, leg.qty, leg.symbol, e
+                    );
+                    Some(leg)
+                }
+            })
+            .collect()
+    }
+}
+
+/// One pair's strategy and the legs held for it.
+struct PairBook {
+    pair: PairConfig,
+    strategy: PairStrategy,
+    held: Vec<Leg>,
+    /// An entry or exit is in flight
+    busy: bool,
+    /// Earliest retry of a failed exit
+    retry_at: Option<Instant>,
+}
+
+/// Follows quotes of the paired symbols and trades their spreads.
+pub struct PairTrader {
+    event_bus: EventBus,
+    store: MarketStore,
+    executor: PairExecutor,
+    config: PairsConfig,
+}
+
+impl PairTrader {
+    pub fn new(
+        event_bus: EventBus,
+        exchange: Arc<dyn TradingApi>,
+        store: MarketStore,
+        symbol_meta: SymbolMetaRegistry,
+        config: &AppConfig,
+    ) -> Self {
+        for pair in &config.pairs.pairs {
+            for symbol in [&pair.symbol_a, &pair.symbol_b] {
+                if !config.symbols.contains(symbol) {
+                    warn!(
+                        "⚠️ [PAIRS] {} is not in `symbols` and will not be streamed",
+                        symbol
+                    );
+                }
+            }
+        }
+        if !exchange.capabilities().supports_short {
+            warn!(
+                "⚠️ [PAIRS] {} cannot sell short - no spread will be entered",
+                exchange.name()
+            );
+        }
+        Self {
+            executor: PairExecutor::new(exchange, symbol_meta, config)
+                .with_event_bus(event_bus.clone()),
+            event_bus,
+            store,
+            config: config.pairs.clone(),
+        }
+    }
+
+    pub async fn start(self) {
+        let mut rx = self.event_bus.subscribe("pairs");
+        let shutdown = self.event_bus.shutdown().clone();
+        let books: Vec<Arc<Mutex<PairBook>>> = self
+            .config
+            .pairs
+            .iter()
+            .map(|pair| {
+                Arc::new(Mutex::new(PairBook {
+                    pair: pair.clone(),
+                    strategy: PairStrategy::new(&self.config),
+                    held: Vec::new(),
+                    busy: false,
+                    retry_at: None,
+                }))
+            })
+            .collect();
+        tokio::spawn(async move {
+            info!(
+                "🔗 Pair Trader started ({} pair(s), entry z {:.1}, exit z {:.1})",
+                books.len(),
+                self.config.entry_z,
+                self.config.exit_z
+            );
+            // Set by the circuit breaker; cleared by /resume. Exits still run.
+            let mut halted = false;
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                match event {
+                    Event::Market(MarketEvent::Quote { symbol, .. }) => {
+                        for book in &books {
+                            self.on_quote(book, &symbol, halted);
+                        }
+                    }
+                    Event::System(system) => {
+                        halted = matches!(system, SystemEvent::Halt { .. });
+                        if let SystemEvent::Halt { flatten: true, .. } = system {
+                            for book in &books {
+                                self.flatten(book);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            for book in &books {
+                let book = book.lock().unwrap();
+                if !book.held.is_empty() {
+                    warn!(
+                        "⚠️ [PAIRS] Stopping with the {} / {} spread still open",
+                        book.pair.symbol_a, book.pair.symbol_b
+                    );
+                }
+            }
+        });
+    }
+
+    fn on_quote(&self, book: &Arc<Mutex<PairBook>>, symbol: &str, halted: bool) {
+        let mut guard = book.lock().unwrap();
+        let (symbol_a, symbol_b) = (guard.pair.symbol_a.clone(), guard.pair.symbol_b.clone());
+        if symbol != symbol_a && symbol != symbol_b {
+            return;
+        }
+        let (Some(quote_a), Some(quote_b)) = (
+            self.store.get_latest_quote(&symbol_a),
+            self.store.get_latest_quote(&symbol_b),
+        ) else {
+            return;
+        };
+        let mid_a = (quote_a.bid_price + quote_a.ask_price) / 2.0;
+        let mid_b = (quote_b.bid_price + quote_b.ask_price) / 2.0;
+        let signal = guard.strategy.on_prices(mid_a, mid_b);
+        if guard.busy {
+            return;
+        }
+        // A previous exit left legs behind: keep trying to close them
+        if guard.strategy.position().is_none() && !guard.held.is_empty() {
+            if guard.retry_at.is_none_or(|at| Instant::now() >= at) {
+                drop(guard);
+                self.flatten(book);
+            }
+            return;
+        }
+
+        match signal {
+            Some(PairSignal::Enter { side, z }) if !halted => {
+                let (side_a, side_b) = side.leg_sides();
+                let leg = |symbol: &str, side: Side, mid: f64| Leg {
+                    symbol: symbol.to_string(),
+                    side,
+                    qty: dec(self.config.leg_notional / mid),
+                    price: dec(mid),
+                };
+                let legs = [leg(&symbol_a, side_a, mid_a), leg(&symbol_b, side_b, mid_b)];
+                let quotes = [
+                    (quote_a.bid_price, quote_a.ask_price),
+                    (quote_b.bid_price, quote_b.ask_price),
+                ];
+                info!(
+                    "🔗 [PAIRS] {} / {} z={:.2} - entering {} spread ({:?} {}, {:?} {})",
+                    symbol_a,
+                    symbol_b,
+                    z,
+                    side.as_str(),
+                    side_a,
+                    symbol_a,
+                    side_b,
+                    symbol_b
+                );
+                guard.busy = true;
+                drop(guard);
+                let (book, executor) = (book.clone(), self.executor.clone());
+                tokio::spawn(async move {
+                    let held = executor.enter(legs, quotes).await;
+                    let mut book = book.lock().unwrap();
+                    if let Some(held) = held {
+                        info!(
+                            "✅ [PAIRS] {} / {} {} spread on",
+                            book.pair.symbol_a,
+                            book.pair.symbol_b,
+                            side.as_str()
+                        );
+                        book.held = held;
+                        book.strategy.set_position(Some(side));
+                    }
+                    book.busy = false;
+                });
+            }
+            Some(PairSignal::Exit { z, stop }) => {
+                let pnl: Decimal = guard
+                    .held
+                    .iter()
+                    .map(|leg| leg.pnl(dec(if leg.symbol == symbol_a { mid_a } else { mid_b })))
+                    .sum();
+                info!(
+                    "{} [PAIRS] {} / {} z={:.2} - closing spread (~${:.2})",
+                    if stop { "🛑" } else { "🔗" },
+                    symbol_a,
+                    symbol_b,
+                    z,
+                    float(pnl)
+                );
+                drop(guard);
+                self.flatten(book);
+            }
+            _ => {}
+        }
+    }
+
+    /// Close whatever the pair holds, in the background.
+    fn flatten(&self, book: &Arc<Mutex<PairBook>>) {
+        let held = {
+            let mut guard = book.lock().unwrap();
+            if guard.busy || guard.held.is_empty() {
+                return;
+            }
+            guard.busy = true;
+            guard.strategy.set_position(None);
+            guard.held.clone()
+        };
+        let (book, executor) = (book.clone(), self.executor.clone());
+        tokio::spawn(async move {
+            let remaining = executor.exit(&held).await;
+            let mut book = book.lock().unwrap();
+            book.retry_at = (!remaining.is_empty()).then(|| Instant::now() + EXIT_RETRY);
+            book.held = remaining;
+            book.busy = false;
+        });
+    }
+}
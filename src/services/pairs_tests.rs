@@ -0,0 +1,314 @@
+//! Unit tests for pairs trading - z-score signals, leg P&L and unwinding of
+//! incomplete entries.
+
+#[cfg(test)]
+mod pairs_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, PairsConfig};
+    use crate::error::AutoHedgeError;
+    use crate::events::{Event, SystemEvent};
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::symbol_meta::SymbolMetaRegistry;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::pairs::*;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn config() -> PairsConfig {
+        PairsConfig {
+            window: 20,
+            min_samples: 10,
+            entry_z: 2.0,
+            exit_z: 0.5,
+            stop_z: 4.0,
+            ..PairsConfig::default()
+        }
+    }
+
+    /// Strategy warmed up on a ratio wobbling around 0.05 (ETH/BTC-like).
+    fn warmed_up() -> PairStrategy {
+        let mut strategy = PairStrategy::new(&config());
+        for i in 0..20 {
+            let wobble = if i % 2 == 0 { 1.0 } else { -1.0 };
+            assert_eq!(strategy.on_prices(3000.0 + wobble, 60_000.0), None);
+        }
+        strategy
+    }
+
+    fn leg(symbol: &str, side: Side, qty: f64, price: f64) -> Leg {
+        Leg {
+            symbol: symbol.to_string(),
+            side,
+            qty: dec(qty),
+            price: dec(price),
+        }
+    }
+
+    // ============= Strategy Tests =============
+
+    #[test]
+    fn test_rich_ratio_enters_short_spread() {
+        let mut strategy = warmed_up();
+        let Some(PairSignal::Enter { side, z }) = strategy.on_prices(3010.0, 60_000.0) else {
+            panic!("expected an entry");
+        };
+        assert_eq!(side, SpreadSide::Short);
+        assert!(z >= 2.0);
+        assert_eq!(side.leg_sides(), (Side::Sell, Side::Buy));
+        // Nothing is held until execution confirms it
+        assert_eq!(strategy.position(), None);
+    }
+
+    #[test]
+    fn test_cheap_ratio_enters_long_spread_and_exits_at_mean() {
+        let mut strategy = warmed_up();
+        assert!(matches!(
+            strategy.on_prices(2990.0, 60_000.0),
+            Some(PairSignal::Enter {
+                side: SpreadSide::Long,
+                ..
+            })
+        ));
+        strategy.set_position(Some(SpreadSide::Long));
+        assert!(matches!(
+            strategy.on_prices(3000.0, 60_000.0),
+            Some(PairSignal::Exit { stop: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_stop_when_spread_keeps_diverging() {
+        let mut strategy = warmed_up();
+        strategy.set_position(Some(SpreadSide::Short));
+        // A even richer: the short spread is losing
+        assert!(matches!(
+            strategy.on_prices(3050.0, 60_000.0),
+            Some(PairSignal::Exit { stop: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_no_signal_during_warmup_or_on_bad_prices() {
+        let mut strategy = PairStrategy::new(&config());
+        for _ in 0..5 {
+            assert_eq!(strategy.on_prices(3000.0, 60_000.0), None);
+        }
+        assert_eq!(strategy.on_prices(3500.0, 60_000.0), None);
+        let mut strategy = warmed_up();
+        assert_eq!(strategy.on_prices(0.0, 60_000.0), None);
+        assert_eq!(strategy.on_prices(3010.0, f64::NAN), None);
+    }
+
+    // ============= Leg Tests =============
+
+    #[test]
+    fn test_leg_pnl_by_side() {
+        assert_eq!(
+            leg("ETH/USD", Side::Buy, 2.0, 100.0).pnl(dec(110.0)),
+            dec(20.0)
+        );
+        assert_eq!(
+            leg("BTC/USD", Side::Sell, 0.5, 100.0).pnl(dec(110.0)),
+            dec(-5.0)
+        );
+    }
+
+    #[test]
+    fn test_unwind_plan_reverses_filled_quantities() {
+        let full = [
+            LegFill {
+                leg: leg("ETH/USD", Side::Buy, 1.0, 3000.0),
+                filled: dec(1.0),
+            },
+            LegFill {
+                leg: leg("BTC/USD", Side::Sell, 0.05, 60_000.0),
+                filled: dec(0.05),
+            },
+        ];
+        assert_eq!(unwind_plan(&full), None);
+
+        let partial = [
+            full[0].clone(),
+            LegFill {
+                filled: dec(0.02),
+                ..full[1].clone()
+            },
+        ];
+        assert_eq!(
+            unwind_plan(&partial),
+            Some(vec![
+                leg("ETH/USD", Side::Sell, 1.0, 3000.0),
+                leg("BTC/USD", Side::Buy, 0.02, 60_000.0),
+            ])
+        );
+
+        let none_filled = [LegFill {
+            filled: Decimal::ZERO,
+            ..full[0].clone()
+        }];
+        assert_eq!(unwind_plan(&none_filled), Some(Vec::new()));
+    }
+
+    // ============= Executor Tests =============
+
+    /// Venue that fills every order except those for `reject`.
+    fn leg_exchange(reject: &'static str, short: bool) -> Arc<MockExchange> {
+        let exchange = MockExchange::new("legs")
+            .with_capabilities(|c| c.supports_short = short)
+            .on_submit(move |_, order| {
+                if order.symbol == reject {
+                    return Err(AutoHedgeError::http(400, "insufficient balance"));
+                }
+                Ok(ack("1", "filled", json!({})))
+            });
+        Arc::new(exchange)
+    }
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+pairs:
+  enabled: true
+  pairs:
+    - symbol_a: ETH/USD
+      symbol_b: BTC/USD
+  leg_timeout_ms: 50
+  poll_ms: 10
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn executor(exchange: Arc<MockExchange>) -> PairExecutor {
+        PairExecutor::new(exchange, SymbolMetaRegistry::new(), &test_config())
+    }
+
+    fn entry_legs() -> [Leg; 2] {
+        [
+            leg("ETH/USD", Side::Sell, 0.1, 3000.0),
+            leg("BTC/USD", Side::Buy, 0.005, 60_000.0),
+        ]
+    }
+
+    const QUOTES: [(f64, f64); 2] = [(2999.0, 3001.0), (59_990.0, 60_010.0)];
+
+    #[tokio::test]
+    async fn test_entry_holds_both_filled_legs() {
+        let exchange = leg_exchange("", true);
+        let held = executor(exchange.clone())
+            .enter(entry_legs(), QUOTES)
+            .await
+            .unwrap();
+        assert_eq!(held.len(), 2);
+        assert_eq!((held[0].side, held[1].side), (Side::Sell, Side::Buy));
+        // Aggressive limits on the far side of the mid
+        assert!(held[0].price < dec(3000.0) && held[1].price > dec(60_000.0));
+
+        let remaining = executor(exchange.clone()).exit(&held).await;
+        assert!(remaining.is_empty());
+        let orders = exchange.submitted();
+        assert_eq!(orders.len(), 4);
+        assert!(matches!(orders[2].order_type, OrderType::Market));
+        assert_eq!(orders[2].side, Side::Buy);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_leg_unwinds_the_filled_one() {
+        let exchange = leg_exchange("BTC/USD", true);
+        assert_eq!(
+            executor(exchange.clone()).enter(entry_legs(), QUOTES).await,
+            None
+        );
+
+        let orders = exchange.submitted();
+        assert_eq!(orders.len(), 3);
+        let unwind = &orders[2];
+        assert_eq!(unwind.symbol, "ETH/USD");
+        assert_eq!(unwind.side, Side::Buy);
+        assert_eq!(unwind.qty, Some(dec(0.1)));
+        assert!(matches!(unwind.order_type, OrderType::Market));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_leg_halts_instead_of_unwinding() {
+        // The BTC/USD leg rests and its state can never be read back
+        let exchange = Arc::new(
+            MockExchange::new("legs")
+                .with_capabilities(|c| c.supports_short = true)
+                .on_submit(|_, order| {
+                    let status = if order.symbol == "BTC/USD" {
+                        "new"
+                    } else {
+                        "filled"
+                    };
+                    Ok(ack(&order.symbol, status, json!({})))
+                })
+                .on_get_order(|_, _| Err(AutoHedgeError::http(503, "unavailable"))),
+        );
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let held = executor(exchange.clone())
+            .with_event_bus(bus)
+            .enter(entry_legs(), QUOTES)
+            .await;
+        assert_eq!(held, None);
+
+        // Nothing is unwound against a leg that may have filled
+        assert_eq!(exchange.submitted().len(), 2);
+        match rx.try_recv() {
+            Ok(Event::System(SystemEvent::Halt {
+                reason, flatten, ..
+            })) => {
+                assert!(reason.contains("unreconciled"), "{}", reason);
+                assert!(!flatten);
+            }
+            other => panic!("expected a halt, got {:?}", other.is_ok()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_entry_where_the_venue_cannot_short() {
+        let exchange = leg_exchange("", false);
+        assert_eq!(
+            executor(exchange.clone()).enter(entry_legs(), QUOTES).await,
+            None
+        );
+        assert!(exchange.submitted().is_empty());
+    }
+}
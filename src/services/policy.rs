@@ -0,0 +1,387 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::{AppConfig, PolicyConfig, TradingWindow};
+use crate::data::store::MarketStore;
+use crate::error::Result;
+use crate::events::{ExecutionReport, OrderRejectedEvent};
+use crate::money;
+
+/// One audited policy decision (written as a JSONL line).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    pub ts: String,
+    pub symbol: String,
+    /// "buy" | "sell"
+    pub side: String,
+    pub allowed: bool,
+    /// Rule that decided the outcome ("restricted_symbol", "embargo", ...) or "pass"
+    pub rule: String,
+    pub reason: String,
+}
+
+impl PolicyDecision {
+    /// The `Event::OrderRejected` payload of a blocked decision.
+    pub fn rejection(&self, correlation_id: Option<String>) -> OrderRejectedEvent {
+        OrderRejectedEvent {
+            symbol: self.symbol.clone(),
+            side: self.side.clone(),
+            reason: self.rule.clone(),
+            detail: self.reason.clone(),
+            correlation_id,
+            timestamp: self.ts.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PolicyState {
+    turnover_day: Option<NaiveDate>,
+    turnover: f64,
+    last_sell: HashMap<String, DateTime<Utc>>,
+}
+
+/// Central compliance layer. The risk engine checks each signal against the
+/// symbol, time and wash-trade rules before sizing; the execution engine checks
+/// the sized entry against the notional, volume and turnover limits.
+///
+/// Policies only constrain new exposure: sells (exits) are always allowed but
+/// still audited, so a restricted or embargoed symbol can be flattened.
+#[derive(Clone)]
+pub struct PolicyEngine {
+    config: PolicyConfig,
+    state: Arc<Mutex<PolicyState>>,
+    audit_path: Option<PathBuf>,
+    /// Bar history the 24h volume check reads
+    store: Option<MarketStore>,
+}
+
+impl PolicyEngine {
+    /// An empty `audit_log_path` disables the JSONL file (decisions are still logged).
+    pub fn new(config: &AppConfig) -> Self {
+        let audit_path = if config.policy.audit_log_path.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(&config.policy.audit_log_path))
+        };
+        Self {
+            config: config.policy.clone(),
+            state: Arc::new(Mutex::new(PolicyState::default())),
+            audit_path,
+            store: None,
+        }
+    }
+
+    /// Read 24h volumes from the store's bars (`max_volume_pct`).
+    pub fn with_market_store(mut self, store: MarketStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Evaluate and audit the symbol, time and wash-trade rules for a signal.
+    pub fn check(&self, symbol: &str, side: &str) -> PolicyDecision {
+        let decision = self.evaluate(symbol, side, Utc::now());
+        self.audit(&decision);
+        decision
+    }
+
+    /// Evaluate a signal at `now` without auditing.
+    pub fn evaluate(&self, symbol: &str, side: &str, now: DateTime<Utc>) -> PolicyDecision {
+        let decide = |allowed: bool, rule: &str, reason: String| PolicyDecision {
+            ts: now.to_rfc3339(),
+            symbol: symbol.to_string(),
+            side: side.to_string(),
+            allowed,
+            rule: rule.to_string(),
+            reason,
+        };
+
+        if !side.eq_ignore_ascii_case("buy") {
+            return decide(true, "exit", "exits are never blocked".to_string());
+        }
+
+        if self
+            .config
+            .restricted_symbols
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(symbol))
+        {
+            return decide(
+                false,
+                "restricted_symbol",
+                format!("{} is on the restricted list", symbol),
+            );
+        }
+
+        for window in &self.config.embargo_windows {
+            if !window.symbols.is_empty()
+                && !window
+                    .symbols
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(symbol))
+            {
+                continue;
+            }
+            let (Ok(start), Ok(end)) = (
+                DateTime::parse_from_rfc3339(&window.start),
+                DateTime::parse_from_rfc3339(&window.end),
+            ) else {
+                continue;
+            };
+            if now >= start && now < end {
+                return decide(
+                    false,
+                    "embargo",
+                    format!(
+                        "embargo {} - {} ({})",
+                        window.start, window.end, window.reason
+                    ),
+                );
+            }
+        }
+
+        let covering: Vec<&TradingWindow> = self
+            .config
+            .trading_windows
+            .iter()
+            .filter(|w| {
+                w.symbols.is_empty() || w.symbols.iter().any(|s| s.eq_ignore_ascii_case(symbol))
+            })
+            .collect();
+        if !covering.is_empty() && !covering.iter().any(|w| window_contains(w, now)) {
+            return decide(
+                false,
+                "trading_window",
+                format!(
+                    "outside trading windows ({})",
+                    covering
+                        .iter()
+                        .map(|w| describe_window(w))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+
+        let state = self.state.lock().unwrap();
+
+        if self.config.wash_trade_window_secs > 0 {
+            if let Some(sold_at) = state.last_sell.get(symbol) {
+                let elapsed = (now - *sold_at).num_seconds();
+                if elapsed >= 0 && (elapsed as u64) < self.config.wash_trade_window_secs {
+                    return decide(
+                        false,
+                        "wash_trade",
+                        format!(
+                            "own sell {}s ago (window {}s)",
+                            elapsed, self.config.wash_trade_window_secs
+                        ),
+                    );
+                }
+            }
+        }
+
+        decide(true, "pass", "all policies passed".to_string())
+    }
+
+    /// Evaluate and audit the sized notional of an entry (run by the execution
+    /// engine once the order's quantity and price are known).
+    pub fn check_size(&self, symbol: &str, notional: f64) -> PolicyDecision {
+        let decision = self.evaluate_size(symbol, notional, Utc::now());
+        self.audit(&decision);
+        decision
+    }
+
+    /// Size rules (`max_trade_notional`, `max_volume_pct`,
+    /// `max_daily_turnover`) for an entry of `notional` at `now`, without auditing.
+    pub fn evaluate_size(&self, symbol: &str, notional: f64, now: DateTime<Utc>) -> PolicyDecision {
+        let decide = |allowed: bool, rule: &str, reason: String| PolicyDecision {
+            ts: now.to_rfc3339(),
+            symbol: symbol.to_string(),
+            side: "buy".to_string(),
+            allowed,
+            rule: rule.to_string(),
+            reason,
+        };
+
+        if let Some(limit) = self.config.max_trade_notional {
+            if notional > limit {
+                return decide(
+                    false,
+                    "max_trade_notional",
+                    format!("order ${:.2} exceeds ${:.2}", notional, limit),
+                );
+            }
+        }
+
+        if let Some(pct) = self.config.max_volume_pct {
+            if let Some(volume) = self.stored_volume_24h(symbol, now) {
+                if notional > volume * pct / 100.0 {
+                    return decide(
+                        false,
+                        "max_volume_pct",
+                        format!(
+                            "order ${:.2} exceeds {}% of stored 24h bar volume ${:.2}",
+                            notional, pct, volume
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(limit) = self.config.max_daily_turnover {
+            let state = self.state.lock().unwrap();
+            let today = if state.turnover_day == Some(now.date_naive()) {
+                state.turnover
+            } else {
+                0.0
+            };
+            if today + notional > limit {
+                return decide(
+                    false,
+                    "max_daily_turnover",
+                    format!(
+                        "turnover ${:.2} + order ${:.2} exceeds ${:.2}",
+                        today, notional, limit
+                    ),
+                );
+            }
+        }
+
+        decide(
+            true,
+            "pass",
+            format!("order ${:.2} within size limits", notional),
+        )
+    }
+
+    /// Notional traded over the 24h before `now` per the stored bars; None
+    /// without a store or bars in that window. The store keeps at most
+    /// `history_limit` bars, so with a short history or fine bars this covers
+    /// only the latest part of the day and understates the real 24h volume.
+    fn stored_volume_24h(&self, symbol: &str, now: DateTime<Utc>) -> Option<f64> {
+        let since = now - chrono::Duration::hours(24);
+        let bars = self.store.as_ref()?.get_bar_history(symbol);
+        let mut seen = false;
+        let mut volume = 0.0;
+        for bar in bars {
+            let Ok(ts) = DateTime::parse_from_rfc3339(&bar.timestamp) else {
+                continue;
+            };
+            if ts >= since && ts <= now {
+                seen = true;
+                volume += bar.close * bar.volume;
+            }
+        }
+        seen.then_some(volume)
+    }
+
+    /// Feed our own executions back in (turnover and wash-trade tracking).
+    pub fn record_execution(&self, report: &ExecutionReport, now: DateTime<Utc>) {
+        if report.status.eq_ignore_ascii_case("rejected")
+            || report.status.eq_ignore_ascii_case("canceled")
+        {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let day = now.date_naive();
+        if state.turnover_day != Some(day) {
+            state.turnover_day = Some(day);
+            state.turnover = 0.0;
+        }
+        if let (Some(price), Some(qty)) = (report.price, report.qty) {
+            state.turnover += money::float(price * qty);
+        }
+        if report.side.eq_ignore_ascii_case("sell") {
+            state.last_sell.insert(report.symbol.clone(), now);
+        }
+    }
+
+    fn audit(&self, decision: &PolicyDecision) {
+        if decision.allowed {
+            info!(
+                "⚖️ [POLICY] ALLOW {} {} ({}: {})",
+                decision.side, decision.symbol, decision.rule, decision.reason
+            );
+        } else {
+            warn!(
+                "⚖️ [POLICY] BLOCK {} {} ({}: {})",
+                decision.side, decision.symbol, decision.rule, decision.reason
+            );
+        }
+
+        if let Some(path) = &self.audit_path {
+            if let Err(e) = Self::append_jsonl(path, decision) {
+                warn!("⚠️ [POLICY] Failed to write audit log: {}", e);
+            }
+        }
+    }
+
+    fn append_jsonl(path: &Path, decision: &PolicyDecision) -> Result<()> {
+        use std::io::Write;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        let line = serde_json::to_string(decision)?;
+        writeln!(f, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Whether `now` falls in a recurring window; unparseable times never match.
+fn window_contains(window: &TradingWindow, now: DateTime<Utc>) -> bool {
+    let (Ok(start), Ok(end)) = (
+        NaiveTime::parse_from_str(&window.start, "%H:%M"),
+        NaiveTime::parse_from_str(&window.end, "%H:%M"),
+    ) else {
+        return false;
+    };
+    let time = now.time();
+    // A window past midnight belongs to the day it opened on
+    let (opened, inside) = if start <= end {
+        (now.date_naive(), time >= start && time < end)
+    } else if time >= start {
+        (now.date_naive(), true)
+    } else {
+        (
+            now.date_naive().pred_opt().unwrap_or(now.date_naive()),
+            time < end,
+        )
+    };
+    // "mon", "Monday", ... all name the same day
+    let weekday = opened.weekday().to_string().to_lowercase();
+    inside
+        && (window.days.is_empty()
+            || window
+                .days
+                .iter()
+                .any(|d| d.trim().to_lowercase().get(..3) == Some(weekday.as_str())))
+}
+
+fn describe_window(window: &TradingWindow) -> String {
+    if window.days.is_empty() {
+        format!("{}-{} UTC", window.start, window.end)
+    } else {
+        format!(
+            "{} {}-{} UTC",
+            window.days.join("/"),
+            window.start,
+            window.end
+        )
+    }
+}
@@ -0,0 +1,289 @@
+//! Unit tests for the policy module - compliance constraints enforced by the risk and execution engines.
+
+#[cfg(test)]
+mod policy_tests {
+    use crate::config::AppConfig;
+    use crate::data::store::{Bar, MarketStore};
+    use crate::events::ExecutionReport;
+    use crate::money::dec;
+    use crate::services::policy::*;
+    use chrono::{DateTime, Duration, Utc};
+    use rust_decimal::Decimal;
+
+    fn test_config(policy_yaml: &str) -> AppConfig {
+        let yaml = format!(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+policy:
+  audit_log_path: ""
+{}
+"#,
+            policy_yaml
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn report(symbol: &str, side: &str, notional: Decimal) -> ExecutionReport {
+        ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: "o1".to_string(),
+            status: "filled".to_string(),
+            side: side.to_string(),
+            price: Some(notional),
+            qty: Some(dec(1.0)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Default / Exit Tests =============
+
+    #[test]
+    fn test_default_policy_allows() {
+        let engine = PolicyEngine::new(&test_config(""));
+        let d = engine.evaluate("BTC/USD", "buy", Utc::now());
+        assert!(d.allowed);
+        assert_eq!(d.rule, "pass");
+    }
+
+    #[test]
+    fn test_exits_never_blocked() {
+        let engine = PolicyEngine::new(&test_config(
+            "  restricted_symbols: [\"BTC/USD\"]\n  max_daily_turnover: 0.0",
+        ));
+        let d = engine.evaluate("BTC/USD", "sell", Utc::now());
+        assert!(d.allowed);
+        assert_eq!(d.rule, "exit");
+    }
+
+    // ============= Rule Tests =============
+
+    #[test]
+    fn test_restricted_symbol_blocked() {
+        let engine = PolicyEngine::new(&test_config("  restricted_symbols: [\"btc/usd\"]"));
+        let d = engine.evaluate("BTC/USD", "buy", Utc::now());
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "restricted_symbol");
+        assert!(engine.evaluate("ETH/USD", "buy", Utc::now()).allowed);
+    }
+
+    #[test]
+    fn test_embargo_window() {
+        let engine = PolicyEngine::new(&test_config(
+            r#"  embargo_windows:
+    - start: "2025-01-01T13:00:00Z"
+      end: "2025-01-01T14:00:00Z"
+      symbols: ["ETH/USD"]
+      reason: "FOMC""#,
+        ));
+        let d = engine.evaluate("ETH/USD", "buy", at("2025-01-01T13:30:00Z"));
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "embargo");
+        assert!(d.reason.contains("FOMC"));
+        // Other symbols and times are unaffected
+        assert!(
+            engine
+                .evaluate("BTC/USD", "buy", at("2025-01-01T13:30:00Z"))
+                .allowed
+        );
+        assert!(
+            engine
+                .evaluate("ETH/USD", "buy", at("2025-01-01T14:00:00Z"))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_max_daily_turnover_resets_next_day() {
+        let engine = PolicyEngine::new(&test_config("  max_daily_turnover: 250.0"));
+        let day1 = at("2025-01-01T10:00:00Z");
+        engine.record_execution(&report("BTC/USD", "buy", dec(100.0)), day1);
+        assert!(engine.evaluate_size("BTC/USD", 100.0, day1).allowed);
+
+        engine.record_execution(&report("BTC/USD", "sell", dec(100.0)), day1);
+        let d = engine.evaluate_size("ETH/USD", 100.0, day1);
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "max_daily_turnover");
+        // A smaller entry still fits under the cap
+        assert!(engine.evaluate_size("ETH/USD", 50.0, day1).allowed);
+
+        assert!(
+            engine
+                .evaluate_size("ETH/USD", 100.0, day1 + Duration::days(1))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_wash_trade_window() {
+        let engine = PolicyEngine::new(&test_config("  wash_trade_window_secs: 60"));
+        let t = at("2025-01-01T10:00:00Z");
+        engine.record_execution(&report("BTC/USD", "sell", dec(100.0)), t);
+
+        let d = engine.evaluate("BTC/USD", "buy", t + Duration::seconds(30));
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "wash_trade");
+        assert!(
+            engine
+                .evaluate("BTC/USD", "buy", t + Duration::seconds(61))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_rejected_executions_ignored() {
+        let engine = PolicyEngine::new(&test_config("  wash_trade_window_secs: 60"));
+        let t = at("2025-01-01T10:00:00Z");
+        let mut r = report("BTC/USD", "sell", dec(100.0));
+        r.status = "rejected".to_string();
+        engine.record_execution(&r, t);
+        assert!(engine.evaluate("BTC/USD", "buy", t).allowed);
+    }
+
+    // ============= Pre-Trade Size / Window Tests =============
+
+    #[test]
+    fn test_max_trade_notional_checks_the_sized_order() {
+        let engine = PolicyEngine::new(&test_config("  max_trade_notional: 50.0"));
+        let d = engine.evaluate_size("BTC/USD", 80.0, Utc::now());
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "max_trade_notional");
+        assert!(d.reason.contains("80.00"), "{}", d.reason);
+        assert!(engine.evaluate_size("BTC/USD", 40.0, Utc::now()).allowed);
+        // Size limits are not judged before sizing
+        assert!(engine.evaluate("BTC/USD", "buy", Utc::now()).allowed);
+    }
+
+    #[test]
+    fn test_max_volume_pct_uses_24h_bars() {
+        let now = at("2025-01-02T12:00:00Z");
+        let store = MarketStore::new(50);
+        let bar = |hours_ago: i64, volume: f64| Bar {
+            symbol: "BTC/USD".to_string(),
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume,
+            timestamp: (now - Duration::hours(hours_ago)).to_rfc3339(),
+        };
+        // $5,000 traded in the window; the 30h-old bar does not count
+        store.update_bar("BTC/USD".to_string(), bar(30, 1_000.0));
+        store.update_bar("BTC/USD".to_string(), bar(2, 30.0));
+        store.update_bar("BTC/USD".to_string(), bar(1, 20.0));
+
+        let engine = PolicyEngine::new(&test_config("  max_volume_pct: 1.0"))
+            .with_market_store(store.clone());
+        let d = engine.evaluate_size("BTC/USD", 100.0, now);
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "max_volume_pct");
+        assert!(d.reason.contains("5000.00"), "{}", d.reason);
+        // Unknown volume is not held against an entry
+        assert!(engine.evaluate_size("ETH/USD", 100.0, now).allowed);
+        assert!(engine.evaluate_size("BTC/USD", 50.0, now).allowed);
+
+        let loose =
+            PolicyEngine::new(&test_config("  max_volume_pct: 2.0")).with_market_store(store);
+        assert!(loose.evaluate_size("BTC/USD", 100.0, now).allowed);
+    }
+
+    #[test]
+    fn test_trading_windows() {
+        let engine = PolicyEngine::new(&test_config(
+            r#"  trading_windows:
+    - days: ["mon", "tue", "Wednesday", "thu", "fri"]
+      start: "13:30"
+      end: "20:00"
+      symbols: ["BTC/USD"]
+    - start: "22:00"
+      end: "02:00"
+      symbols: ["ETH/USD"]"#,
+        ));
+        // 2025-01-01 is a Wednesday
+        assert!(
+            engine
+                .evaluate("BTC/USD", "buy", at("2025-01-01T14:00:00Z"))
+                .allowed
+        );
+        let d = engine.evaluate("BTC/USD", "buy", at("2025-01-01T20:00:00Z"));
+        assert!(!d.allowed);
+        assert_eq!(d.rule, "trading_window");
+        assert!(d.reason.contains("13:30-20:00"), "{}", d.reason);
+        // Saturday
+        assert!(
+            !engine
+                .evaluate("BTC/USD", "buy", at("2025-01-04T14:00:00Z"))
+                .allowed
+        );
+        // Past midnight
+        assert!(
+            engine
+                .evaluate("ETH/USD", "buy", at("2025-01-02T01:00:00Z"))
+                .allowed
+        );
+        assert!(
+            !engine
+                .evaluate("ETH/USD", "buy", at("2025-01-02T12:00:00Z"))
+                .allowed
+        );
+        // Exits stay open outside the window
+        assert!(
+            engine
+                .evaluate("BTC/USD", "sell", at("2025-01-04T14:00:00Z"))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_blocked_decision_becomes_rejection() {
+        let engine = PolicyEngine::new(&test_config("  restricted_symbols: [\"BTC/USD\"]"));
+        let d = engine.evaluate("BTC/USD", "buy", at("2025-01-01T10:00:00Z"));
+        let rejected = d.rejection(Some("c1".to_string()));
+        assert_eq!(rejected.reason, "restricted_symbol");
+        assert_eq!(rejected.side, "buy");
+        assert_eq!(rejected.correlation_id.as_deref(), Some("c1"));
+        assert_eq!(rejected.timestamp, d.ts);
+    }
+}
@@ -1,24 +1,41 @@
 use crate::bus::EventBus;
-use crate::config::AppConfig;
-use crate::events::{AnalysisSignal, Event, MarketEvent};
+use crate::config::{AppConfig, BinanceFuturesConfig, ChatterModule};
+use crate::events::{
+    AnalysisSignal, ControlCommand, Event, ExecutionReport, MarketEvent, SystemEvent, TradeOrigin,
+};
+use crate::exchange::symbol_registry::SymbolRegistry;
 use crate::exchange::traits::TradingApi;
 use crate::exchange::types::{
-    OrderType as ExOrderType, PlaceOrderRequest as ExPlaceOrderRequest, Side as ExSide,
-    TimeInForce as ExTimeInForce,
+    BracketLegs, ExchangeCapabilities, OrderType as ExOrderType,
+    PlaceOrderRequest as ExPlaceOrderRequest, Side as ExSide, TimeInForce as ExTimeInForce,
 };
-use std::collections::HashMap;
+use crate::logging::new_correlation_id;
+use crate::money::{self, dec, float};
+use crate::services::hedging::HedgePosition;
+use crate::services::market_hours::TradingCalendar;
+use crate::services::stale_data::{StaleAction, StaleQuoteWatch};
+use crate::services::synthetic_fill::{Reconciled, SyntheticFillWatch};
+use crate::services::watchdog::ExchangeWatchdog;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::time::{sleep, Duration};
 use tracing::{error, info, warn};
 
 #[derive(Clone, Debug)]
 pub struct PositionInfo {
     pub symbol: String,
-    pub entry_price: f64,
-    pub qty: f64,
-    pub stop_loss: f64,
-    pub take_profit: f64,
+    pub entry_price: Decimal,
+    /// Entry order size (`filled_qty + remaining_qty`)
+    pub qty: Decimal,
+    /// Quantity actually owned; exits sell this
+    pub filled_qty: Decimal,
+    /// Part of the entry order still working on the exchange
+    pub remaining_qty: Decimal,
+    pub stop_loss: Decimal,
+    pub take_profit: Decimal,
     pub entry_time: String,
     pub side: String,                           // "buy" or "sell"
     pub is_closing: bool,                       // New field to prevent double-sells
@@ -26,9 +43,33 @@ pub struct PositionInfo {
     pub last_recreate_attempt: Option<Instant>, // Track last recreation attempt
     pub recreate_attempts: u32,                 // Count failed recreation attempts
     // Trailing stop fields
-    pub highest_price: f64,         // Track highest price for trailing stop
+    pub highest_price: Decimal, // Track highest price for trailing stop
     pub trailing_stop_active: bool, // Is trailing stop activated?
-    pub trailing_stop_price: f64,   // Current trailing stop level
+    pub trailing_stop_price: Decimal, // Current trailing stop level
+    /// Exit legs live on the exchange as a native bracket or OCO pair; monitor
+    /// only observes.
+    pub bracket_managed: bool,
+    /// Native stop-loss resting on the exchange (`supports_stop_orders`); the
+    /// take-profit is then watched locally and `open_order_id` stays empty.
+    pub stop_order_id: Option<String>,
+    /// Correlation id of the entry (None for positions adopted from the exchange)
+    pub correlation_id: Option<String>,
+}
+
+impl PositionInfo {
+    /// Set the owned quantity (reconciled holdings or after a partial exit),
+    /// keeping `qty` = filled + remaining.
+    pub fn set_filled_qty(&mut self, filled_qty: Decimal) {
+        self.filled_qty = filled_qty;
+        self.qty = filled_qty + self.remaining_qty;
+    }
+
+    /// Time since entry at `now`; `None` if `entry_time` does not parse.
+    pub fn held_for(&self, now: chrono::DateTime<chrono::Utc>) -> Option<chrono::Duration> {
+        chrono::DateTime::parse_from_rfc3339(&self.entry_time)
+            .ok()
+            .map(|t| now.signed_duration_since(t))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -36,18 +77,128 @@ pub struct PendingOrder {
     pub order_id: String,
     pub symbol: String,
     pub side: String,
-    pub limit_price: f64,
-    pub qty: f64,
+    pub limit_price: Decimal,
+    pub qty: Decimal,
+    /// Filled so far (partial fills from the user stream or order polling)
+    pub filled_qty: Decimal,
+    /// `qty - filled_qty`
+    pub remaining_qty: Decimal,
     pub created_at: String,
-    pub stop_loss: Option<f64>,
-    pub take_profit: Option<f64>,
+    pub stop_loss: Option<Decimal>,
+    pub take_profit: Option<Decimal>,
     pub last_check_time: Option<std::time::Instant>,
+    /// Entry was submitted with TP/SL legs attached on the exchange.
+    pub bracket: bool,
+    /// Correlation id of the order's trade
+    pub correlation_id: Option<String>,
+}
+
+/// Result of cancelling the TP leg of an emulated OCO pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcoOutcome {
+    /// TP leg is dead without a full fill; the stop leg may fire.
+    StopMayFire,
+    /// TP leg filled before the cancel landed; the position is already flat.
+    TakeProfitFilled,
+    /// TP leg state is unconfirmed (cancel in flight or lookup failed); retry next tick.
+    Retry,
+}
+
+impl OcoOutcome {
+    pub fn from_status(status: &str) -> Self {
+        match status.to_lowercase().as_str() {
+            "filled" => OcoOutcome::TakeProfitFilled,
+            "canceled" | "cancelled" | "expired" | "rejected" => OcoOutcome::StopMayFire,
+            _ => OcoOutcome::Retry,
+        }
+    }
+}
+
+/// Where the exits of a position entered without a bracket are held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitMode {
+    /// Native OCO pair; the exchange manages both legs like a bracket
+    Oco,
+    /// Native stop-loss on the exchange, take-profit watched locally
+    NativeStop,
+    /// Resting TP limit with the stop watched locally (OCO emulation)
+    Emulated,
+}
+
+impl ExitMode {
+    /// Modes to try, best first. A mode the venue refuses at submission falls
+    /// through to the next; `Emulated` needs nothing beyond limit orders.
+    pub fn fallbacks(capabilities: &ExchangeCapabilities) -> Vec<ExitMode> {
+        let mut modes = Vec::with_capacity(3);
+        if capabilities.supports_oco {
+            modes.push(ExitMode::Oco);
+        }
+        if capabilities.supports_stop_orders {
+            modes.push(ExitMode::NativeStop);
+        }
+        modes.push(ExitMode::Emulated);
+        modes
+    }
+}
+
+/// Filled quantity from a raw order payload (Alpaca sends it as a string).
+fn filled_qty(raw: &serde_json::Value) -> Option<Decimal> {
+    raw.get("filled_qty").and_then(money::from_json)
+}
+
+/// P/L of `price` vs `entry` in percent (0 without an entry price).
+fn pl_pct(entry: Decimal, price: Decimal) -> f64 {
+    (price - entry).checked_div(entry).map_or(0.0, float) * 100.0
+}
+
+/// Exit reason on the stop path. Forced closes (`close_at_next_tick`) pull the
+/// stop to `Decimal::MAX` and must not count as stop-losses.
+fn stop_exit_reason(position: &PositionInfo) -> &'static str {
+    if position.stop_loss == Decimal::MAX {
+        "forced_close"
+    } else {
+        "stop_loss"
+    }
+}
+
+/// `price` moved by `pct` percent (negative = down).
+fn offset_pct(price: Decimal, pct: f64) -> Decimal {
+    price * (Decimal::ONE + dec(pct) / Decimal::ONE_HUNDRED)
+}
+
+/// Estimated liquidation price of a futures long in `symbol` opened at `entry`:
+/// entry * (1 - 1/leverage + maintenance margin rate). Exact for isolated
+/// margin; cross margin liquidates further away, so it errs early.
+pub fn liquidation_price(symbol: &str, entry: Decimal, futures: &BinanceFuturesConfig) -> Decimal {
+    let leverage = Decimal::from(futures.leverage_for(symbol));
+    entry
+        * (Decimal::ONE - Decimal::ONE / leverage
+            + dec(futures.maintenance_margin_pct) / Decimal::ONE_HUNDRED)
+}
+
+/// Price at or below which a futures long is closed ahead of liquidation.
+pub fn liquidation_guard_price(
+    symbol: &str,
+    entry: Decimal,
+    futures: &BinanceFuturesConfig,
+) -> Decimal {
+    offset_pct(
+        liquidation_price(symbol, entry, futures),
+        futures.liquidation_buffer_pct,
+    )
 }
 
 #[derive(Clone)]
 pub struct PositionTracker {
     positions: Arc<Mutex<HashMap<String, PositionInfo>>>,
     pending_orders: Arc<Mutex<HashMap<String, PendingOrder>>>,
+    /// When tracked state disagreed with exchange holdings (feeds the health score)
+    mismatches: Arc<Mutex<VecDeque<Instant>>>,
+    /// Hedges by instrument (`Hedger`); never managed by the monitor
+    hedges: Arc<Mutex<HashMap<String, HedgePosition>>>,
+    /// Canonicalizes symbol keys, so a venue spelling ("BTCUSD") finds the
+    /// position opened as "BTC/USD"; None keeps symbols as given
+    symbols: Option<SymbolRegistry>,
 }
 
 impl PositionTracker {
@@ -55,10 +206,47 @@ impl PositionTracker {
         Self {
             positions: Arc::new(Mutex::new(HashMap::new())),
             pending_orders: Arc::new(Mutex::new(HashMap::new())),
+            mismatches: Arc::new(Mutex::new(VecDeque::new())),
+            hedges: Arc::new(Mutex::new(HashMap::new())),
+            symbols: None,
+        }
+    }
+
+    /// Key positions and pending orders by canonical symbol.
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = Some(symbols);
+        self
+    }
+
+    fn key(&self, symbol: &str) -> String {
+        match &self.symbols {
+            Some(registry) => registry.canonical(symbol),
+            None => symbol.to_string(),
+        }
+    }
+
+    /// Tracked position disagreed with the exchange (missing or different qty).
+    pub fn record_mismatch(&self) {
+        let mut mismatches = self.mismatches.lock().unwrap();
+        // Bounded: only the recent window is ever queried
+        if mismatches.len() >= 256 {
+            mismatches.pop_front();
         }
+        mismatches.push_back(Instant::now());
+    }
+
+    /// Reconciliation mismatches recorded within `window`.
+    pub fn recent_mismatches(&self, window: Duration) -> usize {
+        self.mismatches
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.elapsed() <= window)
+            .count()
     }
 
     pub fn add_pending_order(&self, mut order: PendingOrder) {
+        order.symbol = self.key(&order.symbol);
         let mut pending = self.pending_orders.lock().unwrap();
         order.last_check_time = Some(std::time::Instant::now());
         info!(
@@ -85,7 +273,162 @@ impl PositionTracker {
         pending.values().cloned().collect()
     }
 
+    /// Book a (partial) fill of a pending entry order. The filled part becomes
+    /// (or is added to) the symbol's position right away, so exits only ever
+    /// sell what is actually owned. Returns the updated pending order, or None
+    /// if `order_id` is not a pending buy.
+    pub fn record_entry_fill(
+        &self,
+        order_id: &str,
+        fill_qty: Decimal,
+        fill_price: Decimal,
+    ) -> Option<PendingOrder> {
+        let (order, fill) = {
+            let mut pending = self.pending_orders.lock().unwrap();
+            let order = pending.get_mut(order_id).filter(|o| o.side == "buy")?;
+            let fill = fill_qty.min(order.remaining_qty);
+            if fill <= Decimal::ZERO {
+                return Some(order.clone());
+            }
+            order.filled_qty += fill;
+            order.remaining_qty = (order.qty - order.filled_qty).max(Decimal::ZERO);
+            (order.clone(), fill)
+        };
+        let (Some(take_profit), Some(stop_loss)) = (order.take_profit, order.stop_loss) else {
+            return Some(order);
+        };
+
+        let mut positions = self.positions.lock().unwrap();
+        match positions.get_mut(&order.symbol) {
+            Some(pos) => {
+                let filled = pos.filled_qty + fill;
+                if filled > Decimal::ZERO {
+                    pos.entry_price =
+                        (pos.entry_price * pos.filled_qty + fill_price * fill) / filled;
+                }
+                pos.remaining_qty = order.remaining_qty;
+                pos.set_filled_qty(filled);
+            }
+            None => {
+                positions.insert(
+                    order.symbol.clone(),
+                    PositionInfo {
+                        symbol: order.symbol.clone(),
+                        entry_price: fill_price,
+                        qty: order.qty,
+                        filled_qty: fill,
+                        remaining_qty: order.remaining_qty,
+                        stop_loss,
+                        take_profit,
+                        entry_time: chrono::Utc::now().to_rfc3339(),
+                        side: "buy".to_string(),
+                        is_closing: false,
+                        open_order_id: None,
+                        last_recreate_attempt: None,
+                        recreate_attempts: 0,
+                        highest_price: fill_price,
+                        trailing_stop_active: false,
+                        trailing_stop_price: stop_loss,
+                        bracket_managed: order.bracket,
+                        stop_order_id: None,
+                        correlation_id: order.correlation_id.clone(),
+                    },
+                );
+            }
+        }
+        info!(
+            "📊 [TRACKER] Partial fill {} {}: filled={:.8} remaining={:.8} @ ${:.8}",
+            order.symbol, order.order_id, order.filled_qty, order.remaining_qty, fill_price
+        );
+        Some(order)
+    }
+
+    /// Cancel and forget entry orders for `symbol` that are still working, so an
+    /// exit of the filled part is not followed by more fills.
+    pub async fn cancel_working_entries(&self, symbol: &str, exchange: &dyn TradingApi) {
+        let key = self.key(symbol);
+        let entries: Vec<PendingOrder> = self
+            .get_all_pending_orders()
+            .into_iter()
+            .filter(|o| o.symbol == key && o.side == "buy")
+            .collect();
+        for order in entries {
+            if let Err(e) = exchange.cancel_order(&order.order_id).await {
+                warn!(
+                    "⚠️ [TRACKER] Failed to cancel working entry {} for {}: {}",
+                    order.order_id, symbol, e
+                );
+            }
+            self.remove_pending_order(&order.order_id);
+        }
+    }
+
+    /// Amend the resting TP leg of `symbol` to the position's `take_profit` in
+    /// one request, so the position is never left without an exit order.
+    /// Returns false (nothing changed locally) when the venue cannot amend, the
+    /// leg is unknown or partly filled, or the amend failed; the caller then
+    /// falls back to cancel-and-recreate, which settles whatever the venue did.
+    pub async fn replace_take_profit(&self, symbol: &str, exchange: &dyn TradingApi) -> bool {
+        if !exchange.capabilities().supports_order_replace {
+            return false;
+        }
+        let Some(mut position) = self.get_position(symbol) else {
+            return false;
+        };
+        let Some(tp_order_id) = position.open_order_id.clone() else {
+            return false;
+        };
+        let Some(leg) = self
+            .get_all_pending_orders()
+            .into_iter()
+            .find(|o| o.order_id == tp_order_id)
+            .filter(|o| o.filled_qty.is_zero())
+        else {
+            return false;
+        };
+
+        let order = ExPlaceOrderRequest {
+            symbol: symbol.to_string(),
+            side: ExSide::Sell,
+            order_type: ExOrderType::Limit,
+            qty: Some(leg.qty),
+            notional: None,
+            limit_price: Some(position.take_profit),
+            time_in_force: ExTimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        match exchange.replace_order(&tp_order_id, order).await {
+            Ok(ack) => {
+                info!(
+                    "✏️ [TRACKER] Moved TP leg of {} to ${:.8} (order: {} -> {})",
+                    symbol, position.take_profit, tp_order_id, ack.id
+                );
+                self.remove_pending_order(&tp_order_id);
+                self.add_pending_order(PendingOrder {
+                    order_id: ack.id.clone(),
+                    limit_price: position.take_profit,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                    ..leg
+                });
+                position.open_order_id = Some(ack.id);
+                self.add_position(position);
+                true
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ [TRACKER] Replace of TP leg {} for {} failed: {} (falling back to cancel)",
+                    tp_order_id, symbol, e
+                );
+                false
+            }
+        }
+    }
+
     pub fn add_position(&self, mut info: PositionInfo) {
+        info.symbol = self.key(&info.symbol);
         let mut positions = self.positions.lock().unwrap();
         // Ensure is_closing is false initially
         info.is_closing = false;
@@ -98,7 +441,7 @@ impl PositionTracker {
 
     pub fn mark_closing(&self, symbol: &str) {
         let mut positions = self.positions.lock().unwrap();
-        if let Some(pos) = positions.get_mut(symbol) {
+        if let Some(pos) = positions.get_mut(&self.key(symbol)) {
             pos.is_closing = true;
             info!("📊 [TRACKER] Marked position {} as closing", symbol);
         }
@@ -106,7 +449,7 @@ impl PositionTracker {
 
     pub fn remove_position(&self, symbol: &str) -> Option<PositionInfo> {
         let mut positions = self.positions.lock().unwrap();
-        let removed = positions.remove(symbol);
+        let removed = positions.remove(&self.key(symbol));
         if removed.is_some() {
             info!("📊 [TRACKER] Removed position: {}", symbol);
         }
@@ -115,7 +458,7 @@ impl PositionTracker {
 
     pub fn get_position(&self, symbol: &str) -> Option<PositionInfo> {
         let positions = self.positions.lock().unwrap();
-        positions.get(symbol).cloned()
+        positions.get(&self.key(symbol)).cloned()
     }
 
     pub fn get_all_positions(&self) -> Vec<PositionInfo> {
@@ -123,9 +466,46 @@ impl PositionTracker {
         positions.values().cloned().collect()
     }
 
+    /// Symbols with an open position or a working entry order, sorted.
+    pub fn held_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self
+            .get_all_positions()
+            .into_iter()
+            .map(|p| p.symbol)
+            .chain(
+                self.get_all_pending_orders()
+                    .into_iter()
+                    .filter(|o| o.side == "buy")
+                    .map(|o| o.symbol),
+            )
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
     pub fn has_position(&self, symbol: &str) -> bool {
         let positions = self.positions.lock().unwrap();
-        positions.contains_key(symbol)
+        positions.contains_key(&self.key(symbol))
+    }
+
+    pub fn set_hedge(&self, hedge: HedgePosition) {
+        self.hedges
+            .lock()
+            .unwrap()
+            .insert(hedge.symbol.clone(), hedge);
+    }
+
+    pub fn remove_hedge(&self, symbol: &str) -> Option<HedgePosition> {
+        self.hedges.lock().unwrap().remove(symbol)
+    }
+
+    pub fn get_hedge(&self, symbol: &str) -> Option<HedgePosition> {
+        self.hedges.lock().unwrap().get(symbol).cloned()
+    }
+
+    pub fn get_all_hedges(&self) -> Vec<HedgePosition> {
+        self.hedges.lock().unwrap().values().cloned().collect()
     }
 
     /// Best-effort helper used by execution sizing when MarketStore isn't directly available.
@@ -146,6 +526,7 @@ pub struct PositionMonitor {
     tracker: PositionTracker,
     check_interval_secs: u64,
     config: AppConfig,
+    watchdog: Option<ExchangeWatchdog>,
 }
 
 impl PositionMonitor {
@@ -161,9 +542,16 @@ impl PositionMonitor {
             tracker,
             check_interval_secs: 10,
             config,
+            watchdog: None,
         }
     }
 
+    /// Keep retrying exits with escalating backoff while the venue is in safety mode.
+    pub fn with_watchdog(mut self, watchdog: ExchangeWatchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
     pub async fn start(&self) {
         if self.config.exit_on_quotes {
             self.start_quote_driven().await;
@@ -185,8 +573,14 @@ impl PositionMonitor {
             // Initial sync with exchange positions
             Self::sync_positions(&*exchange, &tracker, &config).await;
 
+            let calendar = TradingCalendar::new(&config.trading_mode, &config.market_hours);
+            let shutdown = bus.shutdown().clone();
             loop {
-                sleep(Duration::from_secs(interval)).await;
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = sleep(Duration::from_secs(interval)) => {}
+                }
+                Self::flatten_before_close(&calendar, &tracker);
 
                 let tracked_positions = tracker.get_all_positions();
                 if tracked_positions.is_empty() {
@@ -214,26 +608,157 @@ impl PositionMonitor {
         let bus = self.event_bus.clone();
         let exchange = self.exchange.clone();
         let tracker = self.tracker.clone();
-        let mut rx = self.event_bus.subscribe();
-        let config = self.config.clone();
+        let mut rx = self.event_bus.subscribe("position_monitor");
+        let mut config = self.config.clone();
+        let watchdog = self.watchdog.clone();
 
         tokio::spawn(async move {
             info!(
                 "👁️  Position Monitor Started (quote-driven exits) | chatter={}",
-                config.chatter_level
+                config.chatter_for(ChatterModule::Monitor).as_str()
             );
 
             // Initial sync with exchange positions
             Self::sync_positions(&*exchange, &tracker, &config).await;
 
-            while let Ok(event) = rx.recv().await {
-                let (symbol, current_price) = match event {
-                    Event::Market(MarketEvent::Quote { symbol, bid, .. }) => (symbol, bid),
-                    Event::Market(MarketEvent::Trade { symbol, price, .. }) => (symbol, price),
-                    _ => continue,
+            // Dead-man's switch: REST ticks for positions whose stream went quiet
+            let mut stale_watch = StaleQuoteWatch::new(&config.stale_data, Instant::now());
+            let mut stale_check =
+                tokio::time::interval(Duration::from_secs(config.stale_data.poll_secs.max(1)));
+            let mut polled: VecDeque<(String, f64)> = VecDeque::new();
+
+            // Paper venues: TP limits the market trades through are filled locally
+            let mut synthetic =
+                SyntheticFillWatch::new(&config.synthetic_fills, config.is_paper_venue());
+            let mut reconcile = tokio::time::interval(Duration::from_secs(
+                config.synthetic_fills.reconcile_secs.max(1),
+            ));
+
+            // Stocks: positions are closed ahead of the session close
+            let calendar = TradingCalendar::new(&config.trading_mode, &config.market_hours);
+            let mut close_check = tokio::time::interval(Duration::from_secs(30));
+
+            // Keeps running through the drain so entries filled during it get exits
+            let shutdown = bus.shutdown().clone();
+            loop {
+                // REST ticks run through the same exit checks as streamed ones
+                let (symbol, current_price) = if let Some(tick) = polled.pop_front() {
+                    tick
+                } else {
+                    let event = tokio::select! {
+                        _ = shutdown.stopped() => break,
+                        _ = stale_check.tick(), if config.stale_data.enabled => {
+                            polled = Self::poll_stale_positions(
+                                &mut stale_watch, &*exchange, &tracker,
+                            )
+                            .await;
+                            continue;
+                        }
+                        _ = close_check.tick(), if calendar.flattens() => {
+                            Self::flatten_before_close(&calendar, &tracker);
+                            continue;
+                        }
+                        _ = reconcile.tick(), if synthetic.is_active() => {
+                            Self::reconcile_synthetic_fills(&mut synthetic, &*exchange, &tracker)
+                                .await;
+                            continue;
+                        }
+                        event = rx.recv() => match event {
+                            Ok(event) => event,
+                            Err(RecvError::Lagged(_)) => continue,
+                            Err(RecvError::Closed) => break,
+                        },
+                    };
+                    match event {
+                        Event::Market(MarketEvent::Quote { symbol, bid, .. }) => {
+                            stale_watch.record(&symbol, dec(bid), Instant::now());
+                            (symbol, bid)
+                        }
+                        Event::Market(MarketEvent::Trade { symbol, price, .. }) => {
+                            stale_watch.record(&symbol, dec(price), Instant::now());
+                            (symbol, price)
+                        }
+                        // Liquidation runs on the mark price; exit at the next tick if it nears
+                        Event::Market(MarketEvent::FundingRate {
+                            symbol, mark_price, ..
+                        }) => {
+                            if let Some(position) = tracker.get_position(&symbol) {
+                                Self::guard_liquidation(
+                                    &position,
+                                    dec(mark_price),
+                                    &config,
+                                    &tracker,
+                                );
+                            }
+                            continue;
+                        }
+                        Event::SymbolStatus(status)
+                            if status.exit_only && config.halt_detection.enabled =>
+                        {
+                            let reason = format!("exit-only: {}", status.reason);
+                            Self::close_at_next_tick(&status.symbol, &reason, &tracker);
+                            continue;
+                        }
+                        Event::Control(ControlCommand::ClosePosition { symbol }) => {
+                            Self::close_at_next_tick(&symbol, "operator request", &tracker);
+                            continue;
+                        }
+                        // New TP/SL percentages apply to entries filled from now on
+                        Event::Config(update) => {
+                            config = (*update.config).clone();
+                            continue;
+                        }
+                        Event::System(SystemEvent::Halt {
+                            reason,
+                            flatten: true,
+                            ..
+                        }) => {
+                            let reason = format!("circuit breaker: {}", reason);
+                            for position in tracker.get_all_positions() {
+                                Self::close_at_next_tick(&position.symbol, &reason, &tracker);
+                            }
+                            continue;
+                        }
+                        Event::Control(ControlCommand::AdjustExits {
+                            symbol,
+                            take_profit,
+                            stop_loss,
+                        }) => {
+                            Self::adjust_exits(
+                                &symbol,
+                                take_profit,
+                                stop_loss,
+                                &*exchange,
+                                &tracker,
+                            )
+                            .await;
+                            continue;
+                        }
+                        Event::Execution(report)
+                            if report.is_fill_update() && report.side == "buy" =>
+                        {
+                            let (Some(qty), Some(price)) = (report.qty, report.price) else {
+                                continue;
+                            };
+                            // Book the partial right away; finalize once nothing is left working
+                            if let Some(order) =
+                                tracker.record_entry_fill(&report.order_id, qty, price)
+                            {
+                                if order.remaining_qty <= Decimal::ZERO {
+                                    Self::check_pending_buy_order(
+                                        &order, &*exchange, &tracker, &config,
+                                    )
+                                    .await;
+                                }
+                            }
+                            continue;
+                        }
+                        _ => continue,
+                    }
                 };
 
-                if current_price <= 0.0 {
+                let current_price = dec(current_price);
+                if current_price <= Decimal::ZERO {
                     continue;
                 }
 
@@ -266,6 +791,18 @@ impl PositionMonitor {
                             }
                         }
 
+                        if order.side == "sell"
+                            && synthetic.observe(
+                                &order.order_id,
+                                order.limit_price,
+                                current_price,
+                                Instant::now(),
+                            )
+                        {
+                            Self::fill_synthetically(order, &mut synthetic, &tracker);
+                            continue;
+                        }
+
                         // Rate limit checks: only check every 2 seconds per order
                         if let Some(last_check) = order.last_check_time {
                             if last_check.elapsed() < Duration::from_secs(2) {
@@ -287,24 +824,38 @@ impl PositionMonitor {
                             // Check if filled (Price >= Limit)
                             if current_price >= order.limit_price {
                                 tracker.update_pending_order_check_time(&order.order_id);
-                                Self::check_pending_sell_order(&order, &*exchange, &tracker).await;
+                                Self::check_pending_sell_order(&order, &*exchange, &tracker, &bus)
+                                    .await;
                             }
 
                             // Check Stop Loss condition
                             if let Some(sl) = order.stop_loss {
                                 if current_price <= sl {
                                     warn!("[MONITOR] Price dropped to ${:.2} (SL ${:.2}). Cancelling Limit Sell and exiting.", current_price, sl);
-                                    // Cancel Limit Order
-                                    if let Err(e) = exchange.cancel_order(&order.order_id).await {
-                                        error!("Failed to cancel order {}: {}", order.order_id, e);
+                                    tracker.update_pending_order_check_time(&order.order_id);
+                                    let (outcome, tp_filled) =
+                                        Self::cancel_exit_leg(&order.order_id, "TP", &*exchange)
+                                            .await;
+                                    match outcome {
+                                        OcoOutcome::Retry => continue,
+                                        OcoOutcome::TakeProfitFilled => {
+                                            Self::report_take_profit_fill(order, tp_filled, &bus);
+                                            tracker.remove_pending_order(&order.order_id);
+                                            tracker.remove_position(&order.symbol);
+                                            continue;
+                                        }
+                                        OcoOutcome::StopMayFire => {
+                                            tracker.remove_pending_order(&order.order_id);
+                                        }
                                     }
-                                    tracker.remove_pending_order(&order.order_id);
 
                                     // Trigger Market Sell (Exit Signal)
                                     let pos_info = PositionInfo {
                                         symbol: order.symbol.clone(),
                                         entry_price: order.limit_price, // Approximate
-                                        qty: order.qty,
+                                        qty: order.qty - tp_filled,
+                                        filled_qty: order.qty - tp_filled,
+                                        remaining_qty: Decimal::ZERO,
                                         stop_loss: sl,
                                         take_profit: order.limit_price,
                                         entry_time: order.created_at.clone(),
@@ -316,6 +867,9 @@ impl PositionMonitor {
                                         highest_price: order.limit_price,
                                         trailing_stop_active: false,
                                         trailing_stop_price: sl,
+                                        bracket_managed: false,
+                                        stop_order_id: None,
+                                        correlation_id: None,
                                     };
                                     Self::generate_exit_signal(
                                         &pos_info,
@@ -330,17 +884,75 @@ impl PositionMonitor {
                     }
                 }
 
-                if let Some(position) = tracker.get_position(&symbol) {
+                if let Some(mut position) = tracker.get_position(&symbol) {
                     // Skip if already closing
                     if position.is_closing {
                         continue;
                     }
 
+                    // Native bracket: the exchange owns both exit legs. Only verify the
+                    // position is gone once price crosses either leg.
+                    if position.bracket_managed {
+                        if current_price >= position.take_profit
+                            || current_price <= position.stop_loss
+                        {
+                            Self::verify_bracket_position(&position, &*exchange, &tracker).await;
+                        }
+                        continue;
+                    }
+
+                    // Max hold: a dead trade exits through the stop path like an operator close
+                    if position.stop_loss != Decimal::MAX {
+                        if let Some(max_hold) = config.max_hold_for(&position.symbol) {
+                            let held = position.held_for(chrono::Utc::now());
+                            if held.is_some_and(|h| h >= max_hold) {
+                                let reason =
+                                    format!("max hold {}m reached", max_hold.num_minutes());
+                                Self::close_at_next_tick(&position.symbol, &reason, &tracker);
+                                position.stop_loss = Decimal::MAX;
+                                position.trailing_stop_active = false;
+                            }
+                        }
+                    }
+
+                    // Futures: leave before the exchange liquidates the position
+                    if Self::guard_liquidation(&position, current_price, &config, &tracker) {
+                        position.stop_loss = Decimal::MAX;
+                        position.trailing_stop_active = false;
+                    }
+
+                    // The exchange holds the stop-loss; TP and forced closes cancel it first
+                    if let Some(stop_order_id) = position.stop_order_id.clone() {
+                        Self::watch_native_stop(
+                            &position,
+                            &stop_order_id,
+                            current_price,
+                            &*exchange,
+                            &tracker,
+                            &bus,
+                        )
+                        .await;
+                        continue;
+                    }
+
                     // IMPORTANT: Check if position has an exit order
                     // If open_order_id is None, this position is orphaned!
-                    if position.open_order_id.is_none() {
-                        // Check if we've exceeded retry attempts
-                        if position.recreate_attempts >= 3 {
+                    // (Not while the entry is still working: its TP goes out with the final fill)
+                    if position.open_order_id.is_none() && position.remaining_qty <= Decimal::ZERO {
+                        let safe_mode = watchdog.as_ref().is_some_and(|w| w.is_safe_mode());
+
+                        // Attempts made during a venue outage don't count once it recovers
+                        if let (Some(w), Some(last_attempt)) =
+                            (&watchdog, position.last_recreate_attempt)
+                        {
+                            if position.recreate_attempts > 0 && w.recovered_after(last_attempt) {
+                                position.recreate_attempts = 0;
+                                tracker.add_position(position.clone());
+                            }
+                        }
+
+                        // Check if we've exceeded retry attempts (never give up in safety mode)
+                        if position.recreate_attempts >= 3 && !safe_mode {
                             error!(
                                 "❌ [MONITOR] Position {} has failed {} recreation attempts - removing from tracker",
                                 position.symbol, position.recreate_attempts
@@ -349,10 +961,14 @@ impl PositionMonitor {
                             continue;
                         }
 
-                        // Rate limit recreation attempts - only try every 30 seconds
+                        // Rate limit recreation attempts - every 30 seconds, escalating in safety mode
+                        let retry_delay = match &watchdog {
+                            Some(w) => w.exit_retry_delay(position.recreate_attempts),
+                            None => Duration::from_secs(30),
+                        };
                         if let Some(last_attempt) = position.last_recreate_attempt {
                             let elapsed = last_attempt.elapsed();
-                            if elapsed < Duration::from_secs(30) {
+                            if elapsed < retry_delay {
                                 // Too soon to retry - skip this iteration
                                 continue;
                             }
@@ -401,18 +1017,69 @@ impl PositionMonitor {
                         }
                     }
 
-                    // If we have an open Limit Sell (TP), we don't need to check TP here,
-                    // but we DO need to check SL (which is handled above if we track it as PendingOrder).
-                    // If we have open_order_id, we assume it's being tracked as PendingOrder.
-                    if position.open_order_id.is_some() {
+                    // If we have an open Limit Sell (TP), the pending-order check handles TP.
+                    // SL is emulated OCO: cancel the TP leg and confirm it did not fill
+                    // before firing the stop, so the same qty is never sold twice.
+                    if let Some(tp_order_id) = position.open_order_id.clone() {
+                        if current_price > position.stop_loss {
+                            continue;
+                        }
+                        let recently_checked = pending_orders
+                            .iter()
+                            .find(|o| o.order_id == tp_order_id)
+                            .and_then(|o| o.last_check_time)
+                            .is_some_and(|t| t.elapsed() < Duration::from_secs(2));
+                        if recently_checked {
+                            continue;
+                        }
+                        tracker.update_pending_order_check_time(&tp_order_id);
+
+                        warn!("[MONITOR] STOP LOSS for {} with TP leg {} open: current={:.8} sl={:.8}. Cancelling TP leg.",
+                              position.symbol, tp_order_id, current_price, position.stop_loss);
+                        let (outcome, tp_filled) =
+                            Self::cancel_exit_leg(&tp_order_id, "TP", &*exchange).await;
+                        match outcome {
+                            OcoOutcome::Retry => {}
+                            OcoOutcome::TakeProfitFilled => {
+                                info!(
+                                    "💰 [MONITOR] TP leg {} filled before cancel - {} already closed",
+                                    tp_order_id, position.symbol
+                                );
+                                if let Some(order) =
+                                    pending_orders.iter().find(|o| o.order_id == tp_order_id)
+                                {
+                                    Self::report_take_profit_fill(order, tp_filled, &bus);
+                                }
+                                tracker.remove_pending_order(&tp_order_id);
+                                tracker.remove_position(&position.symbol);
+                            }
+                            OcoOutcome::StopMayFire => {
+                                tracker.remove_pending_order(&tp_order_id);
+                                let mut remaining = position.clone();
+                                remaining.open_order_id = None;
+                                remaining.set_filled_qty(remaining.filled_qty - tp_filled);
+                                if remaining.filled_qty <= Decimal::ZERO {
+                                    tracker.remove_position(&position.symbol);
+                                    continue;
+                                }
+                                tracker.add_position(remaining.clone());
+                                Self::generate_exit_signal(
+                                    &remaining,
+                                    stop_exit_reason(&remaining),
+                                    current_price,
+                                    &bus,
+                                )
+                                .await;
+                                tracker.mark_closing(&position.symbol);
+                            }
+                        }
                         continue;
                     }
 
-                    let pl_pct =
-                        ((current_price - position.entry_price) / position.entry_price) * 100.0;
+                    let pl_pct = pl_pct(position.entry_price, current_price);
 
                     // In verbose mode, log a heartbeat of position evaluation.
-                    if config.chatter_level.to_lowercase() == "verbose" {
+                    if config.chatter_for(ChatterModule::Monitor).is_verbose() {
                         info!("[MONITOR] Check {}: entry={:.8} current={:.8} pl={:.2}% sl={:.8} tp={:.8}",
                               position.symbol, position.entry_price, current_price, pl_pct, position.stop_loss, position.take_profit);
                     }
@@ -429,8 +1096,13 @@ impl PositionMonitor {
                     if current_price <= position.stop_loss {
                         warn!("[MONITOR] SELL trigger (STOP LOSS) for {}: entry={:.8} current={:.8} ({:.2}%) sl={:.8}",
                               position.symbol, position.entry_price, current_price, pl_pct, position.stop_loss);
-                        Self::generate_exit_signal(&position, "stop_loss", current_price, &bus)
-                            .await;
+                        Self::generate_exit_signal(
+                            &position,
+                            stop_exit_reason(&position),
+                            current_price,
+                            &bus,
+                        )
+                        .await;
                         tracker.mark_closing(&position.symbol); // Mark as closing instead of removing
                         continue;
                     }
@@ -456,19 +1128,29 @@ impl PositionMonitor {
                     if symbol.is_empty() || tracker.has_position(&symbol) {
                         continue;
                     }
+                    // A hedge left by an earlier session is not a long to manage
+                    if config.hedging.enabled && symbol == config.hedging.instrument {
+                        warn!(
+                            "⚠️  [MONITOR] Not adopting {} {}: it is the hedge instrument",
+                            pos.qty, symbol
+                        );
+                        continue;
+                    }
 
-                    let avg_entry = pos.avg_entry_price.unwrap_or(0.0);
+                    let avg_entry = pos.avg_entry_price.unwrap_or_default();
                     let qty = pos.qty;
 
-                    if avg_entry > 0.0 {
+                    if avg_entry > Decimal::ZERO {
                         let (tp_pct, sl_pct) = config.get_symbol_params(&symbol);
-                        let stop_loss = avg_entry * (1.0 - sl_pct / 100.0);
-                        let take_profit = avg_entry * (1.0 + tp_pct / 100.0);
+                        let stop_loss = offset_pct(avg_entry, -sl_pct);
+                        let take_profit = offset_pct(avg_entry, tp_pct);
 
                         let pos_info = PositionInfo {
                             symbol: symbol.clone(),
                             entry_price: avg_entry,
                             qty,
+                            filled_qty: qty,
+                            remaining_qty: Decimal::ZERO,
                             stop_loss,
                             take_profit,
                             entry_time: chrono::Utc::now().to_rfc3339(),
@@ -480,6 +1162,9 @@ impl PositionMonitor {
                             highest_price: avg_entry,
                             trailing_stop_active: false,
                             trailing_stop_price: stop_loss,
+                            bracket_managed: false,
+                            stop_order_id: None,
+                            correlation_id: None,
                         };
 
                         tracker.add_position(pos_info.clone());
@@ -504,11 +1189,224 @@ impl PositionMonitor {
         }
     }
 
+    /// REST ticks for open positions whose quote stream went quiet. Positions
+    /// due for a forced close are marked first, so their tick exits them; if
+    /// REST has no price either, the last streamed price stands in.
+    async fn poll_stale_positions(
+        watch: &mut StaleQuoteWatch,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+    ) -> VecDeque<(String, f64)> {
+        let symbols: Vec<String> = tracker
+            .get_all_positions()
+            .into_iter()
+            .filter(|p| !p.bracket_managed && !p.is_closing)
+            .map(|p| p.symbol)
+            .collect();
+        let mut ticks = VecDeque::new();
+        for (symbol, action) in watch.check(&symbols, Instant::now()) {
+            if action == StaleAction::Close {
+                Self::close_at_next_tick(&symbol, "stale market data", tracker);
+            }
+            let bid = match exchange.get_best_bid_ask(&symbol).await {
+                Ok(book) => book.map(|b| b.bid),
+                Err(e) => {
+                    warn!("⚠️ [STALE-DATA] REST price for {} failed: {}", symbol, e);
+                    None
+                }
+            };
+            let bid = match bid {
+                Some(bid) => Some(bid),
+                None if action == StaleAction::Close => watch.last_price(&symbol),
+                None => None,
+            };
+            if let Some(bid) = bid {
+                ticks.push_back((symbol, float(bid)));
+            }
+        }
+        ticks
+    }
+
+    /// Inside the pre-close window: close every position not already closing
+    /// (bracket-managed ones keep their exchange legs).
+    fn flatten_before_close(calendar: &TradingCalendar, tracker: &PositionTracker) {
+        if !calendar.should_flatten(chrono::Utc::now()) {
+            return;
+        }
+        for position in tracker.get_all_positions() {
+            if position.stop_loss != Decimal::MAX && !position.bracket_managed {
+                Self::close_at_next_tick(&position.symbol, "market close", tracker);
+            }
+        }
+    }
+
+    /// Force an exit on the next quote for `symbol`: pull the stop up to
+    /// `Decimal::MAX` so the tick exits through the stop-loss path, which cancels any TP leg
+    /// first (emulated OCO).
+    fn close_at_next_tick(symbol: &str, reason: &str, tracker: &PositionTracker) {
+        let Some(mut position) = tracker.get_position(symbol) else {
+            return;
+        };
+        if position.is_closing {
+            return;
+        }
+        if position.bracket_managed {
+            warn!(
+                "🚫 [MONITOR] Not closing {} ({}) - exits are exchange-managed (bracket), leaving legs in place",
+                symbol, reason
+            );
+            return;
+        }
+        warn!("🚫 [MONITOR] Closing {} at next tick ({})", symbol, reason);
+        position.stop_loss = Decimal::MAX;
+        position.trailing_stop_active = false;
+        tracker.add_position(position);
+    }
+
+    /// Futures: close `position` at the next tick once `price` is within
+    /// `liquidation_buffer_pct` of its estimated liquidation price. Returns
+    /// whether it was flagged.
+    fn guard_liquidation(
+        position: &PositionInfo,
+        price: Decimal,
+        config: &AppConfig,
+        tracker: &PositionTracker,
+    ) -> bool {
+        let Some(futures) = config.futures() else {
+            return false;
+        };
+        if position.stop_loss == Decimal::MAX
+            || price > liquidation_guard_price(&position.symbol, position.entry_price, futures)
+        {
+            return false;
+        }
+        let reason = format!(
+            "price {:.8} within {}% of est. liquidation {:.8}",
+            price,
+            futures.liquidation_buffer_pct,
+            liquidation_price(&position.symbol, position.entry_price, futures)
+        );
+        Self::close_at_next_tick(&position.symbol, &reason, tracker);
+        true
+    }
+
+    /// Operator TP/SL change. A resting TP limit (or native stop) is cancelled
+    /// and re-placed at the new price; if its cancel can't be confirmed the
+    /// old leg stays live.
+    async fn adjust_exits(
+        symbol: &str,
+        take_profit: Option<Decimal>,
+        stop_loss: Option<Decimal>,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+    ) {
+        let Some(mut position) = tracker.get_position(symbol) else {
+            warn!(
+                "⚠️ [MONITOR] Adjust exits: no tracked position for {}",
+                symbol
+            );
+            return;
+        };
+        if position.is_closing || position.bracket_managed {
+            warn!(
+                "⚠️ [MONITOR] Adjust exits for {} ignored (closing or bracket-managed)",
+                symbol
+            );
+            return;
+        }
+        if let Some(sl) = stop_loss {
+            position.stop_loss = sl;
+            // A manual stop replaces the trailing one
+            position.trailing_stop_active = false;
+        }
+        if let Some(tp) = take_profit {
+            position.take_profit = tp;
+        }
+        info!(
+            "🎛️ [MONITOR] Exits for {} set by operator: TP=${:.8} SL=${:.8}",
+            symbol, position.take_profit, position.stop_loss
+        );
+        tracker.add_position(position.clone());
+
+        if let Some(stop_order_id) = position
+            .stop_order_id
+            .clone()
+            .filter(|_| stop_loss.is_some())
+        {
+            let (outcome, stop_filled) =
+                Self::cancel_exit_leg(&stop_order_id, "stop", exchange).await;
+            match outcome {
+                OcoOutcome::TakeProfitFilled => {
+                    info!(
+                        "🛑 [MONITOR] Native stop {} filled before it could be moved - {} already closed",
+                        stop_order_id, symbol
+                    );
+                    tracker.remove_position(symbol);
+                    return;
+                }
+                OcoOutcome::StopMayFire => {
+                    position.set_filled_qty(position.filled_qty - stop_filled);
+                    if position.filled_qty <= Decimal::ZERO {
+                        tracker.remove_position(symbol);
+                        return;
+                    }
+                    position.stop_order_id = Self::place_native_stop(&position, exchange).await;
+                    tracker.add_position(position.clone());
+                }
+                OcoOutcome::Retry => {
+                    warn!(
+                        "⚠️ [MONITOR] Native stop {} for {} not confirmed cancelled - old stop stays live",
+                        stop_order_id, symbol
+                    );
+                }
+            }
+        }
+
+        let Some(tp_order_id) = position
+            .open_order_id
+            .clone()
+            .filter(|_| take_profit.is_some())
+        else {
+            return;
+        };
+        if tracker.replace_take_profit(symbol, exchange).await {
+            return;
+        }
+        let (outcome, tp_filled) = Self::cancel_exit_leg(&tp_order_id, "TP", exchange).await;
+        match outcome {
+            OcoOutcome::TakeProfitFilled => {
+                info!(
+                    "💰 [MONITOR] TP leg {} filled before it could be moved - {} already closed",
+                    tp_order_id, symbol
+                );
+                tracker.remove_pending_order(&tp_order_id);
+                tracker.remove_position(symbol);
+            }
+            OcoOutcome::StopMayFire => {
+                tracker.remove_pending_order(&tp_order_id);
+                position.open_order_id = None;
+                position.set_filled_qty(position.filled_qty - tp_filled);
+                if position.filled_qty <= Decimal::ZERO {
+                    tracker.remove_position(symbol);
+                    return;
+                }
+                tracker.add_position(position.clone());
+                Self::recreate_limit_sell_order(&position, exchange, tracker).await;
+            }
+            OcoOutcome::Retry => {
+                warn!(
+                    "⚠️ [MONITOR] TP leg {} for {} not confirmed cancelled - old TP stays live",
+                    tp_order_id, symbol
+                );
+            }
+        }
+    }
+
     async fn check_position(
         position: &PositionInfo,
         _tracker: &PositionTracker,
         _bus: &EventBus,
-    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> crate::error::Result<bool> {
         // Polling-based exit requires market data access; quote-driven is preferred.
         // Keep polling mode as a no-op for now.
         let _ = position;
@@ -518,27 +1416,48 @@ impl PositionMonitor {
     async fn generate_exit_signal(
         position: &PositionInfo,
         reason: &str,
-        current_price: f64,
+        current_price: Decimal,
         bus: &EventBus,
     ) {
-        let pl_pct = ((current_price - position.entry_price) / position.entry_price) * 100.0;
+        let pl_pct = pl_pct(position.entry_price, current_price);
 
         let thesis = format!(
             "Exit signal for {} due to {}. Entry: ${:.8}, Current: ${:.8}, P/L: {:.2}%",
             position.symbol, reason, position.entry_price, current_price, pl_pct
         );
 
+        // Adopted positions have no entry id; their exit starts one
+        let correlation_id = position
+            .correlation_id
+            .clone()
+            .unwrap_or_else(new_correlation_id);
         let signal = AnalysisSignal {
             symbol: position.symbol.clone(),
             signal: "sell".to_string(),
             confidence: 1.0, // High confidence - triggered by rule
             thesis,
             market_context: format!("Reason: {}", reason),
+            strategy_id: None,
+            correlation_id: Some(correlation_id.clone()),
+            origin: Some(TradeOrigin::new(TradeOrigin::MONITOR_EXIT)),
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            // The stop a stop-loss exit fired at (`stop_limit`, exit slippage)
+            stop_loss: reason
+                .starts_with("stop_loss")
+                .then_some(position.stop_loss)
+                .filter(|stop| *stop != Decimal::MAX),
+            valid_until: None,
+            metadata: HashMap::new(),
         };
 
         match bus.publish(Event::Signal(signal)) {
             Ok(_) => {
-                info!("✅ [MONITOR] Exit signal published for {}", position.symbol);
+                info!(
+                    correlation_id = %correlation_id,
+                    "✅ [MONITOR] Exit signal published for {}", position.symbol
+                );
             }
             Err(e) => {
                 error!("❌ [MONITOR] Failed to publish exit signal: {}", e);
@@ -557,103 +1476,33 @@ impl PositionMonitor {
                 if ack.status.eq_ignore_ascii_case("filled") {
                     // IMPORTANT: Extract actual filled quantity from order response
                     // This prevents "insufficient balance" errors from quantity mismatches
-                    let filled_qty = ack
-                        .raw
-                        .get("filled_qty")
-                        .and_then(|v| v.as_str())
-                        .and_then(|s| s.parse::<f64>().ok())
-                        .or_else(|| ack.raw.get("filled_qty").and_then(|v| v.as_f64()))
-                        .unwrap_or(order.qty);
-
-                    // Warn if there's a quantity mismatch
-                    if (filled_qty - order.qty).abs() > 0.000001 {
-                        warn!(
-                            "⚠️ [MONITOR] Quantity mismatch for {}: ordered={}, filled={} - using filled qty",
-                            order.symbol, order.qty, filled_qty
-                        );
-                    }
-
-                    info!(
-                        "✅ [MONITOR] Pending BUY filled: {} qty={} @ ${:.2}",
-                        order.symbol, filled_qty, order.limit_price
-                    );
-                    tracker.remove_pending_order(&order.order_id);
-
-                    let (tp_pct, sl_pct) = config.get_symbol_params(&order.symbol);
-                    // IMPORTANT: Always recalculate TP/SL based on actual fill price
-                    // The signal's TP might be stale (calculated from mid at signal time)
-                    // which could be LOWER than the aggressive buy limit price
-                    let fill_price = order.limit_price;
-                    let take_profit_price = fill_price * (1.0 + tp_pct / 100.0);
-                    let stop_loss_price = fill_price * (1.0 - sl_pct / 100.0);
-
-                    info!("📊 [MONITOR] Calculating TP/SL from fill price ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
-                          fill_price, take_profit_price, tp_pct, stop_loss_price, sl_pct);
-
-                    // Create Position with ACTUAL filled quantity
-                    let mut pos_info = PositionInfo {
-                        symbol: order.symbol.clone(),
-                        entry_price: fill_price,
-                        qty: filled_qty, // Use actual filled qty
-                        stop_loss: stop_loss_price,
-                        take_profit: take_profit_price,
-                        entry_time: chrono::Utc::now().to_rfc3339(),
-                        side: "buy".to_string(),
-                        is_closing: false,
-                        open_order_id: None,
-                        last_recreate_attempt: None,
-                        recreate_attempts: 0,
-                        highest_price: fill_price,
-                        trailing_stop_active: false,
-                        trailing_stop_price: stop_loss_price,
-                    };
-
-                    // Submit Limit Sell (TP) with ACTUAL filled quantity
-                    let tp_req = ExPlaceOrderRequest {
-                        symbol: order.symbol.clone(),
-                        side: ExSide::Sell,
-                        order_type: ExOrderType::Limit,
-                        qty: Some(filled_qty), // Use actual filled qty
-                        notional: None,
-                        limit_price: Some(pos_info.take_profit),
-                        time_in_force: ExTimeInForce::Gtc, // Crypto usually GTC
-                    };
-
-                    info!(
-                        "🚀 [MONITOR] Submitting Take Profit Limit Sell for {} @ ${:.2}",
-                        order.symbol, pos_info.take_profit
-                    );
-                    match exchange.submit_order(tp_req).await {
-                        Ok(res) => {
-                            info!("✅ [MONITOR] TP Limit Sell Placed: {}", res.id);
-                            pos_info.open_order_id = Some(res.id.clone());
-
-                            // Add TP to Pending Orders
-                            // NOTE: We don't set stop_loss on the sell order itself.
-                            // The position is monitored separately for SL conditions.
-                            // This prevents the TP sell from being cancelled due to SL.
-                            let tp_pending = PendingOrder {
-                                order_id: res.id,
-                                symbol: order.symbol.clone(),
-                                side: "sell".to_string(),
-                                limit_price: pos_info.take_profit,
-                                qty: filled_qty, // Use actual filled qty
-                                created_at: chrono::Utc::now().to_rfc3339(),
-                                stop_loss: None, // Don't attach SL to the sell order
-                                take_profit: None,
-                                last_check_time: None,
-                            };
-                            tracker.add_pending_order(tp_pending);
-                        }
-                        Err(e) => {
-                            error!("❌ [MONITOR] Failed to place TP Limit Sell: {}", e);
+                    let filled_qty = filled_qty(&ack.raw).unwrap_or(order.qty);
+                    Self::open_filled_entry(order, filled_qty, exchange, tracker, config).await;
+                } else if ack.status.eq_ignore_ascii_case("partially_filled") {
+                    // Book executions the user stream has not reported yet
+                    if let Some(filled) = filled_qty(&ack.raw) {
+                        if filled > order.filled_qty {
+                            tracker.record_entry_fill(
+                                &order.order_id,
+                                filled - order.filled_qty,
+                                order.limit_price,
+                            );
                         }
                     }
-
-                    tracker.add_position(pos_info);
                 } else if ack.status.eq_ignore_ascii_case("canceled")
                     || ack.status.eq_ignore_ascii_case("expired")
                 {
+                    let filled = filled_qty(&ack.raw)
+                        .unwrap_or(order.filled_qty)
+                        .max(order.filled_qty);
+                    if filled > Decimal::ZERO {
+                        info!(
+                            "⚠️ [MONITOR] Pending BUY canceled/expired after partial fill: {} filled={}",
+                            order.symbol, filled
+                        );
+                        Self::open_filled_entry(order, filled, exchange, tracker, config).await;
+                        return;
+                    }
                     info!(
                         "❌ [MONITOR] Pending BUY canceled/expired: {}",
                         order.symbol
@@ -665,10 +1514,160 @@ impl PositionMonitor {
         }
     }
 
+    /// Turn a finished entry (fully filled, or cancelled after a partial fill)
+    /// into a position of `filled_qty` and place its take-profit.
+    async fn open_filled_entry(
+        order: &PendingOrder,
+        filled_qty: Decimal,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+        config: &AppConfig,
+    ) {
+        // Warn if there's a quantity mismatch
+        if filled_qty != order.qty {
+            warn!(
+                "⚠️ [MONITOR] Quantity mismatch for {}: ordered={}, filled={} - using filled qty",
+                order.symbol, order.qty, filled_qty
+            );
+        }
+
+        info!(
+            "✅ [MONITOR] Pending BUY filled: {} qty={} @ ${:.2}",
+            order.symbol, filled_qty, order.limit_price
+        );
+        tracker.remove_pending_order(&order.order_id);
+
+        let (tp_pct, sl_pct) = config.get_symbol_params(&order.symbol);
+        // IMPORTANT: Always recalculate TP/SL based on actual fill price
+        // The signal's TP might be stale (calculated from mid at signal time)
+        // which could be LOWER than the aggressive buy limit price
+        // Partial fills already booked carry the VWAP of the executions
+        let fill_price = tracker
+            .get_position(&order.symbol)
+            .filter(|p| p.filled_qty > Decimal::ZERO)
+            .map(|p| p.entry_price)
+            .unwrap_or(order.limit_price);
+        let take_profit_price = offset_pct(fill_price, tp_pct);
+        let stop_loss_price = offset_pct(fill_price, -sl_pct);
+
+        info!("📊 [MONITOR] Calculating TP/SL from fill price ${:.8}: TP=${:.8} (+{:.2}%), SL=${:.8} (-{:.2}%)",
+              fill_price, take_profit_price, tp_pct, stop_loss_price, sl_pct);
+
+        // Create Position with ACTUAL filled quantity
+        let mut pos_info = PositionInfo {
+            symbol: order.symbol.clone(),
+            entry_price: fill_price,
+            qty: filled_qty, // Use actual filled qty
+            filled_qty,
+            remaining_qty: Decimal::ZERO,
+            stop_loss: stop_loss_price,
+            take_profit: take_profit_price,
+            entry_time: chrono::Utc::now().to_rfc3339(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: None,
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: fill_price,
+            trailing_stop_active: false,
+            trailing_stop_price: stop_loss_price,
+            bracket_managed: order.bracket,
+            stop_order_id: None,
+            correlation_id: order.correlation_id.clone(),
+        };
+
+        // Bracket entry: TP/SL legs already rest on the exchange.
+        if order.bracket {
+            if let (Some(tp), Some(sl)) = (order.take_profit, order.stop_loss) {
+                pos_info.take_profit = tp;
+                pos_info.stop_loss = sl;
+                pos_info.trailing_stop_price = sl;
+            }
+            tracker.add_position(pos_info);
+            return;
+        }
+
+        for mode in ExitMode::fallbacks(&exchange.capabilities()) {
+            match mode {
+                // Observed like a bracket from here on
+                ExitMode::Oco => {
+                    if Self::place_native_oco(&pos_info, exchange).await {
+                        pos_info.bracket_managed = true;
+                        tracker.add_position(pos_info);
+                        return;
+                    }
+                }
+                // The stop-loss rests on the exchange, the TP is watched here
+                ExitMode::NativeStop => {
+                    if let Some(stop_order_id) = Self::place_native_stop(&pos_info, exchange).await
+                    {
+                        pos_info.stop_order_id = Some(stop_order_id);
+                        tracker.add_position(pos_info);
+                        return;
+                    }
+                }
+                ExitMode::Emulated => break,
+            }
+        }
+
+        // Submit Limit Sell (TP) with ACTUAL filled quantity
+        let tp_req = ExPlaceOrderRequest {
+            symbol: order.symbol.clone(),
+            side: ExSide::Sell,
+            order_type: ExOrderType::Limit,
+            qty: Some(filled_qty), // Use actual filled qty
+            notional: None,
+            limit_price: Some(pos_info.take_profit),
+            time_in_force: ExTimeInForce::Gtc, // Crypto usually GTC,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+
+        info!(
+            "🚀 [MONITOR] Submitting Take Profit Limit Sell for {} @ ${:.2}",
+            order.symbol, pos_info.take_profit
+        );
+        match exchange.submit_order(tp_req).await {
+            Ok(res) => {
+                info!("✅ [MONITOR] TP Limit Sell Placed: {}", res.id);
+                pos_info.open_order_id = Some(res.id.clone());
+
+                // Add TP to Pending Orders
+                // NOTE: We don't set stop_loss on the sell order itself.
+                // The position is monitored separately for SL conditions.
+                // This prevents the TP sell from being cancelled due to SL.
+                let tp_pending = PendingOrder {
+                    order_id: res.id,
+                    symbol: order.symbol.clone(),
+                    side: "sell".to_string(),
+                    limit_price: pos_info.take_profit,
+                    qty: filled_qty, // Use actual filled qty
+                    filled_qty: Decimal::ZERO,
+                    remaining_qty: filled_qty,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                    stop_loss: None, // Don't attach SL to the sell order
+                    take_profit: None,
+                    last_check_time: None,
+                    bracket: false,
+                    correlation_id: pos_info.correlation_id.clone(),
+                };
+                tracker.add_pending_order(tp_pending);
+            }
+            Err(e) => {
+                error!("❌ [MONITOR] Failed to place TP Limit Sell: {}", e);
+            }
+        }
+
+        tracker.add_position(pos_info);
+    }
+
     async fn check_pending_sell_order(
         order: &PendingOrder,
         exchange: &dyn TradingApi,
         tracker: &PositionTracker,
+        bus: &EventBus,
     ) {
         match exchange.get_order(&order.order_id).await {
             Ok(ack) => {
@@ -677,6 +1676,8 @@ impl PositionMonitor {
                         "💰 [MONITOR] Take Profit Limit Sell FILLED: {} @ ${:.2}",
                         order.symbol, order.limit_price
                     );
+                    let qty = filled_qty(&ack.raw).unwrap_or(order.qty);
+                    Self::report_take_profit_fill(order, qty, bus);
                     tracker.remove_pending_order(&order.order_id);
                     tracker.remove_position(&order.symbol);
                 } else if ack.status.eq_ignore_ascii_case("canceled")
@@ -708,6 +1709,334 @@ impl PositionMonitor {
         }
     }
 
+    /// Publish the fill of TP leg `order` seen by polling, so the reporter and
+    /// the services after it book the exit. Without a user-data stream nothing
+    /// else reports it; with one, the stream's own fill update is booked
+    /// instead (a polled "filled" report is not counted as a fill then).
+    fn report_take_profit_fill(order: &PendingOrder, qty: Decimal, bus: &EventBus) {
+        let qty = if qty > Decimal::ZERO { qty } else { order.qty };
+        let report = ExecutionReport {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id.clone(),
+            status: "filled".to_string(),
+            side: "sell".to_string(),
+            price: Some(order.limit_price),
+            qty: Some(qty),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: order.correlation_id.clone(),
+            metadata: HashMap::new(),
+        };
+        bus.publish(Event::Execution(report)).ok();
+    }
+
+    /// Close the position of TP leg `order` locally, as if the venue had filled it.
+    fn fill_synthetically(
+        order: &PendingOrder,
+        synthetic: &mut SyntheticFillWatch,
+        tracker: &PositionTracker,
+    ) {
+        tracker.remove_pending_order(&order.order_id);
+        let Some(position) = tracker.remove_position(&order.symbol) else {
+            return;
+        };
+        info!(
+            "🧪 [SYNTHETIC] TP {} for {} traded through ${:.8} - treated as filled, reconciling with the exchange",
+            order.order_id, order.symbol, order.limit_price
+        );
+        synthetic.record_fill(&order.order_id, position, order.limit_price, Instant::now());
+    }
+
+    /// Settle synthetic fills against the exchange: confirmed fills are
+    /// dropped, cancelled orders give their position back to the tracker
+    /// without an exit order so the orphan check re-places one.
+    async fn reconcile_synthetic_fills(
+        synthetic: &mut SyntheticFillWatch,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+    ) {
+        for order_id in synthetic.unreconciled() {
+            let status = match exchange.get_order(&order_id).await {
+                Ok(ack) => ack.status,
+                Err(e) => {
+                    warn!("⚠️ [SYNTHETIC] Could not look up TP {}: {}", order_id, e);
+                    continue;
+                }
+            };
+            match Reconciled::from_status(&status) {
+                Reconciled::Pending => {}
+                Reconciled::Confirmed => {
+                    if let Some(fill) = synthetic.resolve(&order_id) {
+                        info!(
+                            "✅ [SYNTHETIC] TP {} for {} confirmed filled by the exchange ({}s after the local fill)",
+                            order_id,
+                            fill.position.symbol,
+                            fill.filled_at.elapsed().as_secs()
+                        );
+                    }
+                }
+                Reconciled::Restore => {
+                    let Some(fill) = synthetic.resolve(&order_id) else {
+                        continue;
+                    };
+                    warn!(
+                        "🔄 [SYNTHETIC] TP {} for {} ended '{}' on the exchange - restoring the position",
+                        order_id, fill.position.symbol, status
+                    );
+                    let mut position = fill.position;
+                    if let Some(current) = tracker.get_position(&position.symbol) {
+                        // Re-entered meanwhile: the venue holds both
+                        let filled_qty = current.filled_qty + position.filled_qty;
+                        position = current;
+                        position.set_filled_qty(filled_qty);
+                    }
+                    position.open_order_id = None;
+                    position.is_closing = false;
+                    position.recreate_attempts = 0;
+                    position.last_recreate_attempt = None;
+                    tracker.add_position(position);
+                }
+            }
+        }
+    }
+
+    /// Emulated OCO: cancel the resting exit leg (the TP limit, or a native
+    /// stop), then read back its final state. Returns the outcome plus any qty
+    /// the leg filled before the cancel landed.
+    pub(crate) async fn cancel_exit_leg(
+        order_id: &str,
+        leg: &str,
+        exchange: &dyn TradingApi,
+    ) -> (OcoOutcome, Decimal) {
+        if let Err(e) = exchange.cancel_order(order_id).await {
+            warn!(
+                "⚠️ [MONITOR] Cancel of {} leg {} failed: {} (checking status)",
+                leg, order_id, e
+            );
+        }
+
+        match exchange.get_order(order_id).await {
+            Ok(ack) => {
+                let outcome = OcoOutcome::from_status(&ack.status);
+                let filled = filled_qty(&ack.raw).unwrap_or_default();
+                if outcome == OcoOutcome::Retry {
+                    warn!(
+                        "⏳ [MONITOR] {} leg {} still {} after cancel - will retry",
+                        leg, order_id, ack.status
+                    );
+                }
+                (outcome, filled)
+            }
+            Err(e) => {
+                error!(
+                    "❌ [MONITOR] Failed to verify {} leg {}: {}",
+                    leg, order_id, e
+                );
+                (OcoOutcome::Retry, Decimal::ZERO)
+            }
+        }
+    }
+
+    /// Rest the position's TP and stop-loss as a native OCO sell. False (logged)
+    /// if the venue refused it; the next exit mode is tried then.
+    async fn place_native_oco(position: &PositionInfo, exchange: &dyn TradingApi) -> bool {
+        let req = ExPlaceOrderRequest {
+            symbol: position.symbol.clone(),
+            side: ExSide::Sell,
+            order_type: ExOrderType::Limit,
+            qty: Some(position.filled_qty),
+            notional: None,
+            limit_price: Some(position.take_profit),
+            time_in_force: ExTimeInForce::Gtc,
+            bracket: None,
+            stop_price: Some(position.stop_loss),
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        let legs = BracketLegs {
+            take_profit: position.take_profit,
+            stop_loss: position.stop_loss,
+        };
+        match exchange.submit_oco_order(req, legs).await {
+            Ok(res) => {
+                info!(
+                    "🛡️ [MONITOR] Native OCO for {}: TP ${:.8} / SL ${:.8} ({})",
+                    position.symbol, position.take_profit, position.stop_loss, res.id
+                );
+                true
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ [MONITOR] Native OCO for {} refused ({}) - falling back",
+                    position.symbol, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Rest a stop-market sell of the position at its stop-loss. None (logged)
+    /// if the venue refused it; the position then falls back to a local stop.
+    async fn place_native_stop(
+        position: &PositionInfo,
+        exchange: &dyn TradingApi,
+    ) -> Option<String> {
+        let stop_req = ExPlaceOrderRequest {
+            symbol: position.symbol.clone(),
+            side: ExSide::Sell,
+            order_type: ExOrderType::StopMarket,
+            qty: Some(position.filled_qty),
+            notional: None,
+            limit_price: None,
+            time_in_force: ExTimeInForce::Gtc,
+            bracket: None,
+            stop_price: Some(position.stop_loss),
+            reduce_only: true,
+            metadata: HashMap::new(),
+        };
+        match exchange.submit_order(stop_req).await {
+            Ok(res) => {
+                info!(
+                    "🛡️ [MONITOR] Native stop for {} @ ${:.8}: {}",
+                    position.symbol, position.stop_loss, res.id
+                );
+                Some(res.id)
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ [MONITOR] Native stop for {} refused ({}) - using a local stop",
+                    position.symbol, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Position whose stop-loss rests on the exchange. Past the stop, read the
+    /// stop order back and drop the position once it filled. At the TP (or a
+    /// forced close) cancel the stop first, then exit through the execution
+    /// engine. Throttled via `last_recreate_attempt` like bracket checks.
+    async fn watch_native_stop(
+        position: &PositionInfo,
+        stop_order_id: &str,
+        price: Decimal,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+        bus: &EventBus,
+    ) {
+        let forced = position.stop_loss == Decimal::MAX;
+        let stopped = !forced && price <= position.stop_loss;
+        if !forced && !stopped && price < position.take_profit {
+            return;
+        }
+        if position
+            .last_recreate_attempt
+            .is_some_and(|last| last.elapsed() < Duration::from_secs(2))
+        {
+            return;
+        }
+        let mut checked = position.clone();
+        checked.last_recreate_attempt = Some(Instant::now());
+        tracker.add_position(checked.clone());
+
+        if stopped {
+            let status = match exchange.get_order(stop_order_id).await {
+                Ok(ack) => ack.status,
+                Err(e) => {
+                    warn!(
+                        "⚠️ [MONITOR] Could not check native stop {} for {}: {}",
+                        stop_order_id, position.symbol, e
+                    );
+                    return;
+                }
+            };
+            match OcoOutcome::from_status(&status) {
+                // The stop leg is the one that filled
+                OcoOutcome::TakeProfitFilled => {
+                    info!(
+                        "🛑 [MONITOR] Native stop {} filled on exchange for {}",
+                        stop_order_id, position.symbol
+                    );
+                    tracker.remove_position(&position.symbol);
+                }
+                OcoOutcome::StopMayFire => {
+                    warn!(
+                        "⚠️ [MONITOR] Native stop {} for {} ended '{}' - using a local stop",
+                        stop_order_id, position.symbol, status
+                    );
+                    checked.stop_order_id = None;
+                    tracker.add_position(checked);
+                }
+                // Not triggered yet, or still filling
+                OcoOutcome::Retry => {}
+            }
+            return;
+        }
+
+        let (outcome, stop_filled) = Self::cancel_exit_leg(stop_order_id, "stop", exchange).await;
+        match outcome {
+            OcoOutcome::Retry => {}
+            OcoOutcome::TakeProfitFilled => {
+                info!(
+                    "🛑 [MONITOR] Native stop {} filled before cancel - {} already closed",
+                    stop_order_id, position.symbol
+                );
+                tracker.remove_position(&position.symbol);
+            }
+            OcoOutcome::StopMayFire => {
+                checked.stop_order_id = None;
+                checked.set_filled_qty(checked.filled_qty - stop_filled);
+                if checked.filled_qty <= Decimal::ZERO {
+                    tracker.remove_position(&position.symbol);
+                    return;
+                }
+                tracker.add_position(checked.clone());
+                let reason = if forced {
+                    stop_exit_reason(&checked)
+                } else {
+                    "take_profit"
+                };
+                Self::generate_exit_signal(&checked, reason, price, bus).await;
+                tracker.mark_closing(&position.symbol);
+            }
+        }
+    }
+
+    /// A native bracket leg may have fired: drop the position once the exchange
+    /// no longer holds it. Throttled via `last_recreate_attempt`, which bracket
+    /// positions never use for recreation.
+    async fn verify_bracket_position(
+        position: &PositionInfo,
+        exchange: &dyn TradingApi,
+        tracker: &PositionTracker,
+    ) {
+        if let Some(last) = position.last_recreate_attempt {
+            if last.elapsed() < Duration::from_secs(2) {
+                return;
+            }
+        }
+        let mut checked = position.clone();
+        checked.last_recreate_attempt = Some(Instant::now());
+        tracker.add_position(checked);
+
+        match exchange.get_positions().await {
+            Ok(positions) => {
+                if !positions.iter().any(|p| p.symbol == position.symbol) {
+                    info!(
+                        "💰 [MONITOR] Bracket exit filled on exchange for {}",
+                        position.symbol
+                    );
+                    tracker.remove_position(&position.symbol);
+                }
+            }
+            Err(e) => warn!(
+                "⚠️ [MONITOR] Could not verify bracket position {}: {}",
+                position.symbol, e
+            ),
+        }
+    }
+
     /// Recreate a limit sell order for a position that lost its exit order
     async fn recreate_limit_sell_order(
         position: &PositionInfo,
@@ -727,11 +2056,12 @@ impl PositionMonitor {
                     (pos.qty, true)
                 } else {
                     // Position not found on exchange - likely already closed
+                    tracker.record_mismatch();
                     warn!(
                         "⚠️ [MONITOR] Position {} not found on exchange during verification - likely already closed",
                         position.symbol
                     );
-                    (0.0, false)
+                    (Decimal::ZERO, false)
                 }
             }
             Err(e) => {
@@ -739,7 +2069,7 @@ impl PositionMonitor {
                     "⚠️ [MONITOR] Could not verify holdings for {}: {} (using tracked qty)",
                     position.symbol, e
                 );
-                (position.qty, true) // Assume exists on API error
+                (position.filled_qty, true) // Assume exists on API error
             }
         };
 
@@ -754,24 +2084,25 @@ impl PositionMonitor {
         }
 
         // If actual quantity differs from tracked, update the position
-        let final_qty = if (actual_qty - position.qty).abs() > 0.000001 {
+        let final_qty = if actual_qty != position.filled_qty {
+            tracker.record_mismatch();
             warn!(
                 "⚠️ [MONITOR] Quantity mismatch for {}: tracked={}, actual={} - using actual",
-                position.symbol, position.qty, actual_qty
+                position.symbol, position.filled_qty, actual_qty
             );
 
             // Update the tracked position with correct quantity
             let mut corrected_pos = position.clone();
-            corrected_pos.qty = actual_qty;
+            corrected_pos.set_filled_qty(actual_qty);
             tracker.add_position(corrected_pos);
 
             actual_qty
         } else {
-            position.qty
+            position.filled_qty
         };
 
         // Safety check: Don't place order if qty is zero or negative
-        if final_qty <= 0.0 {
+        if final_qty <= Decimal::ZERO {
             warn!(
                 "⚠️ [MONITOR] Position {} has zero/negative quantity: {} - removing from tracker",
                 position.symbol, final_qty
@@ -788,6 +2119,10 @@ impl PositionMonitor {
             notional: None,
             limit_price: Some(position.take_profit),
             time_in_force: ExTimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            reduce_only: true,
+            metadata: HashMap::new(),
         };
 
         match exchange.submit_order(tp_req).await {
@@ -808,11 +2143,15 @@ impl PositionMonitor {
                     symbol: position.symbol.clone(),
                     side: "sell".to_string(),
                     limit_price: position.take_profit,
-                    qty: final_qty, // Use final_qty, not position.qty
+                    qty: final_qty, // Use final_qty, not position.filled_qty
+                    filled_qty: Decimal::ZERO,
+                    remaining_qty: final_qty,
                     created_at: chrono::Utc::now().to_rfc3339(),
                     stop_loss: None,
                     take_profit: None,
                     last_check_time: None,
+                    bracket: false,
+                    correlation_id: position.correlation_id.clone(),
                 };
                 tracker.add_pending_order(tp_pending);
             }
@@ -842,7 +2181,7 @@ impl PositionMonitor {
                                 );
 
                                 // Safety check
-                                if verified_qty <= 0.0 {
+                                if verified_qty <= Decimal::ZERO {
                                     error!(
                                         "❌ [MONITOR] Verified qty is invalid: {} - cannot retry",
                                         verified_qty
@@ -852,7 +2191,7 @@ impl PositionMonitor {
 
                                 // Update position with verified quantity
                                 let mut corrected_pos = position.clone();
-                                corrected_pos.qty = verified_qty;
+                                corrected_pos.set_filled_qty(verified_qty);
                                 tracker.add_position(corrected_pos);
 
                                 // Retry with verified quantity
@@ -864,6 +2203,10 @@ impl PositionMonitor {
                                     notional: None,
                                     limit_price: Some(position.take_profit),
                                     time_in_force: ExTimeInForce::Gtc,
+                                    bracket: None,
+                                    stop_price: None,
+                                    reduce_only: true,
+                                    metadata: HashMap::new(),
                                 };
 
                                 match exchange.submit_order(retry_req).await {
@@ -875,7 +2218,7 @@ impl PositionMonitor {
 
                                         // Update position with new order ID
                                         let mut updated_pos = position.clone();
-                                        updated_pos.qty = verified_qty;
+                                        updated_pos.set_filled_qty(verified_qty);
                                         updated_pos.open_order_id = Some(retry_res.id.clone());
                                         tracker.add_position(updated_pos);
 
@@ -886,10 +2229,14 @@ impl PositionMonitor {
                                             side: "sell".to_string(),
                                             limit_price: position.take_profit,
                                             qty: verified_qty,
+                                            filled_qty: Decimal::ZERO,
+                                            remaining_qty: verified_qty,
                                             created_at: chrono::Utc::now().to_rfc3339(),
                                             stop_loss: None,
                                             take_profit: None,
                                             last_check_time: None,
+                                            bracket: false,
+                                            correlation_id: position.correlation_id.clone(),
                                         };
                                         tracker.add_pending_order(tp_pending);
                                     }
@@ -907,6 +2254,7 @@ impl PositionMonitor {
                                 );
 
                                 // Position doesn't exist on exchange - remove from our tracker
+                                tracker.record_mismatch();
                                 tracker.remove_position(&position.symbol);
 
                                 info!(
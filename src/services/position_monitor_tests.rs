@@ -2,16 +2,33 @@
 
 #[cfg(test)]
 mod position_tracker_tests {
-    use crate::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, BinanceFuturesConfig};
+    use crate::error::AutoHedgeError;
+    use crate::events::{Event, ExecutionReport, MarketEvent};
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::symbol_registry::{SymbolRegistry, Venue};
+    use crate::exchange::types::{ExchangeCapabilities, Position, Side};
+    use crate::money::dec;
+    use crate::services::position_monitor::{
+        liquidation_guard_price, liquidation_price, ExitMode, OcoOutcome, PendingOrder,
+        PositionInfo, PositionMonitor, PositionTracker,
+    };
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     // Helper to create test positions
-    fn test_pos(symbol: &str, entry: f64, qty: f64) -> PositionInfo {
+    fn test_pos(symbol: &str, entry: Decimal, qty: Decimal) -> PositionInfo {
         PositionInfo {
             symbol: symbol.to_string(),
             entry_price: entry,
             qty,
-            stop_loss: entry * 0.98,
-            take_profit: entry * 1.02,
+            filled_qty: qty,
+            remaining_qty: Decimal::ZERO,
+            stop_loss: entry * dec(0.98),
+            take_profit: entry * dec(1.02),
             entry_time: chrono::Utc::now().to_rfc3339(),
             side: "buy".to_string(),
             is_closing: false,
@@ -20,7 +37,10 @@ mod position_tracker_tests {
             recreate_attempts: 0,
             highest_price: entry,
             trailing_stop_active: false,
-            trailing_stop_price: entry * 0.98,
+            trailing_stop_price: entry * dec(0.98),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         }
     }
 
@@ -39,7 +59,7 @@ mod position_tracker_tests {
     fn test_add_position() {
         let tracker = PositionTracker::new();
 
-        let pos = test_pos("BTC/USD", 50000.0, 0.1);
+        let pos = test_pos("BTC/USD", dec(50000.0), dec(0.1));
 
         tracker.add_position(pos);
 
@@ -47,25 +67,46 @@ mod position_tracker_tests {
         assert!(!tracker.has_position("ETH/USD"));
     }
 
+    #[test]
+    fn test_symbol_registry_resolves_venue_spellings() {
+        let registry = SymbolRegistry::new(Venue::Alpaca, &["BTC/USD".to_string()]);
+        let tracker = PositionTracker::new().with_symbol_registry(registry);
+
+        // Alpaca reports crypto positions as "BTCUSD"
+        tracker.add_position(test_pos("BTCUSD", dec(50000.0), dec(0.1)));
+        assert!(tracker.has_position("BTC/USD"));
+        assert_eq!(tracker.get_position("BTCUSD").unwrap().symbol, "BTC/USD");
+
+        tracker.mark_closing("BTCUSD");
+        assert!(tracker.get_position("BTC/USD").unwrap().is_closing);
+        assert!(tracker.remove_position("BTCUSD").is_some());
+        assert!(!tracker.has_position("BTC/USD"));
+    }
+
     #[test]
     fn test_get_position() {
         let tracker = PositionTracker::new();
 
         let pos = PositionInfo {
             symbol: "ETH/USD".to_string(),
-            entry_price: 3000.0,
-            qty: 1.0,
-            stop_loss: 2900.0,
-            take_profit: 3100.0,
+            entry_price: dec(3000.0),
+            qty: dec(1.0),
+            filled_qty: dec(1.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(2900.0),
+            take_profit: dec(3100.0),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: Some("order123".to_string()),
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 3000.0,
+            highest_price: dec(3000.0),
             trailing_stop_active: false,
-            trailing_stop_price: 2900.0,
+            trailing_stop_price: dec(2900.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         tracker.add_position(pos);
@@ -73,8 +114,8 @@ mod position_tracker_tests {
         let retrieved = tracker.get_position("ETH/USD");
         assert!(retrieved.is_some());
         let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.entry_price, 3000.0);
-        assert_eq!(retrieved.qty, 1.0);
+        assert_eq!(retrieved.entry_price, dec(3000.0));
+        assert_eq!(retrieved.qty, dec(1.0));
         assert_eq!(retrieved.open_order_id, Some("order123".to_string()));
     }
 
@@ -91,19 +132,24 @@ mod position_tracker_tests {
 
         let pos = PositionInfo {
             symbol: "SOL/USD".to_string(),
-            entry_price: 100.0,
-            qty: 10.0,
-            stop_loss: 95.0,
-            take_profit: 110.0,
+            entry_price: dec(100.0),
+            qty: dec(10.0),
+            filled_qty: dec(10.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(95.0),
+            take_profit: dec(110.0),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 100.0,
+            highest_price: dec(100.0),
             trailing_stop_active: false,
-            trailing_stop_price: 95.0,
+            trailing_stop_price: dec(95.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         tracker.add_position(pos);
@@ -128,19 +174,24 @@ mod position_tracker_tests {
         for symbol in &["BTC/USD", "ETH/USD", "SOL/USD"] {
             let pos = PositionInfo {
                 symbol: symbol.to_string(),
-                entry_price: 100.0,
-                qty: 1.0,
-                stop_loss: 95.0,
-                take_profit: 105.0,
+                entry_price: dec(100.0),
+                qty: dec(1.0),
+                filled_qty: dec(1.0),
+                remaining_qty: Decimal::ZERO,
+                stop_loss: dec(95.0),
+                take_profit: dec(105.0),
                 entry_time: "2025-01-01T00:00:00Z".to_string(),
                 side: "buy".to_string(),
                 is_closing: false,
                 open_order_id: None,
                 last_recreate_attempt: None,
                 recreate_attempts: 0,
-                highest_price: 100.0,
+                highest_price: dec(100.0),
                 trailing_stop_active: false,
-                trailing_stop_price: 95.0,
+                trailing_stop_price: dec(95.0),
+                bracket_managed: false,
+                stop_order_id: None,
+                correlation_id: None,
             };
             tracker.add_position(pos);
         }
@@ -155,19 +206,24 @@ mod position_tracker_tests {
 
         let pos = PositionInfo {
             symbol: "DOGE/USD".to_string(),
-            entry_price: 0.08,
-            qty: 10000.0,
-            stop_loss: 0.07,
-            take_profit: 0.09,
+            entry_price: dec(0.08),
+            qty: dec(10000.0),
+            filled_qty: dec(10000.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(0.07),
+            take_profit: dec(0.09),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 0.08,
+            highest_price: dec(0.08),
             trailing_stop_active: false,
-            trailing_stop_price: 0.07,
+            trailing_stop_price: dec(0.07),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         tracker.add_position(pos);
@@ -189,36 +245,46 @@ mod position_tracker_tests {
 
         let pos1 = PositionInfo {
             symbol: "XRP/USD".to_string(),
-            entry_price: 0.50,
-            qty: 1000.0,
-            stop_loss: 0.45,
-            take_profit: 0.55,
+            entry_price: dec(0.50),
+            qty: dec(1000.0),
+            filled_qty: dec(1000.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(0.45),
+            take_profit: dec(0.55),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 0.50,
+            highest_price: dec(0.50),
             trailing_stop_active: false,
-            trailing_stop_price: 0.45,
+            trailing_stop_price: dec(0.45),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         let pos2 = PositionInfo {
             symbol: "XRP/USD".to_string(),
-            entry_price: 0.55,
-            qty: 2000.0,
-            stop_loss: 0.50,
-            take_profit: 0.60,
+            entry_price: dec(0.55),
+            qty: dec(2000.0),
+            filled_qty: dec(2000.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(0.50),
+            take_profit: dec(0.60),
             entry_time: "2025-01-01T01:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 0.55,
+            highest_price: dec(0.55),
             trailing_stop_active: false,
-            trailing_stop_price: 0.50,
+            trailing_stop_price: dec(0.50),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         tracker.add_position(pos1);
@@ -226,8 +292,8 @@ mod position_tracker_tests {
 
         // Should have the second position
         let pos = tracker.get_position("XRP/USD").unwrap();
-        assert_eq!(pos.entry_price, 0.55);
-        assert_eq!(pos.qty, 2000.0);
+        assert_eq!(pos.entry_price, dec(0.55));
+        assert_eq!(pos.qty, dec(2000.0));
     }
 
     // ============= Pending Order Tests =============
@@ -240,12 +306,16 @@ mod position_tracker_tests {
             order_id: "order123".to_string(),
             symbol: "BTC/USD".to_string(),
             side: "buy".to_string(),
-            limit_price: 50000.0,
-            qty: 0.1,
+            limit_price: dec(50000.0),
+            qty: dec(0.1),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(0.1),
             created_at: "2025-01-01T00:00:00Z".to_string(),
-            stop_loss: Some(49000.0),
-            take_profit: Some(51000.0),
+            stop_loss: Some(dec(49000.0)),
+            take_profit: Some(dec(51000.0)),
             last_check_time: None,
+            bracket: false,
+            correlation_id: None,
         };
 
         tracker.add_pending_order(order);
@@ -255,6 +325,35 @@ mod position_tracker_tests {
         assert_eq!(orders[0].order_id, "order123");
     }
 
+    #[test]
+    fn test_held_symbols_include_working_entries() {
+        let tracker = PositionTracker::new();
+        tracker.add_position(test_pos("ETH/USD", dec(3000.0), dec(1.0)));
+        for (id, symbol, side) in [
+            ("o1", "BTC/USD", "buy"),
+            ("o2", "ETH/USD", "buy"),
+            ("o3", "SOL/USD", "sell"),
+        ] {
+            tracker.add_pending_order(PendingOrder {
+                order_id: id.to_string(),
+                symbol: symbol.to_string(),
+                side: side.to_string(),
+                limit_price: dec(100.0),
+                qty: dec(1.0),
+                filled_qty: Decimal::ZERO,
+                remaining_qty: dec(1.0),
+                created_at: "2025-01-01T00:00:00Z".to_string(),
+                stop_loss: None,
+                take_profit: None,
+                last_check_time: None,
+                bracket: false,
+                correlation_id: None,
+            });
+        }
+
+        assert_eq!(tracker.held_symbols(), ["BTC/USD", "ETH/USD"]);
+    }
+
     #[test]
     fn test_remove_pending_order() {
         let tracker = PositionTracker::new();
@@ -263,12 +362,16 @@ mod position_tracker_tests {
             order_id: "order456".to_string(),
             symbol: "ETH/USD".to_string(),
             side: "sell".to_string(),
-            limit_price: 3100.0,
-            qty: 1.0,
+            limit_price: dec(3100.0),
+            qty: dec(1.0),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(1.0),
             created_at: "2025-01-01T00:00:00Z".to_string(),
             stop_loss: None,
             take_profit: None,
             last_check_time: None,
+            bracket: false,
+            correlation_id: None,
         };
 
         tracker.add_pending_order(order);
@@ -295,12 +398,16 @@ mod position_tracker_tests {
                 order_id: format!("order{}", i),
                 symbol: format!("SYM{}/USD", i),
                 side: "buy".to_string(),
-                limit_price: 100.0 + i as f64,
-                qty: 1.0,
+                limit_price: Decimal::from(100 + i),
+                qty: dec(1.0),
+                filled_qty: Decimal::ZERO,
+                remaining_qty: dec(1.0),
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 stop_loss: None,
                 take_profit: None,
                 last_check_time: None,
+                bracket: false,
+                correlation_id: None,
             };
             tracker.add_pending_order(order);
         }
@@ -317,12 +424,16 @@ mod position_tracker_tests {
             order_id: "order789".to_string(),
             symbol: "SOL/USD".to_string(),
             side: "buy".to_string(),
-            limit_price: 100.0,
-            qty: 10.0,
+            limit_price: dec(100.0),
+            qty: dec(10.0),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(10.0),
             created_at: "2025-01-01T00:00:00Z".to_string(),
             stop_loss: None,
             take_profit: None,
             last_check_time: None,
+            bracket: false,
+            correlation_id: None,
         };
 
         tracker.add_pending_order(order);
@@ -340,25 +451,30 @@ mod position_tracker_tests {
     fn test_position_info_fields() {
         let pos = PositionInfo {
             symbol: "LTC/USD".to_string(),
-            entry_price: 80.0,
-            qty: 5.0,
-            stop_loss: 75.0,
-            take_profit: 88.0,
+            entry_price: dec(80.0),
+            qty: dec(5.0),
+            filled_qty: dec(5.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(75.0),
+            take_profit: dec(88.0),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: true,
             open_order_id: Some("tp_order".to_string()),
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 80.0,
+            highest_price: dec(80.0),
             trailing_stop_active: false,
-            trailing_stop_price: 75.0,
+            trailing_stop_price: dec(75.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         assert_eq!(pos.symbol, "LTC/USD");
-        assert_eq!(pos.entry_price, 80.0);
-        assert_eq!(pos.stop_loss, 75.0);
-        assert_eq!(pos.take_profit, 88.0);
+        assert_eq!(pos.entry_price, dec(80.0));
+        assert_eq!(pos.stop_loss, dec(75.0));
+        assert_eq!(pos.take_profit, dec(88.0));
         assert!(pos.is_closing);
     }
 
@@ -366,24 +482,42 @@ mod position_tracker_tests {
     fn test_position_info_clone() {
         let pos = PositionInfo {
             symbol: "DOT/USD".to_string(),
-            entry_price: 5.0,
-            qty: 100.0,
-            stop_loss: 4.5,
-            take_profit: 5.5,
+            entry_price: dec(5.0),
+            qty: dec(100.0),
+            filled_qty: dec(100.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(4.5),
+            take_profit: dec(5.5),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 5.0,
+            highest_price: dec(5.0),
             trailing_stop_active: false,
-            trailing_stop_price: 4.5,
+            trailing_stop_price: dec(4.5),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
 
         let cloned = pos.clone();
         assert_eq!(cloned.symbol, "DOT/USD");
-        assert_eq!(cloned.qty, 100.0);
+        assert_eq!(cloned.qty, dec(100.0));
+    }
+
+    #[test]
+    fn test_position_held_for() {
+        let mut pos = test_pos("BTC/USD", dec(50000.0), dec(0.1));
+        pos.entry_time = "2026-01-01T10:00:00Z".to_string();
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T11:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(pos.held_for(now), Some(chrono::Duration::minutes(90)));
+
+        pos.entry_time = "not a time".to_string();
+        assert_eq!(pos.held_for(now), None);
     }
 
     // ============= PendingOrder Struct Tests =============
@@ -394,17 +528,21 @@ mod position_tracker_tests {
             order_id: "test_order".to_string(),
             symbol: "SHIB/USD".to_string(),
             side: "sell".to_string(),
-            limit_price: 0.00001,
-            qty: 1000000.0,
+            limit_price: dec(0.00001),
+            qty: dec(1000000.0),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(1000000.0),
             created_at: "2025-01-01T00:00:00Z".to_string(),
-            stop_loss: Some(0.000009),
-            take_profit: Some(0.000011),
+            stop_loss: Some(dec(0.000009)),
+            take_profit: Some(dec(0.000011)),
             last_check_time: None,
+            bracket: false,
+            correlation_id: None,
         };
 
         assert_eq!(order.order_id, "test_order");
         assert_eq!(order.side, "sell");
-        assert_eq!(order.stop_loss, Some(0.000009));
+        assert_eq!(order.stop_loss, Some(dec(0.000009)));
     }
 
     #[test]
@@ -413,20 +551,409 @@ mod position_tracker_tests {
             order_id: "clone_test".to_string(),
             symbol: "ADA/USD".to_string(),
             side: "buy".to_string(),
-            limit_price: 0.35,
-            qty: 500.0,
+            limit_price: dec(0.35),
+            qty: dec(500.0),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(500.0),
             created_at: "2025-01-01T00:00:00Z".to_string(),
             stop_loss: None,
             take_profit: None,
             last_check_time: None,
+            bracket: false,
+            correlation_id: None,
         };
 
         let cloned = order.clone();
         assert_eq!(cloned.order_id, "clone_test");
     }
 
+    // ============= Liquidation Guard Tests =============
+
+    #[test]
+    fn test_liquidation_price_long() {
+        let futures = BinanceFuturesConfig {
+            leverage: 5,
+            maintenance_margin_pct: 0.5,
+            ..BinanceFuturesConfig::default()
+        };
+        // 100 * (1 - 1/5 + 0.005)
+        assert_eq!(
+            liquidation_price("BTC/USDT", dec(100.0), &futures),
+            dec(80.5)
+        );
+        // 2% above liquidation
+        assert_eq!(
+            liquidation_guard_price("BTC/USDT", dec(100.0), &futures),
+            dec(80.5) * dec(1.02)
+        );
+    }
+
+    #[test]
+    fn test_liquidation_price_unlevered() {
+        let futures = BinanceFuturesConfig {
+            leverage: 0,
+            ..BinanceFuturesConfig::default()
+        };
+        // Treated as 1x: only the maintenance margin separates it from zero
+        assert_eq!(
+            liquidation_price("BTC/USDT", dec(100.0), &futures),
+            dec(0.5)
+        );
+    }
+
+    #[test]
+    fn test_liquidation_price_symbol_leverage() {
+        let futures = BinanceFuturesConfig {
+            leverage: 5,
+            symbol_leverage: HashMap::from([("ETH/USDT".to_string(), 10)]),
+            ..BinanceFuturesConfig::default()
+        };
+        // 100 * (1 - 1/10 + 0.005)
+        assert_eq!(
+            liquidation_price("ETH/USDT", dec(100.0), &futures),
+            dec(90.5)
+        );
+        assert_eq!(
+            liquidation_price("BTC/USDT", dec(100.0), &futures),
+            dec(80.5)
+        );
+    }
+
+    // ============= Emulated OCO Tests =============
+
+    #[test]
+    fn test_oco_outcome_tp_filled() {
+        assert_eq!(
+            OcoOutcome::from_status("filled"),
+            OcoOutcome::TakeProfitFilled
+        );
+        assert_eq!(
+            OcoOutcome::from_status("FILLED"),
+            OcoOutcome::TakeProfitFilled
+        );
+    }
+
+    #[test]
+    fn test_oco_outcome_stop_may_fire() {
+        for status in ["canceled", "cancelled", "expired", "rejected"] {
+            assert_eq!(OcoOutcome::from_status(status), OcoOutcome::StopMayFire);
+        }
+    }
+
+    #[test]
+    fn test_oco_outcome_retry_while_cancel_in_flight() {
+        for status in ["new", "pending_cancel", "partially_filled", "accepted"] {
+            assert_eq!(OcoOutcome::from_status(status), OcoOutcome::Retry);
+        }
+    }
+
+    #[test]
+    fn test_exit_mode_fallbacks_follow_capabilities() {
+        let caps = |oco: bool, stop: bool| ExchangeCapabilities {
+            supports_notional_market_buy: false,
+            supports_ws_quotes: false,
+            supports_ws_trades: false,
+            supports_news: false,
+            supports_bracket_orders: false,
+            supports_order_replace: false,
+            supports_stop_orders: stop,
+            supports_oco: oco,
+            supports_short: false,
+            min_notional: Decimal::ZERO,
+        };
+        assert_eq!(
+            ExitMode::fallbacks(&caps(true, true)),
+            [ExitMode::Oco, ExitMode::NativeStop, ExitMode::Emulated]
+        );
+        assert_eq!(
+            ExitMode::fallbacks(&caps(false, true)),
+            [ExitMode::NativeStop, ExitMode::Emulated]
+        );
+        // Emulation is always the last resort
+        assert_eq!(
+            ExitMode::fallbacks(&caps(false, false)),
+            [ExitMode::Emulated]
+        );
+    }
+
+    // ============= Partial Fill Tests =============
+
+    fn test_entry(order_id: &str, symbol: &str, qty: Decimal) -> PendingOrder {
+        PendingOrder {
+            order_id: order_id.to_string(),
+            symbol: symbol.to_string(),
+            side: "buy".to_string(),
+            limit_price: dec(100.0),
+            qty,
+            filled_qty: Decimal::ZERO,
+            remaining_qty: qty,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            stop_loss: Some(dec(98.0)),
+            take_profit: Some(dec(102.0)),
+            last_check_time: None,
+            bracket: false,
+            correlation_id: Some(format!("trade-{}", order_id)),
+        }
+    }
+
+    #[test]
+    fn test_partial_fill_opens_position_for_filled_part() {
+        let tracker = PositionTracker::new();
+        tracker.add_pending_order(test_entry("entry1", "ETH/USD", dec(1.0)));
+
+        let order = tracker
+            .record_entry_fill("entry1", dec(0.4), dec(100.0))
+            .unwrap();
+        assert_eq!(order.filled_qty, dec(0.4));
+        assert_eq!(order.remaining_qty, dec(0.6));
+
+        let pos = tracker.get_position("ETH/USD").unwrap();
+        assert_eq!(pos.filled_qty, dec(0.4));
+        assert_eq!(pos.remaining_qty, dec(0.6));
+        assert_eq!(pos.qty, dec(1.0));
+        assert_eq!(pos.take_profit, dec(102.0));
+        assert_eq!(pos.stop_loss, dec(98.0));
+        assert_eq!(pos.correlation_id.as_deref(), Some("trade-entry1"));
+        // Entry stays pending until the rest fills
+        assert_eq!(tracker.get_all_pending_orders().len(), 1);
+    }
+
+    #[test]
+    fn test_partial_fills_accumulate_at_vwap() {
+        let tracker = PositionTracker::new();
+        tracker.add_pending_order(test_entry("entry1", "ETH/USD", dec(1.0)));
+
+        tracker.record_entry_fill("entry1", dec(0.5), dec(100.0));
+        let order = tracker
+            .record_entry_fill("entry1", dec(0.5), dec(102.0))
+            .unwrap();
+        assert!(order.remaining_qty.abs() < dec(1e-9));
+
+        let pos = tracker.get_position("ETH/USD").unwrap();
+        assert_eq!(pos.filled_qty, dec(1.0));
+        assert!(pos.remaining_qty.abs() < dec(1e-9));
+        assert_eq!(pos.entry_price, dec(101.0));
+    }
+
+    #[test]
+    fn test_fill_capped_at_remaining_qty() {
+        let tracker = PositionTracker::new();
+        tracker.add_pending_order(test_entry("entry1", "ETH/USD", dec(1.0)));
+
+        tracker.record_entry_fill("entry1", dec(0.8), dec(100.0));
+        let order = tracker
+            .record_entry_fill("entry1", dec(0.8), dec(100.0))
+            .unwrap();
+        assert_eq!(order.filled_qty, dec(1.0));
+
+        let pos = tracker.get_position("ETH/USD").unwrap();
+        assert_eq!(pos.filled_qty, dec(1.0));
+        assert_eq!(pos.entry_price, dec(100.0));
+    }
+
+    #[test]
+    fn test_fill_for_unknown_or_sell_order_ignored() {
+        let tracker = PositionTracker::new();
+        let mut tp = test_entry("tp1", "ETH/USD", dec(1.0));
+        tp.side = "sell".to_string();
+        tracker.add_pending_order(tp);
+
+        assert!(tracker
+            .record_entry_fill("tp1", dec(0.5), dec(100.0))
+            .is_none());
+        assert!(tracker
+            .record_entry_fill("missing", dec(0.5), dec(100.0))
+            .is_none());
+        assert!(!tracker.has_position("ETH/USD"));
+    }
+
+    fn fill_report(status: &str, qty: f64, price: f64) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "ETH/USD".to_string(),
+            order_id: "entry1".to_string(),
+            status: status.to_string(),
+            side: "buy".to_string(),
+            price: Some(dec(price)),
+            qty: Some(dec(qty)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn holding(qty: f64) -> Position {
+        Position {
+            symbol: "ETH/USD".to_string(),
+            qty: dec(qty),
+            avg_entry_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_gets_one_take_profit_after_final_fill() {
+        let config: AppConfig = serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["ETH/USD"]
+defaults: { take_profit_pct: 1.0, stop_loss_pct: 0.5, min_order_amount: 10.0, max_order_amount: 100.0 }
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft: { evaluate_every_quotes: 5, min_edge_bps: 10.0, take_profit_bps: 50.0, stop_loss_bps: 25.0, max_spread_bps: 30.0 }
+hybrid: { gate_refresh_quotes: 100, no_trade_cooldown_quotes: 50 }
+llm: { api_key: null, base_url: null, model: "test-model" }
+alpaca: { api_key: "K", secret_key: "S", base_url: "https://paper-api.alpaca.markets" }
+exit_on_quotes: true
+"#,
+        )
+        .unwrap();
+        let exchange = Arc::new(MockExchange::new("mock"));
+        let tracker = PositionTracker::new();
+        tracker.add_pending_order(test_entry("entry1", "ETH/USD", dec(1.0)));
+        let bus = EventBus::new(64);
+        PositionMonitor::new(bus.clone(), exchange.clone(), tracker.clone(), config)
+            .start()
+            .await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // A tick while the entry is still working places no exit for the part
+        exchange.set_positions(vec![holding(0.4)]);
+        bus.publish(fill_report("partial_fill", 0.4, 100.0)).ok();
+        bus.publish(Event::Market(MarketEvent::Quote {
+            symbol: "ETH/USD".to_string(),
+            bid: 101.0,
+            ask: 101.1,
+            timestamp: String::new(),
+        }))
+        .ok();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        exchange.set_positions(vec![holding(1.0)]);
+        bus.publish(fill_report("fill", 0.6, 102.0)).ok();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while exchange.submitted().is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        bus.shutdown().finish();
+
+        let submitted = exchange.submitted();
+        assert_eq!(submitted.len(), 1, "{:?}", exchange.calls());
+        assert_eq!(submitted[0].side, Side::Sell);
+        assert_eq!(submitted[0].qty, Some(dec(1.0)));
+        // TP priced off the VWAP of both fills (101.2), not the limit
+        let pos = tracker.get_position("ETH/USD").unwrap();
+        assert_eq!(pos.entry_price, dec(101.2));
+        assert_eq!(submitted[0].limit_price, Some(pos.take_profit));
+    }
+
+    #[test]
+    fn test_set_filled_qty_keeps_remaining() {
+        let mut pos = test_pos("ETH/USD", dec(100.0), dec(1.0));
+        pos.remaining_qty = dec(0.5);
+        pos.set_filled_qty(dec(0.25));
+        assert_eq!(pos.filled_qty, dec(0.25));
+        assert_eq!(pos.qty, dec(0.75));
+    }
+
     // ============= Concurrent Access Tests =============
 
+    // ============= TP Replace Tests =============
+
+    /// Venue that amends resting orders (if `amends`), answering with `new_id`
+    /// or failing.
+    fn amending(amends: bool, new_id: Option<&'static str>) -> MockExchange {
+        MockExchange::new("amending")
+            .with_capabilities(|c| {
+                c.supports_notional_market_buy = true;
+                c.supports_ws_quotes = true;
+                c.supports_ws_trades = true;
+                c.supports_order_replace = amends;
+            })
+            .on_replace(move |_, _, _| match new_id {
+                Some(id) => Ok(ack(id, "new", serde_json::Value::Null)),
+                None => Err(AutoHedgeError::http(400, "order would immediately match")),
+            })
+    }
+
+    /// ETH position with resting TP leg `tp1`, whose target was moved to 110.
+    fn tracker_with_tp_leg(tp_filled: Decimal) -> PositionTracker {
+        let tracker = PositionTracker::new();
+        let mut pos = test_pos("ETH/USD", dec(100.0), dec(2.0));
+        pos.open_order_id = Some("tp1".to_string());
+        pos.take_profit = dec(110.0);
+        tracker.add_position(pos);
+        tracker.add_pending_order(PendingOrder {
+            side: "sell".to_string(),
+            limit_price: dec(102.0),
+            qty: dec(2.0),
+            filled_qty: tp_filled,
+            remaining_qty: dec(2.0) - tp_filled,
+            stop_loss: None,
+            take_profit: None,
+            ..test_entry("tp1", "ETH/USD", dec(2.0))
+        });
+        tracker
+    }
+
+    #[tokio::test]
+    async fn test_replace_take_profit_moves_leg() {
+        let tracker = tracker_with_tp_leg(Decimal::ZERO);
+        let exchange = amending(true, Some("tp2"));
+
+        assert!(tracker.replace_take_profit("ETH/USD", &exchange).await);
+
+        let replaced = exchange.replaced();
+        assert_eq!(replaced.len(), 1);
+        assert_eq!(replaced[0].0, "tp1");
+        assert_eq!(replaced[0].1.qty, Some(dec(2.0)));
+        assert_eq!(replaced[0].1.limit_price, Some(dec(110.0)));
+
+        let orders = tracker.get_all_pending_orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, "tp2");
+        assert_eq!(orders[0].limit_price, dec(110.0));
+        assert_eq!(
+            tracker.get_position("ETH/USD").unwrap().open_order_id,
+            Some("tp2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replace_take_profit_falls_back() {
+        // Venue cannot amend
+        let tracker = tracker_with_tp_leg(Decimal::ZERO);
+        let exchange = amending(false, Some("tp2"));
+        assert!(!tracker.replace_take_profit("ETH/USD", &exchange).await);
+        assert!(exchange.replaced().is_empty());
+
+        // Partly filled leg: cancel-and-recreate settles the fill
+        let tracker = tracker_with_tp_leg(dec(0.5));
+        let exchange = amending(true, Some("tp2"));
+        assert!(!tracker.replace_take_profit("ETH/USD", &exchange).await);
+        assert!(exchange.replaced().is_empty());
+
+        // Amend rejected: the old leg stays tracked
+        let tracker = tracker_with_tp_leg(Decimal::ZERO);
+        let exchange = amending(true, None);
+        assert!(!tracker.replace_take_profit("ETH/USD", &exchange).await);
+        assert_eq!(tracker.get_all_pending_orders()[0].order_id, "tp1");
+        assert_eq!(
+            tracker.get_position("ETH/USD").unwrap().open_order_id,
+            Some("tp1".to_string())
+        );
+    }
+
     #[test]
     fn test_concurrent_position_access() {
         use std::sync::Arc;
@@ -441,19 +968,24 @@ mod position_tracker_tests {
             let handle = thread::spawn(move || {
                 let pos = PositionInfo {
                     symbol: format!("SYM{}/USD", i),
-                    entry_price: 100.0 + i as f64,
-                    qty: 1.0,
-                    stop_loss: 95.0,
-                    take_profit: 105.0,
+                    entry_price: Decimal::from(100 + i),
+                    qty: dec(1.0),
+                    filled_qty: dec(1.0),
+                    remaining_qty: Decimal::ZERO,
+                    stop_loss: dec(95.0),
+                    take_profit: dec(105.0),
                     entry_time: "2025-01-01T00:00:00Z".to_string(),
                     side: "buy".to_string(),
                     is_closing: false,
                     open_order_id: None,
                     last_recreate_attempt: None,
                     recreate_attempts: 0,
-                    highest_price: 100.0 + i as f64,
+                    highest_price: Decimal::from(100 + i),
                     trailing_stop_active: false,
-                    trailing_stop_price: 95.0,
+                    trailing_stop_price: dec(95.0),
+                    bracket_managed: false,
+                    stop_order_id: None,
+                    correlation_id: None,
                 };
                 tracker_clone.add_position(pos);
             });
@@ -483,12 +1015,16 @@ mod position_tracker_tests {
                     order_id: format!("order{}", i),
                     symbol: format!("SYM{}/USD", i),
                     side: "buy".to_string(),
-                    limit_price: 100.0,
-                    qty: 1.0,
+                    limit_price: dec(100.0),
+                    qty: dec(1.0),
+                    filled_qty: Decimal::ZERO,
+                    remaining_qty: dec(1.0),
                     created_at: "2025-01-01T00:00:00Z".to_string(),
                     stop_loss: None,
                     take_profit: None,
                     last_check_time: None,
+                    bracket: false,
+                    correlation_id: None,
                 };
                 tracker_clone.add_pending_order(order);
             });
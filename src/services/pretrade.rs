@@ -0,0 +1,396 @@
+//! Pre-trade risk simulation.
+//!
+//! Before an entry is submitted, the execution engines add it to a snapshot of
+//! the current book (tracked positions and pending entries marked at the latest
+//! mid) and compute the post-trade metrics: gross and per-symbol exposure,
+//! historical VaR over quote returns, margin usage and the exposure of the
+//! order's correlation set. An order that breaches a limit is shrunk to the
+//! largest size that fits, or rejected when that is below the minimum order
+//! amount; either way an `Event::RiskLimit` names the violated limit. Exits are
+//! never simulated since they only reduce risk.
+
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, PreTradeRiskConfig};
+use crate::data::correlation::correlation;
+use crate::data::store::{MarketStore, Quote};
+use crate::events::{Event, RiskLimitEvent};
+use crate::exchange::types::AccountSummary;
+use crate::money::{dec, float};
+use crate::services::execution_utils::margin_buying_power;
+use crate::services::position_monitor::PositionTracker;
+
+/// Bisection steps used when searching for the largest size that fits.
+const RESIZE_STEPS: usize = 30;
+
+/// An entry about to be submitted.
+#[derive(Clone, Debug)]
+pub struct ProposedOrder {
+    pub symbol: String,
+    pub qty: f64,
+    pub price: f64,
+}
+
+/// The book an order is simulated against.
+#[derive(Clone, Debug, Default)]
+pub struct BookSnapshot {
+    pub equity: f64,
+    pub buying_power: f64,
+    /// Notional exposure per symbol
+    pub exposures: HashMap<String, f64>,
+    /// Quote-to-quote mid returns per symbol (oldest first)
+    pub returns: HashMap<String, Vec<f64>>,
+}
+
+impl BookSnapshot {
+    /// Snapshot tracked positions and pending entries, marked at the latest mid.
+    /// Equity falls back to cash (or buying power) plus exposure when the
+    /// account does not report a portfolio value.
+    pub fn capture(
+        tracker: &PositionTracker,
+        store: &MarketStore,
+        account: Option<&AccountSummary>,
+        order_symbol: &str,
+    ) -> Self {
+        let mark = |symbol: &str, fallback: f64| {
+            store
+                .get_latest_quote(symbol)
+                .filter(|q| q.bid_price > 0.0 && q.ask_price > 0.0)
+                .map(|q| (q.bid_price + q.ask_price) / 2.0)
+                .unwrap_or(fallback)
+        };
+
+        let mut exposures: HashMap<String, f64> = HashMap::new();
+        for pos in tracker.get_all_positions() {
+            let price = mark(&pos.symbol, float(pos.entry_price));
+            *exposures.entry(pos.symbol.clone()).or_default() += float(pos.filled_qty) * price;
+        }
+        for order in tracker.get_all_pending_orders() {
+            if order.side == "buy" {
+                *exposures.entry(order.symbol.clone()).or_default() +=
+                    float(order.remaining_qty * order.limit_price);
+            }
+        }
+
+        let mut returns = HashMap::new();
+        for symbol in exposures
+            .keys()
+            .map(|s| s.as_str())
+            .chain(std::iter::once(order_symbol))
+        {
+            returns.insert(
+                symbol.to_string(),
+                mid_returns(&store.get_quote_history(symbol)),
+            );
+        }
+
+        let gross: f64 = exposures.values().sum();
+        let buying_power = account
+            .and_then(|a| a.buying_power.or(a.cash))
+            .map(float)
+            .unwrap_or(0.0);
+        let equity = account
+            .and_then(|a| {
+                a.portfolio_value
+                    .map(float)
+                    .or(a.cash.map(|c| float(c) + gross))
+            })
+            .unwrap_or(buying_power + gross);
+
+        Self {
+            equity,
+            buying_power,
+            exposures,
+            returns,
+        }
+    }
+}
+
+/// Simple returns between consecutive valid mids.
+pub fn mid_returns(quotes: &[Quote]) -> Vec<f64> {
+    let mids: Vec<f64> = quotes
+        .iter()
+        .filter(|q| q.bid_price > 0.0 && q.ask_price >= q.bid_price)
+        .map(|q| (q.bid_price + q.ask_price) / 2.0)
+        .collect();
+    mids.windows(2).map(|w| (w[1] - w[0]) / w[0]).collect()
+}
+
+/// Historical VaR of `exposures` at `confidence`, in currency, over the most
+/// recent returns shared by every symbol with at least `min_samples` returns.
+/// None if no exposed symbol has enough history.
+pub fn historical_var(
+    exposures: &HashMap<String, f64>,
+    returns: &HashMap<String, Vec<f64>>,
+    confidence: f64,
+    min_samples: usize,
+) -> Option<f64> {
+    let series: Vec<(f64, &Vec<f64>)> = exposures
+        .iter()
+        .filter_map(|(symbol, exposure)| {
+            let r = returns.get(symbol)?;
+            (r.len() >= min_samples.max(1)).then_some((*exposure, r))
+        })
+        .collect();
+    let n = series.iter().map(|(_, r)| r.len()).min()?;
+
+    let mut pnl: Vec<f64> = (0..n)
+        .map(|k| {
+            series
+                .iter()
+                .map(|(exposure, r)| exposure * r[r.len() - n + k])
+                .sum()
+        })
+        .collect();
+    pnl.sort_by(|a, b| a.total_cmp(b));
+    let idx = (((1.0 - confidence.clamp(0.0, 1.0)) * n as f64).floor() as usize).min(n - 1);
+    Some((-pnl[idx]).max(0.0))
+}
+
+/// Portfolio metrics after the order fills.
+#[derive(Clone, Debug, Default)]
+pub struct PostTradeMetrics {
+    pub gross_exposure_pct: f64,
+    pub symbol_exposure_pct: f64,
+    /// None until enough return history exists
+    pub var_pct: Option<f64>,
+    pub margin_usage_pct: f64,
+    pub correlated_exposure_pct: f64,
+    /// Held symbols in the order's correlation set
+    pub correlated: Vec<String>,
+}
+
+/// Add `order` to `book` and compute the post-trade metrics.
+pub fn simulate(
+    book: &BookSnapshot,
+    order: &ProposedOrder,
+    config: &PreTradeRiskConfig,
+) -> PostTradeMetrics {
+    let notional = order.qty * order.price;
+    let pct = |value: f64| value / book.equity * 100.0;
+
+    let current_gross: f64 = book.exposures.values().sum();
+    let mut exposures = book.exposures.clone();
+    *exposures.entry(order.symbol.clone()).or_default() += notional;
+    let gross = current_gross + notional;
+
+    let capacity = current_gross + book.buying_power;
+    let margin_usage_pct = if capacity > 0.0 {
+        gross / capacity * 100.0
+    } else {
+        100.0
+    };
+
+    let empty = Vec::new();
+    let own_returns = book.returns.get(&order.symbol).unwrap_or(&empty);
+    let mut correlated: Vec<String> = book
+        .exposures
+        .keys()
+        .filter(|s| **s != order.symbol)
+        .filter(|s| {
+            book.returns
+                .get(*s)
+                .and_then(|r| correlation(own_returns, r, config.min_return_samples))
+                .is_some_and(|c| c >= config.correlation_threshold)
+        })
+        .cloned()
+        .collect();
+    correlated.sort();
+    let correlated_exposure = exposures[&order.symbol]
+        + correlated
+            .iter()
+            .map(|s| exposures.get(s).copied().unwrap_or(0.0))
+            .sum::<f64>();
+
+    let var = historical_var(
+        &exposures,
+        &book.returns,
+        config.var_confidence,
+        config.min_return_samples,
+    );
+
+    PostTradeMetrics {
+        gross_exposure_pct: pct(gross),
+        symbol_exposure_pct: pct(exposures[&order.symbol]),
+        var_pct: var.map(pct),
+        margin_usage_pct,
+        correlated_exposure_pct: pct(correlated_exposure),
+        correlated,
+    }
+}
+
+/// A post-trade limit the order would violate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitBreach {
+    /// Config key of the limit ("max_symbol_exposure_pct", ...)
+    pub limit: &'static str,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+/// First limit in `metrics` that exceeds its configured threshold (0 = off).
+pub fn first_breach(
+    metrics: &PostTradeMetrics,
+    config: &PreTradeRiskConfig,
+) -> Option<LimitBreach> {
+    let checks = [
+        (
+            "max_symbol_exposure_pct",
+            Some(metrics.symbol_exposure_pct),
+            config.max_symbol_exposure_pct,
+        ),
+        (
+            "max_correlated_exposure_pct",
+            Some(metrics.correlated_exposure_pct),
+            config.max_correlated_exposure_pct,
+        ),
+        (
+            "max_gross_exposure_pct",
+            Some(metrics.gross_exposure_pct),
+            config.max_gross_exposure_pct,
+        ),
+        (
+            "max_margin_usage_pct",
+            Some(metrics.margin_usage_pct),
+            config.max_margin_usage_pct,
+        ),
+        ("max_var_pct", metrics.var_pct, config.max_var_pct),
+    ];
+    checks.into_iter().find_map(|(limit, value, threshold)| {
+        let value = value?;
+        (threshold > 0.0 && value > threshold).then_some(LimitBreach {
+            limit,
+            value,
+            threshold,
+        })
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreTradeDecision {
+    Accept,
+    /// Largest quantity that fits every limit (the breach is at the requested size)
+    Resize {
+        qty: f64,
+        breach: LimitBreach,
+    },
+    Reject(LimitBreach),
+}
+
+/// Simulate `order`; if it breaches a limit, find the largest quantity that does
+/// not. Orders whose fitting notional is below `min_notional` are rejected.
+/// Without a known equity nothing can be judged and the order is accepted.
+pub fn evaluate(
+    book: &BookSnapshot,
+    order: &ProposedOrder,
+    min_notional: f64,
+    config: &PreTradeRiskConfig,
+) -> PreTradeDecision {
+    if book.equity <= 0.0 || order.qty <= 0.0 || order.price <= 0.0 {
+        return PreTradeDecision::Accept;
+    }
+    let Some(breach) = first_breach(&simulate(book, order, config), config) else {
+        return PreTradeDecision::Accept;
+    };
+
+    let fits = |qty: f64| {
+        let resized = ProposedOrder {
+            qty,
+            ..order.clone()
+        };
+        first_breach(&simulate(book, &resized, config), config).is_none()
+    };
+    let (mut lo, mut hi) = (0.0, order.qty);
+    for _ in 0..RESIZE_STEPS {
+        let mid = (lo + hi) / 2.0;
+        if fits(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    if lo > 0.0 && lo * order.price >= min_notional && fits(lo) {
+        PreTradeDecision::Resize { qty: lo, breach }
+    } else {
+        PreTradeDecision::Reject(breach)
+    }
+}
+
+/// Run the pre-trade simulation for an entry. Returns the quantity to submit
+/// (possibly reduced), or None if the order must not be sent. Resizes and
+/// rejections are published as `Event::RiskLimit`.
+pub fn pre_trade_check(
+    bus: &EventBus,
+    config: &AppConfig,
+    tracker: &PositionTracker,
+    store: &MarketStore,
+    account: Option<&AccountSummary>,
+    order: &ProposedOrder,
+) -> Option<f64> {
+    let risk = &config.pretrade_risk;
+    if !risk.enabled {
+        return Some(order.qty);
+    }
+
+    let mut book = BookSnapshot::capture(tracker, store, account, &order.symbol);
+    // Futures margin backs its value times the symbol's leverage in notional
+    book.buying_power = float(margin_buying_power(
+        config,
+        &order.symbol,
+        dec(book.buying_power),
+    ));
+    if bus.diagnostics().trace(&order.symbol) {
+        let m = simulate(&book, order, risk);
+        info!(
+            "🔬 [DIAG] Pre-trade {}: qty={:.8} @ {:.8} | equity=${:.2} gross={:.2}% symbol={:.2}% correlated={:.2}% {:?} margin={:.2}% var={:?}%",
+            order.symbol,
+            order.qty,
+            order.price,
+            book.equity,
+            m.gross_exposure_pct,
+            m.symbol_exposure_pct,
+            m.correlated_exposure_pct,
+            m.correlated,
+            m.margin_usage_pct,
+            m.var_pct
+        );
+    }
+
+    let (approved_qty, breach, action) =
+        match evaluate(&book, order, config.defaults.min_order_amount, risk) {
+            PreTradeDecision::Accept => return Some(order.qty),
+            PreTradeDecision::Resize { qty, breach } => {
+                info!(
+                    "🧮 [PRETRADE] Resized BUY {} {:.8} -> {:.8} ({} {:.2} > {:.2})",
+                    order.symbol, order.qty, qty, breach.limit, breach.value, breach.threshold
+                );
+                (qty, breach, "resized")
+            }
+            PreTradeDecision::Reject(breach) => {
+                warn!(
+                    "🧮 [PRETRADE] Rejected BUY {} qty={:.8} ({} {:.2} > {:.2})",
+                    order.symbol, order.qty, breach.limit, breach.value, breach.threshold
+                );
+                (0.0, breach, "rejected")
+            }
+        };
+
+    bus.publish(Event::RiskLimit(RiskLimitEvent {
+        symbol: order.symbol.clone(),
+        side: "buy".to_string(),
+        action: action.to_string(),
+        requested_qty: dec(order.qty),
+        approved_qty: dec(approved_qty),
+        price: dec(order.price),
+        limit: breach.limit.to_string(),
+        value: dec(breach.value),
+        threshold: dec(breach.threshold),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }))
+    .ok();
+
+    (approved_qty > 0.0).then_some(approved_qty)
+}
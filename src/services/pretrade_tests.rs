@@ -0,0 +1,351 @@
+//! Unit tests for pre-trade risk simulation (exposure, VaR, margin, correlation).
+
+#[cfg(test)]
+mod pretrade_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, PreTradeRiskConfig};
+    use crate::data::store::{MarketStore, Quote};
+    use crate::events::Event;
+    use crate::exchange::types::AccountSummary;
+    use crate::money::dec;
+    use crate::services::position_monitor::{PendingOrder, PositionTracker};
+    use crate::services::pretrade::*;
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    /// Only the limit under test is active.
+    fn only(limit: &str, threshold: f64) -> PreTradeRiskConfig {
+        let mut config = PreTradeRiskConfig {
+            max_gross_exposure_pct: 0.0,
+            max_symbol_exposure_pct: 0.0,
+            max_var_pct: 0.0,
+            max_margin_usage_pct: 0.0,
+            max_correlated_exposure_pct: 0.0,
+            min_return_samples: 5,
+            ..Default::default()
+        };
+        match limit {
+            "max_gross_exposure_pct" => config.max_gross_exposure_pct = threshold,
+            "max_symbol_exposure_pct" => config.max_symbol_exposure_pct = threshold,
+            "max_var_pct" => config.max_var_pct = threshold,
+            "max_margin_usage_pct" => config.max_margin_usage_pct = threshold,
+            "max_correlated_exposure_pct" => config.max_correlated_exposure_pct = threshold,
+            other => panic!("unknown limit {}", other),
+        }
+        config
+    }
+
+    fn book(equity: f64, buying_power: f64, exposures: &[(&str, f64)]) -> BookSnapshot {
+        BookSnapshot {
+            equity,
+            buying_power,
+            exposures: exposures.iter().map(|(s, v)| (s.to_string(), *v)).collect(),
+            returns: HashMap::new(),
+        }
+    }
+
+    fn order(symbol: &str, qty: f64, price: f64) -> ProposedOrder {
+        ProposedOrder {
+            symbol: symbol.to_string(),
+            qty,
+            price,
+        }
+    }
+
+    fn quote(symbol: &str, mid: f64) -> Quote {
+        Quote {
+            symbol: symbol.to_string(),
+            bid_price: mid - 0.5,
+            ask_price: mid + 0.5,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    // ============= Statistics Tests =============
+
+    #[test]
+    fn test_mid_returns() {
+        let quotes = vec![quote("X", 100.0), quote("X", 110.0), quote("X", 99.0)];
+        let r = mid_returns(&quotes);
+        assert_eq!(r.len(), 2);
+        assert!((r[0] - 0.1).abs() < 1e-12);
+        assert!((r[1] + 0.1).abs() < 1e-12);
+        assert!(mid_returns(&quotes[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_historical_var() {
+        let exposures = HashMap::from([("BTC/USD".to_string(), 1000.0)]);
+        let returns = HashMap::from([(
+            "BTC/USD".to_string(),
+            vec![-0.05, -0.01, 0.0, 0.01, 0.02, 0.01, 0.0, 0.01, -0.02, 0.03],
+        )]);
+        // 10 samples at 90%: the worst outcome is the 1st sorted P&L (-$50)
+        let var = historical_var(&exposures, &returns, 0.9, 5).unwrap();
+        assert!((var - 50.0).abs() < 1e-9);
+        // At 80% it is the 2nd worst (-$20)
+        let var = historical_var(&exposures, &returns, 0.8, 5).unwrap();
+        assert!((var - 20.0).abs() < 1e-9);
+        assert!(historical_var(&exposures, &returns, 0.9, 20).is_none());
+    }
+
+    // ============= Limit Tests =============
+
+    #[test]
+    fn test_accepts_order_within_limits() {
+        let config = PreTradeRiskConfig::default();
+        let b = book(1000.0, 900.0, &[("ETH/USD", 100.0)]);
+        let m = simulate(&b, &order("BTC/USD", 1.0, 100.0), &config);
+        assert!((m.gross_exposure_pct - 20.0).abs() < 1e-9);
+        assert!((m.symbol_exposure_pct - 10.0).abs() < 1e-9);
+        assert!((m.margin_usage_pct - 20.0).abs() < 1e-9);
+        assert!(m.var_pct.is_none());
+        assert_eq!(
+            evaluate(&b, &order("BTC/USD", 1.0, 100.0), 10.0, &config),
+            PreTradeDecision::Accept
+        );
+    }
+
+    #[test]
+    fn test_resizes_to_symbol_exposure_limit() {
+        let config = only("max_symbol_exposure_pct", 25.0);
+        // Already $200 of BTC; a $100 buy would take it to 30%
+        let b = book(1000.0, 800.0, &[("BTC/USD", 200.0)]);
+        match evaluate(&b, &order("BTC/USD", 1.0, 100.0), 10.0, &config) {
+            PreTradeDecision::Resize { qty, breach } => {
+                assert!((qty - 0.5).abs() < 1e-6);
+                assert_eq!(breach.limit, "max_symbol_exposure_pct");
+                assert!((breach.value - 30.0).abs() < 1e-9);
+                assert_eq!(breach.threshold, 25.0);
+            }
+            other => panic!("expected resize, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_when_fitting_size_is_below_minimum() {
+        let config = only("max_gross_exposure_pct", 50.0);
+        // $495 held, $5 of room left, minimum order is $10
+        let b = book(1000.0, 505.0, &[("ETH/USD", 495.0)]);
+        match evaluate(&b, &order("BTC/USD", 1.0, 100.0), 10.0, &config) {
+            PreTradeDecision::Reject(breach) => {
+                assert_eq!(breach.limit, "max_gross_exposure_pct");
+                assert!((breach.value - 59.5).abs() < 1e-9);
+            }
+            other => panic!("expected reject, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_margin_usage_limit() {
+        let config = only("max_margin_usage_pct", 90.0);
+        // $800 deployed + $200 buying power: a $150 buy uses 95% of capacity
+        let b = book(1000.0, 200.0, &[("ETH/USD", 800.0)]);
+        match evaluate(&b, &order("BTC/USD", 1.5, 100.0), 10.0, &config) {
+            PreTradeDecision::Resize { qty, breach } => {
+                assert_eq!(breach.limit, "max_margin_usage_pct");
+                assert!((qty - 1.0).abs() < 1e-6);
+            }
+            other => panic!("expected resize, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_correlated_exposure_counts_correlation_set() {
+        let config = PreTradeRiskConfig {
+            correlation_threshold: 0.8,
+            ..only("max_correlated_exposure_pct", 40.0)
+        };
+        let moves = vec![0.01, -0.02, 0.03, -0.01, 0.02, 0.0, 0.01];
+        let mut b = book(1000.0, 500.0, &[("ETH/USD", 300.0), ("DOGE/USD", 200.0)]);
+        b.returns.insert("BTC/USD".to_string(), moves.clone());
+        b.returns.insert(
+            "ETH/USD".to_string(),
+            moves.iter().map(|x| x * 1.5).collect(),
+        );
+        b.returns
+            .insert("DOGE/USD".to_string(), moves.iter().map(|x| -x).collect());
+
+        let m = simulate(&b, &order("BTC/USD", 1.0, 100.0), &config);
+        assert_eq!(m.correlated, vec!["ETH/USD".to_string()]);
+        assert!((m.correlated_exposure_pct - 40.0).abs() < 1e-9);
+        assert_eq!(
+            evaluate(&b, &order("BTC/USD", 1.0, 100.0), 10.0, &config),
+            PreTradeDecision::Accept
+        );
+
+        let decision = evaluate(&b, &order("BTC/USD", 2.0, 100.0), 10.0, &config);
+        assert!(matches!(
+            decision,
+            PreTradeDecision::Resize { ref breach, .. } if breach.limit == "max_correlated_exposure_pct"
+        ));
+    }
+
+    #[test]
+    fn test_var_limit() {
+        let config = PreTradeRiskConfig {
+            var_confidence: 0.9,
+            ..only("max_var_pct", 1.0)
+        };
+        let mut b = book(1000.0, 1000.0, &[]);
+        b.returns.insert(
+            "BTC/USD".to_string(),
+            vec![-0.05, -0.01, 0.0, 0.01, 0.02, 0.01, 0.0, 0.01, -0.02, 0.03],
+        );
+        // $400 at a 5% worst case is $20 = 2% of equity; $200 fits exactly
+        let m = simulate(&b, &order("BTC/USD", 4.0, 100.0), &config);
+        assert!((m.var_pct.unwrap() - 2.0).abs() < 1e-9);
+        match evaluate(&b, &order("BTC/USD", 4.0, 100.0), 10.0, &config) {
+            PreTradeDecision::Resize { qty, breach } => {
+                assert_eq!(breach.limit, "max_var_pct");
+                assert!((qty - 2.0).abs() < 1e-6);
+            }
+            other => panic!("expected resize, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_equity_is_accepted() {
+        let config = only("max_symbol_exposure_pct", 1.0);
+        assert_eq!(
+            evaluate(
+                &book(0.0, 0.0, &[]),
+                &order("BTC/USD", 1.0, 100.0),
+                10.0,
+                &config
+            ),
+            PreTradeDecision::Accept
+        );
+    }
+
+    // ============= Engine Integration Tests =============
+
+    #[test]
+    fn test_capture_marks_positions_and_pending_entries() {
+        let tracker = PositionTracker::new();
+        tracker.add_pending_order(PendingOrder {
+            order_id: "1".to_string(),
+            symbol: "ETH/USD".to_string(),
+            side: "buy".to_string(),
+            limit_price: dec(50.0),
+            qty: dec(2.0),
+            filled_qty: Decimal::ZERO,
+            remaining_qty: dec(2.0),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            stop_loss: None,
+            take_profit: None,
+            last_check_time: None,
+            bracket: false,
+            correlation_id: None,
+        });
+        let store = MarketStore::new(50);
+        for mid in [100.0, 101.0, 102.0] {
+            store.update_quote("BTC/USD".to_string(), quote("BTC/USD", mid));
+        }
+        let account = AccountSummary {
+            buying_power: Some(dec(400.0)),
+            cash: Some(dec(400.0)),
+            portfolio_value: None,
+        };
+
+        let b = BookSnapshot::capture(&tracker, &store, Some(&account), "BTC/USD");
+        assert_eq!(b.exposures.get("ETH/USD"), Some(&100.0));
+        assert_eq!(b.equity, 500.0);
+        assert_eq!(b.buying_power, 400.0);
+        assert_eq!(b.returns["BTC/USD"].len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pre_trade_check_publishes_violated_limit() {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let mut config = test_config();
+        config.pretrade_risk = only("max_symbol_exposure_pct", 5.0);
+        let account = AccountSummary {
+            buying_power: Some(dec(1000.0)),
+            cash: Some(dec(1000.0)),
+            portfolio_value: Some(dec(1000.0)),
+        };
+
+        let qty = pre_trade_check(
+            &bus,
+            &config,
+            &PositionTracker::new(),
+            &MarketStore::new(50),
+            Some(&account),
+            &order("BTC/USD", 1.0, 100.0),
+        );
+        assert!((qty.unwrap() - 0.5).abs() < 1e-6);
+
+        match rx.recv().await.unwrap() {
+            Event::RiskLimit(event) => {
+                assert_eq!(event.action, "resized");
+                assert_eq!(event.limit, "max_symbol_exposure_pct");
+                assert_eq!(event.requested_qty, dec(1.0));
+                assert!((event.value - dec(10.0)).abs() < dec(1e-9));
+                assert_eq!(event.threshold, dec(5.0));
+            }
+            other => panic!("expected risk limit event, got {:?}", other),
+        }
+
+        // Below the $10 minimum: rejected outright
+        config.pretrade_risk = only("max_symbol_exposure_pct", 0.5);
+        assert!(pre_trade_check(
+            &bus,
+            &config,
+            &PositionTracker::new(),
+            &MarketStore::new(50),
+            Some(&account),
+            &order("BTC/USD", 1.0, 100.0),
+        )
+        .is_none());
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            Event::RiskLimit(e) if e.action == "rejected" && e.approved_qty.is_zero()
+        ));
+    }
+}
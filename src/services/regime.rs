@@ -0,0 +1,234 @@
+//! Market regime detection.
+//!
+//! `RegimeService` follows the quote and trade stream and `RegimeDetector`
+//! classifies each symbol over its last `regime.window` quotes:
+//! - illiquid: mean spread above `max_spread_bps`, or fewer than
+//!   `min_trades_per_minute` trades in the last minute
+//! - trending: efficiency ratio (net mid move / summed absolute moves) of at
+//!   least `trend_efficiency`, with `min_volatility_bps` of volatility
+//! - choppy: anything else
+//!
+//! Changes are stored in `MarketStore` and published as `MarketEvent::Regime`.
+//! The strategy engine skips a symbol for every strategy mode whose
+//! `regime.strategies` list excludes its current regime.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::info;
+
+use crate::bus::EventBus;
+use crate::config::{AppConfig, RegimeConfig};
+use crate::data::store::MarketStore;
+use crate::events::{Event, MarketEvent, MarketRegime};
+
+/// A classification and the window statistics behind it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegimeReading {
+    pub symbol: String,
+    pub regime: MarketRegime,
+    pub volatility_bps: f64,
+    pub spread_bps: f64,
+    pub trades_per_minute: f64,
+    pub efficiency: f64,
+}
+
+#[derive(Default)]
+struct SymbolWindow {
+    mids: VecDeque<f64>,
+    spreads_bps: VecDeque<f64>,
+    /// Trade arrival times over the last minute
+    trades: VecDeque<DateTime<Utc>>,
+    regime: Option<MarketRegime>,
+}
+
+impl SymbolWindow {
+    fn prune_trades(&mut self, now: DateTime<Utc>) {
+        while self
+            .trades
+            .front()
+            .is_some_and(|t| now - *t > Duration::minutes(1))
+        {
+            self.trades.pop_front();
+        }
+    }
+}
+
+/// Rolling per-symbol regime classification, owned by the service loop.
+pub struct RegimeDetector {
+    config: RegimeConfig,
+    symbols: HashMap<String, SymbolWindow>,
+}
+
+impl RegimeDetector {
+    pub fn new(config: &RegimeConfig) -> Self {
+        Self {
+            config: config.clone(),
+            symbols: HashMap::new(),
+        }
+    }
+
+    /// New thresholds after a config reload; windows are kept.
+    pub fn set_config(&mut self, config: &RegimeConfig) {
+        self.config = config.clone();
+    }
+
+    pub fn regime(&self, symbol: &str) -> Option<MarketRegime> {
+        self.symbols.get(symbol).and_then(|w| w.regime)
+    }
+
+    pub fn on_trade(&mut self, symbol: &str, now: DateTime<Utc>) {
+        let window = self.symbols.entry(symbol.to_string()).or_default();
+        window.trades.push_back(now);
+        window.prune_trades(now);
+    }
+
+    /// Add a quote and classify the window once it is full; the reading when
+    /// the symbol's regime changed.
+    pub fn on_quote(
+        &mut self,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+        now: DateTime<Utc>,
+    ) -> Option<RegimeReading> {
+        if bid <= 0.0 || ask < bid {
+            return None;
+        }
+        let size = self.config.window.max(2);
+        let window = self.symbols.entry(symbol.to_string()).or_default();
+        let mid = (bid + ask) / 2.0;
+        window.mids.push_back(mid);
+        window.spreads_bps.push_back((ask - bid) / mid * 10_000.0);
+        while window.mids.len() > size {
+            window.mids.pop_front();
+            window.spreads_bps.pop_front();
+        }
+        window.prune_trades(now);
+        if window.mids.len() < size {
+            return None;
+        }
+
+        let returns: Vec<f64> = window
+            .mids
+            .iter()
+            .zip(window.mids.iter().skip(1))
+            .map(|(a, b)| (b - a) / a * 10_000.0)
+            .collect();
+        // Realized volatility: root mean square of the returns
+        let volatility_bps =
+            (returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64).sqrt();
+        let path: f64 = returns.iter().map(|r| r.abs()).sum();
+        let net = returns.iter().sum::<f64>().abs();
+        let efficiency = if path > 0.0 { net / path } else { 0.0 };
+        let spread_bps = window.spreads_bps.iter().sum::<f64>() / window.spreads_bps.len() as f64;
+        let trades_per_minute = window.trades.len() as f64;
+
+        let config = &self.config;
+        let regime = if spread_bps > config.max_spread_bps
+            || (config.min_trades_per_minute > 0.0
+                && trades_per_minute < config.min_trades_per_minute)
+        {
+            MarketRegime::Illiquid
+        } else if efficiency >= config.trend_efficiency
+            && volatility_bps >= config.min_volatility_bps
+        {
+            MarketRegime::Trending
+        } else {
+            MarketRegime::Choppy
+        };
+        if window.regime == Some(regime) {
+            return None;
+        }
+        window.regime = Some(regime);
+        Some(RegimeReading {
+            symbol: symbol.to_string(),
+            regime,
+            volatility_bps,
+            spread_bps,
+            trades_per_minute,
+            efficiency,
+        })
+    }
+}
+
+/// Publishes regime changes for the streamed symbols.
+pub struct RegimeService {
+    event_bus: EventBus,
+    store: MarketStore,
+    config: AppConfig,
+}
+
+impl RegimeService {
+    pub fn new(event_bus: EventBus, store: MarketStore, config: AppConfig) -> Self {
+        Self {
+            event_bus,
+            store,
+            config,
+        }
+    }
+
+    pub async fn start(self) {
+        let mut rx = self.event_bus.subscribe("regime");
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "🌡️ Regime Service started (window {} quotes)",
+                self.config.regime.window
+            );
+            let mut detector = RegimeDetector::new(&self.config.regime);
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                let reading = match event {
+                    Event::Market(MarketEvent::Quote {
+                        symbol, bid, ask, ..
+                    }) => detector.on_quote(&symbol, bid, ask, Utc::now()),
+                    Event::Market(MarketEvent::Trade { symbol, .. }) => {
+                        detector.on_trade(&symbol, Utc::now());
+                        None
+                    }
+                    Event::Config(update) => {
+                        detector.set_config(&update.config.regime);
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(reading) = reading {
+                    self.publish(reading);
+                }
+            }
+        });
+    }
+
+    fn publish(&self, reading: RegimeReading) {
+        info!(
+            "🌡️ [REGIME] {} is {} (volatility {:.2} bps, spread {:.2} bps, {:.0} trades/min, efficiency {:.2})",
+            reading.symbol,
+            reading.regime.as_str(),
+            reading.volatility_bps,
+            reading.spread_bps,
+            reading.trades_per_minute,
+            reading.efficiency
+        );
+        self.store.set_regime(&reading.symbol, reading.regime);
+        self.event_bus
+            .publish(Event::Market(MarketEvent::Regime {
+                symbol: reading.symbol,
+                regime: reading.regime,
+                volatility_bps: reading.volatility_bps,
+                spread_bps: reading.spread_bps,
+                trades_per_minute: reading.trades_per_minute,
+                efficiency: reading.efficiency,
+                timestamp: Utc::now().to_rfc3339(),
+            }))
+            .ok();
+    }
+}
@@ -0,0 +1,121 @@
+//! Unit tests for market regime classification and the per-strategy regime gate.
+
+#[cfg(test)]
+mod regime_tests {
+    use crate::config::RegimeConfig;
+    use crate::events::MarketRegime;
+    use crate::services::regime::*;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn config() -> RegimeConfig {
+        RegimeConfig {
+            enabled: true,
+            window: 20,
+            ..RegimeConfig::default()
+        }
+    }
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+    }
+
+    /// Feed `mids` as 2 bps wide quotes; every regime change on the way.
+    fn feed(detector: &mut RegimeDetector, mids: &[f64]) -> Vec<RegimeReading> {
+        mids.iter()
+            .filter_map(|mid| detector.on_quote("BTC/USD", mid * 0.9999, mid * 1.0001, t0()))
+            .collect()
+    }
+
+    fn uptrend(n: usize) -> Vec<f64> {
+        (0..n).map(|i| 100.0 * (1.0005f64).powi(i as i32)).collect()
+    }
+
+    fn chop(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 100.05 })
+            .collect()
+    }
+
+    // ============= Detector Tests =============
+
+    #[test]
+    fn test_unclassified_until_window_is_full() {
+        let mut detector = RegimeDetector::new(&config());
+        assert!(feed(&mut detector, &uptrend(19)).is_empty());
+        assert_eq!(detector.regime("BTC/USD"), None);
+    }
+
+    #[test]
+    fn test_steady_move_is_trending() {
+        let mut detector = RegimeDetector::new(&config());
+        let readings = feed(&mut detector, &uptrend(40));
+        // Published once, on the change
+        assert_eq!(readings.len(), 1);
+        let reading = &readings[0];
+        assert_eq!(reading.regime, MarketRegime::Trending);
+        assert!((reading.efficiency - 1.0).abs() < 1e-9);
+        assert!((reading.spread_bps - 2.0).abs() < 1e-6);
+        assert_eq!(detector.regime("BTC/USD"), Some(MarketRegime::Trending));
+    }
+
+    #[test]
+    fn test_back_and_forth_is_choppy() {
+        let mut detector = RegimeDetector::new(&config());
+        feed(&mut detector, &uptrend(20));
+        let readings = feed(&mut detector, &chop(20));
+        assert_eq!(readings.last().unwrap().regime, MarketRegime::Choppy);
+        assert!(readings.last().unwrap().efficiency < 0.1);
+    }
+
+    #[test]
+    fn test_wide_spread_is_illiquid() {
+        let mut detector = RegimeDetector::new(&config());
+        let reading = (0..20)
+            .filter_map(|i| {
+                let mid = 100.0 + i as f64;
+                detector.on_quote("BTC/USD", mid * 0.998, mid * 1.002, t0())
+            })
+            .last()
+            .unwrap();
+        assert_eq!(reading.regime, MarketRegime::Illiquid);
+        assert!(reading.spread_bps > 20.0);
+    }
+
+    #[test]
+    fn test_few_trades_is_illiquid() {
+        let mut detector = RegimeDetector::new(&RegimeConfig {
+            min_trades_per_minute: 3.0,
+            ..config()
+        });
+        // Two recent trades, one older than a minute
+        detector.on_trade("BTC/USD", t0() - Duration::seconds(90));
+        detector.on_trade("BTC/USD", t0() - Duration::seconds(30));
+        detector.on_trade("BTC/USD", t0());
+        let readings = feed(&mut detector, &uptrend(20));
+        assert_eq!(readings[0].regime, MarketRegime::Illiquid);
+        assert_eq!(readings[0].trades_per_minute, 2.0);
+
+        detector.on_trade("BTC/USD", t0());
+        let readings = feed(&mut detector, &uptrend(21)[20..]);
+        assert_eq!(readings[0].regime, MarketRegime::Trending);
+    }
+
+    // ============= Gate Tests =============
+
+    #[test]
+    fn test_strategy_regime_gate() {
+        let config = config();
+        assert!(config.allows("hft", Some("trending")));
+        assert!(!config.allows("hft", Some("choppy")));
+        assert!(!config.allows("hft", Some("illiquid")));
+        // Unclassified symbols and unlisted modes are not gated
+        assert!(config.allows("hft", None));
+        assert!(config.allows("llm", Some("choppy")));
+
+        let disabled = RegimeConfig {
+            enabled: false,
+            ..RegimeConfig::default()
+        };
+        assert!(disabled.allows("hft", Some("choppy")));
+    }
+}
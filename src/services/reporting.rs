@@ -1,16 +1,30 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, VecDeque},
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
 
 use crate::{
     bus::EventBus,
-    events::{Event, ExecutionReport, OrderRequest},
+    config::{AppConfig, DrawdownSizingConfig, MarkToMarketConfig},
+    data::store::MarketStore,
+    error::Result,
+    events::{
+        format_metadata, AccountEvent, AnalysisSignal, Event, ExecutionReport, MarketEvent,
+        OrderRequest, TradeClosedEvent, TradeOrigin,
+    },
+    money::{dec, float},
+    services::drawdown_sizing::{drawdown_pct, next_factor, target_factor},
+    services::shadow::{shadow_config, ShadowBook},
+    services::storage::{StorageRecord, StorageWriter, StoredFill, StoredTrade},
+    services::trade_log::{JsonlSink, TradeLogFormat, TradeLogSink},
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -27,39 +41,349 @@ pub struct TradeLogEntry {
     /// "new" | "filled" | "rejected" | ...
     pub status: String,
 
-    pub qty: Option<f64>,
-    pub price: Option<f64>,
+    pub qty: Option<Decimal>,
+    pub price: Option<Decimal>,
 
     /// Estimated notional = qty * price when both are present
-    pub notional: Option<f64>,
+    pub notional: Option<Decimal>,
 
     /// Extra context (best-effort)
     pub notes: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ClosedTrade {
     pub symbol: String,
     pub buy_time: String,
     pub sell_time: String,
-    pub buy_price: f64,
-    pub sell_price: f64,
-    pub qty: f64,
-    pub pnl: f64,
+    pub buy_price: Decimal,
+    pub sell_price: Decimal,
+    pub qty: Decimal,
+    /// Realized P&L in the reporting currency (prices are in the quote currency)
+    pub pnl: Decimal,
     pub pnl_percent: f64,
+    /// Origin of the order that opened the position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<TradeOrigin>,
+    /// Tags of the order that opened the position
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OpenPosition {
     pub symbol: String,
     pub buy_time: String,
-    pub buy_price: f64,
-    pub qty: f64,
+    pub buy_price: Decimal,
+    pub qty: Decimal,
+    /// Quote mid at the last mark (None until first marked)
+    #[serde(default)]
+    pub mark_price: Option<Decimal>,
+    /// (mark - buy) * qty in the reporting currency
+    #[serde(default)]
+    pub unrealized_pnl: Decimal,
+}
+
+impl OpenPosition {
+    fn new(symbol: &str, buy_price: Decimal, qty: Decimal) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            buy_time: Utc::now().to_rfc3339(),
+            buy_price,
+            qty,
+            mark_price: None,
+            unrealized_pnl: Decimal::ZERO,
+        }
+    }
+}
+
+/// Total equity at one mark-to-market.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EquitySample {
+    pub ts: String,
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    /// Starting portfolio value (0 if the venue never reported one) plus net
+    /// transfers plus realized and unrealized P&L
+    pub equity: Decimal,
+}
+
+/// Fill-quality samples kept in the summary (oldest dropped first).
+const MAX_FILL_SAMPLES: usize = 500;
+
+/// Submission acks older than this are forgotten (order never filled).
+const SUBMISSION_TTL_HOURS: i64 = 24;
+
+/// One venue fill matched to its order's submission ack.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FillQuality {
+    pub ts: String,
+    pub symbol: String,
+    pub side: String,
+    pub order_id: String,
+    /// Quote mid when the order was priced
+    pub signal_price: Decimal,
+    /// Submitted limit (`None` for market orders)
+    pub limit_price: Option<Decimal>,
+    pub fill_price: Decimal,
+    pub qty: Decimal,
+    /// Cost vs the signal mid in bps: positive = bought higher / sold lower
+    pub slippage_bps: f64,
+    /// Submission ack to this fill
+    pub time_to_fill_ms: i64,
+}
+
+impl FillQuality {
+    /// Fill vs submitted limit in bps, signed like `slippage_bps`
+    /// (negative = price improvement).
+    pub fn limit_slippage_bps(&self) -> Option<f64> {
+        self.limit_price
+            .filter(|l| *l > Decimal::ZERO)
+            .map(|l| side_bps(&self.side, l, self.fill_price))
+    }
+}
+
+/// `price` vs `reference` in bps, positive when it cost the trade.
+fn side_bps(side: &str, reference: Decimal, price: Decimal) -> f64 {
+    let bps = float((price - reference) / reference) * 10_000.0;
+    if side.eq_ignore_ascii_case("sell") {
+        -bps
+    } else {
+        bps
+    }
+}
+
+/// `notes` with the order's tags appended (unchanged when untagged).
+fn with_tags(notes: String, metadata: &HashMap<String, String>) -> String {
+    if metadata.is_empty() {
+        return notes;
+    }
+    let tags = format!("tags={}", format_metadata(metadata));
+    if notes.is_empty() {
+        tags
+    } else {
+        format!("{} {}", notes, tags)
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Slippage and time-to-fill over the recorded fills (for tuning `aggression_bps`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecutionQuality {
+    pub fills: usize,
+    pub avg_slippage_bps: f64,
+    pub slippage_bps_p50: f64,
+    pub slippage_bps_p90: f64,
+    pub slippage_bps_p99: f64,
+    /// Limit orders only; negative = filled better than the limit
+    pub avg_limit_slippage_bps: Option<f64>,
+    pub time_to_fill_ms_p50: f64,
+    pub time_to_fill_ms_p90: f64,
+    pub time_to_fill_ms_p99: f64,
+}
+
+impl ExecutionQuality {
+    pub fn from_fills<'a>(fills: impl IntoIterator<Item = &'a FillQuality>) -> Self {
+        let fills: Vec<&FillQuality> = fills.into_iter().collect();
+        if fills.is_empty() {
+            return Self::default();
+        }
+        let mut slippage: Vec<f64> = fills.iter().map(|f| f.slippage_bps).collect();
+        slippage.sort_by(|a, b| a.total_cmp(b));
+        let mut ttf: Vec<f64> = fills.iter().map(|f| f.time_to_fill_ms as f64).collect();
+        ttf.sort_by(|a, b| a.total_cmp(b));
+        let limit: Vec<f64> = fills
+            .iter()
+            .filter_map(|f| f.limit_slippage_bps())
+            .collect();
+
+        Self {
+            fills: fills.len(),
+            avg_slippage_bps: slippage.iter().sum::<f64>() / slippage.len() as f64,
+            slippage_bps_p50: percentile(&slippage, 50.0),
+            slippage_bps_p90: percentile(&slippage, 90.0),
+            slippage_bps_p99: percentile(&slippage, 99.0),
+            avg_limit_slippage_bps: (!limit.is_empty())
+                .then(|| limit.iter().sum::<f64>() / limit.len() as f64),
+            time_to_fill_ms_p50: percentile(&ttf, 50.0),
+            time_to_fill_ms_p90: percentile(&ttf, 90.0),
+            time_to_fill_ms_p99: percentile(&ttf, 99.0),
+        }
+    }
+}
+
+/// A stop-loss exit booked against the stop it fired at.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExitSlippage {
+    pub ts: String,
+    pub symbol: String,
+    pub stop_price: Decimal,
+    pub exit_price: Decimal,
+    pub qty: Decimal,
+    /// Exit below the stop in bps (negative = sold above it)
+    pub slippage_bps: f64,
+}
+
+/// Realized slippage of stop-loss exits vs their stops (`stop_limit`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StopExitQuality {
+    pub exits: usize,
+    /// Quantity-weighted
+    pub avg_slippage_bps: f64,
+    pub worst_slippage_bps: f64,
+}
+
+impl StopExitQuality {
+    pub fn from_exits<'a>(exits: impl IntoIterator<Item = &'a ExitSlippage>) -> Self {
+        let exits: Vec<&ExitSlippage> = exits.into_iter().collect();
+        let qty: Decimal = exits.iter().map(|e| e.qty).sum();
+        if qty <= Decimal::ZERO {
+            return Self::default();
+        }
+        let weighted: f64 = exits.iter().map(|e| e.slippage_bps * float(e.qty)).sum();
+        Self {
+            exits: exits.len(),
+            avg_slippage_bps: weighted / float(qty),
+            worst_slippage_bps: exits
+                .iter()
+                .map(|e| e.slippage_bps)
+                .fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+/// Record of one A/B strategy instance (`ab_test`), booked from its tagged
+/// signals and fills.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StrategyResult {
+    /// "live" or "shadow" (paper)
+    pub mode: String,
+    pub signals: u64,
+    pub fills: u64,
+    pub closed_trades: u64,
+    pub winning_trades: u64,
+    pub losing_trades: u64,
+    pub realized_pnl: Decimal,
+    pub open_positions: HashMap<String, OpenPosition>,
+}
+
+impl StrategyResult {
+    pub fn win_rate_pct(&self) -> f64 {
+        if self.closed_trades == 0 {
+            return 0.0;
+        }
+        self.winning_trades as f64 / self.closed_trades as f64 * 100.0
+    }
+
+    /// Book a fill; `pnl` converts a quote-currency P&L for reporting.
+    pub(crate) fn book(
+        &mut self,
+        symbol: &str,
+        side: &str,
+        price: Decimal,
+        qty: Decimal,
+        pnl: impl Fn(Decimal) -> Decimal,
+    ) {
+        self.fills += 1;
+        if side.eq_ignore_ascii_case("buy") {
+            let pos = self
+                .open_positions
+                .entry(symbol.to_string())
+                .or_insert_with(|| OpenPosition::new(symbol, price, Decimal::ZERO));
+            pos.buy_price = (pos.buy_price * pos.qty + price * qty)
+                .checked_div(pos.qty + qty)
+                .unwrap_or(price);
+            pos.qty += qty;
+        } else if side.eq_ignore_ascii_case("sell") {
+            // Exits of positions opened before the test are not attributed
+            let Some(pos) = self.open_positions.get_mut(symbol) else {
+                return;
+            };
+            let closed = qty.min(pos.qty);
+            let pnl = pnl((price - pos.buy_price) * closed);
+            pos.qty -= closed;
+            if pos.qty <= Decimal::ZERO {
+                self.open_positions.remove(symbol);
+            }
+            self.closed_trades += 1;
+            self.realized_pnl += pnl;
+            if pnl > Decimal::ZERO {
+                self.winning_trades += 1;
+            } else {
+                self.losing_trades += 1;
+            }
+        }
+    }
+}
+
+/// Key of orders and trades without a `TradeOrigin` in `PerformanceSummary::origins`.
+pub const UNTAGGED_ORIGIN: &str = "untagged";
+
+/// Record of one trade origin (`TradeOrigin::mode`). Closed trades count
+/// toward the origin of their entry; exits toward `orders` of their own.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OriginResult {
+    pub orders: u64,
+    pub closed_trades: u64,
+    pub winning_trades: u64,
+    pub losing_trades: u64,
+    pub realized_pnl: Decimal,
+    pub win_rate_pct: f64,
+}
+
+/// Running totals of the balance audit trail (`services::balance_audit`),
+/// which explain why venue equity differs from computed equity.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BalanceAuditTotals {
+    pub changes: u64,
+    pub fees: Decimal,
+    pub funding: Decimal,
+    /// Deposits (+) / withdrawals (-) the audit attributed
+    pub transfers: Decimal,
+    pub unexplained: Decimal,
+    /// Venue portfolio value minus computed equity at the last audited change
+    pub equity_gap: Option<Decimal>,
+    /// Part of `equity_gap` that fees, funding and unexplained changes do not
+    /// account for
+    pub residual_gap: Option<Decimal>,
+}
+
+impl OriginResult {
+    fn book(&mut self, pnl: Decimal) {
+        self.closed_trades += 1;
+        self.realized_pnl += pnl;
+        if pnl > Decimal::ZERO {
+            self.winning_trades += 1;
+        } else {
+            self.losing_trades += 1;
+        }
+        self.win_rate_pct = self.winning_trades as f64 / self.closed_trades as f64 * 100.0;
+    }
+}
+
+/// What the reporter remembers from a submission ack until its fills stream in.
+#[derive(Clone, Debug)]
+struct Submission {
+    signal_price: Decimal,
+    limit_price: Option<Decimal>,
+    submitted_at: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PerformanceSummary {
     pub start_time: Option<String>,
+
+    /// Currency of P&L and notional totals (unset: each symbol's quote currency)
+    #[serde(default)]
+    pub reporting_currency: Option<String>,
     pub total_orders: u64,
     pub total_exec_reports: u64,
 
@@ -69,7 +393,7 @@ pub struct PerformanceSummary {
     pub filled: u64,
     pub rejected: u64,
 
-    pub total_notional: f64,
+    pub total_notional: Decimal,
 
     /// Per-symbol trade counts
     pub per_symbol: HashMap<String, u64>,
@@ -82,7 +406,7 @@ pub struct PerformanceSummary {
 
     // === Micro-trading metrics ===
     /// Total realized P&L across all closed trades
-    pub total_realized_pnl: f64,
+    pub total_realized_pnl: Decimal,
 
     /// Number of winning trades
     pub winning_trades: u64,
@@ -91,10 +415,89 @@ pub struct PerformanceSummary {
     pub losing_trades: u64,
 
     /// Sum of profits from winning trades
-    pub total_profit: f64,
+    pub total_profit: Decimal,
 
     /// Sum of losses from losing trades
-    pub total_loss: f64,
+    pub total_loss: Decimal,
+
+    /// P&L of open positions at the last mark
+    #[serde(default)]
+    pub total_unrealized_pnl: Decimal,
+
+    /// First portfolio value reported by the venue (equity curve baseline)
+    #[serde(default)]
+    pub starting_equity: Option<Decimal>,
+
+    /// Timestamped equity, one sample per mark, newest last
+    #[serde(default)]
+    pub equity_curve: VecDeque<EquitySample>,
+
+    /// Drawdown (%) from the rolling equity peak at the last mark
+    #[serde(default)]
+    pub drawdown_pct: f64,
+
+    /// Entry size multiplier from drawdown sizing (unset: full size)
+    #[serde(default)]
+    pub drawdown_size_factor: Option<f64>,
+
+    // === Account state (from Event::Account) ===
+    /// Last known cash balance
+    #[serde(default)]
+    pub account_cash: Option<Decimal>,
+
+    /// Last known portfolio value
+    #[serde(default)]
+    pub account_portfolio_value: Option<Decimal>,
+
+    /// Net external deposits (+) / withdrawals (-) attributed by the balance audit
+    #[serde(default)]
+    pub net_transfers: Decimal,
+
+    /// Attributed balance changes (`Event::Account(BalanceAudited)`)
+    #[serde(default)]
+    pub balance_audit: BalanceAuditTotals,
+
+    /// Loss-streak cooldowns started per symbol (`services::risk_guard`)
+    #[serde(default)]
+    pub cooldowns: HashMap<String, u64>,
+
+    /// Buy signals skipped by entry guards, per guard (`Event::EntrySkipped`)
+    #[serde(default)]
+    pub skipped_entries: HashMap<String, u64>,
+
+    /// Orders blocked by pre-trade compliance, per rule (`Event::OrderRejected`)
+    #[serde(default)]
+    pub rejected_orders: HashMap<String, u64>,
+
+    // === Execution quality (streamed fills only) ===
+    /// Most recent fills matched to their submission, newest last
+    #[serde(default)]
+    pub fill_quality: VecDeque<FillQuality>,
+
+    /// Most recent stop-loss exits vs their stops, newest last
+    #[serde(default)]
+    pub exit_slippage: VecDeque<ExitSlippage>,
+
+    // === A/B test (`ab_test`) ===
+    /// Live and shadow strategy records by strategy id
+    #[serde(default)]
+    pub strategies: BTreeMap<String, StrategyResult>,
+
+    // === Attribution ===
+    /// Orders and closed trades by origin mode ("hft", "llm", "hybrid",
+    /// "monitor_exit", "untagged")
+    #[serde(default)]
+    pub origins: BTreeMap<String, OriginResult>,
+}
+
+/// Closed-trade record behind Kelly sizing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WinLoss {
+    pub wins: u64,
+    pub losses: u64,
+    pub total_profit: Decimal,
+    /// Sum of losses (positive)
+    pub total_loss: Decimal,
 }
 
 /// Computed statistics for display
@@ -107,9 +510,23 @@ pub struct ComputedStats {
     pub profit_factor: f64, // total_profit / total_loss
     pub total_closed_trades: u64,
     pub open_position_count: usize,
+    pub execution: ExecutionQuality,
+    /// Stop-loss exits vs their stops
+    pub stop_exits: StopExitQuality,
+    /// Trade count, win rate and realized P&L by origin mode
+    pub by_origin: BTreeMap<String, OriginResult>,
 }
 
 impl PerformanceSummary {
+    pub fn win_loss(&self) -> WinLoss {
+        WinLoss {
+            wins: self.winning_trades,
+            losses: self.losing_trades,
+            total_profit: self.total_profit,
+            total_loss: self.total_loss,
+        }
+    }
+
     /// Compute derived statistics
     pub fn compute_stats(&self) -> ComputedStats {
         let runtime_minutes = if let Some(ref start) = self.start_time {
@@ -138,14 +555,14 @@ impl PerformanceSummary {
         };
 
         let avg_profit_per_trade = if total_closed > 0 {
-            self.total_realized_pnl / total_closed as f64
+            float(self.total_realized_pnl) / total_closed as f64
         } else {
             0.0
         };
 
-        let profit_factor = if self.total_loss > 0.0 {
-            self.total_profit / self.total_loss
-        } else if self.total_profit > 0.0 {
+        let profit_factor = if self.total_loss > Decimal::ZERO {
+            float(self.total_profit / self.total_loss)
+        } else if self.total_profit > Decimal::ZERO {
             f64::INFINITY
         } else {
             0.0
@@ -159,6 +576,9 @@ impl PerformanceSummary {
             profit_factor,
             total_closed_trades: total_closed,
             open_position_count: self.open_positions.len(),
+            execution: ExecutionQuality::from_fills(&self.fill_quality),
+            stop_exits: StopExitQuality::from_exits(&self.exit_slippage),
+            by_origin: self.origins.clone(),
         }
     }
 }
@@ -167,6 +587,29 @@ impl PerformanceSummary {
 pub struct TradeReporter {
     summary: Arc<Mutex<PerformanceSummary>>,
     log_path: PathBuf,
+    sink: Arc<dyn TradeLogSink>,
+    /// Fills arrive from a user-data stream, so submission acks are not booked
+    streamed_fills: bool,
+    /// Acks waiting for their fills, by order id
+    submissions: Arc<Mutex<HashMap<String, Submission>>>,
+    /// Rates for normalizing P&L into the reporting currency
+    fx: Option<(MarketStore, Arc<AppConfig>)>,
+    /// Quotes that open positions are marked to, with the mark schedule
+    marks: Option<(MarketStore, MarkToMarketConfig)>,
+    /// A/B test: live strategy id and the shadow strategy's paper book
+    ab_test: Option<(String, Arc<Mutex<ShadowBook>>)>,
+    /// Thesis of the live buy signal behind each open position, by symbol
+    theses: Arc<Mutex<HashMap<String, String>>>,
+    /// Origin of the live buy order behind each open position, by symbol
+    entry_origins: Arc<Mutex<HashMap<String, TradeOrigin>>>,
+    /// Tags of the last buy order per symbol (carried onto the closed trade)
+    entry_metadata: Arc<Mutex<HashMap<String, HashMap<String, String>>>>,
+    /// Stop price of the stop-loss exit in flight, by symbol
+    exit_stops: Arc<Mutex<HashMap<String, Decimal>>>,
+    /// Database rows instead of the flat trade log (`database.enabled`)
+    storage: Option<StorageWriter>,
+    /// Drawdown sizing curve applied at each mark (`sizing.drawdown`)
+    drawdown: Option<DrawdownSizingConfig>,
 }
 
 impl TradeReporter {
@@ -174,32 +617,257 @@ impl TradeReporter {
         Self {
             summary: Arc::new(Mutex::new(PerformanceSummary::default())),
             log_path,
+            sink: Arc::new(JsonlSink),
+            streamed_fills: false,
+            submissions: Arc::new(Mutex::new(HashMap::new())),
+            fx: None,
+            marks: None,
+            ab_test: None,
+            theses: Arc::new(Mutex::new(HashMap::new())),
+            entry_origins: Arc::new(Mutex::new(HashMap::new())),
+            entry_metadata: Arc::new(Mutex::new(HashMap::new())),
+            exit_stops: Arc::new(Mutex::new(HashMap::new())),
+            storage: None,
+            drawdown: None,
+        }
+    }
+
+    /// Write the trade log with a different sink (CSV, MessagePack, custom).
+    pub fn with_sink(mut self, sink: Arc<dyn TradeLogSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Store orders, fills, closed trades and equity samples through
+    /// `storage`; the trade log file is no longer written.
+    pub fn with_storage(mut self, storage: Option<StorageWriter>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Book only real fill updates ("fill" / "partial_fill") from a user-data
+    /// stream; partial fills accumulate into the open position.
+    pub fn with_streamed_fills(mut self, streamed_fills: bool) -> Self {
+        self.streamed_fills = streamed_fills;
+        self
+    }
+
+    /// Book P&L and notional in `fx.reporting_currency`, converting each
+    /// symbol's quote currency at the store's current rate.
+    pub fn with_fx(mut self, store: MarketStore, config: &AppConfig) -> Self {
+        self.summary.lock().unwrap().reporting_currency =
+            Some(config.fx.reporting_currency.clone());
+        self.fx = Some((store, Arc::new(config.clone())));
+        self
+    }
+
+    /// Compare the live strategy with a paper-traded shadow instance
+    /// (`ab_test`): shadow signals are filled by a `ShadowBook`, and fills of
+    /// both are booked per strategy id in `strategies`.
+    pub fn with_ab_test(mut self, config: &AppConfig) -> Self {
+        if !config.ab_test.enabled {
+            return self;
+        }
+        match shadow_config(config) {
+            Ok(shadow) => {
+                let book = ShadowBook::new(&config.ab_test.shadow_id, shadow);
+                self.ab_test = Some((config.ab_test.live_id.clone(), Arc::new(Mutex::new(book))));
+            }
+            Err(e) => warn!("🧪 [A/B] Shadow disabled (ab_test.shadow: {})", e),
+        }
+        self
+    }
+
+    /// Mark open positions to `store`'s latest quotes every
+    /// `mark_to_market.interval_secs`, sampling the equity curve each time.
+    pub fn with_mark_to_market(mut self, store: MarketStore, config: &MarkToMarketConfig) -> Self {
+        if config.enabled {
+            self.marks = Some((store, config.clone()));
         }
+        self
+    }
+
+    /// Scale entries down while the equity curve is in drawdown
+    /// (`sizing.drawdown`); needs `mark_to_market` for the curve.
+    pub fn with_drawdown_sizing(mut self, config: &DrawdownSizingConfig) -> Self {
+        if config.enabled {
+            self.drawdown = Some(config.clone());
+        }
+        self
+    }
+
+    /// `amount` in `symbol`'s quote currency, converted to the reporting
+    /// currency. Kept as-is (with a warning) when no rate is known.
+    fn to_reporting(&self, symbol: &str, amount: Decimal) -> Decimal {
+        let Some((store, config)) = &self.fx else {
+            return amount;
+        };
+        match store.quote_fx_rate(config, symbol) {
+            Some(rate) => amount * dec(rate),
+            None => {
+                warn!(
+                    "💱 [REPORT] No {} rate for {} - booking {} unconverted",
+                    config.quote_currency_for(symbol),
+                    symbol,
+                    amount
+                );
+                amount
+            }
+        }
+    }
+
+    pub fn format(&self) -> TradeLogFormat {
+        self.sink.format()
     }
 
     pub fn summary(&self) -> PerformanceSummary {
         self.summary.lock().unwrap().clone()
     }
 
+    /// Mark every open position to its latest quote mid in `store` (positions
+    /// without a quote keep their last mark) and append an equity sample.
+    pub fn mark_to_market(&self, store: &MarketStore, max_samples: usize, now: DateTime<Utc>) {
+        let mut s = self.summary.lock().unwrap();
+        let sample = self.mark(&mut s, store, now);
+        while s.equity_curve.len() >= max_samples.max(1) {
+            s.equity_curve.pop_front();
+        }
+        if let Some(storage) = &self.storage {
+            storage.record(StorageRecord::Equity(sample.clone()));
+        }
+        s.equity_curve.push_back(sample);
+
+        if let Some(config) = &self.drawdown {
+            let Some(drawdown) = drawdown_pct(&s.equity_curve, config.peak_window) else {
+                return;
+            };
+            let current = s.drawdown_size_factor.unwrap_or(1.0);
+            let factor = next_factor(config, current, target_factor(config, drawdown));
+            if factor != current {
+                info!(
+                    "📉 [REPORT] Drawdown {:.2}% - entry size {:.0}% -> {:.0}%",
+                    drawdown,
+                    current * 100.0,
+                    factor * 100.0
+                );
+            }
+            s.drawdown_pct = drawdown;
+            s.drawdown_size_factor = Some(factor);
+        }
+    }
+
+    /// Mark the open positions and value the account on the `/report` basis,
+    /// without adding a sample to `equity_curve`. None until the venue has
+    /// reported a starting portfolio value. `/equity` records these.
+    pub fn equity_now(&self, store: &MarketStore, now: DateTime<Utc>) -> Option<EquitySample> {
+        let mut s = self.summary.lock().unwrap();
+        s.starting_equity?;
+        Some(self.mark(&mut s, store, now))
+    }
+
+    /// Re-mark `s.open_positions` from `store` and value the account: starting
+    /// equity plus net transfers, realized and unrealized P&L.
+    fn mark(
+        &self,
+        s: &mut PerformanceSummary,
+        store: &MarketStore,
+        now: DateTime<Utc>,
+    ) -> EquitySample {
+        for pos in s.open_positions.values_mut() {
+            if let Some(q) = store.get_latest_quote(&pos.symbol) {
+                if q.bid_price > 0.0 && q.ask_price >= q.bid_price {
+                    pos.mark_price = Some(dec((q.bid_price + q.ask_price) / 2.0));
+                }
+            }
+            self.revalue(pos);
+        }
+        s.total_unrealized_pnl = s.open_positions.values().map(|p| p.unrealized_pnl).sum();
+
+        EquitySample {
+            ts: now.to_rfc3339(),
+            realized_pnl: s.total_realized_pnl,
+            unrealized_pnl: s.total_unrealized_pnl,
+            equity: s.starting_equity.unwrap_or_default()
+                + s.net_transfers
+                + s.total_realized_pnl
+                + s.total_unrealized_pnl,
+        }
+    }
+
+    /// Entry size multiplier from drawdown sizing (1.0 when off or unmarked).
+    pub fn drawdown_size_factor(&self) -> f64 {
+        self.summary
+            .lock()
+            .unwrap()
+            .drawdown_size_factor
+            .unwrap_or(1.0)
+    }
+
+    /// Recompute `pos.unrealized_pnl` from its last mark.
+    fn revalue(&self, pos: &mut OpenPosition) {
+        if let Some(mark) = pos.mark_price {
+            pos.unrealized_pnl = self.to_reporting(&pos.symbol, (mark - pos.buy_price) * pos.qty);
+        }
+    }
+
+    /// Session win/loss record (without cloning the trade history).
+    pub fn win_loss(&self) -> WinLoss {
+        self.summary.lock().unwrap().win_loss()
+    }
+
     pub async fn start(&self, event_bus: EventBus) {
-        let mut rx = event_bus.subscribe();
+        let mut rx = event_bus.subscribe("reporter");
         let reporter = self.clone();
 
+        let shutdown = event_bus.shutdown().clone();
+        let worker = shutdown.track_worker();
+
         tokio::spawn(async move {
+            let _worker = worker;
             info!(
-                "📈 TradeReporter started (log: {})",
-                reporter.log_path.display()
+                "📈 TradeReporter started (log: {}, format: {})",
+                reporter.log_path.display(),
+                reporter.format().extension()
             );
 
-            while let Ok(event) = rx.recv().await {
-                match event {
-                    Event::Order(order) => {
-                        reporter.on_order(&order);
+            let mark_every = reporter
+                .marks
+                .as_ref()
+                .map_or(Duration::from_secs(60), |(_, c)| {
+                    Duration::from_secs(c.interval_secs.max(1))
+                });
+            let mut mark_tick = tokio::time::interval(mark_every);
+
+            loop {
+                let event = tokio::select! {
+                    _ = mark_tick.tick(), if reporter.marks.is_some() => {
+                        if let Some((store, config)) = &reporter.marks {
+                            reporter.mark_to_market(store, config.max_equity_samples, Utc::now());
+                        }
+                        if let Err(e) = reporter.flush_summary() {
+                            error!("TradeReporter failed to flush summary: {}", e);
+                        }
+                        continue;
                     }
-                    Event::Execution(exec) => {
-                        reporter.on_execution(&exec);
+                    // Drain is over: record whatever is still queued, then flush and exit
+                    _ = shutdown.stopped() => {
+                        while let Ok(event) = rx.try_recv() {
+                            reporter.on_event(event);
+                        }
+                        if let Err(e) = reporter.flush_summary() {
+                            error!("TradeReporter failed to flush summary: {}", e);
+                        }
+                        info!("📈 TradeReporter flushed and stopped");
+                        break;
                     }
-                    _ => {}
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if let Some(trade) = reporter.on_event(event) {
+                    event_bus.publish(Event::TradeClosed(trade)).ok();
                 }
 
                 // Flush to disk best-effort on every relevant event. Cheap + safe.
@@ -211,6 +879,121 @@ impl TradeReporter {
         });
     }
 
+    /// Book one bus event (called by the `start` loop). Returns the trade an
+    /// exit fill closed, for the loop to publish.
+    pub(crate) fn on_event(&self, event: Event) -> Option<TradeClosedEvent> {
+        match event {
+            Event::Order(order) => self.on_order(&order),
+            Event::Execution(exec) => return self.on_execution(&exec),
+            Event::Account(account) => self.on_account(&account),
+            Event::Cooldown(cooldown) => {
+                let mut s = self.summary.lock().unwrap();
+                *s.cooldowns.entry(cooldown.symbol).or_insert(0) += 1;
+            }
+            Event::Market(MarketEvent::Quote {
+                symbol, bid, ask, ..
+            }) => {
+                if let Some((_, book)) = &self.ab_test {
+                    let fill = book.lock().unwrap().on_quote(&symbol, bid, ask);
+                    if let Some(fill) = fill {
+                        self.on_shadow_fill(&fill);
+                    }
+                }
+            }
+            Event::Signal(signal) => self.on_signal(&signal),
+            Event::Config(update) => {
+                if let Some((_, book)) = &self.ab_test {
+                    match shadow_config(&update.config) {
+                        Ok(shadow) => book.lock().unwrap().set_config(shadow),
+                        Err(e) => warn!("🧪 [A/B] Shadow config not updated: {}", e),
+                    }
+                }
+            }
+            Event::EntrySkipped(skip) => {
+                info!(
+                    "📈 TradeReporter: skipped BUY {} ({}: {})",
+                    skip.symbol, skip.reason, skip.detail
+                );
+                let mut s = self.summary.lock().unwrap();
+                *s.skipped_entries.entry(skip.reason).or_insert(0) += 1;
+            }
+            Event::OrderRejected(rejected) => {
+                info!(
+                    "📈 TradeReporter: rejected {} {} ({}: {})",
+                    rejected.side.to_uppercase(),
+                    rejected.symbol,
+                    rejected.reason,
+                    rejected.detail
+                );
+                let mut s = self.summary.lock().unwrap();
+                *s.rejected_orders.entry(rejected.reason).or_insert(0) += 1;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Count a tagged signal; shadow signals are paper-filled. Live buys
+    /// leave their thesis for the trade they open.
+    fn on_signal(&self, signal: &AnalysisSignal) {
+        let shadow_id = self
+            .ab_test
+            .as_ref()
+            .map(|(_, book)| book.lock().unwrap().id().to_string());
+        if signal.signal.eq_ignore_ascii_case("buy")
+            && (shadow_id.is_none() || signal.strategy_id != shadow_id)
+        {
+            self.theses
+                .lock()
+                .unwrap()
+                .insert(signal.symbol.clone(), signal.thesis.clone());
+        }
+        let (Some((_, book)), Some(id)) = (&self.ab_test, &signal.strategy_id) else {
+            return;
+        };
+        let fill = {
+            let mut book = book.lock().unwrap();
+            let is_shadow = book.id() == id;
+            self.strategy_entry(id, is_shadow, |r| r.signals += 1);
+            is_shadow.then(|| book.on_signal(signal)).flatten()
+        };
+        if let Some(fill) = fill {
+            self.on_shadow_fill(&fill);
+        }
+    }
+
+    fn on_shadow_fill(&self, fill: &ExecutionReport) {
+        let (Some(price), Some(qty), Some(id)) = (fill.price, fill.qty, &fill.strategy_id) else {
+            return;
+        };
+        info!(
+            "🧪 [A/B] {} paper {} {} qty={} @ {}",
+            id,
+            fill.side.to_uppercase(),
+            fill.symbol,
+            qty,
+            price
+        );
+        self.strategy_entry(id, true, |r| {
+            r.book(&fill.symbol, &fill.side, price, qty, |pnl| {
+                self.to_reporting(&fill.symbol, pnl)
+            })
+        });
+    }
+
+    /// Run `update` on the record of strategy `id`, creating it if needed.
+    fn strategy_entry(&self, id: &str, shadow: bool, update: impl FnOnce(&mut StrategyResult)) {
+        let mut s = self.summary.lock().unwrap();
+        let result = s
+            .strategies
+            .entry(id.to_string())
+            .or_insert_with(|| StrategyResult {
+                mode: if shadow { "shadow" } else { "live" }.to_string(),
+                ..StrategyResult::default()
+            });
+        update(result);
+    }
+
     fn on_order(&self, order: &OrderRequest) {
         let mut s = self.summary.lock().unwrap();
         s.total_orders += 1;
@@ -221,9 +1004,33 @@ impl TradeReporter {
             s.sells += 1;
         }
         *s.per_symbol.entry(order.symbol.clone()).or_insert(0) += 1;
+        let mode = order
+            .origin
+            .as_ref()
+            .map_or(UNTAGGED_ORIGIN, |o| o.mode.as_str());
+        s.origins.entry(mode.to_string()).or_default().orders += 1;
 
         drop(s);
 
+        if order.action.eq_ignore_ascii_case("buy") {
+            let mut origins = self.entry_origins.lock().unwrap();
+            match &order.origin {
+                Some(origin) => origins.insert(order.symbol.clone(), origin.clone()),
+                None => origins.remove(&order.symbol),
+            };
+            self.entry_metadata
+                .lock()
+                .unwrap()
+                .insert(order.symbol.clone(), order.metadata.clone());
+        } else if order.action.eq_ignore_ascii_case("sell") {
+            // Monitor stop-loss exits carry the stop they fired at
+            let mut stops = self.exit_stops.lock().unwrap();
+            match order.stop_loss.filter(|stop| *stop > Decimal::ZERO) {
+                Some(stop) => stops.insert(order.symbol.clone(), stop),
+                None => stops.remove(&order.symbol),
+            };
+        }
+
         // Optional: write a log line for orders too (as "status=order_created")
         let entry = TradeLogEntry {
             ts: Utc::now().to_rfc3339(),
@@ -231,24 +1038,129 @@ impl TradeReporter {
             action: order.action.clone(),
             order_id: "unknown".to_string(),
             status: "order_created".to_string(),
-            qty: Some(order.qty).filter(|q| *q > 0.0),
+            qty: Some(order.qty).filter(|q| *q > Decimal::ZERO),
             price: order.limit_price,
             notional: order.limit_price.and_then(|p| {
-                if order.qty > 0.0 {
+                if order.qty > Decimal::ZERO {
                     Some(p * order.qty)
                 } else {
                     None
                 }
             }),
-            notes: Some(format!(
-                "type={} sl={:?} tp={:?}",
-                order.order_type, order.stop_loss, order.take_profit
+            notes: Some(with_tags(
+                format!(
+                    "type={} sl={:?} tp={:?}",
+                    order.order_type, order.stop_loss, order.take_profit
+                ),
+                &order.metadata,
             )),
         };
-        let _ = self.append_jsonl(&entry);
+        self.append_entry(&entry);
     }
 
-    fn on_execution(&self, exec: &ExecutionReport) {
+    fn on_account(&self, event: &AccountEvent) {
+        let mut s = self.summary.lock().unwrap();
+        match event {
+            AccountEvent::BalanceUpdated {
+                cash,
+                portfolio_value,
+                ..
+            } => {
+                s.account_cash = *cash;
+                s.account_portfolio_value = *portfolio_value;
+                if s.starting_equity.is_none() {
+                    s.starting_equity = *portfolio_value;
+                }
+            }
+            AccountEvent::MarginChanged { .. } => {}
+            AccountEvent::BalanceAudited(change) => {
+                if !change.transfer.is_zero() {
+                    s.net_transfers += change.transfer;
+                    info!("💸 [REPORT] External transfer: {:+.2}", change.transfer);
+                }
+                let computed = s.starting_equity.map(|start| {
+                    start + s.net_transfers + s.total_realized_pnl + s.total_unrealized_pnl
+                });
+                let audit = &mut s.balance_audit;
+                audit.changes += 1;
+                audit.fees += change.fees;
+                audit.funding += change.funding;
+                audit.transfers += change.transfer;
+                audit.unexplained += change.unexplained;
+                if let (Some(actual), Some(computed)) = (change.portfolio_value, computed) {
+                    let gap = actual - computed;
+                    audit.equity_gap = Some(gap);
+                    audit.residual_gap =
+                        Some(gap - (audit.funding - audit.fees + audit.unexplained));
+                }
+            }
+        }
+    }
+
+    /// Remember a submission ack, or match a streamed fill to its ack and
+    /// record the fill's slippage and time to fill. Acks booked as fills
+    /// (no user-data stream) carry only our own estimate, so they are skipped.
+    fn track_fill_quality(&self, exec: &ExecutionReport, now: DateTime<Utc>) {
+        if exec.order_id.is_empty() {
+            return;
+        }
+        let mut submissions = self.submissions.lock().unwrap();
+        if !exec.is_fill_update() {
+            if let Some(signal_price) = exec.signal_price.filter(|p| *p > Decimal::ZERO) {
+                submissions.retain(|_, sub| {
+                    now.signed_duration_since(sub.submitted_at).num_hours() < SUBMISSION_TTL_HOURS
+                });
+                submissions.insert(
+                    exec.order_id.clone(),
+                    Submission {
+                        signal_price,
+                        limit_price: exec.limit_price,
+                        submitted_at: now,
+                    },
+                );
+            }
+            return;
+        }
+
+        let Some(sub) = submissions.get(&exec.order_id).cloned() else {
+            return;
+        };
+        if exec.status == "fill" {
+            submissions.remove(&exec.order_id);
+        }
+        drop(submissions);
+        let (Some(fill_price), Some(qty)) = (exec.price, exec.qty) else {
+            return;
+        };
+
+        let sample = FillQuality {
+            ts: now.to_rfc3339(),
+            symbol: exec.symbol.clone(),
+            side: exec.side.to_lowercase(),
+            order_id: exec.order_id.clone(),
+            signal_price: sub.signal_price,
+            limit_price: sub.limit_price,
+            fill_price,
+            qty,
+            slippage_bps: side_bps(&exec.side, sub.signal_price, fill_price),
+            time_to_fill_ms: now
+                .signed_duration_since(sub.submitted_at)
+                .num_milliseconds()
+                .max(0),
+        };
+        let mut s = self.summary.lock().unwrap();
+        if s.fill_quality.len() >= MAX_FILL_SAMPLES {
+            s.fill_quality.pop_front();
+        }
+        s.fill_quality.push_back(sample);
+    }
+
+    fn on_execution(&self, exec: &ExecutionReport) -> Option<TradeClosedEvent> {
+        if self.streamed_fills {
+            self.track_fill_quality(exec, Utc::now());
+        }
+
+        let mut closed = None;
         let mut s = self.summary.lock().unwrap();
 
         // Initialize start_time on first execution
@@ -259,35 +1171,62 @@ impl TradeReporter {
         s.total_exec_reports += 1;
 
         let st = exec.status.to_lowercase();
-        if st.contains("fill") || st == "new" || st == "accepted" {
-            // Assuming "new" or "accepted" means it will be filled for now,
-            // as we don't get async fill updates in this architecture yet.
-            // Ideally we should wait for "filled".
-            // But ExecutionEngine sends "new" immediately after submit.
-            // We'll treat "new" as a fill for reporting purposes to track the lifecycle,
-            // acknowledging this is an estimation.
+        if exec.counts_as_fill(self.streamed_fills) {
+            // Without a user-data stream, "new"/"accepted" acks are booked as
+            // fills at the submitted price (an estimate). With one, only real
+            // fill updates are booked.
 
             if let (Some(qty), Some(price)) = (exec.qty, exec.price) {
+                if let Some(storage) = &self.storage {
+                    storage.record(StorageRecord::Fill(StoredFill {
+                        ts: Utc::now().to_rfc3339(),
+                        symbol: exec.symbol.clone(),
+                        side: exec.side.to_lowercase(),
+                        order_id: exec.order_id.clone(),
+                        qty,
+                        price,
+                        strategy_id: exec.strategy_id.clone(),
+                        correlation_id: exec.correlation_id.clone(),
+                    }));
+                }
                 if exec.side.eq_ignore_ascii_case("buy") {
                     s.buys += 1;
-                    s.open_positions.insert(
-                        exec.symbol.clone(),
-                        OpenPosition {
-                            symbol: exec.symbol.clone(),
-                            buy_time: Utc::now().to_rfc3339(),
-                            buy_price: price,
-                            qty,
+                    let mut merged = match s.open_positions.get(&exec.symbol) {
+                        // Another fill of the same entry: volume-weighted price
+                        Some(open) if exec.is_fill_update() => OpenPosition {
+                            buy_price: (open.buy_price * open.qty + price * qty)
+                                .checked_div(open.qty + qty)
+                                .unwrap_or(price),
+                            qty: open.qty + qty,
+                            ..open.clone()
                         },
-                    );
+                        _ => OpenPosition::new(&exec.symbol, price, qty),
+                    };
+                    self.revalue(&mut merged);
+                    s.open_positions.insert(exec.symbol.clone(), merged);
                 } else if exec.side.eq_ignore_ascii_case("sell") {
                     s.sells += 1;
                     if let Some(open_pos) = s.open_positions.remove(&exec.symbol) {
-                        let pnl = (price - open_pos.buy_price) * qty;
-                        let pnl_percent = (price - open_pos.buy_price) / open_pos.buy_price * 100.0;
+                        // A partial exit leaves the rest of the position open
+                        let partial = exec.is_fill_update() && open_pos.qty > qty;
+                        if partial {
+                            let mut rest = OpenPosition {
+                                qty: open_pos.qty - qty,
+                                ..open_pos.clone()
+                            };
+                            self.revalue(&mut rest);
+                            s.open_positions.insert(exec.symbol.clone(), rest);
+                        }
+                        let pnl =
+                            self.to_reporting(&exec.symbol, (price - open_pos.buy_price) * qty);
+                        let pnl_percent = (price - open_pos.buy_price)
+                            .checked_div(open_pos.buy_price)
+                            .map_or(0.0, float)
+                            * 100.0;
 
                         // Track win/loss metrics
                         s.total_realized_pnl += pnl;
-                        if pnl > 0.0 {
+                        if pnl > Decimal::ZERO {
                             s.winning_trades += 1;
                             s.total_profit += pnl;
                         } else {
@@ -295,6 +1234,54 @@ impl TradeReporter {
                             s.total_loss += pnl.abs();
                         }
 
+                        let thesis = {
+                            let mut theses = self.theses.lock().unwrap();
+                            if partial {
+                                theses.get(&exec.symbol).cloned()
+                            } else {
+                                theses.remove(&exec.symbol)
+                            }
+                        };
+                        let origin = {
+                            let mut origins = self.entry_origins.lock().unwrap();
+                            if partial {
+                                origins.get(&exec.symbol).cloned()
+                            } else {
+                                origins.remove(&exec.symbol)
+                            }
+                        };
+                        let metadata = {
+                            let mut tags = self.entry_metadata.lock().unwrap();
+                            if partial {
+                                tags.get(&exec.symbol).cloned()
+                            } else {
+                                tags.remove(&exec.symbol)
+                            }
+                        };
+                        let mode = origin.as_ref().map_or(UNTAGGED_ORIGIN, |o| o.mode.as_str());
+                        s.origins.entry(mode.to_string()).or_default().book(pnl);
+
+                        let stop = {
+                            let mut stops = self.exit_stops.lock().unwrap();
+                            if partial {
+                                stops.get(&exec.symbol).copied()
+                            } else {
+                                stops.remove(&exec.symbol)
+                            }
+                        };
+                        if let Some(stop) = stop {
+                            if s.exit_slippage.len() >= MAX_FILL_SAMPLES {
+                                s.exit_slippage.pop_front();
+                            }
+                            s.exit_slippage.push_back(ExitSlippage {
+                                ts: Utc::now().to_rfc3339(),
+                                symbol: exec.symbol.clone(),
+                                stop_price: stop,
+                                exit_price: price,
+                                qty,
+                                slippage_bps: side_bps("sell", stop, price),
+                            });
+                        }
                         let trade = ClosedTrade {
                             symbol: exec.symbol.clone(),
                             buy_time: open_pos.buy_time,
@@ -304,7 +1291,27 @@ impl TradeReporter {
                             qty,
                             pnl,
                             pnl_percent,
+                            origin,
+                            metadata: metadata.unwrap_or_default(),
                         };
+                        closed = Some(TradeClosedEvent {
+                            symbol: exec.symbol.clone(),
+                            thesis: thesis.unwrap_or_default(),
+                            entry_price: open_pos.buy_price,
+                            exit_price: price,
+                            qty,
+                            pnl,
+                            pnl_percent,
+                            strategy_id: exec.strategy_id.clone(),
+                            correlation_id: exec.correlation_id.clone(),
+                            timestamp: trade.sell_time.clone(),
+                        });
+                        if let Some(storage) = &self.storage {
+                            storage.record(StorageRecord::ClosedTrade(StoredTrade {
+                                trade: trade.clone(),
+                                strategy_id: exec.strategy_id.clone(),
+                            }));
+                        }
 
                         s.history
                             .entry(exec.symbol.clone())
@@ -312,7 +1319,8 @@ impl TradeReporter {
                             .push(trade);
                     }
                 }
-                s.total_notional += qty * price;
+                s.total_unrealized_pnl = s.open_positions.values().map(|p| p.unrealized_pnl).sum();
+                s.total_notional += self.to_reporting(&exec.symbol, qty * price);
             }
             s.filled += 1;
         } else if st.contains("reject") {
@@ -321,6 +1329,19 @@ impl TradeReporter {
 
         drop(s);
 
+        // A/B test: everything not from the shadow book is the live strategy's
+        if let (Some((live_id, _)), Some(qty), Some(price)) = (&self.ab_test, exec.qty, exec.price)
+        {
+            if exec.counts_as_fill(self.streamed_fills) {
+                let id = exec.strategy_id.as_deref().unwrap_or(live_id);
+                self.strategy_entry(id, false, |r| {
+                    r.book(&exec.symbol, &exec.side, price, qty, |pnl| {
+                        self.to_reporting(&exec.symbol, pnl)
+                    })
+                });
+            }
+        }
+
         let entry = TradeLogEntry {
             ts: Utc::now().to_rfc3339(),
             symbol: exec.symbol.clone(),
@@ -333,33 +1354,23 @@ impl TradeReporter {
                 (Some(q), Some(p)) => Some(q * p),
                 _ => None,
             },
-            notes: None,
+            notes: (!exec.metadata.is_empty()).then(|| with_tags(String::new(), &exec.metadata)),
         };
 
-        let _ = self.append_jsonl(&entry);
+        self.append_entry(&entry);
+        closed
     }
 
-    fn append_jsonl(
-        &self,
-        entry: &TradeLogEntry,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        use std::io::Write;
-
-        if let Some(parent) = self.log_path.parent() {
-            std::fs::create_dir_all(parent)?;
+    fn append_entry(&self, entry: &TradeLogEntry) {
+        if let Some(storage) = &self.storage {
+            storage.record(StorageRecord::Order(entry.clone()));
+            return;
         }
-
-        let mut f = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.log_path)?;
-
-        let line = serde_json::to_string(entry)?;
-        writeln!(f, "{}", line)?;
-        Ok(())
+        // Best-effort: a failed log write must not affect trading
+        let _ = self.sink.append(&self.log_path, entry);
     }
 
-    fn flush_summary(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    fn flush_summary(&self) -> Result<()> {
         let summary_path = self.log_path.with_file_name("trade_summary.json");
 
         let stats_path = self.log_path.with_file_name("trade_stats.json");
@@ -371,8 +1382,11 @@ impl TradeReporter {
         let s = self.summary.lock().unwrap().clone();
         let stats = s.compute_stats();
 
-        // Write full summary
-        std::fs::write(&summary_path, serde_json::to_vec_pretty(&s)?)?;
+        // Write full summary, with the execution-quality percentiles for /report
+        let mut summary_json = serde_json::to_value(&s)?;
+        summary_json["execution_quality"] = serde_json::to_value(&stats.execution)?;
+        summary_json["stop_exit_quality"] = serde_json::to_value(&stats.stop_exits)?;
+        write_atomic(&summary_path, &serde_json::to_vec_pretty(&summary_json)?)?;
 
         // Write computed stats (smaller, easier to read)
         let stats_output = serde_json::json!({
@@ -386,10 +1400,48 @@ impl TradeReporter {
             "winning_trades": s.winning_trades,
             "losing_trades": s.losing_trades,
             "total_realized_pnl": format!("${:.4}", s.total_realized_pnl),
+            "total_unrealized_pnl": format!("${:.4}", s.total_unrealized_pnl),
+            "equity": s.equity_curve.back().map(|e| format!("${:.2}", e.equity)),
             "total_notional_traded": format!("${:.2}", s.total_notional),
+            "fills_measured": stats.execution.fills,
+            "avg_slippage_bps": format!("{:.2}", stats.execution.avg_slippage_bps),
+            "slippage_bps_p90": format!("{:.2}", stats.execution.slippage_bps_p90),
+            "time_to_fill_ms_p50": format!("{:.0}", stats.execution.time_to_fill_ms_p50),
+            "time_to_fill_ms_p90": format!("{:.0}", stats.execution.time_to_fill_ms_p90),
+            "stop_exits": stats.stop_exits.exits,
+            "avg_stop_slippage_bps": format!("{:.2}", stats.stop_exits.avg_slippage_bps),
+            "worst_stop_slippage_bps": format!("{:.2}", stats.stop_exits.worst_slippage_bps),
+            "ab_test": (!s.strategies.is_empty()).then(|| {
+                s.strategies
+                    .iter()
+                    .map(|(id, r)| {
+                        (
+                            id.clone(),
+                            serde_json::json!({
+                                "mode": r.mode,
+                                "signals": r.signals,
+                                "closed_trades": r.closed_trades,
+                                "win_rate_pct": format!("{:.1}%", r.win_rate_pct()),
+                                "realized_pnl": format!("${:.4}", r.realized_pnl),
+                                "open_positions": r.open_positions.len(),
+                            }),
+                        )
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+            }),
         });
-        std::fs::write(&stats_path, serde_json::to_vec_pretty(&stats_output)?)?;
+        write_atomic(&stats_path, &serde_json::to_vec_pretty(&stats_output)?)?;
 
         Ok(())
     }
 }
+
+/// Replace `path` through a temporary file, so readers (and a flush racing
+/// another one) never see a half-written file.
+fn write_atomic(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4().simple()));
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })
+}
@@ -2,7 +2,48 @@
 
 #[cfg(test)]
 mod reporting_tests {
+    use crate::config::AppConfig;
+    use crate::data::store::{MarketStore, Quote};
+    use crate::events::{
+        AccountEvent, AnalysisSignal, BalanceChangeEvent, EntrySkippedEvent, Event,
+        ExecutionReport, MarketEvent, OrderRejectedEvent, OrderRequest, SymbolCooldownEvent,
+        TradeOrigin,
+    };
+    use crate::money::dec;
     use crate::services::reporting::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+
+    fn exec(side: &str, status: &str, price: Decimal, qty: Decimal) -> Event {
+        exec_for("BTC/USD", side, status, price, qty)
+    }
+
+    fn exec_for(symbol: &str, side: &str, status: &str, price: Decimal, qty: Decimal) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: "o1".to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(price),
+            qty: Some(qty),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn reporter(name: &str) -> TradeReporter {
+        let dir = std::env::temp_dir().join(format!(
+            "autohedge_reporting_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        TradeReporter::new(dir.join("trades.jsonl"))
+    }
 
     // ============= PerformanceSummary Tests =============
 
@@ -15,8 +56,8 @@ mod reporting_tests {
         assert_eq!(summary.sells, 0);
         assert_eq!(summary.filled, 0);
         assert_eq!(summary.rejected, 0);
-        assert_eq!(summary.total_notional, 0.0);
-        assert_eq!(summary.total_realized_pnl, 0.0);
+        assert_eq!(summary.total_notional, Decimal::ZERO);
+        assert_eq!(summary.total_realized_pnl, Decimal::ZERO);
         assert_eq!(summary.winning_trades, 0);
         assert_eq!(summary.losing_trades, 0);
     }
@@ -29,12 +70,12 @@ mod reporting_tests {
         summary.sells = 40;
         summary.filled = 95;
         summary.rejected = 5;
-        summary.total_notional = 50000.0;
+        summary.total_notional = dec(50000.0);
         summary.winning_trades = 30;
         summary.losing_trades = 10;
-        summary.total_profit = 500.0;
-        summary.total_loss = 200.0;
-        summary.total_realized_pnl = 300.0;
+        summary.total_profit = dec(500.0);
+        summary.total_loss = dec(200.0);
+        summary.total_realized_pnl = dec(300.0);
 
         assert_eq!(summary.total_orders, 100);
         assert_eq!(summary.winning_trades + summary.losing_trades, 40);
@@ -57,9 +98,9 @@ mod reporting_tests {
         let mut summary = PerformanceSummary::default();
         summary.winning_trades = 7;
         summary.losing_trades = 3;
-        summary.total_profit = 700.0;
-        summary.total_loss = 300.0;
-        summary.total_realized_pnl = 400.0;
+        summary.total_profit = dec(700.0);
+        summary.total_loss = dec(300.0);
+        summary.total_realized_pnl = dec(400.0);
 
         let stats = summary.compute_stats();
 
@@ -74,9 +115,9 @@ mod reporting_tests {
         let mut summary = PerformanceSummary::default();
         summary.winning_trades = 10;
         summary.losing_trades = 0;
-        summary.total_profit = 1000.0;
-        summary.total_loss = 0.0;
-        summary.total_realized_pnl = 1000.0;
+        summary.total_profit = dec(1000.0);
+        summary.total_loss = Decimal::ZERO;
+        summary.total_realized_pnl = dec(1000.0);
 
         let stats = summary.compute_stats();
 
@@ -89,9 +130,9 @@ mod reporting_tests {
         let mut summary = PerformanceSummary::default();
         summary.winning_trades = 0;
         summary.losing_trades = 10;
-        summary.total_profit = 0.0;
-        summary.total_loss = 500.0;
-        summary.total_realized_pnl = -500.0;
+        summary.total_profit = Decimal::ZERO;
+        summary.total_loss = dec(500.0);
+        summary.total_realized_pnl = dec(-500.0);
 
         let stats = summary.compute_stats();
 
@@ -124,14 +165,16 @@ mod reporting_tests {
             symbol: "BTC/USD".to_string(),
             buy_time: "2025-01-01T00:00:00Z".to_string(),
             sell_time: "2025-01-01T01:00:00Z".to_string(),
-            buy_price: 50000.0,
-            sell_price: 51000.0,
-            qty: 0.1,
-            pnl: 100.0, // (51000 - 50000) * 0.1
+            buy_price: dec(50000.0),
+            sell_price: dec(51000.0),
+            qty: dec(0.1),
+            pnl: dec(100.0), // (51000 - 50000) * 0.1
             pnl_percent: 2.0,
+            origin: None,
+            ..Default::default()
         };
 
-        assert_eq!(trade.pnl, 100.0);
+        assert_eq!(trade.pnl, dec(100.0));
         assert_eq!(trade.pnl_percent, 2.0);
     }
 
@@ -141,14 +184,16 @@ mod reporting_tests {
             symbol: "ETH/USD".to_string(),
             buy_time: "2025-01-01T00:00:00Z".to_string(),
             sell_time: "2025-01-01T01:00:00Z".to_string(),
-            buy_price: 3000.0,
-            sell_price: 2900.0,
-            qty: 1.0,
-            pnl: -100.0,
+            buy_price: dec(3000.0),
+            sell_price: dec(2900.0),
+            qty: dec(1.0),
+            pnl: dec(-100.0),
             pnl_percent: -3.33,
+            origin: None,
+            ..Default::default()
         };
 
-        assert!(trade.pnl < 0.0);
+        assert!(trade.pnl < Decimal::ZERO);
         assert!(trade.pnl_percent < 0.0);
     }
 
@@ -159,13 +204,15 @@ mod reporting_tests {
         let pos = OpenPosition {
             symbol: "SOL/USD".to_string(),
             buy_time: "2025-01-01T00:00:00Z".to_string(),
-            buy_price: 100.0,
-            qty: 10.0,
+            buy_price: dec(100.0),
+            qty: dec(10.0),
+            mark_price: None,
+            unrealized_pnl: Decimal::ZERO,
         };
 
         assert_eq!(pos.symbol, "SOL/USD");
-        assert_eq!(pos.buy_price, 100.0);
-        assert_eq!(pos.qty, 10.0);
+        assert_eq!(pos.buy_price, dec(100.0));
+        assert_eq!(pos.qty, dec(10.0));
     }
 
     // ============= TradeLogEntry Tests =============
@@ -178,15 +225,15 @@ mod reporting_tests {
             action: "buy".to_string(),
             order_id: "order123".to_string(),
             status: "filled".to_string(),
-            qty: Some(10000.0),
-            price: Some(0.08),
-            notional: Some(800.0),
+            qty: Some(dec(10000.0)),
+            price: Some(dec(0.08)),
+            notional: Some(dec(800.0)),
             notes: Some("HFT entry".to_string()),
         };
 
         assert_eq!(entry.action, "buy");
         assert_eq!(entry.status, "filled");
-        assert_eq!(entry.notional, Some(800.0));
+        assert_eq!(entry.notional, Some(dec(800.0)));
     }
 
     #[test]
@@ -197,9 +244,9 @@ mod reporting_tests {
             action: "sell".to_string(),
             order_id: "order456".to_string(),
             status: "new".to_string(),
-            qty: Some(1000.0),
-            price: Some(0.55),
-            notional: Some(550.0),
+            qty: Some(dec(1000.0)),
+            price: Some(dec(0.55)),
+            notional: Some(dec(550.0)),
             notes: None,
         };
 
@@ -271,11 +318,13 @@ mod reporting_tests {
             symbol: "BTC/USD".to_string(),
             buy_time: "2025-01-01T00:00:00Z".to_string(),
             sell_time: "2025-01-01T01:00:00Z".to_string(),
-            buy_price: 50000.0,
-            sell_price: 51000.0,
-            qty: 0.1,
-            pnl: 100.0,
+            buy_price: dec(50000.0),
+            sell_price: dec(51000.0),
+            qty: dec(0.1),
+            pnl: dec(100.0),
             pnl_percent: 2.0,
+            origin: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&trade).unwrap();
@@ -295,6 +344,9 @@ mod reporting_tests {
             profit_factor: 1.5,
             total_closed_trades: 50,
             open_position_count: 3,
+            execution: ExecutionQuality::default(),
+            stop_exits: StopExitQuality::default(),
+            by_origin: Default::default(),
         };
 
         assert_eq!(stats.runtime_minutes, 120.0);
@@ -323,11 +375,13 @@ mod reporting_tests {
             symbol: "SOL/USD".to_string(),
             buy_time: "2025-01-01T00:00:00Z".to_string(),
             sell_time: "2025-01-01T01:00:00Z".to_string(),
-            buy_price: 100.0,
-            sell_price: 101.0,
-            qty: 1.0,
-            pnl: 1.0,
+            buy_price: dec(100.0),
+            sell_price: dec(101.0),
+            qty: dec(1.0),
+            pnl: dec(1.0),
             pnl_percent: 1.0,
+            origin: None,
+            ..Default::default()
         };
 
         summary
@@ -348,12 +402,728 @@ mod reporting_tests {
             OpenPosition {
                 symbol: "DOT/USD".to_string(),
                 buy_time: "2025-01-01T00:00:00Z".to_string(),
-                buy_price: 5.0,
-                qty: 100.0,
+                buy_price: dec(5.0),
+                qty: dec(100.0),
+                mark_price: None,
+                unrealized_pnl: Decimal::ZERO,
             },
         );
 
         let stats = summary.compute_stats();
         assert_eq!(stats.open_position_count, 1);
     }
+
+    // ============= Streamed Fill Tests =============
+
+    #[test]
+    fn test_acks_booked_as_fills_without_stream() {
+        let reporter = reporter("acks");
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(exec("sell", "accepted", dec(110.0), dec(1.0)));
+
+        let s = reporter.summary();
+        assert_eq!(s.filled, 2);
+        assert_eq!(s.winning_trades, 1);
+        assert_eq!(s.total_realized_pnl, dec(10.0));
+    }
+
+    #[test]
+    fn test_streamed_partial_fills_accumulate() {
+        let reporter = reporter("partials").with_streamed_fills(true);
+
+        // The submission ack is not a fill once fills are streamed
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        assert!(reporter.summary().open_positions.is_empty());
+
+        reporter.on_event(exec("buy", "partial_fill", dec(100.0), dec(0.4)));
+        reporter.on_event(exec("buy", "fill", dec(101.5), dec(0.6)));
+        let open = reporter.summary().open_positions["BTC/USD"].clone();
+        assert_eq!(open.qty, dec(1.0));
+        assert_eq!(open.buy_price, dec(100.9));
+
+        // Partial exit keeps the remainder open
+        reporter.on_event(exec("sell", "partial_fill", dec(110.0), dec(0.5)));
+        let s = reporter.summary();
+        assert_eq!(s.open_positions["BTC/USD"].qty, dec(0.5));
+        assert_eq!(s.total_realized_pnl, dec(4.55));
+
+        reporter.on_event(exec("sell", "fill", dec(110.0), dec(0.5)));
+        let s = reporter.summary();
+        assert!(s.open_positions.is_empty());
+        assert_eq!(s.total_realized_pnl, dec(9.1));
+        assert_eq!(s.history["BTC/USD"].len(), 2);
+    }
+
+    #[test]
+    fn test_cooldowns_counted_per_symbol() {
+        let reporter = reporter("cooldowns");
+        for symbol in ["BTC/USD", "BTC/USD", "ETH/USD"] {
+            reporter.on_event(Event::Cooldown(SymbolCooldownEvent {
+                symbol: symbol.to_string(),
+                stop_losses: 3,
+                window_minutes: 30,
+                until: "2026-03-02T11:00:00Z".to_string(),
+                timestamp: "2026-03-02T10:00:00Z".to_string(),
+            }));
+        }
+
+        let s = reporter.summary();
+        assert_eq!(s.cooldowns["BTC/USD"], 2);
+        assert_eq!(s.cooldowns["ETH/USD"], 1);
+    }
+
+    #[test]
+    fn test_skipped_entries_counted_per_guard() {
+        let reporter = reporter("skipped");
+        for symbol in ["SOL/USD", "ADA/USD"] {
+            reporter.on_event(Event::EntrySkipped(EntrySkippedEvent {
+                symbol: symbol.to_string(),
+                reason: "correlation_guard".to_string(),
+                detail: "2 held symbols correlated >= 0.90".to_string(),
+                timestamp: "2026-03-02T10:00:00Z".to_string(),
+            }));
+        }
+
+        let s = reporter.summary();
+        assert_eq!(s.skipped_entries["correlation_guard"], 2);
+        assert_eq!(s.total_orders, 0);
+    }
+
+    #[test]
+    fn test_rejected_orders_counted_per_rule() {
+        let reporter = reporter("rejected");
+        for reason in ["max_trade_notional", "trading_window", "trading_window"] {
+            reporter.on_event(Event::OrderRejected(OrderRejectedEvent {
+                symbol: "BTC/USD".to_string(),
+                side: "buy".to_string(),
+                reason: reason.to_string(),
+                detail: "blocked".to_string(),
+                correlation_id: None,
+                timestamp: "2026-03-02T10:00:00Z".to_string(),
+            }));
+        }
+
+        let s = reporter.summary();
+        assert_eq!(s.rejected_orders["trading_window"], 2);
+        assert_eq!(s.rejected_orders["max_trade_notional"], 1);
+        assert_eq!(s.total_orders, 0);
+    }
+
+    // ============= Reporting Currency Tests =============
+
+    fn fx_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "binance"
+symbols: ["ETH/EUR", "ETH/BTC"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+fx:
+  reporting_currency: "USD"
+  rates:
+    EUR: 1.1
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pnl_converted_to_reporting_currency() {
+        let config = fx_config();
+        let store = MarketStore::new(10);
+        for (currency, rate) in &config.fx.rates {
+            store.set_fx_rate(currency, *rate);
+        }
+        let reporter = reporter("fx").with_fx(store, &config);
+
+        reporter.on_event(exec_for("ETH/EUR", "buy", "filled", dec(2000.0), dec(1.0)));
+        reporter.on_event(exec_for("ETH/EUR", "sell", "filled", dec(2100.0), dec(1.0)));
+
+        let s = reporter.summary();
+        assert_eq!(s.reporting_currency.as_deref(), Some("USD"));
+        assert_eq!(s.total_realized_pnl, dec(110.0));
+        assert_eq!(s.history["ETH/EUR"][0].sell_price, dec(2100.0));
+        assert_eq!(s.total_notional, dec(4510.0));
+    }
+
+    #[test]
+    fn test_pnl_unconverted_without_rate() {
+        let config = fx_config();
+        let reporter = reporter("fx_missing").with_fx(MarketStore::new(10), &config);
+
+        reporter.on_event(exec_for("ETH/BTC", "buy", "filled", dec(0.05), dec(2.0)));
+        reporter.on_event(exec_for("ETH/BTC", "sell", "filled", dec(0.06), dec(2.0)));
+
+        assert_eq!(reporter.summary().total_realized_pnl, dec(0.02));
+    }
+
+    // ============= Mark-to-Market Tests =============
+
+    fn quote(store: &MarketStore, symbol: &str, bid: f64, ask: f64) {
+        store.update_quote(
+            symbol.to_string(),
+            Quote {
+                symbol: symbol.to_string(),
+                bid_price: bid,
+                ask_price: ask,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                timestamp: "2026-03-02T10:00:00Z".to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_mark_to_market_splits_realized_and_unrealized() {
+        let store = MarketStore::new(10);
+        let reporter = reporter("mtm").with_streamed_fills(true);
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 10, 0, 0).unwrap();
+        reporter.on_event(Event::Account(AccountEvent::BalanceUpdated {
+            cash: Some(dec(1000.0)),
+            buying_power: None,
+            portfolio_value: Some(dec(1000.0)),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        reporter.on_event(exec("buy", "fill", dec(100.0), dec(2.0)));
+        reporter.on_event(exec_for("ETH/USD", "buy", "fill", dec(50.0), dec(1.0)));
+
+        // ETH has no quote yet: it stays unmarked
+        quote(&store, "BTC/USD", 109.0, 111.0);
+        reporter.mark_to_market(&store, 10, at);
+        let s = reporter.summary();
+        assert_eq!(s.open_positions["BTC/USD"].mark_price, Some(dec(110.0)));
+        assert_eq!(s.open_positions["BTC/USD"].unrealized_pnl, dec(20.0));
+        assert_eq!(s.open_positions["ETH/USD"].mark_price, None);
+        assert_eq!(s.total_unrealized_pnl, dec(20.0));
+        assert_eq!(s.equity_curve[0].equity, dec(1020.0));
+        assert_eq!(s.equity_curve[0].ts, "2026-03-02T10:00:00+00:00");
+
+        // Selling half realizes 10 and leaves 10 unrealized on the rest
+        reporter.on_event(exec("sell", "partial_fill", dec(110.0), dec(1.0)));
+        let s = reporter.summary();
+        assert_eq!(s.total_realized_pnl, dec(10.0));
+        assert_eq!(s.total_unrealized_pnl, dec(10.0));
+
+        quote(&store, "BTC/USD", 99.0, 101.0);
+        reporter.mark_to_market(&store, 10, at + chrono::Duration::seconds(30));
+        let last = reporter.summary().equity_curve.back().cloned().unwrap();
+        assert_eq!(last.realized_pnl, dec(10.0));
+        assert_eq!(last.unrealized_pnl, Decimal::ZERO);
+        assert_eq!(last.equity, dec(1010.0));
+    }
+
+    #[test]
+    fn test_equity_now_values_like_the_curve_without_sampling() {
+        let store = MarketStore::new(10);
+        let reporter = reporter("equity_now").with_streamed_fills(true);
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 10, 0, 0).unwrap();
+        // No valuation before the venue reports a portfolio value
+        assert!(reporter.equity_now(&store, at).is_none());
+
+        reporter.on_event(Event::Account(AccountEvent::BalanceUpdated {
+            cash: Some(dec(1000.0)),
+            buying_power: None,
+            portfolio_value: Some(dec(1000.0)),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        reporter.on_event(exec("buy", "fill", dec(100.0), dec(2.0)));
+        quote(&store, "BTC/USD", 104.0, 106.0);
+
+        let now = reporter.equity_now(&store, at).unwrap();
+        assert_eq!(now.equity, dec(1010.0));
+        assert!(reporter.summary().equity_curve.is_empty());
+        reporter.mark_to_market(&store, 10, at);
+        assert_eq!(reporter.summary().equity_curve.back(), Some(&now));
+    }
+
+    #[test]
+    fn test_equity_curve_keeps_newest_samples() {
+        let store = MarketStore::new(10);
+        let reporter = reporter("mtm_cap");
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 10, 0, 0).unwrap();
+        for i in 0..5 {
+            reporter.mark_to_market(&store, 3, at + chrono::Duration::seconds(i));
+        }
+        let curve = reporter.summary().equity_curve;
+        assert_eq!(curve.len(), 3);
+        assert_eq!(curve[0].ts, "2026-03-02T10:00:02+00:00");
+        assert!(curve.iter().all(|e| e.equity == Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_drawdown_sizing_follows_the_equity_curve() {
+        let store = MarketStore::new(10);
+        let config = crate::config::DrawdownSizingConfig {
+            enabled: true,
+            recovery_step: 0.25,
+            ..Default::default()
+        };
+        let reporter = reporter("drawdown")
+            .with_streamed_fills(true)
+            .with_drawdown_sizing(&config);
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 10, 0, 0).unwrap();
+        reporter.on_event(Event::Account(AccountEvent::BalanceUpdated {
+            cash: Some(dec(1000.0)),
+            buying_power: None,
+            portfolio_value: Some(dec(1000.0)),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        reporter.on_event(exec("buy", "fill", dec(100.0), dec(2.0)));
+        let mark = |mid: f64, secs: i64| {
+            quote(&store, "BTC/USD", mid - 1.0, mid + 1.0);
+            reporter.mark_to_market(&store, 100, at + chrono::Duration::seconds(secs));
+            reporter.drawdown_size_factor()
+        };
+
+        assert_eq!(mark(100.0, 0), 1.0);
+        // -60 on 1000 is a 6% drawdown: half size
+        assert_eq!(mark(70.0, 30), 0.5);
+        // -110 is past 10%: entries pause
+        assert_eq!(mark(45.0, 60), 0.0);
+        assert!(reporter.summary().drawdown_pct > 10.0);
+        // Back to the peak: size returns a step per mark
+        assert_eq!(mark(100.0, 90), 0.25);
+        assert_eq!(mark(100.0, 120), 0.5);
+        assert_eq!(mark(100.0, 150), 0.75);
+        assert_eq!(mark(100.0, 180), 1.0);
+    }
+
+    #[test]
+    fn test_balance_audit_explains_the_equity_gap() {
+        let reporter = reporter("balance_audit").with_streamed_fills(true);
+        reporter.on_event(Event::Account(AccountEvent::BalanceUpdated {
+            cash: Some(dec(1000.0)),
+            buying_power: None,
+            portfolio_value: Some(dec(1000.0)),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        reporter.on_event(exec("buy", "fill", dec(100.0), dec(2.0)));
+        reporter.on_event(Event::Account(AccountEvent::BalanceAudited(
+            BalanceChangeEvent {
+                cash_before: dec(1000.0),
+                cash_after: dec(799.8),
+                change: dec(-200.2),
+                fill_cash_flow: dec(-200.0),
+                fill_notional: dec(200.0),
+                fills: 1,
+                fees: dec(0.2),
+                funding: Decimal::ZERO,
+                transfer: Decimal::ZERO,
+                unexplained: Decimal::ZERO,
+                portfolio_value: Some(dec(999.8)),
+                kind: "fills".to_string(),
+                timestamp: "2026-03-02T10:01:00Z".to_string(),
+            },
+        )));
+
+        let audit = reporter.summary().balance_audit;
+        assert_eq!(audit.changes, 1);
+        assert_eq!(audit.fees, dec(0.2));
+        // Venue equity trails computed equity by exactly the fees
+        assert_eq!(audit.equity_gap, Some(dec(-0.2)));
+        assert_eq!(audit.residual_gap, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_audited_transfer_moves_computed_equity() {
+        let reporter = reporter("audited_transfer");
+        reporter.on_event(Event::Account(AccountEvent::BalanceUpdated {
+            cash: Some(dec(1000.0)),
+            buying_power: None,
+            portfolio_value: Some(dec(1000.0)),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        }));
+        reporter.on_event(Event::Account(AccountEvent::BalanceAudited(
+            BalanceChangeEvent {
+                cash_before: dec(1000.0),
+                cash_after: dec(1500.0),
+                change: dec(500.0),
+                transfer: dec(500.0),
+                portfolio_value: Some(dec(1500.0)),
+                kind: "deposit".to_string(),
+                ..Default::default()
+            },
+        )));
+
+        let s = reporter.summary();
+        assert_eq!(s.net_transfers, dec(500.0));
+        assert_eq!(s.balance_audit.transfers, dec(500.0));
+        // The deposit is in computed equity, so nothing is left unexplained
+        assert_eq!(s.balance_audit.equity_gap, Some(Decimal::ZERO));
+    }
+
+    // ============= Trade Closed Tests =============
+
+    #[test]
+    fn test_exit_fills_return_closed_trades_with_thesis() {
+        let reporter = reporter("trade_closed").with_streamed_fills(true);
+        reporter.on_event(tagged_buy("A"));
+        assert!(reporter
+            .on_event(exec("buy", "fill", dec(100.0), dec(2.0)))
+            .is_none());
+
+        // A partial exit keeps the thesis for the rest of the position
+        let first = reporter
+            .on_event(exec("sell", "partial_fill", dec(110.0), dec(1.0)))
+            .unwrap();
+        assert_eq!(first.thesis, "HFT momentum");
+        assert_eq!(first.entry_price, dec(100.0));
+        assert_eq!(first.pnl, dec(10.0));
+        assert_eq!(first.pnl_percent, 10.0);
+
+        let last = reporter
+            .on_event(exec("sell", "fill", dec(95.0), dec(1.0)))
+            .unwrap();
+        assert_eq!(last.thesis, "HFT momentum");
+        assert_eq!(last.pnl, dec(-5.0));
+
+        // The next position starts without a thesis
+        reporter.on_event(exec("buy", "fill", dec(100.0), dec(1.0)));
+        let next = reporter
+            .on_event(exec("sell", "fill", dec(101.0), dec(1.0)))
+            .unwrap();
+        assert!(next.thesis.is_empty());
+    }
+
+    // ============= A/B Test Tests =============
+
+    fn market_quote(bid: f64, ask: f64) -> Event {
+        Event::Market(MarketEvent::Quote {
+            symbol: "BTC/USD".to_string(),
+            bid,
+            ask,
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+        })
+    }
+
+    fn tagged_buy(strategy_id: &str) -> Event {
+        Event::Signal(AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: "buy".to_string(),
+            confidence: 1.0,
+            thesis: "HFT momentum".to_string(),
+            market_context: String::new(),
+            strategy_id: Some(strategy_id.to_string()),
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: Some(dec(101.5)),
+            stop_loss: Some(dec(98.0)),
+            valid_until: None,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_ab_test_compares_live_and_shadow() {
+        let mut config = fx_config();
+        config.ab_test.enabled = true;
+        let reporter = reporter("ab_test").with_ab_test(&config);
+
+        reporter.on_event(market_quote(99.0, 100.0));
+        reporter.on_event(tagged_buy("B"));
+        reporter.on_event(tagged_buy("A"));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        // Shadow take-profit, then the live position's (untagged) exit
+        reporter.on_event(market_quote(101.5, 101.7));
+        reporter.on_event(exec("sell", "new", dec(102.0), dec(1.0)));
+
+        let s = reporter.summary();
+        let live = &s.strategies["A"];
+        assert_eq!(live.mode, "live");
+        assert_eq!(live.signals, 1);
+        assert_eq!(live.closed_trades, 1);
+        assert_eq!(live.realized_pnl, dec(2.0));
+        let shadow = &s.strategies["B"];
+        assert_eq!(shadow.mode, "shadow");
+        assert_eq!(shadow.signals, 1);
+        assert_eq!(shadow.fills, 2);
+        assert_eq!(shadow.realized_pnl, dec(1.5));
+        assert_eq!(shadow.win_rate_pct(), 100.0);
+        assert!(shadow.open_positions.is_empty());
+
+        // Paper fills stay out of the live totals
+        assert_eq!(s.filled, 2);
+        assert_eq!(s.total_realized_pnl, dec(2.0));
+    }
+
+    #[test]
+    fn test_ab_test_off_ignores_tags() {
+        let reporter = reporter("ab_test_off").with_ab_test(&fx_config());
+        reporter.on_event(market_quote(99.0, 100.0));
+        reporter.on_event(tagged_buy("B"));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        assert!(reporter.summary().strategies.is_empty());
+    }
+
+    // ============= Attribution Tests =============
+
+    fn order(action: &str, origin: Option<TradeOrigin>) -> Event {
+        Event::Order(OrderRequest {
+            symbol: "BTC/USD".to_string(),
+            action: action.to_string(),
+            qty: Decimal::ZERO,
+            order_type: "market".to_string(),
+            limit_price: None,
+            stop_loss: None,
+            take_profit: None,
+            strategy_id: None,
+            correlation_id: None,
+            origin,
+            risk_verdict: None,
+            valid_until: None,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_pnl_attributed_to_entry_origin() {
+        let reporter = reporter("attribution");
+        let llm = TradeOrigin {
+            mode: "llm".to_string(),
+            director_decision_id: Some("d1".to_string()),
+            quant_decision_id: Some("q1".to_string()),
+        };
+        let exit = || order("sell", Some(TradeOrigin::new(TradeOrigin::MONITOR_EXIT)));
+
+        reporter.on_event(order("buy", Some(llm.clone())));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(exit());
+        reporter.on_event(exec("sell", "new", dec(104.0), dec(1.0)));
+
+        reporter.on_event(order("buy", Some(TradeOrigin::new("hft"))));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(exit());
+        reporter.on_event(exec("sell", "new", dec(99.0), dec(1.0)));
+
+        // Untagged buy (e.g. an adopted position)
+        reporter.on_event(order("buy", None));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(exec("sell", "new", dec(101.0), dec(1.0)));
+
+        let s = reporter.summary();
+        let llm_result = &s.origins["llm"];
+        assert_eq!((llm_result.orders, llm_result.closed_trades), (1, 1));
+        assert_eq!(llm_result.realized_pnl, dec(4.0));
+        assert_eq!(llm_result.win_rate_pct, 100.0);
+        let hft = &s.origins["hft"];
+        assert_eq!((hft.winning_trades, hft.losing_trades), (0, 1));
+        assert_eq!(hft.realized_pnl, dec(-1.0));
+        // Exits are counted as orders, P&L stays with the entry
+        assert_eq!(s.origins["monitor_exit"].orders, 2);
+        assert_eq!(s.origins["monitor_exit"].closed_trades, 0);
+        assert_eq!(s.origins[UNTAGGED_ORIGIN].closed_trades, 1);
+
+        // Closed trades keep the decisions behind them
+        let history = &s.history["BTC/USD"];
+        assert_eq!(history[0].origin.as_ref(), Some(&llm));
+        assert!(history[2].origin.is_none());
+        assert_eq!(s.compute_stats().by_origin, s.origins);
+    }
+
+    #[test]
+    fn test_stop_exit_slippage_vs_stop() {
+        let reporter = reporter("stop_exits");
+        let stop_exit = Event::Order(OrderRequest {
+            stop_loss: Some(dec(100.0)),
+            ..match order("sell", Some(TradeOrigin::new(TradeOrigin::MONITOR_EXIT))) {
+                Event::Order(req) => req,
+                _ => unreachable!(),
+            }
+        });
+
+        reporter.on_event(order("buy", None));
+        reporter.on_event(exec("buy", "new", dec(105.0), dec(2.0)));
+        reporter.on_event(stop_exit);
+        // Limit leg 30 bps under the stop, the rest at market 100 bps under
+        reporter.on_event(exec("sell", "partial_fill", dec(99.7), dec(1.0)));
+        reporter.on_event(exec("sell", "new", dec(99.0), dec(1.0)));
+        // A take-profit exit carries no stop
+        reporter.on_event(order("buy", None));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(order(
+            "sell",
+            Some(TradeOrigin::new(TradeOrigin::MONITOR_EXIT)),
+        ));
+        reporter.on_event(exec("sell", "new", dec(102.0), dec(1.0)));
+
+        let s = reporter.summary();
+        assert_eq!(s.exit_slippage.len(), 2);
+        assert!((s.exit_slippage[0].slippage_bps - 30.0).abs() < 1e-6);
+        assert!((s.exit_slippage[1].slippage_bps - 100.0).abs() < 1e-6);
+        let quality = s.compute_stats().stop_exits;
+        assert_eq!(quality.exits, 2);
+        assert!((quality.avg_slippage_bps - 65.0).abs() < 1e-6);
+        assert!((quality.worst_slippage_bps - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_closed_trade_keeps_entry_tags() {
+        let reporter = reporter("tags");
+        let tags = HashMap::from([("experiment".to_string(), "v2".to_string())]);
+        let tagged = match order("buy", Some(TradeOrigin::new("hft"))) {
+            Event::Order(req) => Event::Order(OrderRequest {
+                metadata: tags.clone(),
+                ..req
+            }),
+            _ => unreachable!(),
+        };
+
+        reporter.on_event(tagged);
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(2.0)));
+        reporter.on_event(exec("sell", "partial_fill", dec(101.0), dec(1.0)));
+        reporter.on_event(exec("sell", "new", dec(102.0), dec(1.0)));
+        // The next untagged entry does not inherit them
+        reporter.on_event(order("buy", None));
+        reporter.on_event(exec("buy", "new", dec(100.0), dec(1.0)));
+        reporter.on_event(exec("sell", "new", dec(101.0), dec(1.0)));
+
+        let history = &reporter.summary().history["BTC/USD"];
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].metadata, tags);
+        assert_eq!(history[1].metadata, tags);
+        assert!(history[2].metadata.is_empty());
+    }
+
+    // ============= Execution Quality Tests =============
+
+    fn ack(order_id: &str, side: &str, signal: Decimal, limit: Option<Decimal>) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: order_id.to_string(),
+            status: "new".to_string(),
+            side: side.to_string(),
+            price: limit.or(Some(signal)),
+            qty: Some(dec(1.0)),
+            signal_price: Some(signal),
+            limit_price: limit,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn fill(order_id: &str, side: &str, status: &str, price: Decimal, qty: Decimal) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: order_id.to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(price),
+            qty: Some(qty),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: None,
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    fn sample(slippage_bps: f64, time_to_fill_ms: i64) -> FillQuality {
+        FillQuality {
+            ts: "2026-01-01T00:00:00Z".to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: "buy".to_string(),
+            order_id: "o".to_string(),
+            signal_price: dec(100.0),
+            limit_price: None,
+            fill_price: dec(100.0 * (1.0 + slippage_bps / 10_000.0)),
+            qty: dec(1.0),
+            slippage_bps,
+            time_to_fill_ms,
+        }
+    }
+
+    #[test]
+    fn test_fill_quality_matches_streamed_fills_to_acks() {
+        let reporter = reporter("quality").with_streamed_fills(true);
+
+        reporter.on_event(ack("b1", "buy", dec(100.0), Some(dec(100.15))));
+        reporter.on_event(fill("b1", "buy", "partial_fill", dec(100.10), dec(0.5)));
+        reporter.on_event(fill("b1", "buy", "fill", dec(100.20), dec(0.5)));
+        // Sold below the signal mid: positive slippage for a sell
+        reporter.on_event(ack("s1", "sell", dec(110.0), None));
+        reporter.on_event(fill("s1", "sell", "fill", dec(109.89), dec(1.0)));
+        // Fill without a known submission is not measured
+        reporter.on_event(fill("tp-leg", "sell", "fill", dec(111.0), dec(1.0)));
+
+        let samples = reporter.summary().fill_quality;
+        assert_eq!(samples.len(), 3);
+        assert!((samples[0].slippage_bps - 10.0).abs() < 1e-6);
+        assert!((samples[0].limit_slippage_bps().unwrap() + 5.0).abs() < 0.01);
+        assert!((samples[1].slippage_bps - 20.0).abs() < 1e-6);
+        assert!((samples[2].slippage_bps - 10.0).abs() < 1e-6);
+        assert_eq!(samples[2].limit_slippage_bps(), None);
+        assert!(samples.iter().all(|s| s.time_to_fill_ms >= 0));
+
+        // The last fill closed the order; a repeat is not matched again
+        reporter.on_event(fill("b1", "buy", "fill", dec(100.20), dec(0.5)));
+        assert_eq!(reporter.summary().fill_quality.len(), 3);
+    }
+
+    #[test]
+    fn test_fill_quality_skipped_without_stream() {
+        let reporter = reporter("quality_acks");
+        reporter.on_event(ack("b1", "buy", dec(100.0), Some(dec(100.15))));
+        reporter.on_event(fill("b1", "buy", "fill", dec(100.20), dec(1.0)));
+        assert!(reporter.summary().fill_quality.is_empty());
+    }
+
+    #[test]
+    fn test_execution_quality_percentiles() {
+        let fills: Vec<FillQuality> = (1..=10).map(|i| sample(i as f64, i * 100)).collect();
+        let q = ExecutionQuality::from_fills(&fills);
+
+        assert_eq!(q.fills, 10);
+        assert!((q.avg_slippage_bps - 5.5).abs() < 1e-9);
+        assert_eq!(q.slippage_bps_p50, 5.0);
+        assert_eq!(q.slippage_bps_p90, 9.0);
+        assert_eq!(q.slippage_bps_p99, 10.0);
+        assert_eq!(q.time_to_fill_ms_p50, 500.0);
+        assert_eq!(q.time_to_fill_ms_p90, 900.0);
+        assert_eq!(q.avg_limit_slippage_bps, None);
+    }
+
+    #[test]
+    fn test_execution_quality_in_compute_stats() {
+        let mut summary = PerformanceSummary::default();
+        assert_eq!(summary.compute_stats().execution.fills, 0);
+
+        summary.fill_quality.push_back(sample(4.0, 250));
+        let stats = summary.compute_stats();
+        assert_eq!(stats.execution.fills, 1);
+        assert_eq!(stats.execution.slippage_bps_p99, 4.0);
+        assert_eq!(stats.execution.time_to_fill_ms_p50, 250.0);
+    }
 }
@@ -1,17 +1,39 @@
 use crate::agents::{risk::RiskAgent, Agent};
 use crate::bus::EventBus;
-use crate::config::AppConfig;
-use crate::events::{AnalysisSignal, Event, OrderRequest};
+use crate::config::{AppConfig, ChatterModule};
+use crate::data::correlation::CorrelationTracker;
+use crate::data::store::MarketStore;
+use crate::events::{
+    AccountEvent, AnalysisSignal, ControlCommand, DegradationLevel, Event, OrderRequest,
+    TradeOrigin,
+};
 use crate::exchange::traits::TradingApi;
+use crate::exchange::types::AccountSummary;
 use crate::llm::LLMQueue;
+use crate::logging::trade_span;
+use crate::money;
+use crate::services::policy::PolicyEngine;
+use crate::services::position_monitor::PositionTracker;
+use crate::services::risk_guard::{is_stop_loss_exit, RiskGuard};
+use crate::services::signal_gate::SignalGate;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
 use std::sync::Arc;
-use tracing::{error, info};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+use tracing::{error, info, Instrument};
 
 pub struct RiskEngine {
     event_bus: EventBus,
     exchange: Arc<dyn TradingApi>,
     llm: LLMQueue,
     config: AppConfig,
+    policy: PolicyEngine,
+    guard: RiskGuard,
+    /// Drops repeated signals and overlapping entry decisions
+    gate: SignalGate,
+    /// Rolling correlations and the book they are checked against
+    correlation: Option<(CorrelationTracker, PositionTracker)>,
 }
 
 impl RiskEngine {
@@ -21,96 +43,374 @@ impl RiskEngine {
         llm: LLMQueue,
         config: AppConfig,
     ) -> Self {
+        let policy = PolicyEngine::new(&config);
+        let guard = RiskGuard::new(&config);
         Self {
             event_bus,
             exchange,
             llm,
             config,
+            policy,
+            guard,
+            gate: SignalGate::new(),
+            correlation: None,
         }
     }
 
+    /// Check entries against stored bar volumes (`policy.max_volume_pct`).
+    pub fn with_market_store(mut self, store: MarketStore) -> Self {
+        self.policy = self.policy.with_market_store(store);
+        self
+    }
+
+    /// The policy engine, shared with execution for the sized-order limits
+    /// (turnover and wash-trade state stay in one place).
+    pub fn policy(&self) -> PolicyEngine {
+        self.policy.clone()
+    }
+
+    /// Skip buys that would add to a cluster of correlated holdings
+    /// (`correlation_guard`).
+    pub fn with_correlation_guard(
+        mut self,
+        correlation: CorrelationTracker,
+        positions: PositionTracker,
+    ) -> Self {
+        self.correlation = Some((correlation, positions));
+        self
+    }
+
     pub async fn start(&self) {
-        let mut rx = self.event_bus.subscribe();
+        let mut rx = self.event_bus.subscribe("risk");
         let exchange_clone = self.exchange.clone();
         let llm_clone = self.llm.clone();
         let bus_clone = self.event_bus.clone();
-        let config_clone = self.config.clone();
+        let mut config_clone = self.config.clone();
+        let policy = self.policy.clone();
+        let guard = self.guard.clone();
+        let gate = self.gate.clone();
+        let correlation = self.correlation.clone();
+
+        // Latest balance pushed via Event::Account; avoids a REST round-trip per signal.
+        let latest_account: Arc<RwLock<Option<AccountSummary>>> = Arc::new(RwLock::new(None));
+
+        let shutdown = self.event_bus.shutdown().clone();
 
         tokio::spawn(async move {
             info!("🛡️ Risk Engine Started");
-            while let Ok(event) = rx.recv().await {
-                if let Event::Signal(signal) = event {
-                    let exchange = exchange_clone.clone();
-                    let llm = llm_clone.clone();
-                    let bus = bus_clone.clone();
-                    let config = config_clone.clone();
-
-                    tokio::spawn(async move {
-                        Self::assess_risk(signal, exchange, llm, bus, config).await;
-                    });
+            // Halted/delisted symbols (Event::SymbolStatus): exits only
+            let mut exit_only: HashSet<String> = HashSet::new();
+            // Degradation ladder (Event::Health): entries stop at exit-only
+            let mut health_level = DegradationLevel::Full;
+            // Symbols paused by the operator (/ws control channel)
+            let mut paused: HashSet<String> = HashSet::new();
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                match event {
+                    Event::Signal(signal) => {
+                        // A/B shadow signals are paper-traded by the reporter only
+                        let ab = &config_clone.ab_test;
+                        if ab.enabled
+                            && signal.strategy_id.as_deref() == Some(ab.shadow_id.as_str())
+                        {
+                            continue;
+                        }
+                        let exchange = exchange_clone.clone();
+                        let llm = llm_clone.clone();
+                        let bus = bus_clone.clone();
+                        let mut config = config_clone.clone();
+                        bus.diagnostics().apply(&signal.symbol, &mut config);
+                        let latest_account = latest_account.clone();
+
+                        if signal.signal == "buy" && exit_only.contains(&signal.symbol) {
+                            if !config.chatter_for(ChatterModule::Execution).is_low() {
+                                info!(
+                                    "🚫 [RISK] Ignoring buy for {} (exit-only: halted/delisted)",
+                                    signal.symbol
+                                );
+                            }
+                            continue;
+                        }
+
+                        if signal.signal == "buy" && paused.contains(&signal.symbol) {
+                            if !config.chatter_for(ChatterModule::Execution).is_low() {
+                                info!(
+                                    "⏸️ [RISK] Ignoring buy for {} (paused by operator)",
+                                    signal.symbol
+                                );
+                            }
+                            continue;
+                        }
+
+                        if is_stop_loss_exit(&signal) {
+                            if let Some(cooldown) =
+                                guard.record_stop_loss(&signal.symbol, chrono::Utc::now())
+                            {
+                                bus.publish(Event::Cooldown(cooldown)).ok();
+                            }
+                        }
+
+                        if signal.signal == "buy" {
+                            if let Some(until) =
+                                guard.cooldown_until(&signal.symbol, chrono::Utc::now())
+                            {
+                                if !config.chatter_for(ChatterModule::Execution).is_low() {
+                                    info!(
+                                        "🧊 [RISK] Ignoring buy for {} (loss-streak cooldown until {})",
+                                        signal.symbol,
+                                        until.to_rfc3339()
+                                    );
+                                }
+                                continue;
+                            }
+                        }
+
+                        if signal.signal == "buy" {
+                            if let Some((tracker, positions)) = &correlation {
+                                if let Some(skip) = tracker.check_entry(
+                                    &signal.symbol,
+                                    &positions.held_symbols(),
+                                    chrono::Utc::now(),
+                                ) {
+                                    if !config.chatter_for(ChatterModule::Execution).is_low() {
+                                        info!(
+                                            "🔗 [RISK] Ignoring buy for {} ({})",
+                                            signal.symbol, skip.detail
+                                        );
+                                    }
+                                    bus.publish(Event::EntrySkipped(skip)).ok();
+                                    continue;
+                                }
+                            }
+                        }
+
+                        if signal.signal == "buy" && !health_level.allows_entries() {
+                            if !config.chatter_for(ChatterModule::Execution).is_low() {
+                                info!(
+                                    "🚫 [RISK] Ignoring buy for {} (health: {})",
+                                    signal.symbol,
+                                    health_level.as_str()
+                                );
+                            }
+                            continue;
+                        }
+
+                        // Compliance policies are enforced before any sizing or LLM work
+                        let decision = policy.check(&signal.symbol, &signal.signal);
+                        if bus.diagnostics().trace(&signal.symbol) {
+                            info!(
+                                "🔬 [DIAG] Policy {} {}: allowed={} rule={} ({})",
+                                signal.signal,
+                                signal.symbol,
+                                decision.allowed,
+                                decision.rule,
+                                decision.reason
+                            );
+                        }
+                        if !decision.allowed {
+                            bus.publish(Event::OrderRejected(
+                                decision.rejection(signal.correlation_id.clone()),
+                            ))
+                            .ok();
+                            continue;
+                        }
+
+                        if let Err(drop) =
+                            gate.admit(&config.signal_gate, &signal, chrono::Utc::now())
+                        {
+                            if config.chatter_for(ChatterModule::Execution).is_verbose() {
+                                info!(
+                                    "🔁 [RISK] Ignoring {} for {} ({})",
+                                    signal.signal, signal.symbol, drop
+                                );
+                            }
+                            continue;
+                        }
+
+                        let gate = gate.clone();
+                        let span = trade_span(&signal.symbol, signal.correlation_id.as_deref());
+                        tokio::spawn(
+                            async move {
+                                let symbol = signal.symbol.clone();
+                                let ordered = Self::assess_risk(
+                                    signal,
+                                    exchange,
+                                    llm,
+                                    bus,
+                                    config,
+                                    latest_account,
+                                )
+                                .await;
+                                if !ordered {
+                                    gate.settle(&symbol);
+                                }
+                            }
+                            .instrument(span),
+                        );
+                    }
+                    Event::Execution(report) => {
+                        if report.side.eq_ignore_ascii_case("buy") {
+                            gate.settle(&report.symbol);
+                        }
+                        policy.record_execution(&report, chrono::Utc::now());
+                    }
+                    Event::SymbolStatus(status) if config_clone.halt_detection.enabled => {
+                        if status.exit_only {
+                            exit_only.insert(status.symbol);
+                        } else {
+                            exit_only.remove(&status.symbol);
+                        }
+                    }
+                    Event::Health(health) => {
+                        health_level = health.level;
+                    }
+                    Event::Config(update) => {
+                        config_clone = (*update.config).clone();
+                    }
+                    Event::Control(ControlCommand::PauseSymbol { symbol }) => {
+                        paused.insert(symbol);
+                    }
+                    Event::Control(ControlCommand::ResumeSymbol { symbol }) => {
+                        paused.remove(&symbol);
+                    }
+                    Event::Account(AccountEvent::BalanceUpdated {
+                        cash,
+                        buying_power,
+                        portfolio_value,
+                        ..
+                    }) => {
+                        *latest_account.write().await = Some(AccountSummary {
+                            buying_power,
+                            cash,
+                            portfolio_value,
+                        });
+                    }
+                    _ => {}
                 }
             }
         });
     }
 
+    /// Turn an admitted signal into an order request; false if none was published.
     async fn assess_risk(
         signal: AnalysisSignal,
         exchange: Arc<dyn TradingApi>,
         llm: LLMQueue,
         bus: EventBus,
-        _config: AppConfig,
-    ) {
+        config: AppConfig,
+        latest_account: Arc<RwLock<Option<AccountSummary>>>,
+    ) -> bool {
         // HFT Fast Path
         if signal.thesis.starts_with("HFT") {
-            // Parse TP/SL from market_context "tp=..., sl=..."
-            let mut stop_loss = None;
-            let mut take_profit = None;
-
-            for part in signal.market_context.split(',') {
-                let part = part.trim();
-                if part.starts_with("tp=") {
-                    if let Ok(val) = part["tp=".len()..].parse::<f64>() {
-                        take_profit = Some(val);
-                    }
-                } else if part.starts_with("sl=") {
-                    if let Ok(val) = part["sl=".len()..].parse::<f64>() {
-                        stop_loss = Some(val);
-                    }
-                }
-            }
-
             info!(
                 "🛡️ [RISK] HFT Fast-Approve: {} (SL: {:?}, TP: {:?})",
-                signal.symbol, stop_loss, take_profit
+                signal.symbol, signal.stop_loss, signal.take_profit
             );
 
             let order_req = OrderRequest {
                 symbol: signal.symbol.clone(),
                 action: signal.signal.clone(),
-                qty: 0.0,                          // Execution Agent will determine quantity
+                qty: Decimal::ZERO, // Execution Agent will determine quantity
                 order_type: "hft_buy".to_string(), // Signal for fast execution
+                limit_price: signal.entry_limit,
+                stop_loss: signal.stop_loss,
+                take_profit: signal.take_profit,
+                strategy_id: signal.strategy_id.clone(),
+                correlation_id: signal.correlation_id.clone(),
+                origin: signal.origin.clone(),
+                risk_verdict: Some("HFT fast-approve".to_string()),
+                valid_until: signal.valid_until.clone(),
+                metadata: signal.order_metadata(&config.order_tags),
+            };
+
+            bus.publish(Event::Order(order_req)).ok();
+            return true;
+        }
+
+        // Monitor exits (stop-loss, max hold, forced closes) leave without an
+        // LLM verdict; an unavailable or unwilling model must not hold a position
+        let monitor_exit = signal
+            .origin
+            .as_ref()
+            .is_some_and(|o| o.mode == TradeOrigin::MONITOR_EXIT);
+        if monitor_exit && signal.signal == "sell" {
+            info!(
+                "🛡️ [RISK] Exit Fast-Approve: {} ({})",
+                signal.symbol, signal.market_context
+            );
+            let order_req = OrderRequest {
+                symbol: signal.symbol.clone(),
+                action: signal.signal.clone(),
+                qty: Decimal::ZERO, // Execution sells the tracked position
+                order_type: "market".to_string(),
                 limit_price: None,
-                stop_loss,
-                take_profit,
+                stop_loss: signal.stop_loss, // Stop price of a stop-loss exit
+                take_profit: None,
+                strategy_id: signal.strategy_id.clone(),
+                correlation_id: signal.correlation_id.clone(),
+                origin: signal.origin.clone(),
+                risk_verdict: Some("monitor exit fast-approve".to_string()),
+                valid_until: None,
+                metadata: signal.order_metadata(&config.order_tags),
             };
+            bus.publish(Event::Order(order_req)).ok();
+            return true;
+        }
 
+        // Rule-based entries while the LLM is down (`llm_fallback`): the Risk
+        // agent is as unavailable as the Director, so the strategy's TP/SL stand
+        let fallback = signal
+            .origin
+            .as_ref()
+            .is_some_and(|o| o.mode == TradeOrigin::FALLBACK);
+        if fallback && signal.signal == "buy" {
+            info!(
+                "🛡️ [RISK] Fallback Fast-Approve: {} (SL: {:?}, TP: {:?})",
+                signal.symbol, signal.stop_loss, signal.take_profit
+            );
+            let order_req = OrderRequest {
+                symbol: signal.symbol.clone(),
+                action: signal.signal.clone(),
+                qty: Decimal::ZERO, // Sized to min_order_amount by execution
+                order_type: "market".to_string(),
+                limit_price: signal.entry_limit,
+                stop_loss: signal.stop_loss,
+                take_profit: signal.take_profit,
+                strategy_id: signal.strategy_id.clone(),
+                correlation_id: signal.correlation_id.clone(),
+                origin: signal.origin.clone(),
+                risk_verdict: Some(format!("fallback fast-approve: {}", signal.thesis)),
+                valid_until: signal.valid_until.clone(),
+                metadata: signal.order_metadata(&config.order_tags),
+            };
             bus.publish(Event::Order(order_req)).ok();
-            return;
+            return true;
         }
 
-        // Fetch Account
-        let account = match exchange.get_account().await {
-            Ok(acc) => acc,
-            Err(e) => {
-                error!(
-                    "❌ Risk: Failed to fetch account for {}: {}",
-                    signal.symbol, e
-                );
-                return;
-            }
+        // Use the pushed account snapshot when we have one, otherwise fetch
+        let cached = latest_account.read().await.clone();
+        let account = match cached {
+            Some(acc) => acc,
+            None => match exchange.get_account().await {
+                Ok(acc) => acc,
+                Err(e) => {
+                    error!(
+                        "❌ Risk: Failed to fetch account for {}: {}",
+                        signal.symbol, e
+                    );
+                    return false;
+                }
+            },
         };
 
-        let risk_agent = RiskAgent;
+        let risk_agent = RiskAgent::new(&config, &signal.symbol);
         let risk_input = format!(
             "Asset: {}\nAccount Cash: {:?}\nPortfolio Value: {:?}\nThesis: {}\nQuant: N/A", // Simplifying input for now, Strategy signal could include Quant output
             signal.symbol, account.cash, account.portfolio_value, signal.thesis
@@ -120,7 +420,7 @@ impl RiskEngine {
             Ok(res) => res,
             Err(e) => {
                 error!("❌ Risk Agent Failed: {}", e);
-                return;
+                return false;
             }
         };
 
@@ -131,11 +431,13 @@ impl RiskEngine {
                 "🛡️ [RISK] Rejected trade for {}: {}",
                 signal.symbol, risk_response
             );
-            return;
+            return false;
         }
 
-        // Parse risk response to extract stop_loss and take_profit
+        // The Risk agent's stop_loss / take_profit, else the strategy's
         let (stop_loss, take_profit) = Self::parse_risk_parameters(&risk_response);
+        let stop_loss = stop_loss.or(signal.stop_loss);
+        let take_profit = take_profit.or(signal.take_profit);
 
         info!(
             "🛡️ [RISK] Approved: {} (SL: {:?}, TP: {:?})",
@@ -146,17 +448,24 @@ impl RiskEngine {
         let order_req = OrderRequest {
             symbol: signal.symbol.clone(),
             action: signal.signal.clone(), // "buy" or "sell"
-            qty: 0.0,                      // Execution Agent will determine quantity
+            qty: Decimal::ZERO,            // Execution Agent will determine quantity
             order_type: "market".to_string(),
-            limit_price: None,
+            limit_price: signal.entry_limit,
             stop_loss,
             take_profit,
+            strategy_id: signal.strategy_id.clone(),
+            correlation_id: signal.correlation_id.clone(),
+            origin: signal.origin.clone(),
+            risk_verdict: Some(risk_response),
+            valid_until: signal.valid_until.clone(),
+            metadata: signal.order_metadata(&config.order_tags),
         };
 
         bus.publish(Event::Order(order_req)).ok();
+        true
     }
 
-    fn parse_risk_parameters(risk_response: &str) -> (Option<f64>, Option<f64>) {
+    fn parse_risk_parameters(risk_response: &str) -> (Option<Decimal>, Option<Decimal>) {
         // Try to extract JSON
         let json_str = if let Some(start) = risk_response.find('{') {
             if let Some(end) = risk_response.rfind('}') {
@@ -170,8 +479,8 @@ impl RiskEngine {
 
         // Attempt to parse JSON
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_str) {
-            let stop_loss = json.get("stop_loss").and_then(|v| v.as_f64());
-            let take_profit = json.get("take_profit").and_then(|v| v.as_f64());
+            let stop_loss = json.get("stop_loss").and_then(money::from_json);
+            let take_profit = json.get("take_profit").and_then(money::from_json);
 
             return (stop_loss, take_profit);
         }
@@ -0,0 +1,109 @@
+//! Per-symbol loss-streak cooldown.
+//!
+//! Stop-loss exits published by the position monitor are counted per symbol.
+//! Once a symbol takes `max_stop_losses` of them within `window_minutes`, the
+//! risk engine ignores its buy signals for `cooldown_minutes`, so HFT entries
+//! stop grinding it down during chop. Each cooldown is logged and published as
+//! `Event::Cooldown`. Exits are never blocked.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::warn;
+
+use crate::config::{AppConfig, LossStreakConfig};
+use crate::events::{AnalysisSignal, SymbolCooldownEvent};
+
+/// Whether `signal` is a position monitor exit that fired on the stop-loss
+/// ("stop_loss", or "stop_loss_limit_cancel" with a resting TP). Forced
+/// closes (operator, max hold, halts) carry "forced_close" and do not count.
+pub fn is_stop_loss_exit(signal: &AnalysisSignal) -> bool {
+    signal.signal.eq_ignore_ascii_case("sell")
+        && signal
+            .market_context
+            .strip_prefix("Reason: ")
+            .is_some_and(|reason| reason.starts_with("stop_loss"))
+}
+
+#[derive(Debug, Default)]
+struct SymbolStreak {
+    /// Stop-loss exits inside the window, oldest first
+    stop_losses: VecDeque<DateTime<Utc>>,
+    cooldown_until: Option<DateTime<Utc>>,
+}
+
+/// Shared loss-streak state, consulted by the risk engine for every signal.
+#[derive(Clone)]
+pub struct RiskGuard {
+    config: LossStreakConfig,
+    state: Arc<Mutex<HashMap<String, SymbolStreak>>>,
+}
+
+impl RiskGuard {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            config: config.loss_streak.clone(),
+            state: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record a stop-loss exit of `symbol` at `now`. Returns the cooldown it
+    /// started, if this exit completed a streak.
+    pub fn record_stop_loss(
+        &self,
+        symbol: &str,
+        now: DateTime<Utc>,
+    ) -> Option<SymbolCooldownEvent> {
+        if !self.config.enabled || self.config.max_stop_losses == 0 {
+            return None;
+        }
+        let window = Duration::minutes(self.config.window_minutes as i64);
+        let mut state = self.state.lock().unwrap();
+        let streak = state.entry(symbol.to_string()).or_default();
+        streak.stop_losses.push_back(now);
+        while streak
+            .stop_losses
+            .front()
+            .is_some_and(|t| now.signed_duration_since(*t) > window)
+        {
+            streak.stop_losses.pop_front();
+        }
+        if (streak.stop_losses.len() as u32) < self.config.max_stop_losses {
+            return None;
+        }
+
+        let stop_losses = streak.stop_losses.len() as u32;
+        let until = now + Duration::minutes(self.config.cooldown_minutes as i64);
+        streak.stop_losses.clear();
+        streak.cooldown_until = Some(until);
+        warn!(
+            "🧊 [RISK-GUARD] {} hit {} stop-losses within {}m - no new buys until {}",
+            symbol,
+            stop_losses,
+            self.config.window_minutes,
+            until.to_rfc3339()
+        );
+        Some(SymbolCooldownEvent {
+            symbol: symbol.to_string(),
+            stop_losses,
+            window_minutes: self.config.window_minutes,
+            until: until.to_rfc3339(),
+            timestamp: now.to_rfc3339(),
+        })
+    }
+
+    /// End of the symbol's cooldown if one is active at `now`.
+    pub fn cooldown_until(&self, symbol: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut state = self.state.lock().unwrap();
+        let streak = state.get_mut(symbol)?;
+        match streak.cooldown_until {
+            Some(until) if now < until => Some(until),
+            Some(_) => {
+                streak.cooldown_until = None;
+                None
+            }
+            None => None,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Unit tests for the risk guard - per-symbol loss-streak cooldowns.
+
+#[cfg(test)]
+mod risk_guard_tests {
+    use crate::config::AppConfig;
+    use crate::events::AnalysisSignal;
+    use crate::services::risk_guard::*;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn test_config(loss_streak_yaml: &str) -> AppConfig {
+        let yaml = format!(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+{}
+"#,
+            loss_streak_yaml
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    fn guard() -> RiskGuard {
+        RiskGuard::new(&test_config(
+            "loss_streak:\n  max_stop_losses: 3\n  window_minutes: 30\n  cooldown_minutes: 60",
+        ))
+    }
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + Duration::minutes(minute)
+    }
+
+    fn exit(reason: &str) -> AnalysisSignal {
+        AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: "sell".to_string(),
+            confidence: 1.0,
+            thesis: String::new(),
+            market_context: format!("Reason: {}", reason),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Stop-Loss Detection Tests =============
+
+    #[test]
+    fn test_only_stop_loss_exits_count() {
+        assert!(is_stop_loss_exit(&exit("stop_loss")));
+        assert!(is_stop_loss_exit(&exit("stop_loss_limit_cancel")));
+        assert!(!is_stop_loss_exit(&exit("take_profit")));
+        assert!(!is_stop_loss_exit(&exit("forced_close")));
+
+        let mut buy = exit("stop_loss");
+        buy.signal = "buy".to_string();
+        assert!(!is_stop_loss_exit(&buy));
+    }
+
+    // ============= Cooldown Tests =============
+
+    #[test]
+    fn test_streak_within_window_starts_cooldown() {
+        let guard = guard();
+        assert!(guard.record_stop_loss("BTC/USD", at(0)).is_none());
+        assert!(guard.record_stop_loss("BTC/USD", at(10)).is_none());
+        assert!(guard.cooldown_until("BTC/USD", at(10)).is_none());
+
+        let event = guard.record_stop_loss("BTC/USD", at(20)).unwrap();
+        assert_eq!(event.symbol, "BTC/USD");
+        assert_eq!(event.stop_losses, 3);
+        assert_eq!(event.until, at(80).to_rfc3339());
+
+        assert_eq!(guard.cooldown_until("BTC/USD", at(79)), Some(at(80)));
+        assert!(guard.cooldown_until("ETH/USD", at(79)).is_none());
+        assert!(guard.cooldown_until("BTC/USD", at(80)).is_none());
+    }
+
+    #[test]
+    fn test_stop_losses_outside_window_expire() {
+        let guard = guard();
+        guard.record_stop_loss("BTC/USD", at(0));
+        guard.record_stop_loss("BTC/USD", at(20));
+        // The first stop is 31m old by now
+        assert!(guard.record_stop_loss("BTC/USD", at(31)).is_none());
+        assert!(guard.record_stop_loss("BTC/USD", at(45)).is_some());
+    }
+
+    #[test]
+    fn test_streak_restarts_after_cooldown() {
+        let guard = guard();
+        for minute in [0, 1, 2] {
+            guard.record_stop_loss("BTC/USD", at(minute));
+        }
+        assert!(guard.record_stop_loss("BTC/USD", at(70)).is_none());
+        assert!(guard.cooldown_until("BTC/USD", at(70)).is_none());
+    }
+
+    #[test]
+    fn test_disabled_never_cools_down() {
+        let guard = RiskGuard::new(&test_config("loss_streak:\n  enabled: false"));
+        for minute in 0..10 {
+            assert!(guard.record_stop_loss("BTC/USD", at(minute)).is_none());
+        }
+        assert!(guard.cooldown_until("BTC/USD", at(10)).is_none());
+    }
+}
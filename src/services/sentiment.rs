@@ -0,0 +1,212 @@
+//! News sentiment scoring.
+//!
+//! News items land in `MarketStore` from the news stream. `SentimentService`
+//! polls that buffer, asks `SentimentAgent` (through the LLM queue) to score
+//! each new item for the configured symbols it mentions, stores the latest
+//! score per symbol and publishes `MarketEvent::Sentiment`. HFT/hybrid entries
+//! are skipped while a fresh score sits below `sentiment.block_below`, and the
+//! LLM pipeline sees the score in its market context.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::agents::{sentiment::SentimentAgent, Agent};
+use crate::bus::EventBus;
+use crate::config::AppConfig;
+use crate::data::store::{MarketStore, Sentiment};
+use crate::events::{Event, MarketEvent};
+use crate::llm::LLMQueue;
+
+/// "BTC/USD", "BTC-USD" and "BTCUSD" all compare equal.
+fn normalize(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Configured symbols a news item is tagged with (Alpaca news lists e.g. "BTCUSD").
+pub fn news_symbols(item: &Value, symbols: &[String]) -> Vec<String> {
+    let tagged: Vec<String> = item
+        .get("symbols")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s.as_str())
+        .map(normalize)
+        .collect();
+    symbols
+        .iter()
+        .filter(|s| tagged.contains(&normalize(s)))
+        .cloned()
+        .collect()
+}
+
+/// User prompt for one news item.
+pub fn sentiment_query(item: &Value, symbols: &[String]) -> String {
+    let field = |key: &str| item.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    format!(
+        "Symbols: {}\nHeadline: {}\nSummary: {}",
+        symbols.join(", "),
+        field("headline"),
+        field("summary")
+    )
+}
+
+/// Scores from a `SentimentAgent` response, clamped to -1..1. Symbols that were
+/// not asked for are dropped.
+pub fn parse_scores(response: &str, symbols: &[String]) -> Vec<(String, f64)> {
+    let json_str = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response,
+    };
+    let Ok(json) = serde_json::from_str::<Value>(json_str) else {
+        return Vec::new();
+    };
+    let Some(scores) = json.get("scores").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let score = scores
+                .iter()
+                .find(|(key, _)| normalize(key) == normalize(symbol))
+                .and_then(|(_, v)| v.as_f64())
+                .filter(|v| v.is_finite())?;
+            Some((symbol.clone(), score.clamp(-1.0, 1.0)))
+        })
+        .collect()
+}
+
+/// Latest score for `symbol` if it is younger than `sentiment.max_age_minutes` at `now`.
+pub fn fresh_sentiment(
+    store: &MarketStore,
+    symbol: &str,
+    config: &AppConfig,
+    now: DateTime<Utc>,
+) -> Option<Sentiment> {
+    let sentiment = store.get_sentiment(symbol)?;
+    let scored = DateTime::parse_from_rfc3339(&sentiment.timestamp).ok()?;
+    let age_minutes = now.signed_duration_since(scored).num_seconds() as f64 / 60.0;
+    (age_minutes <= config.sentiment.max_age_minutes).then_some(sentiment)
+}
+
+/// Fresh sentiment that vetoes new entries for `symbol` (below `block_below`).
+pub fn blocking_sentiment(
+    store: &MarketStore,
+    symbol: &str,
+    config: &AppConfig,
+    now: DateTime<Utc>,
+) -> Option<Sentiment> {
+    if !config.sentiment.enabled {
+        return None;
+    }
+    fresh_sentiment(store, symbol, config, now).filter(|s| s.score < config.sentiment.block_below)
+}
+
+/// Scores incoming news per symbol through the LLM queue.
+pub struct SentimentService {
+    event_bus: EventBus,
+    store: MarketStore,
+    llm: LLMQueue,
+    config: AppConfig,
+}
+
+impl SentimentService {
+    pub fn new(event_bus: EventBus, store: MarketStore, llm: LLMQueue, config: AppConfig) -> Self {
+        Self {
+            event_bus,
+            store,
+            llm,
+            config,
+        }
+    }
+
+    /// Store the scores for one item and publish them.
+    pub fn record(&self, headline: &str, scores: &[(String, f64)], now: DateTime<Utc>) {
+        for (symbol, score) in scores {
+            let sentiment = Sentiment {
+                symbol: symbol.clone(),
+                score: *score,
+                headline: headline.to_string(),
+                timestamp: now.to_rfc3339(),
+            };
+            self.store.update_sentiment(sentiment.clone());
+            self.event_bus
+                .publish(Event::Market(MarketEvent::Sentiment {
+                    symbol: sentiment.symbol,
+                    score: sentiment.score,
+                    headline: sentiment.headline,
+                    timestamp: sentiment.timestamp,
+                }))
+                .ok();
+        }
+    }
+
+    async fn score_item(&self, item: &Value) {
+        let symbols = news_symbols(item, &self.config.symbols);
+        if symbols.is_empty() {
+            return;
+        }
+        let headline = item
+            .get("headline")
+            .and_then(|h| h.as_str())
+            .unwrap_or("No Headline");
+        let response = match SentimentAgent
+            .run(&sentiment_query(item, &symbols), &self.llm)
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                error!("❌ [SENTIMENT] Scoring failed for '{}': {}", headline, e);
+                return;
+            }
+        };
+        let scores = parse_scores(&response, &symbols);
+        if scores.is_empty() {
+            warn!("⚠️ [SENTIMENT] No scores parsed for '{}'", headline);
+            return;
+        }
+        for (symbol, score) in &scores {
+            info!("📰 [SENTIMENT] {} {:+.2}: {}", symbol, score, headline);
+        }
+        self.record(headline, &scores, Utc::now());
+    }
+
+    pub async fn start(self) {
+        let shutdown = self.event_bus.shutdown().clone();
+        tokio::spawn(async move {
+            info!(
+                "📰 Sentiment Service started (poll {}s, block below {:+.2})",
+                self.config.sentiment.poll_secs, self.config.sentiment.block_below
+            );
+            // News already buffered at startup is not re-scored
+            let mut last_seen = self.store.get_latest_news().last().cloned();
+            let mut ticker =
+                tokio::time::interval(Duration::from_secs(self.config.sentiment.poll_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = shutdown.draining() => break,
+                    _ = ticker.tick() => {}
+                }
+                let news = self.store.get_latest_news();
+                let start = last_seen
+                    .as_ref()
+                    .and_then(|seen| news.iter().rposition(|n| n == seen))
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                if start >= news.len() {
+                    continue;
+                }
+                last_seen = news.last().cloned();
+                for item in &news[start..] {
+                    self.score_item(item).await;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,189 @@
+//! Unit tests for news sentiment scoring (symbol matching, response parsing, entry veto).
+
+#[cfg(test)]
+mod sentiment_tests {
+    use crate::bus::EventBus;
+    use crate::config::{AppConfig, LlmRetryConfig};
+    use crate::data::store::{MarketStore, Sentiment};
+    use crate::events::{Event, MarketEvent};
+    use crate::llm::{LLMClient, LLMQueue};
+    use crate::services::sentiment::*;
+    use chrono::{DateTime, Duration, Utc};
+    use serde_json::json;
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+sentiment:
+  enabled: true
+  block_below: -0.3
+  max_age_minutes: 30
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn symbols() -> Vec<String> {
+        vec!["BTC/USD".to_string(), "ETH/USD".to_string()]
+    }
+
+    fn scored(store: &MarketStore, symbol: &str, score: f64, ts: DateTime<Utc>) {
+        store.update_sentiment(Sentiment {
+            symbol: symbol.to_string(),
+            score,
+            headline: "headline".to_string(),
+            timestamp: ts.to_rfc3339(),
+        });
+    }
+
+    // ============= Symbol Matching Tests =============
+
+    #[test]
+    fn test_news_symbols_matches_alpaca_tags() {
+        let item = json!({"headline": "h", "symbols": ["BTCUSD", "COIN"]});
+        assert_eq!(news_symbols(&item, &symbols()), vec!["BTC/USD"]);
+
+        let untagged = json!({"headline": "h"});
+        assert!(news_symbols(&untagged, &symbols()).is_empty());
+    }
+
+    #[test]
+    fn test_sentiment_query_lists_symbols_and_text() {
+        let item = json!({"headline": "ETF approved", "summary": "Details"});
+        let query = sentiment_query(&item, &symbols());
+        assert!(query.contains("Symbols: BTC/USD, ETH/USD"));
+        assert!(query.contains("Headline: ETF approved"));
+        assert!(query.contains("Summary: Details"));
+    }
+
+    // ============= Response Parsing Tests =============
+
+    #[test]
+    fn test_parse_scores_clamps_and_filters() {
+        let response = r#"Here you go: {"scores": {"BTCUSD": 1.7, "ETH/USD": -0.25, "SOL/USD": 0.9}, "reasoning": "x"}"#;
+        let scores = parse_scores(response, &symbols());
+        assert_eq!(
+            scores,
+            vec![("BTC/USD".to_string(), 1.0), ("ETH/USD".to_string(), -0.25)]
+        );
+    }
+
+    #[test]
+    fn test_parse_scores_bad_response() {
+        assert!(parse_scores("no json here", &symbols()).is_empty());
+        assert!(parse_scores(r#"{"score": 0.5}"#, &symbols()).is_empty());
+        assert!(parse_scores(r#"{"scores": {"BTC/USD": "up"}}"#, &symbols()).is_empty());
+    }
+
+    // ============= Freshness / Veto Tests =============
+
+    #[test]
+    fn test_fresh_sentiment_expires() {
+        let config = test_config();
+        let store = MarketStore::new(10);
+        let now = at("2026-01-01T12:00:00Z");
+        scored(&store, "BTC/USD", 0.5, now - Duration::minutes(10));
+        scored(&store, "ETH/USD", 0.5, now - Duration::minutes(45));
+
+        assert!(fresh_sentiment(&store, "BTC/USD", &config, now).is_some());
+        assert!(fresh_sentiment(&store, "ETH/USD", &config, now).is_none());
+        assert!(fresh_sentiment(&store, "SOL/USD", &config, now).is_none());
+    }
+
+    #[test]
+    fn test_blocking_sentiment_threshold() {
+        let mut config = test_config();
+        let store = MarketStore::new(10);
+        let now = at("2026-01-01T12:00:00Z");
+        scored(&store, "BTC/USD", -0.6, now);
+        scored(&store, "ETH/USD", -0.1, now);
+
+        assert_eq!(
+            blocking_sentiment(&store, "BTC/USD", &config, now).map(|s| s.score),
+            Some(-0.6)
+        );
+        assert!(blocking_sentiment(&store, "ETH/USD", &config, now).is_none());
+
+        // Disabled: scores never veto
+        config.sentiment.enabled = false;
+        assert!(blocking_sentiment(&store, "BTC/USD", &config, now).is_none());
+    }
+
+    // ============= Service Tests =============
+
+    #[tokio::test]
+    async fn test_record_stores_and_publishes() {
+        let config = test_config();
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe("test");
+        let store = MarketStore::new(10);
+        let llm = LLMQueue::new(
+            LLMClient::new(String::new(), None, "test-model".to_string()),
+            1,
+            1,
+            LlmRetryConfig::default(),
+        );
+        let service = SentimentService::new(bus.clone(), store.clone(), llm, config);
+
+        service.record(
+            "Exchange hacked",
+            &[("BTC/USD".to_string(), -0.8)],
+            at("2026-01-01T12:00:00Z"),
+        );
+
+        assert_eq!(store.get_sentiment("BTC/USD").unwrap().score, -0.8);
+        match rx.try_recv().unwrap() {
+            Event::Market(MarketEvent::Sentiment {
+                symbol,
+                score,
+                headline,
+                ..
+            }) => {
+                assert_eq!(symbol, "BTC/USD");
+                assert_eq!(score, -0.8);
+                assert_eq!(headline, "Exchange hacked");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Shadow (paper) trading for strategy A/B tests.
+//!
+//! With `ab_test.enabled` the strategy engine evaluates a second instance of
+//! the strategy under `shadow_config` (the live config with the
+//! `ab_test.shadow` tunables applied) on the same market data, tagging its
+//! signals with `ab_test.shadow_id`. The risk engine drops those, so they never
+//! reach the exchange; the `TradeReporter` hands them to a `ShadowBook`, which
+//! fills buys at the ask and sells at the bid of the latest quote, exits at the
+//! signal's TP/SL (else the variant's `take_profit_pct`/`stop_loss_pct`) and
+//! returns the fills for the reporter to book under the shadow id.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::config::AppConfig;
+use crate::events::{AnalysisSignal, ExecutionReport};
+use crate::money::dec;
+use crate::services::config_reload::apply_patch;
+
+/// Config the shadow strategy runs under: `config` with `ab_test.shadow`
+/// applied and, if set, `ab_test.shadow_strategy_mode`.
+pub fn shadow_config(config: &AppConfig) -> Result<AppConfig, String> {
+    let mut shadow = config.clone();
+    apply_patch(&mut shadow, &config.ab_test.shadow)?;
+    if let Some(mode) = &config.ab_test.shadow_strategy_mode {
+        shadow.strategy_mode = mode.to_lowercase();
+        for o in shadow
+            .symbol_overrides
+            .iter_mut()
+            .flat_map(|o| o.values_mut())
+        {
+            o.strategy_mode = None;
+        }
+    }
+    Ok(shadow)
+}
+
+struct ShadowPosition {
+    qty: Decimal,
+    take_profit: Decimal,
+    stop_loss: Decimal,
+}
+
+/// Paper positions of the shadow strategy, one per symbol.
+pub struct ShadowBook {
+    id: String,
+    config: AppConfig,
+    /// Latest (bid, ask) per symbol
+    quotes: HashMap<String, (Decimal, Decimal)>,
+    positions: HashMap<String, ShadowPosition>,
+    orders: u64,
+}
+
+impl ShadowBook {
+    pub fn new(id: &str, config: AppConfig) -> Self {
+        Self {
+            id: id.to_string(),
+            config,
+            quotes: HashMap::new(),
+            positions: HashMap::new(),
+            orders: 0,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Swap in the shadow config after a hot reload (open positions keep
+    /// their exits).
+    pub fn set_config(&mut self, config: AppConfig) {
+        self.config = config;
+    }
+
+    /// Record the latest quote; the exit fill if it reached the position's
+    /// take-profit or stop-loss.
+    pub fn on_quote(&mut self, symbol: &str, bid: f64, ask: f64) -> Option<ExecutionReport> {
+        if bid <= 0.0 || ask < bid {
+            return None;
+        }
+        let (bid, ask) = (dec(bid), dec(ask));
+        self.quotes.insert(symbol.to_string(), (bid, ask));
+        let pos = self.positions.get(symbol)?;
+        if bid < pos.take_profit && bid > pos.stop_loss {
+            return None;
+        }
+        let pos = self.positions.remove(symbol)?;
+        Some(self.fill(symbol, "sell", bid, pos.qty))
+    }
+
+    /// Paper-fill a shadow signal at the latest quote: a buy opens a position
+    /// of `defaults.max_order_amount` notional when flat, a sell closes it.
+    pub fn on_signal(&mut self, signal: &AnalysisSignal) -> Option<ExecutionReport> {
+        let (bid, ask) = *self.quotes.get(&signal.symbol)?;
+        if signal.signal.eq_ignore_ascii_case("buy") {
+            if self.positions.contains_key(&signal.symbol) {
+                return None;
+            }
+            let qty = dec(self.config.defaults.max_order_amount).checked_div(ask)?;
+            if qty <= Decimal::ZERO {
+                return None;
+            }
+            let (tp_pct, sl_pct) = self.config.get_symbol_params(&signal.symbol);
+            let hundred = Decimal::ONE_HUNDRED;
+            self.positions.insert(
+                signal.symbol.clone(),
+                ShadowPosition {
+                    qty,
+                    take_profit: signal
+                        .take_profit
+                        .unwrap_or(ask * (hundred + dec(tp_pct)) / hundred),
+                    stop_loss: signal
+                        .stop_loss
+                        .unwrap_or(ask * (hundred - dec(sl_pct)) / hundred),
+                },
+            );
+            Some(self.fill(&signal.symbol, "buy", ask, qty))
+        } else if signal.signal.eq_ignore_ascii_case("sell") {
+            let pos = self.positions.remove(&signal.symbol)?;
+            Some(self.fill(&signal.symbol, "sell", bid, pos.qty))
+        } else {
+            None
+        }
+    }
+
+    fn fill(&mut self, symbol: &str, side: &str, price: Decimal, qty: Decimal) -> ExecutionReport {
+        self.orders += 1;
+        let mid = self
+            .quotes
+            .get(symbol)
+            .map(|(bid, ask)| (bid + ask) / Decimal::TWO);
+        ExecutionReport {
+            symbol: symbol.to_string(),
+            order_id: format!("shadow-{}-{}", self.id, self.orders),
+            status: "filled".to_string(),
+            side: side.to_string(),
+            price: Some(price),
+            qty: Some(qty),
+            signal_price: mid,
+            limit_price: None,
+            strategy_id: Some(self.id.clone()),
+            correlation_id: None,
+            metadata: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Unit tests for A/B shadow trading (shadow config, paper fills and exits).
+
+#[cfg(test)]
+mod shadow_tests {
+    use crate::config::AppConfig;
+    use crate::events::AnalysisSignal;
+    use crate::money::dec;
+    use crate::services::shadow::*;
+    use serde_json::json;
+
+    const CONFIG_YAML: &str = r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides:
+  BTC/USD:
+    strategy_mode: "llm"
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+ab_test:
+  enabled: true
+  shadow_strategy_mode: "HFT"
+  shadow:
+    hft.min_edge_bps: 15
+"#;
+
+    fn test_config() -> AppConfig {
+        AppConfig::parse(CONFIG_YAML).unwrap()
+    }
+
+    /// A shadow signal, with the strategy's (take-profit, stop-loss) if any.
+    fn signal(side: &str, exits: Option<(f64, f64)>) -> AnalysisSignal {
+        AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: side.to_string(),
+            confidence: 1.0,
+            thesis: "HFT momentum".to_string(),
+            market_context: String::new(),
+            strategy_id: Some("B".to_string()),
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: exits.map(|(tp, _)| dec(tp)),
+            stop_loss: exits.map(|(_, sl)| dec(sl)),
+            valid_until: None,
+            ..Default::default()
+        }
+    }
+
+    fn book() -> ShadowBook {
+        ShadowBook::new("B", test_config())
+    }
+
+    // ============= Shadow Config Tests =============
+
+    #[test]
+    fn test_shadow_config_applies_tunables() {
+        let config = test_config();
+        assert_eq!(config.ab_test.live_id, "A");
+        assert_eq!(config.ab_test.shadow_id, "B");
+
+        let shadow = shadow_config(&config).unwrap();
+        assert_eq!(shadow.hft.min_edge_bps, 15.0);
+        assert_eq!(config.hft.min_edge_bps, 10.0);
+        // The shadow mode applies to every symbol
+        assert_eq!(shadow.strategy_mode_for("BTC/USD"), "hft");
+        assert_eq!(config.strategy_mode_for("BTC/USD"), "llm");
+    }
+
+    #[test]
+    fn test_shadow_config_rejects_unknown_keys() {
+        let mut config = test_config();
+        config.ab_test.shadow = json!({"exchange": "binance"}).as_object().unwrap().clone();
+        assert_eq!(
+            shadow_config(&config).unwrap_err(),
+            "exchange is not a hot-reloadable key"
+        );
+    }
+
+    // ============= Paper Fill Tests =============
+
+    #[test]
+    fn test_buy_fills_at_ask_and_exits_at_take_profit() {
+        let mut book = book();
+        // No quote yet: nothing to fill at
+        assert!(book.on_signal(&signal("buy", None)).is_none());
+
+        assert!(book.on_quote("BTC/USD", 99.0, 100.0).is_none());
+        let buy = book.on_signal(&signal("buy", Some((101.5, 98.0)))).unwrap();
+        assert_eq!(buy.side, "buy");
+        assert_eq!(buy.status, "filled");
+        assert_eq!(buy.price, Some(dec(100.0)));
+        assert_eq!(buy.qty, Some(dec(1.0)));
+        assert_eq!(buy.signal_price, Some(dec(99.5)));
+        assert_eq!(buy.strategy_id.as_deref(), Some("B"));
+        assert_eq!(buy.order_id, "shadow-B-1");
+
+        // Already holding: repeated buys are ignored
+        assert!(book
+            .on_signal(&signal("buy", Some((101.5, 98.0))))
+            .is_none());
+        assert!(book.on_quote("BTC/USD", 101.0, 101.2).is_none());
+
+        let exit = book.on_quote("BTC/USD", 101.5, 101.7).unwrap();
+        assert_eq!(exit.side, "sell");
+        assert_eq!(exit.price, Some(dec(101.5)));
+        assert_eq!(exit.qty, Some(dec(1.0)));
+        assert!(book.on_quote("BTC/USD", 90.0, 90.1).is_none());
+    }
+
+    #[test]
+    fn test_default_exits_and_sell_signals() {
+        let mut book = book();
+        book.on_quote("BTC/USD", 49.9, 50.0);
+        let buy = book.on_signal(&signal("buy", None)).unwrap();
+        assert_eq!(buy.qty, Some(dec(2.0)));
+
+        // stop_loss_pct 0.5 below the 50.0 entry
+        assert!(book.on_quote("BTC/USD", 49.8, 49.9).is_none());
+        let exit = book.on_quote("BTC/USD", 49.75, 49.8).unwrap();
+        assert_eq!(exit.price, Some(dec(49.75)));
+
+        // A sell signal closes at the bid; without a position it is ignored
+        book.on_signal(&signal("buy", None)).unwrap();
+        book.on_quote("BTC/USD", 49.9, 50.0);
+        let sell = book.on_signal(&signal("sell", None)).unwrap();
+        assert_eq!(sell.price, Some(dec(49.9)));
+        assert!(book.on_signal(&signal("sell", None)).is_none());
+        assert!(book.on_signal(&signal("no_trade", None)).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Signal deduplication and one entry decision per symbol.
+//!
+//! In hybrid and HFT mode a symbol can emit the same buy on consecutive
+//! quotes, milliseconds apart, and every copy would spawn its own risk and
+//! execution task racing the position tracker checks. The risk engine runs
+//! each signal through `SignalGate::admit` first:
+//! - a signal identical (symbol, side, strategy) to one admitted within
+//!   `dedup_window_ms` is dropped
+//! - a buy is dropped while the symbol already has a buy decision in flight;
+//!   the decision settles on the symbol's next buy execution report, when the
+//!   risk check rejects it, or after `in_flight_timeout_ms`
+//!
+//! Exits are deduplicated but never wait for an entry decision.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::SignalGateConfig;
+use crate::events::AnalysisSignal;
+
+/// Why the gate dropped a signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalDrop {
+    /// Same symbol, side and strategy within `dedup_window_ms`
+    Duplicate,
+    /// A buy decision for the symbol has not settled yet
+    InFlight,
+}
+
+impl std::fmt::Display for SignalDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalDrop::Duplicate => write!(f, "duplicate signal"),
+            SignalDrop::InFlight => write!(f, "entry decision in flight"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SymbolGate {
+    /// Last admission per (side, strategy id)
+    last_admitted: HashMap<(String, Option<String>), DateTime<Utc>>,
+    /// When the symbol's in-flight buy decision was admitted
+    in_flight_since: Option<DateTime<Utc>>,
+}
+
+/// Per-symbol gate state, shared by the risk engine and its tasks.
+#[derive(Clone, Default)]
+pub struct SignalGate {
+    state: Arc<Mutex<HashMap<String, SymbolGate>>>,
+}
+
+impl SignalGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit `signal` at `now`, or say why it is dropped. An admitted buy
+    /// holds the symbol's entry decision until `settle`.
+    pub fn admit(
+        &self,
+        config: &SignalGateConfig,
+        signal: &AnalysisSignal,
+        now: DateTime<Utc>,
+    ) -> Result<(), SignalDrop> {
+        if !config.enabled {
+            return Ok(());
+        }
+        let mut state = self.state.lock().unwrap();
+        let gate = state.entry(signal.symbol.clone()).or_default();
+
+        let key = (signal.signal.to_lowercase(), signal.strategy_id.clone());
+        let window = Duration::milliseconds(config.dedup_window_ms as i64);
+        if gate
+            .last_admitted
+            .get(&key)
+            .is_some_and(|t| now.signed_duration_since(*t) < window)
+        {
+            return Err(SignalDrop::Duplicate);
+        }
+
+        if key.0 == "buy" {
+            let timeout = Duration::milliseconds(config.in_flight_timeout_ms as i64);
+            if gate
+                .in_flight_since
+                .is_some_and(|t| now.signed_duration_since(t) < timeout)
+            {
+                return Err(SignalDrop::InFlight);
+            }
+            gate.in_flight_since = Some(now);
+        }
+        gate.last_admitted.insert(key, now);
+        Ok(())
+    }
+
+    /// The symbol's buy decision is done (reported or rejected); the next
+    /// buy may be admitted.
+    pub fn settle(&self, symbol: &str) {
+        if let Some(gate) = self.state.lock().unwrap().get_mut(symbol) {
+            gate.in_flight_since = None;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Unit tests for signal deduplication and the per-symbol entry gate.
+
+#[cfg(test)]
+mod signal_gate_tests {
+    use crate::config::SignalGateConfig;
+    use crate::events::AnalysisSignal;
+    use crate::services::signal_gate::*;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+    }
+
+    fn ms(n: i64) -> DateTime<Utc> {
+        t0() + Duration::milliseconds(n)
+    }
+
+    fn signal(symbol: &str, side: &str, strategy_id: Option<&str>) -> AnalysisSignal {
+        AnalysisSignal {
+            symbol: symbol.to_string(),
+            signal: side.to_string(),
+            confidence: 0.8,
+            thesis: "HFT edge".to_string(),
+            market_context: "tp=101, sl=99".to_string(),
+            strategy_id: strategy_id.map(|s| s.to_string()),
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
+        }
+    }
+
+    // ============= Dedup Tests =============
+
+    #[test]
+    fn test_identical_signal_within_window_is_duplicate() {
+        let gate = SignalGate::new();
+        let config = SignalGateConfig::default();
+        let sell = signal("BTC/USD", "sell", None);
+        assert_eq!(gate.admit(&config, &sell, t0()), Ok(()));
+        assert_eq!(
+            gate.admit(&config, &sell, ms(5)),
+            Err(SignalDrop::Duplicate)
+        );
+        // Other symbols and strategies are distinct signals
+        assert!(gate
+            .admit(&config, &signal("ETH/USD", "sell", None), ms(5))
+            .is_ok());
+        assert!(gate
+            .admit(&config, &signal("BTC/USD", "sell", Some("b")), ms(5))
+            .is_ok());
+        // Admitted again once the window has passed
+        assert!(gate.admit(&config, &sell, ms(1_000)).is_ok());
+    }
+
+    // ============= In-Flight Tests =============
+
+    #[test]
+    fn test_one_buy_decision_in_flight_per_symbol() {
+        let gate = SignalGate::new();
+        let config = SignalGateConfig::default();
+        assert!(gate
+            .admit(&config, &signal("BTC/USD", "buy", Some("a")), t0())
+            .is_ok());
+        // Past the dedup window, a different strategy still waits
+        assert_eq!(
+            gate.admit(&config, &signal("BTC/USD", "buy", Some("b")), ms(2_000)),
+            Err(SignalDrop::InFlight)
+        );
+        // Exits never wait for the entry
+        assert!(gate
+            .admit(&config, &signal("BTC/USD", "sell", None), ms(2_000))
+            .is_ok());
+
+        gate.settle("BTC/USD");
+        assert!(gate
+            .admit(&config, &signal("BTC/USD", "buy", Some("b")), ms(2_000))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unsettled_decision_expires() {
+        let gate = SignalGate::new();
+        let config = SignalGateConfig::default();
+        let buy = signal("SOL/USD", "buy", None);
+        assert!(gate.admit(&config, &buy, t0()).is_ok());
+        assert_eq!(
+            gate.admit(&config, &buy, ms(9_999)),
+            Err(SignalDrop::InFlight)
+        );
+        assert!(gate.admit(&config, &buy, ms(10_000)).is_ok());
+    }
+
+    #[test]
+    fn test_disabled_gate_admits_everything() {
+        let gate = SignalGate::new();
+        let config = SignalGateConfig {
+            enabled: false,
+            ..SignalGateConfig::default()
+        };
+        let buy = signal("BTC/USD", "buy", None);
+        for _ in 0..3 {
+            assert!(gate.admit(&config, &buy, t0()).is_ok());
+        }
+    }
+}
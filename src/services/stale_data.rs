@@ -0,0 +1,107 @@
+//! Dead-man's switch for the quote-driven position monitor.
+//!
+//! Exits are evaluated on incoming quotes, so a dead stream leaves positions
+//! without SL/TP protection. The monitor records every streamed tick here and
+//! checks its open positions every `stale_data.poll_secs`: symbols quiet for
+//! `stale_secs` are priced over REST instead, and with `close_positions` set
+//! they are market-closed once the stream has been quiet for `close_after_secs`.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::config::StaleDataConfig;
+
+/// What the monitor should do for a stale symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaleAction {
+    /// Evaluate exits on a REST price
+    Poll,
+    /// Force the position closed (then evaluate on a REST price)
+    Close,
+}
+
+/// Per-symbol streamed-tick bookkeeping, owned by the monitor loop.
+pub struct StaleQuoteWatch {
+    config: StaleDataConfig,
+    /// Symbols never streamed count as quiet since the watch started
+    started: Instant,
+    /// Arrival time and price of the last streamed tick per symbol
+    last_seen: HashMap<String, (Instant, Decimal)>,
+    /// Symbols currently priced over REST (logged once per episode)
+    stale: HashSet<String>,
+    /// Symbols already force-closed this episode
+    closed: HashSet<String>,
+}
+
+impl StaleQuoteWatch {
+    pub fn new(config: &StaleDataConfig, now: Instant) -> Self {
+        Self {
+            config: config.clone(),
+            started: now,
+            last_seen: HashMap::new(),
+            stale: HashSet::new(),
+            closed: HashSet::new(),
+        }
+    }
+
+    /// A streamed quote/trade for `symbol` arrived at `now`.
+    pub fn record(&mut self, symbol: &str, price: Decimal, now: Instant) {
+        self.last_seen.insert(symbol.to_string(), (now, price));
+        if self.stale.remove(symbol) {
+            self.closed.remove(symbol);
+            info!(
+                "✅ [STALE-DATA] Quotes for {} resumed - back to streamed exits",
+                symbol
+            );
+        }
+    }
+
+    /// Last streamed price of `symbol`, if any.
+    pub fn last_price(&self, symbol: &str) -> Option<Decimal> {
+        self.last_seen.get(symbol).map(|(_, price)| *price)
+    }
+
+    /// Stale symbols among the open `positions` at `now`. A forced close is
+    /// returned once per stale episode.
+    pub fn check(&mut self, positions: &[String], now: Instant) -> Vec<(String, StaleAction)> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.stale.retain(|s| positions.contains(s));
+        self.closed.retain(|s| positions.contains(s));
+
+        let stale_after = Duration::from_secs(self.config.stale_secs);
+        let close_after = Duration::from_secs(self.config.close_after_secs);
+        let mut actions = Vec::new();
+        for symbol in positions {
+            let since = self
+                .last_seen
+                .get(symbol)
+                .map_or(self.started, |(at, _)| *at);
+            let quiet = now.saturating_duration_since(since);
+            if quiet < stale_after {
+                continue;
+            }
+            if self.stale.insert(symbol.clone()) {
+                warn!(
+                    "⏰ [STALE-DATA] No quotes for {} in {}s - evaluating exits on REST prices",
+                    symbol,
+                    quiet.as_secs()
+                );
+            }
+            let action = if self.config.close_positions
+                && quiet >= close_after
+                && self.closed.insert(symbol.clone())
+            {
+                StaleAction::Close
+            } else {
+                StaleAction::Poll
+            };
+            actions.push((symbol.clone(), action));
+        }
+        actions
+    }
+}
@@ -0,0 +1,130 @@
+//! Unit tests for the stale quote dead-man's switch.
+
+#[cfg(test)]
+mod stale_data_tests {
+    use crate::config::StaleDataConfig;
+    use crate::money::dec;
+    use crate::services::stale_data::*;
+    use std::time::{Duration, Instant};
+
+    fn config() -> StaleDataConfig {
+        StaleDataConfig {
+            stale_secs: 30,
+            close_after_secs: 120,
+            ..StaleDataConfig::default()
+        }
+    }
+
+    fn positions(symbols: &[&str]) -> Vec<String> {
+        symbols.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    // ============= Staleness Tests =============
+
+    #[test]
+    fn test_fresh_quotes_are_not_polled() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(&config(), start);
+        watch.record("BTC/USD", dec(100.0), start + secs(20));
+
+        assert!(watch
+            .check(&positions(&["BTC/USD"]), start + secs(45))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_quiet_symbol_is_polled() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(&config(), start);
+        watch.record("BTC/USD", dec(100.0), start);
+        watch.record("ETH/USD", dec(50.0), start + secs(20));
+
+        let actions = watch.check(&positions(&["BTC/USD", "ETH/USD"]), start + secs(31));
+        assert_eq!(actions, vec![("BTC/USD".to_string(), StaleAction::Poll)]);
+        assert_eq!(watch.last_price("BTC/USD"), Some(dec(100.0)));
+    }
+
+    #[test]
+    fn test_never_quoted_symbol_counts_from_start() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(&config(), start);
+        let held = positions(&["SOL/USD"]);
+
+        assert!(watch.check(&held, start + secs(10)).is_empty());
+        assert_eq!(
+            watch.check(&held, start + secs(30)),
+            vec![("SOL/USD".to_string(), StaleAction::Poll)]
+        );
+    }
+
+    #[test]
+    fn test_streamed_quote_ends_episode() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(&config(), start);
+        let held = positions(&["BTC/USD"]);
+        assert_eq!(watch.check(&held, start + secs(40)).len(), 1);
+
+        watch.record("BTC/USD", dec(101.0), start + secs(41));
+        assert!(watch.check(&held, start + secs(45)).is_empty());
+    }
+
+    // ============= Forced Close Tests =============
+
+    #[test]
+    fn test_close_is_opt_in() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(&config(), start);
+        let actions = watch.check(&positions(&["BTC/USD"]), start + secs(600));
+        assert_eq!(actions, vec![("BTC/USD".to_string(), StaleAction::Poll)]);
+    }
+
+    #[test]
+    fn test_close_once_per_episode() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(
+            &StaleDataConfig {
+                close_positions: true,
+                ..config()
+            },
+            start,
+        );
+        let held = positions(&["BTC/USD"]);
+
+        assert_eq!(watch.check(&held, start + secs(60))[0].1, StaleAction::Poll);
+        assert_eq!(
+            watch.check(&held, start + secs(120))[0].1,
+            StaleAction::Close
+        );
+        assert_eq!(
+            watch.check(&held, start + secs(125))[0].1,
+            StaleAction::Poll
+        );
+
+        // A new episode after quotes resume closes again
+        watch.record("BTC/USD", dec(99.0), start + secs(130));
+        assert_eq!(
+            watch.check(&held, start + secs(250))[0].1,
+            StaleAction::Close
+        );
+    }
+
+    #[test]
+    fn test_disabled_never_acts() {
+        let start = Instant::now();
+        let mut watch = StaleQuoteWatch::new(
+            &StaleDataConfig {
+                enabled: false,
+                close_positions: true,
+                ..config()
+            },
+            start,
+        );
+        assert!(watch
+            .check(&positions(&["BTC/USD"]), start + secs(600))
+            .is_empty());
+    }
+}
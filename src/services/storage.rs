@@ -0,0 +1,418 @@
+//! Database storage of trading records (`database.*`).
+//!
+//! `TradeReporter` hands every order log entry, fill, closed trade and equity
+//! sample to a `StorageWriter`, and the LLM queue every completed call. With
+//! the `database` feature a single writer task inserts them through sqlx into
+//! SQLite or Postgres (the `database.url` scheme picks the driver), so
+//! concurrent sessions never interleave writes, and `/report` reads them back
+//! for a date range with `Database::report`.
+//!
+//! Prices and quantities are stored as decimal strings and timestamps as
+//! RFC 3339 strings, which compare in time order on both backends.
+
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::llm::usage::LlmCall;
+use crate::services::reporting::{ClosedTrade, EquitySample, TradeLogEntry};
+
+/// A fill booked by the reporter.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StoredFill {
+    pub ts: String,
+    pub symbol: String,
+    pub side: String,
+    pub order_id: String,
+    pub qty: Decimal,
+    pub price: Decimal,
+    pub strategy_id: Option<String>,
+    pub correlation_id: Option<String>,
+}
+
+/// A closed (or partly closed) trade with the strategy that exited it.
+#[derive(Clone, Debug, Serialize)]
+pub struct StoredTrade {
+    #[serde(flatten)]
+    pub trade: ClosedTrade,
+    pub strategy_id: Option<String>,
+}
+
+/// One row for the database, by table.
+#[derive(Clone, Debug)]
+pub enum StorageRecord {
+    /// `orders`: the trade log entry of an order or execution report
+    Order(TradeLogEntry),
+    /// `fills`
+    Fill(StoredFill),
+    /// `closed_trades`
+    ClosedTrade(StoredTrade),
+    /// `equity_snapshots`
+    Equity(EquitySample),
+    /// `llm_calls`
+    LlmCall(LlmCall),
+}
+
+impl StorageRecord {
+    pub fn table(&self) -> &'static str {
+        match self {
+            StorageRecord::Order(_) => "orders",
+            StorageRecord::Fill(_) => "fills",
+            StorageRecord::ClosedTrade(_) => "closed_trades",
+            StorageRecord::Equity(_) => "equity_snapshots",
+            StorageRecord::LlmCall(_) => "llm_calls",
+        }
+    }
+}
+
+/// Queue into the database writer task; cheap to clone, never blocks.
+#[derive(Clone)]
+pub struct StorageWriter {
+    tx: UnboundedSender<StorageRecord>,
+}
+
+impl StorageWriter {
+    /// A writer and the receiving end its records are read from.
+    pub fn channel() -> (Self, UnboundedReceiver<StorageRecord>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+
+    pub fn record(&self, record: StorageRecord) {
+        // Best-effort: storage must not affect trading
+        let _ = self.tx.send(record);
+    }
+
+    /// A sender for `LLMQueue::log_calls_to` whose calls end up in `llm_calls`.
+    pub fn llm_calls(&self) -> UnboundedSender<LlmCall> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let writer = self.clone();
+        tokio::spawn(async move {
+            while let Some(call) = rx.recv().await {
+                writer.record(StorageRecord::LlmCall(call));
+            }
+        });
+        tx
+    }
+}
+
+/// Inclusive UTC date range of a `/report` query; open ends are unbounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// Parse `from`/`to` as YYYY-MM-DD.
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self, String> {
+        let date = |name: &str, value: Option<&str>| {
+            value
+                .map(|v| {
+                    NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d")
+                        .map_err(|_| format!("{} must be YYYY-MM-DD", name))
+                })
+                .transpose()
+        };
+        let range = Self {
+            from: date("from", from)?,
+            to: date("to", to)?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err("from must not be after to".to_string());
+            }
+        }
+        Ok(range)
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    /// Timestamp bounds `[start, end)` for comparing RFC 3339 strings.
+    pub fn bounds(&self) -> (String, String) {
+        let start = self
+            .from
+            .map_or_else(String::new, |d| d.format("%Y-%m-%d").to_string());
+        let end = self.to.map_or_else(
+            || "9999".to_string(),
+            |d| (d + Duration::days(1)).format("%Y-%m-%d").to_string(),
+        );
+        (start, end)
+    }
+}
+
+#[cfg(feature = "database")]
+pub use database::{Database, StoredReport};
+
+#[cfg(feature = "database")]
+mod database {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use serde::Serialize;
+    use sqlx::any::{AnyPoolOptions, AnyRow};
+    use sqlx::{AnyPool, Row};
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tracing::{error, info};
+
+    use super::{DateRange, Decimal, StorageRecord, StoredTrade};
+    use crate::config::DatabaseConfig;
+    use crate::llm::usage::UsageTotals;
+    use crate::services::reporting::{ClosedTrade, EquitySample};
+
+    /// Portable between SQLite and Postgres (no generated ids, TEXT decimals).
+    const SCHEMA: [&str; 8] = [
+        "CREATE TABLE IF NOT EXISTS orders (ts TEXT NOT NULL, symbol TEXT NOT NULL, \
+         action TEXT NOT NULL, order_id TEXT NOT NULL, status TEXT NOT NULL, qty TEXT, \
+         price TEXT, notional TEXT, notes TEXT)",
+        "CREATE TABLE IF NOT EXISTS fills (ts TEXT NOT NULL, symbol TEXT NOT NULL, \
+         side TEXT NOT NULL, order_id TEXT NOT NULL, qty TEXT NOT NULL, price TEXT NOT NULL, \
+         strategy_id TEXT, correlation_id TEXT)",
+        "CREATE TABLE IF NOT EXISTS closed_trades (symbol TEXT NOT NULL, buy_time TEXT NOT NULL, \
+         sell_time TEXT NOT NULL, buy_price TEXT NOT NULL, sell_price TEXT NOT NULL, \
+         qty TEXT NOT NULL, pnl TEXT NOT NULL, pnl_percent DOUBLE PRECISION NOT NULL, \
+         strategy_id TEXT)",
+        "CREATE TABLE IF NOT EXISTS equity_snapshots (ts TEXT NOT NULL, realized_pnl TEXT NOT NULL, \
+         unrealized_pnl TEXT NOT NULL, equity TEXT NOT NULL)",
+        "CREATE TABLE IF NOT EXISTS llm_calls (ts TEXT NOT NULL, agent TEXT NOT NULL, \
+         model TEXT NOT NULL, prompt_tokens BIGINT NOT NULL, completion_tokens BIGINT NOT NULL, \
+         cost_usd DOUBLE PRECISION NOT NULL)",
+        "CREATE INDEX IF NOT EXISTS orders_ts ON orders (ts)",
+        "CREATE INDEX IF NOT EXISTS closed_trades_sell_time ON closed_trades (sell_time)",
+        "CREATE INDEX IF NOT EXISTS equity_snapshots_ts ON equity_snapshots (ts)",
+    ];
+
+    /// Stored records of a date range, as returned by `/report`.
+    #[derive(Clone, Debug, Default, Serialize)]
+    pub struct StoredReport {
+        pub from: Option<String>,
+        pub to: Option<String>,
+        pub orders: u64,
+        pub fills: u64,
+        pub winning_trades: u64,
+        pub losing_trades: u64,
+        pub total_realized_pnl: Decimal,
+        pub closed_trades: Vec<StoredTrade>,
+        pub equity_curve: Vec<EquitySample>,
+        pub llm: UsageTotals,
+    }
+
+    #[derive(Clone)]
+    pub struct Database {
+        pool: AnyPool,
+    }
+
+    fn decimal(row: &AnyRow, column: &str) -> Result<Decimal, sqlx::Error> {
+        let text: String = row.try_get(column)?;
+        Decimal::from_str(&text).map_err(|e| sqlx::Error::ColumnDecode {
+            index: column.to_string(),
+            source: Box::new(e),
+        })
+    }
+
+    fn text(value: Option<Decimal>) -> Option<String> {
+        value.map(|v| v.to_string())
+    }
+
+    impl Database {
+        /// Connect to `config.url` and create missing tables.
+        pub async fn connect(config: &DatabaseConfig) -> Result<Self, sqlx::Error> {
+            sqlx::any::install_default_drivers();
+            // SQLite creates the file (mode=rwc) but not its directory
+            if let Some(path) = config.url.strip_prefix("sqlite://") {
+                let path = std::path::Path::new(path.split('?').next().unwrap_or_default());
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            let pool = AnyPoolOptions::new()
+                .max_connections(config.max_connections.max(1))
+                .connect(&config.url)
+                .await?;
+            for statement in SCHEMA {
+                sqlx::query(statement).execute(&pool).await?;
+            }
+            Ok(Self { pool })
+        }
+
+        /// Insert records from `rx` until every `StorageWriter` is dropped.
+        pub fn start(&self, mut rx: UnboundedReceiver<StorageRecord>) {
+            let db = self.clone();
+            tokio::spawn(async move {
+                info!("🗄️ [DB] Storage writer started");
+                while let Some(record) = rx.recv().await {
+                    if let Err(e) = db.insert(&record).await {
+                        error!("❌ [DB] Failed to store {} row: {}", record.table(), e);
+                    }
+                }
+            });
+        }
+
+        pub async fn insert(&self, record: &StorageRecord) -> Result<(), sqlx::Error> {
+            let query = match record {
+                StorageRecord::Order(e) => sqlx::query(
+                    "INSERT INTO orders (ts, symbol, action, order_id, status, qty, price, \
+                     notional, notes) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                )
+                .bind(e.ts.clone())
+                .bind(e.symbol.clone())
+                .bind(e.action.clone())
+                .bind(e.order_id.clone())
+                .bind(e.status.clone())
+                .bind(text(e.qty))
+                .bind(text(e.price))
+                .bind(text(e.notional))
+                .bind(e.notes.clone()),
+                StorageRecord::Fill(f) => sqlx::query(
+                    "INSERT INTO fills (ts, symbol, side, order_id, qty, price, strategy_id, \
+                     correlation_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                )
+                .bind(f.ts.clone())
+                .bind(f.symbol.clone())
+                .bind(f.side.clone())
+                .bind(f.order_id.clone())
+                .bind(f.qty.to_string())
+                .bind(f.price.to_string())
+                .bind(f.strategy_id.clone())
+                .bind(f.correlation_id.clone()),
+                StorageRecord::ClosedTrade(StoredTrade { trade, strategy_id }) => sqlx::query(
+                    "INSERT INTO closed_trades (symbol, buy_time, sell_time, buy_price, \
+                     sell_price, qty, pnl, pnl_percent, strategy_id) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                )
+                .bind(trade.symbol.clone())
+                .bind(trade.buy_time.clone())
+                .bind(trade.sell_time.clone())
+                .bind(trade.buy_price.to_string())
+                .bind(trade.sell_price.to_string())
+                .bind(trade.qty.to_string())
+                .bind(trade.pnl.to_string())
+                .bind(trade.pnl_percent)
+                .bind(strategy_id.clone()),
+                StorageRecord::Equity(s) => sqlx::query(
+                    "INSERT INTO equity_snapshots (ts, realized_pnl, unrealized_pnl, equity) \
+                     VALUES ($1, $2, $3, $4)",
+                )
+                .bind(s.ts.clone())
+                .bind(s.realized_pnl.to_string())
+                .bind(s.unrealized_pnl.to_string())
+                .bind(s.equity.to_string()),
+                StorageRecord::LlmCall(c) => sqlx::query(
+                    "INSERT INTO llm_calls (ts, agent, model, prompt_tokens, completion_tokens, \
+                     cost_usd) VALUES ($1, $2, $3, $4, $5, $6)",
+                )
+                .bind(c.ts.clone())
+                .bind(c.agent.clone())
+                .bind(c.model.clone())
+                .bind(c.prompt_tokens as i64)
+                .bind(c.completion_tokens as i64)
+                .bind(c.cost_usd),
+            };
+            query.execute(&self.pool).await?;
+            Ok(())
+        }
+
+        /// Rows count of `table` with `column` in `[start, end)`.
+        async fn count(
+            &self,
+            table: &str,
+            column: &str,
+            (start, end): &(String, String),
+        ) -> Result<u64, sqlx::Error> {
+            let row = sqlx::query(&format!(
+                "SELECT COUNT(*) AS n FROM {} WHERE {} >= $1 AND {} < $2",
+                table, column, column
+            ))
+            .bind(start.clone())
+            .bind(end.clone())
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(row.try_get::<i64, _>("n")?.max(0) as u64)
+        }
+
+        /// Orders, fills, closed trades, equity samples and LLM spend of `range`.
+        pub async fn report(&self, range: DateRange) -> Result<StoredReport, sqlx::Error> {
+            let bounds = range.bounds();
+            let mut report = StoredReport {
+                from: range.from.map(|d| d.to_string()),
+                to: range.to.map(|d| d.to_string()),
+                orders: self.count("orders", "ts", &bounds).await?,
+                fills: self.count("fills", "ts", &bounds).await?,
+                ..StoredReport::default()
+            };
+
+            let rows = sqlx::query(
+                "SELECT symbol, buy_time, sell_time, buy_price, sell_price, qty, pnl, \
+                 pnl_percent, strategy_id FROM closed_trades \
+                 WHERE sell_time >= $1 AND sell_time < $2 ORDER BY sell_time",
+            )
+            .bind(bounds.0.clone())
+            .bind(bounds.1.clone())
+            .fetch_all(&self.pool)
+            .await?;
+            for row in &rows {
+                let trade = ClosedTrade {
+                    symbol: row.try_get("symbol")?,
+                    buy_time: row.try_get("buy_time")?,
+                    sell_time: row.try_get("sell_time")?,
+                    buy_price: decimal(row, "buy_price")?,
+                    sell_price: decimal(row, "sell_price")?,
+                    qty: decimal(row, "qty")?,
+                    pnl: decimal(row, "pnl")?,
+                    pnl_percent: row.try_get("pnl_percent")?,
+                    origin: None,
+                    metadata: HashMap::new(),
+                };
+                report.total_realized_pnl += trade.pnl;
+                if trade.pnl > Decimal::ZERO {
+                    report.winning_trades += 1;
+                } else {
+                    report.losing_trades += 1;
+                }
+                report.closed_trades.push(StoredTrade {
+                    trade,
+                    strategy_id: row.try_get("strategy_id")?,
+                });
+            }
+
+            let rows = sqlx::query(
+                "SELECT ts, realized_pnl, unrealized_pnl, equity FROM equity_snapshots \
+                 WHERE ts >= $1 AND ts < $2 ORDER BY ts",
+            )
+            .bind(bounds.0.clone())
+            .bind(bounds.1.clone())
+            .fetch_all(&self.pool)
+            .await?;
+            for row in &rows {
+                report.equity_curve.push(EquitySample {
+                    ts: row.try_get("ts")?,
+                    realized_pnl: decimal(row, "realized_pnl")?,
+                    unrealized_pnl: decimal(row, "unrealized_pnl")?,
+                    equity: decimal(row, "equity")?,
+                });
+            }
+
+            let row = sqlx::query(
+                "SELECT COUNT(*) AS calls, \
+                 CAST(COALESCE(SUM(prompt_tokens), 0) AS BIGINT) AS prompt_tokens, \
+                 CAST(COALESCE(SUM(completion_tokens), 0) AS BIGINT) AS completion_tokens, \
+                 CAST(COALESCE(SUM(cost_usd), 0) AS DOUBLE PRECISION) AS cost_usd FROM llm_calls \
+                 WHERE ts >= $1 AND ts < $2",
+            )
+            .bind(bounds.0.clone())
+            .bind(bounds.1.clone())
+            .fetch_one(&self.pool)
+            .await?;
+            report.llm = UsageTotals {
+                calls: row.try_get::<i64, _>("calls")?.max(0) as u64,
+                prompt_tokens: row.try_get::<i64, _>("prompt_tokens")?.max(0) as u64,
+                completion_tokens: row.try_get::<i64, _>("completion_tokens")?.max(0) as u64,
+                cost_usd: row.try_get("cost_usd")?,
+            };
+            Ok(report)
+        }
+    }
+}
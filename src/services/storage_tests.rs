@@ -0,0 +1,138 @@
+//! Unit tests for database storage: report date ranges and the records the
+//! trade reporter hands to the writer.
+
+#[cfg(test)]
+mod storage_tests {
+    use crate::events::{Event, ExecutionReport};
+    use crate::services::reporting::TradeReporter;
+    use crate::services::storage::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn exec(side: &str, price: i64) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: format!("{}-1", side),
+            status: "filled".to_string(),
+            side: side.to_string(),
+            price: Some(Decimal::from(price)),
+            qty: Some(Decimal::from(2)),
+            signal_price: None,
+            limit_price: None,
+            strategy_id: Some("hft".to_string()),
+            correlation_id: None,
+            ..Default::default()
+        })
+    }
+
+    // ============= DateRange Tests =============
+
+    #[test]
+    fn test_date_range_bounds_cover_whole_days() {
+        let range = DateRange::parse(Some("2026-10-01"), Some("2026-10-16")).unwrap();
+        assert_eq!(range.from, NaiveDate::from_ymd_opt(2026, 10, 1));
+        let (start, end) = range.bounds();
+        assert_eq!((start.as_str(), end.as_str()), ("2026-10-01", "2026-10-17"));
+        // RFC 3339 timestamps of the last day sort inside the bounds
+        assert!("2026-10-16T23:59:59.999+00:00" < end.as_str());
+        assert!("2026-10-01T00:00:00+00:00" >= start.as_str());
+
+        let open = DateRange::parse(None, None).unwrap();
+        assert!(open.is_unbounded());
+        assert_eq!(open.bounds(), (String::new(), "9999".to_string()));
+    }
+
+    #[test]
+    fn test_date_range_rejects_bad_input() {
+        assert!(DateRange::parse(Some("16/10/2026"), None).is_err());
+        assert!(DateRange::parse(Some("2026-10-16"), Some("2026-10-01")).is_err());
+    }
+
+    // ============= Reporter Records Tests =============
+
+    #[test]
+    fn test_reporter_sends_rows_instead_of_log_file() {
+        let dir = std::env::temp_dir().join(format!("autohedge_storage_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (writer, mut rx) = StorageWriter::channel();
+        let reporter = TradeReporter::new(dir.join("trades.jsonl")).with_storage(Some(writer));
+
+        reporter.on_event(exec("buy", 100));
+        reporter.on_event(exec("sell", 110));
+
+        let tables: Vec<&str> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|r| {
+                if let StorageRecord::ClosedTrade(t) = &r {
+                    assert_eq!(t.trade.pnl, Decimal::from(20));
+                    assert_eq!(t.strategy_id.as_deref(), Some("hft"));
+                }
+                r.table()
+            })
+            .collect();
+        assert_eq!(
+            tables,
+            ["fills", "orders", "fills", "closed_trades", "orders"]
+        );
+        assert!(!dir.join("trades.jsonl").exists());
+    }
+
+    // ============= Database Tests =============
+
+    #[cfg(feature = "database")]
+    #[tokio::test]
+    async fn test_sqlite_report_round_trip() {
+        use crate::config::DatabaseConfig;
+        use crate::llm::usage::LlmCall;
+        use crate::services::reporting::EquitySample;
+
+        let db = Database::connect(&DatabaseConfig {
+            enabled: true,
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+        })
+        .await
+        .unwrap();
+
+        let (writer, mut rx) = StorageWriter::channel();
+        let reporter = TradeReporter::new("unused.jsonl".into()).with_storage(Some(writer));
+        reporter.on_event(exec("buy", 100));
+        reporter.on_event(exec("sell", 90));
+        while let Ok(record) = rx.try_recv() {
+            db.insert(&record).await.unwrap();
+        }
+        db.insert(&StorageRecord::Equity(EquitySample {
+            ts: "2020-01-01T00:00:00+00:00".to_string(),
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            equity: Decimal::from(1000),
+        }))
+        .await
+        .unwrap();
+        db.insert(&StorageRecord::LlmCall(LlmCall {
+            ts: chrono::Utc::now().to_rfc3339(),
+            agent: "director".to_string(),
+            model: "m".to_string(),
+            prompt_tokens: 100,
+            completion_tokens: 20,
+            cost_usd: 0.5,
+        }))
+        .await
+        .unwrap();
+
+        let report = db.report(DateRange::default()).await.unwrap();
+        assert_eq!((report.orders, report.fills), (2, 2));
+        assert_eq!(report.closed_trades.len(), 1);
+        assert_eq!(report.total_realized_pnl, Decimal::from(-20));
+        assert_eq!((report.winning_trades, report.losing_trades), (0, 1));
+        assert_eq!(report.equity_curve.len(), 1);
+        assert_eq!(report.llm.calls, 1);
+        assert_eq!(report.llm.prompt_tokens, 100);
+
+        // The 2020 equity sample is outside a range starting today
+        let today = chrono::Utc::now().date_naive().to_string();
+        let range = DateRange::parse(Some(&today), None).unwrap();
+        let report = db.report(range).await.unwrap();
+        assert!(report.equity_curve.is_empty());
+        assert_eq!(report.closed_trades.len(), 1);
+    }
+}
@@ -1,14 +1,36 @@
+use crate::agents::memory::{director_decision, MemoryStore};
 use crate::agents::{director::DirectorAgent, quant::QuantAgent, Agent};
 use crate::bus::EventBus;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ChatterModule};
+use crate::data::indicators::{IndicatorKind, IndicatorValue};
 use crate::data::store::{MarketStore, Quote};
-use crate::events::{AnalysisSignal, Event, MarketEvent};
+use crate::events::{
+    AnalysisSignal, ControlCommand, DegradationLevel, Event, MarketEvent, MarketRegime, TradeOrigin,
+};
 use crate::llm::LLMQueue;
+use crate::logging::new_correlation_id;
+use crate::money;
+use crate::services::admission::admit_director;
+use crate::services::llm_fallback::{fallback_signal, rule_director};
+use crate::services::market_hours::TradingCalendar;
+use crate::services::sentiment::{blocking_sentiment, fresh_sentiment};
+use crate::services::shadow::shadow_config;
+use crate::services::strategy_registry::{Strategy, StrategyContext, StrategyRegistry};
+use crate::services::strategy_workers::{EvalJob, SymbolWorkers};
+use async_trait::async_trait;
 use dashmap::DashMap;
-use std::collections::VecDeque;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info, warn};
 
+/// Quotes shown to the LLM agents in the quote history table
+const QUOTE_TABLE_LEN: usize = 50;
+
+/// Non-quote market events queued per symbol worker before new ones are dropped
+const WORKER_QUEUE_LEN: usize = 256;
+
 #[derive(Clone)]
 struct SymbolCooldown {
     quotes_remaining: usize,
@@ -27,6 +49,170 @@ struct HybridGateState {
     cooldown_quotes_remaining: usize,
     allowed: bool,
     last_reason: Option<String>,
+    /// Director decision behind the current gate state
+    director_decision_id: Option<String>,
+    /// Gate last set by the rule-based fallback rather than the Director
+    rule_based: bool,
+}
+
+/// Built-in "llm" mode: Director -> Quant pipeline with a per-symbol no-trade cooldown.
+#[derive(Default)]
+pub struct LlmStrategy {
+    cooldowns: Arc<DashMap<String, SymbolCooldown>>,
+}
+
+#[async_trait]
+impl Strategy for LlmStrategy {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    async fn on_quote(
+        &self,
+        symbol: &str,
+        _bid: f64,
+        _ask: f64,
+        ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        // Check cooldown status
+        if let Some(mut cooldown) = self.cooldowns.get_mut(symbol) {
+            if cooldown.quotes_remaining > 0 {
+                cooldown.quotes_remaining -= 1;
+                if cooldown.quotes_remaining == 0 {
+                    info!(
+                        "⏰ [COOLDOWN] {} cooldown expired. Ready for analysis.",
+                        symbol
+                    );
+                }
+                return None;
+            }
+        }
+        // Cleanup expired cooldowns lazily
+        self.cooldowns
+            .remove_if(symbol, |_, c| c.quotes_remaining == 0);
+
+        // Warm-up Check (symbols with priors may start early at reduced size)
+        let history_len = ctx.store.quote_count(symbol);
+        if history_len < ctx.config.warmup_count
+            && ctx.config.warmup_prior(symbol, history_len).is_none()
+        {
+            return None;
+        }
+
+        // LLM failing: the indicator rules decide instead (`llm_fallback`)
+        if ctx.llm.is_degraded() {
+            return match fallback_signal(&ctx.store, symbol, &ctx.config) {
+                Ok(signal) => {
+                    info!("🧭 [STRATEGY] {} for {}", signal.thesis, symbol);
+                    Some(signal)
+                }
+                Err(thesis) => {
+                    self.cooldowns.insert(
+                        symbol.to_string(),
+                        SymbolCooldown {
+                            quotes_remaining: ctx.config.no_trade_cooldown_quotes,
+                        },
+                    );
+                    if !ctx.config.chatter_for(ChatterModule::Strategy).is_low() {
+                        info!(
+                            "🔴 [STRATEGY] {} for {}. Cooldown: {} quotes.",
+                            thesis, symbol, ctx.config.no_trade_cooldown_quotes
+                        );
+                    }
+                    None
+                }
+            };
+        }
+
+        // Shed load when the LLM queue is backed up and this symbol ranks low
+        if !admit_director(symbol, &ctx.store, &ctx.llm, &ctx.config) {
+            return None;
+        }
+
+        StrategyEngine::analyze_symbol_llm(
+            symbol.to_string(),
+            ctx.store.clone(),
+            ctx.llm.clone(),
+            self.cooldowns.clone(),
+            ctx.config.clone(),
+            ctx.memory.clone(),
+        )
+        .await
+    }
+}
+
+/// Built-in "hft" mode: spread-filtered short-horizon momentum.
+#[derive(Default)]
+pub struct HftStrategy {
+    state: Arc<DashMap<String, HftSymbolState>>,
+}
+
+#[async_trait]
+impl Strategy for HftStrategy {
+    fn name(&self) -> &str {
+        "hft"
+    }
+
+    async fn on_quote(
+        &self,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+        ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        StrategyEngine::evaluate_hft(
+            symbol.to_string(),
+            bid,
+            ask,
+            ctx.store.clone(),
+            self.state.clone(),
+            ctx.config.clone(),
+        )
+        .await
+    }
+}
+
+/// Built-in "hybrid" mode: HFT entries behind a periodically refreshed LLM gate.
+#[derive(Default)]
+pub struct HybridStrategy {
+    hft_state: Arc<DashMap<String, HftSymbolState>>,
+    gate: Arc<DashMap<String, HybridGateState>>,
+}
+
+#[async_trait]
+impl Strategy for HybridStrategy {
+    fn name(&self) -> &str {
+        "hybrid"
+    }
+
+    async fn on_quote(
+        &self,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+        ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        StrategyEngine::evaluate_hybrid(
+            symbol.to_string(),
+            bid,
+            ask,
+            ctx.clone(),
+            self.hft_state.clone(),
+            self.gate.clone(),
+        )
+        .await
+    }
+}
+
+impl StrategyRegistry {
+    /// Registry pre-populated with the built-in "llm", "hft" and "hybrid" strategies.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("llm", Arc::new(LlmStrategy::default()));
+        registry.register("hft", Arc::new(HftStrategy::default()));
+        registry.register("hybrid", Arc::new(HybridStrategy::default()));
+        registry
+    }
 }
 
 pub struct StrategyEngine {
@@ -34,6 +220,7 @@ pub struct StrategyEngine {
     market_store: MarketStore,
     llm: LLMQueue,
     config: AppConfig,
+    registry: StrategyRegistry,
 }
 
 impl StrategyEngine {
@@ -48,130 +235,282 @@ impl StrategyEngine {
             market_store,
             llm,
             config,
+            registry: StrategyRegistry::with_builtins(),
         }
     }
 
+    /// Replace the strategy registry (e.g. to add custom strategies).
+    pub fn with_registry(mut self, registry: StrategyRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
     pub async fn start(&self) {
-        let mut rx = self.event_bus.subscribe();
-        let store_clone = self.market_store.clone();
-        let llm_clone = self.llm.clone();
+        let mut rx = self.event_bus.subscribe("strategy");
         let bus_clone = self.event_bus.clone();
-        let config_clone = self.config.clone();
-
-        // Cooldown tracking for LLM mode: symbol -> quotes_remaining
-        let cooldowns: Arc<DashMap<String, SymbolCooldown>> = Arc::new(DashMap::new());
-
-        // Per-symbol state for HFT mode
-        let hft_state: Arc<DashMap<String, HftSymbolState>> = Arc::new(DashMap::new());
+        let mut ctx = Arc::new(StrategyContext {
+            store: self.market_store.clone(),
+            llm: self.llm.clone(),
+            config: self.config.clone(),
+            memory: MemoryStore::new(&self.config.agent_memory),
+        });
 
-        // Per-symbol gate state for HYBRID mode
-        let hybrid_gate: Arc<DashMap<String, HybridGateState>> = Arc::new(DashMap::new());
+        let registry = self.registry.clone();
+        let mut news_strategies = Self::news_strategies(&registry, &ctx.config);
+        // A/B test: live signals are tagged live_id; a second, independent set
+        // of built-in strategies runs under the shadow config
+        let ab = &self.config.ab_test;
+        let live_id = ab.enabled.then(|| ab.live_id.clone());
+        let shadow_id = ab.shadow_id.clone();
+        let shadow_registry = StrategyRegistry::with_builtins();
+        let mut shadow_ctx = if ab.enabled {
+            Self::shadow_context(&ctx)
+        } else {
+            None
+        };
 
         tokio::spawn(async move {
             info!(
                 "🧠 Strategy Engine Started (mode: {})",
-                config_clone.strategy_mode
+                ctx.config.strategy_mode.to_lowercase()
             );
-            while let Ok(event) = rx.recv().await {
-                if let Event::Market(market_event) = event {
-                    let (symbol, bid, ask) = match &market_event {
-                        MarketEvent::Quote {
-                            symbol, bid, ask, ..
-                        } => (symbol.clone(), *bid, *ask),
-                        MarketEvent::Trade { symbol, price, .. } => {
-                            (symbol.clone(), *price, *price)
+            if let (Some(live), Some(shadow)) = (&live_id, &shadow_ctx) {
+                info!(
+                    "🧪 [A/B] {} trades live, {} in shadow (mode: {})",
+                    live,
+                    shadow_id,
+                    shadow.config.strategy_mode.to_lowercase()
+                );
+            }
+            for symbol in &ctx.config.symbols {
+                let mode = ctx.config.strategy_mode_for(symbol);
+                if mode != ctx.config.strategy_mode.to_lowercase() {
+                    info!("🧠 [STRATEGY] {} uses mode {}", symbol, mode);
+                }
+            }
+            // Strategy per symbol (its symbol_overrides mode, else strategy_mode)
+            let mut strategies: HashMap<String, Arc<dyn Strategy>> = HashMap::new();
+            let mut shadow_strategies: HashMap<String, Arc<dyn Strategy>> = HashMap::new();
+            let mut last_news: Option<Value> = None;
+            // Halted/delisted symbols are not evaluated (no point spending LLM calls)
+            let mut exit_only: HashSet<String> = HashSet::new();
+            // Degradation ladder: nothing is evaluated once halted
+            let mut health_level = DegradationLevel::Full;
+            // Symbols paused by the operator are not evaluated either
+            let mut paused: HashSet<String> = HashSet::new();
+            // Symbols dropped from `symbols` by a config reload (still streamed)
+            let mut removed: HashSet<String> = HashSet::new();
+            // Symbols skipped for a degraded feed (logged on each transition)
+            let mut degraded_feeds: HashSet<String> = HashSet::new();
+            // Stocks: nothing is evaluated outside market hours
+            let calendar = TradingCalendar::new(&ctx.config.trading_mode, &ctx.config.market_hours);
+            let mut market_open = true;
+            // One evaluation task per symbol, events in arrival order
+            let mut workers = SymbolWorkers::new(bus_clone.clone(), WORKER_QUEUE_LEN);
+            let shutdown = bus_clone.shutdown().clone();
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.draining() => {
+                        info!("🧠 Strategy Engine stopped (shutdown)");
+                        return;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if let Event::SymbolStatus(status) = &event {
+                    if ctx.config.halt_detection.enabled {
+                        if status.exit_only {
+                            exit_only.insert(status.symbol.clone());
+                        } else {
+                            exit_only.remove(&status.symbol);
                         }
-                    };
-
-                    let mode = config_clone.strategy_mode.to_lowercase();
-
-                    if mode == "hft" {
-                        let bus = bus_clone.clone();
-                        let tracker = hft_state.clone();
-                        let config = config_clone.clone();
-                        tokio::spawn(async move {
-                            Self::evaluate_hft(symbol, bid, ask, bus, tracker, config).await;
-                        });
+                    }
+                    continue;
+                }
+                if let Event::Health(health) = &event {
+                    health_level = health.level;
+                    continue;
+                }
+                if let Event::Config(update) = &event {
+                    for symbol in &ctx.config.symbols {
+                        if !update.config.symbols.contains(symbol) {
+                            removed.insert(symbol.clone());
+                        }
+                    }
+                    for symbol in &update.config.symbols {
+                        removed.remove(symbol);
+                    }
+                    ctx = Arc::new(StrategyContext {
+                        config: (*update.config).clone(),
+                        ..(*ctx).clone()
+                    });
+                    // Modes may have changed; strategies keep their own state
+                    strategies.clear();
+                    news_strategies = Self::news_strategies(&registry, &ctx.config);
+                    if shadow_ctx.is_some() {
+                        shadow_ctx = Self::shadow_context(&ctx);
+                        shadow_strategies.clear();
+                    }
+                    continue;
+                }
+                match &event {
+                    Event::Control(ControlCommand::PauseSymbol { symbol }) => {
+                        paused.insert(symbol.clone());
                         continue;
                     }
-
-                    if mode == "hybrid" {
-                        let bus = bus_clone.clone();
-                        let config = config_clone.clone();
-                        let store = store_clone.clone();
-                        let llm = llm_clone.clone();
-                        let hft_tracker = hft_state.clone();
-                        let gate = hybrid_gate.clone();
-                        tokio::spawn(async move {
-                            Self::evaluate_hybrid(
-                                symbol,
-                                bid,
-                                ask,
-                                bus,
-                                store,
-                                llm,
-                                hft_tracker,
-                                gate,
-                                config,
-                            )
-                            .await;
-                        });
+                    Event::Control(ControlCommand::ResumeSymbol { symbol }) => {
+                        paused.remove(symbol);
                         continue;
                     }
-
-                    // Default: LLM pipeline ("llm" or anything else)
-
-                    // Check cooldown status
-                    if let Some(mut cooldown) = cooldowns.get_mut(&symbol) {
-                        if cooldown.quotes_remaining > 0 {
-                            cooldown.quotes_remaining -= 1;
-                            if cooldown.quotes_remaining == 0 {
-                                info!(
-                                    "⏰ [COOLDOWN] {} cooldown expired. Ready for analysis.",
-                                    symbol
-                                );
-                                // DashMap doesn't need explicit remove here if we just check > 0
-                                // But to clean up memory we can remove.
-                                // However, get_mut holds a lock shard.
-                                // We can't remove while holding a reference.
-                                // We can just set to 0.
+                    _ => {}
+                }
+                // Fills and rejections become the outcome of the live decisions
+                if let Event::Execution(report) = &event {
+                    if report.strategy_id == live_id {
+                        ctx.memory.record_execution(report);
+                    }
+                    continue;
+                }
+                // Realized results feed the Director's win/loss record
+                if let Event::TradeClosed(trade) = &event {
+                    if trade.strategy_id == live_id {
+                        ctx.memory.record_trade_closed(trade);
+                    }
+                    continue;
+                }
+                if health_level == DegradationLevel::Halted {
+                    continue;
+                }
+                if calendar.accepts_entries(chrono::Utc::now()) != market_open {
+                    market_open = !market_open;
+                    if market_open {
+                        info!("🕒 [MARKET-HOURS] Market open - resuming strategy evaluation");
+                    } else {
+                        info!("🕒 [MARKET-HOURS] Market closed - pausing strategy evaluation");
+                    }
+                }
+                if !market_open {
+                    continue;
+                }
+                if let Event::Market(market_event) = event {
+                    let symbol = market_event.symbol().to_string();
+                    if matches!(
+                        market_event,
+                        MarketEvent::Regime { .. } | MarketEvent::HftThresholds { .. }
+                    ) || exit_only.contains(&symbol)
+                        || paused.contains(&symbol)
+                        || removed.contains(&symbol)
+                    {
+                        continue;
+                    }
+                    if ctx.config.data_quality.skip_degraded {
+                        match ctx.store.data_quality.degraded(&symbol) {
+                            Some(reason) => {
+                                if degraded_feeds.insert(symbol.clone()) {
+                                    warn!(
+                                        "📉 [DATA-QUALITY] Skipping {} - feed degraded ({})",
+                                        symbol, reason
+                                    );
+                                }
+                                continue;
+                            }
+                            None => {
+                                if degraded_feeds.remove(&symbol) {
+                                    info!(
+                                        "✅ [DATA-QUALITY] {} feed recovered - evaluating again",
+                                        symbol
+                                    );
+                                }
                             }
-                            // drop(cooldown) happens automatically
-                            continue;
                         }
                     }
-                    // Cleanup expired cooldowns lazily or just leave them as 0.
-                    // Or use remove_if.
-                    if let Some(cooldown) = cooldowns.get(&symbol) {
-                        if cooldown.quotes_remaining == 0 {
-                            cooldowns.remove(&symbol);
-                        }
+                    // Strategies only trade the regimes `regime.strategies` allows
+                    let regime = ctx.store.get_regime(&symbol);
+                    let regime = regime.as_ref().map(MarketRegime::as_str);
+
+                    // Dispatch news that arrived since the last market event
+                    let news = ctx.store.get_latest_news();
+                    let start = last_news
+                        .as_ref()
+                        .and_then(|seen| news.iter().rposition(|n| n == seen))
+                        .map(|i| i + 1)
+                        .unwrap_or(0);
+                    if start < news.len() {
+                        last_news = news.last().cloned();
+                        let fresh = news[start..].to_vec();
+                        let news_strategies = news_strategies.clone();
+                        let ctx = ctx.clone();
+                        let bus = bus_clone.clone();
+                        let live_id = live_id.clone();
+                        tokio::spawn(async move {
+                            for item in &fresh {
+                                for strategy in &news_strategies {
+                                    if let Some(mut signal) = strategy.on_news(item, &ctx).await {
+                                        signal.strategy_id = live_id.clone();
+                                        signal.correlation_id = Some(new_correlation_id());
+                                        signal.origin.get_or_insert_with(|| {
+                                            TradeOrigin::new(strategy.name())
+                                        });
+                                        bus.publish(Event::Signal(signal)).ok();
+                                    }
+                                }
+                            }
+                        });
                     }
 
-                    // Warm-up Check
-                    let history = store_clone.get_quote_history(&symbol);
-                    if history.len() < config_clone.warmup_count {
+                    let strategy = strategies
+                        .entry(symbol.clone())
+                        .or_insert_with(|| {
+                            Self::resolve(&registry, &ctx.config.strategy_mode_for(&symbol))
+                        })
+                        .clone();
+                    // Symbol under /debug diagnostics: evaluate with verbose chatter
+                    let diagnostics = bus_clone.diagnostics();
+                    let ctx = if diagnostics.level(&symbol).is_some() {
+                        let mut verbose = (*ctx).clone();
+                        diagnostics.apply(&symbol, &mut verbose.config);
+                        Arc::new(verbose)
+                    } else {
+                        ctx.clone()
+                    };
+                    let shadow_ctx = shadow_ctx.as_ref().filter(|shadow| {
+                        shadow
+                            .config
+                            .regime
+                            .allows(&shadow.config.strategy_mode_for(&symbol), regime)
+                    });
+                    if let Some(shadow) = shadow_ctx {
+                        let shadow_strategy = shadow_strategies
+                            .entry(symbol.clone())
+                            .or_insert_with(|| {
+                                Self::resolve(
+                                    &shadow_registry,
+                                    &shadow.config.strategy_mode_for(&symbol),
+                                )
+                            })
+                            .clone();
+                        workers.dispatch(EvalJob {
+                            strategy: shadow_strategy,
+                            event: market_event.clone(),
+                            ctx: shadow.clone(),
+                            strategy_id: Some(shadow_id.clone()),
+                        });
+                    }
+                    if !ctx
+                        .config
+                        .regime
+                        .allows(&ctx.config.strategy_mode_for(&symbol), regime)
+                    {
                         continue;
                     }
-
-                    // Spawn Analysis Task (Parallel)
-                    let store = store_clone.clone();
-                    let llm = llm_clone.clone();
-                    let bus = bus_clone.clone();
-                    let symbol_clone = symbol.clone();
-                    let cooldowns_clone = cooldowns.clone();
-                    let config = config_clone.clone();
-
-                    tokio::spawn(async move {
-                        Self::analyze_symbol_llm(
-                            symbol_clone,
-                            store,
-                            llm,
-                            bus,
-                            cooldowns_clone,
-                            config,
-                        )
-                        .await;
+                    workers.dispatch(EvalJob {
+                        strategy,
+                        event: market_event,
+                        ctx,
+                        strategy_id: live_id.clone(),
                     });
                 }
             }
@@ -179,16 +518,67 @@ impl StrategyEngine {
         });
     }
 
+    /// Context of the A/B shadow strategy; None (shadow disabled) when the
+    /// `ab_test.shadow` tunables do not apply.
+    fn shadow_context(ctx: &StrategyContext) -> Option<Arc<StrategyContext>> {
+        match shadow_config(&ctx.config) {
+            // The shadow's decisions are its own
+            Ok(config) => Some(Arc::new(StrategyContext {
+                memory: MemoryStore::new(&config.agent_memory),
+                config,
+                ..ctx.clone()
+            })),
+            Err(e) => {
+                warn!("[STRATEGY] A/B shadow disabled (ab_test.shadow: {})", e);
+                None
+            }
+        }
+    }
+
+    /// Strategy registered under `mode`. Unknown modes fall back to the LLM pipeline.
+    fn resolve(registry: &StrategyRegistry, mode: &str) -> Arc<dyn Strategy> {
+        registry.get(mode).unwrap_or_else(|| {
+            warn!(
+                "[STRATEGY] Unknown strategy_mode '{}' (registered: {:?}); using llm",
+                mode,
+                registry.names()
+            );
+            registry
+                .get("llm")
+                .unwrap_or_else(|| Arc::new(LlmStrategy::default()))
+        })
+    }
+
+    /// One instance of every strategy in use (global mode plus per-symbol
+    /// overrides), so news reaches each of them once.
+    fn news_strategies(registry: &StrategyRegistry, config: &AppConfig) -> Vec<Arc<dyn Strategy>> {
+        let mut modes = vec![config.strategy_mode.to_lowercase()];
+        for symbol in &config.symbols {
+            let mode = config.strategy_mode_for(symbol);
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+        let mut strategies: Vec<Arc<dyn Strategy>> = Vec::new();
+        for mode in modes {
+            let strategy = Self::resolve(registry, &mode);
+            if !strategies.iter().any(|s| Arc::ptr_eq(s, &strategy)) {
+                strategies.push(strategy);
+            }
+        }
+        strategies
+    }
+
     async fn analyze_symbol_llm(
         symbol: String,
         store: MarketStore,
         llm: LLMQueue,
-        bus: EventBus,
         cooldowns: Arc<DashMap<String, SymbolCooldown>>,
         config: AppConfig,
-    ) {
+        memory: MemoryStore,
+    ) -> Option<AnalysisSignal> {
         // Prepare Data
-        let history = store.get_quote_history(&symbol);
+        let history = store.quote_window(&symbol, QUOTE_TABLE_LEN);
         let news = store.get_latest_news();
         let market_data_str = Self::format_quote_history_table(&history);
 
@@ -208,19 +598,40 @@ impl StrategyEngine {
             format!("Recent News: {:?}", headlines)
         };
 
-        let combined_data = format!("{}\n{}", market_data_str, news_summary);
+        let sentiment_summary = if config.sentiment.enabled {
+            match fresh_sentiment(&store, &symbol, &config, chrono::Utc::now()) {
+                Some(s) => format!("\nNews Sentiment: {:+.2} ({})", s.score, s.headline),
+                None => String::new(),
+            }
+        } else {
+            String::new()
+        };
+
+        let combined_data = format!(
+            "{}\n{}\n{}{}",
+            market_data_str,
+            Self::format_indicator_summary(&store, &symbol),
+            news_summary,
+            sentiment_summary
+        );
 
         // 1. Director
-        let director = DirectorAgent;
-        let director_input = format!("Symbol: {}, Market Context: {}", symbol, combined_data);
+        let director = DirectorAgent::new(&config, &symbol);
+        let mut director_input = format!("Symbol: {}, Market Context: {}", symbol, combined_data);
+        if let Some(recent) = memory.prompt_context(director.name(), &symbol) {
+            director_input = format!("{}\n\n{}", director_input, recent);
+        }
 
         let director_response = match director.run(&director_input, &llm).await {
             Ok(res) => res,
             Err(e) => {
                 error!("❌ Director Failed for {}: {}", symbol, e);
-                return;
+                return None;
             }
         };
+        let (decision, reasoning) = director_decision(&director_response);
+        memory.record(director.name(), &symbol, &decision, &reasoning);
+        let director_decision_id = new_correlation_id();
 
         let lower_resp = director_response.to_lowercase();
         if lower_resp.contains("no_trade")
@@ -239,16 +650,17 @@ impl StrategyEngine {
                 "🔴 [STRATEGY] No trade opportunity for {}. Cooldown: {} quotes.",
                 symbol, config.no_trade_cooldown_quotes
             );
-            return;
+            return None;
         }
 
         info!(
+            director_decision_id = %director_decision_id,
             "🟢 [STRATEGY] Opportunity found for {}! Running Quant...",
             symbol
         );
 
         // 2. Quant
-        let quant = QuantAgent;
+        let quant = QuantAgent::new(&config, &symbol);
         let quant_input = format!(
             "Thesis: {}\n\nMarket Data:\n{}",
             director_response, combined_data
@@ -258,11 +670,13 @@ impl StrategyEngine {
             Ok(res) => res,
             Err(e) => {
                 error!("❌ Quant Failed for {}: {}", symbol, e);
-                return;
+                return None;
             }
         };
 
+        let quant_decision_id = new_correlation_id();
         info!(
+            quant_decision_id = %quant_decision_id,
             "📈 [STRATEGY] Quant Analysis for {}: {}",
             symbol, quant_response
         );
@@ -274,39 +688,77 @@ impl StrategyEngine {
             confidence: 0.0,
             thesis: director_response,
             market_context: combined_data,
+            strategy_id: None,
+            correlation_id: None,
+            origin: Some(TradeOrigin {
+                mode: "llm".to_string(),
+                director_decision_id: Some(director_decision_id),
+                quant_decision_id: Some(quant_decision_id),
+            }),
+            quant_analysis: Some(quant_response),
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            metadata: HashMap::new(),
         };
 
-        bus.publish(Event::Signal(signal)).ok();
+        Some(signal)
     }
 
     async fn evaluate_hft(
         symbol: String,
         bid: f64,
         ask: f64,
-        bus: EventBus,
+        store: MarketStore,
         state: Arc<DashMap<String, HftSymbolState>>,
         config: AppConfig,
-    ) {
+    ) -> Option<AnalysisSignal> {
         if bid <= 0.0 || ask <= 0.0 || ask < bid {
-            if config.chatter_level.to_lowercase() == "verbose" {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 warn!(
                     "[HFT] Skip {}: invalid quote bid={} ask={}",
                     symbol, bid, ask
                 );
             }
-            return;
+            return None;
         }
 
+        // Thresholds tuned by `adaptive_hft`, or as configured
+        let (base_min_edge_bps, max_spread_bps) = match store.get_hft_thresholds(&symbol) {
+            Some(t) => (t.min_edge_bps, t.max_spread_bps),
+            None => (config.hft.min_edge_bps, config.hft.max_spread_bps),
+        };
+
         let mid = (bid + ask) / 2.0;
         let spread_bps = ((ask - bid) / mid) * 10_000.0;
-        if spread_bps > config.hft.max_spread_bps {
-            if config.chatter_level.to_lowercase() == "verbose" {
+        if spread_bps > max_spread_bps {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!(
                     "[HFT] Skip {}: spread_bps={:.2} > max_spread_bps={:.2} (bid={:.8} ask={:.8})",
-                    symbol, spread_bps, config.hft.max_spread_bps, bid, ask
+                    symbol, spread_bps, max_spread_bps, bid, ask
                 );
             }
-            return;
+            return None;
+        }
+
+        // Warmup bypass: sanity-check the live spread against the prior
+        let history_len = store.quote_count(&symbol);
+        let prior = config.warmup_prior(&symbol, history_len).cloned();
+        if let Some(prior) = &prior {
+            let max_warmup_spread = prior.expected_spread_bps * config.warmup.spread_tolerance;
+            if spread_bps > max_warmup_spread {
+                if config.chatter_for(ChatterModule::Strategy).is_verbose() {
+                    info!(
+                        "[HFT] Skip {}: warmup spread_bps={:.2} > prior {:.2} x{:.1}",
+                        symbol,
+                        spread_bps,
+                        prior.expected_spread_bps,
+                        config.warmup.spread_tolerance
+                    );
+                }
+                return None;
+            }
         }
 
         let mut entry = state
@@ -324,25 +776,25 @@ impl StrategyEngine {
         }
 
         if entry.quotes_since_eval < config.hft.evaluate_every_quotes {
-            if config.chatter_level.to_lowercase() == "verbose" {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!(
                     "[HFT] Debounce {}: {}/{} quotes collected (mid={:.8})",
                     symbol, entry.quotes_since_eval, config.hft.evaluate_every_quotes, mid
                 );
             }
             entry.last_mid = Some(mid);
-            return;
+            return None;
         }
         entry.quotes_since_eval = 0;
 
         // Simple momentum edge: compare current mid to mid N steps back.
         let lookback = 10usize.min(entry.mids.len().saturating_sub(1));
         if lookback == 0 {
-            if config.chatter_level.to_lowercase() == "verbose" {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!("[HFT] Skip {}: insufficient history for lookback", symbol);
             }
             entry.last_mid = Some(mid);
-            return;
+            return None;
         }
         let past = entry
             .mids
@@ -354,14 +806,31 @@ impl StrategyEngine {
         entry.last_mid = Some(mid);
         // drop(entry); // DashMap RefMut is dropped here
 
-        if edge_bps < config.hft.min_edge_bps {
-            if config.chatter_level.to_lowercase() == "verbose" {
+        // While warming up on priors, momentum must also clear the prior noise level
+        let min_edge_bps = match &prior {
+            Some(p) => base_min_edge_bps.max(p.volatility_bps),
+            None => base_min_edge_bps,
+        };
+
+        if edge_bps < min_edge_bps {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!(
                     "[HFT] Skip {}: edge_bps={:.2} < min_edge_bps={:.2} (mid={:.8} past={:.8})",
-                    symbol, edge_bps, config.hft.min_edge_bps, mid, past
+                    symbol, edge_bps, min_edge_bps, mid, past
+                );
+            }
+            return None;
+        }
+
+        // Fresh bearish news vetoes momentum entries
+        if let Some(s) = blocking_sentiment(&store, &symbol, &config, chrono::Utc::now()) {
+            if !config.chatter_for(ChatterModule::Strategy).is_low() {
+                info!(
+                    "[HFT] Skip {}: sentiment {:+.2} < {:+.2} ({})",
+                    symbol, s.score, config.sentiment.block_below, s.headline
                 );
             }
-            return;
+            return None;
         }
 
         // If momentum is positive and spread is acceptable, emit a buy signal.
@@ -371,9 +840,9 @@ impl StrategyEngine {
         // This is the key "when HFT will buy" log.
         // - In normal: only log on entry.
         // - In verbose: include more details.
-        if config.chatter_level.to_lowercase() != "low" {
+        if !config.chatter_for(ChatterModule::Strategy).is_low() {
             info!("[HFT] BUY trigger {}: edge_bps={:.2} >= min_edge_bps={:.2}, spread_bps={:.2} <= max_spread_bps={:.2} | entry(mid)={:.8} tp={:.8} sl={:.8}",
-                  symbol, edge_bps, config.hft.min_edge_bps, spread_bps, config.hft.max_spread_bps, mid, tp, sl);
+                  symbol, edge_bps, min_edge_bps, spread_bps, max_spread_bps, mid, tp, sl);
         }
 
         let thesis = format!(
@@ -387,30 +856,46 @@ impl StrategyEngine {
             confidence: 1.0,
             thesis: thesis.clone(),
             market_context: format!("tp={:.8}, sl={:.8}", tp, sl),
+            strategy_id: None,
+            correlation_id: None,
+            origin: None,
+            quant_analysis: None,
+            entry_limit: None,
+            take_profit: Some(money::dec(tp)),
+            stop_loss: Some(money::dec(sl)),
+            valid_until: (config.hft.signal_ttl_ms > 0).then(|| {
+                (chrono::Utc::now()
+                    + chrono::Duration::milliseconds(config.hft.signal_ttl_ms as i64))
+                .to_rfc3339()
+            }),
+            metadata: HashMap::new(),
         };
 
-        bus.publish(Event::Signal(signal)).ok();
+        Some(signal)
     }
 
     async fn evaluate_hybrid(
         symbol: String,
         bid: f64,
         ask: f64,
-        bus: EventBus,
-        store: MarketStore,
-        llm: LLMQueue,
+        ctx: StrategyContext,
         hft_state: Arc<DashMap<String, HftSymbolState>>,
         gate: Arc<DashMap<String, HybridGateState>>,
-        config: AppConfig,
-    ) {
+    ) -> Option<AnalysisSignal> {
+        let StrategyContext {
+            store,
+            llm,
+            config,
+            memory,
+        } = ctx;
         if bid <= 0.0 || ask <= 0.0 || ask < bid {
-            if config.chatter_level.to_lowercase() == "verbose" {
+            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 warn!(
                     "[HYBRID] Skip {}: invalid quote bid={} ask={}",
                     symbol, bid, ask
                 );
             }
-            return;
+            return None;
         }
 
         // Gate bookkeeping (quote based)
@@ -425,6 +910,8 @@ impl StrategyEngine {
                     cooldown_quotes_remaining: 0,
                     allowed: true,
                     last_reason: None,
+                    director_decision_id: None,
+                    rule_based: false,
                 });
 
             if entry.cooldown_quotes_remaining > 0 {
@@ -443,7 +930,7 @@ impl StrategyEngine {
 
             currently_allowed = entry.allowed && entry.cooldown_quotes_remaining == 0;
 
-            if !currently_allowed && config.chatter_level.to_lowercase() == "verbose" {
+            if !currently_allowed && config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!(
                     "[HYBRID] Gate closed for {} (cooldown_remaining={}, quotes_until_refresh={})",
                     symbol, entry.cooldown_quotes_remaining, entry.quotes_until_refresh
@@ -451,24 +938,60 @@ impl StrategyEngine {
             }
         }
 
+        // LLM failing: the indicator rules refresh the gate (`llm_fallback`)
+        if should_refresh && llm.is_degraded() {
+            should_refresh = false;
+            let (allowed, reason) = rule_director(&store, &symbol, &config.llm_fallback);
+            let mut entry = gate.entry(symbol.clone()).or_default();
+            entry.allowed = allowed;
+            entry.last_reason = Some(reason.clone());
+            entry.director_decision_id = None;
+            entry.rule_based = true;
+            if !allowed {
+                entry.cooldown_quotes_remaining = config.hybrid.no_trade_cooldown_quotes;
+            }
+            if !config.chatter_for(ChatterModule::Strategy).is_low() {
+                info!(
+                    "[HYBRID] Gate for {} set by fallback rules: {}",
+                    symbol, reason
+                );
+            }
+        }
+
+        if should_refresh && !admit_director(&symbol, &store, &llm, &config) {
+            // Keep the previous gate; retry at the next refresh interval
+            should_refresh = false;
+        }
+
         if should_refresh {
-            let history = store.get_quote_history(&symbol);
-            if history.len() >= config.warmup_count {
-                if config.chatter_level.to_lowercase() != "low" {
+            let history_len = store.quote_count(&symbol);
+            if history_len >= config.warmup_count
+                || config.warmup_prior(&symbol, history_len).is_some()
+            {
+                if !config.chatter_for(ChatterModule::Strategy).is_low() {
                     info!(
                         "[HYBRID] Refreshing LLM gate for {} (history_len={})",
-                        symbol,
-                        history.len()
+                        symbol, history_len
                     );
                 }
 
-                let combined_data = Self::format_quote_history_table(&history);
-                let director = DirectorAgent;
-                let director_input =
+                let history = store.quote_window(&symbol, QUOTE_TABLE_LEN);
+                let combined_data = format!(
+                    "{}\n{}",
+                    Self::format_quote_history_table(&history),
+                    Self::format_indicator_summary(&store, &symbol)
+                );
+                let director = DirectorAgent::new(&config, &symbol);
+                let mut director_input =
                     format!("Symbol: {}, Market Context: {}", symbol, combined_data);
+                if let Some(recent) = memory.prompt_context(director.name(), &symbol) {
+                    director_input = format!("{}\n\n{}", director_input, recent);
+                }
 
                 match director.run(&director_input, &llm).await {
                     Ok(resp) => {
+                        let (decision, reasoning) = director_decision(&resp);
+                        memory.record(director.name(), &symbol, &decision, &reasoning);
                         let lower = resp.to_lowercase();
                         let allowed = !(lower.contains("no_trade")
                             || lower.contains("no trade")
@@ -477,6 +1000,8 @@ impl StrategyEngine {
                         let mut entry = gate.entry(symbol.clone()).or_default();
                         entry.allowed = allowed;
                         entry.last_reason = Some(resp.clone());
+                        entry.director_decision_id = Some(new_correlation_id());
+                        entry.rule_based = false;
 
                         if !allowed {
                             entry.cooldown_quotes_remaining =
@@ -485,17 +1010,17 @@ impl StrategyEngine {
                                 "[HYBRID] Gate CLOSED for {} by director. Cooldown {} quotes.",
                                 symbol, config.hybrid.no_trade_cooldown_quotes
                             );
-                            if config.chatter_level.to_lowercase() == "verbose" {
+                            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                                 warn!(
                                     "[HYBRID] Director response (no_trade) for {}: {}",
                                     symbol, resp
                                 );
                             }
                         } else {
-                            if config.chatter_level.to_lowercase() != "low" {
+                            if !config.chatter_for(ChatterModule::Strategy).is_low() {
                                 info!("[HYBRID] Gate OPEN for {} by director.", symbol);
                             }
-                            if config.chatter_level.to_lowercase() == "verbose" {
+                            if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                                 info!(
                                     "[HYBRID] Director response (allowed) for {}: {}",
                                     symbol, resp
@@ -510,12 +1035,10 @@ impl StrategyEngine {
                         );
                     }
                 }
-            } else if config.chatter_level.to_lowercase() == "verbose" {
+            } else if config.chatter_for(ChatterModule::Strategy).is_verbose() {
                 info!(
                     "[HYBRID] Skip gate refresh for {}: warmup not met (history_len={}, warmup={})",
-                    symbol,
-                    history.len(),
-                    config.warmup_count
+                    symbol, history_len, config.warmup_count
                 );
             }
         }
@@ -528,10 +1051,60 @@ impl StrategyEngine {
         }
 
         if !currently_allowed {
-            return;
+            return None;
         }
 
-        Self::evaluate_hft(symbol, bid, ask, bus, hft_state, config).await;
+        let (director_decision_id, rule_based) = gate
+            .get(&symbol)
+            .map(|s| (s.director_decision_id.clone(), s.rule_based))
+            .unwrap_or_default();
+        let mut signal = Self::evaluate_hft(symbol, bid, ask, store, hft_state, config).await?;
+        signal.origin = Some(TradeOrigin {
+            mode: if rule_based {
+                TradeOrigin::FALLBACK.to_string()
+            } else {
+                "hybrid".to_string()
+            },
+            director_decision_id,
+            quant_decision_id: None,
+        });
+        Some(signal)
+    }
+
+    fn format_indicator_summary(store: &MarketStore, symbol: &str) -> String {
+        let mut parts = Vec::new();
+        for (label, kind, period) in [
+            ("SMA(20)", IndicatorKind::Sma, 20),
+            ("EMA(20)", IndicatorKind::Ema, 20),
+            ("RSI(14)", IndicatorKind::Rsi, 14),
+            ("MACD(12,26,9)", IndicatorKind::Macd, 9),
+            ("BB(20,2)", IndicatorKind::Bollinger, 20),
+            ("ATR(14)", IndicatorKind::Atr, 14),
+            ("VWAP(20)", IndicatorKind::Vwap, 20),
+        ] {
+            let Some(value) = store.get_indicator(symbol, kind, period) else {
+                continue;
+            };
+            let text = match value {
+                IndicatorValue::Single(v) => format!("{}={:.8}", label, v),
+                IndicatorValue::Macd {
+                    macd,
+                    signal,
+                    histogram,
+                } => format!("{}={:.8}/{:.8}/{:.8}", label, macd, signal, histogram),
+                IndicatorValue::Bands {
+                    upper,
+                    middle,
+                    lower,
+                } => format!("{}={:.8}/{:.8}/{:.8}", label, upper, middle, lower),
+            };
+            parts.push(text);
+        }
+        if parts.is_empty() {
+            "Indicators: warming up.".to_string()
+        } else {
+            format!("Indicators: {}", parts.join(", "))
+        }
     }
 
     fn format_quote_history_table(history: &[Quote]) -> String {
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::agents::memory::MemoryStore;
+use crate::config::AppConfig;
+use crate::data::store::MarketStore;
+use crate::events::AnalysisSignal;
+use crate::llm::LLMQueue;
+
+/// Shared handles a strategy may use while evaluating an event.
+#[derive(Clone)]
+pub struct StrategyContext {
+    pub store: MarketStore,
+    pub llm: LLMQueue,
+    pub config: AppConfig,
+    /// Recent LLM decisions per agent and symbol (`agent_memory`)
+    pub memory: MemoryStore,
+}
+
+/// A pluggable trading strategy. Returned signals are published on the bus
+/// by `StrategyEngine`; strategies keep any per-symbol state internally.
+#[async_trait]
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn on_quote(
+        &self,
+        symbol: &str,
+        bid: f64,
+        ask: f64,
+        ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal>;
+
+    /// Trades are treated as a zero-spread quote unless overridden.
+    async fn on_trade(
+        &self,
+        symbol: &str,
+        price: f64,
+        _size: f64,
+        ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        self.on_quote(symbol, price, price, ctx).await
+    }
+
+    async fn on_news(&self, _item: &Value, _ctx: &StrategyContext) -> Option<AnalysisSignal> {
+        None
+    }
+
+    /// A news sentiment score (-1..1) for `symbol`; the latest is also in `ctx.store`.
+    async fn on_sentiment(
+        &self,
+        _symbol: &str,
+        _score: f64,
+        _ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        None
+    }
+
+    /// A perpetual futures funding update: `rate` per interval (positive means
+    /// longs pay) and the current mark price.
+    async fn on_funding_rate(
+        &self,
+        _symbol: &str,
+        _rate: f64,
+        _mark_price: f64,
+        _ctx: &StrategyContext,
+    ) -> Option<AnalysisSignal> {
+        None
+    }
+}
+
+/// Name -> strategy lookup used to resolve `strategy_mode`.
+#[derive(Clone, Default)]
+pub struct StrategyRegistry {
+    strategies: HashMap<String, Arc<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) a strategy under a case-insensitive name.
+    pub fn register(&mut self, name: &str, strategy: Arc<dyn Strategy>) {
+        self.strategies.insert(name.to_lowercase(), strategy);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Strategy>> {
+        self.strategies.get(&name.to_lowercase()).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.strategies.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
@@ -0,0 +1,77 @@
+//! Unit tests for the strategy registry and plugin trait.
+
+#[cfg(test)]
+mod strategy_registry_tests {
+    use crate::events::AnalysisSignal;
+    use crate::services::strategy_registry::*;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+
+    struct AlwaysBuy;
+
+    #[async_trait]
+    impl Strategy for AlwaysBuy {
+        fn name(&self) -> &str {
+            "always_buy"
+        }
+
+        async fn on_quote(
+            &self,
+            symbol: &str,
+            _bid: f64,
+            ask: f64,
+            _ctx: &StrategyContext,
+        ) -> Option<AnalysisSignal> {
+            Some(AnalysisSignal {
+                symbol: symbol.to_string(),
+                signal: "buy".to_string(),
+                confidence: 1.0,
+                thesis: "test".to_string(),
+                market_context: format!("ask={}", ask),
+                strategy_id: None,
+                correlation_id: None,
+                origin: None,
+                quant_analysis: None,
+                entry_limit: None,
+                take_profit: None,
+                stop_loss: None,
+                valid_until: None,
+                ..Default::default()
+            })
+        }
+    }
+
+    // ============= Registry Tests =============
+
+    #[test]
+    fn test_registry_empty() {
+        let registry = StrategyRegistry::new();
+        assert!(registry.get("llm").is_none());
+        assert!(registry.names().is_empty());
+    }
+
+    #[test]
+    fn test_register_custom_strategy() {
+        let mut registry = StrategyRegistry::new();
+        registry.register("Always_Buy", Arc::new(AlwaysBuy));
+
+        let s = registry.get("always_buy").expect("registered");
+        assert_eq!(s.name(), "always_buy");
+        assert!(registry.get("ALWAYS_BUY").is_some());
+    }
+
+    #[test]
+    fn test_builtins_registered() {
+        let registry = StrategyRegistry::with_builtins();
+        assert_eq!(registry.names(), vec!["hft", "hybrid", "llm"]);
+        assert_eq!(registry.get("hft").unwrap().name(), "hft");
+    }
+
+    #[test]
+    fn test_register_replaces_builtin() {
+        let mut registry = StrategyRegistry::with_builtins();
+        registry.register("hft", Arc::new(AlwaysBuy));
+        assert_eq!(registry.get("hft").unwrap().name(), "always_buy");
+        assert_eq!(registry.names().len(), 3);
+    }
+}
@@ -0,0 +1,205 @@
+//! Per-symbol strategy evaluation workers.
+//!
+//! `StrategyEngine` hands every market event it evaluates to `SymbolWorkers`,
+//! which runs one task per symbol (per strategy id, so an A/B shadow never
+//! delays the live strategy). A symbol's events are evaluated one at a time in
+//! arrival order, and a quote storm costs one task per symbol instead of one
+//! per quote. Quotes go to a single slot that each newer quote replaces, so
+//! they are never dropped and a busy worker evaluates only the newest one.
+//! Trades, sentiment and funding updates are never coalesced: they wait in a
+//! bounded queue, and events arriving at a full queue are dropped and counted.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{mpsc, Notify};
+use tracing::warn;
+
+use crate::bus::EventBus;
+use crate::events::{Event, MarketEvent, TradeOrigin};
+use crate::logging::new_correlation_id;
+use crate::services::strategy_registry::{Strategy, StrategyContext};
+
+/// One market event to evaluate with the strategy and context current when
+/// it arrived.
+pub struct EvalJob {
+    pub strategy: Arc<dyn Strategy>,
+    pub event: MarketEvent,
+    pub ctx: Arc<StrategyContext>,
+    /// Tag for published signals (A/B test id)
+    pub strategy_id: Option<String>,
+}
+
+/// The newest quote not yet evaluated, replaced in place by later quotes.
+#[derive(Default)]
+struct QuoteSlot {
+    /// (arrival number, job)
+    latest: Mutex<Option<(u64, EvalJob)>>,
+    ready: Notify,
+}
+
+impl QuoteSlot {
+    fn put(&self, seq: u64, job: EvalJob) {
+        *self.latest.lock().unwrap() = Some((seq, job));
+        self.ready.notify_one();
+    }
+
+    /// The pending quote, if it arrived before `seq` (or at all, for None).
+    fn take_before(&self, seq: Option<u64>) -> Option<EvalJob> {
+        let mut latest = self.latest.lock().unwrap();
+        match (latest.as_ref(), seq) {
+            (Some((at, _)), Some(seq)) if *at > seq => None,
+            (Some(_), _) => latest.take().map(|(_, job)| job),
+            (None, _) => None,
+        }
+    }
+}
+
+struct Worker {
+    /// Trades, sentiment and funding updates, with their arrival number
+    tx: mpsc::Sender<(u64, EvalJob)>,
+    quote: Arc<QuoteSlot>,
+    /// Arrival counter, to evaluate the pending quote in order
+    seq: u64,
+    /// Events dropped on a full queue since the worker last reported
+    dropped: Arc<AtomicU64>,
+}
+
+/// Worker tasks by (strategy id, symbol), started on a symbol's first event.
+/// Dropping it closes the queues; workers finish what was queued and exit.
+pub struct SymbolWorkers {
+    bus: EventBus,
+    capacity: usize,
+    workers: HashMap<(Option<String>, String), Worker>,
+}
+
+impl SymbolWorkers {
+    pub fn new(bus: EventBus, capacity: usize) -> Self {
+        Self {
+            bus,
+            capacity: capacity.max(1),
+            workers: HashMap::new(),
+        }
+    }
+
+    /// Hand `job` to its symbol's worker. Quotes always replace the pending
+    /// quote; other events are queued. False when the queue was full and the
+    /// job was dropped.
+    pub fn dispatch(&mut self, job: EvalJob) -> bool {
+        let key = (job.strategy_id.clone(), job.event.symbol().to_string());
+        let (bus, capacity) = (&self.bus, self.capacity);
+        let worker = self.workers.entry(key).or_insert_with(|| {
+            let (tx, rx) = mpsc::channel(capacity);
+            let quote = Arc::new(QuoteSlot::default());
+            let dropped = Arc::new(AtomicU64::new(0));
+            tokio::spawn(run(rx, quote.clone(), dropped.clone(), bus.clone()));
+            Worker {
+                tx,
+                quote,
+                seq: 0,
+                dropped,
+            }
+        });
+        worker.seq += 1;
+        if matches!(job.event, MarketEvent::Quote { .. }) {
+            worker.quote.put(worker.seq, job);
+            return true;
+        }
+        match worker.tx.try_send((worker.seq, job)) {
+            Ok(()) => true,
+            Err(_) => {
+                worker.dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+}
+
+async fn run(
+    mut rx: mpsc::Receiver<(u64, EvalJob)>,
+    quote: Arc<QuoteSlot>,
+    dropped: Arc<AtomicU64>,
+    bus: EventBus,
+) {
+    loop {
+        let mut batch = Vec::new();
+        tokio::select! {
+            job = rx.recv() => match job {
+                Some(job) => batch.push(job),
+                None => break,
+            },
+            _ = quote.ready.notified() => {}
+        }
+        while let Ok(job) = rx.try_recv() {
+            batch.push(job);
+        }
+        let lost = dropped.swap(0, Ordering::Relaxed);
+        if lost > 0 {
+            if let Some((_, job)) = batch.first() {
+                warn!(
+                    "⚠️ [STRATEGY] {} queue full - {} event(s) dropped",
+                    job.event.symbol(),
+                    lost
+                );
+            }
+        }
+        for (seq, job) in batch {
+            if let Some(earlier) = quote.take_before(Some(seq)) {
+                evaluate(earlier, &bus).await;
+            }
+            evaluate(job, &bus).await;
+        }
+        if let Some(latest) = quote.take_before(None) {
+            evaluate(latest, &bus).await;
+        }
+    }
+    // The queue closed: a quote still pending is the last event
+    if let Some(latest) = quote.take_before(None) {
+        evaluate(latest, &bus).await;
+    }
+}
+
+/// Evaluate one market event and publish its signal (tagged `strategy_id`
+/// and a new `correlation_id`), if any.
+async fn evaluate(job: EvalJob, bus: &EventBus) {
+    let EvalJob {
+        strategy,
+        event,
+        ctx,
+        strategy_id,
+    } = job;
+    let signal = match &event {
+        MarketEvent::Quote {
+            symbol, bid, ask, ..
+        } => strategy.on_quote(symbol, *bid, *ask, &ctx).await,
+        MarketEvent::Trade {
+            symbol,
+            price,
+            size,
+            ..
+        } => strategy.on_trade(symbol, *price, *size, &ctx).await,
+        MarketEvent::Sentiment { symbol, score, .. } => {
+            strategy.on_sentiment(symbol, *score, &ctx).await
+        }
+        MarketEvent::FundingRate {
+            symbol,
+            rate,
+            mark_price,
+            ..
+        } => {
+            strategy
+                .on_funding_rate(symbol, *rate, *mark_price, &ctx)
+                .await
+        }
+        MarketEvent::Regime { .. } | MarketEvent::HftThresholds { .. } => None,
+    };
+    if let Some(mut signal) = signal {
+        signal.strategy_id = strategy_id;
+        signal.correlation_id = Some(new_correlation_id());
+        signal
+            .origin
+            .get_or_insert_with(|| TradeOrigin::new(strategy.name()));
+        bus.publish(Event::Signal(signal)).ok();
+    }
+}
@@ -0,0 +1,238 @@
+//! Unit tests for per-symbol strategy workers - the latest-quote slot,
+//! ordering and the bounded queue.
+
+#[cfg(test)]
+mod strategy_workers_tests {
+    use crate::agents::memory::MemoryStore;
+    use crate::bus::{BusReceiver, EventBus};
+    use crate::config::{AppConfig, LlmRetryConfig};
+    use crate::data::store::MarketStore;
+    use crate::events::{AnalysisSignal, Event, MarketEvent};
+    use crate::llm::{LLMClient, LLMQueue};
+    use crate::services::strategy_registry::{Strategy, StrategyContext};
+    use crate::services::strategy_workers::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn quote(symbol: &str, bid: f64) -> MarketEvent {
+        MarketEvent::Quote {
+            symbol: symbol.to_string(),
+            bid,
+            ask: bid + 1.0,
+            timestamp: String::new(),
+        }
+    }
+
+    fn trade(symbol: &str, price: f64) -> MarketEvent {
+        MarketEvent::Trade {
+            symbol: symbol.to_string(),
+            price,
+            size: 1.0,
+            timestamp: String::new(),
+        }
+    }
+
+    // ============= Worker Tests =============
+
+    /// Signals on every event, recording the order it saw them in.
+    struct Recorder {
+        seen: Mutex<Vec<f64>>,
+    }
+
+    impl Recorder {
+        fn signal(&self, symbol: &str, value: f64) -> Option<AnalysisSignal> {
+            self.seen.lock().unwrap().push(value);
+            Some(AnalysisSignal {
+                symbol: symbol.to_string(),
+                signal: "buy".to_string(),
+                confidence: 1.0,
+                thesis: value.to_string(),
+                market_context: String::new(),
+                strategy_id: None,
+                correlation_id: None,
+                origin: None,
+                quant_analysis: None,
+                entry_limit: None,
+                take_profit: None,
+                stop_loss: None,
+                valid_until: None,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Strategy for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        async fn on_quote(
+            &self,
+            symbol: &str,
+            bid: f64,
+            _ask: f64,
+            _ctx: &StrategyContext,
+        ) -> Option<AnalysisSignal> {
+            self.signal(symbol, bid)
+        }
+
+        async fn on_trade(
+            &self,
+            symbol: &str,
+            price: f64,
+            _size: f64,
+            _ctx: &StrategyContext,
+        ) -> Option<AnalysisSignal> {
+            self.signal(symbol, -price)
+        }
+    }
+
+    fn test_config() -> AppConfig {
+        serde_yaml::from_str(
+            r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD", "ETH/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 50
+llm_queue_size: 100
+llm_max_concurrent: 3
+no_trade_cooldown_quotes: 10
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 5
+  min_edge_bps: 10.0
+  take_profit_bps: 50.0
+  stop_loss_bps: 25.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+llm:
+  api_key: null
+  base_url: null
+  model: "test-model"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "https://paper-api.alpaca.markets"
+exit_on_quotes: true
+"#,
+        )
+        .unwrap()
+    }
+
+    fn context() -> Arc<StrategyContext> {
+        let config = test_config();
+        Arc::new(StrategyContext {
+            store: MarketStore::new(50),
+            llm: LLMQueue::new(
+                LLMClient::new(String::new(), None, "test-model".to_string()),
+                1,
+                1,
+                LlmRetryConfig::default(),
+            ),
+            memory: MemoryStore::new(&config.agent_memory),
+            config,
+        })
+    }
+
+    fn job(recorder: &Arc<Recorder>, event: MarketEvent) -> EvalJob {
+        EvalJob {
+            strategy: recorder.clone(),
+            event,
+            ctx: context(),
+            strategy_id: Some("live".to_string()),
+        }
+    }
+
+    /// Theses of the next `n` signals on the bus.
+    async fn signals(rx: &mut BusReceiver, n: usize) -> Vec<String> {
+        let mut theses = Vec::new();
+        while theses.len() < n {
+            let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .expect("signal expected")
+                .unwrap();
+            if let Event::Signal(signal) = event {
+                assert_eq!(signal.strategy_id.as_deref(), Some("live"));
+                assert!(signal.correlation_id.is_some());
+                theses.push(signal.thesis);
+            }
+        }
+        theses
+    }
+
+    #[tokio::test]
+    async fn test_worker_evaluates_in_order_and_keeps_the_newest_quote() {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe("test");
+        let recorder = Arc::new(Recorder {
+            seen: Mutex::new(Vec::new()),
+        });
+        let mut workers = SymbolWorkers::new(bus, 16);
+        // Queued before the worker first runs: one backlog
+        for event in [
+            quote("BTC/USD", 1.0),
+            quote("BTC/USD", 2.0),
+            trade("BTC/USD", 3.0),
+            quote("BTC/USD", 4.0),
+            quote("BTC/USD", 5.0),
+        ] {
+            assert!(workers.dispatch(job(&recorder, event)));
+        }
+        // Quote 2 was already superseded by quote 5 when the worker ran
+        assert_eq!(signals(&mut rx, 2).await, ["-3", "5"]);
+        assert_eq!(*recorder.seen.lock().unwrap(), [-3.0, 5.0]);
+
+        // The worker stays up for later events
+        workers.dispatch(job(&recorder, quote("BTC/USD", 6.0)));
+        assert_eq!(signals(&mut rx, 1).await, ["6"]);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_only_that_symbol() {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe("test");
+        let recorder = Arc::new(Recorder {
+            seen: Mutex::new(Vec::new()),
+        });
+        let mut workers = SymbolWorkers::new(bus, 2);
+        assert!(workers.dispatch(job(&recorder, trade("BTC/USD", 1.0))));
+        assert!(workers.dispatch(job(&recorder, trade("BTC/USD", 2.0))));
+        assert!(!workers.dispatch(job(&recorder, trade("BTC/USD", 3.0))));
+        assert!(workers.dispatch(job(&recorder, trade("ETH/USD", 4.0))));
+
+        let mut theses = signals(&mut rx, 3).await;
+        theses.sort();
+        assert_eq!(theses, ["-1", "-2", "-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_quotes_are_never_dropped_on_a_full_queue() {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe("test");
+        let recorder = Arc::new(Recorder {
+            seen: Mutex::new(Vec::new()),
+        });
+        let mut workers = SymbolWorkers::new(bus, 1);
+        assert!(workers.dispatch(job(&recorder, quote("BTC/USD", 1.0))));
+        assert!(workers.dispatch(job(&recorder, trade("BTC/USD", 2.0))));
+        assert!(!workers.dispatch(job(&recorder, trade("BTC/USD", 3.0))));
+        assert!(workers.dispatch(job(&recorder, quote("BTC/USD", 4.0))));
+        assert!(workers.dispatch(job(&recorder, quote("BTC/USD", 5.0))));
+
+        // The newest quote survives the full queue; the dropped trade is lost
+        assert_eq!(signals(&mut rx, 2).await, ["-2", "5"]);
+        assert_eq!(*recorder.seen.lock().unwrap(), [-2.0, 5.0]);
+    }
+}
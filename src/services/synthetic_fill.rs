@@ -0,0 +1,144 @@
+//! Synthetic take-profit fills for paper trading.
+//!
+//! Paper venues fill limit orders against their own thin books, so a TP limit
+//! sell on an illiquid pair can sit unfilled while the market trades well
+//! above it. With `synthetic_fills.enabled` the quote-driven monitor treats a
+//! TP as filled once price has stayed `trade_through_bps` above its limit for
+//! `hold_secs`: the position is closed locally and the order is remembered.
+//! Every `reconcile_secs` the remembered orders are looked up on the exchange;
+//! a real fill confirms them, a cancel/expiry hands the position back to the
+//! monitor (which re-places its exit), anything else keeps waiting.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::config::SyntheticFillConfig;
+use crate::money::dec;
+use crate::services::position_monitor::PositionInfo;
+
+/// Exchange verdict on a synthetically filled order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reconciled {
+    /// The venue filled it too
+    Confirmed,
+    /// The order is gone unfilled; the position is still held
+    Restore,
+    /// Still working on the venue
+    Pending,
+}
+
+impl Reconciled {
+    pub fn from_status(status: &str) -> Self {
+        match status.to_lowercase().as_str() {
+            "filled" => Reconciled::Confirmed,
+            "canceled" | "cancelled" | "expired" | "rejected" => Reconciled::Restore,
+            _ => Reconciled::Pending,
+        }
+    }
+}
+
+/// A TP leg filled locally, awaiting the exchange's verdict.
+#[derive(Clone, Debug)]
+pub struct SyntheticFill {
+    pub order_id: String,
+    /// The position as it was when the fill was assumed
+    pub position: PositionInfo,
+    pub limit_price: Decimal,
+    pub filled_at: Instant,
+}
+
+/// Trade-through bookkeeping per resting TP order, owned by the monitor loop.
+pub struct SyntheticFillWatch {
+    config: SyntheticFillConfig,
+    active: bool,
+    /// When price first traded through each TP limit (reset when it falls back)
+    through_since: HashMap<String, Instant>,
+    filled: HashMap<String, SyntheticFill>,
+}
+
+impl SyntheticFillWatch {
+    /// Active when enabled, and with `paper_only` only on a paper venue.
+    pub fn new(config: &SyntheticFillConfig, paper_venue: bool) -> Self {
+        let active = config.enabled && (paper_venue || !config.paper_only);
+        if config.enabled && !active {
+            warn!(
+                "⚠️ [SYNTHETIC] synthetic_fills.enabled ignored - not a paper venue (paper_only)"
+            );
+        } else if active {
+            info!(
+                "🧪 [SYNTHETIC] TP limits traded through by {}bps for {}s are filled locally",
+                config.trade_through_bps, config.hold_secs
+            );
+        }
+        Self {
+            config: config.clone(),
+            active,
+            through_since: HashMap::new(),
+            filled: HashMap::new(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// A tick at `price` for the TP sell `order_id` resting at `limit`. True
+    /// once price has held at least `trade_through_bps` above the limit for
+    /// `hold_secs`.
+    pub fn observe(
+        &mut self,
+        order_id: &str,
+        limit: Decimal,
+        price: Decimal,
+        now: Instant,
+    ) -> bool {
+        if !self.active || limit <= Decimal::ZERO {
+            return false;
+        }
+        let through =
+            limit * (Decimal::ONE + dec(self.config.trade_through_bps) / Decimal::from(10_000));
+        if price < through {
+            self.through_since.remove(order_id);
+            return false;
+        }
+        let since = *self
+            .through_since
+            .entry(order_id.to_string())
+            .or_insert(now);
+        now.saturating_duration_since(since) >= Duration::from_secs(self.config.hold_secs)
+    }
+
+    /// Remember `position` as closed by a synthetic fill of `order_id`.
+    pub fn record_fill(
+        &mut self,
+        order_id: &str,
+        position: PositionInfo,
+        limit_price: Decimal,
+        now: Instant,
+    ) {
+        self.through_since.remove(order_id);
+        self.filled.insert(
+            order_id.to_string(),
+            SyntheticFill {
+                order_id: order_id.to_string(),
+                position,
+                limit_price,
+                filled_at: now,
+            },
+        );
+    }
+
+    /// Order ids awaiting reconciliation.
+    pub fn unreconciled(&self) -> Vec<String> {
+        self.filled.keys().cloned().collect()
+    }
+
+    /// Stop tracking `order_id` once the exchange has settled it.
+    pub fn resolve(&mut self, order_id: &str) -> Option<SyntheticFill> {
+        self.through_since.remove(order_id);
+        self.filled.remove(order_id)
+    }
+}
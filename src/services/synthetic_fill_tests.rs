@@ -0,0 +1,122 @@
+//! Unit tests for synthetic TP fills on paper venues.
+
+#[cfg(test)]
+mod synthetic_fill_tests {
+    use crate::config::SyntheticFillConfig;
+    use crate::money::dec;
+    use crate::services::position_monitor::PositionInfo;
+    use crate::services::synthetic_fill::*;
+    use rust_decimal::Decimal;
+    use std::time::{Duration, Instant};
+
+    fn config() -> SyntheticFillConfig {
+        SyntheticFillConfig {
+            enabled: true,
+            trade_through_bps: 10.0,
+            hold_secs: 5,
+            ..SyntheticFillConfig::default()
+        }
+    }
+
+    fn position(symbol: &str) -> PositionInfo {
+        PositionInfo {
+            symbol: symbol.to_string(),
+            entry_price: dec(100.0),
+            qty: dec(1.0),
+            filled_qty: dec(1.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(98.0),
+            take_profit: dec(102.0),
+            entry_time: "2026-03-02T10:00:00Z".to_string(),
+            side: "buy".to_string(),
+            is_closing: false,
+            open_order_id: Some("tp1".to_string()),
+            last_recreate_attempt: None,
+            recreate_attempts: 0,
+            highest_price: dec(100.0),
+            trailing_stop_active: false,
+            trailing_stop_price: dec(98.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
+        }
+    }
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    // ============= Activation Tests =============
+
+    #[test]
+    fn test_paper_only_needs_paper_venue() {
+        assert!(SyntheticFillWatch::new(&config(), true).is_active());
+        assert!(!SyntheticFillWatch::new(&config(), false).is_active());
+
+        let anywhere = SyntheticFillConfig {
+            paper_only: false,
+            ..config()
+        };
+        assert!(SyntheticFillWatch::new(&anywhere, false).is_active());
+        assert!(!SyntheticFillWatch::new(&SyntheticFillConfig::default(), true).is_active());
+    }
+
+    // ============= Trade-Through Tests =============
+
+    #[test]
+    fn test_fills_after_holding_through_limit() {
+        let start = Instant::now();
+        let mut watch = SyntheticFillWatch::new(&config(), true);
+        // 10bps above 102.00 is 102.102
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.05), start));
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.2), start + secs(1)));
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.3), start + secs(5)));
+        assert!(watch.observe("tp1", dec(102.0), dec(102.2), start + secs(6)));
+    }
+
+    #[test]
+    fn test_falling_back_restarts_the_clock() {
+        let start = Instant::now();
+        let mut watch = SyntheticFillWatch::new(&config(), true);
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.2), start));
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.0), start + secs(3)));
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.2), start + secs(4)));
+        assert!(!watch.observe("tp1", dec(102.0), dec(102.2), start + secs(8)));
+        assert!(watch.observe("tp1", dec(102.0), dec(102.2), start + secs(9)));
+    }
+
+    #[test]
+    fn test_inactive_watch_never_fills() {
+        let start = Instant::now();
+        let mut watch = SyntheticFillWatch::new(&config(), false);
+        assert!(!watch.observe("tp1", dec(102.0), dec(110.0), start));
+        assert!(!watch.observe("tp1", dec(102.0), dec(110.0), start + secs(60)));
+    }
+
+    // ============= Reconciliation Tests =============
+
+    #[test]
+    fn test_reconciled_from_status() {
+        assert_eq!(Reconciled::from_status("filled"), Reconciled::Confirmed);
+        assert_eq!(Reconciled::from_status("CANCELED"), Reconciled::Restore);
+        assert_eq!(Reconciled::from_status("expired"), Reconciled::Restore);
+        assert_eq!(Reconciled::from_status("new"), Reconciled::Pending);
+        assert_eq!(
+            Reconciled::from_status("partially_filled"),
+            Reconciled::Pending
+        );
+    }
+
+    #[test]
+    fn test_recorded_fills_until_resolved() {
+        let mut watch = SyntheticFillWatch::new(&config(), true);
+        watch.record_fill("tp1", position("BTC/USD"), dec(102.0), Instant::now());
+        assert_eq!(watch.unreconciled(), vec!["tp1".to_string()]);
+
+        let fill = watch.resolve("tp1").unwrap();
+        assert_eq!(fill.position.symbol, "BTC/USD");
+        assert_eq!(fill.limit_price, dec(102.0));
+        assert!(watch.unreconciled().is_empty());
+        assert!(watch.resolve("tp1").is_none());
+    }
+}
@@ -0,0 +1,323 @@
+//! Trade journal: the decision chain behind each executed trade.
+//!
+//! `TradeJournal` follows one trade by its correlation id through the bus:
+//! the entry signal (Director thesis, Quant analysis, market context), the
+//! risk engine's order (Risk verdict, order type, SL/TP), execution acks and
+//! fills of the entry and exit orders, the exit signal's reason and the
+//! realized P&L. Once the trade has an order, every change is written to
+//! `<trade_journal.dir>/<correlation id>.json`; `GET /trades/:order_id`
+//! finds the file by any of its order ids.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::TradeJournalConfig;
+use crate::events::{AnalysisSignal, Event, ExecutionReport, OrderRequest, TradeOrigin};
+
+/// Signals that led to no order within this long are forgotten
+const UNORDERED_TTL_MINUTES: i64 = 10;
+
+/// One side (entry or exit) of a journaled trade.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JournalLeg {
+    /// Why the risk engine let the order through
+    #[serde(default)]
+    pub risk_verdict: Option<String>,
+    /// Order type requested by the risk engine ("market", "hft_buy", ...)
+    #[serde(default)]
+    pub order_type: Option<String>,
+    #[serde(default)]
+    pub stop_loss: Option<Decimal>,
+    #[serde(default)]
+    pub take_profit: Option<Decimal>,
+    /// Venue order ids (re-submissions and TP orders add more)
+    #[serde(default)]
+    pub order_ids: Vec<String>,
+    /// Latest ack or fill status
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub qty: Option<Decimal>,
+    /// Submitted (ack) or filled (fill update) price
+    #[serde(default)]
+    pub price: Option<Decimal>,
+    #[serde(default)]
+    pub limit_price: Option<Decimal>,
+    /// Quote mid when the order was priced
+    #[serde(default)]
+    pub signal_price: Option<Decimal>,
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+}
+
+/// Realized outcome of a closed trade.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalResult {
+    pub pnl: Decimal,
+    pub pnl_percent: f64,
+    pub closed_at: String,
+}
+
+/// Everything known about one trade, from its signal to its exit.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub correlation_id: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub strategy_id: Option<String>,
+    #[serde(default)]
+    pub origin: Option<TradeOrigin>,
+    pub opened_at: String,
+    /// Director thesis (LLM) or the strategy's own reasoning
+    #[serde(default)]
+    pub thesis: String,
+    #[serde(default)]
+    pub quant_analysis: Option<String>,
+    #[serde(default)]
+    pub market_context: String,
+    #[serde(default)]
+    pub entry: JournalLeg,
+    #[serde(default)]
+    pub exit: Option<JournalLeg>,
+    /// Thesis of the exit signal (stop-loss, take-profit, strategy exit)
+    #[serde(default)]
+    pub exit_reason: Option<String>,
+    #[serde(default)]
+    pub result: Option<JournalResult>,
+}
+
+impl JournalEntry {
+    fn new(signal: &AnalysisSignal, correlation_id: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            correlation_id: correlation_id.to_string(),
+            symbol: signal.symbol.clone(),
+            strategy_id: signal.strategy_id.clone(),
+            origin: signal.origin.clone(),
+            opened_at: now.to_rfc3339(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether any order of the trade reached the venue.
+    pub fn executed(&self) -> bool {
+        !self.entry.order_ids.is_empty()
+            || self.exit.as_ref().is_some_and(|e| !e.order_ids.is_empty())
+    }
+
+    pub fn has_order(&self, order_id: &str) -> bool {
+        self.entry.order_ids.iter().any(|id| id == order_id)
+            || self
+                .exit
+                .as_ref()
+                .is_some_and(|e| e.order_ids.iter().any(|id| id == order_id))
+    }
+
+    fn leg_mut(&mut self, side: &str) -> &mut JournalLeg {
+        if side.eq_ignore_ascii_case("sell") {
+            self.exit.get_or_insert_with(JournalLeg::default)
+        } else {
+            &mut self.entry
+        }
+    }
+}
+
+/// Open trades by correlation id, built from bus events.
+#[derive(Debug, Default)]
+pub struct TradeJournal {
+    trades: HashMap<String, JournalEntry>,
+    /// Order id -> correlation id, for fill updates that carry no correlation id
+    orders: HashMap<String, String>,
+}
+
+impl TradeJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a bus event. Returns the updated trade when it should be written
+    /// (it changed and has reached the venue).
+    pub fn apply(&mut self, event: &Event, now: DateTime<Utc>) -> Option<JournalEntry> {
+        let correlation_id = match event {
+            Event::Signal(signal) => {
+                let id = signal.correlation_id.as_deref()?;
+                self.on_signal(signal, id, now);
+                id.to_string()
+            }
+            Event::Order(order) => {
+                let id = order.correlation_id.as_deref()?;
+                self.on_order(order, id)?;
+                id.to_string()
+            }
+            Event::Execution(report) => {
+                let id = report
+                    .correlation_id
+                    .clone()
+                    .or_else(|| self.orders.get(&report.order_id).cloned())?;
+                self.on_execution(report, &id, now)?;
+                id
+            }
+            Event::TradeClosed(trade) => {
+                let id = trade.correlation_id.as_deref()?;
+                let mut entry = self.trades.remove(id)?;
+                entry.result = Some(JournalResult {
+                    pnl: trade.pnl,
+                    pnl_percent: trade.pnl_percent,
+                    closed_at: trade.timestamp.clone(),
+                });
+                self.orders.retain(|_, cid| cid != id);
+                return Some(entry);
+            }
+            _ => return None,
+        };
+        self.trades
+            .get(&correlation_id)
+            .filter(|entry| entry.executed())
+            .cloned()
+    }
+
+    fn on_signal(&mut self, signal: &AnalysisSignal, id: &str, now: DateTime<Utc>) {
+        self.prune(now);
+        let entry = self
+            .trades
+            .entry(id.to_string())
+            .or_insert_with(|| JournalEntry::new(signal, id, now));
+        if signal.signal.eq_ignore_ascii_case("sell") {
+            entry.exit_reason = Some(signal.thesis.clone());
+        } else {
+            entry.thesis = signal.thesis.clone();
+            entry.quant_analysis = signal.quant_analysis.clone();
+            entry.market_context = signal.market_context.clone();
+        }
+    }
+
+    fn on_order(&mut self, order: &OrderRequest, id: &str) -> Option<()> {
+        let leg = self.trades.get_mut(id)?.leg_mut(&order.action);
+        leg.risk_verdict = order.risk_verdict.clone();
+        leg.order_type = Some(order.order_type.clone());
+        leg.stop_loss = order.stop_loss;
+        leg.take_profit = order.take_profit;
+        Some(())
+    }
+
+    fn on_execution(
+        &mut self,
+        report: &ExecutionReport,
+        id: &str,
+        now: DateTime<Utc>,
+    ) -> Option<()> {
+        let leg = self.trades.get_mut(id)?.leg_mut(&report.side);
+        if !report.order_id.is_empty() && !leg.order_ids.contains(&report.order_id) {
+            leg.order_ids.push(report.order_id.clone());
+            leg.submitted_at = Some(now.to_rfc3339());
+            self.orders.insert(report.order_id.clone(), id.to_string());
+        }
+        leg.status = Some(report.status.clone());
+        leg.qty = report.qty.or(leg.qty);
+        leg.price = report.price.or(leg.price);
+        leg.limit_price = report.limit_price.or(leg.limit_price);
+        leg.signal_price = report.signal_price.or(leg.signal_price);
+        Some(())
+    }
+
+    /// Drop signals that never turned into an order.
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::minutes(UNORDERED_TTL_MINUTES);
+        self.trades.retain(|_, entry| {
+            entry.executed()
+                || DateTime::parse_from_rfc3339(&entry.opened_at)
+                    .is_ok_and(|opened| opened >= cutoff)
+        });
+    }
+}
+
+/// Journal file of a trade.
+pub fn journal_path(dir: &Path, correlation_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", correlation_id))
+}
+
+/// Write `entry` to its journal file (temp file + rename).
+pub fn write_entry(dir: &Path, entry: &JournalEntry) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = journal_path(dir, &entry.correlation_id);
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(entry)?)?;
+    std::fs::rename(&tmp, &path)
+}
+
+/// The journaled trade with order `order_id`, if any.
+pub fn read_trade(dir: &Path, order_id: &str) -> Option<JournalEntry> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| std::fs::read_to_string(p).ok())
+        .filter_map(|text| serde_json::from_str::<JournalEntry>(&text).ok())
+        .find(|entry| entry.has_order(order_id))
+}
+
+/// Writes the journal of every executed trade of a session.
+pub struct TradeJournalService {
+    event_bus: EventBus,
+    dir: PathBuf,
+}
+
+impl TradeJournalService {
+    pub fn new(config: &TradeJournalConfig, event_bus: EventBus) -> Self {
+        Self {
+            event_bus,
+            dir: PathBuf::from(&config.dir),
+        }
+    }
+
+    /// Subscribe now and journal until the session is stopped.
+    pub fn start(self) {
+        let mut rx = self.event_bus.subscribe_in_order("trade_journal");
+        let shutdown = self.event_bus.shutdown().clone();
+        let worker = shutdown.track_worker();
+        tokio::spawn(async move {
+            let _worker = worker;
+            info!("📓 [JOURNAL] Journaling trades to {}", self.dir.display());
+            let mut journal = TradeJournal::new();
+            loop {
+                let event = tokio::select! {
+                    // Drain is over: journal whatever is still queued, then exit
+                    _ = shutdown.stopped() => {
+                        while let Ok(event) = rx.try_recv() {
+                            self.record(&mut journal, &event);
+                        }
+                        break;
+                    }
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(n)) => {
+                            warn!("⚠️ [JOURNAL] Lagged, {} event(s) not journaled", n);
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                self.record(&mut journal, &event);
+            }
+        });
+    }
+
+    fn record(&self, journal: &mut TradeJournal, event: &Event) {
+        let Some(entry) = journal.apply(event, Utc::now()) else {
+            return;
+        };
+        if let Err(e) = write_entry(&self.dir, &entry) {
+            warn!(
+                "⚠️ [JOURNAL] Failed to write trade {}: {}",
+                entry.correlation_id, e
+            );
+        }
+    }
+}
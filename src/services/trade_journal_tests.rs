@@ -0,0 +1,178 @@
+//! Unit tests for the trade journal - assembling a trade's decision chain
+//! from bus events and looking it up by order id.
+
+#[cfg(test)]
+mod trade_journal_tests {
+    use crate::events::*;
+    use crate::money::dec;
+    use crate::services::trade_journal::*;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn signal(action: &str, thesis: &str) -> Event {
+        Event::Signal(AnalysisSignal {
+            symbol: "BTC/USD".to_string(),
+            signal: action.to_string(),
+            confidence: 0.8,
+            thesis: thesis.to_string(),
+            market_context: "mid 60000, spread 2bps".to_string(),
+            strategy_id: Some("llm".to_string()),
+            correlation_id: Some("c1".to_string()),
+            origin: None,
+            quant_analysis: (action == "buy").then(|| "momentum z=2.1".to_string()),
+            entry_limit: None,
+            take_profit: None,
+            stop_loss: None,
+            valid_until: None,
+            ..Default::default()
+        })
+    }
+
+    fn order(action: &str, verdict: &str) -> Event {
+        Event::Order(OrderRequest {
+            symbol: "BTC/USD".to_string(),
+            action: action.to_string(),
+            qty: dec(0.01),
+            order_type: "market".to_string(),
+            limit_price: None,
+            stop_loss: Some(dec(59_000.0)),
+            take_profit: Some(dec(61_000.0)),
+            strategy_id: Some("llm".to_string()),
+            correlation_id: Some("c1".to_string()),
+            origin: None,
+            risk_verdict: Some(verdict.to_string()),
+            valid_until: None,
+            ..Default::default()
+        })
+    }
+
+    fn report(order_id: &str, side: &str, status: &str, correlation_id: Option<&str>) -> Event {
+        Event::Execution(ExecutionReport {
+            symbol: "BTC/USD".to_string(),
+            order_id: order_id.to_string(),
+            status: status.to_string(),
+            side: side.to_string(),
+            price: Some(dec(60_010.0)),
+            qty: Some(dec(0.01)),
+            signal_price: Some(dec(60_000.0)),
+            limit_price: None,
+            strategy_id: Some("llm".to_string()),
+            correlation_id: correlation_id.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    fn closed() -> Event {
+        Event::TradeClosed(TradeClosedEvent {
+            symbol: "BTC/USD".to_string(),
+            thesis: "take profit".to_string(),
+            entry_price: dec(60_010.0),
+            exit_price: dec(61_000.0),
+            qty: dec(0.01),
+            pnl: dec(9.9),
+            pnl_percent: 1.65,
+            strategy_id: Some("llm".to_string()),
+            correlation_id: Some("c1".to_string()),
+            timestamp: "2026-10-16T12:30:00Z".to_string(),
+        })
+    }
+
+    // ============= Decision Chain Tests =============
+
+    #[test]
+    fn test_nothing_written_before_an_order_reaches_the_venue() {
+        let mut journal = TradeJournal::new();
+        assert_eq!(journal.apply(&signal("buy", "breakout"), now()), None);
+        assert_eq!(journal.apply(&order("buy", "approved"), now()), None);
+        let entry = journal
+            .apply(&report("o1", "buy", "new", Some("c1")), now())
+            .unwrap();
+        assert_eq!(entry.entry.order_ids, ["o1"]);
+        assert_eq!(entry.entry.risk_verdict.as_deref(), Some("approved"));
+    }
+
+    #[test]
+    fn test_full_decision_chain() {
+        let mut journal = TradeJournal::new();
+        for event in [
+            signal("buy", "breakout above range"),
+            order("buy", "size ok, SL below support"),
+            report("o1", "buy", "new", Some("c1")),
+            // Fill update found by order id
+            report("o1", "buy", "fill", None),
+            signal("sell", "take profit hit"),
+            order("sell", "exit approved"),
+            report("o2", "sell", "new", Some("c1")),
+        ] {
+            journal.apply(&event, now());
+        }
+        let entry = journal.apply(&closed(), now()).unwrap();
+
+        assert_eq!(entry.thesis, "breakout above range");
+        assert_eq!(entry.quant_analysis.as_deref(), Some("momentum z=2.1"));
+        assert_eq!(entry.market_context, "mid 60000, spread 2bps");
+        assert_eq!(entry.entry.status.as_deref(), Some("fill"));
+        assert_eq!(entry.entry.stop_loss, Some(dec(59_000.0)));
+        assert_eq!(entry.entry.signal_price, Some(dec(60_000.0)));
+        let exit = entry.exit.as_ref().unwrap();
+        assert_eq!(exit.order_ids, ["o2"]);
+        assert_eq!(exit.risk_verdict.as_deref(), Some("exit approved"));
+        assert_eq!(entry.exit_reason.as_deref(), Some("take profit hit"));
+        assert_eq!(entry.result.as_ref().unwrap().pnl, dec(9.9));
+        assert!(entry.has_order("o1") && entry.has_order("o2"));
+
+        // Closed trades are forgotten
+        assert_eq!(
+            journal.apply(&report("o1", "buy", "fill", None), now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_signals_without_orders_expire() {
+        let mut journal = TradeJournal::new();
+        journal.apply(&signal("buy", "breakout"), now());
+        // Another trade's signal much later prunes the stale one
+        let mut later = signal("buy", "other");
+        if let Event::Signal(s) = &mut later {
+            s.correlation_id = Some("c2".to_string());
+        }
+        journal.apply(&later, now() + Duration::minutes(30));
+        assert_eq!(journal.apply(&order("buy", "approved"), now()), None);
+        assert_eq!(
+            journal.apply(&report("o1", "buy", "new", Some("c1")), now()),
+            None
+        );
+    }
+
+    // ============= Persistence Tests =============
+
+    #[test]
+    fn test_lookup_by_any_order_id() {
+        let dir = std::env::temp_dir().join(format!("autohedge_journal_{}", std::process::id()));
+        let mut journal = TradeJournal::new();
+        let mut last = None;
+        for event in [
+            signal("buy", "breakout"),
+            order("buy", "approved"),
+            report("o1", "buy", "new", Some("c1")),
+            report("o2", "sell", "new", Some("c1")),
+        ] {
+            if let Some(entry) = journal.apply(&event, now()) {
+                write_entry(&dir, &entry).unwrap();
+                last = Some(entry);
+            }
+        }
+        let last = last.unwrap();
+        assert_eq!(read_trade(&dir, "o1"), Some(last.clone()));
+        assert_eq!(read_trade(&dir, "o2"), Some(last));
+        assert_eq!(read_trade(&dir, "missing"), None);
+        assert!(journal_path(&dir, "c1").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
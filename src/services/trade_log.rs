@@ -0,0 +1,179 @@
+//! Trade log serialization.
+//!
+//! `TradeReporter` appends one `TradeLogEntry` per order/execution through a
+//! `TradeLogSink`. JSONL is the default; CSV (with a header row) and
+//! MessagePack (back-to-back named maps) are selected with `trade_log.format`.
+//! `convert_log` migrates an existing log between formats (see the
+//! `convert_trade_log` binary).
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::error::{AutoHedgeError, Result};
+use crate::services::reporting::TradeLogEntry;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeLogFormat {
+    Jsonl,
+    Csv,
+    MessagePack,
+}
+
+impl TradeLogFormat {
+    /// Parse a config value ("jsonl", "csv", "msgpack").
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "jsonl" | "json" => Some(TradeLogFormat::Jsonl),
+            "csv" => Some(TradeLogFormat::Csv),
+            "msgpack" | "messagepack" | "mpk" => Some(TradeLogFormat::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::parse)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TradeLogFormat::Jsonl => "jsonl",
+            TradeLogFormat::Csv => "csv",
+            TradeLogFormat::MessagePack => "msgpack",
+        }
+    }
+}
+
+/// Encodes trade log entries for one file format.
+pub trait TradeLogSink: Send + Sync {
+    fn format(&self) -> TradeLogFormat;
+
+    /// Write one entry. `first` is true when the file is empty (CSV header).
+    fn write_entry(&self, out: &mut dyn Write, entry: &TradeLogEntry, first: bool) -> Result<()>;
+
+    /// Decode every entry from a log written by this sink.
+    fn read_entries(&self, input: &mut dyn Read) -> Result<Vec<TradeLogEntry>>;
+
+    /// Append one entry to the log at `path`, creating it if needed.
+    fn append(&self, path: &Path, entry: &TradeLogEntry) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+        let first = f.metadata()?.len() == 0;
+        self.write_entry(&mut f, entry, first)
+    }
+}
+
+pub struct JsonlSink;
+
+impl TradeLogSink for JsonlSink {
+    fn format(&self) -> TradeLogFormat {
+        TradeLogFormat::Jsonl
+    }
+
+    fn write_entry(&self, out: &mut dyn Write, entry: &TradeLogEntry, _first: bool) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        writeln!(out, "{}", line)?;
+        Ok(())
+    }
+
+    fn read_entries(&self, input: &mut dyn Read) -> Result<Vec<TradeLogEntry>> {
+        let mut entries = Vec::new();
+        for line in BufReader::new(input).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line)?);
+        }
+        Ok(entries)
+    }
+}
+
+pub struct CsvSink;
+
+impl TradeLogSink for CsvSink {
+    fn format(&self) -> TradeLogFormat {
+        TradeLogFormat::Csv
+    }
+
+    fn write_entry(&self, out: &mut dyn Write, entry: &TradeLogEntry, first: bool) -> Result<()> {
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(first)
+            .from_writer(out);
+        writer.serialize(entry)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn read_entries(&self, input: &mut dyn Read) -> Result<Vec<TradeLogEntry>> {
+        let mut reader = csv::Reader::from_reader(input);
+        let mut entries = Vec::new();
+        for entry in reader.deserialize() {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+}
+
+pub struct MessagePackSink;
+
+impl TradeLogSink for MessagePackSink {
+    fn format(&self) -> TradeLogFormat {
+        TradeLogFormat::MessagePack
+    }
+
+    fn write_entry(&self, out: &mut dyn Write, entry: &TradeLogEntry, _first: bool) -> Result<()> {
+        // Named (map) encoding so the log stays readable if fields are added
+        out.write_all(&rmp_serde::to_vec_named(entry)?)?;
+        Ok(())
+    }
+
+    fn read_entries(&self, input: &mut dyn Read) -> Result<Vec<TradeLogEntry>> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        let mut cursor = std::io::Cursor::new(bytes.as_slice());
+        let mut entries = Vec::new();
+        while (cursor.position() as usize) < bytes.len() {
+            let mut de = rmp_serde::Deserializer::new(&mut cursor);
+            entries.push(serde::Deserialize::deserialize(&mut de)?);
+        }
+        Ok(entries)
+    }
+}
+
+pub fn sink_for(format: TradeLogFormat) -> Box<dyn TradeLogSink> {
+    match format {
+        TradeLogFormat::Jsonl => Box::new(JsonlSink),
+        TradeLogFormat::Csv => Box::new(CsvSink),
+        TradeLogFormat::MessagePack => Box::new(MessagePackSink),
+    }
+}
+
+/// Re-encode the log at `input` into `output`. Returns the number of entries.
+/// An existing output is overwritten.
+pub fn convert_log(
+    input: &Path,
+    from: TradeLogFormat,
+    output: &Path,
+    to: TradeLogFormat,
+) -> Result<usize> {
+    let mut reader =
+        File::open(input).map_err(|e| AutoHedgeError::Io(format!("{}: {}", input.display(), e)))?;
+    let entries = sink_for(from).read_entries(&mut reader)?;
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let sink = sink_for(to);
+    let mut writer = BufWriter::new(File::create(output)?);
+    for (i, entry) in entries.iter().enumerate() {
+        sink.write_entry(&mut writer, entry, i == 0)?;
+    }
+    writer.flush()?;
+    Ok(entries.len())
+}
@@ -0,0 +1,132 @@
+//! Unit tests for trade log sinks - JSONL, CSV and MessagePack round trips and conversion.
+
+#[cfg(test)]
+mod trade_log_tests {
+    use crate::money::dec;
+    use crate::services::reporting::TradeLogEntry;
+    use crate::services::trade_log::*;
+    use std::path::{Path, PathBuf};
+
+    fn entry(symbol: &str, notes: Option<&str>) -> TradeLogEntry {
+        TradeLogEntry {
+            ts: "2025-01-01T00:00:00Z".to_string(),
+            symbol: symbol.to_string(),
+            action: "buy".to_string(),
+            order_id: "o-1".to_string(),
+            status: "filled".to_string(),
+            qty: Some(dec(0.5)),
+            price: Some(dec(100.0)),
+            notional: Some(dec(50.0)),
+            notes: notes.map(str::to_string),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "autohedge_trade_log_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn read_back(path: &Path, format: TradeLogFormat) -> Vec<TradeLogEntry> {
+        let mut f = std::fs::File::open(path).unwrap();
+        sink_for(format).read_entries(&mut f).unwrap()
+    }
+
+    // ============= Format Tests =============
+
+    #[test]
+    fn test_format_parse_and_extension() {
+        assert_eq!(TradeLogFormat::parse("CSV"), Some(TradeLogFormat::Csv));
+        assert_eq!(
+            TradeLogFormat::parse("msgpack"),
+            Some(TradeLogFormat::MessagePack)
+        );
+        assert_eq!(TradeLogFormat::parse("parquet"), None);
+        assert_eq!(
+            TradeLogFormat::from_path(Path::new("data/trades.jsonl")),
+            Some(TradeLogFormat::Jsonl)
+        );
+        assert_eq!(TradeLogFormat::MessagePack.extension(), "msgpack");
+    }
+
+    // ============= Sink Round Trip Tests =============
+
+    #[test]
+    fn test_append_round_trip_all_formats() {
+        let dir = temp_dir("round_trip");
+        for format in [
+            TradeLogFormat::Jsonl,
+            TradeLogFormat::Csv,
+            TradeLogFormat::MessagePack,
+        ] {
+            let path = dir.join(format!("trades.{}", format.extension()));
+            let sink = sink_for(format);
+            sink.append(
+                &path,
+                &entry("BTC/USD", Some("type=limit sl=None, tp=Some(1.0)")),
+            )
+            .unwrap();
+            sink.append(&path, &entry("ETH/USD", None)).unwrap();
+
+            let entries = read_back(&path, format);
+            assert_eq!(entries.len(), 2, "{:?}", format);
+            assert_eq!(entries[0].symbol, "BTC/USD");
+            assert_eq!(
+                entries[0].notes.as_deref(),
+                Some("type=limit sl=None, tp=Some(1.0)")
+            );
+            assert_eq!(entries[1].notes, None);
+            assert_eq!(entries[1].qty, Some(dec(0.5)));
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_csv_header_written_once() {
+        let dir = temp_dir("csv_header");
+        let path = dir.join("trades.csv");
+        let sink = CsvSink;
+        sink.append(&path, &entry("BTC/USD", None)).unwrap();
+        sink.append(&path, &entry("ETH/USD", None)).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("ts,symbol,action"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // ============= Conversion Tests =============
+
+    #[test]
+    fn test_convert_jsonl_to_csv_and_msgpack() {
+        let dir = temp_dir("convert");
+        let jsonl = dir.join("trades.jsonl");
+        JsonlSink.append(&jsonl, &entry("BTC/USD", None)).unwrap();
+        JsonlSink.append(&jsonl, &entry("SOL/USD", None)).unwrap();
+
+        let csv = dir.join("out/trades.csv");
+        let n = convert_log(&jsonl, TradeLogFormat::Jsonl, &csv, TradeLogFormat::Csv).unwrap();
+        assert_eq!(n, 2);
+        assert_eq!(read_back(&csv, TradeLogFormat::Csv)[1].symbol, "SOL/USD");
+
+        let mpk = dir.join("trades.msgpack");
+        convert_log(&csv, TradeLogFormat::Csv, &mpk, TradeLogFormat::MessagePack).unwrap();
+        let entries = read_back(&mpk, TradeLogFormat::MessagePack);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].price, Some(dec(100.0)));
+
+        assert!(convert_log(
+            &dir.join("missing.jsonl"),
+            TradeLogFormat::Jsonl,
+            &csv,
+            TradeLogFormat::Csv
+        )
+        .is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,389 @@
+//! Exchange heartbeat watchdog.
+//!
+//! Every REST call to the trading venue goes through `WatchedExchange`, which
+//! counts consecutive failures. Past `failure_threshold` the venue is put in
+//! safety mode: new entries are refused locally (instead of erroring one by one
+//! at the exchange), exits keep going with escalating retry delays, and a
+//! heartbeat probe clears the mode once REST calls succeed again.
+//!
+//! With a `HaltDetector` attached, entry rejections are also classified per
+//! symbol, and symbols in exit-only mode are refused here as well.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+use crate::bus::Shutdown;
+use crate::config::WatchdogConfig;
+use crate::error::AutoHedgeError;
+use crate::exchange::time_sync::ServerClock;
+use crate::exchange::traits::{ExchangeResult, TradingApi};
+use crate::exchange::types::{
+    AccountSummary, BracketLegs, ExchangeCapabilities, OpenOrder, OrderAck, PlaceOrderRequest,
+    Position, SymbolMeta,
+};
+use crate::services::halt::HaltDetector;
+
+/// Base delay for exit retries; doubled per attempt while in safety mode.
+const EXIT_RETRY_BASE_SECS: u64 = 30;
+
+/// Recent REST outcomes kept for `error_rate`.
+const REST_OUTCOME_HISTORY: usize = 256;
+
+#[derive(Debug, Default)]
+struct WatchdogState {
+    consecutive_failures: u32,
+    safe_mode_since: Option<Instant>,
+    last_recovery: Option<Instant>,
+    /// (time, succeeded) of the latest REST calls, oldest first
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+impl WatchdogState {
+    fn push_outcome(&mut self, ok: bool) {
+        if self.outcomes.len() >= REST_OUTCOME_HISTORY {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back((Instant::now(), ok));
+    }
+}
+
+/// Shared REST health state for one trading venue.
+#[derive(Clone)]
+pub struct ExchangeWatchdog {
+    config: WatchdogConfig,
+    state: Arc<Mutex<WatchdogState>>,
+}
+
+impl ExchangeWatchdog {
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(WatchdogState::default())),
+        }
+    }
+
+    pub fn is_safe_mode(&self) -> bool {
+        self.state.lock().unwrap().safe_mode_since.is_some()
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    /// True if safety mode was cleared after `t` (retry counters from the
+    /// outage should be reset rather than treated as real failures).
+    pub fn recovered_after(&self, t: Instant) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .last_recovery
+            .is_some_and(|r| r > t)
+    }
+
+    /// Share of REST calls within `window` that failed (0 when there were none).
+    pub fn error_rate(&self, window: Duration) -> f64 {
+        let state = self.state.lock().unwrap();
+        let (total, failed) = state
+            .outcomes
+            .iter()
+            .filter(|(t, _)| t.elapsed() <= window)
+            .fold((0usize, 0usize), |(n, f), (_, ok)| {
+                (n + 1, f + usize::from(!ok))
+            });
+        if total == 0 {
+            0.0
+        } else {
+            failed as f64 / total as f64
+        }
+    }
+
+    pub fn record_success(&self, op: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.push_outcome(true);
+        state.consecutive_failures = 0;
+        if let Some(since) = state.safe_mode_since.take() {
+            state.last_recovery = Some(Instant::now());
+            info!(
+                "✅ [WATCHDOG] {} succeeded - leaving safety mode after {:.0}s, entries re-enabled",
+                op,
+                since.elapsed().as_secs_f64()
+            );
+        }
+    }
+
+    pub fn record_failure(&self, op: &str, err: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.push_outcome(false);
+        state.consecutive_failures += 1;
+        if !self.config.enabled || state.safe_mode_since.is_some() {
+            return;
+        }
+        if state.consecutive_failures >= self.config.failure_threshold.max(1) {
+            state.safe_mode_since = Some(Instant::now());
+            error!(
+                "🚨 [WATCHDOG] {} consecutive REST failures (last: {} - {}). SAFETY MODE: new entries blocked, exits retried with backoff",
+                state.consecutive_failures, op, err
+            );
+        }
+    }
+
+    /// Delay before exit retry number `attempts` (0-based). Escalates in safety
+    /// mode, capped at `max_exit_backoff_secs`.
+    pub fn exit_retry_delay(&self, attempts: u32) -> Duration {
+        if !self.is_safe_mode() {
+            return Duration::from_secs(EXIT_RETRY_BASE_SECS);
+        }
+        let secs = EXIT_RETRY_BASE_SECS.saturating_mul(1u64 << attempts.min(16));
+        Duration::from_secs(secs.min(self.config.max_exit_backoff_secs.max(EXIT_RETRY_BASE_SECS)))
+    }
+
+    /// Probe the venue with `get_account` while failures are outstanding, so
+    /// safety mode clears even when no orders are flowing. Exits on shutdown.
+    pub fn start_heartbeat(&self, exchange: Arc<dyn TradingApi>, shutdown: Shutdown) {
+        if !self.config.enabled {
+            return;
+        }
+        let watchdog = self.clone();
+        let interval = Duration::from_secs(self.config.heartbeat_interval_secs.max(1));
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.stopped() => break,
+                    _ = sleep(interval) => {}
+                }
+                if watchdog.consecutive_failures() == 0 {
+                    continue;
+                }
+                match exchange.get_account().await {
+                    Ok(_) => watchdog.record_success("heartbeat"),
+                    Err(e) => {
+                        if watchdog.is_safe_mode() {
+                            warn!(
+                                "💓 [WATCHDOG] Heartbeat failed, still in safety mode: {}",
+                                e
+                            );
+                        }
+                        watchdog.record_failure("heartbeat", &e.to_string());
+                    }
+                }
+            }
+        });
+    }
+
+    fn observe<T>(&self, op: &str, result: ExchangeResult<T>) -> ExchangeResult<T> {
+        match &result {
+            Ok(_) => self.record_success(op),
+            // Unsupported-operation errors are raised locally, not by the venue
+            Err(AutoHedgeError::Unsupported(_)) => {}
+            Err(e) => self.record_failure(op, &e.to_string()),
+        }
+        result
+    }
+}
+
+/// `TradingApi` decorator that feeds every REST result into the watchdog and
+/// refuses new entries while the venue is in safety mode (or the symbol is exit-only).
+pub struct WatchedExchange {
+    inner: Arc<dyn TradingApi>,
+    watchdog: ExchangeWatchdog,
+    halts: Option<HaltDetector>,
+}
+
+impl WatchedExchange {
+    pub fn new(inner: Arc<dyn TradingApi>, watchdog: ExchangeWatchdog) -> Self {
+        Self {
+            inner,
+            watchdog,
+            halts: None,
+        }
+    }
+
+    /// Feed entry rejections to the halt detector and refuse entries for
+    /// exit-only symbols.
+    pub fn with_halt_detector(mut self, halts: HaltDetector) -> Self {
+        self.halts = Some(halts);
+        self
+    }
+
+    /// Why a new entry for `symbol` must not be sent right now, if anything.
+    fn entry_block(&self, symbol: &str) -> Option<String> {
+        if self.watchdog.is_safe_mode() {
+            Some(format!("{} is in safety mode", self.inner.name()))
+        } else if self.halts.as_ref().is_some_and(|h| h.is_exit_only(symbol)) {
+            Some(format!("{} is exit-only (halted/delisted)", symbol))
+        } else {
+            None
+        }
+    }
+
+    fn reject_entry(&self, symbol: &str, why: String) -> ExchangeResult<OrderAck> {
+        warn!("🛑 [WATCHDOG] Entry for {} refused: {}", symbol, why);
+        Err(AutoHedgeError::exchange(format!(
+            "{}: new entries are blocked",
+            why
+        )))
+    }
+
+    fn observe_entry(
+        &self,
+        op: &str,
+        symbol: &str,
+        result: ExchangeResult<OrderAck>,
+    ) -> ExchangeResult<OrderAck> {
+        let Some(halts) = &self.halts else {
+            return self.watchdog.observe(op, result);
+        };
+        match &result {
+            Ok(_) => {
+                halts.record_accepted(symbol);
+                self.watchdog.record_success(op);
+            }
+            Err(e) => {
+                let msg = e.to_string();
+                // The venue answered and the symbol is the problem, not the venue
+                if halts.record_rejection(symbol, &msg) {
+                    self.watchdog.record_success(op);
+                } else if !matches!(e, AutoHedgeError::Unsupported(_)) {
+                    self.watchdog.record_failure(op, &msg);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl TradingApi for WatchedExchange {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> ExchangeCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn get_account(&self) -> ExchangeResult<AccountSummary> {
+        self.watchdog
+            .observe("get_account", self.inner.get_account().await)
+    }
+
+    async fn get_positions(&self) -> ExchangeResult<Vec<Position>> {
+        self.watchdog
+            .observe("get_positions", self.inner.get_positions().await)
+    }
+
+    async fn get_order(&self, order_id: &str) -> ExchangeResult<OrderAck> {
+        self.watchdog
+            .observe("get_order", self.inner.get_order(order_id).await)
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> ExchangeResult<()> {
+        self.watchdog
+            .observe("cancel_order", self.inner.cancel_order(order_id).await)
+    }
+
+    async fn cancel_all_orders(&self) -> ExchangeResult<()> {
+        self.watchdog
+            .observe("cancel_all_orders", self.inner.cancel_all_orders().await)
+    }
+
+    async fn submit_order(&self, order: PlaceOrderRequest) -> ExchangeResult<OrderAck> {
+        if order.reduce_only {
+            return self
+                .watchdog
+                .observe("submit_order", self.inner.submit_order(order).await);
+        }
+        let symbol = order.symbol.clone();
+        if let Some(why) = self.entry_block(&symbol) {
+            return self.reject_entry(&symbol, why);
+        }
+        let result = self.inner.submit_order(order).await;
+        self.observe_entry("submit_order", &symbol, result)
+    }
+
+    async fn submit_bracket_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        let symbol = order.symbol.clone();
+        if let Some(why) = self.entry_block(&symbol) {
+            return self.reject_entry(&symbol, why);
+        }
+        let result = self.inner.submit_bracket_order(order, legs).await;
+        self.observe_entry("submit_bracket_order", &symbol, result)
+    }
+
+    async fn submit_oco_order(
+        &self,
+        order: PlaceOrderRequest,
+        legs: BracketLegs,
+    ) -> ExchangeResult<OrderAck> {
+        self.watchdog.observe(
+            "submit_oco_order",
+            self.inner.submit_oco_order(order, legs).await,
+        )
+    }
+
+    async fn replace_order(
+        &self,
+        order_id: &str,
+        order: PlaceOrderRequest,
+    ) -> ExchangeResult<OrderAck> {
+        self.watchdog.observe(
+            "replace_order",
+            self.inner.replace_order(order_id, order).await,
+        )
+    }
+
+    async fn get_open_orders(&self) -> ExchangeResult<Option<Vec<OpenOrder>>> {
+        self.watchdog
+            .observe("get_open_orders", self.inner.get_open_orders().await)
+    }
+
+    async fn cancel_orders_after(&self, timeout_secs: u64) -> ExchangeResult<bool> {
+        self.watchdog.observe(
+            "cancel_orders_after",
+            self.inner.cancel_orders_after(timeout_secs).await,
+        )
+    }
+
+    async fn get_symbol_meta(&self, symbol: &str) -> ExchangeResult<Option<SymbolMeta>> {
+        self.watchdog
+            .observe("get_symbol_meta", self.inner.get_symbol_meta(symbol).await)
+    }
+
+    fn server_clock(&self) -> Option<ServerClock> {
+        self.inner.server_clock()
+    }
+
+    async fn get_server_time(&self) -> ExchangeResult<Option<i64>> {
+        self.watchdog
+            .observe("get_server_time", self.inner.get_server_time().await)
+    }
+
+    async fn get_historical_bars(&self, symbol: &str, timeframe: &str) -> ExchangeResult<Value> {
+        self.watchdog.observe(
+            "get_historical_bars",
+            self.inner.get_historical_bars(symbol, timeframe).await,
+        )
+    }
+
+    async fn get_bars_since(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: DateTime<Utc>,
+    ) -> ExchangeResult<Value> {
+        self.watchdog.observe(
+            "get_bars_since",
+            self.inner.get_bars_since(symbol, timeframe, since).await,
+        )
+    }
+}
@@ -0,0 +1,158 @@
+//! Unit tests for the exchange watchdog - REST failure tracking and safety mode.
+
+#[cfg(test)]
+mod watchdog_tests {
+    use crate::config::WatchdogConfig;
+    use crate::error::AutoHedgeError;
+    use crate::exchange::mock::{ack, MockExchange};
+    use crate::exchange::traits::TradingApi;
+    use crate::exchange::types::*;
+    use crate::money::dec;
+    use crate::services::watchdog::*;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    /// Venue whose REST calls fail while it is down; orders rest as `new`.
+    fn flaky() -> Arc<MockExchange> {
+        let exchange = MockExchange::new("flaky")
+            .with_capabilities(|c| {
+                c.supports_notional_market_buy = true;
+                c.supports_ws_quotes = true;
+                c.supports_ws_trades = true;
+            })
+            .on_get_order(|_, id| Ok(ack(id, "new", Value::Null)))
+            .on_cancel_all(|_| {
+                Err(AutoHedgeError::Unsupported(
+                    "Flaky cancel_all_orders not implemented".to_string(),
+                ))
+            })
+            .on_submit(|_, order| Ok(ack(&format!("{}-order", order.symbol), "new", Value::Null)));
+        Arc::new(exchange)
+    }
+
+    fn config(threshold: u32) -> WatchdogConfig {
+        WatchdogConfig {
+            failure_threshold: threshold,
+            ..WatchdogConfig::default()
+        }
+    }
+
+    fn order(side: Side) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            symbol: "BTC/USD".to_string(),
+            side,
+            order_type: OrderType::Limit,
+            qty: Some(dec(0.01)),
+            notional: None,
+            limit_price: Some(dec(50000.0)),
+            time_in_force: TimeInForce::Gtc,
+            bracket: None,
+            stop_price: None,
+            ..Default::default()
+        }
+    }
+
+    fn exit(side: Side) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            reduce_only: true,
+            ..order(side)
+        }
+    }
+
+    // ============= Safety Mode Tests =============
+
+    #[test]
+    fn test_enters_safe_mode_at_threshold() {
+        let watchdog = ExchangeWatchdog::new(config(3));
+        watchdog.record_failure("submit_order", "503");
+        watchdog.record_failure("submit_order", "503");
+        assert!(!watchdog.is_safe_mode());
+        watchdog.record_failure("submit_order", "503");
+        assert!(watchdog.is_safe_mode());
+    }
+
+    #[test]
+    fn test_success_resets_and_recovers() {
+        let watchdog = ExchangeWatchdog::new(config(2));
+        watchdog.record_failure("get_order", "timeout");
+        watchdog.record_success("get_order");
+        watchdog.record_failure("get_order", "timeout");
+        assert!(!watchdog.is_safe_mode());
+
+        watchdog.record_failure("get_order", "timeout");
+        assert!(watchdog.is_safe_mode());
+        let before = Instant::now();
+        watchdog.record_success("heartbeat");
+        assert!(!watchdog.is_safe_mode());
+        assert_eq!(watchdog.consecutive_failures(), 0);
+        assert!(watchdog.recovered_after(before));
+    }
+
+    #[test]
+    fn test_disabled_never_enters_safe_mode() {
+        let watchdog = ExchangeWatchdog::new(WatchdogConfig {
+            enabled: false,
+            ..config(1)
+        });
+        watchdog.record_failure("submit_order", "503");
+        watchdog.record_failure("submit_order", "503");
+        assert!(!watchdog.is_safe_mode());
+    }
+
+    #[test]
+    fn test_exit_retry_delay_escalates_in_safe_mode() {
+        let watchdog = ExchangeWatchdog::new(WatchdogConfig {
+            max_exit_backoff_secs: 200,
+            ..config(1)
+        });
+        assert_eq!(watchdog.exit_retry_delay(5), Duration::from_secs(30));
+
+        watchdog.record_failure("submit_order", "503");
+        assert_eq!(watchdog.exit_retry_delay(0), Duration::from_secs(30));
+        assert_eq!(watchdog.exit_retry_delay(1), Duration::from_secs(60));
+        assert_eq!(watchdog.exit_retry_delay(2), Duration::from_secs(120));
+        assert_eq!(watchdog.exit_retry_delay(3), Duration::from_secs(200));
+        assert_eq!(watchdog.exit_retry_delay(40), Duration::from_secs(200));
+    }
+
+    // ============= WatchedExchange Tests =============
+
+    #[tokio::test]
+    async fn test_unimplemented_ops_do_not_count_as_failures() {
+        let inner = flaky();
+        let watchdog = ExchangeWatchdog::new(config(1));
+        let watched = WatchedExchange::new(inner, watchdog.clone());
+
+        assert!(watched.cancel_all_orders().await.is_err());
+        assert_eq!(watchdog.consecutive_failures(), 0);
+        assert!(!watchdog.is_safe_mode());
+    }
+
+    #[tokio::test]
+    async fn test_watched_exchange_blocks_entries_allows_exits() {
+        let inner = flaky();
+        let watchdog = ExchangeWatchdog::new(config(2));
+        let watched = WatchedExchange::new(inner.clone(), watchdog.clone());
+
+        inner.set_down(true);
+        assert!(watched.get_positions().await.is_err());
+        assert!(watched.submit_order(order(Side::Buy)).await.is_err());
+        assert!(watchdog.is_safe_mode());
+        assert_eq!(inner.submitted().len(), 1);
+
+        // Entries of either side are refused locally; exits still reach the venue
+        assert!(watched.submit_order(order(Side::Buy)).await.is_err());
+        assert!(watched.submit_order(order(Side::Sell)).await.is_err());
+        assert_eq!(inner.submitted().len(), 1);
+        assert!(watched.submit_order(exit(Side::Sell)).await.is_err());
+        assert!(watched.submit_order(exit(Side::Buy)).await.is_err());
+        assert_eq!(inner.submitted().len(), 3);
+
+        // Venue recovers: the next successful call clears safety mode
+        inner.set_down(false);
+        assert!(watched.submit_order(exit(Side::Sell)).await.is_ok());
+        assert!(!watchdog.is_safe_mode());
+        assert!(watched.submit_order(order(Side::Buy)).await.is_ok());
+    }
+}
@@ -154,7 +154,7 @@ impl WebSocketService {
         write: &mut SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
         key: &str,
         secret: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> crate::error::Result<()> {
         let auth_msg = json!({
             "action": "auth",
             "key": key,
@@ -168,7 +168,7 @@ impl WebSocketService {
         write: &mut SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
         symbols: &[String],
         is_crypto: bool,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> crate::error::Result<()> {
         let sub_msg = if is_crypto {
             json!({
                 "action": "subscribe",
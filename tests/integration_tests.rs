@@ -4,8 +4,12 @@
 use rust_autohedge::bus::EventBus;
 use rust_autohedge::data::store::{MarketStore, Quote};
 use rust_autohedge::events::{AnalysisSignal, Event, ExecutionReport, MarketEvent, OrderRequest};
-use rust_autohedge::services::execution_utils::{aggressive_limit_price, compute_order_sizing};
+use rust_autohedge::money::dec;
+use rust_autohedge::services::execution_utils::{
+    aggressive_limit_price, compute_order_sizing, SizingStrategy,
+};
 use rust_autohedge::services::position_monitor::{PendingOrder, PositionInfo, PositionTracker};
+use rust_decimal::Decimal;
 
 /// Test the complete flow from market data to signal generation
 #[tokio::test]
@@ -33,7 +37,7 @@ async fn test_market_data_to_signal_flow() {
         timestamp: "2025-01-01T00:00:00Z".to_string(),
     });
 
-    let mut rx = bus.subscribe();
+    let mut rx = bus.subscribe("test");
     bus.publish(event).unwrap();
 
     // Verify event received
@@ -49,7 +53,7 @@ async fn test_market_data_to_signal_flow() {
 #[tokio::test]
 async fn test_signal_to_order_flow() {
     let bus = EventBus::new(100);
-    let mut rx = bus.subscribe();
+    let mut rx = bus.subscribe("test");
 
     // Create analysis signal
     let signal = AnalysisSignal {
@@ -58,6 +62,15 @@ async fn test_signal_to_order_flow() {
         confidence: 0.9,
         thesis: "HFT momentum: edge_bps=15.0".to_string(),
         market_context: "tp=3100.0, sl=2900.0".to_string(),
+        strategy_id: None,
+        correlation_id: None,
+        origin: None,
+        quant_analysis: None,
+        entry_limit: Some(Decimal::new(3000, 0)),
+        take_profit: Some(Decimal::new(3100, 0)),
+        stop_loss: Some(Decimal::new(2900, 0)),
+        valid_until: None,
+        ..Default::default()
     };
 
     bus.publish(Event::Signal(signal)).unwrap();
@@ -67,9 +80,10 @@ async fn test_signal_to_order_flow() {
         assert_eq!(sig.symbol, "ETH/USD");
         assert_eq!(sig.signal, "buy");
 
-        // Parse TP/SL from market_context
-        assert!(sig.market_context.contains("tp="));
-        assert!(sig.market_context.contains("sl="));
+        // Exit levels travel typed, not in the market context text
+        assert_eq!(sig.entry_limit, Some(Decimal::new(3000, 0)));
+        assert_eq!(sig.take_profit, Some(Decimal::new(3100, 0)));
+        assert_eq!(sig.stop_loss, Some(Decimal::new(2900, 0)));
     }
 }
 
@@ -77,17 +91,23 @@ async fn test_signal_to_order_flow() {
 #[tokio::test]
 async fn test_order_to_execution_flow() {
     let bus = EventBus::new(100);
-    let mut rx = bus.subscribe();
+    let mut rx = bus.subscribe("test");
 
     // Create order request
     let order = OrderRequest {
         symbol: "SOL/USD".to_string(),
         action: "buy".to_string(),
-        qty: 10.0,
+        qty: dec(10.0),
         order_type: "limit".to_string(),
-        limit_price: Some(100.0),
-        stop_loss: Some(95.0),
-        take_profit: Some(110.0),
+        limit_price: Some(dec(100.0)),
+        stop_loss: Some(dec(95.0)),
+        take_profit: Some(dec(110.0)),
+        strategy_id: None,
+        correlation_id: None,
+        origin: None,
+        risk_verdict: None,
+        valid_until: None,
+        ..Default::default()
     };
 
     bus.publish(Event::Order(order)).unwrap();
@@ -96,7 +116,7 @@ async fn test_order_to_execution_flow() {
     if let Ok(Event::Order(ord)) = rx.recv().await {
         assert_eq!(ord.symbol, "SOL/USD");
         assert_eq!(ord.action, "buy");
-        assert_eq!(ord.limit_price, Some(100.0));
+        assert_eq!(ord.limit_price, Some(dec(100.0)));
     }
 
     // Simulate execution report
@@ -105,8 +125,13 @@ async fn test_order_to_execution_flow() {
         order_id: "order123".to_string(),
         status: "filled".to_string(),
         side: "buy".to_string(),
-        price: Some(100.0),
-        qty: Some(10.0),
+        price: Some(dec(100.0)),
+        qty: Some(dec(10.0)),
+        signal_price: None,
+        limit_price: None,
+        strategy_id: None,
+        correlation_id: None,
+        ..Default::default()
     };
 
     bus.publish(Event::Execution(report)).unwrap();
@@ -126,12 +151,16 @@ fn test_position_tracking_flow() {
         order_id: "order123".to_string(),
         symbol: "DOGE/USD".to_string(),
         side: "buy".to_string(),
-        limit_price: 0.08,
-        qty: 10000.0,
+        limit_price: dec(0.08),
+        qty: dec(10000.0),
+        filled_qty: Decimal::ZERO,
+        remaining_qty: dec(10000.0),
         created_at: "2025-01-01T00:00:00Z".to_string(),
-        stop_loss: Some(0.075),
-        take_profit: Some(0.085),
+        stop_loss: Some(dec(0.075)),
+        take_profit: Some(dec(0.085)),
         last_check_time: None,
+        bracket: false,
+        correlation_id: None,
     };
 
     tracker.add_pending_order(pending_order);
@@ -142,19 +171,24 @@ fn test_position_tracking_flow() {
 
     let position = PositionInfo {
         symbol: "DOGE/USD".to_string(),
-        entry_price: 0.08,
-        qty: 10000.0,
-        stop_loss: 0.075,
-        take_profit: 0.085,
+        entry_price: dec(0.08),
+        qty: dec(10000.0),
+        filled_qty: dec(10000.0),
+        remaining_qty: Decimal::ZERO,
+        stop_loss: dec(0.075),
+        take_profit: dec(0.085),
         entry_time: "2025-01-01T00:00:00Z".to_string(),
         side: "buy".to_string(),
         is_closing: false,
         open_order_id: None,
         last_recreate_attempt: None,
         recreate_attempts: 0,
-        highest_price: 0.08,
+        highest_price: dec(0.08),
         trailing_stop_active: false,
-        trailing_stop_price: 0.075,
+        trailing_stop_price: dec(0.075),
+        bracket_managed: false,
+        stop_order_id: None,
+        correlation_id: None,
     };
 
     tracker.add_position(position);
@@ -176,27 +210,31 @@ fn test_order_sizing_integration() {
     let limit_price = aggressive_limit_price(bid, ask, "buy", 10.0);
     assert!(limit_price > mid);
     assert!(limit_price <= ask);
+    let limit_price = dec(limit_price);
 
     // Calculate order sizing
     let sizing = compute_order_sizing(
         limit_price,
-        10000.0, // buying power
-        10.0,    // min order
-        100.0,   // max order
-        0.05,    // 5% of balance
+        dec(10000.0),                           // buying power
+        dec(10.0),                              // min order
+        dec(100.0),                             // max order
+        &SizingStrategy::BalancePct(dec(0.05)), // 5% of balance
+        None,
     )
     .unwrap();
 
-    assert!(sizing.notional >= 10.0);
-    assert!(sizing.notional <= 100.0);
+    assert!(sizing.notional >= dec(10.0));
+    assert!(sizing.notional <= dec(100.0));
 
     // Create position after fill
     let position = PositionInfo {
         symbol: "TEST/USD".to_string(),
         entry_price: limit_price,
         qty: sizing.qty,
-        stop_loss: limit_price * 0.99,
-        take_profit: limit_price * 1.01,
+        filled_qty: sizing.qty,
+        remaining_qty: Decimal::ZERO,
+        stop_loss: limit_price * dec(0.99),
+        take_profit: limit_price * dec(1.01),
         entry_time: "2025-01-01T00:00:00Z".to_string(),
         side: "buy".to_string(),
         is_closing: false,
@@ -205,7 +243,10 @@ fn test_order_sizing_integration() {
         recreate_attempts: 0,
         highest_price: limit_price,
         trailing_stop_active: false,
-        trailing_stop_price: limit_price * 0.99,
+        trailing_stop_price: limit_price * dec(0.99),
+        bracket_managed: false,
+        stop_order_id: None,
+        correlation_id: None,
     };
 
     tracker.add_position(position);
@@ -238,19 +279,24 @@ async fn test_multi_symbol_flow() {
     for symbol in &["BTC/USD", "ETH/USD"] {
         let pos = PositionInfo {
             symbol: symbol.to_string(),
-            entry_price: 1000.0,
-            qty: 1.0,
-            stop_loss: 950.0,
-            take_profit: 1050.0,
+            entry_price: dec(1000.0),
+            qty: dec(1.0),
+            filled_qty: dec(1.0),
+            remaining_qty: Decimal::ZERO,
+            stop_loss: dec(950.0),
+            take_profit: dec(1050.0),
             entry_time: "2025-01-01T00:00:00Z".to_string(),
             side: "buy".to_string(),
             is_closing: false,
             open_order_id: None,
             last_recreate_attempt: None,
             recreate_attempts: 0,
-            highest_price: 1000.0,
+            highest_price: dec(1000.0),
             trailing_stop_active: false,
-            trailing_stop_price: 950.0,
+            trailing_stop_price: dec(950.0),
+            bracket_managed: false,
+            stop_order_id: None,
+            correlation_id: None,
         };
         tracker.add_position(pos);
     }
@@ -356,12 +402,16 @@ fn test_position_lifecycle() {
         order_id: "buy123".to_string(),
         symbol: "XRP/USD".to_string(),
         side: "buy".to_string(),
-        limit_price: 0.50,
-        qty: 1000.0,
+        limit_price: dec(0.50),
+        qty: dec(1000.0),
+        filled_qty: Decimal::ZERO,
+        remaining_qty: dec(1000.0),
         created_at: "2025-01-01T00:00:00Z".to_string(),
-        stop_loss: Some(0.48),
-        take_profit: Some(0.52),
+        stop_loss: Some(dec(0.48)),
+        take_profit: Some(dec(0.52)),
         last_check_time: None,
+        bracket: false,
+        correlation_id: None,
     };
     tracker.add_pending_order(order);
 
@@ -369,19 +419,24 @@ fn test_position_lifecycle() {
     tracker.remove_pending_order("buy123");
     let position = PositionInfo {
         symbol: "XRP/USD".to_string(),
-        entry_price: 0.50,
-        qty: 1000.0,
-        stop_loss: 0.48,
-        take_profit: 0.52,
+        entry_price: dec(0.50),
+        qty: dec(1000.0),
+        filled_qty: dec(1000.0),
+        remaining_qty: Decimal::ZERO,
+        stop_loss: dec(0.48),
+        take_profit: dec(0.52),
         entry_time: "2025-01-01T00:00:00Z".to_string(),
         side: "buy".to_string(),
         is_closing: false,
         open_order_id: None,
         last_recreate_attempt: None,
         recreate_attempts: 0,
-        highest_price: 0.50,
+        highest_price: dec(0.50),
         trailing_stop_active: false,
-        trailing_stop_price: 0.48,
+        trailing_stop_price: dec(0.48),
+        bracket_managed: false,
+        stop_order_id: None,
+        correlation_id: None,
     };
     tracker.add_position(position);
 
@@ -390,12 +445,16 @@ fn test_position_lifecycle() {
         order_id: "sell456".to_string(),
         symbol: "XRP/USD".to_string(),
         side: "sell".to_string(),
-        limit_price: 0.52,
-        qty: 1000.0,
+        limit_price: dec(0.52),
+        qty: dec(1000.0),
+        filled_qty: Decimal::ZERO,
+        remaining_qty: dec(1000.0),
         created_at: "2025-01-01T00:01:00Z".to_string(),
         stop_loss: None,
         take_profit: None,
         last_check_time: None,
+        bracket: false,
+        correlation_id: None,
     };
     tracker.add_pending_order(tp_order);
 
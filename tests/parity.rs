@@ -0,0 +1,329 @@
+//! Sim-to-live parity harness (`cargo test --features parity --test parity`).
+//!
+//! Runs the trading services the way `/start` wires them (strategy, risk,
+//! fast execution, position monitor and trade reporter on one event bus)
+//! against the in-process `PaperExchange`, drives them with a scripted quote
+//! sequence and checks the end state on both sides: positions opened and
+//! closed, orders the venue received, and reporter totals that agree with
+//! the venue's cash.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rust_autohedge::bus::EventBus;
+use rust_autohedge::config::{AppConfig, LlmRetryConfig};
+use rust_autohedge::data::store::{MarketStore, Quote};
+use rust_autohedge::events::{Event, MarketEvent};
+use rust_autohedge::exchange::paper::PaperExchange;
+use rust_autohedge::exchange::traits::TradingApi;
+use rust_autohedge::exchange::types::{OrderType, Side};
+use rust_autohedge::llm::{LLMClient, LLMQueue};
+use rust_autohedge::money::dec;
+use rust_autohedge::services::execution_fast::ExecutionEngine;
+use rust_autohedge::services::position_monitor::{PositionMonitor, PositionTracker};
+use rust_autohedge::services::reporting::{PerformanceSummary, TradeReporter};
+use rust_autohedge::services::risk::RiskEngine;
+use rust_autohedge::services::strategy::StrategyEngine;
+use rust_decimal::Decimal;
+
+const SYMBOL: &str = "BTC/USD";
+const STARTING_CASH: f64 = 10_000.0;
+
+/// HFT on one symbol: buys after a 5 bps rise between consecutive quotes,
+/// $50 entries at the ask, TP +1% / SL -0.5% managed by the monitor.
+fn parity_config() -> AppConfig {
+    serde_yaml::from_str(
+        r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 1.0
+  stop_loss_pct: 0.5
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 0
+llm_queue_size: 10
+llm_max_concurrent: 1
+no_trade_cooldown_quotes: 0
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 1
+  min_edge_bps: 5.0
+  take_profit_bps: 100.0
+  stop_loss_bps: 50.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+micro_trade:
+  target_balance_pct: 0.005
+  aggression_bps: 0.0
+  min_order_interval_ms: 0
+  account_cache_secs: 0
+  crypto_time_in_force: "gtc"
+  use_trailing_stop: false
+sizing:
+  method: "fixed_notional"
+  fixed_notional: 50.0
+llm:
+  api_key: null
+  base_url: null
+  model: "parity"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "http://127.0.0.1:9"
+exit_on_quotes: true
+"#,
+    )
+    .unwrap()
+}
+
+/// The service stack of one trading session on a paper venue.
+struct Harness {
+    bus: EventBus,
+    store: MarketStore,
+    exchange: PaperExchange,
+    tracker: PositionTracker,
+    reporter: TradeReporter,
+}
+
+impl Harness {
+    async fn start(exchange: PaperExchange) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "autohedge_parity_{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let mut config = parity_config();
+        config.policy.audit_log_path = dir.join("policy_audit.jsonl").display().to_string();
+        let bus = EventBus::new(1024);
+        let store = MarketStore::new(config.history_limit);
+        let tracker = PositionTracker::new();
+        let llm = LLMQueue::new(
+            LLMClient::new(String::new(), None, config.llm.model.clone()),
+            1,
+            1,
+            LlmRetryConfig::default(),
+        );
+        let venue: Arc<dyn TradingApi> = Arc::new(exchange.clone());
+
+        let reporter = TradeReporter::new(dir.join("trades.jsonl"));
+        reporter.start(bus.clone()).await;
+
+        StrategyEngine::new(bus.clone(), store.clone(), llm.clone(), config.clone())
+            .start()
+            .await;
+        RiskEngine::new(bus.clone(), venue.clone(), llm.clone(), config.clone())
+            .start()
+            .await;
+        ExecutionEngine::new(
+            bus.clone(),
+            venue.clone(),
+            store.clone(),
+            llm,
+            config.clone(),
+            tracker.clone(),
+        )
+        .with_reporter(reporter.clone())
+        .start()
+        .await;
+        PositionMonitor::new(bus.clone(), venue, tracker.clone(), config)
+            .start()
+            .await;
+        // Let every service subscribe and finish its startup sync
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Self {
+            bus,
+            store,
+            exchange,
+            tracker,
+            reporter,
+        }
+    }
+
+    /// Publish `quotes` one at a time the way a market stream would (venue
+    /// first, then the store and the bus), letting the services settle in
+    /// between.
+    async fn feed(&self, quotes: &[(f64, f64)]) {
+        for &(bid, ask) in quotes {
+            self.exchange.on_quote(SYMBOL, dec(bid), dec(ask));
+            let timestamp = chrono::Utc::now().to_rfc3339();
+            self.store.update_quote(
+                SYMBOL.to_string(),
+                Quote {
+                    symbol: SYMBOL.to_string(),
+                    bid_price: bid,
+                    ask_price: ask,
+                    bid_size: 10.0,
+                    ask_size: 10.0,
+                    timestamp: timestamp.clone(),
+                },
+            );
+            self.bus
+                .publish(Event::Market(MarketEvent::Quote {
+                    symbol: SYMBOL.to_string(),
+                    bid,
+                    ask,
+                    timestamp,
+                }))
+                .ok();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Let the monitor's per-order re-check window (2s) lapse so the next
+    /// quote gets its orders looked up at the venue again.
+    async fn settle(&self) {
+        tokio::time::sleep(Duration::from_millis(2100)).await;
+    }
+
+    /// Open a position: rising mids trigger the HFT buy at the mid, the
+    /// next quote brings the ask down to it and the monitor sees the fill
+    /// and rests its take-profit.
+    async fn enter(&self) {
+        self.feed(&[(100.00, 100.02), (100.20, 100.22)]).await;
+        self.settle().await;
+        self.feed(&[(100.19, 100.21)]).await;
+        self.settle().await;
+    }
+
+    /// Wait (up to 5s) until the reporter summary satisfies `done`.
+    async fn summary_when(&self, done: impl Fn(&PerformanceSummary) -> bool) -> PerformanceSummary {
+        for _ in 0..100 {
+            let summary = self.reporter.summary();
+            if done(&summary) {
+                return summary;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        self.reporter.summary()
+    }
+
+    /// (side, type, status) of every order the venue received, in order.
+    fn orders(&self) -> Vec<(Side, OrderType, String)> {
+        self.exchange
+            .orders()
+            .into_iter()
+            .map(|o| (o.request.side, o.request.order_type, o.status))
+            .collect()
+    }
+}
+
+/// Realized P&L the venue saw (cash change once flat).
+fn venue_pnl(exchange: &PaperExchange) -> Decimal {
+    exchange.cash() - dec(STARTING_CASH)
+}
+
+/// Round trips the reporter has closed on `SYMBOL`.
+fn closed_trades(summary: &PerformanceSummary) -> usize {
+    summary.history.get(SYMBOL).map_or(0, Vec::len)
+}
+
+fn assert_close(actual: Decimal, expected: Decimal) {
+    assert!(
+        (actual - expected).abs() < dec(1e-9),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+fn order_kinds(harness: &Harness) -> Vec<(Side, &'static str, String)> {
+    harness
+        .orders()
+        .into_iter()
+        .map(|(side, order_type, status)| {
+            let kind = match order_type {
+                OrderType::Market => "market",
+                OrderType::Limit => "limit",
+                OrderType::StopMarket => "stop_market",
+                OrderType::StopLimit => "stop_limit",
+            };
+            (side, kind, status)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_take_profit_round_trip() {
+    let harness = Harness::start(PaperExchange::new(dec(STARTING_CASH))).await;
+    harness.enter().await;
+    assert!(harness.tracker.has_position(SYMBOL));
+    assert!(harness.exchange.position_qty(SYMBOL) > Decimal::ZERO);
+
+    // Gap up through the TP (a spread too wide for a new HFT entry)
+    harness.feed(&[(101.40, 101.80)]).await;
+    let summary = harness.summary_when(|s| closed_trades(s) == 1).await;
+
+    assert_eq!(
+        order_kinds(&harness),
+        [
+            (Side::Buy, "limit", "filled".to_string()),
+            (Side::Sell, "limit", "filled".to_string()),
+        ]
+    );
+    assert!(!harness.tracker.has_position(SYMBOL));
+    assert!(harness.tracker.get_all_pending_orders().is_empty());
+    assert_eq!(harness.exchange.position_qty(SYMBOL), Decimal::ZERO);
+
+    assert_eq!((summary.winning_trades, summary.losing_trades), (1, 0));
+    assert!(summary.open_positions.is_empty());
+    assert!(summary.total_realized_pnl > Decimal::ZERO);
+    assert_close(summary.total_realized_pnl, venue_pnl(&harness.exchange));
+}
+
+#[tokio::test]
+async fn test_stop_loss_cancels_take_profit_and_exits() {
+    let harness = Harness::start(PaperExchange::new(dec(STARTING_CASH))).await;
+    harness.enter().await;
+    assert!(harness.tracker.has_position(SYMBOL));
+
+    // Below the -0.5% stop: the TP leg is cancelled and the position sold
+    harness.feed(&[(99.50, 99.52)]).await;
+    let summary = harness.summary_when(|s| closed_trades(s) == 1).await;
+
+    assert_eq!(
+        order_kinds(&harness),
+        [
+            (Side::Buy, "limit", "filled".to_string()),
+            (Side::Sell, "limit", "canceled".to_string()),
+            (Side::Sell, "market", "filled".to_string()),
+        ]
+    );
+    assert!(!harness.tracker.has_position(SYMBOL));
+    assert_eq!(harness.exchange.position_qty(SYMBOL), Decimal::ZERO);
+
+    assert_eq!((summary.winning_trades, summary.losing_trades), (0, 1));
+    assert!(summary.open_positions.is_empty());
+    assert!(summary.total_realized_pnl < Decimal::ZERO);
+    assert_close(summary.total_realized_pnl, venue_pnl(&harness.exchange));
+}
+
+#[tokio::test]
+async fn test_adopted_venue_position_is_exited() {
+    // A position left by an earlier session: adopted with default TP/SL
+    let exchange =
+        PaperExchange::new(dec(STARTING_CASH)).with_position(SYMBOL, dec(0.5), dec(100.0));
+    let harness = Harness::start(exchange).await;
+    assert!(harness.tracker.has_position(SYMBOL));
+    harness.settle().await;
+
+    harness.feed(&[(99.40, 99.42)]).await;
+    harness.summary_when(|s| s.filled == 1).await;
+
+    assert_eq!(
+        order_kinds(&harness),
+        [
+            (Side::Sell, "limit", "canceled".to_string()),
+            (Side::Sell, "market", "filled".to_string()),
+        ]
+    );
+    assert!(!harness.tracker.has_position(SYMBOL));
+    assert_eq!(harness.exchange.position_qty(SYMBOL), Decimal::ZERO);
+    assert_close(harness.exchange.cash(), dec(STARTING_CASH + 0.5 * 99.40));
+}
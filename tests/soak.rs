@@ -0,0 +1,252 @@
+//! Chaos soak run (`cargo test --release --features chaos,parity --test soak`).
+//!
+//! Runs the HFT service stack against the in-process `PaperExchange` behind a
+//! `ChaosExchange` for `SOAK_SECS` seconds (default 20; set 86400 for a 24h
+//! soak) on a seeded random-walk quote stream. REST calls time out, fills show
+//! up late and the quote stream drops out (the venue keeps trading while the
+//! bot sees nothing, then quotes resume). Afterwards the faults are paused,
+//! the market is held flat until the services settle, and the bot's view is
+//! checked against the venue. A live divergence between the tracker and the
+//! venue is printed; a restarted session must agree with the venue again.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_autohedge::bus::EventBus;
+use rust_autohedge::chaos::{ChaosExchange, ChaosInjector, Fault};
+use rust_autohedge::config::{AppConfig, ChaosConfig, LlmRetryConfig};
+use rust_autohedge::data::store::{MarketStore, Quote};
+use rust_autohedge::events::{DisconnectEvent, Event, MarketEvent};
+use rust_autohedge::exchange::paper::PaperExchange;
+use rust_autohedge::exchange::traits::TradingApi;
+use rust_autohedge::llm::{LLMClient, LLMQueue};
+use rust_autohedge::money::dec;
+use rust_autohedge::services::execution_fast::ExecutionEngine;
+use rust_autohedge::services::position_monitor::{PositionMonitor, PositionTracker};
+use rust_autohedge::services::reporting::TradeReporter;
+use rust_autohedge::services::risk::RiskEngine;
+use rust_autohedge::services::strategy::StrategyEngine;
+use rust_decimal::Decimal;
+
+const SYMBOL: &str = "BTC/USD";
+const QUOTE_INTERVAL: Duration = Duration::from_millis(100);
+/// Quotes the stream stays down after an injected disconnect
+const OUTAGE_QUOTES: usize = 30;
+
+/// The parity stack's HFT config with a $50 fixed entry.
+fn soak_config() -> AppConfig {
+    serde_yaml::from_str(
+        r#"
+trading_mode: "crypto"
+exchange: "alpaca"
+symbols: ["BTC/USD"]
+defaults:
+  take_profit_pct: 0.3
+  stop_loss_pct: 0.3
+  min_order_amount: 10.0
+  max_order_amount: 100.0
+symbol_overrides: null
+history_limit: 50
+warmup_count: 0
+llm_queue_size: 10
+llm_max_concurrent: 1
+no_trade_cooldown_quotes: 0
+strategy_mode: "hft"
+chatter_level: "low"
+hft:
+  evaluate_every_quotes: 1
+  min_edge_bps: 5.0
+  take_profit_bps: 30.0
+  stop_loss_bps: 30.0
+  max_spread_bps: 30.0
+hybrid:
+  gate_refresh_quotes: 100
+  no_trade_cooldown_quotes: 50
+micro_trade:
+  target_balance_pct: 0.005
+  aggression_bps: 0.0
+  min_order_interval_ms: 0
+  account_cache_secs: 0
+  crypto_time_in_force: "gtc"
+  use_trailing_stop: false
+sizing:
+  method: "fixed_notional"
+  fixed_notional: 50.0
+llm:
+  api_key: null
+  base_url: null
+  model: "soak"
+alpaca:
+  api_key: "K"
+  secret_key: "S"
+  base_url: "http://127.0.0.1:9"
+exit_on_quotes: true
+"#,
+    )
+    .unwrap()
+}
+
+fn soak_secs() -> u64 {
+    std::env::var("SOAK_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(20)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_chaos_soak() {
+    let dir =
+        std::env::temp_dir().join(format!("autohedge_soak_{}", uuid::Uuid::new_v4().simple()));
+    let mut config = soak_config();
+    config.policy.audit_log_path = dir.join("policy_audit.jsonl").display().to_string();
+    let chaos = Arc::new(ChaosInjector::new(&ChaosConfig {
+        enabled: true,
+        seed: 7,
+        ws_disconnects_per_hour: 720.0,
+        rest_timeout_pct: 10.0,
+        rest_timeout_ms: 300,
+        llm_failure_pct: 0.0,
+        delayed_fill_pct: 50.0,
+        fill_delay_ms: 1_500,
+    }));
+
+    let bus = EventBus::new(4096);
+    let store = MarketStore::new(config.history_limit);
+    let tracker = PositionTracker::new();
+    let paper = PaperExchange::new(dec(10_000.0));
+    let venue: Arc<dyn TradingApi> =
+        Arc::new(ChaosExchange::new(Arc::new(paper.clone()), chaos.clone()));
+    let llm = LLMQueue::new(
+        LLMClient::new(String::new(), None, config.llm.model.clone()),
+        1,
+        1,
+        LlmRetryConfig::default(),
+    );
+    let reporter = TradeReporter::new(dir.join("trades.jsonl"));
+    reporter.start(bus.clone()).await;
+    StrategyEngine::new(bus.clone(), store.clone(), llm.clone(), config.clone())
+        .start()
+        .await;
+    RiskEngine::new(bus.clone(), venue.clone(), llm.clone(), config.clone())
+        .start()
+        .await;
+    ExecutionEngine::new(
+        bus.clone(),
+        venue.clone(),
+        store.clone(),
+        llm,
+        config.clone(),
+        tracker.clone(),
+    )
+    .with_reporter(reporter.clone())
+    .start()
+    .await;
+    let (restart_venue, restart_config) = (venue.clone(), config.clone());
+    PositionMonitor::new(bus.clone(), venue, tracker.clone(), config)
+        .start()
+        .await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut mid = 100.0_f64;
+    let mut outage = 0;
+    let mut delivered = 0u64;
+    let feed = |mid: f64, stream_up: bool| {
+        let (bid, ask) = (mid - 0.01, mid + 0.01);
+        paper.on_quote(SYMBOL, dec(bid), dec(ask));
+        if !stream_up {
+            return false;
+        }
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        store.update_quote(
+            SYMBOL.to_string(),
+            Quote {
+                symbol: SYMBOL.to_string(),
+                bid_price: bid,
+                ask_price: ask,
+                bid_size: 10.0,
+                ask_size: 10.0,
+                timestamp: timestamp.clone(),
+            },
+        );
+        bus.publish(Event::Market(MarketEvent::Quote {
+            symbol: SYMBOL.to_string(),
+            bid,
+            ask,
+            timestamp,
+        }))
+        .ok();
+        true
+    };
+
+    let deadline = Instant::now() + Duration::from_secs(soak_secs());
+    while Instant::now() < deadline {
+        // Random walk with a slight momentum so HFT entries keep coming
+        mid *= 1.0 + rng.gen_range(-0.0008..0.001);
+        if outage == 0 && chaos.roll(Fault::WsDisconnect) {
+            bus.publish(Event::Disconnected(DisconnectEvent {
+                stream: "market_ws".to_string(),
+                reason: "chaos: injected disconnect".to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            }))
+            .ok();
+            outage = OUTAGE_QUOTES;
+        }
+        let stream_up = outage == 0;
+        outage = outage.saturating_sub(1);
+        if feed(mid, stream_up) {
+            delivered += 1;
+        }
+        tokio::time::sleep(QUOTE_INTERVAL).await;
+    }
+
+    // Wind down: no more faults, a flat market until late fills show and the
+    // monitor has re-checked its orders
+    chaos.set_paused(true);
+    for _ in 0..60 {
+        feed(mid, true);
+        tokio::time::sleep(QUOTE_INTERVAL).await;
+    }
+    let stats = chaos.stats();
+    let summary = reporter.summary();
+    let held = paper.position_qty(SYMBOL);
+    println!(
+        "soak: {} quotes delivered, {} venue orders, {} closed trades, faults {:?}",
+        delivered,
+        paper.orders().len(),
+        summary.history.get(SYMBOL).map_or(0, Vec::len),
+        stats
+    );
+    // The live session only adopts venue holdings at startup: an order that
+    // reached the venue behind a timeout stays untracked until a restart
+    if tracker.has_position(SYMBOL) != (held > Decimal::ZERO) {
+        println!(
+            "soak: live tracker diverged (tracked: {}, venue holds {})",
+            tracker.has_position(SYMBOL),
+            held
+        );
+    }
+
+    assert!(stats.rest_timeouts + stats.delayed_fills + stats.ws_disconnects > 0);
+    assert!(!paper.orders().is_empty(), "the bot never traded");
+
+    // A restarted session reconciles with the venue
+    let restarted = PositionTracker::new();
+    PositionMonitor::new(
+        bus.clone(),
+        restart_venue,
+        restarted.clone(),
+        restart_config,
+    )
+    .start()
+    .await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(
+        restarted.has_position(SYMBOL),
+        held > Decimal::ZERO,
+        "restarted tracker and venue disagree (venue holds {})",
+        held
+    );
+}